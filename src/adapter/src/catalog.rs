@@ -1265,6 +1265,16 @@ impl Catalog {
             .filter(|entry| entry.is_secret() && entry.id().is_user())
     }
 
+    /// Returns the type of the user-defined type with the given OID, if any.
+    ///
+    /// [`mz_pgrepr::Type::from_oid`] only resolves the OIDs of builtin types.
+    pub fn resolve_user_type_oid(&self, oid: u32) -> Option<ScalarType> {
+        let entry = self.entries().find(|entry| {
+            entry.item_type() == CatalogItemType::Type && entry.id().is_user() && entry.oid() == oid
+        })?;
+        mz_sql::plan::scalar_type_from_catalog(&self.for_system_session(), entry.id(), &[]).ok()
+    }
+
     pub fn get_network_policy(&self, network_policy_id: NetworkPolicyId) -> &NetworkPolicy {
         self.state.get_network_policy(&network_policy_id)
     }
@@ -3487,6 +3497,16 @@ mod tests {
                         | ScalarType::VarChar { .. }
                         | ScalarType::Jsonb
                         | ScalarType::Uuid
+                        | ScalarType::Inet
+                        | ScalarType::Cidr
+                        | ScalarType::MacAddr
+                        | ScalarType::MacAddr8
+                        | ScalarType::TsVector
+                        | ScalarType::TsQuery
+                        | ScalarType::JsonPath
+                        | ScalarType::Enum { .. }
+                        | ScalarType::Geometry
+                        | ScalarType::Geography
                         | ScalarType::Array(_)
                         | ScalarType::Record { .. }
                         | ScalarType::Oid
@@ -28,9 +28,9 @@ use mz_catalog::durable::objects::{
 use mz_catalog::durable::{CatalogError, SystemObjectMapping};
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
-    CatalogEntry, CatalogItem, Cluster, ClusterReplica, DataSourceDesc, Database, Func, Index, Log,
-    NetworkPolicy, Role, Schema, Source, StateDiff, StateUpdate, StateUpdateKind, Table,
-    TableDataSource, TemporaryItem, Type, UpdateFrom,
+    CatalogEntry, CatalogItem, Cluster, ClusterReplica, DataSourceDesc, Database, Func,
+    FuncDefinition, Index, Log, NetworkPolicy, Role, Schema, Source, StateDiff, StateUpdate,
+    StateUpdateKind, Table, TableDataSource, TemporaryItem, Type, UpdateFrom,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_compute_types::config::ComputeReplicaConfig;
@@ -776,8 +776,10 @@ impl CatalogState {
                     oid,
                     name.clone(),
                     CatalogItem::Func(Func {
-                        inner: func.inner,
+                        create_sql: None,
+                        inner: FuncDefinition::Builtin(func.inner),
                         global_id,
+                        resolved_ids: ResolvedIds::empty(),
                     }),
                     MZ_SYSTEM_ROLE_ID,
                     PrivilegeMap::default(),
@@ -985,11 +987,15 @@ impl CatalogState {
                 let entry = match retractions.items.remove(&key) {
                     Some(mut retraction) => {
                         assert_eq!(retraction.id, item.id);
-                        // We only reparse the SQL if it's changed. Otherwise, we use the existing
-                        // item. This is a performance optimization and not needed for correctness.
-                        // This makes it difficult to use the `UpdateFrom` trait, but the structure
-                        // is still the same as the trait.
-                        if retraction.create_sql() != create_sql {
+                        // We only reparse the SQL if it or the item's versions have changed.
+                        // Otherwise, we use the existing item. This is a performance optimization
+                        // and not needed for correctness. This makes it difficult to use the
+                        // `UpdateFrom` trait, but the structure is still the same as the trait.
+                        let latest_global_id =
+                            extra_versions.values().last().copied().unwrap_or(global_id);
+                        if retraction.create_sql() != create_sql
+                            || retraction.latest_global_id() != latest_global_id
+                        {
                             let item = self
                                 .deserialize_item(
                                     global_id,
@@ -1640,21 +1646,12 @@ impl CatalogState {
             &metadata.name().qualifiers.schema_spec,
             conn_id,
         );
-        if metadata.item_type() == CatalogItemType::Type {
-            schema
-                .types
-                .remove(&metadata.name().item)
-                .expect("catalog out of sync");
-        } else {
-            // Functions would need special handling, but we don't yet support
-            // dropping functions.
-            assert_ne!(metadata.item_type(), CatalogItemType::Func);
-
-            schema
-                .items
-                .remove(&metadata.name().item)
-                .expect("catalog out of sync");
-        };
+        match metadata.item_type() {
+            CatalogItemType::Type => schema.types.remove(&metadata.name().item),
+            CatalogItemType::Func => schema.functions.remove(&metadata.name().item),
+            _ => schema.items.remove(&metadata.name().item),
+        }
+        .expect("catalog out of sync");
 
         if !id.is_system() {
             if let Some(cluster_id) = metadata.item().cluster_id() {
@@ -1923,9 +1920,6 @@ fn sort_updates_inner(updates: Vec<StateUpdate>) -> Vec<StateUpdate> {
         // Partition items into groups s.t. each item in one group has a predefined order with all
         // items in other groups. For example, all sinks are ordered greater than all tables.
         let mut types = Vec::new();
-        // N.B. Functions can depend on system tables, but not user tables.
-        // TODO(udf): This will change when UDFs are supported.
-        let mut funcs = Vec::new();
         let mut secrets = Vec::new();
         let mut connections = Vec::new();
         let mut sources = Vec::new();
@@ -1937,12 +1931,14 @@ fn sort_updates_inner(updates: Vec<StateUpdate>) -> Vec<StateUpdate> {
         for update in item_updates {
             match update.0.item_type() {
                 CatalogItemType::Type => types.push(update),
-                CatalogItemType::Func => funcs.push(update),
                 CatalogItemType::Secret => secrets.push(update),
                 CatalogItemType::Connection => connections.push(update),
                 CatalogItemType::Source => sources.push(update),
                 CatalogItemType::Table => tables.push(update),
-                CatalogItemType::View
+                // User-defined functions can depend on relations and other
+                // functions, and relations can depend on them.
+                CatalogItemType::Func
+                | CatalogItemType::View
                 | CatalogItemType::MaterializedView
                 | CatalogItemType::Index => derived_items.push(update),
                 CatalogItemType::Sink => sinks.push(update),
@@ -1953,7 +1949,6 @@ fn sort_updates_inner(updates: Vec<StateUpdate>) -> Vec<StateUpdate> {
         // Within each group, sort by ID.
         for group in [
             &mut types,
-            &mut funcs,
             &mut secrets,
             &mut connections,
             &mut sources,
@@ -1967,7 +1962,6 @@ fn sort_updates_inner(updates: Vec<StateUpdate>) -> Vec<StateUpdate> {
 
         iter::empty()
             .chain(types)
-            .chain(funcs)
             .chain(secrets)
             .chain(connections)
             .chain(sources)
@@ -18,23 +18,24 @@ use mz_catalog::builtin::{
     MZ_AWS_PRIVATELINK_CONNECTIONS, MZ_BASE_TYPES, MZ_CLUSTERS, MZ_CLUSTER_REPLICAS,
     MZ_CLUSTER_REPLICA_METRICS, MZ_CLUSTER_REPLICA_SIZES, MZ_CLUSTER_REPLICA_STATUSES,
     MZ_CLUSTER_SCHEDULES, MZ_CLUSTER_WORKLOAD_CLASSES, MZ_COLUMNS, MZ_COMMENTS, MZ_CONNECTIONS,
-    MZ_CONTINUAL_TASKS, MZ_DATABASES, MZ_DEFAULT_PRIVILEGES, MZ_EGRESS_IPS, MZ_FUNCTIONS,
-    MZ_HISTORY_RETENTION_STRATEGIES, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_INTERNAL_CLUSTER_REPLICAS,
-    MZ_KAFKA_CONNECTIONS, MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_KAFKA_SOURCE_TABLES, MZ_LIST_TYPES,
-    MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS, MZ_MATERIALIZED_VIEW_REFRESH_STRATEGIES,
-    MZ_MYSQL_SOURCE_TABLES, MZ_NETWORK_POLICIES, MZ_NETWORK_POLICY_RULES, MZ_OBJECT_DEPENDENCIES,
-    MZ_OPERATORS, MZ_PENDING_CLUSTER_REPLICAS, MZ_POSTGRES_SOURCES, MZ_POSTGRES_SOURCE_TABLES,
-    MZ_PSEUDO_TYPES, MZ_ROLES, MZ_ROLE_MEMBERS, MZ_ROLE_PARAMETERS, MZ_SCHEMAS, MZ_SECRETS,
-    MZ_SESSIONS, MZ_SINKS, MZ_SOURCES, MZ_SOURCE_REFERENCES, MZ_SSH_TUNNEL_CONNECTIONS,
-    MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES, MZ_TABLES, MZ_TYPES,
-    MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
+    MZ_CONTINUAL_TASKS, MZ_DATABASES, MZ_DEFAULT_PRIVILEGES, MZ_EGRESS_IPS, MZ_ENUM_LABELS,
+    MZ_FUNCTIONS, MZ_HISTORY_RETENTION_STRATEGIES, MZ_INDEXES, MZ_INDEX_COLUMNS,
+    MZ_INTERNAL_CLUSTER_REPLICAS, MZ_KAFKA_CONNECTIONS, MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES,
+    MZ_KAFKA_SOURCE_TABLES, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS,
+    MZ_MATERIALIZED_VIEW_REFRESH_STRATEGIES, MZ_MYSQL_SOURCE_TABLES, MZ_NETWORK_POLICIES,
+    MZ_NETWORK_POLICY_RULES, MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_PENDING_CLUSTER_REPLICAS,
+    MZ_POSTGRES_SOURCES, MZ_POSTGRES_SOURCE_TABLES, MZ_PSEUDO_TYPES, MZ_ROLES, MZ_ROLE_MEMBERS,
+    MZ_ROLE_PARAMETERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS, MZ_SOURCES,
+    MZ_SOURCE_REFERENCES, MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS,
+    MZ_SYSTEM_PRIVILEGES, MZ_TABLES, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
 };
 use mz_catalog::config::AwsPrincipalContext;
 use mz_catalog::durable::SourceReferences;
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
     CatalogItem, ClusterReplicaProcessStatus, ClusterVariant, Connection, ContinualTask,
-    DataSourceDesc, Func, Index, MaterializedView, Sink, Table, TableDataSource, Type, View,
+    DataSourceDesc, Func, FuncDefinition, Index, MaterializedView, Sink, Table, TableDataSource,
+    Type, View,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_controller::clusters::{
@@ -581,6 +582,9 @@ impl CatalogState {
                                         diff,
                                     )
                                 }
+                                // TODO(sql_server2): Add a builtin table that
+                                // tracks the upstream tables of SQL Server sources.
+                                "sql-server" => vec![],
                                 // Load generator sources don't have any special
                                 // updates.
                                 "load-generator" => vec![],
@@ -696,6 +700,9 @@ impl CatalogState {
                                     diff,
                                 )
                             }
+                            // TODO(sql_server2): Add a builtin table that
+                            // tracks the upstream tables of SQL Server sources.
+                            "sql-server" => vec![],
                             // Load generator sources don't have any special
                             // updates.
                             "load-generator" => vec![],
@@ -723,7 +730,7 @@ impl CatalogState {
                 self.pack_type_update(id, oid, schema_id, name, owner_id, privileges, ty, diff)
             }
             CatalogItem::Func(func) => {
-                self.pack_func_update(id, schema_id, name, owner_id, func, diff)
+                self.pack_func_update(id, oid, schema_id, name, owner_id, func, diff)
             }
             CatalogItem::Secret(_) => {
                 self.pack_secret_update(id, oid, schema_id, name, owner_id, privileges, diff)
@@ -1086,6 +1093,7 @@ impl CatalogState {
                     ConnectionDetails::AwsPrivatelink(..) => "aws-privatelink",
                     ConnectionDetails::Ssh { .. } => "ssh-tunnel",
                     ConnectionDetails::MySql { .. } => "mysql",
+                    ConnectionDetails::SqlServer(_) => "sql-server",
                 }),
                 Datum::String(&owner_id.to_string()),
                 privileges,
@@ -1128,7 +1136,8 @@ impl CatalogState {
             }
             ConnectionDetails::Csr(_)
             | ConnectionDetails::Postgres(_)
-            | ConnectionDetails::MySql(_) => (),
+            | ConnectionDetails::MySql(_)
+            | ConnectionDetails::SqlServer(_) => (),
         };
         updates
     }
@@ -1508,6 +1517,9 @@ impl CatalogState {
                     diff,
                 ));
             }
+            StorageSinkConnection::Iceberg(_)
+            | StorageSinkConnection::S3(_)
+            | StorageSinkConnection::Postgres(_) => {}
         };
 
         let create_stmt = mz_sql::parse::parse(&sink.create_sql)
@@ -1738,12 +1750,27 @@ impl CatalogState {
             ));
         }
 
+        if let CatalogType::Enum { labels } = &typ.details.typ {
+            for (position, label) in (1u32..).zip(labels) {
+                out.push(BuiltinTableUpdate::row(
+                    &*MZ_ENUM_LABELS,
+                    Row::pack_slice(&[
+                        Datum::String(&id.to_string()),
+                        Datum::UInt32(position),
+                        Datum::String(label),
+                    ]),
+                    diff,
+                ));
+            }
+        }
+
         out
     }
 
     fn pack_func_update(
         &self,
         id: CatalogItemId,
+        oid: u32,
         schema_id: &SchemaSpecifier,
         name: &str,
         owner_id: &RoleId,
@@ -1752,6 +1779,12 @@ impl CatalogState {
     ) -> Vec<BuiltinTableUpdate<&'static BuiltinTable>> {
         let mut updates = vec![];
         for func_impl_details in func.inner.func_impls() {
+            // User-defined functions have a single implementation, which is
+            // identified by the OID of the function itself.
+            let impl_oid = match func.inner {
+                FuncDefinition::Builtin(_) => func_impl_details.oid,
+                FuncDefinition::User(_) => oid,
+            };
             let arg_type_ids = func_impl_details
                 .arg_typs
                 .iter()
@@ -1776,7 +1809,7 @@ impl CatalogState {
                 &*MZ_FUNCTIONS,
                 Row::pack_slice(&[
                     Datum::String(&id.to_string()),
-                    Datum::UInt32(func_impl_details.oid),
+                    Datum::UInt32(impl_oid),
                     Datum::String(&schema_id.to_string()),
                     Datum::String(name),
                     arg_type_ids,
@@ -1798,11 +1831,11 @@ impl CatalogState {
                 diff,
             ));
 
-            if let mz_sql::func::Func::Aggregate(_) = func.inner {
+            if let mz_sql::func::Func::Aggregate(_) = &*func.inner {
                 updates.push(BuiltinTableUpdate::row(
                     &*MZ_AGGREGATES,
                     Row::pack_slice(&[
-                        Datum::UInt32(func_impl_details.oid),
+                        Datum::UInt32(impl_oid),
                         // TODO(database-issues#1064): Support ordered-set aggregate functions.
                         Datum::String("n"),
                         Datum::Int16(0),
@@ -547,6 +547,9 @@ impl CatalogState {
                         )
                         | Statement::CreateTable(ast::CreateTableStatement { name, .. })
                         | Statement::CreateType(ast::CreateTypeStatement { name, .. })
+                        | Statement::CreateFunction(ast::CreateFunctionStatement {
+                            name, ..
+                        })
                         | Statement::CreateSecret(ast::CreateSecretStatement { name, .. }) => {
                             let [db_component, schema_component, item_component] = &name.0[..]
                             else {
@@ -30,9 +30,9 @@ use mz_catalog::expr_cache::LocalExpressions;
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
     CatalogCollectionEntry, CatalogEntry, CatalogItem, Cluster, ClusterReplica, CommentsMap,
-    Connection, DataSourceDesc, Database, DefaultPrivileges, Index, MaterializedView,
-    NetworkPolicy, Role, Schema, Secret, Sink, Source, SourceReferences, Table, TableDataSource,
-    Type, View,
+    Connection, DataSourceDesc, Database, DefaultPrivileges, Func, FuncDefinition, Index,
+    MaterializedView, NetworkPolicy, Role, Schema, Secret, Sink, Source, SourceReferences, Table,
+    TableDataSource, Type, View,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_controller::clusters::{
@@ -71,9 +71,9 @@ use mz_sql::names::{
     ResolvedDatabaseSpecifier, ResolvedIds, SchemaId, SchemaSpecifier, SystemObjectId,
 };
 use mz_sql::plan::{
-    CreateConnectionPlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateSecretPlan,
-    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, Params,
-    Plan, PlanContext,
+    CreateConnectionPlan, CreateFunctionPlan, CreateIndexPlan, CreateMaterializedViewPlan,
+    CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
+    CreateViewPlan, Params, Plan, PlanContext,
 };
 use mz_sql::rbac;
 use mz_sql::session::metadata::SessionMetadata;
@@ -406,6 +406,7 @@ impl CatalogState {
             item @ (CatalogItem::View(_)
             | CatalogItem::MaterializedView(_)
             | CatalogItem::Connection(_)
+            | CatalogItem::Func(_)
             | CatalogItem::ContinualTask(_)) => {
                 // TODO(jkosh44) Unclear if this table wants to include all uses or only references.
                 for item_id in item.references().items() {
@@ -423,7 +424,6 @@ impl CatalogState {
             CatalogItem::Table(_)
             | CatalogItem::Source(_)
             | CatalogItem::Type(_)
-            | CatalogItem::Func(_)
             | CatalogItem::Secret(_) => (),
         }
     }
@@ -732,6 +732,14 @@ impl CatalogState {
                     .expect("version to exist");
                 RelationVersionSelector::Specific(*version)
             }
+            CatalogItem::MaterializedView(mv) => {
+                let (version, _) = mv
+                    .collections
+                    .iter()
+                    .find(|(_version, gid)| *gid == id)
+                    .expect("version to exist");
+                RelationVersionSelector::Specific(*version)
+            }
             _ => RelationVersionSelector::Latest,
         };
         CatalogCollectionEntry { entry, version }
@@ -1002,7 +1010,13 @@ impl CatalogState {
         local_expression_cache: &mut LocalExpressionCache,
         previous_item: Option<CatalogItem>,
     ) -> Result<CatalogItem, AdapterError> {
-        let cached_expr = local_expression_cache.remove_cached_expression(&global_id);
+        // Materialized views get a new `GlobalId` whenever their definition is replaced, so the
+        // cached expression belongs to the latest version.
+        let cache_id = extra_versions
+            .last_key_value()
+            .map(|(_version, gid)| *gid)
+            .unwrap_or(global_id);
+        let cached_expr = local_expression_cache.remove_cached_expression(&cache_id);
         match self.parse_item_inner(
             global_id,
             create_sql,
@@ -1016,7 +1030,7 @@ impl CatalogState {
             Ok((item, uncached_expr)) => {
                 if let Some((uncached_expr, optimizer_features)) = uncached_expr {
                     local_expression_cache.insert_uncached_expression(
-                        global_id,
+                        cache_id,
                         uncached_expr,
                         optimizer_features,
                     );
@@ -1025,7 +1039,7 @@ impl CatalogState {
             }
             Err((err, cached_expr)) => {
                 if let Some(local_expr) = cached_expr {
-                    local_expression_cache.insert_cached_expression(global_id, local_expr);
+                    local_expression_cache.insert_cached_expression(cache_id, local_expr);
                 }
                 Err(err)
             }
@@ -1311,9 +1325,15 @@ impl CatalogState {
                     .map(|gid| self.get_entry_by_global_id(&gid).id())
                     .collect();
 
+                let collections = extra_versions
+                    .iter()
+                    .map(|(version, gid)| (*version, *gid))
+                    .chain([(RelationVersion::root(), global_id)].into_iter())
+                    .collect();
+
                 CatalogItem::MaterializedView(MaterializedView {
                     create_sql: materialized_view.create_sql,
-                    global_id,
+                    collections,
                     raw_expr,
                     optimized_expr,
                     desc,
@@ -1379,6 +1399,12 @@ impl CatalogState {
                     resolved_ids,
                 })
             }
+            Plan::CreateFunction(CreateFunctionPlan { function, .. }) => CatalogItem::Func(Func {
+                create_sql: Some(function.create_sql),
+                inner: FuncDefinition::User(Arc::new(function.inner)),
+                global_id,
+                resolved_ids,
+            }),
             Plan::CreateSecret(CreateSecretPlan { secret, .. }) => CatalogItem::Secret(Secret {
                 create_sql: secret.create_sql,
                 global_id,
@@ -1806,6 +1832,18 @@ impl CatalogState {
             CatalogType::VarChar => CatalogType::VarChar,
             CatalogType::Int2Vector => CatalogType::Int2Vector,
             CatalogType::MzAclItem => CatalogType::MzAclItem,
+            CatalogType::Inet => CatalogType::Inet,
+            CatalogType::Cidr => CatalogType::Cidr,
+            CatalogType::MacAddr => CatalogType::MacAddr,
+            CatalogType::MacAddr8 => CatalogType::MacAddr8,
+            CatalogType::TsVector => CatalogType::TsVector,
+            CatalogType::TsQuery => CatalogType::TsQuery,
+            CatalogType::JsonPath => CatalogType::JsonPath,
+            CatalogType::Enum { labels } => CatalogType::Enum {
+                labels: labels.clone(),
+            },
+            CatalogType::Geometry => CatalogType::Geometry,
+            CatalogType::Geography => CatalogType::Geography,
         };
 
         BuiltinType {
@@ -28,8 +28,8 @@ use mz_catalog::builtin::BuiltinLog;
 use mz_catalog::durable::{NetworkPolicy, Transaction};
 use mz_catalog::memory::error::{AmbiguousRename, Error, ErrorKind};
 use mz_catalog::memory::objects::{
-    CatalogItem, ClusterConfig, DataSourceDesc, SourceReferences, StateDiff, StateUpdate,
-    StateUpdateKind, TemporaryItem,
+    CatalogItem, ClusterConfig, DataSourceDesc, MaterializedView, SourceReferences, StateDiff,
+    StateUpdate, StateUpdateKind, TemporaryItem,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_controller::clusters::{ManagedReplicaLocation, ReplicaConfig, ReplicaLocation};
@@ -98,6 +98,14 @@ pub enum Op {
         typ: ColumnType,
         sql: RawDataType,
     },
+    /// Replaces the definition of a materialized view with `to_item`, whose output is written
+    /// to the existing persist shard under `new_global_id`.
+    ReplaceMaterializedView {
+        id: CatalogItemId,
+        new_global_id: GlobalId,
+        name: QualifiedItemName,
+        to_item: MaterializedView,
+    },
     CreateDatabase {
         name: String,
         owner_id: RoleId,
@@ -412,9 +420,15 @@ impl Catalog {
                 }
             })
             .collect();
+        // The plans of a replaced materialized view definition are dropped along with it.
+        let replaced_global_ids = ops.iter().filter_map(|op| match op {
+            Op::ReplaceMaterializedView { id, .. } => Some(self.get_entry(id).latest_global_id()),
+            _ => None,
+        });
         let dropped_global_ids = drop_ids
             .iter()
             .flat_map(|item_id| self.get_global_ids(item_id))
+            .chain(replaced_global_ids)
             .collect();
 
         let temporary_ids = self.temporary_ids(&ops, temporary_drops)?;
@@ -740,6 +754,58 @@ impl Catalog {
                 tx.update_item(id, new_entry.into())?;
                 storage_collections_to_register.insert(new_global_id, shard_id);
             }
+            Op::ReplaceMaterializedView {
+                id,
+                new_global_id,
+                name,
+                mut to_item,
+            } => {
+                let mut entry = state.get_entry(&id).clone();
+                let CatalogItem::MaterializedView(mv) = &entry.item else {
+                    return Err(AdapterError::Internal(format!(
+                        "expected materialized view, found {:?}",
+                        entry.item.typ()
+                    )));
+                };
+                // All versions of a materialized view share the same shard, so it shouldn't
+                // matter what GlobalId we use here.
+                let shard_id = state
+                    .storage_metadata()
+                    .get_collection_shard(mv.global_id())?;
+                let (latest_version, _) = mv
+                    .collections
+                    .last_key_value()
+                    .expect("at least one version of a materialized view");
+                let new_version = latest_version.bump();
+
+                to_item.collections = mv.collections.clone();
+                to_item.collections.insert(new_version, new_global_id);
+                entry.name = name.clone();
+                entry.item = CatalogItem::MaterializedView(to_item);
+                tx.update_item(id, entry.into())?;
+                storage_collections_to_register.insert(new_global_id, shard_id);
+
+                let mut full_name = Self::full_name_detail(
+                    &state.resolve_full_name(&name, session.map(|session| session.conn_id())),
+                );
+                full_name.item = name.item;
+
+                CatalogState::add_to_audit_log(
+                    &state.system_configuration,
+                    oracle_write_ts,
+                    session,
+                    tx,
+                    audit_events,
+                    EventType::Alter,
+                    ObjectType::MaterializedView,
+                    EventDetails::UpdateItemV1(mz_audit_log::UpdateItemV1 {
+                        id: id.to_string(),
+                        name: full_name,
+                    }),
+                )?;
+
+                Self::log_update(state, &id);
+            }
             Op::CreateDatabase { name, owner_id } => {
                 let database_owner_privileges = vec![rbac::owner_privilege(
                     mz_sql::catalog::ObjectType::Database,
@@ -32,6 +32,7 @@ use mz_ore::result::ResultExt;
 use mz_ore::task::AbortOnDropHandle;
 use mz_ore::thread::JoinOnDropHandle;
 use mz_ore::tracing::OpenTelemetryContext;
+use mz_pgwire_common::ScramSecret;
 use mz_repr::{CatalogItemId, Row, RowIterator, ScalarType};
 use mz_sql::ast::{Raw, Statement};
 use mz_sql::catalog::{EnvironmentId, SessionCatalog};
@@ -437,6 +438,21 @@ Issue a SQL query to get started. Need help?
         rx.await.expect("coordinator unexpectedly gone")
     }
 
+    /// Returns the SCRAM-SHA-256 verifier of the password of the role named `role_name`.
+    ///
+    /// Returns `None` if the role does not exist, is not allowed to log in, or does not have a
+    /// password.
+    pub async fn get_role_password(&self, role_name: &str) -> Option<ScramSecret> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Command::CatalogSnapshot { tx });
+        let CatalogSnapshot { catalog } = rx.await.expect("coordinator unexpectedly gone");
+        let attributes = &catalog.try_get_role_by_name(role_name)?.attributes;
+        if !attributes.can_login_with_password() {
+            return None;
+        }
+        attributes.password_hash.as_ref()?.parse().ok()
+    }
+
     #[instrument(level = "debug")]
     fn send(&self, cmd: Command) {
         self.inner_cmd_tx
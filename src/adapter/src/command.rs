@@ -292,6 +292,8 @@ pub enum ExecuteResponse {
     CreatedMaterializedView,
     /// The requested continual task was created.
     CreatedContinualTask,
+    /// The requested function was created.
+    CreatedFunction,
     /// The requested type was created.
     CreatedType,
     /// The requested network policy was created.
@@ -328,6 +330,9 @@ pub enum ExecuteResponse {
     GrantedRole,
     /// The specified number of rows were inserted into the requested table.
     Inserted(usize),
+    /// The specified number of rows were changed by a `MERGE` into the
+    /// requested table.
+    Merged(usize),
     /// The specified prepared statement was created.
     Prepare,
     /// A user-requested warning was raised.
@@ -463,6 +468,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             }
             ExecuteResponseKind::CreatedNetworkPolicy => Ok(ExecuteResponse::CreatedNetworkPolicy),
             ExecuteResponseKind::CreatedContinualTask => Ok(ExecuteResponse::CreatedContinualTask),
+            ExecuteResponseKind::CreatedFunction => Ok(ExecuteResponse::CreatedFunction),
             ExecuteResponseKind::CreatedType => Ok(ExecuteResponse::CreatedType),
             ExecuteResponseKind::Deallocate => Err(()),
             ExecuteResponseKind::DeclaredCursor => Ok(ExecuteResponse::DeclaredCursor),
@@ -476,6 +482,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::GrantedPrivilege => Ok(ExecuteResponse::GrantedPrivilege),
             ExecuteResponseKind::GrantedRole => Ok(ExecuteResponse::GrantedRole),
             ExecuteResponseKind::Inserted => Err(()),
+            ExecuteResponseKind::Merged => Err(()),
             ExecuteResponseKind::Prepare => Ok(ExecuteResponse::Prepare),
             ExecuteResponseKind::Raised => Ok(ExecuteResponse::Raised),
             ExecuteResponseKind::ReassignOwned => Ok(ExecuteResponse::ReassignOwned),
@@ -525,6 +532,7 @@ impl ExecuteResponse {
             CreatedViews { .. } => Some("CREATE VIEWS".into()),
             CreatedMaterializedView { .. } => Some("CREATE MATERIALIZED VIEW".into()),
             CreatedContinualTask { .. } => Some("CREATE CONTINUAL TASK".into()),
+            CreatedFunction => Some("CREATE FUNCTION".into()),
             CreatedType => Some("CREATE TYPE".into()),
             CreatedNetworkPolicy => Some("CREATE NETWORKPOLICY".into()),
             Deallocate { all } => Some(format!("DEALLOCATE{}", if *all { " ALL" } else { "" })),
@@ -548,6 +556,7 @@ impl ExecuteResponse {
                 // have OIDs.
                 Some(format!("INSERT 0 {}", n))
             }
+            Merged(n) => Some(format!("MERGE {}", n)),
             Prepare => Some("PREPARE".into()),
             Raised => Some("RAISE".into()),
             ReassignOwned => Some("REASSIGN OWNED".into()),
@@ -612,10 +621,11 @@ impl ExecuteResponse {
             CreateSecret => &[CreatedSecret],
             CreateSink => &[CreatedSink],
             CreateTable => &[CreatedTable],
-            CreateView => &[CreatedView],
-            CreateMaterializedView => &[CreatedMaterializedView],
+            CreateView => &[CreatedView, AlteredObject],
+            CreateMaterializedView => &[CreatedMaterializedView, AlteredObject],
             CreateContinualTask => &[CreatedContinualTask],
             CreateIndex => &[CreatedIndex],
+            CreateFunction => &[CreatedFunction],
             CreateType => &[CreatedType],
             PlanKind::Deallocate => &[ExecuteResponseKind::Deallocate],
             CreateNetworkPolicy => &[CreatedNetworkPolicy],
@@ -634,6 +644,7 @@ impl ExecuteResponse {
             Execute | ReadThenWrite => &[
                 Deleted,
                 Inserted,
+                Merged,
                 SendingRows,
                 SendingRowsImmediate,
                 Updated,
@@ -2042,7 +2042,7 @@ impl Coordinator {
                         .entry(policy.expect("materialized views have a compaction window"))
                         .or_insert_with(Default::default)
                         .storage_ids
-                        .insert(mview.global_id());
+                        .extend(mview.global_ids());
 
                     let mut df_desc = self
                         .catalog()
@@ -2730,20 +2730,31 @@ impl Coordinator {
                     };
                 }
                 CatalogItem::MaterializedView(mv) => {
-                    let collection_desc = CollectionDescription {
-                        desc: mv.desc.clone(),
-                        data_source: DataSource::Other,
-                        since: mv.initial_as_of.clone(),
-                        status_collection_id: None,
-                        timeline: None,
-                    };
+                    // All versions of a materialized view write into the same shard, whose
+                    // compaction is driven by the first version.
+                    let primary_id = mv.primary_global_id();
+                    let collection_descs = mv.global_ids().map(|gid| {
+                        let (primary, since) = if gid == primary_id {
+                            (None, mv.initial_as_of.clone())
+                        } else {
+                            (Some(primary_id), None)
+                        };
+                        let collection_desc = CollectionDescription {
+                            desc: mv.desc.clone(),
+                            data_source: DataSource::Other { primary },
+                            since,
+                            status_collection_id: None,
+                            timeline: None,
+                        };
+                        (gid, collection_desc)
+                    });
                     compute_collections.push((mv.global_id(), mv.desc.clone()));
-                    collections.push((mv.global_id(), collection_desc));
+                    collections.extend(collection_descs);
                 }
                 CatalogItem::ContinualTask(ct) => {
                     let collection_desc = CollectionDescription {
                         desc: ct.desc.clone(),
-                        data_source: DataSource::Other,
+                        data_source: DataSource::Other { primary: None },
                         since: ct.initial_as_of.clone(),
                         status_collection_id: None,
                         timeline: None,
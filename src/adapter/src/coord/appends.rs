@@ -949,6 +949,7 @@ pub(crate) fn waiting_on_startup_appends(
         Plan::ExplainTimestamp(ExplainTimestampPlan { raw_plan, .. }) => raw_plan.depends_on(),
         Plan::CreateConnection(_)
         | Plan::CreateDatabase(_)
+        | Plan::CreateFunction(_)
         | Plan::CreateSchema(_)
         | Plan::CreateRole(_)
         | Plan::CreateNetworkPolicy(_)
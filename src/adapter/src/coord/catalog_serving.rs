@@ -70,6 +70,7 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         ),
         Plan::CreateConnection(_)
         | Plan::CreateDatabase(_)
+        | Plan::CreateFunction(_)
         | Plan::CreateSchema(_)
         | Plan::CreateRole(_)
         | Plan::CreateNetworkPolicy(_)
@@ -30,10 +30,12 @@ use mz_repr::role_id::RoleId;
 use mz_repr::{ScalarType, Timestamp};
 use mz_sql::ast::{
     AlterConnectionAction, AlterConnectionStatement, AlterSourceAction, AstInfo, ConstantVisitor,
-    CopyRelation, CopyStatement, CreateSourceOptionName, Raw, Statement, SubscribeStatement,
+    CopyRelation, CopyStatement, CreateSourceOptionName, Raw, RawItemName, Statement,
+    SubscribeStatement,
 };
-use mz_sql::catalog::RoleAttributes;
+use mz_sql::catalog::{RoleAttributes, SessionCatalog};
 use mz_sql::names::{Aug, PartialItemName, ResolvedIds};
+use mz_sql::normalize;
 use mz_sql::plan::{
     AbortTransactionPlan, CommitTransactionPlan, CreateRolePlan, Params, Plan,
     StatementClassification, TransactionType,
@@ -43,7 +45,7 @@ use mz_sql::pure::{
 };
 use mz_sql::rbac;
 use mz_sql::rbac::CREATE_ITEM_USAGE;
-use mz_sql::session::user::User;
+use mz_sql::session::user::{User, MZ_SYSTEM_ROLE_ID};
 use mz_sql::session::vars::{
     EndTransactionAction, OwnedVarInput, Value, Var, NETWORK_POLICY, STATEMENT_LOGGING_SAMPLE_RATE,
 };
@@ -82,7 +84,22 @@ impl Coordinator {
     pub(crate) fn handle_command(&mut self, mut cmd: Command) -> LocalBoxFuture<()> {
         async move {
             if let Some(session) = cmd.session_mut() {
-                session.apply_external_metadata_updates();
+                if session.apply_external_metadata_updates() {
+                    // The session was refreshed with a new token, whose groups might differ from
+                    // those that the session was established with.
+                    let role_id = session.role_metadata().authenticated_role;
+                    let groups = session
+                        .user()
+                        .external_metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.groups.clone());
+                    if let Some(groups) = groups {
+                        if let Err(e) = self.sync_external_role_memberships(role_id, &groups).await
+                        {
+                            warn!(?role_id, "failed to sync role memberships: {e}");
+                        }
+                    }
+                }
             }
             match cmd {
                 Command::Startup {
@@ -335,6 +352,63 @@ impl Coordinator {
         }
     }
 
+    /// Makes the role `role_id` a member of exactly those user roles that are named after one
+    /// of `groups`, the groups that an external identity provider reports for the user.
+    ///
+    /// Memberships that are synchronized this way are granted by the system role. Only those
+    /// are revoked when the identity provider stops reporting the group, so that memberships
+    /// granted with `GRANT` are left alone.
+    async fn sync_external_role_memberships(
+        &mut self,
+        role_id: RoleId,
+        groups: &[String],
+    ) -> Result<(), AdapterError> {
+        let catalog = self.catalog();
+        let group_ids: BTreeSet<_> = groups
+            .iter()
+            .filter_map(|group| catalog.try_get_role_by_name(group))
+            .map(|role| role.id)
+            .filter(|id| id.is_user() && *id != role_id)
+            .collect();
+        let membership = &catalog.state().get_role(&role_id).membership.map;
+
+        let mut ops = Vec::new();
+        for group_id in &group_ids {
+            // A group whose role is already a member of the user's role would introduce a
+            // cycle.
+            if membership.contains_key(group_id)
+                || catalog
+                    .state()
+                    .collect_role_membership(group_id)
+                    .contains(&role_id)
+            {
+                continue;
+            }
+            ops.push(catalog::Op::GrantRole {
+                role_id: *group_id,
+                member_id: role_id,
+                grantor_id: MZ_SYSTEM_ROLE_ID,
+            });
+        }
+        for (group_id, grantor_id) in membership {
+            if *grantor_id == MZ_SYSTEM_ROLE_ID
+                && group_id.is_user()
+                && !group_ids.contains(group_id)
+            {
+                ops.push(catalog::Op::RevokeRole {
+                    role_id: *group_id,
+                    member_id: role_id,
+                    grantor_id: *grantor_id,
+                });
+            }
+        }
+
+        if ops.is_empty() {
+            return Ok(());
+        }
+        self.catalog_transact_conn(None, ops).await
+    }
+
     // Failible startup work that needs to be cleaned up on error.
     async fn handle_startup_inner(
         &mut self,
@@ -364,6 +438,16 @@ impl Coordinator {
             return Err(AdapterError::UserSessionsDisallowed);
         }
 
+        // Identity providers that report the groups of a user manage the memberships of the
+        // user's role.
+        if let Some(groups) = user
+            .external_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.groups.as_ref())
+        {
+            self.sync_external_role_memberships(role_id, groups).await?;
+        }
+
         // Initialize the default session variables for this role.
         let mut session_defaults = BTreeMap::new();
         let system_config = self.catalog().state().system_config();
@@ -669,10 +753,19 @@ impl Coordinator {
                     }
 
                     Statement::Insert(InsertStatement {
-                        source, returning, ..
-                    }) if returning.is_empty() && ConstantVisitor::insert_source(source) => {
+                        table_name,
+                        source,
+                        on_conflict,
+                        returning,
+                        ..
+                    }) if on_conflict.is_none()
+                        && returning.is_empty()
+                        && ConstantVisitor::insert_source(source)
+                        && !self.has_enforced_keys(ctx.session(), table_name) =>
+                    {
                         // Inserting from constant values statements that do not need to execute on
-                        // any cluster (no RETURNING) is always safe.
+                        // any cluster (no RETURNING) is always safe, unless the table has unique
+                        // keys that the insert must read the table to enforce.
                     }
 
                     // These statements must be kept in-sync with `must_serialize_ddl()`.
@@ -708,11 +801,13 @@ impl Coordinator {
                     | Statement::AlterSystemResetAll(_)
                     | Statement::AlterSystemSet(_)
                     | Statement::AlterTableAddColumn(_)
+                    | Statement::AlterViewQuery(_)
                     | Statement::AlterNetworkPolicy(_)
                     | Statement::CreateCluster(_)
                     | Statement::CreateClusterReplica(_)
                     | Statement::CreateConnection(_)
                     | Statement::CreateDatabase(_)
+                    | Statement::CreateFunction(_)
                     | Statement::CreateIndex(_)
                     | Statement::CreateMaterializedView(_)
                     | Statement::CreateContinualTask(_)
@@ -734,6 +829,7 @@ impl Coordinator {
                     | Statement::GrantPrivileges(_)
                     | Statement::GrantRole(_)
                     | Statement::Insert(_)
+                    | Statement::Merge(_)
                     | Statement::ReassignOwned(_)
                     | Statement::RevokePrivileges(_)
                     | Statement::RevokeRole(_)
@@ -1088,6 +1184,20 @@ impl Coordinator {
         true
     }
 
+    /// Whether `table_name` names a table with unique keys, which writes to the table must read
+    /// the table to enforce.
+    fn has_enforced_keys(&self, session: &Session, table_name: &RawItemName) -> bool {
+        let catalog = self.catalog().for_session(session);
+        let Ok(name) = normalize::unresolved_item_name(table_name.name().clone()) else {
+            return false;
+        };
+        let Ok(item) = catalog.resolve_item(&name) else {
+            return false;
+        };
+        item.desc(&catalog.resolve_full_name(item.name()))
+            .map_or(false, |desc| !desc.typ().keys.is_empty())
+    }
+
     /// Chooses a timestamp for `mz_now()`, if `mz_now()` occurs in a REFRESH option of the
     /// materialized view. Additionally, if `acquire_read_holds` is true and the MV has any REFRESH
     /// option, this function grabs read holds at the earliest possible time on input collections
@@ -44,7 +44,7 @@ use mz_sql::session::vars::{
     MAX_CREDIT_CONSUMPTION_RATE, MAX_DATABASES, MAX_KAFKA_CONNECTIONS, MAX_MATERIALIZED_VIEWS,
     MAX_MYSQL_CONNECTIONS, MAX_NETWORK_POLICIES, MAX_OBJECTS_PER_SCHEMA, MAX_POSTGRES_CONNECTIONS,
     MAX_REPLICAS_PER_CLUSTER, MAX_ROLES, MAX_SCHEMAS_PER_DATABASE, MAX_SECRETS, MAX_SINKS,
-    MAX_SOURCES, MAX_TABLES,
+    MAX_SOURCES, MAX_SQL_SERVER_CONNECTIONS, MAX_TABLES,
 };
 use mz_storage_client::controller::{CollectionDescription, DataSource, ExportDescription};
 use mz_storage_types::connections::inline::IntoInlineConnection;
@@ -198,6 +198,7 @@ impl Coordinator {
         let mut storage_sink_gids_to_drop = vec![];
         let mut indexes_to_drop = vec![];
         let mut materialized_views_to_drop = vec![];
+        let mut materialized_view_versions_to_drop = vec![];
         let mut continual_tasks_to_drop = vec![];
         let mut views_to_drop = vec![];
         let mut replication_slots_to_drop: Vec<(PostgresConnection, String)> = vec![];
@@ -262,6 +263,12 @@ impl Coordinator {
                                     CatalogItem::MaterializedView(mv) => {
                                         materialized_views_to_drop
                                             .push((mv.cluster_id, mv.global_id()));
+                                        // Only the latest version of a materialized view is
+                                        // maintained by a dataflow, but every version has a
+                                        // storage collection.
+                                        materialized_view_versions_to_drop.extend(
+                                            mv.global_ids().filter(|gid| *gid != mv.global_id()),
+                                        );
                                     }
                                     CatalogItem::View(view) => {
                                         views_to_drop.push((*id, view.clone()))
@@ -403,6 +410,7 @@ impl Coordinator {
             .chain(storage_sink_gids_to_drop.iter().copied())
             .chain(indexes_to_drop.iter().map(|(_, gid)| *gid))
             .chain(materialized_views_to_drop.iter().map(|(_, gid)| *gid))
+            .chain(materialized_view_versions_to_drop.iter().copied())
             .chain(continual_tasks_to_drop.iter().map(|(_, _, gid)| *gid))
             .chain(views_to_drop.iter().map(|(_id, view)| view.global_id()))
             .collect();
@@ -479,6 +487,7 @@ impl Coordinator {
             .chain(storage_sink_gids_to_drop.iter().copied())
             .chain(table_gids_to_drop.iter().map(|(_, gid)| *gid))
             .chain(materialized_views_to_drop.iter().map(|(_, gid)| *gid))
+            .chain(materialized_view_versions_to_drop.iter().copied())
             .chain(continual_tasks_to_drop.iter().map(|(_, _, gid)| *gid));
         let compute_ids_to_drop = indexes_to_drop
             .iter()
@@ -694,7 +703,10 @@ impl Coordinator {
                 self.drop_indexes(indexes_to_drop);
             }
             if !materialized_views_to_drop.is_empty() {
-                self.drop_materialized_views(materialized_views_to_drop);
+                self.drop_materialized_views(
+                    materialized_views_to_drop,
+                    materialized_view_versions_to_drop,
+                );
             }
             if !continual_tasks_to_drop.is_empty() {
                 self.drop_continual_tasks(continual_tasks_to_drop);
@@ -1109,9 +1121,16 @@ impl Coordinator {
     }
 
     /// A convenience method for dropping materialized views.
-    fn drop_materialized_views(&mut self, mviews: Vec<(ClusterId, GlobalId)>) {
+    ///
+    /// `prior_versions` are the [`GlobalId`]s of replaced definitions of the materialized views,
+    /// which only have storage collections.
+    fn drop_materialized_views(
+        &mut self,
+        mviews: Vec<(ClusterId, GlobalId)>,
+        prior_versions: Vec<GlobalId>,
+    ) {
         let mut by_cluster: BTreeMap<_, Vec<_>> = BTreeMap::new();
-        let mut mv_gids = Vec::new();
+        let mut mv_gids = prior_versions;
         for (cluster_id, gid) in mviews {
             by_cluster.entry(cluster_id).or_default().push(gid);
             mv_gids.push(gid);
@@ -1402,6 +1421,7 @@ impl Coordinator {
         let mut new_kafka_connections = 0;
         let mut new_postgres_connections = 0;
         let mut new_mysql_connections = 0;
+        let mut new_sql_server_connections = 0;
         let mut new_aws_privatelink_connections = 0;
         let mut new_tables = 0;
         let mut new_sources = 0;
@@ -1469,6 +1489,7 @@ impl Coordinator {
                             ConnectionDetails::Kafka(_) => new_kafka_connections += 1,
                             ConnectionDetails::Postgres(_) => new_postgres_connections += 1,
                             ConnectionDetails::MySql(_) => new_mysql_connections += 1,
+                            ConnectionDetails::SqlServer(_) => new_sql_server_connections += 1,
                             ConnectionDetails::AwsPrivatelink(_) => {
                                 new_aws_privatelink_connections += 1
                             }
@@ -1615,6 +1636,7 @@ impl Coordinator {
                 | Op::AlterRetainHistory { .. }
                 | Op::AlterNetworkPolicy { .. }
                 | Op::AlterAddColumn { .. }
+                | Op::ReplaceMaterializedView { .. }
                 | Op::UpdatePrivilege { .. }
                 | Op::UpdateDefaultPrivilege { .. }
                 | Op::GrantRole { .. }
@@ -1639,6 +1661,7 @@ impl Coordinator {
         let mut current_aws_privatelink_connections = 0;
         let mut current_postgres_connections = 0;
         let mut current_mysql_connections = 0;
+        let mut current_sql_server_connections = 0;
         let mut current_kafka_connections = 0;
         for c in self.catalog().user_connections() {
             let connection = c
@@ -1649,6 +1672,7 @@ impl Coordinator {
                 ConnectionDetails::AwsPrivatelink(_) => current_aws_privatelink_connections += 1,
                 ConnectionDetails::Postgres(_) => current_postgres_connections += 1,
                 ConnectionDetails::MySql(_) => current_mysql_connections += 1,
+                ConnectionDetails::SqlServer(_) => current_sql_server_connections += 1,
                 ConnectionDetails::Kafka(_) => current_kafka_connections += 1,
                 ConnectionDetails::Csr(_)
                 | ConnectionDetails::Ssh { .. }
@@ -1676,6 +1700,13 @@ impl Coordinator {
             "MySQL Connection",
             MAX_MYSQL_CONNECTIONS.name(),
         )?;
+        self.validate_resource_limit(
+            current_sql_server_connections,
+            new_sql_server_connections,
+            SystemVars::max_sql_server_connections,
+            "SQL Server Connection",
+            MAX_SQL_SERVER_CONNECTIONS.name(),
+        )?;
         self.validate_resource_limit(
             current_aws_privatelink_connections,
             new_aws_privatelink_connections,
@@ -262,6 +262,12 @@ impl Coordinator {
                 Plan::CreateIndex(plan) => {
                     self.sequence_create_index(ctx, plan, resolved_ids).await;
                 }
+                Plan::CreateFunction(plan) => {
+                    let result = self
+                        .sequence_create_function(ctx.session(), plan, resolved_ids)
+                        .await;
+                    ctx.retire(result);
+                }
                 Plan::CreateType(plan) => {
                     let result = self
                         .sequence_create_type(ctx.session(), plan, resolved_ids)
@@ -813,6 +819,7 @@ impl Coordinator {
                     kind: MutationKind::Insert,
                     returning: Vec::new(),
                     max_result_size: catalog.system_config().max_result_size(),
+                    affected_rows: None,
                 };
                 Self::send_diffs(session, diffs_plan)
             }
@@ -828,7 +835,9 @@ impl Coordinator {
         session: &mut Session,
         mut plan: plan::SendDiffsPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
-        let affected_rows = {
+        let affected_rows = if let Some(affected_rows) = plan.affected_rows {
+            affected_rows
+        } else {
             let mut affected_rows = Diff::from(0);
             let mut all_positive_diffs = true;
             // If all diffs are positive, the number of affected rows is just the
@@ -897,6 +906,8 @@ impl Coordinator {
             MutationKind::Delete => ExecuteResponse::Deleted(affected_rows),
             MutationKind::Insert => ExecuteResponse::Inserted(affected_rows),
             MutationKind::Update => ExecuteResponse::Updated(affected_rows / 2),
+            MutationKind::Upsert => ExecuteResponse::Inserted(affected_rows),
+            MutationKind::Merge => ExecuteResponse::Merged(affected_rows),
         })
     }
 }
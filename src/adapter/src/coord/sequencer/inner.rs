@@ -24,7 +24,8 @@ use mz_adapter_types::compaction::CompactionWindow;
 use mz_adapter_types::connection::ConnectionId;
 use mz_adapter_types::dyncfgs::ENABLE_MULTI_REPLICA_SOURCES;
 use mz_catalog::memory::objects::{
-    CatalogItem, Cluster, Connection, DataSourceDesc, Sink, Source, Table, TableDataSource, Type,
+    CatalogItem, Cluster, Connection, DataSourceDesc, Func, FuncDefinition, Sink, Source, Table,
+    TableDataSource, Type,
 };
 use mz_cloud_resources::VpcEndpointConfig;
 use mz_controller_types::ReplicaId;
@@ -48,7 +49,10 @@ use mz_repr::{
     RelationVersionSelector, Row, RowArena, RowIterator, Timestamp,
 };
 use mz_sql::ast::AlterSourceAddSubsourceOption;
-use mz_sql::ast::{CreateSubsourceStatement, MySqlConfigOptionName, UnresolvedItemName};
+use mz_sql::ast::{
+    CreateSubsourceStatement, MySqlConfigOptionName, SqlServerConfigOption,
+    SqlServerConfigOptionName, UnresolvedItemName,
+};
 use mz_sql::catalog::{
     CatalogCluster, CatalogClusterReplica, CatalogDatabase, CatalogError,
     CatalogItem as SqlCatalogItem, CatalogItemType, CatalogRole, CatalogSchema, CatalogTypeDetails,
@@ -99,6 +103,8 @@ use tracing::{info, warn, Instrument, Span};
 use crate::catalog::{self, Catalog, ConnCatalog, DropObjectInfo, UpdatePrivilegeVariant};
 use crate::command::{ExecuteResponse, Response};
 use crate::coord::appends::{BuiltinTableAppendNotify, DeferredOp, DeferredPlan, PendingWriteTxn};
+use crate::coord::sequencer::inner::changes::ChangeDecoder;
+use crate::coord::sequencer::inner::unique_keys::UniqueKeyCheck;
 use crate::coord::{
     validate_ip_with_policy_rules, AlterConnectionValidationReady, AlterSinkReadyContext,
     Coordinator, CreateConnectionValidationReady, DeferredPlanStatement, ExecuteContext,
@@ -117,6 +123,7 @@ use crate::session::{
 use crate::util::{viewable_variables, ClientTransmitter, ResultExt};
 use crate::{PeekResponseUnary, ReadHolds};
 
+mod changes;
 mod cluster;
 mod copy_from;
 mod create_continual_task;
@@ -127,6 +134,7 @@ mod explain_timestamp;
 mod peek;
 mod secret;
 mod subscribe;
+mod unique_keys;
 
 /// Attempts to evaluate an expression. If an error is returned then the error is sent
 /// to the client and the function is exited.
@@ -329,6 +337,15 @@ impl Coordinator {
                                 }
                             }
                         }
+                        GenericSourceConnection::SqlServer(_) => {
+                            if let Some(cluster) = self.catalog().try_get_cluster(cluster_id) {
+                                if cluster.replica_ids().len() > 1 {
+                                    return Err(AdapterError::Unsupported(
+                                        "SQL Server sources in clusters with >1 replicas",
+                                    ));
+                                }
+                            }
+                        }
                         GenericSourceConnection::Kafka(_)
                         | GenericSourceConnection::LoadGenerator(_) => {
                             if let Some(cluster) = self.catalog().try_get_cluster(cluster_id) {
@@ -1461,6 +1478,47 @@ impl Coordinator {
         }
     }
 
+    #[instrument]
+    pub(super) async fn sequence_create_function(
+        &mut self,
+        session: &Session,
+        plan: plan::CreateFunctionPlan,
+        resolved_ids: ResolvedIds,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let plan::CreateFunctionPlan {
+            name,
+            function,
+            replace: _,
+            drop_ids,
+        } = plan;
+        let id_ts = self.get_catalog_write_ts().await;
+        let (item_id, global_id) = self.catalog_mut().allocate_user_id(id_ts).await?;
+        let func = Func {
+            create_sql: Some(function.create_sql),
+            inner: FuncDefinition::User(Arc::new(function.inner)),
+            global_id,
+            resolved_ids,
+        };
+        let ops = vec![
+            catalog::Op::DropObjects(
+                drop_ids
+                    .into_iter()
+                    .map(catalog::DropObjectInfo::Item)
+                    .collect(),
+            ),
+            catalog::Op::CreateItem {
+                id: item_id,
+                name,
+                item: CatalogItem::Func(func),
+                owner_id: *session.current_role_id(),
+            },
+        ];
+        match self.catalog_transact(Some(session), ops).await {
+            Ok(()) => Ok(ExecuteResponse::CreatedFunction),
+            Err(err) => Err(err),
+        }
+    }
+
     #[instrument]
     pub(super) async fn sequence_comment_on(
         &mut self,
@@ -2795,6 +2853,8 @@ impl Coordinator {
                     assignments: BTreeMap::new(),
                     kind: MutationKind::Insert,
                     returning: plan.returning,
+                    changes: None,
+                    enforced_keys: Vec::new(),
                 };
 
                 self.sequence_read_then_write(ctx, read_then_write_plan)
@@ -2868,6 +2928,8 @@ impl Coordinator {
             mut assignments,
             finishing,
             returning,
+            changes,
+            enforced_keys,
         } = plan;
 
         // Read then writes can be queued, so re-verify the id exists.
@@ -3052,52 +3114,80 @@ impl Coordinator {
                 return_if_err!(prep_scalar_expr(expr, style.clone()), ctx);
             }
 
-            let make_diffs =
-                move |mut rows: Box<dyn RowIterator>| -> Result<Vec<(Row, Diff)>, AdapterError> {
-                    let arena = RowArena::new();
-                    let mut diffs = Vec::new();
-                    let mut datum_vec = mz_repr::DatumVec::new();
-
-                    while let Some(row) = rows.next() {
-                        if !assignments.is_empty() {
-                            assert!(
-                                matches!(kind, MutationKind::Update),
-                                "only updates support assignments"
-                            );
-                            let mut datums = datum_vec.borrow_with(row);
-                            let mut updates = vec![];
-                            for (idx, expr) in &assignments {
-                                let updated = match expr.eval(&datums, &arena) {
-                                    Ok(updated) => updated,
-                                    Err(e) => return Err(AdapterError::Unstructured(anyhow!(e))),
-                                };
-                                updates.push((*idx, updated));
-                            }
-                            for (idx, new_value) in updates {
-                                datums[idx] = new_value;
+            let make_diffs = move |mut rows: Box<dyn RowIterator>| -> Result<
+                (Vec<(Row, Diff)>, Option<usize>),
+                AdapterError,
+            > {
+                let arena = RowArena::new();
+                let mut diffs = Vec::new();
+                let mut datum_vec = mz_repr::DatumVec::new();
+                let mut change_decoder = changes
+                    .map(|changes| ChangeDecoder::new(changes, kind, desc.arity()));
+                let mut unique_key_check = (!enforced_keys.is_empty())
+                    .then(|| UniqueKeyCheck::new(enforced_keys, desc.arity()));
+
+                while let Some(row) = rows.next() {
+                    let selected;
+                    let row = match &mut unique_key_check {
+                        Some(unique_key_check) => match unique_key_check.read(row) {
+                            Some(row) => {
+                                selected = row;
+                                &*selected
                             }
-                            let updated = Row::pack_slice(&datums);
-                            diffs.push((updated, 1));
+                            None => continue,
+                        },
+                        None => row,
+                    };
+                    if let Some(change_decoder) = &mut change_decoder {
+                        change_decoder.decode(row, &mut diffs)?;
+                        continue;
+                    }
+                    if !assignments.is_empty() {
+                        assert!(
+                            matches!(kind, MutationKind::Update),
+                            "only updates support assignments"
+                        );
+                        let mut datums = datum_vec.borrow_with(row);
+                        let mut updates = vec![];
+                        for (idx, expr) in &assignments {
+                            let updated = match expr.eval(&datums, &arena) {
+                                Ok(updated) => updated,
+                                Err(e) => return Err(AdapterError::Unstructured(anyhow!(e))),
+                            };
+                            updates.push((*idx, updated));
                         }
-                        match kind {
-                            // Updates and deletes always remove the
-                            // current row. Updates will also add an
-                            // updated value.
-                            MutationKind::Update | MutationKind::Delete => {
-                                diffs.push((row.to_owned(), -1))
-                            }
-                            MutationKind::Insert => diffs.push((row.to_owned(), 1)),
+                        for (idx, new_value) in updates {
+                            datums[idx] = new_value;
                         }
+                        let updated = Row::pack_slice(&datums);
+                        diffs.push((updated, 1));
                     }
-                    for (row, diff) in &diffs {
-                        if *diff > 0 {
-                            for (idx, datum) in row.iter().enumerate() {
-                                desc.constraints_met(idx, &datum)?;
-                            }
+                    match kind {
+                        // Updates and deletes always remove the
+                        // current row. Updates will also add an
+                        // updated value.
+                        MutationKind::Update | MutationKind::Delete => {
+                            diffs.push((row.to_owned(), -1))
+                        }
+                        MutationKind::Insert => diffs.push((row.to_owned(), 1)),
+                        MutationKind::Upsert | MutationKind::Merge => {
+                            unreachable!("{kind:?} requires changes")
                         }
                     }
-                    Ok(diffs)
-                };
+                }
+                for (row, diff) in &diffs {
+                    if *diff > 0 {
+                        for (idx, datum) in row.iter().enumerate() {
+                            desc.constraints_met(idx, &datum)?;
+                        }
+                    }
+                }
+                if let Some(unique_key_check) = unique_key_check {
+                    unique_key_check.validate(&diffs, &desc)?;
+                }
+                let affected_rows = change_decoder.map(|decoder| decoder.affected_rows());
+                Ok((diffs, affected_rows))
+            };
             let diffs = match peek_response {
                 ExecuteResponse::SendingRows { future: batch, .. } => {
                     // TODO(jkosh44): This timeout should be removed;
@@ -3132,6 +3222,10 @@ impl Coordinator {
                     "unexpected peek response: {resp:?}"
                 ))),
             };
+            let (diffs, affected_rows) = match diffs {
+                Ok((diffs, affected_rows)) => (Ok(diffs), affected_rows),
+                Err(e) => (Err(e), None),
+            };
             let mut returning_rows = Vec::new();
             let mut diff_err: Option<AdapterError> = None;
             if !returning.is_empty() && diffs.is_ok() {
@@ -3236,6 +3330,7 @@ impl Coordinator {
                             kind,
                             returning: returning_rows,
                             max_result_size,
+                            affected_rows,
                         },
                     );
                     ctx.retire(result);
@@ -3397,6 +3492,12 @@ impl Coordinator {
                 if let Some(inherit) = attrs.inherit {
                     attributes.inherit = inherit;
                 }
+                if let Some(login) = attrs.login {
+                    attributes.login = Some(login);
+                }
+                if let Some(password_hash) = attrs.password_hash {
+                    attributes.password_hash = password_hash;
+                }
 
                 if let Some(notice) = self.should_emit_rbac_notice(session) {
                     notices.push(notice);
@@ -4121,6 +4222,75 @@ impl Coordinator {
                             });
                         }
                     }
+                    CreateSourceConnection::SqlServer {
+                        options: curr_options,
+                        ..
+                    } => {
+                        let mz_sql::plan::SqlServerConfigOptionExtracted {
+                            mut text_columns,
+                            mut exclude_columns,
+                            ..
+                        } = curr_options.clone().try_into()?;
+
+                        // Drop both exclude and text columns; we will add them back in
+                        // as appropriate below.
+                        curr_options.retain(|o| {
+                            !matches!(
+                                o.name,
+                                SqlServerConfigOptionName::TextColumns
+                                    | SqlServerConfigOptionName::ExcludeColumns
+                            )
+                        });
+
+                        // Drop all text / exclude columns that are not currently referred to.
+                        // Column references are `schema.table.column`, while the references
+                        // of the subsources are `database.schema.table`.
+                        let column_referenced =
+                            |column_qualified_reference: &UnresolvedItemName| {
+                                mz_ore::soft_assert_eq_or_log!(
+                                column_qualified_reference.0.len(),
+                                3,
+                                "all TEXT COLUMNS & EXCLUDE COLUMNS values must be column-qualified references"
+                            );
+                                let table = &column_qualified_reference.0[..2];
+                                curr_references
+                                    .iter()
+                                    .any(|reference| reference.0.ends_with(table))
+                            };
+                        text_columns.retain(column_referenced);
+                        exclude_columns.retain(column_referenced);
+
+                        // Merge the current text / exclude columns into the new text / exclude columns.
+                        new_text_columns.extend(text_columns);
+                        new_exclude_columns.extend(exclude_columns);
+
+                        // If we have text columns, add them to the options.
+                        if !new_text_columns.is_empty() {
+                            new_text_columns.sort();
+                            let new_text_columns = new_text_columns
+                                .into_iter()
+                                .map(WithOptionValue::UnresolvedItemName)
+                                .collect();
+
+                            curr_options.push(SqlServerConfigOption {
+                                name: SqlServerConfigOptionName::TextColumns,
+                                value: Some(WithOptionValue::Sequence(new_text_columns)),
+                            });
+                        }
+                        // If we have exclude columns, add them to the options.
+                        if !new_exclude_columns.is_empty() {
+                            new_exclude_columns.sort();
+                            let new_exclude_columns = new_exclude_columns
+                                .into_iter()
+                                .map(WithOptionValue::UnresolvedItemName)
+                                .collect();
+
+                            curr_options.push(SqlServerConfigOption {
+                                name: SqlServerConfigOptionName::ExcludeColumns,
+                                value: Some(WithOptionValue::Sequence(new_exclude_columns)),
+                            });
+                        }
+                    }
                     _ => return Err(purification_err()),
                 };
 
@@ -0,0 +1,113 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of the changes selected by `INSERT ... ON CONFLICT` and `MERGE`
+//! statements.
+
+use std::collections::BTreeSet;
+
+use mz_repr::{Datum, Diff, Row, RowRef};
+use mz_sql::plan::{ChangesPlan, MutationKind};
+
+use crate::AdapterError;
+
+/// Translates the rows selected for a [`ChangesPlan`] into updates to the
+/// target table.
+pub(super) struct ChangeDecoder {
+    plan: ChangesPlan,
+    kind: MutationKind,
+    arity: usize,
+    /// The old rows that have been retracted so far.
+    retracted: BTreeSet<Row>,
+    /// For each of `plan.unique_keys`, the key values of the rows inserted so
+    /// far.
+    inserted_keys: Vec<BTreeSet<Row>>,
+    /// The number of rows affected so far.
+    affected_rows: usize,
+}
+
+impl ChangeDecoder {
+    /// Creates a decoder for changes to a table of the given arity.
+    pub(super) fn new(plan: ChangesPlan, kind: MutationKind, arity: usize) -> Self {
+        let inserted_keys = plan.unique_keys.iter().map(|_| BTreeSet::new()).collect();
+        ChangeDecoder {
+            plan,
+            kind,
+            arity,
+            retracted: BTreeSet::new(),
+            inserted_keys,
+            affected_rows: 0,
+        }
+    }
+
+    /// Decodes `row`, pushing the resulting updates to the table onto `diffs`.
+    pub(super) fn decode(
+        &mut self,
+        row: &RowRef,
+        diffs: &mut Vec<(Row, Diff)>,
+    ) -> Result<(), AdapterError> {
+        let datums: Vec<_> = row.iter().collect();
+        let copies = match datums[0] {
+            Datum::Null => None,
+            Datum::Int64(copies) => Some(copies),
+            datum => unreachable!("unexpected number of copies: {datum:?}"),
+        };
+        let old = &datums[1..self.arity + 1];
+        let insert = datums[self.arity + 1] == Datum::True;
+        let new = &datums[self.arity + 2..];
+
+        // New rows must not duplicate the unique keys of other new rows. Rows
+        // that replace an old row keep its key, so can't introduce duplicates.
+        if insert && copies.is_none() {
+            let mut keys = Vec::with_capacity(self.plan.unique_keys.len());
+            for (key, inserted) in self.plan.unique_keys.iter().zip(&self.inserted_keys) {
+                let key = Row::pack(key.iter().map(|i| new[*i]));
+                if inserted.contains(&key) {
+                    if self.plan.skip_duplicates {
+                        return Ok(());
+                    }
+                    return Err(self.row_affected_twice());
+                }
+                keys.push(key);
+            }
+            for (key, inserted) in keys.into_iter().zip(&mut self.inserted_keys) {
+                inserted.insert(key);
+            }
+        }
+
+        if let Some(copies) = copies {
+            let old = Row::pack_slice(old);
+            if !self.retracted.insert(old.clone()) {
+                return Err(self.row_affected_twice());
+            }
+            diffs.push((old, -copies));
+        }
+        if insert {
+            diffs.push((Row::pack_slice(new), copies.unwrap_or(1)));
+        }
+        if copies.is_some() || insert {
+            let copies = usize::try_from(copies.unwrap_or(1)).expect("copies must be positive");
+            self.affected_rows += copies;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of rows affected by the decoded changes.
+    pub(super) fn affected_rows(&self) -> usize {
+        self.affected_rows
+    }
+
+    fn row_affected_twice(&self) -> AdapterError {
+        let command = match self.kind {
+            MutationKind::Merge => "MERGE",
+            _ => "ON CONFLICT DO UPDATE",
+        };
+        AdapterError::RowAffectedTwice(command)
+    }
+}
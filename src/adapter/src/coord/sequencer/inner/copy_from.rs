@@ -81,6 +81,11 @@ impl Coordinator {
                 mz_storage_types::oneshot_sources::ContentFormat::Csv(csv.to_owned())
             }
             CopyFormatParams::Parquet => mz_storage_types::oneshot_sources::ContentFormat::Parquet,
+            CopyFormatParams::Avro => mz_storage_types::oneshot_sources::ContentFormat::Avro,
+            CopyFormatParams::Orc => mz_storage_types::oneshot_sources::ContentFormat::Orc,
+            CopyFormatParams::Json(json) => {
+                mz_storage_types::oneshot_sources::ContentFormat::Json(json)
+            }
             CopyFormatParams::Text(_) | CopyFormatParams::Binary => {
                 mz_ore::soft_panic_or_log!("unsupported formats should be rejected in planning");
                 ctx.retire(Err(AdapterError::Unsupported("COPY FROM URL format")));
@@ -21,11 +21,11 @@ use mz_repr::explain::{ExprHumanizerExt, TransientItem};
 use mz_repr::optimize::OptimizerFeatures;
 use mz_repr::optimize::OverrideFrom;
 use mz_repr::refresh_schedule::RefreshSchedule;
-use mz_repr::{CatalogItemId, Datum, Row};
+use mz_repr::{CatalogItemId, Datum, RelationVersion, Row};
 use mz_sql::ast::ExplainStage;
-use mz_sql::catalog::CatalogError;
+use mz_sql::catalog::{CatalogError, ObjectType};
 use mz_sql::names::ResolvedIds;
-use mz_sql::plan;
+use mz_sql::plan::{self, InPlaceReplacement};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql_parser::ast;
 use mz_sql_parser::ast::display::AstDisplay;
@@ -566,7 +566,9 @@ impl Coordinator {
                             refresh_schedule,
                             ..
                         },
+                    replace,
                     drop_ids,
+                    replace_in_place,
                     if_not_exists,
                     ..
                 },
@@ -603,13 +605,61 @@ impl Coordinator {
             "materialized view timestamp selection",
         );
 
-        let initial_as_of = storage_as_of.clone();
+        // A materialized view that is replaced in place keeps its storage collection, so the new
+        // definition must produce exactly the same output schema, and it keeps the `initial_as_of`
+        // the collection was created with.
+        let replaced = match (replace, replace_in_place) {
+            (Some(replace_id), Some(in_place)) => {
+                let CatalogItem::MaterializedView(mv) =
+                    self.catalog().get_entry(&replace_id).item()
+                else {
+                    return Err(AdapterError::internal(
+                        "create materialized view",
+                        format!("{replace_id} is not a materialized view"),
+                    ));
+                };
+                let reason = if self.materialized_view_output_complete(mv)? {
+                    Some("its output is complete and can no longer change")
+                } else if global_lir_plan.desc() != &mv.desc {
+                    Some("the new definition has a different output schema")
+                } else {
+                    None
+                };
+                match reason {
+                    None => Some((replace_id, in_place, mv.clone())),
+                    // Fall back to dropping and recreating the materialized view, if the plan
+                    // allows for it.
+                    Some(_)
+                        if in_place == InPlaceReplacement::CreateOrReplace
+                            && !drop_ids.is_empty() =>
+                    {
+                        None
+                    }
+                    Some(reason) => {
+                        let full_name = self.catalog().resolve_full_name(&name, None);
+                        return Err(AdapterError::MaterializedViewReplacement {
+                            name: full_name.to_string(),
+                            reason,
+                        });
+                    }
+                }
+            }
+            _ => None,
+        };
 
-        // Update the `create_sql` with the selected `as_of`. This is how we make sure the `as_of`
+        let initial_as_of = match &replaced {
+            Some((_, _, mv)) => mv
+                .initial_as_of
+                .clone()
+                .unwrap_or_else(|| storage_as_of.clone()),
+            None => storage_as_of.clone(),
+        };
+
+        // Update the `create_sql` with the initial `as_of`. This is how we make sure the `as_of`
         // is persisted to the catalog and can be relied on during bootstrapping.
-        // This has to be the `storage_as_of`, because bootstrapping uses this in
+        // This has to be the `storage_as_of` of the collection, because bootstrapping uses this in
         // `bootstrap_storage_collections`.
-        if let Some(storage_as_of_ts) = storage_as_of.as_option() {
+        if let Some(storage_as_of_ts) = initial_as_of.as_option() {
             let stmt = mz_sql::parse::parse(&create_sql)
                 .map_err(|_| {
                     AdapterError::internal(
@@ -626,33 +676,44 @@ impl Coordinator {
             create_sql = stmt.to_ast_string_stable();
         }
 
-        let ops = vec![
-            catalog::Op::DropObjects(
-                drop_ids
-                    .into_iter()
-                    .map(catalog::DropObjectInfo::Item)
-                    .collect(),
-            ),
-            catalog::Op::CreateItem {
-                id: item_id,
+        let materialized_view = MaterializedView {
+            create_sql,
+            raw_expr: raw_expr.into(),
+            optimized_expr: local_mir_plan.expr().into(),
+            desc: global_lir_plan.desc().clone(),
+            collections: btreemap! { RelationVersion::root() => global_id },
+            resolved_ids,
+            dependencies,
+            cluster_id,
+            non_null_assertions,
+            custom_logical_compaction_window: compaction_window,
+            refresh_schedule: refresh_schedule.clone(),
+            initial_as_of: Some(initial_as_of.clone()),
+        };
+        let ops = match &replaced {
+            // The newly allocated `item_id` goes unused, the new definition is added as a new
+            // version of the existing item.
+            Some((replace_id, _, _)) => vec![catalog::Op::ReplaceMaterializedView {
+                id: *replace_id,
+                new_global_id: global_id,
                 name: name.clone(),
-                item: CatalogItem::MaterializedView(MaterializedView {
-                    create_sql,
-                    raw_expr: raw_expr.into(),
-                    optimized_expr: local_mir_plan.expr().into(),
-                    desc: global_lir_plan.desc().clone(),
-                    global_id,
-                    resolved_ids,
-                    dependencies,
-                    cluster_id,
-                    non_null_assertions,
-                    custom_logical_compaction_window: compaction_window,
-                    refresh_schedule: refresh_schedule.clone(),
-                    initial_as_of: Some(initial_as_of.clone()),
-                }),
-                owner_id: *session.current_role_id(),
-            },
-        ];
+                to_item: materialized_view,
+            }],
+            None => vec![
+                catalog::Op::DropObjects(
+                    drop_ids
+                        .into_iter()
+                        .map(catalog::DropObjectInfo::Item)
+                        .collect(),
+                ),
+                catalog::Op::CreateItem {
+                    id: item_id,
+                    name: name.clone(),
+                    item: CatalogItem::MaterializedView(materialized_view),
+                    owner_id: *session.current_role_id(),
+                },
+            ],
+        };
 
         // Pre-allocate a vector of transient GlobalIds for each notice.
         let notice_ids = std::iter::repeat_with(|| self.allocate_transient_id())
@@ -683,7 +744,18 @@ impl Coordinator {
 
                 let storage_metadata = coord.catalog.state().storage_metadata();
 
-                // Announce the creation of the materialized view source.
+                // Announce the creation of the materialized view source. A replacement
+                // definition writes into the shard of the existing collection, whose first
+                // version drives compaction.
+                let (data_source, since) = match &replaced {
+                    Some((_, _, mv)) => (
+                        DataSource::Other {
+                            primary: Some(mv.primary_global_id()),
+                        },
+                        None,
+                    ),
+                    None => (DataSource::Other { primary: None }, Some(storage_as_of)),
+                };
                 coord
                     .controller
                     .storage
@@ -694,8 +766,8 @@ impl Coordinator {
                             global_id,
                             CollectionDescription {
                                 desc: output_desc,
-                                data_source: DataSource::Other,
-                                since: Some(storage_as_of),
+                                data_source,
+                                since,
                                 status_collection_id: None,
                                 timeline: None,
                             },
@@ -705,12 +777,27 @@ impl Coordinator {
                     .unwrap_or_terminate("cannot fail to append");
 
                 coord
-                    .initialize_storage_read_policies(
-                        btreeset![item_id],
+                    .initialize_read_policies(
+                        &CollectionIdBundle {
+                            storage_ids: btreeset![global_id],
+                            compute_ids: BTreeMap::new(),
+                        },
                         compaction_window.unwrap_or(CompactionWindow::Default),
                     )
                     .await;
 
+                // Stop the dataflow of the previous definition before the new one starts
+                // writing into the shared shard.
+                if let Some((_, _, mv)) = &replaced {
+                    let compute = &mut coord.controller.compute;
+                    // A cluster could have been dropped, so verify it exists.
+                    if compute.instance_exists(mv.cluster_id) {
+                        compute
+                            .drop_collections(mv.cluster_id, vec![mv.global_id()])
+                            .unwrap_or_terminate("cannot fail to drop collections");
+                    }
+                }
+
                 coord
                     .ship_dataflow_and_notice_builtin_table_updates(
                         df_desc,
@@ -722,7 +809,12 @@ impl Coordinator {
             .await;
 
         match transact_result {
-            Ok(_) => Ok(ExecuteResponse::CreatedMaterializedView),
+            Ok(_) => match replaced {
+                Some((_, InPlaceReplacement::Alter, _)) => {
+                    Ok(ExecuteResponse::AlteredObject(ObjectType::MaterializedView))
+                }
+                _ => Ok(ExecuteResponse::CreatedMaterializedView),
+            },
             Err(AdapterError::Catalog(mz_catalog::memory::error::Error {
                 kind:
                     mz_catalog::memory::error::ErrorKind::Sql(CatalogError::ItemAlreadyExists(_, _)),
@@ -877,6 +969,49 @@ impl Coordinator {
         Ok(StageResult::Response(Self::send_immediate_rows(rows)))
     }
 
+    /// Reports whether the output of the given materialized view is complete, or will become
+    /// complete because none of the inputs of its dataflow can advance anymore.
+    ///
+    /// The storage collection of such a materialized view gets sealed, after which it can no
+    /// longer accept the output of a new definition.
+    fn materialized_view_output_complete(
+        &self,
+        mview: &MaterializedView,
+    ) -> Result<bool, AdapterError> {
+        let (_since, write_frontier) = self
+            .controller
+            .storage
+            .collection_frontiers(mview.global_id())
+            .map_err(AdapterError::Storage)?;
+        if write_frontier.is_empty() {
+            return Ok(true);
+        }
+        let Some(plan) = self.catalog().try_get_physical_plan(&mview.global_id()) else {
+            return Ok(false);
+        };
+        for id in plan.source_imports.keys() {
+            let (_since, upper) = self
+                .controller
+                .storage
+                .collection_frontiers(*id)
+                .map_err(AdapterError::Storage)?;
+            if !upper.is_empty() {
+                return Ok(false);
+            }
+        }
+        for id in plan.index_imports.keys() {
+            let frontiers = self
+                .controller
+                .compute
+                .collection_frontiers(*id, Some(mview.cluster_id))
+                .map_err(|e| AdapterError::internal("create materialized view", e))?;
+            if !frontiers.write_frontier.is_empty() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     pub(crate) async fn explain_pushdown_materialized_view(
         &self,
         ctx: ExecuteContext,
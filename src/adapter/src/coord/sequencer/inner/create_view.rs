@@ -17,9 +17,9 @@ use mz_repr::explain::{ExprHumanizerExt, TransientItem};
 use mz_repr::optimize::{OptimizerFeatures, OverrideFrom};
 use mz_repr::{Datum, RelationDesc, Row};
 use mz_sql::ast::ExplainStage;
-use mz_sql::catalog::CatalogError;
+use mz_sql::catalog::{CatalogError, ObjectType};
 use mz_sql::names::ResolvedIds;
-use mz_sql::plan::{self};
+use mz_sql::plan::{self, InPlaceReplacement};
 use mz_sql::session::metadata::SessionMetadata;
 use tracing::Span;
 
@@ -381,7 +381,9 @@ impl Coordinator {
                             column_names,
                             temporary,
                         },
+                    replace,
                     drop_ids,
+                    replace_in_place,
                     if_not_exists,
                     ..
                 },
@@ -390,36 +392,56 @@ impl Coordinator {
             ..
         }: CreateViewFinish,
     ) -> Result<StageResult<Box<CreateViewStage>>, AdapterError> {
-        let ops = vec![
-            catalog::Op::DropObjects(
-                drop_ids
-                    .iter()
-                    .map(|id| catalog::DropObjectInfo::Item(*id))
-                    .collect(),
-            ),
-            catalog::Op::CreateItem {
-                id: item_id,
-                name: name.clone(),
-                item: CatalogItem::View(View {
-                    create_sql: create_sql.clone(),
-                    global_id,
-                    raw_expr: raw_expr.into(),
-                    desc: RelationDesc::new(optimized_expr.typ(), column_names.clone()),
-                    optimized_expr: optimized_expr.into(),
-                    conn_id: if temporary {
-                        Some(session.conn_id().clone())
-                    } else {
-                        None
-                    },
-                    resolved_ids: resolved_ids.clone(),
-                    dependencies: dependencies.clone(),
-                }),
-                owner_id: *session.current_role_id(),
+        let view = CatalogItem::View(View {
+            create_sql: create_sql.clone(),
+            global_id,
+            raw_expr: raw_expr.into(),
+            desc: RelationDesc::new(optimized_expr.typ(), column_names.clone()),
+            optimized_expr: optimized_expr.into(),
+            conn_id: if temporary {
+                Some(session.conn_id().clone())
+            } else {
+                None
             },
-        ];
+            resolved_ids: resolved_ids.clone(),
+            dependencies: dependencies.clone(),
+        });
+        let (ops, response) = match (replace, replace_in_place) {
+            // Keep the existing item, and only swap in the new definition. The
+            // newly allocated `item_id` goes unused.
+            (Some(replace_id), Some(in_place)) => {
+                let ops = vec![catalog::Op::UpdateItem {
+                    id: replace_id,
+                    name: name.clone(),
+                    to_item: view,
+                }];
+                let response = match in_place {
+                    InPlaceReplacement::CreateOrReplace => ExecuteResponse::CreatedView,
+                    InPlaceReplacement::Alter => ExecuteResponse::AlteredObject(ObjectType::View),
+                };
+                (ops, response)
+            }
+            _ => {
+                let ops = vec![
+                    catalog::Op::DropObjects(
+                        drop_ids
+                            .iter()
+                            .map(|id| catalog::DropObjectInfo::Item(*id))
+                            .collect(),
+                    ),
+                    catalog::Op::CreateItem {
+                        id: item_id,
+                        name: name.clone(),
+                        item: view,
+                        owner_id: *session.current_role_id(),
+                    },
+                ];
+                (ops, ExecuteResponse::CreatedView)
+            }
+        };
 
         match self.catalog_transact(Some(session), ops).await {
-            Ok(()) => Ok(StageResult::Response(ExecuteResponse::CreatedView)),
+            Ok(()) => Ok(StageResult::Response(response)),
             Err(AdapterError::Catalog(mz_catalog::memory::error::Error {
                 kind:
                     mz_catalog::memory::error::ErrorKind::Sql(CatalogError::ItemAlreadyExists(_, _)),
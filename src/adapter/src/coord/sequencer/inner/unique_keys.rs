@@ -0,0 +1,88 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Enforcement of the unique keys of tables by read-then-write statements.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::Itertools;
+use mz_repr::{Datum, Diff, RelationDesc, Row, RowRef};
+
+use crate::AdapterError;
+
+/// Checks that the updates made by a read-then-write statement do not violate
+/// the unique keys of the target table.
+///
+/// The rows read by the statement have the form documented on
+/// [`mz_sql::plan::ReadThenWritePlan::enforced_keys`].
+pub(super) struct UniqueKeyCheck {
+    keys: Vec<Vec<usize>>,
+    arity: usize,
+    /// For each of `keys`, the number of rows of the table with each value of
+    /// the key.
+    counts: Vec<BTreeMap<Row, Diff>>,
+}
+
+impl UniqueKeyCheck {
+    /// Creates a check of the given keys of a table of the given arity.
+    pub(super) fn new(keys: Vec<Vec<usize>>, arity: usize) -> Self {
+        let counts = keys.iter().map(|_| BTreeMap::new()).collect();
+        UniqueKeyCheck {
+            keys,
+            arity,
+            counts,
+        }
+    }
+
+    /// Processes a row read by the statement.
+    ///
+    /// Returns the row selected for the write, or `None` if `row` is one of
+    /// the current rows of the table.
+    pub(super) fn read(&mut self, row: &RowRef) -> Option<Row> {
+        let datums: Vec<_> = row.iter().collect();
+        let (selected, current) = datums[1..].split_at(datums.len() - 1 - self.arity);
+        if datums[0] != Datum::True {
+            return Some(Row::pack_slice(selected));
+        }
+        for (key, counts) in self.keys.iter().zip_eq(&mut self.counts) {
+            let key = Row::pack(key.iter().map(|i| current[*i]));
+            *counts.entry(key).or_default() += 1;
+        }
+        None
+    }
+
+    /// Verifies that applying `diffs` to the current rows of the table leaves
+    /// at most one row with each value of each key.
+    pub(super) fn validate(
+        mut self,
+        diffs: &[(Row, Diff)],
+        desc: &RelationDesc,
+    ) -> Result<(), AdapterError> {
+        for (key, counts) in self.keys.iter().zip_eq(&mut self.counts) {
+            let mut inserted = BTreeSet::new();
+            for (row, diff) in diffs {
+                let datums: Vec<_> = row.iter().collect();
+                let value = Row::pack(key.iter().map(|i| datums[*i]));
+                *counts.entry(value.clone()).or_default() += diff;
+                if *diff > 0 {
+                    inserted.insert(value);
+                }
+            }
+            for value in inserted {
+                if counts[&value] > 1 {
+                    return Err(AdapterError::UniqueViolation {
+                        columns: key.iter().map(|i| desc.get_name(*i).to_string()).collect(),
+                        values: value.iter().map(|datum| datum.to_string()).collect(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -86,10 +86,14 @@ impl PreparedStatementLoggingInfo {
     ) -> Self {
         let kind = stmt.map(StatementKind::from);
         let sql = match kind {
-            // We __always__ want to redact SQL statements that might contain secret values.
-            Some(StatementKind::CreateSecret | StatementKind::AlterSecret) => {
-                stmt.map(|s| s.to_ast_string_redacted()).unwrap_or_default()
-            }
+            // We __always__ want to redact SQL statements that might contain secret values, like
+            // secrets and role passwords.
+            Some(
+                StatementKind::CreateSecret
+                | StatementKind::AlterSecret
+                | StatementKind::CreateRole
+                | StatementKind::AlterRole,
+            ) => stmt.map(|s| s.to_ast_string_redacted()).unwrap_or_default(),
             _ => raw_sql,
         };
 
@@ -95,6 +95,15 @@ pub enum AdapterError {
     InvalidTableMutationSelection,
     /// Expression violated a column's constraint
     ConstraintViolation(NotNullViolation),
+    /// The named command attempted to affect the same row of a table more
+    /// than once.
+    RowAffectedTwice(&'static str),
+    /// A write would leave more than one row of a table with the same value
+    /// of one of the table's unique keys.
+    UniqueViolation {
+        columns: Vec<String>,
+        values: Vec<String>,
+    },
     /// Transaction cluster was dropped in the middle of a transaction.
     ConcurrentClusterDrop,
     /// Target cluster has no replicas to service query.
@@ -217,6 +226,11 @@ pub enum AdapterError {
     /// A CREATE MATERIALIZED VIEW statement tried to acquire a read hold at a REFRESH AT time,
     /// but was unable to get a precise read hold.
     InputNotReadableAtRefreshAtTime(Timestamp, Antichain<Timestamp>),
+    /// The definition of a materialized view could not be replaced in place.
+    MaterializedViewReplacement {
+        name: String,
+        reason: &'static str,
+    },
     /// A humanized version of [`StorageError::RtrTimeout`].
     RtrTimeout(String),
     /// A humanized version of [`StorageError::RtrDropFailure`].
@@ -279,6 +293,11 @@ impl AdapterError {
                     ),
                 }
             )),
+            AdapterError::UniqueViolation { columns, values } => Some(format!(
+                "Key ({})=({}) already exists.",
+                columns.join(", "),
+                values.join(", ")
+            )),
             AdapterError::SourceOrSinkSizeRequired { .. } => Some(
                 "Either specify the cluster that will maintain this object via IN CLUSTER or \
                 specify size via SIZE option."
@@ -414,6 +433,12 @@ impl AdapterError {
             AdapterError::AlterClusterTimeout => Some(
                 "Consider increasing the timeout duration in the alter cluster statement.".into(),
             ),
+            AdapterError::MaterializedViewReplacement { .. } => Some(
+                "Use DROP MATERIALIZED VIEW and CREATE MATERIALIZED VIEW instead.".into(),
+            ),
+            AdapterError::RowAffectedTwice(_) => Some(
+                "Ensure that no more than one source row matches any one target row.".into(),
+            ),
             _ => None,
         }
     }
@@ -466,6 +491,8 @@ impl AdapterError {
             AdapterError::SourceOrSinkSizeRequired { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::InvalidTableMutationSelection => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::ConstraintViolation(NotNullViolation(_)) => SqlState::NOT_NULL_VIOLATION,
+            AdapterError::RowAffectedTwice(_) => SqlState::CARDINALITY_VIOLATION,
+            AdapterError::UniqueViolation { .. } => SqlState::UNIQUE_VIOLATION,
             AdapterError::ConcurrentClusterDrop => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::NoClusterReplicasAvailable { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
@@ -541,6 +568,7 @@ impl AdapterError {
             // `DATA_EXCEPTION`, similarly to `AbsurdSubscribeBounds`.
             AdapterError::MaterializedViewWouldNeverRefresh(_, _) => SqlState::DATA_EXCEPTION,
             AdapterError::InputNotReadableAtRefreshAtTime(_, _) => SqlState::DATA_EXCEPTION,
+            AdapterError::MaterializedViewReplacement { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::RtrTimeout(_) => SqlState::QUERY_CANCELED,
             AdapterError::RtrDropFailure(_) => SqlState::UNDEFINED_OBJECT,
             AdapterError::UnreadableSinkCollection => SqlState::from_code("MZ009"),
@@ -614,6 +642,12 @@ impl fmt::Display for AdapterError {
             AdapterError::ConstraintViolation(not_null_violation) => {
                 write!(f, "{}", not_null_violation)
             }
+            AdapterError::RowAffectedTwice(command) => {
+                write!(f, "{command} command cannot affect row a second time")
+            }
+            AdapterError::UniqueViolation { .. } => {
+                write!(f, "duplicate key value violates unique constraint")
+            }
             AdapterError::ConcurrentClusterDrop => {
                 write!(f, "the transaction's active cluster has been dropped")
             }
@@ -765,6 +799,13 @@ impl fmt::Display for AdapterError {
                     "REFRESH AT requested for a time where not all the inputs are readable"
                 )
             }
+            AdapterError::MaterializedViewReplacement { name, reason } => {
+                write!(
+                    f,
+                    "cannot replace materialized view {} in place: {reason}",
+                    name.quoted()
+                )
+            }
             AdapterError::RtrTimeout(_) => {
                 write!(f, "timed out before ingesting the source's visible frontier when real-time-recency query issued")
             }
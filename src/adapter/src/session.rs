@@ -813,21 +813,24 @@ impl<T: TimestampManipulation> Session<T> {
     }
 
     /// Drains any external metadata updates and applies the changes from the latest update.
-    pub fn apply_external_metadata_updates(&mut self) {
+    ///
+    /// Returns whether the metadata was updated.
+    pub fn apply_external_metadata_updates(&mut self) -> bool {
         // If no sender is registered then there isn't anything to do.
         let Some(rx) = &mut self.external_metadata_rx else {
-            return;
+            return false;
         };
 
         // If the value hasn't changed then return.
         if !rx.has_changed().unwrap_or(false) {
-            return;
+            return false;
         }
 
         // Update our metadata! Note the short critical section (just a clone) to avoid blocking
         // the sending side of this watch channel.
         let metadata = rx.borrow_and_update().clone();
         self.vars.set_external_user_metadata(metadata);
+        true
     }
 
     /// Initializes the session's role metadata.
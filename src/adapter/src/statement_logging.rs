@@ -221,6 +221,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::CreatedViews
             | ExecuteResponse::CreatedMaterializedView
             | ExecuteResponse::CreatedContinualTask
+            | ExecuteResponse::CreatedFunction
             | ExecuteResponse::CreatedType
             | ExecuteResponse::CreatedNetworkPolicy
             | ExecuteResponse::Deallocate { .. }
@@ -234,6 +235,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::GrantedPrivilege
             | ExecuteResponse::GrantedRole
             | ExecuteResponse::Inserted(_)
+            | ExecuteResponse::Merged(_)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
             | ExecuteResponse::ReassignOwned
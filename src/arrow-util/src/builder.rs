@@ -283,6 +283,26 @@ fn scalar_to_arrow_datatype(scalar_type: &ScalarType) -> Result<(DataType, Strin
                 "map",
             )
         }
+        ScalarType::Record {
+            fields,
+            custom_id: _,
+            custom_oid: _,
+        } => {
+            // Records are represented as a struct with a field for each field of the record.
+            let fields = fields
+                .iter()
+                .map(|(name, typ)| {
+                    let (inner_type, inner_name) = scalar_to_arrow_datatype(&typ.scalar_type)?;
+                    Ok(field_with_typename(
+                        name.as_str(),
+                        inner_type,
+                        typ.nullable,
+                        &inner_name,
+                    ))
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+            (DataType::Struct(fields.into()), "record")
+        }
         _ => anyhow::bail!("{:?} unimplemented", scalar_type),
     };
     Ok((data_type, extension_name.to_lowercase()))
@@ -692,6 +712,18 @@ impl ArrowColumn {
                 }
                 struct_builder.append(true)
             }
+            (ColBuilder::StructBuilder(struct_builder), Datum::List(record)) => {
+                // We've received a record datum, which is represented as an Arrow struct with a
+                // field for each field of the record.
+                for (i, datum) in record.iter().enumerate() {
+                    let field_builder: &mut ArrowColumn =
+                        struct_builder.field_builder(i).ok_or_else(|| {
+                            anyhow::anyhow!("Record datum has more fields than its struct")
+                        })?;
+                    field_builder.append_datum(datum)?;
+                }
+                struct_builder.append(true)
+            }
             (ColBuilder::ListBuilder(list_builder), Datum::List(list)) => {
                 let inner_builder = list_builder.values();
                 for datum in list.into_iter() {
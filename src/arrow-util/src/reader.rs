@@ -340,6 +340,7 @@ fn scalar_type_and_array_to_reader(
             ScalarType::Record {
                 fields,
                 custom_id: _,
+                custom_oid: _,
             },
             DataType::Struct(_),
         ) => {
@@ -786,6 +787,7 @@ impl ColReader {
 mod tests {
     use arrow::datatypes::Field;
     use mz_ore::collections::CollectionExt;
+    use mz_repr::ColumnName;
 
     use super::*;
 
@@ -809,6 +811,19 @@ mod tests {
                 }
                 .nullable(true),
             )
+            .with_column(
+                "record",
+                ScalarType::Record {
+                    fields: [
+                        (ColumnName::from("a"), ScalarType::Int64.nullable(false)),
+                        (ColumnName::from("b"), ScalarType::String.nullable(true)),
+                    ]
+                    .into(),
+                    custom_id: None,
+                    custom_oid: None,
+                }
+                .nullable(true),
+            )
             .finish();
 
         let mut og_row = Row::default();
@@ -829,8 +844,9 @@ mod tests {
             )
             .expect("failed to pack JSON");
         packer.push_list([Datum::UInt32(200), Datum::UInt32(300)]);
+        packer.push_list([Datum::Int64(7), Datum::Null]);
 
-        let null_row = Row::pack(vec![Datum::Null; 9]);
+        let null_row = Row::pack(vec![Datum::Null; 10]);
 
         // Encode our data with our ArrowBuilder.
         let mut builder = crate::builder::ArrowBuilder::new(&desc, 2, 46).unwrap();
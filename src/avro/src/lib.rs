@@ -350,7 +350,7 @@ pub use crate::decode::{
     ValueOrReader,
 };
 pub use crate::encode::encode as encode_unchecked;
-pub use crate::reader::{from_avro_datum, Block, BlockIter, Reader};
+pub use crate::reader::{from_avro_datum, Block, BlockDecoder, BlockIter, Reader};
 pub use crate::schema::{ParseSchemaError, Schema};
 pub use crate::types::SchemaResolutionError;
 pub use crate::writer::{to_avro_datum, write_avro_datum, ValidationError, Writer};
@@ -140,11 +140,24 @@ pub struct Block {
 }
 
 impl<R: AvroRead> BlockIter<R> {
+    /// Creates a `BlockIter` without a reader `Schema`. The blocks must be decoded with the
+    /// writer schema, or a schema resolved from it.
+    pub fn new(inner: R) -> Result<Self, AvroError> {
+        Ok(Self {
+            inner: Reader::new(inner)?,
+        })
+    }
+
     pub fn with_schema(reader_schema: &Schema, inner: R) -> Result<Self, AvroError> {
         Ok(Self {
             inner: Reader::with_schema(reader_schema, inner)?,
         })
     }
+
+    /// Get a reference to the writer `Schema`.
+    pub fn writer_schema(&self) -> &Schema {
+        self.inner.writer_schema()
+    }
 }
 
 impl<R: AvroRead> Iterator for BlockIter<R> {
@@ -168,6 +181,94 @@ impl<R: AvroRead> Iterator for BlockIter<R> {
     }
 }
 
+/// Splits the bytes of an OCF file into `Block`s as they arrive.
+///
+/// Unlike a `BlockIter`, which pulls bytes from a reader, bytes are pushed into a `BlockDecoder`,
+/// so a file that is fetched asynchronously can be decoded without buffering all of it.
+#[derive(Debug, Default)]
+pub struct BlockDecoder {
+    header: Option<Header>,
+    /// Bytes that have been pushed but not yet decoded.
+    buf: Vec<u8>,
+}
+
+impl BlockDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the next bytes of the file.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Get a reference to the writer `Schema`, once the header has been decoded.
+    pub fn writer_schema(&self) -> Option<&Schema> {
+        self.header.as_ref().map(|header| &header.writer_schema)
+    }
+
+    /// Decodes the header, if necessary, and the next block of the file.
+    ///
+    /// Returns `None` if more bytes need to be pushed first.
+    pub fn next_block(&mut self) -> Result<Option<Block>, AvroError> {
+        let mut remaining = &self.buf[..];
+        if self.header.is_none() {
+            match Header::from_reader(&mut remaining) {
+                Ok(header) => self.header = Some(header),
+                Err(AvroError::IO(std::io::ErrorKind::UnexpectedEof)) => return Ok(None),
+                Err(e) => return Err(e),
+            }
+        }
+        let header = self.header.as_ref().expect("decoded above");
+
+        // Only consume the bytes of the block once all of them have arrived.
+        let mut rest = remaining;
+        let block = match Self::read_block(header, &mut rest) {
+            Ok(block) => {
+                remaining = rest;
+                Some(block)
+            }
+            Err(AvroError::IO(std::io::ErrorKind::UnexpectedEof)) => None,
+            Err(e) => return Err(e),
+        };
+        let consumed = self.buf.len() - remaining.len();
+        self.buf.drain(..consumed);
+        Ok(block)
+    }
+
+    /// Checks that the file did not end in the middle of its header or of a block.
+    pub fn finish(&self) -> Result<(), AvroError> {
+        if self.header.is_none() || !self.buf.is_empty() {
+            return Err(AvroError::IO(std::io::ErrorKind::UnexpectedEof));
+        }
+        Ok(())
+    }
+
+    fn read_block(header: &Header, remaining: &mut &[u8]) -> Result<Block, AvroError> {
+        let len = util::read_long(remaining)?;
+        let len = usize::try_from(len).map_err(|_| DecodeError::ExpectedNonnegInteger(len))?;
+        let size = util::read_long(remaining)?;
+        let size = usize::try_from(size).map_err(|_| DecodeError::ExpectedNonnegInteger(size))?;
+        let size = util::safe_len(size)?;
+        if remaining.len() < size + header.marker.len() {
+            return Err(AvroError::IO(std::io::ErrorKind::UnexpectedEof));
+        }
+        let (bytes, rest) = remaining.split_at(size);
+        let (marker, rest) = rest.split_at(header.marker.len());
+        if marker != header.marker {
+            return Err(DecodeError::MismatchedBlockHeader {
+                expected: header.marker,
+                actual: marker.try_into().expect("known to be 16 bytes"),
+            }
+            .into());
+        }
+        let mut bytes = bytes.to_vec();
+        header.codec.decompress(&mut bytes)?;
+        *remaining = rest;
+        Ok(Block { bytes, len })
+    }
+}
+
 impl<R: AvroRead> Reader<R> {
     /// Creates a `Reader` given something implementing the `tokio::io::AsyncRead` trait to read from.
     /// No reader `Schema` will be set.
@@ -933,7 +1034,7 @@ pub fn from_avro_datum<R: AvroRead>(schema: &Schema, reader: &mut R) -> Result<V
 mod tests {
     use std::io::Cursor;
 
-    use mz_ore::assert_err;
+    use mz_ore::{assert_err, assert_none, assert_ok};
 
     use crate::types::{Record, ToAvro};
     use crate::Reader;
@@ -1046,6 +1147,33 @@ mod tests {
         }
     }
 
+    #[mz_ore::test]
+    fn test_block_decoder() {
+        let expected: Vec<_> = BlockIter::new(ENCODED)
+            .unwrap()
+            .map(|block| block.unwrap().bytes)
+            .collect();
+
+        // Push the file one byte at a time, so every possible split is hit.
+        let mut decoder = BlockDecoder::new();
+        let mut blocks = vec![];
+        for byte in ENCODED {
+            decoder.push(&[*byte]);
+            while let Some(block) = decoder.next_block().unwrap() {
+                blocks.push(block.bytes);
+            }
+        }
+        assert_eq!(blocks, expected);
+        assert!(decoder.writer_schema().is_some());
+        assert_ok!(decoder.finish());
+
+        // A file that ends in the middle of a block is incomplete.
+        let mut decoder = BlockDecoder::new();
+        decoder.push(&ENCODED[..ENCODED.len() - 1]);
+        assert_none!(decoder.next_block().unwrap());
+        assert_err!(decoder.finish());
+    }
+
     #[mz_ore::test]
     fn test_reader_empty_buffer() {
         let empty = Cursor::new(Vec::new());
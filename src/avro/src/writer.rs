@@ -72,6 +72,8 @@ pub struct Writer<W> {
     codec: Option<Codec>,
     marker: [u8; 16],
     has_header: bool,
+    user_metadata: BTreeMap<String, Vec<u8>>,
+    header_schema: Option<String>,
 }
 
 impl<W: Write> Writer<W> {
@@ -107,6 +109,8 @@ impl<W: Write> Writer<W> {
             codec,
             marker,
             has_header: false,
+            user_metadata: BTreeMap::new(),
+            header_schema: None,
         }
     }
 
@@ -126,6 +130,8 @@ impl<W: Write> Writer<W> {
             codec: Some(codec),
             marker,
             has_header: true,
+            user_metadata: BTreeMap::new(),
+            header_schema: None,
         })
     }
 
@@ -134,6 +140,38 @@ impl<W: Write> Writer<W> {
         &self.schema
     }
 
+    /// Adds a key-value pair to the user metadata stored in the file header.
+    ///
+    /// Returns an error if the header has already been written or if `key` is in the `avro.`
+    /// namespace, which is reserved by the specification.
+    pub fn add_user_metadata(
+        &mut self,
+        key: String,
+        value: impl Into<Vec<u8>>,
+    ) -> Result<(), Error> {
+        if self.has_header {
+            return Err(ValidationError::new("header has already been written").into());
+        }
+        if key.starts_with("avro.") {
+            return Err(ValidationError::new(format!("metadata key {key} is reserved")).into());
+        }
+        self.user_metadata.insert(key, value.into());
+        Ok(())
+    }
+
+    /// Overrides the JSON schema document that is stored in the file header.
+    ///
+    /// [`Schema`] does not retain attributes it does not know about, which some readers rely on,
+    /// e.g. the `field-id`s of Apache Iceberg manifests. The caller is responsible for `schema`
+    /// describing the same schema as the one the `Writer` encodes values with.
+    pub fn set_header_schema(&mut self, schema: String) -> Result<(), Error> {
+        if self.has_header {
+            return Err(ValidationError::new("header has already been written").into());
+        }
+        self.header_schema = Some(schema);
+        Ok(())
+    }
+
     /// Append a compatible value (implementing the `ToAvro` trait) to a `Writer`, also performing
     /// schema validation.
     ///
@@ -301,9 +339,15 @@ impl<W: Write> Writer<W> {
 
     /// Create an Avro header based on schema, codec and sync marker.
     fn header(&self) -> Result<Vec<u8>, Error> {
-        let schema_bytes = serde_json::to_string(&self.schema)?.into_bytes();
+        let schema_bytes = match &self.header_schema {
+            Some(schema) => schema.clone().into_bytes(),
+            None => serde_json::to_string(&self.schema)?.into_bytes(),
+        };
 
         let mut metadata = BTreeMap::new();
+        for (key, value) in &self.user_metadata {
+            metadata.insert(key.as_str(), Value::Bytes(value.clone()));
+        }
         metadata.insert("avro.schema", Value::Bytes(schema_bytes));
         if let Some(codec) = self.codec {
             metadata.insert("avro.codec", codec.avro());
@@ -585,6 +629,43 @@ mod tests {
         );
     }
 
+    #[mz_ore::test]
+    fn test_writer_user_metadata() {
+        let schema = Schema::from_str(SCHEMA).unwrap();
+        let mut writer = Writer::new(schema.clone(), Vec::new());
+        writer
+            .add_user_metadata("format-version".into(), "2")
+            .unwrap();
+        assert!(writer
+            .add_user_metadata("avro.codec".into(), "null")
+            .is_err());
+        let header_schema = r#"{"type":"record","name":"test","fields":[{"name":"a","type":"long","field-id":1},{"name":"b","type":"string","field-id":2}]}"#;
+        writer.set_header_schema(header_schema.into()).unwrap();
+
+        let mut record = Record::new(schema.top_node()).unwrap();
+        record.put("a", 27i64);
+        record.put("b", "foo");
+        writer.append(record).unwrap();
+        assert!(writer.add_user_metadata("late".into(), "value").is_err());
+        writer.flush().unwrap();
+        let result = writer.into_inner();
+
+        let contains = |needle: &[u8]| result.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"format-version"));
+        assert!(contains(header_schema.as_bytes()));
+
+        let mut reader = Reader::new(&result[..]).unwrap();
+        assert_eq!(reader.writer_schema(), &schema);
+        let value = reader.next().unwrap().unwrap();
+        assert_eq!(
+            value,
+            Value::Record(vec![
+                ("a".into(), Value::Long(27)),
+                ("b".into(), Value::String("foo".into())),
+            ])
+        );
+    }
+
     #[mz_ore::test]
     #[cfg_attr(miri, ignore)] // slow
     fn test_writer_roundtrip() {
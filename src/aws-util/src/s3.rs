@@ -8,8 +8,11 @@
 // by the Apache License, Version 2.0.
 
 use aws_sdk_s3::config::Builder;
+use aws_sdk_s3::error::SdkError;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
 use aws_types::sdk_config::SdkConfig;
 use bytes::Bytes;
+use http::StatusCode;
 
 pub use aws_sdk_s3::Client;
 
@@ -49,6 +52,121 @@ pub async fn list_bucket_path(
         .transpose()
 }
 
+/// Fetches the contents of the object at `key`, returning `None` if the object
+/// does not exist.
+pub async fn get_object_if_exists(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<Bytes>, anyhow::Error> {
+    let res = client.get_object().bucket(bucket).key(key).send().await;
+    match res {
+        Ok(object) => Ok(Some(object.body.collect().await?.into_bytes())),
+        Err(SdkError::ServiceError(err)) if err.err().is_no_such_key() => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Fetches the contents and the entity tag of the object at `key`, returning
+/// `None` if the object does not exist.
+pub async fn get_object_with_e_tag_if_exists(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<(Bytes, String)>, anyhow::Error> {
+    let res = client.get_object().bucket(bucket).key(key).send().await;
+    match res {
+        Ok(object) => {
+            let e_tag = object
+                .e_tag
+                .ok_or_else(|| anyhow::anyhow!("e_tag not provided from get_object"))?;
+            Ok(Some((object.body.collect().await?.into_bytes(), e_tag)))
+        }
+        Err(SdkError::ServiceError(err)) if err.err().is_no_such_key() => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Writes `body` to the object at `key`, but only if no object exists at `key`
+/// or, if `e_tag` is given, only if the entity tag of the object at `key` is
+/// `e_tag`.
+///
+/// Returns the entity tag of the new object, or `None` if the condition did
+/// not hold. S3 rejects one of two concurrent conditional writes to the same
+/// key with a conflict, which is also reported as the condition not holding.
+pub async fn put_object_if(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    e_tag: Option<&str>,
+    body: Vec<u8>,
+) -> Result<Option<String>, anyhow::Error> {
+    let req = client
+        .put_object()
+        .bucket(bucket)
+        .key(key)
+        .body(body.into());
+    let req = match e_tag {
+        Some(e_tag) => req.if_match(e_tag),
+        None => req.if_none_match("*"),
+    };
+    match req.send().await {
+        Ok(res) => {
+            let e_tag = res
+                .e_tag
+                .ok_or_else(|| anyhow::anyhow!("e_tag not provided from put_object"))?;
+            Ok(Some(e_tag))
+        }
+        Err(err) if is_conditional_failure(err.raw_response()) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Returns whether the object at `key` exists and has the entity tag `e_tag`.
+pub async fn object_has_e_tag(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    e_tag: &str,
+) -> Result<bool, anyhow::Error> {
+    let res = client
+        .head_object()
+        .bucket(bucket)
+        .key(key)
+        .if_match(e_tag)
+        .send()
+        .await;
+    match res {
+        Ok(_) => Ok(true),
+        Err(SdkError::ServiceError(err)) if err.err().is_not_found() => Ok(false),
+        Err(err) if is_conditional_failure(err.raw_response()) => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Reports whether a request failed because of its preconditions, or because
+/// of a concurrent conditional request.
+fn is_conditional_failure(res: Option<&HttpResponse>) -> bool {
+    res.is_some_and(|res| {
+        let status = res.status().as_u16();
+        status == StatusCode::PRECONDITION_FAILED || status == StatusCode::CONFLICT
+    })
+}
+
+/// Returns whether an object exists at `key`.
+pub async fn object_exists(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<bool, anyhow::Error> {
+    let res = client.head_object().bucket(bucket).key(key).send().await;
+    match res {
+        Ok(_) => Ok(true),
+        Err(SdkError::ServiceError(err)) if err.err().is_not_found() => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
 /// A wrapper around [`ByteStream`] that implements the [`futures::stream::Stream`] trait.
 ///
 /// [`ByteStream`]: aws_smithy_types::byte_stream::ByteStream
@@ -116,6 +116,8 @@ impl RustType<crate::objects::RoleAttributes> for RoleAttributes {
     fn into_proto(&self) -> crate::objects::RoleAttributes {
         crate::objects::RoleAttributes {
             inherit: self.inherit,
+            login: self.login,
+            password_hash: self.password_hash.clone(),
         }
     }
 
@@ -123,6 +125,8 @@ impl RustType<crate::objects::RoleAttributes> for RoleAttributes {
         let mut attributes = RoleAttributes::new();
 
         attributes.inherit = proto.inherit;
+        attributes.login = proto.login;
+        attributes.password_hash = proto.password_hash;
 
         Ok(attributes)
     }
@@ -1051,6 +1051,20 @@ pub const TYPE_ANYNONARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_ANYENUM: BuiltinType<NameReference> = BuiltinType {
+    name: "anyenum",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_ANYENUM_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Pseudo,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 3504,
+            typreceive_oid: 0,
+        }),
+    },
+};
+
 pub const TYPE_ANYRANGE: BuiltinType<NameReference> = BuiltinType {
     name: "anyrange",
     schema: PG_CATALOG_SCHEMA,
@@ -1750,6 +1764,270 @@ pub const TYPE_ACL_ITEM_ARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_INET: BuiltinType<NameReference> = BuiltinType {
+    name: "inet",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 869,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Inet,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 910,
+            typreceive_oid: 2496,
+        }),
+    },
+};
+
+pub const TYPE_INET_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_inet",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 1041,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_INET.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_CIDR: BuiltinType<NameReference> = BuiltinType {
+    name: "cidr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 650,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Cidr,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 1267,
+            typreceive_oid: 2498,
+        }),
+    },
+};
+
+pub const TYPE_CIDR_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_cidr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 651,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_CIDR.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_MAC_ADDR: BuiltinType<NameReference> = BuiltinType {
+    name: "macaddr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 829,
+    details: CatalogTypeDetails {
+        typ: CatalogType::MacAddr,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 436,
+            typreceive_oid: 2494,
+        }),
+    },
+};
+
+pub const TYPE_MAC_ADDR_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_macaddr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 1040,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_MAC_ADDR.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_MAC_ADDR8: BuiltinType<NameReference> = BuiltinType {
+    name: "macaddr8",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 774,
+    details: CatalogTypeDetails {
+        typ: CatalogType::MacAddr8,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 4110,
+            typreceive_oid: 4113,
+        }),
+    },
+};
+
+pub const TYPE_MAC_ADDR8_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_macaddr8",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 775,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_MAC_ADDR8.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_TS_VECTOR: BuiltinType<NameReference> = BuiltinType {
+    name: "tsvector",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 3614,
+    details: CatalogTypeDetails {
+        typ: CatalogType::TsVector,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 3610,
+            typreceive_oid: 3639,
+        }),
+    },
+};
+
+pub const TYPE_TS_VECTOR_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_tsvector",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 3643,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_TS_VECTOR.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_TS_QUERY: BuiltinType<NameReference> = BuiltinType {
+    name: "tsquery",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 3615,
+    details: CatalogTypeDetails {
+        typ: CatalogType::TsQuery,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 3612,
+            typreceive_oid: 3641,
+        }),
+    },
+};
+
+pub const TYPE_TS_QUERY_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_tsquery",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 3645,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_TS_QUERY.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_JSONPATH: BuiltinType<NameReference> = BuiltinType {
+    name: "jsonpath",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 4072,
+    details: CatalogTypeDetails {
+        typ: CatalogType::JsonPath,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 4001,
+            typreceive_oid: 4002,
+        }),
+    },
+};
+
+pub const TYPE_JSONPATH_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_jsonpath",
+    schema: PG_CATALOG_SCHEMA,
+    oid: 4073,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_JSONPATH.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_GEOMETRY: BuiltinType<NameReference> = BuiltinType {
+    name: "geometry",
+    schema: MZ_CATALOG_SCHEMA,
+    oid: mz_pgrepr::oid::TYPE_GEOMETRY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Geometry,
+        array_id: None,
+        pg_metadata: None,
+    },
+};
+
+pub const TYPE_GEOMETRY_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_geometry",
+    schema: MZ_CATALOG_SCHEMA,
+    oid: mz_pgrepr::oid::TYPE_GEOMETRY_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_GEOMETRY.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_GEOGRAPHY: BuiltinType<NameReference> = BuiltinType {
+    name: "geography",
+    schema: MZ_CATALOG_SCHEMA,
+    oid: mz_pgrepr::oid::TYPE_GEOGRAPHY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Geography,
+        array_id: None,
+        pg_metadata: None,
+    },
+};
+
+pub const TYPE_GEOGRAPHY_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_geography",
+    schema: MZ_CATALOG_SCHEMA,
+    oid: mz_pgrepr::oid::TYPE_GEOGRAPHY_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_GEOGRAPHY.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
 pub const TYPE_INTERNAL: BuiltinType<NameReference> = BuiltinType {
     name: "internal",
     schema: PG_CATALOG_SCHEMA,
@@ -2546,6 +2824,19 @@ pub static MZ_TYPE_PG_METADATA: LazyLock<BuiltinTable> = LazyLock::new(|| Builti
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
 });
+/// The labels of enum types. Labels are numbered from 1 in declaration order.
+pub static MZ_ENUM_LABELS: LazyLock<BuiltinTable> = LazyLock::new(|| BuiltinTable {
+    name: "mz_enum_labels",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_ENUM_LABELS_OID,
+    desc: RelationDesc::builder()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("position", ScalarType::UInt32.nullable(false))
+        .with_column("label", ScalarType::String.nullable(false))
+        .finish(),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
 pub static MZ_ARRAY_TYPES: LazyLock<BuiltinTable> = LazyLock::new(|| BuiltinTable {
     name: "mz_array_types",
     schema: MZ_CATALOG_SCHEMA,
@@ -4766,7 +5057,12 @@ SELECT
     NULL::pg_catalog.int2 AS typlen,
     -- 'a' is used internally to denote an array type, but in postgres they show up
     -- as 'b'.
-    (CASE mztype WHEN 'a' THEN 'b' ELSE mztype END)::pg_catalog.char AS typtype,
+    (CASE
+        WHEN category = 'enum' THEN 'e'
+        WHEN category = 'composite' THEN 'c'
+        WHEN mztype = 'a' THEN 'b'
+        ELSE mztype
+    END)::pg_catalog.char AS typtype,
     (CASE category
         WHEN 'array' THEN 'A'
         WHEN 'bit-string' THEN 'V'
@@ -4786,7 +5082,9 @@ SELECT
     END)::pg_catalog.char AS typcategory,
     -- In pg only the 'box' type is not ','.
     ','::pg_catalog.char AS typdelim,
-    0::pg_catalog.oid AS typrelid,
+    -- Composite types don't have a separate pg_class entry, so their fields are
+    -- listed in pg_attribute under the OID of the type itself.
+    (CASE category WHEN 'composite' THEN mz_types.oid ELSE 0 END)::pg_catalog.oid AS typrelid,
     coalesce(
         (
             SELECT t.oid
@@ -4891,12 +5189,17 @@ SELECT
     d.name as database_name,
     pg_type_all_databases.database_name as pg_type_database_name
 FROM (
-    -- pg_attribute catalogs columns on relations and indexes
+    -- pg_attribute catalogs columns on relations and indexes, and fields of
+    -- composite types
     SELECT id, oid, schema_id, name, type FROM mz_catalog.mz_relations
     UNION ALL
         SELECT mz_indexes.id, mz_indexes.oid, mz_relations.schema_id, mz_indexes.name, 'index' AS type
         FROM mz_catalog.mz_indexes
         JOIN mz_catalog.mz_relations ON mz_indexes.on_id = mz_relations.id
+    UNION ALL
+        SELECT id, oid, schema_id, name, 'type' AS type
+        FROM mz_catalog.mz_types
+        WHERE category = 'composite'
 ) AS class_objects
 JOIN mz_catalog.mz_columns ON class_objects.id = mz_columns.id
 JOIN mz_internal.pg_type_all_databases ON pg_type_all_databases.oid = mz_columns.type_oid
@@ -5009,10 +5312,11 @@ pub static PG_ENUM: LazyLock<BuiltinView> = LazyLock::new(|| BuiltinView {
     column_defs: None,
     sql: "SELECT
     NULL::pg_catalog.oid AS oid,
-    NULL::pg_catalog.oid AS enumtypid,
-    NULL::pg_catalog.float4 AS enumsortorder,
-    NULL::pg_catalog.text AS enumlabel
-WHERE false",
+    mz_types.oid AS enumtypid,
+    mz_enum_labels.position::pg_catalog.float4 AS enumsortorder,
+    mz_enum_labels.label AS enumlabel
+FROM mz_internal.mz_enum_labels
+JOIN mz_catalog.mz_types ON mz_types.id = mz_enum_labels.id",
     access: vec![PUBLIC_SELECT],
 });
 
@@ -9201,6 +9505,7 @@ pub static BUILTINS_STATIC: LazyLock<Vec<Builtin<NameReference>>> = LazyLock::ne
         Builtin::Type(&TYPE_ANYARRAY),
         Builtin::Type(&TYPE_ANYELEMENT),
         Builtin::Type(&TYPE_ANYNONARRAY),
+        Builtin::Type(&TYPE_ANYENUM),
         Builtin::Type(&TYPE_ANYRANGE),
         Builtin::Type(&TYPE_BOOL),
         Builtin::Type(&TYPE_BOOL_ARRAY),
@@ -9286,6 +9591,24 @@ pub static BUILTINS_STATIC: LazyLock<Vec<Builtin<NameReference>>> = LazyLock::ne
         Builtin::Type(&TYPE_MZ_ACL_ITEM_ARRAY),
         Builtin::Type(&TYPE_ACL_ITEM),
         Builtin::Type(&TYPE_ACL_ITEM_ARRAY),
+        Builtin::Type(&TYPE_INET),
+        Builtin::Type(&TYPE_INET_ARRAY),
+        Builtin::Type(&TYPE_CIDR),
+        Builtin::Type(&TYPE_CIDR_ARRAY),
+        Builtin::Type(&TYPE_MAC_ADDR),
+        Builtin::Type(&TYPE_MAC_ADDR_ARRAY),
+        Builtin::Type(&TYPE_MAC_ADDR8),
+        Builtin::Type(&TYPE_MAC_ADDR8_ARRAY),
+        Builtin::Type(&TYPE_TS_VECTOR),
+        Builtin::Type(&TYPE_TS_VECTOR_ARRAY),
+        Builtin::Type(&TYPE_TS_QUERY),
+        Builtin::Type(&TYPE_TS_QUERY_ARRAY),
+        Builtin::Type(&TYPE_JSONPATH),
+        Builtin::Type(&TYPE_JSONPATH_ARRAY),
+        Builtin::Type(&TYPE_GEOMETRY),
+        Builtin::Type(&TYPE_GEOMETRY_ARRAY),
+        Builtin::Type(&TYPE_GEOGRAPHY),
+        Builtin::Type(&TYPE_GEOGRAPHY_ARRAY),
         Builtin::Type(&TYPE_INTERNAL),
     ];
     for (schema, funcs) in &[
@@ -9359,6 +9682,7 @@ pub static BUILTINS_STATIC: LazyLock<Vec<Builtin<NameReference>>> = LazyLock::ne
         Builtin::Table(&MZ_MATERIALIZED_VIEW_REFRESH_STRATEGIES),
         Builtin::Table(&MZ_TYPES),
         Builtin::Table(&MZ_TYPE_PG_METADATA),
+        Builtin::Table(&MZ_ENUM_LABELS),
         Builtin::Table(&MZ_ARRAY_TYPES),
         Builtin::Table(&MZ_BASE_TYPES),
         Builtin::Table(&MZ_LIST_TYPES),
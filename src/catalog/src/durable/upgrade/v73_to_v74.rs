@@ -0,0 +1,53 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::durable::upgrade::wire_compatible::{wire_compatible, WireCompatible};
+use crate::durable::upgrade::MigrationAction;
+use crate::durable::upgrade::{objects_v73 as v73, objects_v74 as v74};
+
+wire_compatible!(v73::RoleKey with v74::RoleKey);
+wire_compatible!(v73::RoleMembership with v74::RoleMembership);
+wire_compatible!(v73::RoleVars with v74::RoleVars);
+
+/// In v74 we added the optional `login` and `password_hash` fields to `RoleAttributes`.
+///
+/// The fields are left unset for existing roles. All roles are rewritten, so that their
+/// serialization includes the new fields.
+pub fn upgrade(
+    snapshot: Vec<v73::StateUpdateKind>,
+) -> Vec<MigrationAction<v73::StateUpdateKind, v74::StateUpdateKind>> {
+    snapshot
+        .into_iter()
+        .filter_map(|update| match &update.kind {
+            Some(v73::state_update_kind::Kind::Role(old_role)) => {
+                let new_role = v74::state_update_kind::Role {
+                    key: old_role.key.as_ref().map(WireCompatible::convert),
+                    value: old_role.value.as_ref().map(|value| v74::RoleValue {
+                        name: value.name.clone(),
+                        attributes: value.attributes.as_ref().map(|attributes| {
+                            v74::RoleAttributes {
+                                inherit: attributes.inherit,
+                                login: None,
+                                password_hash: None,
+                            }
+                        }),
+                        membership: value.membership.as_ref().map(WireCompatible::convert),
+                        vars: value.vars.as_ref().map(WireCompatible::convert),
+                        oid: value.oid,
+                    }),
+                };
+                let new_role = v74::StateUpdateKind {
+                    kind: Some(v74::state_update_kind::Kind::Role(new_role)),
+                };
+                Some(MigrationAction::Update(update, new_role))
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -654,7 +654,6 @@ impl mz_sql::catalog::CatalogCollectionItem for CatalogCollectionEntry {
             CatalogItem::Source(source) => source.global_id,
             CatalogItem::Log(log) => log.global_id,
             CatalogItem::View(view) => view.global_id,
-            CatalogItem::MaterializedView(mv) => mv.global_id,
             CatalogItem::Sink(sink) => sink.global_id,
             CatalogItem::Index(index) => index.global_id,
             CatalogItem::Type(ty) => ty.global_id,
@@ -662,6 +661,13 @@ impl mz_sql::catalog::CatalogCollectionItem for CatalogCollectionEntry {
             CatalogItem::Secret(secret) => secret.global_id,
             CatalogItem::Connection(conn) => conn.global_id,
             CatalogItem::ContinualTask(ct) => ct.global_id,
+            CatalogItem::MaterializedView(mv) => match self.version {
+                RelationVersionSelector::Latest => mv.global_id(),
+                RelationVersionSelector::Specific(version) => *mv
+                    .collections
+                    .get(&version)
+                    .expect("catalog corruption, missing version!"),
+            },
             CatalogItem::Table(table) => match self.version {
                 RelationVersionSelector::Latest => {
                     let (_version, gid) = table
@@ -705,7 +711,7 @@ impl mz_sql::catalog::CatalogItem for CatalogCollectionEntry {
         self.entry.oid()
     }
 
-    fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.entry.func()
     }
 
@@ -1190,7 +1196,9 @@ impl Source {
                     // These multi-output sources do not use their primary
                     // source's data shard, so we don't include it in accounting
                     // for users.
-                    GenericSourceConnection::Postgres(_) | GenericSourceConnection::MySql(_) => 0,
+                    GenericSourceConnection::Postgres(_)
+                    | GenericSourceConnection::MySql(_)
+                    | GenericSourceConnection::SqlServer(_) => 0,
                     GenericSourceConnection::LoadGenerator(lg) => {
                         // TODO: make this a method on the load generator.
                         if lg.load_generator.views().is_empty() {
@@ -1265,6 +1273,7 @@ impl Sink {
         match &self.envelope {
             SinkEnvelope::Debezium => Some("debezium"),
             SinkEnvelope::Upsert => Some("upsert"),
+            SinkEnvelope::Append => None,
         }
     }
 
@@ -1273,13 +1282,23 @@ impl Sink {
     /// both the same (either avro or json), we return the value format name,
     /// otherwise we return a composite name.
     pub fn combined_format(&self) -> Cow<'_, str> {
-        let StorageSinkConnection::Kafka(connection) = &self.connection;
-        connection.format.get_format_name()
+        match &self.connection {
+            StorageSinkConnection::Kafka(connection) => connection.format.get_format_name(),
+            StorageSinkConnection::Iceberg(_) => Cow::Borrowed("parquet"),
+            StorageSinkConnection::S3(connection) => Cow::Borrowed(connection.format_name()),
+            // Rows are written to Postgres in its text format.
+            StorageSinkConnection::Postgres(_) => Cow::Borrowed("text"),
+        }
     }
 
     /// Output distinct key_format and value_format of the sink.
     pub fn formats(&self) -> (Option<&str>, &str) {
-        let StorageSinkConnection::Kafka(connection) = &self.connection;
+        let connection = match &self.connection {
+            StorageSinkConnection::Kafka(connection) => connection,
+            StorageSinkConnection::Iceberg(_) => return (None, "parquet"),
+            StorageSinkConnection::S3(connection) => return (None, connection.format_name()),
+            StorageSinkConnection::Postgres(_) => return (None, "text"),
+        };
         let key_format = connection
             .format
             .key_format
@@ -1330,8 +1349,10 @@ impl View {
 pub struct MaterializedView {
     /// Parse-able SQL that defines this materialized view.
     pub create_sql: String,
-    /// [`GlobalId`] used to reference this materialized view from outside the catalog.
-    pub global_id: GlobalId,
+    /// Versions of this materialized view's definition, and the [`GlobalId`]s that refer to
+    /// them. All versions write into the same persist shard.
+    #[serde(serialize_with = "mz_ore::serde::map_key_to_string")]
+    pub collections: BTreeMap<RelationVersion, GlobalId>,
     /// Raw high-level expression from planning, derived from the `create_sql`.
     pub raw_expr: Arc<HirRelationExpr>,
     /// Optimized mid-level expression, derived from the `raw_expr`.
@@ -1360,9 +1381,29 @@ pub struct MaterializedView {
 }
 
 impl MaterializedView {
-    /// The single [`GlobalId`] this [`MaterializedView`] can be referenced by.
+    /// Returns all of the [`GlobalId`]s that this [`MaterializedView`] can be referenced by.
+    pub fn global_ids(&self) -> impl Iterator<Item = GlobalId> + '_ {
+        self.collections.values().copied()
+    }
+
+    /// Returns the [`GlobalId`] of the latest definition of this [`MaterializedView`], which
+    /// its dataflow writes to.
     pub fn global_id(&self) -> GlobalId {
-        self.global_id
+        *self
+            .collections
+            .last_key_value()
+            .expect("at least one version of a materialized view")
+            .1
+    }
+
+    /// Returns the [`GlobalId`] of the first definition of this [`MaterializedView`], which
+    /// drives compaction of the persist shard shared by all versions.
+    pub fn primary_global_id(&self) -> GlobalId {
+        *self
+            .collections
+            .first_key_value()
+            .expect("at least one version of a materialized view")
+            .1
     }
 }
 
@@ -1413,11 +1454,36 @@ pub struct Type {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Func {
-    /// Static definition of the function.
+    /// Parse-able SQL that defines this function, if it is user-defined.
+    pub create_sql: Option<String>,
+    /// Definition of the function.
     #[serde(skip)]
-    pub inner: &'static mz_sql::func::Func,
+    pub inner: FuncDefinition,
     /// [`GlobalId`] used to reference this function from outside the catalog.
     pub global_id: GlobalId,
+    /// Other catalog objects referenced by this function.
+    pub resolved_ids: ResolvedIds,
+}
+
+/// The definition of a [`Func`].
+#[derive(Debug, Clone)]
+pub enum FuncDefinition {
+    /// A builtin function, which is defined statically.
+    Builtin(&'static mz_sql::func::Func),
+    /// A user-defined function, which is planned from its `CREATE FUNCTION`
+    /// statement.
+    User(Arc<mz_sql::func::Func>),
+}
+
+impl Deref for FuncDefinition {
+    type Target = mz_sql::func::Func;
+
+    fn deref(&self) -> &mz_sql::func::Func {
+        match self {
+            FuncDefinition::Builtin(func) => func,
+            FuncDefinition::User(func) => func,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1572,7 +1638,6 @@ impl CatalogItem {
             CatalogItem::Log(log) => log.global_id,
             CatalogItem::Sink(sink) => sink.global_id,
             CatalogItem::View(view) => view.global_id,
-            CatalogItem::MaterializedView(mv) => mv.global_id,
             CatalogItem::ContinualTask(ct) => ct.global_id,
             CatalogItem::Index(index) => index.global_id,
             CatalogItem::Func(func) => func.global_id,
@@ -1582,20 +1647,24 @@ impl CatalogItem {
             CatalogItem::Table(table) => {
                 return itertools::Either::Left(table.collections.values().copied());
             }
+            CatalogItem::MaterializedView(mv) => {
+                return itertools::Either::Left(mv.collections.values().copied());
+            }
         };
         itertools::Either::Right(std::iter::once(gid))
     }
 
     /// Returns the most up-to-date [`GlobalId`] for this item.
     ///
-    /// Note: The only type of object that can have multiple [`GlobalId`]s are tables.
+    /// Note: The only types of object that can have multiple [`GlobalId`]s are tables and
+    /// materialized views.
     pub fn latest_global_id(&self) -> GlobalId {
         match self {
             CatalogItem::Source(source) => source.global_id,
             CatalogItem::Log(log) => log.global_id,
             CatalogItem::Sink(sink) => sink.global_id,
             CatalogItem::View(view) => view.global_id,
-            CatalogItem::MaterializedView(mv) => mv.global_id,
+            CatalogItem::MaterializedView(mv) => mv.global_id(),
             CatalogItem::ContinualTask(ct) => ct.global_id,
             CatalogItem::Index(index) => index.global_id,
             CatalogItem::Func(func) => func.global_id,
@@ -1653,12 +1722,9 @@ impl CatalogItem {
         }
     }
 
-    pub fn func(
-        &self,
-        entry: &CatalogEntry,
-    ) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self, entry: &CatalogEntry) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         match &self {
-            CatalogItem::Func(func) => Ok(func.inner),
+            CatalogItem::Func(func) => Ok(&*func.inner),
             _ => Err(SqlCatalogError::UnexpectedType {
                 name: entry.name().item.to_string(),
                 actual_type: entry.item_type(),
@@ -1703,7 +1769,7 @@ impl CatalogItem {
     pub fn references(&self) -> &ResolvedIds {
         static EMPTY: LazyLock<ResolvedIds> = LazyLock::new(ResolvedIds::empty);
         match self {
-            CatalogItem::Func(_) => &*EMPTY,
+            CatalogItem::Func(func) => &func.resolved_ids,
             CatalogItem::Index(idx) => &idx.resolved_ids,
             CatalogItem::Sink(sink) => &sink.resolved_ids,
             CatalogItem::Source(source) => &source.resolved_ids,
@@ -1840,7 +1906,11 @@ impl CatalogItem {
                 i.create_sql = i.create_sql.map(do_rewrite).transpose()?;
                 Ok(CatalogItem::Type(i))
             }
-            CatalogItem::Func(i) => Ok(CatalogItem::Func(i.clone())),
+            CatalogItem::Func(i) => {
+                let mut i = i.clone();
+                i.create_sql = i.create_sql.map(do_rewrite).transpose()?;
+                Ok(CatalogItem::Func(i))
+            }
             CatalogItem::ContinualTask(i) => {
                 let mut i = i.clone();
                 i.create_sql = do_rewrite(i.create_sql)?;
@@ -1908,7 +1978,12 @@ impl CatalogItem {
                 i.create_sql = do_rewrite(i.create_sql)?;
                 Ok(CatalogItem::Secret(i))
             }
-            CatalogItem::Func(_) | CatalogItem::Type(_) => {
+            CatalogItem::Func(i) => {
+                let mut i = i.clone();
+                i.create_sql = i.create_sql.map(do_rewrite).transpose()?;
+                Ok(CatalogItem::Func(i))
+            }
+            CatalogItem::Type(_) => {
                 unreachable!("{}s cannot be renamed", self.typ())
             }
             CatalogItem::Connection(i) => {
@@ -2226,7 +2301,11 @@ impl CatalogItem {
             }
             CatalogItem::View(view) => (view.create_sql.clone(), view.global_id, BTreeMap::new()),
             CatalogItem::MaterializedView(mview) => {
-                (mview.create_sql.clone(), mview.global_id, BTreeMap::new())
+                let mut collections = mview.collections.clone();
+                let global_id = collections
+                    .remove(&RelationVersion::root())
+                    .expect("at least one version");
+                (mview.create_sql.clone(), global_id, collections)
             }
             CatalogItem::Index(index) => {
                 (index.create_sql.clone(), index.global_id, BTreeMap::new())
@@ -2247,7 +2326,13 @@ impl CatalogItem {
                 connection.global_id,
                 BTreeMap::new(),
             ),
-            CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
+            CatalogItem::Func(func) => {
+                let create_sql = func
+                    .create_sql
+                    .clone()
+                    .expect("builtin functions cannot be serialized");
+                (create_sql, func.global_id, BTreeMap::new())
+            }
             CatalogItem::ContinualTask(ct) => {
                 (ct.create_sql.clone(), ct.global_id, BTreeMap::new())
             }
@@ -2278,8 +2363,12 @@ impl CatalogItem {
                 (create_sql, source.global_id, BTreeMap::new())
             }
             CatalogItem::View(view) => (view.create_sql, view.global_id, BTreeMap::new()),
-            CatalogItem::MaterializedView(mview) => {
-                (mview.create_sql, mview.global_id, BTreeMap::new())
+            CatalogItem::MaterializedView(mut mview) => {
+                let global_id = mview
+                    .collections
+                    .remove(&RelationVersion::root())
+                    .expect("at least one version");
+                (mview.create_sql, global_id, mview.collections)
             }
             CatalogItem::Index(index) => (index.create_sql, index.global_id, BTreeMap::new()),
             CatalogItem::Sink(sink) => (sink.create_sql, sink.global_id, BTreeMap::new()),
@@ -2291,7 +2380,12 @@ impl CatalogItem {
             CatalogItem::Connection(connection) => {
                 (connection.create_sql, connection.global_id, BTreeMap::new())
             }
-            CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
+            CatalogItem::Func(func) => {
+                let create_sql = func
+                    .create_sql
+                    .expect("builtin functions cannot be serialized");
+                (create_sql, func.global_id, BTreeMap::new())
+            }
             CatalogItem::ContinualTask(ct) => (ct.create_sql, ct.global_id, BTreeMap::new()),
         }
     }
@@ -2318,7 +2412,7 @@ impl CatalogEntry {
     }
 
     /// Returns the [`mz_sql::func::Func`] associated with this `CatalogEntry`.
-    pub fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.item.func(self)
     }
 
@@ -3026,7 +3120,7 @@ impl mz_sql::catalog::CatalogSchema for Schema {
     }
 
     fn has_items(&self) -> bool {
-        !self.items.is_empty()
+        !self.items.is_empty() || !self.functions.is_empty()
     }
 
     fn item_ids(&self) -> Box<dyn Iterator<Item = CatalogItemId> + '_> {
@@ -3187,7 +3281,7 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         self.oid()
     }
 
-    fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.func()
     }
 
@@ -3219,7 +3313,9 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
             }
             CatalogItem::Secret(Secret { create_sql, .. }) => create_sql,
             CatalogItem::Connection(Connection { create_sql, .. }) => create_sql,
-            CatalogItem::Func(_) => "<builtin>",
+            CatalogItem::Func(Func { create_sql, .. }) => {
+                create_sql.as_deref().unwrap_or("<builtin>")
+            }
             CatalogItem::Log(_) => "<builtin>",
             CatalogItem::ContinualTask(ContinualTask { create_sql, .. }) => create_sql,
         }
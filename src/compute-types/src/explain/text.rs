@@ -36,7 +36,8 @@ use crate::plan::join::delta_join::{DeltaPathPlan, DeltaStagePlan};
 use crate::plan::join::linear_join::LinearStagePlan;
 use crate::plan::join::{DeltaJoinPlan, JoinClosure, LinearJoinPlan};
 use crate::plan::reduce::{
-    AccumulablePlan, BasicPlan, CollationPlan, HierarchicalPlan, SingleBasicPlan,
+    AccumulablePlan, BasicPlan, CollationPlan, HierarchicalPlan, SingleBasicPlan, SketchPlan,
+    WindowPlan,
 };
 use crate::plan::{AvailableCollections, LirId, Plan, PlanNode};
 
@@ -268,6 +269,14 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
                         writeln!(f, "{}Reduce::Basic{}", ctx.indent, annotations)?;
                         ctx.indented(|ctx| plan.fmt_text(f, ctx))?;
                     }
+                    ReducePlan::Sketch(plan) => {
+                        writeln!(f, "{}Reduce::Sketch{}", ctx.indent, annotations)?;
+                        ctx.indented(|ctx| plan.fmt_text(f, ctx))?;
+                    }
+                    ReducePlan::Window(plan) => {
+                        writeln!(f, "{}Reduce::Window{}", ctx.indent, annotations)?;
+                        ctx.indented(|ctx| plan.fmt_text(f, ctx))?;
+                    }
                     ReducePlan::Collation(plan) => {
                         writeln!(f, "{}Reduce::Collation{}", ctx.indent, annotations)?;
                         ctx.indented(|ctx| plan.fmt_text(f, ctx))?;
@@ -756,6 +765,34 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for BasicPlan {
     }
 }
 
+impl DisplayText<PlanRenderingContext<'_, Plan>> for SketchPlan {
+    fn fmt_text(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        ctx: &mut PlanRenderingContext<'_, Plan>,
+    ) -> fmt::Result {
+        let mode = HumanizedExplain::new(ctx.config.redacted);
+        for (i, (i_datum, agg)) in self.aggrs.iter().enumerate() {
+            let agg = mode.expr(agg, None);
+            writeln!(f, "{}aggrs[{}]=({}, {})", ctx.indent, i, i_datum, agg)?;
+        }
+        Ok(())
+    }
+}
+
+impl DisplayText<PlanRenderingContext<'_, Plan>> for WindowPlan {
+    fn fmt_text(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        ctx: &mut PlanRenderingContext<'_, Plan>,
+    ) -> fmt::Result {
+        let mode = HumanizedExplain::new(ctx.config.redacted);
+        let agg = mode.expr(&self.expr, None);
+        writeln!(f, "{}aggr=({}, {})", ctx.indent, self.index, agg)?;
+        Ok(())
+    }
+}
+
 impl DisplayText<PlanRenderingContext<'_, Plan>> for CollationPlan {
     fn fmt_text(
         &self,
@@ -771,6 +808,7 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for CollationPlan {
                     ReductionType::Accumulable => "a".to_string(),
                     ReductionType::Hierarchical => "h".to_string(),
                     ReductionType::Basic => "b".to_string(),
+                    ReductionType::Sketch => "s".to_string(),
                 })
                 .collect::<Vec<_>>();
             let aggregate_types = separated(", ", aggregate_types);
@@ -788,6 +826,10 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for CollationPlan {
             writeln!(f, "{}basic", ctx.indent)?;
             ctx.indented(|ctx| plan.fmt_text(f, ctx))?;
         }
+        if let Some(plan) = &self.sketch {
+            writeln!(f, "{}sketch", ctx.indent)?;
+            ctx.indented(|ctx| plan.fmt_text(f, ctx))?;
+        }
         Ok(())
     }
 }
@@ -23,7 +23,7 @@ use timely::progress::Timestamp;
 
 use crate::dataflows::{BuildDesc, DataflowDescription, IndexImport};
 use crate::plan::join::{DeltaJoinPlan, JoinPlan, LinearJoinPlan};
-use crate::plan::reduce::{KeyValPlan, ReducePlan};
+use crate::plan::reduce::{KeyValPlan, ReducePlan, WindowPlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
 use crate::plan::{AvailableCollections, GetPlan, LirId, Plan, PlanNode};
@@ -37,6 +37,8 @@ pub(super) struct Context {
     debug_info: LirDebugInfo,
     /// Whether to enable fusion of MFPs in reductions.
     enable_reduce_mfp_fusion: bool,
+    /// Whether to enable incremental rendering of window functions.
+    enable_incremental_window_functions: bool,
 }
 
 impl Context {
@@ -49,6 +51,7 @@ impl Context {
                 id: GlobalId::Transient(0),
             },
             enable_reduce_mfp_fusion: features.enable_reduce_mfp_fusion,
+            enable_incremental_window_functions: features.enable_incremental_window_functions,
         }
     }
 
@@ -995,6 +998,12 @@ This is not expected to cause incorrect results, but could indicate a performanc
         fused_unnest_list: bool,
     ) -> Result<(Plan<T>, AvailableCollections), String> {
         let input_arity = input.arity();
+        // Some window functions whose results are unnested can be rendered incrementally.
+        let window_plan = if fused_unnest_list && self.enable_incremental_window_functions {
+            WindowPlan::try_new(aggregates, &input.typ().column_types)
+        } else {
+            None
+        };
         let (input, keys) = self.lower_mir_expr(input)?;
         let (input_key, permutation_and_new_arity) =
             if let Some((input_key, permutation, thinning)) = keys.arbitrary_arrangement() {
@@ -1011,12 +1020,15 @@ This is not expected to cause incorrect results, but could indicate a performanc
             aggregates,
             permutation_and_new_arity,
         );
-        let reduce_plan = ReducePlan::create_from(
-            aggregates.clone(),
-            *monotonic,
-            *expected_group_size,
-            fused_unnest_list,
-        );
+        let reduce_plan = match window_plan {
+            Some(window_plan) => ReducePlan::Window(window_plan),
+            None => ReducePlan::create_from(
+                aggregates.clone(),
+                *monotonic,
+                *expected_group_size,
+                fused_unnest_list,
+            ),
+        };
         // Return the plan, and the keys it produces.
         let mfp_after;
         let output_arity;
@@ -24,8 +24,8 @@
 //! duplicate updates.
 //!
 //! In order to optimize the performance of our rendered dataflow, we divide all aggregations
-//! into three distinct types. Each type gets rendered separately, with its own specialized plan
-//! and dataflow. The three types are as follows:
+//! into four distinct types. Each type gets rendered separately, with its own specialized plan
+//! and dataflow. The four types are as follows:
 //!
 //! 1. Accumulable:
 //!    Accumulable reductions can be computed inline in a Differential update's `difference`
@@ -52,6 +52,14 @@
 //!    Basic reductions are a bit like the Hufflepuffs of this trifecta. They are neither accumulable nor
 //!    hierarchical (most likely they are associative but don't involve any data reduction) and so for these
 //!    we can't do much more than just defer to Differential's reduce operator and eat a large maintenance cost.
+//! 4. Sketch:
+//!    Sketch reductions compute approximate answers, such as the number of distinct values, from a
+//!    summary of the input rather than from the input itself. Each input value is mapped to one of a
+//!    bounded number of sketch coordinates, and the summary records how many values each coordinate
+//!    has. These counts can be maintained under arbitrary changes to the input, like accumulable
+//!    reductions, but unlike them they are not reduced to a single value: the estimate is computed
+//!    from all of a key's coordinate counts, whose number is bounded regardless of the number of
+//!    records for the key. `approx_count_distinct()` is an example of a sketch reduction.
 //!
 //! When we render these reductions we want to limit the number of arrangements we produce. When we build a
 //! dataflow for a reduction containing multiple types of reductions, we have no choice but to divide up the
@@ -59,14 +67,25 @@
 //! back in the requested output order. However, if we only need to perform aggregations of a single reduction
 //! type, we can specialize and render the dataflow to compute those aggregations in the correct order, and
 //! return the output arrangement directly and avoid the extra collation arrangement.
+//!
+//! Window functions are planned as basic reductions that produce a list of results for each window
+//! partition, which is then unnested (or, with `fused_unnest_list`, not even materialized as a
+//! list). Any change to a partition recomputes the results for the entire partition. The ranking
+//! window functions `row_number`, `rank`, and `dense_rank`, as well as `lag` and `lead`, can
+//! instead be planned as a [`WindowPlan`], which maintains, for each row, the number of rows that
+//! precede it in its partition, or the row that immediately precedes it. This only requires work
+//! proportional to the logarithm of the size of the partition for each row that changes, in
+//! addition to the work of updating the results that change.
 
 use std::collections::BTreeMap;
 
 use mz_expr::{
     permutation_for_arrangement, AggregateExpr, AggregateFunc, MapFilterProject, MirScalarExpr,
+    VariadicFunc,
 };
 use mz_ore::{assert_none, soft_assert_or_log};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::{ColumnType, Datum, ScalarType};
 use proptest::prelude::{any, Arbitrary, BoxedStrategy};
 use proptest::strategy::Strategy;
 use proptest_derive::Arbitrary;
@@ -76,7 +95,7 @@ use crate::plan::{bucketing_of_expected_group_size, AvailableCollections};
 
 include!(concat!(env!("OUT_DIR"), "/mz_compute_types.plan.reduce.rs"));
 
-/// This enum represents the three potential types of aggregations.
+/// This enum represents the four potential types of aggregations.
 #[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum ReductionType {
     /// Accumulable functions can be subtracted from (are invertible), and associative.
@@ -91,6 +110,10 @@ pub enum ReductionType {
     /// Basic, for lack of a better word, are functions that are neither accumulable
     /// nor hierarchical. Examples include jsonb_agg.
     Basic,
+    /// Sketch functions are estimated from the number of inputs mapped to each of
+    /// a bounded set of sketch coordinates, which can be maintained like accumulable
+    /// functions. Examples include approx_count_distinct.
+    Sketch,
 }
 
 impl columnation::Columnation for ReductionType {
@@ -105,6 +128,7 @@ impl RustType<ProtoReductionType> for ReductionType {
                 ReductionType::Accumulable => Kind::Accumulable(()),
                 ReductionType::Hierarchical => Kind::Hierarchical(()),
                 ReductionType::Basic => Kind::Basic(()),
+                ReductionType::Sketch => Kind::Sketch(()),
             }),
         }
     }
@@ -118,6 +142,7 @@ impl RustType<ProtoReductionType> for ReductionType {
             Kind::Accumulable(()) => ReductionType::Accumulable,
             Kind::Hierarchical(()) => ReductionType::Hierarchical,
             Kind::Basic(()) => ReductionType::Basic,
+            Kind::Sketch(()) => ReductionType::Sketch,
         })
     }
 }
@@ -130,6 +155,7 @@ impl TryFrom<&ReducePlan> for ReductionType {
             ReducePlan::Hierarchical(_) => Ok(ReductionType::Hierarchical),
             ReducePlan::Accumulable(_) => Ok(ReductionType::Accumulable),
             ReducePlan::Basic(_) => Ok(ReductionType::Basic),
+            ReducePlan::Sketch(_) => Ok(ReductionType::Sketch),
             _ => Err(()),
         }
     }
@@ -140,8 +166,8 @@ impl TryFrom<&ReducePlan> for ReductionType {
 ///
 /// The provided reduce expression can have no
 /// aggregations, in which case its just a `Distinct` and otherwise
-/// it's composed of a combination of accumulable, hierarchical and
-/// basic aggregations.
+/// it's composed of a combination of accumulable, hierarchical, basic
+/// and sketch aggregations.
 ///
 /// We want to try to centralize as much decision making about the
 /// shape / general computation of the rendered dataflow graph
@@ -158,6 +184,10 @@ pub enum ReducePlan {
     Hierarchical(HierarchicalPlan),
     /// Plan for computing only basic aggregations.
     Basic(BasicPlan),
+    /// Plan for computing only sketch aggregations.
+    Sketch(SketchPlan),
+    /// Plan for incrementally computing a single ranking window function.
+    Window(WindowPlan),
     /// Plan for computing a mix of different kinds of aggregations.
     /// We need to do extra work here to reassemble results back in the
     /// requested order.
@@ -236,6 +266,8 @@ impl RustType<ProtoReducePlan> for ReducePlan {
                 ReducePlan::Hierarchical(plan) => Hierarchical(plan.into_proto()),
                 ReducePlan::Basic(plan) => Basic(plan.into_proto()),
                 ReducePlan::Collation(plan) => Collation(plan.into_proto()),
+                ReducePlan::Sketch(plan) => Sketch(plan.into_proto()),
+                ReducePlan::Window(plan) => Window(plan.into_proto()),
             }),
         }
     }
@@ -251,6 +283,8 @@ impl RustType<ProtoReducePlan> for ReducePlan {
             Hierarchical(plan) => ReducePlan::Hierarchical(plan.into_rust()?),
             Basic(plan) => ReducePlan::Basic(plan.into_rust()?),
             Collation(plan) => ReducePlan::Collation(plan.into_rust()?),
+            Sketch(plan) => ReducePlan::Sketch(plan.into_rust()?),
+            Window(plan) => ReducePlan::Window(plan.into_rust()?),
         })
     }
 }
@@ -569,6 +603,170 @@ impl RustType<ProtoBasicPlan> for BasicPlan {
     }
 }
 
+/// Plan for computing a set of sketch aggregations.
+///
+/// Each aggregation maps its inputs to sketch coordinates, and the
+/// coordinates of all aggregations are arranged together by key. As each
+/// aggregation has a bounded number of coordinates, so does every key,
+/// no matter how many records it has. A single reduce operator then
+/// estimates all of the aggregations from the multiplicities of their
+/// coordinates.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SketchPlan {
+    /// The aggregations to compute, stored in order. Each element represents:
+    /// `(index of the datum among inputs, aggregation expr)`
+    pub aggrs: Vec<(usize, AggregateExpr)>,
+}
+
+impl RustType<ProtoSketchPlan> for SketchPlan {
+    fn into_proto(&self) -> ProtoSketchPlan {
+        ProtoSketchPlan {
+            aggrs: self.aggrs.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSketchPlan) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            aggrs: proto.aggrs.into_rust()?,
+        })
+    }
+}
+
+/// Plan for incrementally computing a ranking window function, `lag`, or `lead`.
+///
+/// This plan replaces a basic aggregation with `fused_unnest_list` for the ranking window
+/// functions `row_number`, `rank`, and `dense_rank`, and for `lag` and `lead` with an offset of 1
+/// that respect nulls, if their ORDER BY consists of a single column of a type whose values can be
+/// mapped to integers in an order-preserving way (see [`window_order_type_supported`]). Such a
+/// mapping assigns each row a position within its window partition, and the result for a row is
+/// determined by the number of rows (or, for `dense_rank`, distinct positions) at smaller
+/// positions, and, for `row_number`, by the order of the rows at the same position.
+///
+/// To maintain these counts, each row contributes to one bucket of positions at each level of a
+/// binary tree: the buckets at level `l` cover `2^l` consecutive positions. The number of rows
+/// at positions smaller than `p` is the sum of the counts of at most one bucket per level, namely,
+/// the bucket at level `l` just before the one containing `p`, for each `l` where bit `l` of `p`
+/// is set. The counts of the buckets and the lookups are maintained by arrangements keyed by the
+/// window partition and the bucket, so that a change to a row updates one bucket per level, and
+/// only the results of the rows whose counts change need to be recomputed.
+///
+/// The result of `lag` for a row is determined by the previous row at the same position, or, for
+/// the first row at a position, by the last row at the preceding position. To find the preceding
+/// positions, each node of the binary tree records the smallest and the largest position it
+/// contains, and a node whose children both contain positions links the smallest position of
+/// its right child to the largest position of its left child. A change to a row thus only
+/// updates the nodes on the path from its position to the root, and only the results of the rows
+/// whose preceding row changes need to be recomputed. `lead` is computed as `lag` over the
+/// reverse order.
+///
+/// Other window functions, notably the value window functions `first_value` and `last_value`,
+/// `lag` and `lead` with other offsets or `IGNORE NULLS`, as well as window aggregations, are not
+/// covered by this plan. Like ranking window functions whose ORDER BY is not supported, they are
+/// rendered as basic aggregations that recompute the results for the entire window partition
+/// whenever it changes.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+pub struct WindowPlan {
+    /// The index in the set of inputs that we are aggregating over.
+    pub index: usize,
+    /// The window function that we should compute.
+    pub expr: AggregateExpr,
+}
+
+impl WindowPlan {
+    /// Returns a plan for the given aggregations of a reduction whose result list is unnested,
+    /// if they consist of a single window function that we can compute incrementally.
+    /// `input_type` are the column types of the input of the reduction.
+    pub fn try_new(aggregates: &[AggregateExpr], input_type: &[ColumnType]) -> Option<Self> {
+        let [aggr] = aggregates else {
+            return None;
+        };
+        let order_by = match &aggr.func {
+            AggregateFunc::RowNumber { order_by }
+            | AggregateFunc::Rank { order_by }
+            | AggregateFunc::DenseRank { order_by } => order_by,
+            AggregateFunc::LagLead {
+                order_by,
+                lag_lead: _,
+                ignore_nulls: false,
+            } if lag_lead_offset_is_one(&aggr.expr) => order_by,
+            _ => return None,
+        };
+        let [order] = &order_by[..] else {
+            return None;
+        };
+        if aggr.distinct {
+            return None;
+        }
+        // The input of a window function is a record of its payload, i.e., the original rows and
+        // the arguments, followed by the ORDER BY expressions.
+        match aggr.expr.typ(input_type).scalar_type {
+            ScalarType::Record { fields, .. } => fields
+                .get(order.column + 1)
+                .filter(|(_, typ)| window_order_type_supported(&typ.scalar_type))
+                .map(|_| WindowPlan {
+                    index: 0,
+                    expr: aggr.clone(),
+                }),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the input `expr` of `lag` or `lead` passes a literal offset of 1.
+///
+/// The input is a record of the original row and a record of the arguments, i.e., the value, the
+/// offset, and the default, followed by the ORDER BY expressions.
+fn lag_lead_offset_is_one(expr: &MirScalarExpr) -> bool {
+    fn record_fields(expr: &MirScalarExpr) -> Option<&[MirScalarExpr]> {
+        match expr {
+            MirScalarExpr::CallVariadic {
+                func: VariadicFunc::RecordCreate { .. },
+                exprs,
+            } => Some(exprs),
+            _ => None,
+        }
+    }
+    let offset = record_fields(expr)
+        .and_then(|fields| record_fields(fields.first()?))
+        .and_then(|payload| record_fields(payload.get(1)?))
+        .and_then(|args| args.get(1)?.as_literal());
+    offset == Some(Ok(Datum::Int32(1)))
+}
+
+/// Whether a `WindowPlan` supports ORDER BY columns of the given type. The values of supported
+/// types can be mapped to 64-bit integers such that their order is preserved.
+pub fn window_order_type_supported(typ: &ScalarType) -> bool {
+    matches!(
+        typ,
+        ScalarType::Int16
+            | ScalarType::Int32
+            | ScalarType::Int64
+            | ScalarType::UInt16
+            | ScalarType::UInt32
+            | ScalarType::UInt64
+            | ScalarType::MzTimestamp
+            | ScalarType::Date
+            | ScalarType::Timestamp { .. }
+            | ScalarType::TimestampTz { .. }
+    )
+}
+
+impl RustType<ProtoWindowPlan> for WindowPlan {
+    fn into_proto(&self) -> ProtoWindowPlan {
+        ProtoWindowPlan {
+            index: self.index.into_proto(),
+            expr: Some(self.expr.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoWindowPlan) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            index: proto.index.into_rust()?,
+            expr: proto.expr.into_rust_if_some("ProtoWindowPlan::expr")?,
+        })
+    }
+}
+
 /// Plan for collating the results of computing multiple aggregation
 /// types.
 ///
@@ -581,6 +779,8 @@ pub struct CollationPlan {
     pub hierarchical: Option<HierarchicalPlan>,
     /// Basic aggregation results to collate, if any.
     pub basic: Option<BasicPlan>,
+    /// Sketch aggregation results to collate, if any.
+    pub sketch: Option<SketchPlan>,
     /// When we get results back from each of the different
     /// aggregation types, they will be subsequences of
     /// the sequence aggregations in the original reduce expression.
@@ -605,6 +805,7 @@ impl RustType<ProtoCollationPlan> for CollationPlan {
             accumulable: self.accumulable.into_proto(),
             hierarchical: self.hierarchical.into_proto(),
             basic: self.basic.into_proto(),
+            sketch: self.sketch.into_proto(),
             aggregate_types: self.aggregate_types.into_proto(),
         }
     }
@@ -614,6 +815,7 @@ impl RustType<ProtoCollationPlan> for CollationPlan {
             accumulable: proto.accumulable.into_rust()?,
             hierarchical: proto.hierarchical.into_rust()?,
             basic: proto.basic.into_rust()?,
+            sketch: proto.sketch.into_rust()?,
             aggregate_types: proto.aggregate_types.into_rust()?,
         })
     }
@@ -636,7 +838,7 @@ impl ReducePlan {
         }
 
         // Otherwise, we need to group aggregations according to their
-        // reduction type (accumulable, hierarchical, or basic)
+        // reduction type (accumulable, hierarchical, basic, or sketch)
         let mut reduction_types = BTreeMap::new();
         // We need to make sure that each list of aggregates by type forms
         // a subsequence of the overall sequence of aggregates.
@@ -670,7 +872,7 @@ impl ReducePlan {
 
         // First, lets sanity check that we don't have an impossible number
         // of reduction types.
-        assert!(plan.len() <= 3);
+        assert!(plan.len() <= 4);
 
         let mut collation: CollationPlan = Default::default();
 
@@ -697,7 +899,11 @@ impl ReducePlan {
                     assert_none!(collation.basic);
                     collation.basic = Some(e);
                 }
-                ReducePlan::Distinct | ReducePlan::Collation(_) => {
+                ReducePlan::Sketch(e) => {
+                    assert_none!(collation.sketch);
+                    collation.sketch = Some(e);
+                }
+                ReducePlan::Distinct | ReducePlan::Window(_) | ReducePlan::Collation(_) => {
                     panic!("Inner reduce plan was unsupported type!")
                 }
             }
@@ -797,6 +1003,9 @@ impl ReducePlan {
                     ReducePlan::Basic(BasicPlan::Multiple(aggregates_list))
                 }
             }
+            ReductionType::Sketch => ReducePlan::Sketch(SketchPlan {
+                aggrs: aggregates_list,
+            }),
         }
     }
 
@@ -989,6 +1198,9 @@ pub fn convert_indexes_to_skips(mut indexes: Vec<usize>) -> Vec<usize> {
 /// significant input data). Hierarchical aggregates can be rendered more efficiently if the
 /// input stream is append-only as then we only need to retain the "currently winning" value.
 /// Every hierarchical aggregate needs to supply a corresponding ReductionMonoid implementation.
+///
+/// Sketch aggregations will be estimated from the multiplicities of the sketch coordinates
+/// of their inputs, which are bounded in number for each key.
 pub fn reduction_type(func: &AggregateFunc) -> ReductionType {
     match func {
         AggregateFunc::SumInt16
@@ -1047,12 +1259,22 @@ pub fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::RowNumber { .. }
         | AggregateFunc::Rank { .. }
         | AggregateFunc::DenseRank { .. }
+        | AggregateFunc::PercentRank { .. }
+        | AggregateFunc::CumeDist { .. }
+        | AggregateFunc::Ntile { .. }
         | AggregateFunc::LagLead { .. }
         | AggregateFunc::FirstValue { .. }
         | AggregateFunc::LastValue { .. }
+        | AggregateFunc::NthValue { .. }
         | AggregateFunc::WindowAggregate { .. }
         | AggregateFunc::FusedValueWindowFunc { .. }
-        | AggregateFunc::FusedWindowAggregate { .. } => ReductionType::Basic,
+        | AggregateFunc::FusedWindowAggregate { .. }
+        | AggregateFunc::PercentileCont { .. }
+        | AggregateFunc::PercentileDisc { .. }
+        | AggregateFunc::Mode { .. } => ReductionType::Basic,
+        AggregateFunc::ApproxCountDistinct | AggregateFunc::ApproxPercentile { .. } => {
+            ReductionType::Sketch
+        }
     }
 }
 
@@ -924,6 +924,8 @@ impl<'a, T> std::fmt::Display for RenderPlanExprHumanizer<'a, T> {
                         write!(f, ")")
                     }
                     ReducePlan::Basic(..) => write!(f, "Basic"),
+                    ReducePlan::Sketch(..) => write!(f, "Sketch"),
+                    ReducePlan::Window(..) => write!(f, "Window"),
                     ReducePlan::Collation(..) => write!(f, "Collation"),
                 }
             }
@@ -27,11 +27,13 @@ use differential_dataflow::IntoOwned;
 use differential_dataflow::{Collection, Diff as _};
 use mz_compute_types::plan::reduce::{
     reduction_type, AccumulablePlan, BasicPlan, BucketedPlan, HierarchicalPlan, KeyValPlan,
-    MonotonicPlan, ReducePlan, ReductionType, SingleBasicPlan,
+    MonotonicPlan, ReducePlan, ReductionType, SingleBasicPlan, SketchPlan, WindowPlan,
 };
 use mz_expr::{
-    AggregateExpr, AggregateFunc, EvalError, MapFilterProject, MirScalarExpr, SafeMfpPlan,
+    AggregateExpr, AggregateFunc, ColumnOrder, EvalError, LagLeadType, MapFilterProject,
+    MirScalarExpr, SafeMfpPlan,
 };
+use mz_ore::cast::{CastFrom, ReinterpretCast};
 use mz_repr::adt::numeric::{self, Numeric, NumericAgg};
 use mz_repr::fixed_length::ToDatumIter;
 use mz_repr::{Datum, DatumList, DatumVec, Diff, Row, RowArena, SharedRow};
@@ -265,6 +267,17 @@ where
                 errors.push(errs);
                 output
             }
+            ReducePlan::Sketch(SketchPlan { aggrs }) => {
+                let (output, errs) =
+                    self.build_sketch_aggregates(collection, aggrs, key_arity, mfp_after);
+                errors.push(errs);
+                output
+            }
+            ReducePlan::Window(plan) => {
+                let (output, errs) = self.build_window(collection, plan, key_arity, mfp_after);
+                errors.extend(errs);
+                output
+            }
             // Otherwise, we need to render something different for each type of
             // reduction, and then stitch them together.
             ReducePlan::Collation(expr) => {
@@ -275,6 +288,7 @@ where
                     expr.hierarchical.map(ReducePlan::Hierarchical),
                     expr.accumulable.map(ReducePlan::Accumulable),
                     expr.basic.map(ReducePlan::Basic),
+                    expr.sketch.map(ReducePlan::Sketch),
                 ]
                 .into_iter()
                 .flat_map(std::convert::identity)
@@ -376,8 +390,9 @@ where
                         let mut accumulable = DatumList::empty().iter();
                         let mut hierarchical = DatumList::empty().iter();
                         let mut basic = DatumList::empty().iter();
+                        let mut sketch = DatumList::empty().iter();
 
-                        // Note that hierarchical and basic reductions guard against negative
+                        // Note that hierarchical, basic and sketch reductions guard against negative
                         // multiplicities, and if we only had accumulable aggregations, we would not
                         // have produced a collation plan, so we do not repeat the check here.
                         if input.len() != n_distinct_aggregate_types {
@@ -391,6 +406,7 @@ where
                                 ReductionType::Accumulable => accumulable = row.iter(),
                                 ReductionType::Hierarchical => hierarchical = row.iter(),
                                 ReductionType::Basic => basic = row.iter(),
+                                ReductionType::Sketch => sketch = row.iter(),
                             }
                         }
 
@@ -406,6 +422,7 @@ where
                                 ReductionType::Accumulable => accumulable.next(),
                                 ReductionType::Hierarchical => hierarchical.next(),
                                 ReductionType::Basic => basic.next(),
+                                ReductionType::Sketch => sketch.next(),
                             };
                             let Some(datum) = datum else { return };
                             datums_local.push(datum);
@@ -418,8 +435,12 @@ where
                         // Note that we also do not want to have anything left over to stich. If we
                         // do, then we also have an error, reported elsewhere, and would violate
                         // join semantics.
-                        if (accumulable.next(), hierarchical.next(), basic.next())
-                            == (None, None, None)
+                        if (
+                            accumulable.next(),
+                            hierarchical.next(),
+                            basic.next(),
+                            sketch.next(),
+                        ) == (None, None, None, None)
                         {
                             if let Some(row) = evaluate_mfp_after(
                                 &mfp_after1,
@@ -453,6 +474,7 @@ where
                     let mut accumulable = DatumList::empty().iter();
                     let mut hierarchical = DatumList::empty().iter();
                     let mut basic = DatumList::empty().iter();
+                    let mut sketch = DatumList::empty().iter();
                     for (item, _) in input.iter() {
                         let reduction_type = &item.0;
                         let row = &item.1;
@@ -460,6 +482,7 @@ where
                             ReductionType::Accumulable => accumulable = row.iter(),
                             ReductionType::Hierarchical => hierarchical = row.iter(),
                             ReductionType::Basic => basic = row.iter(),
+                            ReductionType::Sketch => sketch = row.iter(),
                         }
                     }
 
@@ -473,6 +496,7 @@ where
                             ReductionType::Accumulable => accumulable.next(),
                             ReductionType::Hierarchical => hierarchical.next(),
                             ReductionType::Basic => basic.next(),
+                            ReductionType::Sketch => sketch.next(),
                         };
                         if let Some(datum) = datum {
                             datums_local.push(datum);
@@ -488,7 +512,12 @@ where
 
                     // Note that we also do not want to have anything left over to stich.
                     // If we do, then we also have an error and would violate join semantics.
-                    if (accumulable.next(), hierarchical.next(), basic.next()) != (None, None, None)
+                    if (
+                        accumulable.next(),
+                        hierarchical.next(),
+                        basic.next(),
+                        sketch.next(),
+                    ) != (None, None, None, None)
                     {
                         let message = "Rows too large for key in ReduceCollation";
                         error_logger.log(message, &format!("key={key:?}"));
@@ -750,6 +779,20 @@ where
             }
         }
 
+        // Ordered-set aggregations maintain the values of each group in a tree, rather than
+        // reducing all values of a group whenever it changes.
+        if func.is_ordered_set() {
+            assert!(!fused_unnest_list);
+            return self.build_ordered_set_aggregate(
+                partial,
+                func,
+                validating && err_output.is_none(),
+                err_output,
+                key_arity,
+                mfp_after,
+            );
+        }
+
         // Allocations for the two closures.
         let mut datums1 = DatumVec::new();
         let mut datums2 = DatumVec::new();
@@ -944,6 +987,712 @@ where
         (oks, err_output)
     }
 
+    /// Build the dataflow to compute an ordered-set aggregation, i.e., `percentile_cont`,
+    /// `percentile_disc` or `mode`, on the values of `input`.
+    ///
+    /// We maintain a tree over the non-null values of each group, which are ordered by their
+    /// keys (see [`ordered_set_key`]). The nodes at depth `d` cover the values whose keys share
+    /// their `4 * d` most significant bits, and the leaves hold the values with equal keys. A
+    /// change to a value only changes the nodes on the path from its leaf to the root, so the work
+    /// per update is proportional to the depth of the tree rather than to the number of distinct
+    /// values in the group, unless many values share a key.
+    ///
+    /// Percentiles are computed from the root down: each node counts the values under each of its
+    /// children, the root determines the ranks of the values that the percentile depends on, and
+    /// each node forwards the ranks to the children that contain them. The mode is computed from
+    /// the leaves up: each node selects the most frequent of the values its children selected.
+    fn build_ordered_set_aggregate<S>(
+        &self,
+        input: Collection<S, (Row, Row), Diff>,
+        func: AggregateFunc,
+        must_validate: bool,
+        err_output: Option<Collection<S, DataflowError, Diff>>,
+        key_arity: usize,
+        mfp_after: Option<SafeMfpPlan>,
+    ) -> (
+        RowRowArrangement<S>,
+        Option<Collection<S, DataflowError, Diff>>,
+    )
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // Key the non-null values by their leaves.
+        let values = input.flat_map(move |(key, value)| {
+            let datum = value.unpack_first();
+            if datum.is_null() {
+                return None;
+            }
+            let leaf = ordered_set_node(&key, ordered_set_key(datum), ORDERED_SET_DEPTH);
+            Some((leaf, value))
+        });
+
+        let (leaves, results) = match func {
+            AggregateFunc::Mode { desc } => {
+                let leaves = values
+                    .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                        "Arranged OrderedSet Leaves",
+                    );
+                let mut nodes = leaves
+                    .mz_reduce_abelian::<_, _, _, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                        "OrderedSet Leaves",
+                        move |_leaf, source, target| {
+                            if let Some((count, value)) = ordered_set_mode(desc, true, source) {
+                                target.push((SharedRow::pack([Datum::Int64(count), value]), 1));
+                            }
+                        },
+                    )
+                    .as_collection(move |leaf, mode| {
+                        let parent = ordered_set_parent(leaf, key_arity, ORDERED_SET_DEPTH);
+                        (parent, mode.into_owned())
+                    });
+                for depth in (0..ORDERED_SET_DEPTH).rev() {
+                    nodes = nodes
+                        .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                            "Arranged OrderedSet Nodes",
+                        )
+                        .mz_reduce_abelian::<_, _, _, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                            "OrderedSet Nodes",
+                            move |_node, source, target| {
+                                if let Some((count, value)) = ordered_set_mode(desc, false, source)
+                                {
+                                    let mode = [Datum::Int64(count), value];
+                                    target.push((SharedRow::pack(mode), 1));
+                                }
+                            },
+                        )
+                        .as_collection(move |node, mode| {
+                            (
+                                ordered_set_parent(node, key_arity, depth),
+                                mode.into_owned(),
+                            )
+                        });
+                }
+                (leaves, nodes)
+            }
+            _ => {
+                // A query for a value that the percentile depends on holds the index of the value
+                // among those values, its rank among the values under the node the query arrives
+                // at, and the total number of values. Each node forwards the queries to the
+                // children that contain the ranks, with the ranks among the values under them.
+                let mut queries: Option<Collection<S, (Row, Row), Diff>> = None;
+                for depth in 0..ORDERED_SET_DEPTH {
+                    let counts = input.flat_map(move |(key, value)| {
+                        let datum = value.unpack_first();
+                        if datum.is_null() {
+                            return None;
+                        }
+                        let ordered = ordered_set_key(datum);
+                        let node = ordered_set_node(&key, ordered, depth);
+                        let digit = ordered_set_digit(ordered, depth);
+                        Some((node, SharedRow::pack([Datum::UInt8(digit)])))
+                    });
+                    let nodes = match queries {
+                        Some(queries) => counts.concat(&queries),
+                        None => counts,
+                    };
+                    // The root creates the queries from the total number of values.
+                    let root = (depth == 0).then(|| func.clone());
+                    let forwarded = nodes
+                        .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                            "Arranged OrderedSet Nodes",
+                        )
+                        .mz_reduce_abelian::<_, _, _, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                            "OrderedSet Nodes",
+                            move |_node, source, target| {
+                                for query in forward_ordered_set_queries(root.as_ref(), source) {
+                                    target.push((query, 1));
+                                }
+                            },
+                        )
+                        .as_collection(move |node, query| {
+                            // The queries end with the digits of the children they are for.
+                            let query: Vec<_> = query.to_datum_iter().collect();
+                            let child = ordered_set_child(node, key_arity, query[3]);
+                            (child, SharedRow::pack(&query[..3]))
+                        });
+                    queries = Some(forwarded);
+                }
+
+                // The leaves answer the queries with the values at the ranks.
+                let leaves = values
+                    .concat(&queries.expect("the tree has at least one level of nodes"))
+                    .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                        "Arranged OrderedSet Leaves",
+                    );
+                let results = leaves
+                    .mz_reduce_abelian::<_, _, _, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                        "OrderedSet Leaves",
+                        |_leaf, source, target| {
+                            for result in answer_ordered_set_queries(source) {
+                                target.push((result, 1));
+                            }
+                        },
+                    )
+                    .as_collection(move |leaf, result| {
+                        let key = SharedRow::pack(leaf.to_datum_iter().take(key_arity));
+                        (key, result.into_owned())
+                    });
+                (leaves, results)
+            }
+        };
+
+        // Every group has a result, even if all its values are null, so we also count the
+        // records of each group.
+        let groups = input.map(|(key, _value)| (key, Row::default()));
+        let arranged = groups
+            .concat(&results)
+            .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                "Arranged OrderedSet",
+            );
+
+        let mut datums1 = DatumVec::new();
+        let mut datums2 = DatumVec::new();
+        let mfp_after1 = mfp_after.clone();
+        let mfp_after2 = mfp_after.filter(|mfp| mfp.could_error());
+        let func2 = func.clone();
+
+        let oks = arranged.mz_reduce_abelian::<_, _, _, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+            "OrderedSet",
+            move |key, source, target| {
+                let temp_storage = RowArena::new();
+                let mut datums_local = datums1.borrow();
+                datums_local.extend(key.to_datum_iter());
+                let key_len = datums_local.len();
+                datums_local.push(ordered_set_result(&func, source));
+                if let Some(row) =
+                    evaluate_mfp_after(&mfp_after1, &mut datums_local, &temp_storage, key_len)
+                {
+                    target.push((row, 1));
+                }
+            },
+        );
+
+        let mut errs = err_output.into_iter().collect::<Vec<_>>();
+        if must_validate {
+            let error_logger = self.error_logger();
+            let errs_leaves = leaves
+                .mz_reduce_abelian::<_, _, _, RowErrBuilder<_, _>, RowErrSpine<_, _>>(
+                    "OrderedSet Error Check",
+                    move |_leaf, source, target| {
+                        for (value, count) in source.iter() {
+                            if count.is_positive() {
+                                continue;
+                            }
+                            let value = value.into_owned();
+                            let message = "Non-positive accumulation in ReduceInaccumulable";
+                            error_logger.log(message, &format!("value={value:?}, count={count}"));
+                            target.push((EvalError::Internal(message.into()).into(), 1));
+                            return;
+                        }
+                    },
+                )
+                .as_collection(|_, v| v.into_owned());
+            errs.push(errs_leaves);
+        }
+        if must_validate || mfp_after2.is_some() {
+            let error_logger = self.error_logger();
+            let errs_groups = arranged
+                .mz_reduce_abelian::<_, _, _, RowErrBuilder<_, _>, RowErrSpine<_, _>>(
+                    "OrderedSet Group Error Check",
+                    move |key, source, target| {
+                        // The leaves only hold the non-null values, so we also check the number of
+                        // records of the group.
+                        if must_validate {
+                            for (value, count) in source.iter() {
+                                if value.to_datum_iter().next().is_some() || count.is_positive() {
+                                    continue;
+                                }
+                                let key = key.into_owned();
+                                let message = "Non-positive accumulation in ReduceInaccumulable";
+                                error_logger.log(message, &format!("key={key:?}, count={count}"));
+                                target.push((EvalError::Internal(message.into()).into(), 1));
+                                return;
+                            }
+                        }
+
+                        let Some(mfp) = &mfp_after2 else { return };
+                        let temp_storage = RowArena::new();
+                        let mut datums_local = datums2.borrow();
+                        datums_local.extend(key.to_datum_iter());
+                        datums_local.push(ordered_set_result(&func2, source));
+                        if let Err(e) = mfp.evaluate_inner(&mut datums_local, &temp_storage) {
+                            target.push((e.into(), 1));
+                        }
+                    },
+                )
+                .as_collection(|_, v| v.into_owned());
+            errs.push(errs_groups);
+        }
+        let err_output = (!errs.is_empty())
+            .then(|| differential_dataflow::collection::concatenate(&mut input.scope(), errs));
+        (oks, err_output)
+    }
+
+    /// Build the dataflow to compute and arrange multiple sketch aggregations.
+    ///
+    /// Each aggregation maps its input values to sketch coordinates, which we tag with the
+    /// position of the aggregation in `aggrs` and arrange together with the coordinates of the
+    /// other aggregations. Every aggregation has a bounded number of distinct coordinates, and
+    /// so the arrangement holds a bounded number of values for each key, no matter how many
+    /// records the key has. A single reduce operator then estimates each aggregation from the
+    /// multiplicities of its coordinates.
+    fn build_sketch_aggregates<S>(
+        &self,
+        input: Collection<S, (Row, Row), Diff>,
+        aggrs: Vec<(usize, AggregateExpr)>,
+        key_arity: usize,
+        mfp_after: Option<SafeMfpPlan>,
+    ) -> (RowRowArrangement<S>, Collection<S, DataflowError, Diff>)
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        let mut err_output = Vec::new();
+        let mut to_concat = Vec::new();
+        for (position, (index, aggr)) in aggrs.iter().enumerate() {
+            let index = *index;
+            // Extract the value we were asked to aggregate over.
+            let mut partial = input.map(move |(key, row)| {
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                let value = row.iter().nth(index).unwrap();
+                row_builder.packer().push(value);
+                (key, row_builder.clone())
+            });
+
+            // If `distinct` is set, we restrict ourselves to the distinct `(key, val)`. Duplicate
+            // values do not change the estimated number of distinct values, so there is no need
+            // to do so for `approx_count_distinct`.
+            if aggr.distinct && aggr.func != AggregateFunc::ApproxCountDistinct {
+                // We map `(Row, Row)` to `Row` to take advantage of `Row*Spine` types.
+                let pairer = Pairer::new(key_arity);
+                let keyed = partial.map(move |(key, val)| pairer.merge(&key, &val));
+                let (oks, errs) = self
+                    .build_reduce_inaccumulable_distinct::<_, _, RowValBuilder<Result<(), String>, _,_>, RowValSpine<Result<(), String>, _, _>>(keyed, None)
+                    .as_collection(|k, v| (k.into_owned(), v.as_ref().map(|&()| ()).map_err(|m| m.as_str().into())))
+                    .map_fallible::<CapacityContainerBuilder<_>, CapacityContainerBuilder<_>, _, _, _>("Demux Errors", move |(key_val, result)| match result {
+                        Ok(()) => Ok(pairer.split(&key_val)),
+                        Err(m) => Err(EvalError::Internal(m).into()),
+                    });
+                err_output.push(errs);
+                partial = oks;
+            }
+
+            let func = aggr.func.clone();
+            let position = u64::cast_from(position);
+            to_concat.push(partial.map(move |(key, val)| {
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                let coordinate = func.sketch_coordinate(val.unpack_first());
+                row_builder
+                    .packer()
+                    .extend([Datum::UInt64(position), coordinate]);
+                (key, row_builder.clone())
+            }));
+        }
+
+        let arranged =
+            differential_dataflow::collection::concatenate(&mut input.scope(), to_concat)
+                .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                "Arranged ReduceSketch",
+            );
+
+        // Allocations for the two closures.
+        let mut datums1 = DatumVec::new();
+        let mut datums2 = DatumVec::new();
+        let mfp_after1 = mfp_after.clone();
+        let mfp_after2 = mfp_after.filter(|mfp| mfp.could_error());
+        let funcs: Vec<_> = aggrs.into_iter().map(|(_, aggr)| aggr.func).collect();
+        let funcs2 = funcs.clone();
+
+        let oks = arranged.mz_reduce_abelian::<_, _, _, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+            "ReduceSketch",
+            move |key, source, target| {
+                let temp_storage = RowArena::new();
+                let datum_iter = key.to_datum_iter();
+                let mut datums_local = datums1.borrow();
+                datums_local.extend(datum_iter);
+                let key_len = datums_local.len();
+
+                for (position, func) in funcs.iter().enumerate() {
+                    let position = u64::cast_from(position);
+                    let coordinates = source.iter().filter_map(|(v, w)| {
+                        let mut datums = v.to_datum_iter();
+                        if datums.next().unwrap().unwrap_uint64() != position {
+                            return None;
+                        }
+                        // Note that in the non-positive case, this is wrong, but harmless
+                        // because our other reduction will produce an error.
+                        let count = usize::try_from(*w).unwrap_or(0);
+                        Some((datums.next().unwrap(), count))
+                    });
+                    datums_local.push(func.eval_sketch(coordinates));
+                }
+
+                if let Some(row) =
+                    evaluate_mfp_after(&mfp_after1, &mut datums_local, &temp_storage, key_len)
+                {
+                    target.push((row, 1));
+                }
+            },
+        );
+
+        let error_logger = self.error_logger();
+        let errs = arranged
+            .mz_reduce_abelian::<_, _, _, RowErrBuilder<_, _>, RowErrSpine<_, _>>(
+                "ReduceSketch Error Check",
+                move |key, source, target| {
+                    // Negative counts would be surprising, but until we are 100% certain we won't
+                    // see them, we should report when we do.
+                    for (value, count) in source.iter() {
+                        if count.is_positive() {
+                            continue;
+                        }
+                        let value = value.into_owned();
+                        let message = "Non-positive accumulation in ReduceSketch";
+                        error_logger.log(message, &format!("value={value:?}, count={count}"));
+                        target.push((EvalError::Internal(message.into()).into(), 1));
+                        return;
+                    }
+
+                    // If `mfp_after` can error, then try to evaluate it here.
+                    let Some(mfp) = &mfp_after2 else { return };
+                    let temp_storage = RowArena::new();
+                    let datum_iter = key.to_datum_iter();
+                    let mut datums_local = datums2.borrow();
+                    datums_local.extend(datum_iter);
+
+                    for (position, func) in funcs2.iter().enumerate() {
+                        let position = u64::cast_from(position);
+                        let coordinates = source.iter().filter_map(|(v, w)| {
+                            let mut datums = v.to_datum_iter();
+                            if datums.next().unwrap().unwrap_uint64() != position {
+                                return None;
+                            }
+                            let count = usize::try_from(*w).unwrap_or(0);
+                            Some((datums.next().unwrap(), count))
+                        });
+                        datums_local.push(func.eval_sketch(coordinates));
+                    }
+
+                    if let Err(e) = mfp.evaluate_inner(&mut datums_local, &temp_storage) {
+                        target.push((e.into(), 1));
+                    }
+                },
+            )
+            .as_collection(|_, v| v.into_owned());
+        err_output.push(errs);
+
+        let errs = differential_dataflow::collection::concatenate(&mut input.scope(), err_output);
+        (oks, errs)
+    }
+
+    /// Build the dataflow to incrementally compute a window function planned as a [`WindowPlan`].
+    ///
+    /// Each row is keyed by its window partition and its position within the partition (see
+    /// [`window_position`]). For every position, we maintain markers that summarize the rows at
+    /// the other positions of the same partition that the results for the rows at the position
+    /// depend on. A final reduction computes these results from the rows at the position and
+    /// its markers (see [`window_results`]), so that a change to a row only updates the results
+    /// of the rows whose markers change, rather than recomputing the results for the entire
+    /// partition.
+    fn build_window<S>(
+        &self,
+        input: Collection<S, (Row, Row), Diff>,
+        plan: WindowPlan,
+        key_arity: usize,
+        mfp_after: Option<SafeMfpPlan>,
+    ) -> (
+        RowRowArrangement<S>,
+        Option<Collection<S, DataflowError, Diff>>,
+    )
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        let WindowPlan { index, expr } = plan;
+        let func = expr.func;
+        let order = window_order(&func);
+
+        // Key each row by its window partition and position. The value we aggregate over is a
+        // record of the payload, i.e., the original rows and any arguments, followed by the
+        // ORDER BY values.
+        let records = input.map(move |(key, row)| {
+            let binding = SharedRow::get();
+            let mut row_builder = binding.borrow_mut();
+            let value = row.iter().nth(index).unwrap();
+            let mut fields = value.unwrap_list().iter();
+            let payload = fields.next().unwrap();
+            let position = window_position(fields.nth(order.column).unwrap(), &order);
+            let key_position =
+                row_builder.pack_using(key.iter().chain(window_position_datums(position)));
+            let payload = row_builder.pack_using([payload]);
+            (key_position, payload)
+        });
+
+        let positions: KeyCollection<_, _, _> =
+            records.map(|(key_position, _payload)| key_position).into();
+        let positions = positions
+            .mz_arrange::<RowBatcher<_, _>, RowBuilder<_, _>, RowSpine<_, _>>(
+                "Arranged Window Positions",
+            )
+            .mz_reduce_abelian::<_, _, _, RowBuilder<_, _>, RowSpine<_, _>>(
+                "Window Positions",
+                |_key_position, _source, target| target.push(((), 1)),
+            )
+            .as_collection(|key_position, _| key_position.into_owned());
+
+        let markers = match &func {
+            AggregateFunc::LagLead { .. } => {
+                self.build_window_lag_lead_markers(&records, &positions, &func, key_arity)
+            }
+            _ => self.build_window_rank_markers(&records, &positions, &func, key_arity),
+        };
+
+        let arranged = records
+            .concat(&markers)
+            .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                "Arranged Window Rows",
+            );
+
+        // Allocations for the two closures.
+        let mut datums1 = DatumVec::new();
+        let mut datums2 = DatumVec::new();
+        let mfp_after1 = mfp_after.clone();
+        let mfp_after2 = mfp_after.filter(|mfp| mfp.could_error());
+        let func2 = func.clone();
+
+        let oks = arranged
+            .mz_reduce_abelian::<_, _, _, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                "Window",
+                move |key_position, source, target| {
+                    let temp_storage = RowArena::new();
+                    let mut datums_local = datums1.borrow();
+                    datums_local.extend(key_position.to_datum_iter().take(key_arity));
+                    for datum in window_results(&func, source, &temp_storage) {
+                        datums_local.truncate(key_arity);
+                        datums_local.push(datum);
+                        if let Some(row) = evaluate_mfp_after(
+                            &mfp_after1,
+                            &mut datums_local,
+                            &temp_storage,
+                            key_arity,
+                        ) {
+                            target.push((row, 1));
+                        }
+                    }
+                },
+            )
+            // Re-key the results by their window partition only.
+            .as_collection(move |key_position, row| {
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                let key = row_builder.pack_using(key_position.to_datum_iter().take(key_arity));
+                (key, row.into_owned())
+            })
+            .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                "Arranged Window",
+            );
+
+        // As for other window functions, we do not validate the multiplicities of the input, but
+        // report errors of `mfp_after`, if it can error.
+        let errs = mfp_after2.map(|mfp| {
+            arranged
+                .mz_reduce_abelian::<_, _, _, RowErrBuilder<_, _>, RowErrSpine<_, _>>(
+                    "Window Error Check",
+                    move |key_position, source, target| {
+                        let temp_storage = RowArena::new();
+                        let mut datums_local = datums2.borrow();
+                        datums_local.extend(key_position.to_datum_iter().take(key_arity));
+                        for datum in window_results(&func2, source, &temp_storage) {
+                            datums_local.truncate(key_arity);
+                            datums_local.push(datum);
+                            if let Err(e) = mfp.evaluate_inner(&mut datums_local, &temp_storage) {
+                                target.push((e.into(), 1));
+                            }
+                        }
+                    },
+                )
+                .as_collection(|_, v| v.into_owned())
+        });
+        (oks, errs)
+    }
+
+    /// Build the markers of a ranking window function for the rows keyed by their window
+    /// partition and position.
+    ///
+    /// The markers of a position are empty rows whose multiplicities sum to the number of rows
+    /// (or, for `dense_rank`, distinct positions) at smaller positions of the same partition,
+    /// which is the sum of the counts of at most one bucket per level of a binary tree over the
+    /// positions.
+    fn build_window_rank_markers<S>(
+        &self,
+        records: &Collection<S, (Row, Row), Diff>,
+        positions: &Collection<S, Row, Diff>,
+        func: &AggregateFunc,
+        key_arity: usize,
+    ) -> Collection<S, (Row, Row), Diff>
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // Count the rows, or distinct positions, at each position in one bucket per level.
+        let counts = match func {
+            AggregateFunc::DenseRank { .. } => positions.map(|key_position| (key_position, 1)),
+            _ => records.map(|(key_position, payload)| {
+                let rows = payload.unpack_first().unwrap_list().iter().count();
+                (key_position, Diff::try_from(rows).expect("must fit"))
+            }),
+        };
+        let buckets: KeyCollection<_, _, _> = counts
+            .flat_map(move |(key_position, count)| {
+                let position = read_window_position(&key_position, key_arity);
+                (0..WINDOW_POSITION_BITS)
+                    .map(|level| {
+                        let bucket = window_bucket(&key_position, key_arity, level, position);
+                        (bucket, count)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .explode_one(|(bucket, count)| (bucket, count))
+            .into();
+        let buckets = buckets.mz_arrange::<RowBatcher<_, _>, RowBuilder<_, _>, RowSpine<_, _>>(
+            "Arranged Window Buckets",
+        );
+
+        // Look up the buckets that together cover the positions smaller than each position. The
+        // join multiplies the counts of the buckets into markers for the position.
+        let lookups = positions
+            .flat_map(move |key_position| {
+                let position = read_window_position(&key_position, key_arity);
+                (0..WINDOW_POSITION_BITS)
+                    .filter(|level| position & (1 << level) != 0)
+                    .map(|level| {
+                        let preceding = position - (1 << level);
+                        let bucket = window_bucket(&key_position, key_arity, level, preceding);
+                        (bucket, key_position.clone())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                "Arranged Window Lookups",
+            );
+        lookups.join_core(&buckets, |_bucket, key_position, _| {
+            Some((key_position.into_owned(), Row::default()))
+        })
+    }
+
+    /// Build the markers of `lag` or `lead` for the rows keyed by their window partition and
+    /// position.
+    ///
+    /// The marker of a position is a row of two datums, the second of which is the value of the
+    /// last row at the preceding position of the same partition. To find the preceding positions,
+    /// we maintain a binary tree over the positions of each partition, whose nodes at level `l`
+    /// cover `2^l` consecutive positions and record the smallest and largest position they
+    /// contain. Every node whose children both contain positions links the smallest position of
+    /// its right child to the largest position of its left child, which precedes it. Every
+    /// position but the first of its partition is linked by exactly one node, and a change to a
+    /// position only changes the nodes, and thus the links, on its path to the root.
+    fn build_window_lag_lead_markers<S>(
+        &self,
+        records: &Collection<S, (Row, Row), Diff>,
+        positions: &Collection<S, Row, Diff>,
+        func: &AggregateFunc,
+        key_arity: usize,
+    ) -> Collection<S, (Row, Row), Diff>
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // The leaves of the tree are the positions themselves. The values of the nodes start with
+        // a flag that tells links apart from the ranges of positions the nodes cover.
+        let mut nodes = positions.map(move |key_position| {
+            let position = read_window_position(&key_position, key_arity);
+            let node = window_bucket(&key_position, key_arity, 0, position);
+            (node, window_node_value(false, position, position))
+        });
+        let mut links = Vec::new();
+        for level in 1..=WINDOW_POSITION_BITS {
+            let parents = nodes
+                .map(move |(child, value)| {
+                    let (_, first, _) = read_window_node_value(value.iter());
+                    (window_bucket(&child, key_arity, level, first), value)
+                })
+                .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                    "Arranged Window Nodes",
+                )
+                .mz_reduce_abelian::<_, _, _, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                    "Window Nodes",
+                    |_node, source, target| {
+                        // The children cover disjoint ranges of positions, so we can order them by
+                        // their smallest positions.
+                        let mut children: Vec<_> = source
+                            .iter()
+                            .map(|(value, _diff)| {
+                                let (_, first, last) =
+                                    read_window_node_value(value.to_datum_iter());
+                                (first, last)
+                            })
+                            .collect();
+                        children.sort_unstable();
+                        let (first, _) = children[0];
+                        let (_, last) = children[children.len() - 1];
+                        target.push((window_node_value(false, first, last), 1));
+                        if let [(_, preceding), (position, _)] = children[..] {
+                            target.push((window_node_value(true, position, preceding), 1));
+                        }
+                    },
+                )
+                .as_collection(|node, value| (node.into_owned(), value.into_owned()));
+            nodes = parents.filter(|(_node, value)| !read_window_node_value(value.iter()).0);
+            links.push(parents.filter(|(_node, value)| read_window_node_value(value.iter()).0));
+        }
+
+        // Key the links by the window partition and the preceding position.
+        let links = differential_dataflow::collection::concatenate(&mut positions.scope(), links)
+            .map(move |(node, value)| {
+                let (_, position, preceding) = read_window_node_value(value.iter());
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                let mut key_position = |position| {
+                    row_builder.pack_using(
+                        node.iter()
+                            .take(key_arity)
+                            .chain(window_position_datums(position)),
+                    )
+                };
+                (key_position(preceding), key_position(position))
+            })
+            .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                "Arranged Window Links",
+            );
+
+        // The value of the last row at each position.
+        let reverse = window_reverse(func);
+        let ends = records
+            .mz_arrange::<RowRowBatcher<_, _>, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                "Arranged Window Ends",
+            )
+            .mz_reduce_abelian::<_, _, _, RowRowBuilder<_, _>, RowRowSpine<_, _>>(
+                "Window Ends",
+                move |_key_position, source, target| {
+                    let payloads = source
+                        .iter()
+                        .filter(|(_payload, diff)| diff.is_positive())
+                        .map(|(payload, _diff)| payload.to_datum_iter().next().unwrap());
+                    let last = if reverse {
+                        payloads.min()
+                    } else {
+                        payloads.max()
+                    };
+                    if let Some(last) = last {
+                        let (_row, (value, _offset, _default)) = lag_lead_payload(last);
+                        target.push((SharedRow::pack([Datum::Null, value]), 1));
+                    }
+                },
+            );
+
+        links.join_core(&ends, |_preceding, key_position, marker| {
+            Some((key_position.into_owned(), marker.into_owned()))
+        })
+    }
+
     fn build_reduce_inaccumulable_distinct<S, V, Bu, Tr>(
         &self,
         input: Collection<S, Row, Diff>,
@@ -1698,6 +2447,501 @@ fn evaluate_mfp_after<'a, 'b>(
     }
 }
 
+/// The number of bits of the key of a value of an ordered-set aggregation that select a child of
+/// a node of the tree that the aggregation maintains.
+const ORDERED_SET_DIGIT_BITS: u32 = 4;
+
+/// The depth of the leaves of the tree that an ordered-set aggregation maintains.
+const ORDERED_SET_DEPTH: u32 = u64::BITS / ORDERED_SET_DIGIT_BITS;
+
+/// Maps a value of an ordered-set aggregation to the key that places it in the tree that the
+/// aggregation maintains.
+///
+/// A value that is smaller than another never has a larger key. Integers, floats, dates and
+/// timestamps are mapped to 64-bit integers, in the case of timestamps at microsecond precision.
+/// Strings and byte strings are mapped to their first eight bytes. All other values have the
+/// same key, and so are all held by a single leaf.
+fn ordered_set_key(datum: Datum) -> u64 {
+    // Flipping the sign bit maps signed integers to unsigned ones in order.
+    let signed = |i: i64| u64::reinterpret_cast(i) ^ (1 << (u64::BITS - 1));
+    let prefix = |bytes: &[u8]| {
+        let mut prefix = [0; 8];
+        let len = bytes.len().min(prefix.len());
+        prefix[..len].copy_from_slice(&bytes[..len]);
+        u64::from_be_bytes(prefix)
+    };
+    match datum {
+        Datum::Int16(i) => signed(i64::from(i)),
+        Datum::Int32(i) => signed(i64::from(i)),
+        Datum::Int64(i) => signed(i),
+        Datum::UInt8(u) => u64::from(u),
+        Datum::UInt16(u) => u64::from(u),
+        Datum::UInt32(u) => u64::from(u),
+        Datum::UInt64(u) => u,
+        Datum::Float32(f) => u64::from(ordered_f32_bits(f.into_inner())) << u32::BITS,
+        Datum::Float64(f) => ordered_f64_bits(f.into_inner()),
+        Datum::MzTimestamp(t) => u64::from(t),
+        Datum::Date(d) => signed(i64::from(d.pg_epoch_days())),
+        Datum::Timestamp(t) => signed(t.and_utc().timestamp_micros()),
+        Datum::TimestampTz(t) => signed(t.timestamp_micros()),
+        Datum::String(s) => prefix(s.as_bytes()),
+        Datum::Bytes(b) => prefix(b),
+        _ => 0,
+    }
+}
+
+/// Maps a float to an integer such that the order of floats, in which all NaNs are equal and
+/// greater than any other value, is preserved. Negative zero maps to the integer just below that
+/// of positive zero.
+fn ordered_f64_bits(f: f64) -> u64 {
+    let bits = if f.is_nan() { f64::NAN } else { f }.to_bits();
+    // Negative floats order in reverse of their bits, and before all positive ones.
+    if bits >> (u64::BITS - 1) == 1 {
+        !bits
+    } else {
+        bits | (1 << (u64::BITS - 1))
+    }
+}
+
+/// Like [`ordered_f64_bits`], but for 32-bit floats.
+fn ordered_f32_bits(f: f32) -> u32 {
+    let bits = if f.is_nan() { f32::NAN } else { f }.to_bits();
+    if bits >> (u32::BITS - 1) == 1 {
+        !bits
+    } else {
+        bits | (1 << (u32::BITS - 1))
+    }
+}
+
+/// Returns the node at `depth` of the tree of an ordered-set aggregation over the values of
+/// `group` that covers the values with the given key.
+fn ordered_set_node(group: &Row, key: u64, depth: u32) -> Row {
+    let prefix = key
+        .checked_shr(u64::BITS - ORDERED_SET_DIGIT_BITS * depth)
+        .unwrap_or(0);
+    SharedRow::pack(group.iter().chain([Datum::UInt64(prefix)]))
+}
+
+/// Returns the digit of `key` that selects the child of the node at `depth` that covers the key.
+fn ordered_set_digit(key: u64, depth: u32) -> u8 {
+    let digit = key >> (u64::BITS - ORDERED_SET_DIGIT_BITS * (depth + 1));
+    u8::try_from(digit & ((1 << ORDERED_SET_DIGIT_BITS) - 1)).expect("must fit")
+}
+
+/// Returns the child of a node of the tree of an ordered-set aggregation that `digit` selects.
+fn ordered_set_child<'a>(
+    node: impl IntoIterator<Item = Datum<'a>>,
+    key_arity: usize,
+    digit: Datum,
+) -> Row {
+    let mut datums = node.into_iter();
+    let binding = SharedRow::get();
+    let mut row_builder = binding.borrow_mut();
+    let mut packer = row_builder.packer();
+    packer.extend(datums.by_ref().take(key_arity));
+    let prefix = datums.next().unwrap().unwrap_uint64();
+    packer.push(Datum::UInt64(
+        (prefix << ORDERED_SET_DIGIT_BITS) | u64::from(digit.unwrap_uint8()),
+    ));
+    row_builder.clone()
+}
+
+/// Returns the parent of a node at `depth` of the tree of an ordered-set aggregation, or, if the
+/// node is the root, its group.
+fn ordered_set_parent<'a>(
+    node: impl IntoIterator<Item = Datum<'a>>,
+    key_arity: usize,
+    depth: u32,
+) -> Row {
+    let mut datums = node.into_iter();
+    let binding = SharedRow::get();
+    let mut row_builder = binding.borrow_mut();
+    let mut packer = row_builder.packer();
+    packer.extend(datums.by_ref().take(key_arity));
+    if depth > 0 {
+        let prefix = datums.next().unwrap().unwrap_uint64();
+        packer.push(Datum::UInt64(prefix >> ORDERED_SET_DIGIT_BITS));
+    }
+    row_builder.clone()
+}
+
+/// Returns the queries that the root of the tree of a percentile sends for the values that the
+/// percentile depends on, given the total number of values: the index of each value among those
+/// values, its rank among all values in ascending order, and the total.
+fn ordered_set_queries(
+    func: &AggregateFunc,
+    total: u64,
+) -> Vec<(Datum<'static>, u64, Datum<'static>)> {
+    let desc = matches!(
+        func,
+        AggregateFunc::PercentileCont { desc: true, .. }
+            | AggregateFunc::PercentileDisc { desc: true, .. }
+    );
+    func.percentile_positions(usize::cast_from(total))
+        .into_iter()
+        .enumerate()
+        .map(|(index, position)| {
+            let position = u64::cast_from(position);
+            let rank = if desc { total - 1 - position } else { position };
+            (
+                Datum::UInt64(u64::cast_from(index)),
+                rank,
+                Datum::UInt64(total),
+            )
+        })
+        .collect()
+}
+
+/// Forwards the queries that arrive at a node of the tree of a percentile to the children of the
+/// node that contain the values they are for, or, if `root` is set, creates the queries.
+///
+/// `source` contains the counts of the values under each child, as rows of the digit that selects
+/// the child, and the queries, as rows of the index of a value among those that the percentile
+/// depends on, its rank among the values under the node, and the total number of values. The
+/// forwarded queries hold the ranks among the values under the children, followed by the digits
+/// that select the children.
+fn forward_ordered_set_queries(
+    root: Option<&AggregateFunc>,
+    source: &[(DatumSeq<'_>, Diff)],
+) -> Vec<Row> {
+    let mut children = Vec::new();
+    let mut queries = Vec::new();
+    for (value, diff) in source {
+        let mut datums = *value;
+        let first = datums.next().unwrap();
+        match datums.next() {
+            None => children.push((first.unwrap_uint8(), *diff)),
+            Some(rank) => queries.push((first, rank.unwrap_uint64(), datums.next().unwrap())),
+        }
+    }
+    if let Some(func) = root {
+        // Non-positive multiplicities are reported by the error check.
+        let total = children
+            .iter()
+            .map(|(_, count)| u64::try_from(*count).unwrap_or(0))
+            .sum();
+        queries = ordered_set_queries(func, total);
+    }
+    children.sort_unstable();
+
+    let mut forwarded = Vec::with_capacity(queries.len());
+    for (index, mut rank, total) in queries {
+        for (digit, count) in &children {
+            let count = u64::try_from(*count).unwrap_or(0);
+            if rank < count {
+                let query = [index, Datum::UInt64(rank), total, Datum::UInt8(*digit)];
+                forwarded.push(SharedRow::pack(query));
+                break;
+            }
+            rank -= count;
+        }
+    }
+    forwarded
+}
+
+/// Answers the queries that arrive at a leaf of the tree of a percentile with the values at their
+/// ranks.
+///
+/// `source` contains the values of the leaf and their multiplicities, and the queries, as in
+/// [`forward_ordered_set_queries`]. The answers are rows of the index of a value among those that
+/// the percentile depends on, the value, and the total number of values.
+fn answer_ordered_set_queries(source: &[(DatumSeq<'_>, Diff)]) -> Vec<Row> {
+    let mut values = Vec::new();
+    let mut queries = Vec::new();
+    for (value, diff) in source {
+        let mut datums = *value;
+        let first = datums.next().unwrap();
+        match datums.next() {
+            // Non-positive multiplicities are reported by the error check.
+            None if diff.is_positive() => values.push((first, *diff)),
+            None => {}
+            Some(rank) => queries.push((first, rank.unwrap_uint64(), datums.next().unwrap())),
+        }
+    }
+    values.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut answers = Vec::with_capacity(queries.len());
+    for (index, mut rank, total) in queries {
+        for (value, count) in &values {
+            let count = u64::try_from(*count).expect("must be positive");
+            if rank < count {
+                answers.push(SharedRow::pack([index, *value, total]));
+                break;
+            }
+            rank -= count;
+        }
+    }
+    answers
+}
+
+/// Selects the mode among the candidates of a node of the tree that `mode` maintains, as a pair of
+/// its count and value.
+///
+/// The candidates of a leaf are its values and their multiplicities, and those of other nodes are
+/// pairs of counts and values that their children selected. Like the non-incremental
+/// implementation, ties are broken in favor of the value that sorts first in the order of the
+/// aggregate.
+fn ordered_set_mode<'a>(
+    desc: bool,
+    leaf: bool,
+    source: &'a [(DatumSeq<'_>, Diff)],
+) -> Option<(Diff, Datum<'a>)> {
+    let mut mode: Option<(Diff, Datum)> = None;
+    for (candidate, diff) in source {
+        let mut datums = *candidate;
+        let (count, value) = if leaf {
+            // Non-positive multiplicities are reported by the error check.
+            if !diff.is_positive() {
+                continue;
+            }
+            (*diff, datums.next().unwrap())
+        } else {
+            let count = datums.next().unwrap().unwrap_int64();
+            (count, datums.next().unwrap())
+        };
+        let preferred = match mode {
+            None => true,
+            Some((mode_count, _)) if count != mode_count => count > mode_count,
+            Some((_, mode_value)) if desc => value > mode_value,
+            Some((_, mode_value)) => value < mode_value,
+        };
+        if preferred {
+            mode = Some((count, value));
+        }
+    }
+    mode
+}
+
+/// Returns the result of an ordered-set aggregation for a group.
+///
+/// `source` contains a row without datums, whose multiplicity is the number of records in the
+/// group, and what the tree over the non-null values of the group found: for `mode`, a pair of the
+/// count and the value of the mode, and for percentiles, a triple of the index, the value, and the
+/// total number of values for each value that the percentile depends on.
+fn ordered_set_result<'a>(func: &AggregateFunc, source: &'a [(DatumSeq<'_>, Diff)]) -> Datum<'a> {
+    let mut total = 0;
+    let mut values = Vec::new();
+    for (result, _diff) in source {
+        let mut datums = *result;
+        match (datums.next(), datums.next(), datums.next()) {
+            (Some(index), Some(value), Some(n)) => {
+                values.push((index.unwrap_uint64(), value));
+                total = n.unwrap_uint64();
+            }
+            (Some(_count), Some(mode), None) => return mode,
+            _ => {}
+        }
+    }
+    if values.is_empty() {
+        return Datum::Null;
+    }
+    values.sort_unstable_by_key(|(index, _)| *index);
+    let values: Vec<_> = values.into_iter().map(|(_, value)| value).collect();
+    func.eval_percentile(usize::cast_from(total), &values)
+}
+
+/// The number of bits of the positions that a `WindowPlan` assigns to rows.
+const WINDOW_POSITION_BITS: u32 = u64::BITS + 1;
+
+/// Returns the order in which a `WindowPlan` assigns positions to the rows of a window partition.
+///
+/// We compute `lead` as `lag` over the reverse order.
+fn window_order(func: &AggregateFunc) -> ColumnOrder {
+    match func {
+        AggregateFunc::RowNumber { order_by }
+        | AggregateFunc::Rank { order_by }
+        | AggregateFunc::DenseRank { order_by }
+        | AggregateFunc::LagLead { order_by, .. } => {
+            let order = order_by[0].clone();
+            if window_reverse(func) {
+                ColumnOrder {
+                    desc: !order.desc,
+                    nulls_last: !order.nulls_last,
+                    ..order
+                }
+            } else {
+                order
+            }
+        }
+        _ => unreachable!("window plans only compute ranking window functions, lag, and lead"),
+    }
+}
+
+/// Whether a `WindowPlan` orders the rows of a window partition in reverse.
+fn window_reverse(func: &AggregateFunc) -> bool {
+    matches!(
+        func,
+        AggregateFunc::LagLead {
+            lag_lead: LagLeadType::Lead,
+            ..
+        }
+    )
+}
+
+/// Returns the position of a row with the given ORDER BY value within its window partition.
+///
+/// Positions sort in the same order as the values they are assigned to under `order`, and equal
+/// positions correspond to equal values. Non-null values are mapped to 64-bit integers, in the
+/// case of timestamps at microsecond precision, and nulls take the position before or after all
+/// of them.
+fn window_position(datum: Datum, order: &ColumnOrder) -> u128 {
+    // Flipping the sign bit maps signed integers to unsigned ones in order.
+    let signed = |i: i64| u64::reinterpret_cast(i) ^ (1 << (u64::BITS - 1));
+    let value = match datum {
+        Datum::Null if order.nulls_last => return 1 << u64::BITS,
+        Datum::Null => return 0,
+        Datum::Int16(i) => signed(i64::from(i)),
+        Datum::Int32(i) => signed(i64::from(i)),
+        Datum::Int64(i) => signed(i),
+        Datum::UInt16(u) => u64::from(u),
+        Datum::UInt32(u) => u64::from(u),
+        Datum::UInt64(u) => u,
+        Datum::MzTimestamp(t) => u64::from(t),
+        Datum::Date(d) => signed(i64::from(d.pg_epoch_days())),
+        Datum::Timestamp(t) => signed(t.and_utc().timestamp_micros()),
+        Datum::TimestampTz(t) => signed(t.timestamp_micros()),
+        datum => panic!("unsupported window ORDER BY value: {datum:?}"),
+    };
+    let value = u128::from(if order.desc { !value } else { value });
+    if order.nulls_last {
+        value
+    } else {
+        value + 1
+    }
+}
+
+/// Returns the datums that encode a position.
+fn window_position_datums<'a>(position: u128) -> [Datum<'a>; 2] {
+    let high = u64::try_from(position >> u64::BITS).expect("must fit");
+    let low = u64::try_from(position & u128::from(u64::MAX)).expect("must fit");
+    [Datum::UInt64(high), Datum::UInt64(low)]
+}
+
+/// Reads a position encoded by [`window_position_datums`] from `datums`.
+fn read_window_position_datums<'a>(datums: &mut impl Iterator<Item = Datum<'a>>) -> u128 {
+    let high = datums.next().unwrap().unwrap_uint64();
+    let low = datums.next().unwrap().unwrap_uint64();
+    (u128::from(high) << u64::BITS) | u128::from(low)
+}
+
+/// Reads the position of a row keyed by its window partition and position.
+fn read_window_position(key_position: &Row, key_arity: usize) -> u128 {
+    read_window_position_datums(&mut key_position.iter().skip(key_arity))
+}
+
+/// Returns the value of a node of the tree that `lag` and `lead` maintain over the positions of a
+/// window partition, which is either the range of positions the node covers or a link from a
+/// position to the position that precedes it.
+fn window_node_value(link: bool, a: u128, b: u128) -> Row {
+    SharedRow::pack(
+        std::iter::once(Datum::from(link))
+            .chain(window_position_datums(a))
+            .chain(window_position_datums(b)),
+    )
+}
+
+/// Reads the value of a node encoded by [`window_node_value`].
+fn read_window_node_value<'a>(datums: impl IntoIterator<Item = Datum<'a>>) -> (bool, u128, u128) {
+    let mut datums = datums.into_iter();
+    let link = datums.next().unwrap().unwrap_bool();
+    let a = read_window_position_datums(&mut datums);
+    let b = read_window_position_datums(&mut datums);
+    (link, a, b)
+}
+
+/// Splits the payload of `lag` or `lead` into the original row and the arguments, i.e., the
+/// value, the offset, and the default.
+fn lag_lead_payload(payload: Datum) -> (Datum, (Datum, Datum, Datum)) {
+    let mut fields = payload.unwrap_list().iter();
+    let row = fields.next().unwrap();
+    let mut args = fields.next().unwrap().unwrap_list().iter();
+    let value = args.next().unwrap();
+    let offset = args.next().unwrap();
+    let default = args.next().unwrap();
+    (row, (value, offset, default))
+}
+
+/// Returns the key of the bucket at `level` that contains `position`, within the window
+/// partition of `key_position`.
+fn window_bucket(key_position: &Row, key_arity: usize, level: u32, position: u128) -> Row {
+    let binding = SharedRow::get();
+    let mut row_builder = binding.borrow_mut();
+    let mut packer = row_builder.packer();
+    packer.extend(key_position.iter().take(key_arity));
+    packer.push(Datum::UInt32(level));
+    packer.extend(window_position_datums(position >> level));
+    row_builder.clone()
+}
+
+/// Returns the results of a window function for the rows at a single position of a window
+/// partition.
+///
+/// `source` contains the payloads of the rows and the markers of the position. For ranking window
+/// functions, the payloads are lists of original rows, and the markers have no datums and
+/// multiplicities that sum to the number of rows (or, for `dense_rank`, distinct positions) at
+/// smaller positions. For `lag` and `lead`, the payloads are records of the original row and the
+/// arguments, and the marker, if any, holds the value of the last row at the preceding position.
+/// Like the non-incremental implementation, rows at the same position are ordered by their
+/// payloads, and the results are pairs of the result and the original row.
+fn window_results<'a>(
+    func: &AggregateFunc,
+    source: &'a [(DatumSeq<'_>, Diff)],
+    temp_storage: &'a RowArena,
+) -> Vec<Datum<'a>> {
+    let mut preceding = 0;
+    let mut previous = None;
+    let mut payloads = Vec::new();
+    for (value, diff) in source {
+        let mut datums = value.to_datum_iter();
+        match (datums.next(), datums.next()) {
+            (None, _) => preceding += diff,
+            (Some(_), Some(end)) => previous = Some(end),
+            (Some(payload), None) => {
+                // Note that in the non-positive case, this is wrong, but window functions
+                // do not validate multiplicities.
+                let count = usize::try_from(*diff).unwrap_or(0);
+                payloads.extend(std::iter::repeat(payload).take(count));
+            }
+        }
+    }
+    payloads.sort_unstable();
+    if window_reverse(func) {
+        payloads.reverse();
+    }
+    if let AggregateFunc::LagLead { .. } = func {
+        return payloads
+            .into_iter()
+            .map(|payload| {
+                let (row, (value, _offset, default)) = lag_lead_payload(payload);
+                let result = previous.replace(value).unwrap_or(default);
+                temp_storage.make_datum(|packer| {
+                    packer.push_list_with(|packer| {
+                        packer.push(result);
+                        packer.push(row);
+                    });
+                })
+            })
+            .collect();
+    }
+    payloads
+        .into_iter()
+        .flat_map(|payload| payload.unwrap_list().iter())
+        .zip(0..)
+        .map(|(row, i)| {
+            let rank = match func {
+                AggregateFunc::RowNumber { .. } => preceding + 1 + i,
+                _ => preceding + 1,
+            };
+            temp_storage.make_datum(|packer| {
+                packer.push_list_with(|packer| {
+                    packer.push(Datum::Int64(rank));
+                    packer.push(row);
+                });
+            })
+        })
+        .collect()
+}
+
 fn accumulable_zero(aggr_func: &AggregateFunc) -> Accum {
     match aggr_func {
         AggregateFunc::Any | AggregateFunc::All => Accum::Bool {
@@ -2506,12 +3750,21 @@ mod monoids {
             | AggregateFunc::RowNumber { .. }
             | AggregateFunc::Rank { .. }
             | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::PercentRank { .. }
+            | AggregateFunc::CumeDist { .. }
+            | AggregateFunc::Ntile { .. }
             | AggregateFunc::LagLead { .. }
             | AggregateFunc::FirstValue { .. }
             | AggregateFunc::LastValue { .. }
+            | AggregateFunc::NthValue { .. }
             | AggregateFunc::WindowAggregate { .. }
             | AggregateFunc::FusedValueWindowFunc { .. }
-            | AggregateFunc::FusedWindowAggregate { .. } => None,
+            | AggregateFunc::FusedWindowAggregate { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::ApproxCountDistinct
+            | AggregateFunc::ApproxPercentile { .. } => None,
         }
     }
 }
@@ -2532,7 +3785,7 @@ mod window_agg_helpers {
     impl mz_expr::OneByOneAggr for OneByOneAggrImpls {
         fn new(agg: &AggregateFunc, reverse: bool) -> Self {
             match reduction_type(agg) {
-                ReductionType::Basic => {
+                ReductionType::Basic | ReductionType::Sketch => {
                     OneByOneAggrImpls::Basic(mz_expr::NaiveOneByOneAggr::new(agg, reverse))
                 }
                 ReductionType::Accumulable => {
@@ -2621,3 +3874,35 @@ mod window_agg_helpers {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_ordered_set_keys() {
+        // Values that are smaller than others never have larger keys.
+        for typ in ScalarType::enumerate() {
+            let datums: Vec<_> = typ.interesting_datums().collect();
+            for datum in &datums {
+                for other in &datums {
+                    if datum < other {
+                        assert!(
+                            ordered_set_key(*datum) <= ordered_set_key(*other),
+                            "{datum:?} and {other:?}"
+                        );
+                    }
+                }
+            }
+        }
+
+        // Negative zero keeps its sign, and sorts before positive zero.
+        assert!(ordered_f64_bits(-0.0) < ordered_f64_bits(0.0));
+        assert!(ordered_f64_bits(-f64::MIN_POSITIVE) < ordered_f64_bits(-0.0));
+        assert!(ordered_f32_bits(-0.0) < ordered_f32_bits(0.0));
+        assert_eq!(ordered_f64_bits(f64::NAN), ordered_f64_bits(-f64::NAN));
+        assert!(ordered_f64_bits(f64::INFINITY) < ordered_f64_bits(f64::NAN));
+    }
+}
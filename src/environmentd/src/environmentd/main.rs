@@ -43,6 +43,7 @@ use mz_catalog::config::ClusterReplicaSizeMap;
 use mz_cloud_resources::{AwsExternalIdPrefix, CloudResourceController};
 use mz_controller::ControllerConfig;
 use mz_frontegg_auth::{Authenticator, FronteggCliArgs};
+use mz_oidc_auth::{Authenticator as OidcAuthenticator, OidcCliArgs};
 use mz_orchestrator::Orchestrator;
 use mz_orchestrator_kubernetes::{
     KubernetesImagePullPolicy, KubernetesOrchestrator, KubernetesOrchestratorConfig,
@@ -208,6 +209,17 @@ pub struct Args {
     /// Frontegg arguments.
     #[clap(flatten)]
     frontegg: FronteggCliArgs,
+    /// OIDC arguments.
+    #[clap(flatten)]
+    oidc: OidcCliArgs,
+    /// Whether to require clients to authenticate with the password of a role
+    /// that has the `LOGIN` attribute.
+    #[clap(
+        long,
+        env = "PASSWORD_AUTH",
+        conflicts_with_all = &["frontegg_tenant", "oidc_issuer"]
+    )]
+    password_auth: bool,
 
     // === Orchestrator options. ===
     /// The service orchestrator implementation to use.
@@ -745,6 +757,10 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
     // Configure connections.
     let tls = args.tls.into_config()?;
     let frontegg = Authenticator::from_args(args.frontegg, &metrics_registry)?;
+    let oidc = OidcAuthenticator::from_args(args.oidc)?;
+    if frontegg.is_some() && oidc.is_some() {
+        bail!("--frontegg-tenant and --oidc-issuer cannot be specified together");
+    }
 
     // Configure CORS.
     let allowed_origins = if !args.cors_allowed_origin.is_empty() {
@@ -1060,6 +1076,8 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                 tls,
                 tls_reload_certs: mz_server_core::default_cert_reload_ticker(),
                 frontegg,
+                oidc,
+                password_auth: args.password_auth,
                 cors_allowed_origin,
                 egress_addresses: args.announce_egress_address,
                 http_host_name: args.http_host_name,
@@ -21,8 +21,8 @@ use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::net::{IpAddr, SocketAddr};
 use std::pin::Pin;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 use async_trait::async_trait;
@@ -44,15 +44,18 @@ use mz_adapter::session::{Session, SessionConfig};
 use mz_adapter::{AdapterError, AdapterNotice, Client, SessionClient, WebhookAppenderCache};
 use mz_frontegg_auth::{Authenticator as FronteggAuthentication, Error as FronteggError};
 use mz_http_util::DynamicFilterTarget;
+use mz_oidc_auth::{Authenticator as OidcAuthentication, Error as OidcError};
 use mz_ore::cast::u64_to_usize;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::str::StrExt;
-use mz_pgwire_common::{ConnectionCounter, ConnectionHandle};
+use mz_ore::task::{self, JoinHandleExt};
+use mz_pgwire_common::{ConnectionCounter, ConnectionHandle, ScramSecret};
 use mz_repr::user::ExternalUserMetadata;
 use mz_server_core::{Connection, ConnectionHandler, ReloadingSslContext, Server};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::user::{HTTP_DEFAULT_USER, SUPPORT_USER_NAME, SYSTEM_USER_NAME};
 use mz_sql::session::vars::{Value, Var, VarInput, WELCOME_MESSAGE};
+use openssl::sha::Sha256;
 use openssl::ssl::Ssl;
 use prometheus::{
     COMPUTE_METRIC_QUERIES, FRONTIER_METRIC_QUERIES, STORAGE_METRIC_QUERIES, USAGE_METRIC_QUERIES,
@@ -93,6 +96,8 @@ pub struct HttpConfig {
     pub source: &'static str,
     pub tls: Option<ReloadingTlsConfig>,
     pub frontegg: Option<FronteggAuthentication>,
+    pub oidc: Option<OidcAuthentication>,
+    pub password_auth: bool,
     pub adapter_client: mz_adapter::Client,
     pub allowed_origin: AllowOrigin,
     pub active_connection_counter: ConnectionCounter,
@@ -113,9 +118,108 @@ pub enum TlsMode {
     Require,
 }
 
+/// The mechanism used to authenticate HTTP requests.
+#[derive(Clone)]
+enum Authenticator {
+    /// No authentication. The user name in the request is trusted.
+    None,
+    /// Frontegg app passwords or JWTs.
+    Frontegg(FronteggAuthentication),
+    /// JWTs issued by an OIDC provider.
+    Oidc(OidcAuthentication),
+    /// The passwords of roles in the catalog.
+    Password(PasswordAuthenticator),
+}
+
+/// How long a successful password verification is remembered.
+const PASSWORD_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Verifies passwords against the verifiers stored for roles in the catalog.
+///
+/// HTTP clients usually send the password with every request, and verifying a password runs
+/// thousands of iterations of PBKDF2. To avoid repeating that work, successful verifications are
+/// remembered for [`PASSWORD_CACHE_TTL`], for as long as the role's verifier doesn't change.
+#[derive(Clone)]
+struct PasswordAuthenticator {
+    adapter_client: mz_adapter::Client,
+    /// A random key that the cached passwords are hashed with, so that the cache doesn't hold
+    /// the passwords themselves.
+    cache_key: [u8; 32],
+    /// The recent successful verifications, by user name.
+    verified: Arc<Mutex<BTreeMap<String, VerifiedPassword>>>,
+}
+
+struct VerifiedPassword {
+    /// The verifier that the password matched.
+    secret: ScramSecret,
+    /// The keyed hash of the password.
+    password: [u8; 32],
+    /// When the password was verified.
+    at: Instant,
+}
+
+impl PasswordAuthenticator {
+    fn new(adapter_client: mz_adapter::Client) -> PasswordAuthenticator {
+        PasswordAuthenticator {
+            adapter_client,
+            cache_key: rand::random(),
+            verified: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Reports whether `password` is the password of the role named `username`.
+    ///
+    /// Roles that don't exist or can't log in are checked against a mock verifier, so that the
+    /// response time doesn't reveal which roles exist.
+    async fn authenticate(&self, username: &str, password: &str) -> bool {
+        let secret = self
+            .adapter_client
+            .get_role_password(username)
+            .await
+            .unwrap_or_else(|| ScramSecret::mock(username));
+        let hashed = self.hash(password);
+
+        {
+            let mut verified = self.verified.lock().expect("lock poisoned");
+            verified.retain(|_, v| v.at.elapsed() < PASSWORD_CACHE_TTL);
+            if let Some(v) = verified.get(username) {
+                if v.secret == secret && v.password == hashed {
+                    return true;
+                }
+            }
+        }
+
+        let valid = task::spawn_blocking(|| "verify_password", {
+            let secret = secret.clone();
+            let password = password.to_string();
+            move || secret.verify_password(&password)
+        })
+        .wait_and_assert_finished()
+        .await;
+        if valid {
+            self.verified.lock().expect("lock poisoned").insert(
+                username.to_string(),
+                VerifiedPassword {
+                    secret,
+                    password: hashed,
+                    at: Instant::now(),
+                },
+            );
+        }
+        valid
+    }
+
+    fn hash(&self, password: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.cache_key);
+        hasher.update(password.as_bytes());
+        hasher.finish()
+    }
+}
+
 #[derive(Clone)]
 pub struct WsState {
-    frontegg: Arc<Option<FronteggAuthentication>>,
+    authenticator: Authenticator,
     adapter_client_rx: Delayed<mz_adapter::Client>,
     active_connection_counter: ConnectionCounter,
     helm_chart_version: Option<String>,
@@ -139,6 +243,8 @@ impl HttpServer {
             source,
             tls,
             frontegg,
+            oidc,
+            password_auth,
             adapter_client,
             allowed_origin,
             active_connection_counter,
@@ -148,8 +254,16 @@ impl HttpServer {
         }: HttpConfig,
     ) -> HttpServer {
         let tls_mode = tls.as_ref().map(|tls| tls.mode).unwrap_or(TlsMode::Disable);
-        let frontegg = Arc::new(frontegg);
-        let base_frontegg = Arc::clone(&frontegg);
+        let authenticator = match (frontegg, oidc, password_auth) {
+            (None, None, false) => Authenticator::None,
+            (Some(frontegg), None, false) => Authenticator::Frontegg(frontegg),
+            (None, Some(oidc), false) => Authenticator::Oidc(oidc),
+            (None, None, true) => {
+                Authenticator::Password(PasswordAuthenticator::new(adapter_client.clone()))
+            }
+            _ => panic!("at most one authentication mechanism can be enabled"),
+        };
+        let base_authenticator = authenticator.clone();
         let (adapter_client_tx, adapter_client_rx) = oneshot::channel();
         adapter_client_tx
             .send(adapter_client.clone())
@@ -159,8 +273,8 @@ impl HttpServer {
 
         let base_router = base_router(BaseRouterConfig { profiling: false })
             .layer(middleware::from_fn(move |req, next| {
-                let base_frontegg = Arc::clone(&base_frontegg);
-                async move { http_auth(req, next, tls_mode, base_frontegg.as_ref().as_ref()).await }
+                let base_authenticator = base_authenticator.clone();
+                async move { http_auth(req, next, tls_mode, &base_authenticator).await }
             }))
             .layer(Extension(adapter_client_rx.clone()))
             .layer(Extension(active_connection_counter.clone()))
@@ -180,7 +294,7 @@ impl HttpServer {
         let ws_router = Router::new()
             .route("/api/experimental/sql", routing::get(sql::handle_sql_ws))
             .with_state(WsState {
-                frontegg,
+                authenticator,
                 adapter_client_rx,
                 active_connection_counter,
                 helm_chart_version,
@@ -448,7 +562,7 @@ impl InternalHttpServer {
             // attach the x-materialize-user header to all requests it proxies to this api.
             .layer(middleware::from_fn(internal_http_auth))
             .with_state(WsState {
-                frontegg: Arc::new(None),
+                authenticator: Authenticator::None,
                 adapter_client_rx,
                 active_connection_counter,
                 helm_chart_version: helm_chart_version.clone(),
@@ -677,8 +791,12 @@ enum AuthError {
     InvalidLogin(String),
     #[error("{0}")]
     Frontegg(#[from] FronteggError),
+    #[error("{0}")]
+    Oidc(#[from] OidcError),
     #[error("missing authorization header")]
     MissingHttpAuthentication,
+    #[error("password authentication failed for user {}", .0.quoted())]
+    InvalidPassword(String),
     #[error("{0}")]
     MismatchedUser(String),
     #[error("unexpected credentials")]
@@ -707,7 +825,7 @@ async fn http_auth(
     mut req: Request,
     next: Next,
     tls_mode: TlsMode,
-    frontegg: Option<&FronteggAuthentication>,
+    authenticator: &Authenticator,
 ) -> impl IntoResponse {
     // First, extract the username from the certificate, validating that the
     // connection matches the TLS configuration along the way.
@@ -718,18 +836,18 @@ async fn http_auth(
         (TlsMode::Require, ConnProtocol::Http) => return Err(AuthError::HttpsRequired),
         (TlsMode::Require, ConnProtocol::Https { .. }) => {}
     }
-    let creds = match frontegg {
-        None => {
-            // If no Frontegg authentication, use whatever is in the HTTP auth
-            // header (without checking the password), or fall back to the
-            // default user.
+    let creds = match authenticator {
+        Authenticator::None => {
+            // If no authentication, use whatever is in the HTTP auth header
+            // (without checking the password), or fall back to the default
+            // user.
             if let Some(basic) = req.headers().typed_get::<Authorization<Basic>>() {
                 Credentials::User(basic.username().to_string())
             } else {
                 Credentials::DefaultUser
             }
         }
-        Some(_) => {
+        _ => {
             if let Some(basic) = req.headers().typed_get::<Authorization<Basic>>() {
                 Credentials::Password {
                     username: basic.username().to_string(),
@@ -745,7 +863,7 @@ async fn http_auth(
         }
     };
 
-    let user = auth(frontegg, creds).await?;
+    let user = auth(authenticator, creds).await?;
 
     // Add the authenticated user as an extension so downstream handlers can
     // inspect it if necessary.
@@ -757,7 +875,7 @@ async fn http_auth(
 
 async fn init_ws(
     WsState {
-        frontegg,
+        authenticator,
         adapter_client_rx,
         active_connection_counter,
         helm_chart_version,
@@ -785,8 +903,8 @@ async fn init_ws(
             }
         }
     };
-    let (user, options) = match (frontegg.as_ref(), existing_user, ws_auth) {
-        (Some(frontegg), None, ws_auth) => {
+    let (user, options) = match (authenticator, existing_user, ws_auth) {
+        (Authenticator::Frontegg(_) | Authenticator::Oidc(_), None, ws_auth) => {
             let (creds, options) = match ws_auth {
                 WebSocketAuth::Basic {
                     user,
@@ -807,31 +925,50 @@ async fn init_ws(
                     anyhow::bail!("expected auth information");
                 }
             };
-            (auth(Some(frontegg), creds).await?, options)
+            (auth(authenticator, creds).await?, options)
         }
         (
+            Authenticator::Password(_),
             None,
+            WebSocketAuth::Basic {
+                user,
+                password,
+                options,
+            },
+        ) => {
+            let creds = Credentials::Password {
+                username: user,
+                password,
+            };
+            (auth(authenticator, creds).await?, options)
+        }
+        (
+            Authenticator::None,
             None,
             WebSocketAuth::Basic {
                 user,
                 password: _,
                 options,
             },
-        ) => (auth(None, Credentials::User(user)).await?, options),
-        // No frontegg, specified existing user, we only accept options only.
-        (None, Some(existing_user), WebSocketAuth::OptionsOnly { options }) => {
+        ) => (auth(authenticator, Credentials::User(user)).await?, options),
+        // No authentication, specified existing user, we only accept options only.
+        (Authenticator::None, Some(existing_user), WebSocketAuth::OptionsOnly { options }) => {
             (existing_user, options)
         }
-        // No frontegg, specified existing user, we do not expect basic or bearer auth.
-        (None, Some(_), WebSocketAuth::Basic { .. } | WebSocketAuth::Bearer { .. }) => {
+        // No authentication, specified existing user, we do not expect basic or bearer auth.
+        (
+            Authenticator::None,
+            Some(_),
+            WebSocketAuth::Basic { .. } | WebSocketAuth::Bearer { .. },
+        ) => {
             warn!("Unexpected bearer or basic auth provided when using user header");
             anyhow::bail!("unexpected")
         }
-        // Specifying both frontegg and an existing user should not be possible.
-        (Some(_), Some(_), _) => anyhow::bail!("unexpected"),
-        // No frontegg, no existing user, and no passed username.
-        (None, None, WebSocketAuth::Bearer { .. } | WebSocketAuth::OptionsOnly { .. }) => {
-            warn!("Unexpected auth type when not using frontegg or user header");
+        // Specifying both authentication and an existing user should not be possible.
+        (_, Some(_), _) => anyhow::bail!("unexpected"),
+        // No existing user, and no passed username.
+        (_, None, WebSocketAuth::Bearer { .. } | WebSocketAuth::OptionsOnly { .. }) => {
+            warn!("Unexpected auth type for the configured authentication");
             anyhow::bail!("unexpected")
         }
     };
@@ -857,10 +994,7 @@ enum Credentials {
     Token { token: String },
 }
 
-async fn auth(
-    frontegg: Option<&FronteggAuthentication>,
-    creds: Credentials,
-) -> Result<AuthedUser, AuthError> {
+async fn auth(authenticator: &Authenticator, creds: Credentials) -> Result<AuthedUser, AuthError> {
     // There are three places a username may be specified:
     //
     //   - certificate common name
@@ -870,20 +1004,31 @@ async fn auth(
     // We verify that if any of these are present, they must match any other
     // that is also present.
 
-    // Then, handle Frontegg authentication if required.
-    let (name, external_metadata_rx) = match (frontegg, creds) {
-        // If no Frontegg authentication, allow the default user.
-        (None, Credentials::DefaultUser) => (HTTP_DEFAULT_USER.name.to_string(), None),
-        // If no Frontegg authentication, allow a protocol-specified user.
-        (None, Credentials::User(name)) => (name, None),
-        // With frontegg disabled, specifying credentials is an error.
-        (None, _) => return Err(AuthError::UnexpectedCredentials),
+    // Then, handle authentication if required.
+    let (name, external_metadata_rx) = match (authenticator, creds) {
+        // If no authentication, allow the default user.
+        (Authenticator::None, Credentials::DefaultUser) => {
+            (HTTP_DEFAULT_USER.name.to_string(), None)
+        }
+        // If no authentication, allow a protocol-specified user.
+        (Authenticator::None, Credentials::User(name)) => (name, None),
+        // With authentication disabled, specifying credentials is an error.
+        (Authenticator::None, _) => return Err(AuthError::UnexpectedCredentials),
+        // If we require password auth, the password must match the one stored
+        // for the role.
+        (Authenticator::Password(password_auth), Credentials::Password { username, password }) => {
+            if !password_auth.authenticate(&username, &password).await {
+                return Err(AuthError::InvalidPassword(username));
+            }
+            (username, None)
+        }
+        (Authenticator::Password(_), _) => return Err(AuthError::MissingHttpAuthentication),
         // If we require Frontegg auth, fetch credentials from the HTTP auth
         // header. Basic auth comes with a username/password, where the password
         // is the client+secret pair. Bearer auth is an existing JWT that must
         // be validated. In either case, if a username was specified in the
         // client cert, it must match that of the JWT.
-        (Some(frontegg), creds) => match creds {
+        (Authenticator::Frontegg(frontegg), creds) => match creds {
             Credentials::Password { username, password } => {
                 let auth_session = frontegg.authenticate(&username, &password).await?;
                 let user = auth_session.user().into();
@@ -895,6 +1040,29 @@ async fn auth(
                 let (_, external_metadata_rx) = watch::channel(ExternalUserMetadata {
                     user_id: claims.user_id,
                     admin: claims.is_admin,
+                    groups: None,
+                });
+                (claims.user, Some(external_metadata_rx))
+            }
+            Credentials::DefaultUser | Credentials::User(_) => {
+                return Err(AuthError::MissingHttpAuthentication)
+            }
+        },
+        // OIDC authentication works like Frontegg authentication, except that
+        // the password presented with basic auth is itself a JWT.
+        (Authenticator::Oidc(oidc), creds) => match creds {
+            Credentials::Password { username, password } => {
+                let auth_session = oidc.authenticate(&username, &password).await?;
+                let user = auth_session.user().into();
+                let external_metadata_rx = Some(auth_session.external_metadata_rx());
+                (user, external_metadata_rx)
+            }
+            Credentials::Token { token } => {
+                let claims = oidc.validate_access_token(&token, None).await?;
+                let (_, external_metadata_rx) = watch::channel(ExternalUserMetadata {
+                    user_id: claims.user_id,
+                    admin: claims.is_admin,
+                    groups: Some(claims.groups.clone()),
                 });
                 (claims.user, Some(external_metadata_rx))
             }
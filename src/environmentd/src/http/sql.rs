@@ -1382,6 +1382,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::CreatedViews { .. }
         | ExecuteResponse::CreatedMaterializedView { .. }
         | ExecuteResponse::CreatedContinualTask { .. }
+        | ExecuteResponse::CreatedFunction
         | ExecuteResponse::CreatedType
         | ExecuteResponse::CreatedNetworkPolicy
         | ExecuteResponse::Comment
@@ -1394,6 +1395,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::GrantedPrivilege
         | ExecuteResponse::GrantedRole
         | ExecuteResponse::Inserted(_)
+        | ExecuteResponse::Merged(_)
         | ExecuteResponse::Copied(_)
         | ExecuteResponse::Raised
         | ExecuteResponse::ReassignOwned
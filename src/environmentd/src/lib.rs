@@ -38,6 +38,7 @@ use mz_catalog::durable::BootstrapArgs;
 use mz_cloud_resources::CloudResourceController;
 use mz_controller::ControllerConfig;
 use mz_frontegg_auth::Authenticator as FronteggAuthentication;
+use mz_oidc_auth::Authenticator as OidcAuthentication;
 use mz_ore::future::OreFutureExt;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::NowFn;
@@ -91,6 +92,12 @@ pub struct Config {
     pub tls_reload_certs: ReloadTrigger,
     /// Frontegg JWT authentication configuration.
     pub frontegg: Option<FronteggAuthentication>,
+    /// OIDC JWT authentication configuration. Mutually exclusive with
+    /// `frontegg`.
+    pub oidc: Option<OidcAuthentication>,
+    /// Whether to authenticate external connections with the passwords of
+    /// roles in the catalog. Mutually exclusive with `frontegg` and `oidc`.
+    pub password_auth: bool,
     /// Origins for which cross-origin resource sharing (CORS) for HTTP requests
     /// is permitted.
     pub cors_allowed_origin: AllowOrigin,
@@ -727,6 +734,8 @@ impl Listeners {
                 tls: pgwire_tls.clone(),
                 adapter_client: adapter_client.clone(),
                 frontegg: config.frontegg.clone(),
+                oidc: config.oidc.clone(),
+                password_auth: config.password_auth,
                 metrics: metrics.clone(),
                 internal: false,
                 active_connection_counter: active_connection_counter.clone(),
@@ -757,6 +766,8 @@ impl Listeners {
                 }),
                 adapter_client: adapter_client.clone(),
                 frontegg: None,
+                oidc: None,
+                password_auth: false,
                 metrics: metrics.clone(),
                 internal: true,
                 active_connection_counter: active_connection_counter.clone(),
@@ -778,6 +789,8 @@ impl Listeners {
                 source: "external",
                 tls: http_tls,
                 frontegg: config.frontegg.clone(),
+                oidc: config.oidc.clone(),
+                password_auth: config.password_auth,
                 adapter_client: adapter_client.clone(),
                 allowed_origin: config.cors_allowed_origin.clone(),
                 active_connection_counter: active_connection_counter.clone(),
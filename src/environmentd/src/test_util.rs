@@ -80,7 +80,9 @@ use tungstenite::stream::MaybeTlsStream;
 use tungstenite::{Message, WebSocket};
 use url::Url;
 
-use crate::{CatalogConfig, FronteggAuthentication, WebSocketAuth, WebSocketResponse};
+use crate::{
+    CatalogConfig, FronteggAuthentication, OidcAuthentication, WebSocketAuth, WebSocketResponse,
+};
 
 pub static KAFKA_ADDRS: LazyLock<String> =
     LazyLock::new(|| env::var("KAFKA_ADDRS").unwrap_or_else(|_| "localhost:9092".into()));
@@ -91,6 +93,8 @@ pub struct TestHarness {
     data_directory: Option<PathBuf>,
     tls: Option<TlsCertConfig>,
     frontegg: Option<FronteggAuthentication>,
+    oidc: Option<OidcAuthentication>,
+    password_auth: bool,
     unsafe_mode: bool,
     workers: usize,
     now: NowFn,
@@ -125,6 +129,8 @@ impl Default for TestHarness {
             data_directory: None,
             tls: None,
             frontegg: None,
+            oidc: None,
+            password_auth: false,
             unsafe_mode: false,
             workers: 1,
             now: SYSTEM_TIME.clone(),
@@ -243,6 +249,16 @@ impl TestHarness {
         self
     }
 
+    pub fn with_oidc(mut self, oidc: &OidcAuthentication) -> Self {
+        self.oidc = Some(oidc.clone());
+        self
+    }
+
+    pub fn with_password_auth(mut self) -> Self {
+        self.password_auth = true;
+        self
+    }
+
     pub fn with_now(mut self, now: NowFn) -> Self {
         self.now = now;
         self
@@ -535,6 +551,8 @@ impl Listeners {
                 cloud_resource_controller: None,
                 tls: config.tls,
                 frontegg: config.frontegg,
+                oidc: config.oidc,
+                password_auth: config.password_auth,
                 unsafe_mode: config.unsafe_mode,
                 all_features: false,
                 metrics_registry: metrics_registry.clone(),
@@ -13,12 +13,13 @@ use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::future::IntoFuture;
 use std::io::{Read, Write};
-use std::net::{IpAddr, Ipv4Addr, TcpStream};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
 use std::num::NonZeroUsize;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use axum::{routing, Form, Json, Router};
 use chrono::Utc;
 use headers::Authorization;
 use http_body_util::BodyExt;
@@ -40,6 +41,10 @@ use mz_frontegg_auth::{
 use mz_frontegg_mock::{
     models::ApiToken, models::TenantApiTokenConfig, models::UserConfig, FronteggMockServer,
 };
+use mz_oidc_auth::{
+    Authenticator as OidcAuthentication, AuthenticatorConfig as OidcConfig, JwkSet, JwksSource,
+    RefreshConfig as OidcRefreshConfig, DEFAULT_GROUPS_CLAIM,
+};
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::{NowFn, SYSTEM_TIME};
 use mz_ore::retry::Retry;
@@ -52,6 +57,7 @@ use postgres::config::SslMode;
 use postgres::error::SqlState;
 use serde::Deserialize;
 use serde_json::json;
+use tokio::net::TcpListener;
 use tokio::time::sleep;
 use tungstenite::protocol::frame::coding::CloseCode;
 use tungstenite::Message;
@@ -1253,6 +1259,460 @@ async fn test_auth_base_require_tls_frontegg() {
     .await;
 }
 
+/// The issuer of the JWTs in the OIDC tests.
+const OIDC_ISSUER: &str = "https://idp.example.com";
+
+/// Publishes the CA's key as the only signing key of the OIDC identity provider.
+fn make_oidc_jwks(ca: &Ca) -> JwkSet {
+    let rsa = ca.pkey.rsa().unwrap();
+    serde_json::from_value(json!({
+        "keys": [{
+            "kty": "RSA",
+            "kid": "idp-key",
+            "use": "sig",
+            "alg": "RS256",
+            "n": base64::encode_config(rsa.n().to_vec(), base64::URL_SAFE_NO_PAD),
+            "e": base64::encode_config(rsa.e().to_vec(), base64::URL_SAFE_NO_PAD),
+        }],
+    }))
+    .unwrap()
+}
+
+fn make_oidc_jwt(
+    encoding_key: &EncodingKey,
+    user: &str,
+    groups: &[&str],
+    audience: &str,
+    expires_in_secs: i64,
+) -> String {
+    let mut header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256);
+    header.kid = Some("idp-key".into());
+    let claims = json!({
+        "iss": OIDC_ISSUER,
+        "aud": audience,
+        "sub": Uuid::new_v4().to_string(),
+        "exp": SYSTEM_TIME.as_secs() + expires_in_secs,
+        "preferred_username": user,
+        "groups": groups,
+    });
+    jsonwebtoken::encode(&header, &claims, encoding_key).unwrap()
+}
+
+#[allow(clippy::unit_arg)]
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
+async fn test_auth_oidc() {
+    let ca = Ca::new_root("test ca").unwrap();
+    let (server_cert, server_key) = ca
+        .request_cert("server", vec![IpAddr::V4(Ipv4Addr::LOCALHOST)])
+        .unwrap();
+
+    let oidc_auth = OidcAuthentication::new(OidcConfig {
+        issuer: OIDC_ISSUER.into(),
+        audience: Some("materialize".into()),
+        jwks: JwksSource::Static(make_oidc_jwks(&ca)),
+        user_claim: "preferred_username".into(),
+        groups_claim: DEFAULT_GROUPS_CLAIM.into(),
+        admin_group: Some("mz-admins".into()),
+        refresh: None,
+        now: SYSTEM_TIME.clone(),
+    });
+
+    let encoding_key =
+        EncodingKey::from_rsa_pem(&ca.pkey.private_key_to_pem_pkcs8().unwrap()).unwrap();
+    let other_ca = Ca::new_root("other ca").unwrap();
+    let other_encoding_key =
+        EncodingKey::from_rsa_pem(&other_ca.pkey.private_key_to_pem_pkcs8().unwrap()).unwrap();
+
+    let user = "alice";
+    let admin_user = "bob";
+    let jwt = make_oidc_jwt(&encoding_key, user, &["analysts"], "materialize", 1_000);
+    let admin_jwt = make_oidc_jwt(
+        &encoding_key,
+        admin_user,
+        &["analysts", "mz-admins"],
+        "materialize",
+        1_000,
+    );
+    let expired_jwt = make_oidc_jwt(&encoding_key, user, &[], "materialize", -1_000);
+    let wrong_audience_jwt = make_oidc_jwt(&encoding_key, user, &[], "other", 1_000);
+    let forged_jwt = make_oidc_jwt(&other_encoding_key, user, &[], "materialize", 1_000);
+
+    let server = test_util::TestHarness::default()
+        .with_tls(server_cert, server_key)
+        .with_oidc(&oidc_auth)
+        .start()
+        .await;
+
+    run_tests(
+        "TlsMode::Require, OIDC",
+        &server,
+        &[
+            // A valid JWT as the password should succeed.
+            TestCase::Pgwire {
+                user_to_auth_as: user,
+                user_reported_by_system: user,
+                password: Some(&jwt),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::SuccessSuperuserCheck(false),
+            },
+            TestCase::Http {
+                user_to_auth_as: user,
+                user_reported_by_system: user,
+                scheme: Scheme::HTTPS,
+                headers: &make_header(Authorization::basic(user, &jwt)),
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+            TestCase::Http {
+                user_to_auth_as: user,
+                user_reported_by_system: user,
+                scheme: Scheme::HTTPS,
+                headers: &make_header(Authorization::bearer(&jwt).unwrap()),
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+            TestCase::Ws {
+                auth: &WebSocketAuth::Bearer {
+                    token: jwt.clone(),
+                    options: BTreeMap::default(),
+                },
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+            // Members of the admin group are superusers.
+            TestCase::Pgwire {
+                user_to_auth_as: admin_user,
+                user_reported_by_system: admin_user,
+                password: Some(&admin_jwt),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::SuccessSuperuserCheck(true),
+            },
+            // The user must match the user claim of the JWT.
+            TestCase::Pgwire {
+                user_to_auth_as: admin_user,
+                user_reported_by_system: admin_user,
+                password: Some(&jwt),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(err.message(), "invalid password");
+                    assert_eq!(*err.code(), SqlState::INVALID_PASSWORD);
+                })),
+            },
+            // Expired JWTs, JWTs for another audience, and JWTs not signed by
+            // the identity provider should fail.
+            TestCase::Pgwire {
+                user_to_auth_as: user,
+                user_reported_by_system: user,
+                password: Some(&expired_jwt),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(err.message(), "invalid password");
+                    assert_eq!(*err.code(), SqlState::INVALID_PASSWORD);
+                })),
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: user,
+                user_reported_by_system: user,
+                password: Some(&wrong_audience_jwt),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(err.message(), "invalid password");
+                    assert_eq!(*err.code(), SqlState::INVALID_PASSWORD);
+                })),
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: user,
+                user_reported_by_system: user,
+                password: Some(&forged_jwt),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(err.message(), "invalid password");
+                    assert_eq!(*err.code(), SqlState::INVALID_PASSWORD);
+                })),
+            },
+            TestCase::Http {
+                user_to_auth_as: user,
+                user_reported_by_system: user,
+                scheme: Scheme::HTTPS,
+                headers: &make_header(Authorization::bearer(&expired_jwt).unwrap()),
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Err(Box::new(|code, message| {
+                    assert_eq!(code, Some(StatusCode::UNAUTHORIZED));
+                    assert_eq!(message, "unauthorized");
+                })),
+            },
+            // Without a password, authentication should fail.
+            TestCase::Pgwire {
+                user_to_auth_as: user,
+                user_reported_by_system: user,
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(err.message(), "invalid password");
+                    assert_eq!(*err.code(), SqlState::INVALID_PASSWORD);
+                })),
+            },
+        ],
+    )
+    .await;
+
+    // The groups of a user are synchronized into the memberships of its role
+    // at the start of each session.
+    let connect = |user: &str, password: &str| {
+        server
+            .connect()
+            .ssl_mode(SslMode::Require)
+            .user(user)
+            .password(password)
+            .with_tls(make_pg_tls(Box::new(|b: &mut SslConnectorBuilder| {
+                Ok(b.set_verify(SslVerifyMode::NONE))
+            })))
+    };
+    async fn memberships(client: &tokio_postgres::Client, user: &str) -> Vec<String> {
+        client
+            .query(
+                "SELECT r.name FROM mz_role_members m
+                 JOIN mz_roles r ON m.role_id = r.id
+                 JOIN mz_roles u ON m.member = u.id
+                 WHERE u.name = $1
+                 ORDER BY r.name",
+                &[&user],
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect()
+    }
+    let admin_client = connect(admin_user, &admin_jwt).await.unwrap();
+    admin_client
+        .batch_execute("CREATE ROLE analysts; CREATE ROLE reporting; GRANT reporting TO alice")
+        .await
+        .unwrap();
+    let _client = connect(user, &jwt).await.unwrap();
+    assert_eq!(
+        memberships(&admin_client, user).await,
+        ["analysts", "reporting"]
+    );
+    // Memberships are revoked once the group is no longer reported, unless
+    // they were granted explicitly.
+    let no_groups_jwt = make_oidc_jwt(&encoding_key, user, &[], "materialize", 1_000);
+    let _client = connect(user, &no_groups_jwt).await.unwrap();
+    assert_eq!(memberships(&admin_client, user).await, ["reporting"]);
+    // Groups that would make a role a member of itself are skipped.
+    admin_client
+        .batch_execute("GRANT alice TO analysts")
+        .await
+        .unwrap();
+    let _client = connect(user, &jwt).await.unwrap();
+    assert_eq!(memberships(&admin_client, user).await, ["reporting"]);
+
+    // Sessions end when the JWT that they were established with expires.
+    let short_lived_jwt = make_oidc_jwt(&encoding_key, user, &[], "materialize", 5);
+    let pg_client = server
+        .connect()
+        .ssl_mode(SslMode::Require)
+        .user(user)
+        .password(&short_lived_jwt)
+        .with_tls(make_pg_tls(Box::new(|b: &mut SslConnectorBuilder| {
+            Ok(b.set_verify(SslVerifyMode::NONE))
+        })))
+        .await
+        .unwrap();
+    assert_eq!(
+        pg_client
+            .query_one("SELECT current_user", &[])
+            .await
+            .unwrap()
+            .get::<_, String>(0),
+        user
+    );
+    tokio::time::sleep(Duration::from_secs(6)).await;
+    assert!(pg_client
+        .query_one("SELECT current_user", &[])
+        .await
+        .is_err());
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
+async fn test_auth_oidc_refresh() {
+    let ca = Ca::new_root("test ca").unwrap();
+    let (server_cert, server_key) = ca
+        .request_cert("server", vec![IpAddr::V4(Ipv4Addr::LOCALHOST)])
+        .unwrap();
+    let encoding_key =
+        EncodingKey::from_rsa_pem(&ca.pkey.private_key_to_pem_pkcs8().unwrap()).unwrap();
+
+    let user = "alice";
+    let refresh_token = "alice-refresh-token";
+
+    // The token endpoint of the identity provider issues short-lived tokens
+    // with the user's current groups, until the refresh token is revoked.
+    let groups = Arc::new(Mutex::new(Some(vec!["analysts"])));
+    let router = Router::new().route(
+        "/token",
+        routing::post({
+            let groups = Arc::clone(&groups);
+            move |Form(form): Form<BTreeMap<String, String>>| {
+                assert_eq!(form["grant_type"], "refresh_token");
+                assert_eq!(form["client_id"], "materialize");
+                let response = match groups.lock().unwrap().as_deref() {
+                    Some(groups) if form["refresh_token"] == refresh_token => {
+                        let access_token =
+                            make_oidc_jwt(&encoding_key, user, groups, "materialize", 5);
+                        Ok(Json(json!({
+                            "access_token": access_token,
+                            "token_type": "Bearer",
+                            "expires_in": 5,
+                        })))
+                    }
+                    _ => Err(StatusCode::BAD_REQUEST),
+                };
+                async move { response }
+            }
+        }),
+    );
+    let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+        .await
+        .unwrap();
+    let token_url = format!("http://{}/token", listener.local_addr().unwrap());
+    let _idp = mz_ore::task::spawn(|| "oidc_token_endpoint", async move {
+        axum::serve(listener, router.into_make_service())
+            .await
+            .unwrap()
+    })
+    .abort_on_drop();
+
+    let oidc_auth = OidcAuthentication::new(OidcConfig {
+        issuer: OIDC_ISSUER.into(),
+        audience: Some("materialize".into()),
+        jwks: JwksSource::Static(make_oidc_jwks(&ca)),
+        user_claim: "preferred_username".into(),
+        groups_claim: DEFAULT_GROUPS_CLAIM.into(),
+        admin_group: None,
+        refresh: Some(OidcRefreshConfig {
+            token_url,
+            client_id: "materialize".into(),
+        }),
+        now: SYSTEM_TIME.clone(),
+    });
+    let server = test_util::TestHarness::default()
+        .with_tls(server_cert, server_key)
+        .with_oidc(&oidc_auth)
+        .start()
+        .await;
+    let admin_client = server.connect().internal().await.unwrap();
+    admin_client
+        .batch_execute("CREATE ROLE analysts")
+        .await
+        .unwrap();
+
+    async fn memberships(client: &tokio_postgres::Client, user: &str) -> Vec<String> {
+        client
+            .query(
+                "SELECT r.name FROM mz_role_members m
+                 JOIN mz_roles r ON m.role_id = r.id
+                 JOIN mz_roles u ON m.member = u.id
+                 WHERE u.name = $1
+                 ORDER BY r.name",
+                &[&user],
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect()
+    }
+
+    // Clients can authenticate with a refresh token in place of a JWT.
+    let pg_client = server
+        .connect()
+        .ssl_mode(SslMode::Require)
+        .user(user)
+        .password(refresh_token)
+        .with_tls(make_pg_tls(Box::new(|b: &mut SslConnectorBuilder| {
+            Ok(b.set_verify(SslVerifyMode::NONE))
+        })))
+        .await
+        .unwrap();
+    assert_eq!(memberships(&pg_client, user).await, ["analysts"]);
+
+    // Revoking a group while the session is open revokes the membership once
+    // the session is refreshed, which outlives the first access token.
+    *groups.lock().unwrap() = Some(vec![]);
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async(|_| async {
+            match memberships(&pg_client, user).await.as_slice() {
+                [] => Ok(()),
+                memberships => Err(format!("still a member of {memberships:?}")),
+            }
+        })
+        .await
+        .unwrap();
+    sleep(Duration::from_secs(6)).await;
+    assert_eq!(
+        pg_client
+            .query_one("SELECT current_user", &[])
+            .await
+            .unwrap()
+            .get::<_, String>(0),
+        user
+    );
+
+    // Granting the group again restores the membership.
+    *groups.lock().unwrap() = Some(vec!["analysts"]);
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async(|_| async {
+            match memberships(&pg_client, user).await.as_slice() {
+                [m] if m == "analysts" => Ok(()),
+                memberships => Err(format!("member of {memberships:?}")),
+            }
+        })
+        .await
+        .unwrap();
+
+    // Once the refresh token is revoked, the session ends with its current
+    // access token.
+    *groups.lock().unwrap() = None;
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async(|_| async {
+            match pg_client.query_one("SELECT current_user", &[]).await {
+                Ok(_) => Err("session still open"),
+                Err(_) => Ok(()),
+            }
+        })
+        .await
+        .unwrap();
+
+    // Refresh tokens that the identity provider doesn't know are rejected.
+    let err = server
+        .connect()
+        .ssl_mode(SslMode::Require)
+        .user(user)
+        .password("unknown-refresh-token")
+        .with_tls(make_pg_tls(Box::new(|b: &mut SslConnectorBuilder| {
+            Ok(b.set_verify(SslVerifyMode::NONE))
+        })))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.as_db_error().unwrap().code(),
+        &SqlState::INVALID_PASSWORD
+    );
+}
+
 #[allow(clippy::unit_arg)]
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
@@ -9,6 +9,7 @@
 
 //! Integration tests for pgwire functionality.
 
+use std::error::Error;
 use std::io::{Read, Write};
 use std::net::TcpStream;
 use std::path::PathBuf;
@@ -23,7 +24,7 @@ use mz_ore::{assert_err, assert_ok};
 use mz_pgrepr::{Numeric, Record};
 use postgres::binary_copy::BinaryCopyOutIter;
 use postgres::error::SqlState;
-use postgres::types::Type;
+use postgres::types::{to_sql_checked, IsNull, Kind, ToSql, Type};
 use postgres::SimpleQueryMessage;
 use postgres_array::{Array, Dimension};
 use tokio::sync::mpsc;
@@ -64,6 +65,46 @@ fn test_bind_params() {
         assert_eq!(val, 42);
     }
 
+    // Ensure that a client can specify a user-defined enum type by its OID.
+    {
+        #[derive(Debug)]
+        struct Label(&'static str);
+
+        impl ToSql for Label {
+            fn to_sql(
+                &self,
+                _: &Type,
+                out: &mut BytesMut,
+            ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+                out.extend_from_slice(self.0.as_bytes());
+                Ok(IsNull::No)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                matches!(ty.kind(), Kind::Enum(_))
+            }
+
+            to_sql_checked!();
+        }
+
+        client
+            .batch_execute("CREATE TYPE mood AS ENUM ('sad', 'ok', 'happy')")
+            .unwrap();
+        let mood = client.prepare("SELECT 'ok'::mood").unwrap().columns()[0]
+            .type_()
+            .clone();
+        assert_eq!(
+            mood.kind(),
+            &Kind::Enum(vec!["sad".into(), "ok".into(), "happy".into()])
+        );
+        let stmt = client
+            .prepare_typed("SELECT $1::text, $1 > 'ok'", &[mood])
+            .unwrap();
+        let row = client.query_one(&stmt, &[&Label("happy")]).unwrap();
+        assert_eq!(row.get::<_, String>(0), "happy");
+        assert!(row.get::<_, bool>(1));
+    }
+
     // Ensure that unspecified type hints are inferred.
     {
         let stmt = client
@@ -530,6 +571,18 @@ fn test_record_types() {
     let record: Record<(i32, String)> = row.get(0);
     assert_eq!(record, Record((321, "123".into())));
 
+    // Named composite types are described with their own OID, so that clients
+    // can look up their fields.
+    let ty = row.columns()[0].type_();
+    assert_eq!(ty.name(), "named_composite");
+    match ty.kind() {
+        Kind::Composite(fields) => {
+            let fields: Vec<_> = fields.iter().map(|f| (f.name(), f.type_())).collect();
+            assert_eq!(fields, [("a", &Type::INT4), ("b", &Type::TEXT)]);
+        }
+        kind => panic!("unexpected kind {kind:?}"),
+    }
+
     client
         .batch_execute("CREATE TABLE has_named_composites (f named_composite)")
         .unwrap();
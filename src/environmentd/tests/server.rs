@@ -226,6 +226,9 @@ fn test_statement_logging_immediate() {
         "DELETE FROM t;",
         "CREATE SECRET s AS 'hunter2';",
         "DROP SECRET s;",
+        "CREATE ROLE r WITH PASSWORD 'r0lepassw0rd';",
+        "ALTER ROLE r WITH PASSWORD 'n3wpassw0rd';",
+        "DROP ROLE r;",
         "",
         "CREATE SOURCE s FROM LOAD GENERATOR COUNTER",
         "PREPARE foo AS SELECT * FROM t",
@@ -241,6 +244,8 @@ fn test_statement_logging_immediate() {
         "SHOW application_name",
     ];
     let constants: &[&str] = &["1", "2", "3", "hunter2", "my_application"];
+    // Secret values must not even show up in the unredacted SQL.
+    let secrets: &[&str] = &["hunter2", "r0lepassw0rd", "n3wpassw0rd"];
 
     for &statement in successful_immediates {
         client.execute(statement, &[]).unwrap();
@@ -296,7 +301,7 @@ ORDER BY mseh.began_at;",
         };
         assert_eq!(r.sample_rate, 1.0);
 
-        let expected_sql = if r.sql.contains("SECRET") {
+        let expected_sql = if r.sql.contains("SECRET") || r.sql.contains("PASSWORD") {
             mz_sql::parse::parse(&r.sql)
                 .unwrap()
                 .into_element()
@@ -306,6 +311,9 @@ ORDER BY mseh.began_at;",
             stmt.chars().filter(|&ch| ch != ';').collect::<String>()
         };
         assert_eq!(r.sql, expected_sql);
+        for secret in secrets {
+            assert!(!r.sql.contains(secret));
+        }
         assert_eq!(r.finished_status, "success");
         assert!(r.prepared_at <= r.began_at);
         assert!(r.began_at <= r.finished_at);
@@ -2703,6 +2703,11 @@ impl AggregateExpr {
             | AggregateFunc::MinTimestampTz
             | AggregateFunc::Any
             | AggregateFunc::All
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::ApproxCountDistinct
+            | AggregateFunc::ApproxPercentile { .. }
             | AggregateFunc::Dummy => self.expr.is_literal(),
             AggregateFunc::Count => self.expr.is_literal_null(),
             _ => self.expr.is_literal_err(),
@@ -2714,8 +2719,9 @@ impl AggregateExpr {
     /// returned by `on_unique`, which is cheaper. (See `ReduceElision`.)
     pub fn on_unique(&self, input_type: &[ColumnType]) -> MirScalarExpr {
         match &self.func {
-            // Count is one if non-null, and zero if null.
-            AggregateFunc::Count => self
+            // Count is one if non-null, and zero if null. A single value is
+            // also counted exactly by `approx_count_distinct`.
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => self
                 .expr
                 .clone()
                 .call_unary(UnaryFunc::IsNull(crate::func::IsNull))
@@ -2817,13 +2823,66 @@ impl AggregateExpr {
                 .clone()
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0))),
 
-            // RowNumber, Rank, DenseRank take a list of records and output a list containing exactly 1 element
+            // RowNumber, Rank, DenseRank, PercentRank, CumeDist take a list of records and output a
+            // list containing exactly 1 element
             AggregateFunc::RowNumber { .. } => {
-                self.on_unique_ranking_window_funcs(input_type, "?row_number?")
+                self.on_unique_ranking_window_funcs(input_type, "?row_number?", Datum::Int64(1))
+            }
+            AggregateFunc::Rank { .. } => {
+                self.on_unique_ranking_window_funcs(input_type, "?rank?", Datum::Int64(1))
             }
-            AggregateFunc::Rank { .. } => self.on_unique_ranking_window_funcs(input_type, "?rank?"),
             AggregateFunc::DenseRank { .. } => {
-                self.on_unique_ranking_window_funcs(input_type, "?dense_rank?")
+                self.on_unique_ranking_window_funcs(input_type, "?dense_rank?", Datum::Int64(1))
+            }
+            AggregateFunc::PercentRank { .. } => self.on_unique_ranking_window_funcs(
+                input_type,
+                "?percent_rank?",
+                Datum::Float64(0.0.into()),
+            ),
+            AggregateFunc::CumeDist { .. } => self.on_unique_ranking_window_funcs(
+                input_type,
+                "?cume_dist?",
+                Datum::Float64(1.0.into()),
+            ),
+
+            // The input type for Ntile is ((OriginalRow, NumBuckets), OrderByExprs...)
+            AggregateFunc::Ntile { .. } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type_with_orig_row = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                let ntile_return_type =
+                    return_type_with_orig_row.unwrap_record_element_type()[0].clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the number of buckets
+                let num_buckets = tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+                let (result_expr, column_name) =
+                    Self::on_unique_ntile(num_buckets, ntile_return_type);
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type_with_orig_row,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![column_name, ColumnName::from("?record?")],
+                        },
+                        exprs: vec![result_expr, original_row],
+                    }],
+                }
             }
 
             // The input type for LagLead is ((OriginalRow, (InputValue, Offset, Default)), OrderByExprs...)
@@ -2953,12 +3012,54 @@ impl AggregateExpr {
                 }
             }
 
+            // The input type for NthValue is ((OriginalRow, (InputValue, N)), OrderByExprs...)
+            AggregateFunc::NthValue { window_frame, .. } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type_with_orig_row = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                let nth_value_return_type =
+                    return_type_with_orig_row.unwrap_record_element_type()[0].clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the encoded args
+                let encoded_args =
+                    tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+                let (result_expr, column_name) =
+                    Self::on_unique_nth_value(window_frame, encoded_args, nth_value_return_type);
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type_with_orig_row,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![column_name, ColumnName::from("?record?")],
+                        },
+                        exprs: vec![result_expr, original_row],
+                    }],
+                }
+            }
+
             // The input type for window aggs is ((OriginalRow, InputValue), OrderByExprs...)
             // See an example MIR in `window_func_applied_to`.
             AggregateFunc::WindowAggregate {
                 wrapped_aggregate,
                 window_frame,
                 order_by: _,
+                distinct: _, // We have just one input element; DISTINCT doesn't matter.
             } => {
                 // TODO: deduplicate code between the various window function cases.
 
@@ -3123,6 +3224,7 @@ impl AggregateExpr {
                         AggregateFunc::FirstValue {
                             window_frame,
                             order_by,
+                            ignore_nulls: _,
                         } => {
                             assert_eq!(order_by, outer_order_by);
                             Self::on_unique_first_value_last_value(
@@ -3134,6 +3236,7 @@ impl AggregateExpr {
                         AggregateFunc::LastValue {
                             window_frame,
                             order_by,
+                            ignore_nulls: _,
                         } => {
                             assert_eq!(order_by, outer_order_by);
                             Self::on_unique_first_value_last_value(
@@ -3142,6 +3245,22 @@ impl AggregateExpr {
                                 return_type_for_func,
                             )
                         }
+                        AggregateFunc::NthValue {
+                            window_frame,
+                            order_by,
+                            ignore_nulls: _,
+                        } => {
+                            assert_eq!(order_by, outer_order_by);
+                            Self::on_unique_nth_value(
+                                window_frame,
+                                args_for_func,
+                                return_type_for_func,
+                            )
+                        }
+                        AggregateFunc::Ntile { order_by } => {
+                            assert_eq!(order_by, outer_order_by);
+                            Self::on_unique_ntile(args_for_func, return_type_for_func)
+                        }
                         _ => panic!("unknown function in FusedValueWindowFunc"),
                     };
                     func_result_exprs.push(result);
@@ -3212,16 +3331,30 @@ impl AggregateExpr {
             | AggregateFunc::SumNumeric
             | AggregateFunc::Any
             | AggregateFunc::All
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            // The percentile of a single value is that value, which is as
+            // accurate as the sketch would be.
+            | AggregateFunc::ApproxPercentile { .. }
             | AggregateFunc::Dummy => self.expr.clone(),
         }
     }
 
-    /// `on_unique` for ROW_NUMBER, RANK, DENSE_RANK
+    /// `on_unique` for ROW_NUMBER, RANK, DENSE_RANK, PERCENT_RANK, CUME_DIST, which all have
+    /// the same `result` for a partition of a single row.
     fn on_unique_ranking_window_funcs(
         &self,
         input_type: &[ColumnType],
         col_name: &str,
+        result: Datum<'static>,
     ) -> MirScalarExpr {
+        let result_type = self
+            .typ(input_type)
+            .scalar_type
+            .unwrap_list_element_type()
+            .unwrap_record_element_type()[0]
+            .clone();
         let list = self
             .expr
             .clone()
@@ -3249,10 +3382,7 @@ impl AggregateExpr {
                 func: VariadicFunc::RecordCreate {
                     field_names: vec![ColumnName::from(col_name), ColumnName::from("?record?")],
                 },
-                exprs: vec![
-                    MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
-                    record,
-                ],
+                exprs: vec![MirScalarExpr::literal_ok(result, result_type), record],
             }],
         }
     }
@@ -3308,6 +3438,50 @@ impl AggregateExpr {
         (result_expr, ColumnName::from("?first_value?"))
     }
 
+    /// `on_unique` for `nth_value`
+    fn on_unique_nth_value(
+        window_frame: &WindowFrame,
+        encoded_args: MirScalarExpr,
+        return_type: ScalarType,
+    ) -> (MirScalarExpr, ColumnName) {
+        let expr = encoded_args
+            .clone()
+            .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+        let n = encoded_args.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+        // If the window frame includes the current (single) row, return its value if N is 1,
+        // null otherwise
+        let result_expr = if window_frame.includes_current_row() {
+            n.call_binary(
+                MirScalarExpr::literal_ok(Datum::Int32(1), ScalarType::Int32),
+                crate::BinaryFunc::Eq,
+            )
+            .if_then_else(expr, MirScalarExpr::literal_null(return_type))
+        } else {
+            MirScalarExpr::literal_null(return_type)
+        };
+        (result_expr, ColumnName::from("?nth_value?"))
+    }
+
+    /// `on_unique` for `ntile`
+    fn on_unique_ntile(
+        num_buckets: MirScalarExpr,
+        return_type: ScalarType,
+    ) -> (MirScalarExpr, ColumnName) {
+        // A single row is in the first bucket, unless the number of buckets is null or not
+        // positive.
+        let result_expr = num_buckets
+            .call_binary(
+                MirScalarExpr::literal_ok(Datum::Int32(0), ScalarType::Int32),
+                crate::BinaryFunc::Gt,
+            )
+            .if_then_else(
+                MirScalarExpr::literal_ok(Datum::Int32(1), ScalarType::Int32),
+                MirScalarExpr::literal_null(return_type),
+            );
+        (result_expr, ColumnName::from("?ntile?"))
+    }
+
     /// `on_unique` for window aggregations
     fn on_unique_window_agg(
         window_frame: &WindowFrame,
@@ -3915,8 +4089,8 @@ pub enum WindowFrameUnits {
     /// Each peer group is treated as the unit of work for bounds,
     /// and offset-based bounds use the value of the ORDER BY expression
     Range,
-    /// Each peer group is treated as the unit of work for bounds.
-    /// Groups is currently not supported, and it is rejected during planning.
+    /// Each peer group is treated as the unit of work for bounds,
+    /// and offset-based bounds count peer groups
     Groups,
 }
 
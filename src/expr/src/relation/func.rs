@@ -10,6 +10,7 @@
 #![allow(missing_docs)]
 
 use std::cmp::{max, min};
+use std::collections::BTreeSet;
 use std::iter::Sum;
 use std::ops::Deref;
 use std::{fmt, iter};
@@ -18,14 +19,14 @@ use chrono::{DateTime, NaiveDateTime, NaiveTime, Utc};
 use dec::OrderedDecimal;
 use itertools::Itertools;
 use mz_lowertest::MzReflect;
-use mz_ore::cast::CastFrom;
+use mz_ore::cast::{CastFrom, CastLossy};
 
-use mz_ore::soft_assert_or_log;
 use mz_ore::str::separated;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::date::Date;
 use mz_repr::adt::interval::Interval;
+use mz_repr::adt::jsonpath::JsonPath;
 use mz_repr::adt::numeric::{self, Numeric, NumericMaxScale};
 use mz_repr::adt::regex::Regex as ReprRegex;
 use mz_repr::adt::timestamp::{CheckedTimestamp, TimestampLike};
@@ -42,18 +43,20 @@ use crate::explain::{HumanizedExpr, HumanizerMode};
 use crate::relation::proto_aggregate_func::{
     self, ProtoColumnOrders, ProtoFusedValueWindowFunc, ProtoFusedWindowAggregate,
 };
-use crate::relation::proto_table_func::ProtoTabletizedScalar;
+use crate::relation::proto_table_func::{ProtoJsonTable, ProtoTabletizedScalar};
 use crate::relation::{
     compare_columns, proto_table_func, ColumnOrder, ProtoAggregateFunc, ProtoTableFunc,
     WindowFrame, WindowFrameBound, WindowFrameUnits,
 };
-use crate::scalar::func::{add_timestamp_months, jsonb_stringify};
+use crate::scalar::func::{add_timestamp_months, eval_jsonb_path, jsonb_stringify};
 use crate::EvalError;
 use crate::WindowFrameBound::{
     CurrentRow, OffsetFollowing, OffsetPreceding, UnboundedFollowing, UnboundedPreceding,
 };
 use crate::WindowFrameUnits::{Groups, Range, Rows};
 
+mod sketch;
+
 include!(concat!(env!("OUT_DIR"), "/mz_expr.relation.func.rs"));
 
 // TODO(jamii) be careful about overflow in sum/avg
@@ -193,6 +196,65 @@ where
         })
 }
 
+/// Collapses weighted datums into their distinct non-null values and the total
+/// multiplicity of each, sorted in ascending order, or in descending order if
+/// `desc` is set.
+///
+/// The input need not be consolidated, but consolidated input makes the sort
+/// proportional to the number of distinct values rather than to the number of
+/// records.
+fn sorted_value_counts<'a, I>(datums: I, desc: bool) -> Vec<(Datum<'a>, usize)>
+where
+    I: IntoIterator<Item = (Datum<'a>, usize)>,
+{
+    let mut values: Vec<_> = datums
+        .into_iter()
+        .filter(|(d, n)| !d.is_null() && *n > 0)
+        .collect();
+    if desc {
+        values.sort_by(|(a, _), (b, _)| b.cmp(a));
+    } else {
+        values.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+    values.dedup_by(|(d, n), (prev_d, prev_n)| {
+        if d == prev_d {
+            *prev_n += *n;
+            true
+        } else {
+            false
+        }
+    });
+    values
+}
+
+/// Returns the value at the zero-based position `idx` in the multiset
+/// described by `values`, the distinct values and their multiplicities in
+/// sorted order.
+fn nth_sorted_value<'a>(values: &[(Datum<'a>, usize)], idx: usize) -> Datum<'a> {
+    let mut seen = 0;
+    for (d, n) in values {
+        seen += n;
+        if idx < seen {
+            return *d;
+        }
+    }
+    unreachable!("index {idx} out of bounds for {seen} values")
+}
+
+/// Returns the most frequent value in the sorted input. Ties are broken in
+/// favor of the value that sorts first.
+fn mode<'a>(values: &[(Datum<'a>, usize)]) -> Datum<'a> {
+    let mut mode = Datum::Null;
+    let mut mode_count = 0;
+    for (d, n) in values {
+        if *n > mode_count {
+            mode = *d;
+            mode_count = *n;
+        }
+    }
+    mode
+}
+
 fn string_agg<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -576,6 +638,230 @@ where
     })
 }
 
+/// The expected input is in the format of `[((OriginalRow, [EncodedArgs]), OrderByExprs...)]`
+/// The output is in the format of `[result_value, original_row]`.
+/// See an example at `lag_lead`, where the input-output formats are similar.
+fn percent_rank<'a, I>(
+    datums: I,
+    callers_temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let temp_storage = RowArena::new();
+    let datums = percent_rank_no_list(datums, &temp_storage, order_by);
+
+    callers_temp_storage.make_datum(|packer| {
+        packer.push_list(datums);
+    })
+}
+
+/// Like `percent_rank`, but doesn't perform the final wrapping in a list, returning an Iterator
+/// instead.
+fn percent_rank_no_list<'a: 'b, 'b, I>(
+    datums: I,
+    callers_temp_storage: &'b RowArena,
+    order_by: &[ColumnOrder],
+) -> impl Iterator<Item = Datum<'b>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // `(rank - 1) / (partition size - 1)`, where `rank - 1` is the number of rows before the
+    // peer group.
+    relative_rank_no_list(
+        datums,
+        callers_temp_storage,
+        order_by,
+        |peer_group_start, _peer_group_end, len| {
+            if len > 1 {
+                f64::cast_lossy(peer_group_start) / f64::cast_lossy(len - 1)
+            } else {
+                0.0
+            }
+        },
+    )
+}
+
+/// The expected input is in the format of `[((OriginalRow, [EncodedArgs]), OrderByExprs...)]`
+/// The output is in the format of `[result_value, original_row]`.
+/// See an example at `lag_lead`, where the input-output formats are similar.
+fn cume_dist<'a, I>(
+    datums: I,
+    callers_temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let temp_storage = RowArena::new();
+    let datums = cume_dist_no_list(datums, &temp_storage, order_by);
+
+    callers_temp_storage.make_datum(|packer| {
+        packer.push_list(datums);
+    })
+}
+
+/// Like `cume_dist`, but doesn't perform the final wrapping in a list, returning an Iterator
+/// instead.
+fn cume_dist_no_list<'a: 'b, 'b, I>(
+    datums: I,
+    callers_temp_storage: &'b RowArena,
+    order_by: &[ColumnOrder],
+) -> impl Iterator<Item = Datum<'b>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // The fraction of rows that are before or peers of the current row.
+    relative_rank_no_list(
+        datums,
+        callers_temp_storage,
+        order_by,
+        |_peer_group_start, peer_group_end, len| {
+            f64::cast_lossy(peer_group_end) / f64::cast_lossy(len)
+        },
+    )
+}
+
+/// The common part of `percent_rank` and `cume_dist`. `relative_rank` computes the result for the
+/// rows of a peer group from the start (inclusive) and end (exclusive) index of the peer group,
+/// and the size of the window partition.
+fn relative_rank_no_list<'a: 'b, 'b, I, F>(
+    datums: I,
+    callers_temp_storage: &'b RowArena,
+    order_by: &[ColumnOrder],
+    relative_rank: F,
+) -> impl Iterator<Item = Datum<'b>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+    F: Fn(usize, usize, usize) -> f64,
+{
+    // Keep the row used for ordering around, as it is used to determine the peer groups
+    let datums = order_aggregate_datums_with_rank(datums, order_by);
+
+    let (original_rows, order_by_rows): (Vec<_>, Vec<_>) = datums
+        .into_iter()
+        .map(|(d0, order_row)| {
+            d0.unwrap_list()
+                .iter()
+                .map(move |d1| (d1, order_row.clone()))
+        })
+        .flatten()
+        .unzip();
+
+    let len = original_rows.len();
+    callers_temp_storage.reserve(len);
+    let mut results = Vec::with_capacity(len);
+    for (&peer_group_start, &peer_group_end) in
+        peer_group_starts(&order_by_rows).iter().tuple_windows()
+    {
+        let result_value =
+            Datum::Float64(relative_rank(peer_group_start, peer_group_end, len).into());
+        for original_row in &original_rows[peer_group_start..peer_group_end] {
+            results.push(callers_temp_storage.make_datum(|packer| {
+                packer.push_list_with(|packer| {
+                    packer.push(result_value);
+                    packer.push(*original_row);
+                });
+            }));
+        }
+    }
+    results.into_iter()
+}
+
+/// Returns the index of the first row of each peer group of a sorted window partition, followed
+/// by the size of the partition. Rows are peers if their ORDER BY values are equal.
+fn peer_group_starts(order_by_rows: &[Row]) -> Vec<usize> {
+    let mut starts = (0..order_by_rows.len())
+        .filter(|i| *i == 0 || order_by_rows[i - 1] != order_by_rows[*i])
+        .collect_vec();
+    starts.push(order_by_rows.len());
+    starts
+}
+
+/// The expected input is in the format of `[((OriginalRow, NumBuckets), OrderByExprs...)]`
+/// The output is in the format of `[result_value, original_row]`.
+/// See an example at `lag_lead`, where the input-output formats are similar.
+fn ntile<'a, I>(
+    datums: I,
+    callers_temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let temp_storage = RowArena::new();
+    let iter = ntile_no_list(datums, &temp_storage, order_by);
+    callers_temp_storage.make_datum(|packer| {
+        packer.push_list(iter);
+    })
+}
+
+/// Like `ntile`, but doesn't perform the final wrapping in a list, returning an Iterator
+/// instead.
+fn ntile_no_list<'a: 'b, 'b, I>(
+    datums: I,
+    callers_temp_storage: &'b RowArena,
+    order_by: &[ColumnOrder],
+) -> impl Iterator<Item = Datum<'b>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Sort the datums according to the ORDER BY expressions and return the (OriginalRow, NumBuckets) record
+    let datums = order_aggregate_datums(datums, order_by);
+
+    // Decode the input (OriginalRow, NumBuckets) into separate datums
+    let (orig_rows, num_buckets): (Vec<_>, Vec<_>) = datums
+        .into_iter()
+        .map(|d| {
+            let mut iter = d.unwrap_list().iter();
+            let original_row = iter.next().unwrap();
+            let num_buckets = iter.next().unwrap();
+            (original_row, num_buckets)
+        })
+        .unzip();
+
+    let results = ntile_inner(&num_buckets);
+
+    callers_temp_storage.reserve(results.len());
+    results
+        .into_iter()
+        .zip_eq(orig_rows)
+        .map(|(result_value, original_row)| {
+            callers_temp_storage.make_datum(|packer| {
+                packer.push_list_with(|packer| {
+                    packer.push(result_value);
+                    packer.push(original_row);
+                });
+            })
+        })
+}
+
+/// Divides the rows of a window partition into buckets numbered from 1, whose sizes differ by at
+/// most one row, with the larger buckets first. As in Postgres, the number of buckets is taken
+/// from the first row of the partition. If it is null or not positive, then every row gets null.
+fn ntile_inner<'a>(num_buckets: &[Datum]) -> Vec<Datum<'a>> {
+    let len = num_buckets.len();
+    let num_buckets = match num_buckets.first() {
+        Some(Datum::Int32(n)) if *n > 0 => usize::cast_from(n.unsigned_abs()),
+        _ => return vec![Datum::Null; len],
+    };
+    let bucket_size = len / num_buckets;
+    // The first `len % num_buckets` buckets get one extra row.
+    let num_large_buckets = len % num_buckets;
+    let rows_in_large_buckets = num_large_buckets * (bucket_size + 1);
+    (0..len)
+        .map(|idx| {
+            let bucket = if idx < rows_in_large_buckets {
+                idx / (bucket_size + 1)
+            } else {
+                num_large_buckets + (idx - rows_in_large_buckets) / bucket_size
+            };
+            Datum::Int32(i32::try_from(bucket + 1).expect("at most the number of buckets"))
+        })
+        .collect()
+}
+
 /// The expected input is in the format of `[((OriginalRow, EncodedArgs), OrderByExprs...)]`
 /// For example,
 ///
@@ -850,12 +1136,13 @@ fn first_value<'a, I>(
     callers_temp_storage: &'a RowArena,
     order_by: &[ColumnOrder],
     window_frame: &WindowFrame,
+    ignore_nulls: bool,
 ) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
     let temp_storage = RowArena::new();
-    let iter = first_value_no_list(datums, &temp_storage, order_by, window_frame);
+    let iter = first_value_no_list(datums, &temp_storage, order_by, window_frame, ignore_nulls);
     callers_temp_storage.make_datum(|packer| {
         packer.push_list(iter);
     })
@@ -868,31 +1155,35 @@ fn first_value_no_list<'a: 'b, 'b, I>(
     callers_temp_storage: &'b RowArena,
     order_by: &[ColumnOrder],
     window_frame: &WindowFrame,
+    ignore_nulls: bool,
 ) -> impl Iterator<Item = Datum<'b>>
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
-    // Sort the datums according to the ORDER BY expressions and return the (OriginalRow, InputValue) record
-    let datums = order_aggregate_datums(datums, order_by);
-
-    // Decode the input (OriginalRow, InputValue) into separate datums
-    let (orig_rows, args): (Vec<_>, Vec<_>) = datums
-        .into_iter()
-        .map(|d| {
-            let mut iter = d.unwrap_list().iter();
-            let original_row = iter.next().unwrap();
-            let arg = iter.next().unwrap();
+    // Sort the datums according to the ORDER BY expressions and return the ((OriginalRow, InputValue), OrderByRow) record
+    // The OrderByRow is kept around because it is required to compute the peer groups in RANGE and GROUPS mode
+    let datums = order_aggregate_datums_with_rank(datums, order_by);
 
-            (original_row, arg)
-        })
-        .unzip();
+    // Decode the input (OriginalRow, InputValue) into separate datums, while keeping the OrderByRow
+    let size_hint = datums.size_hint().0;
+    let mut args = Vec::with_capacity(size_hint);
+    let mut original_rows = Vec::with_capacity(size_hint);
+    let mut order_by_rows = Vec::with_capacity(size_hint);
+    for (d, order_by_row) in datums.into_iter() {
+        let mut iter = d.unwrap_list().iter();
+        let original_row = iter.next().unwrap();
+        let arg = iter.next().unwrap();
+        order_by_rows.push(order_by_row);
+        original_rows.push(original_row);
+        args.push(arg);
+    }
 
-    let results = first_value_inner(args, window_frame);
+    let results = first_value_inner(args, &order_by_rows, order_by, window_frame, ignore_nulls);
 
     callers_temp_storage.reserve(results.len());
     results
         .into_iter()
-        .zip_eq(orig_rows)
+        .zip_eq(original_rows)
         .map(|(result_value, original_row)| {
             callers_temp_storage.make_datum(|packer| {
                 packer.push_list_with(|packer| {
@@ -903,7 +1194,27 @@ where
         })
 }
 
-fn first_value_inner<'a>(datums: Vec<Datum<'a>>, window_frame: &WindowFrame) -> Vec<Datum<'a>> {
+fn first_value_inner<'a>(
+    datums: Vec<Datum<'a>>,
+    order_by_rows: &[Row],
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+    ignore_nulls: bool,
+) -> Vec<Datum<'a>> {
+    // The below handles only frames whose bounds don't depend on peer groups.
+    if ignore_nulls
+        || !(window_frame.units == WindowFrameUnits::Rows
+            || *window_frame == WindowFrame::default())
+    {
+        return framed_values(
+            &datums,
+            order_by_rows,
+            order_by,
+            window_frame,
+            ignore_nulls,
+            FramePosition::Nth(0),
+        );
+    }
     let length = datums.len();
     let mut result: Vec<Datum> = Vec::with_capacity(length);
     for (idx, current_datum) in datums.iter().enumerate() {
@@ -971,12 +1282,13 @@ fn last_value<'a, I>(
     callers_temp_storage: &'a RowArena,
     order_by: &[ColumnOrder],
     window_frame: &WindowFrame,
+    ignore_nulls: bool,
 ) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
     let temp_storage = RowArena::new();
-    let iter = last_value_no_list(datums, &temp_storage, order_by, window_frame);
+    let iter = last_value_no_list(datums, &temp_storage, order_by, window_frame, ignore_nulls);
     callers_temp_storage.make_datum(|packer| {
         packer.push_list(iter);
     })
@@ -989,6 +1301,7 @@ fn last_value_no_list<'a: 'b, 'b, I>(
     callers_temp_storage: &'b RowArena,
     order_by: &[ColumnOrder],
     window_frame: &WindowFrame,
+    ignore_nulls: bool,
 ) -> impl Iterator<Item = Datum<'b>>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -1011,7 +1324,7 @@ where
         args.push(arg);
     }
 
-    let results = last_value_inner(args, &order_by_rows, window_frame);
+    let results = last_value_inner(args, &order_by_rows, order_by, window_frame, ignore_nulls);
 
     callers_temp_storage.reserve(results.len());
     results
@@ -1030,8 +1343,25 @@ where
 fn last_value_inner<'a>(
     args: Vec<Datum<'a>>,
     order_by_rows: &Vec<Row>,
+    order_by: &[ColumnOrder],
     window_frame: &WindowFrame,
+    ignore_nulls: bool,
 ) -> Vec<Datum<'a>> {
+    // The below handles only frames whose bounds depend on peer groups just for the default
+    // frame.
+    if ignore_nulls
+        || !(window_frame.units == WindowFrameUnits::Rows
+            || *window_frame == WindowFrame::default())
+    {
+        return framed_values(
+            &args,
+            order_by_rows,
+            order_by,
+            window_frame,
+            ignore_nulls,
+            FramePosition::Last,
+        );
+    }
     let length = args.len();
     let mut results: Vec<Datum> = Vec::with_capacity(length);
     for (idx, (current_datum, order_by_row)) in args.iter().zip_eq(order_by_rows).enumerate() {
@@ -1042,8 +1372,8 @@ fn last_value_inner<'a>(
                 WindowFrameUnits::Range => {
                     // When in RANGE mode, return the last value of the peer group
                     // The peer group is the group of rows with the same ORDER BY value
-                    // Note: Range is only supported for the default window frame (RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW),
-                    // which is why it does not appear in the other branches
+                    // Note: Other RANGE frames than the default one (RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)
+                    // are handled by `framed_values`, which is why it does not appear in the other branches
                     let target_idx = order_by_rows[idx..]
                         .iter()
                         .enumerate()
@@ -1054,7 +1384,7 @@ fn last_value_inner<'a>(
                         + idx;
                     args[target_idx]
                 }
-                // GROUPS is not supported, and forbidden during planning
+                // GROUPS frames are handled by `framed_values`
                 WindowFrameUnits::Groups => unreachable!(),
             },
             WindowFrameBound::UnboundedFollowing => {
@@ -1116,11 +1446,384 @@ fn last_value_inner<'a>(
     results
 }
 
+/// The expected input is in the format of `[((OriginalRow, (InputValue, N)), OrderByExprs...)]`
+fn nth_value<'a, I>(
+    datums: I,
+    callers_temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+    ignore_nulls: bool,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let temp_storage = RowArena::new();
+    let iter = nth_value_no_list(datums, &temp_storage, order_by, window_frame, ignore_nulls);
+    callers_temp_storage.make_datum(|packer| {
+        packer.push_list(iter);
+    })
+}
+
+/// Like `nth_value`, but doesn't perform the final wrapping in a list, returning an Iterator
+/// instead.
+fn nth_value_no_list<'a: 'b, 'b, I>(
+    datums: I,
+    callers_temp_storage: &'b RowArena,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+    ignore_nulls: bool,
+) -> impl Iterator<Item = Datum<'b>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Sort the datums according to the ORDER BY expressions and return the ((OriginalRow, (InputValue, N)), OrderByRow) record
+    // The OrderByRow is kept around because it is required to compute the peer groups in RANGE and GROUPS mode
+    let datums = order_aggregate_datums_with_rank(datums, order_by);
+
+    // Decode the input (OriginalRow, (InputValue, N)) into separate datums, while keeping the OrderByRow
+    let size_hint = datums.size_hint().0;
+    let mut argss = Vec::with_capacity(size_hint);
+    let mut original_rows = Vec::with_capacity(size_hint);
+    let mut order_by_rows = Vec::with_capacity(size_hint);
+    for (d, order_by_row) in datums.into_iter() {
+        let mut iter = d.unwrap_list().iter();
+        let original_row = iter.next().unwrap();
+        let encoded_args = iter.next().unwrap();
+        order_by_rows.push(order_by_row);
+        original_rows.push(original_row);
+        argss.push(unwrap_nth_value_encoded_args(encoded_args));
+    }
+
+    let results = nth_value_inner(argss, &order_by_rows, order_by, window_frame, ignore_nulls);
+
+    callers_temp_storage.reserve(results.len());
+    results
+        .into_iter()
+        .zip_eq(original_rows)
+        .map(|(result_value, original_row)| {
+            callers_temp_storage.make_datum(|packer| {
+                packer.push_list_with(|packer| {
+                    packer.push(result_value);
+                    packer.push(original_row);
+                });
+            })
+        })
+}
+
+/// Unwraps the `(InputValue, N)` record of `nth_value`.
+fn unwrap_nth_value_encoded_args(encoded_args: Datum) -> (Datum, Datum) {
+    let mut encoded_args_iter = encoded_args.unwrap_list().iter();
+    let (input_value, n) = (
+        encoded_args_iter.next().unwrap(),
+        encoded_args_iter.next().unwrap(),
+    );
+    (input_value, n)
+}
+
+/// Each element of `argss` contains the input value and `N` of a row. As in Postgres, `N` is
+/// evaluated for each row separately. The result is null if `N` is null or not positive.
+fn nth_value_inner<'a>(
+    argss: Vec<(Datum<'a>, Datum<'a>)>,
+    order_by_rows: &[Row],
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+    ignore_nulls: bool,
+) -> Vec<Datum<'a>> {
+    let (args, ns): (Vec<_>, Vec<_>) = argss.into_iter().unzip();
+    let non_nulls = ignore_nulls.then(|| NonNullPositions::new(&args));
+    window_frame_bounds(args.len(), order_by_rows, order_by, window_frame)
+        .into_iter()
+        .zip_eq(ns)
+        .map(|(frame, n)| match n {
+            Datum::Int32(n) if n > 0 => {
+                let position = FramePosition::Nth(usize::cast_from(n.unsigned_abs()) - 1);
+                value_in_frame(&args, non_nulls.as_ref(), frame, position)
+            }
+            _ => Datum::Null,
+        })
+        .collect()
+}
+
+/// Which value of a window frame a value window function returns.
+#[derive(Clone, Copy, Debug)]
+enum FramePosition {
+    /// The value at the given (0-based) position from the start of the frame.
+    Nth(usize),
+    /// The last value of the frame.
+    Last,
+}
+
+/// Returns the value at `position` in the frame of each row of a window partition. If
+/// `ignore_nulls`, then only non-null values are considered.
+fn framed_values<'a>(
+    args: &[Datum<'a>],
+    order_by_rows: &[Row],
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+    ignore_nulls: bool,
+    position: FramePosition,
+) -> Vec<Datum<'a>> {
+    let non_nulls = ignore_nulls.then(|| NonNullPositions::new(args));
+    window_frame_bounds(args.len(), order_by_rows, order_by, window_frame)
+        .into_iter()
+        .map(|frame| value_in_frame(args, non_nulls.as_ref(), frame, position))
+        .collect()
+}
+
+/// Returns the value at `position` in the given frame of `args`, or null if the frame is not long
+/// enough. If `non_nulls` is given, then null values are skipped.
+fn value_in_frame<'a>(
+    args: &[Datum<'a>],
+    non_nulls: Option<&NonNullPositions>,
+    (frame_start, frame_end): (usize, usize),
+    position: FramePosition,
+) -> Datum<'a> {
+    let idx = match (non_nulls, position) {
+        (None, FramePosition::Nth(n)) => frame_start.checked_add(n).filter(|i| *i < frame_end),
+        (None, FramePosition::Last) => (frame_start < frame_end).then(|| frame_end - 1),
+        (Some(non_nulls), FramePosition::Nth(n)) => non_nulls.nth(frame_start, frame_end, n),
+        (Some(non_nulls), FramePosition::Last) => non_nulls.last(frame_start, frame_end),
+    };
+    idx.map_or(Datum::Null, |i| args[i])
+}
+
+/// Indexes the non-null values of a window partition, so that the `n`th non-null value of any
+/// frame can be found in constant time.
+#[derive(Debug)]
+struct NonNullPositions {
+    /// The indexes of the non-null values, in increasing order.
+    positions: Vec<usize>,
+    /// For each index of the partition, the number of non-null values before it. There is one
+    /// more element at the end, which is the number of all non-null values.
+    counts_before: Vec<usize>,
+}
+
+impl NonNullPositions {
+    fn new(args: &[Datum]) -> Self {
+        let mut positions = Vec::new();
+        let mut counts_before = Vec::with_capacity(args.len() + 1);
+        for (idx, arg) in args.iter().enumerate() {
+            counts_before.push(positions.len());
+            if !arg.is_null() {
+                positions.push(idx);
+            }
+        }
+        counts_before.push(positions.len());
+        NonNullPositions {
+            positions,
+            counts_before,
+        }
+    }
+
+    /// Returns the index of the `n`th (0-based) non-null value in `frame_start..frame_end`.
+    fn nth(&self, frame_start: usize, frame_end: usize, n: usize) -> Option<usize> {
+        let position = self.counts_before[frame_start].checked_add(n)?;
+        self.positions
+            .get(position)
+            .copied()
+            .filter(|idx| *idx < frame_end)
+    }
+
+    /// Returns the index of the last non-null value in `frame_start..frame_end`.
+    fn last(&self, frame_start: usize, frame_end: usize) -> Option<usize> {
+        let count_before_end = self.counts_before[frame_end];
+        (count_before_end > self.counts_before[frame_start])
+            .then(|| self.positions[count_before_end - 1])
+    }
+}
+
+/// Computes the frame of each row of a sorted window partition of size `len`. A frame is given by
+/// the index of its first row and the index just after its last row, which are equal for empty
+/// frames.
+///
+/// `order_by_rows` are the ORDER BY values of the rows, which determine the peer groups in RANGE
+/// and GROUPS mode, and the frame bounds with offsets in RANGE mode. They are not used in ROWS
+/// mode.
+fn window_frame_bounds(
+    len: usize,
+    order_by_rows: &[Row],
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+) -> Vec<(usize, usize)> {
+    // In ROWS mode, each row is a peer group on its own.
+    let group_starts = match window_frame.units {
+        Rows => (0..=len).collect_vec(),
+        Range | Groups => peer_group_starts(order_by_rows),
+    };
+    let num_groups = group_starts.len() - 1;
+    let range_search = match window_frame.units {
+        Range => Some(RangeFrameSearch::new(order_by_rows, order_by)),
+        Rows | Groups => None,
+    };
+
+    // Returns where the given bound puts the frame boundary for the given row of the given peer
+    // group, if it's an offset in RANGE mode. As in Postgres, the offset bounds of a row whose
+    // ORDER BY value is null are the bounds of its peer group.
+    let range_boundary = |row: usize, group: usize, bound: &WindowFrameBound, frame_start: bool| {
+        let range_search = range_search.as_ref()?;
+        let (offset, preceding) = match bound {
+            OffsetPreceding(offset) => (*offset, true),
+            OffsetFollowing(offset) => (*offset, false),
+            UnboundedPreceding | CurrentRow | UnboundedFollowing => return None,
+        };
+        let peer_group_boundary = if frame_start {
+            group_starts[group]
+        } else {
+            group_starts[group + 1]
+        };
+        Some(
+            range_search
+                .boundary(row, offset, preceding, frame_start)
+                .unwrap_or(peer_group_boundary),
+        )
+    };
+    // Returns where the given bound puts the frame boundary when counting peer groups from the
+    // start of the given peer group.
+    let group_boundary = |group: usize, bound: &WindowFrameBound| match bound {
+        UnboundedPreceding => 0,
+        OffsetPreceding(offset) => group_starts[group.saturating_sub(usize::cast_from(*offset))],
+        CurrentRow => group_starts[group],
+        OffsetFollowing(offset) => {
+            group_starts[min(group.saturating_add(usize::cast_from(*offset)), num_groups)]
+        }
+        UnboundedFollowing => len,
+    };
+
+    let mut frames = Vec::with_capacity(len);
+    for (group, (&group_start, &group_end)) in group_starts.iter().tuple_windows().enumerate() {
+        for row in group_start..group_end {
+            let frame_start = range_boundary(row, group, &window_frame.start_bound, true)
+                .unwrap_or_else(|| group_boundary(group, &window_frame.start_bound));
+            let frame_end = range_boundary(row, group, &window_frame.end_bound, false)
+                .unwrap_or_else(|| group_boundary(group + 1, &window_frame.end_bound));
+            frames.push((frame_start, max(frame_start, frame_end)));
+        }
+    }
+    frames
+}
+
+/// Finds the boundaries of RANGE frames with offsets, which are determined by comparing the value
+/// of the (single) ORDER BY column with the value of the current row plus or minus the offset.
+struct RangeFrameSearch<'a> {
+    /// The ORDER BY values of the rows.
+    keys: Vec<Datum<'a>>,
+    /// Whether the partition is sorted in descending order.
+    desc: bool,
+    /// The rows whose ORDER BY value is not null. (Nulls are sorted to one end of the partition.)
+    non_null_start: usize,
+    non_null_end: usize,
+}
+
+impl<'a> RangeFrameSearch<'a> {
+    fn new(order_by_rows: &'a [Row], order_by: &[ColumnOrder]) -> Self {
+        assert_eq!(
+            order_by.len(),
+            1,
+            "the planner allows offsets in RANGE mode only with one ORDER BY column"
+        );
+        let keys = order_by_rows
+            .iter()
+            .map(|row| row.unpack_first())
+            .collect_vec();
+        let non_null_start = keys
+            .iter()
+            .position(|key| !key.is_null())
+            .unwrap_or(keys.len());
+        let non_null_end = keys
+            .iter()
+            .rposition(|key| !key.is_null())
+            .map_or(non_null_start, |idx| idx + 1);
+        RangeFrameSearch {
+            keys,
+            desc: order_by[0].desc,
+            non_null_start,
+            non_null_end,
+        }
+    }
+
+    /// Returns the first row that belongs to the frame of `row` (if `frame_start`) or the first row
+    /// after the frame (if not `frame_start`), when the frame bound is `offset` PRECEDING (if
+    /// `preceding`) or FOLLOWING. Returns `None` if the value of `row` is null.
+    fn boundary(
+        &self,
+        row: usize,
+        offset: u64,
+        preceding: bool,
+        frame_start: bool,
+    ) -> Option<usize> {
+        let key = self.keys[row];
+        if key.is_null() {
+            return None;
+        }
+        // PRECEDING moves towards smaller values in ascending order, but towards larger values in
+        // descending order.
+        let target = match range_frame_target(key, offset, preceding != self.desc) {
+            Some(target) => target,
+            // The target is out of the range of the type, so it is beyond all values.
+            None if preceding => return Some(self.non_null_start),
+            None => return Some(self.non_null_end),
+        };
+        let keys = &self.keys[self.non_null_start..self.non_null_end];
+        let before_boundary = |key: &Datum| match (self.desc, frame_start) {
+            (false, true) => *key < target,
+            (false, false) => *key <= target,
+            (true, true) => *key > target,
+            (true, false) => *key >= target,
+        };
+        Some(self.non_null_start + keys.partition_point(before_boundary))
+    }
+}
+
+/// Returns `value` minus (if `subtract`) or plus `offset`, or `None` if the result is out of the
+/// range of the type of `value`. The planner allows offsets in RANGE mode only with numeric ORDER
+/// BY columns.
+fn range_frame_target<'a>(value: Datum<'a>, offset: u64, subtract: bool) -> Option<Datum<'a>> {
+    let shift = |value: i128| {
+        if subtract {
+            value - i128::from(offset)
+        } else {
+            value + i128::from(offset)
+        }
+    };
+    let target = match value {
+        Datum::Int16(v) => Datum::Int16(i16::try_from(shift(v.into())).ok()?),
+        Datum::Int32(v) => Datum::Int32(i32::try_from(shift(v.into())).ok()?),
+        Datum::Int64(v) => Datum::Int64(i64::try_from(shift(v.into())).ok()?),
+        Datum::UInt16(v) => Datum::UInt16(u16::try_from(shift(v.into())).ok()?),
+        Datum::UInt32(v) => Datum::UInt32(u32::try_from(shift(v.into())).ok()?),
+        Datum::UInt64(v) => Datum::UInt64(u64::try_from(shift(v.into())).ok()?),
+        Datum::Float32(v) => {
+            let offset = offset.to_f32()?;
+            Datum::Float32(if subtract { *v - offset } else { *v + offset }.into())
+        }
+        Datum::Float64(v) => {
+            let offset = offset.to_f64()?;
+            Datum::Float64(if subtract { *v - offset } else { *v + offset }.into())
+        }
+        Datum::Numeric(v) => {
+            let mut cx = numeric::cx_datum();
+            let mut target = v.0;
+            let offset = Numeric::from(offset);
+            if subtract {
+                cx.sub(&mut target, &offset);
+            } else {
+                cx.add(&mut target, &offset);
+            }
+            if cx.status().overflow() {
+                return None;
+            }
+            Datum::from(target)
+        }
+        _ => unreachable!("the planner allows offsets in RANGE mode only for numeric types"),
+    };
+    Some(target)
+}
+
 /// Executes `FusedValueWindowFunc` on a reduction group.
 /// The expected input is in the format of `[((OriginalRow, (Args1, Args2, ...)), OrderByExprs...)]`
 /// where `Args1`, `Args2`, are the arguments of each of the fused functions. For functions that
-/// have only a single argument (first_value/last_value), these are simple values. For functions
-/// that have multiple arguments (lag/lead), these are also records.
+/// have only a single argument (first_value/last_value/ntile), these are simple values. For functions
+/// that have multiple arguments (lag/lead/nth_value), these are also records.
 fn fused_value_window_func<'a, I>(
     input_datums: I,
     callers_temp_storage: &'a RowArena,
@@ -1148,9 +1851,15 @@ fn fused_value_window_func_no_list<'a: 'b, 'b, I>(
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
-    let has_last_value = funcs
-        .iter()
-        .any(|f| matches!(f, AggregateFunc::LastValue { .. }));
+    // The OrderByRows are required to compute the peer groups in RANGE and GROUPS mode
+    let needs_order_by_rows = funcs.iter().any(|f| {
+        matches!(
+            f,
+            AggregateFunc::FirstValue { .. }
+                | AggregateFunc::LastValue { .. }
+                | AggregateFunc::NthValue { .. }
+        )
+    });
 
     let input_datums_with_ranks = order_aggregate_datums_with_rank(input_datums, order_by);
 
@@ -1167,7 +1876,7 @@ where
             let encoded_args = argss_iter.next().unwrap();
             encoded_argsss[i].push(encoded_args);
         }
-        if has_last_value {
+        if needs_order_by_rows {
             order_by_rows.push(order_by_row);
         }
     }
@@ -1190,20 +1899,60 @@ where
             AggregateFunc::FirstValue {
                 order_by: inner_order_by,
                 window_frame,
+                ignore_nulls,
             } => {
                 assert_eq!(order_by, inner_order_by);
                 // (No unwrapping to do on the args here, because there is only 1 arg, so it's not
                 // wrapped into a record.)
-                first_value_inner(encoded_argss, window_frame)
+                first_value_inner(
+                    encoded_argss,
+                    &order_by_rows,
+                    order_by,
+                    window_frame,
+                    *ignore_nulls,
+                )
             }
             AggregateFunc::LastValue {
                 order_by: inner_order_by,
                 window_frame,
+                ignore_nulls,
+            } => {
+                assert_eq!(order_by, inner_order_by);
+                // (No unwrapping to do on the args here, because there is only 1 arg, so it's not
+                // wrapped into a record.)
+                last_value_inner(
+                    encoded_argss,
+                    &order_by_rows,
+                    order_by,
+                    window_frame,
+                    *ignore_nulls,
+                )
+            }
+            AggregateFunc::NthValue {
+                order_by: inner_order_by,
+                window_frame,
+                ignore_nulls,
+            } => {
+                assert_eq!(order_by, inner_order_by);
+                let unwrapped_argss = encoded_argss
+                    .into_iter()
+                    .map(|encoded_args| unwrap_nth_value_encoded_args(encoded_args))
+                    .collect();
+                nth_value_inner(
+                    unwrapped_argss,
+                    &order_by_rows,
+                    order_by,
+                    window_frame,
+                    *ignore_nulls,
+                )
+            }
+            AggregateFunc::Ntile {
+                order_by: inner_order_by,
             } => {
                 assert_eq!(order_by, inner_order_by);
                 // (No unwrapping to do on the args here, because there is only 1 arg, so it's not
                 // wrapped into a record.)
-                last_value_inner(encoded_argss, &order_by_rows, window_frame)
+                ntile_inner(&encoded_argss)
             }
             _ => panic!("unknown window function in FusedValueWindowFunc"),
         };
@@ -1241,6 +1990,7 @@ fn window_aggr<'a, I, A>(
     wrapped_aggregate: &AggregateFunc,
     order_by: &[ColumnOrder],
     window_frame: &WindowFrame,
+    distinct: bool,
 ) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -1253,6 +2003,7 @@ where
         wrapped_aggregate,
         order_by,
         window_frame,
+        distinct,
     );
     callers_temp_storage.make_datum(|packer| {
         packer.push_list(iter);
@@ -1267,6 +2018,7 @@ fn window_aggr_no_list<'a: 'b, 'b, I, A>(
     wrapped_aggregate: &AggregateFunc,
     order_by: &[ColumnOrder],
     window_frame: &WindowFrame,
+    distinct: bool,
 ) -> impl Iterator<Item = Datum<'b>>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -1296,6 +2048,7 @@ where
         wrapped_aggregate,
         order_by,
         window_frame,
+        distinct,
         callers_temp_storage,
     );
 
@@ -1313,12 +2066,14 @@ where
         })
 }
 
+/// If `distinct`, then each distinct value of a frame is fed into `wrapped_aggregate` only once.
 fn window_aggr_inner<'a, A>(
     mut args: Vec<Datum<'a>>,
     order_by_rows: &Vec<Row>,
     wrapped_aggregate: &AggregateFunc,
     order_by: &[ColumnOrder],
     window_frame: &WindowFrame,
+    distinct: bool,
     temp_storage: &'a RowArena,
 ) -> Vec<Datum<'a>>
 where
@@ -1327,19 +2082,19 @@ where
     let length = args.len();
     let mut result: Vec<Datum> = Vec::with_capacity(length);
 
-    // In this degenerate case, all results would be `wrapped_aggregate.default()` (usually null).
-    // However, this currently can't happen, because
-    // - Groups frame mode is currently not supported;
-    // - Range frame mode is currently supported only for the default frame, which includes the
-    //   current row.
-    soft_assert_or_log!(
-        !((matches!(window_frame.units, WindowFrameUnits::Groups)
-            || matches!(window_frame.units, WindowFrameUnits::Range))
-            && !window_frame.includes_current_row()),
-        "window frame without current row"
-    );
-
-    if (matches!(
+    if distinct {
+        // The specialized implementations below feed each value into the aggregation exactly once
+        // per frame, so they can't deduplicate.
+        let frames = window_frame_bounds(length, order_by_rows, order_by, window_frame);
+        aggregate_frames::<A>(
+            args,
+            &frames,
+            &mut result,
+            wrapped_aggregate,
+            true,
+            temp_storage,
+        );
+    } else if (matches!(
         window_frame.start_bound,
         WindowFrameBound::UnboundedPreceding
     ) && matches!(window_frame.end_bound, WindowFrameBound::UnboundedFollowing))
@@ -1492,9 +2247,9 @@ where
                 );
                 result.reverse();
             }
-            (Range, UnboundedPreceding, CurrentRow) => {
-                // Note that for the default frame, the RANGE frame mode is identical to the GROUPS
-                // frame mode.
+            (Range | Groups, UnboundedPreceding, CurrentRow) => {
+                // Note that for this frame, the RANGE frame mode is identical to the GROUPS frame
+                // mode.
                 groups_between_unbounded_preceding_and_current_row::<A>(
                     args,
                     order_by_rows,
@@ -1613,25 +2368,21 @@ where
             (Rows, UnboundedPreceding, OffsetPreceding(_))
             | (Rows, UnboundedPreceding, OffsetFollowing(_))
             | (Rows, OffsetPreceding(..), UnboundedFollowing)
-            | (Rows, OffsetFollowing(..), UnboundedFollowing) => {
-                // Unsupported. Bail in the planner.
-                // https://github.com/MaterializeInc/database-issues/issues/6720
-                unreachable!()
-            }
-            (Range, _, _) => {
-                // Unsupported.
-                // The planner doesn't allow Range frame mode for now (except for the default
-                // frame), see https://github.com/MaterializeInc/database-issues/issues/6585
-                // Note that it would be easy to handle (Range, CurrentRow, UnboundedFollowing):
-                // it would be similar to (Rows, CurrentRow, UnboundedFollowing), but would call
-                // groups_between_unbounded_preceding_current_row.
-                unreachable!()
-            }
-            (Groups, _, _) => {
-                // Unsupported.
-                // The planner doesn't allow Groups frame mode for now, see
-                // https://github.com/MaterializeInc/database-issues/issues/6588
-                unreachable!()
+            | (Rows, OffsetFollowing(..), UnboundedFollowing)
+            | (Range, _, _)
+            | (Groups, _, _) => {
+                // The remaining frames are computed from their bounds. Frames that only grow in
+                // one direction (e.g., mixed unbounded - offset frames) are still computed
+                // incrementally.
+                let frames = window_frame_bounds(length, order_by_rows, order_by, window_frame);
+                aggregate_frames::<A>(
+                    args,
+                    &frames,
+                    &mut result,
+                    wrapped_aggregate,
+                    false,
+                    temp_storage,
+                );
             }
         }
     }
@@ -1639,6 +2390,78 @@ where
     result
 }
 
+/// Computes `wrapped_aggregate` on each of the given `frames` of `args`. (See `window_frame_bounds`
+/// for how frames are represented.) If `distinct`, then each distinct value of a frame is
+/// aggregated only once.
+fn aggregate_frames<'a, A>(
+    args: Vec<Datum<'a>>,
+    frames: &[(usize, usize)],
+    result: &mut Vec<Datum<'a>>,
+    wrapped_aggregate: &AggregateFunc,
+    distinct: bool,
+    temp_storage: &'a RowArena,
+) where
+    A: OneByOneAggr,
+{
+    let len = args.len();
+    let grows_forward = frames.iter().all(|(start, _)| *start == 0)
+        && frames.iter().tuple_windows().all(|(a, b)| a.1 <= b.1);
+    let grows_backward = frames.iter().all(|(_, end)| *end == len)
+        && frames.iter().tuple_windows().all(|(a, b)| a.0 <= b.0);
+    if grows_forward || grows_backward {
+        // Each frame contains the previous one (or the next one, when growing backward), so we can
+        // give the values to the aggregation one by one.
+        let mut one_by_one_aggr = A::new(wrapped_aggregate, !grows_forward);
+        let mut seen = BTreeSet::new();
+        let mut give = |one_by_one_aggr: &mut A, arg: Datum<'a>| {
+            if !distinct || seen.insert(arg) {
+                one_by_one_aggr.give(&arg);
+            }
+        };
+        let first_result = result.len();
+        if grows_forward {
+            let mut next = 0;
+            for (frame_start, frame_end) in frames {
+                while next < *frame_end {
+                    give(&mut one_by_one_aggr, args[next]);
+                    next += 1;
+                }
+                result.push(if frame_start == frame_end {
+                    wrapped_aggregate.default()
+                } else {
+                    one_by_one_aggr.get_current_aggregate(temp_storage)
+                });
+            }
+        } else {
+            let mut next = len;
+            for (frame_start, frame_end) in frames.iter().rev() {
+                while next > *frame_start {
+                    next -= 1;
+                    give(&mut one_by_one_aggr, args[next]);
+                }
+                result.push(if frame_start == frame_end {
+                    wrapped_aggregate.default()
+                } else {
+                    one_by_one_aggr.get_current_aggregate(temp_storage)
+                });
+            }
+            result[first_result..].reverse();
+        }
+    } else {
+        for (frame_start, frame_end) in frames {
+            let frame_values = args[*frame_start..*frame_end].iter().cloned();
+            let result_value = if frame_start == frame_end {
+                wrapped_aggregate.default()
+            } else if distinct {
+                wrapped_aggregate.eval(frame_values.collect::<BTreeSet<_>>(), temp_storage)
+            } else {
+                wrapped_aggregate.eval(frame_values, temp_storage)
+            };
+            result.push(result_value);
+        }
+    }
+}
+
 /// Computes a bundle of fused window aggregations.
 /// The input is similar to `window_aggr`, but `InputValue` is not just a single value, but a record
 /// where each component is the input to one of the aggregations.
@@ -1708,6 +2531,7 @@ where
             wrapped_aggr,
             order_by,
             window_frame,
+            false,
             callers_temp_storage,
         );
         for (results, result) in results_per_row.iter_mut().zip_eq(results) {
@@ -1892,6 +2716,15 @@ pub enum AggregateFunc {
     DenseRank {
         order_by: Vec<ColumnOrder>,
     },
+    PercentRank {
+        order_by: Vec<ColumnOrder>,
+    },
+    CumeDist {
+        order_by: Vec<ColumnOrder>,
+    },
+    Ntile {
+        order_by: Vec<ColumnOrder>,
+    },
     LagLead {
         order_by: Vec<ColumnOrder>,
         lag_lead: LagLeadType,
@@ -1900,10 +2733,17 @@ pub enum AggregateFunc {
     FirstValue {
         order_by: Vec<ColumnOrder>,
         window_frame: WindowFrame,
+        ignore_nulls: bool,
     },
     LastValue {
         order_by: Vec<ColumnOrder>,
         window_frame: WindowFrame,
+        ignore_nulls: bool,
+    },
+    NthValue {
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+        ignore_nulls: bool,
     },
     /// Several value window functions fused into one function, to amortize overheads.
     FusedValueWindowFunc {
@@ -1916,12 +2756,39 @@ pub enum AggregateFunc {
         wrapped_aggregate: Box<AggregateFunc>,
         order_by: Vec<ColumnOrder>,
         window_frame: WindowFrame,
+        /// Whether the wrapped aggregate is computed over the distinct values of each frame.
+        distinct: bool,
     },
     FusedWindowAggregate {
         wrapped_aggregates: Vec<AggregateFunc>,
         order_by: Vec<ColumnOrder>,
         window_frame: WindowFrame,
     },
+    /// Computes the value at `fraction` of the way through the sorted
+    /// `Datum::Float64`s, interpolating between adjacent values if needed.
+    PercentileCont {
+        fraction: OrderedFloat<f64>,
+        desc: bool,
+    },
+    /// Computes the first value whose position in the sorted input equals or
+    /// exceeds `fraction`.
+    PercentileDisc {
+        fraction: OrderedFloat<f64>,
+        desc: bool,
+    },
+    /// Computes the most frequent value, preferring the value that sorts first
+    /// in case of ties.
+    Mode {
+        desc: bool,
+    },
+    /// Estimates the number of distinct non-null values with a HyperLogLog
+    /// sketch.
+    ApproxCountDistinct,
+    /// Estimates the value at `fraction` of the way through the sorted
+    /// `Datum::Float64`s with a relative-error quantile sketch.
+    ApproxPercentile {
+        fraction: OrderedFloat<f64>,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -2020,15 +2887,37 @@ impl Arbitrary for AggregateFunc {
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::DenseRank { order_by })
                 .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::PercentRank { order_by })
+                .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::CumeDist { order_by })
+                .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::Ntile { order_by })
+                .boxed(),
+            (
+                vec(proptest_any::<ColumnOrder>(), 1..4),
+                proptest_any::<LagLeadType>(),
+                proptest_any::<bool>(),
+            )
+                .prop_map(
+                    |(order_by, lag_lead, ignore_nulls)| AggregateFunc::LagLead {
+                        order_by,
+                        lag_lead,
+                        ignore_nulls,
+                    },
+                )
+                .boxed(),
             (
                 vec(proptest_any::<ColumnOrder>(), 1..4),
-                proptest_any::<LagLeadType>(),
+                proptest_any::<WindowFrame>(),
                 proptest_any::<bool>(),
             )
                 .prop_map(
-                    |(order_by, lag_lead, ignore_nulls)| AggregateFunc::LagLead {
+                    |(order_by, window_frame, ignore_nulls)| AggregateFunc::FirstValue {
                         order_by,
-                        lag_lead,
+                        window_frame,
                         ignore_nulls,
                     },
                 )
@@ -2036,19 +2925,48 @@ impl Arbitrary for AggregateFunc {
             (
                 vec(proptest_any::<ColumnOrder>(), 1..4),
                 proptest_any::<WindowFrame>(),
+                proptest_any::<bool>(),
             )
-                .prop_map(|(order_by, window_frame)| AggregateFunc::FirstValue {
-                    order_by,
-                    window_frame,
-                })
+                .prop_map(
+                    |(order_by, window_frame, ignore_nulls)| AggregateFunc::LastValue {
+                        order_by,
+                        window_frame,
+                        ignore_nulls,
+                    },
+                )
                 .boxed(),
             (
                 vec(proptest_any::<ColumnOrder>(), 1..4),
                 proptest_any::<WindowFrame>(),
+                proptest_any::<bool>(),
             )
-                .prop_map(|(order_by, window_frame)| AggregateFunc::LastValue {
-                    order_by,
-                    window_frame,
+                .prop_map(
+                    |(order_by, window_frame, ignore_nulls)| AggregateFunc::NthValue {
+                        order_by,
+                        window_frame,
+                        ignore_nulls,
+                    },
+                )
+                .boxed(),
+            (0.0..=1.0f64, proptest_any::<bool>())
+                .prop_map(|(fraction, desc)| AggregateFunc::PercentileCont {
+                    fraction: OrderedFloat(fraction),
+                    desc,
+                })
+                .boxed(),
+            (0.0..=1.0f64, proptest_any::<bool>())
+                .prop_map(|(fraction, desc)| AggregateFunc::PercentileDisc {
+                    fraction: OrderedFloat(fraction),
+                    desc,
+                })
+                .boxed(),
+            proptest_any::<bool>()
+                .prop_map(|desc| AggregateFunc::Mode { desc })
+                .boxed(),
+            Just(AggregateFunc::ApproxCountDistinct).boxed(),
+            (0.0..=1.0f64)
+                .prop_map(|fraction| AggregateFunc::ApproxPercentile {
+                    fraction: OrderedFloat(fraction),
                 })
                 .boxed(),
             Just(AggregateFunc::Dummy).boxed(),
@@ -2136,6 +3054,9 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::RowNumber { order_by } => Kind::RowNumber(order_by.into_proto()),
                 AggregateFunc::Rank { order_by } => Kind::Rank(order_by.into_proto()),
                 AggregateFunc::DenseRank { order_by } => Kind::DenseRank(order_by.into_proto()),
+                AggregateFunc::PercentRank { order_by } => Kind::PercentRank(order_by.into_proto()),
+                AggregateFunc::CumeDist { order_by } => Kind::CumeDist(order_by.into_proto()),
+                AggregateFunc::Ntile { order_by } => Kind::Ntile(order_by.into_proto()),
                 AggregateFunc::LagLead {
                     order_by,
                     lag_lead,
@@ -2153,25 +3074,40 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::FirstValue {
                     order_by,
                     window_frame,
+                    ignore_nulls,
                 } => Kind::FirstValue(proto_aggregate_func::ProtoFramedWindowFunc {
                     order_by: Some(order_by.into_proto()),
                     window_frame: Some(window_frame.into_proto()),
+                    ignore_nulls: *ignore_nulls,
                 }),
                 AggregateFunc::LastValue {
                     order_by,
                     window_frame,
+                    ignore_nulls,
                 } => Kind::LastValue(proto_aggregate_func::ProtoFramedWindowFunc {
                     order_by: Some(order_by.into_proto()),
                     window_frame: Some(window_frame.into_proto()),
+                    ignore_nulls: *ignore_nulls,
+                }),
+                AggregateFunc::NthValue {
+                    order_by,
+                    window_frame,
+                    ignore_nulls,
+                } => Kind::NthValue(proto_aggregate_func::ProtoFramedWindowFunc {
+                    order_by: Some(order_by.into_proto()),
+                    window_frame: Some(window_frame.into_proto()),
+                    ignore_nulls: *ignore_nulls,
                 }),
                 AggregateFunc::WindowAggregate {
                     wrapped_aggregate,
                     order_by,
                     window_frame,
+                    distinct,
                 } => Kind::WindowAggregate(Box::new(proto_aggregate_func::ProtoWindowAggregate {
                     wrapped_aggregate: Some(wrapped_aggregate.into_proto()),
                     order_by: Some(order_by.into_proto()),
                     window_frame: Some(window_frame.into_proto()),
+                    distinct: *distinct,
                 })),
                 AggregateFunc::FusedValueWindowFunc { funcs, order_by } => {
                     Kind::FusedValueWindowFunc(ProtoFusedValueWindowFunc {
@@ -2188,6 +3124,21 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     order_by: Some(order_by.into_proto()),
                     window_frame: Some(window_frame.into_proto()),
                 }),
+                AggregateFunc::PercentileCont { fraction, desc } => {
+                    Kind::PercentileCont(proto_aggregate_func::ProtoPercentile {
+                        fraction: fraction.0,
+                        desc: *desc,
+                    })
+                }
+                AggregateFunc::PercentileDisc { fraction, desc } => {
+                    Kind::PercentileDisc(proto_aggregate_func::ProtoPercentile {
+                        fraction: fraction.0,
+                        desc: *desc,
+                    })
+                }
+                AggregateFunc::Mode { desc } => Kind::Mode(*desc),
+                AggregateFunc::ApproxCountDistinct => Kind::ApproxCountDistinct(()),
+                AggregateFunc::ApproxPercentile { fraction } => Kind::ApproxPercentile(fraction.0),
                 AggregateFunc::Dummy => Kind::Dummy(()),
             }),
         }
@@ -2275,6 +3226,15 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::DenseRank(order_by) => AggregateFunc::DenseRank {
                 order_by: order_by.into_rust()?,
             },
+            Kind::PercentRank(order_by) => AggregateFunc::PercentRank {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::CumeDist(order_by) => AggregateFunc::CumeDist {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::Ntile(order_by) => AggregateFunc::Ntile {
+                order_by: order_by.into_rust()?,
+            },
             Kind::LagLead(pll) => AggregateFunc::LagLead {
                 order_by: pll.order_by.into_rust_if_some("ProtoLagLead::order_by")?,
                 lag_lead: match pll.lag_lead {
@@ -2299,6 +3259,7 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 window_frame: pfv
                     .window_frame
                     .into_rust_if_some("ProtoFramedWindowFunc::window_frame")?,
+                ignore_nulls: pfv.ignore_nulls,
             },
             Kind::LastValue(pfv) => AggregateFunc::LastValue {
                 order_by: pfv
@@ -2307,6 +3268,16 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 window_frame: pfv
                     .window_frame
                     .into_rust_if_some("ProtoFramedWindowFunc::window_frame")?,
+                ignore_nulls: pfv.ignore_nulls,
+            },
+            Kind::NthValue(pfv) => AggregateFunc::NthValue {
+                order_by: pfv
+                    .order_by
+                    .into_rust_if_some("ProtoFramedWindowFunc::order_by")?,
+                window_frame: pfv
+                    .window_frame
+                    .into_rust_if_some("ProtoFramedWindowFunc::window_frame")?,
+                ignore_nulls: pfv.ignore_nulls,
             },
             Kind::WindowAggregate(paf) => AggregateFunc::WindowAggregate {
                 wrapped_aggregate: paf
@@ -2318,6 +3289,7 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 window_frame: paf
                     .window_frame
                     .into_rust_if_some("ProtoWindowAggregate::window_frame")?,
+                distinct: paf.distinct,
             },
             Kind::FusedValueWindowFunc(fvwf) => AggregateFunc::FusedValueWindowFunc {
                 funcs: fvwf.funcs.into_rust()?,
@@ -2334,6 +3306,19 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     .window_frame
                     .into_rust_if_some("ProtoFusedWindowAggregate::window_frame")?,
             },
+            Kind::PercentileCont(pp) => AggregateFunc::PercentileCont {
+                fraction: OrderedFloat(pp.fraction),
+                desc: pp.desc,
+            },
+            Kind::PercentileDisc(pp) => AggregateFunc::PercentileDisc {
+                fraction: OrderedFloat(pp.fraction),
+                desc: pp.desc,
+            },
+            Kind::Mode(desc) => AggregateFunc::Mode { desc },
+            Kind::ApproxCountDistinct(()) => AggregateFunc::ApproxCountDistinct,
+            Kind::ApproxPercentile(fraction) => AggregateFunc::ApproxPercentile {
+                fraction: OrderedFloat(fraction),
+            },
             Kind::Dummy(()) => AggregateFunc::Dummy,
         })
     }
@@ -2413,6 +3398,9 @@ impl AggregateFunc {
             AggregateFunc::RowNumber { order_by } => row_number(datums, temp_storage, order_by),
             AggregateFunc::Rank { order_by } => rank(datums, temp_storage, order_by),
             AggregateFunc::DenseRank { order_by } => dense_rank(datums, temp_storage, order_by),
+            AggregateFunc::PercentRank { order_by } => percent_rank(datums, temp_storage, order_by),
+            AggregateFunc::CumeDist { order_by } => cume_dist(datums, temp_storage, order_by),
+            AggregateFunc::Ntile { order_by } => ntile(datums, temp_storage, order_by),
             AggregateFunc::LagLead {
                 order_by,
                 lag_lead: lag_lead_type,
@@ -2421,21 +3409,30 @@ impl AggregateFunc {
             AggregateFunc::FirstValue {
                 order_by,
                 window_frame,
-            } => first_value(datums, temp_storage, order_by, window_frame),
+                ignore_nulls,
+            } => first_value(datums, temp_storage, order_by, window_frame, *ignore_nulls),
             AggregateFunc::LastValue {
                 order_by,
                 window_frame,
-            } => last_value(datums, temp_storage, order_by, window_frame),
+                ignore_nulls,
+            } => last_value(datums, temp_storage, order_by, window_frame, *ignore_nulls),
+            AggregateFunc::NthValue {
+                order_by,
+                window_frame,
+                ignore_nulls,
+            } => nth_value(datums, temp_storage, order_by, window_frame, *ignore_nulls),
             AggregateFunc::WindowAggregate {
                 wrapped_aggregate,
                 order_by,
                 window_frame,
+                distinct,
             } => window_aggr::<_, NaiveOneByOneAggr>(
                 datums,
                 temp_storage,
                 wrapped_aggregate,
                 order_by,
                 window_frame,
+                *distinct,
             ),
             AggregateFunc::FusedValueWindowFunc { funcs, order_by } => {
                 fused_value_window_func(datums, temp_storage, funcs, order_by)
@@ -2451,10 +3448,128 @@ impl AggregateFunc {
                 order_by,
                 window_frame,
             ),
+            AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::ApproxCountDistinct
+            | AggregateFunc::ApproxPercentile { .. } => {
+                self.eval_weighted(datums.into_iter().map(|d| (d, 1)), temp_storage)
+            }
             AggregateFunc::Dummy => Datum::Dummy,
         }
     }
 
+    /// Like `eval`, but takes each input datum along with its multiplicity.
+    ///
+    /// Ordered-set and sketch aggregates are computed directly from the
+    /// multiplicities, so the work they do is proportional to the number of
+    /// distinct inputs rather than to the number of records. All other
+    /// functions see each datum repeated according to its multiplicity.
+    pub fn eval_weighted<'a, I>(&self, datums: I, temp_storage: &'a RowArena) -> Datum<'a>
+    where
+        I: IntoIterator<Item = (Datum<'a>, usize)>,
+    {
+        match self {
+            AggregateFunc::PercentileCont { desc, .. }
+            | AggregateFunc::PercentileDisc { desc, .. }
+            | AggregateFunc::Mode { desc } => {
+                self.eval_ordered_set(&sorted_value_counts(datums, *desc))
+            }
+            AggregateFunc::ApproxCountDistinct | AggregateFunc::ApproxPercentile { .. } => self
+                .eval_sketch(
+                    datums
+                        .into_iter()
+                        .map(|(d, n)| (self.sketch_coordinate(d), n)),
+                ),
+            _ => self.eval(
+                datums
+                    .into_iter()
+                    .flat_map(|(d, n)| iter::repeat(d).take(n)),
+                temp_storage,
+            ),
+        }
+    }
+
+    /// Evaluates an ordered-set aggregate over the distinct non-null values of
+    /// its input and their multiplicities, in the order of the aggregate.
+    fn eval_ordered_set<'a>(&self, values: &[(Datum<'a>, usize)]) -> Datum<'a> {
+        match self {
+            AggregateFunc::PercentileCont { .. } | AggregateFunc::PercentileDisc { .. } => {
+                let total = values.iter().map(|(_, n)| n).sum();
+                let values: Vec<_> = self
+                    .percentile_positions(total)
+                    .into_iter()
+                    .map(|position| nth_sorted_value(values, position))
+                    .collect();
+                self.eval_percentile(total, &values)
+            }
+            AggregateFunc::Mode { .. } => mode(values),
+            _ => panic!("{} is not an ordered-set aggregate", self.name()),
+        }
+    }
+
+    /// Returns the zero-based positions, in the order of the aggregate, of the
+    /// values that `percentile_cont` or `percentile_disc` is computed from,
+    /// given `total` non-null input values.
+    ///
+    /// Like PostgreSQL, `percentile_cont` interpolates between the values
+    /// nearest to `fraction` of the way through the sorted input, and
+    /// `percentile_disc` selects the first value whose position equals or
+    /// exceeds `fraction`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not `percentile_cont` or `percentile_disc`.
+    pub fn percentile_positions(&self, total: usize) -> Vec<usize> {
+        if total == 0 {
+            return vec![];
+        }
+        match self {
+            AggregateFunc::PercentileCont { fraction, .. } => {
+                let position = fraction.into_inner() * f64::cast_lossy(total - 1);
+                let lo = usize::cast_lossy(position.floor());
+                let hi = usize::cast_lossy(position.ceil());
+                if lo == hi {
+                    vec![lo]
+                } else {
+                    vec![lo, hi]
+                }
+            }
+            AggregateFunc::PercentileDisc { fraction, .. } => {
+                let row =
+                    usize::cast_lossy((fraction.into_inner() * f64::cast_lossy(total)).ceil());
+                vec![row.saturating_sub(1)]
+            }
+            _ => panic!("{} is not a percentile", self.name()),
+        }
+    }
+
+    /// Evaluates `percentile_cont` or `percentile_disc` from the values at the
+    /// positions returned by [`AggregateFunc::percentile_positions`] for the
+    /// same `total`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not `percentile_cont` or `percentile_disc`, or if
+    /// `values` does not match the positions.
+    pub fn eval_percentile<'a>(&self, total: usize, values: &[Datum<'a>]) -> Datum<'a> {
+        match (self, values) {
+            (AggregateFunc::PercentileCont { .. } | AggregateFunc::PercentileDisc { .. }, []) => {
+                Datum::Null
+            }
+            (
+                AggregateFunc::PercentileCont { .. } | AggregateFunc::PercentileDisc { .. },
+                [value],
+            ) => *value,
+            (AggregateFunc::PercentileCont { fraction, .. }, [lo, hi]) => {
+                let position = fraction.into_inner() * f64::cast_lossy(total - 1);
+                let (lo, hi) = (lo.unwrap_float64(), hi.unwrap_float64());
+                Datum::from(lo + (hi - lo) * (position - position.floor()))
+            }
+            _ => panic!("invalid values for {}: {values:?}", self.name()),
+        }
+    }
+
     /// Like `eval`, but it's given a [OneByOneAggr]. If `self` is a `WindowAggregate`, then
     /// the given [OneByOneAggr] will be used to evaluate the wrapped aggregate inside the
     /// `WindowAggregate`. If `self` is not a `WindowAggregate`, then it simply calls `eval`.
@@ -2472,12 +3587,14 @@ impl AggregateFunc {
                 wrapped_aggregate,
                 order_by,
                 window_frame,
+                distinct,
             } => window_aggr::<_, W>(
                 datums,
                 temp_storage,
                 wrapped_aggregate,
                 order_by,
                 window_frame,
+                *distinct,
             ),
             AggregateFunc::FusedWindowAggregate {
                 wrapped_aggregates,
@@ -2515,6 +3632,15 @@ impl AggregateFunc {
             AggregateFunc::DenseRank { order_by } => {
                 dense_rank_no_list(datums, temp_storage, order_by).collect_vec()
             }
+            AggregateFunc::PercentRank { order_by } => {
+                percent_rank_no_list(datums, temp_storage, order_by).collect_vec()
+            }
+            AggregateFunc::CumeDist { order_by } => {
+                cume_dist_no_list(datums, temp_storage, order_by).collect_vec()
+            }
+            AggregateFunc::Ntile { order_by } => {
+                ntile_no_list(datums, temp_storage, order_by).collect_vec()
+            }
             AggregateFunc::LagLead {
                 order_by,
                 lag_lead: lag_lead_type,
@@ -2524,11 +3650,21 @@ impl AggregateFunc {
             AggregateFunc::FirstValue {
                 order_by,
                 window_frame,
-            } => first_value_no_list(datums, temp_storage, order_by, window_frame).collect_vec(),
+                ignore_nulls,
+            } => first_value_no_list(datums, temp_storage, order_by, window_frame, *ignore_nulls)
+                .collect_vec(),
             AggregateFunc::LastValue {
                 order_by,
                 window_frame,
-            } => last_value_no_list(datums, temp_storage, order_by, window_frame).collect_vec(),
+                ignore_nulls,
+            } => last_value_no_list(datums, temp_storage, order_by, window_frame, *ignore_nulls)
+                .collect_vec(),
+            AggregateFunc::NthValue {
+                order_by,
+                window_frame,
+                ignore_nulls,
+            } => nth_value_no_list(datums, temp_storage, order_by, window_frame, *ignore_nulls)
+                .collect_vec(),
             AggregateFunc::FusedValueWindowFunc { funcs, order_by } => {
                 fused_value_window_func_no_list(datums, temp_storage, funcs, order_by).collect_vec()
             }
@@ -2536,12 +3672,14 @@ impl AggregateFunc {
                 wrapped_aggregate,
                 order_by,
                 window_frame,
+                distinct,
             } => window_aggr_no_list::<_, W>(
                 datums,
                 temp_storage,
                 wrapped_aggregate,
                 order_by,
                 window_frame,
+                *distinct,
             )
             .collect_vec(),
             AggregateFunc::FusedWindowAggregate {
@@ -2565,7 +3703,7 @@ impl AggregateFunc {
     /// input relation.
     pub fn default(&self) -> Datum<'static> {
         match self {
-            AggregateFunc::Count => Datum::Int64(0),
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => Datum::Int64(0),
             AggregateFunc::Any => Datum::False,
             AggregateFunc::All => Datum::True,
             AggregateFunc::Dummy => Datum::Dummy,
@@ -2585,9 +3723,13 @@ impl AggregateFunc {
             AggregateFunc::RowNumber { .. }
             | AggregateFunc::Rank { .. }
             | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::PercentRank { .. }
+            | AggregateFunc::CumeDist { .. }
+            | AggregateFunc::Ntile { .. }
             | AggregateFunc::LagLead { .. }
             | AggregateFunc::FirstValue { .. }
             | AggregateFunc::LastValue { .. }
+            | AggregateFunc::NthValue { .. }
             | AggregateFunc::WindowAggregate { .. }
             | AggregateFunc::FusedValueWindowFunc { .. }
             | AggregateFunc::FusedWindowAggregate { .. } => Datum::empty_list(),
@@ -2638,7 +3780,12 @@ impl AggregateFunc {
             | AggregateFunc::JsonbAgg { .. }
             | AggregateFunc::JsonbObjectAgg { .. }
             | AggregateFunc::MapAgg { .. }
-            | AggregateFunc::StringAgg { .. } => Datum::Null,
+            | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::ApproxCountDistinct
+            | AggregateFunc::ApproxPercentile { .. } => Datum::Null,
         }
     }
 
@@ -2647,9 +3794,13 @@ impl AggregateFunc {
             AggregateFunc::RowNumber { .. }
             | AggregateFunc::Rank { .. }
             | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::PercentRank { .. }
+            | AggregateFunc::CumeDist { .. }
+            | AggregateFunc::Ntile { .. }
             | AggregateFunc::LagLead { .. }
             | AggregateFunc::FirstValue { .. }
             | AggregateFunc::LastValue { .. }
+            | AggregateFunc::NthValue { .. }
             | AggregateFunc::WindowAggregate { .. }
             | AggregateFunc::FusedValueWindowFunc { .. }
             | AggregateFunc::FusedWindowAggregate { .. } => true,
@@ -2705,7 +3856,74 @@ impl AggregateFunc {
             | AggregateFunc::JsonbAgg { .. }
             | AggregateFunc::JsonbObjectAgg { .. }
             | AggregateFunc::MapAgg { .. }
-            | AggregateFunc::StringAgg { .. } => false,
+            | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::ApproxCountDistinct
+            | AggregateFunc::ApproxPercentile { .. } => false,
+        }
+    }
+
+    /// Returns whether this is an ordered-set aggregate, i.e., one that is
+    /// called with a `WITHIN GROUP (ORDER BY ...)` clause and whose result
+    /// depends on the position of values in the sorted input.
+    pub fn is_ordered_set(&self) -> bool {
+        matches!(
+            self,
+            AggregateFunc::PercentileCont { .. }
+                | AggregateFunc::PercentileDisc { .. }
+                | AggregateFunc::Mode { .. }
+        )
+    }
+
+    /// Returns the coordinate of `datum` in the sketch of this aggregate.
+    ///
+    /// Null inputs are mapped to a null coordinate, which does not contribute
+    /// to the estimate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not a sketch aggregate.
+    pub fn sketch_coordinate<'a>(&self, datum: Datum<'a>) -> Datum<'a> {
+        if datum.is_null() {
+            return Datum::Null;
+        }
+        match self {
+            AggregateFunc::ApproxCountDistinct => Datum::UInt64(sketch::hll_coordinate(datum)),
+            AggregateFunc::ApproxPercentile { .. } => {
+                Datum::Int64(sketch::quantile_coordinate(datum.unwrap_float64()))
+            }
+            _ => panic!("{} is not a sketch aggregate", self.name()),
+        }
+    }
+
+    /// Estimates the result of this aggregate from the multiplicities of the
+    /// sketch coordinates of its inputs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not a sketch aggregate.
+    pub fn eval_sketch<'a, I>(&self, coordinates: I) -> Datum<'a>
+    where
+        I: IntoIterator<Item = (Datum<'a>, usize)>,
+    {
+        let coordinates = coordinates.into_iter().filter(|(c, _)| !c.is_null());
+        match self {
+            AggregateFunc::ApproxCountDistinct => Datum::Int64(sketch::hll_estimate(
+                coordinates.map(|(c, n)| (c.unwrap_uint64(), n)),
+            )),
+            AggregateFunc::ApproxPercentile { fraction } => {
+                let estimate = sketch::quantile_estimate(
+                    coordinates.map(|(c, n)| (c.unwrap_int64(), n)),
+                    fraction.into_inner(),
+                );
+                match estimate {
+                    Some(estimate) => Datum::Float64(estimate.into()),
+                    None => Datum::Null,
+                }
+            }
+            _ => panic!("{} is not a sketch aggregate", self.name()),
         }
     }
 
@@ -2743,14 +3961,43 @@ impl AggregateFunc {
                 }
             }
             AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::PercentileCont { .. } => ScalarType::Float64,
+            AggregateFunc::ApproxCountDistinct => ScalarType::Int64,
+            AggregateFunc::ApproxPercentile { .. } => ScalarType::Float64,
             AggregateFunc::RowNumber { .. } => {
-                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?row_number?")
+                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?row_number?", ScalarType::Int64)
             }
             AggregateFunc::Rank { .. } => {
-                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?rank?")
+                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?rank?", ScalarType::Int64)
             }
             AggregateFunc::DenseRank { .. } => {
-                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?dense_rank?")
+                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?dense_rank?", ScalarType::Int64)
+            }
+            AggregateFunc::PercentRank { .. } => {
+                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?percent_rank?", ScalarType::Float64)
+            }
+            AggregateFunc::CumeDist { .. } => {
+                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?cume_dist?", ScalarType::Float64)
+            }
+            AggregateFunc::Ntile { .. } => {
+                // The input type for Ntile is ((OriginalRow, NumBuckets), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Record {
+                        fields: [
+                            // null when the number of buckets is null or not positive
+                            (ColumnName::from("?ntile?"), ScalarType::Int32.nullable(true)),
+                            (ColumnName::from("?orig_row?"), original_row_type),
+                        ].into(),
+                        custom_id: None,
+                        custom_oid: None,
+                    }),
+                    custom_id: None,
+                }
             }
             AggregateFunc::LagLead { lag_lead: lag_lead_type, .. } => {
                 // The input type for Lag is ((OriginalRow, EncodedArgs), OrderByExprs...)
@@ -2768,6 +4015,7 @@ impl AggregateFunc {
                             (ColumnName::from("?orig_row?"), original_row_type),
                         ].into(),
                         custom_id: None,
+                        custom_oid: None,
                     }),
                     custom_id: None,
                 }
@@ -2789,6 +4037,7 @@ impl AggregateFunc {
                             (ColumnName::from("?orig_row?"), original_row_type),
                         ].into(),
                         custom_id: None,
+                        custom_oid: None,
                     }),
                     custom_id: None,
                 }
@@ -2810,6 +4059,29 @@ impl AggregateFunc {
                             (ColumnName::from("?orig_row?"), original_row_type),
                         ].into(),
                         custom_id: None,
+                        custom_oid: None,
+                    }),
+                    custom_id: None,
+                }
+            }
+            AggregateFunc::NthValue { .. } => {
+                // The input type for NthValue is ((OriginalRow, (Arg, N)), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+                let value_type = Self::nth_value_output_type_inner_from_encoded_args(
+                    fields[0].unwrap_record_element_type()[1],
+                );
+
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Record {
+                        fields: [
+                            (ColumnName::from("?nth_value?"), value_type),
+                            (ColumnName::from("?orig_row?"), original_row_type),
+                        ].into(),
+                        custom_id: None,
+                        custom_oid: None,
                     }),
                     custom_id: None,
                 }
@@ -2834,6 +4106,7 @@ impl AggregateFunc {
                             (ColumnName::from("?orig_row?"), original_row_type),
                         ].into(),
                         custom_id: None,
+                        custom_oid: None,
                     }),
                     custom_id: None,
                 }
@@ -2862,10 +4135,12 @@ impl AggregateFunc {
                             (ColumnName::from("?fused_window_agg?"), ScalarType::Record {
                                 fields: out_fields.into(),
                                 custom_id: None,
+                                custom_oid: None,
                             }.nullable(false)),
                             (ColumnName::from("?orig_row?"), original_row_type),
                         ].into(),
                         custom_id: None,
+                        custom_oid: None,
                     }),
                     custom_id: None,
                 }
@@ -2873,8 +4148,8 @@ impl AggregateFunc {
             AggregateFunc::FusedValueWindowFunc { funcs, order_by: _ } => {
                 // The input type is ((OriginalRow, EncodedArgs), OrderByExprs...)
                 // where EncodedArgs is a record, where each element is the argument to one of the
-                // function calls that got fused. This is a record for lag/lead, and a simple type
-                // for first_value/last_value.
+                // function calls that got fused. This is a record for lag/lead/nth_value, and a
+                // simple type for first_value/last_value/ntile.
                 let fields = input_type.scalar_type.unwrap_record_element_type();
                 let original_row_type = fields[0].unwrap_record_element_type()[0]
                     .clone()
@@ -2905,14 +4180,28 @@ impl AggregateFunc {
                                                 arg_type.clone().nullable(true),
                                             )
                                         }
+                                        AggregateFunc::NthValue { .. } => {
+                                            (
+                                                ColumnName::from("?nth_value?"),
+                                                Self::nth_value_output_type_inner_from_encoded_args(arg_type),
+                                            )
+                                        }
+                                        AggregateFunc::Ntile { .. } => {
+                                            (
+                                                ColumnName::from("?ntile?"),
+                                                ScalarType::Int32.nullable(true),
+                                            )
+                                        }
                                         _ => panic!("FusedValueWindowFunc has an unknown function"),
                                     }
                                 }).collect(),
                                 custom_id: None,
+                                custom_oid: None,
                             }.nullable(false)),
                             (ColumnName::from("?orig_row?"), original_row_type),
                         ].into(),
                         custom_id: None,
+                        custom_oid: None,
                     }),
                     custom_id: None,
                 }
@@ -2957,12 +4246,14 @@ impl AggregateFunc {
             | AggregateFunc::MinTime
             | AggregateFunc::SumFloat32
             | AggregateFunc::SumFloat64
-            | AggregateFunc::SumNumeric => input_type.scalar_type.clone(),
+            | AggregateFunc::SumNumeric
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. } => input_type.scalar_type.clone(),
         };
         // Count never produces null, and other aggregations only produce
         // null in the presence of null inputs.
         let nullable = match self {
-            AggregateFunc::Count => false,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => false,
             // Use the nullability of the underlying column being aggregated, not the Records wrapping it
             AggregateFunc::StringAgg { .. } => match input_type.scalar_type {
                 // The outer Record wraps the input in the first position, and any ORDER BY expressions afterwards
@@ -2978,16 +4269,17 @@ impl AggregateFunc {
         scalar_type.nullable(nullable)
     }
 
-    /// Compute output type for ROW_NUMBER, RANK, DENSE_RANK
-    fn output_type_ranking_window_funcs(input_type: &ColumnType, col_name: &str) -> ScalarType {
+    /// Compute output type for ROW_NUMBER, RANK, DENSE_RANK, PERCENT_RANK, CUME_DIST
+    fn output_type_ranking_window_funcs(
+        input_type: &ColumnType,
+        col_name: &str,
+        result_type: ScalarType,
+    ) -> ScalarType {
         match input_type.scalar_type {
             ScalarType::Record { ref fields, .. } => ScalarType::List {
                 element_type: Box::new(ScalarType::Record {
                     fields: [
-                        (
-                            ColumnName::from(col_name),
-                            ScalarType::Int64.nullable(false),
-                        ),
+                        (ColumnName::from(col_name), result_type.nullable(false)),
                         (ColumnName::from("?orig_row?"), {
                             let inner = match &fields[0].1.scalar_type {
                                 ScalarType::List { element_type, .. } => element_type.clone(),
@@ -2998,6 +4290,7 @@ impl AggregateFunc {
                     ]
                     .into(),
                     custom_id: None,
+                    custom_oid: None,
                 }),
                 custom_id: None,
             },
@@ -3017,6 +4310,16 @@ impl AggregateFunc {
             .nullable(true)
     }
 
+    /// Given the `EncodedArgs` part of `((OriginalRow, EncodedArgs), OrderByExprs...)` of
+    /// nth_value, this computes the type of the first field of the output type.
+    fn nth_value_output_type_inner_from_encoded_args(encoded_args_type: &ScalarType) -> ColumnType {
+        // The output type is the same as the type of the first argument, but always nullable.
+        // (It's null when the frame has fewer than N rows.)
+        encoded_args_type.unwrap_record_element_type()[0]
+            .clone()
+            .nullable(true)
+    }
+
     fn lag_lead_result_column_name(lag_lead_type: &LagLeadType) -> ColumnName {
         ColumnName::from(match lag_lead_type {
             LagLeadType::Lag => "?lag?",
@@ -3069,7 +4372,11 @@ impl AggregateFunc {
             | AggregateFunc::SumFloat32
             | AggregateFunc::SumFloat64
             | AggregateFunc::SumNumeric
-            | AggregateFunc::StringAgg { .. } => true,
+            | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::ApproxPercentile { .. } => true,
             // Count is never null
             AggregateFunc::Count => false,
             _ => false,
@@ -3324,6 +4631,9 @@ impl AggregateFunc {
             Self::RowNumber { .. } => "row_number",
             Self::Rank { .. } => "rank",
             Self::DenseRank { .. } => "dense_rank",
+            Self::PercentRank { .. } => "percent_rank",
+            Self::CumeDist { .. } => "cume_dist",
+            Self::Ntile { .. } => "ntile",
             Self::LagLead {
                 lag_lead: LagLeadType::Lag,
                 ..
@@ -3334,9 +4644,15 @@ impl AggregateFunc {
             } => "lead",
             Self::FirstValue { .. } => "first_value",
             Self::LastValue { .. } => "last_value",
+            Self::NthValue { .. } => "nth_value",
             Self::WindowAggregate { .. } => "window_agg",
             Self::FusedValueWindowFunc { .. } => "fused_value_window_func",
             Self::FusedWindowAggregate { .. } => "fused_window_agg",
+            Self::PercentileCont { .. } => "percentile_cont",
+            Self::PercentileDisc { .. } => "percentile_disc",
+            Self::Mode { .. } => "mode",
+            Self::ApproxCountDistinct => "approx_count_distinct",
+            Self::ApproxPercentile { .. } => "approx_percentile",
             Self::Dummy => "dummy",
         }
     }
@@ -3358,7 +4674,10 @@ where
             | StringAgg { order_by }
             | RowNumber { order_by }
             | Rank { order_by }
-            | DenseRank { order_by } => {
+            | DenseRank { order_by }
+            | PercentRank { order_by }
+            | CumeDist { order_by }
+            | Ntile { order_by } => {
                 let order_by = order_by.iter().map(|col| self.child(col));
                 write!(f, "{}[order_by=[{}]]", name, separated(", ", order_by))
             }
@@ -3379,23 +4698,24 @@ where
             FirstValue {
                 order_by,
                 window_frame,
-            } => {
-                let order_by = order_by.iter().map(|col| self.child(col));
-                f.write_str(name)?;
-                f.write_str("[")?;
-                write!(f, "order_by=[{}]", separated(", ", order_by))?;
-                if *window_frame != WindowFrame::default() {
-                    write!(f, " {}", window_frame)?;
-                }
-                f.write_str("]")
+                ignore_nulls,
+            }
+            | LastValue {
+                order_by,
+                window_frame,
+                ignore_nulls,
             }
-            LastValue {
+            | NthValue {
                 order_by,
                 window_frame,
+                ignore_nulls,
             } => {
                 let order_by = order_by.iter().map(|col| self.child(col));
                 f.write_str(name)?;
                 f.write_str("[")?;
+                if *ignore_nulls {
+                    f.write_str("ignore_nulls=true, ")?;
+                }
                 write!(f, "order_by=[{}]", separated(", ", order_by))?;
                 if *window_frame != WindowFrame::default() {
                     write!(f, " {}", window_frame)?;
@@ -3406,12 +4726,16 @@ where
                 wrapped_aggregate,
                 order_by,
                 window_frame,
+                distinct,
             } => {
                 let order_by = order_by.iter().map(|col| self.child(col));
                 let wrapped_aggregate = self.child(wrapped_aggregate.deref());
                 f.write_str(name)?;
                 f.write_str("[")?;
                 write!(f, "{} ", wrapped_aggregate)?;
+                if *distinct {
+                    f.write_str("distinct=true ")?;
+                }
                 write!(f, "order_by=[{}]", separated(", ", order_by))?;
                 if *window_frame != WindowFrame::default() {
                     write!(f, " {}", window_frame)?;
@@ -3427,6 +4751,15 @@ where
                 write!(f, "order_by=[{}]", separated(", ", order_by))?;
                 f.write_str("]")
             }
+            PercentileCont { fraction, desc } | PercentileDisc { fraction, desc } => {
+                write!(f, "{}[fraction={}", name, fraction)?;
+                if *desc {
+                    f.write_str(", desc=true")?;
+                }
+                f.write_str("]")
+            }
+            Mode { desc: true } => write!(f, "{}[desc=true]", name),
+            ApproxPercentile { fraction } => write!(f, "{}[fraction={}]", name, fraction),
             _ => f.write_str(name),
         }
     }
@@ -3517,6 +4850,122 @@ impl AnalyzedRegex {
     }
 }
 
+/// A column of a `JSON_TABLE`.
+#[derive(
+    Arbitrary, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub enum JsonTableColumn {
+    /// A column that numbers the rows, starting from one.
+    Ordinality,
+    /// A column that holds the item that the jsonpath `path` selects from the
+    /// row's item, as `jsonb` if `jsonb` is set and as text otherwise.
+    Value { path: String, jsonb: bool },
+    /// A column that reports whether the jsonpath `path` selects any items
+    /// from the row's item.
+    Exists { path: String },
+}
+
+impl RustType<ProtoJsonTableColumn> for JsonTableColumn {
+    fn into_proto(&self) -> ProtoJsonTableColumn {
+        use proto_json_table_column::{Kind, ProtoValue};
+
+        ProtoJsonTableColumn {
+            kind: Some(match self {
+                JsonTableColumn::Ordinality => Kind::Ordinality(()),
+                JsonTableColumn::Value { path, jsonb } => Kind::Value(ProtoValue {
+                    path: path.clone(),
+                    jsonb: *jsonb,
+                }),
+                JsonTableColumn::Exists { path } => Kind::Exists(path.clone()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonTableColumn) -> Result<Self, TryFromProtoError> {
+        use proto_json_table_column::{Kind, ProtoValue};
+
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoJsonTableColumn::kind"))?;
+        Ok(match kind {
+            Kind::Ordinality(()) => JsonTableColumn::Ordinality,
+            Kind::Value(ProtoValue { path, jsonb }) => JsonTableColumn::Value { path, jsonb },
+            Kind::Exists(path) => JsonTableColumn::Exists { path },
+        })
+    }
+}
+
+fn jsonb_path_query(datums: &[Datum]) -> Result<Vec<(Row, Diff)>, EvalError> {
+    let rows = eval_jsonb_path(datums, |items| {
+        Ok(items
+            .iter()
+            .map(|item| {
+                let mut row = Row::default();
+                item.push(&mut row.packer());
+                (row, 1)
+            })
+            .collect())
+    })?;
+    Ok(rows.unwrap_or_default())
+}
+
+fn json_table(
+    datums: &[Datum],
+    path: &str,
+    columns: &[JsonTableColumn],
+    temp_storage: &RowArena,
+) -> Result<Vec<(Row, Diff)>, EvalError> {
+    let parse = |path: &str| -> JsonPath { path.parse().expect("jsonpaths are canonical") };
+    let column_paths: Vec<_> = columns
+        .iter()
+        .map(|column| match column {
+            JsonTableColumn::Ordinality => None,
+            JsonTableColumn::Value { path, .. } | JsonTableColumn::Exists { path } => {
+                Some(parse(path))
+            }
+        })
+        .collect();
+    let path = parse(path);
+    let vars = datums[1];
+    // Like the default `EMPTY ON ERROR` behavior of PostgreSQL, errors produce
+    // an empty table.
+    let Ok(items) = path.query(datums[0], vars) else {
+        return Ok(vec![]);
+    };
+
+    let mut rows = Vec::with_capacity(items.len());
+    let mut row = Row::default();
+    for (i, item) in items.into_iter().enumerate() {
+        let mut packer = row.packer();
+        for (column, path) in columns.iter().zip(&column_paths) {
+            let found = path.as_ref().map(|path| path.query(item, vars));
+            let datum = match (column, found.as_ref().map(|found| found.as_deref())) {
+                (JsonTableColumn::Ordinality, _) => {
+                    let n = i + 1;
+                    i32::try_from(n)
+                        .map_err(|_| EvalError::Int32OutOfRange(n.to_string().into()))?
+                        .into()
+                }
+                // Like the default `NULL ON EMPTY` and `NULL ON ERROR`
+                // behavior of PostgreSQL, the column is NULL unless the path
+                // selects a single item.
+                (JsonTableColumn::Value { jsonb: true, .. }, Some(Ok([item]))) => {
+                    temp_storage.make_datum(|packer| item.push(packer))
+                }
+                (JsonTableColumn::Value { jsonb: false, .. }, Some(Ok([item]))) => item
+                    .scalar()
+                    .map_or(Datum::Null, |datum| jsonb_stringify(datum, temp_storage)),
+                (JsonTableColumn::Value { .. }, _) => Datum::Null,
+                (JsonTableColumn::Exists { .. }, Some(Ok(found))) => (!found.is_empty()).into(),
+                (JsonTableColumn::Exists { .. }, _) => Datum::False,
+            };
+            packer.push(datum);
+        }
+        rows.push((row.clone(), 1));
+    }
+    Ok(rows)
+}
+
 pub fn csv_extract(a: Datum, n_cols: usize) -> impl Iterator<Item = (Row, Diff)> + '_ {
     let bytes = a.unwrap_str().as_bytes();
     let mut row = Row::default();
@@ -3639,6 +5088,14 @@ pub enum TableFunc {
         name: String,
         relation: RelationType,
     },
+    JsonbPathQuery,
+    /// Produces a row for each item that the jsonpath `path` selects from the
+    /// first argument, given the variables in the second argument, with a
+    /// column for each of `columns`.
+    JsonTable {
+        path: String,
+        columns: Vec<JsonTableColumn>,
+    },
 }
 
 impl RustType<ProtoTableFunc> for TableFunc {
@@ -3673,6 +5130,11 @@ impl RustType<ProtoTableFunc> for TableFunc {
                         relation: Some(relation.into_proto()),
                     })
                 }
+                TableFunc::JsonbPathQuery => Kind::JsonbPathQuery(()),
+                TableFunc::JsonTable { path, columns } => Kind::JsonTable(ProtoJsonTable {
+                    path: path.clone(),
+                    columns: columns.into_proto(),
+                }),
             }),
         }
     }
@@ -3717,6 +5179,11 @@ impl RustType<ProtoTableFunc> for TableFunc {
                     .relation
                     .into_rust_if_some("ProtoTabletizedScalar::relation")?,
             },
+            Kind::JsonbPathQuery(()) => TableFunc::JsonbPathQuery,
+            Kind::JsonTable(v) => TableFunc::JsonTable {
+                path: v.path,
+                columns: v.columns.into_rust()?,
+            },
         })
     }
 }
@@ -3796,6 +5263,10 @@ impl TableFunc {
                 let r = Row::pack_slice(datums);
                 Ok(Box::new(std::iter::once((r, 1))))
             }
+            TableFunc::JsonbPathQuery => Ok(Box::new(jsonb_path_query(datums)?.into_iter())),
+            TableFunc::JsonTable { path, columns } => Ok(Box::new(
+                json_table(datums, path, columns, temp_storage)?.into_iter(),
+            )),
         }
     }
 
@@ -3924,6 +5395,28 @@ impl TableFunc {
             TableFunc::TabletizedScalar { relation, .. } => {
                 return relation.clone();
             }
+            TableFunc::JsonbPathQuery => {
+                let column_types = vec![ScalarType::Jsonb.nullable(false)];
+                let keys = vec![];
+                (column_types, keys)
+            }
+            TableFunc::JsonTable { columns, .. } => {
+                let column_types = columns
+                    .iter()
+                    .map(|column| match column {
+                        JsonTableColumn::Ordinality => ScalarType::Int32.nullable(false),
+                        JsonTableColumn::Value { jsonb: true, .. } => {
+                            ScalarType::Jsonb.nullable(true)
+                        }
+                        JsonTableColumn::Value { jsonb: false, .. } => {
+                            ScalarType::String.nullable(true)
+                        }
+                        JsonTableColumn::Exists { .. } => ScalarType::Bool.nullable(false),
+                    })
+                    .collect();
+                let keys = vec![];
+                (column_types, keys)
+            }
         };
 
         if !keys.is_empty() {
@@ -3953,6 +5446,8 @@ impl TableFunc {
             TableFunc::UnnestMap { .. } => 2,
             TableFunc::Wrap { width, .. } => *width,
             TableFunc::TabletizedScalar { relation, .. } => relation.column_types.len(),
+            TableFunc::JsonbPathQuery => 1,
+            TableFunc::JsonTable { columns, .. } => columns.len(),
         }
     }
 
@@ -3973,7 +5468,9 @@ impl TableFunc {
             | TableFunc::Repeat
             | TableFunc::UnnestArray { .. }
             | TableFunc::UnnestList { .. }
-            | TableFunc::UnnestMap { .. } => true,
+            | TableFunc::UnnestMap { .. }
+            | TableFunc::JsonbPathQuery
+            | TableFunc::JsonTable { .. } => true,
             TableFunc::Wrap { .. } => false,
             TableFunc::TabletizedScalar { .. } => false,
         }
@@ -4002,6 +5499,8 @@ impl TableFunc {
             TableFunc::UnnestMap { .. } => true,
             TableFunc::Wrap { .. } => true,
             TableFunc::TabletizedScalar { .. } => true,
+            TableFunc::JsonbPathQuery => true,
+            TableFunc::JsonTable { .. } => true,
         }
     }
 }
@@ -4027,6 +5526,8 @@ impl fmt::Display for TableFunc {
             TableFunc::UnnestMap { .. } => f.write_str("unnest_map"),
             TableFunc::Wrap { width, .. } => write!(f, "wrap{}", width),
             TableFunc::TabletizedScalar { name, .. } => f.write_str(name),
+            TableFunc::JsonbPathQuery => f.write_str("jsonb_path_query"),
+            TableFunc::JsonTable { path, .. } => write!(f, "json_table({:?}, _)", path),
         }
     }
 }
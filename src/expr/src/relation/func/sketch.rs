@@ -0,0 +1,259 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Sketches backing the approximate aggregate functions.
+//!
+//! Each sketch assigns every input value a *coordinate*, and summarizes a
+//! multiset of values by how many of them have each coordinate. Because that
+//! summary consists of counts, the sketches of two inputs merge by adding
+//! their counts, and values are removed by subtracting theirs. Dataflows can
+//! thus maintain sketches under arbitrary insertions and retractions, while the
+//! number of distinct coordinates, and so the size of a sketch, is bounded
+//! independently of the number of values summarized.
+
+use std::hash::{Hash, Hasher};
+
+use mz_ore::cast::{CastFrom, CastLossy};
+use mz_repr::Datum;
+use seahash::SeaHasher;
+
+/// The number of hash bits that select a HyperLogLog register.
+const HLL_PRECISION: u32 = 12;
+
+/// The number of HyperLogLog registers.
+///
+/// The standard error of the estimate is about `1.04 / sqrt(HLL_REGISTERS)`,
+/// i.e., 1.6%.
+const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// The number of distinct ranks a HyperLogLog coordinate can encode.
+const HLL_RANKS: u64 = 64;
+
+/// Returns the HyperLogLog coordinate of `datum`.
+///
+/// The coordinate identifies the register selected by the leading bits of the
+/// datum's hash, and the rank of the first set bit among the remaining bits. A
+/// register's value is the greatest rank among the coordinates present for it.
+pub fn hll_coordinate(datum: Datum) -> u64 {
+    let mut hasher = SeaHasher::new();
+    datum.hash(&mut hasher);
+    let hash = hasher.finish();
+    let register = hash >> (u64::BITS - HLL_PRECISION);
+    // The sentinel bit bounds the rank by `u64::BITS - HLL_PRECISION + 1`.
+    let rest = (hash << HLL_PRECISION) | (1 << (HLL_PRECISION - 1));
+    let rank = u64::from(rest.leading_zeros() + 1);
+    register * HLL_RANKS + rank
+}
+
+/// Estimates the number of distinct values from the multiplicities of their
+/// HyperLogLog coordinates.
+pub fn hll_estimate<I>(coordinates: I) -> i64
+where
+    I: IntoIterator<Item = (u64, usize)>,
+{
+    let mut registers = vec![0u8; HLL_REGISTERS];
+    for (coordinate, count) in coordinates {
+        if count == 0 {
+            continue;
+        }
+        let register = usize::cast_from(coordinate / HLL_RANKS);
+        let rank = u8::try_from(coordinate % HLL_RANKS).expect("rank is less than 64");
+        registers[register] = registers[register].max(rank);
+    }
+
+    let m = f64::cast_lossy(HLL_REGISTERS);
+    let alpha = 0.7213 / (1.0 + 1.079 / m);
+    let sum: f64 = registers.iter().map(|r| 2f64.powi(-i32::from(*r))).sum();
+    let mut estimate = alpha * m * m / sum;
+    // The raw estimate is biased for small cardinalities, which linear
+    // counting over the empty registers estimates well.
+    let empty = registers.iter().filter(|r| **r == 0).count();
+    if estimate <= 2.5 * m && empty > 0 {
+        estimate = m * (m / f64::cast_lossy(empty)).ln();
+    }
+    i64::cast_lossy(estimate.round())
+}
+
+/// The relative accuracy of the quantile sketch: each estimate is within this
+/// fraction of the magnitude of the value it estimates.
+const QUANTILE_RELATIVE_ACCURACY: f64 = 0.01;
+
+/// Added to bucket indexes so that the magnitude of every finite, nonzero
+/// value's coordinate is positive.
+const QUANTILE_INDEX_OFFSET: i64 = 1 << 20;
+
+/// The magnitude of the coordinates of infinite values, which is greater than
+/// that of the coordinate of any finite value.
+const QUANTILE_INFINITY: i64 = 1 << 21;
+
+/// The coordinate of NaN, which sorts after all other values.
+const QUANTILE_NAN: i64 = i64::MAX;
+
+/// Returns the logarithm of the ratio between adjacent bucket boundaries.
+fn quantile_log_gamma() -> f64 {
+    ((1.0 + QUANTILE_RELATIVE_ACCURACY) / (1.0 - QUANTILE_RELATIVE_ACCURACY)).ln()
+}
+
+/// Returns the quantile sketch coordinate of `value`.
+///
+/// Positive values are assigned to the logarithmically sized buckets
+/// `(γ^(i-1), γ^i]`, and negative values to their mirror images, such that
+/// coordinates sort in the same order as the values they represent. Zero and
+/// subnormal values share the coordinate 0.
+pub fn quantile_coordinate(value: f64) -> i64 {
+    if value.is_nan() {
+        return QUANTILE_NAN;
+    }
+    if value.abs() < f64::MIN_POSITIVE {
+        return 0;
+    }
+    let magnitude = if value.is_infinite() {
+        QUANTILE_INFINITY
+    } else {
+        let index = i64::cast_lossy((value.abs().ln() / quantile_log_gamma()).ceil());
+        index + QUANTILE_INDEX_OFFSET
+    };
+    if value.is_sign_positive() {
+        magnitude
+    } else {
+        -magnitude
+    }
+}
+
+/// Returns the value that represents all values with `coordinate`.
+///
+/// The representative of bucket `(γ^(i-1), γ^i]` is `2γ^i / (γ + 1)`, which
+/// is within the relative accuracy of every value in the bucket.
+fn quantile_value(coordinate: i64) -> f64 {
+    if coordinate == QUANTILE_NAN {
+        return f64::NAN;
+    }
+    let magnitude = match coordinate.abs() {
+        0 => 0.0,
+        QUANTILE_INFINITY => f64::INFINITY,
+        magnitude => {
+            let log_gamma = quantile_log_gamma();
+            let index = f64::cast_lossy(magnitude - QUANTILE_INDEX_OFFSET);
+            // Computed in log space so that the representatives of the largest
+            // buckets do not overflow.
+            (index * log_gamma + (2.0 / (log_gamma.exp() + 1.0)).ln()).exp()
+        }
+    };
+    if coordinate < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Estimates the first value whose position in the sorted input equals or
+/// exceeds `fraction` from the multiplicities of the values' quantile sketch
+/// coordinates.
+///
+/// Returns `None` if there are no values.
+pub fn quantile_estimate<I>(coordinates: I, fraction: f64) -> Option<f64>
+where
+    I: IntoIterator<Item = (i64, usize)>,
+{
+    let mut buckets: Vec<_> = coordinates
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    buckets.sort_unstable_by_key(|(coordinate, _)| *coordinate);
+    let total: usize = buckets.iter().map(|(_, count)| count).sum();
+    let rank = usize::cast_lossy((fraction * f64::cast_lossy(total)).ceil()).max(1);
+    let mut seen = 0;
+    for (coordinate, count) in &buckets {
+        seen += count;
+        if seen >= rank {
+            return Some(quantile_value(*coordinate));
+        }
+    }
+    buckets
+        .last()
+        .map(|(coordinate, _)| quantile_value(*coordinate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_hll_estimate() {
+        for n in [0, 1, 10, 1_000, 100_000] {
+            let coordinates = (0..n).map(|i| (hll_coordinate(Datum::Int64(i)), 1));
+            let estimate = hll_estimate(coordinates);
+            let error = (estimate - n).abs();
+            // Allow for five standard errors.
+            assert!(
+                f64::cast_lossy(error) <= 0.08 * f64::cast_lossy(n) + 1.0,
+                "n={n}, estimate={estimate}"
+            );
+        }
+
+        // Duplicates, and values whose multiplicity has dropped to zero, do
+        // not contribute.
+        let distinct = (0..10).map(|i| (hll_coordinate(Datum::Int64(i)), 1));
+        let coordinates = (0..100)
+            .map(|i| (hll_coordinate(Datum::Int64(i % 10)), 2))
+            .chain((10..20).map(|i| (hll_coordinate(Datum::Int64(i)), 0)));
+        assert_eq!(hll_estimate(coordinates), hll_estimate(distinct));
+    }
+
+    #[mz_ore::test]
+    fn test_quantile_estimate() {
+        let values: Vec<f64> = (1..=1000).map(|i| f64::from(i) * 1.5).collect();
+        let coordinates = || values.iter().map(|v| (quantile_coordinate(*v), 1));
+        for fraction in [0.0, 0.01, 0.25, 0.5, 0.99, 1.0] {
+            let estimate = quantile_estimate(coordinates(), fraction).unwrap();
+            let rank = usize::cast_lossy((fraction * 1000.0).ceil()).max(1);
+            let exact = values[rank - 1];
+            assert!(
+                (estimate - exact).abs() <= 1.0001 * QUANTILE_RELATIVE_ACCURACY * exact,
+                "fraction={fraction}, estimate={estimate}, exact={exact}"
+            );
+        }
+        assert_eq!(quantile_estimate(std::iter::empty(), 0.5), None);
+    }
+
+    #[mz_ore::test]
+    fn test_quantile_coordinate_order() {
+        let values = [
+            f64::NEG_INFINITY,
+            -1e300,
+            -2.0,
+            -1.0,
+            -1e-300,
+            0.0,
+            1e-300,
+            1.0,
+            2.0,
+            1e300,
+            f64::INFINITY,
+            f64::NAN,
+        ];
+        let coordinates: Vec<_> = values.iter().map(|v| quantile_coordinate(*v)).collect();
+        assert!(
+            coordinates.windows(2).all(|w| w[0] < w[1]),
+            "{coordinates:?}"
+        );
+        for value in values {
+            let estimate = quantile_value(quantile_coordinate(value));
+            if value.is_nan() {
+                assert!(estimate.is_nan());
+            } else if value.is_infinite() || value == 0.0 {
+                assert_eq!(estimate, value);
+            } else {
+                let error = (estimate - value).abs();
+                let bound = 1.0001 * QUANTILE_RELATIVE_ACCURACY * value.abs();
+                assert!(error <= bound, "{value}");
+            }
+        }
+    }
+}
@@ -41,12 +41,16 @@ use mz_proto::chrono::any_naive_datetime;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::date::Date;
+use mz_repr::adt::geo::Geometry;
 use mz_repr::adt::interval::{Interval, RoundBehavior};
 use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::adt::jsonpath::{JsonPath, JsonPathItem};
 use mz_repr::adt::mz_acl_item::{AclItem, AclMode, MzAclItem};
+use mz_repr::adt::network::Inet;
 use mz_repr::adt::numeric::{self, DecimalLike, Numeric, NumericMaxScale};
 use mz_repr::adt::range::{self, Range, RangeBound, RangeOps};
 use mz_repr::adt::regex::{any_regex, Regex};
+use mz_repr::adt::text_search::{TextSearchConfig, TsQuery, TsVector};
 use mz_repr::adt::timestamp::{CheckedTimestamp, TimestampLike};
 use mz_repr::role_id::RoleId;
 use mz_repr::{strconv, ColumnName, ColumnType, Datum, DatumType, Row, RowArena, ScalarType};
@@ -2029,6 +2033,166 @@ fn mz_acl_item_contains_privilege(a: Datum<'_>, b: Datum<'_>) -> Result<Datum<'s
     Ok(contains.into())
 }
 
+fn inet_contains<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().contains(&b.unwrap_inet()))
+}
+
+fn inet_contains_or_eq<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().contains_or_eq(&b.unwrap_inet()))
+}
+
+fn inet_overlaps<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().overlaps(&b.unwrap_inet()))
+}
+
+fn inet_with_masklen(a: Datum<'_>, b: Datum<'_>) -> Result<Inet, EvalError> {
+    let inet = a.unwrap_inet();
+    let masklen = b.unwrap_int32();
+    // Like PostgreSQL, a mask length of -1 means the maximum for the family.
+    if masklen == -1 {
+        return Ok(inet
+            .with_netmask(inet.max_netmask())
+            .expect("valid netmask"));
+    }
+    u8::try_from(masklen)
+        .ok()
+        .and_then(|masklen| inet.with_netmask(masklen).ok())
+        .ok_or_else(|| {
+            EvalError::InvalidParameterValue(format!("invalid mask length: {}", masklen).into())
+        })
+}
+
+fn inet_set_masklen(a: Datum<'_>, b: Datum<'_>) -> Result<Datum<'static>, EvalError> {
+    Ok(Datum::Inet(inet_with_masklen(a, b)?))
+}
+
+/// Like [`inet_set_masklen`], but clears the bits to the right of the new
+/// netmask, as required for `cidr` values.
+fn cidr_set_masklen(a: Datum<'_>, b: Datum<'_>) -> Result<Datum<'static>, EvalError> {
+    Ok(Datum::Inet(inet_with_masklen(a, b)?.network()))
+}
+
+fn text_search_config(a: Datum<'_>) -> Result<TextSearchConfig, EvalError> {
+    TextSearchConfig::from_name(a.unwrap_str())
+        .map_err(|e| EvalError::InvalidParameterValue(e.to_string().into()))
+}
+
+fn to_ts_vector<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let vector = text_search_config(a)?.to_tsvector(b.unwrap_str());
+    Ok(Datum::String(temp_storage.push_string(vector.to_string())))
+}
+
+fn to_ts_query<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let query = text_search_config(a)?
+        .to_tsquery(b.unwrap_str())
+        .map_err(|e| EvalError::InvalidParameterValue(e.to_string().into()))?;
+    Ok(Datum::String(temp_storage.push_string(query.to_string())))
+}
+
+fn plain_to_ts_query<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let query = text_search_config(a)?.plainto_tsquery(b.unwrap_str());
+    Ok(Datum::String(temp_storage.push_string(query.to_string())))
+}
+
+fn websearch_to_ts_query<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let query = text_search_config(a)?.websearch_to_tsquery(b.unwrap_str());
+    Ok(Datum::String(temp_storage.push_string(query.to_string())))
+}
+
+fn unwrap_ts_vector(d: Datum<'_>) -> TsVector {
+    d.unwrap_str()
+        .parse()
+        .expect("tsvector datums are canonical")
+}
+
+fn unwrap_ts_query(d: Datum<'_>) -> TsQuery {
+    d.unwrap_str()
+        .parse()
+        .expect("tsquery datums are canonical")
+}
+
+fn ts_match<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(unwrap_ts_query(b).matches(&unwrap_ts_vector(a)))
+}
+
+fn ts_rank<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(unwrap_ts_query(b).rank(&unwrap_ts_vector(a)))
+}
+
+fn st_make_point<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let point = Geometry::point(a.unwrap_float64(), b.unwrap_float64()).map_err(geo_error)?;
+    Ok(Datum::Bytes(temp_storage.push_bytes(point.to_ewkb())))
+}
+
+fn st_set_srid<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let geometry = unwrap_geometry(a.unwrap_bytes())
+        .with_srid(b.unwrap_int32())
+        .map_err(geo_error)?;
+    Ok(Datum::Bytes(temp_storage.push_bytes(geometry.to_ewkb())))
+}
+
+fn st_distance<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let a = unwrap_geometry(a.unwrap_bytes());
+    let b = unwrap_geometry(b.unwrap_bytes());
+    Ok(Datum::from(a.distance(&b).map_err(geo_error)?))
+}
+
+fn st_distance_sphere<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let a = unwrap_geometry(a.unwrap_bytes());
+    let b = unwrap_geometry(b.unwrap_bytes());
+    Ok(Datum::from(a.sphere_distance(&b).map_err(geo_error)?))
+}
+
+fn st_intersects<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let a = unwrap_geometry(a.unwrap_bytes());
+    let b = unwrap_geometry(b.unwrap_bytes());
+    Ok(Datum::from(a.intersects(&b).map_err(geo_error)?))
+}
+
+fn st_contains<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let a = unwrap_geometry(a.unwrap_bytes());
+    let b = unwrap_geometry(b.unwrap_bytes());
+    Ok(Datum::from(a.contains(&b).map_err(geo_error)?))
+}
+
+fn st_geo_hash_precision<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let precision = usize::try_from(b.unwrap_int32()).map_err(|_| {
+        EvalError::InvalidParameterValue("geohash precision must not be negative".into())
+    })?;
+    let geohash = unwrap_geometry(a.unwrap_bytes())
+        .geohash(Some(precision))
+        .map_err(geo_error)?;
+    Ok(Datum::String(temp_storage.push_string(geohash)))
+}
+
 // transliterated from postgres/src/backend/utils/adt/misc.c
 fn parse_ident<'a>(
     a: Datum<'a>,
@@ -2387,6 +2551,26 @@ pub enum BinaryFunc {
     RangeDifference,
     UuidGenerateV5,
     MzAclItemContainsPrivilege,
+    InetContains,
+    InetContainsOrEq,
+    InetContainedBy,
+    InetContainedByOrEq,
+    InetOverlaps,
+    InetSetMasklen,
+    CidrSetMasklen,
+    ToTsVector,
+    ToTsQuery,
+    PlainToTsQuery,
+    WebsearchToTsQuery,
+    TsMatch,
+    TsRank,
+    StMakePoint,
+    StSetSrid,
+    StDistance,
+    StDistanceSphere,
+    StIntersects,
+    StContains,
+    StGeoHashPrecision,
     ParseIdent,
     PrettySql,
     RegexpReplace {
@@ -2658,6 +2842,26 @@ impl BinaryFunc {
             BinaryFunc::RangeDifference => range_difference(a, b, temp_storage),
             BinaryFunc::UuidGenerateV5 => Ok(uuid_generate_v5(a, b)),
             BinaryFunc::MzAclItemContainsPrivilege => mz_acl_item_contains_privilege(a, b),
+            BinaryFunc::InetContains => Ok(inet_contains(a, b)),
+            BinaryFunc::InetContainsOrEq => Ok(inet_contains_or_eq(a, b)),
+            BinaryFunc::InetContainedBy => Ok(inet_contains(b, a)),
+            BinaryFunc::InetContainedByOrEq => Ok(inet_contains_or_eq(b, a)),
+            BinaryFunc::InetOverlaps => Ok(inet_overlaps(a, b)),
+            BinaryFunc::InetSetMasklen => inet_set_masklen(a, b),
+            BinaryFunc::CidrSetMasklen => cidr_set_masklen(a, b),
+            BinaryFunc::ToTsVector => to_ts_vector(a, b, temp_storage),
+            BinaryFunc::ToTsQuery => to_ts_query(a, b, temp_storage),
+            BinaryFunc::PlainToTsQuery => plain_to_ts_query(a, b, temp_storage),
+            BinaryFunc::WebsearchToTsQuery => websearch_to_ts_query(a, b, temp_storage),
+            BinaryFunc::TsMatch => Ok(ts_match(a, b)),
+            BinaryFunc::TsRank => Ok(ts_rank(a, b)),
+            BinaryFunc::StMakePoint => st_make_point(a, b, temp_storage),
+            BinaryFunc::StSetSrid => st_set_srid(a, b, temp_storage),
+            BinaryFunc::StDistance => st_distance(a, b),
+            BinaryFunc::StDistanceSphere => st_distance_sphere(a, b),
+            BinaryFunc::StIntersects => st_intersects(a, b),
+            BinaryFunc::StContains => st_contains(a, b),
+            BinaryFunc::StGeoHashPrecision => st_geo_hash_precision(a, b, temp_storage),
             BinaryFunc::ParseIdent => parse_ident(a, b, temp_storage),
             BinaryFunc::PrettySql => pretty_sql(a, b, temp_storage),
             BinaryFunc::RegexpReplace { regex } => match regex {
@@ -2777,6 +2981,7 @@ impl BinaryFunc {
                     ("dst_offset".into(), ScalarType::Interval.nullable(false)),
                 ].into(),
                 custom_id: None,
+                custom_oid: None,
             }.nullable(true),
 
             SubTime => ScalarType::Interval.nullable(in_nullable),
@@ -2855,6 +3060,25 @@ impl BinaryFunc {
 
             MzAclItemContainsPrivilege => ScalarType::Bool.nullable(in_nullable),
 
+            InetContains
+            | InetContainsOrEq
+            | InetContainedBy
+            | InetContainedByOrEq
+            | InetOverlaps => ScalarType::Bool.nullable(in_nullable),
+            InetSetMasklen => ScalarType::Inet.nullable(in_nullable),
+            CidrSetMasklen => ScalarType::Cidr.nullable(in_nullable),
+            ToTsVector => ScalarType::TsVector.nullable(in_nullable),
+            ToTsQuery | PlainToTsQuery | WebsearchToTsQuery => {
+                ScalarType::TsQuery.nullable(in_nullable)
+            }
+            TsMatch => ScalarType::Bool.nullable(in_nullable),
+            TsRank => ScalarType::Float32.nullable(in_nullable),
+            StMakePoint | StSetSrid => ScalarType::Geometry.nullable(in_nullable),
+            // The distance to or from an empty geometry is NULL.
+            StDistance | StDistanceSphere => ScalarType::Float64.nullable(true),
+            StIntersects | StContains => ScalarType::Bool.nullable(in_nullable),
+            StGeoHashPrecision => ScalarType::String.nullable(in_nullable),
+
             ParseIdent => ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable),
             PrettySql => ScalarType::String.nullable(in_nullable),
             RegexpReplace { .. } => ScalarType::String.nullable(in_nullable),
@@ -3062,6 +3286,24 @@ impl BinaryFunc {
             | RangeDifference
             | UuidGenerateV5
             | MzAclItemContainsPrivilege
+            | InetContains
+            | InetContainsOrEq
+            | InetContainedBy
+            | InetContainedByOrEq
+            | InetOverlaps
+            | InetSetMasklen
+            | CidrSetMasklen
+            | ToTsVector
+            | ToTsQuery
+            | PlainToTsQuery
+            | WebsearchToTsQuery
+            | TsMatch
+            | TsRank
+            | StMakePoint
+            | StSetSrid
+            | StIntersects
+            | StContains
+            | StGeoHashPrecision
             | ParseIdent
             | PrettySql
             | RegexpReplace { .. }
@@ -3077,7 +3319,9 @@ impl BinaryFunc {
             | ListLengthMax { .. }
             | ArrayLength
             | ArrayLower
-            | ArrayUpper => true,
+            | ArrayUpper
+            | StDistance
+            | StDistanceSphere => true,
         }
     }
 
@@ -3218,7 +3462,13 @@ impl BinaryFunc {
             | RangeAdjacent
             | RangeUnion
             | RangeIntersection
-            | RangeDifference => true,
+            | RangeDifference
+            | InetContains
+            | InetContainsOrEq
+            | InetContainedBy
+            | InetContainedByOrEq
+            | InetOverlaps
+            | TsMatch => true,
             ToCharTimestamp
             | ToCharTimestampTz
             | AgeTimestamp
@@ -3269,6 +3519,20 @@ impl BinaryFunc {
             | GetBit
             | GetByte
             | MzAclItemContainsPrivilege
+            | InetSetMasklen
+            | CidrSetMasklen
+            | ToTsVector
+            | ToTsQuery
+            | PlainToTsQuery
+            | WebsearchToTsQuery
+            | TsRank
+            | StMakePoint
+            | StSetSrid
+            | StDistance
+            | StDistanceSphere
+            | StIntersects
+            | StContains
+            | StGeoHashPrecision
             | ConstantTimeEqBytes
             | ConstantTimeEqString
             | ParseIdent
@@ -3343,6 +3607,12 @@ impl BinaryFunc {
             | BinaryFunc::MapContainsAllKeys
             | BinaryFunc::MapContainsAnyKeys
             | BinaryFunc::MapContainsMap => false,
+            BinaryFunc::InetContains
+            | BinaryFunc::InetContainsOrEq
+            | BinaryFunc::InetContainedBy
+            | BinaryFunc::InetContainedByOrEq
+            | BinaryFunc::InetOverlaps => false,
+            BinaryFunc::TsMatch | BinaryFunc::TsRank => false,
             _ => true,
         }
     }
@@ -3549,6 +3819,26 @@ impl BinaryFunc {
             | BinaryFunc::RangeDifference => (false, false),
             BinaryFunc::UuidGenerateV5 => (false, false),
             BinaryFunc::MzAclItemContainsPrivilege => (false, false),
+            BinaryFunc::InetContains => (false, false),
+            BinaryFunc::InetContainsOrEq => (false, false),
+            BinaryFunc::InetContainedBy => (false, false),
+            BinaryFunc::InetContainedByOrEq => (false, false),
+            BinaryFunc::InetOverlaps => (false, false),
+            BinaryFunc::InetSetMasklen => (false, false),
+            BinaryFunc::CidrSetMasklen => (false, false),
+            BinaryFunc::ToTsVector => (false, false),
+            BinaryFunc::ToTsQuery => (false, false),
+            BinaryFunc::PlainToTsQuery => (false, false),
+            BinaryFunc::WebsearchToTsQuery => (false, false),
+            BinaryFunc::TsMatch => (false, false),
+            BinaryFunc::TsRank => (false, false),
+            BinaryFunc::StMakePoint => (false, false),
+            BinaryFunc::StSetSrid => (false, false),
+            BinaryFunc::StDistance => (false, false),
+            BinaryFunc::StDistanceSphere => (false, false),
+            BinaryFunc::StIntersects => (false, false),
+            BinaryFunc::StContains => (false, false),
+            BinaryFunc::StGeoHashPrecision => (false, false),
             BinaryFunc::ParseIdent => (false, false),
             BinaryFunc::ConstantTimeEqBytes | BinaryFunc::ConstantTimeEqString => (false, false),
             BinaryFunc::PrettySql => (false, false),
@@ -3764,6 +4054,26 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::RangeDifference => f.write_str("-"),
             BinaryFunc::UuidGenerateV5 => f.write_str("uuid_generate_v5"),
             BinaryFunc::MzAclItemContainsPrivilege => f.write_str("mz_aclitem_contains_privilege"),
+            BinaryFunc::InetContains => f.write_str(">>"),
+            BinaryFunc::InetContainsOrEq => f.write_str(">>="),
+            BinaryFunc::InetContainedBy => f.write_str("<<"),
+            BinaryFunc::InetContainedByOrEq => f.write_str("<<="),
+            BinaryFunc::InetOverlaps => f.write_str("&&"),
+            BinaryFunc::InetSetMasklen => f.write_str("set_masklen"),
+            BinaryFunc::CidrSetMasklen => f.write_str("set_masklen"),
+            BinaryFunc::ToTsVector => f.write_str("to_tsvector"),
+            BinaryFunc::ToTsQuery => f.write_str("to_tsquery"),
+            BinaryFunc::PlainToTsQuery => f.write_str("plainto_tsquery"),
+            BinaryFunc::WebsearchToTsQuery => f.write_str("websearch_to_tsquery"),
+            BinaryFunc::TsMatch => f.write_str("@@"),
+            BinaryFunc::TsRank => f.write_str("ts_rank"),
+            BinaryFunc::StMakePoint => f.write_str("st_makepoint"),
+            BinaryFunc::StSetSrid => f.write_str("st_setsrid"),
+            BinaryFunc::StDistance => f.write_str("st_distance"),
+            BinaryFunc::StDistanceSphere => f.write_str("st_distancesphere"),
+            BinaryFunc::StIntersects => f.write_str("st_intersects"),
+            BinaryFunc::StContains => f.write_str("st_contains"),
+            BinaryFunc::StGeoHashPrecision => f.write_str("st_geohash"),
             BinaryFunc::ParseIdent => f.write_str("parse_ident"),
             BinaryFunc::PrettySql => f.write_str("pretty_sql"),
             BinaryFunc::RegexpReplace { regex } => match regex {
@@ -4188,6 +4498,26 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::RangeDifference => RangeDifference(()),
             BinaryFunc::UuidGenerateV5 => UuidGenerateV5(()),
             BinaryFunc::MzAclItemContainsPrivilege => MzAclItemContainsPrivilege(()),
+            BinaryFunc::InetContains => InetContains(()),
+            BinaryFunc::InetContainsOrEq => InetContainsOrEq(()),
+            BinaryFunc::InetContainedBy => InetContainedBy(()),
+            BinaryFunc::InetContainedByOrEq => InetContainedByOrEq(()),
+            BinaryFunc::InetOverlaps => InetOverlaps(()),
+            BinaryFunc::InetSetMasklen => InetSetMasklen(()),
+            BinaryFunc::CidrSetMasklen => CidrSetMasklen(()),
+            BinaryFunc::ToTsVector => ToTsVector(()),
+            BinaryFunc::ToTsQuery => ToTsQuery(()),
+            BinaryFunc::PlainToTsQuery => PlainToTsQuery(()),
+            BinaryFunc::WebsearchToTsQuery => WebsearchToTsQuery(()),
+            BinaryFunc::TsMatch => TsMatch(()),
+            BinaryFunc::TsRank => TsRank(()),
+            BinaryFunc::StMakePoint => StMakePoint(()),
+            BinaryFunc::StSetSrid => StSetSrid(()),
+            BinaryFunc::StDistance => StDistance(()),
+            BinaryFunc::StDistanceSphere => StDistanceSphere(()),
+            BinaryFunc::StIntersects => StIntersects(()),
+            BinaryFunc::StContains => StContains(()),
+            BinaryFunc::StGeoHashPrecision => StGeoHashPrecision(()),
             BinaryFunc::ParseIdent => ParseIdent(()),
             BinaryFunc::ConstantTimeEqBytes => ConstantTimeEqBytes(()),
             BinaryFunc::ConstantTimeEqString => ConstantTimeEqString(()),
@@ -4409,6 +4739,26 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 RangeDifference(()) => Ok(BinaryFunc::RangeDifference),
                 UuidGenerateV5(()) => Ok(BinaryFunc::UuidGenerateV5),
                 MzAclItemContainsPrivilege(()) => Ok(BinaryFunc::MzAclItemContainsPrivilege),
+                InetContains(()) => Ok(BinaryFunc::InetContains),
+                InetContainsOrEq(()) => Ok(BinaryFunc::InetContainsOrEq),
+                InetContainedBy(()) => Ok(BinaryFunc::InetContainedBy),
+                InetContainedByOrEq(()) => Ok(BinaryFunc::InetContainedByOrEq),
+                InetOverlaps(()) => Ok(BinaryFunc::InetOverlaps),
+                InetSetMasklen(()) => Ok(BinaryFunc::InetSetMasklen),
+                CidrSetMasklen(()) => Ok(BinaryFunc::CidrSetMasklen),
+                ToTsVector(()) => Ok(BinaryFunc::ToTsVector),
+                ToTsQuery(()) => Ok(BinaryFunc::ToTsQuery),
+                PlainToTsQuery(()) => Ok(BinaryFunc::PlainToTsQuery),
+                WebsearchToTsQuery(()) => Ok(BinaryFunc::WebsearchToTsQuery),
+                TsMatch(()) => Ok(BinaryFunc::TsMatch),
+                TsRank(()) => Ok(BinaryFunc::TsRank),
+                StMakePoint(()) => Ok(BinaryFunc::StMakePoint),
+                StSetSrid(()) => Ok(BinaryFunc::StSetSrid),
+                StDistance(()) => Ok(BinaryFunc::StDistance),
+                StDistanceSphere(()) => Ok(BinaryFunc::StDistanceSphere),
+                StIntersects(()) => Ok(BinaryFunc::StIntersects),
+                StContains(()) => Ok(BinaryFunc::StContains),
+                StGeoHashPrecision(()) => Ok(BinaryFunc::StGeoHashPrecision),
                 ParseIdent(()) => Ok(BinaryFunc::ParseIdent),
                 ConstantTimeEqBytes(()) => Ok(BinaryFunc::ConstantTimeEqBytes),
                 ConstantTimeEqString(()) => Ok(BinaryFunc::ConstantTimeEqString),
@@ -4812,6 +5162,7 @@ derive_unary!(
     CastUuidToString,
     CastRecordToString,
     CastRecord1ToRecord2,
+    CastStringToRecord,
     CastArrayToArray,
     CastArrayToJsonb,
     CastArrayToString,
@@ -4932,7 +5283,52 @@ derive_unary!(
     KafkaMurmur2Bytes,
     KafkaMurmur2String,
     SeahashBytes,
-    SeahashString
+    SeahashString,
+    CastStringToInet,
+    CastInetToString,
+    CastStringToCidr,
+    CastInetToCidr,
+    CastCidrToInet,
+    InetNetwork,
+    InetHost,
+    InetAbbrev,
+    InetMasklen,
+    InetFamily,
+    InetBroadcast,
+    InetNetmask,
+    InetHostmask,
+    CastStringToMacAddr,
+    CastMacAddrToString,
+    CastStringToMacAddr8,
+    CastMacAddr8ToString,
+    CastMacAddrToMacAddr8,
+    CastMacAddr8ToMacAddr,
+    CastStringToTsVector,
+    CastTsVectorToString,
+    CastStringToTsQuery,
+    CastTsQueryToString,
+    CastStringToJsonPath,
+    CastJsonPathToString,
+    CastStringToEnum,
+    CastEnumToString,
+    CastStringToGeometry,
+    CastGeometryToString,
+    CastStringToGeography,
+    CastGeographyToString,
+    CastGeometryToGeography,
+    CastGeographyToGeometry,
+    StGeomFromWkb,
+    StGeomFromGeoJson,
+    StSrid,
+    StX,
+    StY,
+    StGeometryType,
+    StAsBinary,
+    StAsText,
+    StAsEwkt,
+    StAsGeoJson,
+    StGeoHash,
+    GeoHashNeighbors
 );
 
 impl UnaryFunc {
@@ -5217,6 +5613,17 @@ impl Arbitrary for UnaryFunc {
                     })
                 })
                 .boxed(),
+            (
+                any::<ScalarType>(),
+                proptest::collection::vec(any::<MirScalarExpr>(), 1..5),
+            )
+                .prop_map(|(return_ty, cast_exprs)| {
+                    UnaryFunc::CastStringToRecord(CastStringToRecord {
+                        return_ty,
+                        cast_exprs: cast_exprs.into(),
+                    })
+                })
+                .boxed(),
             CastArrayToJsonb::arbitrary().prop_map_into().boxed(),
             CastArrayToString::arbitrary().prop_map_into().boxed(),
             CastListToString::arbitrary().prop_map_into().boxed(),
@@ -5345,6 +5752,51 @@ impl Arbitrary for UnaryFunc {
             AclItemGrantee::arbitrary().prop_map_into().boxed(),
             AclItemPrivileges::arbitrary().prop_map_into().boxed(),
             QuoteIdent::arbitrary().prop_map_into().boxed(),
+            CastStringToInet::arbitrary().prop_map_into().boxed(),
+            CastInetToString::arbitrary().prop_map_into().boxed(),
+            CastStringToCidr::arbitrary().prop_map_into().boxed(),
+            CastInetToCidr::arbitrary().prop_map_into().boxed(),
+            CastCidrToInet::arbitrary().prop_map_into().boxed(),
+            InetNetwork::arbitrary().prop_map_into().boxed(),
+            InetHost::arbitrary().prop_map_into().boxed(),
+            InetAbbrev::arbitrary().prop_map_into().boxed(),
+            InetMasklen::arbitrary().prop_map_into().boxed(),
+            InetFamily::arbitrary().prop_map_into().boxed(),
+            InetBroadcast::arbitrary().prop_map_into().boxed(),
+            InetNetmask::arbitrary().prop_map_into().boxed(),
+            InetHostmask::arbitrary().prop_map_into().boxed(),
+            CastStringToMacAddr::arbitrary().prop_map_into().boxed(),
+            CastMacAddrToString::arbitrary().prop_map_into().boxed(),
+            CastStringToMacAddr8::arbitrary().prop_map_into().boxed(),
+            CastMacAddr8ToString::arbitrary().prop_map_into().boxed(),
+            CastMacAddrToMacAddr8::arbitrary().prop_map_into().boxed(),
+            CastMacAddr8ToMacAddr::arbitrary().prop_map_into().boxed(),
+            CastStringToTsVector::arbitrary().prop_map_into().boxed(),
+            CastTsVectorToString::arbitrary().prop_map_into().boxed(),
+            CastStringToTsQuery::arbitrary().prop_map_into().boxed(),
+            CastTsQueryToString::arbitrary().prop_map_into().boxed(),
+            CastStringToJsonPath::arbitrary().prop_map_into().boxed(),
+            CastJsonPathToString::arbitrary().prop_map_into().boxed(),
+            CastStringToEnum::arbitrary().prop_map_into().boxed(),
+            CastEnumToString::arbitrary().prop_map_into().boxed(),
+            CastStringToGeometry::arbitrary().prop_map_into().boxed(),
+            CastGeometryToString::arbitrary().prop_map_into().boxed(),
+            CastStringToGeography::arbitrary().prop_map_into().boxed(),
+            CastGeographyToString::arbitrary().prop_map_into().boxed(),
+            CastGeometryToGeography::arbitrary().prop_map_into().boxed(),
+            CastGeographyToGeometry::arbitrary().prop_map_into().boxed(),
+            StGeomFromWkb::arbitrary().prop_map_into().boxed(),
+            StGeomFromGeoJson::arbitrary().prop_map_into().boxed(),
+            StSrid::arbitrary().prop_map_into().boxed(),
+            StX::arbitrary().prop_map_into().boxed(),
+            StY::arbitrary().prop_map_into().boxed(),
+            StGeometryType::arbitrary().prop_map_into().boxed(),
+            StAsBinary::arbitrary().prop_map_into().boxed(),
+            StAsText::arbitrary().prop_map_into().boxed(),
+            StAsEwkt::arbitrary().prop_map_into().boxed(),
+            StAsGeoJson::arbitrary().prop_map_into().boxed(),
+            StGeoHash::arbitrary().prop_map_into().boxed(),
+            GeoHashNeighbors::arbitrary().prop_map_into().boxed(),
         ])
     }
 }
@@ -5602,6 +6054,10 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                     cast_exprs: inner.cast_exprs.into_proto(),
                 })
             }
+            UnaryFunc::CastStringToRecord(inner) => CastStringToRecord(ProtoCastRecord1ToRecord2 {
+                return_ty: Some(inner.return_ty.into_proto()),
+                cast_exprs: inner.cast_exprs.into_proto(),
+            }),
             UnaryFunc::CastArrayToArray(inner) => {
                 CastArrayToArray(Box::new(ProtoCastToVariableType {
                     return_ty: Some(inner.return_ty.into_proto()),
@@ -5758,6 +6214,51 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::KafkaMurmur2String(_) => KafkaMurmur2String(()),
             UnaryFunc::SeahashBytes(_) => SeahashBytes(()),
             UnaryFunc::SeahashString(_) => SeahashString(()),
+            UnaryFunc::CastStringToInet(_) => CastStringToInet(()),
+            UnaryFunc::CastInetToString(_) => CastInetToString(()),
+            UnaryFunc::CastStringToCidr(_) => CastStringToCidr(()),
+            UnaryFunc::CastInetToCidr(_) => CastInetToCidr(()),
+            UnaryFunc::CastCidrToInet(_) => CastCidrToInet(()),
+            UnaryFunc::InetNetwork(_) => InetNetwork(()),
+            UnaryFunc::InetHost(_) => InetHost(()),
+            UnaryFunc::InetAbbrev(_) => InetAbbrev(()),
+            UnaryFunc::InetMasklen(_) => InetMasklen(()),
+            UnaryFunc::InetFamily(_) => InetFamily(()),
+            UnaryFunc::InetBroadcast(_) => InetBroadcast(()),
+            UnaryFunc::InetNetmask(_) => InetNetmask(()),
+            UnaryFunc::InetHostmask(_) => InetHostmask(()),
+            UnaryFunc::CastStringToMacAddr(_) => CastStringToMacAddr(()),
+            UnaryFunc::CastMacAddrToString(_) => CastMacAddrToString(()),
+            UnaryFunc::CastStringToMacAddr8(_) => CastStringToMacAddr8(()),
+            UnaryFunc::CastMacAddr8ToString(_) => CastMacAddr8ToString(()),
+            UnaryFunc::CastMacAddrToMacAddr8(_) => CastMacAddrToMacAddr8(()),
+            UnaryFunc::CastMacAddr8ToMacAddr(_) => CastMacAddr8ToMacAddr(()),
+            UnaryFunc::CastStringToTsVector(_) => CastStringToTsVector(()),
+            UnaryFunc::CastTsVectorToString(_) => CastTsVectorToString(()),
+            UnaryFunc::CastStringToTsQuery(_) => CastStringToTsQuery(()),
+            UnaryFunc::CastTsQueryToString(_) => CastTsQueryToString(()),
+            UnaryFunc::CastStringToJsonPath(_) => CastStringToJsonPath(()),
+            UnaryFunc::CastJsonPathToString(_) => CastJsonPathToString(()),
+            UnaryFunc::CastStringToEnum(func) => CastStringToEnum(func.return_ty.into_proto()),
+            UnaryFunc::CastEnumToString(func) => CastEnumToString(func.ty.into_proto()),
+            UnaryFunc::CastStringToGeometry(_) => CastStringToGeometry(()),
+            UnaryFunc::CastGeometryToString(_) => CastGeometryToString(()),
+            UnaryFunc::CastStringToGeography(_) => CastStringToGeography(()),
+            UnaryFunc::CastGeographyToString(_) => CastGeographyToString(()),
+            UnaryFunc::CastGeometryToGeography(_) => CastGeometryToGeography(()),
+            UnaryFunc::CastGeographyToGeometry(_) => CastGeographyToGeometry(()),
+            UnaryFunc::StGeomFromWkb(_) => StGeomFromWkb(()),
+            UnaryFunc::StGeomFromGeoJson(_) => StGeomFromGeoJson(()),
+            UnaryFunc::StSrid(_) => StSrid(()),
+            UnaryFunc::StX(_) => StX(()),
+            UnaryFunc::StY(_) => StY(()),
+            UnaryFunc::StGeometryType(_) => StGeometryType(()),
+            UnaryFunc::StAsBinary(_) => StAsBinary(()),
+            UnaryFunc::StAsText(_) => StAsText(()),
+            UnaryFunc::StAsEwkt(_) => StAsEwkt(()),
+            UnaryFunc::StAsGeoJson(_) => StAsGeoJson(()),
+            UnaryFunc::StGeoHash(_) => StGeoHash(()),
+            UnaryFunc::GeoHashNeighbors(_) => GeoHashNeighbors(()),
         };
         ProtoUnaryFunc { kind: Some(kind) }
     }
@@ -6053,6 +6554,13 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                     cast_exprs: inner.cast_exprs.into_rust()?,
                 }
                 .into()),
+                CastStringToRecord(inner) => Ok(impls::CastStringToRecord {
+                    return_ty: inner
+                        .return_ty
+                        .into_rust_if_some("ProtoCastStringToRecord::return_ty")?,
+                    cast_exprs: inner.cast_exprs.into_rust()?,
+                }
+                .into()),
                 CastArrayToArray(inner) => Ok(impls::CastArrayToArray {
                     return_ty: inner
                         .return_ty
@@ -6256,6 +6764,57 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 KafkaMurmur2String(()) => Ok(impls::KafkaMurmur2String.into()),
                 SeahashBytes(()) => Ok(impls::SeahashBytes.into()),
                 SeahashString(()) => Ok(impls::SeahashString.into()),
+                CastStringToInet(()) => Ok(impls::CastStringToInet.into()),
+                CastInetToString(()) => Ok(impls::CastInetToString.into()),
+                CastStringToCidr(()) => Ok(impls::CastStringToCidr.into()),
+                CastInetToCidr(()) => Ok(impls::CastInetToCidr.into()),
+                CastCidrToInet(()) => Ok(impls::CastCidrToInet.into()),
+                InetNetwork(()) => Ok(impls::InetNetwork.into()),
+                InetHost(()) => Ok(impls::InetHost.into()),
+                InetAbbrev(()) => Ok(impls::InetAbbrev.into()),
+                InetMasklen(()) => Ok(impls::InetMasklen.into()),
+                InetFamily(()) => Ok(impls::InetFamily.into()),
+                InetBroadcast(()) => Ok(impls::InetBroadcast.into()),
+                InetNetmask(()) => Ok(impls::InetNetmask.into()),
+                InetHostmask(()) => Ok(impls::InetHostmask.into()),
+                CastStringToMacAddr(()) => Ok(impls::CastStringToMacAddr.into()),
+                CastMacAddrToString(()) => Ok(impls::CastMacAddrToString.into()),
+                CastStringToMacAddr8(()) => Ok(impls::CastStringToMacAddr8.into()),
+                CastMacAddr8ToString(()) => Ok(impls::CastMacAddr8ToString.into()),
+                CastMacAddrToMacAddr8(()) => Ok(impls::CastMacAddrToMacAddr8.into()),
+                CastMacAddr8ToMacAddr(()) => Ok(impls::CastMacAddr8ToMacAddr.into()),
+                CastStringToTsVector(()) => Ok(impls::CastStringToTsVector.into()),
+                CastTsVectorToString(()) => Ok(impls::CastTsVectorToString.into()),
+                CastStringToTsQuery(()) => Ok(impls::CastStringToTsQuery.into()),
+                CastTsQueryToString(()) => Ok(impls::CastTsQueryToString.into()),
+                CastStringToJsonPath(()) => Ok(impls::CastStringToJsonPath.into()),
+                CastJsonPathToString(()) => Ok(impls::CastJsonPathToString.into()),
+                CastStringToEnum(return_ty) => Ok(impls::CastStringToEnum {
+                    return_ty: return_ty.into_rust()?,
+                }
+                .into()),
+                CastEnumToString(ty) => Ok(impls::CastEnumToString {
+                    ty: ty.into_rust()?,
+                }
+                .into()),
+                CastStringToGeometry(()) => Ok(impls::CastStringToGeometry.into()),
+                CastGeometryToString(()) => Ok(impls::CastGeometryToString.into()),
+                CastStringToGeography(()) => Ok(impls::CastStringToGeography.into()),
+                CastGeographyToString(()) => Ok(impls::CastGeographyToString.into()),
+                CastGeometryToGeography(()) => Ok(impls::CastGeometryToGeography.into()),
+                CastGeographyToGeometry(()) => Ok(impls::CastGeographyToGeometry.into()),
+                StGeomFromWkb(()) => Ok(impls::StGeomFromWkb.into()),
+                StGeomFromGeoJson(()) => Ok(impls::StGeomFromGeoJson.into()),
+                StSrid(()) => Ok(impls::StSrid.into()),
+                StX(()) => Ok(impls::StX.into()),
+                StY(()) => Ok(impls::StY.into()),
+                StGeometryType(()) => Ok(impls::StGeometryType.into()),
+                StAsBinary(()) => Ok(impls::StAsBinary.into()),
+                StAsText(()) => Ok(impls::StAsText.into()),
+                StAsEwkt(()) => Ok(impls::StAsEwkt.into()),
+                StAsGeoJson(()) => Ok(impls::StAsGeoJson.into()),
+                StGeoHash(()) => Ok(impls::StGeoHash.into()),
+                GeoHashNeighbors(()) => Ok(impls::GeoHashNeighbors.into()),
             }
         } else {
             Err(TryFromProtoError::missing_field("ProtoUnaryFunc::kind"))
@@ -6778,6 +7337,71 @@ fn jsonb_build_object<'a>(
     })
 }
 
+/// Evaluates the jsonpath in `datums[1]` against the `jsonb` value in
+/// `datums[0]`, with the variables in `datums[2]`, and passes the selected
+/// items to `f`.
+///
+/// If `datums[3]` is true, errors are suppressed and reported as `None`, like
+/// the `silent` argument of the PostgreSQL functions.
+pub(crate) fn eval_jsonb_path<'a, T>(
+    datums: &[Datum<'a>],
+    f: impl FnOnce(Vec<JsonPathItem<'_>>) -> Result<T, anyhow::Error>,
+) -> Result<Option<T>, EvalError> {
+    let path: JsonPath = datums[1]
+        .unwrap_str()
+        .parse()
+        .expect("jsonpath datums are canonical");
+    match path.query(datums[0], datums[2]).and_then(f) {
+        Ok(result) => Ok(Some(result)),
+        Err(_) if datums[3].unwrap_bool() => Ok(None),
+        Err(e) => Err(EvalError::InvalidParameterValue(e.to_string().into())),
+    }
+}
+
+fn jsonb_path_exists<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let exists = eval_jsonb_path(datums, |items| Ok(!items.is_empty()))?;
+    Ok(exists.into())
+}
+
+fn jsonb_path_match<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let result = eval_jsonb_path(datums, |items| match items[..] {
+        [JsonPathItem::Datum(Datum::True)] => Ok(Datum::True),
+        [JsonPathItem::Datum(Datum::False)] => Ok(Datum::False),
+        [JsonPathItem::Datum(Datum::JsonNull)] => Ok(Datum::Null),
+        _ => Err(anyhow::anyhow!("single boolean result is expected")),
+    })?;
+    Ok(result.unwrap_or(Datum::Null))
+}
+
+fn jsonb_path_query_array<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let array = eval_jsonb_path(datums, |items| {
+        Ok(temp_storage.make_datum(|packer| {
+            packer.push_list_with(|packer| {
+                for item in &items {
+                    item.push(packer);
+                }
+            })
+        }))
+    })?;
+    // Like PostgreSQL, suppressed errors produce an empty array.
+    Ok(array.unwrap_or_else(Datum::empty_list))
+}
+
+fn jsonb_path_query_first<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let first = eval_jsonb_path(datums, |items| {
+        Ok(items
+            .first()
+            .map(|item| temp_storage.make_datum(|packer| item.push(packer))))
+    })?;
+    Ok(first.flatten().into())
+}
+
 fn map_build<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     // Collect into a `BTreeMap` to provide the same semantics as it.
     let map: std::collections::BTreeMap<&str, _> = datums
@@ -6927,7 +7551,9 @@ where
         TimestampTz { .. } => Ok(strconv::format_timestamptz(buf, &d.unwrap_timestamptz())),
         Interval => Ok(strconv::format_interval(buf, d.unwrap_interval())),
         Bytes => Ok(strconv::format_bytes(buf, d.unwrap_bytes())),
-        String | VarChar { .. } | PgLegacyName => Ok(strconv::format_string(buf, d.unwrap_str())),
+        String | VarChar { .. } | PgLegacyName | TsVector | TsQuery | JsonPath => {
+            Ok(strconv::format_string(buf, d.unwrap_str()))
+        }
         Char { length } => Ok(strconv::format_string(
             buf,
             &mz_repr::adt::char::format_str_pad(d.unwrap_str(), *length),
@@ -6984,6 +7610,18 @@ where
             None => Ok::<_, EvalError>(buf.write_null()),
         }),
         MzAclItem => Ok(strconv::format_mz_acl_item(buf, d.unwrap_mz_acl_item())),
+        Inet => Ok(strconv::format_inet(buf, d.unwrap_inet())),
+        Cidr => Ok(strconv::format_cidr(buf, d.unwrap_inet())),
+        MacAddr => Ok(strconv::format_macaddr(buf, d.unwrap_macaddr())),
+        MacAddr8 => Ok(strconv::format_macaddr8(buf, d.unwrap_macaddr8())),
+        Enum { labels, .. } => Ok(strconv::format_string(
+            buf,
+            &labels[usize::cast_from(d.unwrap_uint32())],
+        )),
+        Geometry | Geography => Ok(strconv::format_geometry(
+            buf,
+            &unwrap_geometry(d.unwrap_bytes()),
+        )),
     }
 }
 
@@ -7848,6 +8486,10 @@ pub enum VariadicFunc {
     TimezoneTime,
     RegexpSplitToArray,
     RegexpReplace,
+    JsonbPathExists,
+    JsonbPathMatch,
+    JsonbPathQueryArray,
+    JsonbPathQueryFirst,
 }
 
 impl VariadicFunc {
@@ -7951,6 +8593,10 @@ impl VariadicFunc {
                 regexp_split_to_array(ds[0], ds[1], flags, temp_storage)
             }
             VariadicFunc::RegexpReplace => regexp_replace_dynamic(&ds, temp_storage),
+            VariadicFunc::JsonbPathExists => jsonb_path_exists(&ds),
+            VariadicFunc::JsonbPathMatch => jsonb_path_match(&ds),
+            VariadicFunc::JsonbPathQueryArray => jsonb_path_query_array(&ds, temp_storage),
+            VariadicFunc::JsonbPathQueryFirst => jsonb_path_query_first(&ds, temp_storage),
         }
     }
 
@@ -7997,7 +8643,11 @@ impl VariadicFunc {
             | VariadicFunc::ArrayFill { .. }
             | VariadicFunc::TimezoneTime
             | VariadicFunc::RegexpSplitToArray
-            | VariadicFunc::RegexpReplace => false,
+            | VariadicFunc::RegexpReplace
+            | VariadicFunc::JsonbPathExists
+            | VariadicFunc::JsonbPathMatch
+            | VariadicFunc::JsonbPathQueryArray
+            | VariadicFunc::JsonbPathQueryFirst => false,
         }
     }
 
@@ -8074,6 +8724,7 @@ impl VariadicFunc {
                     .zip_eq(input_types)
                     .collect(),
                 custom_id: None,
+                custom_oid: None,
             }
             .nullable(false),
             SplitPart => ScalarType::String.nullable(in_nullable),
@@ -8102,6 +8753,9 @@ impl VariadicFunc {
                 ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable)
             }
             RegexpReplace => ScalarType::String.nullable(in_nullable),
+            JsonbPathExists | JsonbPathMatch => ScalarType::Bool.nullable(true),
+            JsonbPathQueryArray => ScalarType::Jsonb.nullable(in_nullable),
+            JsonbPathQueryFirst => ScalarType::Jsonb.nullable(true),
         }
     }
 
@@ -8173,14 +8827,18 @@ impl VariadicFunc {
             | ArrayFill { .. }
             | TimezoneTime
             | RegexpSplitToArray
-            | RegexpReplace => false,
+            | RegexpReplace
+            | JsonbPathQueryArray => false,
             Coalesce
             | Greatest
             | Least
             | MakeTimestamp
             | ArrayIndex { .. }
             | ListIndex
-            | RegexpMatch => true,
+            | RegexpMatch
+            | JsonbPathExists
+            | JsonbPathMatch
+            | JsonbPathQueryFirst => true,
         }
     }
 
@@ -8286,7 +8944,11 @@ impl VariadicFunc {
             | VariadicFunc::DateDiffTime
             | VariadicFunc::TimezoneTime
             | VariadicFunc::RegexpSplitToArray
-            | VariadicFunc::RegexpReplace => false,
+            | VariadicFunc::RegexpReplace
+            | VariadicFunc::JsonbPathExists
+            | VariadicFunc::JsonbPathMatch
+            | VariadicFunc::JsonbPathQueryArray
+            | VariadicFunc::JsonbPathQueryFirst => false,
         }
     }
 }
@@ -8344,6 +9006,10 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::TimezoneTime => f.write_str("timezonet"),
             VariadicFunc::RegexpSplitToArray => f.write_str("regexp_split_to_array"),
             VariadicFunc::RegexpReplace => f.write_str("regexp_replace"),
+            VariadicFunc::JsonbPathExists => f.write_str("jsonb_path_exists"),
+            VariadicFunc::JsonbPathMatch => f.write_str("jsonb_path_match"),
+            VariadicFunc::JsonbPathQueryArray => f.write_str("jsonb_path_query_array"),
+            VariadicFunc::JsonbPathQueryFirst => f.write_str("jsonb_path_query_first"),
         }
     }
 }
@@ -8414,6 +9080,10 @@ impl Arbitrary for VariadicFunc {
             ScalarType::arbitrary()
                 .prop_map(|elem_type| VariadicFunc::ArrayFill { elem_type })
                 .boxed(),
+            Just(VariadicFunc::JsonbPathExists).boxed(),
+            Just(VariadicFunc::JsonbPathMatch).boxed(),
+            Just(VariadicFunc::JsonbPathQueryArray).boxed(),
+            Just(VariadicFunc::JsonbPathQueryFirst).boxed(),
         ])
     }
 }
@@ -8466,6 +9136,10 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
             VariadicFunc::TimezoneTime => TimezoneTime(()),
             VariadicFunc::RegexpSplitToArray => RegexpSplitToArray(()),
             VariadicFunc::RegexpReplace => RegexpReplace(()),
+            VariadicFunc::JsonbPathExists => JsonbPathExists(()),
+            VariadicFunc::JsonbPathMatch => JsonbPathMatch(()),
+            VariadicFunc::JsonbPathQueryArray => JsonbPathQueryArray(()),
+            VariadicFunc::JsonbPathQueryFirst => JsonbPathQueryFirst(()),
         };
         ProtoVariadicFunc { kind: Some(kind) }
     }
@@ -8532,6 +9206,10 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
                 TimezoneTime(()) => Ok(VariadicFunc::TimezoneTime),
                 RegexpSplitToArray(()) => Ok(VariadicFunc::RegexpSplitToArray),
                 RegexpReplace(()) => Ok(VariadicFunc::RegexpReplace),
+                JsonbPathExists(()) => Ok(VariadicFunc::JsonbPathExists),
+                JsonbPathMatch(()) => Ok(VariadicFunc::JsonbPathMatch),
+                JsonbPathQueryArray(()) => Ok(VariadicFunc::JsonbPathQueryArray),
+                JsonbPathQueryFirst(()) => Ok(VariadicFunc::JsonbPathQueryFirst),
             }
         } else {
             Err(TryFromProtoError::missing_field(
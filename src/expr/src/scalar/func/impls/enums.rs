@@ -0,0 +1,140 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use mz_lowertest::MzReflect;
+use mz_ore::cast::CastFrom;
+use mz_repr::{ColumnType, Datum, RowArena, ScalarType};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::scalar::func::LazyUnaryFunc;
+use crate::{EvalError, MirScalarExpr};
+
+/// Casts a string to a value of the enum type `return_ty` by looking up the
+/// string among the type's labels.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToEnum {
+    pub return_ty: ScalarType,
+}
+
+impl LazyUnaryFunc for CastStringToEnum {
+    fn eval<'a>(
+        &'a self,
+        datums: &[Datum<'a>],
+        temp_storage: &'a RowArena,
+        a: &'a MirScalarExpr,
+    ) -> Result<Datum<'a>, EvalError> {
+        let a = a.eval(datums, temp_storage)?;
+        if a.is_null() {
+            return Ok(Datum::Null);
+        }
+        let s = a.unwrap_str();
+        let labels = self.return_ty.unwrap_enum_labels();
+        match labels.iter().position(|l| l == s) {
+            Some(i) => Ok(Datum::UInt32(
+                u32::try_from(i).expect("enum has too many labels"),
+            )),
+            None => Err(EvalError::InvalidParameterValue(
+                format!("invalid input value for enum: \"{s}\"").into(),
+            )),
+        }
+    }
+
+    fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        self.return_ty.clone().nullable(input_type.nullable)
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        false
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        true
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastEnumToString {
+            ty: self.return_ty.clone(),
+        })
+    }
+
+    fn is_monotone(&self) -> bool {
+        false
+    }
+}
+
+impl fmt::Display for CastStringToEnum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_enum")
+    }
+}
+
+/// Casts a value of the enum type `ty` to its label.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastEnumToString {
+    pub ty: ScalarType,
+}
+
+impl LazyUnaryFunc for CastEnumToString {
+    fn eval<'a>(
+        &'a self,
+        datums: &[Datum<'a>],
+        temp_storage: &'a RowArena,
+        a: &'a MirScalarExpr,
+    ) -> Result<Datum<'a>, EvalError> {
+        let a = a.eval(datums, temp_storage)?;
+        if a.is_null() {
+            return Ok(Datum::Null);
+        }
+        let labels = self.ty.unwrap_enum_labels();
+        Ok(Datum::String(&labels[usize::cast_from(a.unwrap_uint32())]))
+    }
+
+    fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        ScalarType::String.nullable(input_type.nullable)
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        false
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        true
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastStringToEnum {
+            return_ty: self.ty.clone(),
+        })
+    }
+
+    fn is_monotone(&self) -> bool {
+        false
+    }
+}
+
+impl fmt::Display for CastEnumToString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("enum_to_text")
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use mz_lowertest::MzReflect;
+use mz_ore::result::ResultExt;
+use mz_repr::adt::geo::{self, Geometry};
+use mz_repr::{strconv, ArrayRustType, ColumnType, ScalarType};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::scalar::func::EagerUnaryFunc;
+use crate::EvalError;
+
+/// Decodes a `geometry` or `geography` datum.
+pub(crate) fn unwrap_geometry(a: &[u8]) -> Geometry {
+    Geometry::from_ewkb(a).expect("geometry datums are valid")
+}
+
+/// Converts an error from a geospatial operation into an [`EvalError`].
+pub(crate) fn geo_error(e: anyhow::Error) -> EvalError {
+    EvalError::InvalidParameterValue(e.to_string().into())
+}
+
+/// Parses a `geometry` value from a string, normalizing it to its canonical
+/// EWKB representation.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToGeometry;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToGeometry {
+    type Input = &'a str;
+    type Output = Result<Vec<u8>, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<Vec<u8>, EvalError> {
+        strconv::parse_geometry(a)
+            .map(|geometry| geometry.to_ewkb())
+            .err_into()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Geometry.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastGeometryToString)
+    }
+}
+
+impl fmt::Display for CastStringToGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_geometry")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "geometry_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToGeometry)]
+    fn cast_geometry_to_string<'a>(a: &'a [u8]) -> String {
+        unwrap_geometry(a).to_string()
+    }
+);
+
+/// Parses a `geography` value from a string, normalizing it to its canonical
+/// EWKB representation.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToGeography;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToGeography {
+    type Input = &'a str;
+    type Output = Result<Vec<u8>, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<Vec<u8>, EvalError> {
+        strconv::parse_geography(a)
+            .map(|geography| geography.to_ewkb())
+            .err_into()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Geography.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastGeographyToString)
+    }
+}
+
+impl fmt::Display for CastStringToGeography {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_geography")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "geography_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToGeography)]
+    fn cast_geography_to_string<'a>(a: &'a [u8]) -> String {
+        unwrap_geometry(a).to_string()
+    }
+);
+
+/// Converts a `geometry` into a `geography`, which requires its coordinates to
+/// be longitudes and latitudes.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastGeometryToGeography;
+
+impl<'a> EagerUnaryFunc<'a> for CastGeometryToGeography {
+    type Input = &'a [u8];
+    type Output = Result<Vec<u8>, EvalError>;
+
+    fn call(&self, a: &'a [u8]) -> Result<Vec<u8>, EvalError> {
+        let geography = unwrap_geometry(a).into_geography().map_err(geo_error)?;
+        Ok(geography.to_ewkb())
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Geography.nullable(input.nullable)
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        true
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastGeographyToGeometry)
+    }
+}
+
+impl fmt::Display for CastGeometryToGeography {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("geometry_to_geography")
+    }
+}
+
+/// Converts a `geography` into a `geometry` with the same SRID and
+/// coordinates.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastGeographyToGeometry;
+
+impl<'a> EagerUnaryFunc<'a> for CastGeographyToGeometry {
+    type Input = &'a [u8];
+    type Output = &'a [u8];
+
+    fn call(&self, a: &'a [u8]) -> &'a [u8] {
+        // Both types share the same representation.
+        a
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Geometry.nullable(input.nullable)
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        true
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastGeometryToGeography)
+    }
+}
+
+impl fmt::Display for CastGeographyToGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("geography_to_geometry")
+    }
+}
+
+/// Decodes a `geometry` from its (extended) well-known binary representation.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct StGeomFromWkb;
+
+impl<'a> EagerUnaryFunc<'a> for StGeomFromWkb {
+    type Input = &'a [u8];
+    type Output = Result<Vec<u8>, EvalError>;
+
+    fn call(&self, a: &'a [u8]) -> Result<Vec<u8>, EvalError> {
+        let geometry = Geometry::from_ewkb(a).map_err(geo_error)?;
+        Ok(geometry.to_ewkb())
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Geometry.nullable(input.nullable)
+    }
+}
+
+impl fmt::Display for StGeomFromWkb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("st_geomfromwkb")
+    }
+}
+
+/// Parses a `geometry` from its GeoJSON representation.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct StGeomFromGeoJson;
+
+impl<'a> EagerUnaryFunc<'a> for StGeomFromGeoJson {
+    type Input = &'a str;
+    type Output = Result<Vec<u8>, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<Vec<u8>, EvalError> {
+        let geometry = Geometry::from_geojson(a).map_err(geo_error)?;
+        Ok(geometry.to_ewkb())
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Geometry.nullable(input.nullable)
+    }
+}
+
+impl fmt::Display for StGeomFromGeoJson {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("st_geomfromgeojson")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "st_srid"]
+    fn st_srid<'a>(a: &'a [u8]) -> i32 {
+        unwrap_geometry(a).srid()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "st_x"]
+    fn st_x<'a>(a: &'a [u8]) -> Result<Option<f64>, EvalError> {
+        let point = unwrap_geometry(a).as_point().map_err(geo_error)?;
+        Ok(point.map(|c| c.x))
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "st_y"]
+    fn st_y<'a>(a: &'a [u8]) -> Result<Option<f64>, EvalError> {
+        let point = unwrap_geometry(a).as_point().map_err(geo_error)?;
+        Ok(point.map(|c| c.y))
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "st_geometrytype"]
+    fn st_geometry_type<'a>(a: &'a [u8]) -> &'a str {
+        unwrap_geometry(a).type_name()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "st_asbinary"]
+    fn st_as_binary<'a>(a: &'a [u8]) -> Vec<u8> {
+        unwrap_geometry(a).to_wkb()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "st_astext"]
+    fn st_as_text<'a>(a: &'a [u8]) -> String {
+        unwrap_geometry(a).to_wkt()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "st_asewkt"]
+    fn st_as_ewkt<'a>(a: &'a [u8]) -> String {
+        unwrap_geometry(a).to_ewkt()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "st_asgeojson"]
+    fn st_as_geo_json<'a>(a: &'a [u8]) -> String {
+        unwrap_geometry(a).to_geojson()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "st_geohash"]
+    fn st_geo_hash<'a>(a: &'a [u8]) -> Result<String, EvalError> {
+        unwrap_geometry(a).geohash(None).map_err(geo_error)
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "geohash_neighbors"]
+    fn geo_hash_neighbors<'a>(a: &'a str) -> Result<ArrayRustType<String>, EvalError> {
+        geo::geohash_neighbors(a)
+            .map(ArrayRustType)
+            .map_err(geo_error)
+    }
+);
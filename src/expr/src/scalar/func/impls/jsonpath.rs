@@ -0,0 +1,60 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use mz_lowertest::MzReflect;
+use mz_ore::result::ResultExt;
+use mz_repr::{strconv, ColumnType, ScalarType};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::scalar::func::EagerUnaryFunc;
+use crate::EvalError;
+
+/// Parses a `jsonpath` value from a string, normalizing it to its canonical
+/// text representation.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToJsonPath;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToJsonPath {
+    type Input = &'a str;
+    type Output = Result<String, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<String, EvalError> {
+        strconv::parse_jsonpath(a)
+            .map(|path| path.to_string())
+            .err_into()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::JsonPath.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastJsonPathToString)
+    }
+}
+
+impl fmt::Display for CastStringToJsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_jsonpath")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "jsonpath_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToJsonPath)]
+    fn cast_json_path_to_string<'a>(a: &'a str) -> &'a str {
+        a
+    }
+);
@@ -0,0 +1,242 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use mz_lowertest::MzReflect;
+use mz_ore::result::ResultExt;
+use mz_repr::adt::network::{Inet, MacAddr, MacAddr8};
+use mz_repr::{strconv, ColumnType, ScalarType};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::scalar::func::EagerUnaryFunc;
+use crate::EvalError;
+
+sqlfunc!(
+    #[sqlname = "text_to_inet"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastInetToString)]
+    fn cast_string_to_inet<'a>(a: &'a str) -> Result<Inet, EvalError> {
+        strconv::parse_inet(a).err_into()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "inet_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToInet)]
+    fn cast_inet_to_string(a: Inet) -> String {
+        // Unlike the output function, the cast to text always includes the
+        // netmask.
+        let mut buf = String::new();
+        strconv::format_cidr(&mut buf, a);
+        buf
+    }
+);
+
+/// Parses a `cidr` value from a string.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToCidr;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToCidr {
+    type Input = &'a str;
+    type Output = Result<Inet, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<Inet, EvalError> {
+        strconv::parse_cidr(a).err_into()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Cidr.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastInetToString)
+    }
+}
+
+impl fmt::Display for CastStringToCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_cidr")
+    }
+}
+
+/// Converts an `inet` value to a `cidr` value by clearing the bits to the
+/// right of the netmask.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastInetToCidr;
+
+impl<'a> EagerUnaryFunc<'a> for CastInetToCidr {
+    type Input = Inet;
+    type Output = Inet;
+
+    fn call(&self, a: Inet) -> Inet {
+        a.network()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Cidr.nullable(input.nullable)
+    }
+}
+
+impl fmt::Display for CastInetToCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("inet_to_cidr")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "cidr_to_inet"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastInetToCidr)]
+    #[is_monotone = true]
+    fn cast_cidr_to_inet(a: Inet) -> Inet {
+        a
+    }
+);
+
+/// Returns the network part of an `inet` value as a `cidr` value.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct InetNetwork;
+
+impl<'a> EagerUnaryFunc<'a> for InetNetwork {
+    type Input = Inet;
+    type Output = Inet;
+
+    fn call(&self, a: Inet) -> Inet {
+        a.network()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Cidr.nullable(input.nullable)
+    }
+}
+
+impl fmt::Display for InetNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("network")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "host"]
+    fn inet_host(a: Inet) -> String {
+        a.addr().to_string()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "abbrev"]
+    fn inet_abbrev(a: Inet) -> String {
+        let mut buf = String::new();
+        strconv::format_inet(&mut buf, a);
+        buf
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "masklen"]
+    fn inet_masklen(a: Inet) -> i32 {
+        i32::from(a.netmask())
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "family"]
+    fn inet_family(a: Inet) -> i32 {
+        a.family()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "broadcast"]
+    fn inet_broadcast(a: Inet) -> Inet {
+        a.broadcast()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "netmask"]
+    fn inet_netmask(a: Inet) -> Inet {
+        a.netmask_addr()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "hostmask"]
+    fn inet_hostmask(a: Inet) -> Inet {
+        a.hostmask_addr()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "text_to_macaddr"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastMacAddrToString)]
+    fn cast_string_to_mac_addr<'a>(a: &'a str) -> Result<MacAddr, EvalError> {
+        strconv::parse_macaddr(a).err_into()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "macaddr_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToMacAddr)]
+    fn cast_mac_addr_to_string(a: MacAddr) -> String {
+        let mut buf = String::with_capacity(17);
+        strconv::format_macaddr(&mut buf, a);
+        buf
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "text_to_macaddr8"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastMacAddr8ToString)]
+    fn cast_string_to_mac_addr8<'a>(a: &'a str) -> Result<MacAddr8, EvalError> {
+        strconv::parse_macaddr8(a).err_into()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "macaddr8_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToMacAddr8)]
+    fn cast_mac_addr8_to_string(a: MacAddr8) -> String {
+        let mut buf = String::with_capacity(23);
+        strconv::format_macaddr8(&mut buf, a);
+        buf
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "macaddr_to_macaddr8"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastMacAddr8ToMacAddr)]
+    fn cast_mac_addr_to_mac_addr8(a: MacAddr) -> MacAddr8 {
+        a.to_macaddr8()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "macaddr8_to_macaddr"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastMacAddrToMacAddr8)]
+    fn cast_mac_addr8_to_mac_addr(a: MacAddr8) -> Result<MacAddr, EvalError> {
+        a.to_macaddr()
+            .map_err(|e| EvalError::InvalidParameterValue(e.to_string().into()))
+    }
+);
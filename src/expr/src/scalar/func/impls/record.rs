@@ -11,7 +11,7 @@ use std::fmt;
 
 use itertools::Itertools;
 use mz_lowertest::MzReflect;
-use mz_repr::{ColumnType, Datum, RowArena, ScalarType};
+use mz_repr::{strconv, ColumnType, Datum, RowArena, ScalarType};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
@@ -136,6 +136,75 @@ impl fmt::Display for CastRecord1ToRecord2 {
     }
 }
 
+/// Casts a string to the record type `return_ty` by parsing the text of each
+/// field and casting it to the field's type using the corresponding element of
+/// `cast_exprs`.
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
+pub struct CastStringToRecord {
+    pub return_ty: ScalarType,
+    pub cast_exprs: Box<[MirScalarExpr]>,
+}
+
+impl LazyUnaryFunc for CastStringToRecord {
+    fn eval<'a>(
+        &'a self,
+        datums: &[Datum<'a>],
+        temp_storage: &'a RowArena,
+        a: &'a MirScalarExpr,
+    ) -> Result<Datum<'a>, EvalError> {
+        let a = a.eval(datums, temp_storage)?;
+        if a.is_null() {
+            return Ok(Datum::Null);
+        }
+        let field_texts = strconv::parse_record(a.unwrap_str(), self.cast_exprs.len())?;
+        let mut cast_datums = Vec::with_capacity(field_texts.len());
+        for (field_text, cast_expr) in field_texts.into_iter().zip_eq(&self.cast_exprs) {
+            cast_datums.push(match field_text {
+                Some(t) => {
+                    let t = temp_storage.push_string(t);
+                    cast_expr.eval(&[Datum::String(t)], temp_storage)?
+                }
+                None => Datum::Null,
+            });
+        }
+        Ok(temp_storage.make_datum(|packer| packer.push_list(cast_datums)))
+    }
+
+    fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        self.return_ty
+            .without_modifiers()
+            .nullable(input_type.nullable)
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        false
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        false
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastRecordToString {
+            ty: self.return_ty.clone(),
+        })
+    }
+
+    fn is_monotone(&self) -> bool {
+        false
+    }
+}
+
+impl fmt::Display for CastStringToRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("strtorecord")
+    }
+}
+
 #[derive(
     Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
 )]
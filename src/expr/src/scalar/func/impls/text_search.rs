@@ -0,0 +1,101 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use mz_lowertest::MzReflect;
+use mz_ore::result::ResultExt;
+use mz_repr::{strconv, ColumnType, ScalarType};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::scalar::func::EagerUnaryFunc;
+use crate::EvalError;
+
+/// Parses a `tsvector` value from a string, normalizing it to its canonical
+/// text representation.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToTsVector;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToTsVector {
+    type Input = &'a str;
+    type Output = Result<String, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<String, EvalError> {
+        strconv::parse_tsvector(a)
+            .map(|vector| vector.to_string())
+            .err_into()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::TsVector.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastTsVectorToString)
+    }
+}
+
+impl fmt::Display for CastStringToTsVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_tsvector")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "tsvector_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToTsVector)]
+    fn cast_ts_vector_to_string<'a>(a: &'a str) -> &'a str {
+        a
+    }
+);
+
+/// Parses a `tsquery` value from a string, normalizing it to its canonical
+/// text representation.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToTsQuery;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToTsQuery {
+    type Input = &'a str;
+    type Output = Result<String, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<String, EvalError> {
+        strconv::parse_tsquery(a)
+            .map(|query| query.to_string())
+            .err_into()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::TsQuery.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastTsQueryToString)
+    }
+}
+
+impl fmt::Display for CastStringToTsQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_tsquery")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "tsquery_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToTsQuery)]
+    fn cast_ts_query_to_string<'a>(a: &'a str) -> &'a str {
+        a
+    }
+);
@@ -517,6 +517,7 @@ impl AuthenticatorInner {
                     external_metadata_tx.send_replace(ExternalUserMetadata {
                         admin: claims.is_admin,
                         user_id: claims.user_id,
+                        groups: None,
                     });
                 }
 
@@ -764,6 +765,7 @@ impl ValidatedClaims {
         ExternalUserMetadata {
             admin: self.is_admin,
             user_id: self.user_id,
+            groups: None,
         }
     }
 }
@@ -13,12 +13,14 @@ mod decode;
 mod encode;
 mod schema;
 
-pub use crate::avro::decode::{Decoder, DiffPair};
+pub use crate::avro::decode::{decode_ocf_block, Decoder, DiffPair};
 pub use crate::avro::encode::{
     encode_datums_as_avro, encode_debezium_transaction_unchecked, get_debezium_transaction_schema,
     AvroEncoder, AvroSchemaGenerator, DocTarget,
 };
-pub use crate::avro::schema::{parse_schema, schema_to_relationdesc, ConfluentAvroResolver};
+pub use crate::avro::schema::{
+    parse_schema, relationdesc_to_reader_schema, schema_to_relationdesc, ConfluentAvroResolver,
+};
 
 fn is_null(schema: &SchemaPieceOrNamed) -> bool {
     matches!(schema, SchemaPieceOrNamed::Piece(SchemaPiece::Null))
@@ -27,11 +29,13 @@ fn is_null(schema: &SchemaPieceOrNamed) -> bool {
 #[cfg(test)]
 mod tests {
     use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+    use mz_avro::schema::resolve_schemas;
     use mz_avro::types::{DecimalValue, Value};
+    use mz_avro::{BlockIter, Writer};
     use mz_repr::adt::date::Date;
     use mz_repr::adt::numeric::{self, NumericMaxScale};
     use mz_repr::adt::timestamp::CheckedTimestamp;
-    use mz_repr::{Datum, RelationDesc, ScalarType};
+    use mz_repr::{Datum, RelationDesc, Row, ScalarType};
     use ordered_float::OrderedFloat;
 
     use super::*;
@@ -179,4 +183,74 @@ mod tests {
 
         Ok(())
     }
+
+    #[mz_ore::test]
+    fn test_decode_ocf_block() -> anyhow::Result<()> {
+        let writer_schema = r#"{
+            "type": "record",
+            "name": "com.acme.event",
+            "fields": [
+                { "name": "id", "type": "int" },
+                { "name": "name", "type": "string" },
+                { "name": "extra", "type": "boolean" }
+            ]
+        }"#;
+        let mut writer = Writer::new(parse_schema(writer_schema)?, vec![]);
+        for (id, name) in [(1, "one"), (2, "two")] {
+            writer.append(Value::Record(vec![
+                ("id".into(), Value::Int(id)),
+                ("name".into(), Value::String(name.into())),
+                ("extra".into(), Value::Boolean(true)),
+            ]))?;
+        }
+        writer.flush()?;
+        let bytes = writer.into_inner();
+
+        // Ints are promoted to longs, extra fields are skipped and missing nullable columns
+        // default to null.
+        let desc = RelationDesc::builder()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("name", ScalarType::String.nullable(true))
+            .with_column("note", ScalarType::String.nullable(true))
+            .finish();
+        let reader_schema = relationdesc_to_reader_schema(&desc, "com.acme.event")?;
+
+        let mut blocks = BlockIter::new(&bytes[..])?;
+        let resolved_schema = resolve_schemas(blocks.writer_schema(), &reader_schema)?;
+        let block = blocks.next().expect("one block")?;
+        let mut rows = vec![];
+        decode_ocf_block(&resolved_schema, &block.bytes, block.len, &mut rows)?;
+        assert_eq!(
+            rows,
+            vec![
+                Row::pack([Datum::Int64(1), Datum::String("one"), Datum::Null]),
+                Row::pack([Datum::Int64(2), Datum::String("two"), Datum::Null]),
+            ]
+        );
+
+        // Columns without a default must be present in the writer schema.
+        let desc = RelationDesc::builder()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("missing", ScalarType::Int64.nullable(false))
+            .finish();
+        let reader_schema = relationdesc_to_reader_schema(&desc, "com.acme.event")?;
+        let blocks = BlockIter::new(&bytes[..])?;
+        let err = resolve_schemas(blocks.writer_schema(), &reader_schema).unwrap_err();
+        assert!(err.to_string().contains("missing"), "{err}");
+
+        Ok(())
+    }
+
+    #[mz_ore::test]
+    fn test_reader_schema_unsupported_types() {
+        let desc = RelationDesc::builder()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column(
+                "tz",
+                ScalarType::TimestampTz { precision: None }.nullable(true),
+            )
+            .finish();
+        let err = relationdesc_to_reader_schema(&desc, "row").unwrap_err();
+        assert!(err.to_string().contains("tz"), "{err}");
+    }
 }
@@ -16,7 +16,7 @@ use anyhow::{Context, Error};
 use mz_avro::error::{DecodeError, Error as AvroError};
 use mz_avro::{
     define_unexpected, give_value, AvroArrayAccess, AvroDecode, AvroDeserializer, AvroMapAccess,
-    AvroRead, AvroRecordAccess, GeneralDeserializer, StatefulAvroDecodable, ValueDecoder,
+    AvroRead, AvroRecordAccess, GeneralDeserializer, Schema, StatefulAvroDecodable, ValueDecoder,
     ValueOrReader,
 };
 use mz_ore::error::ErrorExt;
@@ -135,6 +135,37 @@ impl Decoder {
     }
 }
 
+/// Decodes the `count` records in a block of an Avro Object Container File into `rows`.
+///
+/// `resolved_schema` is the result of resolving the writer schema of the file against a reader
+/// schema for the rows, see [`crate::avro::relationdesc_to_reader_schema`].
+pub fn decode_ocf_block(
+    resolved_schema: &Schema,
+    mut bytes: &[u8],
+    count: usize,
+    rows: &mut Vec<Row>,
+) -> Result<(), Error> {
+    let mut buf = vec![];
+    for _ in 0..count {
+        let mut row = Row::default();
+        {
+            let mut packer = row.packer();
+            let dec = AvroFlatDecoder {
+                packer: &mut packer,
+                buf: &mut buf,
+                is_top: true,
+            };
+            let dsr = GeneralDeserializer {
+                schema: resolved_schema.top_node(),
+            };
+            dsr.deserialize(&mut bytes, dec)
+                .context("unable to decode row")?;
+        }
+        rows.push(row);
+    }
+    Ok(())
+}
+
 pub struct AvroStringDecoder<'a> {
     pub buf: &'a mut Vec<u8>,
 }
@@ -349,9 +349,19 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                     debug_assert_eq!(buf.len(), 16);
                     buf
                 }),
-                ScalarType::Bytes => Value::Bytes(Vec::from(datum.unwrap_bytes())),
-                ScalarType::String | ScalarType::VarChar { .. } | ScalarType::PgLegacyName => {
-                    Value::String(datum.unwrap_str().to_owned())
+                // Geometries are encoded as their extended well-known binary
+                // representation.
+                ScalarType::Bytes | ScalarType::Geometry | ScalarType::Geography => {
+                    Value::Bytes(Vec::from(datum.unwrap_bytes()))
+                }
+                ScalarType::String
+                | ScalarType::VarChar { .. }
+                | ScalarType::PgLegacyName
+                | ScalarType::TsVector
+                | ScalarType::TsQuery
+                | ScalarType::JsonPath => Value::String(datum.unwrap_str().to_owned()),
+                ScalarType::Enum { labels, .. } => {
+                    Value::String(labels[usize::cast_from(datum.unwrap_uint32())].clone())
                 }
                 ScalarType::Char { length } => {
                     let s = mz_repr::adt::char::format_str_pad(datum.unwrap_str(), *length);
@@ -418,6 +428,10 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                 ScalarType::MzTimestamp => Value::String(datum.unwrap_mz_timestamp().to_string()),
                 ScalarType::Range { .. } => Value::String(datum.unwrap_range().to_string()),
                 ScalarType::MzAclItem => Value::String(datum.unwrap_mz_acl_item().to_string()),
+                ScalarType::Inet => Value::String(datum.unwrap_inet().to_string()),
+                ScalarType::Cidr => Value::String(datum.unwrap_inet().display_cidr().to_string()),
+                ScalarType::MacAddr => Value::String(datum.unwrap_macaddr().to_string()),
+                ScalarType::MacAddr8 => Value::String(datum.unwrap_macaddr8().to_string()),
             };
             if typ.nullable {
                 val = Value::Union {
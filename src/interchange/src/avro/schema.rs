@@ -53,7 +53,7 @@ use mz_repr::adt::timestamp::TimestampPrecision;
 use mz_repr::{ColumnName, ColumnType, RelationDesc, ScalarType};
 use tracing::warn;
 
-use crate::avro::is_null;
+use crate::avro::{is_null, AvroSchemaGenerator};
 
 pub fn parse_schema(schema: &str) -> anyhow::Result<Schema> {
     let schema = serde_json::from_str(schema)?;
@@ -70,6 +70,42 @@ pub fn schema_to_relationdesc(schema: Schema) -> Result<RelationDesc, anyhow::Er
     )?))
 }
 
+/// Builds an Avro reader schema for decoding records into rows of `desc`.
+///
+/// Schema resolution only matches records with the same name, so the top-level record is named
+/// `fullname`, which should be the name of the writer's top-level record. Columns whose type does
+/// not survive a round trip through Avro are rejected.
+pub fn relationdesc_to_reader_schema(
+    desc: &RelationDesc,
+    fullname: &str,
+) -> Result<Schema, anyhow::Error> {
+    let generator = AvroSchemaGenerator::new(
+        desc.clone(),
+        false,
+        BTreeMap::new(),
+        fullname,
+        true,
+        None,
+        false,
+    )?;
+    let schema = generator.schema().clone();
+    let decoded_desc = schema_to_relationdesc(schema.clone())?;
+
+    let mut errs = vec![];
+    for ((name, typ), decoded_typ) in desc.iter().zip(decoded_desc.iter_types()) {
+        if !typ.scalar_type.structural_eq(&decoded_typ.scalar_type) {
+            errs.push(format!("{}: {:?}", name, typ.scalar_type));
+        }
+    }
+    if !errs.is_empty() {
+        bail!(
+            "Cannot decode the following columns/types from Avro: {:?}",
+            errs
+        );
+    }
+    Ok(schema)
+}
+
 /// Convert an Avro schema to a series of columns and names, flattening the top-level record,
 /// if the top node is indeed a record.
 fn validate_schema_1(schema: SchemaNode) -> anyhow::Result<Vec<(ColumnName, ColumnType)>> {
@@ -261,6 +297,7 @@ fn validate_schema_2(
             ScalarType::Record {
                 fields: columns.into(),
                 custom_id: None,
+                custom_oid: None,
             }
         }
         SchemaPiece::Array(inner) => {
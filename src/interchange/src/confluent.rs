@@ -52,20 +52,82 @@ pub fn extract_avro_header(buf: &[u8]) -> Result<(i32, &[u8])> {
     extract_schema_id(buf, "avro")
 }
 
+pub fn extract_json_header(buf: &[u8]) -> Result<(i32, &[u8])> {
+    extract_schema_id(buf, "json")
+}
+
+/// Extracts the schema ID and the message indexes placed in front of a
+/// Confluent-style Protobuf message.
+///
+/// The message indexes identify which message in the schema the datum was
+/// written with. They are encoded as a zigzag varint array length followed by
+/// that many zigzag varint indexes, where the common case of `[0]` (the first
+/// message in the file) is shortened to a single zero byte.
+///
+/// This function returns the schema ID and a subslice of the rest of the
+/// buffer.
 pub fn extract_protobuf_header(buf: &[u8]) -> Result<(i32, &[u8])> {
-    let (schema_id, buf) = extract_schema_id(buf, "protobuf")?;
-
-    match buf.get(0) {
-        Some(0) => Ok((schema_id, &buf[1..])),
-        Some(message_id) => bail!(
-            "unsupported Confluent-style protobuf message descriptor id: \
-                expected 0, but found: {}",
-            message_id
-        ),
-        None => bail!(
+    let (schema_id, mut buf) = extract_schema_id(buf, "protobuf")?;
+
+    if buf.is_empty() {
+        bail!(
             "Confluent-style protobuf datum is too few bytes: expected a message id after magic \
             and schema id, got a buffer of length {}",
             buf.len()
-        ),
+        );
+    }
+    let count = decode_zigzag_varint(&mut buf)?;
+    if count < 0 {
+        bail!(
+            "invalid Confluent-style protobuf message indexes: negative array length {}",
+            count
+        );
+    }
+    for _ in 0..count {
+        let index = decode_zigzag_varint(&mut buf)?;
+        if index < 0 {
+            bail!(
+                "invalid Confluent-style protobuf message indexes: negative message index {}",
+                index
+            );
+        }
+    }
+    Ok((schema_id, buf))
+}
+
+fn decode_zigzag_varint(buf: &mut &[u8]) -> Result<i64> {
+    let n = match prost::encoding::decode_varint(buf) {
+        Ok(n) => n,
+        Err(e) => bail!("invalid Confluent-style protobuf message indexes: {}", e),
+    };
+    let magnitude = i64::try_from(n >> 1).expect("shifted u64 fits in i64");
+    if n & 1 == 0 {
+        Ok(magnitude)
+    } else {
+        Ok(-magnitude - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_extract_protobuf_header() {
+        // The shortened form of the `[0]` message index array.
+        let buf = [0, 0, 0, 0, 7, 0, 42];
+        assert_eq!(extract_protobuf_header(&buf).unwrap(), (7, &[42][..]));
+
+        // An explicit array of two indexes, `[1, 2]`.
+        let buf = [0, 0, 0, 0, 7, 4, 2, 4, 42];
+        assert_eq!(extract_protobuf_header(&buf).unwrap(), (7, &[42][..]));
+
+        // A negative array length.
+        let buf = [0, 0, 0, 0, 7, 123, 42];
+        assert!(extract_protobuf_header(&buf).is_err());
+
+        // A truncated index array.
+        let buf = [0, 0, 0, 0, 7, 4, 2];
+        assert!(extract_protobuf_header(&buf).is_err());
     }
 }
@@ -152,6 +152,7 @@ pub(crate) fn dbz_envelope(
         scalar_type: ScalarType::Record {
             fields: names_and_types.into(),
             custom_id: Some(DBZ_ROW_TYPE_ID),
+            custom_oid: None,
         },
     };
     vec![("before".into(), row.clone()), ("after".into(), row)]
@@ -10,12 +10,18 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 
+use anyhow::{anyhow, bail, Context};
+use mz_ore::cast::CastFrom;
+use mz_ore::str::StrExt;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::char;
-use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::adt::geo::Geometry;
+use mz_repr::adt::jsonb::{JsonbPacker, JsonbRef};
 use mz_repr::adt::numeric::{NUMERIC_AGG_MAX_PRECISION, NUMERIC_DATUM_MAX_PRECISION};
-use mz_repr::{CatalogItemId, ColumnName, ColumnType, Datum, RelationDesc, ScalarType};
-use serde_json::{json, Map};
+use mz_repr::{
+    strconv, CatalogItemId, ColumnName, ColumnType, Datum, RelationDesc, Row, RowPacker, ScalarType,
+};
+use serde_json::{json, Map, Value};
 
 use crate::avro::DocTarget;
 use crate::encode::{column_names_and_types, Encode, TypedDatum};
@@ -150,8 +156,19 @@ impl ToJson for TypedDatum<'_> {
                 serde_json::Value::String(format!("{}", datum.unwrap_interval()))
             }
             ScalarType::Bytes => json!(datum.unwrap_bytes()),
-            ScalarType::String | ScalarType::VarChar { .. } | ScalarType::PgLegacyName => {
-                json!(datum.unwrap_str())
+            ScalarType::Geometry | ScalarType::Geography => {
+                let geometry =
+                    Geometry::from_ewkb(datum.unwrap_bytes()).expect("geometry datums are valid");
+                serde_json::from_str(&geometry.to_geojson()).expect("GeoJSON is valid JSON")
+            }
+            ScalarType::String
+            | ScalarType::VarChar { .. }
+            | ScalarType::PgLegacyName
+            | ScalarType::TsVector
+            | ScalarType::TsQuery
+            | ScalarType::JsonPath => json!(datum.unwrap_str()),
+            ScalarType::Enum { labels, .. } => {
+                json!(labels[usize::cast_from(datum.unwrap_uint32())])
             }
             ScalarType::Char { length } => {
                 let s = char::format_str_pad(datum.unwrap_str(), *length);
@@ -231,6 +248,10 @@ impl ToJson for TypedDatum<'_> {
                 json!(datum.unwrap_range().to_string())
             }
             ScalarType::MzAclItem => json!(datum.unwrap_mz_acl_item().to_string()),
+            ScalarType::Inet => json!(datum.unwrap_inet().to_string()),
+            ScalarType::Cidr => json!(datum.unwrap_inet().display_cidr().to_string()),
+            ScalarType::MacAddr => json!(datum.unwrap_macaddr().to_string()),
+            ScalarType::MacAddr8 => json!(datum.unwrap_macaddr8().to_string()),
         };
         // We don't need to recurse into map or object here because those already recursively call
         // .json() with the number policy to generate the member Values.
@@ -301,11 +322,15 @@ fn build_row_schema_field_type(
             },
         }),
         ScalarType::Interval => type_namer.interval_type(),
-        ScalarType::Bytes => json!("bytes"),
+        ScalarType::Bytes | ScalarType::Geometry | ScalarType::Geography => json!("bytes"),
         ScalarType::String
         | ScalarType::Char { .. }
         | ScalarType::VarChar { .. }
-        | ScalarType::PgLegacyName => {
+        | ScalarType::PgLegacyName
+        | ScalarType::TsVector
+        | ScalarType::TsQuery
+        | ScalarType::JsonPath
+        | ScalarType::Enum { .. } => {
             json!("string")
         }
         ScalarType::Jsonb => json!({
@@ -395,6 +420,9 @@ fn build_row_schema_field_type(
         // https://debezium.io/documentation/reference/stable/connectors/postgresql.html
         ScalarType::Range { .. } => json!("string"),
         ScalarType::MzAclItem => json!("string"),
+        ScalarType::Inet | ScalarType::Cidr | ScalarType::MacAddr | ScalarType::MacAddr8 => {
+            json!("string")
+        }
     };
     if typ.nullable {
         // Should be revisited if we ever support a different kind of union scheme.
@@ -498,6 +526,160 @@ pub fn build_row_schema_json(
     }
 }
 
+/// Derives the columns described by a JSON Schema document.
+///
+/// The schema must describe an object. Each of the object's properties becomes
+/// a column, in the order the properties are declared. Properties that are not
+/// `required`, or whose type admits `null`, become nullable columns. Scalar
+/// types are mapped onto the closest SQL type; objects, arrays and anything
+/// more elaborate than a single type are kept as `jsonb`.
+pub fn json_schema_to_columns(
+    schema: &str,
+) -> Result<Vec<(ColumnName, ColumnType)>, anyhow::Error> {
+    let schema: Value = serde_json::from_str(schema).context("parsing JSON schema")?;
+    if json_schema_types(&schema) != ["object"] {
+        bail!("JSON schema must describe an object");
+    }
+    let Some(Value::Object(properties)) = schema.get("properties") else {
+        bail!("JSON schema must declare the properties of its object");
+    };
+    let required: BTreeSet<&str> = match schema.get("required") {
+        Some(Value::Array(required)) => required.iter().filter_map(|r| r.as_str()).collect(),
+        _ => BTreeSet::new(),
+    };
+
+    let mut columns = Vec::with_capacity(properties.len());
+    for (name, property) in properties {
+        let mut ty = json_schema_column_type(property);
+        if !required.contains(name.as_str()) {
+            ty.nullable = true;
+        }
+        columns.push((ColumnName::from(name.as_str()), ty));
+    }
+    Ok(columns)
+}
+
+/// Returns the types named by the `type` keyword of a JSON Schema.
+fn json_schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(|ty| ty.as_str()).collect(),
+        _ => vec![],
+    }
+}
+
+fn json_schema_column_type(schema: &Value) -> ColumnType {
+    let mut types = json_schema_types(schema);
+    let nullable = types.contains(&"null");
+    types.retain(|ty| *ty != "null");
+    let scalar_type = match types.as_slice() {
+        ["string"] => match schema.get("format").and_then(|f| f.as_str()) {
+            Some("date-time") => ScalarType::TimestampTz { precision: None },
+            Some("date") => ScalarType::Date,
+            Some("time") => ScalarType::Time,
+            Some("uuid") => ScalarType::Uuid,
+            _ => ScalarType::String,
+        },
+        ["integer"] => ScalarType::Int64,
+        ["number"] => ScalarType::Float64,
+        ["boolean"] => ScalarType::Bool,
+        _ => ScalarType::Jsonb,
+    };
+    scalar_type.nullable(nullable)
+}
+
+/// Decodes JSON objects into rows whose columns are described by a JSON
+/// Schema.
+#[derive(Debug)]
+pub struct JsonSchemaDecoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+    confluent_wire_format: bool,
+    row: Row,
+}
+
+impl JsonSchemaDecoder {
+    /// Constructs a decoder for the given JSON Schema document.
+    pub fn new(schema: &str, confluent_wire_format: bool) -> Result<Self, anyhow::Error> {
+        Ok(JsonSchemaDecoder {
+            columns: json_schema_to_columns(schema)?,
+            confluent_wire_format,
+            row: Row::default(),
+        })
+    }
+
+    /// Describes the columns of the rows produced by this decoder.
+    pub fn columns(&self) -> &[(ColumnName, ColumnType)] {
+        &self.columns
+    }
+
+    /// Decodes an encoded JSON object into a [`Row`].
+    pub fn decode(&mut self, mut bytes: &[u8]) -> Result<Row, anyhow::Error> {
+        if self.confluent_wire_format {
+            // Like for Protobuf, we decode using the schema we know about
+            // rather than the one the message was written with, so the schema
+            // ID is ignored.
+            let (_schema_id, adjusted_bytes) = crate::confluent::extract_json_header(bytes)?;
+            bytes = adjusted_bytes;
+        }
+        let value: Value = serde_json::from_slice(bytes).context("parsing JSON")?;
+        let Value::Object(mut object) = value else {
+            bail!("expected a JSON object, found {}", value);
+        };
+        let mut packer = self.row.packer();
+        for (name, ty) in &self.columns {
+            let value = object.remove(name.as_str()).unwrap_or(Value::Null);
+            pack_json_schema_value(&mut packer, name, ty, value)?;
+        }
+        Ok(self.row.clone())
+    }
+}
+
+fn pack_json_schema_value(
+    packer: &mut RowPacker,
+    name: &ColumnName,
+    ty: &ColumnType,
+    value: Value,
+) -> Result<(), anyhow::Error> {
+    match (value, &ty.scalar_type) {
+        (Value::Null, _) if ty.nullable => packer.push(Datum::Null),
+        (value, ScalarType::Jsonb) => JsonbPacker::new(packer).pack_serde_json(value)?,
+        (Value::Null, _) => bail!(
+            "missing value for required field {}",
+            name.as_str().quoted()
+        ),
+        (Value::Bool(b), ScalarType::Bool) => packer.push(Datum::from(b)),
+        (Value::Number(n), ScalarType::Int64) => {
+            let n = n.as_i64().ok_or_else(|| {
+                anyhow!(
+                    "value {} for field {} is out of range for a 64-bit integer",
+                    n,
+                    name.as_str().quoted()
+                )
+            })?;
+            packer.push(Datum::Int64(n))
+        }
+        (Value::Number(n), ScalarType::Float64) => {
+            // Every JSON number without arbitrary precision fits into an
+            // `f64`.
+            let n = n.as_f64().expect("JSON number representable as f64");
+            packer.push(Datum::from(n))
+        }
+        (Value::String(s), ScalarType::String) => packer.push(Datum::String(&s)),
+        (Value::String(s), ScalarType::Date) => packer.push(Datum::Date(strconv::parse_date(&s)?)),
+        (Value::String(s), ScalarType::Time) => packer.push(Datum::Time(strconv::parse_time(&s)?)),
+        (Value::String(s), ScalarType::TimestampTz { .. }) => {
+            packer.push(Datum::TimestampTz(strconv::parse_timestamptz(&s)?))
+        }
+        (Value::String(s), ScalarType::Uuid) => packer.push(Datum::Uuid(strconv::parse_uuid(&s)?)),
+        (value, _) => bail!(
+            "value {} for field {} does not match its schema",
+            value,
+            name.as_str().quoted()
+        ),
+    }
+    Ok(())
+}
+
 /// Naming helper for use when constructing an Avro schema.
 #[derive(Default)]
 struct Namer {
@@ -567,3 +749,71 @@ impl Namer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "name": {"type": "string"},
+            "score": {"type": ["number", "null"]},
+            "active": {"type": "boolean"},
+            "tags": {"type": "array", "items": {"type": "string"}}
+        },
+        "required": ["id", "name", "score"]
+    }"#;
+
+    #[mz_ore::test]
+    fn test_json_schema_to_columns() {
+        let columns = json_schema_to_columns(SCHEMA).unwrap();
+        let expected = vec![
+            ("id", ScalarType::Int64.nullable(false)),
+            ("name", ScalarType::String.nullable(false)),
+            ("score", ScalarType::Float64.nullable(true)),
+            ("active", ScalarType::Bool.nullable(true)),
+            ("tags", ScalarType::Jsonb.nullable(true)),
+        ];
+        let expected: Vec<_> = expected
+            .into_iter()
+            .map(|(name, ty)| (ColumnName::from(name), ty))
+            .collect();
+        assert_eq!(columns, expected);
+
+        assert!(json_schema_to_columns(r#"{"type": "string"}"#).is_err());
+        assert!(json_schema_to_columns(r#"{"type": "object"}"#).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_json_schema_decode() {
+        let mut decoder = JsonSchemaDecoder::new(SCHEMA, false).unwrap();
+
+        let row = decoder
+            .decode(br#"{"id": 1, "name": "a", "score": null, "tags": ["x"]}"#)
+            .unwrap();
+        let datums: Vec<_> = row.iter().collect();
+        assert_eq!(datums[0], Datum::Int64(1));
+        assert_eq!(datums[1], Datum::String("a"));
+        assert_eq!(datums[2], Datum::Null);
+        assert_eq!(datums[3], Datum::Null);
+        assert_eq!(
+            JsonbRef::from_datum(datums[4]).to_serde_json(),
+            json!(["x"])
+        );
+
+        // A missing required field, a mistyped field, and a non-object.
+        assert!(decoder.decode(br#"{"name": "a", "score": 1.5}"#).is_err());
+        assert!(decoder
+            .decode(br#"{"id": "1", "name": "a", "score": 1.5}"#)
+            .is_err());
+        assert!(decoder.decode(b"[1, 2]").is_err());
+
+        let mut decoder = JsonSchemaDecoder::new(SCHEMA, true).unwrap();
+        let mut bytes = vec![0, 0, 0, 0, 1];
+        bytes.extend_from_slice(br#"{"id": 2, "name": "b", "score": 0.5}"#);
+        let row = decoder.decode(&bytes).unwrap();
+        assert_eq!(row.iter().next(), Some(Datum::Int64(2)));
+    }
+}
@@ -11,12 +11,17 @@ use std::collections::BTreeSet;
 
 use anyhow::{anyhow, bail, Context};
 use mz_ore::str::StrExt;
+use mz_repr::adt::jsonb::JsonbPacker;
 use mz_repr::{ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
 use prost_reflect::{
     Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor,
     ReflectMessage, Value,
 };
 
+mod encode;
+
+pub use crate::protobuf::encode::{ProtobufEncoder, ProtobufSchemaGenerator};
+
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
 pub struct DecodedDescriptors {
@@ -37,7 +42,7 @@ impl DecodedDescriptors {
             )
         })?;
         let mut seen_messages = BTreeSet::new();
-        seen_messages.insert(message_descriptor.name().to_owned());
+        seen_messages.insert(message_descriptor.full_name().to_owned());
         let mut columns = vec![];
         for field in message_descriptor.fields() {
             let name = ColumnName::from(field.name());
@@ -61,6 +66,21 @@ impl DecodedDescriptors {
     }
 }
 
+/// Returns the fully qualified name of the first message declared in the file
+/// named `file_name` within an encoded `FileDescriptorSet`, or `None` if that
+/// file declares no messages.
+pub fn first_message_name(bytes: &[u8], file_name: &str) -> Result<Option<String>, anyhow::Error> {
+    let fds = DescriptorPool::decode(bytes).context("decoding file descriptor set")?;
+    let file = fds.get_file_by_name(file_name).ok_or_else(|| {
+        anyhow!(
+            "protobuf file {} not found in file descriptor set",
+            file_name.quoted(),
+        )
+    })?;
+    let message_name = file.messages().next().map(|m| m.full_name().to_owned());
+    Ok(message_name)
+}
+
 /// Decodes a particular Protobuf message from its wire format.
 #[derive(Debug)]
 pub struct Decoder {
@@ -106,7 +126,9 @@ impl Decoder {
         }
         let message = DynamicMessage::decode(self.descriptors.message_descriptor.clone(), bytes)?;
         let mut packer = self.row.packer();
-        pack_message(&mut packer, &message)?;
+        let mut seen_messages = BTreeSet::new();
+        seen_messages.insert(message.descriptor().full_name().to_owned());
+        pack_message(&mut packer, &mut seen_messages, &message)?;
         Ok(Some(self.row.clone()))
     }
 }
@@ -149,27 +171,39 @@ fn derive_inner_type(
         Kind::Bytes => Ok(ScalarType::Bytes.nullable(false)),
         Kind::Enum(_) => Ok(ScalarType::String.nullable(false)),
         Kind::Message(m) => {
-            if seen_messages.contains(m.name()) {
-                bail!("Recursive types are not supported: {}", m.name());
+            // Recursive message types cannot be described by a finite record
+            // type, so we fall back to representing the recursive field as
+            // JSON.
+            if seen_messages.contains(m.full_name()) {
+                return Ok(ScalarType::Jsonb.nullable(true));
             }
-            seen_messages.insert(m.name().to_owned());
+            seen_messages.insert(m.full_name().to_owned());
             let mut fields = Vec::with_capacity(m.fields().len());
             for field in m.fields() {
                 let column_name = ColumnName::from(field.name());
                 let column_type = derive_column_type(seen_messages, &field)?;
                 fields.push((column_name, column_type))
             }
-            seen_messages.remove(m.name());
+            seen_messages.remove(m.full_name());
             let ty = ScalarType::Record {
                 fields: fields.into(),
                 custom_id: None,
+                custom_oid: None,
             };
             Ok(ty.nullable(true))
         }
     }
 }
 
-fn pack_message(packer: &mut RowPacker, message: &DynamicMessage) -> Result<(), anyhow::Error> {
+/// Packs the fields of `message` into `packer`.
+///
+/// `seen_messages` tracks the message types that enclose `message`, so that
+/// recursive fields are packed as JSON, mirroring [`derive_inner_type`].
+fn pack_message(
+    packer: &mut RowPacker,
+    seen_messages: &mut BTreeSet<String>,
+    message: &DynamicMessage,
+) -> Result<(), anyhow::Error> {
     for field_desc in message.descriptor().fields() {
         if !message.has_field(&field_desc) {
             if field_desc.cardinality() == Cardinality::Required {
@@ -184,13 +218,14 @@ fn pack_message(packer: &mut RowPacker, message: &DynamicMessage) -> Result<(),
             }
         }
         let value = message.get_field(&field_desc);
-        pack_value(packer, &field_desc, &*value)?;
+        pack_value(packer, seen_messages, &field_desc, &*value)?;
     }
     Ok(())
 }
 
 fn pack_value(
     packer: &mut RowPacker,
+    seen_messages: &mut BTreeSet<String>,
     field_desc: &FieldDescriptor,
     value: &Value,
 ) -> Result<(), anyhow::Error> {
@@ -222,11 +257,22 @@ fn pack_value(
             })?;
             packer.push(Datum::String(value.name()));
         }
-        Value::Message(m) => packer.push_list_with(|packer| pack_message(packer, m))?,
+        Value::Message(m) => {
+            let name = m.descriptor().full_name().to_owned();
+            if seen_messages.contains(&name) {
+                let json = serde_json::to_value(m)
+                    .with_context(|| format!("converting protobuf message {} to JSON", name))?;
+                JsonbPacker::new(packer).pack_serde_json(json)?;
+            } else {
+                seen_messages.insert(name.clone());
+                packer.push_list_with(|packer| pack_message(packer, seen_messages, m))?;
+                seen_messages.remove(&name);
+            }
+        }
         Value::List(values) => {
             packer.push_list_with(|packer| {
                 for value in values {
-                    pack_value(packer, field_desc, value)?;
+                    pack_value(packer, seen_messages, field_desc, value)?;
                 }
                 Ok::<_, anyhow::Error>(())
             })?;
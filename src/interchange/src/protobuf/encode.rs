@@ -0,0 +1,374 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Write};
+
+use byteorder::{NetworkEndian, WriteBytesExt};
+use bytes::{Bytes, BytesMut};
+use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, Value};
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, OneofDescriptorProto,
+};
+
+use crate::encode::{column_names_and_types, Encode};
+use crate::envelopes;
+
+/// The name of the file that holds generated message descriptors.
+const FILE_NAME: &str = "materialize.proto";
+
+/// Generates a Protobuf message schema that describes a relation.
+///
+/// Each column becomes a field of the message, numbered in column order.
+/// Nullable columns become `optional` fields and records become nested
+/// messages. Types without a natural Protobuf counterpart are represented by
+/// their text encoding in a `string` field.
+#[derive(Debug, Clone)]
+pub struct ProtobufSchemaGenerator {
+    columns: Vec<(ColumnName, ColumnType)>,
+    message_name: String,
+    file: FileDescriptorProto,
+}
+
+impl ProtobufSchemaGenerator {
+    pub fn new(desc: RelationDesc, debezium: bool, message_name: &str) -> Self {
+        let mut columns = column_names_and_types(desc);
+        if debezium {
+            columns = envelopes::dbz_envelope(columns);
+        }
+        let message = MessageBuilder::default().build(message_name, message_name, &columns);
+        let file = FileDescriptorProto {
+            name: Some(FILE_NAME.into()),
+            syntax: Some("proto3".into()),
+            message_type: vec![message],
+            ..Default::default()
+        };
+        ProtobufSchemaGenerator {
+            columns,
+            message_name: message_name.into(),
+            file,
+        }
+    }
+
+    /// Returns the `.proto` source of the generated schema, suitable for
+    /// publishing to a schema registry.
+    pub fn schema(&self) -> String {
+        let mut out = String::from("syntax = \"proto3\";\n");
+        for message in &self.file.message_type {
+            out.push('\n');
+            write_message(&mut out, message, 0).expect("writing to string cannot fail");
+        }
+        out
+    }
+
+    /// Returns the descriptor of the generated message.
+    fn message_descriptor(&self) -> MessageDescriptor {
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_proto(self.file.clone())
+            .expect("generated descriptors are valid");
+        pool.get_message_by_name(&self.message_name)
+            .expect("generated file contains the message")
+    }
+}
+
+/// Builds message descriptors for a list of columns.
+#[derive(Default)]
+struct MessageBuilder {
+    record_index: usize,
+}
+
+impl MessageBuilder {
+    fn build(
+        &mut self,
+        name: &str,
+        full_name: &str,
+        columns: &[(ColumnName, ColumnType)],
+    ) -> DescriptorProto {
+        let mut message = DescriptorProto {
+            name: Some(name.into()),
+            ..Default::default()
+        };
+        for ((_name, ty), (number, field_name)) in
+            columns.iter().zip((1..).zip(field_names(columns)))
+        {
+            let mut field = FieldDescriptorProto {
+                name: Some(field_name.clone()),
+                number: Some(number),
+                label: Some(i32::from(Label::Optional)),
+                ..Default::default()
+            };
+            match &ty.scalar_type {
+                ScalarType::Record { fields, .. } => {
+                    // Message fields track presence on their own.
+                    let nested_name = format!("Record{}", self.record_index);
+                    self.record_index += 1;
+                    let nested_full_name = format!("{full_name}.{nested_name}");
+                    let nested = self.build(&nested_name, &nested_full_name, fields);
+                    message.nested_type.push(nested);
+                    field.r#type = Some(i32::from(Type::Message));
+                    field.type_name = Some(format!(".{nested_full_name}"));
+                }
+                scalar_type => {
+                    field.r#type = Some(i32::from(scalar_field_type(scalar_type)));
+                    if ty.nullable {
+                        // Proto3 `optional` fields are modeled as a synthetic
+                        // oneof containing just that field.
+                        let oneof_index = i32::try_from(message.oneof_decl.len())
+                            .expect("number of fields fits into an i32");
+                        message.oneof_decl.push(OneofDescriptorProto {
+                            name: Some(format!("_{field_name}")),
+                            ..Default::default()
+                        });
+                        field.oneof_index = Some(oneof_index);
+                        field.proto3_optional = Some(true);
+                    }
+                }
+            }
+            message.field.push(field);
+        }
+        message
+    }
+}
+
+/// Turns the column names into valid, unique Protobuf field names.
+fn field_names(columns: &[(ColumnName, ColumnType)]) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    let mut names = Vec::with_capacity(columns.len());
+    for (name, _ty) in columns {
+        let mut valid: String = name
+            .as_str()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if !valid.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            valid.insert(0, '_');
+        }
+        let stem = valid.clone();
+        let mut i = 1;
+        while seen.contains(&valid) {
+            valid = format!("{stem}{i}");
+            i += 1;
+        }
+        seen.insert(valid.clone());
+        names.push(valid);
+    }
+    names
+}
+
+fn scalar_field_type(scalar_type: &ScalarType) -> Type {
+    match scalar_type {
+        ScalarType::Bool => Type::Bool,
+        ScalarType::Int16 | ScalarType::Int32 => Type::Int32,
+        ScalarType::Int64 => Type::Int64,
+        ScalarType::UInt16 | ScalarType::UInt32 => Type::Uint32,
+        ScalarType::UInt64 => Type::Uint64,
+        ScalarType::Float32 => Type::Float,
+        ScalarType::Float64 => Type::Double,
+        ScalarType::Bytes => Type::Bytes,
+        _ => Type::String,
+    }
+}
+
+fn write_message(out: &mut String, message: &DescriptorProto, depth: usize) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    writeln!(out, "{indent}message {} {{", message.name())?;
+    for nested in &message.nested_type {
+        write_message(out, nested, depth + 1)?;
+    }
+    for field in &message.field {
+        let ty = match field.r#type() {
+            Type::Message => {
+                // Nested messages are declared within the message that refers
+                // to them, so their simple name resolves.
+                let type_name = field.type_name();
+                type_name
+                    .rsplit('.')
+                    .next()
+                    .unwrap_or(type_name)
+                    .to_string()
+            }
+            ty => ty.as_str_name()["TYPE_".len()..].to_lowercase(),
+        };
+        let label = if field.proto3_optional() {
+            "optional "
+        } else {
+            ""
+        };
+        writeln!(
+            out,
+            "{indent}  {label}{ty} {} = {};",
+            field.name(),
+            field.number()
+        )?;
+    }
+    writeln!(out, "{indent}}}")
+}
+
+/// Manages encoding of Protobuf-encoded bytes in the Confluent wire format.
+#[derive(Debug)]
+pub struct ProtobufEncoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+    message: MessageDescriptor,
+    schema_id: i32,
+}
+
+impl ProtobufEncoder {
+    /// Constructs an encoder for the message generated by `schema`, which
+    /// has been registered under `schema_id`.
+    pub fn new(schema: ProtobufSchemaGenerator, schema_id: i32) -> Self {
+        let message = schema.message_descriptor();
+        ProtobufEncoder {
+            columns: schema.columns,
+            message,
+            schema_id,
+        }
+    }
+}
+
+impl Encode for ProtobufEncoder {
+    fn encode_unchecked(&self, row: Row) -> Vec<u8> {
+        let mut buf = vec![];
+        encode_protobuf_header(&mut buf, self.schema_id);
+        let message = encode_datums_as_protobuf(&self.message, row.iter(), &self.columns);
+        message
+            .encode(&mut buf)
+            .expect("writing to vec cannot fail");
+        buf
+    }
+
+    fn hash(&self, buf: &[u8]) -> u64 {
+        // Compute a stable hash by ignoring the header which might contain a
+        // non-deterministic schema id.
+        let (_schema_id, payload) = crate::confluent::extract_protobuf_header(buf).unwrap();
+        seahash::hash(payload)
+    }
+}
+
+fn encode_protobuf_header(buf: &mut Vec<u8>, schema_id: i32) {
+    // The header is the same as Avro's, followed by the path of indexes to the
+    // encoded message within its file. The generated message is always the
+    // first one, which is encoded as a single zero.
+    //
+    // https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+    buf.write_u8(0).expect("writing to vec cannot fail");
+    buf.write_i32::<NetworkEndian>(schema_id)
+        .expect("writing to vec cannot fail");
+    buf.write_u8(0).expect("writing to vec cannot fail");
+}
+
+/// Encodes a sequence of `Datum`s as a message described by `descriptor`.
+fn encode_datums_as_protobuf<'a, I>(
+    descriptor: &MessageDescriptor,
+    datums: I,
+    columns: &[(ColumnName, ColumnType)],
+) -> DynamicMessage
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut message = DynamicMessage::new(descriptor.clone());
+    for ((datum, (_name, ty)), field) in datums.into_iter().zip(columns).zip(descriptor.fields()) {
+        if datum.is_null() {
+            continue;
+        }
+        let value = match &ty.scalar_type {
+            ScalarType::Record { fields, .. } => {
+                let nested = field
+                    .kind()
+                    .as_message()
+                    .cloned()
+                    .expect("record fields are messages");
+                Value::Message(encode_datums_as_protobuf(
+                    &nested,
+                    datum.unwrap_list().iter(),
+                    fields,
+                ))
+            }
+            ScalarType::Bool => Value::Bool(datum.unwrap_bool()),
+            ScalarType::Int16 => Value::I32(i32::from(datum.unwrap_int16())),
+            ScalarType::Int32 => Value::I32(datum.unwrap_int32()),
+            ScalarType::Int64 => Value::I64(datum.unwrap_int64()),
+            ScalarType::UInt16 => Value::U32(u32::from(datum.unwrap_uint16())),
+            ScalarType::UInt32 => Value::U32(datum.unwrap_uint32()),
+            ScalarType::UInt64 => Value::U64(datum.unwrap_uint64()),
+            ScalarType::Float32 => Value::F32(datum.unwrap_float32()),
+            ScalarType::Float64 => Value::F64(datum.unwrap_float64()),
+            ScalarType::Bytes => Value::Bytes(Bytes::copy_from_slice(datum.unwrap_bytes())),
+            ScalarType::String => Value::String(datum.unwrap_str().to_owned()),
+            scalar_type => {
+                let mut buf = BytesMut::new();
+                if let Some(value) = mz_pgrepr::Value::from_datum(datum, scalar_type) {
+                    value.encode_text(&mut buf);
+                }
+                Value::String(String::from_utf8_lossy(&buf).into_owned())
+            }
+        };
+        message.set_field(&field, value);
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use prost_types::FileDescriptorSet;
+
+    use super::*;
+    use crate::protobuf::{DecodedDescriptors, Decoder};
+
+    #[mz_ore::test]
+    fn test_protobuf_roundtrip() {
+        let desc = RelationDesc::builder()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("my name", ScalarType::String.nullable(true))
+            .with_column("score", ScalarType::Float64.nullable(false))
+            .with_column("day", ScalarType::Date.nullable(false))
+            .finish();
+        let generator = ProtobufSchemaGenerator::new(desc, false, "Row");
+        assert_eq!(
+            generator.schema(),
+            "syntax = \"proto3\";\n\
+             \n\
+             message Row {\n  \
+               int64 id = 1;\n  \
+               optional string my_name = 2;\n  \
+               double score = 3;\n  \
+               string day = 4;\n\
+             }\n"
+        );
+
+        let fds = FileDescriptorSet {
+            file: vec![generator.file.clone()],
+        };
+        let descriptors =
+            DecodedDescriptors::from_bytes(&fds.encode_to_vec(), "Row".into()).unwrap();
+        let mut decoder = Decoder::new(descriptors, true).unwrap();
+
+        let encoder = ProtobufEncoder::new(generator, 7);
+        let date = mz_repr::adt::date::Date::from_pg_epoch(0).unwrap();
+        let row = Row::pack_slice(&[
+            Datum::Int64(42),
+            Datum::String("a"),
+            Datum::Float64(1.5.into()),
+            Datum::Date(date),
+        ]);
+        let bytes = encoder.encode_unchecked(row);
+        assert_eq!(&bytes[..6], &[0, 0, 0, 0, 7, 0]);
+
+        let decoded = decoder.decode(&bytes).unwrap().unwrap();
+        let expected = Row::pack_slice(&[
+            Datum::Int64(42),
+            Datum::String("a"),
+            Datum::Float64(1.5.into()),
+            Datum::String("2000-01-01"),
+        ]);
+        assert_eq!(decoded, expected);
+    }
+}
@@ -0,0 +1,489 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use mz_ore::now::NowFn;
+use mz_ore::time::DurationExt;
+use mz_repr::user::ExternalUserMetadata;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::watch;
+use tokio::time;
+use uuid::Uuid;
+
+use crate::jwks::KeyStore;
+use crate::{Error, JwksSource, OidcCliArgs};
+
+/// The claim that contains the name of the user, unless otherwise configured.
+pub const DEFAULT_USER_CLAIM: &str = "sub";
+
+/// The claim that contains the groups of the user, unless otherwise configured.
+pub const DEFAULT_GROUPS_CLAIM: &str = "groups";
+
+/// The maximum length of a user name.
+pub const MAX_USER_NAME_LENGTH: usize = 255;
+
+/// The algorithms that tokens may be signed with.
+///
+/// Symmetric algorithms are deliberately excluded: the keys in a JWKS are
+/// public, so anyone could forge a token signed with one.
+const SUPPORTED_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// Configures an [`Authenticator`].
+#[derive(Clone, Debug)]
+pub struct AuthenticatorConfig {
+    /// The issuer of JWTs, which must match their `iss` claim.
+    pub issuer: String,
+    /// The audience that JWTs must be issued for, if any.
+    pub audience: Option<String>,
+    /// The source of the JWKS used to validate JWTs.
+    pub jwks: JwksSource,
+    /// The claim containing the name of the user.
+    pub user_claim: String,
+    /// The claim containing the groups that the user is a member of.
+    pub groups_claim: String,
+    /// Name of the group whose members are administrators.
+    pub admin_group: Option<String>,
+    /// Allows clients to authenticate with refresh tokens, if specified.
+    pub refresh: Option<RefreshConfig>,
+    /// Function to provide system time to validate exp (expires at) field of JWTs.
+    pub now: NowFn,
+}
+
+/// Configures how refresh tokens are exchanged for access tokens.
+#[derive(Clone, Debug)]
+pub struct RefreshConfig {
+    /// The URL of the issuer's token endpoint.
+    pub token_url: String,
+    /// The ID of the client that the refresh tokens were issued to.
+    pub client_id: String,
+}
+
+/// Facilitates authenticating users with JWTs issued by an OIDC identity
+/// provider.
+#[derive(Clone, Debug)]
+pub struct Authenticator {
+    inner: Arc<AuthenticatorInner>,
+}
+
+impl Authenticator {
+    /// Creates a new authenticator.
+    pub fn new(config: AuthenticatorConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .expect("must build Client");
+        Authenticator {
+            inner: Arc::new(AuthenticatorInner {
+                keys: KeyStore::new(config.issuer.clone(), config.jwks),
+                client,
+                refresh: config.refresh,
+                issuer: config.issuer,
+                audience: config.audience,
+                user_claim: config.user_claim,
+                groups_claim: config.groups_claim,
+                admin_group: config.admin_group,
+                now: config.now,
+            }),
+        }
+    }
+
+    /// Create an [`Authenticator`] from [`OidcCliArgs`].
+    pub fn from_args(args: OidcCliArgs) -> Result<Option<Self>, Error> {
+        let Some(issuer) = args.oidc_issuer else {
+            return Ok(None);
+        };
+        let jwks = match (args.oidc_jwks_url, args.oidc_jwks_file) {
+            (None, None) => JwksSource::Discover,
+            (Some(url), None) => JwksSource::Url(url),
+            (None, Some(path)) => {
+                let jwks = std::fs::read(&path)
+                    .with_context(|| format!("reading {path:?} for --oidc-jwks-file"))?;
+                let jwks = serde_json::from_slice(&jwks)
+                    .with_context(|| format!("parsing {path:?} for --oidc-jwks-file"))?;
+                JwksSource::Static(jwks)
+            }
+            (Some(_), Some(_)) => unreachable!("clap enforced"),
+        };
+        let refresh = match (args.oidc_token_url, args.oidc_client_id) {
+            (Some(token_url), Some(client_id)) => Some(RefreshConfig {
+                token_url,
+                client_id,
+            }),
+            (None, None) => None,
+            _ => unreachable!("clap enforced"),
+        };
+        let config = AuthenticatorConfig {
+            issuer,
+            audience: args.oidc_audience,
+            jwks,
+            user_claim: args.oidc_user_claim,
+            groups_claim: args.oidc_groups_claim,
+            admin_group: args.oidc_admin_group,
+            refresh,
+            now: mz_ore::now::SYSTEM_TIME.clone(),
+        };
+        Ok(Some(Self::new(config)))
+    }
+
+    /// Establishes a new authentication session with the token that the
+    /// client presented as its password.
+    ///
+    /// The token is either a JWT, or, if the `Authenticator` is configured to
+    /// refresh sessions, a refresh token.
+    ///
+    /// If successful, returns a handle to the authentication session.
+    /// Otherwise, returns the authentication error.
+    pub async fn authenticate(
+        &self,
+        expected_user: &str,
+        token: &str,
+    ) -> Result<AuthSessionHandle, Error> {
+        let result = match (jsonwebtoken::decode_header(token), &self.inner.refresh) {
+            (Err(_), Some(refresh)) => self
+                .inner
+                .refresh_access_token(refresh, token, expected_user)
+                .await
+                .map(|(claims, refresh_token)| (claims, Some(refresh_token))),
+            _ => self
+                .validate_access_token(token, Some(expected_user))
+                .await
+                .map(|claims| (claims, None)),
+        };
+        match result {
+            Ok((claims, refresh_token)) => {
+                tracing::debug!("authentication successful");
+                Ok(self.inner.start_session(claims, refresh_token))
+            }
+            Err(e) => {
+                tracing::debug!(error = ?e, "authentication failed");
+                Err(e)
+            }
+        }
+    }
+
+    /// Validates an access token, returning the validated claims.
+    ///
+    /// The following validations are always performed:
+    ///
+    ///   * The token is signed by a key in the issuer's JWKS.
+    ///
+    ///   * The token is not expired, according to the `Authenticator`'s clock.
+    ///
+    ///   * The issuer, and the audience if configured, match the
+    ///     `Authenticator`'s.
+    ///
+    /// If `expected_user` is provided, the token's user name is additionally
+    /// validated to match `expected_user`.
+    pub async fn validate_access_token(
+        &self,
+        token: &str,
+        expected_user: Option<&str>,
+    ) -> Result<ValidatedClaims, Error> {
+        self.inner.validate_access_token(token, expected_user).await
+    }
+}
+
+/// The subset of a token endpoint's response that we care about.
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+}
+
+#[derive(Debug)]
+struct AuthenticatorInner {
+    issuer: String,
+    audience: Option<String>,
+    keys: KeyStore,
+    client: reqwest::Client,
+    refresh: Option<RefreshConfig>,
+    user_claim: String,
+    groups_claim: String,
+    admin_group: Option<String>,
+    now: NowFn,
+}
+
+impl AuthenticatorInner {
+    /// Starts an authentication session for `claims`.
+    ///
+    /// Sessions established with a refresh token exchange it for a new access
+    /// token shortly before the current one expires, and publish the claims of
+    /// the new token. A session expires once its access token does and can't
+    /// be refreshed.
+    fn start_session(
+        self: &Arc<Self>,
+        mut claims: ValidatedClaims,
+        mut refresh_token: Option<String>,
+    ) -> AuthSessionHandle {
+        let user = claims.user.clone();
+        let (external_metadata_tx, external_metadata_rx) =
+            watch::channel(claims.to_external_user_metadata());
+
+        let name = format!("oidc-auth-session-{user}");
+        mz_ore::task::spawn(|| name, {
+            let inner = Arc::clone(self);
+            async move {
+                loop {
+                    let valid_for = Duration::try_from_secs_i64(claims.exp - inner.now.as_secs())
+                        .unwrap_or(Duration::ZERO);
+                    let (Some(refresh), Some(token)) = (&inner.refresh, &refresh_token) else {
+                        // The session can't be refreshed, so it lasts exactly
+                        // until its access token expires.
+                        tokio::select! {
+                            _ = time::sleep(valid_for) => (),
+                            _ = external_metadata_tx.closed() => (),
+                        }
+                        break;
+                    };
+
+                    // Like the Frontegg authenticator, refresh once 80% of the
+                    // token's validity has passed.
+                    tokio::select! {
+                        _ = time::sleep(valid_for.saturating_mul_f64(0.8)) => (),
+                        // There are no more handles to the session.
+                        _ = external_metadata_tx.closed() => break,
+                    }
+                    match inner
+                        .refresh_access_token(refresh, token, &claims.user)
+                        .await
+                    {
+                        Ok((new_claims, new_refresh_token)) => {
+                            tracing::debug!("refresh successful");
+                            external_metadata_tx
+                                .send_replace(new_claims.to_external_user_metadata());
+                            claims = new_claims;
+                            refresh_token = Some(new_refresh_token);
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = ?e, "refresh failed");
+                            // The session remains valid until its current
+                            // access token expires.
+                            let valid_for =
+                                Duration::try_from_secs_i64(claims.exp - inner.now.as_secs())
+                                    .unwrap_or(Duration::ZERO);
+                            time::sleep(valid_for).await;
+                            break;
+                        }
+                    }
+                }
+                tracing::debug!(%user, "authentication session expired");
+            }
+        });
+
+        AuthSessionHandle {
+            user: claims.user,
+            external_metadata_rx,
+        }
+    }
+
+    /// Exchanges a refresh token for an access token at the issuer's token
+    /// endpoint, returning the claims of the validated access token and the
+    /// refresh token to use next time.
+    async fn refresh_access_token(
+        &self,
+        refresh: &RefreshConfig,
+        refresh_token: &str,
+        expected_user: &str,
+    ) -> Result<(ValidatedClaims, String), Error> {
+        let response: TokenResponse = self
+            .client
+            .post(&refresh.token_url)
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+                ("client_id", &refresh.client_id),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let claims = self
+            .validate_access_token(&response.access_token, Some(expected_user))
+            .await?;
+        // Identity providers that rotate refresh tokens return a new one along
+        // with each access token.
+        let refresh_token = response
+            .refresh_token
+            .unwrap_or_else(|| refresh_token.to_string());
+        Ok((claims, refresh_token))
+    }
+
+    async fn validate_access_token(
+        &self,
+        token: &str,
+        expected_user: Option<&str>,
+    ) -> Result<ValidatedClaims, Error> {
+        let header = jsonwebtoken::decode_header(token)?;
+        if !SUPPORTED_ALGORITHMS.contains(&header.alg) {
+            return Err(Error::UnsupportedAlgorithm(header.alg));
+        }
+        let key = self.keys.get(header.kid.as_deref()).await?;
+        let decoding_key = DecodingKey::from_jwk(&key)?;
+
+        let mut validation = Validation::new(header.alg);
+        // We validate the token expiration with our own now function.
+        validation.validate_exp = false;
+        validation.set_issuer(&[&self.issuer]);
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        let msg =
+            jsonwebtoken::decode::<BTreeMap<String, Value>>(token, &decoding_key, &validation)?;
+        let claims = msg.claims;
+        let string_claim = |name: &str| {
+            claims
+                .get(name)
+                .and_then(Value::as_str)
+                .ok_or_else(|| Error::MissingClaim(name.into()))
+        };
+
+        let exp = claims
+            .get("exp")
+            .and_then(Value::as_i64)
+            .ok_or_else(|| Error::MissingClaim("exp".into()))?;
+        if exp < self.now.as_secs() {
+            return Err(Error::TokenExpired);
+        }
+
+        let user = string_claim(&self.user_claim)?;
+        if let Some(expected_user) = expected_user {
+            validate_user(user, expected_user)?;
+        }
+
+        // OIDC subjects are only unique per issuer, and needn't be UUIDs, so
+        // we derive a stable UUID from both.
+        let sub = string_claim("sub")?;
+        let user_id = Uuid::new_v5(
+            &Uuid::NAMESPACE_URL,
+            format!("{}#{}", self.issuer, sub).as_bytes(),
+        );
+
+        // Some identity providers emit a single group as a string rather than
+        // as an array.
+        let groups: Vec<String> = match claims.get(&self.groups_claim) {
+            Some(Value::Array(groups)) => groups
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect(),
+            Some(Value::String(group)) => vec![group.clone()],
+            _ => vec![],
+        };
+        // The user is an administrator if they are a member of the admin
+        // group that the `Authenticator` has been configured with.
+        let is_admin = self
+            .admin_group
+            .as_ref()
+            .is_some_and(|admin_group| groups.contains(admin_group));
+
+        Ok(ValidatedClaims {
+            exp,
+            user_id,
+            user: user.to_string(),
+            groups,
+            is_admin,
+            _private: (),
+        })
+    }
+}
+
+/// A handle to an authentication session.
+///
+/// An authentication session represents a duration of time during which a
+/// user's authentication is known to be valid. A session established with a
+/// JWT lasts exactly until the token expires. A session established with a
+/// refresh token is refreshed in the background, with the claims of each new
+/// access token, until refreshing fails.
+///
+/// [`AuthSessionHandle::expired`] can be used to learn when the session ends.
+#[derive(Debug, Clone)]
+pub struct AuthSessionHandle {
+    user: String,
+    external_metadata_rx: watch::Receiver<ExternalUserMetadata>,
+}
+
+impl AuthSessionHandle {
+    /// Returns the name of the user that created the session.
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Mints a receiver for updates to the session user's external metadata.
+    pub fn external_metadata_rx(&self) -> watch::Receiver<ExternalUserMetadata> {
+        self.external_metadata_rx.clone()
+    }
+
+    /// Completes when the authentication session has expired.
+    pub async fn expired(&mut self) {
+        // As in the Frontegg authenticator, the external metadata channel is
+        // closed when the session expires.
+        let _ = self.external_metadata_rx.wait_for(|_| false).await;
+    }
+}
+
+/// Claims that have been validated by [`Authenticator::validate_access_token`].
+#[derive(Clone, Debug)]
+pub struct ValidatedClaims {
+    /// The time at which the claims expire, represented in seconds since the
+    /// Unix epoch.
+    pub exp: i64,
+    /// The ID of the authenticated user, derived from the issuer and the
+    /// token's `sub` claim.
+    pub user_id: Uuid,
+    /// The name of the authenticated user, from the configured user claim.
+    pub user: String,
+    /// The groups that the user is a member of, from the configured groups
+    /// claim.
+    pub groups: Vec<String>,
+    /// Whether the authenticated user is an administrator.
+    pub is_admin: bool,
+    // Prevent construction outside of `Authenticator::validate_access_token`.
+    _private: (),
+}
+
+impl ValidatedClaims {
+    /// Constructs an [`ExternalUserMetadata`] from the claims data.
+    fn to_external_user_metadata(&self) -> ExternalUserMetadata {
+        ExternalUserMetadata {
+            admin: self.is_admin,
+            user_id: self.user_id,
+            groups: Some(self.groups.clone()),
+        }
+    }
+}
+
+fn validate_user(user: &str, expected_user: &str) -> Result<(), Error> {
+    // Impose a maximum length on user names for sanity.
+    if user.len() > MAX_USER_NAME_LENGTH {
+        return Err(Error::UserNameTooLong);
+    }
+    if user != expected_user {
+        return Err(Error::WrongUser);
+    }
+    Ok(())
+}
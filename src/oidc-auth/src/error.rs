@@ -0,0 +1,47 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use jsonwebtoken::Algorithm;
+use thiserror::Error;
+
+#[derive(Clone, Error, Debug)]
+pub enum Error {
+    #[error("invalid token format: {0}")]
+    InvalidTokenFormat(#[from] jsonwebtoken::errors::Error),
+    #[error("unsupported token signing algorithm: {0:?}")]
+    UnsupportedAlgorithm(Algorithm),
+    #[error("no signing key found for authentication token")]
+    UnknownSigningKey,
+    #[error("request to identity provider failed: {0}")]
+    ReqwestError(Arc<reqwest::Error>),
+    #[error("authentication token missing claim {0}")]
+    MissingClaim(String),
+    #[error("authentication token expired")]
+    TokenExpired,
+    #[error("user in access token did not match the expected user")]
+    WrongUser,
+    #[error("user name too long")]
+    UserNameTooLong,
+    #[error("internal error")]
+    Internal(Arc<anyhow::Error>),
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(value: anyhow::Error) -> Self {
+        Error::Internal(Arc::new(value))
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(value: reqwest::Error) -> Self {
+        Error::ReqwestError(Arc::new(value))
+    }
+}
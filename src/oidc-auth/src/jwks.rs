@@ -0,0 +1,152 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Fetching and caching of the keys used to validate JWTs.
+
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use crate::Error;
+
+/// The minimum amount of time between fetches of the JWKS.
+///
+/// Tokens signed by an unknown key trigger a fetch, in case the issuer has
+/// rotated its keys. This bounds how often invalid tokens can make us contact
+/// the issuer.
+const MIN_REFETCH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The source of the JWKS used to validate JWTs.
+#[derive(Clone, Debug)]
+pub enum JwksSource {
+    /// The JWKS is fetched from the URL that the issuer advertises in its
+    /// OpenID provider configuration.
+    Discover,
+    /// The JWKS is fetched from the specified URL.
+    Url(String),
+    /// A static JWKS, which is never refetched. Useful for testing.
+    Static(JwkSet),
+}
+
+/// The subset of an OpenID provider configuration that we care about.
+#[derive(Deserialize)]
+struct ProviderMetadata {
+    jwks_uri: String,
+}
+
+/// Caches the keys of an issuer, refetching them when a token is signed by an
+/// unknown key.
+#[derive(Debug)]
+pub(crate) struct KeyStore {
+    issuer: String,
+    source: JwksSource,
+    client: reqwest::Client,
+    state: Mutex<KeyStoreState>,
+}
+
+#[derive(Debug)]
+struct KeyStoreState {
+    keys: Option<JwkSet>,
+    fetched_at: Option<Instant>,
+}
+
+impl KeyStoreState {
+    fn find(&self, kid: Option<&str>) -> Option<Jwk> {
+        let keys = self.keys.as_ref()?;
+        match kid {
+            Some(kid) => keys.find(kid).cloned(),
+            // Tokens without a key ID can only be matched unambiguously if
+            // the issuer has a single key.
+            None => match keys.keys.as_slice() {
+                [key] => Some(key.clone()),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl KeyStore {
+    pub(crate) fn new(issuer: String, source: JwksSource) -> Self {
+        let state = KeyStoreState {
+            keys: match &source {
+                JwksSource::Static(keys) => Some(keys.clone()),
+                JwksSource::Discover | JwksSource::Url(_) => None,
+            },
+            fetched_at: None,
+        };
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .expect("must build Client");
+        KeyStore {
+            issuer,
+            source,
+            client,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Returns the key with the specified ID.
+    ///
+    /// If the key is unknown, the JWKS is refetched, unless it was fetched
+    /// within the last [`MIN_REFETCH_INTERVAL`].
+    pub(crate) async fn get(&self, kid: Option<&str>) -> Result<Jwk, Error> {
+        let mut state = self.state.lock().await;
+        if let Some(key) = state.find(kid) {
+            return Ok(key);
+        }
+
+        let refetch = match &self.source {
+            JwksSource::Static(_) => false,
+            JwksSource::Discover | JwksSource::Url(_) => match state.fetched_at {
+                Some(fetched_at) => fetched_at.elapsed() >= MIN_REFETCH_INTERVAL,
+                None => true,
+            },
+        };
+        if refetch {
+            state.keys = Some(self.fetch().await?);
+            state.fetched_at = Some(Instant::now());
+        }
+        state.find(kid).ok_or(Error::UnknownSigningKey)
+    }
+
+    async fn fetch(&self) -> Result<JwkSet, Error> {
+        let url = match &self.source {
+            JwksSource::Discover => {
+                let url = format!(
+                    "{}/.well-known/openid-configuration",
+                    self.issuer.trim_end_matches('/')
+                );
+                let metadata: ProviderMetadata = self
+                    .client
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                metadata.jwks_uri
+            }
+            JwksSource::Url(url) => url.clone(),
+            JwksSource::Static(keys) => return Ok(keys.clone()),
+        };
+        tracing::debug!(%url, "fetching JWKS");
+        let keys = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(keys)
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Authentication via OpenID Connect (OIDC) identity providers.
+//!
+//! Clients authenticate by presenting a JSON Web Token (JWT) issued by the
+//! configured OIDC issuer as their password. The token's signature is
+//! validated against the issuer's JSON Web Key Set (JWKS), and its claims are
+//! mapped to the name of a role and to the groups that the role is a member
+//! of. At the start of each session, the role is made a member of the existing
+//! roles that are named after its groups.
+//!
+//! A session established with a JWT ends when the token expires, and the
+//! client must reconnect with a new token. If a token endpoint and client ID
+//! are configured, clients can instead present a refresh token as their
+//! password. The refresh token is exchanged for an access token, and exchanged
+//! again shortly before each access token expires, so that the session lasts
+//! for as long as the identity provider issues new tokens. The groups of each
+//! new token are synchronized into the role's memberships.
+
+mod auth;
+mod error;
+mod jwks;
+
+use std::path::PathBuf;
+
+pub use auth::{
+    AuthSessionHandle, Authenticator, AuthenticatorConfig, RefreshConfig, ValidatedClaims,
+    DEFAULT_GROUPS_CLAIM, DEFAULT_USER_CLAIM,
+};
+pub use error::Error;
+pub use jsonwebtoken::jwk::JwkSet;
+pub use jwks::JwksSource;
+
+/// Command line arguments for OIDC authentication.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct OidcCliArgs {
+    /// Enables OIDC authentication with JWTs issued by the specified issuer.
+    ///
+    /// The issuer must match the `iss` claim of presented tokens exactly.
+    #[clap(long, env = "OIDC_ISSUER", value_name = "URL")]
+    oidc_issuer: Option<String>,
+    /// The URL of the JWKS used to validate JWTs. If not specified, the URL is
+    /// discovered from the issuer's `/.well-known/openid-configuration`.
+    #[clap(
+        long,
+        env = "OIDC_JWKS_URL",
+        requires = "oidc_issuer",
+        conflicts_with = "oidc_jwks_file"
+    )]
+    oidc_jwks_url: Option<String>,
+    /// Path to a static JWKS used to validate JWTs, in place of fetching the
+    /// JWKS from the issuer.
+    #[clap(long, env = "OIDC_JWKS_FILE", requires = "oidc_issuer")]
+    oidc_jwks_file: Option<PathBuf>,
+    /// The audience that JWTs must be issued for. If not specified, the `aud`
+    /// claim is not validated.
+    #[clap(long, env = "OIDC_AUDIENCE", requires = "oidc_issuer")]
+    oidc_audience: Option<String>,
+    /// The claim containing the name of the role to authenticate as.
+    #[clap(long, env = "OIDC_USER_CLAIM", default_value = DEFAULT_USER_CLAIM)]
+    oidc_user_claim: String,
+    /// The claim containing the groups that the user is a member of.
+    #[clap(long, env = "OIDC_GROUPS_CLAIM", default_value = DEFAULT_GROUPS_CLAIM)]
+    oidc_groups_claim: String,
+    /// The name of the group whose members are administrators.
+    #[clap(long, env = "OIDC_ADMIN_GROUP", requires = "oidc_issuer")]
+    oidc_admin_group: Option<String>,
+    /// The URL of the issuer's token endpoint. If specified, clients can
+    /// authenticate with refresh tokens, which are exchanged for JWTs at this
+    /// endpoint.
+    #[clap(
+        long,
+        env = "OIDC_TOKEN_URL",
+        requires_all = &["oidc_issuer", "oidc_client_id"]
+    )]
+    oidc_token_url: Option<String>,
+    /// The ID of the client that refresh tokens are issued to.
+    #[clap(long, env = "OIDC_CLIENT_ID", requires = "oidc_token_url")]
+    oidc_client_id: Option<String>,
+}
@@ -10,37 +10,49 @@
 //! CLI introspection tools for persist
 
 use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, bail};
+use bytes::Bytes;
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
 use futures_util::{stream, StreamExt, TryStreamExt};
 use mz_dyncfg::{Config, ConfigSet};
+use mz_ore::cast::CastFrom;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::SYSTEM_TIME;
 use mz_ore::url::SensitiveUrl;
 use mz_persist::location::{Blob, Consensus, ExternalError};
 use mz_persist_types::codec_impls::TodoSchema;
 use mz_persist_types::{Codec, Codec64};
+use mz_proto::{IntoRustIfSome, ProtoType, RustType};
 use prometheus::proto::{MetricFamily, MetricType};
+use prost::Message;
 use semver::Version;
+use sha2::{Digest, Sha256};
 use timely::progress::{Antichain, Timestamp};
 use tracing::{info, warn};
 
 use crate::async_runtime::IsolatedRuntime;
 use crate::cache::StateCache;
 use crate::cfg::{all_dyncfgs, COMPACTION_MEMORY_BOUND_BYTES};
-use crate::cli::args::{make_blob, make_consensus, StateArgs, StoreArgs};
-use crate::cli::inspect::FAKE_OPAQUE_CODEC;
+use crate::cli::args::{make_blob, make_consensus, StateArgs, StoreArgs, NO_COMMIT};
+use crate::cli::inspect::{FAKE_OPAQUE_CODEC, KVTD_CODECS};
 use crate::internal::compact::{CompactConfig, CompactReq, Compactor};
 use crate::internal::encoding::Schemas;
 use crate::internal::gc::{GarbageCollector, GcReq};
 use crate::internal::machine::Machine;
+use crate::internal::paths::PartialBatchKey;
+use crate::internal::state::{
+    BatchPart, HollowBatch, HollowRun, ProtoArchivedBlob, ProtoHollowRun, ProtoShardArchive,
+    RunPart, Since, SnapshotErr, Upper,
+};
 use crate::internal::trace::FueledMergeRes;
+use crate::read::{LeasedReaderId, READER_LEASE_DURATION};
 use crate::rpc::{NoopPubSubSender, PubSubSender};
 use crate::write::{WriteHandle, WriterId};
 use crate::{
@@ -77,6 +89,11 @@ pub(crate) enum Command {
     /// Attempt to ensure that all the files referenced by consensus are available
     /// in Blob.
     RestoreBlob(RestoreBlobArgs),
+    /// Writes the contents of a shard as of some time to a portable archive.
+    ExportShard(ExportShardArgs),
+    /// Restores a shard from an archive written by `export-shard` under a new
+    /// shard id.
+    ImportShard(ImportShardArgs),
 }
 
 /// Manually completes all fueled compactions in a shard.
@@ -123,6 +140,38 @@ pub(crate) struct RestoreBlobArgs {
     concurrency: usize,
 }
 
+/// Writes the contents of a shard as of some time to a portable archive.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct ExportShardArgs {
+    #[clap(flatten)]
+    state: StateArgs,
+
+    /// The time at which the exported shard must be readable. Defaults to the
+    /// since of the shard.
+    #[clap(long)]
+    as_of: Option<u64>,
+
+    /// Blob to write the archive to, e.g. `file:///path/to/archive`.
+    #[clap(long)]
+    archive_uri: SensitiveUrl,
+}
+
+/// Restores a shard from an archive written by `export-shard`.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct ImportShardArgs {
+    #[clap(flatten)]
+    state: StoreArgs,
+
+    /// Blob to read the archive from.
+    #[clap(long)]
+    archive_uri: SensitiveUrl,
+
+    /// The shard to restore the archive into, which must not have any contents
+    /// yet. Defaults to a newly generated shard id.
+    #[clap(long)]
+    shard_id: Option<String>,
+}
+
 /// Runs the given read-write admin command.
 pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
     match command.command {
@@ -374,6 +423,89 @@ pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
                 bail!("referenced blobs were not restored: {not_restored:#?}")
             }
         }
+        Command::ExportShard(args) => {
+            let ExportShardArgs {
+                state:
+                    StateArgs {
+                        shard_id,
+                        consensus_uri,
+                        blob_uri,
+                    },
+                as_of,
+                archive_uri,
+            } = args;
+            let shard_id = ShardId::from_str(&shard_id).expect("invalid shard id");
+            let commit = command.commit;
+            let configs = all_dyncfgs(ConfigSet::default());
+            // TODO: Fetch the latest values of these configs from Launch Darkly.
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone(), configs);
+            let metrics_registry = MetricsRegistry::new();
+            let metrics = Arc::new(Metrics::new(&cfg, &metrics_registry));
+            // The contents of the exported shard are never modified, only the
+            // readers registered in its state.
+            let consensus =
+                make_consensus(&cfg, &consensus_uri, commit, Arc::clone(&metrics)).await?;
+            let blob = make_blob(&cfg, &blob_uri, NO_COMMIT, Arc::clone(&metrics)).await?;
+            let archive = make_blob(&cfg, &archive_uri, commit, Arc::clone(&metrics)).await?;
+
+            let manifest = export_shard(
+                &cfg,
+                consensus,
+                blob,
+                archive.as_ref(),
+                metrics,
+                shard_id,
+                as_of,
+                commit,
+            )
+            .await?;
+            info!(
+                "exported {} batches and {} blobs of shard {shard_id}",
+                manifest.batches.len(),
+                manifest.blobs.len(),
+            );
+            if !commit {
+                info!("skipping writing the archive because --commit is not set");
+            }
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
+        Command::ImportShard(args) => {
+            let ImportShardArgs {
+                state:
+                    StoreArgs {
+                        consensus_uri,
+                        blob_uri,
+                    },
+                archive_uri,
+                shard_id,
+            } = args;
+            let shard_id = match shard_id {
+                Some(shard_id) => ShardId::from_str(&shard_id).expect("invalid shard id"),
+                None => ShardId::new(),
+            };
+            let commit = command.commit;
+            let configs = all_dyncfgs(ConfigSet::default());
+            // TODO: Fetch the latest values of these configs from Launch Darkly.
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone(), configs);
+            let metrics_registry = MetricsRegistry::new();
+            let metrics = Arc::new(Metrics::new(&cfg, &metrics_registry));
+            let consensus =
+                make_consensus(&cfg, &consensus_uri, commit, Arc::clone(&metrics)).await?;
+            let blob = make_blob(&cfg, &blob_uri, commit, Arc::clone(&metrics)).await?;
+            let archive = make_blob(&cfg, &archive_uri, NO_COMMIT, Arc::clone(&metrics)).await?;
+
+            import_shard(
+                &cfg,
+                archive.as_ref(),
+                consensus,
+                blob,
+                metrics,
+                shard_id,
+                commit,
+            )
+            .await?;
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
     }
     Ok(())
 }
@@ -588,7 +720,7 @@ where
         let state = match state_res {
             Ok(state) => state,
             Err(codec) => {
-                let mut kvtd = crate::cli::inspect::KVTD_CODECS.lock().expect("lockable");
+                let mut kvtd = KVTD_CODECS.lock().expect("lockable");
                 *kvtd = codec.actual;
                 continue;
             }
@@ -669,6 +801,299 @@ async fn force_gc(
     Ok(Box::new(machine))
 }
 
+/// The key of the manifest in a shard archive.
+const ARCHIVE_MANIFEST_KEY: &str = "manifest";
+
+/// The key of a blob in a shard archive, given its shard-independent key.
+fn archive_blob_key(key: &str) -> String {
+    format!("blobs/{key}")
+}
+
+/// Writes the contents of a shard as of `as_of` (or its since, if not given)
+/// to `archive`.
+///
+/// The archive consists of a [ProtoShardArchive] manifest with the batches
+/// needed to read the shard at `as_of`, plus a copy of every blob referenced by
+/// them. Blobs are stored under their shard-independent keys, so that
+/// [import_shard] can restore them under a different [ShardId].
+///
+/// While the blobs are copied, the shard is held at the exported seqno and
+/// since by a leased reader, so that garbage collection can't delete them.
+/// Without `commit`, the reader is not actually registered.
+async fn export_shard(
+    cfg: &PersistConfig,
+    consensus: Arc<dyn Consensus>,
+    blob: Arc<dyn Blob>,
+    archive: &dyn Blob,
+    metrics: Arc<Metrics>,
+    shard_id: ShardId,
+    as_of: Option<u64>,
+    commit: bool,
+) -> anyhow::Result<ProtoShardArchive> {
+    let machine = make_machine(
+        cfg,
+        consensus,
+        Arc::clone(&blob),
+        metrics,
+        shard_id,
+        commit,
+        None,
+    )
+    .await?;
+    let reader_id = LeasedReaderId::new();
+    let (reader_state, _maintenance) = machine
+        .register_leased_reader(
+            &reader_id,
+            "admin export",
+            READER_LEASE_DURATION.get(cfg),
+            (cfg.now)(),
+            false,
+        )
+        .await;
+    let res = export_batches(
+        cfg,
+        &machine,
+        blob.as_ref(),
+        archive,
+        &reader_id,
+        as_of.map_or(reader_state.since, Antichain::from_elem),
+    )
+    .await;
+    let (_seqno, _maintenance) = machine.expire_leased_reader(&reader_id).await;
+    res
+}
+
+/// Copies the batches of the shard needed to read it at `as_of` to `archive`,
+/// while heartbeating the leased reader that holds them back.
+async fn export_batches(
+    cfg: &PersistConfig,
+    machine: &Machine<crate::cli::inspect::K, crate::cli::inspect::V, u64, i64>,
+    blob: &dyn Blob,
+    archive: &dyn Blob,
+    reader_id: &LeasedReaderId,
+    as_of: Antichain<u64>,
+) -> anyhow::Result<ProtoShardArchive> {
+    let shard_id = machine.shard_id();
+    if as_of.is_empty() {
+        bail!("shard {shard_id} has been finalized");
+    }
+    // The snapshot is taken at the seqno the reader was registered at, which
+    // it holds back until it's expired.
+    let batches = match machine.applier.snapshot(&as_of) {
+        Ok(batches) => batches,
+        Err(SnapshotErr::AsOfNotYetAvailable(_seqno, Upper(upper))) => bail!(
+            "as_of {:?} is not yet available, upper is {:?}",
+            as_of.elements(),
+            upper.elements()
+        ),
+        Err(SnapshotErr::AsOfHistoricalDistinctionsLost(Since(since))) => bail!(
+            "as_of {:?} is not beyond the since {:?}",
+            as_of.elements(),
+            since.elements()
+        ),
+    };
+    let schemas = machine.applier.schemas(|_seqno, schemas| schemas.clone());
+    let (key_codec, val_codec, ts_codec, diff_codec, _) =
+        KVTD_CODECS.lock().expect("lockable").clone();
+
+    let heartbeat_interval = READER_LEASE_DURATION.get(cfg) / 4;
+    let mut last_heartbeat = (cfg.now)();
+    let mut blobs = Vec::new();
+    let mut copied = BTreeSet::new();
+    let mut part_queue: Vec<_> = batches.iter().flat_map(|b| b.parts.clone()).collect();
+    while let Some(part) = part_queue.pop() {
+        let (key, is_run) = match part {
+            RunPart::Single(BatchPart::Inline { .. }) => continue,
+            RunPart::Single(BatchPart::Hollow(part)) => (part.key, false),
+            RunPart::Many(run) => (run.key, true),
+        };
+        if !copied.insert(key.clone()) {
+            continue;
+        }
+        let blob_key = key.complete(&shard_id);
+        let value = blob
+            .get(&blob_key)
+            .await?
+            .ok_or_else(|| anyhow!("referenced blob {blob_key} is missing"))?;
+        let value = Bytes::from(value.into_contiguous());
+        if is_run {
+            let run: ProtoHollowRun = Message::decode(value.clone())?;
+            let run: HollowRun<u64> = run.into_rust()?;
+            part_queue.extend(run.parts);
+        }
+        blobs.push(ProtoArchivedBlob {
+            key: key.0.clone(),
+            size_bytes: u64::cast_from(value.len()),
+            sha256: Sha256::digest(&value).to_vec(),
+        });
+        archive.set(&archive_blob_key(&key.0), value).await?;
+
+        let now = (cfg.now)();
+        if Duration::from_millis(now.saturating_sub(last_heartbeat)) >= heartbeat_interval {
+            let (_seqno, existed, _maintenance) =
+                machine.heartbeat_leased_reader(reader_id, now).await;
+            if !existed {
+                bail!("lease of reader {reader_id} expired while exporting shard {shard_id}");
+            }
+            last_heartbeat = now;
+        }
+    }
+
+    let manifest = ProtoShardArchive {
+        applier_version: cfg.build_version.to_string(),
+        shard_id: shard_id.to_string(),
+        key_codec,
+        val_codec,
+        ts_codec,
+        diff_codec,
+        as_of: Some(as_of.into_proto()),
+        schemas: schemas
+            .iter()
+            .map(|(id, schema)| (id.into_proto(), schema.into_proto()))
+            .collect(),
+        batches: batches.iter().map(|b| b.into_proto()).collect(),
+        blobs,
+    };
+    // Write the manifest last, so that an archive with a manifest is complete.
+    archive
+        .set(ARCHIVE_MANIFEST_KEY, Bytes::from(manifest.encode_to_vec()))
+        .await?;
+    Ok(manifest)
+}
+
+/// Restores a shard from an archive written by [export_shard] into the given
+/// shard, which must not have any contents yet.
+///
+/// Every blob is verified against the checksum recorded in the manifest before
+/// it's copied, and the batches are only added to the shard once all of them
+/// have been copied. Without `commit`, the archive is only verified.
+async fn import_shard(
+    cfg: &PersistConfig,
+    archive: &dyn Blob,
+    consensus: Arc<dyn Consensus>,
+    blob: Arc<dyn Blob>,
+    metrics: Arc<Metrics>,
+    shard_id: ShardId,
+    commit: bool,
+) -> anyhow::Result<()> {
+    let manifest = archive
+        .get(ARCHIVE_MANIFEST_KEY)
+        .await?
+        .ok_or_else(|| anyhow!("archive has no manifest"))?;
+    let manifest = ProtoShardArchive::decode(Bytes::from(manifest.into_contiguous()))?;
+    let archive_version = Version::parse(&manifest.applier_version)?;
+    if archive_version > cfg.build_version {
+        bail!(
+            "archive was written by version {archive_version}, which is newer than this tool's {}",
+            cfg.build_version
+        );
+    }
+    // Like the rest of the admin commands, we can only handle the timestamp
+    // and diff types used by Materialize.
+    if manifest.ts_codec != <u64 as Codec64>::codec_name()
+        || manifest.diff_codec != <i64 as Codec64>::codec_name()
+    {
+        bail!(
+            "unsupported timestamp and diff codecs {} and {}",
+            manifest.ts_codec,
+            manifest.diff_codec
+        );
+    }
+    let as_of: Antichain<u64> = manifest
+        .as_of
+        .into_rust_if_some("ProtoShardArchive::as_of")?;
+    let mut schemas = BTreeMap::new();
+    for (id, schema) in manifest.schemas {
+        schemas.insert(id.into_rust()?, schema.into_rust()?);
+    }
+    let batches = manifest
+        .batches
+        .into_iter()
+        .map(|b| b.into_rust())
+        .collect::<Result<Vec<HollowBatch<u64>>, _>>()?;
+    let upper = batches.last().map_or_else(
+        || Antichain::from_elem(u64::minimum()),
+        |b| b.desc.upper().clone(),
+    );
+
+    let machine = if commit {
+        {
+            let mut kvtd = crate::cli::inspect::KVTD_CODECS.lock().expect("lockable");
+            *kvtd = (
+                manifest.key_codec,
+                manifest.val_codec,
+                manifest.ts_codec,
+                manifest.diff_codec,
+                None,
+            );
+        }
+        let state_versions = Arc::new(StateVersions::new(
+            cfg.clone(),
+            consensus,
+            Arc::clone(&blob),
+            Arc::clone(&metrics),
+        ));
+        let machine = Machine::<crate::cli::inspect::K, crate::cli::inspect::V, u64, i64>::new(
+            cfg.clone(),
+            shard_id,
+            Arc::clone(&metrics),
+            state_versions,
+            Arc::new(StateCache::new(cfg, metrics, Arc::new(NoopPubSubSender))),
+            Arc::new(NoopPubSubSender),
+            Arc::new(IsolatedRuntime::default()),
+            Diagnostics::from_purpose("admin import"),
+        )
+        .await?;
+        // Bail before copying anything into a shard that's already in use. An
+        // upper that matches the archive is a retry of an earlier import.
+        let shard_upper = machine.applier.clone_upper();
+        if shard_upper != Antichain::from_elem(u64::minimum()) && shard_upper != upper {
+            bail!("shard {shard_id} already has contents");
+        }
+        Some(machine)
+    } else {
+        None
+    };
+
+    for archived in &manifest.blobs {
+        let value = archive
+            .get(&archive_blob_key(&archived.key))
+            .await?
+            .ok_or_else(|| anyhow!("archived blob {} is missing", archived.key))?;
+        let value = Bytes::from(value.into_contiguous());
+        if u64::cast_from(value.len()) != archived.size_bytes
+            || Sha256::digest(&value).as_slice() != archived.sha256.as_slice()
+        {
+            bail!("archived blob {} does not match its checksum", archived.key);
+        }
+        if commit {
+            let key = PartialBatchKey(archived.key.clone()).complete(&shard_id);
+            blob.set(&key, value).await?;
+        }
+    }
+
+    let Some(machine) = machine else {
+        info!(
+            "verified {} archived blobs; skipping import because --commit is not set",
+            manifest.blobs.len()
+        );
+        return Ok(());
+    };
+    let (imported, maintenance) = machine.import_batches(&schemas, &batches, &as_of).await;
+    if !maintenance.is_empty() {
+        info!("ignoring non-empty requested maintenance: {maintenance:?}")
+    }
+    if !imported {
+        bail!("shard {shard_id} already has contents");
+    }
+    info!(
+        "imported shard {} as of {:?} as shard {shard_id}",
+        manifest.shard_id,
+        as_of.elements()
+    );
+    Ok(())
+}
+
 /// Exposed for `mz-catalog`.
 pub const CATALOG_FORCE_COMPACTION_FUEL: Config<usize> = Config::new(
     "persist_catalog_force_compaction_fuel",
@@ -799,12 +1224,19 @@ pub async fn dangerous_force_compaction_and_break_pushdown<K, V, T, D>(
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
     use std::time::Duration;
 
+    use bytes::Bytes;
     use mz_dyncfg::ConfigUpdates;
+    use mz_persist::location::Blob;
+    use mz_persist::mem::{MemBlob, MemBlobConfig};
     use mz_persist_types::ShardId;
+    use timely::progress::Antichain;
 
-    use crate::tests::new_test_client;
+    use crate::batch::INLINE_WRITES_SINGLE_MAX_BYTES;
+    use crate::tests::{all_ok, new_test_client};
+    use crate::StateVersions;
 
     #[mz_persist_proc::test(tokio::test)]
     #[cfg_attr(miri, ignore)]
@@ -829,4 +1261,126 @@ mod tests {
             assert!(batches_after < 2, "{} vs {}", num_batches, batches_after);
         }
     }
+
+    #[mz_persist_proc::test(tokio::test)]
+    #[cfg_attr(miri, ignore)]
+    async fn export_import_shard(dyncfgs: ConfigUpdates) {
+        let data = [
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+        ];
+
+        let source = new_test_client(&dyncfgs).await;
+        // Make sure that some of the data ends up in blobs.
+        source.cfg.set_config(&INLINE_WRITES_SINGLE_MAX_BYTES, 0);
+        let shard_id = ShardId::new();
+        let (mut write, mut read) = source
+            .expect_open::<String, String, u64, i64>(shard_id)
+            .await;
+        write.expect_compare_and_append(&data[..1], 0, 2).await;
+        write.expect_compare_and_append(&data[1..2], 2, 3).await;
+        write.expect_compare_and_append(&data[2..], 3, 4).await;
+        read.downgrade_since(&Antichain::from_elem(2)).await;
+
+        // Anything that isn't readable can't be exported.
+        let archive = MemBlob::open(MemBlobConfig::default());
+        for as_of in [1, 4] {
+            let res = super::export_shard(
+                &source.cfg,
+                Arc::clone(&source.consensus),
+                Arc::clone(&source.blob),
+                &archive,
+                Arc::clone(&source.metrics),
+                shard_id,
+                Some(as_of),
+                true,
+            )
+            .await;
+            assert!(res.is_err(), "as_of {as_of}");
+        }
+
+        let manifest = super::export_shard(
+            &source.cfg,
+            Arc::clone(&source.consensus),
+            Arc::clone(&source.blob),
+            &archive,
+            Arc::clone(&source.metrics),
+            shard_id,
+            None,
+            true,
+        )
+        .await
+        .expect("exportable shard");
+        assert!(!manifest.blobs.is_empty());
+
+        // The export held the shard with a reader of its own, which it expired
+        // once it was done.
+        let mut states = StateVersions::new(
+            source.cfg.clone(),
+            Arc::clone(&source.consensus),
+            Arc::clone(&source.blob),
+            Arc::clone(&source.metrics),
+        )
+        .fetch_all_live_states::<u64>(shard_id)
+        .await
+        .expect("initialized shard")
+        .check_ts_codec()
+        .expect("u64 timestamps");
+        let mut max_readers = 0;
+        while let Some(state) = states.next(|_| {}) {
+            max_readers = max_readers.max(state.collections.leased_readers.len());
+        }
+        assert_eq!(max_readers, 2);
+        assert_eq!(states.state().collections.leased_readers.len(), 1);
+
+        // Import into a different location, twice to check that retries are
+        // idempotent.
+        let target = new_test_client(&dyncfgs).await;
+        let new_shard_id = ShardId::new();
+        for _ in 0..2 {
+            super::import_shard(
+                &target.cfg,
+                &archive,
+                Arc::clone(&target.consensus),
+                Arc::clone(&target.blob),
+                Arc::clone(&target.metrics),
+                new_shard_id,
+                true,
+            )
+            .await
+            .expect("valid archive");
+        }
+        let (_, mut new_read) = target
+            .expect_open::<String, String, u64, i64>(new_shard_id)
+            .await;
+        assert_eq!(new_read.since(), &Antichain::from_elem(2));
+        assert_eq!(
+            new_read.expect_snapshot_and_fetch(2).await,
+            all_ok(&data[..2], 2)
+        );
+
+        // A corrupted blob is detected before anything is imported.
+        let key = super::archive_blob_key(&manifest.blobs[0].key);
+        archive
+            .set(&key, Bytes::from_static(b"corrupted"))
+            .await
+            .expect("set");
+        let corrupted_shard_id = ShardId::new();
+        let res = super::import_shard(
+            &target.cfg,
+            &archive,
+            Arc::clone(&target.consensus),
+            Arc::clone(&target.blob),
+            Arc::clone(&target.metrics),
+            corrupted_shard_id,
+            true,
+        )
+        .await;
+        assert!(res.is_err());
+        let (_, corrupted_read) = target
+            .expect_open::<String, String, u64, i64>(corrupted_shard_id)
+            .await;
+        assert_eq!(corrupted_read.since(), &Antichain::from_elem(0));
+    }
 }
@@ -9,6 +9,7 @@
 
 //! Implementation of the persist state machine.
 
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::future::Future;
 use std::ops::ControlFlow::{self, Continue};
@@ -49,9 +50,9 @@ use crate::internal::maintenance::{RoutineMaintenance, WriterMaintenance};
 use crate::internal::metrics::{CmdMetrics, Metrics, MetricsRetryStream, RetryMetrics};
 use crate::internal::paths::PartialRollupKey;
 use crate::internal::state::{
-    CompareAndAppendBreak, CriticalReaderState, HandleDebugState, HollowBatch, HollowRollup,
-    IdempotencyToken, LeasedReaderState, NoOpStateTransition, Since, SnapshotErr, StateCollections,
-    Upper,
+    CompareAndAppendBreak, CriticalReaderState, EncodedSchemas, HandleDebugState, HollowBatch,
+    HollowRollup, IdempotencyToken, LeasedReaderState, NoOpStateTransition, Since, SnapshotErr,
+    StateCollections, Upper,
 };
 use crate::internal::state_versions::StateVersions;
 use crate::internal::trace::{ApplyMergeResult, FueledMergeRes};
@@ -264,6 +265,23 @@ where
         (state, maintenance)
     }
 
+    /// Populates this shard, which must be empty, with the given schemas and
+    /// batches. Returns false if the shard already had contents of its own.
+    pub async fn import_batches(
+        &self,
+        schemas: &BTreeMap<SchemaId, EncodedSchemas>,
+        batches: &[HollowBatch<T>],
+        since: &Antichain<T>,
+    ) -> (bool, RoutineMaintenance) {
+        let metrics = Arc::clone(&self.applier.metrics);
+        let (_seqno, imported, maintenance) = self
+            .apply_unbatched_idempotent_cmd(&metrics.cmds.import_batches, |_seqno, _cfg, state| {
+                state.import_batches(schemas, batches, since)
+            })
+            .await;
+        (imported, maintenance)
+    }
+
     pub async fn spine_exert(&self, fuel: usize) -> (Vec<CompactReq<T>>, RoutineMaintenance) {
         // Performance special case for no-ops, to avoid the State clones.
         if fuel == 0 || self.applier.all_batches().len() < 2 {
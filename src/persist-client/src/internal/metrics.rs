@@ -428,6 +428,7 @@ impl MetricsVecs {
             become_tombstone: self.cmd_metrics("become_tombstone"),
             compare_and_evolve_schema: self.cmd_metrics("compare_and_evolve_schema"),
             spine_exert: self.cmd_metrics("spine_exert"),
+            import_batches: self.cmd_metrics("import_batches"),
             fetch_upper_count: registry.register(metric!(
                 name: "mz_persist_cmd_fetch_upper_count",
                 help: "count of fetch_upper calls",
@@ -637,6 +638,7 @@ pub struct CmdsMetrics {
     pub(crate) become_tombstone: CmdMetrics,
     pub(crate) compare_and_evolve_schema: CmdMetrics,
     pub(crate) spine_exert: CmdMetrics,
+    pub(crate) import_batches: CmdMetrics,
     pub(crate) fetch_upper_count: IntCounter,
 }
 
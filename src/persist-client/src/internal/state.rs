@@ -1623,6 +1623,47 @@ where
         Continue(apply_merge_result)
     }
 
+    /// Populates an empty shard with the given schemas and batches, which are
+    /// readable as of `since`.
+    ///
+    /// This is used to restore a shard from an archive, so the batches are
+    /// expected to be contiguous, starting at the minimum timestamp. Returns
+    /// false if the shard already has contents of its own.
+    pub fn import_batches(
+        &mut self,
+        schemas: &BTreeMap<SchemaId, EncodedSchemas>,
+        batches: &[HollowBatch<T>],
+        since: &Antichain<T>,
+    ) -> ControlFlow<NoOpStateTransition<bool>, bool> {
+        let upper = match batches.last() {
+            Some(batch) => batch.desc.upper().clone(),
+            None => Antichain::from_elem(T::minimum()),
+        };
+        // A retry of an import that already committed.
+        if self.trace.upper() == &upper && self.trace.since() == since && &self.schemas == schemas {
+            return Break(NoOpStateTransition(true));
+        }
+
+        let is_empty = self.trace.upper() == &Antichain::from_elem(T::minimum())
+            && self.trace.since() == &Antichain::from_elem(T::minimum())
+            && self.schemas.is_empty()
+            && self.writers.is_empty()
+            && self.leased_readers.is_empty()
+            && self.critical_readers.is_empty();
+        if !is_empty {
+            return Break(NoOpStateTransition(false));
+        }
+
+        self.schemas.clone_from(schemas);
+        // The since has to be downgraded first: the spine requires the since of
+        // each batch to not be beyond its own.
+        self.trace.downgrade_since(since);
+        for batch in batches {
+            self.trace.push_batch_no_merge_reqs(batch.clone());
+        }
+        Continue(true)
+    }
+
     pub fn spine_exert(
         &mut self,
         fuel: usize,
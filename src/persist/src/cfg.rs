@@ -22,7 +22,7 @@ use mz_postgres_client::metrics::PostgresClientMetrics;
 use mz_postgres_client::PostgresClientKnobs;
 
 use crate::azure::{AzureBlob, AzureBlobConfig};
-use crate::file::{FileBlob, FileBlobConfig};
+use crate::file::{FileBlob, FileBlobConfig, FileConsensus, FileConsensusConfig};
 use crate::location::{Blob, Consensus, Determinate, ExternalError};
 use crate::mem::{MemBlob, MemBlobConfig, MemConsensus};
 use crate::metrics::S3BlobMetrics;
@@ -211,6 +211,8 @@ impl BlobConfig {
 /// Config for an implementation of [Consensus].
 #[derive(Debug, Clone)]
 pub enum ConsensusConfig {
+    /// Config for [FileConsensus].
+    File(FileConsensusConfig),
     /// Config for [PostgresConsensus].
     Postgres(PostgresConsensusConfig),
     /// Config for [MemConsensus], only available in testing.
@@ -221,6 +223,7 @@ impl ConsensusConfig {
     /// Opens the associated implementation of [Consensus].
     pub async fn open(self) -> Result<Arc<dyn Consensus>, ExternalError> {
         match self {
+            ConsensusConfig::File(config) => Ok(Arc::new(FileConsensus::open(config).await?)),
             ConsensusConfig::Postgres(config) => {
                 Ok(Arc::new(PostgresConsensus::open(config).await?))
             }
@@ -235,6 +238,7 @@ impl ConsensusConfig {
         metrics: PostgresClientMetrics,
     ) -> Result<Self, ExternalError> {
         let config = match url.scheme() {
+            "file" => Ok(ConsensusConfig::File(FileConsensusConfig::from(url.path()))),
             "postgres" | "postgresql" => Ok(ConsensusConfig::Postgres(
                 PostgresConsensusConfig::new(url, knobs, metrics)?,
             )),
@@ -7,22 +7,32 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-//! File backed implementations for testing and benchmarking.
+//! File backed implementations for testing, benchmarking and single-node
+//! deployments.
 
-use std::io::ErrorKind;
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use anyhow::anyhow;
+use async_stream::try_stream;
 use async_trait::async_trait;
 use bytes::Bytes;
 use fail::fail_point;
 use mz_ore::bytes::SegmentedBytes;
 use mz_ore::cast::CastFrom;
+use nix::fcntl::{flock, FlockArg};
 use tokio::fs::{self, File};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::error::Error;
-use crate::location::{Blob, BlobMetadata, Determinate, ExternalError};
+use crate::location::{
+    Blob, BlobMetadata, CaSResult, Consensus, Determinate, ExternalError, ResultStream, SeqNo,
+    VersionedData,
+};
 
 /// Configuration for opening a [FileBlob].
 #[derive(Debug, Clone)]
@@ -269,9 +279,497 @@ impl Blob for FileBlob {
     }
 }
 
+/// Configuration for opening a [FileConsensus].
+#[derive(Debug, Clone)]
+pub struct FileConsensusConfig {
+    base_dir: PathBuf,
+}
+
+impl<P: AsRef<Path>> From<P> for FileConsensusConfig {
+    fn from(base_dir: P) -> Self {
+        FileConsensusConfig {
+            base_dir: base_dir.as_ref().to_path_buf(),
+        }
+    }
+}
+
+/// Implementation of [Consensus] backed by an append-only log file.
+///
+/// Every committed `compare_and_set` and `truncate` is appended to a log in
+/// the configured directory and fsync'd before it is acknowledged. The current
+/// state is kept in memory and brought up to date by replaying whatever
+/// suffix of the log it hasn't seen yet, so that the same directory can be
+/// used concurrently by several processes on one host, e.g. `environmentd`
+/// and its `clusterd`s. Access to the log is serialized with an advisory lock
+/// on a separate lock file, which means the directory must not live on a
+/// network filesystem.
+///
+/// Once enough of the log consists of truncated versions, it is compacted by
+/// atomically replacing it with a log containing only the live versions.
+#[derive(Debug)]
+pub struct FileConsensus {
+    core: Arc<Mutex<FileConsensusCore>>,
+}
+
+impl FileConsensus {
+    const LOG_FILE: &'static str = "consensus.log";
+    const LOCK_FILE: &'static str = "consensus.lock";
+
+    /// Opens the given location for non-exclusive read-write access.
+    pub async fn open(config: FileConsensusConfig) -> Result<Self, ExternalError> {
+        let core = mz_ore::task::spawn_blocking(
+            || "persist::file_consensus::open",
+            move || FileConsensusCore::open(config.base_dir),
+        )
+        .await??;
+        Ok(FileConsensus {
+            core: Arc::new(Mutex::new(core)),
+        })
+    }
+
+    /// Runs `f` against the core on a thread where blocking is acceptable,
+    /// because the core does synchronous file I/O and waits for the file
+    /// lock.
+    async fn run<T, F>(&self, name: &'static str, f: F) -> Result<T, ExternalError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut FileConsensusCore) -> Result<T, ExternalError> + Send + 'static,
+    {
+        let core = Arc::clone(&self.core);
+        mz_ore::task::spawn_blocking(
+            || name,
+            move || {
+                let mut core = core.lock().map_err(Error::from)?;
+                f(&mut core)
+            },
+        )
+        .await?
+    }
+}
+
+#[async_trait]
+impl Consensus for FileConsensus {
+    fn list_keys(&self) -> ResultStream<String> {
+        let keys = self.run("persist::file_consensus::list_keys", |core| {
+            let _lock = core.lock(FlockArg::LockShared)?;
+            core.refresh()?;
+            Ok(core.data.keys().cloned().collect::<Vec<_>>())
+        });
+        Box::pin(try_stream! {
+            for key in keys.await? {
+                yield key;
+            }
+        })
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<VersionedData>, ExternalError> {
+        let key = key.to_owned();
+        self.run("persist::file_consensus::head", move |core| {
+            let _lock = core.lock(FlockArg::LockShared)?;
+            core.refresh()?;
+            Ok(core.head(&key).cloned())
+        })
+        .await
+    }
+
+    async fn compare_and_set(
+        &self,
+        key: &str,
+        expected: Option<SeqNo>,
+        new: VersionedData,
+    ) -> Result<CaSResult, ExternalError> {
+        if let Some(expected) = expected {
+            if new.seqno <= expected {
+                return Err(ExternalError::from(
+                        anyhow!("new seqno must be strictly greater than expected. Got new: {:?} expected: {:?}",
+                                 new.seqno, expected)));
+            }
+        }
+
+        if new.seqno.0 > i64::MAX.try_into().expect("i64::MAX known to fit in u64") {
+            return Err(ExternalError::from(anyhow!(
+                "sequence numbers must fit within [0, i64::MAX], received: {:?}",
+                new.seqno
+            )));
+        }
+
+        let key = key.to_owned();
+        self.run("persist::file_consensus::compare_and_set", move |core| {
+            let _lock = core.lock(FlockArg::LockExclusive)?;
+            core.refresh()?;
+
+            let seqno = core.head(&key).map(|data| data.seqno);
+            if seqno != expected {
+                return Ok(CaSResult::ExpectationMismatch);
+            }
+
+            core.append(LogRecord::Set { key, data: new })?;
+            Ok(CaSResult::Committed)
+        })
+        .await
+    }
+
+    async fn scan(
+        &self,
+        key: &str,
+        from: SeqNo,
+        limit: usize,
+    ) -> Result<Vec<VersionedData>, ExternalError> {
+        let key = key.to_owned();
+        self.run("persist::file_consensus::scan", move |core| {
+            let _lock = core.lock(FlockArg::LockShared)?;
+            core.refresh()?;
+
+            let results = if let Some(values) = core.data.get(&key) {
+                let from_idx = values.partition_point(|x| x.seqno < from);
+                let from_values = &values[from_idx..];
+                let from_values = &from_values[..usize::min(limit, from_values.len())];
+                from_values.to_vec()
+            } else {
+                Vec::new()
+            };
+            Ok(results)
+        })
+        .await
+    }
+
+    async fn truncate(&self, key: &str, seqno: SeqNo) -> Result<usize, ExternalError> {
+        let key = key.to_owned();
+        self.run("persist::file_consensus::truncate", move |core| {
+            let _lock = core.lock(FlockArg::LockExclusive)?;
+            core.refresh()?;
+
+            let values = match core.data.get(&key) {
+                Some(values) if values.last().is_some_and(|data| data.seqno >= seqno) => values,
+                _ => {
+                    return Err(ExternalError::from(anyhow!(
+                        "upper bound too high for truncate: {:?}",
+                        seqno
+                    )))
+                }
+            };
+            let deleted = values.partition_point(|x| x.seqno < seqno);
+            if deleted > 0 {
+                core.append(LogRecord::Truncate { key, seqno })?;
+                core.maybe_compact()?;
+            }
+            Ok(deleted)
+        })
+        .await
+    }
+}
+
+/// A change to the state of a [FileConsensus], as recorded in its log.
+#[derive(Debug)]
+enum LogRecord {
+    Set { key: String, data: VersionedData },
+    Truncate { key: String, seqno: SeqNo },
+}
+
+impl LogRecord {
+    const SET: u8 = 0;
+    const TRUNCATE: u8 = 1;
+
+    /// The size of the header of each record in the log, which holds the
+    /// length and the CRC32 checksum of its payload.
+    const HEADER_LEN: usize = 8;
+
+    /// Appends this record, including its header, to `buf`.
+    fn encode(&self, buf: &mut Vec<u8>) -> Result<(), ExternalError> {
+        let mut payload = Vec::new();
+        let (kind, key, seqno) = match self {
+            LogRecord::Set { key, data } => (LogRecord::SET, key, data.seqno),
+            LogRecord::Truncate { key, seqno } => (LogRecord::TRUNCATE, key, *seqno),
+        };
+        payload.push(kind);
+        let key_len = u32::try_from(key.len())
+            .map_err(|_| anyhow!("consensus key too long: {} bytes", key.len()))?;
+        payload.extend_from_slice(&key_len.to_le_bytes());
+        payload.extend_from_slice(key.as_bytes());
+        payload.extend_from_slice(&seqno.0.to_le_bytes());
+        if let LogRecord::Set { data, .. } = self {
+            payload.extend_from_slice(&data.data);
+        }
+
+        let payload_len = u32::try_from(payload.len())
+            .map_err(|_| anyhow!("consensus data too large: {} bytes", payload.len()))?;
+        buf.extend_from_slice(&payload_len.to_le_bytes());
+        buf.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+        buf.extend_from_slice(&payload);
+        Ok(())
+    }
+
+    /// Decodes the record at the start of `buf`, returning it along with its
+    /// encoded length.
+    ///
+    /// Returns `None` if `buf` holds an incomplete record, or a record whose
+    /// checksum doesn't match and that runs up to the end of `buf`, which is
+    /// what the tail of the log looks like if a process crashed in the middle
+    /// of appending to it. A damaged record that is followed by more data was
+    /// acknowledged, so it is reported as an error instead.
+    fn decode(buf: &[u8]) -> Result<Option<(LogRecord, usize)>, ExternalError> {
+        let Some((header, rest)) = buf.split_first_chunk::<{ LogRecord::HEADER_LEN }>() else {
+            return Ok(None);
+        };
+        let (len, crc) = header.split_at(4);
+        let len = usize::cast_from(u32::from_le_bytes(len.try_into().expect("4 bytes")));
+        let crc = u32::from_le_bytes(crc.try_into().expect("4 bytes"));
+        let Some(payload) = rest.get(..len) else {
+            return Ok(None);
+        };
+        if crc32fast::hash(payload) != crc {
+            if rest.len() > len {
+                return Err(ExternalError::from(anyhow!(
+                    "corrupt consensus log record: checksum mismatch"
+                )));
+            }
+            return Ok(None);
+        }
+
+        let invalid = || ExternalError::from(anyhow!("invalid consensus log record"));
+        let (&kind, payload) = payload.split_first().ok_or_else(invalid)?;
+        let (key_len, payload) = payload.split_first_chunk::<4>().ok_or_else(invalid)?;
+        let key_len = usize::cast_from(u32::from_le_bytes(*key_len));
+        if payload.len() < key_len {
+            return Err(invalid());
+        }
+        let (key, payload) = payload.split_at(key_len);
+        let key = String::from_utf8(key.to_vec()).map_err(|_| invalid())?;
+        let (seqno, payload) = payload.split_first_chunk::<8>().ok_or_else(invalid)?;
+        let seqno = SeqNo(u64::from_le_bytes(*seqno));
+        let record = match kind {
+            LogRecord::SET => LogRecord::Set {
+                key,
+                data: VersionedData {
+                    seqno,
+                    data: Bytes::copy_from_slice(payload),
+                },
+            },
+            LogRecord::TRUNCATE if payload.is_empty() => LogRecord::Truncate { key, seqno },
+            _ => return Err(invalid()),
+        };
+        Ok(Some((record, LogRecord::HEADER_LEN + len)))
+    }
+}
+
+/// The state of a [FileConsensus], along with the handles of the files that
+/// it is made durable in.
+#[derive(Debug)]
+struct FileConsensusCore {
+    base_dir: PathBuf,
+    lock_file: std::fs::File,
+    /// The log, opened for appending.
+    log: std::fs::File,
+    /// The device and inode numbers of `log`, which are used to detect that
+    /// another process has replaced the log by compacting it.
+    log_id: (u64, u64),
+    /// The length of the prefix of `log` that is reflected in `data`.
+    offset: u64,
+    /// The number of records in the prefix of `log` that is reflected in
+    /// `data`.
+    records: usize,
+    data: BTreeMap<String, Vec<VersionedData>>,
+}
+
+impl FileConsensusCore {
+    /// The minimum number of records in the log before it is considered for
+    /// compaction.
+    const COMPACTION_MIN_RECORDS: usize = 1024;
+
+    fn open(base_dir: PathBuf) -> Result<Self, ExternalError> {
+        std::fs::create_dir_all(&base_dir)?;
+        let lock_file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(base_dir.join(FileConsensus::LOCK_FILE))?;
+
+        let lock = Flock::acquire(&lock_file, FlockArg::LockExclusive)?;
+        let log_path = base_dir.join(FileConsensus::LOG_FILE);
+        let created = !log_path.try_exists()?;
+        let log = Self::open_log(&log_path)?;
+        if created {
+            // fsync the directory so the new log is guaranteed to be visible.
+            std::fs::File::open(&base_dir)?.sync_all()?;
+        }
+        let log_id = Self::file_id(&log.metadata()?);
+        drop(lock);
+
+        let mut core = FileConsensusCore {
+            base_dir,
+            lock_file,
+            log,
+            log_id,
+            offset: 0,
+            records: 0,
+            data: BTreeMap::new(),
+        };
+        let lock = core.lock(FlockArg::LockShared)?;
+        core.refresh()?;
+        drop(lock);
+        Ok(core)
+    }
+
+    fn open_log(path: &Path) -> Result<std::fs::File, ExternalError> {
+        let log = std::fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        Ok(log)
+    }
+
+    fn file_id(metadata: &std::fs::Metadata) -> (u64, u64) {
+        (metadata.dev(), metadata.ino())
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.base_dir.join(FileConsensus::LOG_FILE)
+    }
+
+    fn lock(&self, arg: FlockArg) -> Result<Flock, ExternalError> {
+        Flock::acquire(&self.lock_file, arg)
+    }
+
+    fn head(&self, key: &str) -> Option<&VersionedData> {
+        self.data.get(key).and_then(|values| values.last())
+    }
+
+    /// Applies any records that other processes have appended to the log
+    /// since it was last read.
+    ///
+    /// Must be called while holding the lock.
+    fn refresh(&mut self) -> Result<(), ExternalError> {
+        let log_path = self.log_path();
+        let log_id = Self::file_id(&std::fs::metadata(&log_path)?);
+        if log_id != self.log_id {
+            // The log was compacted, so replay the new one from the start.
+            self.log = Self::open_log(&log_path)?;
+            self.log_id = Self::file_id(&self.log.metadata()?);
+            self.offset = 0;
+            self.records = 0;
+            self.data.clear();
+        }
+
+        let mut buf = Vec::new();
+        self.log.seek(SeekFrom::Start(self.offset))?;
+        self.log.read_to_end(&mut buf)?;
+        let mut pos = 0;
+        while let Some((record, len)) = LogRecord::decode(&buf[pos..])? {
+            self.apply(record);
+            pos += len;
+        }
+        self.offset += u64::cast_from(pos);
+        Ok(())
+    }
+
+    /// Durably appends `record` to the log, then applies it.
+    ///
+    /// Must be called while holding the lock exclusively, after a
+    /// [Self::refresh].
+    fn append(&mut self, record: LogRecord) -> Result<(), ExternalError> {
+        // Anything past the prefix that refresh was able to read is the
+        // remains of an incomplete append, which was never acknowledged.
+        if self.log.metadata()?.len() > self.offset {
+            self.log.set_len(self.offset)?;
+        }
+
+        let mut buf = Vec::new();
+        record.encode(&mut buf)?;
+        self.log.write_all(&buf)?;
+
+        fail_point!("fileconsensus_append_sync", |_| {
+            Err(ExternalError::from(anyhow!(
+                "FileConsensus::append_sync fail point reached for file {:?}",
+                self.log_path()
+            )))
+        });
+
+        self.log.sync_data()?;
+        self.offset += u64::cast_from(buf.len());
+        self.apply(record);
+        Ok(())
+    }
+
+    fn apply(&mut self, record: LogRecord) {
+        match record {
+            LogRecord::Set { key, data } => self.data.entry(key).or_default().push(data),
+            LogRecord::Truncate { key, seqno } => {
+                if let Some(values) = self.data.get_mut(&key) {
+                    values.retain(|val| val.seqno >= seqno);
+                }
+            }
+        }
+        self.records += 1;
+    }
+
+    /// Replaces the log with one that contains only the live versions, if
+    /// most of its records are no longer needed.
+    ///
+    /// Must be called while holding the lock exclusively.
+    fn maybe_compact(&mut self) -> Result<(), ExternalError> {
+        let live = self.data.values().map(|values| values.len()).sum::<usize>();
+        if self.records < Self::COMPACTION_MIN_RECORDS || self.records < live * 2 {
+            return Ok(());
+        }
+
+        let mut buf = Vec::new();
+        for (key, values) in &self.data {
+            for data in values {
+                LogRecord::Set {
+                    key: key.clone(),
+                    data: data.clone(),
+                }
+                .encode(&mut buf)?;
+            }
+        }
+
+        // To replace the log atomically, write to a temp file and rename it
+        // into place.
+        let log_path = self.log_path();
+        let mut tmp_path = log_path.clone();
+        tmp_path.set_extension("tmp");
+        let mut tmp = std::fs::File::create(&tmp_path)?;
+        tmp.write_all(&buf)?;
+        tmp.sync_all()?;
+        drop(tmp);
+        std::fs::rename(&tmp_path, &log_path)?;
+        std::fs::File::open(&self.base_dir)?.sync_all()?;
+
+        self.log = Self::open_log(&log_path)?;
+        self.log_id = Self::file_id(&self.log.metadata()?);
+        self.offset = u64::cast_from(buf.len());
+        self.records = live;
+        Ok(())
+    }
+}
+
+/// An advisory lock on a file, which is released when dropped.
+struct Flock(RawFd);
+
+impl Flock {
+    fn acquire(file: &std::fs::File, arg: FlockArg) -> Result<Self, ExternalError> {
+        let fd = file.as_raw_fd();
+        flock(fd, arg).map_err(std::io::Error::from)?;
+        Ok(Flock(fd))
+    }
+}
+
+impl Drop for Flock {
+    fn drop(&mut self) {
+        // Closing the file would release the lock anyway, so there's nothing
+        // useful to do with an error here.
+        let _ = flock(self.0, FlockArg::Unlock);
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::location::tests::blob_impl_test;
+    use futures_util::TryStreamExt;
+
+    use crate::location::tests::{blob_impl_test, consensus_impl_test};
+    use crate::location::SCAN_ALL;
 
     use super::*;
 
@@ -296,4 +794,153 @@ mod tests {
 
         Ok(())
     }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `flock` on OS `linux`
+    async fn file_consensus() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let base_dir = temp_dir.path().join("consensus");
+        consensus_impl_test(|| FileConsensus::open(base_dir.clone().into())).await
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `flock` on OS `linux`
+    async fn file_consensus_shared_log() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let base_dir = temp_dir.path().join("consensus");
+        let a = FileConsensus::open(base_dir.clone().into()).await?;
+        let b = FileConsensus::open(base_dir.clone().into()).await?;
+
+        let version = |seqno| VersionedData {
+            seqno: SeqNo(seqno),
+            data: Bytes::from(format!("v{seqno}")),
+        };
+
+        // Writes through one instance are visible through another instance
+        // of the same directory, and the instances agree on the current
+        // version.
+        assert_eq!(
+            a.compare_and_set("k", None, version(1)).await,
+            Ok(CaSResult::Committed)
+        );
+        assert_eq!(b.head("k").await, Ok(Some(version(1))));
+        assert_eq!(
+            a.compare_and_set("k", None, version(2)).await,
+            Ok(CaSResult::ExpectationMismatch)
+        );
+        assert_eq!(
+            b.compare_and_set("k", Some(SeqNo(1)), version(2)).await,
+            Ok(CaSResult::Committed)
+        );
+        assert_eq!(a.head("k").await, Ok(Some(version(2))));
+
+        // Enough truncated versions cause the log to be compacted, which the
+        // other instance notices.
+        let last = 2 * u64::cast_from(FileConsensusCore::COMPACTION_MIN_RECORDS);
+        for seqno in 3..=last {
+            assert_eq!(
+                a.compare_and_set("k", Some(SeqNo(seqno - 1)), version(seqno))
+                    .await,
+                Ok(CaSResult::Committed)
+            );
+        }
+        let log_len = || std::fs::metadata(base_dir.join(FileConsensus::LOG_FILE));
+        let before = log_len().map_err(Error::from)?.len();
+        assert_eq!(
+            a.truncate("k", SeqNo(last - 1)).await,
+            Ok(usize::cast_from(last - 2))
+        );
+        assert!(log_len().map_err(Error::from)?.len() < before);
+        assert_eq!(
+            b.scan("k", SeqNo(0), SCAN_ALL).await,
+            Ok(vec![version(last - 1), version(last)])
+        );
+        assert_eq!(
+            b.compare_and_set("k", Some(SeqNo(last)), version(last + 1))
+                .await,
+            Ok(CaSResult::Committed)
+        );
+        assert_eq!(a.head("k").await, Ok(Some(version(last + 1))));
+
+        // An incomplete append at the end of the log is ignored when it is
+        // replayed, and then overwritten.
+        let mut log = std::fs::OpenOptions::new()
+            .append(true)
+            .open(base_dir.join(FileConsensus::LOG_FILE))
+            .map_err(Error::from)?;
+        log.write_all(&[0xff; 6]).map_err(Error::from)?;
+        drop(log);
+        let c = FileConsensus::open(base_dir.clone().into()).await?;
+        assert_eq!(c.head("k").await, Ok(Some(version(last + 1))));
+        assert_eq!(
+            c.compare_and_set("other", None, version(1)).await,
+            Ok(CaSResult::Committed)
+        );
+
+        // Everything is still there after reopening the directory.
+        drop((a, b, c));
+        let d = FileConsensus::open(base_dir.into()).await?;
+        let keys: Vec<_> = d.list_keys().try_collect().await?;
+        assert_eq!(keys, vec!["k".to_owned(), "other".to_owned()]);
+        assert_eq!(
+            d.scan("k", SeqNo(0), SCAN_ALL).await,
+            Ok(vec![version(last - 1), version(last), version(last + 1)])
+        );
+        assert_eq!(d.head("other").await, Ok(Some(version(1))));
+
+        Ok(())
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `flock` on OS `linux`
+    async fn file_consensus_corrupt_log() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let base_dir = temp_dir.path().join("consensus");
+        let log_path = base_dir.join(FileConsensus::LOG_FILE);
+        let version = |seqno| VersionedData {
+            seqno: SeqNo(seqno),
+            data: Bytes::from(format!("v{seqno}")),
+        };
+
+        let consensus = FileConsensus::open(base_dir.clone().into()).await?;
+        for seqno in 1..=3 {
+            let expected = (seqno > 1).then(|| SeqNo(seqno - 1));
+            assert_eq!(
+                consensus
+                    .compare_and_set("k", expected, version(seqno))
+                    .await,
+                Ok(CaSResult::Committed)
+            );
+        }
+        drop(consensus);
+
+        // A complete record with a bad checksum at the end of the log is the
+        // remains of an incomplete append.
+        let mut torn = Vec::new();
+        LogRecord::Set {
+            key: "k".into(),
+            data: version(4),
+        }
+        .encode(&mut torn)?;
+        *torn.last_mut().expect("non-empty record") ^= 0xff;
+        let mut log = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&log_path)
+            .map_err(Error::from)?;
+        log.write_all(&torn).map_err(Error::from)?;
+        drop(log);
+        let consensus = FileConsensus::open(base_dir.clone().into()).await?;
+        assert_eq!(consensus.head("k").await, Ok(Some(version(3))));
+        drop(consensus);
+
+        // A damaged record in the middle of the log is an error, and the
+        // records after it are left alone.
+        let mut contents = std::fs::read(&log_path).map_err(Error::from)?;
+        contents[LogRecord::HEADER_LEN + 1] ^= 0xff;
+        std::fs::write(&log_path, &contents).map_err(Error::from)?;
+        assert!(FileConsensus::open(base_dir.clone().into()).await.is_err());
+        assert_eq!(std::fs::read(&log_path).map_err(Error::from)?, contents);
+
+        Ok(())
+    }
 }
@@ -439,6 +439,9 @@ pub enum CopyFormatParams<'a> {
     Csv(CopyCsvFormatParams<'a>),
     Binary,
     Parquet,
+    Avro,
+    Orc,
+    Json(CopyJsonFormatParams),
 }
 
 impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
@@ -450,6 +453,9 @@ impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
                 Self::Csv(f) => Kind::Csv(f.into_proto()),
                 Self::Binary => Kind::Binary(()),
                 Self::Parquet => Kind::Parquet(ProtoCopyParquetFormatParams::default()),
+                Self::Avro => Kind::Avro(ProtoCopyAvroFormatParams::default()),
+                Self::Orc => Kind::Orc(ProtoCopyOrcFormatParams::default()),
+                Self::Json(f) => Kind::Json(f.into_proto()),
             }),
         }
     }
@@ -461,6 +467,9 @@ impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
             Some(Kind::Csv(f)) => Ok(Self::Csv(f.into_rust()?)),
             Some(Kind::Binary(())) => Ok(Self::Binary),
             Some(Kind::Parquet(ProtoCopyParquetFormatParams {})) => Ok(Self::Parquet),
+            Some(Kind::Avro(ProtoCopyAvroFormatParams {})) => Ok(Self::Avro),
+            Some(Kind::Orc(ProtoCopyOrcFormatParams {})) => Ok(Self::Orc),
+            Some(Kind::Json(f)) => Ok(Self::Json(f.into_rust()?)),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoCopyFormatParams::kind",
             )),
@@ -488,6 +497,9 @@ impl CopyFormatParams<'static> {
             &CopyFormatParams::Csv(_) => "csv",
             &CopyFormatParams::Binary => "bin",
             &CopyFormatParams::Parquet => "parquet",
+            &CopyFormatParams::Avro => "avro",
+            &CopyFormatParams::Orc => "orc",
+            &CopyFormatParams::Json(_) => "json",
         }
     }
 
@@ -497,6 +509,9 @@ impl CopyFormatParams<'static> {
             CopyFormatParams::Csv(params) => params.header,
             CopyFormatParams::Binary => false,
             CopyFormatParams::Parquet => false,
+            CopyFormatParams::Avro => false,
+            CopyFormatParams::Orc => false,
+            CopyFormatParams::Json(_) => false,
         }
     }
 }
@@ -518,6 +533,9 @@ pub fn decode_copy_format<'a>(
             // TODO(cf2): Support Parquet over STDIN.
             Err(io::Error::new(io::ErrorKind::Unsupported, "parquet format"))
         }
+        CopyFormatParams::Avro => Err(io::Error::new(io::ErrorKind::Unsupported, "avro format")),
+        CopyFormatParams::Orc => Err(io::Error::new(io::ErrorKind::Unsupported, "orc format")),
+        CopyFormatParams::Json(params) => decode_copy_format_json(data, column_types, params),
     }
 }
 
@@ -536,6 +554,9 @@ pub fn encode_copy_format<'a>(
             // TODO(cf2): Support Parquet over STDIN.
             Err(io::Error::new(io::ErrorKind::Unsupported, "parquet format"))
         }
+        CopyFormatParams::Avro => Err(io::Error::new(io::ErrorKind::Unsupported, "avro format")),
+        CopyFormatParams::Orc => Err(io::Error::new(io::ErrorKind::Unsupported, "orc format")),
+        CopyFormatParams::Json(_) => Err(io::Error::new(io::ErrorKind::Unsupported, "json format")),
     }
 }
 
@@ -565,6 +586,9 @@ pub fn encode_copy_format_header<'a>(
             // TODO(cf2): Support Parquet over STDIN.
             Err(io::Error::new(io::ErrorKind::Unsupported, "parquet format"))
         }
+        CopyFormatParams::Avro => Err(io::Error::new(io::ErrorKind::Unsupported, "avro format")),
+        CopyFormatParams::Orc => Err(io::Error::new(io::ErrorKind::Unsupported, "orc format")),
+        CopyFormatParams::Json(_) => Err(io::Error::new(io::ErrorKind::Unsupported, "json format")),
     }
 }
 
@@ -853,6 +877,155 @@ pub fn decode_copy_format_csv(
     Ok(rows)
 }
 
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CopyJsonFormatParams {
+    /// Whether the data is a single top-level JSON array, rather than
+    /// newline-delimited JSON values.
+    pub array: bool,
+    /// Dot-separated paths, one per column, whose values are projected into
+    /// the columns. If `None`, each JSON value is decoded into a single column.
+    pub paths: Option<Vec<String>>,
+}
+
+impl RustType<ProtoCopyJsonFormatParams> for CopyJsonFormatParams {
+    fn into_proto(&self) -> ProtoCopyJsonFormatParams {
+        ProtoCopyJsonFormatParams {
+            array: self.array,
+            paths: self
+                .paths
+                .as_ref()
+                .map(|paths| proto_copy_json_format_params::ProtoPaths {
+                    paths: paths.clone(),
+                }),
+        }
+    }
+
+    fn from_proto(proto: ProtoCopyJsonFormatParams) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            array: proto.array,
+            paths: proto.paths.map(|p| p.paths),
+        })
+    }
+}
+
+impl CopyJsonFormatParams {
+    pub fn try_new(
+        array: bool,
+        paths: Option<Vec<String>>,
+        num_columns: usize,
+    ) -> Result<CopyJsonFormatParams, String> {
+        match &paths {
+            None if num_columns != 1 => Err(format!(
+                "COPY FORMAT JSON without JSON PATHS requires exactly one column, found {}",
+                num_columns
+            )),
+            Some(paths) if paths.len() != num_columns => Err(format!(
+                "COPY JSON PATHS must specify one path per column, found {} paths for {} columns",
+                paths.len(),
+                num_columns
+            )),
+            _ => Ok(CopyJsonFormatParams { array, paths }),
+        }
+    }
+}
+
+pub fn decode_copy_format_json(
+    data: &[u8],
+    column_types: &[mz_pgrepr::Type],
+    params: CopyJsonFormatParams,
+) -> Result<Vec<Row>, io::Error> {
+    let values = if params.array {
+        match serde_json::from_slice(data) {
+            Ok(serde_json::Value::Array(values)) => values,
+            Ok(_) => {
+                let msg = "expected a JSON array";
+                return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+            }
+            Err(err) => {
+                let msg = format!("invalid JSON: {}", err);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+            }
+        }
+    } else {
+        let mut values = Vec::new();
+        for line in data.split(|b| *b == b'\n') {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line == END_OF_COPY_MARKER {
+                break;
+            }
+            if line.iter().all(|b| b.is_ascii_whitespace()) {
+                continue;
+            }
+            match serde_json::from_slice(line) {
+                Ok(value) => values.push(value),
+                Err(err) => {
+                    let msg = format!("invalid JSON: {}", err);
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+                }
+            }
+        }
+        values
+    };
+
+    let mut rows = Vec::with_capacity(values.len());
+    let mut row = Row::default();
+    for value in &values {
+        pack_copy_json_value(value, column_types, params.paths.as_deref(), &mut row)?;
+        rows.push(row.clone());
+    }
+    Ok(rows)
+}
+
+/// Decodes a single JSON value into `row` according to `paths`.
+///
+/// `jsonb` columns receive the JSON value as-is, JSON strings are decoded as
+/// the text representation of the column's type, and all other values are
+/// decoded from their JSON text. Missing values and JSON `null`s become `NULL`.
+pub fn pack_copy_json_value(
+    value: &serde_json::Value,
+    column_types: &[mz_pgrepr::Type],
+    paths: Option<&[String]>,
+    row: &mut Row,
+) -> Result<(), io::Error> {
+    let mut packer = row.packer();
+    for (idx, typ) in column_types.iter().enumerate() {
+        let field = match paths {
+            Some(paths) => lookup_json_path(value, &paths[idx]),
+            None => Some(value),
+        };
+        let text = match (field, typ) {
+            (None | Some(serde_json::Value::Null), _) => {
+                packer.push(Datum::Null);
+                continue;
+            }
+            (Some(field), mz_pgrepr::Type::Jsonb) => Cow::Owned(field.to_string()),
+            (Some(serde_json::Value::String(s)), _) => Cow::Borrowed(s.as_str()),
+            (Some(field), _) => Cow::Owned(field.to_string()),
+        };
+        if let Err(err) = mz_pgrepr::Value::decode_text_into_row(typ, &text, &mut packer) {
+            let msg = format!("unable to decode column: {}", err);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        }
+    }
+    Ok(())
+}
+
+/// Resolves a dot-separated path, where numeric segments index into arrays.
+/// The empty path refers to `value` itself.
+fn lookup_json_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    path.split('.')
+        .try_fold(value, |value, segment| match value {
+            serde_json::Value::Object(map) => map.get(segment),
+            serde_json::Value::Array(elements) => {
+                segment.parse::<usize>().ok().and_then(|i| elements.get(i))
+            }
+            _ => None,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use mz_ore::collections::CollectionExt;
@@ -1132,6 +1305,54 @@ mod tests {
         Ok(())
     }
 
+    #[mz_ore::test]
+    fn test_copy_json_format() {
+        let column_types = [
+            mz_pgrepr::Type::Int4,
+            mz_pgrepr::Type::Text,
+            mz_pgrepr::Type::Jsonb,
+        ];
+        let paths = Some(vec![
+            "id".to_string(),
+            "tags.1".to_string(),
+            "meta".to_string(),
+        ]);
+        let expected = vec![
+            Row::pack([Datum::Int32(1), Datum::from("b"), Datum::Null]),
+            Row::pack([Datum::Int32(2), Datum::Null, Datum::Null]),
+        ];
+
+        let lines = br#"{"id": 1, "tags": ["a", "b"]}
+
+{"id": "2", "meta": null}
+\.
+"#;
+        let params = CopyJsonFormatParams::try_new(false, paths.clone(), 3).unwrap();
+        let rows = decode_copy_format_json(lines, &column_types, params).unwrap();
+        assert_eq!(rows, expected);
+
+        let array = br#"[{"id": 1, "tags": ["a", "b"]}, {"id": "2", "meta": null}]"#;
+        let params = CopyJsonFormatParams::try_new(true, paths, 3).unwrap();
+        let rows = decode_copy_format_json(array, &column_types, params).unwrap();
+        assert_eq!(rows, expected);
+
+        let params = CopyJsonFormatParams::try_new(true, None, 1).unwrap();
+        let err = decode_copy_format_json(b"{}", &column_types[..1], params).unwrap_err();
+        assert_eq!(err.to_string(), "expected a JSON array");
+
+        assert_eq!(
+            CopyJsonFormatParams::try_new(false, None, 2),
+            Err("COPY FORMAT JSON without JSON PATHS requires exactly one column, found 2".into())
+        );
+        assert_eq!(
+            CopyJsonFormatParams::try_new(false, Some(vec!["a".into()]), 2),
+            Err(
+                "COPY JSON PATHS must specify one path per column, found 1 paths for 2 columns"
+                    .into()
+            )
+        );
+    }
+
     proptest! {
         #[mz_ore::test]
         #[cfg_attr(miri, ignore)]
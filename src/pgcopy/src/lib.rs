@@ -16,7 +16,8 @@
 mod copy;
 
 pub use copy::{
-    decode_copy_format, encode_copy_format, encode_copy_format_header, CopyCsvFormatParams,
-    CopyFormatParams, CopyTextFormatParams, CopyTextFormatParser, ProtoCopyCsvFormatParams,
-    ProtoCopyFormatParams, ProtoCopyTextFormatParams,
+    decode_copy_format, encode_copy_format, encode_copy_format_header, pack_copy_json_value,
+    CopyCsvFormatParams, CopyFormatParams, CopyJsonFormatParams, CopyTextFormatParams,
+    CopyTextFormatParser, ProtoCopyCsvFormatParams, ProtoCopyFormatParams,
+    ProtoCopyJsonFormatParams, ProtoCopyTextFormatParams,
 };
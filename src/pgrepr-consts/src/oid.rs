@@ -22,6 +22,7 @@ pub const TYPE_ANYCOMPATIBLE_OID: u32 = 5077;
 pub const TYPE_ANYCOMPATIBLEARRAY_OID: u32 = 5078;
 pub const TYPE_ANYCOMPATIBLENONARRAY_OID: u32 = 5079;
 pub const TYPE_ANYELEMENT_OID: u32 = 2283;
+pub const TYPE_ANYENUM_OID: u32 = 3500;
 pub const TYPE_ANYNONARRAY_OID: u32 = 2776;
 pub const TYPE_BOOL_ARRAY_OID: u32 = 1000;
 pub const TYPE_BOOL_OID: u32 = 16;
@@ -773,3 +774,58 @@ pub const INDEX_MZ_CLUSTER_DEPLOYMENT_LINEAGE_IND_OID: u32 = 17050;
 pub const INDEX_MZ_CLUSTER_REPLICA_FRONTIERS_IND_OID: u32 = 17051;
 pub const INDEX_MZ_COMPUTE_HYDRATION_TIMES_IND_OID: u32 = 17052;
 pub const VIEW_MZ_MAPPABLE_OBJECTS_OID: u32 = 17053;
+pub const FUNC_APPROX_COUNT_DISTINCT_OID: u32 = 17054;
+pub const FUNC_APPROX_PERCENTILE_OID: u32 = 17055;
+pub const FUNC_JSONB_PATH_EXISTS_DEFAULT_VARS_OID: u32 = 17056;
+pub const FUNC_JSONB_PATH_EXISTS_DEFAULT_SILENT_OID: u32 = 17057;
+pub const FUNC_JSONB_PATH_MATCH_DEFAULT_VARS_OID: u32 = 17058;
+pub const FUNC_JSONB_PATH_MATCH_DEFAULT_SILENT_OID: u32 = 17059;
+pub const FUNC_JSONB_PATH_QUERY_DEFAULT_VARS_OID: u32 = 17060;
+pub const FUNC_JSONB_PATH_QUERY_DEFAULT_SILENT_OID: u32 = 17061;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_DEFAULT_VARS_OID: u32 = 17062;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_DEFAULT_SILENT_OID: u32 = 17063;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_DEFAULT_VARS_OID: u32 = 17064;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_DEFAULT_SILENT_OID: u32 = 17065;
+pub const TABLE_MZ_ENUM_LABELS_OID: u32 = 17066;
+pub const TYPE_GEOMETRY_OID: u32 = 17067;
+pub const TYPE_GEOMETRY_ARRAY_OID: u32 = 17068;
+pub const TYPE_GEOGRAPHY_OID: u32 = 17069;
+pub const TYPE_GEOGRAPHY_ARRAY_OID: u32 = 17070;
+pub const FUNC_ST_MAKEPOINT_OID: u32 = 17071;
+pub const FUNC_ST_POINT_OID: u32 = 17072;
+pub const FUNC_ST_SETSRID_OID: u32 = 17073;
+pub const FUNC_ST_SRID_GEOMETRY_OID: u32 = 17074;
+pub const FUNC_ST_SRID_GEOGRAPHY_OID: u32 = 17075;
+pub const FUNC_ST_X_OID: u32 = 17076;
+pub const FUNC_ST_Y_OID: u32 = 17077;
+pub const FUNC_ST_GEOMETRYTYPE_OID: u32 = 17078;
+pub const FUNC_ST_GEOMFROMTEXT_OID: u32 = 17079;
+pub const FUNC_ST_GEOMFROMTEXT_SRID_OID: u32 = 17080;
+pub const FUNC_ST_GEOGFROMTEXT_OID: u32 = 17081;
+pub const FUNC_ST_GEOMFROMWKB_OID: u32 = 17082;
+pub const FUNC_ST_GEOMFROMWKB_SRID_OID: u32 = 17083;
+pub const FUNC_ST_GEOMFROMGEOJSON_OID: u32 = 17084;
+pub const FUNC_ST_ASBINARY_GEOMETRY_OID: u32 = 17085;
+pub const FUNC_ST_ASBINARY_GEOGRAPHY_OID: u32 = 17086;
+pub const FUNC_ST_ASTEXT_GEOMETRY_OID: u32 = 17087;
+pub const FUNC_ST_ASTEXT_GEOGRAPHY_OID: u32 = 17088;
+pub const FUNC_ST_ASEWKT_GEOMETRY_OID: u32 = 17089;
+pub const FUNC_ST_ASEWKT_GEOGRAPHY_OID: u32 = 17090;
+pub const FUNC_ST_ASGEOJSON_GEOMETRY_OID: u32 = 17091;
+pub const FUNC_ST_ASGEOJSON_GEOGRAPHY_OID: u32 = 17092;
+pub const FUNC_ST_DISTANCE_GEOMETRY_OID: u32 = 17093;
+pub const FUNC_ST_DISTANCE_GEOGRAPHY_OID: u32 = 17094;
+pub const FUNC_ST_DISTANCESPHERE_OID: u32 = 17095;
+pub const FUNC_ST_DWITHIN_GEOMETRY_OID: u32 = 17096;
+pub const FUNC_ST_DWITHIN_GEOGRAPHY_OID: u32 = 17097;
+pub const FUNC_ST_INTERSECTS_GEOMETRY_OID: u32 = 17098;
+pub const FUNC_ST_INTERSECTS_GEOGRAPHY_OID: u32 = 17099;
+pub const FUNC_ST_CONTAINS_OID: u32 = 17100;
+pub const FUNC_ST_WITHIN_OID: u32 = 17101;
+pub const FUNC_ST_GEOHASH_OID: u32 = 17102;
+pub const FUNC_ST_GEOHASH_PRECISION_OID: u32 = 17103;
+pub const FUNC_GEOHASH_NEIGHBORS_OID: u32 = 17104;
+pub const FUNC_EQ_GEOMETRY_OID: u32 = 17105;
+pub const FUNC_NOT_EQ_GEOMETRY_OID: u32 = 17106;
+pub const FUNC_EQ_GEOGRAPHY_OID: u32 = 17107;
+pub const FUNC_NOT_EQ_GEOGRAPHY_OID: u32 = 17108;
@@ -27,9 +27,13 @@ pub mod oid;
 pub use types::{
     Type, TypeConversionError, TypeFromOidError, ANYCOMPATIBLELIST, ANYCOMPATIBLEMAP, LIST, MAP,
 };
+pub use value::geo::Geometry;
 pub use value::interval::Interval;
 pub use value::jsonb::Jsonb;
+pub use value::jsonpath::JsonPath;
+pub use value::network::{Inet, MacAddr, MacAddr8};
 pub use value::numeric::Numeric;
 pub use value::record::Record;
+pub use value::text_search::{TsQuery, TsVector};
 pub use value::unsigned::{UInt2, UInt4, UInt8};
 pub use value::{values_from_row, Value};
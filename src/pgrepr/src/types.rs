@@ -22,7 +22,7 @@ use mz_repr::adt::timestamp::{
 };
 use mz_repr::adt::varchar::{InvalidVarCharMaxLengthError, VarCharMaxLength};
 use mz_repr::namespaces::MZ_CATALOG_SCHEMA;
-use mz_repr::ScalarType;
+use mz_repr::{CatalogItemId, ScalarType};
 
 use crate::oid;
 
@@ -105,7 +105,12 @@ pub enum Type {
     /// An object identifier.
     Oid,
     /// A sequence of heterogeneous values.
-    Record(Vec<Type>),
+    Record {
+        /// The OID of the composite type, or `None` for an anonymous record.
+        oid: Option<u32>,
+        /// The types of the fields of the record.
+        fields: Vec<Type>,
+    },
     /// A variable-length string.
     Text,
     /// A (usually) fixed-length string.
@@ -163,6 +168,34 @@ pub enum Type {
     /// A list of privileges granted to a user that uses [`mz_repr::adt::system::Oid`]s for role
     /// references. This type is used primarily for compatibility with PostgreSQL.
     AclItem,
+    /// An IPv4 or IPv6 host address, with an optional subnet.
+    Inet,
+    /// An IPv4 or IPv6 network.
+    Cidr,
+    /// A MAC address in EUI-48 format.
+    MacAddr,
+    /// A MAC address in EUI-64 format.
+    MacAddr8,
+    /// A document preprocessed for text search.
+    TsVector,
+    /// A text search query.
+    TsQuery,
+    /// A SQL/JSON path expression.
+    JsonPath,
+    /// A user-defined enum type.
+    Enum {
+        /// The OID of the type.
+        oid: u32,
+        /// The ID of the catalog item that defines the type.
+        id: CatalogItemId,
+        /// The labels of the type, in declaration order.
+        labels: Vec<String>,
+    },
+    /// A planar geometry with a spatial reference system.
+    Geometry,
+    /// A geometry whose coordinates are longitudes and latitudes on the WGS 84
+    /// spheroid.
+    Geography,
 }
 
 /// An unpacked [`typmod`](Type::typmod) for a [`Type`].
@@ -528,6 +561,46 @@ pub static MZ_ACL_ITEM_ARRAY: LazyLock<postgres_types::Type> = LazyLock::new(||
     )
 });
 
+/// An anonymous [`Type::Geometry`], akin to [`postgres_types::Type::TEXT`].
+pub static GEOMETRY: LazyLock<postgres_types::Type> = LazyLock::new(|| {
+    postgres_types::Type::new(
+        "geometry".to_owned(),
+        oid::TYPE_GEOMETRY_OID,
+        postgres_types::Kind::Pseudo,
+        MZ_CATALOG_SCHEMA.to_owned(),
+    )
+});
+
+/// An anonymous [`Type::Array`], akin to [`postgres_types::Type::TEXT_ARRAY`].
+pub static GEOMETRY_ARRAY: LazyLock<postgres_types::Type> = LazyLock::new(|| {
+    postgres_types::Type::new(
+        "_geometry".to_owned(),
+        oid::TYPE_GEOMETRY_ARRAY_OID,
+        postgres_types::Kind::Pseudo,
+        MZ_CATALOG_SCHEMA.to_owned(),
+    )
+});
+
+/// An anonymous [`Type::Geography`], akin to [`postgres_types::Type::TEXT`].
+pub static GEOGRAPHY: LazyLock<postgres_types::Type> = LazyLock::new(|| {
+    postgres_types::Type::new(
+        "geography".to_owned(),
+        oid::TYPE_GEOGRAPHY_OID,
+        postgres_types::Kind::Pseudo,
+        MZ_CATALOG_SCHEMA.to_owned(),
+    )
+});
+
+/// An anonymous [`Type::Array`], akin to [`postgres_types::Type::TEXT_ARRAY`].
+pub static GEOGRAPHY_ARRAY: LazyLock<postgres_types::Type> = LazyLock::new(|| {
+    postgres_types::Type::new(
+        "_geography".to_owned(),
+        oid::TYPE_GEOGRAPHY_ARRAY_OID,
+        postgres_types::Kind::Pseudo,
+        MZ_CATALOG_SCHEMA.to_owned(),
+    )
+});
+
 impl Type {
     /// Returns the type corresponding to the provided OID, if the OID is known.
     pub fn from_oid(oid: u32) -> Result<Type, TypeFromOidError> {
@@ -569,6 +642,13 @@ impl Type {
             postgres_types::Type::TIMESTAMP => Type::Timestamp { precision: None },
             postgres_types::Type::TIMESTAMPTZ => Type::TimestampTz { precision: None },
             postgres_types::Type::UUID => Type::Uuid,
+            postgres_types::Type::INET => Type::Inet,
+            postgres_types::Type::CIDR => Type::Cidr,
+            postgres_types::Type::MACADDR => Type::MacAddr,
+            postgres_types::Type::MACADDR8 => Type::MacAddr8,
+            postgres_types::Type::TS_VECTOR => Type::TsVector,
+            postgres_types::Type::TSQUERY => Type::TsQuery,
+            postgres_types::Type::JSONPATH => Type::JsonPath,
             postgres_types::Type::REGCLASS => Type::RegClass,
             postgres_types::Type::REGPROC => Type::RegProc,
             postgres_types::Type::REGTYPE => Type::RegType,
@@ -606,6 +686,13 @@ impl Type {
                 Type::Array(Box::new(Type::TimestampTz { precision: None }))
             }
             postgres_types::Type::UUID_ARRAY => Type::Array(Box::new(Type::Uuid)),
+            postgres_types::Type::INET_ARRAY => Type::Array(Box::new(Type::Inet)),
+            postgres_types::Type::CIDR_ARRAY => Type::Array(Box::new(Type::Cidr)),
+            postgres_types::Type::MACADDR_ARRAY => Type::Array(Box::new(Type::MacAddr)),
+            postgres_types::Type::MACADDR8_ARRAY => Type::Array(Box::new(Type::MacAddr8)),
+            postgres_types::Type::TS_VECTOR_ARRAY => Type::Array(Box::new(Type::TsVector)),
+            postgres_types::Type::TSQUERY_ARRAY => Type::Array(Box::new(Type::TsQuery)),
+            postgres_types::Type::JSONPATH_ARRAY => Type::Array(Box::new(Type::JsonPath)),
             postgres_types::Type::VARCHAR_ARRAY => {
                 Type::Array(Box::new(Type::VarChar { max_length: None }))
             }
@@ -722,7 +809,7 @@ impl Type {
                 Type::Name { .. } => &postgres_types::Type::NAME_ARRAY,
                 Type::Numeric { .. } => &postgres_types::Type::NUMERIC_ARRAY,
                 Type::Oid => &postgres_types::Type::OID_ARRAY,
-                Type::Record(_) => &postgres_types::Type::RECORD_ARRAY,
+                Type::Record { .. } => &postgres_types::Type::RECORD_ARRAY,
                 Type::Text => &postgres_types::Type::TEXT_ARRAY,
                 Type::BpChar { .. } => &postgres_types::Type::BPCHAR_ARRAY,
                 Type::VarChar { .. } => &postgres_types::Type::VARCHAR_ARRAY,
@@ -731,6 +818,16 @@ impl Type {
                 Type::Timestamp { .. } => &postgres_types::Type::TIMESTAMP_ARRAY,
                 Type::TimestampTz { .. } => &postgres_types::Type::TIMESTAMPTZ_ARRAY,
                 Type::Uuid => &postgres_types::Type::UUID_ARRAY,
+                Type::Inet => &postgres_types::Type::INET_ARRAY,
+                Type::Cidr => &postgres_types::Type::CIDR_ARRAY,
+                Type::MacAddr => &postgres_types::Type::MACADDR_ARRAY,
+                Type::MacAddr8 => &postgres_types::Type::MACADDR8_ARRAY,
+                Type::TsVector => &postgres_types::Type::TS_VECTOR_ARRAY,
+                Type::TsQuery => &postgres_types::Type::TSQUERY_ARRAY,
+                Type::JsonPath => &postgres_types::Type::JSONPATH_ARRAY,
+                // Enum types do not have a corresponding array type in the
+                // catalog, so their arrays are described as `anyarray`.
+                Type::Enum { .. } => &postgres_types::Type::ANYARRAY,
                 Type::RegClass => &postgres_types::Type::REGCLASS_ARRAY,
                 Type::RegProc => &postgres_types::Type::REGPROC_ARRAY,
                 Type::RegType => &postgres_types::Type::REGTYPE_ARRAY,
@@ -746,6 +843,8 @@ impl Type {
                     _ => unreachable!(),
                 },
                 Type::MzAclItem => &MZ_ACL_ITEM_ARRAY,
+                Type::Geometry => &GEOMETRY_ARRAY,
+                Type::Geography => &GEOGRAPHY_ARRAY,
             },
             Type::Bool => &postgres_types::Type::BOOL,
             Type::Bytea => &postgres_types::Type::BYTEA,
@@ -767,7 +866,7 @@ impl Type {
             Type::Name => &postgres_types::Type::NAME,
             Type::Numeric { .. } => &postgres_types::Type::NUMERIC,
             Type::Oid => &postgres_types::Type::OID,
-            Type::Record(_) => &postgres_types::Type::RECORD,
+            Type::Record { .. } => &postgres_types::Type::RECORD,
             Type::Text => &postgres_types::Type::TEXT,
             Type::BpChar { .. } => &postgres_types::Type::BPCHAR,
             Type::VarChar { .. } => &postgres_types::Type::VARCHAR,
@@ -776,6 +875,16 @@ impl Type {
             Type::Timestamp { .. } => &postgres_types::Type::TIMESTAMP,
             Type::TimestampTz { .. } => &postgres_types::Type::TIMESTAMPTZ,
             Type::Uuid => &postgres_types::Type::UUID,
+            Type::Inet => &postgres_types::Type::INET,
+            Type::Cidr => &postgres_types::Type::CIDR,
+            Type::MacAddr => &postgres_types::Type::MACADDR,
+            Type::MacAddr8 => &postgres_types::Type::MACADDR8,
+            Type::TsVector => &postgres_types::Type::TS_VECTOR,
+            Type::TsQuery => &postgres_types::Type::TSQUERY,
+            Type::JsonPath => &postgres_types::Type::JSONPATH,
+            // Enum types are created at runtime and so have no static
+            // description; `oid` returns the OID of the specific type.
+            Type::Enum { .. } => &postgres_types::Type::ANYENUM,
             Type::RegClass => &postgres_types::Type::REGCLASS,
             Type::RegProc => &postgres_types::Type::REGPROC,
             Type::RegType => &postgres_types::Type::REGTYPE,
@@ -791,6 +900,8 @@ impl Type {
                 t => unreachable!("{t:?} is not a range element type"),
             },
             Type::MzAclItem => &MZ_ACL_ITEM,
+            Type::Geometry => &GEOMETRY,
+            Type::Geography => &GEOGRAPHY,
         }
     }
 
@@ -846,6 +957,8 @@ impl Type {
                 oid::TYPE_UINT8_ARRAY_OID => "uint8[]",
                 oid::TYPE_MZ_TIMESTAMP_ARRAY_OID => "mz_timestamp[]",
                 oid::TYPE_MZ_ACL_ITEM_ARRAY_OID => "mz_aclitem[]",
+                oid::TYPE_GEOMETRY_ARRAY_OID => "geometry[]",
+                oid::TYPE_GEOGRAPHY_ARRAY_OID => "geography[]",
                 _ => other.name(),
             },
         }
@@ -855,7 +968,11 @@ impl Type {
     ///
     /// [OID]: https://www.postgresql.org/docs/current/datatype-oid.html
     pub fn oid(&self) -> u32 {
-        self.inner().oid()
+        match self {
+            Type::Enum { oid, .. } => *oid,
+            Type::Record { oid: Some(oid), .. } => *oid,
+            _ => self.inner().oid(),
+        }
     }
 
     /// Returns the constraint on the type, if any.
@@ -909,7 +1026,7 @@ impl Type {
             | Type::Numeric { constraints: None }
             | Type::Int2Vector
             | Type::Oid
-            | Type::Record(_)
+            | Type::Record { .. }
             | Type::RegClass
             | Type::RegProc
             | Type::RegType
@@ -919,10 +1036,20 @@ impl Type {
             | Type::Timestamp { precision: None }
             | Type::TimestampTz { precision: None }
             | Type::Uuid
+            | Type::Inet
+            | Type::Cidr
+            | Type::MacAddr
+            | Type::MacAddr8
+            | Type::TsVector
+            | Type::TsQuery
+            | Type::JsonPath
+            | Type::Enum { .. }
             | Type::MzTimestamp
             | Type::VarChar { max_length: None }
             | Type::Range { .. }
-            | Type::MzAclItem => None,
+            | Type::MzAclItem
+            | Type::Geometry
+            | Type::Geography => None,
         }
     }
 
@@ -951,7 +1078,7 @@ impl Type {
             Type::Name { .. } => 64,
             Type::Numeric { .. } => -1,
             Type::Oid => 4,
-            Type::Record(_) => -1,
+            Type::Record { .. } => -1,
             Type::Text => -1,
             Type::BpChar { .. } => -1,
             Type::VarChar { .. } => -1,
@@ -960,6 +1087,14 @@ impl Type {
             Type::Timestamp { .. } => 8,
             Type::TimestampTz { .. } => 8,
             Type::Uuid => 16,
+            Type::Inet => -1,
+            Type::Cidr => -1,
+            Type::MacAddr => 6,
+            Type::MacAddr8 => 8,
+            Type::TsVector => -1,
+            Type::TsQuery => -1,
+            Type::JsonPath => -1,
+            Type::Enum { .. } => 4,
             Type::RegClass => 4,
             Type::RegProc => 4,
             Type::RegType => 4,
@@ -972,6 +1107,8 @@ impl Type {
             Type::Range { .. } => -1,
             Type::MzAclItem => MzAclItem::binary_size().try_into().expect("must fit"),
             Type::AclItem => AclItem::binary_size().try_into().expect("must fit"),
+            Type::Geometry => -1,
+            Type::Geography => -1,
         }
     }
 
@@ -1054,9 +1191,10 @@ impl TryFrom<&Type> for ScalarType {
                 Ok(ScalarType::Numeric { max_scale })
             }
             Type::Oid => Ok(ScalarType::Oid),
-            Type::Record(_) => Ok(ScalarType::Record {
+            Type::Record { .. } => Ok(ScalarType::Record {
                 fields: [].into(),
                 custom_id: None,
+                custom_oid: None,
             }),
             Type::Text => Ok(ScalarType::String),
             Type::Time { precision: None } => Ok(ScalarType::Time),
@@ -1095,6 +1233,18 @@ impl TryFrom<&Type> for ScalarType {
                 },
             }),
             Type::Uuid => Ok(ScalarType::Uuid),
+            Type::Inet => Ok(ScalarType::Inet),
+            Type::Cidr => Ok(ScalarType::Cidr),
+            Type::MacAddr => Ok(ScalarType::MacAddr),
+            Type::MacAddr8 => Ok(ScalarType::MacAddr8),
+            Type::TsVector => Ok(ScalarType::TsVector),
+            Type::TsQuery => Ok(ScalarType::TsQuery),
+            Type::JsonPath => Ok(ScalarType::JsonPath),
+            Type::Enum { oid, id, labels } => Ok(ScalarType::Enum {
+                labels: labels.clone().into(),
+                custom_id: *id,
+                custom_oid: *oid,
+            }),
             Type::RegClass => Ok(ScalarType::RegClass),
             Type::RegProc => Ok(ScalarType::RegProc),
             Type::RegType => Ok(ScalarType::RegType),
@@ -1104,6 +1254,8 @@ impl TryFrom<&Type> for ScalarType {
                 element_type: Box::new(TryFrom::try_from(&**element_type)?),
             }),
             Type::MzAclItem => Ok(ScalarType::MzAclItem),
+            Type::Geometry => Ok(ScalarType::Geometry),
+            Type::Geography => Ok(ScalarType::Geography),
         }
     }
 }
@@ -1233,12 +1385,15 @@ impl From<&ScalarType> for Type {
             },
             ScalarType::PgLegacyName => Type::Name,
             ScalarType::Oid => Type::Oid,
-            ScalarType::Record { fields, .. } => Type::Record(
-                fields
+            ScalarType::Record {
+                fields, custom_oid, ..
+            } => Type::Record {
+                oid: *custom_oid,
+                fields: fields
                     .iter()
                     .map(|(_name, ty)| Type::from(&ty.scalar_type))
                     .collect(),
-            ),
+            },
             ScalarType::String => Type::Text,
             ScalarType::Char { length } => Type::BpChar {
                 length: (*length).map(CharLength::from),
@@ -1254,6 +1409,22 @@ impl From<&ScalarType> for Type {
                 precision: (*precision).map(TimestampPrecision::from),
             },
             ScalarType::Uuid => Type::Uuid,
+            ScalarType::Inet => Type::Inet,
+            ScalarType::Cidr => Type::Cidr,
+            ScalarType::MacAddr => Type::MacAddr,
+            ScalarType::MacAddr8 => Type::MacAddr8,
+            ScalarType::TsVector => Type::TsVector,
+            ScalarType::TsQuery => Type::TsQuery,
+            ScalarType::JsonPath => Type::JsonPath,
+            ScalarType::Enum {
+                labels,
+                custom_id,
+                custom_oid,
+            } => Type::Enum {
+                oid: *custom_oid,
+                id: *custom_id,
+                labels: labels.to_vec(),
+            },
             ScalarType::Numeric { max_scale } => Type::Numeric {
                 constraints: Some(NumericConstraints {
                     max_precision: i32::from(NUMERIC_DATUM_MAX_PRECISION),
@@ -1272,6 +1443,8 @@ impl From<&ScalarType> for Type {
                 element_type: Box::new(From::from(&**element_type)),
             },
             ScalarType::MzAclItem => Type::MzAclItem,
+            ScalarType::Geometry => Type::Geometry,
+            ScalarType::Geography => Type::Geography,
         }
     }
 }
@@ -13,7 +13,7 @@ use std::{io, str};
 
 use bytes::{BufMut, BytesMut};
 use chrono::{DateTime, NaiveDateTime, NaiveTime, Utc};
-use mz_ore::cast::ReinterpretCast;
+use mz_ore::cast::{CastFrom, ReinterpretCast};
 use mz_pgwire_common::Format;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::char;
@@ -29,12 +29,19 @@ use postgres_types::{FromSql, IsNull, ToSql, Type as PgType};
 use uuid::Uuid;
 
 use crate::types::{UINT2, UINT4, UINT8};
-use crate::{Interval, Jsonb, Numeric, Type, UInt2, UInt4, UInt8};
+use crate::{
+    Geometry, Inet, Interval, JsonPath, Jsonb, MacAddr, MacAddr8, Numeric, TsQuery, TsVector, Type,
+    UInt2, UInt4, UInt8,
+};
 
+pub mod geo;
 pub mod interval;
 pub mod jsonb;
+pub mod jsonpath;
+pub mod network;
 pub mod numeric;
 pub mod record;
+pub mod text_search;
 pub mod unsigned;
 
 /// A PostgreSQL datum.
@@ -116,6 +123,26 @@ pub enum Value {
     /// A list of privileges granted to a user that uses [`mz_repr::adt::system::Oid`]s for role
     /// references. This type is used primarily for compatibility with PostgreSQL.
     AclItem(AclItem),
+    /// An IPv4 or IPv6 host address, with an optional subnet.
+    Inet(Inet),
+    /// An IPv4 or IPv6 network.
+    Cidr(Inet),
+    /// A MAC address in EUI-48 format.
+    MacAddr(MacAddr),
+    /// A MAC address in EUI-64 format.
+    MacAddr8(MacAddr8),
+    /// A document preprocessed for text search.
+    TsVector(TsVector),
+    /// A text search query.
+    TsQuery(TsQuery),
+    /// A SQL/JSON path expression.
+    JsonPath(JsonPath),
+    /// A label of a user-defined enum type.
+    Enum(String),
+    /// A planar geometry.
+    Geometry(Geometry),
+    /// A geometry on the WGS 84 spheroid.
+    Geography(Geometry),
 }
 
 impl Value {
@@ -163,6 +190,28 @@ impl Value {
                 Some(Value::Jsonb(Jsonb(JsonbRef::from_datum(datum).to_owned())))
             }
             (Datum::Uuid(u), ScalarType::Uuid) => Some(Value::Uuid(u)),
+            (Datum::Inet(i), ScalarType::Inet) => Some(Value::Inet(Inet(i))),
+            (Datum::Inet(i), ScalarType::Cidr) => Some(Value::Cidr(Inet(i))),
+            (Datum::MacAddr(m), ScalarType::MacAddr) => Some(Value::MacAddr(MacAddr(m))),
+            (Datum::MacAddr8(m), ScalarType::MacAddr8) => Some(Value::MacAddr8(MacAddr8(m))),
+            (Datum::String(s), ScalarType::TsVector) => Some(Value::TsVector(TsVector(
+                strconv::parse_tsvector(s).expect("tsvector datums are valid"),
+            ))),
+            (Datum::String(s), ScalarType::TsQuery) => Some(Value::TsQuery(TsQuery(
+                strconv::parse_tsquery(s).expect("tsquery datums are valid"),
+            ))),
+            (Datum::String(s), ScalarType::JsonPath) => Some(Value::JsonPath(JsonPath(
+                strconv::parse_jsonpath(s).expect("jsonpath datums are valid"),
+            ))),
+            (Datum::UInt32(i), ScalarType::Enum { labels, .. }) => {
+                Some(Value::Enum(labels[usize::cast_from(i)].clone()))
+            }
+            (Datum::Bytes(b), ScalarType::Geometry) => Some(Value::Geometry(Geometry(
+                mz_repr::adt::geo::Geometry::from_ewkb(b).expect("geometry datums are valid"),
+            ))),
+            (Datum::Bytes(b), ScalarType::Geography) => Some(Value::Geography(Geometry(
+                mz_repr::adt::geo::Geometry::from_ewkb(b).expect("geography datums are valid"),
+            ))),
             (Datum::Array(array), ScalarType::Array(elem_type)) => {
                 let dims = array.dims().into_iter().collect();
                 let elements = array
@@ -286,10 +335,22 @@ impl Value {
                 })
             }
             Value::Oid(oid) => Datum::UInt32(oid),
-            Value::Record(_) => {
-                // This situation is handled gracefully by Value::decode; if we
-                // wind up here it's a programming error.
-                unreachable!("into_datum cannot be called on Value::Record");
+            Value::Record(fields) => {
+                let field_pg_types = match typ {
+                    Type::Record { fields, .. } => fields,
+                    _ => panic!(
+                        "Value::Record should have type Type::Record. Found {:?}",
+                        typ
+                    ),
+                };
+                buf.make_datum(|packer| {
+                    packer.push_list(fields.into_iter().zip(field_pg_types).map(
+                        |(field, field_pg_type)| match field {
+                            Some(field) => field.into_datum(buf, field_pg_type),
+                            None => Datum::Null,
+                        },
+                    ));
+                })
             }
             Value::Time(t) => Datum::Time(t),
             Value::Timestamp(ts) => Datum::Timestamp(ts),
@@ -313,6 +374,26 @@ impl Value {
             }
             Value::MzAclItem(mz_acl_item) => Datum::MzAclItem(mz_acl_item),
             Value::AclItem(acl_item) => Datum::AclItem(acl_item),
+            Value::Inet(inet) | Value::Cidr(inet) => Datum::Inet(inet.0),
+            Value::MacAddr(macaddr) => Datum::MacAddr(macaddr.0),
+            Value::MacAddr8(macaddr8) => Datum::MacAddr8(macaddr8.0),
+            Value::TsVector(tsvector) => Datum::String(buf.push_string(tsvector.to_string())),
+            Value::TsQuery(tsquery) => Datum::String(buf.push_string(tsquery.to_string())),
+            Value::JsonPath(jsonpath) => Datum::String(buf.push_string(jsonpath.to_string())),
+            Value::Enum(label) => {
+                let labels = match typ {
+                    Type::Enum { labels, .. } => labels,
+                    _ => panic!("Value::Enum should have type Type::Enum. Found {:?}", typ),
+                };
+                let i = labels
+                    .iter()
+                    .position(|l| *l == label)
+                    .expect("enum values are validated when decoded");
+                Datum::UInt32(u32::try_from(i).expect("enum has too many labels"))
+            }
+            Value::Geometry(geometry) | Value::Geography(geometry) => {
+                Datum::Bytes(buf.push_bytes(geometry.0.to_ewkb()))
+            }
         }
     }
 
@@ -397,6 +478,17 @@ impl Value {
             .expect("provided closure never fails"),
             Value::MzAclItem(mz_acl_item) => strconv::format_mz_acl_item(buf, *mz_acl_item),
             Value::AclItem(acl_item) => strconv::format_acl_item(buf, *acl_item),
+            Value::Inet(inet) => strconv::format_inet(buf, inet.0),
+            Value::Cidr(cidr) => strconv::format_cidr(buf, cidr.0),
+            Value::MacAddr(macaddr) => strconv::format_macaddr(buf, macaddr.0),
+            Value::MacAddr8(macaddr8) => strconv::format_macaddr8(buf, macaddr8.0),
+            Value::TsVector(tsvector) => strconv::format_tsvector(buf, &tsvector.0),
+            Value::TsQuery(tsquery) => strconv::format_tsquery(buf, &tsquery.0),
+            Value::JsonPath(jsonpath) => strconv::format_jsonpath(buf, &jsonpath.0),
+            Value::Enum(label) => strconv::format_string(buf, label),
+            Value::Geometry(geometry) | Value::Geography(geometry) => {
+                strconv::format_geometry(buf, &geometry.0)
+            }
         }
     }
 
@@ -493,7 +585,7 @@ impl Value {
                 let nfields = pg_len("record field length", fields.len())?;
                 buf.put_i32(nfields);
                 let field_types = match ty {
-                    Type::Record(fields) => fields,
+                    Type::Record { fields, .. } => fields,
                     _ => unreachable!(),
                 };
                 for (f, ty) in fields.iter().zip(field_types) {
@@ -537,6 +629,19 @@ impl Value {
                 Ok(postgres_types::IsNull::No)
             }
             Value::AclItem(_) => Err("aclitem has no binary encoding".into()),
+            Value::Inet(inet) => inet.to_sql(&PgType::INET, buf),
+            Value::Cidr(cidr) => cidr.to_sql(&PgType::CIDR, buf),
+            Value::MacAddr(macaddr) => macaddr.to_sql(&PgType::MACADDR, buf),
+            Value::MacAddr8(macaddr8) => macaddr8.to_sql(&PgType::MACADDR8, buf),
+            Value::TsVector(tsvector) => tsvector.to_sql(&PgType::TS_VECTOR, buf),
+            Value::TsQuery(tsquery) => tsquery.to_sql(&PgType::TSQUERY, buf),
+            Value::JsonPath(jsonpath) => jsonpath.to_sql(&PgType::JSONPATH, buf),
+            // Like PostgreSQL, the binary encoding of an enum value is its
+            // label.
+            Value::Enum(label) => label.to_sql(&PgType::TEXT, buf),
+            Value::Geometry(geometry) | Value::Geography(geometry) => {
+                geometry.to_sql(ty.inner(), buf)
+            }
         }
         .expect("encode_binary should never trigger a to_sql failure");
         if let IsNull::Yes = is_null {
@@ -580,6 +685,16 @@ impl Value {
             ScalarType::PgLegacyName => true,
             ScalarType::Jsonb => true,
             ScalarType::Uuid => true,
+            ScalarType::Inet => true,
+            ScalarType::Cidr => true,
+            ScalarType::MacAddr => true,
+            ScalarType::MacAddr8 => true,
+            ScalarType::TsVector => true,
+            ScalarType::TsQuery => true,
+            ScalarType::JsonPath => true,
+            ScalarType::Enum { .. } => true,
+            ScalarType::Geometry => true,
+            ScalarType::Geography => true,
             ScalarType::Array(elem_type) => Self::can_encode_binary(elem_type),
             ScalarType::Int2Vector => false, // "binary encoding of int2vector is not implemented"
             ScalarType::List { .. } => false, // "binary encoding of list types is not implemented"
@@ -658,9 +773,20 @@ impl Value {
             Type::Oid | Type::RegClass | Type::RegProc | Type::RegType => {
                 Value::Oid(strconv::parse_oid(s)?)
             }
-            Type::Record(_) => {
+            Type::Record { fields, .. } if fields.is_empty() => {
                 return Err("input of anonymous composite types is not implemented".into())
             }
+            Type::Record { fields, .. } => Value::Record(
+                strconv::parse_record(s, fields.len())?
+                    .into_iter()
+                    .zip(fields)
+                    .map(|(field_text, field_type)| {
+                        field_text
+                            .map(|t| Value::decode_text(field_type, t.as_bytes()))
+                            .transpose()
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
             Type::Text => Value::Text(s.to_owned()),
             Type::BpChar { .. } => Value::BpChar(s.to_owned()),
             Type::VarChar { .. } => Value::VarChar(s.to_owned()),
@@ -675,6 +801,16 @@ impl Value {
             })?),
             Type::MzAclItem => Value::MzAclItem(strconv::parse_mz_acl_item(s)?),
             Type::AclItem => Value::AclItem(strconv::parse_acl_item(s)?),
+            Type::Inet => Value::Inet(Inet(strconv::parse_inet(s)?)),
+            Type::Cidr => Value::Cidr(Inet(strconv::parse_cidr(s)?)),
+            Type::MacAddr => Value::MacAddr(MacAddr(strconv::parse_macaddr(s)?)),
+            Type::MacAddr8 => Value::MacAddr8(MacAddr8(strconv::parse_macaddr8(s)?)),
+            Type::TsVector => Value::TsVector(TsVector(strconv::parse_tsvector(s)?)),
+            Type::TsQuery => Value::TsQuery(TsQuery(strconv::parse_tsquery(s)?)),
+            Type::JsonPath => Value::JsonPath(JsonPath(strconv::parse_jsonpath(s)?)),
+            Type::Enum { labels, .. } => Value::Enum(labels[parse_enum_label(labels, s)?].clone()),
+            Type::Geometry => Value::Geometry(Geometry(strconv::parse_geometry(s)?)),
+            Type::Geography => Value::Geography(Geometry(strconv::parse_geography(s)?)),
         })
     }
 
@@ -762,9 +898,21 @@ impl Value {
             Type::Oid | Type::RegClass | Type::RegProc | Type::RegType => {
                 packer.push(Datum::UInt32(strconv::parse_oid(s)?))
             }
-            Type::Record(_) => {
+            Type::Record { fields, .. } if fields.is_empty() => {
                 return Err("input of anonymous composite types is not implemented".into())
             }
+            Type::Record { fields, .. } => {
+                let field_texts = strconv::parse_record(s, fields.len())?;
+                packer.push_list_with(|packer| {
+                    for (field_text, field_type) in field_texts.into_iter().zip(fields) {
+                        match field_text {
+                            Some(t) => Value::decode_text_into_row(field_type, &t, packer)?,
+                            None => packer.push(Datum::Null),
+                        }
+                    }
+                    Ok::<_, Box<dyn Error + Sync + Send>>(())
+                })?;
+            }
             Type::Text => packer.push(Datum::String(s)),
             Type::BpChar { .. } => packer.push(Datum::String(s.trim_end())),
             Type::VarChar { .. } => packer.push(Datum::String(s)),
@@ -789,6 +937,21 @@ impl Value {
             }
             Type::MzAclItem => packer.push(Datum::MzAclItem(strconv::parse_mz_acl_item(s)?)),
             Type::AclItem => packer.push(Datum::AclItem(strconv::parse_acl_item(s)?)),
+            Type::Inet => packer.push(Datum::Inet(strconv::parse_inet(s)?)),
+            Type::Cidr => packer.push(Datum::Inet(strconv::parse_cidr(s)?)),
+            Type::MacAddr => packer.push(Datum::MacAddr(strconv::parse_macaddr(s)?)),
+            Type::MacAddr8 => packer.push(Datum::MacAddr8(strconv::parse_macaddr8(s)?)),
+            Type::TsVector => packer.push(Datum::String(&strconv::parse_tsvector(s)?.to_string())),
+            Type::TsQuery => packer.push(Datum::String(&strconv::parse_tsquery(s)?.to_string())),
+            Type::JsonPath => packer.push(Datum::String(&strconv::parse_jsonpath(s)?.to_string())),
+            Type::Enum { labels, .. } => {
+                let i = parse_enum_label(labels, s)?;
+                packer.push(Datum::UInt32(
+                    u32::try_from(i).expect("enum has too many labels"),
+                ))
+            }
+            Type::Geometry => packer.push(Datum::Bytes(&strconv::parse_geometry(s)?.to_ewkb())),
+            Type::Geography => packer.push(Datum::Bytes(&strconv::parse_geography(s)?.to_ewkb())),
         })
     }
 
@@ -831,7 +994,9 @@ impl Value {
             Type::Oid | Type::RegClass | Type::RegProc | Type::RegType => {
                 u32::from_sql(ty.inner(), raw).map(Value::Oid)
             }
-            Type::Record(_) => Err("input of anonymous composite types is not implemented".into()),
+            Type::Record { .. } => {
+                Err("input of anonymous composite types is not implemented".into())
+            }
             Type::Text => String::from_sql(ty.inner(), raw).map(Value::Text),
             Type::BpChar { .. } => String::from_sql(ty.inner(), raw).map(Value::BpChar),
             Type::VarChar { .. } => String::from_sql(ty.inner(), raw).map(Value::VarChar),
@@ -859,10 +1024,35 @@ impl Value {
                 Ok(Value::MzAclItem(mz_acl_item))
             }
             Type::AclItem => Err("aclitem has no binary encoding".into()),
+            Type::Inet => Inet::from_sql(ty.inner(), raw).map(Value::Inet),
+            Type::Cidr => Inet::from_sql(ty.inner(), raw).map(Value::Cidr),
+            Type::MacAddr => MacAddr::from_sql(ty.inner(), raw).map(Value::MacAddr),
+            Type::MacAddr8 => MacAddr8::from_sql(ty.inner(), raw).map(Value::MacAddr8),
+            Type::TsVector => TsVector::from_sql(ty.inner(), raw).map(Value::TsVector),
+            Type::TsQuery => TsQuery::from_sql(ty.inner(), raw).map(Value::TsQuery),
+            Type::JsonPath => JsonPath::from_sql(ty.inner(), raw).map(Value::JsonPath),
+            Type::Enum { labels, .. } => {
+                let s = str::from_utf8(raw)?;
+                Ok(Value::Enum(labels[parse_enum_label(labels, s)?].clone()))
+            }
+            Type::Geometry => Geometry::from_sql(ty.inner(), raw).map(Value::Geometry),
+            Type::Geography => {
+                let Geometry(geometry) = Geometry::from_sql(ty.inner(), raw)?;
+                Ok(Value::Geography(Geometry(geometry.into_geography()?)))
+            }
         }
     }
 }
 
+/// Returns the index of the label `s` in `labels`, or an error if `s` is not
+/// one of the labels of the enum type.
+fn parse_enum_label(labels: &[String], s: &str) -> Result<usize, Box<dyn Error + Sync + Send>> {
+    labels
+        .iter()
+        .position(|l| l == s)
+        .ok_or_else(|| format!("invalid input value for enum: \"{s}\"").into())
+}
+
 fn encode_element(buf: &mut BytesMut, elem: Option<&Value>, ty: &Type) -> Result<(), io::Error> {
     match elem {
         None => buf.put_i32(-1),
@@ -0,0 +1,57 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::error::Error;
+use std::fmt;
+
+use bytes::{BufMut, BytesMut};
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+/// A wrapper for the `repr` crate's [`Geometry`](mz_repr::adt::geo::Geometry)
+/// type that can be serialized to and deserialized from the PostgreSQL binary
+/// format.
+///
+/// The binary format of the PostGIS `geometry` and `geography` types is the
+/// extended well-known binary (EWKB) representation of the value.
+#[derive(Debug, Clone)]
+pub struct Geometry(pub mz_repr::adt::geo::Geometry);
+
+impl ToSql for Geometry {
+    fn to_sql(
+        &self,
+        _: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        out.put_slice(&self.0.to_ewkb());
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        // PostGIS is an extension, so its types do not have fixed OIDs.
+        matches!(ty.name(), "geometry" | "geography")
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Geometry {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Geometry, Box<dyn Error + Sync + Send>> {
+        Ok(Geometry(mz_repr::adt::geo::Geometry::from_ewkb(raw)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(ty.name(), "geometry" | "geography")
+    }
+}
+
+impl fmt::Display for Geometry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::error::Error;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use bytes::{BufMut, BytesMut};
+use mz_repr::adt::network::{Inet as ReprInet, MacAddr as ReprMacAddr, MacAddr8 as ReprMacAddr8};
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+/// The address family tag PostgreSQL uses for IPv4 addresses on the wire.
+///
+/// This is `AF_INET` as defined on the PostgreSQL server, which is not
+/// necessarily the same as the client platform's `AF_INET`.
+const PGSQL_AF_INET: u8 = 2;
+/// The address family tag PostgreSQL uses for IPv6 addresses on the wire.
+const PGSQL_AF_INET6: u8 = 3;
+
+/// A wrapper for the `repr` crate's [`Inet`](mz_repr::adt::network::Inet)
+/// type that can be serialized to and deserialized from the PostgreSQL binary
+/// format.
+///
+/// The same wrapper is used for both the `inet` and `cidr` types; the type
+/// passed to [`ToSql::to_sql`] determines which is written.
+#[derive(Debug, Clone)]
+pub struct Inet(pub ReprInet);
+
+impl fmt::Display for Inet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToSql for Inet {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        // Postgres represents inet and cidr values as a one byte address
+        // family, followed by the number of bits in the netmask, followed by
+        // a one byte flag indicating whether the value is a cidr, followed by
+        // the number of bytes in the address, followed by the address itself.
+        //
+        // Postgres implementation: https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/network.c#L283
+        let is_cidr = *ty == Type::CIDR;
+        match self.0.addr() {
+            IpAddr::V4(addr) => {
+                out.put_u8(PGSQL_AF_INET);
+                out.put_u8(self.0.netmask());
+                out.put_u8(is_cidr.into());
+                out.put_u8(4);
+                out.put_slice(&addr.octets());
+            }
+            IpAddr::V6(addr) => {
+                out.put_u8(PGSQL_AF_INET6);
+                out.put_u8(self.0.netmask());
+                out.put_u8(is_cidr.into());
+                out.put_u8(16);
+                out.put_slice(&addr.octets());
+            }
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::INET | Type::CIDR)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Inet {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Inet, Box<dyn Error + Sync + Send>> {
+        let (family, netmask, addr) = match raw {
+            [family, netmask, _is_cidr, _nb, addr @ ..] => (*family, *netmask, addr),
+            _ => return Err("invalid length in external \"inet\" value".into()),
+        };
+        let addr = match family {
+            PGSQL_AF_INET => {
+                let octets: [u8; 4] = addr
+                    .try_into()
+                    .map_err(|_| "invalid length in external \"inet\" value")?;
+                IpAddr::V4(Ipv4Addr::from(octets))
+            }
+            PGSQL_AF_INET6 => {
+                let octets: [u8; 16] = addr
+                    .try_into()
+                    .map_err(|_| "invalid length in external \"inet\" value")?;
+                IpAddr::V6(Ipv6Addr::from(octets))
+            }
+            _ => return Err("invalid address family in external \"inet\" value".into()),
+        };
+        let inet = ReprInet::new(addr, netmask)?;
+        if *ty == Type::CIDR && !inet.is_network() {
+            return Err("invalid external \"cidr\" value".into());
+        }
+        Ok(Inet(inet))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::INET | Type::CIDR)
+    }
+}
+
+/// A wrapper for the `repr` crate's
+/// [`MacAddr`](mz_repr::adt::network::MacAddr) type that can be serialized to
+/// and deserialized from the PostgreSQL binary format.
+#[derive(Debug, Clone)]
+pub struct MacAddr(pub ReprMacAddr);
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToSql for MacAddr {
+    fn to_sql(
+        &self,
+        _: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        out.put_slice(&self.0 .0);
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::MACADDR)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for MacAddr {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<MacAddr, Box<dyn Error + Sync + Send>> {
+        let bytes = raw
+            .try_into()
+            .map_err(|_| "invalid length in external \"macaddr\" value")?;
+        Ok(MacAddr(ReprMacAddr(bytes)))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::MACADDR)
+    }
+}
+
+/// A wrapper for the `repr` crate's
+/// [`MacAddr8`](mz_repr::adt::network::MacAddr8) type that can be serialized
+/// to and deserialized from the PostgreSQL binary format.
+#[derive(Debug, Clone)]
+pub struct MacAddr8(pub ReprMacAddr8);
+
+impl fmt::Display for MacAddr8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToSql for MacAddr8 {
+    fn to_sql(
+        &self,
+        _: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        out.put_slice(&self.0 .0);
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::MACADDR8)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for MacAddr8 {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<MacAddr8, Box<dyn Error + Sync + Send>> {
+        // Like Postgres, accept the 6 byte EUI-48 format too.
+        match raw.len() {
+            6 => {
+                let bytes = raw.try_into().expect("checked length");
+                Ok(MacAddr8(ReprMacAddr(bytes).to_macaddr8()))
+            }
+            _ => {
+                let bytes = raw
+                    .try_into()
+                    .map_err(|_| "invalid length in external \"macaddr8\" value")?;
+                Ok(MacAddr8(ReprMacAddr8(bytes)))
+            }
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::MACADDR8)
+    }
+}
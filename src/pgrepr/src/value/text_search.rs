@@ -0,0 +1,309 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::error::Error;
+use std::fmt;
+
+use bytes::{Buf, BufMut, BytesMut};
+use mz_repr::adt::text_search::{
+    TsPosition, TsQuery as ReprTsQuery, TsQueryNode, TsQueryOperand, TsVector as ReprTsVector,
+    MAX_PHRASE_DISTANCE,
+};
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+/// The tag of an operand in the binary representation of a `tsquery`.
+const QI_VAL: u8 = 1;
+/// The tag of an operator in the binary representation of a `tsquery`.
+const QI_OPR: u8 = 2;
+
+const OP_NOT: u8 = 1;
+const OP_AND: u8 = 2;
+const OP_OR: u8 = 3;
+const OP_PHRASE: u8 = 4;
+
+/// A wrapper for the `repr` crate's
+/// [`TsVector`](mz_repr::adt::text_search::TsVector) type that can be
+/// serialized to and deserialized from the PostgreSQL binary format.
+#[derive(Debug, Clone)]
+pub struct TsVector(pub ReprTsVector);
+
+impl fmt::Display for TsVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToSql for TsVector {
+    fn to_sql(
+        &self,
+        _: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        // Postgres represents a tsvector as the number of lexemes, followed by
+        // each lexeme as a null-terminated string, the number of its
+        // positions, and the positions themselves.
+        //
+        // Postgres implementation: https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/tsvector.c#L409
+        let lexemes = self.0.lexemes();
+        out.put_i32(i32::try_from(lexemes.len())?);
+        for lexeme in lexemes {
+            out.put_slice(lexeme.text().as_bytes());
+            out.put_u8(0);
+            out.put_u16(u16::try_from(lexeme.positions().len())?);
+            for position in lexeme.positions() {
+                out.put_u16(position.to_bits());
+            }
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::TS_VECTOR)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for TsVector {
+    fn from_sql(_: &Type, mut raw: &'a [u8]) -> Result<TsVector, Box<dyn Error + Sync + Send>> {
+        let len = usize::try_from(get_i32(&mut raw)?).map_err(|_| "invalid size of tsvector")?;
+        let mut lexemes = Vec::with_capacity(len.min(raw.len()));
+        for _ in 0..len {
+            let text = get_cstring(&mut raw)?;
+            let npos = get_u16(&mut raw)?;
+            let positions = (0..npos)
+                .map(|_| get_u16(&mut raw).map(TsPosition::from_bits))
+                .collect::<Result<_, _>>()?;
+            lexemes.push((text, positions));
+        }
+        Ok(TsVector(ReprTsVector::new(lexemes)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::TS_VECTOR)
+    }
+}
+
+/// A wrapper for the `repr` crate's
+/// [`TsQuery`](mz_repr::adt::text_search::TsQuery) type that can be
+/// serialized to and deserialized from the PostgreSQL binary format.
+#[derive(Debug, Clone)]
+pub struct TsQuery(pub ReprTsQuery);
+
+impl fmt::Display for TsQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToSql for TsQuery {
+    fn to_sql(
+        &self,
+        _: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        // Postgres represents a tsquery as the number of its nodes, followed
+        // by the nodes in prefix order, where the right operand of an
+        // operator precedes its left operand.
+        //
+        // Postgres implementation: https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/tsquery.c#L1152
+        let mut items = BytesMut::new();
+        let mut len = 0;
+        if let Some(root) = self.0.root() {
+            put_node(&mut items, root, &mut len);
+        }
+        out.put_i32(len);
+        out.put_slice(&items);
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::TSQUERY)
+    }
+
+    to_sql_checked!();
+}
+
+fn put_node(out: &mut BytesMut, node: &TsQueryNode, len: &mut i32) {
+    *len += 1;
+    match node {
+        TsQueryNode::Operand(operand) => {
+            out.put_u8(QI_VAL);
+            out.put_u8(operand.weights);
+            out.put_u8(operand.prefix.into());
+            out.put_slice(operand.lexeme.as_bytes());
+            out.put_u8(0);
+        }
+        TsQueryNode::Not(node) => {
+            out.put_u8(QI_OPR);
+            out.put_u8(OP_NOT);
+            put_node(out, node, len);
+        }
+        TsQueryNode::And(left, right) | TsQueryNode::Or(left, right) => {
+            out.put_u8(QI_OPR);
+            out.put_u8(match node {
+                TsQueryNode::And(..) => OP_AND,
+                _ => OP_OR,
+            });
+            put_node(out, right, len);
+            put_node(out, left, len);
+        }
+        TsQueryNode::Phrase {
+            left,
+            right,
+            distance,
+        } => {
+            out.put_u8(QI_OPR);
+            out.put_u8(OP_PHRASE);
+            out.put_u16(*distance);
+            put_node(out, right, len);
+            put_node(out, left, len);
+        }
+    }
+}
+
+impl<'a> FromSql<'a> for TsQuery {
+    fn from_sql(_: &Type, mut raw: &'a [u8]) -> Result<TsQuery, Box<dyn Error + Sync + Send>> {
+        let len = usize::try_from(get_i32(&mut raw)?).map_err(|_| "invalid size of tsquery")?;
+        let mut remaining = len;
+        let root = match len {
+            0 => None,
+            _ => Some(get_node(&mut raw, &mut remaining)?),
+        };
+        if remaining != 0 || !raw.is_empty() {
+            return Err("malformed tsquery: extra nodes".into());
+        }
+        Ok(TsQuery(ReprTsQuery::new(root)))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::TSQUERY)
+    }
+}
+
+fn get_node(
+    raw: &mut &[u8],
+    remaining: &mut usize,
+) -> Result<TsQueryNode, Box<dyn Error + Sync + Send>> {
+    *remaining = remaining
+        .checked_sub(1)
+        .ok_or("malformed tsquery: operand not found")?;
+    match get_u8(raw)? {
+        QI_VAL => {
+            let weights = get_u8(raw)?;
+            if weights > 0b1111 {
+                return Err("invalid tsquery: invalid weight bitmap".into());
+            }
+            let prefix = get_u8(raw)? != 0;
+            let lexeme = get_cstring(raw)?;
+            if lexeme.is_empty() {
+                return Err("invalid tsquery: empty lexeme".into());
+            }
+            Ok(TsQueryNode::Operand(TsQueryOperand {
+                lexeme,
+                weights,
+                prefix,
+            }))
+        }
+        QI_OPR => match get_u8(raw)? {
+            OP_NOT => Ok(TsQueryNode::Not(Box::new(get_node(raw, remaining)?))),
+            oper @ (OP_AND | OP_OR) => {
+                let right = Box::new(get_node(raw, remaining)?);
+                let left = Box::new(get_node(raw, remaining)?);
+                Ok(match oper {
+                    OP_AND => TsQueryNode::And(left, right),
+                    _ => TsQueryNode::Or(left, right),
+                })
+            }
+            OP_PHRASE => {
+                let distance = get_u16(raw)?;
+                if distance > MAX_PHRASE_DISTANCE {
+                    return Err("invalid tsquery: distance in phrase operator out of range".into());
+                }
+                let right = Box::new(get_node(raw, remaining)?);
+                let left = Box::new(get_node(raw, remaining)?);
+                Ok(TsQueryNode::Phrase {
+                    left,
+                    right,
+                    distance,
+                })
+            }
+            oper => Err(format!("invalid tsquery: unrecognized operator type {oper}").into()),
+        },
+        typ => Err(format!("invalid tsquery: unrecognized item type {typ}").into()),
+    }
+}
+
+fn get_u8(raw: &mut &[u8]) -> Result<u8, Box<dyn Error + Sync + Send>> {
+    if raw.remaining() < 1 {
+        return Err("insufficient data left in message".into());
+    }
+    Ok(raw.get_u8())
+}
+
+fn get_u16(raw: &mut &[u8]) -> Result<u16, Box<dyn Error + Sync + Send>> {
+    if raw.remaining() < 2 {
+        return Err("insufficient data left in message".into());
+    }
+    Ok(raw.get_u16())
+}
+
+fn get_i32(raw: &mut &[u8]) -> Result<i32, Box<dyn Error + Sync + Send>> {
+    if raw.remaining() < 4 {
+        return Err("insufficient data left in message".into());
+    }
+    Ok(raw.get_i32())
+}
+
+fn get_cstring(raw: &mut &[u8]) -> Result<String, Box<dyn Error + Sync + Send>> {
+    let len = raw
+        .iter()
+        .position(|b| *b == 0)
+        .ok_or("invalid string in message")?;
+    let s = std::str::from_utf8(&raw[..len])?.to_owned();
+    raw.advance(len + 1);
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_binary_roundtrip() {
+        for s in ["", "'a' 'b c'", "'fat':2,11 'it''s':1A,3B 'rat':16383C"] {
+            let vector = TsVector(s.parse().unwrap());
+            let mut buf = BytesMut::new();
+            vector.to_sql(&Type::TS_VECTOR, &mut buf).unwrap();
+            let decoded = TsVector::from_sql(&Type::TS_VECTOR, &buf).unwrap();
+            assert_eq!(decoded.0, vector.0);
+        }
+
+        for s in [
+            "",
+            "'a'",
+            "'fat':AB & !( 'rat':* <-> 'cat' ) | 'a' <0> 'b'",
+            "'a' <-> ( 'b' <3> 'c' )",
+        ] {
+            let query = TsQuery(s.parse().unwrap());
+            let mut buf = BytesMut::new();
+            query.to_sql(&Type::TSQUERY, &mut buf).unwrap();
+            let decoded = TsQuery::from_sql(&Type::TSQUERY, &buf).unwrap();
+            assert_eq!(decoded.0, query.0);
+        }
+
+        // 'a' & 'b', with the right operand first.
+        let raw = [
+            0, 0, 0, 3, QI_OPR, OP_AND, QI_VAL, 0, 0, b'b', 0, QI_VAL, 0, 0, b'a', 0,
+        ];
+        let query = TsQuery::from_sql(&Type::TSQUERY, &raw).unwrap();
+        assert_eq!(query.to_string(), "'a' & 'b'");
+        assert!(TsQuery::from_sql(&Type::TSQUERY, &raw[..8]).is_err());
+    }
+}
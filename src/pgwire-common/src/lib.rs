@@ -16,6 +16,7 @@ mod codec;
 mod conn;
 mod format;
 mod message;
+mod scram;
 mod severity;
 
 pub use codec::{
@@ -31,4 +32,5 @@ pub use message::{
     ErrorResponse, FrontendMessage, FrontendStartupMessage, VERSIONS, VERSION_3, VERSION_CANCEL,
     VERSION_GSSENC, VERSION_SSL,
 };
+pub use scram::{ScramError, ScramExchange, ScramSecret, SCRAM_SHA_256};
 pub use severity::Severity;
@@ -182,6 +182,10 @@ pub enum FrontendMessage {
     Password {
         password: String,
     },
+
+    /// A response to an authentication request, whose contents depend on the
+    /// kind of authentication that was requested.
+    RawAuthentication(Vec<u8>),
 }
 
 impl FrontendMessage {
@@ -202,6 +206,7 @@ impl FrontendMessage {
             FrontendMessage::CopyDone => "copy_done",
             FrontendMessage::CopyFail(_) => "copy_fail",
             FrontendMessage::Password { .. } => "password",
+            FrontendMessage::RawAuthentication(_) => "raw_authentication",
         }
     }
 }
@@ -0,0 +1,444 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SCRAM-SHA-256 password hashing and authentication, as described in [RFC 5802] and
+//! [RFC 7677].
+//!
+//! Passwords are stored as verifiers in the same format as PostgreSQL's `pg_authid.rolpassword`:
+//!
+//! ```text
+//! SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>
+//! ```
+//!
+//! Unlike PostgreSQL, passwords are not normalized with SASLprep before they are hashed, which
+//! matches PostgreSQL's behavior for passwords that are not valid UTF-8 or contain prohibited
+//! characters.
+//!
+//! [RFC 5802]: https://datatracker.ietf.org/doc/html/rfc5802
+//! [RFC 7677]: https://datatracker.ietf.org/doc/html/rfc7677
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+/// The name of the SCRAM-SHA-256 SASL mechanism.
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+/// The number of PBKDF2 iterations used for new verifiers, which matches PostgreSQL's default.
+const DEFAULT_ITERATIONS: u32 = 4096;
+/// The length of the salt of new verifiers, which matches PostgreSQL's default.
+const SALT_LEN: usize = 16;
+/// The length of the random part of the server's nonce.
+const NONCE_LEN: usize = 18;
+/// The length of a SHA-256 digest.
+const KEY_LEN: usize = 32;
+
+type Key = [u8; KEY_LEN];
+
+/// A secret from which the salts and keys of mock verifiers are derived, which is chosen when the
+/// server starts, like PostgreSQL's `mock_auth_nonce`.
+static MOCK_NONCE: LazyLock<Key> = LazyLock::new(|| {
+    let mut nonce = [0; KEY_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    nonce
+});
+
+/// A SCRAM-SHA-256 verifier of a password, from which the password cannot be recovered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramSecret {
+    iterations: u32,
+    salt: Vec<u8>,
+    stored_key: Key,
+    server_key: Key,
+}
+
+impl ScramSecret {
+    /// Hashes `password` with a random salt.
+    pub fn new(password: &str) -> ScramSecret {
+        let mut salt = vec![0; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        ScramSecret::with_salt(password, salt, DEFAULT_ITERATIONS)
+    }
+
+    fn with_salt(password: &str, salt: Vec<u8>, iterations: u32) -> ScramSecret {
+        let salted_password = salted_password(password.as_bytes(), &salt, iterations);
+        let client_key = hmac(&salted_password, b"Client Key");
+        ScramSecret {
+            iterations,
+            salt,
+            stored_key: Sha256::digest(client_key).into(),
+            server_key: hmac(&salted_password, b"Server Key"),
+        }
+    }
+
+    /// Returns a verifier for `username` that no password matches.
+    ///
+    /// Authenticating against a mock verifier takes as many round trips as authenticating against
+    /// a real one, which avoids revealing whether a role exists or has a password. The salt and
+    /// keys are derived from `username` and a per-server secret, as PostgreSQL's `scram_mock_salt`
+    /// does, so that repeated attempts for the same role see the same salt, just as they would for
+    /// a real verifier.
+    pub fn mock(username: &str) -> ScramSecret {
+        ScramSecret::mock_with_nonce(username, &MOCK_NONCE)
+    }
+
+    fn mock_with_nonce(username: &str, nonce: &[u8]) -> ScramSecret {
+        let derive = |label: &[u8]| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(nonce).expect("HMAC accepts any key size");
+            mac.update(label);
+            mac.update(username.as_bytes());
+            Key::from(mac.finalize().into_bytes())
+        };
+        // The stored key is not the hash of any known client key, so no proof matches it.
+        ScramSecret {
+            iterations: DEFAULT_ITERATIONS,
+            salt: derive(b"Salt")[..SALT_LEN].to_vec(),
+            stored_key: derive(b"Stored Key"),
+            server_key: derive(b"Server Key"),
+        }
+    }
+
+    /// Reports whether `password` matches this verifier.
+    pub fn verify_password(&self, password: &str) -> bool {
+        let other = ScramSecret::with_salt(password, self.salt.clone(), self.iterations);
+        bool::from(self.stored_key[..].ct_eq(&other.stored_key[..]))
+    }
+
+    /// Starts a SCRAM exchange with a client that wants to prove that it knows the password of
+    /// this verifier.
+    ///
+    /// Returns the state of the exchange and the server-first-message to send to the client.
+    pub fn start_exchange(
+        self,
+        client_first_message: &[u8],
+    ) -> Result<(ScramExchange, String), ScramError> {
+        let mut nonce = [0; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        self.start_exchange_with_nonce(client_first_message, &base64::encode(nonce))
+    }
+
+    fn start_exchange_with_nonce(
+        self,
+        client_first_message: &[u8],
+        server_nonce: &str,
+    ) -> Result<(ScramExchange, String), ScramError> {
+        let client_first_message = std::str::from_utf8(client_first_message)
+            .map_err(|_| ScramError::Protocol("message is not valid UTF-8"))?;
+
+        // gs2-header = gs2-cbind-flag "," [ authzid ] ","
+        let (cbind_flag, rest) = client_first_message
+            .split_once(',')
+            .ok_or(ScramError::Protocol("malformed client-first-message"))?;
+        match cbind_flag {
+            "n" | "y" => (),
+            _ if cbind_flag.starts_with("p=") => {
+                return Err(ScramError::Protocol("channel binding is not supported"));
+            }
+            _ => return Err(ScramError::Protocol("malformed gs2-cbind-flag")),
+        }
+        let (authzid, client_first_bare) = rest
+            .split_once(',')
+            .ok_or(ScramError::Protocol("malformed client-first-message"))?;
+        if !authzid.is_empty() {
+            return Err(ScramError::Protocol(
+                "authorization identities are not supported",
+            ));
+        }
+        let gs2_header =
+            &client_first_message[..client_first_message.len() - client_first_bare.len()];
+
+        // client-first-message-bare = [reserved-mext ","] username "," nonce ["," extensions]
+        //
+        // The username is ignored, because the user was already specified in the startup message.
+        let mut attrs = client_first_bare.split(',');
+        match attrs.next() {
+            Some(username) if username.starts_with("n=") => (),
+            Some(mext) if mext.starts_with("m=") => {
+                return Err(ScramError::Protocol(
+                    "mandatory extensions are not supported",
+                ));
+            }
+            _ => return Err(ScramError::Protocol("expected username attribute")),
+        }
+        let client_nonce = attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("r="))
+            .ok_or(ScramError::Protocol("expected nonce attribute"))?;
+        if client_nonce.is_empty() || !client_nonce.bytes().all(is_printable) {
+            return Err(ScramError::Protocol("malformed nonce"));
+        }
+
+        let nonce = format!("{client_nonce}{server_nonce}");
+        let server_first_message = format!(
+            "r={nonce},s={},i={}",
+            base64::encode(&self.salt),
+            self.iterations
+        );
+        let exchange = ScramExchange {
+            secret: self,
+            channel_binding: base64::encode(gs2_header),
+            nonce,
+            client_first_bare: client_first_bare.into(),
+            server_first_message: server_first_message.clone(),
+        };
+        Ok((exchange, server_first_message))
+    }
+}
+
+impl fmt::Display for ScramSecret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{SCRAM_SHA_256}${}:{}${}:{}",
+            self.iterations,
+            base64::encode(&self.salt),
+            base64::encode(self.stored_key),
+            base64::encode(self.server_key),
+        )
+    }
+}
+
+impl FromStr for ScramSecret {
+    type Err = ScramError;
+
+    fn from_str(s: &str) -> Result<ScramSecret, ScramError> {
+        fn parse(s: &str) -> Option<ScramSecret> {
+            let rest = s.strip_prefix(SCRAM_SHA_256)?.strip_prefix('$')?;
+            let (iterations_and_salt, keys) = rest.split_once('$')?;
+            let (iterations, salt) = iterations_and_salt.split_once(':')?;
+            let (stored_key, server_key) = keys.split_once(':')?;
+            let iterations = iterations.parse().ok().filter(|i| *i > 0)?;
+            let salt = base64::decode(salt).ok()?;
+            let stored_key = base64::decode(stored_key).ok()?.try_into().ok()?;
+            let server_key = base64::decode(server_key).ok()?.try_into().ok()?;
+            Some(ScramSecret {
+                iterations,
+                salt,
+                stored_key,
+                server_key,
+            })
+        }
+        parse(s).ok_or(ScramError::InvalidSecret)
+    }
+}
+
+/// The server's state in the middle of a SCRAM exchange, after the server-first-message has been
+/// sent.
+#[derive(Debug)]
+pub struct ScramExchange {
+    secret: ScramSecret,
+    channel_binding: String,
+    nonce: String,
+    client_first_bare: String,
+    server_first_message: String,
+}
+
+impl ScramExchange {
+    /// Verifies the client's proof in the client-final-message.
+    ///
+    /// Returns the server-final-message to send to the client if the proof is valid.
+    pub fn finish(self, client_final_message: &[u8]) -> Result<String, ScramError> {
+        let client_final_message = std::str::from_utf8(client_final_message)
+            .map_err(|_| ScramError::Protocol("message is not valid UTF-8"))?;
+
+        // client-final-message = client-final-message-without-proof "," proof
+        let (without_proof, proof) = client_final_message
+            .rsplit_once(',')
+            .ok_or(ScramError::Protocol("malformed client-final-message"))?;
+        let proof = proof
+            .strip_prefix("p=")
+            .and_then(|proof| base64::decode(proof).ok())
+            .ok_or(ScramError::Protocol("malformed proof"))?;
+
+        // client-final-message-without-proof = channel-binding "," nonce ["," extensions]
+        let mut attrs = without_proof.split(',');
+        let channel_binding = attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("c="))
+            .ok_or(ScramError::Protocol("expected channel binding attribute"))?;
+        if channel_binding != self.channel_binding {
+            return Err(ScramError::Protocol("unexpected channel binding"));
+        }
+        let nonce = attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("r="))
+            .ok_or(ScramError::Protocol("expected nonce attribute"))?;
+        if nonce != self.nonce {
+            return Err(ScramError::Protocol("unexpected nonce"));
+        }
+
+        let auth_message = format!(
+            "{},{},{}",
+            self.client_first_bare, self.server_first_message, without_proof
+        );
+        let client_signature = hmac(&self.secret.stored_key, auth_message.as_bytes());
+        if proof.len() != KEY_LEN {
+            return Err(ScramError::Protocol("malformed proof"));
+        }
+        let client_key: Vec<_> = proof
+            .iter()
+            .zip(client_signature)
+            .map(|(p, s)| p ^ s)
+            .collect();
+        let stored_key = Sha256::digest(client_key);
+        if !bool::from(self.secret.stored_key[..].ct_eq(&stored_key[..])) {
+            return Err(ScramError::InvalidProof);
+        }
+
+        let server_signature = hmac(&self.secret.server_key, auth_message.as_bytes());
+        Ok(format!("v={}", base64::encode(server_signature)))
+    }
+}
+
+/// An error during SCRAM authentication.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScramError {
+    /// The client sent a message that does not follow the protocol.
+    Protocol(&'static str),
+    /// The client's proof does not match the verifier.
+    InvalidProof,
+    /// The string is not a SCRAM-SHA-256 verifier.
+    InvalidSecret,
+}
+
+impl fmt::Display for ScramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScramError::Protocol(e) => write!(f, "invalid SCRAM exchange: {e}"),
+            ScramError::InvalidProof => f.write_str("invalid password"),
+            ScramError::InvalidSecret => f.write_str("invalid SCRAM-SHA-256 verifier"),
+        }
+    }
+}
+
+impl Error for ScramError {}
+
+fn is_printable(c: u8) -> bool {
+    (0x21..=0x7e).contains(&c) && c != b','
+}
+
+fn hmac(key: &[u8], message: &[u8]) -> Key {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key size");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// Computes PBKDF2-HMAC-SHA-256 with a single block of output, which RFC 5802 calls `Hi`.
+fn salted_password(password: &[u8], salt: &[u8], iterations: u32) -> Key {
+    let mut mac = Hmac::<Sha256>::new_from_slice(password).expect("HMAC accepts any key size");
+    mac.update(salt);
+    mac.update(&1u32.to_be_bytes());
+    let mut u: Key = mac.finalize().into_bytes().into();
+    let mut result = u;
+    for _ in 1..iterations {
+        u = hmac(password, &u);
+        for (r, u) in result.iter_mut().zip(u) {
+            *r ^= u;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn test_rfc_7677_exchange() {
+        // The example exchange from section 3 of RFC 7677.
+        let salt = base64::decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap();
+        let secret = ScramSecret::with_salt("pencil", salt, 4096);
+        let (exchange, server_first) = secret
+            .start_exchange_with_nonce(
+                b"n,,n=user,r=rOprNGfwEbeRWgbNEkqO",
+                "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0",
+            )
+            .unwrap();
+        assert_eq!(
+            server_first,
+            "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096"
+        );
+        let server_final = exchange
+            .finish(b"c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=")
+            .unwrap();
+        assert_eq!(
+            server_final,
+            "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4="
+        );
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn test_secret() {
+        let secret = ScramSecret::new("hunter2");
+        let parsed: ScramSecret = secret.to_string().parse().unwrap();
+        assert_eq!(parsed, secret);
+        assert!(parsed.verify_password("hunter2"));
+        assert!(!parsed.verify_password("hunter3"));
+        assert!(!ScramSecret::mock("").verify_password(""));
+
+        for invalid in [
+            "hunter2",
+            "md5a3556571e93b0d20722ba62be61e8c2d",
+            "SCRAM-SHA-256$0:c2FsdA==$AAAA:AAAA",
+            "SCRAM-SHA-256$4096:c2FsdA==$AAAA",
+        ] {
+            assert_eq!(
+                invalid.parse::<ScramSecret>(),
+                Err(ScramError::InvalidSecret)
+            );
+        }
+
+        let (exchange, _) = secret.clone().start_exchange(b"n,,n=,r=abc").unwrap();
+        assert_eq!(
+            exchange.finish(b"c=biws,r=abc,p=AAAA"),
+            Err(ScramError::Protocol("unexpected nonce"))
+        );
+        assert_eq!(
+            secret
+                .start_exchange(b"p=tls-server-end-point,,n=,r=abc")
+                .unwrap_err(),
+            ScramError::Protocol("channel binding is not supported"),
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_mock_secret() {
+        // Mock exchanges for the same user must be indistinguishable from exchanges against a real
+        // verifier, whose salt doesn't change between attempts.
+        let salt = |user: &str| {
+            let client_first_message = format!("n,,n={user},r=abc");
+            let (_, server_first) = ScramSecret::mock(user)
+                .start_exchange(client_first_message.as_bytes())
+                .unwrap();
+            let (_, attrs) = server_first.split_once(",s=").unwrap();
+            attrs.split_once(',').unwrap().0.to_string()
+        };
+        assert_eq!(salt("alice"), salt("alice"));
+        assert_ne!(salt("alice"), salt("bob"));
+        assert_eq!(
+            base64::decode(salt("alice")).unwrap().len(),
+            SALT_LEN,
+            "mock salts look like real ones"
+        );
+
+        // Different servers derive different salts.
+        assert_ne!(
+            ScramSecret::mock_with_nonce("alice", &[0; KEY_LEN]).salt,
+            ScramSecret::mock_with_nonce("alice", &[1; KEY_LEN]).salt,
+        );
+    }
+}
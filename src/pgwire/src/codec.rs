@@ -232,6 +232,9 @@ impl Encoder<BackendMessage> for Codec {
         let byte = match &msg {
             BackendMessage::AuthenticationOk => b'R',
             BackendMessage::AuthenticationCleartextPassword => b'R',
+            BackendMessage::AuthenticationSasl { .. } => b'R',
+            BackendMessage::AuthenticationSaslContinue(_) => b'R',
+            BackendMessage::AuthenticationSaslFinal(_) => b'R',
             BackendMessage::RowDescription(_) => b'T',
             BackendMessage::DataRow(_) => b'D',
             BackendMessage::CommandComplete { .. } => b'C',
@@ -289,6 +292,21 @@ impl Encoder<BackendMessage> for Codec {
             BackendMessage::AuthenticationCleartextPassword => {
                 dst.put_u32(3);
             }
+            BackendMessage::AuthenticationSasl { mechanisms } => {
+                dst.put_u32(10);
+                for mechanism in mechanisms {
+                    dst.put_string(mechanism);
+                }
+                dst.put_u8(b'\0');
+            }
+            BackendMessage::AuthenticationSaslContinue(data) => {
+                dst.put_u32(11);
+                dst.put_slice(data.as_bytes());
+            }
+            BackendMessage::AuthenticationSaslFinal(data) => {
+                dst.put_u32(12);
+                dst.put_slice(data.as_bytes());
+            }
             BackendMessage::RowDescription(fields) => {
                 dst.put_length_i16(fields.len())?;
                 for f in &fields {
@@ -446,7 +464,7 @@ impl Decoder for Codec {
                         b'X' => decode_terminate(buf)?,
 
                         // Authentication.
-                        b'p' => decode_password(buf)?,
+                        b'p' => decode_raw_authentication(buf, frame_len)?,
 
                         // Copy from flow.
                         b'f' => decode_copy_fail(buf)?,
@@ -475,10 +493,35 @@ fn decode_terminate(mut _buf: Cursor) -> Result<FrontendMessage, io::Error> {
     Ok(FrontendMessage::Terminate)
 }
 
-fn decode_password(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
-    Ok(FrontendMessage::Password {
-        password: buf.read_cstr()?.to_owned(),
-    })
+fn decode_raw_authentication(
+    mut buf: Cursor,
+    frame_len: usize,
+) -> Result<FrontendMessage, io::Error> {
+    let mut data = Vec::with_capacity(frame_len);
+    for _ in 0..frame_len {
+        data.push(buf.read_byte()?);
+    }
+    Ok(FrontendMessage::RawAuthentication(data))
+}
+
+/// Decodes the contents of a [`FrontendMessage::RawAuthentication`] that
+/// responds to a request for a cleartext password.
+pub fn decode_password(mut buf: Cursor) -> Result<String, io::Error> {
+    Ok(buf.read_cstr()?.to_owned())
+}
+
+/// Decodes the contents of a [`FrontendMessage::RawAuthentication`] that
+/// starts a SASL exchange, returning the selected mechanism and the client's
+/// initial response.
+pub fn decode_sasl_initial_response(mut buf: Cursor) -> Result<(String, Vec<u8>), io::Error> {
+    let mechanism = buf.read_cstr()?.to_owned();
+    // A length of -1 indicates that there is no initial response.
+    let len = buf.read_i32()?;
+    let mut response = vec![];
+    for _ in 0..len {
+        response.push(buf.read_byte()?);
+    }
+    Ok((mechanism, response))
 }
 
 fn decode_query(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
@@ -21,6 +21,11 @@ use mz_repr::{ColumnName, RelationDesc};
 pub enum BackendMessage {
     AuthenticationOk,
     AuthenticationCleartextPassword,
+    AuthenticationSasl {
+        mechanisms: Vec<&'static str>,
+    },
+    AuthenticationSaslContinue(String),
+    AuthenticationSaslFinal(String),
     CommandComplete {
         tag: String,
     },
@@ -27,13 +27,15 @@ use mz_adapter::{
     PeekResponseUnary, RowsFuture,
 };
 use mz_frontegg_auth::Authenticator as FronteggAuthentication;
+use mz_oidc_auth::Authenticator as OidcAuthentication;
 use mz_ore::cast::CastFrom;
 use mz_ore::netio::AsyncReady;
 use mz_ore::str::StrExt;
 use mz_ore::{assert_none, assert_ok, instrument};
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
 use mz_pgwire_common::{
-    ConnectionCounter, ErrorResponse, Format, FrontendMessage, Severity, VERSIONS, VERSION_3,
+    ConnectionCounter, Cursor, ErrorResponse, Format, FrontendMessage, ScramError, ScramSecret,
+    Severity, SCRAM_SHA_256, VERSIONS, VERSION_3,
 };
 use mz_repr::{
     CatalogItemId, Datum, RelationDesc, RelationType, RowArena, RowIterator, RowRef, ScalarType,
@@ -55,7 +57,7 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, debug_span, warn, Instrument};
 use uuid::Uuid;
 
-use crate::codec::FramedConn;
+use crate::codec::{decode_password, decode_sasl_initial_response, FramedConn};
 use crate::message::{self, BackendMessage};
 
 /// Reports whether the given stream begins with a pgwire handshake.
@@ -94,6 +96,10 @@ pub struct RunParams<'a, A> {
     pub params: BTreeMap<String, String>,
     /// Frontegg authentication.
     pub frontegg: Option<&'a FronteggAuthentication>,
+    /// OIDC authentication.
+    pub oidc: Option<&'a OidcAuthentication>,
+    /// Whether password authentication is enabled.
+    pub password_auth: bool,
     /// Whether this is an internal server that permits access to restricted
     /// system resources.
     pub internal: bool,
@@ -122,6 +128,8 @@ pub async fn run<'a, A>(
         version,
         mut params,
         frontegg,
+        oidc,
+        password_auth,
         internal,
         active_connection_counter,
         helm_chart_version,
@@ -164,19 +172,13 @@ where
     }
 
     let (mut session, expired) = if let Some(frontegg) = frontegg {
-        conn.send(BackendMessage::AuthenticationCleartextPassword)
-            .await?;
-        conn.flush().await?;
-        let password = match conn.recv().await? {
-            Some(FrontendMessage::Password { password }) => password,
-            _ => {
-                return conn
-                    .send(ErrorResponse::fatal(
-                        SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
-                        "expected Password message",
-                    ))
-                    .await
-            }
+        let Some(password) = recv_cleartext_password(conn).await? else {
+            return conn
+                .send(ErrorResponse::fatal(
+                    SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+                    "expected Password message",
+                ))
+                .await;
         };
 
         let auth_response = frontegg.authenticate(&user, &password).await;
@@ -198,7 +200,42 @@ where
                     helm_chart_version,
                 });
                 let expired = async move { auth_session.expired().await };
-                (session, expired.left_future())
+                (session, expired.left_future().left_future())
+            }
+            Err(err) => {
+                warn!(?err, "pgwire connection failed authentication");
+                return conn
+                    .send(ErrorResponse::fatal(
+                        SqlState::INVALID_PASSWORD,
+                        "invalid password",
+                    ))
+                    .await;
+            }
+        }
+    } else if let Some(oidc) = oidc {
+        // The client presents a JWT issued by the OIDC provider, or a refresh
+        // token to exchange for one, as its password.
+        let Some(token) = recv_cleartext_password(conn).await? else {
+            return conn
+                .send(ErrorResponse::fatal(
+                    SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+                    "expected Password message",
+                ))
+                .await;
+        };
+
+        match oidc.authenticate(&user, &token).await {
+            Ok(mut auth_session) => {
+                let session = adapter_client.new_session(SessionConfig {
+                    conn_id: conn.conn_id().clone(),
+                    uuid: conn_uuid,
+                    user: auth_session.user().into(),
+                    client_ip: conn.peer_addr().clone(),
+                    external_metadata_rx: Some(auth_session.external_metadata_rx()),
+                    helm_chart_version,
+                });
+                let expired = async move { auth_session.expired().await };
+                (session, expired.right_future().left_future())
             }
             Err(err) => {
                 warn!(?err, "pgwire connection failed authentication");
@@ -211,6 +248,11 @@ where
             }
         }
     } else {
+        if password_auth {
+            if let Err(err) = authenticate_with_password(conn, &adapter_client, &user).await? {
+                return conn.send(err).await;
+            }
+        }
         let session = adapter_client.new_session(SessionConfig {
             conn_id: conn.conn_id().clone(),
             uuid: conn_uuid,
@@ -219,7 +261,7 @@ where
             external_metadata_rx: None,
             helm_chart_version,
         });
-        // No frontegg check, so auth session lasts indefinitely.
+        // No frontegg or OIDC check, so auth session lasts indefinitely.
         let auth_session = pending().right_future();
         (session, auth_session)
     };
@@ -409,6 +451,103 @@ fn split_options(value: &str) -> Vec<String> {
     strs
 }
 
+/// Requests a cleartext password from the client.
+///
+/// Returns `None` if the client responds with anything but a password.
+async fn recv_cleartext_password<A>(conn: &mut FramedConn<A>) -> Result<Option<String>, io::Error>
+where
+    A: AsyncRead + AsyncWrite + AsyncReady + Send + Sync + Unpin,
+{
+    conn.send(BackendMessage::AuthenticationCleartextPassword)
+        .await?;
+    conn.flush().await?;
+    let password = match conn.recv().await? {
+        Some(FrontendMessage::RawAuthentication(data)) => decode_password(Cursor::new(&data)).ok(),
+        _ => None,
+    };
+    Ok(password)
+}
+
+/// Authenticates `user` via a SCRAM-SHA-256 exchange against the password that
+/// is stored for the role in the catalog.
+///
+/// Returns the error to report to the client if authentication fails.
+async fn authenticate_with_password<A>(
+    conn: &mut FramedConn<A>,
+    adapter_client: &mz_adapter::Client,
+    user: &str,
+) -> Result<Result<(), ErrorResponse>, io::Error>
+where
+    A: AsyncRead + AsyncWrite + AsyncReady + Send + Sync + Unpin,
+{
+    let protocol_violation = |msg: String| ErrorResponse::fatal(SqlState::PROTOCOL_VIOLATION, msg);
+
+    conn.send(BackendMessage::AuthenticationSasl {
+        mechanisms: vec![SCRAM_SHA_256],
+    })
+    .await?;
+    conn.flush().await?;
+    let initial_response = match conn.recv().await? {
+        Some(FrontendMessage::RawAuthentication(data)) => {
+            decode_sasl_initial_response(Cursor::new(&data)).ok()
+        }
+        _ => None,
+    };
+    let Some((mechanism, client_first_message)) = initial_response else {
+        return Ok(Err(protocol_violation(
+            "expected SASLInitialResponse message".into(),
+        )));
+    };
+    if mechanism != SCRAM_SHA_256 {
+        return Ok(Err(protocol_violation(format!(
+            "client selected an invalid SASL authentication mechanism: {}",
+            mechanism.quoted()
+        ))));
+    }
+
+    // Roles that don't exist or can't log in with a password are
+    // authenticated against a mock verifier, so that the exchange doesn't
+    // reveal which roles exist.
+    let secret = adapter_client
+        .get_role_password(user)
+        .await
+        .unwrap_or_else(|| ScramSecret::mock(user));
+    let (exchange, server_first_message) = match secret.start_exchange(&client_first_message) {
+        Ok(res) => res,
+        Err(err) => return Ok(Err(protocol_violation(err.to_string()))),
+    };
+    conn.send(BackendMessage::AuthenticationSaslContinue(
+        server_first_message,
+    ))
+    .await?;
+    conn.flush().await?;
+    let client_final_message = match conn.recv().await? {
+        Some(FrontendMessage::RawAuthentication(data)) => data,
+        _ => {
+            return Ok(Err(protocol_violation(
+                "expected SASLResponse message".into(),
+            )))
+        }
+    };
+    match exchange.finish(&client_final_message) {
+        Ok(server_final_message) => {
+            conn.send(BackendMessage::AuthenticationSaslFinal(
+                server_final_message,
+            ))
+            .await?;
+            Ok(Ok(()))
+        }
+        Err(ScramError::InvalidProof) => {
+            warn!(user, "pgwire connection failed password authentication");
+            Ok(Err(ErrorResponse::fatal(
+                SqlState::INVALID_PASSWORD,
+                format!("password authentication failed for user {}", user.quoted()),
+            )))
+        }
+        Err(err) => Ok(Err(protocol_violation(err.to_string()))),
+    }
+}
+
 #[derive(Debug)]
 enum State {
     Ready,
@@ -579,7 +718,8 @@ where
             Some(FrontendMessage::CopyData(_))
             | Some(FrontendMessage::CopyDone)
             | Some(FrontendMessage::CopyFail(_))
-            | Some(FrontendMessage::Password { .. }) => State::Drain,
+            | Some(FrontendMessage::Password { .. })
+            | Some(FrontendMessage::RawAuthentication(_)) => State::Drain,
             None => State::Done,
         };
 
@@ -772,12 +912,20 @@ where
                 },
                 Err(_) if oid == 0 => param_types.push(None),
                 Err(e) => {
-                    return self
-                        .error(ErrorResponse::error(
-                            SqlState::PROTOCOL_VIOLATION,
-                            e.to_string(),
-                        ))
-                        .await;
+                    // User-defined types, like enums, are only known to the
+                    // catalog.
+                    let catalog = self.adapter_client.catalog_snapshot().await;
+                    match catalog.resolve_user_type_oid(oid) {
+                        Some(ty) => param_types.push(Some(ty)),
+                        None => {
+                            return self
+                                .error(ErrorResponse::error(
+                                    SqlState::PROTOCOL_VIOLATION,
+                                    e.to_string(),
+                                ))
+                                .await;
+                        }
+                    }
                 }
             }
         }
@@ -1779,6 +1927,7 @@ where
             | ExecuteResponse::CreatedSink { .. }
             | ExecuteResponse::CreatedSource { .. }
             | ExecuteResponse::CreatedTable { .. }
+            | ExecuteResponse::CreatedFunction
             | ExecuteResponse::CreatedType
             | ExecuteResponse::CreatedView { .. }
             | ExecuteResponse::CreatedViews { .. }
@@ -1793,6 +1942,7 @@ where
             | ExecuteResponse::GrantedPrivilege
             | ExecuteResponse::GrantedRole
             | ExecuteResponse::Inserted(..)
+            | ExecuteResponse::Merged(..)
             | ExecuteResponse::Copied(..)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
@@ -2011,8 +2161,8 @@ where
                 CopyFormatParams::Csv(CopyCsvFormatParams::default()),
                 Format::Text,
             ),
-            CopyFormat::Parquet => {
-                let text = "Parquet format is not supported".to_string();
+            CopyFormat::Parquet | CopyFormat::Avro | CopyFormat::Orc | CopyFormat::Json { .. } => {
+                let text = format!("{format:?} format is not supported");
                 return self
                     .error(ErrorResponse::error(SqlState::INTERNAL_ERROR, text.clone()))
                     .await
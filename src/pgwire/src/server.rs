@@ -15,6 +15,7 @@ use std::str::FromStr;
 use anyhow::Context;
 use async_trait::async_trait;
 use mz_frontegg_auth::Authenticator as FronteggAuthentication;
+use mz_oidc_auth::Authenticator as OidcAuthentication;
 use mz_pgwire_common::{
     decode_startup, Conn, ConnectionCounter, FrontendStartupMessage, ACCEPT_SSL_ENCRYPTION,
     CONN_UUID_KEY, MZ_FORWARDED_FOR_KEY, REJECT_ENCRYPTION,
@@ -48,6 +49,18 @@ pub struct Config {
     /// a valid Frontegg API token as a password to authenticate. Otherwise,
     /// password authentication is disabled.
     pub frontegg: Option<FronteggAuthentication>,
+    /// The OIDC authentication configuration.
+    ///
+    /// If present, OIDC authentication is enabled, and users may present a
+    /// valid JWT issued by the OIDC provider as a password to authenticate.
+    /// Cannot be combined with Frontegg or password authentication.
+    pub oidc: Option<OidcAuthentication>,
+    /// Whether password authentication is enabled.
+    ///
+    /// If true, users must authenticate with the password of a role that has
+    /// the `LOGIN` attribute via SCRAM-SHA-256. Cannot be combined with
+    /// Frontegg authentication.
+    pub password_auth: bool,
     /// The registry entries that the pgwire server uses to report metrics.
     pub metrics: MetricsConfig,
     /// Whether this is an internal server that permits access to restricted
@@ -64,6 +77,8 @@ pub struct Server {
     tls: Option<ReloadingTlsConfig>,
     adapter_client: mz_adapter::Client,
     frontegg: Option<FronteggAuthentication>,
+    oidc: Option<OidcAuthentication>,
+    password_auth: bool,
     metrics: Metrics,
     internal: bool,
     active_connection_counter: ConnectionCounter,
@@ -89,6 +104,8 @@ impl Server {
             tls: config.tls,
             adapter_client: config.adapter_client,
             frontegg: config.frontegg,
+            oidc: config.oidc,
+            password_auth: config.password_auth,
             metrics: Metrics::new(config.metrics, config.label),
             internal: config.internal,
             active_connection_counter: config.active_connection_counter,
@@ -103,6 +120,8 @@ impl Server {
     ) -> impl Future<Output = Result<(), anyhow::Error>> + 'static + Send {
         let adapter_client = self.adapter_client.clone();
         let frontegg = self.frontegg.clone();
+        let oidc = self.oidc.clone();
+        let password_auth = self.password_auth;
         let tls = self.tls.clone();
         let internal = self.internal;
         let metrics = self.metrics.clone();
@@ -176,6 +195,8 @@ impl Server {
                                     version,
                                     params,
                                     frontegg: frontegg.as_ref(),
+                                    oidc: oidc.as_ref(),
+                                    password_auth,
                                     internal,
                                     active_connection_counter,
                                     helm_chart_version,
@@ -21,7 +21,9 @@ pub mod desc;
 #[cfg(feature = "schemas")]
 pub mod schemas;
 #[cfg(feature = "schemas")]
-pub use schemas::{get_schemas, publication_info};
+pub use schemas::{
+    get_enum_types, get_schemas, get_type_names, publication_info, PostgresEnumDesc,
+};
 #[cfg(feature = "tunnel")]
 pub mod tunnel;
 #[cfg(feature = "tunnel")]
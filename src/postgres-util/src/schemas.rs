@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeMap;
+
 use anyhow::anyhow;
 use tokio_postgres::types::Oid;
 use tokio_postgres::Client;
@@ -191,3 +193,59 @@ pub async fn publication_info(
 
     Ok(table_infos)
 }
+
+/// The name and labels of an enum type in an upstream Postgres database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostgresEnumDesc {
+    /// The unqualified name of the type.
+    pub name: String,
+    /// The type's labels, in sort order.
+    pub labels: Vec<String>,
+}
+
+/// Fetches the enum types among `type_oids` from an upstream Postgres
+/// database. OIDs that do not refer to enum types are omitted from the result.
+pub async fn get_enum_types(
+    client: &Client,
+    type_oids: &[Oid],
+) -> Result<BTreeMap<Oid, PostgresEnumDesc>, PostgresError> {
+    let rows = client
+        .query(
+            "SELECT t.oid, t.typname, e.enumlabel
+            FROM pg_catalog.pg_type AS t
+                JOIN pg_catalog.pg_enum AS e ON e.enumtypid = t.oid
+            WHERE t.oid = ANY($1)
+            ORDER BY t.oid, e.enumsortorder",
+            &[&type_oids],
+        )
+        .await?;
+
+    let mut enum_types = BTreeMap::new();
+    for row in rows {
+        let oid: Oid = row.get("oid");
+        let desc = enum_types.entry(oid).or_insert_with(|| PostgresEnumDesc {
+            name: row.get("typname"),
+            labels: vec![],
+        });
+        desc.labels.push(row.get("enumlabel"));
+    }
+    Ok(enum_types)
+}
+
+/// Fetches the names of the types among `type_oids` from an upstream Postgres
+/// database, keyed by OID.
+pub async fn get_type_names(
+    client: &Client,
+    type_oids: &[Oid],
+) -> Result<BTreeMap<Oid, String>, PostgresError> {
+    let rows = client
+        .query(
+            "SELECT oid, typname FROM pg_catalog.pg_type WHERE oid = ANY($1)",
+            &[&type_oids],
+        )
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.get("oid"), row.get("typname")))
+        .collect())
+}
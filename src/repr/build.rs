@@ -23,6 +23,7 @@ fn main() {
                 "repr/src/adt/datetime.proto",
                 "repr/src/adt/interval.proto",
                 "repr/src/adt/mz_acl_item.proto",
+                "repr/src/adt/network.proto",
                 "repr/src/adt/numeric.proto",
                 "repr/src/adt/range.proto",
                 "repr/src/adt/regex.proto",
@@ -21,13 +21,17 @@ pub mod array;
 pub mod char;
 pub mod date;
 pub mod datetime;
+pub mod geo;
 pub mod interval;
 pub mod jsonb;
+pub mod jsonpath;
 pub mod mz_acl_item;
+pub mod network;
 pub mod numeric;
 pub mod pg_legacy_name;
 pub mod range;
 pub mod regex;
 pub mod system;
+pub mod text_search;
 pub mod timestamp;
 pub mod varchar;
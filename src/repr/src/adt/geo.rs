@@ -0,0 +1,1726 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Geospatial abstract data types.
+//!
+//! These types are modeled after the `geometry` and `geography` types of the
+//! PostGIS extension to PostgreSQL, and the functions that construct, measure
+//! and relate them. See:
+//!   * <https://postgis.net/docs/reference.html>
+//!   * <https://libgeos.org/specifications/wkb/>
+//!   * <https://datatracker.ietf.org/doc/html/rfc7946>
+//!
+//! Only two-dimensional points, linestrings and polygons, and collections of
+//! each, are supported. Values of both types are stored as [`Datum::Bytes`]s
+//! that hold their canonical extended well-known binary (EWKB) representation,
+//! i.e. the output of [`Geometry::to_ewkb`]. A `geography` value is a
+//! [`Geometry`] whose SRID is [`SRID_WGS84`], and whose coordinates are
+//! longitudes and latitudes in degrees.
+//!
+//! [`Datum::Bytes`]: crate::Datum::Bytes
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error};
+use proptest::prelude::*;
+use serde_json::Value as JsonValue;
+
+/// The SRID of longitudes and latitudes on the WGS 84 spheroid, which is the
+/// only spatial reference system supported for `geography` values.
+pub const SRID_WGS84: i32 = 4326;
+
+/// The greatest SRID that PostGIS accepts.
+pub const MAX_SRID: i32 = 999_999;
+
+/// The mean radius of the earth, in meters, which is used to measure distances
+/// between `geography` values.
+pub const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+/// The number of characters of the geohash of a point when no precision is
+/// requested.
+pub const MAX_GEOHASH_PRECISION: usize = 20;
+
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+const WKB_POINT: u32 = 1;
+const WKB_LINESTRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTIPOINT: u32 = 4;
+const WKB_MULTILINESTRING: u32 = 5;
+const WKB_MULTIPOLYGON: u32 = 6;
+const WKB_GEOMETRYCOLLECTION: u32 = 7;
+
+const EWKB_Z_FLAG: u32 = 0x8000_0000;
+const EWKB_M_FLAG: u32 = 0x4000_0000;
+const EWKB_SRID_FLAG: u32 = 0x2000_0000;
+
+/// The number of decimal digits with which `ST_AsText` writes coordinates.
+const WKT_DECIMAL_DIGITS: usize = 15;
+
+/// The number of decimal digits with which `ST_AsGeoJSON` writes coordinates.
+const GEOJSON_DECIMAL_DIGITS: usize = 9;
+
+/// A two-dimensional coordinate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coord {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// The shape of a [`Geometry`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// A point, or `None` for the empty point.
+    Point(Option<Coord>),
+    /// A linestring, which is empty or has at least two points.
+    LineString(Vec<Coord>),
+    /// A polygon, described by its exterior ring followed by its holes, if it
+    /// is not empty. Each ring is closed and has at least four points.
+    Polygon(Vec<Vec<Coord>>),
+    MultiPoint(Vec<Coord>),
+    MultiLineString(Vec<Vec<Coord>>),
+    MultiPolygon(Vec<Vec<Vec<Coord>>>),
+}
+
+impl Shape {
+    /// Returns the name of the shape's type, as used in its well-known text
+    /// representation.
+    fn wkt_name(&self) -> &'static str {
+        match self {
+            Shape::Point(_) => "POINT",
+            Shape::LineString(_) => "LINESTRING",
+            Shape::Polygon(_) => "POLYGON",
+            Shape::MultiPoint(_) => "MULTIPOINT",
+            Shape::MultiLineString(_) => "MULTILINESTRING",
+            Shape::MultiPolygon(_) => "MULTIPOLYGON",
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Shape::Point(point) => point.is_none(),
+            Shape::LineString(points) | Shape::MultiPoint(points) => points.is_empty(),
+            Shape::Polygon(rings) | Shape::MultiLineString(rings) => rings.is_empty(),
+            Shape::MultiPolygon(polygons) => polygons.is_empty(),
+        }
+    }
+
+    /// Returns the topological dimension of the shape: 0 for points, 1 for
+    /// linestrings and 2 for polygons.
+    fn dimension(&self) -> u8 {
+        match self {
+            Shape::Point(_) | Shape::MultiPoint(_) => 0,
+            Shape::LineString(_) | Shape::MultiLineString(_) => 1,
+            Shape::Polygon(_) | Shape::MultiPolygon(_) => 2,
+        }
+    }
+
+    fn for_each_coord_mut(&mut self, mut f: impl FnMut(&mut Coord)) {
+        match self {
+            Shape::Point(point) => point.iter_mut().for_each(f),
+            Shape::LineString(points) | Shape::MultiPoint(points) => points.iter_mut().for_each(f),
+            Shape::Polygon(rings) | Shape::MultiLineString(rings) => {
+                rings.iter_mut().flatten().for_each(f)
+            }
+            Shape::MultiPolygon(polygons) => {
+                for coord in polygons.iter_mut().flatten().flatten() {
+                    f(coord);
+                }
+            }
+        }
+    }
+
+    /// Returns all of the shape's coordinates.
+    fn coords(&self) -> Vec<Coord> {
+        let mut coords = vec![];
+        self.clone().for_each_coord_mut(|c| coords.push(*c));
+        coords
+    }
+
+    /// Returns the shape's isolated points, i.e. those that are not vertices
+    /// of a linestring or polygon.
+    fn points(&self) -> &[Coord] {
+        match self {
+            Shape::Point(point) => point.as_slice(),
+            Shape::MultiPoint(points) => points,
+            _ => &[],
+        }
+    }
+
+    /// Returns the shape's linestrings.
+    fn lines(&self) -> Vec<&[Coord]> {
+        match self {
+            Shape::LineString(points) if !points.is_empty() => vec![points],
+            Shape::MultiLineString(lines) => lines.iter().map(|l| l.as_slice()).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the shape's polygons.
+    fn polygons(&self) -> Vec<&[Vec<Coord>]> {
+        match self {
+            Shape::Polygon(rings) if !rings.is_empty() => vec![rings],
+            Shape::MultiPolygon(polygons) => polygons.iter().map(|p| p.as_slice()).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the segments of the shape's linestrings and polygon rings.
+    fn segments(&self) -> Vec<(Coord, Coord)> {
+        let rings = self.polygons().into_iter().flatten().map(|r| r.as_slice());
+        self.lines()
+            .into_iter()
+            .chain(rings)
+            .flat_map(|points| points.windows(2).map(|w| (w[0], w[1])))
+            .collect()
+    }
+
+    /// Determines where `p` lies relative to the shape.
+    fn locate(&self, p: Coord) -> Location {
+        let mut location = Location::Exterior;
+        for point in self.points() {
+            if *point == p {
+                return Location::Interior;
+            }
+        }
+        for line in self.lines() {
+            if !line.windows(2).any(|w| on_segment(p, w[0], w[1])) {
+                continue;
+            }
+            let closed = line.first() == line.last();
+            if !closed && (line.first() == Some(&p) || line.last() == Some(&p)) {
+                location = Location::Boundary;
+            } else {
+                return Location::Interior;
+            }
+        }
+        for polygon in self.polygons() {
+            match locate_in_polygon(p, polygon) {
+                Location::Interior => return Location::Interior,
+                Location::Boundary => location = Location::Boundary,
+                Location::Exterior => (),
+            }
+        }
+        location
+    }
+}
+
+/// The location of a point relative to a shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Location {
+    Interior,
+    Boundary,
+    Exterior,
+}
+
+/// A geometry with a spatial reference system, i.e. the PostGIS `geometry`
+/// type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geometry {
+    /// The spatial reference system identifier, which is 0 if the spatial
+    /// reference system is unknown.
+    srid: i32,
+    shape: Shape,
+}
+
+impl Geometry {
+    /// Constructs a geometry, validating its SRID and shape.
+    pub fn new(srid: i32, mut shape: Shape) -> Result<Geometry, Error> {
+        if !(0..=MAX_SRID).contains(&srid) {
+            bail!("SRID must be between 0 and {MAX_SRID}, got {srid}");
+        }
+        let mut finite = true;
+        shape.for_each_coord_mut(|c| {
+            finite &= c.x.is_finite() && c.y.is_finite();
+            // Normalize negative zeros, so that equal geometries have equal
+            // encodings.
+            c.x += 0.0;
+            c.y += 0.0;
+        });
+        if !finite {
+            bail!("coordinates must be finite");
+        }
+        let valid_line = |points: &Vec<Coord>| points.len() >= 2;
+        let valid_polygon = |rings: &Vec<Vec<Coord>>| {
+            rings
+                .iter()
+                .all(|ring| ring.len() >= 4 && ring.first() == ring.last())
+        };
+        let valid = match &shape {
+            Shape::Point(_) | Shape::MultiPoint(_) => true,
+            Shape::LineString(points) => points.is_empty() || valid_line(points),
+            Shape::MultiLineString(lines) => lines.iter().all(valid_line),
+            Shape::Polygon(rings) => valid_polygon(rings),
+            Shape::MultiPolygon(polygons) => polygons
+                .iter()
+                .all(|rings| !rings.is_empty() && valid_polygon(rings)),
+        };
+        if !valid {
+            bail!(
+                "{} requires more points or has non-closed rings",
+                shape.wkt_name()
+            );
+        }
+        Ok(Geometry { srid, shape })
+    }
+
+    /// Constructs a point without a spatial reference system.
+    pub fn point(x: f64, y: f64) -> Result<Geometry, Error> {
+        Geometry::new(0, Shape::Point(Some(Coord { x, y })))
+    }
+
+    /// Returns the geometry's spatial reference system identifier.
+    pub fn srid(&self) -> i32 {
+        self.srid
+    }
+
+    /// Returns the geometry's shape.
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Returns the geometry with its spatial reference system identifier
+    /// replaced by `srid`, without transforming its coordinates.
+    pub fn with_srid(self, srid: i32) -> Result<Geometry, Error> {
+        Geometry::new(srid, self.shape)
+    }
+
+    /// Converts the geometry into a `geography` value, which requires its
+    /// coordinates to be longitudes and latitudes. A geometry without a
+    /// spatial reference system is assumed to use [`SRID_WGS84`].
+    pub fn into_geography(self) -> Result<Geometry, Error> {
+        if self.srid != 0 && self.srid != SRID_WGS84 {
+            bail!(
+                "only SRID {SRID_WGS84} is supported for geography, got {}",
+                self.srid
+            );
+        }
+        if let Some((min, max)) = self.bounding_box() {
+            if min.x < -180.0 || max.x > 180.0 || min.y < -90.0 || max.y > 90.0 {
+                bail!("coordinate values are out of range [-180 -90, 180 90] for geography");
+            }
+        }
+        Ok(Geometry {
+            srid: SRID_WGS84,
+            shape: self.shape,
+        })
+    }
+
+    /// Reports whether the geometry has no points.
+    pub fn is_empty(&self) -> bool {
+        self.shape.is_empty()
+    }
+
+    /// Returns the name of the geometry's type as reported by
+    /// `ST_GeometryType`, e.g. `ST_Point`.
+    pub fn type_name(&self) -> &'static str {
+        match self.shape {
+            Shape::Point(_) => "ST_Point",
+            Shape::LineString(_) => "ST_LineString",
+            Shape::Polygon(_) => "ST_Polygon",
+            Shape::MultiPoint(_) => "ST_MultiPoint",
+            Shape::MultiLineString(_) => "ST_MultiLineString",
+            Shape::MultiPolygon(_) => "ST_MultiPolygon",
+        }
+    }
+
+    /// Returns the coordinates of the geometry if it is a non-empty point.
+    pub fn as_point(&self) -> Result<Option<Coord>, Error> {
+        match self.shape {
+            Shape::Point(point) => Ok(point),
+            _ => bail!("argument must be a point, got {}", self.type_name()),
+        }
+    }
+
+    /// Returns the lower left and upper right corners of the geometry's
+    /// bounding box, if it is not empty.
+    pub fn bounding_box(&self) -> Option<(Coord, Coord)> {
+        let coords = self.shape.coords();
+        let first = *coords.first()?;
+        Some(coords.iter().fold((first, first), |(min, max), c| {
+            (
+                Coord {
+                    x: min.x.min(c.x),
+                    y: min.y.min(c.y),
+                },
+                Coord {
+                    x: max.x.max(c.x),
+                    y: max.y.max(c.y),
+                },
+            )
+        }))
+    }
+
+    fn check_srid(&self, other: &Geometry) -> Result<(), Error> {
+        if self.srid != other.srid {
+            bail!(
+                "operation on mixed SRID geometries ({}, {}) != ({}, {})",
+                self.type_name(),
+                self.srid,
+                other.type_name(),
+                other.srid
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the minimum cartesian distance between the geometries, or
+    /// `None` if either is empty.
+    pub fn distance(&self, other: &Geometry) -> Result<Option<f64>, Error> {
+        self.check_srid(other)?;
+        Ok(self.min_distance(other, planar_distance, planar_segment_distance))
+    }
+
+    /// Returns the minimum distance in meters between the geographies, or
+    /// `None` if either is empty, measured along great circles of a sphere
+    /// with radius [`EARTH_RADIUS_METERS`].
+    pub fn sphere_distance(&self, other: &Geometry) -> Result<Option<f64>, Error> {
+        self.check_srid(other)?;
+        let distance = self.min_distance(other, sphere_distance, sphere_segment_distance);
+        Ok(distance.map(|d| d * EARTH_RADIUS_METERS))
+    }
+
+    fn min_distance(
+        &self,
+        other: &Geometry,
+        point_distance: fn(Coord, Coord) -> f64,
+        segment_distance: fn(Coord, Coord, Coord) -> f64,
+    ) -> Option<f64> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+        if self.intersects_shape(other) {
+            return Some(0.0);
+        }
+        let (a_points, b_points) = (self.shape.points(), other.shape.points());
+        let (a_segments, b_segments) = (self.shape.segments(), other.shape.segments());
+        let mut min = f64::INFINITY;
+        for p in a_points {
+            for q in b_points {
+                min = min.min(point_distance(*p, *q));
+            }
+            for (q1, q2) in &b_segments {
+                min = min.min(segment_distance(*p, *q1, *q2));
+            }
+        }
+        for (p1, p2) in &a_segments {
+            for q in b_points {
+                min = min.min(segment_distance(*q, *p1, *p2));
+            }
+            // The geometries don't intersect, so the closest points of two
+            // segments include at least one of their endpoints.
+            for (q1, q2) in &b_segments {
+                min = min
+                    .min(segment_distance(*p1, *q1, *q2))
+                    .min(segment_distance(*p2, *q1, *q2))
+                    .min(segment_distance(*q1, *p1, *p2))
+                    .min(segment_distance(*q2, *p1, *p2));
+            }
+        }
+        Some(min)
+    }
+
+    /// Reports whether the geometries share any point.
+    ///
+    /// The edges of `geography` values are treated as straight lines in
+    /// longitude and latitude.
+    pub fn intersects(&self, other: &Geometry) -> Result<bool, Error> {
+        self.check_srid(other)?;
+        Ok(self.intersects_shape(other))
+    }
+
+    fn intersects_shape(&self, other: &Geometry) -> bool {
+        let (a, b) = (&self.shape, &other.shape);
+        if a.is_empty() || b.is_empty() {
+            return false;
+        }
+        if a.coords()
+            .iter()
+            .any(|p| b.locate(*p) != Location::Exterior)
+            || b.coords()
+                .iter()
+                .any(|p| a.locate(*p) != Location::Exterior)
+        {
+            return true;
+        }
+        let b_segments = b.segments();
+        a.segments().iter().any(|(p1, p2)| {
+            b_segments
+                .iter()
+                .any(|(q1, q2)| segments_intersect(*p1, *p2, *q1, *q2))
+        })
+    }
+
+    /// Reports whether no point of `other` lies in the exterior of the
+    /// geometry, and at least one point of the interior of `other` lies in the
+    /// interior of the geometry.
+    pub fn contains(&self, other: &Geometry) -> Result<bool, Error> {
+        self.check_srid(other)?;
+        let (a, b) = (&self.shape, &other.shape);
+        if a.is_empty() || b.is_empty() || b.dimension() > a.dimension() {
+            return Ok(false);
+        }
+
+        // Every vertex of `b`, and every piece of its segments between the
+        // points where they meet `a`, must lie within `a`.
+        let mut interior = false;
+        let a_segments = a.segments();
+        let b_segments = b.segments();
+        let pieces = b_segments
+            .iter()
+            .flat_map(|(p, q)| piece_midpoints(*p, *q, &a_segments, a.points()));
+        for p in b.coords().into_iter().chain(pieces) {
+            match a.locate(p) {
+                Location::Exterior => return Ok(false),
+                Location::Interior => interior = true,
+                Location::Boundary => (),
+            }
+        }
+        if b.dimension() < 2 {
+            return Ok(interior);
+        }
+
+        // The boundary of `a` must not enter the interior of `b`...
+        let pieces = a_segments
+            .iter()
+            .flat_map(|(p, q)| piece_midpoints(*p, *q, &b_segments, &[]));
+        for p in a.coords().into_iter().chain(pieces) {
+            if b.locate(p) == Location::Interior {
+                return Ok(false);
+            }
+        }
+        // ...so the interior of each polygon of `b` lies either entirely
+        // inside or entirely outside of `a`.
+        for polygon in b.polygons() {
+            match interior_point(polygon) {
+                Some(p) if a.locate(p) == Location::Interior => (),
+                _ => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns the geohash of the geometry's bounding box.
+    ///
+    /// If `precision` is `None`, returns the geohash of the smallest cell that
+    /// contains the bounding box, or the geohash with
+    /// [`MAX_GEOHASH_PRECISION`] characters for a point. Otherwise, returns the
+    /// geohash of the center of the bounding box with `precision` characters.
+    pub fn geohash(&self, precision: Option<usize>) -> Result<String, Error> {
+        let (min, max) = self
+            .bounding_box()
+            .ok_or_else(|| anyhow!("cannot compute the geohash of an empty geometry"))?;
+        if min.x < -180.0 || max.x > 180.0 || min.y < -90.0 || max.y > 90.0 {
+            bail!(
+                "geohash requires inputs in decimal degrees, got ({} {}, {} {})",
+                min.x,
+                min.y,
+                max.x,
+                max.y
+            );
+        }
+        match precision {
+            Some(precision) => {
+                let center = Coord {
+                    x: (min.x + max.x) / 2.0,
+                    y: (min.y + max.y) / 2.0,
+                };
+                Ok(encode_geohash(center, precision.min(MAX_GEOHASH_PRECISION)))
+            }
+            None => {
+                let min = encode_geohash(min, MAX_GEOHASH_PRECISION);
+                let max = encode_geohash(max, MAX_GEOHASH_PRECISION);
+                let len = min
+                    .bytes()
+                    .zip(max.bytes())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                Ok(min[..len].to_owned())
+            }
+        }
+    }
+
+    /// Decodes a geometry from its well-known binary (WKB) or extended
+    /// well-known binary (EWKB) representation, in either byte order.
+    pub fn from_ewkb(buf: &[u8]) -> Result<Geometry, Error> {
+        let mut reader = WkbReader {
+            buf,
+            big_endian: false,
+        };
+        let (srid, shape) = reader.read_geometry()?;
+        if !reader.buf.is_empty() {
+            bail!("invalid WKB: unexpected data after geometry");
+        }
+        Geometry::new(srid.unwrap_or(0), shape)
+    }
+
+    /// Returns the geometry's canonical extended well-known binary (EWKB)
+    /// representation, which is little-endian and includes the SRID if it is
+    /// not 0.
+    pub fn to_ewkb(&self) -> Vec<u8> {
+        let srid = (self.srid != 0).then_some(self.srid);
+        let mut buf = vec![];
+        write_wkb(&mut buf, &self.shape, srid);
+        buf
+    }
+
+    /// Returns the geometry's little-endian well-known binary (WKB)
+    /// representation, which omits the SRID.
+    pub fn to_wkb(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        write_wkb(&mut buf, &self.shape, None);
+        buf
+    }
+
+    /// Returns the geometry's well-known text (WKT) representation, e.g.
+    /// `POINT(1 2)`.
+    pub fn to_wkt(&self) -> String {
+        let mut s = self.shape.wkt_name().to_owned();
+        if self.is_empty() {
+            s.push_str(" EMPTY");
+            return s;
+        }
+        let coords = |s: &mut String, points: &[Coord]| {
+            s.push('(');
+            for (i, c) in points.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                s.push_str(&format_number(c.x, WKT_DECIMAL_DIGITS));
+                s.push(' ');
+                s.push_str(&format_number(c.y, WKT_DECIMAL_DIGITS));
+            }
+            s.push(')');
+        };
+        let lists = |s: &mut String, lists: &[Vec<Coord>]| {
+            s.push('(');
+            for (i, points) in lists.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                coords(s, points);
+            }
+            s.push(')');
+        };
+        match &self.shape {
+            Shape::Point(point) => coords(&mut s, point.as_slice()),
+            Shape::LineString(points) => coords(&mut s, points),
+            Shape::Polygon(rings) | Shape::MultiLineString(rings) => lists(&mut s, rings),
+            Shape::MultiPoint(points) => {
+                let points: Vec<_> = points.iter().map(|p| vec![*p]).collect();
+                lists(&mut s, &points);
+            }
+            Shape::MultiPolygon(polygons) => {
+                s.push('(');
+                for (i, rings) in polygons.iter().enumerate() {
+                    if i > 0 {
+                        s.push(',');
+                    }
+                    lists(&mut s, rings);
+                }
+                s.push(')');
+            }
+        }
+        s
+    }
+
+    /// Returns the geometry's extended well-known text (EWKT) representation,
+    /// which prefixes the WKT representation with the SRID if it is not 0,
+    /// e.g. `SRID=4326;POINT(1 2)`.
+    pub fn to_ewkt(&self) -> String {
+        match self.srid {
+            0 => self.to_wkt(),
+            srid => format!("SRID={srid};{}", self.to_wkt()),
+        }
+    }
+
+    /// Returns the geometry's GeoJSON representation, e.g.
+    /// `{"type":"Point","coordinates":[1,2]}`.
+    pub fn to_geojson(&self) -> String {
+        fn coord(s: &mut String, c: &Coord) {
+            s.push('[');
+            s.push_str(&format_number(c.x, GEOJSON_DECIMAL_DIGITS));
+            s.push(',');
+            s.push_str(&format_number(c.y, GEOJSON_DECIMAL_DIGITS));
+            s.push(']');
+        }
+        fn array<T>(s: &mut String, items: &[T], mut f: impl FnMut(&mut String, &T)) {
+            s.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                f(s, item);
+            }
+            s.push(']');
+        }
+        let (name, mut s) = match &self.shape {
+            Shape::Point(_) => ("Point", String::new()),
+            Shape::LineString(_) => ("LineString", String::new()),
+            Shape::Polygon(_) => ("Polygon", String::new()),
+            Shape::MultiPoint(_) => ("MultiPoint", String::new()),
+            Shape::MultiLineString(_) => ("MultiLineString", String::new()),
+            Shape::MultiPolygon(_) => ("MultiPolygon", String::new()),
+        };
+        s.push_str(&format!("{{\"type\":\"{name}\",\"coordinates\":"));
+        match &self.shape {
+            Shape::Point(Some(c)) => coord(&mut s, c),
+            Shape::Point(None) => s.push_str("[]"),
+            Shape::LineString(points) | Shape::MultiPoint(points) => array(&mut s, points, coord),
+            Shape::Polygon(rings) | Shape::MultiLineString(rings) => {
+                array(&mut s, rings, |s, points| array(s, points, coord))
+            }
+            Shape::MultiPolygon(polygons) => array(&mut s, polygons, |s, rings| {
+                array(s, rings, |s, points| array(s, points, coord))
+            }),
+        }
+        s.push('}');
+        s
+    }
+
+    /// Parses a geometry from its GeoJSON representation. The geometry's SRID
+    /// is [`SRID_WGS84`], as GeoJSON coordinates are always longitudes and
+    /// latitudes.
+    pub fn from_geojson(s: &str) -> Result<Geometry, Error> {
+        let value: JsonValue =
+            serde_json::from_str(s).map_err(|e| anyhow!("invalid GeoJSON: {e}"))?;
+        let typ = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| anyhow!("invalid GeoJSON: missing type"))?;
+        if typ == "GeometryCollection" {
+            bail!("geometry collections are not supported");
+        }
+        let coordinates = value
+            .get("coordinates")
+            .ok_or_else(|| anyhow!("invalid GeoJSON: missing coordinates"))?;
+        let shape = match typ {
+            "Point" => match json_array(coordinates)?.is_empty() {
+                true => Shape::Point(None),
+                false => Shape::Point(Some(json_coord(coordinates)?)),
+            },
+            "LineString" => Shape::LineString(json_coords(coordinates)?),
+            "Polygon" => Shape::Polygon(json_list(coordinates, json_coords)?),
+            "MultiPoint" => Shape::MultiPoint(json_coords(coordinates)?),
+            "MultiLineString" => Shape::MultiLineString(json_list(coordinates, json_coords)?),
+            "MultiPolygon" => Shape::MultiPolygon(json_list(coordinates, |polygon| {
+                json_list(polygon, json_coords)
+            })?),
+            _ => bail!("invalid GeoJSON: unknown type \"{typ}\""),
+        };
+        Geometry::new(SRID_WGS84, shape)
+    }
+}
+
+/// Parses a geometry from its well-known text (WKT) representation, its
+/// extended well-known text (EWKT) representation, or its hex-encoded
+/// well-known binary (WKB) or extended well-known binary (EWKB)
+/// representation.
+impl FromStr for Geometry {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Geometry, Error> {
+        let s = s.trim();
+        if s.starts_with('0') {
+            let buf = hex::decode(s).map_err(|_| anyhow!("invalid hex-encoded WKB"))?;
+            return Geometry::from_ewkb(&buf);
+        }
+        let mut parser = WktParser { s: s.as_bytes() };
+        let srid = parser.srid()?;
+        let shape = parser.shape()?;
+        parser.skip_whitespace();
+        if !parser.s.is_empty() {
+            bail!("parse error - invalid geometry");
+        }
+        Geometry::new(srid, shape)
+    }
+}
+
+/// Writes the geometry's canonical text representation, which is its
+/// hex-encoded extended well-known binary (EWKB) representation, as in
+/// PostGIS.
+impl fmt::Display for Geometry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&hex::encode_upper(self.to_ewkb()))
+    }
+}
+
+impl Arbitrary for Geometry {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Geometry>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        // Coordinates are valid longitudes and latitudes, so that the
+        // geometries can also be used as geographies.
+        let coord = (-360..=360i32, -360..=360i32).prop_map(|(x, y)| Coord {
+            x: f64::from(x) / 4.0,
+            y: f64::from(y) / 4.0,
+        });
+        let square = (coord.clone(), 1..=4i32).prop_map(|(c, size)| {
+            let size = f64::from(size);
+            let corner = |dx: f64, dy: f64| Coord {
+                x: (c.x + dx).clamp(-90.0, 90.0),
+                y: (c.y + dy).clamp(-90.0, 90.0),
+            };
+            vec![
+                corner(0.0, 0.0),
+                corner(size, 0.0),
+                corner(size, size),
+                corner(0.0, size),
+                corner(0.0, 0.0),
+            ]
+        });
+        let shape = prop_oneof![
+            proptest::option::of(coord.clone()).prop_map(Shape::Point),
+            proptest::collection::vec(coord.clone(), 2..6).prop_map(Shape::LineString),
+            square.prop_map(|ring| Shape::Polygon(vec![ring])),
+            proptest::collection::vec(coord, 0..4).prop_map(Shape::MultiPoint),
+        ];
+        (prop_oneof![Just(0), Just(SRID_WGS84)], shape)
+            .prop_map(|(srid, shape)| Geometry::new(srid, shape).expect("valid geometry"))
+            .boxed()
+    }
+}
+
+/// Returns the geohash of the cell that `geohash` identifies and of the up to
+/// eight cells of the same size that surround it.
+///
+/// Equi-joining on these cells finds all pairs of points that are closer to
+/// each other than the size of a cell.
+pub fn geohash_neighbors(geohash: &str) -> Result<Vec<String>, Error> {
+    let (min, max) = decode_geohash(geohash)?;
+    let (width, height) = (max.x - min.x, max.y - min.y);
+    let center = Coord {
+        x: (min.x + max.x) / 2.0,
+        y: (min.y + max.y) / 2.0,
+    };
+    let mut neighbors = vec![];
+    for dy in [1.0, 0.0, -1.0] {
+        let y = center.y + dy * height;
+        if !(-90.0..=90.0).contains(&y) {
+            continue;
+        }
+        for dx in [-1.0, 0.0, 1.0] {
+            let mut x = center.x + dx * width;
+            if x > 180.0 {
+                x -= 360.0;
+            } else if x < -180.0 {
+                x += 360.0;
+            }
+            let neighbor = encode_geohash(Coord { x, y }, geohash.len());
+            if !neighbors.contains(&neighbor) {
+                neighbors.push(neighbor);
+            }
+        }
+    }
+    Ok(neighbors)
+}
+
+fn encode_geohash(c: Coord, precision: usize) -> String {
+    let mut ranges = [(-180.0, 180.0), (-90.0, 90.0)];
+    let values = [c.x, c.y];
+    let mut geohash = String::with_capacity(precision);
+    let mut bit = 0;
+    while geohash.len() < precision {
+        let mut index = 0;
+        for _ in 0..5 {
+            // Bits alternate between longitude and latitude, starting with
+            // longitude.
+            let range = &mut ranges[bit % 2];
+            let mid = (range.0 + range.1) / 2.0;
+            index <<= 1;
+            if values[bit % 2] >= mid {
+                index |= 1;
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            bit += 1;
+        }
+        geohash.push(char::from(GEOHASH_ALPHABET[index]));
+    }
+    geohash
+}
+
+/// Returns the lower left and upper right corners of the cell that `geohash`
+/// identifies.
+fn decode_geohash(geohash: &str) -> Result<(Coord, Coord), Error> {
+    if geohash.is_empty() {
+        bail!("invalid geohash: \"\"");
+    }
+    let mut ranges = [(-180.0, 180.0), (-90.0, 90.0)];
+    let mut bit = 0;
+    for c in geohash.bytes() {
+        let index = GEOHASH_ALPHABET
+            .iter()
+            .position(|a| *a == c.to_ascii_lowercase())
+            .ok_or_else(|| anyhow!("invalid geohash: \"{geohash}\""))?;
+        for shift in (0..5).rev() {
+            let range = &mut ranges[bit % 2];
+            let mid = (range.0 + range.1) / 2.0;
+            if index & (1 << shift) != 0 {
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            bit += 1;
+        }
+    }
+    let [(min_x, max_x), (min_y, max_y)] = ranges;
+    Ok((Coord { x: min_x, y: min_y }, Coord { x: max_x, y: max_y }))
+}
+
+/// Formats `n` as the shortest decimal that round-trips, rounded to at most
+/// `digits` decimal digits and without trailing zeros, as PostGIS does.
+fn format_number(n: f64, digits: usize) -> String {
+    let mut s = n.to_string();
+    let decimals = s.split_once('.').map_or(0, |(_, frac)| frac.len());
+    if decimals > digits {
+        s = format!("{n:.digits$}");
+        let len = s.trim_end_matches('0').trim_end_matches('.').len();
+        s.truncate(len);
+    }
+    if s == "-0" {
+        s.remove(0);
+    }
+    s
+}
+
+fn json_array(value: &JsonValue) -> Result<&Vec<JsonValue>, Error> {
+    value
+        .as_array()
+        .ok_or_else(|| anyhow!("invalid GeoJSON: expected an array of coordinates"))
+}
+
+fn json_coord(value: &JsonValue) -> Result<Coord, Error> {
+    match json_array(value)?.as_slice() {
+        [x, y] => match (x.as_f64(), y.as_f64()) {
+            (Some(x), Some(y)) => Ok(Coord { x, y }),
+            _ => bail!("invalid GeoJSON: coordinates must be numbers"),
+        },
+        [_, _, _, ..] => bail!("only two-dimensional geometries are supported"),
+        _ => bail!("invalid GeoJSON: a position requires two coordinates"),
+    }
+}
+
+fn json_coords(value: &JsonValue) -> Result<Vec<Coord>, Error> {
+    json_list(value, json_coord)
+}
+
+fn json_list<T>(
+    value: &JsonValue,
+    f: impl Fn(&JsonValue) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    json_array(value)?.iter().map(f).collect()
+}
+
+/// A parser for the (extended) well-known text representation of geometries.
+struct WktParser<'a> {
+    s: &'a [u8],
+}
+
+impl WktParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while let [c, rest @ ..] = self.s {
+            if !c.is_ascii_whitespace() {
+                break;
+            }
+            self.s = rest;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.s.first().copied()
+    }
+
+    fn consume(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.s = &self.s[1..];
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), Error> {
+        if !self.consume(c) {
+            bail!("parse error - invalid geometry");
+        }
+        Ok(())
+    }
+
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> String {
+        self.skip_whitespace();
+        let len = self.s.iter().take_while(|c| f(**c)).count();
+        let (token, rest) = self.s.split_at(len);
+        self.s = rest;
+        String::from_utf8_lossy(token).to_ascii_uppercase()
+    }
+
+    /// Parses the optional `SRID=<srid>;` prefix of EWKT.
+    fn srid(&mut self) -> Result<i32, Error> {
+        self.skip_whitespace();
+        if self.s.len() < 5 || !self.s[..5].eq_ignore_ascii_case(b"SRID=") {
+            return Ok(0);
+        }
+        self.s = &self.s[5..];
+        let srid = self.take_while(|c| c == b'-' || c.is_ascii_digit());
+        let srid = srid
+            .parse()
+            .map_err(|_| anyhow!("parse error - invalid SRID"))?;
+        self.expect(b';')?;
+        Ok(srid)
+    }
+
+    fn shape(&mut self) -> Result<Shape, Error> {
+        let tag = self.take_while(|c| c.is_ascii_alphabetic());
+        let modifier = self.take_while(|c| c.is_ascii_alphabetic());
+        let empty = match modifier.as_str() {
+            "" => false,
+            "EMPTY" => true,
+            "Z" | "M" | "ZM" => bail!("only two-dimensional geometries are supported"),
+            _ => bail!("parse error - invalid geometry"),
+        };
+        let shape = match tag.as_str() {
+            "POINT" => Shape::Point(match empty {
+                true => None,
+                false => {
+                    self.expect(b'(')?;
+                    let c = self.coord()?;
+                    self.expect(b')')?;
+                    Some(c)
+                }
+            }),
+            "LINESTRING" if empty => Shape::LineString(vec![]),
+            "LINESTRING" => Shape::LineString(self.coords()?),
+            "POLYGON" if empty => Shape::Polygon(vec![]),
+            "POLYGON" => Shape::Polygon(self.list(|p| p.coords())?),
+            "MULTIPOINT" if empty => Shape::MultiPoint(vec![]),
+            // The points of a multipoint may or may not be parenthesized.
+            "MULTIPOINT" => Shape::MultiPoint(self.list(|p| match p.consume(b'(') {
+                true => {
+                    let c = p.coord()?;
+                    p.expect(b')')?;
+                    Ok(c)
+                }
+                false => p.coord(),
+            })?),
+            "MULTILINESTRING" if empty => Shape::MultiLineString(vec![]),
+            "MULTILINESTRING" => Shape::MultiLineString(self.list(|p| p.coords())?),
+            "MULTIPOLYGON" if empty => Shape::MultiPolygon(vec![]),
+            "MULTIPOLYGON" => Shape::MultiPolygon(self.list(|p| p.list(|p| p.coords()))?),
+            "GEOMETRYCOLLECTION" => bail!("geometry collections are not supported"),
+            "POINTZ" | "POINTM" | "POINTZM" | "LINESTRINGZ" | "LINESTRINGM" | "POLYGONZ"
+            | "POLYGONM" => bail!("only two-dimensional geometries are supported"),
+            _ => bail!("parse error - invalid geometry"),
+        };
+        Ok(shape)
+    }
+
+    fn number(&mut self) -> Result<f64, Error> {
+        let number = self
+            .take_while(|c| c.is_ascii_digit() || matches!(c, b'+' | b'-' | b'.' | b'e' | b'E'));
+        number
+            .parse()
+            .map_err(|_| anyhow!("parse error - invalid geometry"))
+    }
+
+    fn coord(&mut self) -> Result<Coord, Error> {
+        let x = self.number()?;
+        let y = self.number()?;
+        if matches!(self.peek(), Some(c) if c.is_ascii_digit() || matches!(c, b'+' | b'-' | b'.')) {
+            bail!("only two-dimensional geometries are supported");
+        }
+        Ok(Coord { x, y })
+    }
+
+    fn coords(&mut self) -> Result<Vec<Coord>, Error> {
+        self.list(|p| p.coord())
+    }
+
+    /// Parses a parenthesized, comma-separated list of items.
+    fn list<T>(&mut self, f: impl Fn(&mut Self) -> Result<T, Error>) -> Result<Vec<T>, Error> {
+        self.expect(b'(')?;
+        let mut items = vec![f(self)?];
+        while self.consume(b',') {
+            items.push(f(self)?);
+        }
+        self.expect(b')')?;
+        Ok(items)
+    }
+}
+
+/// A reader of the (extended) well-known binary representation of geometries.
+struct WkbReader<'a> {
+    buf: &'a [u8],
+    big_endian: bool,
+}
+
+impl WkbReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        if self.buf.len() < N {
+            bail!("invalid WKB: unexpected end of input");
+        }
+        let (bytes, rest) = self.buf.split_at(N);
+        self.buf = rest;
+        Ok(bytes.try_into().expect("known to be N bytes"))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.take()?;
+        Ok(match self.big_endian {
+            true => u32::from_be_bytes(bytes),
+            false => u32::from_le_bytes(bytes),
+        })
+    }
+
+    fn read_f64(&mut self) -> Result<f64, Error> {
+        let bytes = self.take()?;
+        Ok(match self.big_endian {
+            true => f64::from_be_bytes(bytes),
+            false => f64::from_le_bytes(bytes),
+        })
+    }
+
+    /// Reads a count of items that each occupy at least `min_size` bytes,
+    /// rejecting counts that can't fit in the remaining input.
+    fn read_count(&mut self, min_size: usize) -> Result<usize, Error> {
+        let count = usize::try_from(self.read_u32()?)?;
+        if count.saturating_mul(min_size) > self.buf.len() {
+            bail!("invalid WKB: unexpected end of input");
+        }
+        Ok(count)
+    }
+
+    fn read_coord(&mut self) -> Result<Coord, Error> {
+        Ok(Coord {
+            x: self.read_f64()?,
+            y: self.read_f64()?,
+        })
+    }
+
+    fn read_coords(&mut self) -> Result<Vec<Coord>, Error> {
+        let count = self.read_count(16)?;
+        (0..count).map(|_| self.read_coord()).collect()
+    }
+
+    fn read_rings(&mut self) -> Result<Vec<Vec<Coord>>, Error> {
+        let count = self.read_count(4)?;
+        (0..count).map(|_| self.read_coords()).collect()
+    }
+
+    /// Reads a geometry's byte order, type and optional SRID.
+    fn read_header(&mut self) -> Result<(u32, Option<i32>), Error> {
+        let [byte_order] = self.take()?;
+        self.big_endian = match byte_order {
+            0 => true,
+            1 => false,
+            _ => bail!("invalid WKB: unknown byte order {byte_order}"),
+        };
+        let typ = self.read_u32()?;
+        // EWKB flags Z and M coordinates in the high bits of the type, while
+        // ISO WKB adds 1000, 2000 or 3000 to it.
+        if typ & (EWKB_Z_FLAG | EWKB_M_FLAG) != 0 || typ & 0x0fff_ffff >= 1000 {
+            bail!("only two-dimensional geometries are supported");
+        }
+        let srid = match typ & EWKB_SRID_FLAG {
+            0 => None,
+            _ => Some(i32::from_ne_bytes(self.read_u32()?.to_ne_bytes())),
+        };
+        Ok((typ & 0x0fff_ffff, srid))
+    }
+
+    fn read_geometry(&mut self) -> Result<(Option<i32>, Shape), Error> {
+        let (typ, srid) = self.read_header()?;
+        let shape = match typ {
+            WKB_POINT => {
+                let c = self.read_coord()?;
+                // The empty point is encoded with NaN coordinates.
+                Shape::Point((!c.x.is_nan() || !c.y.is_nan()).then_some(c))
+            }
+            WKB_LINESTRING => Shape::LineString(self.read_coords()?),
+            WKB_POLYGON => Shape::Polygon(self.read_rings()?),
+            WKB_MULTIPOINT => {
+                Shape::MultiPoint(self.read_members(WKB_POINT, |shape| match shape {
+                    Shape::Point(Some(c)) => Ok(c),
+                    _ => bail!("invalid WKB: multipoint contains an empty point"),
+                })?)
+            }
+            WKB_MULTILINESTRING => {
+                Shape::MultiLineString(self.read_members(WKB_LINESTRING, |shape| match shape {
+                    Shape::LineString(points) => Ok(points),
+                    _ => unreachable!(),
+                })?)
+            }
+            WKB_MULTIPOLYGON => {
+                Shape::MultiPolygon(self.read_members(WKB_POLYGON, |shape| match shape {
+                    Shape::Polygon(rings) => Ok(rings),
+                    _ => unreachable!(),
+                })?)
+            }
+            WKB_GEOMETRYCOLLECTION => bail!("geometry collections are not supported"),
+            _ => bail!("invalid WKB: unknown geometry type {typ}"),
+        };
+        Ok((srid, shape))
+    }
+
+    /// Reads the members of a multi-geometry, which must all be of type `typ`.
+    fn read_members<T>(
+        &mut self,
+        typ: u32,
+        f: impl Fn(Shape) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
+        let count = self.read_count(5)?;
+        let mut members = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (member_typ, _) = self.read_header()?;
+            if member_typ != typ {
+                bail!("invalid WKB: multi-geometry contains a member of type {member_typ}");
+            }
+            // Each member has its own header, which may switch the byte
+            // order.
+            let shape = match typ {
+                WKB_POINT => {
+                    let c = self.read_coord()?;
+                    Shape::Point((!c.x.is_nan() || !c.y.is_nan()).then_some(c))
+                }
+                WKB_LINESTRING => Shape::LineString(self.read_coords()?),
+                _ => Shape::Polygon(self.read_rings()?),
+            };
+            members.push(f(shape)?);
+        }
+        Ok(members)
+    }
+}
+
+fn write_wkb(buf: &mut Vec<u8>, shape: &Shape, srid: Option<i32>) {
+    let typ = match shape {
+        Shape::Point(_) => WKB_POINT,
+        Shape::LineString(_) => WKB_LINESTRING,
+        Shape::Polygon(_) => WKB_POLYGON,
+        Shape::MultiPoint(_) => WKB_MULTIPOINT,
+        Shape::MultiLineString(_) => WKB_MULTILINESTRING,
+        Shape::MultiPolygon(_) => WKB_MULTIPOLYGON,
+    };
+    write_header(buf, typ, srid);
+    match shape {
+        Shape::Point(point) => {
+            let c = point.unwrap_or(Coord {
+                x: f64::NAN,
+                y: f64::NAN,
+            });
+            write_coord(buf, c);
+        }
+        Shape::LineString(points) => write_coords(buf, points),
+        Shape::Polygon(rings) => write_rings(buf, rings),
+        Shape::MultiPoint(points) => {
+            write_count(buf, points.len());
+            for c in points {
+                write_header(buf, WKB_POINT, None);
+                write_coord(buf, *c);
+            }
+        }
+        Shape::MultiLineString(lines) => {
+            write_count(buf, lines.len());
+            for points in lines {
+                write_header(buf, WKB_LINESTRING, None);
+                write_coords(buf, points);
+            }
+        }
+        Shape::MultiPolygon(polygons) => {
+            write_count(buf, polygons.len());
+            for rings in polygons {
+                write_header(buf, WKB_POLYGON, None);
+                write_rings(buf, rings);
+            }
+        }
+    }
+}
+
+fn write_header(buf: &mut Vec<u8>, typ: u32, srid: Option<i32>) {
+    // Always write little-endian data.
+    buf.push(1);
+    match srid {
+        Some(srid) => {
+            buf.extend((typ | EWKB_SRID_FLAG).to_le_bytes());
+            buf.extend(srid.to_le_bytes());
+        }
+        None => buf.extend(typ.to_le_bytes()),
+    }
+}
+
+fn write_count(buf: &mut Vec<u8>, count: usize) {
+    let count = u32::try_from(count).expect("geometries have fewer than 2^32 parts");
+    buf.extend(count.to_le_bytes());
+}
+
+fn write_coord(buf: &mut Vec<u8>, c: Coord) {
+    buf.extend(c.x.to_le_bytes());
+    buf.extend(c.y.to_le_bytes());
+}
+
+fn write_coords(buf: &mut Vec<u8>, points: &[Coord]) {
+    write_count(buf, points.len());
+    for c in points {
+        write_coord(buf, *c);
+    }
+}
+
+fn write_rings(buf: &mut Vec<u8>, rings: &[Vec<Coord>]) {
+    write_count(buf, rings.len());
+    for ring in rings {
+        write_coords(buf, ring);
+    }
+}
+
+/// Returns twice the signed area of the triangle `abc`, which is positive if
+/// `c` lies to the left of the line through `a` and `b`, negative if it lies
+/// to the right, and zero if it lies on the line.
+fn orientation(a: Coord, b: Coord, c: Coord) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Reports whether `p` lies on the segment from `a` to `b`.
+fn on_segment(p: Coord, a: Coord, b: Coord) -> bool {
+    orientation(a, b, p) == 0.0
+        && a.x.min(b.x) <= p.x
+        && p.x <= a.x.max(b.x)
+        && a.y.min(b.y) <= p.y
+        && p.y <= a.y.max(b.y)
+}
+
+fn segments_intersect(p1: Coord, p2: Coord, q1: Coord, q2: Coord) -> bool {
+    let d1 = orientation(q1, q2, p1);
+    let d2 = orientation(q1, q2, p2);
+    let d3 = orientation(p1, p2, q1);
+    let d4 = orientation(p1, p2, q2);
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+    on_segment(p1, q1, q2)
+        || on_segment(p2, q1, q2)
+        || on_segment(q1, p1, p2)
+        || on_segment(q2, p1, p2)
+}
+
+/// Returns the midpoints of the pieces into which the segment from `p` to `q`
+/// is split by the points where it meets `segments` and `points`.
+fn piece_midpoints(
+    p: Coord,
+    q: Coord,
+    segments: &[(Coord, Coord)],
+    points: &[Coord],
+) -> Vec<Coord> {
+    let d = Coord {
+        x: q.x - p.x,
+        y: q.y - p.y,
+    };
+    let len2 = d.x * d.x + d.y * d.y;
+    if len2 == 0.0 {
+        return vec![];
+    }
+    let param = |c: Coord| ((c.x - p.x) * d.x + (c.y - p.y) * d.y) / len2;
+    let mut params = vec![0.0, 1.0];
+    for c in points {
+        if on_segment(*c, p, q) {
+            params.push(param(*c));
+        }
+    }
+    for (r, s) in segments {
+        if !segments_intersect(p, q, *r, *s) {
+            continue;
+        }
+        let e = Coord {
+            x: s.x - r.x,
+            y: s.y - r.y,
+        };
+        let denominator = d.x * e.y - d.y * e.x;
+        if denominator == 0.0 {
+            // The segments are collinear and overlap.
+            for c in [r, s] {
+                if on_segment(*c, p, q) {
+                    params.push(param(*c));
+                }
+            }
+        } else {
+            params.push(((r.x - p.x) * e.y - (r.y - p.y) * e.x) / denominator);
+        }
+    }
+    params.retain(|t| (0.0..=1.0).contains(t));
+    params.sort_by(f64::total_cmp);
+    params.dedup();
+    params
+        .windows(2)
+        .map(|w| {
+            let t = (w[0] + w[1]) / 2.0;
+            Coord {
+                x: p.x + t * d.x,
+                y: p.y + t * d.y,
+            }
+        })
+        .collect()
+}
+
+/// Reports whether `p` lies inside `ring`, which it must not lie on.
+fn in_ring(p: Coord, ring: &[Coord]) -> bool {
+    let mut inside = false;
+    for w in ring.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        if (a.y > p.y) != (b.y > p.y) {
+            let x = a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if p.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn locate_in_polygon(p: Coord, rings: &[Vec<Coord>]) -> Location {
+    if rings
+        .iter()
+        .any(|ring| ring.windows(2).any(|w| on_segment(p, w[0], w[1])))
+    {
+        return Location::Boundary;
+    }
+    let (exterior, holes) = rings.split_first().expect("polygon is not empty");
+    if in_ring(p, exterior) && !holes.iter().any(|hole| in_ring(p, hole)) {
+        Location::Interior
+    } else {
+        Location::Exterior
+    }
+}
+
+/// Returns a point in the interior of the polygon described by `rings`.
+///
+/// The point is the midpoint of the widest span of the polygon along a
+/// horizontal line that passes through no vertex.
+fn interior_point(rings: &[Vec<Coord>]) -> Option<Coord> {
+    let mut ys: Vec<f64> = rings.iter().flatten().map(|c| c.y).collect();
+    ys.sort_by(f64::total_cmp);
+    ys.dedup();
+    let i = ys.len().checked_sub(1)? / 2;
+    let y = (ys[i] + *ys.get(i + 1)?) / 2.0;
+    let mut xs = vec![];
+    for w in rings.iter().flat_map(|ring| ring.windows(2)) {
+        let (a, b) = (w[0], w[1]);
+        if (a.y > y) != (b.y > y) {
+            xs.push(a.x + (y - a.y) * (b.x - a.x) / (b.y - a.y));
+        }
+    }
+    xs.sort_by(f64::total_cmp);
+    let (x0, x1) = xs
+        .chunks_exact(2)
+        .map(|span| (span[0], span[1]))
+        .max_by(|a, b| (a.1 - a.0).total_cmp(&(b.1 - b.0)))?;
+    Some(Coord {
+        x: (x0 + x1) / 2.0,
+        y,
+    })
+}
+
+fn planar_distance(p: Coord, q: Coord) -> f64 {
+    (p.x - q.x).hypot(p.y - q.y)
+}
+
+/// Returns the distance from `p` to the segment from `a` to `b`.
+fn planar_segment_distance(p: Coord, a: Coord, b: Coord) -> f64 {
+    let d = Coord {
+        x: b.x - a.x,
+        y: b.y - a.y,
+    };
+    let len2 = d.x * d.x + d.y * d.y;
+    if len2 == 0.0 {
+        return planar_distance(p, a);
+    }
+    let t = (((p.x - a.x) * d.x + (p.y - a.y) * d.y) / len2).clamp(0.0, 1.0);
+    planar_distance(
+        p,
+        Coord {
+            x: a.x + t * d.x,
+            y: a.y + t * d.y,
+        },
+    )
+}
+
+type Vector = [f64; 3];
+
+/// Returns the unit vector that points from the center of the sphere to the
+/// longitude and latitude `c`.
+fn unit_vector(c: Coord) -> Vector {
+    let (lon, lat) = (c.x.to_radians(), c.y.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+fn dot(u: Vector, v: Vector) -> f64 {
+    u[0] * v[0] + u[1] * v[1] + u[2] * v[2]
+}
+
+fn cross(u: Vector, v: Vector) -> Vector {
+    [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ]
+}
+
+fn norm(u: Vector) -> f64 {
+    dot(u, u).sqrt()
+}
+
+/// Returns the angle between two vectors, in radians.
+fn angle(u: Vector, v: Vector) -> f64 {
+    norm(cross(u, v)).atan2(dot(u, v))
+}
+
+/// Returns the central angle between two longitudes and latitudes.
+fn sphere_distance(p: Coord, q: Coord) -> f64 {
+    angle(unit_vector(p), unit_vector(q))
+}
+
+/// Returns the central angle between `p` and the closest point on the arc of
+/// the great circle from `a` to `b`.
+fn sphere_segment_distance(p: Coord, a: Coord, b: Coord) -> f64 {
+    let (p, a, b) = (unit_vector(p), unit_vector(a), unit_vector(b));
+    let endpoints = angle(p, a).min(angle(p, b));
+    let n = cross(a, b);
+    let len = norm(n);
+    if len < 1e-15 {
+        return endpoints;
+    }
+    let n = [n[0] / len, n[1] / len, n[2] / len];
+    // Project `p` onto the plane of the great circle, and check whether the
+    // projection lies between `a` and `b`.
+    let h = dot(p, n);
+    let projection = [p[0] - h * n[0], p[1] - h * n[1], p[2] - h * n[2]];
+    if dot(cross(a, projection), n) >= 0.0 && dot(cross(projection, b), n) >= 0.0 {
+        h.abs().min(1.0).asin()
+    } else {
+        endpoints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn geom(s: &str) -> Geometry {
+        s.parse().unwrap()
+    }
+
+    #[mz_ore::test]
+    fn test_wkt_roundtrip() {
+        for (input, expected) in [
+            ("POINT(1 2)", "POINT(1 2)"),
+            ("point ( -1.5  2e1 )", "POINT(-1.5 20)"),
+            ("POINT EMPTY", "POINT EMPTY"),
+            ("LINESTRING(0 0, 1 1, 2 0)", "LINESTRING(0 0,1 1,2 0)"),
+            (
+                "POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,2 1,2 2,1 1))",
+                "POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,2 1,2 2,1 1))",
+            ),
+            ("MULTIPOINT(0 0, 1 1)", "MULTIPOINT((0 0),(1 1))"),
+            ("MULTIPOINT((0 0),(1 1))", "MULTIPOINT((0 0),(1 1))"),
+            (
+                "MULTILINESTRING((0 0,1 1),(2 2,3 3))",
+                "MULTILINESTRING((0 0,1 1),(2 2,3 3))",
+            ),
+            (
+                "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))",
+                "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))",
+            ),
+            ("POINT(0.1 -0)", "POINT(0.1 0)"),
+        ] {
+            let g = geom(input);
+            assert_eq!(g.to_wkt(), expected, "input: {input}");
+            assert_eq!(Geometry::from_ewkb(&g.to_ewkb()).unwrap(), g);
+            assert_eq!(geom(&g.to_string()), g);
+        }
+
+        let g = geom("SRID=4326;POINT(1 2)");
+        assert_eq!(g.srid(), 4326);
+        assert_eq!(g.to_ewkt(), "SRID=4326;POINT(1 2)");
+        assert_eq!(
+            g.to_string(),
+            "0101000020E6100000000000000000F03F0000000000000040"
+        );
+        assert_eq!(
+            geom("0101000020E6100000000000000000F03F0000000000000040"),
+            g
+        );
+        // Big-endian WKB.
+        assert_eq!(
+            geom("00000000013FF00000000000004000000000000000"),
+            geom("POINT(1 2)")
+        );
+
+        for (input, err) in [
+            ("POINT(1)", "parse error - invalid geometry"),
+            (
+                "POINT(1 2 3)",
+                "only two-dimensional geometries are supported",
+            ),
+            (
+                "POINT Z (1 2 3)",
+                "only two-dimensional geometries are supported",
+            ),
+            (
+                "LINESTRING(0 0)",
+                "LINESTRING requires more points or has non-closed rings",
+            ),
+            (
+                "POLYGON((0 0,1 0,1 1,0 1))",
+                "POLYGON requires more points or has non-closed rings",
+            ),
+            (
+                "GEOMETRYCOLLECTION(POINT(0 0))",
+                "geometry collections are not supported",
+            ),
+            ("CIRCLE(0 0)", "parse error - invalid geometry"),
+            (
+                "SRID=-1;POINT(0 0)",
+                "SRID must be between 0 and 999999, got -1",
+            ),
+            ("0101", "invalid WKB: unexpected end of input"),
+        ] {
+            assert_eq!(
+                input.parse::<Geometry>().unwrap_err().to_string(),
+                err,
+                "input: {input}"
+            );
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_geojson() {
+        for (input, expected) in [
+            ("POINT(1 2)", r#"{"type":"Point","coordinates":[1,2]}"#),
+            ("POINT EMPTY", r#"{"type":"Point","coordinates":[]}"#),
+            (
+                "LINESTRING(0 0,1.5 1)",
+                r#"{"type":"LineString","coordinates":[[0,0],[1.5,1]]}"#,
+            ),
+            (
+                "POLYGON((0 0,1 0,1 1,0 0))",
+                r#"{"type":"Polygon","coordinates":[[[0,0],[1,0],[1,1],[0,0]]]}"#,
+            ),
+            (
+                "MULTIPOLYGON(((0 0,1 0,1 1,0 0)))",
+                r#"{"type":"MultiPolygon","coordinates":[[[[0,0],[1,0],[1,1],[0,0]]]]}"#,
+            ),
+        ] {
+            let g = geom(input);
+            assert_eq!(g.to_geojson(), expected);
+            let parsed = Geometry::from_geojson(expected).unwrap();
+            assert_eq!(parsed.srid(), SRID_WGS84);
+            assert_eq!(parsed.shape(), g.shape());
+        }
+        assert!(Geometry::from_geojson(r#"{"type":"Point","coordinates":[1,2,3]}"#).is_err());
+        assert!(Geometry::from_geojson(r#"{"type":"Circle","coordinates":[1,2]}"#).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_relationships() {
+        let square = geom("POLYGON((0 0,10 0,10 10,0 10,0 0))");
+        let donut = geom("POLYGON((0 0,10 0,10 10,0 10,0 0),(4 4,6 4,6 6,4 6,4 4))");
+        for (a, b, intersects, contains, distance) in [
+            (&square, "POINT(5 5)", true, true, 0.0),
+            (&square, "POINT(0 5)", true, false, 0.0),
+            (&square, "POINT(13 14)", false, false, 5.0),
+            (&square, "LINESTRING(1 1,9 9)", true, true, 0.0),
+            (&square, "LINESTRING(0 0,10 0)", true, false, 0.0),
+            (&square, "LINESTRING(5 5,15 5)", true, false, 0.0),
+            (&square, "POLYGON((2 2,3 2,3 3,2 2))", true, true, 0.0),
+            (
+                &square,
+                "POLYGON((0 0,10 0,10 10,0 10,0 0))",
+                true,
+                true,
+                0.0,
+            ),
+            (&square, "POLYGON((12 0,14 0,14 2,12 0))", false, false, 2.0),
+            (&donut, "POINT(5 5)", false, false, 1.0),
+            (&donut, "POLYGON((4 4,6 4,6 6,4 6,4 4))", true, false, 0.0),
+            (&donut, "POLYGON((3 3,7 3,7 7,3 7,3 3))", true, false, 0.0),
+            (&donut, "POLYGON((1 1,3 1,3 3,1 3,1 1))", true, true, 0.0),
+        ] {
+            let b = geom(b);
+            assert_eq!(a.intersects(&b).unwrap(), intersects, "{}", b.to_wkt());
+            assert_eq!(a.contains(&b).unwrap(), contains, "{}", b.to_wkt());
+            assert_eq!(a.distance(&b).unwrap(), Some(distance), "{}", b.to_wkt());
+        }
+
+        let line = geom("LINESTRING(0 0,10 0)");
+        assert!(line.contains(&geom("POINT(5 0)")).unwrap());
+        assert!(!line.contains(&geom("POINT(0 0)")).unwrap());
+        assert!(line.contains(&geom("LINESTRING(2 0,8 0)")).unwrap());
+        assert!(!line.contains(&geom("LINESTRING(2 0,12 0)")).unwrap());
+        assert!(!line.contains(&square).unwrap());
+        assert_eq!(
+            geom("LINESTRING(0 0,1 1)")
+                .distance(&geom("LINESTRING(3 0,3 5)"))
+                .unwrap(),
+            Some(2.0)
+        );
+        assert_eq!(square.distance(&geom("POINT EMPTY")).unwrap(), None);
+        assert_eq!(
+            square
+                .distance(&geom("SRID=4326;POINT(0 0)"))
+                .unwrap_err()
+                .to_string(),
+            "operation on mixed SRID geometries (ST_Polygon, 0) != (ST_Point, 4326)"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_sphere_distance() {
+        let geog = |s: &str| geom(s).into_geography().unwrap();
+        // One degree of longitude along the equator.
+        let d = geog("POINT(0 0)")
+            .sphere_distance(&geog("POINT(1 0)"))
+            .unwrap()
+            .unwrap();
+        assert!((d - 111_195.08).abs() < 0.01, "{d}");
+        // The closest point of the arc is in its middle.
+        let d = geog("POINT(0 1)")
+            .sphere_distance(&geog("LINESTRING(-1 0,1 0)"))
+            .unwrap()
+            .unwrap();
+        assert!((d - 111_195.08).abs() < 0.01, "{d}");
+        assert!(geom("POINT(200 0)").into_geography().is_err());
+        assert!(geom("SRID=3857;POINT(0 0)").into_geography().is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_geohash() {
+        let g = geom("POINT(-126 48)");
+        assert_eq!(g.geohash(None).unwrap(), "c0w3hf1s70w3hf1s70w3");
+        assert_eq!(g.geohash(Some(5)).unwrap(), "c0w3h");
+        let g = geom("LINESTRING(-126 48,-126.01 48.01)");
+        assert_eq!(g.geohash(None).unwrap(), "c0w3h");
+        assert!(geom("POINT(500 0)").geohash(None).is_err());
+
+        let (min, max) = decode_geohash("c0w3h").unwrap();
+        assert!(min.x <= -126.0 && -126.0 <= max.x && min.y <= 48.0 && 48.0 <= max.y);
+
+        let neighbors = geohash_neighbors("c0w3h").unwrap();
+        assert_eq!(neighbors.len(), 9);
+        assert_eq!(neighbors[4], "c0w3h");
+        for n in &neighbors {
+            assert!(geohash_neighbors(n).unwrap().contains(&"c0w3h".to_owned()));
+        }
+        // Cells at the poles have no neighbors beyond them, and cells at the
+        // antimeridian wrap around.
+        assert_eq!(geohash_neighbors("z").unwrap().len(), 6);
+        assert!(geohash_neighbors("0").unwrap().contains(&"p".to_owned()));
+        assert!(geohash_neighbors("a").is_err());
+    }
+}
@@ -0,0 +1,1872 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The SQL/JSON path language.
+//!
+//! This module implements PostgreSQL's `jsonpath` type, a parser for its text
+//! representation and an evaluator that applies paths to `jsonb` values. See:
+//!   * <https://www.postgresql.org/docs/current/functions-json.html#FUNCTIONS-SQLJSON-PATH>
+//!   * <https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/jsonpath.c>
+//!   * <https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/jsonpath_exec.c>
+//!
+//! Values of the type are stored as [`Datum::String`]s that hold their
+//! canonical text representation, i.e. the output of the [`Display`]
+//! implementation of [`JsonPath`].
+//!
+//! [`Display`]: fmt::Display
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error};
+use dec::{OrderedDecimal, Rounding};
+use proptest::prelude::*;
+use regex::{Regex, RegexBuilder};
+
+use crate::adt::numeric::{self, Numeric};
+use crate::{Datum, RowPacker};
+
+/// The bound of a `.**` accessor that denotes the deepest level.
+const LAST_LEVEL: u32 = u32::MAX;
+
+/// A parsed SQL/JSON path expression.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    /// Whether the path is evaluated in strict mode rather than lax mode.
+    strict: bool,
+    expr: Node,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    /// `$`
+    Root,
+    /// `@`
+    Current,
+    /// `last`, the index of the last element of the array being subscripted.
+    Last,
+    /// `$name`
+    Variable(String),
+    Null,
+    Bool(bool),
+    Numeric(OrderedDecimal<Numeric>),
+    String(String),
+    Accessor(Box<Node>, Accessor),
+    Arithmetic(ArithOp, Box<Node>, Box<Node>),
+    Unary(UnaryOp, Box<Node>),
+    Compare(CmpOp, Box<Node>, Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Not(Box<Node>),
+    IsUnknown(Box<Node>),
+    Exists(Box<Node>),
+    StartsWith(Box<Node>, Box<Node>),
+    LikeRegex {
+        expr: Box<Node>,
+        pattern: String,
+        flags: String,
+        regex: Regex,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum Accessor {
+    /// `.key`
+    Member(String),
+    /// `.*`
+    AnyMember,
+    /// `[*]`
+    AnyElement,
+    /// `[subscript, ...]`
+    Elements(Vec<Subscript>),
+    /// `.**{first to last}`
+    Descendants { first: u32, last: u32 },
+    /// `?(predicate)`
+    Filter(Box<Node>),
+    /// `.method()`
+    Method(Method),
+}
+
+#[derive(Debug, Clone)]
+struct Subscript {
+    from: Node,
+    to: Option<Node>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Method {
+    Type,
+    Size,
+    Double,
+    Ceiling,
+    Floor,
+    Abs,
+    KeyValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnaryOp {
+    Plus,
+    Minus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+impl Method {
+    fn from_name(name: &str) -> Option<Method> {
+        Some(match name {
+            "type" => Method::Type,
+            "size" => Method::Size,
+            "double" => Method::Double,
+            "ceiling" => Method::Ceiling,
+            "floor" => Method::Floor,
+            "abs" => Method::Abs,
+            "keyvalue" => Method::KeyValue,
+            _ => return None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Method::Type => "type",
+            Method::Size => "size",
+            Method::Double => "double",
+            Method::Ceiling => "ceiling",
+            Method::Floor => "floor",
+            Method::Abs => "abs",
+            Method::KeyValue => "keyvalue",
+        }
+    }
+}
+
+impl ArithOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            ArithOp::Add => "+",
+            ArithOp::Sub => "-",
+            ArithOp::Mul => "*",
+            ArithOp::Div => "/",
+            ArithOp::Mod => "%",
+        }
+    }
+}
+
+impl CmpOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            CmpOp::Eq => "==",
+            CmpOp::NotEq => "!=",
+            CmpOp::Lt => "<",
+            CmpOp::Lte => "<=",
+            CmpOp::Gt => ">",
+            CmpOp::Gte => ">=",
+        }
+    }
+}
+
+impl Node {
+    /// Reports whether the node is a predicate, i.e. evaluates to a boolean
+    /// or unknown, rather than to a sequence of items.
+    fn is_predicate(&self) -> bool {
+        matches!(
+            self,
+            Node::Compare(..)
+                | Node::And(..)
+                | Node::Or(..)
+                | Node::Not(..)
+                | Node::IsUnknown(..)
+                | Node::Exists(..)
+                | Node::StartsWith(..)
+                | Node::LikeRegex { .. }
+        )
+    }
+
+    /// The binding power of the node's operator, which determines where
+    /// parentheses are required when printing it.
+    fn priority(&self) -> u8 {
+        match self {
+            Node::Or(..) => 0,
+            Node::And(..) => 1,
+            Node::Compare(..) | Node::StartsWith(..) => 2,
+            Node::Arithmetic(ArithOp::Add | ArithOp::Sub, ..) => 3,
+            Node::Arithmetic(ArithOp::Mul | ArithOp::Div | ArithOp::Mod, ..) => 4,
+            Node::Unary(..) => 5,
+            _ => 6,
+        }
+    }
+
+    /// Reports whether accessors can be appended to the printed node without
+    /// enclosing it in parentheses.
+    fn is_primary(&self) -> bool {
+        matches!(
+            self,
+            Node::Root
+                | Node::Current
+                | Node::Last
+                | Node::Variable(_)
+                | Node::Null
+                | Node::Bool(_)
+                | Node::String(_)
+                | Node::Accessor(..)
+        ) || matches!(self, Node::Numeric(n) if !n.0.is_negative())
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<JsonPath, Error> {
+        let tokens = lex(s)?;
+        let mut parser = Parser {
+            input: s,
+            tokens,
+            pos: 0,
+            filter_depth: 0,
+            subscript_depth: 0,
+        };
+        let strict = if parser.consume_word("strict") {
+            true
+        } else {
+            parser.consume_word("lax");
+            false
+        };
+        let expr = parser.parse_or()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(parser.syntax_error());
+        }
+        Ok(JsonPath { strict, expr })
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.strict {
+            f.write_str("strict ")?;
+        }
+        fmt_node(f, &self.expr, true)
+    }
+}
+
+/// Prints `node`, enclosing it in parentheses if it is an operator and
+/// `brackets` is set.
+fn fmt_node(f: &mut fmt::Formatter, node: &Node, brackets: bool) -> fmt::Result {
+    match node {
+        Node::Root => f.write_str("$"),
+        Node::Current => f.write_str("@"),
+        Node::Last => f.write_str("last"),
+        Node::Variable(name) => {
+            f.write_str("$")?;
+            fmt_string(f, name)
+        }
+        Node::Null => f.write_str("null"),
+        Node::Bool(b) => write!(f, "{b}"),
+        Node::Numeric(n) => f.write_str(&n.0.to_standard_notation_string()),
+        Node::String(s) => fmt_string(f, s),
+        Node::Accessor(base, accessor) => {
+            fmt_node(f, base, !base.is_primary())?;
+            fmt_accessor(f, accessor)
+        }
+        Node::Arithmetic(op, left, right) => {
+            fmt_binary(f, node, left, op.symbol(), right, brackets)
+        }
+        Node::Compare(op, left, right) => fmt_binary(f, node, left, op.symbol(), right, brackets),
+        Node::And(left, right) => fmt_binary(f, node, left, "&&", right, brackets),
+        Node::Or(left, right) => fmt_binary(f, node, left, "||", right, brackets),
+        Node::StartsWith(left, right) => fmt_binary(f, node, left, "starts with", right, brackets),
+        Node::Unary(op, arg) => {
+            if brackets {
+                f.write_str("(")?;
+            }
+            f.write_str(match op {
+                UnaryOp::Plus => "+",
+                UnaryOp::Minus => "-",
+            })?;
+            fmt_node(f, arg, arg.priority() <= node.priority())?;
+            if brackets {
+                f.write_str(")")?;
+            }
+            Ok(())
+        }
+        Node::Not(arg) => {
+            f.write_str("!(")?;
+            fmt_node(f, arg, false)?;
+            f.write_str(")")
+        }
+        Node::IsUnknown(arg) => {
+            f.write_str("(")?;
+            fmt_node(f, arg, false)?;
+            f.write_str(") is unknown")
+        }
+        Node::Exists(arg) => {
+            f.write_str("exists (")?;
+            fmt_node(f, arg, false)?;
+            f.write_str(")")
+        }
+        Node::LikeRegex {
+            expr,
+            pattern,
+            flags,
+            regex: _,
+        } => {
+            if brackets {
+                f.write_str("(")?;
+            }
+            fmt_node(f, expr, true)?;
+            f.write_str(" like_regex ")?;
+            fmt_string(f, pattern)?;
+            if !flags.is_empty() {
+                f.write_str(" flag ")?;
+                fmt_string(f, flags)?;
+            }
+            if brackets {
+                f.write_str(")")?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn fmt_binary(
+    f: &mut fmt::Formatter,
+    node: &Node,
+    left: &Node,
+    op: &str,
+    right: &Node,
+    brackets: bool,
+) -> fmt::Result {
+    if brackets {
+        f.write_str("(")?;
+    }
+    fmt_node(f, left, left.priority() <= node.priority())?;
+    write!(f, " {op} ")?;
+    fmt_node(f, right, right.priority() <= node.priority())?;
+    if brackets {
+        f.write_str(")")?;
+    }
+    Ok(())
+}
+
+fn fmt_accessor(f: &mut fmt::Formatter, accessor: &Accessor) -> fmt::Result {
+    match accessor {
+        Accessor::Member(key) => {
+            f.write_str(".")?;
+            fmt_string(f, key)
+        }
+        Accessor::AnyMember => f.write_str(".*"),
+        Accessor::AnyElement => f.write_str("[*]"),
+        Accessor::Elements(subscripts) => {
+            f.write_str("[")?;
+            for (i, subscript) in subscripts.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                fmt_node(f, &subscript.from, false)?;
+                if let Some(to) = &subscript.to {
+                    f.write_str(" to ")?;
+                    fmt_node(f, to, false)?;
+                }
+            }
+            f.write_str("]")
+        }
+        Accessor::Descendants { first, last } => {
+            let fmt_level = |f: &mut fmt::Formatter, level: u32| match level {
+                LAST_LEVEL => f.write_str("last"),
+                level => write!(f, "{level}"),
+            };
+            f.write_str(".**")?;
+            match (*first, *last) {
+                (0, LAST_LEVEL) => Ok(()),
+                (first, last) if first == last => {
+                    f.write_str("{")?;
+                    fmt_level(f, first)?;
+                    f.write_str("}")
+                }
+                (first, last) => {
+                    f.write_str("{")?;
+                    fmt_level(f, first)?;
+                    f.write_str(" to ")?;
+                    fmt_level(f, last)?;
+                    f.write_str("}")
+                }
+            }
+        }
+        Accessor::Filter(predicate) => {
+            f.write_str("?(")?;
+            fmt_node(f, predicate, false)?;
+            f.write_str(")")
+        }
+        Accessor::Method(method) => write!(f, ".{}()", method.name()),
+    }
+}
+
+/// Prints `s` as a double-quoted string, escaped like a JSON string.
+fn fmt_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '\u{8}' => f.write_str("\\b")?,
+            '\u{c}' => f.write_str("\\f")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            c if c < ' ' => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    f.write_str("\"")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// `$`
+    Root,
+    /// `@`
+    Current,
+    /// `$name` or `$"name"`
+    Variable(String),
+    /// A double-quoted string.
+    String(String),
+    Numeric(OrderedDecimal<Numeric>),
+    /// An unquoted identifier, which is either a keyword or a key, depending
+    /// on where it appears.
+    Word(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    Comma,
+    Dot,
+    Question,
+    Star,
+    StarStar,
+    Plus,
+    Minus,
+    Slash,
+    Percent,
+    Not,
+    And,
+    Or,
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+/// A token and the byte range of the input that it was lexed from.
+type Spanned = (Token, usize, usize);
+
+/// Reports whether `c` can appear in an unquoted identifier.
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !"?%$.[]{}()|&!=<>@#,*:-+/\\\"'`".contains(c)
+}
+
+fn lex(s: &str) -> Result<Vec<Spanned>, Error> {
+    let mut tokens = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut next_is = |expected: char| {
+            if chars.peek().map(|(_, c)| *c) == Some(expected) {
+                chars.next();
+                true
+            } else {
+                false
+            }
+        };
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            ',' => Token::Comma,
+            '.' => Token::Dot,
+            '?' => Token::Question,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '@' => Token::Current,
+            '*' if next_is('*') => Token::StarStar,
+            '*' => Token::Star,
+            '&' if next_is('&') => Token::And,
+            '|' if next_is('|') => Token::Or,
+            '=' if next_is('=') => Token::Eq,
+            '!' if next_is('=') => Token::NotEq,
+            '!' => Token::Not,
+            '<' if next_is('=') => Token::Lte,
+            '<' if next_is('>') => Token::NotEq,
+            '<' => Token::Lt,
+            '>' if next_is('=') => Token::Gte,
+            '>' => Token::Gt,
+            '"' => Token::String(lex_string(s, &mut chars)?),
+            '$' => match chars.peek() {
+                Some((_, '"')) => {
+                    chars.next();
+                    Token::Variable(lex_string(s, &mut chars)?)
+                }
+                Some((_, c)) if is_word_char(*c) => {
+                    Token::Variable(lex_word(s, chars.next().unwrap().0, &mut chars))
+                }
+                _ => Token::Root,
+            },
+            c if c.is_ascii_digit() => {
+                let end = lex_number(s, start);
+                while chars.peek().is_some_and(|(i, _)| *i < end) {
+                    chars.next();
+                }
+                if let Some((i, c)) = chars.peek().filter(|(_, c)| is_word_char(*c)) {
+                    bail!(
+                        "trailing junk after numeric literal at or near \"{}\" of jsonpath input",
+                        &s[start..i + c.len_utf8()]
+                    );
+                }
+                let mut cx = numeric::cx_datum();
+                let mut n = cx
+                    .parse(&s[start..end])
+                    .map_err(|_| anyhow!("invalid numeric literal {}", &s[start..end]))?;
+                if cx.status().overflow() || numeric::munge_numeric(&mut n).is_err() {
+                    bail!("numeric literal {} is out of range", &s[start..end]);
+                }
+                Token::Numeric(OrderedDecimal(n))
+            }
+            c if is_word_char(c) => Token::Word(lex_word(s, start, &mut chars)),
+            _ => bail!(
+                "syntax error at or near \"{}\" of jsonpath input",
+                &s[start..start + c.len_utf8()]
+            ),
+        };
+        let end = chars.peek().map_or(s.len(), |(i, _)| *i);
+        tokens.push((token, start, end));
+    }
+    Ok(tokens)
+}
+
+fn lex_word(
+    s: &str,
+    start: usize,
+    chars: &mut std::iter::Peekable<std::str::CharIndices>,
+) -> String {
+    let mut end = s.len();
+    while let Some((i, c)) = chars.peek() {
+        if !is_word_char(*c) {
+            end = *i;
+            break;
+        }
+        chars.next();
+    }
+    s[start..end].to_owned()
+}
+
+/// Returns the end of the number that starts at byte `start` of `s`.
+fn lex_number(s: &str, start: usize) -> usize {
+    let bytes = s.as_bytes();
+    let digits = |mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    };
+    let mut end = digits(start);
+    if end + 1 < bytes.len() && bytes[end] == b'.' && bytes[end + 1].is_ascii_digit() {
+        end = digits(end + 1);
+    }
+    if end < bytes.len() && matches!(bytes[end], b'e' | b'E') {
+        let mut exp = end + 1;
+        if exp < bytes.len() && matches!(bytes[exp], b'+' | b'-') {
+            exp += 1;
+        }
+        if exp < bytes.len() && bytes[exp].is_ascii_digit() {
+            end = digits(exp);
+        }
+    }
+    end
+}
+
+/// Lexes the remainder of a double-quoted string whose opening quote has
+/// already been consumed.
+fn lex_string(
+    s: &str,
+    chars: &mut std::iter::Peekable<std::str::CharIndices>,
+) -> Result<String, Error> {
+    let mut out = String::new();
+    loop {
+        match chars.next() {
+            None => bail!("unexpected end of quoted string at end of jsonpath input"),
+            Some((_, '"')) => return Ok(out),
+            Some((_, '\\')) => match chars.next() {
+                None => bail!("unexpected end of quoted string at end of jsonpath input"),
+                Some((_, 'b')) => out.push('\u{8}'),
+                Some((_, 'f')) => out.push('\u{c}'),
+                Some((_, 'n')) => out.push('\n'),
+                Some((_, 'r')) => out.push('\r'),
+                Some((_, 't')) => out.push('\t'),
+                Some((_, 'v')) => out.push('\u{b}'),
+                Some((_, 'x')) => {
+                    let code = lex_hex(s, chars, 2, 2)?;
+                    out.push(char::from_u32(code).expect("two hex digits are a valid char"));
+                }
+                Some((_, 'u')) => {
+                    let mut code = if chars.peek().map(|(_, c)| *c) == Some('{') {
+                        chars.next();
+                        let code = lex_hex(s, chars, 1, 6)?;
+                        if chars.next().map(|(_, c)| c) != Some('}') {
+                            bail!("invalid Unicode escape sequence in jsonpath input");
+                        }
+                        code
+                    } else {
+                        lex_hex(s, chars, 4, 4)?
+                    };
+                    // Combine a high surrogate with the low surrogate that
+                    // must follow it.
+                    if (0xD800..0xDC00).contains(&code) {
+                        let low = match (chars.next(), chars.next()) {
+                            (Some((_, '\\')), Some((_, 'u'))) => lex_hex(s, chars, 4, 4)?,
+                            _ => bail!("invalid Unicode surrogate pair in jsonpath input"),
+                        };
+                        if !(0xDC00..0xE000).contains(&low) {
+                            bail!("invalid Unicode surrogate pair in jsonpath input");
+                        }
+                        code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                    }
+                    match char::from_u32(code) {
+                        Some('\0') => bail!("unsupported Unicode escape sequence: \\u0000 cannot be converted to text"),
+                        Some(c) => out.push(c),
+                        None => bail!("invalid Unicode escape value in jsonpath input"),
+                    }
+                }
+                Some((_, c)) => out.push(c),
+            },
+            Some((_, c)) => out.push(c),
+        }
+    }
+}
+
+/// Lexes between `min` and `max` hexadecimal digits.
+fn lex_hex(
+    s: &str,
+    chars: &mut std::iter::Peekable<std::str::CharIndices>,
+    min: usize,
+    max: usize,
+) -> Result<u32, Error> {
+    let start = chars.peek().map_or(s.len(), |(i, _)| *i);
+    let mut len = 0;
+    while len < max && chars.peek().is_some_and(|(_, c)| c.is_ascii_hexdigit()) {
+        chars.next();
+        len += 1;
+    }
+    if len < min {
+        bail!("invalid hexadecimal character sequence in jsonpath input");
+    }
+    Ok(u32::from_str_radix(&s[start..start + len], 16).expect("validated hex digits"))
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Spanned>,
+    pos: usize,
+    /// The number of enclosing filter expressions, in which `@` is valid.
+    filter_depth: usize,
+    /// The number of enclosing array subscripts, in which `last` is valid.
+    subscript_depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _, _)| token)
+    }
+
+    /// Consumes the next token. Consuming the end of the input advances past
+    /// it, so that backing up after an unexpected token always works.
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn consume(&mut self, expected: &Token) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn consume_word(&mut self, word: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(w)) if w == word => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, expected: &Token) -> Result<(), Error> {
+        if self.consume(expected) {
+            Ok(())
+        } else {
+            Err(self.syntax_error())
+        }
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<(), Error> {
+        if self.consume_word(word) {
+            Ok(())
+        } else {
+            Err(self.syntax_error())
+        }
+    }
+
+    /// Returns a syntax error that points at the next token.
+    fn syntax_error(&self) -> Error {
+        match self.tokens.get(self.pos) {
+            Some((_, start, end)) => anyhow!(
+                "syntax error at or near \"{}\" of jsonpath input",
+                &self.input[*start..*end]
+            ),
+            None => anyhow!("syntax error at end of jsonpath input"),
+        }
+    }
+
+    /// Requires that `node` is a predicate, or a value expression if
+    /// `predicate` is false.
+    fn check(&self, node: Node, predicate: bool) -> Result<Node, Error> {
+        if node.is_predicate() == predicate {
+            Ok(node)
+        } else {
+            Err(self.syntax_error())
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Node, Error> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            left = self.check(left, true)?;
+            self.pos += 1;
+            let right = self.parse_and()?;
+            let right = self.check(right, true)?;
+            left = Node::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, Error> {
+        let mut left = self.parse_not()?;
+        while self.peek() == Some(&Token::And) {
+            left = self.check(left, true)?;
+            self.pos += 1;
+            let right = self.parse_not()?;
+            let right = self.check(right, true)?;
+            left = Node::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Node, Error> {
+        if !self.consume(&Token::Not) {
+            return self.parse_comparison();
+        }
+        let arg = if self.consume(&Token::LParen) {
+            let arg = self.parse_or()?;
+            let arg = self.check(arg, true)?;
+            self.expect(&Token::RParen)?;
+            arg
+        } else if self.consume_word("exists") {
+            self.parse_exists()?
+        } else {
+            return Err(self.syntax_error());
+        };
+        Ok(Node::Not(Box::new(arg)))
+    }
+
+    fn parse_exists(&mut self) -> Result<Node, Error> {
+        self.expect(&Token::LParen)?;
+        let arg = self.parse_or()?;
+        let arg = self.check(arg, false)?;
+        self.expect(&Token::RParen)?;
+        Ok(Node::Exists(Box::new(arg)))
+    }
+
+    fn parse_comparison(&mut self) -> Result<Node, Error> {
+        let left = self.parse_additive()?;
+        let op = match self.peek() {
+            Some(Token::Eq) => CmpOp::Eq,
+            Some(Token::NotEq) => CmpOp::NotEq,
+            Some(Token::Lt) => CmpOp::Lt,
+            Some(Token::Lte) => CmpOp::Lte,
+            Some(Token::Gt) => CmpOp::Gt,
+            Some(Token::Gte) => CmpOp::Gte,
+            Some(Token::Word(w)) if w == "starts" => {
+                let left = self.check(left, false)?;
+                self.pos += 1;
+                self.expect_word("with")?;
+                let right = match self.next() {
+                    Some(Token::String(s)) => Node::String(s),
+                    Some(Token::Variable(name)) => Node::Variable(name),
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.syntax_error());
+                    }
+                };
+                return Ok(Node::StartsWith(Box::new(left), Box::new(right)));
+            }
+            Some(Token::Word(w)) if w == "like_regex" => {
+                let expr = self.check(left, false)?;
+                self.pos += 1;
+                let pattern = match self.next() {
+                    Some(Token::String(s)) => s,
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.syntax_error());
+                    }
+                };
+                let flags = if self.consume_word("flag") {
+                    match self.next() {
+                        Some(Token::String(s)) => s,
+                        _ => {
+                            self.pos -= 1;
+                            return Err(self.syntax_error());
+                        }
+                    }
+                } else {
+                    String::new()
+                };
+                let (flags, regex) = build_regex(&pattern, &flags)?;
+                return Ok(Node::LikeRegex {
+                    expr: Box::new(expr),
+                    pattern,
+                    flags,
+                    regex,
+                });
+            }
+            _ => return Ok(left),
+        };
+        let left = self.check(left, false)?;
+        self.pos += 1;
+        let right = self.parse_additive()?;
+        let right = self.check(right, false)?;
+        Ok(Node::Compare(op, Box::new(left), Box::new(right)))
+    }
+
+    fn parse_additive(&mut self) -> Result<Node, Error> {
+        let mut left = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => ArithOp::Add,
+                Some(Token::Minus) => ArithOp::Sub,
+                _ => return Ok(left),
+            };
+            left = self.check(left, false)?;
+            self.pos += 1;
+            let right = self.parse_multiplicative()?;
+            let right = self.check(right, false)?;
+            left = Node::Arithmetic(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Node, Error> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => ArithOp::Mul,
+                Some(Token::Slash) => ArithOp::Div,
+                Some(Token::Percent) => ArithOp::Mod,
+                _ => return Ok(left),
+            };
+            left = self.check(left, false)?;
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            let right = self.check(right, false)?;
+            left = Node::Arithmetic(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Node, Error> {
+        let op = match self.peek() {
+            Some(Token::Plus) => UnaryOp::Plus,
+            Some(Token::Minus) => UnaryOp::Minus,
+            _ => return self.parse_accessor_expr(),
+        };
+        self.pos += 1;
+        let arg = self.parse_unary()?;
+        let arg = self.check(arg, false)?;
+        // Like PostgreSQL, fold signs into numeric literals.
+        Ok(match (op, arg) {
+            (UnaryOp::Plus, Node::Numeric(n)) => Node::Numeric(n),
+            (UnaryOp::Minus, Node::Numeric(mut n)) => {
+                numeric::cx_datum().neg(&mut n.0);
+                numeric::munge_numeric(&mut n.0)?;
+                Node::Numeric(n)
+            }
+            (op, arg) => Node::Unary(op, Box::new(arg)),
+        })
+    }
+
+    fn parse_accessor_expr(&mut self) -> Result<Node, Error> {
+        let mut node = self.parse_primary()?;
+        loop {
+            let accessor = match self.peek() {
+                Some(Token::Dot) => {
+                    self.pos += 1;
+                    self.parse_dot_accessor()?
+                }
+                Some(Token::LBracket) => {
+                    self.pos += 1;
+                    self.parse_subscripts()?
+                }
+                Some(Token::Question) => {
+                    self.pos += 1;
+                    self.expect(&Token::LParen)?;
+                    self.filter_depth += 1;
+                    let predicate = self.parse_or()?;
+                    let predicate = self.check(predicate, true)?;
+                    self.filter_depth -= 1;
+                    self.expect(&Token::RParen)?;
+                    Accessor::Filter(Box::new(predicate))
+                }
+                _ => return Ok(node),
+            };
+            node = Node::Accessor(Box::new(node), accessor);
+        }
+    }
+
+    fn parse_dot_accessor(&mut self) -> Result<Accessor, Error> {
+        match self.next() {
+            Some(Token::Star) => Ok(Accessor::AnyMember),
+            Some(Token::StarStar) => {
+                if !self.consume(&Token::LBrace) {
+                    return Ok(Accessor::Descendants {
+                        first: 0,
+                        last: LAST_LEVEL,
+                    });
+                }
+                let first = self.parse_level()?;
+                let last = if self.consume_word("to") {
+                    self.parse_level()?
+                } else {
+                    first
+                };
+                self.expect(&Token::RBrace)?;
+                Ok(Accessor::Descendants { first, last })
+            }
+            Some(Token::String(key)) => Ok(Accessor::Member(key)),
+            Some(Token::Word(word)) => {
+                if !self.consume(&Token::LParen) {
+                    return Ok(Accessor::Member(word));
+                }
+                let method = match Method::from_name(&word) {
+                    Some(method) => method,
+                    None if word == "datetime" => {
+                        bail!("jsonpath item method .datetime() is not supported")
+                    }
+                    None => {
+                        self.pos -= 1;
+                        return Err(self.syntax_error());
+                    }
+                };
+                self.expect(&Token::RParen)?;
+                Ok(Accessor::Method(method))
+            }
+            Some(_) => {
+                self.pos -= 1;
+                Err(self.syntax_error())
+            }
+            None => Err(self.syntax_error()),
+        }
+    }
+
+    fn parse_level(&mut self) -> Result<u32, Error> {
+        match self.next() {
+            Some(Token::Word(w)) if w == "last" => Ok(LAST_LEVEL),
+            Some(Token::Numeric(n)) if n.0.exponent() >= 0 => {
+                match numeric::cx_datum().try_into_i32(n.0).map(u32::try_from) {
+                    Ok(Ok(level)) => Ok(level),
+                    _ => {
+                        self.pos -= 1;
+                        Err(self.syntax_error())
+                    }
+                }
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.syntax_error())
+            }
+        }
+    }
+
+    fn parse_subscripts(&mut self) -> Result<Accessor, Error> {
+        if self.consume(&Token::Star) {
+            self.expect(&Token::RBracket)?;
+            return Ok(Accessor::AnyElement);
+        }
+        self.subscript_depth += 1;
+        let mut subscripts = vec![];
+        loop {
+            let from = self.parse_additive()?;
+            let from = self.check(from, false)?;
+            let to = if self.consume_word("to") {
+                let to = self.parse_additive()?;
+                Some(self.check(to, false)?)
+            } else {
+                None
+            };
+            subscripts.push(Subscript { from, to });
+            if !self.consume(&Token::Comma) {
+                break;
+            }
+        }
+        self.subscript_depth -= 1;
+        self.expect(&Token::RBracket)?;
+        Ok(Accessor::Elements(subscripts))
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, Error> {
+        let node = match self.next() {
+            Some(Token::Root) => Node::Root,
+            Some(Token::Current) => {
+                if self.filter_depth == 0 {
+                    bail!("@ is not allowed in root expressions");
+                }
+                Node::Current
+            }
+            Some(Token::Variable(name)) => Node::Variable(name),
+            Some(Token::String(s)) => Node::String(s),
+            Some(Token::Numeric(n)) => Node::Numeric(n),
+            Some(Token::Word(w)) => match w.as_str() {
+                "null" => Node::Null,
+                "true" => Node::Bool(true),
+                "false" => Node::Bool(false),
+                "last" => {
+                    if self.subscript_depth == 0 {
+                        bail!("LAST is allowed only in array subscripts");
+                    }
+                    Node::Last
+                }
+                "exists" => self.parse_exists()?,
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.syntax_error());
+                }
+            },
+            Some(Token::LParen) => {
+                let node = self.parse_or()?;
+                self.expect(&Token::RParen)?;
+                if node.is_predicate() && self.consume_word("is") {
+                    self.expect_word("unknown")?;
+                    Node::IsUnknown(Box::new(node))
+                } else {
+                    node
+                }
+            }
+            Some(_) => {
+                self.pos -= 1;
+                return Err(self.syntax_error());
+            }
+            None => return Err(self.syntax_error()),
+        };
+        Ok(node)
+    }
+}
+
+/// Compiles the pattern of a `like_regex` predicate, returning the flags in
+/// their canonical order and the compiled regex.
+fn build_regex(pattern: &str, flags: &str) -> Result<(String, Regex), Error> {
+    let mut canonical = String::new();
+    for flag in ['i', 's', 'm', 'x', 'q'] {
+        if flags.contains(flag) {
+            canonical.push(flag);
+        }
+    }
+    if let Some(c) = flags.chars().find(|c| !"ismxq".contains(*c)) {
+        bail!("unrecognized flag character \"{c}\" in LIKE_REGEX predicate");
+    }
+    let pattern = if canonical.contains('q') {
+        regex::escape(pattern)
+    } else {
+        pattern.to_owned()
+    };
+    let regex = RegexBuilder::new(&pattern)
+        .case_insensitive(canonical.contains('i'))
+        .dot_matches_new_line(canonical.contains('s'))
+        .multi_line(canonical.contains('m'))
+        .ignore_whitespace(canonical.contains('x') && !canonical.contains('q'))
+        .build()
+        .map_err(|e| anyhow!("invalid regular expression: {e}"))?;
+    Ok((canonical, regex))
+}
+
+/// An item produced by evaluating a [`JsonPath`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JsonPathItem<'a> {
+    /// A JSON value, represented as a `jsonb` datum.
+    Datum(Datum<'a>),
+    /// An object produced by the `.keyvalue()` item method, which has the keys
+    /// `id`, `key` and `value`.
+    ///
+    /// Unlike PostgreSQL, which derives `id` from the position of the object
+    /// in its binary representation, Materialize always sets it to zero.
+    KeyValue { key: &'a str, value: Datum<'a> },
+}
+
+impl<'a> From<Datum<'a>> for JsonPathItem<'a> {
+    fn from(datum: Datum<'a>) -> JsonPathItem<'a> {
+        JsonPathItem::Datum(datum)
+    }
+}
+
+impl<'a> JsonPathItem<'a> {
+    /// Pushes the item onto `packer` as a `jsonb` datum.
+    pub fn push(&self, packer: &mut RowPacker) {
+        match self {
+            JsonPathItem::Datum(datum) => packer.push(datum),
+            JsonPathItem::KeyValue { .. } => packer.push_dict_with(|packer| {
+                for (key, value) in self.entries().expect("keyvalue items are objects") {
+                    packer.push(Datum::String(key));
+                    packer.push(value);
+                }
+            }),
+        }
+    }
+
+    /// Returns the datum that represents the item if it is a JSON scalar.
+    pub fn scalar(&self) -> Option<Datum<'a>> {
+        match self {
+            JsonPathItem::Datum(Datum::Map(_) | Datum::List(_)) | JsonPathItem::KeyValue { .. } => {
+                None
+            }
+            JsonPathItem::Datum(datum) => Some(*datum),
+        }
+    }
+
+    /// Returns the entries of the item, in key order, if it is an object.
+    fn entries(&self) -> Option<Vec<(&'a str, Datum<'a>)>> {
+        match self {
+            JsonPathItem::Datum(Datum::Map(map)) => Some(map.iter().collect()),
+            JsonPathItem::KeyValue { key, value } => Some(vec![
+                ("id", Datum::Numeric(OrderedDecimal(Numeric::zero()))),
+                ("key", Datum::String(key)),
+                ("value", *value),
+            ]),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of the item if it is an array.
+    fn elements(&self) -> Option<Vec<JsonPathItem<'a>>> {
+        match self {
+            JsonPathItem::Datum(Datum::List(list)) => {
+                Some(list.iter().map(JsonPathItem::Datum).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the members of the item if it is an object, or its elements
+    /// if it is an array.
+    fn children(&self) -> Option<Vec<JsonPathItem<'a>>> {
+        match self.entries() {
+            Some(entries) => Some(
+                entries
+                    .into_iter()
+                    .map(|(_, value)| JsonPathItem::Datum(value))
+                    .collect(),
+            ),
+            None => self.elements(),
+        }
+    }
+
+    fn numeric(&self) -> Option<Numeric> {
+        match self {
+            JsonPathItem::Datum(Datum::Numeric(n)) => Some(n.0),
+            _ => None,
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            JsonPathItem::Datum(Datum::Map(_)) | JsonPathItem::KeyValue { .. } => "object",
+            JsonPathItem::Datum(Datum::List(_)) => "array",
+            JsonPathItem::Datum(Datum::String(_)) => "string",
+            JsonPathItem::Datum(Datum::Numeric(_)) => "number",
+            JsonPathItem::Datum(Datum::True | Datum::False) => "boolean",
+            JsonPathItem::Datum(_) => "null",
+        }
+    }
+}
+
+impl JsonPath {
+    /// Reports whether the path is evaluated in strict mode.
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Reports whether the path is a predicate check expression, i.e. whether
+    /// it evaluates to a single boolean or unknown.
+    pub fn is_predicate(&self) -> bool {
+        self.expr.is_predicate()
+    }
+
+    /// Returns the items that the path selects from `target`.
+    ///
+    /// `vars` must be a `jsonb` object that holds the values of the variables
+    /// that the path refers to.
+    pub fn query<'a>(
+        &'a self,
+        target: impl Into<JsonPathItem<'a>>,
+        vars: Datum<'a>,
+    ) -> Result<Vec<JsonPathItem<'a>>, Error> {
+        if !matches!(vars, Datum::Map(_)) {
+            bail!("\"vars\" argument is not an object");
+        }
+        let executor = Executor {
+            root: target.into(),
+            vars,
+            lax: !self.strict,
+        };
+        executor.eval(&self.expr, Context::default())
+    }
+
+    /// Reports whether the path selects any items from `target`.
+    pub fn exists<'a>(
+        &'a self,
+        target: impl Into<JsonPathItem<'a>>,
+        vars: Datum<'a>,
+    ) -> Result<bool, Error> {
+        Ok(!self.query(target, vars)?.is_empty())
+    }
+}
+
+/// The state of the evaluation of a node.
+#[derive(Debug, Default, Clone, Copy)]
+struct Context<'a> {
+    /// The item that `@` refers to.
+    current: Option<JsonPathItem<'a>>,
+    /// The value of `last`.
+    last: Option<i32>,
+}
+
+struct Executor<'a> {
+    root: JsonPathItem<'a>,
+    vars: Datum<'a>,
+    lax: bool,
+}
+
+impl<'a> Executor<'a> {
+    fn eval(&self, node: &'a Node, cx: Context<'a>) -> Result<Vec<JsonPathItem<'a>>, Error> {
+        let item = match node {
+            Node::Root => self.root,
+            Node::Current => cx.current.expect("@ is only valid in filters"),
+            Node::Last => {
+                let last = cx.last.expect("last is only valid in subscripts");
+                JsonPathItem::Datum(Datum::Numeric(OrderedDecimal(last.into())))
+            }
+            Node::Variable(name) => {
+                let Datum::Map(vars) = self.vars else {
+                    unreachable!("vars are validated to be an object")
+                };
+                match vars.iter().find(|(key, _)| key == name) {
+                    Some((_, value)) => JsonPathItem::Datum(value),
+                    None => bail!("could not find jsonpath variable \"{name}\""),
+                }
+            }
+            Node::Null => JsonPathItem::Datum(Datum::JsonNull),
+            Node::Bool(b) => JsonPathItem::Datum(Datum::from(*b)),
+            Node::Numeric(n) => JsonPathItem::Datum(Datum::Numeric(*n)),
+            Node::String(s) => JsonPathItem::Datum(Datum::String(s)),
+            Node::Accessor(base, accessor) => {
+                let mut out = vec![];
+                for item in self.eval(base, cx)? {
+                    self.access(accessor, item, cx, self.lax, &mut out)?;
+                }
+                return Ok(out);
+            }
+            Node::Arithmetic(op, left, right) => {
+                let left = self.eval_unwrapped(left, cx)?;
+                let right = self.eval_unwrapped(right, cx)?;
+                let operand = |items: Vec<JsonPathItem>, side: &str| {
+                    let n = match items.as_slice() {
+                        [item] => item.numeric(),
+                        _ => None,
+                    };
+                    n.ok_or_else(|| {
+                        anyhow!(
+                            "{side} operand of jsonpath operator {} is not a single numeric value",
+                            op.symbol()
+                        )
+                    })
+                };
+                let a = operand(left, "left")?;
+                let b = operand(right, "right")?;
+                JsonPathItem::Datum(Datum::Numeric(OrderedDecimal(arithmetic(*op, a, b)?)))
+            }
+            Node::Unary(op, arg) => {
+                let mut out = vec![];
+                for item in self.eval_unwrapped(arg, cx)? {
+                    let Some(mut n) = item.numeric() else {
+                        bail!(
+                            "operand of unary jsonpath operator {} is not a numeric value",
+                            match op {
+                                UnaryOp::Plus => "+",
+                                UnaryOp::Minus => "-",
+                            }
+                        );
+                    };
+                    if *op == UnaryOp::Minus {
+                        numeric::cx_datum().neg(&mut n);
+                        numeric::munge_numeric(&mut n)?;
+                    }
+                    out.push(JsonPathItem::Datum(Datum::Numeric(OrderedDecimal(n))));
+                }
+                return Ok(out);
+            }
+            _ => JsonPathItem::Datum(match self.predicate(node, cx) {
+                Some(b) => Datum::from(b),
+                None => Datum::JsonNull,
+            }),
+        };
+        Ok(vec![item])
+    }
+
+    /// Like [`Executor::eval`], but unwraps arrays in the result in lax mode.
+    fn eval_unwrapped(
+        &self,
+        node: &'a Node,
+        cx: Context<'a>,
+    ) -> Result<Vec<JsonPathItem<'a>>, Error> {
+        let items = self.eval(node, cx)?;
+        if !self.lax {
+            return Ok(items);
+        }
+        let mut out = vec![];
+        for item in items {
+            match item.elements() {
+                Some(elements) => out.extend(elements),
+                None => out.push(item),
+            }
+        }
+        Ok(out)
+    }
+
+    /// Reports a structural error, which is ignored in lax mode.
+    fn structural_error(&self, msg: &str) -> Result<(), Error> {
+        if self.lax {
+            Ok(())
+        } else {
+            Err(anyhow!("{msg}"))
+        }
+    }
+
+    /// Applies `accessor` to `item`, pushing the resulting items onto `out`.
+    ///
+    /// If `unwrap` is set, accessors that do not apply to arrays are applied
+    /// to the elements of arrays instead.
+    fn access(
+        &self,
+        accessor: &'a Accessor,
+        item: JsonPathItem<'a>,
+        cx: Context<'a>,
+        unwrap: bool,
+        out: &mut Vec<JsonPathItem<'a>>,
+    ) -> Result<(), Error> {
+        let unwrap_array = |out: &mut Vec<JsonPathItem<'a>>| -> Result<bool, Error> {
+            match item.elements() {
+                Some(elements) if unwrap => {
+                    for element in elements {
+                        self.access(accessor, element, cx, false, out)?;
+                    }
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        };
+        match accessor {
+            Accessor::Member(key) => match item.entries() {
+                Some(entries) => match entries.into_iter().find(|(k, _)| k == key) {
+                    Some((_, value)) => out.push(JsonPathItem::Datum(value)),
+                    None => self
+                        .structural_error(&format!("JSON object does not contain key \"{key}\""))?,
+                },
+                None if unwrap_array(out)? => {}
+                None => self.structural_error(
+                    "jsonpath member accessor can only be applied to an object",
+                )?,
+            },
+            Accessor::AnyMember => match item.entries() {
+                Some(entries) => out.extend(
+                    entries
+                        .into_iter()
+                        .map(|(_, value)| JsonPathItem::Datum(value)),
+                ),
+                None if unwrap_array(out)? => {}
+                None => self.structural_error(
+                    "jsonpath wildcard member accessor can only be applied to an object",
+                )?,
+            },
+            Accessor::AnyElement => match item.elements() {
+                Some(elements) => out.extend(elements),
+                None if self.lax => out.push(item),
+                None => self.structural_error(
+                    "jsonpath wildcard array accessor can only be applied to an array",
+                )?,
+            },
+            Accessor::Elements(subscripts) => {
+                let elements = match item.elements() {
+                    Some(elements) => elements,
+                    None if self.lax => vec![item],
+                    None => {
+                        return self.structural_error(
+                            "jsonpath array accessor can only be applied to an array",
+                        )
+                    }
+                };
+                let len = i32::try_from(elements.len())
+                    .map_err(|_| anyhow!("jsonpath array is too large"))?;
+                let cx = Context {
+                    last: Some(len - 1),
+                    ..cx
+                };
+                for subscript in subscripts {
+                    let from = self.subscript(&subscript.from, cx)?;
+                    let to = match &subscript.to {
+                        Some(to) => self.subscript(to, cx)?,
+                        None => from,
+                    };
+                    if from < 0 || from > to || to >= len {
+                        self.structural_error("jsonpath array subscript is out of bounds")?;
+                    }
+                    for i in from.max(0)..=to.min(len - 1) {
+                        let i = usize::try_from(i).expect("index is non-negative");
+                        out.push(elements[i]);
+                    }
+                }
+            }
+            Accessor::Descendants { first, last } => {
+                if *first == 0 {
+                    out.push(item);
+                }
+                self.descend(item, 1, *first, *last, out);
+            }
+            Accessor::Filter(predicate) => {
+                if !unwrap_array(out)? {
+                    let cx = Context {
+                        current: Some(item),
+                        ..cx
+                    };
+                    if self.predicate(predicate, cx) == Some(true) {
+                        out.push(item);
+                    }
+                }
+            }
+            Accessor::Method(method) => self.method(*method, item, unwrap, out, unwrap_array)?,
+        }
+        Ok(())
+    }
+
+    fn method(
+        &self,
+        method: Method,
+        item: JsonPathItem<'a>,
+        unwrap: bool,
+        out: &mut Vec<JsonPathItem<'a>>,
+        unwrap_array: impl Fn(&mut Vec<JsonPathItem<'a>>) -> Result<bool, Error>,
+    ) -> Result<(), Error> {
+        let push_numeric = |out: &mut Vec<JsonPathItem<'a>>, mut n: Numeric| -> Result<(), Error> {
+            numeric::munge_numeric(&mut n)?;
+            out.push(JsonPathItem::Datum(Datum::Numeric(OrderedDecimal(n))));
+            Ok(())
+        };
+        match method {
+            Method::Type => out.push(JsonPathItem::Datum(Datum::String(item.type_name()))),
+            Method::Size => match item.elements() {
+                Some(elements) => {
+                    let len = i64::try_from(elements.len()).expect("array length fits in i64");
+                    push_numeric(out, len.into())?;
+                }
+                None if self.lax => push_numeric(out, Numeric::from(1))?,
+                None => self.structural_error(
+                    "jsonpath item method .size() can only be applied to an array",
+                )?,
+            },
+            _ if unwrap && unwrap_array(out)? => {}
+            Method::Ceiling | Method::Floor | Method::Abs => {
+                let Some(mut n) = item.numeric() else {
+                    bail!(
+                        "jsonpath item method .{}() can only be applied to a numeric value",
+                        method.name()
+                    );
+                };
+                let mut cx = numeric::cx_datum();
+                match method {
+                    Method::Ceiling => {
+                        cx.set_rounding(Rounding::Ceiling);
+                        cx.round(&mut n);
+                    }
+                    Method::Floor => {
+                        cx.set_rounding(Rounding::Floor);
+                        cx.round(&mut n);
+                    }
+                    _ => cx.abs(&mut n),
+                }
+                push_numeric(out, n)?;
+            }
+            Method::Double => {
+                let f = match item {
+                    JsonPathItem::Datum(Datum::Numeric(n)) => {
+                        match n.0.to_string().parse::<f64>() {
+                            Ok(f) if f.is_finite() => f,
+                            _ => bail!("numeric argument of jsonpath item method .double() is out of range for type double precision"),
+                        }
+                    }
+                    JsonPathItem::Datum(Datum::String(s)) => match s.trim().parse::<f64>() {
+                        Ok(f) if f.is_finite() => f,
+                        _ => bail!("string argument of jsonpath item method .double() is not a valid representation of a double precision number"),
+                    },
+                    _ => bail!(
+                        "jsonpath item method .double() can only be applied to a string or numeric value"
+                    ),
+                };
+                push_numeric(out, Numeric::from(f))?;
+            }
+            Method::KeyValue => match item.entries() {
+                Some(entries) => out.extend(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| JsonPathItem::KeyValue { key, value }),
+                ),
+                None => bail!("jsonpath item method .keyvalue() can only be applied to an object"),
+            },
+        }
+        Ok(())
+    }
+
+    /// Pushes the descendants of `item` between `first` and `last` levels
+    /// below it onto `out`, where the children of `item` are at `level`.
+    fn descend(
+        &self,
+        item: JsonPathItem<'a>,
+        level: u32,
+        first: u32,
+        last: u32,
+        out: &mut Vec<JsonPathItem<'a>>,
+    ) {
+        for child in item.children().unwrap_or_default() {
+            let is_container = child.children().is_some();
+            // `.**{last}` selects the leaves, whatever their depth.
+            if level >= first || (first == LAST_LEVEL && last == LAST_LEVEL && !is_container) {
+                out.push(child);
+            }
+            if level < last && is_container {
+                self.descend(child, level + 1, first, last, out);
+            }
+        }
+    }
+
+    /// Evaluates an array subscript to an index.
+    fn subscript(&self, node: &'a Node, cx: Context<'a>) -> Result<i32, Error> {
+        let items = self.eval(node, cx)?;
+        let n = match items.as_slice() {
+            [item] => item.numeric(),
+            _ => None,
+        };
+        let Some(mut n) = n else {
+            bail!("jsonpath array subscript is not a single numeric value");
+        };
+        let mut cx = numeric::cx_datum();
+        cx.set_rounding(Rounding::Down);
+        cx.round(&mut n);
+        cx.try_into_i32(n)
+            .map_err(|_| anyhow!("jsonpath array subscript is out of integer range"))
+    }
+
+    /// Evaluates a predicate, returning `None` if its result is unknown.
+    fn predicate(&self, node: &'a Node, cx: Context<'a>) -> Option<bool> {
+        match node {
+            Node::And(left, right) => match self.predicate(left, cx) {
+                Some(false) => Some(false),
+                Some(true) => self.predicate(right, cx),
+                None => match self.predicate(right, cx) {
+                    Some(false) => Some(false),
+                    _ => None,
+                },
+            },
+            Node::Or(left, right) => match self.predicate(left, cx) {
+                Some(true) => Some(true),
+                Some(false) => self.predicate(right, cx),
+                None => match self.predicate(right, cx) {
+                    Some(true) => Some(true),
+                    _ => None,
+                },
+            },
+            Node::Not(arg) => self.predicate(arg, cx).map(|b| !b),
+            Node::IsUnknown(arg) => Some(self.predicate(arg, cx).is_none()),
+            Node::Exists(arg) => self.eval(arg, cx).ok().map(|items| !items.is_empty()),
+            Node::Compare(op, left, right) => {
+                let (Ok(left), Ok(right)) = (
+                    self.eval_unwrapped(left, cx),
+                    self.eval_unwrapped(right, cx),
+                ) else {
+                    return None;
+                };
+                self.any(
+                    left.iter()
+                        .flat_map(|l| right.iter().map(move |r| compare_items(*op, l, r))),
+                )
+            }
+            Node::StartsWith(left, prefix) => {
+                let (Ok(left), Ok(prefix)) = (self.eval_unwrapped(left, cx), self.eval(prefix, cx))
+                else {
+                    return None;
+                };
+                self.any(left.iter().flat_map(|l| {
+                    prefix.iter().map(move |p| match (l, p) {
+                        (
+                            JsonPathItem::Datum(Datum::String(l)),
+                            JsonPathItem::Datum(Datum::String(p)),
+                        ) => Some(l.starts_with(p)),
+                        _ => None,
+                    })
+                }))
+            }
+            Node::LikeRegex { expr, regex, .. } => {
+                let Ok(items) = self.eval_unwrapped(expr, cx) else {
+                    return None;
+                };
+                self.any(items.iter().map(|item| match item {
+                    JsonPathItem::Datum(Datum::String(s)) => Some(regex.is_match(s)),
+                    _ => None,
+                }))
+            }
+            _ => unreachable!("parser only accepts predicates here"),
+        }
+    }
+
+    /// Combines the results of comparing pairs of items. The result is true
+    /// if any comparison is true, unless a comparison is unknown in strict
+    /// mode. In lax mode, evaluation stops at the first true comparison.
+    fn any(&self, results: impl Iterator<Item = Option<bool>>) -> Option<bool> {
+        let mut found = false;
+        let mut unknown = false;
+        for result in results {
+            match result {
+                Some(true) if self.lax => return Some(true),
+                Some(true) => found = true,
+                Some(false) => {}
+                None if self.lax => unknown = true,
+                None => return None,
+            }
+        }
+        if found {
+            Some(true)
+        } else if unknown {
+            None
+        } else {
+            Some(false)
+        }
+    }
+}
+
+fn compare_items(op: CmpOp, left: &JsonPathItem, right: &JsonPathItem) -> Option<bool> {
+    let ordering = match (left.scalar(), right.scalar()) {
+        (Some(Datum::JsonNull), Some(Datum::JsonNull)) => Ordering::Equal,
+        // Null is only equal to itself, even if the other item is not a
+        // scalar.
+        (Some(Datum::JsonNull), _) | (_, Some(Datum::JsonNull)) => return Some(op == CmpOp::NotEq),
+        (Some(l @ (Datum::True | Datum::False)), Some(r @ (Datum::True | Datum::False))) => {
+            (l == Datum::True).cmp(&(r == Datum::True))
+        }
+        (Some(Datum::Numeric(l)), Some(Datum::Numeric(r))) => l.cmp(&r),
+        (Some(Datum::String(l)), Some(Datum::String(r))) => l.cmp(r),
+        _ => return None,
+    };
+    Some(match op {
+        CmpOp::Eq => ordering == Ordering::Equal,
+        CmpOp::NotEq => ordering != Ordering::Equal,
+        CmpOp::Lt => ordering == Ordering::Less,
+        CmpOp::Lte => ordering != Ordering::Greater,
+        CmpOp::Gt => ordering == Ordering::Greater,
+        CmpOp::Gte => ordering != Ordering::Less,
+    })
+}
+
+fn arithmetic(op: ArithOp, mut a: Numeric, b: Numeric) -> Result<Numeric, Error> {
+    let mut cx = numeric::cx_datum();
+    match op {
+        ArithOp::Add => cx.add(&mut a, &b),
+        ArithOp::Sub => cx.sub(&mut a, &b),
+        ArithOp::Mul => cx.mul(&mut a, &b),
+        ArithOp::Div | ArithOp::Mod if b.is_zero() => bail!("division by zero"),
+        ArithOp::Div => cx.div(&mut a, &b),
+        ArithOp::Mod => cx.rem(&mut a, &b),
+    }
+    if cx.status().overflow() || a.is_infinite() {
+        bail!("value overflows numeric format");
+    }
+    numeric::munge_numeric(&mut a)?;
+    Ok(a)
+}
+
+impl Arbitrary for JsonPath {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<JsonPath>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::sample::select(vec![
+            "$",
+            "strict $",
+            "$.a",
+            "$.\"a b\"[*]",
+            "$[0 to last, 2]",
+            "$.**{1 to 2}.b",
+            "$.a ? (@ > 1 && @ < 10)",
+            "strict $.a[*] ? (@.b starts with \"x\")",
+            "$.a.size() + 1",
+            "-$.a * 2",
+            "exists($.a ? (@ like_regex \"^x\" flag \"i\"))",
+            "$.* == $x",
+            "$.a.keyvalue().key",
+        ])
+        .prop_map(|s| s.parse().expect("valid jsonpath"))
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_parse_display() {
+        for (input, expected) in [
+            ("$", "$"),
+            ("lax $", "$"),
+            ("strict $", "strict $"),
+            ("$.a.b", "$.\"a\".\"b\""),
+            ("$.\"a\\\"b\"", "$.\"a\\\"b\""),
+            ("$a", "$\"a\""),
+            ("$[*]", "$[*]"),
+            ("$.*", "$.*"),
+            ("$[1, 2 to last]", "$[1,2 to last]"),
+            ("$[last - 1]", "$[last - 1]"),
+            ("$.**", "$.**"),
+            ("$.**{2}", "$.**{2}"),
+            ("$.**{2 to last}", "$.**{2 to last}"),
+            ("$.a ? (@ > 1)", "$.\"a\"?(@ > 1)"),
+            (
+                "$ ? (@.a == 1 || @.b == 2 && @.c == 3)",
+                "$?(@.\"a\" == 1 || @.\"b\" == 2 && @.\"c\" == 3)",
+            ),
+            (
+                "$ ? ((@.a == 1 || @.b == 2) && @.c == 3)",
+                "$?((@.\"a\" == 1 || @.\"b\" == 2) && @.\"c\" == 3)",
+            ),
+            ("$.a == 1", "($.\"a\" == 1)"),
+            ("1 + 2 * 3", "(1 + 2 * 3)"),
+            ("(1 + 2) * 3", "((1 + 2) * 3)"),
+            ("1 - (2 - 3)", "(1 - (2 - 3))"),
+            ("-1", "-1"),
+            ("- $.a", "(-$.\"a\")"),
+            ("-(1 + 2)", "(-(1 + 2))"),
+            ("1.5e1", "15"),
+            ("$.a.type()", "$.\"a\".type()"),
+            ("$.a.type", "$.\"a\".\"type\""),
+            ("$.size().double()", "$.size().double()"),
+            ("$ ? (!(@ > 1))", "$?(!(@ > 1))"),
+            ("$ ? ((@ > 1) is unknown)", "$?((@ > 1) is unknown)"),
+            ("$ ? (exists (@.a))", "$?(exists (@.\"a\"))"),
+            ("$ ? (@ starts with \"a\")", "$?(@ starts with \"a\")"),
+            (
+                "$ ? (@ like_regex \"a.c\" flag \"si\")",
+                "$?(@ like_regex \"a.c\" flag \"is\")",
+            ),
+            ("\"a\\tb\\u0041\\x42\"", "\"a\\tbAB\""),
+            ("($.a + 1).abs()", "($.\"a\" + 1).abs()"),
+            ("null", "null"),
+            ("true", "true"),
+        ] {
+            let path: JsonPath = input.parse().unwrap();
+            assert_eq!(path.to_string(), expected, "input: {input}");
+            let reparsed: JsonPath = expected.parse().unwrap();
+            assert_eq!(reparsed.to_string(), expected, "input: {expected}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_parse_errors() {
+        for (input, expected) in [
+            ("", "syntax error at end of jsonpath input"),
+            ("$.", "syntax error at end of jsonpath input"),
+            ("$ $", "syntax error at or near \"$\" of jsonpath input"),
+            ("@", "@ is not allowed in root expressions"),
+            ("last", "LAST is allowed only in array subscripts"),
+            ("$ ? (@)", "syntax error at or near \")\" of jsonpath input"),
+            (
+                "$ ? (@ > 1 > 2)",
+                "syntax error at or near \">\" of jsonpath input",
+            ),
+            ("$ && $", "syntax error at or near \"&&\" of jsonpath input"),
+            ("$.a()", "syntax error at or near \"(\" of jsonpath input"),
+            (
+                "$.datetime()",
+                "jsonpath item method .datetime() is not supported",
+            ),
+            (
+                "\"abc",
+                "unexpected end of quoted string at end of jsonpath input",
+            ),
+            (
+                "1a",
+                "trailing junk after numeric literal at or near \"1a\" of jsonpath input",
+            ),
+            (
+                "$ ? (@ like_regex \"a\" flag \"z\")",
+                "unrecognized flag character \"z\" in LIKE_REGEX predicate",
+            ),
+        ] {
+            let err = input.parse::<JsonPath>().unwrap_err();
+            assert_eq!(err.to_string(), expected, "input: {input}");
+        }
+    }
+
+    proptest! {
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // too slow
+        fn test_display_roundtrip(path: JsonPath) {
+            let s = path.to_string();
+            let reparsed: JsonPath = s.parse().unwrap();
+            prop_assert_eq!(reparsed.to_string(), s);
+        }
+    }
+}
@@ -0,0 +1,642 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Network address abstract data types.
+//!
+//! These types are modeled after PostgreSQL's `inet`, `cidr`, `macaddr` and
+//! `macaddr8` types. See:
+//!   * <https://www.postgresql.org/docs/current/datatype-net-types.html>
+//!   * <https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/network.c>
+//!   * <https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/mac.c>
+//!   * <https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/mac8.c>
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error};
+use columnation::{Columnation, CopyRegion};
+use mz_proto::{RustType, TryFromProtoError};
+use proptest::arbitrary::Arbitrary;
+use proptest::prelude::*;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+include!(concat!(env!("OUT_DIR"), "/mz_repr.adt.network.rs"));
+
+/// The family tag used in the binary encoding of IPv4 addresses.
+const FAMILY_V4: u8 = 4;
+/// The family tag used in the binary encoding of IPv6 addresses.
+const FAMILY_V6: u8 = 6;
+
+/// An IPv4 or IPv6 host address, with an optional subnet, i.e. the
+/// PostgreSQL `inet` type.
+///
+/// The same representation is used for the `cidr` type, whose values
+/// additionally have no bits set to the right of the netmask. See
+/// [`Inet::is_network`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Inet {
+    addr: IpAddr,
+    netmask: u8,
+}
+
+impl Inet {
+    /// Constructs a new `Inet` from an address and the number of bits in its
+    /// netmask.
+    pub fn new(addr: IpAddr, netmask: u8) -> Result<Inet, Error> {
+        let inet = Inet { addr, netmask };
+        if netmask > inet.max_netmask() {
+            bail!("invalid mask length: {}", netmask);
+        }
+        Ok(inet)
+    }
+
+    /// Constructs a new `Inet` that describes the single host `addr`.
+    pub fn for_host(addr: IpAddr) -> Inet {
+        let netmask = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        Inet { addr, netmask }
+    }
+
+    /// Returns the address, including any bits to the right of the netmask.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the number of bits in the netmask.
+    pub fn netmask(&self) -> u8 {
+        self.netmask
+    }
+
+    /// Returns the family of the address, i.e. `4` or `6`.
+    pub fn family(&self) -> i32 {
+        match self.addr {
+            IpAddr::V4(_) => 4,
+            IpAddr::V6(_) => 6,
+        }
+    }
+
+    /// Returns the largest netmask valid for this address's family.
+    pub fn max_netmask(&self) -> u8 {
+        match self.addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    /// Reports whether no bits are set to the right of the netmask, i.e.
+    /// whether this value is a valid `cidr`.
+    pub fn is_network(&self) -> bool {
+        self.addr_bits() & !self.mask_bits(self.netmask) == 0
+    }
+
+    /// Returns the network part of the address, with the bits to the right
+    /// of the netmask cleared.
+    pub fn network(&self) -> Inet {
+        let bits = self.addr_bits() & self.mask_bits(self.netmask);
+        self.with_addr_bits(bits, self.netmask)
+    }
+
+    /// Returns the broadcast address of the network, with the bits to the
+    /// right of the netmask set.
+    pub fn broadcast(&self) -> Inet {
+        let bits = self.addr_bits() | (!self.mask_bits(self.netmask) & self.width_bits());
+        self.with_addr_bits(bits, self.netmask)
+    }
+
+    /// Returns the netmask of the network as a host address.
+    pub fn netmask_addr(&self) -> Inet {
+        let bits = self.mask_bits(self.netmask);
+        self.with_addr_bits(bits, self.max_netmask())
+    }
+
+    /// Returns the host mask of the network as a host address.
+    pub fn hostmask_addr(&self) -> Inet {
+        let bits = !self.mask_bits(self.netmask) & self.width_bits();
+        self.with_addr_bits(bits, self.max_netmask())
+    }
+
+    /// Returns a copy of this value with the netmask set to `netmask`.
+    pub fn with_netmask(&self, netmask: u8) -> Result<Inet, Error> {
+        Inet::new(self.addr, netmask)
+    }
+
+    /// Reports whether `self` strictly contains `other`, i.e. the `>>`
+    /// operator.
+    pub fn contains(&self, other: &Inet) -> bool {
+        self.netmask < other.netmask && self.contains_or_eq(other)
+    }
+
+    /// Reports whether `self` contains or equals `other`, i.e. the `>>=`
+    /// operator.
+    pub fn contains_or_eq(&self, other: &Inet) -> bool {
+        self.family() == other.family()
+            && self.netmask <= other.netmask
+            && self.prefix_eq(other, self.netmask)
+    }
+
+    /// Reports whether either of `self` and `other` contains or equals the
+    /// other, i.e. the `&&` operator.
+    pub fn overlaps(&self, other: &Inet) -> bool {
+        self.family() == other.family()
+            && self.prefix_eq(other, std::cmp::min(self.netmask, other.netmask))
+    }
+
+    /// Reports whether the first `bits` bits of the two addresses are equal.
+    fn prefix_eq(&self, other: &Inet, bits: u8) -> bool {
+        let mask = self.mask_bits(bits);
+        self.addr_bits() & mask == other.addr_bits() & mask
+    }
+
+    /// Returns the address as an integer.
+    fn addr_bits(&self) -> u128 {
+        match self.addr {
+            IpAddr::V4(addr) => u128::from(u32::from(addr)),
+            IpAddr::V6(addr) => u128::from(addr),
+        }
+    }
+
+    /// Returns an integer with all bits of the address's width set.
+    fn width_bits(&self) -> u128 {
+        match self.addr {
+            IpAddr::V4(_) => u128::from(u32::MAX),
+            IpAddr::V6(_) => u128::MAX,
+        }
+    }
+
+    /// Returns an integer with the leftmost `bits` bits of the address's
+    /// width set.
+    fn mask_bits(&self, bits: u8) -> u128 {
+        let zeros = u32::from(self.max_netmask() - bits);
+        u128::MAX.checked_shl(zeros).unwrap_or(0) & self.width_bits()
+    }
+
+    fn with_addr_bits(&self, bits: u128, netmask: u8) -> Inet {
+        let addr = match self.addr {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(
+                u32::try_from(bits).expect("masked to 32 bits"),
+            )),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(bits)),
+        };
+        Inet { addr, netmask }
+    }
+
+    /// Parses a `cidr` value.
+    ///
+    /// In addition to the syntax accepted for `inet` values, IPv4 networks
+    /// may be abbreviated by omitting trailing octets, in which case the
+    /// netmask defaults to the classful netmask of the network. Values with
+    /// bits set to the right of the netmask are rejected.
+    pub fn parse_cidr(s: &str) -> Result<Inet, Error> {
+        let (addr, netmask) = match s.split_once('/') {
+            Some((addr, netmask)) => (addr, Some(parse_netmask(netmask)?)),
+            None => (s, None),
+        };
+        let inet = match addr.parse::<IpAddr>() {
+            Ok(addr) => match netmask {
+                Some(netmask) => Inet::new(addr, netmask)?,
+                None => Inet::for_host(addr),
+            },
+            Err(_) => parse_abbreviated_ipv4(addr, netmask)?,
+        };
+        if !inet.is_network() {
+            bail!("Value has bits set to right of mask.");
+        }
+        Ok(inet)
+    }
+
+    /// Returns the binary encoding of this value, which is the family tag,
+    /// followed by the netmask, followed by the big endian address.
+    pub fn encode_binary(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(2 + 16);
+        match self.addr {
+            IpAddr::V4(addr) => {
+                res.push(FAMILY_V4);
+                res.push(self.netmask);
+                res.extend_from_slice(&addr.octets());
+            }
+            IpAddr::V6(addr) => {
+                res.push(FAMILY_V6);
+                res.push(self.netmask);
+                res.extend_from_slice(&addr.octets());
+            }
+        }
+        res
+    }
+
+    /// Decodes a value from the encoding produced by [`Inet::encode_binary`].
+    pub fn decode_binary(raw: &[u8]) -> Result<Inet, Error> {
+        let (family, netmask, addr) = match raw {
+            [family, netmask, addr @ ..] => (*family, *netmask, addr),
+            _ => bail!(
+                "invalid binary size, expecting at least 2, found {}",
+                raw.len()
+            ),
+        };
+        let addr = match family {
+            FAMILY_V4 => {
+                let octets: [u8; 4] = addr
+                    .try_into()
+                    .map_err(|_| anyhow!("invalid IPv4 address size: {}", addr.len()))?;
+                IpAddr::V4(Ipv4Addr::from(octets))
+            }
+            FAMILY_V6 => {
+                let octets: [u8; 16] = addr
+                    .try_into()
+                    .map_err(|_| anyhow!("invalid IPv6 address size: {}", addr.len()))?;
+                IpAddr::V6(Ipv6Addr::from(octets))
+            }
+            _ => bail!("invalid address family: {}", family),
+        };
+        Inet::new(addr, netmask)
+    }
+}
+
+/// Parses the netmask portion of an `inet` or `cidr` value.
+fn parse_netmask(s: &str) -> Result<u8, Error> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        bail!("invalid mask length: {}", s);
+    }
+    s.parse().map_err(|_| anyhow!("invalid mask length: {}", s))
+}
+
+/// Parses an IPv4 network written with fewer than four octets, e.g. `10.1`.
+///
+/// See `inet_cidr_pton_ipv4` in PostgreSQL's `inet_cidr_ntop.c`.
+fn parse_abbreviated_ipv4(s: &str, netmask: Option<u8>) -> Result<Inet, Error> {
+    let mut octets = [0u8; 4];
+    let mut n = 0;
+    for part in s.split('.') {
+        if n == octets.len() || part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            bail!("invalid address: {}", s);
+        }
+        octets[n] = part
+            .parse()
+            .map_err(|_| anyhow!("invalid address: {}", s))?;
+        n += 1;
+    }
+    let given_bits = u8::try_from(n * 8).expect("at most 32 bits");
+    let netmask = match netmask {
+        Some(netmask) => netmask,
+        None => {
+            // Classful network.
+            let mut bits = match octets[0] {
+                240..=255 => 32,
+                224..=239 => 8,
+                192..=223 => 24,
+                128..=191 => 16,
+                _ => 8,
+            };
+            if bits < given_bits {
+                bits = given_bits;
+            }
+            // Multicast networks are 224/4.
+            if bits == 8 && octets[0] == 224 {
+                bits = 4;
+            }
+            bits
+        }
+    };
+    Inet::new(IpAddr::V4(Ipv4Addr::from(octets)), netmask)
+}
+
+impl Ord for Inet {
+    /// Compares two values the same way PostgreSQL does: IPv4 addresses sort
+    /// before IPv6 addresses, and otherwise values are ordered by their
+    /// common network prefix, then by netmask, and finally by the full
+    /// address.
+    fn cmp(&self, other: &Inet) -> Ordering {
+        self.family()
+            .cmp(&other.family())
+            .then_with(|| {
+                let mask = self.mask_bits(std::cmp::min(self.netmask, other.netmask));
+                (self.addr_bits() & mask).cmp(&(other.addr_bits() & mask))
+            })
+            .then_with(|| self.netmask.cmp(&other.netmask))
+            .then_with(|| self.addr_bits().cmp(&other.addr_bits()))
+    }
+}
+
+impl PartialOrd for Inet {
+    fn partial_cmp(&self, other: &Inet) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for Inet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Inet, Error> {
+        let (addr, netmask) = match s.split_once('/') {
+            Some((addr, netmask)) => (addr, Some(parse_netmask(netmask)?)),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr.parse()?;
+        match netmask {
+            Some(netmask) => Inet::new(addr, netmask),
+            None => Ok(Inet::for_host(addr)),
+        }
+    }
+}
+
+impl fmt::Display for Inet {
+    /// Formats the value as an `inet`, which omits the netmask for host
+    /// addresses. Use [`Inet::display_cidr`] to always include the netmask.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.netmask == self.max_netmask() {
+            write!(f, "{}", self.addr)
+        } else {
+            write!(f, "{}/{}", self.addr, self.netmask)
+        }
+    }
+}
+
+impl Inet {
+    /// Returns an object that formats the value as a `cidr`, which always
+    /// includes the netmask.
+    pub fn display_cidr(&self) -> impl fmt::Display + '_ {
+        struct DisplayCidr<'a>(&'a Inet);
+
+        impl fmt::Display for DisplayCidr<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}/{}", self.0.addr, self.0.netmask)
+            }
+        }
+
+        DisplayCidr(self)
+    }
+}
+
+impl RustType<ProtoInet> for Inet {
+    fn into_proto(&self) -> ProtoInet {
+        let address = match self.addr {
+            IpAddr::V4(addr) => addr.octets().to_vec(),
+            IpAddr::V6(addr) => addr.octets().to_vec(),
+        };
+        ProtoInet {
+            address,
+            netmask: u32::from(self.netmask),
+        }
+    }
+
+    fn from_proto(proto: ProtoInet) -> Result<Self, TryFromProtoError> {
+        let addr = match proto.address.len() {
+            4 => IpAddr::V4(Ipv4Addr::from(
+                <[u8; 4]>::try_from(proto.address.as_slice()).expect("checked length"),
+            )),
+            16 => IpAddr::V6(Ipv6Addr::from(
+                <[u8; 16]>::try_from(proto.address.as_slice()).expect("checked length"),
+            )),
+            n => {
+                return Err(TryFromProtoError::InvalidFieldError(format!(
+                    "invalid inet address length: {n}"
+                )))
+            }
+        };
+        let netmask = u8::try_from(proto.netmask)?;
+        Inet::new(addr, netmask).map_err(|e| TryFromProtoError::InvalidFieldError(e.to_string()))
+    }
+}
+
+impl Columnation for Inet {
+    type InnerRegion = CopyRegion<Inet>;
+}
+
+impl Arbitrary for Inet {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            (any::<[u8; 4]>(), 0..=32u8).prop_map(|(octets, netmask)| Inet {
+                addr: IpAddr::V4(Ipv4Addr::from(octets)),
+                netmask,
+            }),
+            (any::<[u8; 16]>(), 0..=128u8).prop_map(|(octets, netmask)| Inet {
+                addr: IpAddr::V6(Ipv6Addr::from(octets)),
+                netmask,
+            }),
+        ]
+        .boxed()
+    }
+}
+
+/// A 6 byte MAC address, i.e. the PostgreSQL `macaddr` type.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Arbitrary,
+)]
+pub struct MacAddr(pub [u8; 6]);
+
+/// An 8 byte MAC address in EUI-64 format, i.e. the PostgreSQL `macaddr8`
+/// type.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Arbitrary,
+)]
+pub struct MacAddr8(pub [u8; 8]);
+
+impl MacAddr {
+    /// Converts the address to EUI-64 format by inserting `FF:FE` in the
+    /// middle of the address.
+    pub fn to_macaddr8(&self) -> MacAddr8 {
+        let [a, b, c, d, e, f] = self.0;
+        MacAddr8([a, b, c, 0xff, 0xfe, d, e, f])
+    }
+}
+
+impl MacAddr8 {
+    /// Converts the address to a 6 byte address. Only addresses with `FF:FE`
+    /// as their fourth and fifth bytes can be converted.
+    pub fn to_macaddr(&self) -> Result<MacAddr, Error> {
+        match self.0 {
+            [a, b, c, 0xff, 0xfe, d, e, f] => Ok(MacAddr([a, b, c, d, e, f])),
+            _ => bail!(
+                "macaddr8 data out of range to convert to macaddr: \
+                 Only addresses that have FF and FE as values in the 4th and 5th \
+                 bytes from the left, for example xx:xx:xx:ff:fe:xx:xx:xx, are \
+                 eligible to be converted from macaddr8 to macaddr."
+            ),
+        }
+    }
+}
+
+/// Parses the hexadecimal digits of a MAC address into bytes.
+///
+/// Digits may be split into equally sized groups of whole bytes separated by
+/// `:`, `-` or `.`, and a single kind of separator must be used throughout.
+fn parse_mac_bytes(s: &str) -> Result<Vec<u8>, Error> {
+    let s = s.trim();
+    let separator = s.chars().find(|c| matches!(c, ':' | '-' | '.'));
+    let groups: Vec<&str> = match separator {
+        Some(separator) => s.split(separator).collect(),
+        None => vec![s],
+    };
+    let group_len = groups[0].len();
+    if group_len == 0 || group_len % 2 != 0 {
+        bail!("invalid MAC address: {}", s);
+    }
+    let mut bytes = vec![];
+    for group in groups {
+        if group.len() != group_len || !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("invalid MAC address: {}", s);
+        }
+        for i in (0..group.len()).step_by(2) {
+            bytes.push(u8::from_str_radix(&group[i..i + 2], 16)?);
+        }
+    }
+    Ok(bytes)
+}
+
+impl FromStr for MacAddr {
+    type Err = Error;
+
+    /// Accepts the formats that PostgreSQL accepts, e.g. `08:00:2b:01:02:03`,
+    /// `08-00-2b-01-02-03`, `08002b:010203`, `08002b-010203`,
+    /// `0800.2b01.0203`, `0800-2b01-0203` and `08002b010203`.
+    fn from_str(s: &str) -> Result<MacAddr, Error> {
+        let bytes = parse_mac_bytes(s)?;
+        let bytes: [u8; 6] = bytes
+            .try_into()
+            .map_err(|_| anyhow!("invalid MAC address: {}", s))?;
+        Ok(MacAddr(bytes))
+    }
+}
+
+impl FromStr for MacAddr8 {
+    type Err = Error;
+
+    /// Accepts the same formats as [`MacAddr`] with either 6 or 8 bytes. 6
+    /// byte addresses are converted to EUI-64 format.
+    fn from_str(s: &str) -> Result<MacAddr8, Error> {
+        let bytes = parse_mac_bytes(s)?;
+        match bytes.len() {
+            6 => Ok(MacAddr(bytes.try_into().expect("checked length")).to_macaddr8()),
+            8 => Ok(MacAddr8(bytes.try_into().expect("checked length"))),
+            _ => bail!("invalid MAC address: {}", s),
+        }
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02x}:{b:02x}:{c:02x}:{d:02x}:{e:02x}:{g:02x}")
+    }
+}
+
+impl fmt::Display for MacAddr8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c, d, e, g, h, i] = self.0;
+        write!(
+            f,
+            "{a:02x}:{b:02x}:{c:02x}:{d:02x}:{e:02x}:{g:02x}:{h:02x}:{i:02x}"
+        )
+    }
+}
+
+impl Columnation for MacAddr {
+    type InnerRegion = CopyRegion<MacAddr>;
+}
+
+impl Columnation for MacAddr8 {
+    type InnerRegion = CopyRegion<MacAddr8>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_inet_roundtrip() {
+        for s in [
+            "192.168.1.5",
+            "192.168.1.0/24",
+            "::1",
+            "2001:db8::/32",
+            "0.0.0.0/0",
+        ] {
+            let inet: Inet = s.parse().unwrap();
+            assert_eq!(inet.to_string(), s);
+            assert_eq!(Inet::decode_binary(&inet.encode_binary()).unwrap(), inet);
+        }
+        assert!("192.168.1.5/33".parse::<Inet>().is_err());
+        assert!("192.168.1".parse::<Inet>().is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_cidr() {
+        let cases = [
+            ("10", "10.0.0.0/8"),
+            ("10.1", "10.1.0.0/16"),
+            ("128.1", "128.1.0.0/16"),
+            ("192.168.1", "192.168.1.0/24"),
+            ("224", "224.0.0.0/4"),
+            ("10.1.2.3", "10.1.2.3/32"),
+            ("10.1/24", "10.1.0.0/24"),
+            ("2001:db8::/32", "2001:db8::/32"),
+        ];
+        for (input, expected) in cases {
+            let cidr = Inet::parse_cidr(input).unwrap();
+            assert_eq!(cidr.display_cidr().to_string(), expected, "{input}");
+        }
+        assert!(Inet::parse_cidr("192.168.1.5/24").is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_inet_operations() {
+        let net: Inet = "192.168.1.0/24".parse().unwrap();
+        let host: Inet = "192.168.1.5".parse().unwrap();
+        let other: Inet = "10.0.0.0/8".parse().unwrap();
+        assert!(net.contains(&host));
+        assert!(!host.contains(&net));
+        assert!(net.contains_or_eq(&net));
+        assert!(!net.contains(&net));
+        assert!(net.overlaps(&host));
+        assert!(!net.overlaps(&other));
+        assert_eq!(host.with_netmask(24).unwrap().network(), net);
+        assert_eq!(net.broadcast().to_string(), "192.168.1.255/24".to_string());
+        assert_eq!(net.netmask_addr().to_string(), "255.255.255.0");
+        assert_eq!(net.hostmask_addr().to_string(), "0.0.0.255");
+
+        // IPv4 sorts before IPv6, and networks sort before the hosts they
+        // contain.
+        let v6: Inet = "::1".parse().unwrap();
+        assert!(host < v6);
+        assert!(net < host);
+    }
+
+    #[mz_ore::test]
+    fn test_macaddr() {
+        let expected = "08:00:2b:01:02:03";
+        for s in [
+            "08:00:2b:01:02:03",
+            "08-00-2b-01-02-03",
+            "08002b:010203",
+            "08002b-010203",
+            "0800.2b01.0203",
+            "0800-2b01-0203",
+            "08002b010203",
+        ] {
+            let mac: MacAddr = s.parse().unwrap();
+            assert_eq!(mac.to_string(), expected, "{s}");
+        }
+        assert!("08:00:2b:01:02".parse::<MacAddr>().is_err());
+        assert!("08:00:2b:01:02:0g".parse::<MacAddr>().is_err());
+
+        let mac8: MacAddr8 = "08:00:2b:01:02:03".parse().unwrap();
+        assert_eq!(mac8.to_string(), "08:00:2b:ff:fe:01:02:03");
+        assert_eq!(mac8.to_macaddr().unwrap().to_string(), expected);
+        let mac8: MacAddr8 = "08:00:2b:01:02:03:04:05".parse().unwrap();
+        assert!(mac8.to_macaddr().is_err());
+    }
+}
@@ -0,0 +1,1610 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Text search abstract data types.
+//!
+//! These types are modeled after PostgreSQL's `tsvector` and `tsquery` types,
+//! and the functions that construct, match and rank them. See:
+//!   * <https://www.postgresql.org/docs/current/textsearch.html>
+//!   * <https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/tsvector.c>
+//!   * <https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/tsquery.c>
+//!   * <https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/tsvector_op.c>
+//!   * <https://github.com/postgres/postgres/blob/REL_16_STABLE/src/backend/utils/adt/tsrank.c>
+//!
+//! Values of both types are stored as [`Datum::String`]s that hold their
+//! canonical text representation, i.e. the output of their
+//! [`Display`](fmt::Display) implementations.
+//!
+//! [`Datum::String`]: crate::Datum::String
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error};
+use mz_ore::cast::CastLossy;
+use proptest::prelude::*;
+use proptest_derive::Arbitrary;
+
+mod english;
+
+/// The greatest position of a lexeme in a [`TsVector`]. Greater positions are
+/// clamped to it.
+pub const MAX_POSITION: u16 = (1 << 14) - 1;
+
+/// The greatest distance of a phrase operator in a [`TsQuery`].
+pub const MAX_PHRASE_DISTANCE: u16 = 1 << 14;
+
+/// The maximum length of a lexeme, in bytes.
+pub const MAX_LEXEME_LEN: usize = (1 << 11) - 1;
+
+/// The maximum number of positions of a lexeme in a [`TsVector`]. Further
+/// positions are dropped.
+const MAX_POSITIONS_PER_LEXEME: usize = 256;
+
+/// The weight of an occurrence of a lexeme, which determines how much the
+/// occurrence contributes to [`TsQuery::rank`].
+#[derive(Arbitrary, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TsWeight {
+    D,
+    C,
+    B,
+    A,
+}
+
+impl TsWeight {
+    /// Returns the weight's numeric code, which ranges from 0 for `D` to 3 for
+    /// `A`.
+    pub fn code(&self) -> u8 {
+        match self {
+            TsWeight::D => 0,
+            TsWeight::C => 1,
+            TsWeight::B => 2,
+            TsWeight::A => 3,
+        }
+    }
+
+    /// Returns the weight whose numeric code is the two low bits of `code`.
+    pub fn from_code(code: u16) -> TsWeight {
+        match code & 0b11 {
+            0 => TsWeight::D,
+            1 => TsWeight::C,
+            2 => TsWeight::B,
+            _ => TsWeight::A,
+        }
+    }
+
+    /// Returns the bit that represents the weight in
+    /// [`TsQueryOperand::weights`].
+    fn mask(&self) -> u8 {
+        1 << self.code()
+    }
+
+    fn from_char(c: char) -> Option<TsWeight> {
+        match c.to_ascii_uppercase() {
+            'A' => Some(TsWeight::A),
+            'B' => Some(TsWeight::B),
+            'C' => Some(TsWeight::C),
+            'D' => Some(TsWeight::D),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TsWeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            TsWeight::A => "A",
+            TsWeight::B => "B",
+            TsWeight::C => "C",
+            TsWeight::D => "D",
+        })
+    }
+}
+
+/// An occurrence of a lexeme in a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TsPosition {
+    /// The 1-based index of the word that produced the lexeme.
+    pub position: u16,
+    pub weight: TsWeight,
+}
+
+impl TsPosition {
+    /// Returns the PostgreSQL binary representation of the position, which
+    /// stores the weight's code in the two high bits.
+    pub fn to_bits(&self) -> u16 {
+        (u16::from(self.weight.code()) << 14) | self.position
+    }
+
+    /// Decodes the PostgreSQL binary representation of a position.
+    pub fn from_bits(bits: u16) -> TsPosition {
+        TsPosition {
+            position: bits & MAX_POSITION,
+            weight: TsWeight::from_code(bits >> 14),
+        }
+    }
+}
+
+/// A lexeme of a [`TsVector`], with the positions at which it occurs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TsLexeme {
+    text: String,
+    positions: Vec<TsPosition>,
+}
+
+impl TsLexeme {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the sorted, distinct positions of the lexeme, which are empty
+    /// if the vector does not record them.
+    pub fn positions(&self) -> &[TsPosition] {
+        &self.positions
+    }
+}
+
+/// A document preprocessed for text search, i.e. the PostgreSQL `tsvector`
+/// type.
+///
+/// A vector is a sorted list of distinct lexemes, each with the positions at
+/// which it occurs in the document.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TsVector {
+    lexemes: Vec<TsLexeme>,
+}
+
+impl TsVector {
+    /// Constructs a vector from lexemes and their positions.
+    ///
+    /// Like PostgreSQL, duplicate lexemes are merged, positions are sorted and
+    /// deduplicated, retaining the greatest weight of each position, and
+    /// positions greater than [`MAX_POSITION`] are clamped to it.
+    pub fn new<I>(lexemes: I) -> Result<TsVector, Error>
+    where
+        I: IntoIterator<Item = (String, Vec<TsPosition>)>,
+    {
+        let mut merged: BTreeMap<String, Vec<TsPosition>> = BTreeMap::new();
+        for (text, positions) in lexemes {
+            validate_lexeme(&text)?;
+            let entry = merged.entry(text).or_default();
+            for mut position in positions {
+                if position.position == 0 {
+                    bail!("wrong position info in tsvector");
+                }
+                position.position = position.position.min(MAX_POSITION);
+                entry.push(position);
+            }
+        }
+        Ok(TsVector::from_map(merged))
+    }
+
+    fn from_map(lexemes: BTreeMap<String, Vec<TsPosition>>) -> TsVector {
+        let lexemes = lexemes
+            .into_iter()
+            .map(|(text, mut positions)| {
+                positions.sort();
+                positions.dedup_by(|next, prev| {
+                    let duplicate = next.position == prev.position;
+                    if duplicate {
+                        prev.weight = prev.weight.max(next.weight);
+                    }
+                    duplicate
+                });
+                positions.truncate(MAX_POSITIONS_PER_LEXEME);
+                TsLexeme { text, positions }
+            })
+            .collect();
+        TsVector { lexemes }
+    }
+
+    /// Returns the vector's lexemes, in sorted order.
+    pub fn lexemes(&self) -> &[TsLexeme] {
+        &self.lexemes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lexemes.is_empty()
+    }
+
+    /// Returns the lexemes that `operand` matches, i.e. the lexeme that equals
+    /// the operand's lexeme, or, for prefix operands, all lexemes that start
+    /// with it.
+    fn matching(&self, operand: &TsQueryOperand) -> &[TsLexeme] {
+        let start = self
+            .lexemes
+            .partition_point(|l| l.text.as_str() < operand.lexeme.as_str());
+        let len = self.lexemes[start..]
+            .iter()
+            .take_while(|l| {
+                if operand.prefix {
+                    l.text.starts_with(&operand.lexeme)
+                } else {
+                    l.text == operand.lexeme
+                }
+            })
+            .count();
+        &self.lexemes[start..start + len]
+    }
+}
+
+impl FromStr for TsVector {
+    type Err = Error;
+
+    /// Parses a vector, e.g. `'fat':2 'rat':3A,7`.
+    fn from_str(s: &str) -> Result<TsVector, Error> {
+        let syntax_error = || anyhow!("syntax error in tsvector: \"{}\"", s);
+        let mut lexemes = vec![];
+        let mut chars = s.chars().peekable();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.peek().is_none() {
+                break;
+            }
+            let text = parse_word(&mut chars, |c| c.is_whitespace() || c == ':')
+                .ok_or_else(syntax_error)?;
+            let mut positions = vec![];
+            if chars.next_if_eq(&':').is_some() {
+                loop {
+                    let mut digits = String::new();
+                    while let Some(digit) = chars.next_if(|c| c.is_ascii_digit()) {
+                        digits.push(digit);
+                    }
+                    if digits.is_empty() {
+                        return Err(syntax_error());
+                    }
+                    let position = digits.parse::<u16>().unwrap_or(u16::MAX);
+                    let weight = match chars.peek().copied().and_then(TsWeight::from_char) {
+                        Some(weight) => {
+                            chars.next();
+                            weight
+                        }
+                        None => TsWeight::D,
+                    };
+                    positions.push(TsPosition { position, weight });
+                    if chars.next_if_eq(&',').is_none() {
+                        break;
+                    }
+                }
+            }
+            if chars.peek().is_some_and(|c| !c.is_whitespace()) {
+                return Err(syntax_error());
+            }
+            lexemes.push((text, positions));
+        }
+        TsVector::new(lexemes)
+    }
+}
+
+impl fmt::Display for TsVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, lexeme) in self.lexemes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write_quoted(f, &lexeme.text)?;
+            for (j, position) in lexeme.positions.iter().enumerate() {
+                f.write_str(if j == 0 { ":" } else { "," })?;
+                write!(f, "{}", position.position)?;
+                if position.weight != TsWeight::D {
+                    write!(f, "{}", position.weight)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Arbitrary for TsVector {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<TsVector>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let position = (1..=MAX_POSITION, any::<TsWeight>())
+            .prop_map(|(position, weight)| TsPosition { position, weight });
+        proptest::collection::vec(
+            (
+                "[a-z'\\\\ ]{1,8}",
+                proptest::collection::vec(position, 0..4),
+            ),
+            0..6,
+        )
+        .prop_map(|lexemes| TsVector::new(lexemes).expect("valid lexemes"))
+        .boxed()
+    }
+}
+
+/// A text search query, i.e. the PostgreSQL `tsquery` type.
+///
+/// A query without nodes matches nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TsQuery {
+    root: Option<TsQueryNode>,
+}
+
+/// A node of a [`TsQuery`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TsQueryNode {
+    /// Matches the lexemes that the operand describes.
+    Operand(TsQueryOperand),
+    /// Matches if the node does not, i.e. `!`.
+    Not(Box<TsQueryNode>),
+    /// Matches if both nodes match, i.e. `&`.
+    And(Box<TsQueryNode>, Box<TsQueryNode>),
+    /// Matches if either node matches, i.e. `|`.
+    Or(Box<TsQueryNode>, Box<TsQueryNode>),
+    /// Matches if `right` matches `distance` positions after `left`, i.e.
+    /// `<->` or `<N>`.
+    Phrase {
+        left: Box<TsQueryNode>,
+        right: Box<TsQueryNode>,
+        distance: u16,
+    },
+}
+
+/// A lexeme that a [`TsQuery`] searches for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TsQueryOperand {
+    pub lexeme: String,
+    /// The weights of the occurrences that the operand matches, as a bit mask
+    /// with bit 3 set for weight `A` through bit 0 for weight `D`. An empty
+    /// mask matches occurrences of all weights.
+    pub weights: u8,
+    /// Whether the operand matches all lexemes that start with `lexeme`.
+    pub prefix: bool,
+}
+
+impl TsQueryOperand {
+    fn matches_weight(&self, weight: TsWeight) -> bool {
+        self.weights == 0 || self.weights & weight.mask() != 0
+    }
+}
+
+impl fmt::Display for TsQueryOperand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_quoted(f, &self.lexeme)?;
+        if self.prefix || self.weights != 0 {
+            f.write_str(":")?;
+            if self.prefix {
+                f.write_str("*")?;
+            }
+            for weight in [TsWeight::A, TsWeight::B, TsWeight::C, TsWeight::D] {
+                if self.weights & weight.mask() != 0 {
+                    write!(f, "{weight}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TsQuery {
+    pub fn new(root: Option<TsQueryNode>) -> TsQuery {
+        TsQuery { root }
+    }
+
+    pub fn root(&self) -> Option<&TsQueryNode> {
+        self.root.as_ref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Reports whether `vector` matches the query, i.e. the `@@` operator.
+    pub fn matches(&self, vector: &TsVector) -> bool {
+        match &self.root {
+            Some(root) if !vector.is_empty() => root.matches(vector),
+            _ => false,
+        }
+    }
+
+    /// Ranks how well `vector` matches the query, i.e. the `ts_rank`
+    /// function with the default weights and normalization.
+    ///
+    /// The rank is computed with the same sequence of single and double
+    /// precision operations as in PostgreSQL, so that the results agree.
+    pub fn rank(&self, vector: &TsVector) -> f32 {
+        let Some(root) = &self.root else {
+            return 0.0;
+        };
+        if vector.is_empty() {
+            return 0.0;
+        }
+        let mut operands = vec![];
+        root.collect_operands(&mut operands);
+        operands.sort_by(|a, b| a.lexeme.cmp(&b.lexeme));
+        operands.dedup_by(|a, b| a.lexeme == b.lexeme);
+        let rank = match root {
+            TsQueryNode::And(..) | TsQueryNode::Phrase { .. } if operands.len() > 1 => {
+                rank_and(vector, &operands)
+            }
+            _ => rank_or(vector, &operands),
+        };
+        if rank < 0.0 {
+            1e-20
+        } else {
+            rank
+        }
+    }
+}
+
+impl FromStr for TsQuery {
+    type Err = Error;
+
+    /// Parses a query, e.g. `'fat' & ( 'rat':AB | 'cat':* )`, taking its
+    /// operands verbatim.
+    fn from_str(s: &str) -> Result<TsQuery, Error> {
+        parse_query(s, |text, weights, prefix| {
+            ParsedNode::Operand(TsQueryOperand {
+                lexeme: text.into(),
+                weights,
+                prefix,
+            })
+        })
+    }
+}
+
+impl fmt::Display for TsQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.root {
+            Some(root) => root.fmt_infix(f, 0, false),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Arbitrary for TsQuery {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<TsQuery>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let operand =
+            ("[a-z'\\\\ ]{1,8}", 0..16u8, any::<bool>()).prop_map(|(lexeme, weights, prefix)| {
+                TsQueryNode::Operand(TsQueryOperand {
+                    lexeme,
+                    weights,
+                    prefix,
+                })
+            });
+        let node = operand.prop_recursive(4, 16, 2, |inner| {
+            prop_oneof![
+                inner
+                    .clone()
+                    .prop_map(|node| TsQueryNode::Not(Box::new(node))),
+                (inner.clone(), inner.clone())
+                    .prop_map(|(l, r)| TsQueryNode::And(Box::new(l), Box::new(r))),
+                (inner.clone(), inner.clone())
+                    .prop_map(|(l, r)| TsQueryNode::Or(Box::new(l), Box::new(r))),
+                (inner.clone(), inner, 0..=MAX_PHRASE_DISTANCE).prop_map(|(l, r, distance)| {
+                    TsQueryNode::Phrase {
+                        left: Box::new(l),
+                        right: Box::new(r),
+                        distance,
+                    }
+                }),
+            ]
+        });
+        proptest::option::of(node).prop_map(TsQuery::new).boxed()
+    }
+}
+
+impl TsQueryNode {
+    /// Returns the precedence of the node's operator, which is greater for
+    /// operators that bind more tightly.
+    fn priority(&self) -> u8 {
+        match self {
+            TsQueryNode::Or(..) => 1,
+            TsQueryNode::And(..) => 2,
+            TsQueryNode::Phrase { .. } => 3,
+            TsQueryNode::Not(_) => 4,
+            TsQueryNode::Operand(_) => 5,
+        }
+    }
+
+    /// Writes the node in infix notation, parenthesizing it like PostgreSQL
+    /// does if it binds less tightly than its parent, or if it is a phrase
+    /// that is the right operand of a phrase.
+    fn fmt_infix(
+        &self,
+        f: &mut fmt::Formatter,
+        parent_priority: u8,
+        right_of_phrase: bool,
+    ) -> fmt::Result {
+        let priority = self.priority();
+        let (left, right) = match self {
+            TsQueryNode::Operand(operand) => return write!(f, "{operand}"),
+            TsQueryNode::Not(node) => {
+                f.write_str("!")?;
+                return node.fmt_infix(f, priority, false);
+            }
+            TsQueryNode::And(left, right)
+            | TsQueryNode::Or(left, right)
+            | TsQueryNode::Phrase { left, right, .. } => (left, right),
+        };
+        let is_phrase = matches!(self, TsQueryNode::Phrase { .. });
+        let parenthesize = priority < parent_priority || (is_phrase && right_of_phrase);
+        if parenthesize {
+            f.write_str("( ")?;
+        }
+        left.fmt_infix(f, priority, false)?;
+        match self {
+            TsQueryNode::And(..) => f.write_str(" & ")?,
+            TsQueryNode::Or(..) => f.write_str(" | ")?,
+            TsQueryNode::Phrase { distance: 1, .. } => f.write_str(" <-> ")?,
+            TsQueryNode::Phrase { distance, .. } => write!(f, " <{distance}> ")?,
+            TsQueryNode::Operand(_) | TsQueryNode::Not(_) => unreachable!(),
+        }
+        right.fmt_infix(f, priority, is_phrase)?;
+        if parenthesize {
+            f.write_str(" )")?;
+        }
+        Ok(())
+    }
+
+    fn collect_operands<'a>(&'a self, operands: &mut Vec<&'a TsQueryOperand>) {
+        match self {
+            TsQueryNode::Operand(operand) => operands.push(operand),
+            TsQueryNode::Not(node) => node.collect_operands(operands),
+            TsQueryNode::And(left, right)
+            | TsQueryNode::Or(left, right)
+            | TsQueryNode::Phrase { left, right, .. } => {
+                left.collect_operands(operands);
+                right.collect_operands(operands);
+            }
+        }
+    }
+
+    fn matches(&self, vector: &TsVector) -> bool {
+        match self {
+            TsQueryNode::Operand(operand) => vector.matching(operand).iter().any(|lexeme| {
+                if lexeme.positions.is_empty() {
+                    operand.weights == 0
+                } else {
+                    lexeme
+                        .positions
+                        .iter()
+                        .any(|p| operand.matches_weight(p.weight))
+                }
+            }),
+            TsQueryNode::Not(node) => !node.matches(vector),
+            TsQueryNode::And(left, right) => left.matches(vector) && right.matches(vector),
+            TsQueryNode::Or(left, right) => left.matches(vector) || right.matches(vector),
+            TsQueryNode::Phrase { .. } => {
+                // Without positions, the vector cannot match a phrase.
+                let mut positions = PhrasePositions::default();
+                self.match_positions(vector, &mut positions) == PhraseMatch::Yes
+            }
+        }
+    }
+
+    /// Computes the positions at which the node matches, for the evaluation of
+    /// an enclosing phrase operator.
+    fn match_positions(&self, vector: &TsVector, out: &mut PhrasePositions) -> PhraseMatch {
+        match self {
+            TsQueryNode::Operand(operand) => {
+                let mut without_positions = false;
+                for lexeme in vector.matching(operand) {
+                    if lexeme.positions.is_empty() {
+                        without_positions |= operand.weights == 0;
+                    }
+                    out.positions.extend(
+                        lexeme
+                            .positions
+                            .iter()
+                            .filter(|p| operand.matches_weight(p.weight))
+                            .map(|p| u32::from(p.position)),
+                    );
+                }
+                out.positions.sort_unstable();
+                out.positions.dedup();
+                if !out.positions.is_empty() {
+                    PhraseMatch::Yes
+                } else if without_positions {
+                    PhraseMatch::Maybe
+                } else {
+                    PhraseMatch::No
+                }
+            }
+            TsQueryNode::Not(node) => match node.match_positions(vector, out) {
+                PhraseMatch::No => {
+                    // Matching nowhere becomes matching everywhere.
+                    out.negated = true;
+                    PhraseMatch::Yes
+                }
+                PhraseMatch::Yes if !out.positions.is_empty() => {
+                    out.negated = !out.negated;
+                    PhraseMatch::Yes
+                }
+                PhraseMatch::Yes => {
+                    // Matching everywhere becomes matching nowhere.
+                    out.negated = false;
+                    PhraseMatch::No
+                }
+                PhraseMatch::Maybe => PhraseMatch::Maybe,
+            },
+            TsQueryNode::And(left, right) | TsQueryNode::Phrase { left, right, .. } => {
+                let mut l = PhrasePositions::default();
+                let left_match = left.match_positions(vector, &mut l);
+                if left_match == PhraseMatch::No {
+                    return PhraseMatch::No;
+                }
+                let mut r = PhrasePositions::default();
+                let right_match = right.match_positions(vector, &mut r);
+                if right_match == PhraseMatch::No {
+                    return PhraseMatch::No;
+                }
+                if left_match == PhraseMatch::Maybe || right_match == PhraseMatch::Maybe {
+                    return PhraseMatch::Maybe;
+                }
+                let (left_offset, right_offset) = match self {
+                    TsQueryNode::Phrase { distance, .. } => {
+                        // Align the ends of the left matches with the ends of
+                        // the right matches that follow them.
+                        let distance = u32::from(*distance);
+                        out.width = distance + l.width + r.width;
+                        (distance + r.width, 0)
+                    }
+                    _ => {
+                        out.width = l.width.max(r.width);
+                        (out.width - l.width, out.width - r.width)
+                    }
+                };
+                match (l.negated, r.negated) {
+                    (true, true) => {
+                        out.merge(&l, &r, left_offset, right_offset, Emit::Any);
+                        out.negated = true;
+                        PhraseMatch::Yes
+                    }
+                    (true, false) => out.merge(&l, &r, left_offset, right_offset, Emit::RightOnly),
+                    (false, true) => out.merge(&l, &r, left_offset, right_offset, Emit::LeftOnly),
+                    (false, false) => out.merge(&l, &r, left_offset, right_offset, Emit::Both),
+                }
+            }
+            TsQueryNode::Or(left, right) => {
+                let mut l = PhrasePositions::default();
+                let left_match = left.match_positions(vector, &mut l);
+                let mut r = PhrasePositions::default();
+                let right_match = right.match_positions(vector, &mut r);
+                if left_match == PhraseMatch::No && right_match == PhraseMatch::No {
+                    return PhraseMatch::No;
+                }
+                if left_match == PhraseMatch::Maybe || right_match == PhraseMatch::Maybe {
+                    return PhraseMatch::Maybe;
+                }
+                if left_match == PhraseMatch::No {
+                    l.width = r.width;
+                }
+                if right_match == PhraseMatch::No {
+                    r.width = l.width;
+                }
+                out.width = l.width.max(r.width);
+                let (left_offset, right_offset) = (out.width - l.width, out.width - r.width);
+                let emit = match (l.negated, r.negated) {
+                    (true, true) => Emit::Both,
+                    (true, false) => Emit::LeftOnly,
+                    (false, true) => Emit::RightOnly,
+                    (false, false) => {
+                        return out.merge(&l, &r, left_offset, right_offset, Emit::Any);
+                    }
+                };
+                out.merge(&l, &r, left_offset, right_offset, emit);
+                out.negated = true;
+                PhraseMatch::Yes
+            }
+        }
+    }
+}
+
+/// Whether a node matches at the positions of a [`TsVector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PhraseMatch {
+    No,
+    /// The node's lexemes occur, but the vector does not record their
+    /// positions.
+    Maybe,
+    Yes,
+}
+
+/// The positions at which a node matches.
+#[derive(Debug, Default)]
+struct PhrasePositions {
+    /// The sorted positions at which the matches end.
+    positions: Vec<u32>,
+    /// The distance between the start and the end of each match.
+    width: u32,
+    /// Whether the node matches at every position except `positions`.
+    negated: bool,
+}
+
+/// Which positions [`PhrasePositions::merge`] retains.
+#[derive(Debug, Clone, Copy)]
+enum Emit {
+    /// The positions of both inputs.
+    Both,
+    /// The positions of only the left input.
+    LeftOnly,
+    /// The positions of only the right input.
+    RightOnly,
+    /// The positions of either input.
+    Any,
+}
+
+impl PhrasePositions {
+    /// Sets the positions to those that `emit` selects among the positions of
+    /// `left` and `right`, after adding their respective offsets.
+    fn merge(
+        &mut self,
+        left: &PhrasePositions,
+        right: &PhrasePositions,
+        left_offset: u32,
+        right_offset: u32,
+        emit: Emit,
+    ) -> PhraseMatch {
+        let mut left_positions = left.positions.iter().map(|p| p + left_offset).peekable();
+        let mut right_positions = right.positions.iter().map(|p| p + right_offset).peekable();
+        loop {
+            let (position, selected) = match (left_positions.peek(), right_positions.peek()) {
+                (None, None) => break,
+                (Some(l), Some(r)) if l == r => {
+                    right_positions.next();
+                    (
+                        left_positions.next(),
+                        matches!(emit, Emit::Both | Emit::Any),
+                    )
+                }
+                (Some(l), Some(r)) if l > r => (
+                    right_positions.next(),
+                    matches!(emit, Emit::RightOnly | Emit::Any),
+                ),
+                (None, Some(_)) => (
+                    right_positions.next(),
+                    matches!(emit, Emit::RightOnly | Emit::Any),
+                ),
+                (Some(_), _) => (
+                    left_positions.next(),
+                    matches!(emit, Emit::LeftOnly | Emit::Any),
+                ),
+            };
+            if selected {
+                self.positions.extend(position);
+            }
+        }
+        if self.positions.is_empty() {
+            PhraseMatch::No
+        } else {
+            PhraseMatch::Yes
+        }
+    }
+}
+
+/// The weights of occurrences with the weights `D`, `C`, `B` and `A` when
+/// ranking.
+const RANK_WEIGHTS: [f32; 4] = [0.1, 0.2, 0.4, 1.0];
+
+fn rank_weight(position: &TsPosition) -> f32 {
+    RANK_WEIGHTS[usize::from(position.weight.code())]
+}
+
+/// Rounds `f` to single precision, like the implicit conversions of
+/// PostgreSQL's ranking functions.
+fn round_f32(f: f64) -> f32 {
+    #[allow(clippy::as_conversions)]
+    {
+        f as f32
+    }
+}
+
+fn to_f32(n: usize) -> f32 {
+    round_f32(f64::cast_lossy(n))
+}
+
+/// Ranks the occurrences of each operand independently of the others.
+fn rank_or(vector: &TsVector, operands: &[&TsQueryOperand]) -> f32 {
+    // Lexemes without positions rank as a single occurrence of weight D.
+    let no_positions = [TsPosition {
+        position: 0,
+        weight: TsWeight::D,
+    }];
+    let mut rank: f32 = 0.0;
+    for operand in operands {
+        for lexeme in vector.matching(operand) {
+            let positions = match lexeme.positions.as_slice() {
+                [] => &no_positions,
+                positions => positions,
+            };
+            // Sum the weights of the occurrences, divided by the squares of
+            // their indexes, but count the greatest weight in full.
+            let mut sum: f32 = 0.0;
+            let mut max_weight: f32 = -1.0;
+            let mut max_index: f32 = 1.0;
+            for (i, position) in positions.iter().enumerate() {
+                let weight = rank_weight(position);
+                let index = to_f32(i + 1);
+                sum += weight / (index * index);
+                if weight > max_weight {
+                    max_weight = weight;
+                    max_index = index;
+                }
+            }
+            let term = max_weight + sum - max_weight / (max_index * max_index);
+            // The limit of the sum of 1/i^2 is pi^2/6.
+            rank = round_f32(f64::from(rank) + f64::from(term) / 1.64493406685);
+        }
+    }
+    rank / to_f32(operands.len())
+}
+
+/// Ranks the proximity of the occurrences of pairs of distinct operands.
+///
+/// Returns a negative rank if no two operands occur.
+fn rank_and(vector: &TsVector, operands: &[&TsQueryOperand]) -> f32 {
+    // Lexemes without positions rank as a single occurrence of weight D at
+    // the greatest position.
+    let no_positions = [TsPosition {
+        position: MAX_POSITION,
+        weight: TsWeight::D,
+    }];
+    let mut rank: f32 = -1.0;
+    let mut operand_positions: Vec<Option<(&[TsPosition], bool)>> = vec![None; operands.len()];
+    for (i, operand) in operands.iter().enumerate() {
+        for lexeme in vector.matching(operand) {
+            let (positions, has_positions) = match lexeme.positions.as_slice() {
+                [] => (&no_positions[..], false),
+                positions => (positions, true),
+            };
+            operand_positions[i] = Some((positions, has_positions));
+            for (other_positions, other_has_positions) in operand_positions[..i].iter().flatten() {
+                for position in positions {
+                    for other in *other_positions {
+                        let distance = match position.position.abs_diff(other.position) {
+                            0 if has_positions && *other_has_positions => continue,
+                            0 => MAX_POSITION + 1,
+                            distance => distance,
+                        };
+                        let weight = rank_weight(position) * rank_weight(other);
+                        let current = round_f32(f64::from(weight * word_distance(distance)).sqrt());
+                        rank = if rank < 0.0 {
+                            current
+                        } else {
+                            round_f32(1.0 - (1.0 - f64::from(rank)) * (1.0 - f64::from(current)))
+                        };
+                    }
+                }
+            }
+        }
+    }
+    rank
+}
+
+/// Returns the weight of a pair of occurrences `distance` positions apart.
+fn word_distance(distance: u16) -> f32 {
+    if distance > 100 {
+        return 1e-30;
+    }
+    round_f32(1.0 / (1.005 + 0.05 * (f64::from(distance) / 1.5 - 2.0).exp()))
+}
+
+/// A text search configuration, which determines how documents and queries
+/// are split into words, and how words are normalized to lexemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSearchConfig {
+    /// Lowercases words, drops English stop words and reduces the remaining
+    /// words to their stems.
+    English,
+    /// Lowercases words.
+    Simple,
+}
+
+impl TextSearchConfig {
+    /// The configuration of the text search functions whose configuration
+    /// argument is omitted.
+    pub const DEFAULT: TextSearchConfig = TextSearchConfig::English;
+
+    /// Looks up a configuration by name, which may be qualified with the
+    /// `pg_catalog` schema.
+    pub fn from_name(name: &str) -> Result<TextSearchConfig, Error> {
+        let lowercase = name.to_lowercase();
+        match lowercase.strip_prefix("pg_catalog.").unwrap_or(&lowercase) {
+            "english" => Ok(TextSearchConfig::English),
+            "simple" => Ok(TextSearchConfig::Simple),
+            _ => bail!("text search configuration \"{}\" does not exist", name),
+        }
+    }
+
+    /// Splits `text` into words, which are the runs of alphanumeric
+    /// characters, and normalizes them to lexemes, or to `None` if they are
+    /// stop words.
+    fn lexemes<'a>(&self, text: &'a str) -> impl Iterator<Item = Option<String>> + 'a {
+        let config = *self;
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty() && word.len() <= MAX_LEXEME_LEN)
+            .map(move |word| {
+                let word = word.to_lowercase();
+                match config {
+                    TextSearchConfig::English if english::is_stop_word(&word) => None,
+                    TextSearchConfig::English => Some(english::stem(&word)),
+                    TextSearchConfig::Simple => Some(word),
+                }
+            })
+    }
+
+    /// Reduces a document to a vector, i.e. the `to_tsvector` function.
+    pub fn to_tsvector(&self, document: &str) -> TsVector {
+        let mut lexemes: BTreeMap<String, Vec<TsPosition>> = BTreeMap::new();
+        // Stop words are dropped, but still occupy their positions.
+        for (i, lexeme) in self.lexemes(document).enumerate() {
+            if let Some(lexeme) = lexeme {
+                let position = u16::try_from(i + 1).map_or(MAX_POSITION, |p| p.min(MAX_POSITION));
+                lexemes.entry(lexeme).or_default().push(TsPosition {
+                    position,
+                    weight: TsWeight::D,
+                });
+            }
+        }
+        TsVector::from_map(lexemes)
+    }
+
+    /// Parses a query and normalizes its operands, i.e. the `to_tsquery`
+    /// function.
+    ///
+    /// Operands that consist of several words match the phrase of those
+    /// words. Operands that are stop words are removed.
+    pub fn to_tsquery(&self, query: &str) -> Result<TsQuery, Error> {
+        parse_query(query, |text, weights, prefix| {
+            self.operand(text, weights, prefix)
+        })
+    }
+
+    /// Returns a query that matches all words of `text`, i.e. the
+    /// `plainto_tsquery` function.
+    pub fn plainto_tsquery(&self, text: &str) -> TsQuery {
+        let root = self
+            .lexemes(text)
+            .flatten()
+            .map(|lexeme| {
+                TsQueryNode::Operand(TsQueryOperand {
+                    lexeme,
+                    weights: 0,
+                    prefix: false,
+                })
+            })
+            .reduce(|left, right| TsQueryNode::And(Box::new(left), Box::new(right)));
+        TsQuery { root }
+    }
+
+    /// Converts a query in the syntax of web search engines to a query, i.e.
+    /// the `websearch_to_tsquery` function.
+    ///
+    /// Unquoted words must all match, quoted text must match as a phrase,
+    /// `or` separates alternatives, and a leading `-` negates a word or quoted
+    /// text. Like in PostgreSQL, no input is a syntax error.
+    pub fn websearch_to_tsquery(&self, query: &str) -> TsQuery {
+        let mut alternatives: Vec<Vec<ParsedNode>> = vec![vec![]];
+        let mut rest = query;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            let negated = match rest.strip_prefix('-') {
+                Some(r) if r.starts_with(|c: char| !c.is_whitespace()) => {
+                    rest = r;
+                    true
+                }
+                Some(r) => {
+                    rest = r;
+                    continue;
+                }
+                None => false,
+            };
+            let (text, quoted) = match rest.strip_prefix('"') {
+                Some(r) => {
+                    let end = r.find('"').unwrap_or(r.len());
+                    rest = r.get(end + 1..).unwrap_or("");
+                    (&r[..end], true)
+                }
+                None => {
+                    let end = rest
+                        .find(|c: char| c.is_whitespace() || c == '"')
+                        .unwrap_or(rest.len());
+                    let text = &rest[..end];
+                    rest = &rest[end..];
+                    (text, false)
+                }
+            };
+            let terms = alternatives.last_mut().expect("at least one alternative");
+            if !negated && !quoted && !terms.is_empty() && text.eq_ignore_ascii_case("or") {
+                alternatives.push(vec![]);
+                continue;
+            }
+            let node = self.operand(text, 0, false);
+            terms.push(match negated {
+                true => ParsedNode::Not(Box::new(node)),
+                false => node,
+            });
+        }
+        let root = alternatives
+            .into_iter()
+            .filter_map(|terms| {
+                terms
+                    .into_iter()
+                    .reduce(|left, right| ParsedNode::And(Box::new(left), Box::new(right)))
+            })
+            .reduce(|left, right| ParsedNode::Or(Box::new(left), Box::new(right)));
+        TsQuery {
+            root: root.and_then(|root| root.remove_stop_words().0),
+        }
+    }
+
+    /// Normalizes the words of a query operand: a single word becomes an
+    /// operand, several words become the phrase of their operands.
+    fn operand(&self, text: &str, weights: u8, prefix: bool) -> ParsedNode {
+        self.lexemes(text)
+            .map(|lexeme| match lexeme {
+                Some(lexeme) => ParsedNode::Operand(TsQueryOperand {
+                    lexeme,
+                    weights,
+                    prefix,
+                }),
+                None => ParsedNode::Stop,
+            })
+            .reduce(|left, right| ParsedNode::Phrase(Box::new(left), Box::new(right), 1))
+            .unwrap_or(ParsedNode::Stop)
+    }
+}
+
+/// A node of a query that is being parsed, whose operands may be stop words.
+#[derive(Debug)]
+enum ParsedNode {
+    Stop,
+    Operand(TsQueryOperand),
+    Not(Box<ParsedNode>),
+    And(Box<ParsedNode>, Box<ParsedNode>),
+    Or(Box<ParsedNode>, Box<ParsedNode>),
+    Phrase(Box<ParsedNode>, Box<ParsedNode>, u16),
+}
+
+impl ParsedNode {
+    /// Removes the stop words from the node, along with the operators that
+    /// are left without operands.
+    ///
+    /// Like PostgreSQL, removing a stop word from a phrase widens the distance
+    /// between the words around it, so that `a <-> the <-> b` becomes
+    /// `a <2> b`. To that end, also returns the number of positions that were
+    /// removed from the start and from the end of the node.
+    fn remove_stop_words(self) -> (Option<TsQueryNode>, u16, u16) {
+        let (left, right, distance, is_and) = match self {
+            ParsedNode::Stop => return (None, 0, 0),
+            ParsedNode::Operand(operand) => return (Some(TsQueryNode::Operand(operand)), 0, 0),
+            ParsedNode::Not(node) => {
+                let (node, start, end) = node.remove_stop_words();
+                return (
+                    node.map(|node| TsQueryNode::Not(Box::new(node))),
+                    start,
+                    end,
+                );
+            }
+            ParsedNode::And(left, right) => (left, right, None, true),
+            ParsedNode::Or(left, right) => (left, right, None, false),
+            ParsedNode::Phrase(left, right, distance) => (left, right, Some(distance), false),
+        };
+        let (left, left_start, left_end) = left.remove_stop_words();
+        let (right, right_start, right_end) = right.remove_stop_words();
+        match (left, right, distance) {
+            (None, None, Some(distance)) => {
+                // The parent must count the removed distance only once.
+                let removed = left_start
+                    .saturating_add(distance)
+                    .saturating_add(right_start);
+                (None, removed, removed)
+            }
+            (None, None, None) => (None, 0, 0),
+            (None, Some(right), Some(distance)) => {
+                let start = left_start
+                    .saturating_add(distance)
+                    .saturating_add(right_start);
+                (Some(right), start, right_end)
+            }
+            (None, Some(right), None) => (Some(right), right_start, right_end),
+            (Some(left), None, Some(distance)) => {
+                let end = left_end.saturating_add(distance).saturating_add(right_end);
+                (Some(left), left_start, end)
+            }
+            (Some(left), None, None) => (Some(left), left_start, left_end),
+            (Some(left), Some(right), Some(distance)) => {
+                let distance = distance
+                    .saturating_add(left_end)
+                    .saturating_add(right_start)
+                    .min(MAX_PHRASE_DISTANCE);
+                let node = TsQueryNode::Phrase {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    distance,
+                };
+                (Some(node), left_start, right_end)
+            }
+            (Some(left), Some(right), None) => {
+                let (left, right) = (Box::new(left), Box::new(right));
+                let node = match is_and {
+                    true => TsQueryNode::And(left, right),
+                    false => TsQueryNode::Or(left, right),
+                };
+                (Some(node), 0, 0)
+            }
+        }
+    }
+}
+
+/// A token of a query in the `tsquery` syntax.
+#[derive(Debug)]
+enum QueryToken {
+    Operand {
+        text: String,
+        weights: u8,
+        prefix: bool,
+    },
+    Not,
+    And,
+    Or,
+    Phrase(u16),
+    Open,
+    Close,
+}
+
+/// Reports whether `c` is a character that ends an unquoted query operand.
+fn is_query_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '!' | '&' | '|' | '(' | ')' | '<' | ':')
+}
+
+/// Splits a query in the `tsquery` syntax into tokens.
+fn tokenize_query(s: &str) -> Result<Vec<QueryToken>, Error> {
+    let syntax_error = || anyhow!("syntax error in tsquery: \"{}\"", s);
+    let mut tokens = vec![];
+    let mut chars = s.chars().peekable();
+    let mut expect_operand = true;
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(c) = chars.peek().copied() else {
+            break;
+        };
+        if expect_operand {
+            match c {
+                '!' => {
+                    chars.next();
+                    tokens.push(QueryToken::Not);
+                }
+                '(' => {
+                    chars.next();
+                    tokens.push(QueryToken::Open);
+                }
+                '&' | '|' | '<' | ')' | ':' => return Err(syntax_error()),
+                _ => {
+                    let text =
+                        parse_word(&mut chars, is_query_delimiter).ok_or_else(syntax_error)?;
+                    if text.len() > MAX_LEXEME_LEN {
+                        bail!("word is too long in tsquery: \"{}\"", s);
+                    }
+                    let mut weights = 0;
+                    let mut prefix = false;
+                    if chars.next_if_eq(&':').is_some() {
+                        loop {
+                            if chars.next_if_eq(&'*').is_some() {
+                                prefix = true;
+                            } else if let Some(weight) =
+                                chars.peek().copied().and_then(TsWeight::from_char)
+                            {
+                                chars.next();
+                                weights |= weight.mask();
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    tokens.push(QueryToken::Operand {
+                        text,
+                        weights,
+                        prefix,
+                    });
+                    expect_operand = false;
+                }
+            }
+        } else {
+            chars.next();
+            match c {
+                '&' => tokens.push(QueryToken::And),
+                '|' => tokens.push(QueryToken::Or),
+                ')' => {
+                    tokens.push(QueryToken::Close);
+                    continue;
+                }
+                '<' => {
+                    let distance = if chars.next_if_eq(&'-').is_some() {
+                        1
+                    } else {
+                        let mut digits = String::new();
+                        while let Some(digit) = chars.next_if(|c| c.is_ascii_digit()) {
+                            digits.push(digit);
+                        }
+                        if digits.is_empty() {
+                            return Err(syntax_error());
+                        }
+                        match digits.parse::<u16>() {
+                            Ok(distance) if distance <= MAX_PHRASE_DISTANCE => distance,
+                            _ => bail!(
+                                "distance in phrase operator must be an integer value between \
+                                 zero and {} inclusive",
+                                MAX_PHRASE_DISTANCE
+                            ),
+                        }
+                    };
+                    if chars.next_if_eq(&'>').is_none() {
+                        return Err(syntax_error());
+                    }
+                    tokens.push(QueryToken::Phrase(distance));
+                }
+                _ => return Err(syntax_error()),
+            }
+            expect_operand = true;
+        }
+    }
+    if expect_operand && !tokens.is_empty() {
+        return Err(syntax_error());
+    }
+    Ok(tokens)
+}
+
+/// Parses a query in the `tsquery` syntax, converting its operands to nodes
+/// with `operand`, which is passed each operand's text, weights and whether it
+/// is a prefix.
+fn parse_query<F>(s: &str, operand: F) -> Result<TsQuery, Error>
+where
+    F: FnMut(&str, u8, bool) -> ParsedNode,
+{
+    let mut parser = QueryParser {
+        tokens: tokenize_query(s)?.into_iter().peekable(),
+        operand,
+    };
+    if parser.tokens.peek().is_none() {
+        return Ok(TsQuery::default());
+    }
+    let root = parser.parse_or();
+    match root {
+        Some(root) if parser.tokens.peek().is_none() => Ok(TsQuery {
+            root: root.remove_stop_words().0,
+        }),
+        _ => bail!("syntax error in tsquery: \"{}\"", s),
+    }
+}
+
+/// A recursive descent parser for the tokens of a query.
+///
+/// The operators bind, from most to least tightly: `!`, `<->`, `&` and `|`.
+/// The binary operators are left associative.
+struct QueryParser<F> {
+    tokens: Peekable<std::vec::IntoIter<QueryToken>>,
+    operand: F,
+}
+
+impl<F> QueryParser<F>
+where
+    F: FnMut(&str, u8, bool) -> ParsedNode,
+{
+    fn parse_or(&mut self) -> Option<ParsedNode> {
+        let mut node = self.parse_and()?;
+        while self.next_if(|t| matches!(t, QueryToken::Or)).is_some() {
+            node = ParsedNode::Or(Box::new(node), Box::new(self.parse_and()?));
+        }
+        Some(node)
+    }
+
+    fn parse_and(&mut self) -> Option<ParsedNode> {
+        let mut node = self.parse_phrase()?;
+        while self.next_if(|t| matches!(t, QueryToken::And)).is_some() {
+            node = ParsedNode::And(Box::new(node), Box::new(self.parse_phrase()?));
+        }
+        Some(node)
+    }
+
+    fn parse_phrase(&mut self) -> Option<ParsedNode> {
+        let mut node = self.parse_not()?;
+        while let Some(QueryToken::Phrase(distance)) =
+            self.next_if(|t| matches!(t, QueryToken::Phrase(_)))
+        {
+            node = ParsedNode::Phrase(Box::new(node), Box::new(self.parse_not()?), distance);
+        }
+        Some(node)
+    }
+
+    fn parse_not(&mut self) -> Option<ParsedNode> {
+        match self.tokens.next()? {
+            QueryToken::Not => Some(ParsedNode::Not(Box::new(self.parse_not()?))),
+            QueryToken::Open => {
+                let node = self.parse_or()?;
+                match self.tokens.next()? {
+                    QueryToken::Close => Some(node),
+                    _ => None,
+                }
+            }
+            QueryToken::Operand {
+                text,
+                weights,
+                prefix,
+            } => Some((self.operand)(&text, weights, prefix)),
+            _ => None,
+        }
+    }
+
+    fn next_if(&mut self, f: impl FnOnce(&QueryToken) -> bool) -> Option<QueryToken> {
+        self.tokens.next_if(f)
+    }
+}
+
+/// Parses a lexeme, which is either quoted with single quotes, in which case
+/// quotes are escaped by doubling them, or ends before a character for which
+/// `is_delimiter` returns true. In both cases, backslashes escape the next
+/// character.
+///
+/// Returns `None` if the lexeme is empty or its quotes are unterminated.
+fn parse_word<I>(chars: &mut Peekable<I>, is_delimiter: impl Fn(char) -> bool) -> Option<String>
+where
+    I: Iterator<Item = char>,
+{
+    let mut word = String::new();
+    if chars.next_if_eq(&'\'').is_some() {
+        loop {
+            match chars.next()? {
+                '\\' => word.push(chars.next()?),
+                '\'' if chars.next_if_eq(&'\'').is_some() => word.push('\''),
+                '\'' => break,
+                c => word.push(c),
+            }
+        }
+    } else {
+        while let Some(c) = chars.next_if(|c| !is_delimiter(*c)) {
+            match c {
+                '\\' => word.push(chars.next()?),
+                c => word.push(c),
+            }
+        }
+    }
+    (!word.is_empty()).then_some(word)
+}
+
+/// Writes `lexeme` in single quotes, escaping quotes and backslashes by
+/// doubling them.
+fn write_quoted(f: &mut fmt::Formatter, lexeme: &str) -> fmt::Result {
+    f.write_str("'")?;
+    for c in lexeme.chars() {
+        if c == '\'' || c == '\\' {
+            write!(f, "{c}")?;
+        }
+        write!(f, "{c}")?;
+    }
+    f.write_str("'")
+}
+
+fn validate_lexeme(lexeme: &str) -> Result<(), Error> {
+    if lexeme.is_empty() {
+        bail!("lexeme must not be empty");
+    }
+    if lexeme.len() > MAX_LEXEME_LEN {
+        bail!(
+            "word is too long ({} bytes, max {} bytes)",
+            lexeme.len(),
+            MAX_LEXEME_LEN
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_tsvector_text() {
+        for (input, expected) in [
+            (
+                "a fat cat sat on a mat and ate a fat rat",
+                "'a' 'and' 'ate' 'cat' 'fat' 'mat' 'on' 'rat' 'sat'",
+            ),
+            (
+                "a:1 fat:2 cat:3 sat:4 on:5 a:6 mat:7 and:8 ate:9 a:10 fat:11 rat:12",
+                "'a':1,6,10 'and':8 'ate':9 'cat':3 'fat':2,11 'mat':7 'on':5 'rat':12 'sat':4",
+            ),
+            (
+                "the lexeme '    ' contains spaces",
+                "'    ' 'contains' 'lexeme' 'spaces' 'the'",
+            ),
+            (
+                "the lexeme 'Joe''s' contains a quote",
+                "'Joe''s' 'a' 'contains' 'lexeme' 'quote' 'the'",
+            ),
+            ("a:1A fat:2B,4C cat:5D", "'a':1A 'cat':5 'fat':2B,4C"),
+            ("a:3,1b,3a a:99999", "'a':1B,3A,16383"),
+            (r"back\\slash", r"'back\\slash'"),
+            ("", ""),
+        ] {
+            let vector: TsVector = input.parse().unwrap();
+            assert_eq!(vector.to_string(), expected, "input: {input}");
+            let reparsed: TsVector = expected.parse().unwrap();
+            assert_eq!(reparsed, vector);
+        }
+
+        for input in ["'unterminated", "a:", "a:0", "a:1x", "a:b"] {
+            assert!(input.parse::<TsVector>().is_err(), "input: {input}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_tsquery_text() {
+        for (input, expected) in [
+            ("fat & rat", "'fat' & 'rat'"),
+            ("fat & (rat | cat)", "'fat' & ( 'rat' | 'cat' )"),
+            ("fat | rat & cat", "'fat' | 'rat' & 'cat'"),
+            ("!(a <-> b)", "!( 'a' <-> 'b' )"),
+            ("!!a", "!!'a'"),
+            ("a <-> (b <-> c)", "'a' <-> ( 'b' <-> 'c' )"),
+            ("(a <-> b) <-> c", "'a' <-> 'b' <-> 'c'"),
+            ("a <3> b & c <0> d", "'a' <3> 'b' & 'c' <0> 'd'"),
+            ("fat:ab & cat", "'fat':AB & 'cat'"),
+            ("super:*dA", "'super':*AD"),
+            ("'it''s' | 'a b'", "'it''s' | 'a b'"),
+            ("", ""),
+        ] {
+            let query: TsQuery = input.parse().unwrap();
+            assert_eq!(query.to_string(), expected, "input: {input}");
+            let reparsed: TsQuery = expected.parse().unwrap();
+            assert_eq!(reparsed.to_string(), expected);
+        }
+
+        for input in ["a &", "& a", "a b", "(a", "a)", "a <x> b", "a <-", "!"] {
+            let err = input.parse::<TsQuery>().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("syntax error in tsquery: \"{input}\""),
+            );
+        }
+        assert_eq!(
+            "a <16385> b".parse::<TsQuery>().unwrap_err().to_string(),
+            "distance in phrase operator must be an integer value between zero and 16384 inclusive",
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_config() {
+        let english = TextSearchConfig::from_name("pg_catalog.English").unwrap();
+        assert_eq!(english, TextSearchConfig::English);
+        assert!(TextSearchConfig::from_name("french").is_err());
+
+        assert_eq!(
+            english
+                .to_tsvector("The quick brown fox jumped over the lazy dog")
+                .to_string(),
+            "'brown':3 'dog':9 'fox':4 'jump':5 'lazi':8 'quick':2",
+        );
+        assert_eq!(
+            TextSearchConfig::Simple
+                .to_tsvector("The quick brown fox")
+                .to_string(),
+            "'brown':3 'fox':4 'quick':2 'the':1",
+        );
+
+        for (input, expected) in [
+            ("The & Fat & Rats", "'fat' & 'rat'"),
+            ("supernovae & stars:*", "'supernova' & 'star':*"),
+            ("supernovae <-> the <-> stars", "'supernova' <2> 'star'"),
+            ("'fat cats':B | the", "'fat':B <-> 'cat':B"),
+            ("!the & cat", "'cat'"),
+            ("the | a", ""),
+        ] {
+            let query = english.to_tsquery(input).unwrap();
+            assert_eq!(query.to_string(), expected, "input: {input}");
+        }
+
+        assert_eq!(
+            english.plainto_tsquery("The Fat Rats").to_string(),
+            "'fat' & 'rat'",
+        );
+
+        for (input, expected) in [
+            (
+                "\"supernovae stars\" -crab",
+                "'supernova' <-> 'star' & !'crab'",
+            ),
+            (
+                "\"sad cat\" or \"fat rat\"",
+                "'sad' <-> 'cat' | 'fat' <-> 'rat'",
+            ),
+            (
+                "signal -\"segmentation fault\"",
+                "'signal' & !( 'segment' <-> 'fault' )",
+            ),
+            ("or cat or - dog or", "'cat' | 'dog'"),
+            ("\"unterminated quote", "'untermin' <-> 'quot'"),
+            ("", ""),
+        ] {
+            let query = english.websearch_to_tsquery(input);
+            assert_eq!(query.to_string(), expected, "input: {input}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_matches() {
+        let english = TextSearchConfig::English;
+        let vector = english.to_tsvector("a fat cat sat on a mat and ate a fat rat");
+        for (query, expected) in [
+            ("fat & rat", true),
+            ("fat & cow", false),
+            ("fat | cow", true),
+            ("!cow", true),
+            ("fat <-> rat", true),
+            ("cat <-> rat", false),
+            ("cat <2> mat", false),
+            ("cat <4> mat", true),
+            ("sat <-> !cat", true),
+            ("!fat <-> rat", false),
+            ("(fat | sat) <-> (rat | mat)", true),
+            ("fa:*", true),
+            ("fat:A", false),
+        ] {
+            let query = english.to_tsquery(query).unwrap();
+            assert_eq!(query.matches(&vector), expected, "query: {query}");
+        }
+
+        // Without positions, only non-phrase queries match.
+        let vector: TsVector = "fat rat".parse().unwrap();
+        assert!(english.to_tsquery("fat & rat").unwrap().matches(&vector));
+        assert!(!english.to_tsquery("fat <-> rat").unwrap().matches(&vector));
+        assert!(!english.to_tsquery("fat:A").unwrap().matches(&vector));
+
+        assert!(!TsQuery::default().matches(&vector));
+        assert!(!english
+            .to_tsquery("!cow")
+            .unwrap()
+            .matches(&TsVector::default()));
+    }
+
+    #[mz_ore::test]
+    fn test_rank() {
+        let english = TextSearchConfig::English;
+        let vector = english.to_tsvector("The quick brown fox");
+        let query = english.to_tsquery("fox").unwrap();
+        assert_eq!(query.rank(&vector), 0.06079271);
+
+        let query = english.to_tsquery("quick & fox").unwrap();
+        let near = query.rank(&vector);
+        let far = query.rank(&english.to_tsvector("The quick brown and lazy fox"));
+        assert!(near > far, "{near} <= {far}");
+
+        assert_eq!(TsQuery::default().rank(&vector), 0.0);
+        assert_eq!(query.rank(&TsVector::default()), 0.0);
+    }
+
+    proptest! {
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // too slow
+        fn tsvector_text_roundtrip(vector in any::<TsVector>()) {
+            let text = vector.to_string();
+            let reparsed: TsVector = text.parse().unwrap();
+            assert_eq!(reparsed, vector);
+        }
+
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // too slow
+        fn tsquery_text_roundtrip(query in any::<TsQuery>()) {
+            let text = query.to_string();
+            let reparsed: TsQuery = text.parse().unwrap();
+            assert_eq!(reparsed.to_string(), text);
+        }
+    }
+}
@@ -0,0 +1,409 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The stop words and stemmer of the `english` text search configuration.
+
+/// The words that the `english` configuration ignores, in sorted order.
+///
+/// This is the stop word list of the Snowball English stemmer, which
+/// PostgreSQL's `english` configuration also uses.
+#[rustfmt::skip]
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "am", "an", "and", "any", "are",
+    "as", "at", "be", "because", "been", "before", "being", "below", "between", "both", "but",
+    "by", "can", "did", "do", "does", "doing", "don", "down", "during", "each", "few", "for",
+    "from", "further", "had", "has", "have", "having", "he", "her", "here", "hers", "herself",
+    "him", "himself", "his", "how", "i", "if", "in", "into", "is", "it", "its", "itself", "just",
+    "me", "more", "most", "my", "myself", "no", "nor", "not", "now", "of", "off", "on", "once",
+    "only", "or", "other", "our", "ours", "ourselves", "out", "over", "own", "s", "same", "she",
+    "should", "so", "some", "such", "t", "than", "that", "the", "their", "theirs", "them",
+    "themselves", "then", "there", "these", "they", "this", "those", "through", "to", "too",
+    "under", "until", "up", "very", "was", "we", "were", "what", "when", "where", "which", "while",
+    "who", "whom", "why", "will", "with", "you", "your", "yours", "yourself", "yourselves",
+];
+
+/// Reports whether the lowercase `word` is an English stop word.
+pub(super) fn is_stop_word(word: &str) -> bool {
+    STOP_WORDS.binary_search(&word).is_ok()
+}
+
+/// Words whose stems the algorithm does not derive, and their stems.
+const EXCEPTIONS: &[(&str, &str)] = &[
+    ("andes", "andes"),
+    ("atlas", "atlas"),
+    ("bias", "bias"),
+    ("cosmos", "cosmos"),
+    ("dying", "die"),
+    ("early", "earli"),
+    ("gently", "gentl"),
+    ("howe", "howe"),
+    ("idly", "idl"),
+    ("lying", "lie"),
+    ("news", "news"),
+    ("only", "onli"),
+    ("singly", "singl"),
+    ("skies", "sky"),
+    ("skis", "ski"),
+    ("sky", "sky"),
+    ("tying", "tie"),
+    ("ugly", "ugli"),
+];
+
+/// Words that are left alone once their plural ending has been removed.
+const POST_PLURAL_EXCEPTIONS: &[&str] = &[
+    "canning", "earring", "exceed", "herring", "inning", "outing", "proceed", "succeed",
+];
+
+/// Reduces a lowercase English word to its stem, using the Snowball English
+/// ("Porter2") stemming algorithm.
+///
+/// Only words that consist of ASCII letters are stemmed; all other words are
+/// returned unchanged.
+///
+/// See <https://snowballstem.org/algorithms/english/stemmer.html>.
+pub(super) fn stem(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return word.into();
+    }
+    if let Some((_, stem)) = EXCEPTIONS.iter().find(|(w, _)| *w == word) {
+        return (*stem).into();
+    }
+
+    let mut w = word.as_bytes().to_vec();
+    // Distinguish the consonant `y`s, which are marked as `Y`, from the vowel
+    // `y`s.
+    for i in 0..w.len() {
+        if w[i] == b'y' && (i == 0 || is_vowel(w[i - 1])) {
+            w[i] = b'Y';
+        }
+    }
+    let r1 = ["gener", "commun", "arsen"]
+        .iter()
+        .find(|prefix| w.starts_with(prefix.as_bytes()))
+        .map(|prefix| prefix.len())
+        .unwrap_or_else(|| region_start(&w, 0));
+    let r2 = region_start(&w, r1);
+    let mut stemmer = Stemmer { w, r1, r2 };
+
+    stemmer.step_1a();
+    if !POST_PLURAL_EXCEPTIONS
+        .iter()
+        .any(|e| e.as_bytes() == stemmer.w.as_slice())
+    {
+        stemmer.step_1b();
+        stemmer.step_1c();
+        stemmer.step_2();
+        stemmer.step_3();
+        stemmer.step_4();
+        stemmer.step_5();
+    }
+
+    stemmer
+        .w
+        .into_iter()
+        .map(|b| char::from(b.to_ascii_lowercase()))
+        .collect()
+}
+
+fn is_vowel(b: u8) -> bool {
+    matches!(b, b'a' | b'e' | b'i' | b'o' | b'u' | b'y')
+}
+
+fn is_double(w: &[u8]) -> bool {
+    match w {
+        [.., a, b] => {
+            a == b
+                && matches!(
+                    a,
+                    b'b' | b'd' | b'f' | b'g' | b'm' | b'n' | b'p' | b'r' | b't'
+                )
+        }
+        _ => false,
+    }
+}
+
+/// Returns the start of the region after the first non-vowel that follows a
+/// vowel at or after `from`, or the length of `w` if there is no such region.
+fn region_start(w: &[u8], from: usize) -> usize {
+    (from + 1..w.len())
+        .find(|i| is_vowel(w[i - 1]) && !is_vowel(w[*i]))
+        .map(|i| i + 1)
+        .unwrap_or(w.len())
+}
+
+/// Reports whether `w` ends in a short syllable, i.e. either a non-vowel, a
+/// vowel and a non-vowel other than `w`, `x` or `Y`, or, if `w` has two
+/// letters, a vowel and a non-vowel.
+fn ends_in_short_syllable(w: &[u8]) -> bool {
+    match w {
+        [a, b] => is_vowel(*a) && !is_vowel(*b),
+        [.., a, b, c] => {
+            !is_vowel(*a) && is_vowel(*b) && !is_vowel(*c) && !matches!(c, b'w' | b'x' | b'Y')
+        }
+        _ => false,
+    }
+}
+
+struct Stemmer {
+    w: Vec<u8>,
+    /// The start of the region R1.
+    r1: usize,
+    /// The start of the region R2.
+    r2: usize,
+}
+
+impl Stemmer {
+    fn ends_with(&self, suffix: &str) -> bool {
+        self.w.ends_with(suffix.as_bytes())
+    }
+
+    /// Returns the longest suffix in `suffixes` that the word ends with,
+    /// along with the position at which it starts.
+    fn longest_suffix<'a>(
+        &self,
+        suffixes: &[(&'a str, &'a str)],
+    ) -> Option<(&'a str, &'a str, usize)> {
+        suffixes
+            .iter()
+            .filter(|(suffix, _)| self.ends_with(suffix))
+            .max_by_key(|(suffix, _)| suffix.len())
+            .map(|(suffix, replacement)| (*suffix, *replacement, self.w.len() - suffix.len()))
+    }
+
+    fn replace(&mut self, start: usize, replacement: &str) {
+        self.w.truncate(start);
+        self.w.extend_from_slice(replacement.as_bytes());
+    }
+
+    fn contains_vowel(&self, end: usize) -> bool {
+        self.w[..end].iter().any(|b| is_vowel(*b))
+    }
+
+    fn is_short(&self) -> bool {
+        self.r1 >= self.w.len() && ends_in_short_syllable(&self.w)
+    }
+
+    /// Removes plural endings.
+    fn step_1a(&mut self) {
+        let len = self.w.len();
+        if self.ends_with("sses") {
+            self.w.truncate(len - 2);
+        } else if self.ends_with("ied") || self.ends_with("ies") {
+            let start = len - 3;
+            self.replace(start, if start > 1 { "i" } else { "ie" });
+        } else if self.ends_with("s")
+            && !self.ends_with("us")
+            && !self.ends_with("ss")
+            && self.contains_vowel(len - 2)
+        {
+            self.w.truncate(len - 1);
+        }
+    }
+
+    /// Removes past tense and progressive endings.
+    fn step_1b(&mut self) {
+        const SUFFIXES: &[(&str, &str)] = &[
+            ("eed", "ee"),
+            ("eedly", "ee"),
+            ("ed", ""),
+            ("edly", ""),
+            ("ing", ""),
+            ("ingly", ""),
+        ];
+        let Some((suffix, replacement, start)) = self.longest_suffix(SUFFIXES) else {
+            return;
+        };
+        if suffix.starts_with("eed") {
+            if start >= self.r1 {
+                self.replace(start, replacement);
+            }
+        } else if self.contains_vowel(start) {
+            self.w.truncate(start);
+            if self.ends_with("at") || self.ends_with("bl") || self.ends_with("iz") {
+                self.w.push(b'e');
+            } else if is_double(&self.w) {
+                self.w.pop();
+            } else if self.is_short() {
+                self.w.push(b'e');
+            }
+        }
+    }
+
+    /// Replaces a final `y` that follows a non-vowel with `i`.
+    fn step_1c(&mut self) {
+        let len = self.w.len();
+        if len > 2 && matches!(self.w[len - 1], b'y' | b'Y') && !is_vowel(self.w[len - 2]) {
+            self.w[len - 1] = b'i';
+        }
+    }
+
+    /// Reduces double suffixes to single ones.
+    fn step_2(&mut self) {
+        const SUFFIXES: &[(&str, &str)] = &[
+            ("tional", "tion"),
+            ("enci", "ence"),
+            ("anci", "ance"),
+            ("abli", "able"),
+            ("entli", "ent"),
+            ("izer", "ize"),
+            ("ization", "ize"),
+            ("ational", "ate"),
+            ("ation", "ate"),
+            ("ator", "ate"),
+            ("alism", "al"),
+            ("aliti", "al"),
+            ("alli", "al"),
+            ("fulness", "ful"),
+            ("ousli", "ous"),
+            ("ousness", "ous"),
+            ("iveness", "ive"),
+            ("iviti", "ive"),
+            ("biliti", "ble"),
+            ("bli", "ble"),
+            ("ogi", "og"),
+            ("fulli", "ful"),
+            ("lessli", "less"),
+            ("li", ""),
+        ];
+        let Some((suffix, replacement, start)) = self.longest_suffix(SUFFIXES) else {
+            return;
+        };
+        if start < self.r1 {
+            return;
+        }
+        let preceding = start.checked_sub(1).map(|i| self.w[i]);
+        let applies = match suffix {
+            "ogi" => preceding == Some(b'l'),
+            "li" => matches!(
+                preceding,
+                Some(b'c' | b'd' | b'e' | b'g' | b'h' | b'k' | b'm' | b'n' | b'r' | b't')
+            ),
+            _ => true,
+        };
+        if applies {
+            self.replace(start, replacement);
+        }
+    }
+
+    /// Removes or simplifies derivational suffixes.
+    fn step_3(&mut self) {
+        const SUFFIXES: &[(&str, &str)] = &[
+            ("tional", "tion"),
+            ("ational", "ate"),
+            ("alize", "al"),
+            ("icate", "ic"),
+            ("iciti", "ic"),
+            ("ical", "ic"),
+            ("ful", ""),
+            ("ness", ""),
+            ("ative", ""),
+        ];
+        let Some((suffix, replacement, start)) = self.longest_suffix(SUFFIXES) else {
+            return;
+        };
+        let region = if suffix == "ative" { self.r2 } else { self.r1 };
+        if start >= region {
+            self.replace(start, replacement);
+        }
+    }
+
+    /// Removes the remaining derivational suffixes.
+    fn step_4(&mut self) {
+        const SUFFIXES: &[(&str, &str)] = &[
+            ("al", ""),
+            ("ance", ""),
+            ("ence", ""),
+            ("er", ""),
+            ("ic", ""),
+            ("able", ""),
+            ("ible", ""),
+            ("ant", ""),
+            ("ement", ""),
+            ("ment", ""),
+            ("ent", ""),
+            ("ism", ""),
+            ("ate", ""),
+            ("iti", ""),
+            ("ous", ""),
+            ("ive", ""),
+            ("ize", ""),
+            ("ion", ""),
+        ];
+        let Some((suffix, _, start)) = self.longest_suffix(SUFFIXES) else {
+            return;
+        };
+        if start < self.r2 {
+            return;
+        }
+        if suffix != "ion" || (start > 0 && matches!(self.w[start - 1], b's' | b't')) {
+            self.w.truncate(start);
+        }
+    }
+
+    /// Removes a final `e` or the second of a final `ll`.
+    fn step_5(&mut self) {
+        let len = self.w.len();
+        if self.ends_with("e") {
+            let start = len - 1;
+            if start >= self.r2 || (start >= self.r1 && !ends_in_short_syllable(&self.w[..start])) {
+                self.w.truncate(start);
+            }
+        } else if self.ends_with("ll") && len > self.r2 {
+            self.w.truncate(len - 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_stop_words_sorted() {
+        assert!(STOP_WORDS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[mz_ore::test]
+    fn test_stem() {
+        for (word, expected) in [
+            ("a", "a"),
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("ties", "tie"),
+            ("cats", "cat"),
+            ("gas", "gas"),
+            ("kiwis", "kiwi"),
+            ("agreed", "agre"),
+            ("feed", "feed"),
+            ("hopping", "hop"),
+            ("hoping", "hope"),
+            ("filing", "file"),
+            ("luxuriated", "luxuri"),
+            ("cry", "cri"),
+            ("say", "say"),
+            ("lazy", "lazi"),
+            ("quickly", "quick"),
+            ("jumps", "jump"),
+            ("running", "run"),
+            ("supernovae", "supernova"),
+            ("generously", "generous"),
+            ("relational", "relat"),
+            ("conditional", "condit"),
+            ("hopefulness", "hope"),
+            ("electricity", "electr"),
+            ("adjustment", "adjust"),
+            ("controll", "control"),
+            ("skies", "sky"),
+            ("proceed", "proceed"),
+            ("café", "café"),
+        ] {
+            assert_eq!(stem(word), expected, "{word}");
+        }
+    }
+}
@@ -105,6 +105,8 @@ optimizer_feature_flags!({
     enable_new_outer_join_lowering: bool,
     // Bound from `SystemVars::enable_reduce_mfp_fusion`.
     enable_reduce_mfp_fusion: bool,
+    // Bound from `SystemVars::enable_incremental_window_functions`.
+    enable_incremental_window_functions: bool,
     // Enable joint HIR ⇒ MIR lowering of stacks of left joins.
     enable_variadic_left_join_lowering: bool,
     // Enable cardinality estimation
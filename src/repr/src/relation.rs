@@ -76,10 +76,15 @@ impl ColumnType {
                 })
             }
             (
-                ScalarType::Record { fields, custom_id },
+                ScalarType::Record {
+                    fields,
+                    custom_id,
+                    custom_oid,
+                },
                 ScalarType::Record {
                     fields: other_fields,
                     custom_id: other_custom_id,
+                    ..
                 },
             ) => {
                 if custom_id != other_custom_id {
@@ -108,6 +113,7 @@ impl ColumnType {
                     scalar_type: ScalarType::Record {
                         fields: union_fields.into(),
                         custom_id,
+                        custom_oid,
                     },
                     nullable: self.nullable || other.nullable,
                 })
@@ -13,6 +13,7 @@ use std::cmp::Ordering;
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Debug};
 use std::mem::{size_of, transmute};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::ops::Deref;
 use std::rc::Rc;
 use std::str;
@@ -36,6 +37,7 @@ use crate::adt::array::{
 use crate::adt::date::Date;
 use crate::adt::interval::Interval;
 use crate::adt::mz_acl_item::{AclItem, MzAclItem};
+use crate::adt::network::{Inet, MacAddr, MacAddr8};
 use crate::adt::numeric;
 use crate::adt::numeric::Numeric;
 use crate::adt::range::{
@@ -906,6 +908,11 @@ enum Tag {
     UInt64_48,
     UInt64_56,
     UInt64_64,
+
+    Inet4,
+    Inet6,
+    MacAddr,
+    MacAddr8,
 }
 
 impl Tag {
@@ -1425,6 +1432,18 @@ pub unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
                 .expect("invalid aclitem");
             Datum::AclItem(acl_item)
         }
+        Tag::Inet4 => {
+            let netmask = read_byte(data, offset);
+            let addr = Ipv4Addr::from(read_byte_array::<4>(data, offset));
+            Datum::Inet(Inet::new(IpAddr::V4(addr), netmask).expect("invalid inet"))
+        }
+        Tag::Inet6 => {
+            let netmask = read_byte(data, offset);
+            let addr = Ipv6Addr::from(read_byte_array::<16>(data, offset));
+            Datum::Inet(Inet::new(IpAddr::V6(addr), netmask).expect("invalid inet"))
+        }
+        Tag::MacAddr => Datum::MacAddr(MacAddr(read_byte_array(data, offset))),
+        Tag::MacAddr8 => Datum::MacAddr8(MacAddr8(read_byte_array(data, offset))),
     }
 }
 
@@ -1803,6 +1822,26 @@ where
             data.push(Tag::AclItem.into());
             data.extend_from_slice(&acl_item.encode_binary());
         }
+        Datum::Inet(inet) => match inet.addr() {
+            IpAddr::V4(addr) => {
+                data.push(Tag::Inet4.into());
+                data.push(inet.netmask());
+                data.extend_from_slice(&addr.octets());
+            }
+            IpAddr::V6(addr) => {
+                data.push(Tag::Inet6.into());
+                data.push(inet.netmask());
+                data.extend_from_slice(&addr.octets());
+            }
+        },
+        Datum::MacAddr(macaddr) => {
+            data.push(Tag::MacAddr.into());
+            data.extend_from_slice(&macaddr.0);
+        }
+        Datum::MacAddr8(macaddr8) => {
+            data.push(Tag::MacAddr8.into());
+            data.extend_from_slice(&macaddr8.0);
+        }
     }
 }
 
@@ -1915,6 +1954,12 @@ pub fn datum_size(datum: &Datum) -> usize {
         }
         Datum::MzAclItem(_) => 1 + MzAclItem::binary_size(),
         Datum::AclItem(_) => 1 + AclItem::binary_size(),
+        Datum::Inet(inet) => match inet.addr() {
+            IpAddr::V4(_) => 1 + 1 + 4,
+            IpAddr::V6(_) => 1 + 1 + 16,
+        },
+        Datum::MacAddr(_) => 1 + 6,
+        Datum::MacAddr8(_) => 1 + 8,
     }
 }
 
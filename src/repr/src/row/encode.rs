@@ -51,6 +51,7 @@ use crate::adt::datetime::PackedNaiveTime;
 use crate::adt::interval::PackedInterval;
 use crate::adt::jsonb::{JsonbPacker, JsonbRef};
 use crate::adt::mz_acl_item::{PackedAclItem, PackedMzAclItem};
+use crate::adt::network::{Inet, MacAddr, MacAddr8};
 use crate::adt::numeric::{Numeric, PackedNumeric};
 use crate::adt::range::{Range, RangeInner, RangeLowerBound, RangeUpperBound};
 use crate::adt::timestamp::{CheckedTimestamp, PackedNaiveDateTime};
@@ -78,6 +79,8 @@ mod fixed_binary_sizes {
     pub const ARRAY_DIMENSION_FIXED_BYTES: i32 = PackedArrayDimension::SIZE as i32;
 
     pub const UUID_FIXED_BYTES: i32 = 16;
+    pub const MAC_ADDR_FIXED_BYTES: i32 = 6;
+    pub const MAC_ADDR8_FIXED_BYTES: i32 = 8;
     static_assertions::const_assert_eq!(UUID_FIXED_BYTES as usize, std::mem::size_of::<Uuid>());
 }
 use fixed_binary_sizes::*;
@@ -95,6 +98,7 @@ pub fn preserves_order(scalar_type: &ScalarType) -> bool {
         | ScalarType::UInt16
         | ScalarType::UInt32
         | ScalarType::UInt64
+        | ScalarType::Enum { .. }
         | ScalarType::Numeric { .. }
         | ScalarType::Date
         | ScalarType::Time
@@ -102,11 +106,15 @@ pub fn preserves_order(scalar_type: &ScalarType) -> bool {
         | ScalarType::TimestampTz { .. }
         | ScalarType::Interval
         | ScalarType::Bytes
+        | ScalarType::Geometry
+        | ScalarType::Geography
         | ScalarType::String
         | ScalarType::Uuid
         | ScalarType::MzTimestamp
         | ScalarType::MzAclItem
-        | ScalarType::AclItem => true,
+        | ScalarType::AclItem
+        | ScalarType::MacAddr
+        | ScalarType::MacAddr8 => true,
         // We sort records lexicographically; a record has a meaningful sort if all its fields do.
         ScalarType::Record { fields, .. } => fields
             .iter()
@@ -118,6 +126,9 @@ pub fn preserves_order(scalar_type: &ScalarType) -> bool {
         // don't yet care to make strong guarantees one way or the other.
         ScalarType::PgLegacyChar
         | ScalarType::PgLegacyName
+        | ScalarType::TsVector
+        | ScalarType::TsQuery
+        | ScalarType::JsonPath
         | ScalarType::Char { .. }
         | ScalarType::VarChar { .. }
         | ScalarType::Jsonb
@@ -129,7 +140,9 @@ pub fn preserves_order(scalar_type: &ScalarType) -> bool {
         | ScalarType::RegType
         | ScalarType::RegClass
         | ScalarType::Int2Vector
-        | ScalarType::Range { .. } => false,
+        | ScalarType::Range { .. }
+        | ScalarType::Inet
+        | ScalarType::Cidr => false,
     }
 }
 
@@ -198,6 +211,9 @@ enum DatumColumnEncoder {
     Uuid(FixedSizeBinaryBuilder),
     AclItem(FixedSizeBinaryBuilder),
     MzAclItem(BinaryBuilder),
+    Inet(BinaryBuilder),
+    MacAddr(FixedSizeBinaryBuilder),
+    MacAddr8(FixedSizeBinaryBuilder),
     Range(BinaryBuilder),
     /// Hand rolled "StringBuilder" that reduces the number of copies required
     /// to serialize JSON.
@@ -288,6 +304,9 @@ impl DatumColumnEncoder {
             DatumColumnEncoder::Uuid(a) => a.len() * size_of::<Uuid>(),
             DatumColumnEncoder::AclItem(a) => a.len() * PackedAclItem::SIZE,
             DatumColumnEncoder::MzAclItem(a) => a.values_slice().len(),
+            DatumColumnEncoder::Inet(a) => a.values_slice().len(),
+            DatumColumnEncoder::MacAddr(a) => a.len() * 6,
+            DatumColumnEncoder::MacAddr8(a) => a.len() * 8,
             DatumColumnEncoder::Range(a) => a.values_slice().len(),
             DatumColumnEncoder::Jsonb { buf, .. } => buf.len(),
             DatumColumnEncoder::Array { dims, vals, .. } => {
@@ -385,6 +404,15 @@ impl DatumColumnEncoder {
                 let packed = PackedMzAclItem::from_value(val);
                 builder.append_value(packed.as_bytes());
             }
+            (DatumColumnEncoder::Inet(builder), Datum::Inet(val)) => {
+                builder.append_value(val.encode_binary());
+            }
+            (DatumColumnEncoder::MacAddr(builder), Datum::MacAddr(val)) => {
+                builder.append_value(val.0).expect("known correct size")
+            }
+            (DatumColumnEncoder::MacAddr8(builder), Datum::MacAddr8(val)) => {
+                builder.append_value(val.0).expect("known correct size")
+            }
             (DatumColumnEncoder::Range(builder), d @ Datum::Range(_)) => {
                 let proto = ProtoDatum::from(d);
                 let bytes = proto.encode_to_vec();
@@ -549,6 +577,9 @@ impl DatumColumnEncoder {
             DatumColumnEncoder::Uuid(builder) => builder.append_null(),
             DatumColumnEncoder::AclItem(builder) => builder.append_null(),
             DatumColumnEncoder::MzAclItem(builder) => builder.append_null(),
+            DatumColumnEncoder::Inet(builder) => builder.append_null(),
+            DatumColumnEncoder::MacAddr(builder) => builder.append_null(),
+            DatumColumnEncoder::MacAddr8(builder) => builder.append_null(),
             DatumColumnEncoder::Range(builder) => builder.append_null(),
             DatumColumnEncoder::Jsonb {
                 offsets,
@@ -735,6 +766,9 @@ impl DatumColumnEncoder {
             }
             DatumColumnEncoder::AclItem(mut builder) => Arc::new(builder.finish()),
             DatumColumnEncoder::MzAclItem(mut builder) => Arc::new(builder.finish()),
+            DatumColumnEncoder::Inet(mut builder) => Arc::new(builder.finish()),
+            DatumColumnEncoder::MacAddr(mut builder) => Arc::new(builder.finish()),
+            DatumColumnEncoder::MacAddr8(mut builder) => Arc::new(builder.finish()),
             DatumColumnEncoder::Range(mut builder) => Arc::new(builder.finish()),
             DatumColumnEncoder::Jsonb {
                 offsets,
@@ -902,6 +936,9 @@ enum DatumColumnDecoder {
     Range(BinaryArray),
     MzAclItem(BinaryArray),
     AclItem(FixedSizeBinaryArray),
+    Inet(BinaryArray),
+    MacAddr(FixedSizeBinaryArray),
+    MacAddr8(FixedSizeBinaryArray),
 }
 
 impl DatumColumnDecoder {
@@ -1024,6 +1061,24 @@ impl DatumColumnDecoder {
                     Datum::MzAclItem(packed.into_value())
                 })
             }
+            DatumColumnDecoder::Inet(array) => {
+                array.is_valid(idx).then(|| array.value(idx)).map(|x| {
+                    let inet = Inet::decode_binary(x).expect("failed to roundtrip inet");
+                    Datum::Inet(inet)
+                })
+            }
+            DatumColumnDecoder::MacAddr(array) => {
+                array.is_valid(idx).then(|| array.value(idx)).map(|x| {
+                    let bytes = x.try_into().expect("failed to roundtrip macaddr");
+                    Datum::MacAddr(MacAddr(bytes))
+                })
+            }
+            DatumColumnDecoder::MacAddr8(array) => {
+                array.is_valid(idx).then(|| array.value(idx)).map(|x| {
+                    let bytes = x.try_into().expect("failed to roundtrip macaddr8");
+                    Datum::MacAddr8(MacAddr8(bytes))
+                })
+            }
             DatumColumnDecoder::Range(array) => {
                 let Some(val) = array.is_valid(idx).then(|| array.value(idx)) else {
                     packer.push(Datum::Null);
@@ -1206,6 +1261,9 @@ impl DatumColumnDecoder {
             }
             DatumColumnDecoder::AclItem(_)
             | DatumColumnDecoder::MzAclItem(_)
+            | DatumColumnDecoder::Inet(_)
+            | DatumColumnDecoder::MacAddr(_)
+            | DatumColumnDecoder::MacAddr8(_)
             | DatumColumnDecoder::Range(_) => ColumnStatKinds::None,
             DatumColumnDecoder::Json(a) => stats_for_json(a.iter()).values,
             DatumColumnDecoder::Array { .. }
@@ -1240,6 +1298,9 @@ impl DatumColumnDecoder {
             DatumColumnDecoder::Uuid(a) => ArrayOrd::FixedSizeBinary(a.clone()).goodbytes(),
             DatumColumnDecoder::AclItem(a) => ArrayOrd::FixedSizeBinary(a.clone()).goodbytes(),
             DatumColumnDecoder::MzAclItem(a) => ArrayOrd::Binary(a.clone()).goodbytes(),
+            DatumColumnDecoder::Inet(a) => ArrayOrd::Binary(a.clone()).goodbytes(),
+            DatumColumnDecoder::MacAddr(a) => ArrayOrd::FixedSizeBinary(a.clone()).goodbytes(),
+            DatumColumnDecoder::MacAddr8(a) => ArrayOrd::FixedSizeBinary(a.clone()).goodbytes(),
             DatumColumnDecoder::Range(a) => ArrayOrd::Binary(a.clone()).goodbytes(),
             DatumColumnDecoder::Json(a) => ArrayOrd::String(a.clone()).goodbytes(),
             DatumColumnDecoder::Array { dims, vals, .. } => {
@@ -1557,7 +1618,8 @@ fn array_to_decoder(
             | ScalarType::Oid
             | ScalarType::RegClass
             | ScalarType::RegProc
-            | ScalarType::RegType,
+            | ScalarType::RegType
+            | ScalarType::Enum { .. },
         ) => {
             let array = downcast_array::<UInt32Array>(array)?;
             DatumColumnDecoder::U32(array.clone())
@@ -1602,12 +1664,15 @@ fn array_to_decoder(
             ScalarType::String
             | ScalarType::PgLegacyName
             | ScalarType::Char { .. }
-            | ScalarType::VarChar { .. },
+            | ScalarType::VarChar { .. }
+            | ScalarType::TsVector
+            | ScalarType::TsQuery
+            | ScalarType::JsonPath,
         ) => {
             let array = downcast_array::<StringArray>(array)?;
             DatumColumnDecoder::String(array.clone())
         }
-        (DataType::Binary, ScalarType::Bytes) => {
+        (DataType::Binary, ScalarType::Bytes | ScalarType::Geometry | ScalarType::Geography) => {
             let array = downcast_array::<BinaryArray>(array)?;
             DatumColumnDecoder::Bytes(array.clone())
         }
@@ -1647,6 +1712,18 @@ fn array_to_decoder(
             let array = downcast_array::<BinaryArray>(array)?;
             DatumColumnDecoder::MzAclItem(array.clone())
         }
+        (DataType::Binary, ScalarType::Inet | ScalarType::Cidr) => {
+            let array = downcast_array::<BinaryArray>(array)?;
+            DatumColumnDecoder::Inet(array.clone())
+        }
+        (DataType::FixedSizeBinary(MAC_ADDR_FIXED_BYTES), ScalarType::MacAddr) => {
+            let array = downcast_array::<FixedSizeBinaryArray>(array)?;
+            DatumColumnDecoder::MacAddr(array.clone())
+        }
+        (DataType::FixedSizeBinary(MAC_ADDR8_FIXED_BYTES), ScalarType::MacAddr8) => {
+            let array = downcast_array::<FixedSizeBinaryArray>(array)?;
+            DatumColumnDecoder::MacAddr8(array.clone())
+        }
         (DataType::Binary, ScalarType::Range { .. }) => {
             let array = downcast_array::<BinaryArray>(array)?;
             DatumColumnDecoder::Range(array.clone())
@@ -1761,7 +1838,8 @@ fn scalar_type_to_encoder(col_ty: &ScalarType) -> Result<DatumColumnEncoder, any
         | ScalarType::Oid
         | ScalarType::RegClass
         | ScalarType::RegProc
-        | ScalarType::RegType => DatumColumnEncoder::U32(UInt32Builder::new()),
+        | ScalarType::RegType
+        | ScalarType::Enum { .. } => DatumColumnEncoder::U32(UInt32Builder::new()),
         ScalarType::UInt64 => DatumColumnEncoder::U64(UInt64Builder::new()),
         ScalarType::Int16 => DatumColumnEncoder::I16(Int16Builder::new()),
         ScalarType::Int32 => DatumColumnEncoder::I32(Int32Builder::new()),
@@ -1776,8 +1854,13 @@ fn scalar_type_to_encoder(col_ty: &ScalarType) -> Result<DatumColumnEncoder, any
         ScalarType::String
         | ScalarType::PgLegacyName
         | ScalarType::Char { .. }
-        | ScalarType::VarChar { .. } => DatumColumnEncoder::String(StringBuilder::new()),
-        ScalarType::Bytes => DatumColumnEncoder::Bytes(BinaryBuilder::new()),
+        | ScalarType::VarChar { .. }
+        | ScalarType::TsVector
+        | ScalarType::TsQuery
+        | ScalarType::JsonPath => DatumColumnEncoder::String(StringBuilder::new()),
+        ScalarType::Bytes | ScalarType::Geometry | ScalarType::Geography => {
+            DatumColumnEncoder::Bytes(BinaryBuilder::new())
+        }
         ScalarType::Date => DatumColumnEncoder::Date(Int32Builder::new()),
         ScalarType::Time => DatumColumnEncoder::Time(FixedSizeBinaryBuilder::new(TIME_FIXED_BYTES)),
         ScalarType::Timestamp { .. } => {
@@ -1795,6 +1878,13 @@ fn scalar_type_to_encoder(col_ty: &ScalarType) -> Result<DatumColumnEncoder, any
             DatumColumnEncoder::AclItem(FixedSizeBinaryBuilder::new(ACL_ITEM_FIXED_BYTES))
         }
         ScalarType::MzAclItem => DatumColumnEncoder::MzAclItem(BinaryBuilder::new()),
+        ScalarType::Inet | ScalarType::Cidr => DatumColumnEncoder::Inet(BinaryBuilder::new()),
+        ScalarType::MacAddr => {
+            DatumColumnEncoder::MacAddr(FixedSizeBinaryBuilder::new(MAC_ADDR_FIXED_BYTES))
+        }
+        ScalarType::MacAddr8 => {
+            DatumColumnEncoder::MacAddr8(FixedSizeBinaryBuilder::new(MAC_ADDR8_FIXED_BYTES))
+        }
         ScalarType::Range { .. } => DatumColumnEncoder::Range(BinaryBuilder::new()),
         ScalarType::Jsonb => DatumColumnEncoder::Jsonb {
             offsets: vec![0],
@@ -2018,6 +2108,9 @@ impl<'a> From<Datum<'a>> for ProtoDatum {
             })),
             Datum::MzAclItem(x) => DatumType::MzAclItem(x.into_proto()),
             Datum::AclItem(x) => DatumType::AclItem(x.into_proto()),
+            Datum::Inet(x) => DatumType::Inet(x.into_proto()),
+            Datum::MacAddr(x) => DatumType::MacAddr(x.0.to_vec()),
+            Datum::MacAddr8(x) => DatumType::MacAddr8(x.0.to_vec()),
         };
         ProtoDatum {
             datum_type: Some(datum_type),
@@ -2165,6 +2258,15 @@ impl RowPacker<'_> {
             }
             Some(DatumType::MzAclItem(x)) => self.push(Datum::MzAclItem(x.clone().into_rust()?)),
             Some(DatumType::AclItem(x)) => self.push(Datum::AclItem(x.clone().into_rust()?)),
+            Some(DatumType::Inet(x)) => self.push(Datum::Inet(x.clone().into_rust()?)),
+            Some(DatumType::MacAddr(x)) => {
+                let bytes = x.as_slice().try_into().map_err(|_| "invalid macaddr")?;
+                self.push(Datum::MacAddr(MacAddr(bytes)))
+            }
+            Some(DatumType::MacAddr8(x)) => {
+                let bytes = x.as_slice().try_into().map_err(|_| "invalid macaddr8")?;
+                self.push(Datum::MacAddr8(MacAddr8(bytes)))
+            }
             None => return Err("unknown datum type".into()),
         };
         Ok(())
@@ -2316,6 +2418,10 @@ mod tests {
                         // We don't collect stats for these types.
                         ScalarType::AclItem
                         | ScalarType::MzAclItem
+                        | ScalarType::Inet
+                        | ScalarType::Cidr
+                        | ScalarType::MacAddr
+                        | ScalarType::MacAddr8
                         | ScalarType::Range { .. }
                         | ScalarType::Array(_)
                         | ScalarType::Map { .. }
@@ -2595,6 +2701,7 @@ mod tests {
                     ]
                     .into(),
                     custom_id: None,
+                    custom_oid: None,
                 }
                 .nullable(true),
             )
@@ -31,13 +31,17 @@ use uuid::Uuid;
 use crate::adt::array::{Array, ArrayDimension};
 use crate::adt::char::{Char, CharLength};
 use crate::adt::date::Date;
+use crate::adt::geo::Geometry;
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
+use crate::adt::jsonpath::JsonPath;
 use crate::adt::mz_acl_item::{AclItem, AclMode, MzAclItem};
+use crate::adt::network::{Inet, MacAddr, MacAddr8};
 use crate::adt::numeric::{Numeric, NumericMaxScale};
 use crate::adt::pg_legacy_name::PgLegacyName;
 use crate::adt::range::{Range, RangeLowerBound, RangeUpperBound};
 use crate::adt::system::{Oid, PgLegacyChar, RegClass, RegProc, RegType};
+use crate::adt::text_search::{TsQuery, TsVector};
 use crate::adt::timestamp::{
     CheckedTimestamp, TimestampError, TimestampPrecision, HIGH_DATE, LOW_DATE,
 };
@@ -135,6 +139,12 @@ pub enum Datum<'a> {
     /// A list of privileges granted to a user that uses [`Oid`]s for role references.
     /// This type is used primarily for compatibility with PostgreSQL.
     AclItem(AclItem),
+    /// An IPv4 or IPv6 address with a netmask.
+    Inet(Inet),
+    /// A 6 byte MAC address.
+    MacAddr(MacAddr),
+    /// An 8 byte MAC address.
+    MacAddr8(MacAddr8),
     /// A placeholder value.
     ///
     /// Dummy values are never meant to be observed. Many operations on `Datum`
@@ -946,6 +956,45 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the inet value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Inet`].
+    #[track_caller]
+    pub fn unwrap_inet(&self) -> Inet {
+        match self {
+            Datum::Inet(inet) => *inet,
+            _ => panic!("Datum::unwrap_inet called on {:?}", self),
+        }
+    }
+
+    /// Unwraps the macaddr value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::MacAddr`].
+    #[track_caller]
+    pub fn unwrap_macaddr(&self) -> MacAddr {
+        match self {
+            Datum::MacAddr(macaddr) => *macaddr,
+            _ => panic!("Datum::unwrap_macaddr called on {:?}", self),
+        }
+    }
+
+    /// Unwraps the macaddr8 value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::MacAddr8`].
+    #[track_caller]
+    pub fn unwrap_macaddr8(&self) -> MacAddr8 {
+        match self {
+            Datum::MacAddr8(macaddr8) => *macaddr8,
+            _ => panic!("Datum::unwrap_macaddr8 called on {:?}", self),
+        }
+    }
+
     /// Reports whether this datum is an instance of the specified column type.
     pub fn is_instance_of(self, column_type: &ColumnType) -> bool {
         fn is_instance_of_scalar(datum: Datum, scalar_type: &ScalarType) -> bool {
@@ -989,6 +1038,9 @@ impl<'a> Datum<'a> {
                     (Datum::UInt32(_), ScalarType::RegProc) => true,
                     (Datum::UInt32(_), ScalarType::RegType) => true,
                     (Datum::UInt32(_), ScalarType::UInt32) => true,
+                    (Datum::UInt32(i), ScalarType::Enum { labels, .. }) => {
+                        usize::cast_from(i) < labels.len()
+                    }
                     (Datum::UInt32(_), _) => false,
                     (Datum::UInt64(_), ScalarType::UInt64) => true,
                     (Datum::UInt64(_), _) => false,
@@ -1006,12 +1058,17 @@ impl<'a> Datum<'a> {
                     (Datum::TimestampTz(_), _) => false,
                     (Datum::Interval(_), ScalarType::Interval) => true,
                     (Datum::Interval(_), _) => false,
-                    (Datum::Bytes(_), ScalarType::Bytes) => true,
+                    (Datum::Bytes(_), ScalarType::Bytes)
+                    | (Datum::Bytes(_), ScalarType::Geometry)
+                    | (Datum::Bytes(_), ScalarType::Geography) => true,
                     (Datum::Bytes(_), _) => false,
                     (Datum::String(_), ScalarType::String)
                     | (Datum::String(_), ScalarType::VarChar { .. })
                     | (Datum::String(_), ScalarType::Char { .. })
-                    | (Datum::String(_), ScalarType::PgLegacyName) => true,
+                    | (Datum::String(_), ScalarType::PgLegacyName)
+                    | (Datum::String(_), ScalarType::TsVector)
+                    | (Datum::String(_), ScalarType::TsQuery)
+                    | (Datum::String(_), ScalarType::JsonPath) => true,
                     (Datum::String(_), _) => false,
                     (Datum::Uuid(_), ScalarType::Uuid) => true,
                     (Datum::Uuid(_), _) => false,
@@ -1066,6 +1123,13 @@ impl<'a> Datum<'a> {
                     (Datum::MzAclItem(_), _) => false,
                     (Datum::AclItem(_), ScalarType::AclItem) => true,
                     (Datum::AclItem(_), _) => false,
+                    (Datum::Inet(_), ScalarType::Inet) => true,
+                    (Datum::Inet(inet), ScalarType::Cidr) => inet.is_network(),
+                    (Datum::Inet(_), _) => false,
+                    (Datum::MacAddr(_), ScalarType::MacAddr) => true,
+                    (Datum::MacAddr(_), _) => false,
+                    (Datum::MacAddr8(_), ScalarType::MacAddr8) => true,
+                    (Datum::MacAddr8(_), _) => false,
                 }
             }
         }
@@ -1291,6 +1355,27 @@ impl<'a> From<MzAclItem> for Datum<'a> {
     }
 }
 
+impl<'a> From<Inet> for Datum<'a> {
+    #[inline]
+    fn from(inet: Inet) -> Self {
+        Datum::Inet(inet)
+    }
+}
+
+impl<'a> From<MacAddr> for Datum<'a> {
+    #[inline]
+    fn from(macaddr: MacAddr) -> Self {
+        Datum::MacAddr(macaddr)
+    }
+}
+
+impl<'a> From<MacAddr8> for Datum<'a> {
+    #[inline]
+    fn from(macaddr8: MacAddr8) -> Self {
+        Datum::MacAddr8(macaddr8)
+    }
+}
+
 impl<'a, T> From<Option<T>> for Datum<'a>
 where
     Datum<'a>: From<T>,
@@ -1383,6 +1468,9 @@ impl fmt::Display for Datum<'_> {
             Datum::Range(i) => write!(f, "{}", i),
             Datum::MzAclItem(mz_acl_item) => write!(f, "{mz_acl_item}"),
             Datum::AclItem(acl_item) => write!(f, "{acl_item}"),
+            Datum::Inet(inet) => write!(f, "{inet}"),
+            Datum::MacAddr(macaddr) => write!(f, "{macaddr}"),
+            Datum::MacAddr8(macaddr8) => write!(f, "{macaddr8}"),
         }
     }
 }
@@ -1504,6 +1592,9 @@ pub enum ScalarType {
         /// Boxed slice to reduce the size of the enum variant.
         fields: Box<[(ColumnName, ColumnType)]>,
         custom_id: Option<CatalogItemId>,
+        /// The OID of the composite type, if the record is of a custom type.
+        #[serde(default)]
+        custom_oid: Option<u32>,
     },
     /// A PostgreSQL object identifier.
     Oid,
@@ -1534,6 +1625,43 @@ pub enum ScalarType {
     MzAclItem,
     /// The type of [`Datum::AclItem`]
     AclItem,
+    /// The type of [`Datum::Inet`].
+    Inet,
+    /// Stored as [`Datum::Inet`], but only admits values that have no bits
+    /// set to the right of the netmask.
+    Cidr,
+    /// The type of [`Datum::MacAddr`].
+    MacAddr,
+    /// The type of [`Datum::MacAddr8`].
+    MacAddr8,
+    /// Stored as [`Datum::String`], but only admits the canonical text
+    /// representation of a [`TsVector`](crate::adt::text_search::TsVector).
+    TsVector,
+    /// Stored as [`Datum::String`], but only admits the canonical text
+    /// representation of a [`TsQuery`](crate::adt::text_search::TsQuery).
+    TsQuery,
+    /// Stored as [`Datum::String`], but only admits the canonical text
+    /// representation of a [`JsonPath`](crate::adt::jsonpath::JsonPath).
+    JsonPath,
+    /// A user-defined enum type.
+    ///
+    /// Values are stored as [`Datum::UInt32`] holding the index of their label,
+    /// so that they sort in the order in which the labels were declared.
+    Enum {
+        /// The labels of the enum, in declaration order.
+        labels: Box<[String]>,
+        custom_id: CatalogItemId,
+        /// The OID of the enum type, which clients use to identify values of
+        /// the type.
+        custom_oid: u32,
+    },
+    /// Stored as [`Datum::Bytes`], but only admits the canonical EWKB
+    /// representation of a [`Geometry`](crate::adt::geo::Geometry).
+    Geometry,
+    /// Stored as [`Datum::Bytes`], but only admits the canonical EWKB
+    /// representation of a [`Geometry`](crate::adt::geo::Geometry) whose SRID
+    /// is [`SRID_WGS84`](crate::adt::geo::SRID_WGS84).
+    Geography,
 }
 
 impl RustType<ProtoRecordField> for (ColumnName, ColumnType) {
@@ -1608,8 +1736,13 @@ impl RustType<ProtoScalarType> for ScalarType {
                     element_type: Some(element_type.into_proto()),
                     custom_id: custom_id.map(|id| id.into_proto()),
                 })),
-                ScalarType::Record { custom_id, fields } => Record(ProtoRecord {
+                ScalarType::Record {
+                    custom_id,
+                    custom_oid,
+                    fields,
+                } => Record(ProtoRecord {
                     custom_id: custom_id.map(|id| id.into_proto()),
+                    custom_oid: *custom_oid,
                     fields: fields.into_proto(),
                 }),
                 ScalarType::Array(typ) => Array(typ.into_proto()),
@@ -1626,6 +1759,24 @@ impl RustType<ProtoScalarType> for ScalarType {
                 })),
                 ScalarType::MzAclItem => MzAclItem(()),
                 ScalarType::AclItem => AclItem(()),
+                ScalarType::Inet => Inet(()),
+                ScalarType::Cidr => Cidr(()),
+                ScalarType::MacAddr => MacAddr(()),
+                ScalarType::MacAddr8 => MacAddr8(()),
+                ScalarType::TsVector => TsVector(()),
+                ScalarType::TsQuery => TsQuery(()),
+                ScalarType::JsonPath => JsonPath(()),
+                ScalarType::Enum {
+                    labels,
+                    custom_id,
+                    custom_oid,
+                } => Enum(ProtoEnum {
+                    labels: labels.to_vec(),
+                    custom_id: Some(custom_id.into_proto()),
+                    custom_oid: *custom_oid,
+                }),
+                ScalarType::Geometry => Geometry(()),
+                ScalarType::Geography => Geography(()),
             }),
         }
     }
@@ -1692,6 +1843,7 @@ impl RustType<ProtoScalarType> for ScalarType {
             }),
             Record(x) => Ok(ScalarType::Record {
                 custom_id: x.custom_id.map(|id| id.into_rust().unwrap()),
+                custom_oid: x.custom_oid,
                 fields: x.fields.into_rust()?,
             }),
             Map(x) => Ok(ScalarType::Map {
@@ -1712,6 +1864,20 @@ impl RustType<ProtoScalarType> for ScalarType {
             }),
             MzAclItem(()) => Ok(ScalarType::MzAclItem),
             AclItem(()) => Ok(ScalarType::AclItem),
+            Inet(()) => Ok(ScalarType::Inet),
+            Cidr(()) => Ok(ScalarType::Cidr),
+            MacAddr(()) => Ok(ScalarType::MacAddr),
+            MacAddr8(()) => Ok(ScalarType::MacAddr8),
+            TsVector(()) => Ok(ScalarType::TsVector),
+            TsQuery(()) => Ok(ScalarType::TsQuery),
+            JsonPath(()) => Ok(ScalarType::JsonPath),
+            Enum(x) => Ok(ScalarType::Enum {
+                labels: x.labels.into(),
+                custom_id: x.custom_id.into_rust_if_some("ProtoEnum::custom_id")?,
+                custom_oid: x.custom_oid,
+            }),
+            Geometry(()) => Ok(ScalarType::Geometry),
+            Geography(()) => Ok(ScalarType::Geography),
         }
     }
 }
@@ -1839,6 +2005,9 @@ impl_datum_type_copy!(Interval, Interval);
 impl_datum_type_copy!(Date, Date);
 impl_datum_type_copy!(NaiveTime, Time);
 impl_datum_type_copy!(Uuid, Uuid);
+impl_datum_type_copy!(Inet, Inet);
+impl_datum_type_copy!(MacAddr, MacAddr);
+impl_datum_type_copy!(MacAddr8, MacAddr8);
 impl_datum_type_copy!('a, &'a str, String);
 impl_datum_type_copy!('a, &'a [u8], Bytes);
 impl_datum_type_copy!(crate::Timestamp, MzTimestamp);
@@ -2662,6 +2831,18 @@ impl ScalarType {
         }
     }
 
+    /// Returns the labels of a [`ScalarType::Enum`], in declaration order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on anything other than a [`ScalarType::Enum`].
+    pub fn unwrap_enum_labels(&self) -> &[String] {
+        match self {
+            ScalarType::Enum { labels, .. } => labels,
+            _ => panic!("ScalarType::unwrap_enum_labels called on {:?}", self),
+        }
+    }
+
     /// Returns number of dimensions/axes (also known as "rank") on a
     /// [`ScalarType::List`].
     ///
@@ -2703,6 +2884,7 @@ impl ScalarType {
             Record {
                 fields,
                 custom_id: None,
+                custom_oid: None,
             } => {
                 let fields = fields
                     .iter()
@@ -2719,6 +2901,7 @@ impl ScalarType {
                 Record {
                     fields,
                     custom_id: None,
+                    custom_oid: None,
                 }
             }
             Array(a) => Array(Box::new(a.without_modifiers())),
@@ -2888,6 +3071,7 @@ impl ScalarType {
                         .map(|(_, t)| t)
                         .any(|t| t.scalar_type.is_custom_type())
             }
+            Enum { .. } => true,
             _ => false,
         }
     }
@@ -2944,10 +3128,12 @@ impl ScalarType {
                 Record {
                     fields: fields_a,
                     custom_id: oid_a,
+                    custom_oid: _,
                 },
                 Record {
                     fields: fields_b,
                     custom_id: oid_b,
+                    custom_oid: _,
                 },
             ) => {
                 (oid_a == oid_b || structure_only)
@@ -2961,6 +3147,18 @@ impl ScalarType {
                                 && a.1.scalar_type.eq_inner(&b.1.scalar_type, structure_only)
                         })
             }
+            (
+                Enum {
+                    labels: labels_a,
+                    custom_id: id_a,
+                    ..
+                },
+                Enum {
+                    labels: labels_b,
+                    custom_id: id_b,
+                    ..
+                },
+            ) => id_a == id_b || (structure_only && labels_a == labels_b),
             (s, o) => ScalarBaseType::from(s) == ScalarBaseType::from(o),
         }
     }
@@ -3387,6 +3585,91 @@ impl ScalarType {
         });
         // aclitem has no binary encoding so we can't test it here.
         static ACLITEM: LazyLock<Row> = LazyLock::new(|| Row::pack_slice(&[]));
+        static INET: LazyLock<Row> = LazyLock::new(|| {
+            Row::pack_slice(&[
+                Datum::Inet("0.0.0.0/0".parse().expect("valid inet")),
+                Datum::Inet("192.168.1.5".parse().expect("valid inet")),
+                Datum::Inet("192.168.1.5/24".parse().expect("valid inet")),
+                Datum::Inet("::".parse().expect("valid inet")),
+                Datum::Inet("2001:db8::1/64".parse().expect("valid inet")),
+                Datum::Inet("::ffff:1.2.3.4".parse().expect("valid inet")),
+            ])
+        });
+        static CIDR: LazyLock<Row> = LazyLock::new(|| {
+            Row::pack_slice(&[
+                Datum::Inet("0.0.0.0/0".parse().expect("valid cidr")),
+                Datum::Inet("192.168.1.0/24".parse().expect("valid cidr")),
+                Datum::Inet("192.168.1.5/32".parse().expect("valid cidr")),
+                Datum::Inet("2001:db8::/32".parse().expect("valid cidr")),
+            ])
+        });
+        static MACADDR: LazyLock<Row> = LazyLock::new(|| {
+            Row::pack_slice(&[
+                Datum::MacAddr(MacAddr([0; 6])),
+                Datum::MacAddr(MacAddr([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03])),
+                Datum::MacAddr(MacAddr([0xff; 6])),
+            ])
+        });
+        static MACADDR8: LazyLock<Row> = LazyLock::new(|| {
+            Row::pack_slice(&[
+                Datum::MacAddr8(MacAddr8([0; 8])),
+                Datum::MacAddr8(MacAddr8([0x08, 0x00, 0x2b, 0xff, 0xfe, 0x01, 0x02, 0x03])),
+                Datum::MacAddr8(MacAddr8([0xff; 8])),
+            ])
+        });
+        static TSVECTOR: LazyLock<Row> = LazyLock::new(|| {
+            Row::pack_slice(&[
+                Datum::String(""),
+                Datum::String("'a' 'b c'"),
+                Datum::String("'fat':2,11 'it''s':1A,3B 'rat':16383C"),
+            ])
+        });
+        static TSQUERY: LazyLock<Row> = LazyLock::new(|| {
+            Row::pack_slice(&[
+                Datum::String(""),
+                Datum::String("'a'"),
+                Datum::String("'fat':AB & !( 'rat':* <-> 'cat' ) | 'a' <16384> 'b'"),
+            ])
+        });
+        static JSONPATH: LazyLock<Row> = LazyLock::new(|| {
+            Row::pack_slice(&[
+                Datum::String("$"),
+                Datum::String("strict $.\"a\"[*]?(@ > 1 && @.\"b\" starts with \"x\")"),
+                Datum::String("$.**{1 to last}.keyvalue().\"key\""),
+            ])
+        });
+        static ENUM: LazyLock<Row> = LazyLock::new(|| Row::pack_slice(&[Datum::UInt32(0)]));
+        static GEOMETRY: LazyLock<Row> = LazyLock::new(|| {
+            let geometries: Vec<_> = [
+                "POINT EMPTY",
+                "POINT(-0.5 1e300)",
+                "SRID=3857;LINESTRING(0 0,1 1)",
+                "POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,2 1,2 2,1 1))",
+                "MULTIPOINT((0 0),(1 1))",
+            ]
+            .iter()
+            .map(|s| s.parse::<Geometry>().expect("valid geometry").to_ewkb())
+            .collect();
+            Row::pack(geometries.iter().map(|g| Datum::Bytes(g)))
+        });
+        static GEOGRAPHY: LazyLock<Row> = LazyLock::new(|| {
+            let geographies: Vec<_> = [
+                "POINT EMPTY",
+                "POINT(-180 -90)",
+                "POINT(180 90)",
+                "LINESTRING(-179 0,179 0)",
+            ]
+            .iter()
+            .map(|s| {
+                let geometry = s.parse::<Geometry>().expect("valid geometry");
+                geometry
+                    .into_geography()
+                    .expect("valid geography")
+                    .to_ewkb()
+            })
+            .collect();
+            Row::pack(geographies.iter().map(|g| Datum::Bytes(g)))
+        });
 
         let iter: Box<dyn Iterator<Item = Datum<'static>>> = match self {
             ScalarType::Bool => Box::new((*BOOL).iter()),
@@ -3436,6 +3719,23 @@ impl ScalarType {
             ScalarType::Range { .. } => Box::new((*RANGE).iter()),
             ScalarType::MzAclItem { .. } => Box::new((*MZACLITEM).iter()),
             ScalarType::AclItem { .. } => Box::new((*ACLITEM).iter()),
+            ScalarType::Inet => Box::new((*INET).iter()),
+            ScalarType::Cidr => Box::new((*CIDR).iter()),
+            ScalarType::MacAddr => Box::new((*MACADDR).iter()),
+            ScalarType::MacAddr8 => Box::new((*MACADDR8).iter()),
+            ScalarType::TsVector => Box::new((*TSVECTOR).iter()),
+            ScalarType::TsQuery => Box::new((*TSQUERY).iter()),
+            ScalarType::JsonPath => Box::new((*JSONPATH).iter()),
+            ScalarType::Enum { labels, .. } => {
+                // Only the first label is known to exist.
+                if labels.is_empty() {
+                    Box::new(std::iter::empty())
+                } else {
+                    Box::new((*ENUM).iter())
+                }
+            }
+            ScalarType::Geometry => Box::new((*GEOMETRY).iter()),
+            ScalarType::Geography => Box::new((*GEOGRAPHY).iter()),
         };
 
         iter
@@ -3494,6 +3794,15 @@ impl ScalarType {
             ScalarType::Int2Vector,
             ScalarType::MzTimestamp,
             ScalarType::MzAclItem,
+            ScalarType::Inet,
+            ScalarType::Cidr,
+            ScalarType::MacAddr,
+            ScalarType::MacAddr8,
+            ScalarType::TsVector,
+            ScalarType::TsQuery,
+            ScalarType::JsonPath,
+            ScalarType::Geometry,
+            ScalarType::Geography,
             // TODO: Fill in some variants of these.
             /*
             ScalarType::AclItem,
@@ -3505,6 +3814,7 @@ impl ScalarType {
             ScalarType::Record {
                 fields: todo!(),
                 custom_id: todo!(),
+                custom_oid: todo!(),
             },
             ScalarType::Map {
                 value_type: todo!(),
@@ -3554,7 +3864,17 @@ impl ScalarType {
             | ScalarType::Int2Vector
             | ScalarType::MzTimestamp
             | ScalarType::Range { .. }
-            | ScalarType::MzAclItem { .. }) => Ok(t),
+            | ScalarType::MzAclItem { .. }
+            | ScalarType::Inet
+            | ScalarType::Cidr
+            | ScalarType::MacAddr
+            | ScalarType::MacAddr8
+            | ScalarType::TsVector
+            | ScalarType::TsQuery
+            | ScalarType::JsonPath
+            | ScalarType::Geometry
+            | ScalarType::Geography
+            | ScalarType::Enum { .. }) => Ok(t),
 
             ScalarType::Array(elem) => Ok(elem.array_of_self_elem_type()?),
 
@@ -3612,6 +3932,26 @@ impl Arbitrary for ScalarType {
             Just(ScalarType::Uuid).boxed(),
             Just(ScalarType::AclItem).boxed(),
             Just(ScalarType::MzAclItem).boxed(),
+            Just(ScalarType::Inet).boxed(),
+            Just(ScalarType::Cidr).boxed(),
+            Just(ScalarType::MacAddr).boxed(),
+            Just(ScalarType::MacAddr8).boxed(),
+            Just(ScalarType::TsVector).boxed(),
+            Just(ScalarType::TsQuery).boxed(),
+            Just(ScalarType::JsonPath).boxed(),
+            Just(ScalarType::Geometry).boxed(),
+            Just(ScalarType::Geography).boxed(),
+            (
+                prop::collection::vec(any::<String>(), 1..5),
+                any::<CatalogItemId>(),
+                any::<u32>(),
+            )
+                .prop_map(|(labels, custom_id, custom_oid)| ScalarType::Enum {
+                    labels: labels.into(),
+                    custom_id,
+                    custom_oid,
+                })
+                .boxed(),
             Just(ScalarType::Oid).boxed(),
             Just(ScalarType::RegProc).boxed(),
             Just(ScalarType::RegType).boxed(),
@@ -3684,10 +4024,11 @@ impl Arbitrary for ScalarType {
                         prop::collection::vec((any::<ColumnName>(), column_type_strat), 0..10);
 
                     // Now we combine it with the default strategies to get Records.
-                    (fields_strat, any::<Option<CatalogItemId>>())
-                        .prop_map(|(fields, custom_id)| ScalarType::Record {
+                    (fields_strat, any::<Option<(CatalogItemId, u32)>>())
+                        .prop_map(|(fields, custom)| ScalarType::Record {
                             fields: fields.into(),
-                            custom_id,
+                            custom_id: custom.map(|(id, _)| id),
+                            custom_oid: custom.map(|(_, oid)| oid),
                         })
                         .boxed()
                 },
@@ -3767,6 +4108,10 @@ pub enum PropDatum {
     AclItem(AclItem),
     MzAclItem(MzAclItem),
 
+    Inet(Inet),
+    MacAddr(MacAddr),
+    MacAddr8(MacAddr8),
+
     JsonNull,
     Uuid(Uuid),
     Dummy,
@@ -3897,6 +4242,34 @@ pub fn arb_datum_for_scalar(scalar_type: &ScalarType) -> impl Strategy<Value = P
             .boxed(),
         ScalarType::AclItem => any::<AclItem>().prop_map(PropDatum::AclItem).boxed(),
         ScalarType::MzAclItem => any::<MzAclItem>().prop_map(PropDatum::MzAclItem).boxed(),
+        ScalarType::Inet => any::<Inet>().prop_map(PropDatum::Inet).boxed(),
+        ScalarType::Cidr => any::<Inet>()
+            .prop_map(|inet| PropDatum::Inet(inet.network()))
+            .boxed(),
+        ScalarType::MacAddr => any::<MacAddr>().prop_map(PropDatum::MacAddr).boxed(),
+        ScalarType::MacAddr8 => any::<MacAddr8>().prop_map(PropDatum::MacAddr8).boxed(),
+        ScalarType::TsVector => any::<TsVector>()
+            .prop_map(|vector| PropDatum::String(vector.to_string()))
+            .boxed(),
+        ScalarType::TsQuery => any::<TsQuery>()
+            .prop_map(|query| PropDatum::String(query.to_string()))
+            .boxed(),
+        ScalarType::JsonPath => any::<JsonPath>()
+            .prop_map(|path| PropDatum::String(path.to_string()))
+            .boxed(),
+        ScalarType::Enum { labels, .. } => {
+            let len = u32::try_from(labels.len()).expect("enum has too many labels");
+            (0..len.max(1)).prop_map(PropDatum::UInt32).boxed()
+        }
+        ScalarType::Geometry => any::<Geometry>()
+            .prop_map(|geometry| PropDatum::Bytes(geometry.to_ewkb()))
+            .boxed(),
+        ScalarType::Geography => any::<Geometry>()
+            .prop_map(|geometry| {
+                let geography = geometry.into_geography().expect("valid geography");
+                PropDatum::Bytes(geography.to_ewkb())
+            })
+            .boxed(),
         ScalarType::Range { element_type } => {
             let data_strat = (
                 arb_datum_for_scalar(element_type),
@@ -4327,6 +4700,9 @@ impl<'a> From<&'a PropDatum> for Datum<'a> {
             }
             AclItem(i) => Datum::AclItem(*i),
             MzAclItem(i) => Datum::MzAclItem(*i),
+            Inet(i) => Datum::Inet(*i),
+            MacAddr(m) => Datum::MacAddr(*m),
+            MacAddr8(m) => Datum::MacAddr8(*m),
             JsonNull => Datum::JsonNull,
             Uuid(u) => Datum::from(*u),
             Dummy => Datum::Dummy,
@@ -4346,6 +4722,7 @@ fn verify_base_eq_record_nullability() {
         )]
         .into(),
         custom_id: None,
+        custom_oid: None,
     };
     let s2 = ScalarType::Record {
         fields: [(
@@ -4357,10 +4734,12 @@ fn verify_base_eq_record_nullability() {
         )]
         .into(),
         custom_id: None,
+        custom_oid: None,
     };
     let s3 = ScalarType::Record {
         fields: [].into(),
         custom_id: None,
+        custom_oid: None,
     };
     assert!(s1.base_eq(&s2));
     assert!(!s1.base_eq(&s3));
@@ -129,7 +129,8 @@ pub fn col_values<'a>(
             | ScalarType::Oid
             | ScalarType::RegClass
             | ScalarType::RegProc
-            | ScalarType::RegType,
+            | ScalarType::RegType
+            | ScalarType::Enum { .. },
             ColumnStatKinds::Primitive(U32(stats)),
         ) => map_stats(stats, Datum::UInt32),
         (ScalarType::UInt64, ColumnStatKinds::Primitive(U64(stats))) => {
@@ -169,12 +170,16 @@ pub fn col_values<'a>(
             ScalarType::String
             | ScalarType::PgLegacyName
             | ScalarType::Char { .. }
-            | ScalarType::VarChar { .. },
+            | ScalarType::VarChar { .. }
+            | ScalarType::TsVector
+            | ScalarType::TsQuery
+            | ScalarType::JsonPath,
             ColumnStatKinds::Primitive(String(stats)),
         ) => map_stats(stats, Datum::String),
-        (ScalarType::Bytes, ColumnStatKinds::Bytes(BytesStats::Primitive(stats))) => {
-            Some((Datum::Bytes(&stats.lower), Datum::Bytes(&stats.upper)))
-        }
+        (
+            ScalarType::Bytes | ScalarType::Geometry | ScalarType::Geography,
+            ColumnStatKinds::Bytes(BytesStats::Primitive(stats)),
+        ) => Some((Datum::Bytes(&stats.lower), Datum::Bytes(&stats.upper))),
         (ScalarType::Date, ColumnStatKinds::Primitive(I32(stats))) => {
             let lower = soft_expect_or_log(Date::from_pg_epoch(stats.lower))?;
             let upper = soft_expect_or_log(Date::from_pg_epoch(stats.upper))?;
@@ -228,6 +233,10 @@ pub fn col_values<'a>(
         (
             ScalarType::AclItem
             | ScalarType::MzAclItem
+            | ScalarType::Inet
+            | ScalarType::Cidr
+            | ScalarType::MacAddr
+            | ScalarType::MacAddr8
             | ScalarType::Range { .. }
             | ScalarType::Array(_)
             | ScalarType::Map { .. }
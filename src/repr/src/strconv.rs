@@ -52,12 +52,16 @@ use uuid::Uuid;
 use crate::adt::array::ArrayDimension;
 use crate::adt::date::Date;
 use crate::adt::datetime::{self, DateTimeField, ParsedDateTime};
+use crate::adt::geo::Geometry;
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
+use crate::adt::jsonpath::JsonPath;
 use crate::adt::mz_acl_item::{AclItem, MzAclItem};
+use crate::adt::network::{Inet, MacAddr, MacAddr8};
 use crate::adt::numeric::{self, Numeric, NUMERIC_DATUM_MAX_PRECISION};
 use crate::adt::pg_legacy_name::NAME_MAX_BYTES;
 use crate::adt::range::{Range, RangeBound, RangeInner};
+use crate::adt::text_search::{TsQuery, TsVector};
 use crate::adt::timestamp::CheckedTimestamp;
 
 include!(concat!(env!("OUT_DIR"), "/mz_repr.strconv.rs"));
@@ -1731,6 +1735,142 @@ pub fn parse_acl_item(s: &str) -> Result<AclItem, ParseError> {
         .map_err(|e| ParseError::invalid_input_syntax("aclitem", s).with_details(e))
 }
 
+/// Parses an [`Inet`] from `s`.
+pub fn parse_inet(s: &str) -> Result<Inet, ParseError> {
+    s.trim()
+        .parse()
+        .map_err(|e| ParseError::invalid_input_syntax("inet", s).with_details(e))
+}
+
+/// Writes an `inet` to `buf`.
+pub fn format_inet<F>(buf: &mut F, inet: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{inet}");
+    Nestable::Yes
+}
+
+/// Parses a `cidr` from `s`.
+pub fn parse_cidr(s: &str) -> Result<Inet, ParseError> {
+    Inet::parse_cidr(s.trim())
+        .map_err(|e| ParseError::invalid_input_syntax("cidr", s).with_details(e))
+}
+
+/// Writes a `cidr` to `buf`.
+///
+/// Unlike `inet` values, `cidr` values always include the netmask.
+pub fn format_cidr<F>(buf: &mut F, cidr: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", cidr.display_cidr());
+    Nestable::Yes
+}
+
+/// Parses a [`MacAddr`] from `s`.
+pub fn parse_macaddr(s: &str) -> Result<MacAddr, ParseError> {
+    s.trim()
+        .parse()
+        .map_err(|e| ParseError::invalid_input_syntax("macaddr", s).with_details(e))
+}
+
+/// Writes a `macaddr` to `buf`.
+pub fn format_macaddr<F>(buf: &mut F, macaddr: MacAddr) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{macaddr}");
+    Nestable::Yes
+}
+
+/// Parses a [`MacAddr8`] from `s`.
+pub fn parse_macaddr8(s: &str) -> Result<MacAddr8, ParseError> {
+    s.trim()
+        .parse()
+        .map_err(|e| ParseError::invalid_input_syntax("macaddr8", s).with_details(e))
+}
+
+/// Writes a `macaddr8` to `buf`.
+pub fn format_macaddr8<F>(buf: &mut F, macaddr8: MacAddr8) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{macaddr8}");
+    Nestable::Yes
+}
+
+/// Parses a [`TsVector`] from `s`.
+pub fn parse_tsvector(s: &str) -> Result<TsVector, ParseError> {
+    s.parse()
+        .map_err(|e| ParseError::invalid_input_syntax("tsvector", s).with_details(e))
+}
+
+/// Writes a `tsvector` to `buf`.
+pub fn format_tsvector<F>(buf: &mut F, tsvector: &TsVector) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{tsvector}");
+    Nestable::MayNeedEscaping
+}
+
+/// Parses a [`TsQuery`] from `s`.
+pub fn parse_tsquery(s: &str) -> Result<TsQuery, ParseError> {
+    s.parse()
+        .map_err(|e| ParseError::invalid_input_syntax("tsquery", s).with_details(e))
+}
+
+/// Writes a `tsquery` to `buf`.
+pub fn format_tsquery<F>(buf: &mut F, tsquery: &TsQuery) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{tsquery}");
+    Nestable::MayNeedEscaping
+}
+
+/// Parses a [`JsonPath`] from `s`.
+pub fn parse_jsonpath(s: &str) -> Result<JsonPath, ParseError> {
+    s.parse()
+        .map_err(|e| ParseError::invalid_input_syntax("jsonpath", s).with_details(e))
+}
+
+/// Writes a `jsonpath` to `buf`.
+pub fn format_jsonpath<F>(buf: &mut F, jsonpath: &JsonPath) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{jsonpath}");
+    Nestable::MayNeedEscaping
+}
+
+/// Parses a `geometry` from `s`, which may be its (extended) well-known text
+/// representation or its hex-encoded (extended) well-known binary
+/// representation.
+pub fn parse_geometry(s: &str) -> Result<Geometry, ParseError> {
+    s.parse()
+        .map_err(|e| ParseError::invalid_input_syntax("geometry", s).with_details(e))
+}
+
+/// Parses a `geography` from `s`, which accepts the same representations as
+/// [`parse_geometry`].
+pub fn parse_geography(s: &str) -> Result<Geometry, ParseError> {
+    s.parse()
+        .and_then(Geometry::into_geography)
+        .map_err(|e| ParseError::invalid_input_syntax("geography", s).with_details(e))
+}
+
+/// Writes a `geometry` or `geography` to `buf`, as its hex-encoded extended
+/// well-known binary representation.
+pub fn format_geometry<F>(buf: &mut F, geometry: &Geometry) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{geometry}");
+    Nestable::Yes
+}
+
 pub trait ElementEscaper {
     fn needs_escaping(elem: &[u8]) -> bool;
     fn escape_char(c: u8) -> u8;
@@ -1888,6 +2028,68 @@ where
     }
 }
 
+/// Parses the text representation of a record with `nfields` fields, returning
+/// the text of each field, or `None` for fields that are NULL.
+pub fn parse_record(s: &str, nfields: usize) -> Result<Vec<Option<String>>, ParseError> {
+    parse_record_inner(s, nfields)
+        .map_err(|details| ParseError::invalid_input_syntax("record", s).with_details(details))
+}
+
+fn parse_record_inner(s: &str, nfields: usize) -> Result<Vec<Option<String>>, String> {
+    let buf = &mut LexBuf::new(s);
+
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    if !buf.consume('(') {
+        bail!("Missing left parenthesis.")
+    }
+
+    let mut elems = Vec::with_capacity(nfields);
+    for i in 0..nfields {
+        if i > 0 && !buf.consume(',') {
+            bail!("Too few columns.")
+        }
+        // An empty field is NULL; to specify an empty string, quote it.
+        if matches!(buf.peek(), Some(',' | ')')) {
+            elems.push(None);
+            continue;
+        }
+        let mut elem = String::new();
+        let mut in_quotes = false;
+        loop {
+            match buf.next() {
+                Some('"') if in_quotes && buf.peek() == Some('"') => {
+                    buf.next();
+                    elem.push('"');
+                }
+                Some('"') => in_quotes = !in_quotes,
+                Some('\\') => match buf.next() {
+                    Some(c) => elem.push(c),
+                    None => bail!("Unexpected end of input."),
+                },
+                Some(',' | ')') if !in_quotes => {
+                    buf.prev();
+                    break;
+                }
+                Some(c) => elem.push(c),
+                None => bail!("Unexpected end of input."),
+            }
+        }
+        elems.push(Some(elem));
+    }
+
+    match buf.next() {
+        Some(')') => (),
+        Some(',') => bail!("Too many columns."),
+        _ => bail!("Unexpected end of input."),
+    }
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    if buf.next().is_some() {
+        bail!("Junk after right parenthesis.")
+    }
+
+    Ok(elems)
+}
+
 pub fn format_record<F, T, E>(
     buf: &mut F,
     elems: impl IntoIterator<Item = T>,
@@ -17,4 +17,7 @@ pub struct ExternalUserMetadata {
     pub user_id: Uuid,
     /// Indicates if the user is an admin in the external system.
     pub admin: bool,
+    /// The groups that the user is a member of in the external system, if the
+    /// external system manages the user's role memberships.
+    pub groups: Option<Vec<String>>,
 }
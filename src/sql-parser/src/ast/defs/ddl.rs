@@ -168,7 +168,15 @@ pub enum AvroSchema<T: AstInfo> {
 impl<T: AstInfo> AstDisplay for AvroSchema<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
-            Self::Csr { csr_connection } => {
+            Self::Csr {
+                message_name,
+                csr_connection,
+            } => {
+                if let Some(message_name) = message_name {
+                    f.write_str("MESSAGE '");
+                    f.write_node(&display::escape_single_quote_string(message_name));
+                    f.write_str("' ");
+                }
                 f.write_node(csr_connection);
             }
             Self::InlineSchema {
@@ -191,6 +199,9 @@ impl_display_t!(AvroSchema);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ProtobufSchema<T: AstInfo> {
     Csr {
+        /// The fully qualified name of the message to decode, if the schema
+        /// contains more than one message.
+        message_name: Option<String>,
         csr_connection: CsrConnectionProtobuf<T>,
     },
     InlineSchema {
@@ -202,7 +213,15 @@ pub enum ProtobufSchema<T: AstInfo> {
 impl<T: AstInfo> AstDisplay for ProtobufSchema<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
-            Self::Csr { csr_connection } => {
+            Self::Csr {
+                message_name,
+                csr_connection,
+            } => {
+                if let Some(message_name) = message_name {
+                    f.write_str("MESSAGE '");
+                    f.write_node(&display::escape_single_quote_string(message_name));
+                    f.write_str("' ");
+                }
                 f.write_node(csr_connection);
             }
             Self::InlineSchema {
@@ -382,6 +401,25 @@ impl<T: AstInfo> AstDisplay for CsrConnectionProtobuf<T> {
 }
 impl_display_t!(CsrConnectionProtobuf);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsrConnectionJson<T: AstInfo> {
+    pub connection: CsrConnection<T>,
+    pub seed: Option<CsrSeedJson>,
+}
+
+impl<T: AstInfo> AstDisplay for CsrConnectionJson<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("USING CONFLUENT SCHEMA REGISTRY ");
+        f.write_node(&self.connection);
+
+        if let Some(seed) = &self.seed {
+            f.write_str(" ");
+            f.write_node(seed);
+        }
+    }
+}
+impl_display_t!(CsrConnectionJson);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CsrSeedAvro {
     pub key_schema: Option<String>,
@@ -439,6 +477,27 @@ impl AstDisplay for CsrSeedProtobufSchema {
 }
 impl_display!(CsrSeedProtobufSchema);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsrSeedJson {
+    pub key_schema: Option<String>,
+    pub value_schema: String,
+}
+
+impl AstDisplay for CsrSeedJson {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SEED");
+        if let Some(key_schema) = &self.key_schema {
+            f.write_str(" KEY SCHEMA '");
+            f.write_node(&display::escape_single_quote_string(key_schema));
+            f.write_str("'");
+        }
+        f.write_str(" VALUE SCHEMA '");
+        f.write_node(&display::escape_single_quote_string(&self.value_schema));
+        f.write_str("'");
+    }
+}
+impl_display!(CsrSeedJson);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum FormatSpecifier<T: AstInfo> {
     /// `CREATE SOURCE/SINK .. FORMAT`
@@ -478,6 +537,9 @@ pub enum Format<T: AstInfo> {
     Json {
         array: bool,
     },
+    /// `JSON USING CONFLUENT SCHEMA REGISTRY ..`: JSON decoded into typed
+    /// columns according to a registered JSON Schema.
+    JsonSchema(CsrConnectionJson<T>),
     Text,
 }
 
@@ -739,6 +801,10 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                     f.write_str(" ARRAY");
                 }
             }
+            Self::JsonSchema(csr_connection) => {
+                f.write_str("JSON ");
+                f.write_node(csr_connection);
+            }
             Self::Text => f.write_str("TEXT"),
         }
     }
@@ -1076,6 +1142,113 @@ pub struct KafkaSinkConfigOption<T: AstInfo> {
 impl_display_for_with_option!(KafkaSinkConfigOption);
 impl_display_t!(KafkaSinkConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IcebergSinkConfigOptionName {
+    Path,
+}
+
+impl AstDisplay for IcebergSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            IcebergSinkConfigOptionName::Path => "PATH",
+        })
+    }
+}
+impl_display!(IcebergSinkConfigOptionName);
+
+impl WithOptionName for IcebergSinkConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            IcebergSinkConfigOptionName::Path => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IcebergSinkConfigOption<T: AstInfo> {
+    pub name: IcebergSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(IcebergSinkConfigOption);
+impl_display_t!(IcebergSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum S3SinkConfigOptionName {
+    Path,
+    Format,
+}
+
+impl AstDisplay for S3SinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            S3SinkConfigOptionName::Path => "PATH",
+            S3SinkConfigOptionName::Format => "FORMAT",
+        })
+    }
+}
+impl_display!(S3SinkConfigOptionName);
+
+impl WithOptionName for S3SinkConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            S3SinkConfigOptionName::Path | S3SinkConfigOptionName::Format => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct S3SinkConfigOption<T: AstInfo> {
+    pub name: S3SinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(S3SinkConfigOption);
+impl_display_t!(S3SinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PostgresSinkConfigOptionName {
+    Table,
+}
+
+impl AstDisplay for PostgresSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            PostgresSinkConfigOptionName::Table => "TABLE",
+        })
+    }
+}
+impl_display!(PostgresSinkConfigOptionName);
+
+impl WithOptionName for PostgresSinkConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            PostgresSinkConfigOptionName::Table => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PostgresSinkConfigOption<T: AstInfo> {
+    pub name: PostgresSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(PostgresSinkConfigOption);
+impl_display_t!(PostgresSinkConfigOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PgConfigOptionName {
     /// Hex encoded string of binary serialization of
@@ -1188,12 +1361,22 @@ pub enum SqlServerConfigOptionName {
     /// The name for the "capture job" that will get spawn in SQL Server to
     /// populate the change table that we read from.
     CaptureInstance,
+    /// Hex encoded string of binary serialization of
+    /// `mz_storage_types::sources::sql_server::SqlServerSourceExtras`
+    Details,
+    /// Columns whose types you want to unconditionally format as text
+    TextColumns,
+    /// Columns you want to exclude
+    ExcludeColumns,
 }
 
 impl AstDisplay for SqlServerConfigOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
             SqlServerConfigOptionName::CaptureInstance => "CAPTURE INSTANCE",
+            SqlServerConfigOptionName::Details => "DETAILS",
+            SqlServerConfigOptionName::TextColumns => "TEXT COLUMNS",
+            SqlServerConfigOptionName::ExcludeColumns => "EXCLUDE COLUMNS",
         })
     }
 }
@@ -1207,7 +1390,10 @@ impl WithOptionName for SqlServerConfigOptionName {
     /// on the conservative side and return `true`.
     fn redact_value(&self) -> bool {
         match self {
-            SqlServerConfigOptionName::CaptureInstance => false,
+            SqlServerConfigOptionName::CaptureInstance
+            | SqlServerConfigOptionName::Details
+            | SqlServerConfigOptionName::TextColumns
+            | SqlServerConfigOptionName::ExcludeColumns => false,
         }
     }
 }
@@ -1444,9 +1630,23 @@ pub enum CreateSinkConnection<T: AstInfo> {
     Kafka {
         connection: T::ItemName,
         options: Vec<KafkaSinkConfigOption<T>>,
-        key: Option<KafkaSinkKey>,
+        key: Option<SinkKey>,
         headers: Option<Ident>,
     },
+    Iceberg {
+        connection: T::ItemName,
+        options: Vec<IcebergSinkConfigOption<T>>,
+        key: Option<SinkKey>,
+    },
+    S3 {
+        connection: T::ItemName,
+        options: Vec<S3SinkConfigOption<T>>,
+    },
+    Postgres {
+        connection: T::ItemName,
+        options: Vec<PostgresSinkConfigOption<T>>,
+        key: Option<SinkKey>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
@@ -1473,18 +1673,62 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
                     f.write_node(headers);
                 }
             }
+            CreateSinkConnection::Iceberg {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("ICEBERG CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::S3 {
+                connection,
+                options,
+            } => {
+                f.write_str("S3 CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSinkConnection::Postgres {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("POSTGRES CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
         }
     }
 }
 impl_display_t!(CreateSinkConnection);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct KafkaSinkKey {
+pub struct SinkKey {
     pub key_columns: Vec<Ident>,
     pub not_enforced: bool,
 }
 
-impl AstDisplay for KafkaSinkKey {
+impl AstDisplay for SinkKey {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(" KEY (");
         f.write_node(&display::comma_separated(&self.key_columns));
@@ -873,6 +873,11 @@ impl<T: AstInfo> AstDisplay for Function<T> {
         }
         f.write_node(&self.args);
         f.write_str(")");
+        if let FunctionArgs::WithinGroup { order_by, .. } = &self.args {
+            f.write_str(" WITHIN GROUP (ORDER BY ");
+            f.write_node(&display::comma_separated(order_by));
+            f.write_str(")");
+        }
         if let Some(filter) = &self.filter {
             f.write_str(" FILTER (WHERE ");
             f.write_node(&filter);
@@ -895,6 +900,13 @@ pub enum FunctionArgs<T: AstInfo> {
         args: Vec<Expr<T>>,
         order_by: Vec<OrderByExpr<T>>,
     },
+    /// The direct arguments of an ordered-set aggregate, along with the
+    /// aggregated arguments from its `WITHIN GROUP (ORDER BY ...)` clause, as
+    /// in `percentile_cont(0.5) WITHIN GROUP (ORDER BY x)`.
+    WithinGroup {
+        args: Vec<Expr<T>>,
+        order_by: Vec<OrderByExpr<T>>,
+    },
 }
 
 impl<T: AstInfo> FunctionArgs<T> {
@@ -909,7 +921,9 @@ impl<T: AstInfo> FunctionArgs<T> {
     pub fn len(&self) -> Option<usize> {
         match self {
             FunctionArgs::Star => None,
-            FunctionArgs::Args { args, .. } => Some(args.len()),
+            FunctionArgs::Args { args, .. } | FunctionArgs::WithinGroup { args, .. } => {
+                Some(args.len())
+            }
         }
     }
 
@@ -921,7 +935,7 @@ impl<T: AstInfo> FunctionArgs<T> {
     ) {
         let args = match self {
             FunctionArgs::Star => unreachable!(),
-            FunctionArgs::Args { args, .. } => args,
+            FunctionArgs::Args { args, .. } | FunctionArgs::WithinGroup { args, .. } => args,
         };
         soft_assert_eq_or_log!(args.len(), kws.len());
         let mut delim = "";
@@ -949,6 +963,11 @@ impl<T: AstInfo> AstDisplay for FunctionArgs<T> {
                     f.write_node(&display::comma_separated(order_by));
                 }
             }
+            // The `WITHIN GROUP` clause follows the closing parenthesis, so
+            // it is printed by `Function`.
+            FunctionArgs::WithinGroup { args, .. } => {
+                f.write_node(&display::comma_separated(args));
+            }
         }
     }
 }
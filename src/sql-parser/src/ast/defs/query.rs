@@ -582,6 +582,15 @@ pub enum TableFactor<T: AstInfo> {
         join: Box<TableWithJoins<T>>,
         alias: Option<TableAlias>,
     },
+    /// A `JSON_TABLE(...)` expression, which produces a row for each item that
+    /// a SQL/JSON path selects from a `jsonb` value.
+    JsonTable {
+        context: Expr<T>,
+        path: String,
+        passing: Vec<JsonTablePassing<T>>,
+        columns: Vec<JsonTableColumn<T>>,
+        alias: Option<TableAlias>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for TableFactor<T> {
@@ -649,6 +658,29 @@ impl<T: AstInfo> AstDisplay for TableFactor<T> {
                     f.write_node(alias);
                 }
             }
+            TableFactor::JsonTable {
+                context,
+                path,
+                passing,
+                columns,
+                alias,
+            } => {
+                f.write_str("JSON_TABLE(");
+                f.write_node(context);
+                f.write_str(", ");
+                f.write_node(&display::escaped_string_literal(path));
+                if !passing.is_empty() {
+                    f.write_str(" PASSING ");
+                    f.write_node(&display::comma_separated(passing));
+                }
+                f.write_str(" COLUMNS (");
+                f.write_node(&display::comma_separated(columns));
+                f.write_str("))");
+                if let Some(alias) = alias {
+                    f.write_str(" AS ");
+                    f.write_node(alias);
+                }
+            }
         }
     }
 }
@@ -678,6 +710,74 @@ impl AstDisplay for TableAlias {
 }
 impl_display!(TableAlias);
 
+/// A `value AS name` argument of the `PASSING` clause of `JSON_TABLE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JsonTablePassing<T: AstInfo> {
+    pub expr: Expr<T>,
+    pub name: Ident,
+}
+
+impl<T: AstInfo> AstDisplay for JsonTablePassing<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.expr);
+        f.write_str(" AS ");
+        f.write_node(&self.name);
+    }
+}
+impl_display_t!(JsonTablePassing);
+
+/// A column in the `COLUMNS` clause of `JSON_TABLE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum JsonTableColumn<T: AstInfo> {
+    /// `name FOR ORDINALITY`
+    Ordinality { name: Ident },
+    /// `name data_type [PATH 'path']`
+    Value {
+        name: Ident,
+        data_type: T::DataType,
+        path: Option<String>,
+    },
+    /// `name data_type EXISTS [PATH 'path']`
+    Exists {
+        name: Ident,
+        data_type: T::DataType,
+        path: Option<String>,
+    },
+}
+
+impl<T: AstInfo> AstDisplay for JsonTableColumn<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            JsonTableColumn::Ordinality { name } => {
+                f.write_node(name);
+                f.write_str(" FOR ORDINALITY");
+            }
+            JsonTableColumn::Value {
+                name,
+                data_type,
+                path,
+            }
+            | JsonTableColumn::Exists {
+                name,
+                data_type,
+                path,
+            } => {
+                f.write_node(name);
+                f.write_str(" ");
+                f.write_node(data_type);
+                if let JsonTableColumn::Exists { .. } = self {
+                    f.write_str(" EXISTS");
+                }
+                if let Some(path) = path {
+                    f.write_str(" PATH ");
+                    f.write_node(&display::escaped_string_literal(path));
+                }
+            }
+        }
+    }
+}
+impl_display_t!(JsonTableColumn);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Join<T: AstInfo> {
     pub relation: TableFactor<T>,
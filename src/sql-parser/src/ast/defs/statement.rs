@@ -46,6 +46,7 @@ pub enum Statement<T: AstInfo> {
     Copy(CopyStatement<T>),
     Update(UpdateStatement<T>),
     Delete(DeleteStatement<T>),
+    Merge(MergeStatement<T>),
     CreateConnection(CreateConnectionStatement<T>),
     CreateDatabase(CreateDatabaseStatement),
     CreateSchema(CreateSchemaStatement),
@@ -60,6 +61,7 @@ pub enum Statement<T: AstInfo> {
     CreateTableFromSource(CreateTableFromSourceStatement<T>),
     CreateIndex(CreateIndexStatement<T>),
     CreateType(CreateTypeStatement<T>),
+    CreateFunction(CreateFunctionStatement<T>),
     CreateRole(CreateRoleStatement),
     CreateCluster(CreateClusterStatement<T>),
     CreateClusterReplica(CreateClusterReplicaStatement<T>),
@@ -82,6 +84,7 @@ pub enum Statement<T: AstInfo> {
     AlterNetworkPolicy(AlterNetworkPolicyStatement<T>),
     AlterRole(AlterRoleStatement<T>),
     AlterTableAddColumn(AlterTableAddColumnStatement<T>),
+    AlterViewQuery(AlterViewQueryStatement<T>),
     Discard(DiscardStatement),
     DropObjects(DropObjectsStatement),
     DropOwned(DropOwnedStatement<T>),
@@ -122,6 +125,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Copy(stmt) => f.write_node(stmt),
             Statement::Update(stmt) => f.write_node(stmt),
             Statement::Delete(stmt) => f.write_node(stmt),
+            Statement::Merge(stmt) => f.write_node(stmt),
             Statement::CreateConnection(stmt) => f.write_node(stmt),
             Statement::CreateDatabase(stmt) => f.write_node(stmt),
             Statement::CreateSchema(stmt) => f.write_node(stmt),
@@ -138,6 +142,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateRole(stmt) => f.write_node(stmt),
             Statement::CreateSecret(stmt) => f.write_node(stmt),
             Statement::CreateType(stmt) => f.write_node(stmt),
+            Statement::CreateFunction(stmt) => f.write_node(stmt),
             Statement::CreateCluster(stmt) => f.write_node(stmt),
             Statement::CreateClusterReplica(stmt) => f.write_node(stmt),
             Statement::CreateNetworkPolicy(stmt) => f.write_node(stmt),
@@ -158,6 +163,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::AlterConnection(stmt) => f.write_node(stmt),
             Statement::AlterRole(stmt) => f.write_node(stmt),
             Statement::AlterTableAddColumn(stmt) => f.write_node(stmt),
+            Statement::AlterViewQuery(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropObjects(stmt) => f.write_node(stmt),
             Statement::DropOwned(stmt) => f.write_node(stmt),
@@ -201,6 +207,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::Copy => "copy",
         StatementKind::Update => "update",
         StatementKind::Delete => "delete",
+        StatementKind::Merge => "merge",
         StatementKind::CreateConnection => "create_connection",
         StatementKind::CreateDatabase => "create_database",
         StatementKind::CreateSchema => "create_schema",
@@ -215,6 +222,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::CreateTableFromSource => "create_table_from_source",
         StatementKind::CreateIndex => "create_index",
         StatementKind::CreateType => "create_type",
+        StatementKind::CreateFunction => "create_function",
         StatementKind::CreateRole => "create_role",
         StatementKind::CreateCluster => "create_cluster",
         StatementKind::CreateClusterReplica => "create_cluster_replica",
@@ -237,6 +245,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::AlterOwner => "alter_owner",
         StatementKind::AlterConnection => "alter_connection",
         StatementKind::AlterTableAddColumn => "alter_table",
+        StatementKind::AlterViewQuery => "alter_view_query",
         StatementKind::Discard => "discard",
         StatementKind::DropObjects => "drop_objects",
         StatementKind::DropOwned => "drop_owned",
@@ -297,6 +306,8 @@ pub struct InsertStatement<T: AstInfo> {
     pub columns: Vec<Ident>,
     /// A SQL query that specifies what to insert.
     pub source: InsertSource<T>,
+    /// ON CONFLICT
+    pub on_conflict: Option<OnConflict<T>>,
     /// RETURNING
     pub returning: Vec<SelectItem<T>>,
 }
@@ -312,6 +323,10 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
         }
         f.write_str(" ");
         f.write_node(&self.source);
+        if let Some(on_conflict) = &self.on_conflict {
+            f.write_str(" ");
+            f.write_node(on_conflict);
+        }
         if !self.returning.is_empty() {
             f.write_str(" RETURNING ");
             f.write_node(&display::comma_separated(&self.returning));
@@ -376,6 +391,7 @@ pub enum CopyOptionName {
     MaxFileSize,
     Files,
     Pattern,
+    JsonPaths,
 }
 
 impl AstDisplay for CopyOptionName {
@@ -391,6 +407,7 @@ impl AstDisplay for CopyOptionName {
             CopyOptionName::MaxFileSize => "MAX FILE SIZE",
             CopyOptionName::Files => "FILES",
             CopyOptionName::Pattern => "PATTERN",
+            CopyOptionName::JsonPaths => "JSON PATHS",
         })
     }
 }
@@ -410,7 +427,8 @@ impl WithOptionName for CopyOptionName {
             | CopyOptionName::Quote
             | CopyOptionName::Header
             | CopyOptionName::AwsConnection
-            | CopyOptionName::MaxFileSize => false,
+            | CopyOptionName::MaxFileSize
+            | CopyOptionName::JsonPaths => false,
             CopyOptionName::Files | CopyOptionName::Pattern => true,
         }
     }
@@ -537,6 +555,116 @@ impl<T: AstInfo> AstDisplay for DeleteStatement<T> {
 }
 impl_display_t!(DeleteStatement);
 
+/// `MERGE`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeStatement<T: AstInfo> {
+    /// `INTO`
+    pub table_name: T::ItemName,
+    /// `AS`
+    pub alias: Option<TableAlias>,
+    /// `USING`
+    pub using: TableWithJoins<T>,
+    /// `ON`
+    pub on: Expr<T>,
+    /// `WHEN [NOT] MATCHED ...`
+    pub clauses: Vec<MergeClause<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MergeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("MERGE INTO ");
+        f.write_node(&self.table_name);
+        if let Some(alias) = &self.alias {
+            f.write_str(" AS ");
+            f.write_node(alias);
+        }
+        f.write_str(" USING ");
+        f.write_node(&self.using);
+        f.write_str(" ON ");
+        f.write_node(&self.on);
+        for clause in &self.clauses {
+            f.write_str(" ");
+            f.write_node(clause);
+        }
+    }
+}
+impl_display_t!(MergeStatement);
+
+/// A `WHEN [NOT] MATCHED [AND <condition>] THEN <action>` clause of a `MERGE`
+/// statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeClause<T: AstInfo> {
+    /// Whether the clause applies to source rows that match a row of the
+    /// target table, rather than to those that don't.
+    pub matched: bool,
+    /// `AND`
+    pub condition: Option<Expr<T>>,
+    /// `THEN`
+    pub action: MergeAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for MergeClause<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("WHEN ");
+        if !self.matched {
+            f.write_str("NOT ");
+        }
+        f.write_str("MATCHED");
+        if let Some(condition) = &self.condition {
+            f.write_str(" AND ");
+            f.write_node(condition);
+        }
+        f.write_str(" THEN ");
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(MergeClause);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MergeAction<T: AstInfo> {
+    /// `INSERT [(<columns>)] VALUES (<values>)`, or `INSERT DEFAULT VALUES`
+    /// if `values` is empty.
+    Insert {
+        columns: Vec<Ident>,
+        values: Vec<Expr<T>>,
+    },
+    /// `UPDATE SET <assignments>`
+    Update { assignments: Vec<Assignment<T>> },
+    /// `DELETE`
+    Delete,
+    /// `DO NOTHING`
+    DoNothing,
+}
+
+impl<T: AstInfo> AstDisplay for MergeAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            MergeAction::Insert { columns, values } => {
+                f.write_str("INSERT");
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+                if values.is_empty() {
+                    f.write_str(" DEFAULT VALUES");
+                } else {
+                    f.write_str(" VALUES (");
+                    f.write_node(&display::comma_separated(values));
+                    f.write_str(")");
+                }
+            }
+            MergeAction::Update { assignments } => {
+                f.write_str("UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+            }
+            MergeAction::Delete => f.write_str("DELETE"),
+            MergeAction::DoNothing => f.write_str("DO NOTHING"),
+        }
+    }
+}
+impl_display_t!(MergeAction);
+
 /// `CREATE DATABASE`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateDatabaseStatement {
@@ -1904,9 +2032,13 @@ pub enum RoleAttribute {
     Inherit,
     /// The `NOINHERIT` option.
     NoInherit,
-    // The following are not supported, but included to give helpful error messages.
+    /// The `LOGIN` option.
     Login,
+    /// The `NOLOGIN` option.
     NoLogin,
+    /// The `PASSWORD` option, where `None` represents `PASSWORD NULL`.
+    Password(Option<String>),
+    // The following are not supported, but included to give helpful error messages.
     SuperUser,
     NoSuperUser,
     CreateCluster,
@@ -1926,6 +2058,15 @@ impl AstDisplay for RoleAttribute {
             RoleAttribute::NoLogin => f.write_str("NOLOGIN"),
             RoleAttribute::Inherit => f.write_str("INHERIT"),
             RoleAttribute::NoInherit => f.write_str("NOINHERIT"),
+            RoleAttribute::Password(None) => f.write_str("PASSWORD NULL"),
+            RoleAttribute::Password(Some(_)) if f.redacted() => {
+                f.write_str("PASSWORD '<REDACTED>'")
+            }
+            RoleAttribute::Password(Some(password)) => {
+                f.write_str("PASSWORD '");
+                f.write_node(&display::escape_single_quote_string(password));
+                f.write_str("'");
+            }
             RoleAttribute::CreateCluster => f.write_str("CREATECLUSTER"),
             RoleAttribute::NoCreateCluster => f.write_str("NOCREATECLUSTER"),
             RoleAttribute::CreateDB => f.write_str("CREATEDB"),
@@ -2158,11 +2299,152 @@ impl<T: AstInfo> AstDisplay for CreateTypeStatement<T> {
                 }
                 f.write_str(")");
             }
+            CreateTypeAs::Enum { labels } => {
+                f.write_str(&self.as_type);
+                f.write_str("(");
+                for (i, label) in labels.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ");
+                    }
+                    f.write_str("'");
+                    f.write_node(&display::escape_single_quote_string(label));
+                    f.write_str("'");
+                }
+                f.write_str(")");
+            }
         };
     }
 }
 impl_display_t!(CreateTypeStatement);
 
+/// `CREATE FUNCTION ..`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateFunctionStatement<T: AstInfo> {
+    pub if_exists: IfExistsBehavior,
+    /// Name of the created function.
+    pub name: UnresolvedItemName,
+    /// The function's parameters.
+    pub parameters: Vec<FunctionParameter<T>>,
+    /// The type of the function's result.
+    pub returns: FunctionReturnType<T>,
+    /// The language the function is written in, if specified.
+    pub language: Option<Ident>,
+    /// The function's definition.
+    pub body: FunctionBody<T>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateFunctionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE");
+        if self.if_exists == IfExistsBehavior::Replace {
+            f.write_str(" OR REPLACE");
+        }
+        f.write_str(" FUNCTION ");
+        f.write_node(&self.name);
+        f.write_str("(");
+        f.write_node(&display::comma_separated(&self.parameters));
+        f.write_str(") RETURNS ");
+        f.write_node(&self.returns);
+        if let Some(language) = &self.language {
+            f.write_str(" LANGUAGE ");
+            f.write_node(language);
+        }
+        f.write_str(" ");
+        f.write_node(&self.body);
+    }
+}
+impl_display_t!(CreateFunctionStatement);
+
+/// A parameter in a `CREATE FUNCTION` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionParameter<T: AstInfo> {
+    pub name: Option<Ident>,
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for FunctionParameter<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        if let Some(name) = &self.name {
+            f.write_node(name);
+            f.write_str(" ");
+        }
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(FunctionParameter);
+
+/// A column of the table returned by a `CREATE FUNCTION .. RETURNS TABLE`
+/// function.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionColumn<T: AstInfo> {
+    pub name: Ident,
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for FunctionColumn<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        f.write_str(" ");
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(FunctionColumn);
+
+/// The `RETURNS` clause of a `CREATE FUNCTION` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FunctionReturnType<T: AstInfo> {
+    /// `RETURNS <type>`
+    Scalar(T::DataType),
+    /// `RETURNS SETOF <type>`
+    SetOf(T::DataType),
+    /// `RETURNS TABLE (<columns>)`
+    Table(Vec<FunctionColumn<T>>),
+}
+
+impl<T: AstInfo> AstDisplay for FunctionReturnType<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            FunctionReturnType::Scalar(data_type) => f.write_node(data_type),
+            FunctionReturnType::SetOf(data_type) => {
+                f.write_str("SETOF ");
+                f.write_node(data_type);
+            }
+            FunctionReturnType::Table(columns) => {
+                f.write_str("TABLE (");
+                f.write_node(&display::comma_separated(columns));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(FunctionReturnType);
+
+/// The body of a `CREATE FUNCTION` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FunctionBody<T: AstInfo> {
+    /// `RETURN <expr>`
+    Return(Expr<T>),
+    /// `BEGIN ATOMIC <query>; END`
+    Atomic(Query<T>),
+}
+
+impl<T: AstInfo> AstDisplay for FunctionBody<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            FunctionBody::Return(expr) => {
+                f.write_str("RETURN ");
+                f.write_node(expr);
+            }
+            FunctionBody::Atomic(query) => {
+                f.write_str("BEGIN ATOMIC ");
+                f.write_node(query);
+                f.write_str("; END");
+            }
+        }
+    }
+}
+impl_display_t!(FunctionBody);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ClusterOptionName {
     /// The `AVAILABILITY ZONES [[=] '[' <values> ']' ]` option.
@@ -2578,6 +2860,9 @@ pub enum CreateTypeAs<T: AstInfo> {
     Record {
         column_defs: Vec<ColumnDef<T>>,
     },
+    Enum {
+        labels: Vec<String>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateTypeAs<T> {
@@ -2586,6 +2871,7 @@ impl<T: AstInfo> AstDisplay for CreateTypeAs<T> {
             CreateTypeAs::List { .. } => f.write_str("LIST "),
             CreateTypeAs::Map { .. } => f.write_str("MAP "),
             CreateTypeAs::Record { .. } => f.write_str("RECORD "),
+            CreateTypeAs::Enum { .. } => f.write_str("ENUM "),
         }
     }
 }
@@ -3175,6 +3461,34 @@ impl<T: AstInfo> AstDisplay for AlterTableAddColumnStatement<T> {
 
 impl_display_t!(AlterTableAddColumnStatement);
 
+/// `ALTER [MATERIALIZED] VIEW ... AS ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterViewQueryStatement<T: AstInfo> {
+    pub object_type: ObjectType,
+    pub if_exists: bool,
+    pub name: UnresolvedItemName,
+    pub query: Query<T>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterViewQueryStatement<T> {
+    fn fmt<W>(&self, f: &mut AstFormatter<W>)
+    where
+        W: fmt::Write,
+    {
+        f.write_str("ALTER ");
+        f.write_node(&self.object_type);
+        f.write_str(" ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" AS ");
+        f.write_node(&self.query);
+    }
+}
+
+impl_display_t!(AlterViewQueryStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscardStatement {
     pub target: DiscardTarget,
@@ -3643,6 +3957,20 @@ impl<T: AstInfo> AstDisplay for ShowCreateIndexStatement<T> {
 }
 impl_display_t!(ShowCreateIndexStatement);
 
+/// `SHOW CREATE FUNCTION <function>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShowCreateFunctionStatement<T: AstInfo> {
+    pub function_name: T::ItemName,
+}
+
+impl<T: AstInfo> AstDisplay for ShowCreateFunctionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW CREATE FUNCTION ");
+        f.write_node(&self.function_name);
+    }
+}
+impl_display_t!(ShowCreateFunctionStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateConnectionStatement<T: AstInfo> {
     pub connection_name: T::ItemName,
@@ -4031,6 +4359,59 @@ impl<T: AstInfo> AstDisplay for InsertSource<T> {
 }
 impl_display_t!(InsertSource);
 
+/// `ON CONFLICT [(<columns>)] <action>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnConflict<T: AstInfo> {
+    /// The columns of the unique key whose conflicts are handled. If empty,
+    /// conflicts on any unique key are handled.
+    pub columns: Vec<Ident>,
+    pub action: OnConflictAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for OnConflict<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ON CONFLICT ");
+        if !self.columns.is_empty() {
+            f.write_str("(");
+            f.write_node(&display::comma_separated(&self.columns));
+            f.write_str(") ");
+        }
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(OnConflict);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OnConflictAction<T: AstInfo> {
+    /// `DO NOTHING`
+    DoNothing,
+    /// `DO UPDATE SET <assignments> [WHERE <selection>]`
+    DoUpdate {
+        assignments: Vec<Assignment<T>>,
+        selection: Option<Expr<T>>,
+    },
+}
+
+impl<T: AstInfo> AstDisplay for OnConflictAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            OnConflictAction::DoNothing => f.write_str("DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                f.write_str("DO UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+                if let Some(selection) = selection {
+                    f.write_str(" WHERE ");
+                    f.write_node(selection);
+                }
+            }
+        }
+    }
+}
+impl_display_t!(OnConflictAction);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
 pub enum ObjectType {
     Table,
@@ -4947,6 +5328,7 @@ pub enum ShowStatement<T: AstInfo> {
     ShowCreateTable(ShowCreateTableStatement<T>),
     ShowCreateSink(ShowCreateSinkStatement<T>),
     ShowCreateIndex(ShowCreateIndexStatement<T>),
+    ShowCreateFunction(ShowCreateFunctionStatement<T>),
     ShowCreateConnection(ShowCreateConnectionStatement<T>),
     ShowCreateCluster(ShowCreateClusterStatement<T>),
     ShowVariable(ShowVariableStatement),
@@ -4964,6 +5346,7 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
             ShowStatement::ShowCreateTable(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateSink(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateIndex(stmt) => f.write_node(stmt),
+            ShowStatement::ShowCreateFunction(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateConnection(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateCluster(stmt) => f.write_node(stmt),
             ShowStatement::ShowVariable(stmt) => f.write_node(stmt),
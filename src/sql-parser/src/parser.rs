@@ -397,6 +397,9 @@ impl<'a> Parser<'a> {
                 Token::Keyword(UPDATE) => {
                     Ok(self.parse_update().map_parser_err(StatementKind::Update)?)
                 }
+                Token::Keyword(MERGE) => {
+                    Ok(self.parse_merge().map_parser_err(StatementKind::Merge)?)
+                }
                 Token::Keyword(ALTER) => Ok(self.parse_alter()?),
                 Token::Keyword(COPY) => Ok(self.parse_copy()?),
                 Token::Keyword(SET) => Ok(self.parse_set()?),
@@ -742,7 +745,7 @@ impl<'a> Parser<'a> {
             self.parse_at_most_one_keyword(&[ALL, DISTINCT], &format!("function: {}", name))?,
             Some(DISTINCT),
         );
-        let args = self.parse_optional_args(true)?;
+        let mut args = self.parse_optional_args(true)?;
 
         if distinct && matches!(args, FunctionArgs::Star) {
             return Err(self.error(
@@ -751,6 +754,39 @@ impl<'a> Parser<'a> {
             ));
         }
 
+        let within_group_pos = self.peek_pos();
+        if self.parse_keywords(&[WITHIN, GROUP]) {
+            self.expect_token(&Token::LParen)?;
+            self.expect_keywords(&[ORDER, BY])?;
+            let order_by = self.parse_comma_separated(Parser::parse_order_by_expr)?;
+            self.expect_token(&Token::RParen)?;
+            args = match args {
+                FunctionArgs::Star => {
+                    return Err(self.error(
+                        within_group_pos,
+                        "cannot use * with WITHIN GROUP".to_string(),
+                    ))
+                }
+                FunctionArgs::Args {
+                    order_by: inner_order_by,
+                    ..
+                } if !inner_order_by.is_empty() => {
+                    return Err(self.error(
+                        within_group_pos,
+                        "cannot use multiple ORDER BY clauses with WITHIN GROUP".to_string(),
+                    ))
+                }
+                FunctionArgs::Args { .. } if distinct => {
+                    return Err(self.error(
+                        within_group_pos,
+                        "cannot use DISTINCT with WITHIN GROUP".to_string(),
+                    ))
+                }
+                FunctionArgs::Args { args, .. } => FunctionArgs::WithinGroup { args, order_by },
+                FunctionArgs::WithinGroup { .. } => unreachable!("parsed as plain args"),
+            };
+        }
+
         let filter = if self.parse_keyword(FILTER) {
             self.expect_token(&Token::LParen)?;
             self.expect_keyword(WHERE)?;
@@ -1860,6 +1896,9 @@ impl<'a> Parser<'a> {
         } else if self.peek_keyword(ROLE) {
             self.parse_create_role()
                 .map_parser_err(StatementKind::CreateRole)
+        } else if self.peek_keyword(FUNCTION) || self.peek_keywords(&[OR, REPLACE, FUNCTION]) {
+            self.parse_create_function()
+                .map_parser_err(StatementKind::CreateFunction)
         } else if self.peek_keyword(CLUSTER) {
             self.next_token();
             if self.peek_keyword(REPLICA) {
@@ -1937,13 +1976,13 @@ impl<'a> Parser<'a> {
                 let expected_msg = match (parsed_or_replace, parsed_temporary) {
                     (true, true) => "VIEW after CREATE OR REPLACE TEMPORARY",
                     (true, false) => {
-                        "[TEMPORARY] VIEW, or MATERIALIZED VIEW after CREATE OR REPLACE"
+                        "FUNCTION, [TEMPORARY] VIEW, or MATERIALIZED VIEW after CREATE OR REPLACE"
                     }
                     (false, true) => "TABLE, or VIEW after CREATE TEMPORARY",
                     (false, false) => {
                         "DATABASE, SCHEMA, ROLE, TYPE, INDEX, SINK, SOURCE, [TEMPORARY] TABLE, \
-                        SECRET, [OR REPLACE] [TEMPORARY] VIEW, or [OR REPLACE] MATERIALIZED VIEW \
-                        after CREATE"
+                        SECRET, [OR REPLACE] FUNCTION, [OR REPLACE] [TEMPORARY] VIEW, or \
+                        [OR REPLACE] MATERIALIZED VIEW after CREATE"
                     }
                 };
                 self.expected(self.peek_pos(), expected_msg, self.peek_token())
@@ -2003,8 +2042,12 @@ impl<'a> Parser<'a> {
             };
             Format::Csv { columns, delimiter }
         } else if self.parse_keyword(JSON) {
-            let array = self.parse_keyword(ARRAY);
-            Format::Json { array }
+            if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
+                Format::JsonSchema(self.parse_csr_connection_json()?)
+            } else {
+                let array = self.parse_keyword(ARRAY);
+                Format::Json { array }
+            }
         } else if self.parse_keyword(TEXT) {
             Format::Text
         } else if self.parse_keyword(BYTES) {
@@ -2061,10 +2104,20 @@ impl<'a> Parser<'a> {
     fn parse_protobuf_schema(&mut self) -> Result<ProtobufSchema<Raw>, ParserError> {
         if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
             let csr_connection = self.parse_csr_connection_proto()?;
-            Ok(ProtobufSchema::Csr { csr_connection })
+            Ok(ProtobufSchema::Csr {
+                message_name: None,
+                csr_connection,
+            })
         } else if self.parse_keyword(MESSAGE) {
             let message_name = self.parse_literal_string()?;
             self.expect_keyword(USING)?;
+            if self.parse_keywords(&[CONFLUENT, SCHEMA, REGISTRY]) {
+                let csr_connection = self.parse_csr_connection_proto()?;
+                return Ok(ProtobufSchema::Csr {
+                    message_name: Some(message_name),
+                    csr_connection,
+                });
+            }
             self.expect_keyword(SCHEMA)?;
             let schema = Schema {
                 schema: self.parse_literal_string()?,
@@ -2258,6 +2311,28 @@ impl<'a> Parser<'a> {
         Ok(CsrConnectionProtobuf { connection, seed })
     }
 
+    fn parse_csr_connection_json(&mut self) -> Result<CsrConnectionJson<Raw>, ParserError> {
+        let connection = self.parse_csr_connection_reference()?;
+        let seed = if self.parse_keyword(SEED) {
+            let key_schema = if self.parse_keyword(KEY) {
+                self.expect_keyword(SCHEMA)?;
+                Some(self.parse_literal_string()?)
+            } else {
+                None
+            };
+            self.expect_keywords(&[VALUE, SCHEMA])?;
+            let value_schema = self.parse_literal_string()?;
+            Some(CsrSeedJson {
+                key_schema,
+                value_schema,
+            })
+        } else {
+            None
+        };
+
+        Ok(CsrConnectionJson { connection, seed })
+    }
+
     fn parse_source_error_policy_option(&mut self) -> Result<SourceErrorPolicy, ParserError> {
         match self.expect_one_of_keywords(&[INLINE])? {
             INLINE => Ok(SourceErrorPolicy::Inline {
@@ -3439,11 +3514,26 @@ impl<'a> Parser<'a> {
     fn parse_sql_server_connection_option(
         &mut self,
     ) -> Result<SqlServerConfigOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[CAPTURE])? {
+        let name = match self.expect_one_of_keywords(&[CAPTURE, DETAILS, TEXT, EXCLUDE])? {
             CAPTURE => {
                 self.expect_keyword(INSTANCE)?;
                 SqlServerConfigOptionName::CaptureInstance
             }
+            DETAILS => SqlServerConfigOptionName::Details,
+            TEXT => {
+                self.expect_keyword(COLUMNS)?;
+                return Ok(SqlServerConfigOption {
+                    name: SqlServerConfigOptionName::TextColumns,
+                    value: self.parse_sql_server_column_names()?,
+                });
+            }
+            EXCLUDE => {
+                self.expect_keyword(COLUMNS)?;
+                return Ok(SqlServerConfigOption {
+                    name: SqlServerConfigOptionName::ExcludeColumns,
+                    value: self.parse_sql_server_column_names()?,
+                });
+            }
             _ => unreachable!(),
         };
 
@@ -3453,6 +3543,24 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_sql_server_column_names(
+        &mut self,
+    ) -> Result<Option<WithOptionValue<Raw>>, ParserError> {
+        let _ = self.consume_token(&Token::Eq);
+
+        let value = self
+            .parse_option_sequence(Parser::parse_item_name)?
+            .map(|inner| {
+                WithOptionValue::Sequence(
+                    inner
+                        .into_iter()
+                        .map(WithOptionValue::UnresolvedItemName)
+                        .collect_vec(),
+                )
+            });
+        Ok(value)
+    }
+
     fn parse_load_generator_option(&mut self) -> Result<LoadGeneratorOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[
             AS,
@@ -3517,7 +3625,99 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_iceberg_sink_config_option(
+        &mut self,
+    ) -> Result<IcebergSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PATH])? {
+            PATH => IcebergSinkConfigOptionName::Path,
+            _ => unreachable!(),
+        };
+        Ok(IcebergSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_s3_sink_config_option(&mut self) -> Result<S3SinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PATH, FORMAT])? {
+            PATH => S3SinkConfigOptionName::Path,
+            FORMAT => S3SinkConfigOptionName::Format,
+            _ => unreachable!(),
+        };
+        Ok(S3SinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_postgres_sink_config_option(
+        &mut self,
+    ) -> Result<PostgresSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[TABLE])? {
+            TABLE => PostgresSinkConfigOptionName::Table,
+            _ => unreachable!(),
+        };
+        Ok(PostgresSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_create_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        if self.parse_keyword(ICEBERG) {
+            self.expect_keyword(CONNECTION)?;
+            let connection = self.parse_raw_name()?;
+            let options = if self.consume_token(&Token::LParen) {
+                let options =
+                    self.parse_comma_separated(Parser::parse_iceberg_sink_config_option)?;
+                self.expect_token(&Token::RParen)?;
+                options
+            } else {
+                vec![]
+            };
+            let key = self.parse_sink_key()?;
+            return Ok(CreateSinkConnection::Iceberg {
+                connection,
+                options,
+                key,
+            });
+        }
+
+        if self.parse_keyword(S3) {
+            self.expect_keyword(CONNECTION)?;
+            let connection = self.parse_raw_name()?;
+            let options = if self.consume_token(&Token::LParen) {
+                let options = self.parse_comma_separated(Parser::parse_s3_sink_config_option)?;
+                self.expect_token(&Token::RParen)?;
+                options
+            } else {
+                vec![]
+            };
+            return Ok(CreateSinkConnection::S3 {
+                connection,
+                options,
+            });
+        }
+
+        if self.parse_keyword(POSTGRES) {
+            self.expect_keyword(CONNECTION)?;
+            let connection = self.parse_raw_name()?;
+            let options = if self.consume_token(&Token::LParen) {
+                let options =
+                    self.parse_comma_separated(Parser::parse_postgres_sink_config_option)?;
+                self.expect_token(&Token::RParen)?;
+                options
+            } else {
+                vec![]
+            };
+            let key = self.parse_sink_key()?;
+            return Ok(CreateSinkConnection::Postgres {
+                connection,
+                options,
+                key,
+            });
+        }
+
         self.expect_keyword(KAFKA)?;
         self.expect_keyword(CONNECTION)?;
 
@@ -3531,27 +3731,7 @@ impl<'a> Parser<'a> {
             vec![]
         };
 
-        // one token of lookahead:
-        // * `KEY (` means we're parsing a list of columns for the key
-        // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
-        let key =
-            if self.peek_keyword(KEY) && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT)) {
-                let _ = self.expect_keyword(KEY);
-                let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
-
-                let not_enforced = if self.peek_keywords(&[NOT, ENFORCED]) {
-                    let _ = self.expect_keywords(&[NOT, ENFORCED])?;
-                    true
-                } else {
-                    false
-                };
-                Some(KafkaSinkKey {
-                    key_columns,
-                    not_enforced,
-                })
-            } else {
-                None
-            };
+        let key = self.parse_sink_key()?;
 
         let headers = if self.parse_keyword(HEADERS) {
             Some(self.parse_identifier()?)
@@ -3567,6 +3747,29 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_sink_key(&mut self) -> Result<Option<SinkKey>, ParserError> {
+        // one token of lookahead:
+        // * `KEY (` means we're parsing a list of columns for the key
+        // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
+        if self.peek_keyword(KEY) && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT)) {
+            let _ = self.expect_keyword(KEY);
+            let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
+
+            let not_enforced = if self.peek_keywords(&[NOT, ENFORCED]) {
+                let _ = self.expect_keywords(&[NOT, ENFORCED])?;
+                true
+            } else {
+                false
+            };
+            Ok(Some(SinkKey {
+                key_columns,
+                not_enforced,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn parse_create_view(&mut self) -> Result<Statement<Raw>, ParserError> {
         let mut if_exists = if self.parse_keyword(OR) {
             self.expect_keyword(REPLACE)?;
@@ -3761,6 +3964,7 @@ impl<'a> Parser<'a> {
             table_name: name.clone(),
             columns: Vec::new(),
             source: InsertSource::Query(transform.clone()),
+            on_conflict: None,
             returning: Vec::new(),
         };
 
@@ -3823,6 +4027,7 @@ impl<'a> Parser<'a> {
                 limit: None,
                 offset: None,
             }),
+            on_conflict: None,
             returning: Vec::new(),
         };
 
@@ -4114,11 +4319,11 @@ impl<'a> Parser<'a> {
         self.expect_keyword(ROLE)?;
         let name = self.parse_identifier()?;
         let _ = self.parse_keyword(WITH);
-        let options = self.parse_role_attributes();
+        let options = self.parse_role_attributes()?;
         Ok(Statement::CreateRole(CreateRoleStatement { name, options }))
     }
 
-    fn parse_role_attributes(&mut self) -> Vec<RoleAttribute> {
+    fn parse_role_attributes(&mut self) -> Result<Vec<RoleAttribute>, ParserError> {
         let mut options = vec![];
         loop {
             match self.parse_one_of_keywords(&[
@@ -4134,6 +4339,7 @@ impl<'a> Parser<'a> {
                 NOCREATEDB,
                 CREATEROLE,
                 NOCREATEROLE,
+                PASSWORD,
             ]) {
                 None => break,
                 Some(SUPERUSER) => options.push(RoleAttribute::SuperUser),
@@ -4148,10 +4354,18 @@ impl<'a> Parser<'a> {
                 Some(NOCREATEDB) => options.push(RoleAttribute::NoCreateDB),
                 Some(CREATEROLE) => options.push(RoleAttribute::CreateRole),
                 Some(NOCREATEROLE) => options.push(RoleAttribute::NoCreateRole),
+                Some(PASSWORD) => {
+                    let password = if self.parse_keyword(NULL) {
+                        None
+                    } else {
+                        Some(self.parse_literal_string()?)
+                    };
+                    options.push(RoleAttribute::Password(password));
+                }
                 Some(_) => unreachable!(),
             }
         }
-        options
+        Ok(options)
     }
 
     fn parse_create_secret(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -4172,7 +4386,7 @@ impl<'a> Parser<'a> {
         let name = self.parse_item_name()?;
         self.expect_keyword(AS)?;
 
-        match self.parse_one_of_keywords(&[LIST, MAP]) {
+        match self.parse_one_of_keywords(&[LIST, MAP, ENUM]) {
             Some(LIST) => {
                 self.expect_token(&Token::LParen)?;
                 let options = self.parse_comma_separated(Parser::parse_create_type_list_option)?;
@@ -4191,6 +4405,20 @@ impl<'a> Parser<'a> {
                     as_type: CreateTypeAs::Map { options },
                 }))
             }
+            Some(ENUM) => {
+                self.expect_token(&Token::LParen)?;
+                let labels = if self.consume_token(&Token::RParen) {
+                    vec![]
+                } else {
+                    let labels = self.parse_comma_separated(Parser::parse_literal_string)?;
+                    self.expect_token(&Token::RParen)?;
+                    labels
+                };
+                Ok(Statement::CreateType(CreateTypeStatement {
+                    name,
+                    as_type: CreateTypeAs::Enum { labels },
+                }))
+            }
             None => {
                 let column_defs = self.parse_composite_type_definition()?;
 
@@ -4203,6 +4431,87 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_create_function(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let if_exists = if self.parse_keyword(OR) {
+            self.expect_keyword(REPLACE)?;
+            IfExistsBehavior::Replace
+        } else {
+            IfExistsBehavior::Error
+        };
+        self.expect_keyword(FUNCTION)?;
+        let name = self.parse_item_name()?;
+
+        self.expect_token(&Token::LParen)?;
+        let parameters = if self.consume_token(&Token::RParen) {
+            vec![]
+        } else {
+            let parameters = self.parse_comma_separated(Parser::parse_function_parameter)?;
+            self.expect_token(&Token::RParen)?;
+            parameters
+        };
+
+        self.expect_keyword(RETURNS)?;
+        let returns = if self.parse_keyword(SETOF) {
+            FunctionReturnType::SetOf(self.parse_data_type()?)
+        } else if self.parse_keyword(TABLE) {
+            self.expect_token(&Token::LParen)?;
+            let columns = self.parse_comma_separated(|parser| {
+                Ok(FunctionColumn {
+                    name: parser.parse_identifier()?,
+                    data_type: parser.parse_data_type()?,
+                })
+            })?;
+            self.expect_token(&Token::RParen)?;
+            FunctionReturnType::Table(columns)
+        } else {
+            FunctionReturnType::Scalar(self.parse_data_type()?)
+        };
+
+        let language = if self.parse_keyword(LANGUAGE) {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+
+        let body = if self.parse_keyword(RETURN) {
+            FunctionBody::Return(self.parse_expr()?)
+        } else {
+            self.expect_keywords(&[BEGIN, ATOMIC])?;
+            let query = self.parse_query()?;
+            self.expect_token(&Token::Semicolon)?;
+            self.expect_keyword(END)?;
+            FunctionBody::Atomic(query)
+        };
+
+        Ok(Statement::CreateFunction(CreateFunctionStatement {
+            if_exists,
+            name,
+            parameters,
+            returns,
+            language,
+            body,
+        }))
+    }
+
+    fn parse_function_parameter(&mut self) -> Result<FunctionParameter<Raw>, ParserError> {
+        // Parameter names are optional, so a data type that is directly
+        // followed by the end of the parameter is an unnamed parameter.
+        let index = self.index;
+        if let Ok(data_type) = self.parse_data_type() {
+            if matches!(self.peek_token(), Some(Token::Comma) | Some(Token::RParen)) {
+                return Ok(FunctionParameter {
+                    name: None,
+                    data_type,
+                });
+            }
+        }
+        self.index = index;
+        Ok(FunctionParameter {
+            name: Some(self.parse_identifier()?),
+            data_type: self.parse_data_type()?,
+        })
+    }
+
     fn parse_create_type_list_option(&mut self) -> Result<CreateTypeListOption<Raw>, ParserError> {
         self.expect_keywords(&[ELEMENT, TYPE])?;
         let name = CreateTypeListOptionName::ElementType;
@@ -4638,6 +4947,7 @@ impl<'a> Parser<'a> {
             | ObjectType::Type
             | ObjectType::Secret
             | ObjectType::Connection
+            | ObjectType::Func
             | ObjectType::ContinualTask => {
                 let names = self.parse_comma_separated(|parser| {
                     Ok(UnresolvedObjectName::Item(parser.parse_item_name()?))
@@ -4653,7 +4963,7 @@ impl<'a> Parser<'a> {
                     cascade,
                 }))
             }
-            ObjectType::Func | ObjectType::Subsource => parser_err!(
+            ObjectType::Subsource => parser_err!(
                 self,
                 self.peek_prev_pos(),
                 format!("Unsupported DROP on {object_type}")
@@ -5347,7 +5657,7 @@ impl<'a> Parser<'a> {
             | ObjectType::MaterializedView
             | ObjectType::Table
             | ObjectType::ContinualTask => self.parse_alter_views(object_type),
-            ObjectType::Type => {
+            ObjectType::Type | ObjectType::Func => {
                 let if_exists = self
                     .parse_if_exists()
                     .map_parser_err(StatementKind::AlterOwner)?;
@@ -5428,7 +5738,7 @@ impl<'a> Parser<'a> {
             ObjectType::NetworkPolicy => self
                 .parse_alter_network_policy()
                 .map_parser_err(StatementKind::AlterNetworkPolicy),
-            ObjectType::Func | ObjectType::Subsource => parser_err!(
+            ObjectType::Subsource => parser_err!(
                 self,
                 self.peek_prev_pos(),
                 format!("Unsupported ALTER on {object_type}")
@@ -6088,7 +6398,7 @@ impl<'a> Parser<'a> {
             }
             Some(WITH) | None => {
                 let _ = self.parse_keyword(WITH);
-                let attrs = self.parse_role_attributes();
+                let attrs = self.parse_role_attributes()?;
                 AlterRoleOption::Attributes(attrs)
             }
             Some(k) => unreachable!("unmatched keyword: {k}"),
@@ -6170,10 +6480,12 @@ impl<'a> Parser<'a> {
     ) -> Result<Statement<Raw>, ParserStatementError> {
         let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
         let name = self.parse_item_name().map_no_statement_parser_err()?;
-        let keywords = if object_type == ObjectType::Table {
-            [SET, RENAME, OWNER, RESET, ADD].as_slice()
-        } else {
-            [SET, RENAME, OWNER, RESET].as_slice()
+        let keywords = match object_type {
+            ObjectType::Table => [SET, RENAME, OWNER, RESET, ADD].as_slice(),
+            ObjectType::View | ObjectType::MaterializedView => {
+                [SET, RENAME, OWNER, RESET, AS].as_slice()
+            }
+            _ => [SET, RENAME, OWNER, RESET].as_slice(),
         };
 
         let action = self
@@ -6264,6 +6576,17 @@ impl<'a> Parser<'a> {
                     },
                 ))
             }
+            AS => {
+                let query = self
+                    .parse_query()
+                    .map_parser_err(StatementKind::AlterViewQuery)?;
+                Ok(Statement::AlterViewQuery(AlterViewQueryStatement {
+                    object_type,
+                    if_exists,
+                    name,
+                    query,
+                }))
+            }
             _ => unreachable!(),
         }
     }
@@ -6454,9 +6777,22 @@ impl<'a> Parser<'a> {
 
     fn parse_copy_option(&mut self) -> Result<CopyOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[
-            FORMAT, DELIMITER, NULL, ESCAPE, QUOTE, HEADER, AWS, MAX, FILES, PATTERN,
+            FORMAT, DELIMITER, NULL, ESCAPE, QUOTE, HEADER, AWS, MAX, FILES, PATTERN, JSON,
         ])? {
-            FORMAT => CopyOptionName::Format,
+            FORMAT => {
+                // `JSON ARRAY` is the only multi-word format, so we fold it
+                // into a single identifier that the planner recognizes.
+                let _ = self.consume_token(&Token::Eq);
+                if self.parse_keywords(&[JSON, ARRAY]) {
+                    return Ok(CopyOption {
+                        name: CopyOptionName::Format,
+                        value: Some(WithOptionValue::UnresolvedItemName(UnresolvedItemName(
+                            vec![Ident::new_unchecked("json array")],
+                        ))),
+                    });
+                }
+                CopyOptionName::Format
+            }
             DELIMITER => CopyOptionName::Delimiter,
             NULL => CopyOptionName::Null,
             ESCAPE => CopyOptionName::Escape,
@@ -6475,6 +6811,10 @@ impl<'a> Parser<'a> {
             }
             FILES => CopyOptionName::Files,
             PATTERN => CopyOptionName::Pattern,
+            JSON => {
+                self.expect_keyword(PATHS)?;
+                CopyOptionName::JsonPaths
+            }
             _ => unreachable!(),
         };
         Ok(CopyOption {
@@ -7853,6 +8193,12 @@ impl<'a> Parser<'a> {
             Ok(ShowStatement::ShowCreateIndex(ShowCreateIndexStatement {
                 index_name: self.parse_raw_name()?,
             }))
+        } else if self.parse_keywords(&[CREATE, FUNCTION]) {
+            Ok(ShowStatement::ShowCreateFunction(
+                ShowCreateFunctionStatement {
+                    function_name: self.parse_raw_name()?,
+                },
+            ))
         } else if self.parse_keywords(&[CREATE, CONNECTION]) {
             Ok(ShowStatement::ShowCreateConnection(
                 ShowCreateConnectionStatement {
@@ -8023,6 +8369,8 @@ impl<'a> Parser<'a> {
                 return self.parse_derived_table_factor(Lateral);
             } else if self.parse_keywords(&[ROWS, FROM]) {
                 return self.parse_rows_from();
+            } else if self.peek_json_table() {
+                return self.parse_json_table();
             } else {
                 let name = self.parse_raw_name()?;
                 self.expect_token(&Token::LParen)?;
@@ -8095,6 +8443,8 @@ impl<'a> Parser<'a> {
             })
         } else if self.parse_keywords(&[ROWS, FROM]) {
             Ok(self.parse_rows_from()?)
+        } else if self.peek_json_table() {
+            self.parse_json_table()
         } else {
             let name = self.parse_raw_name()?;
             if self.consume_token(&Token::LParen) {
@@ -8121,6 +8471,73 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Reports whether the next tokens start a `JSON_TABLE(...)` table factor.
+    ///
+    /// `JSON_TABLE` is not a keyword, so that it remains usable as an
+    /// identifier.
+    fn peek_json_table(&self) -> bool {
+        matches!(self.peek_token(), Some(Token::Ident(id)) if id.as_str() == "json_table")
+            && matches!(self.peek_nth_token(1), Some(Token::LParen))
+    }
+
+    fn parse_json_table(&mut self) -> Result<TableFactor<Raw>, ParserError> {
+        self.next_token();
+        self.expect_token(&Token::LParen)?;
+        let context = self.parse_expr()?;
+        self.expect_token(&Token::Comma)?;
+        let path = self.parse_literal_string()?;
+        let passing = if self.parse_keyword(PASSING) {
+            self.parse_comma_separated(|parser| {
+                let expr = parser.parse_expr()?;
+                parser.expect_keyword(AS)?;
+                let name = parser.parse_identifier()?;
+                Ok(JsonTablePassing { expr, name })
+            })?
+        } else {
+            vec![]
+        };
+        self.expect_keyword(COLUMNS)?;
+        self.expect_token(&Token::LParen)?;
+        let columns = self.parse_comma_separated(Parser::parse_json_table_column)?;
+        self.expect_token(&Token::RParen)?;
+        self.expect_token(&Token::RParen)?;
+        let alias = self.parse_optional_table_alias()?;
+        Ok(TableFactor::JsonTable {
+            context,
+            path,
+            passing,
+            columns,
+            alias,
+        })
+    }
+
+    fn parse_json_table_column(&mut self) -> Result<JsonTableColumn<Raw>, ParserError> {
+        let name = self.parse_identifier()?;
+        if self.parse_keywords(&[FOR, ORDINALITY]) {
+            return Ok(JsonTableColumn::Ordinality { name });
+        }
+        let data_type = self.parse_data_type()?;
+        let exists = self.parse_keyword(EXISTS);
+        let path = if self.parse_keyword(PATH) {
+            Some(self.parse_literal_string()?)
+        } else {
+            None
+        };
+        Ok(if exists {
+            JsonTableColumn::Exists {
+                name,
+                data_type,
+                path,
+            }
+        } else {
+            JsonTableColumn::Value {
+                name,
+                data_type,
+                path,
+            }
+        })
+    }
+
     fn parse_rows_from(&mut self) -> Result<TableFactor<Raw>, ParserError> {
         self.expect_token(&Token::LParen)?;
         let functions = self.parse_comma_separated(Parser::parse_named_function)?;
@@ -8186,15 +8603,46 @@ impl<'a> Parser<'a> {
         } else {
             InsertSource::Query(self.parse_query()?)
         };
+        let on_conflict = if self.parse_keywords(&[ON, CONFLICT]) {
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
         let returning = self.parse_returning()?;
         Ok(Statement::Insert(InsertStatement {
             table_name,
             columns,
             source,
+            on_conflict,
             returning,
         }))
     }
 
+    /// Parses the remainder of an `ON CONFLICT` clause, assuming that the
+    /// `ON CONFLICT` tokens have already been consumed.
+    fn parse_on_conflict(&mut self) -> Result<OnConflict<Raw>, ParserError> {
+        let columns = self.parse_parenthesized_column_list(Optional)?;
+        self.expect_keyword(DO)?;
+        let action = match self.expect_one_of_keywords(&[NOTHING, UPDATE])? {
+            NOTHING => OnConflictAction::DoNothing,
+            UPDATE => {
+                self.expect_keyword(SET)?;
+                let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+                let selection = if self.parse_keyword(WHERE) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                OnConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                }
+            }
+            _ => unreachable!(),
+        };
+        Ok(OnConflict { columns, action })
+    }
+
     fn parse_returning(&mut self) -> Result<Vec<SelectItem<Raw>>, ParserError> {
         Ok(if self.parse_keyword(RETURNING) {
             self.parse_comma_separated(Parser::parse_select_item)?
@@ -8229,6 +8677,97 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    /// Parses a `MERGE` statement, assuming that the `MERGE` token has already
+    /// been consumed.
+    fn parse_merge(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(INTO)?;
+        let table_name = RawItemName::Name(self.parse_item_name()?);
+        // As in UPDATE, the alias doesn't support columns.
+        let alias = self.parse_optional_alias(Keyword::is_reserved_in_table_alias)?;
+        let alias = alias.map(|name| TableAlias {
+            name,
+            columns: Vec::new(),
+            strict: false,
+        });
+
+        self.expect_keyword(USING)?;
+        let using = self.parse_table_and_joins()?;
+        self.expect_keyword(ON)?;
+        let on = self.parse_expr()?;
+
+        let mut clauses = vec![];
+        while self.parse_keyword(WHEN) {
+            clauses.push(self.parse_merge_clause()?);
+        }
+        if clauses.is_empty() {
+            return self.expected(self.peek_pos(), WHEN, self.peek_token());
+        }
+
+        Ok(Statement::Merge(MergeStatement {
+            table_name,
+            alias,
+            using,
+            on,
+            clauses,
+        }))
+    }
+
+    /// Parses a `[NOT] MATCHED ... THEN <action>` clause of a `MERGE`
+    /// statement, assuming that the `WHEN` token has already been consumed.
+    fn parse_merge_clause(&mut self) -> Result<MergeClause<Raw>, ParserError> {
+        let matched = !self.parse_keyword(NOT);
+        self.expect_keyword(MATCHED)?;
+        let condition = if self.parse_keyword(AND) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        self.expect_keyword(THEN)?;
+
+        let action = if matched {
+            match self.expect_one_of_keywords(&[UPDATE, DELETE, DO])? {
+                UPDATE => {
+                    self.expect_keyword(SET)?;
+                    let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+                    MergeAction::Update { assignments }
+                }
+                DELETE => MergeAction::Delete,
+                DO => {
+                    self.expect_keyword(NOTHING)?;
+                    MergeAction::DoNothing
+                }
+                _ => unreachable!(),
+            }
+        } else {
+            match self.expect_one_of_keywords(&[INSERT, DO])? {
+                INSERT => {
+                    let columns = self.parse_parenthesized_column_list(Optional)?;
+                    let values = if self.parse_keywords(&[DEFAULT, VALUES]) {
+                        vec![]
+                    } else {
+                        self.expect_keyword(VALUES)?;
+                        self.expect_token(&Token::LParen)?;
+                        let values = self.parse_comma_separated(Parser::parse_expr)?;
+                        self.expect_token(&Token::RParen)?;
+                        values
+                    };
+                    MergeAction::Insert { columns, values }
+                }
+                DO => {
+                    self.expect_keyword(NOTHING)?;
+                    MergeAction::DoNothing
+                }
+                _ => unreachable!(),
+            }
+        };
+
+        Ok(MergeClause {
+            matched,
+            condition,
+            action,
+        })
+    }
+
     /// Parse a `var = expr` assignment, used in an UPDATE statement
     fn parse_assignment(&mut self) -> Result<Assignment<Raw>, ParserError> {
         let id = self.parse_identifier()?;
@@ -8840,7 +9379,8 @@ impl<'a> Parser<'a> {
             Statement::Select(_)
                 | Statement::Insert(_)
                 | Statement::Delete(_)
-                | Statement::Update(_),
+                | Statement::Update(_)
+                | Statement::Merge(_),
         ) {
             return parser_err!(self, pos, "unpreparable statement").map_no_statement_parser_err();
         }
@@ -9119,7 +9659,6 @@ impl<'a> Parser<'a> {
             | ObjectType::Index
             | ObjectType::ClusterReplica
             | ObjectType::Role
-            | ObjectType::Func
             | ObjectType::Subsource => {
                 parser_err!(
                     self,
@@ -9134,6 +9673,7 @@ impl<'a> Parser<'a> {
             | ObjectType::Connection
             | ObjectType::Database
             | ObjectType::Schema
+            | ObjectType::Func
             | ObjectType::NetworkPolicy => Ok(object_type),
         }
     }
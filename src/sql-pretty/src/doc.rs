@@ -295,6 +295,9 @@ pub(crate) fn doc_insert<T: AstInfo>(v: &InsertStatement<T>) -> RcDoc {
         _ => doc_display(&v.source, "insert source"),
     };
     let mut doc = intersperse_line_nest([intersperse_line_nest(first), sources]);
+    if let Some(on_conflict) = &v.on_conflict {
+        doc = nest(doc, doc_display(on_conflict, "on conflict"));
+    }
     if !v.returning.is_empty() {
         doc = nest(
             doc,
@@ -710,6 +713,7 @@ pub fn doc_expr<T: AstInfo>(v: &Expr<T>) -> RcDoc {
 fn doc_function<T: AstInfo>(v: &Function<T>) -> RcDoc {
     match &v.args {
         FunctionArgs::Star => doc_display_pass(v),
+        FunctionArgs::WithinGroup { .. } => doc_display(v, "function within group"),
         FunctionArgs::Args { args, order_by } => {
             if args.is_empty() {
                 // Nullary, don't allow newline between parens, so just delegate.
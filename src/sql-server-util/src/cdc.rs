@@ -0,0 +1,373 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Replicating changes from SQL Server using [Change Data Capture].
+//!
+//! When CDC is enabled for a table SQL Server's capture job reads the
+//! transaction log and records every change to the table in a "change table",
+//! which we can query through the `cdc.fn_cdc_get_all_changes_<capture_instance>`
+//! table valued function. Every change is tagged with the [`Lsn`] of the
+//! transaction that committed it, which we use as the timestamp of the change.
+//!
+//! [Change Data Capture]: https://learn.microsoft.com/en-us/sql/relational-databases/track-changes/about-change-data-capture-sql-server
+
+use std::fmt;
+
+use futures::{Stream, StreamExt};
+use mz_repr::Diff;
+use serde::{Deserialize, Serialize};
+use timely::order::{PartialOrder, TotalOrder};
+use timely::progress::timestamp::{PathSummary, Refines, Timestamp};
+
+use crate::desc::SqlServerTableDesc;
+use crate::{quote_identifier, Client, SqlServerError};
+
+/// A Log Sequence Number, uniquely identifies a record in the SQL Server
+/// transaction log.
+///
+/// An LSN is 10 bytes long and made up of the ID of the Virtual Log File
+/// (VLF), the ID of the log block within the VLF, and the ID of the record
+/// within the log block.
+///
+/// See: <https://learn.microsoft.com/en-us/sql/relational-databases/sql-server-transaction-log-architecture-and-management-guide>
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Lsn {
+    /// Virtual Log File ID.
+    pub vlf_id: u32,
+    /// Log block ID.
+    pub block_id: u32,
+    /// Log record ID.
+    pub record_id: u16,
+}
+
+impl Lsn {
+    /// The size of an [`Lsn`] when encoded as bytes.
+    pub const SIZE: usize = 10;
+
+    /// The largest possible [`Lsn`].
+    pub const MAX: Lsn = Lsn {
+        vlf_id: u32::MAX,
+        block_id: u32::MAX,
+        record_id: u16::MAX,
+    };
+
+    /// Interprets the `binary(10)` representation of an LSN returned by SQL Server.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, SqlServerError> {
+        let Ok(bytes) = <[u8; Self::SIZE]>::try_from(bytes) else {
+            return Err(SqlServerError::InvalidLsn(bytes.to_vec()));
+        };
+        Ok(Self::from_bytes(bytes))
+    }
+
+    /// Interprets the `binary(10)` representation of an LSN.
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        let [v0, v1, v2, v3, b0, b1, b2, b3, r0, r1] = bytes;
+        Lsn {
+            vlf_id: u32::from_be_bytes([v0, v1, v2, v3]),
+            block_id: u32::from_be_bytes([b0, b1, b2, b3]),
+            record_id: u16::from_be_bytes([r0, r1]),
+        }
+    }
+
+    /// Interprets the `numeric(25,0)` representation of an LSN returned by
+    /// some of SQL Server's dynamic management views, e.g.
+    /// `sys.dm_tran_database_transactions`.
+    pub fn try_from_numeric(value: i128) -> Result<Self, SqlServerError> {
+        let invalid = || SqlServerError::InvalidLsn(value.to_be_bytes().to_vec());
+        let value = u128::try_from(value).map_err(|_| invalid())?;
+        let vlf_id = u32::try_from(value / 1_000_000_000_000_000).map_err(|_| invalid())?;
+        let block_id = u32::try_from((value / 100_000) % 10_000_000_000).map_err(|_| invalid())?;
+        let record_id = u16::try_from(value % 100_000).map_err(|_| invalid())?;
+        Ok(Lsn {
+            vlf_id,
+            block_id,
+            record_id,
+        })
+    }
+
+    /// Returns the `binary(10)` representation of this LSN.
+    pub fn as_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..4].copy_from_slice(&self.vlf_id.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.block_id.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.record_id.to_be_bytes());
+        bytes
+    }
+
+    /// Returns the next largest possible [`Lsn`], saturating at [`Lsn::MAX`].
+    ///
+    /// Note: the returned [`Lsn`] might not exist upstream, it is only used to
+    /// express "everything after `self`", e.g. in a frontier.
+    pub fn increment(&self) -> Lsn {
+        let (record_id, carry) = self.record_id.overflowing_add(1);
+        let (block_id, carry) = if carry {
+            self.block_id.overflowing_add(1)
+        } else {
+            (self.block_id, false)
+        };
+        let vlf_id = if carry {
+            match self.vlf_id.checked_add(1) {
+                Some(vlf_id) => vlf_id,
+                None => return Lsn::MAX,
+            }
+        } else {
+            self.vlf_id
+        };
+        Lsn {
+            vlf_id,
+            block_id,
+            record_id,
+        }
+    }
+}
+
+impl fmt::Display for Lsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Matches the format SQL Server uses in e.g. `fn_dblog`.
+        write!(
+            f,
+            "{:08x}:{:08x}:{:04x}",
+            self.vlf_id, self.block_id, self.record_id
+        )
+    }
+}
+
+impl Timestamp for Lsn {
+    // No need to describe complex summaries.
+    type Summary = ();
+
+    fn minimum() -> Self {
+        Lsn::default()
+    }
+}
+
+impl TotalOrder for Lsn {}
+
+impl PartialOrder for Lsn {
+    fn less_equal(&self, other: &Self) -> bool {
+        self <= other
+    }
+}
+
+impl PathSummary<Lsn> for () {
+    fn results_in(&self, src: &Lsn) -> Option<Lsn> {
+        Some(*src)
+    }
+
+    fn followed_by(&self, _other: &Self) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl Refines<()> for Lsn {
+    fn to_inner(_other: ()) -> Self {
+        Self::minimum()
+    }
+
+    fn to_outer(self) -> () {}
+
+    fn summarize(_path: Self::Summary) -> <() as Timestamp>::Summary {}
+}
+
+impl columnation::Columnation for Lsn {
+    type InnerRegion = columnation::CopyRegion<Lsn>;
+}
+
+/// The kind of change recorded in the `__$operation` column of a change table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Delete,
+    Insert,
+    UpdateOld,
+    UpdateNew,
+}
+
+impl Operation {
+    fn try_from_sql_server(val: i32) -> Result<Self, SqlServerError> {
+        let op = match val {
+            1 => Operation::Delete,
+            2 => Operation::Insert,
+            3 => Operation::UpdateOld,
+            4 => Operation::UpdateNew,
+            other => {
+                return Err(SqlServerError::Generic(anyhow::anyhow!(
+                    "unknown CDC operation {other}"
+                )))
+            }
+        };
+        Ok(op)
+    }
+
+    /// The [`Diff`] this kind of change should be emitted with.
+    pub fn diff(&self) -> Diff {
+        match self {
+            Operation::Delete | Operation::UpdateOld => -1,
+            Operation::Insert | Operation::UpdateNew => 1,
+        }
+    }
+}
+
+/// A single change read from a CDC change table.
+#[derive(Debug)]
+pub struct Change {
+    /// [`Lsn`] of the transaction that committed this change.
+    pub lsn: Lsn,
+    /// The kind of change.
+    pub operation: Operation,
+    /// The changed row, columns start at [`CHANGE_METADATA_COLUMNS`] and match
+    /// [`SqlServerTableDesc::select_list`].
+    pub row: tiberius::Row,
+}
+
+/// The number of metadata columns that precede the table's columns in a
+/// [`Change::row`].
+pub const CHANGE_METADATA_COLUMNS: usize = 3;
+
+/// Returns the largest [`Lsn`] that the CDC capture job has processed for the
+/// current database.
+///
+/// Returns `None` if the capture job has not processed any changes yet.
+pub async fn get_max_lsn(client: &mut Client) -> Result<Option<Lsn>, SqlServerError> {
+    let rows = client
+        .simple_query("SELECT sys.fn_cdc_get_max_lsn()")
+        .await?;
+    single_lsn(&rows)
+}
+
+/// Returns the smallest [`Lsn`] that is still available in the change table of
+/// `capture_instance`, changes before this have been cleaned up.
+///
+/// Returns `None` if the capture instance does not exist.
+pub async fn get_min_lsn(
+    client: &mut Client,
+    capture_instance: &str,
+) -> Result<Option<Lsn>, SqlServerError> {
+    let rows = client
+        .query("SELECT sys.fn_cdc_get_min_lsn(@P1)", &[&capture_instance])
+        .await?;
+    single_lsn(&rows)
+}
+
+fn single_lsn(rows: &[tiberius::Row]) -> Result<Option<Lsn>, SqlServerError> {
+    match rows {
+        [row] => {
+            let bytes: Option<&[u8]> = row.try_get(0)?;
+            match bytes {
+                // SQL Server returns an all zero LSN instead of NULL in some
+                // cases, e.g. for a capture instance that doesn't exist.
+                Some(bytes) => {
+                    let lsn = Lsn::try_from_bytes(bytes)?;
+                    Ok((lsn != Lsn::minimum()).then_some(lsn))
+                }
+                None => Ok(None),
+            }
+        }
+        other => Err(SqlServerError::Generic(anyhow::anyhow!(
+            "expected one row, got {}",
+            other.len()
+        ))),
+    }
+}
+
+/// Returns a stream of all changes recorded for `capture_instance` whose
+/// [`Lsn`] is in the range `[from, to]`, ordered by the order in which they
+/// were applied upstream.
+///
+/// `from` must not be less than the minimum [`Lsn`] of the capture instance,
+/// see [`get_min_lsn`].
+pub fn get_changes<'a>(
+    client: &'a mut Client,
+    capture_instance: &str,
+    desc: &SqlServerTableDesc,
+    from: Lsn,
+    to: Lsn,
+) -> impl Stream<Item = Result<Change, SqlServerError>> + 'a {
+    let function = quote_identifier(&format!("fn_cdc_get_all_changes_{capture_instance}"));
+    let select_list = desc.select_list();
+    let select_list = if select_list.is_empty() {
+        String::new()
+    } else {
+        format!(", {select_list}")
+    };
+    let query = format!(
+        "SELECT [__$start_lsn], [__$seqval], [__$operation]{select_list} \
+         FROM [cdc].{function}(@P1, @P2, N'all update old') \
+         ORDER BY [__$start_lsn], [__$seqval], [__$operation]"
+    );
+    let from = from.as_bytes();
+    let to = to.as_bytes();
+
+    client
+        .query_streaming(query, &[&&from[..], &&to[..]])
+        .map(|row| {
+            let row = row?;
+            let lsn: &[u8] = row
+                .try_get(0)?
+                .ok_or_else(|| anyhow::anyhow!("missing __$start_lsn"))?;
+            let lsn = Lsn::try_from_bytes(lsn)?;
+            let operation: i32 = row
+                .try_get(2)?
+                .ok_or_else(|| anyhow::anyhow!("missing __$operation"))?;
+            let operation = Operation::try_from_sql_server(operation)?;
+            Ok::<_, SqlServerError>(Change {
+                lsn,
+                operation,
+                row,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_lsn_roundtrip() {
+        let lsn = Lsn {
+            vlf_id: 0x2a,
+            block_id: 0x1f8,
+            record_id: 3,
+        };
+        assert_eq!(Lsn::from_bytes(lsn.as_bytes()), lsn);
+        assert_eq!(lsn.to_string(), "0000002a:000001f8:0003");
+        assert_eq!(
+            Lsn::try_from_numeric(42_000_000_050_400_003).unwrap(),
+            Lsn {
+                vlf_id: 42,
+                block_id: 504,
+                record_id: 3,
+            }
+        );
+        assert!(Lsn::try_from_bytes(&[0; 4]).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_lsn_ordering() {
+        let a = Lsn {
+            vlf_id: 1,
+            block_id: u32::MAX,
+            record_id: u16::MAX,
+        };
+        let b = a.increment();
+        assert_eq!(
+            b,
+            Lsn {
+                vlf_id: 2,
+                block_id: 0,
+                record_id: 0,
+            }
+        );
+        assert!(a < b);
+        // Byte-wise ordering must match the ordering of the struct.
+        assert!(a.as_bytes() < b.as_bytes());
+        assert_eq!(Lsn::MAX.increment(), Lsn::MAX);
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Configuration for connecting to a SQL Server instance, optionally via a tunnel.
+
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+
+use mz_ore::future::InTask;
+use mz_repr::CatalogItemId;
+use mz_ssh_util::tunnel::{SshTimeoutConfig, SshTunnelConfig};
+use mz_ssh_util::tunnel_manager::SshTunnelManager;
+
+use crate::SqlServerError;
+
+/// Configures an optional tunnel for use when connecting to a SQL Server
+/// instance.
+#[derive(Debug, Clone)]
+pub enum TunnelConfig {
+    /// Establish a direct TCP connection to the database host.
+    /// If `resolved_ips` is not None, the provided IPs will be used
+    /// rather than resolving the hostname.
+    Direct {
+        resolved_ips: Option<BTreeSet<IpAddr>>,
+    },
+    /// Establish a TCP connection to the database via an SSH tunnel.
+    Ssh {
+        config: SshTunnelConfig,
+        manager: SshTunnelManager,
+        timeout: SshTimeoutConfig,
+    },
+    /// Establish a TCP connection to the database via an AWS PrivateLink
+    /// service.
+    AwsPrivatelink {
+        /// The ID of the AWS PrivateLink service.
+        connection_id: CatalogItemId,
+    },
+}
+
+/// Configuration for SQL Server connections.
+///
+/// This wraps [`tiberius::Config`] to allow the configuration of a tunnel via
+/// a [`TunnelConfig`].
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub(crate) inner: tiberius::Config,
+    pub(crate) host: String,
+    pub(crate) port: u16,
+    pub(crate) tunnel: TunnelConfig,
+    pub(crate) in_task: InTask,
+}
+
+impl Config {
+    /// Creates a new [`Config`] that authenticates as `user` to `database` on
+    /// the SQL Server instance at `host` and `port`.
+    ///
+    /// Note: `host` and `port` always describe the upstream instance, even if
+    /// the connection is routed through a tunnel.
+    pub fn new(
+        host: String,
+        port: u16,
+        database: String,
+        user: String,
+        password: String,
+        tunnel: TunnelConfig,
+        in_task: InTask,
+    ) -> Self {
+        let mut inner = tiberius::Config::new();
+        inner.host(&host);
+        inner.port(port);
+        inner.database(database);
+        inner.application_name("materialize");
+        inner.authentication(tiberius::AuthMethod::sql_server(user, password));
+        // TODO(sql_server2): Support TLS.
+        inner.encryption(tiberius::EncryptionLevel::NotSupported);
+
+        Config {
+            inner,
+            host,
+            port,
+            tunnel,
+            in_task,
+        }
+    }
+
+    /// Creates a [`Config`] from an [ADO .NET connection string] that connects
+    /// directly to the specified host.
+    ///
+    /// [ADO .NET connection string]: https://learn.microsoft.com/en-us/dotnet/framework/data/adonet/connection-strings
+    pub fn from_ado_string(s: &str) -> Result<Self, SqlServerError> {
+        let inner = tiberius::Config::from_ado_string(s)?;
+        let addr = inner.get_addr();
+        let (host, port) = match addr.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port.parse().map_err(|_| {
+                    SqlServerError::InvalidClientConfig(format!("invalid port in '{addr}'"))
+                })?;
+                (host.to_string(), port)
+            }
+            None => (addr.clone(), 1433),
+        };
+        Ok(Config {
+            inner,
+            host,
+            port,
+            tunnel: TunnelConfig::Direct { resolved_ips: None },
+            in_task: InTask::No,
+        })
+    }
+
+    /// The upstream host this [`Config`] connects to.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The upstream port this [`Config`] connects to.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
@@ -0,0 +1,552 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Descriptions of SQL Server tables and the decoding of their rows.
+//!
+//! A [`SqlServerTableDesc`] is created from the metadata we read upstream
+//! during purification (see [`crate::inspect`]) and is durably recorded as part
+//! of a source export, so we can decode rows consistently for the lifetime of
+//! the export.
+
+use std::collections::BTreeSet;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, SubsecRound};
+use itertools::Itertools;
+use mz_ore::cast::CastFrom;
+use mz_proto::{ProtoType, RustType, TryFromProtoError};
+use mz_repr::adt::date::Date;
+use mz_repr::adt::numeric::{cx_datum, NumericMaxScale};
+use mz_repr::adt::timestamp::{CheckedTimestamp, TimestampPrecision};
+use mz_repr::{ColumnType, Datum, RowPacker, ScalarType};
+use proptest::prelude::any;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use tiberius::ColumnData;
+
+use crate::{quote_identifier, SqlServerError, UnsupportedDataType};
+
+include!(concat!(env!("OUT_DIR"), "/mz_sql_server_util.rs"));
+
+/// Raw metadata about a table in SQL Server, as read from the system catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlServerTableRaw {
+    /// Name of the schema the table belongs to.
+    pub schema_name: String,
+    /// Name of the table.
+    pub name: String,
+    /// Name of the CDC capture instance that tracks this table.
+    pub capture_instance: String,
+    /// Columns tracked by the capture instance, in ordinal order.
+    pub columns: Vec<SqlServerColumnRaw>,
+}
+
+/// Raw metadata about a column in SQL Server, as read from the system catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlServerColumnRaw {
+    /// Name of the column.
+    pub name: String,
+    /// Name of the column's data type, e.g. `nvarchar`.
+    pub data_type: String,
+    /// Whether the column is nullable.
+    pub is_nullable: bool,
+    /// Maximum length in bytes of the column, `-1` for `MAX` types.
+    pub max_length: i16,
+    /// Precision of the column, only meaningful for numeric types.
+    pub precision: u8,
+    /// Scale of the column, only meaningful for numeric and temporal types.
+    pub scale: u8,
+    /// Whether the column is part of the table's primary key.
+    pub primary_key: bool,
+}
+
+/// Materialize's description of a table in SQL Server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerTableDesc {
+    /// Name of the schema the table belongs to.
+    pub schema_name: String,
+    /// Name of the table.
+    pub name: String,
+    /// Columns of the table, in the order they are defined upstream.
+    #[proptest(strategy = "proptest::collection::vec(any::<SqlServerColumnDesc>(), 0..4)")]
+    pub columns: Vec<SqlServerColumnDesc>,
+}
+
+impl SqlServerTableDesc {
+    /// Creates a [`SqlServerTableDesc`] from the raw upstream metadata.
+    ///
+    /// Columns named in `text_columns` are ingested as `text` regardless of
+    /// their upstream type, and columns named in `exclude_columns` are not
+    /// ingested at all.
+    pub fn new(
+        raw: &SqlServerTableRaw,
+        text_columns: &BTreeSet<&str>,
+        exclude_columns: &BTreeSet<&str>,
+    ) -> Result<Self, SqlServerError> {
+        let qualified_table_name = format!("{}.{}", raw.schema_name, raw.name);
+        let mut unsupported = vec![];
+        let mut columns = Vec::with_capacity(raw.columns.len());
+
+        for col in &raw.columns {
+            let column_type = if exclude_columns.contains(col.name.as_str()) {
+                None
+            } else if text_columns.contains(col.name.as_str()) {
+                Some(ScalarType::String.nullable(col.is_nullable))
+            } else {
+                match parse_data_type(col) {
+                    Some(scalar_type) => Some(scalar_type.nullable(col.is_nullable)),
+                    None => {
+                        unsupported.push(UnsupportedDataType {
+                            column_type: col.data_type.clone(),
+                            qualified_table_name: qualified_table_name.clone(),
+                            column_name: col.name.clone(),
+                        });
+                        continue;
+                    }
+                }
+            };
+            columns.push(SqlServerColumnDesc {
+                name: col.name.clone(),
+                column_type,
+                data_type: col.data_type.clone(),
+                primary_key: col.primary_key,
+            });
+        }
+
+        if !unsupported.is_empty() {
+            return Err(SqlServerError::UnsupportedDataTypes {
+                columns: unsupported,
+            });
+        }
+
+        Ok(SqlServerTableDesc {
+            schema_name: raw.schema_name.clone(),
+            name: raw.name.clone(),
+            columns,
+        })
+    }
+
+    /// Returns the comma separated list of expressions that should be selected
+    /// from the upstream table, or a CDC change table, to produce values that
+    /// can be decoded with [`SqlServerTableDesc::decode_row`].
+    pub fn select_list(&self) -> String {
+        self.columns
+            .iter()
+            .filter_map(|col| {
+                let column_type = col.column_type.as_ref()?;
+                let ident = quote_identifier(&col.name);
+                let expr = match column_type.scalar_type {
+                    // Text columns, and types we represent as text, are cast
+                    // upstream so we only ever receive strings for them.
+                    ScalarType::String if !is_string_type(&col.data_type) => {
+                        format!("CAST({ident} AS NVARCHAR(MAX)) AS {ident}")
+                    }
+                    _ => ident,
+                };
+                Some(expr)
+            })
+            .join(", ")
+    }
+
+    /// Decodes the values of `row`, starting at column `offset`, into `packer`.
+    ///
+    /// The columns of `row` must match those produced by
+    /// [`SqlServerTableDesc::select_list`].
+    pub fn decode_row(
+        &self,
+        row: &tiberius::Row,
+        offset: usize,
+        packer: &mut RowPacker,
+    ) -> Result<(), SqlServerError> {
+        let mut values = row.cells().skip(offset).map(|(_col, data)| data);
+        for col in &self.columns {
+            let Some(column_type) = &col.column_type else {
+                continue;
+            };
+            let value = values
+                .next()
+                .ok_or_else(|| SqlServerError::ValueDecodeError {
+                    column_name: col.name.clone(),
+                    qualified_table_name: format!("{}.{}", self.schema_name, self.name),
+                    error: "missing value".to_string(),
+                })?;
+            decode_value(column_type, value, packer).map_err(|err| {
+                SqlServerError::ValueDecodeError {
+                    column_name: col.name.clone(),
+                    qualified_table_name: format!("{}.{}", self.schema_name, self.name),
+                    error: err.to_string(),
+                }
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl RustType<ProtoSqlServerTableDesc> for SqlServerTableDesc {
+    fn into_proto(&self) -> ProtoSqlServerTableDesc {
+        ProtoSqlServerTableDesc {
+            schema_name: self.schema_name.clone(),
+            name: self.name.clone(),
+            columns: self.columns.iter().map(|c| c.into_proto()).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerTableDesc) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerTableDesc {
+            schema_name: proto.schema_name,
+            name: proto.name,
+            columns: proto
+                .columns
+                .into_iter()
+                .map(SqlServerColumnDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Materialize's description of a column in a SQL Server table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerColumnDesc {
+    /// Name of the column.
+    pub name: String,
+    /// The intended data type of this column within Materialize.
+    ///
+    /// If this is `None`, the column is intended to be excluded from
+    /// Materialize.
+    pub column_type: Option<ColumnType>,
+    /// Name of the column's data type upstream, e.g. `nvarchar`.
+    pub data_type: String,
+    /// Whether the column is part of the table's primary key.
+    pub primary_key: bool,
+}
+
+impl RustType<ProtoSqlServerColumnDesc> for SqlServerColumnDesc {
+    fn into_proto(&self) -> ProtoSqlServerColumnDesc {
+        ProtoSqlServerColumnDesc {
+            name: self.name.clone(),
+            column_type: self.column_type.into_proto(),
+            data_type: self.data_type.clone(),
+            primary_key: self.primary_key,
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerColumnDesc) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerColumnDesc {
+            name: proto.name,
+            column_type: proto.column_type.into_rust()?,
+            data_type: proto.data_type,
+            primary_key: proto.primary_key,
+        })
+    }
+}
+
+/// Returns whether values of the upstream `data_type` are received as strings.
+fn is_string_type(data_type: &str) -> bool {
+    matches!(
+        data_type,
+        "char" | "varchar" | "nchar" | "nvarchar" | "text" | "ntext" | "sysname"
+    )
+}
+
+/// Maps an upstream SQL Server column to the [`ScalarType`] we represent it
+/// as, returning `None` if the type is not supported.
+fn parse_data_type(col: &SqlServerColumnRaw) -> Option<ScalarType> {
+    let scalar_type = match col.data_type.as_str() {
+        "bit" => ScalarType::Bool,
+        // `tinyint` is an unsigned 8-bit integer in SQL Server.
+        "tinyint" | "smallint" => ScalarType::Int16,
+        "int" => ScalarType::Int32,
+        "bigint" => ScalarType::Int64,
+        "real" => ScalarType::Float32,
+        "float" => ScalarType::Float64,
+        "decimal" | "numeric" => ScalarType::Numeric {
+            max_scale: Some(NumericMaxScale::try_from(i64::from(col.scale)).ok()?),
+        },
+        "char" | "varchar" | "nchar" | "nvarchar" | "text" | "ntext" | "sysname" | "xml" => {
+            ScalarType::String
+        }
+        "binary" | "varbinary" | "image" => ScalarType::Bytes,
+        "uniqueidentifier" => ScalarType::Uuid,
+        "date" => ScalarType::Date,
+        "time" => ScalarType::Time,
+        "smalldatetime" => ScalarType::Timestamp {
+            precision: Some(TimestampPrecision::try_from(0i64).ok()?),
+        },
+        "datetime" => ScalarType::Timestamp {
+            precision: Some(TimestampPrecision::try_from(3i64).ok()?),
+        },
+        "datetime2" => ScalarType::Timestamp {
+            precision: Some(timestamp_precision(col.scale)),
+        },
+        "datetimeoffset" => ScalarType::TimestampTz {
+            precision: Some(timestamp_precision(col.scale)),
+        },
+        // TODO(sql_server2): Support `money`, `smallmoney`, and the spatial
+        // types. Users can ingest these today via TEXT COLUMNS.
+        _ => return None,
+    };
+    Some(scalar_type)
+}
+
+/// SQL Server supports up to 100ns precision, we only support microseconds.
+fn timestamp_precision(scale: u8) -> TimestampPrecision {
+    let scale = scale.min(mz_repr::adt::timestamp::MAX_PRECISION);
+    TimestampPrecision::try_from(i64::from(scale)).expect("known valid")
+}
+
+/// Decodes a single SQL Server value into `packer`.
+fn decode_value(
+    column_type: &ColumnType,
+    value: &ColumnData<'static>,
+    packer: &mut RowPacker,
+) -> Result<(), anyhow::Error> {
+    match (&column_type.scalar_type, value) {
+        (_, value) if is_null(value) => {
+            if !column_type.nullable {
+                anyhow::bail!("received NULL for non-nullable column");
+            }
+            packer.push(Datum::Null);
+        }
+        (ScalarType::Bool, ColumnData::Bit(Some(val))) => packer.push(Datum::from(*val)),
+        (ScalarType::Int16, ColumnData::U8(Some(val))) => {
+            packer.push(Datum::Int16(i16::from(*val)))
+        }
+        (ScalarType::Int16, ColumnData::I16(Some(val))) => packer.push(Datum::Int16(*val)),
+        (ScalarType::Int32, ColumnData::I32(Some(val))) => packer.push(Datum::Int32(*val)),
+        (ScalarType::Int64, ColumnData::I64(Some(val))) => packer.push(Datum::Int64(*val)),
+        (ScalarType::Float32, ColumnData::F32(Some(val))) => packer.push(Datum::from(*val)),
+        (ScalarType::Float64, ColumnData::F64(Some(val))) => packer.push(Datum::from(*val)),
+        (ScalarType::String, ColumnData::String(Some(val))) => packer.push(Datum::String(val)),
+        (ScalarType::String, ColumnData::Xml(Some(val))) => {
+            packer.push(Datum::String(val.as_ref().as_ref()))
+        }
+        (ScalarType::Bytes, ColumnData::Binary(Some(val))) => packer.push(Datum::Bytes(val)),
+        (ScalarType::Uuid, ColumnData::Guid(Some(val))) => packer.push(Datum::Uuid(*val)),
+        (ScalarType::Numeric { .. }, ColumnData::Numeric(Some(val))) => {
+            let mut cx = cx_datum();
+            let mut numeric = cx.from_i128(val.value());
+            numeric.set_exponent(-i32::from(val.scale()));
+            packer.push(Datum::from(numeric));
+        }
+        (ScalarType::Date, ColumnData::Date(Some(val))) => {
+            let date = Date::try_from(decode_date(val)?)?;
+            packer.push(Datum::Date(date));
+        }
+        (ScalarType::Time, ColumnData::Time(Some(val))) => {
+            packer.push(Datum::Time(decode_time(val)?.round_subsecs(6)));
+        }
+        (ScalarType::Timestamp { .. }, value) => {
+            let ts = match value {
+                ColumnData::DateTime2(Some(val)) => {
+                    NaiveDateTime::new(decode_date(&val.date())?, decode_time(&val.time())?)
+                }
+                ColumnData::DateTime(Some(val)) => {
+                    // Fractions of a second are stored in 1/300th increments.
+                    let nanos = u64::from(val.seconds_fragments()) * 1_000_000_000 / 300;
+                    datetime_epoch()
+                        .checked_add_signed(chrono::Duration::days(i64::from(val.days())))
+                        .and_then(|ts| {
+                            ts.checked_add_signed(chrono::Duration::nanoseconds(
+                                i64::try_from(nanos).ok()?,
+                            ))
+                        })
+                        .ok_or_else(|| anyhow::anyhow!("datetime out of range: {val:?}"))?
+                }
+                ColumnData::SmallDateTime(Some(val)) => {
+                    // Fractions of a day are stored in minutes.
+                    datetime_epoch()
+                        .checked_add_signed(chrono::Duration::days(i64::from(val.days())))
+                        .and_then(|ts| {
+                            ts.checked_add_signed(chrono::Duration::minutes(i64::from(
+                                val.seconds_fragments(),
+                            )))
+                        })
+                        .ok_or_else(|| anyhow::anyhow!("smalldatetime out of range: {val:?}"))?
+                }
+                other => anyhow::bail!("unexpected value for timestamp column: {other:?}"),
+            };
+            let ts = CheckedTimestamp::try_from(ts.round_subsecs(6))?;
+            packer.push(Datum::from(ts));
+        }
+        (ScalarType::TimestampTz { .. }, ColumnData::DateTimeOffset(Some(val))) => {
+            // The wire format of a `datetimeoffset` is the UTC time plus the
+            // offset of the originating time zone, which we drop.
+            let dt2 = val.datetime2();
+            let ts = NaiveDateTime::new(decode_date(&dt2.date())?, decode_time(&dt2.time())?);
+            let ts = CheckedTimestamp::try_from(ts.round_subsecs(6).and_utc())?;
+            packer.push(Datum::from(ts));
+        }
+        (scalar_type, value) => {
+            anyhow::bail!("unexpected value for type {scalar_type:?}: {value:?}")
+        }
+    }
+    Ok(())
+}
+
+fn is_null(value: &ColumnData<'_>) -> bool {
+    match value {
+        ColumnData::U8(v) => v.is_none(),
+        ColumnData::I16(v) => v.is_none(),
+        ColumnData::I32(v) => v.is_none(),
+        ColumnData::I64(v) => v.is_none(),
+        ColumnData::F32(v) => v.is_none(),
+        ColumnData::F64(v) => v.is_none(),
+        ColumnData::Bit(v) => v.is_none(),
+        ColumnData::String(v) => v.is_none(),
+        ColumnData::Guid(v) => v.is_none(),
+        ColumnData::Binary(v) => v.is_none(),
+        ColumnData::Numeric(v) => v.is_none(),
+        ColumnData::Xml(v) => v.is_none(),
+        ColumnData::DateTime(v) => v.is_none(),
+        ColumnData::SmallDateTime(v) => v.is_none(),
+        ColumnData::Time(v) => v.is_none(),
+        ColumnData::Date(v) => v.is_none(),
+        ColumnData::DateTime2(v) => v.is_none(),
+        ColumnData::DateTimeOffset(v) => v.is_none(),
+    }
+}
+
+/// The epoch of `datetime` and `smalldatetime` values, 1900-01-01.
+fn datetime_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1900, 1, 1)
+        .expect("known valid")
+        .and_time(NaiveTime::MIN)
+}
+
+/// Decodes a `date`, stored as the number of days since 0001-01-01.
+fn decode_date(val: &tiberius::time::Date) -> Result<NaiveDate, anyhow::Error> {
+    NaiveDate::from_ymd_opt(1, 1, 1)
+        .expect("known valid")
+        .checked_add_days(chrono::Days::new(u64::from(val.days())))
+        .ok_or_else(|| anyhow::anyhow!("date out of range: {val:?}"))
+}
+
+/// Decodes a `time`, stored as the number of `10^-scale` second increments
+/// since midnight.
+fn decode_time(val: &tiberius::time::Time) -> Result<NaiveTime, anyhow::Error> {
+    let increments_per_sec = 10u64.pow(u32::from(val.scale()));
+    let secs = val.increments() / increments_per_sec;
+    let nanos = (val.increments() % increments_per_sec) * 10u64.pow(9 - u32::from(val.scale()));
+    NaiveTime::from_num_seconds_from_midnight_opt(u32::try_from(secs)?, u32::try_from(nanos)?)
+        .ok_or_else(|| anyhow::anyhow!("time out of range: {val:?}"))
+}
+
+/// Returns the total size in bytes of the values of the provided columns, used
+/// for progress statistics.
+pub fn row_size(row: &tiberius::Row) -> u64 {
+    row.cells()
+        .map(|(_col, data)| match data {
+            ColumnData::String(Some(s)) => u64::cast_from(s.len()),
+            ColumnData::Binary(Some(b)) => u64::cast_from(b.len()),
+            ColumnData::Xml(Some(x)) => u64::cast_from(x.as_ref().as_ref().len()),
+            _ => 8,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::collections::CollectionExt;
+    use mz_repr::Row;
+
+    use super::*;
+
+    fn raw_column(name: &str, data_type: &str) -> SqlServerColumnRaw {
+        SqlServerColumnRaw {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable: true,
+            max_length: 8,
+            precision: 0,
+            scale: 0,
+            primary_key: false,
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_table_desc() {
+        let raw = SqlServerTableRaw {
+            schema_name: "dbo".to_string(),
+            name: "t".to_string(),
+            capture_instance: "dbo_t".to_string(),
+            columns: vec![
+                raw_column("a", "int"),
+                raw_column("b", "money"),
+                raw_column("c", "geography"),
+            ],
+        };
+        let text = BTreeSet::from(["b"]);
+        let exclude = BTreeSet::from(["c"]);
+
+        let desc = SqlServerTableDesc::new(&raw, &text, &exclude).unwrap();
+        assert_eq!(desc.columns.len(), 3);
+        assert_eq!(
+            desc.columns[0].column_type,
+            Some(ScalarType::Int32.nullable(true))
+        );
+        assert_eq!(
+            desc.columns[1].column_type,
+            Some(ScalarType::String.nullable(true))
+        );
+        assert_eq!(desc.columns[2].column_type, None);
+        assert_eq!(desc.select_list(), "[a], CAST([b] AS NVARCHAR(MAX)) AS [b]");
+
+        let err = SqlServerTableDesc::new(&raw, &text, &BTreeSet::new()).unwrap_err();
+        let SqlServerError::UnsupportedDataTypes { columns } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(columns.into_element().column_name, "c");
+    }
+
+    #[mz_ore::test]
+    fn test_decode_temporal() {
+        let date = tiberius::time::Date::new(738_000);
+        assert_eq!(
+            decode_date(&date).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 7, 30).unwrap()
+        );
+
+        // 01:02:03.1234567 at a scale of 7.
+        let time = tiberius::time::Time::new(37_231_234_567, 7);
+        assert_eq!(
+            decode_time(&time).unwrap(),
+            NaiveTime::from_hms_nano_opt(1, 2, 3, 123_456_700).unwrap()
+        );
+
+        let mut row = Row::default();
+        decode_value(
+            &ScalarType::Time.nullable(false),
+            &ColumnData::Time(Some(time)),
+            &mut row.packer(),
+        )
+        .unwrap();
+        assert_eq!(
+            row.unpack_first(),
+            Datum::Time(NaiveTime::from_hms_micro_opt(1, 2, 3, 123_457).unwrap())
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_decode_null() {
+        let mut row = Row::default();
+        let err = decode_value(
+            &ScalarType::Int32.nullable(false),
+            &ColumnData::I32(None),
+            &mut row.packer(),
+        );
+        assert!(err.is_err());
+
+        decode_value(
+            &ScalarType::Int32.nullable(true),
+            &ColumnData::I32(None),
+            &mut row.packer(),
+        )
+        .unwrap();
+        assert_eq!(row.unpack_first(), Datum::Null);
+    }
+}
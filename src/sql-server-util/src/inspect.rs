@@ -0,0 +1,253 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Inspecting the state and metadata of an upstream SQL Server database.
+
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use futures::{Stream, TryStreamExt};
+
+use crate::cdc::Lsn;
+use crate::desc::{SqlServerColumnRaw, SqlServerTableDesc, SqlServerTableRaw};
+use crate::{quote_identifier, Client, SqlServerError, Transaction};
+
+/// Ensures that Change Data Capture is enabled for the current database.
+pub async fn ensure_database_cdc_enabled(client: &mut Client) -> Result<(), SqlServerError> {
+    static QUERY: &str =
+        "SELECT name, is_cdc_enabled FROM sys.databases WHERE database_id = DB_ID();";
+    let rows = client.simple_query(QUERY).await?;
+    match &rows[..] {
+        [row] => {
+            let name: &str = row.try_get(0)?.context("missing database name")?;
+            let is_cdc_enabled: bool = row.try_get(1)?.context("missing is_cdc_enabled")?;
+            if !is_cdc_enabled {
+                return Err(SqlServerError::InvalidSetup(format!(
+                    "CDC is not enabled for database '{name}', \
+                     see `sys.sp_cdc_enable_db` for how to enable it"
+                )));
+            }
+            Ok(())
+        }
+        other => Err(SqlServerError::Generic(anyhow::anyhow!(
+            "expected one row, got {}",
+            other.len()
+        ))),
+    }
+}
+
+/// Ensures that snapshot isolation is allowed for the current database.
+///
+/// Tables are read in a `SNAPSHOT` transaction, see [`snapshot_table`].
+pub async fn ensure_snapshot_isolation_enabled(client: &mut Client) -> Result<(), SqlServerError> {
+    static QUERY: &str =
+        "SELECT name, snapshot_isolation_state FROM sys.databases WHERE database_id = DB_ID();";
+    let rows = client.simple_query(QUERY).await?;
+    match &rows[..] {
+        [row] => {
+            let name: &str = row.try_get(0)?.context("missing database name")?;
+            let state: u8 = row
+                .try_get(1)?
+                .context("missing snapshot_isolation_state")?;
+            // 1 is ON, the other states are OFF or in transition.
+            if state != 1 {
+                return Err(SqlServerError::InvalidSetup(format!(
+                    "snapshot isolation is not enabled for database '{name}', \
+                     see `ALTER DATABASE ... SET ALLOW_SNAPSHOT_ISOLATION ON` for how to enable it"
+                )));
+            }
+            Ok(())
+        }
+        other => Err(SqlServerError::Generic(anyhow::anyhow!(
+            "expected one row, got {}",
+            other.len()
+        ))),
+    }
+}
+
+/// Returns all of the tables in the current database that have a CDC capture
+/// instance, along with the columns tracked by each capture instance.
+///
+/// A table can have up to two capture instances in which case it will be
+/// returned twice.
+pub async fn get_tables(client: &mut Client) -> Result<Vec<SqlServerTableRaw>, SqlServerError> {
+    // Note: `TYPE_NAME(c.system_type_id)` resolves alias types, e.g. `sysname`,
+    // to their underlying system type.
+    static QUERY: &str = "
+SELECT
+    s.name AS schema_name,
+    t.name AS table_name,
+    ct.capture_instance,
+    c.name AS column_name,
+    TYPE_NAME(c.system_type_id) AS data_type,
+    c.is_nullable,
+    c.max_length,
+    c.precision,
+    c.scale,
+    CAST(CASE WHEN pk.column_id IS NULL THEN 0 ELSE 1 END AS bit) AS is_primary_key
+FROM cdc.change_tables ct
+JOIN sys.tables t ON t.object_id = ct.source_object_id
+JOIN sys.schemas s ON s.schema_id = t.schema_id
+JOIN cdc.captured_columns cc ON cc.object_id = ct.object_id
+JOIN sys.columns c ON c.object_id = t.object_id AND c.column_id = cc.column_id
+LEFT JOIN (
+    SELECT ic.object_id, ic.column_id
+    FROM sys.indexes i
+    JOIN sys.index_columns ic ON ic.object_id = i.object_id AND ic.index_id = i.index_id
+    WHERE i.is_primary_key = 1
+) pk ON pk.object_id = t.object_id AND pk.column_id = c.column_id
+ORDER BY s.name, t.name, ct.capture_instance, cc.column_ordinal;";
+
+    let rows = client.simple_query(QUERY).await?;
+
+    let mut tables: BTreeMap<(String, String, String), Vec<SqlServerColumnRaw>> = BTreeMap::new();
+    for row in rows.iter() {
+        let schema_name: &str = row.try_get(0)?.context("missing schema_name")?;
+        let table_name: &str = row.try_get(1)?.context("missing table_name")?;
+        let capture_instance: &str = row.try_get(2)?.context("missing capture_instance")?;
+        let column = SqlServerColumnRaw {
+            name: row
+                .try_get::<&str, _>(3)?
+                .context("missing column_name")?
+                .to_string(),
+            data_type: row
+                .try_get::<&str, _>(4)?
+                .context("missing data_type")?
+                .to_string(),
+            is_nullable: row.try_get(5)?.unwrap_or(true),
+            max_length: row.try_get(6)?.context("missing max_length")?,
+            precision: row.try_get(7)?.context("missing precision")?,
+            scale: row.try_get(8)?.context("missing scale")?,
+            primary_key: row.try_get(9)?.context("missing is_primary_key")?,
+        };
+        tables
+            .entry((
+                schema_name.to_string(),
+                table_name.to_string(),
+                capture_instance.to_string(),
+            ))
+            .or_default()
+            .push(column);
+    }
+
+    let tables = tables
+        .into_iter()
+        .map(
+            |((schema_name, name, capture_instance), columns)| SqlServerTableRaw {
+                schema_name,
+                name,
+                capture_instance,
+                columns,
+            },
+        )
+        .collect();
+    Ok(tables)
+}
+
+/// Acquires a shared lock on the table `schema_name.table_name` that is held
+/// until `txn` completes.
+///
+/// While the lock is held other transactions cannot modify the table, this
+/// waits for any in-flight transactions that modified the table to complete.
+pub async fn lock_table_shared(
+    txn: &mut Transaction<'_>,
+    schema_name: &str,
+    table_name: &str,
+) -> Result<(), SqlServerError> {
+    let query = format!(
+        "SELECT TOP 0 * FROM {}.{} WITH (TABLOCK, HOLDLOCK);",
+        quote_identifier(schema_name),
+        quote_identifier(table_name)
+    );
+    txn.simple_query(query).await?;
+    Ok(())
+}
+
+/// Returns the [`Lsn`] of the first record written to the transaction log by
+/// `txn`, forcing a record to be written by creating a savepoint.
+///
+/// Every transaction that committed before this point has a smaller [`Lsn`].
+pub async fn get_transaction_lsn(txn: &mut Transaction<'_>) -> Result<Lsn, SqlServerError> {
+    // A transaction is only assigned a begin LSN once it writes to the log,
+    // which creating a savepoint does.
+    txn.simple_query("SAVE TRANSACTION mz_snapshot;").await?;
+
+    static QUERY: &str = "
+SELECT dt.database_transaction_begin_lsn
+FROM sys.dm_tran_database_transactions dt
+JOIN sys.dm_tran_current_transaction ct ON ct.transaction_id = dt.transaction_id
+WHERE dt.database_id = DB_ID() AND dt.database_transaction_begin_lsn IS NOT NULL;";
+    let rows = txn.simple_query(QUERY).await?;
+    match &rows[..] {
+        [row] => {
+            let lsn: tiberius::numeric::Numeric = row
+                .try_get(0)?
+                .context("missing database_transaction_begin_lsn")?;
+            Lsn::try_from_numeric(lsn.value())
+        }
+        other => Err(SqlServerError::Generic(anyhow::anyhow!(
+            "expected one row, got {}",
+            other.len()
+        ))),
+    }
+}
+
+/// Returns the number of rows of the table described by `desc`.
+///
+/// In a `SNAPSHOT` transaction this is the first read of the table, so it also
+/// establishes the snapshot that [`snapshot_table`] reads.
+pub async fn get_table_row_count(
+    txn: &mut Transaction<'_>,
+    desc: &SqlServerTableDesc,
+) -> Result<u64, SqlServerError> {
+    let query = format!(
+        "SELECT COUNT_BIG(*) FROM {}.{};",
+        quote_identifier(&desc.schema_name),
+        quote_identifier(&desc.name)
+    );
+    let rows = txn.simple_query(query).await?;
+    match &rows[..] {
+        [row] => {
+            let count: i64 = row.try_get(0)?.context("missing count")?;
+            Ok(u64::try_from(count).context("negative count")?)
+        }
+        other => Err(SqlServerError::Generic(anyhow::anyhow!(
+            "expected one row, got {}",
+            other.len()
+        ))),
+    }
+}
+
+/// Returns a stream of all rows of the table described by `desc`.
+///
+/// The columns of each row match [`SqlServerTableDesc::select_list`]. To read
+/// a consistent snapshot of several tables without blocking writes to them,
+/// `txn` should use the `SNAPSHOT` isolation level.
+pub fn snapshot_table<'a>(
+    txn: &'a mut Transaction<'_>,
+    desc: &SqlServerTableDesc,
+) -> impl Stream<Item = Result<tiberius::Row, SqlServerError>> + 'a {
+    let select_list = desc.select_list();
+    // A table where all columns are excluded still has rows.
+    let select_list = if select_list.is_empty() {
+        "NULL AS [mz_empty]".to_string()
+    } else {
+        select_list
+    };
+    let query = format!(
+        "SELECT {select_list} FROM {}.{};",
+        quote_identifier(&desc.schema_name),
+        quote_identifier(&desc.name)
+    );
+    // NB: Going through the client directly keeps the lifetime of the
+    // transaction out of the type of the returned stream.
+    txn.client
+        .query_streaming(query, &[])
+        .map_err(SqlServerError::from)
+}
@@ -9,11 +9,13 @@
 
 use std::borrow::Cow;
 use std::future::IntoFuture;
+use std::net::SocketAddr;
 
 use anyhow::Context;
 use derivative::Derivative;
 use futures::future::BoxFuture;
-use futures::FutureExt;
+use futures::{stream, FutureExt, Stream, StreamExt};
+use mz_ssh_util::tunnel_manager::ManagedSshTunnelHandle;
 use smallvec::SmallVec;
 use tiberius::ToSql;
 use tokio::net::TcpStream;
@@ -21,8 +23,15 @@ use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::sync::oneshot;
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
-// Re-export tiberius' Config type since it's needed by our Client wrapper.
-pub use tiberius::Config;
+pub mod cdc;
+pub mod config;
+pub mod desc;
+pub mod inspect;
+
+pub use config::{Config, TunnelConfig};
+pub use desc::{
+    ProtoSqlServerColumnDesc, ProtoSqlServerTableDesc, SqlServerColumnDesc, SqlServerTableDesc,
+};
 
 /// Higher level wrapper around a [`tiberius::Client`] that models transaction
 /// management like other database clients.
@@ -41,22 +50,69 @@ pub struct Client {
 static_assertions::assert_not_impl_all!(Client: Clone);
 
 impl Client {
-    pub async fn connect(config: tiberius::Config) -> Result<(Self, Connection), anyhow::Error> {
-        let tcp = TcpStream::connect(config.get_addr()).await?;
+    /// Connects to the SQL Server instance described by `config`, establishing
+    /// a tunnel first if one is configured.
+    pub async fn connect(config: Config) -> Result<(Self, Connection), SqlServerError> {
+        let (tcp, ssh_tunnel_handle) = match &config.tunnel {
+            TunnelConfig::Direct { resolved_ips } => {
+                let tcp = match resolved_ips {
+                    Some(ips) => {
+                        let addrs: Vec<_> = ips
+                            .iter()
+                            .map(|ip| SocketAddr::new(*ip, config.port))
+                            .collect();
+                        TcpStream::connect(&addrs[..]).await?
+                    }
+                    None => TcpStream::connect((config.host.as_str(), config.port)).await?,
+                };
+                (tcp, None)
+            }
+            TunnelConfig::Ssh {
+                config: ssh_config,
+                manager,
+                timeout,
+            } => {
+                let handle = manager
+                    .connect(
+                        ssh_config.clone(),
+                        &config.host,
+                        config.port,
+                        *timeout,
+                        config.in_task,
+                    )
+                    .await
+                    .map_err(SqlServerError::Ssh)?;
+                let tcp = TcpStream::connect(handle.local_addr()).await?;
+                (tcp, Some(handle))
+            }
+            TunnelConfig::AwsPrivatelink { connection_id } => {
+                let privatelink_host = mz_cloud_resources::vpc_endpoint_host(*connection_id, None);
+                let tcp = TcpStream::connect((privatelink_host.as_str(), config.port)).await?;
+                (tcp, None)
+            }
+        };
         tcp.set_nodelay(true)?;
-        Self::connect_raw(config, tcp).await
+
+        let (client, mut connection) = Self::connect_raw(config.inner, tcp).await?;
+        connection._ssh_tunnel_handle = ssh_tunnel_handle;
+        Ok((client, connection))
     }
 
     pub async fn connect_raw(
         config: tiberius::Config,
         tcp: tokio::net::TcpStream,
-    ) -> Result<(Self, Connection), anyhow::Error> {
-        let client = tiberius::Client::connect(config, tcp.compat_write())
-            .await
-            .context("connecting to SQL Server")?;
+    ) -> Result<(Self, Connection), SqlServerError> {
+        let client = tiberius::Client::connect(config, tcp.compat_write()).await?;
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
-        Ok((Client { tx }, Connection { rx, client }))
+        Ok((
+            Client { tx },
+            Connection {
+                rx,
+                client,
+                _ssh_tunnel_handle: None,
+            },
+        ))
     }
 
     /// Executes SQL statements in SQL Server, returning the number of rows effected.
@@ -135,6 +191,56 @@ impl Client {
         }
     }
 
+    /// Executes a SQL statement in SQL Server, returning a stream of the
+    /// resulting rows.
+    ///
+    /// Unlike [`Client::query`] the rows are not collected in memory, they are
+    /// read from SQL Server as the stream is polled. The connection can't run
+    /// any other statement until the stream is exhausted or dropped.
+    pub fn query_streaming<'c, Q>(
+        &'c mut self,
+        query: Q,
+        params: &[&dyn tiberius::ToSql],
+    ) -> impl Stream<Item = Result<tiberius::Row, anyhow::Error>> + 'c
+    where
+        Q: Into<Cow<'c, str>> + 'c,
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let (rows_tx, rows_rx) = tokio::sync::mpsc::channel(STREAMING_ROWS_BUFFER);
+
+        let params = params
+            .iter()
+            .map(|p| OwnedColumnData::from(p.to_sql()))
+            .collect();
+        let kind = RequestKind::QueryStreaming {
+            query: query.into().to_string(),
+            params,
+            rows: rows_tx,
+        };
+        let sent = self
+            .tx
+            .send(Request { tx, kind })
+            .context("sending request");
+
+        let rows = stream::unfold(rows_rx, |mut rows_rx| async move {
+            let row = rows_rx.recv().await?;
+            Some((Ok(row), rows_rx))
+        });
+        // Once all rows have been received, report whether the statement as a
+        // whole failed.
+        let result = stream::once(async move {
+            sent?;
+            match rx.await.context("channel")?.context("query streaming")? {
+                Response::Rows(_) => Ok::<_, anyhow::Error>(()),
+                other @ Response::Execute { .. } => Err(anyhow::anyhow!(
+                    "programming error! expected Response::Rows, got {other:?}"
+                )),
+            }
+        })
+        .filter_map(|result| async move { result.err().map(Err) });
+        rows.chain(result)
+    }
+
     /// Executes multiple queries, delimited with `;` and return multiple
     /// result sets; one for each query.
     ///
@@ -245,6 +351,18 @@ impl<'a> Transaction<'a> {
         self.client.query(query, params).await
     }
 
+    /// See [`Client::query_streaming`].
+    pub fn query_streaming<'c, Q>(
+        &'c mut self,
+        query: Q,
+        params: &[&dyn tiberius::ToSql],
+    ) -> impl Stream<Item = Result<tiberius::Row, anyhow::Error>> + 'c
+    where
+        Q: Into<Cow<'c, str>> + 'c,
+    {
+        self.client.query_streaming(query, params)
+    }
+
     /// See [`Client::simple_query`].
     pub async fn simple_query<'q>(
         &mut self,
@@ -257,7 +375,7 @@ impl<'a> Transaction<'a> {
     pub async fn rollback(self) -> Result<(), anyhow::Error> {
         static ROLLBACK_QUERY: &str = "ROLLBACK TRANSACTION";
         self.client
-            .execute(ROLLBACK_QUERY, &[])
+            .simple_query(ROLLBACK_QUERY)
             .await
             .context("rollback")?;
         Ok(())
@@ -267,7 +385,7 @@ impl<'a> Transaction<'a> {
     pub async fn commit(self) -> Result<(), anyhow::Error> {
         static COMMIT_QUERY: &str = "COMMIT TRANSACTION";
         self.client
-            .execute(COMMIT_QUERY, &[])
+            .simple_query(COMMIT_QUERY)
             .await
             .context("commit")?;
         Ok(())
@@ -320,6 +438,67 @@ impl TransactionIsolationLevel {
     }
 }
 
+/// A SQL Server column whose data type we cannot ingest.
+#[derive(Debug, Clone)]
+pub struct UnsupportedDataType {
+    pub column_type: String,
+    pub qualified_table_name: String,
+    pub column_name: String,
+}
+
+impl std::fmt::Display for UnsupportedDataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}.{}' of type '{}'",
+            self.qualified_table_name, self.column_name, self.column_type
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SqlServerError {
+    #[error("error creating SQL Server connection with config: {0}")]
+    InvalidClientConfig(String),
+    #[error("error setting up ssh: {0}")]
+    Ssh(#[source] anyhow::Error),
+    #[error("error decoding value for '{qualified_table_name}' column '{column_name}': {error}")]
+    ValueDecodeError {
+        column_name: String,
+        qualified_table_name: String,
+        error: String,
+    },
+    #[error("unsupported data types: {columns:?}")]
+    UnsupportedDataTypes { columns: Vec<UnsupportedDataType> },
+    #[error("invalid SQL Server setup: {0}")]
+    InvalidSetup(String),
+    #[error("invalid LSN: {0:?}")]
+    InvalidLsn(Vec<u8>),
+    /// An I/O error, e.g. while establishing the TCP connection.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// An error returned by the underlying tiberius client.
+    #[error(transparent)]
+    SqlServer(#[from] tiberius::error::Error),
+    /// Any other error we bail on.
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+}
+
+/// Quotes a SQL Server identifier, e.g. a schema, table, or column name.
+///
+/// See: <https://learn.microsoft.com/en-us/sql/t-sql/functions/quotename-transact-sql>
+pub fn quote_identifier(identifier: &str) -> String {
+    let mut escaped = identifier.replace(']', "]]");
+    escaped.insert(0, '[');
+    escaped.push(']');
+    escaped
+}
+
+/// The number of rows of a [`Client::query_streaming`] statement that are
+/// buffered before the connection waits for them to be consumed.
+const STREAMING_ROWS_BUFFER: usize = 1024;
+
 #[derive(Debug)]
 enum Response {
     Execute { rows_affected: SmallVec<[u64; 1]> },
@@ -345,14 +524,26 @@ enum RequestKind {
         #[derivative(Debug = "ignore")]
         params: SmallVec<[OwnedColumnData; 4]>,
     },
+    QueryStreaming {
+        query: String,
+        #[derivative(Debug = "ignore")]
+        params: SmallVec<[OwnedColumnData; 4]>,
+        #[derivative(Debug = "ignore")]
+        rows: tokio::sync::mpsc::Sender<tiberius::Row>,
+    },
     SimpleQuery {
         query: String,
     },
 }
 
+/// The connection to a SQL Server instance, see [`Client`].
+///
+/// Holds the SSH tunnel handle, if any, so the tunnel is kept alive for the
+/// lifetime of the connection.
 pub struct Connection {
     rx: UnboundedReceiver<Request>,
     client: tiberius::Client<Compat<TcpStream>>,
+    _ssh_tunnel_handle: Option<ManagedSshTunnelHandle>,
 }
 
 impl Connection {
@@ -363,6 +554,7 @@ impl Connection {
             let context = match &kind {
                 RequestKind::Execute { .. } => "execute",
                 RequestKind::Query { .. } => "query",
+                RequestKind::QueryStreaming { .. } => "query streaming",
                 RequestKind::SimpleQuery { .. } => "simple query",
             };
             let response = Connection::handle_request(&mut self.client, kind)
@@ -401,7 +593,8 @@ impl Connection {
 
                 let mut results = result.into_results().await.context("into results")?;
                 if results.is_empty() {
-                    anyhow::bail!("got empty response")
+                    // Statements that don't return a result set, e.g. `BEGIN TRANSACTION`.
+                    Ok(Response::Rows(SmallVec::new()))
                 } else if results.len() == 1 {
                     // TODO(sql_server3): Don't use `into_results()` above, instead directly
                     // push onto a SmallVec to avoid the heap allocations.
@@ -411,12 +604,32 @@ impl Connection {
                     anyhow::bail!("Query only supports 1 statement, got {}", results.len())
                 }
             }
+            RequestKind::QueryStreaming {
+                query,
+                params,
+                rows,
+            } => {
+                #[allow(clippy::as_conversions)]
+                let params: SmallVec<[&dyn ToSql; 4]> =
+                    params.iter().map(|x| x as &dyn ToSql).collect();
+                let mut stream = client
+                    .query(query, params.as_slice())
+                    .await?
+                    .into_row_stream();
+                while let Some(row) = stream.next().await {
+                    // The rest of the result set must still be read when the
+                    // stream was dropped, so the connection can be reused.
+                    let _ = rows.send(row?).await;
+                }
+                Ok(Response::Rows(SmallVec::new()))
+            }
             RequestKind::SimpleQuery { query } => {
                 let result = client.simple_query(query).await?;
 
                 let mut results = result.into_results().await.context("into results")?;
                 if results.is_empty() {
-                    anyhow::bail!("got empty response")
+                    // Statements that don't return a result set, e.g. `BEGIN TRANSACTION`.
+                    Ok(Response::Rows(SmallVec::new()))
                 } else if results.len() == 1 {
                     // TODO(sql_server3): Don't use `into_results()` above, instead directly
                     // push onto a SmallVec to avoid the heap allocations.
@@ -531,3 +744,18 @@ impl<'a, T: tiberius::ToSql> From<&'a T> for OwnedColumnData {
         OwnedColumnData::from(value.to_sql())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::quote_identifier;
+
+    #[mz_ore::test]
+    fn test_identifier_quoting() {
+        let expected = vec!["[a]", "[naughty]]sql]", "[[;naughty;sql;]]]"];
+        let input = ["a", "naughty]sql", "[;naughty;sql;]"]
+            .iter()
+            .map(|raw_str| quote_identifier(raw_str))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, input);
+    }
+}
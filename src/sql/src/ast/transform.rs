@@ -18,10 +18,11 @@ use mz_sql_parser::ast::CreateTableFromSourceStatement;
 use crate::ast::visit::{self, Visit};
 use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
-    AstInfo, CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement,
-    CreateTableStatement, CreateViewStatement, CreateWebhookSourceStatement, Expr, Ident, Query,
-    Raw, RawItemName, Statement, UnresolvedItemName, ViewDefinition,
+    AstInfo, CreateConnectionStatement, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateSecretStatement, CreateSinkStatement,
+    CreateSourceStatement, CreateSubsourceStatement, CreateTableStatement, CreateViewStatement,
+    CreateWebhookSourceStatement, Expr, FunctionBody, Ident, Query, Raw, RawItemName, Select,
+    SelectItem, SetExpr, Statement, UnresolvedItemName, ViewDefinition,
 };
 use crate::names::FullItemName;
 
@@ -47,6 +48,7 @@ pub fn create_stmt_rename_schema_refs(
         | stmt @ Statement::CreateTableFromSource(_)
         | stmt @ Statement::CreateIndex(_)
         | stmt @ Statement::CreateType(_)
+        | stmt @ Statement::CreateFunction(_)
         | stmt @ Statement::CreateSecret(_) => {
             let mut visitor = CreateSqlRewriteSchema {
                 database,
@@ -151,6 +153,7 @@ pub fn create_stmt_rename(create_stmt: &mut Statement<Raw>, to_item_name: String
         | Statement::CreateTable(CreateTableStatement { name, .. })
         | Statement::CreateTableFromSource(CreateTableFromSourceStatement { name, .. })
         | Statement::CreateSecret(CreateSecretStatement { name, .. })
+        | Statement::CreateFunction(CreateFunctionStatement { name, .. })
         | Statement::CreateConnection(CreateConnectionStatement { name, .. })
         | Statement::CreateWebhookSource(CreateWebhookSourceStatement { name, .. }) => {
             // The last name in an ItemName is the item name. The item name
@@ -206,6 +209,28 @@ pub fn create_stmt_rename_refs(
         | Statement::CreateMaterializedView(CreateMaterializedViewStatement { query, .. }) => {
             rewrite_query(from_name, to_item_name, query)?;
         }
+        Statement::CreateFunction(CreateFunctionStatement { body, .. }) => match body {
+            FunctionBody::Atomic(query) => rewrite_query(from_name, to_item_name, query)?,
+            FunctionBody::Return(expr) => {
+                // Rewrite the expression as the sole projection of a query, so
+                // that it is subject to the same ambiguity checks as queries.
+                let select = Select::default().project(SelectItem::Expr {
+                    expr: expr.clone(),
+                    alias: None,
+                });
+                let mut query = Query::select(select);
+                rewrite_query(from_name, to_item_name, &mut query)?;
+                match query.body {
+                    SetExpr::Select(mut select) => match select.projection.pop() {
+                        Some(SelectItem::Expr {
+                            expr: rewritten, ..
+                        }) => *expr = rewritten,
+                        _ => unreachable!("projection preserved by rewrite"),
+                    },
+                    _ => unreachable!("query body preserved by rewrite"),
+                }
+            }
+        },
         Statement::CreateSource(_)
         | Statement::CreateSubsource(_)
         | Statement::CreateTable(_)
@@ -485,6 +485,14 @@ pub trait CatalogSchema {
 pub struct RoleAttributes {
     /// Indicates whether the role has inheritance of privileges.
     pub inherit: bool,
+    /// Indicates whether the role is allowed to log in, if it was specified.
+    ///
+    /// Password authentication only admits roles that were explicitly given the `LOGIN`
+    /// attribute.
+    pub login: Option<bool>,
+    /// The SCRAM-SHA-256 verifier of the role's password, if it has one.
+    #[serde(skip)]
+    pub password_hash: Option<String>,
     // Force use of constructor.
     _private: (),
 }
@@ -494,6 +502,8 @@ impl RoleAttributes {
     pub const fn new() -> RoleAttributes {
         RoleAttributes {
             inherit: true,
+            login: None,
+            password_hash: None,
             _private: (),
         }
     }
@@ -508,13 +518,26 @@ impl RoleAttributes {
     pub const fn is_inherit(&self) -> bool {
         self.inherit
     }
+
+    /// Returns whether or not the role can log in with a password.
+    pub fn can_login_with_password(&self) -> bool {
+        self.login == Some(true) && self.password_hash.is_some()
+    }
 }
 
 impl From<PlannedRoleAttributes> for RoleAttributes {
-    fn from(PlannedRoleAttributes { inherit }: PlannedRoleAttributes) -> RoleAttributes {
+    fn from(
+        PlannedRoleAttributes {
+            inherit,
+            login,
+            password_hash,
+        }: PlannedRoleAttributes,
+    ) -> RoleAttributes {
         let default_attributes = RoleAttributes::new();
         RoleAttributes {
             inherit: inherit.unwrap_or(default_attributes.inherit),
+            login,
+            password_hash: password_hash.flatten(),
             _private: (),
         }
     }
@@ -643,7 +666,7 @@ pub trait CatalogItem {
     ///
     /// If the catalog item is not of a type that produces functions (i.e.,
     /// anything other than a function), it returns an error.
-    fn func(&self) -> Result<&'static Func, CatalogError>;
+    fn func(&self) -> Result<&Func, CatalogError>;
 
     /// Returns the resolved source connection.
     ///
@@ -960,6 +983,18 @@ pub enum CatalogType<T: TypeReference> {
     VarChar,
     Int2Vector,
     MzAclItem,
+    Inet,
+    Cidr,
+    MacAddr,
+    MacAddr8,
+    TsVector,
+    TsQuery,
+    JsonPath,
+    Enum {
+        labels: Vec<String>,
+    },
+    Geometry,
+    Geography,
 }
 
 impl CatalogType<IdReference> {
@@ -22,21 +22,23 @@ use mz_ore::str::StrExt;
 use mz_pgrepr::oid;
 use mz_repr::role_id::RoleId;
 use mz_repr::{ColumnName, ColumnType, Datum, RelationType, Row, ScalarBaseType, ScalarType};
+use ordered_float::OrderedFloat;
 
-use crate::ast::{SelectStatement, Statement};
+use crate::ast::{Expr, FunctionBody, SelectStatement, Statement};
 use crate::catalog::{CatalogType, TypeCategory, TypeReference};
-use crate::names::{self, ResolvedItemName};
+use crate::names::{self, Aug, PartialItemName, ResolvedItemName};
 use crate::plan::error::PlanError;
 use crate::plan::hir::{
     AggregateFunc, BinaryFunc, CoercibleScalarExpr, CoercibleScalarType, ColumnOrder,
     HirRelationExpr, HirScalarExpr, ScalarWindowFunc, TableFunc, UnaryFunc, UnmaterializableFunc,
     ValueWindowFunc, VariadicFunc,
 };
-use crate::plan::query::{self, ExprContext, QueryContext};
+use crate::plan::query::{self, ExprContext, QueryContext, QueryLifetime};
 use crate::plan::scope::Scope;
 use crate::plan::side_effecting_func::PG_CATALOG_SEF_BUILTINS;
 use crate::plan::transform_ast;
 use crate::plan::typeconv::{self, CastContext};
+use crate::plan::StatementContext;
 use crate::session::vars::{self, ENABLE_TIME_AT_TIME_ZONE};
 
 /// A specifier for a function or an operator.
@@ -78,7 +80,15 @@ impl TypeCategory {
             | ScalarType::Bytes
             | ScalarType::Jsonb
             | ScalarType::Uuid
-            | ScalarType::MzAclItem => Self::UserDefined,
+            | ScalarType::MzAclItem
+            | ScalarType::MacAddr
+            | ScalarType::MacAddr8
+            | ScalarType::TsVector
+            | ScalarType::TsQuery
+            | ScalarType::JsonPath
+            | ScalarType::Geometry
+            | ScalarType::Geography => Self::UserDefined,
+            ScalarType::Inet | ScalarType::Cidr => Self::NetworkAddress,
             ScalarType::Date
             | ScalarType::Time
             | ScalarType::Timestamp { .. }
@@ -113,6 +123,7 @@ impl TypeCategory {
             ScalarType::Map { .. } => Self::Pseudo,
             ScalarType::MzTimestamp => Self::Numeric,
             ScalarType::Range { .. } => Self::Range,
+            ScalarType::Enum { .. } => Self::Enum,
         }
     }
 
@@ -123,6 +134,7 @@ impl TypeCategory {
             | ParamType::ArrayAny
             | ParamType::ArrayAnyCompatible
             | ParamType::AnyCompatible
+            | ParamType::EnumAny
             | ParamType::ListAny
             | ParamType::ListAnyCompatible
             | ParamType::ListElementAnyCompatible
@@ -152,7 +164,15 @@ impl TypeCategory {
             | CatalogType::Bytes
             | CatalogType::Jsonb
             | CatalogType::Uuid
-            | CatalogType::MzAclItem => Self::UserDefined,
+            | CatalogType::MzAclItem
+            | CatalogType::MacAddr
+            | CatalogType::MacAddr8
+            | CatalogType::TsVector
+            | CatalogType::TsQuery
+            | CatalogType::JsonPath
+            | CatalogType::Geometry
+            | CatalogType::Geography => Self::UserDefined,
+            CatalogType::Inet | CatalogType::Cidr => Self::NetworkAddress,
             CatalogType::Date
             | CatalogType::Time
             | CatalogType::Timestamp
@@ -181,6 +201,7 @@ impl TypeCategory {
             CatalogType::Map { .. } | CatalogType::Pseudo => Self::Pseudo,
             CatalogType::MzTimestamp => Self::String,
             CatalogType::Range { .. } => Self::Range,
+            CatalogType::Enum { .. } => Self::Enum,
         }
     }
 
@@ -199,13 +220,13 @@ impl TypeCategory {
             | Self::Enum
             | Self::Geometric
             | Self::List
-            | Self::NetworkAddress
             | Self::Pseudo
             | Self::Range
             | Self::Unknown
             | Self::UserDefined => None,
             Self::Boolean => Some(ScalarType::Bool),
             Self::DateTime => Some(ScalarType::TimestampTz { precision: None }),
+            Self::NetworkAddress => Some(ScalarType::Inet),
             Self::Numeric => Some(ScalarType::Float64),
             Self::String => Some(ScalarType::String),
             Self::Timespan => Some(ScalarType::Interval),
@@ -477,6 +498,223 @@ fn experimental_sql_impl_table_func(
     sql_impl_table_func_inner(sql, Some(feature))
 }
 
+/// The result of a user-defined function.
+#[derive(Debug, Clone)]
+pub enum UserFuncReturnType {
+    /// The function returns a single value of the given type.
+    Scalar(ScalarType),
+    /// The function returns a set of rows with the given columns.
+    Table(Vec<(ColumnName, ScalarType)>),
+}
+
+/// The definition of a user-defined function, from which the function is
+/// planned whenever it is invoked.
+struct UserFuncDefinition {
+    /// The scope to which the function's parameters are bound.
+    param_scope: Scope,
+    param_types: Vec<ScalarType>,
+    body: FunctionBody<Aug>,
+}
+
+impl UserFuncDefinition {
+    /// Plans the body of the function with `f`.
+    ///
+    /// Parameters are bound to the columns of a scope directly enclosing the
+    /// body, so that unqualified references to the columns of the body's own
+    /// relations take precedence over parameters of the same name. `$n`
+    /// references to parameters are supported too.
+    fn plan<R, F>(&self, qcx: &QueryContext, f: F) -> Result<R, PlanError>
+    where
+        F: FnOnce(&mut QueryContext, &FunctionBody<Aug>) -> Result<R, PlanError>,
+    {
+        let mut scx = qcx.scx.clone();
+        scx.param_types = RefCell::new(
+            self.param_types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| (i + 1, ty.clone()))
+                .collect(),
+        );
+        let mut qcx = QueryContext::root(&scx, qcx.lifetime);
+        qcx.outer_scopes = vec![self.param_scope.clone()];
+        qcx.outer_relation_types = vec![RelationType::new(
+            self.param_types
+                .iter()
+                .map(|ty| ty.clone().nullable(true))
+                .collect(),
+        )];
+
+        let mut body = self.body.clone();
+        transform_ast::transform(&scx, &mut body)?;
+        f(&mut qcx, &body)
+    }
+}
+
+/// Constructs the definition of the user-defined function `name` from the SQL
+/// `body` of its `CREATE FUNCTION` statement.
+///
+/// The body may refer to the function's parameters by name, optionally
+/// qualified by the function's name, or by position using the standard
+/// parameter syntax (`$1`, `$2`, ...). Like the SQL implementations of built-in
+/// functions, the body is planned whenever the function is invoked and inlined
+/// into the invoking expression. It is planned once here too, to report any
+/// errors when the function is created.
+pub fn plan_user_defined_func(
+    scx: &StatementContext,
+    name: &str,
+    params: Vec<(Option<ColumnName>, ScalarType)>,
+    return_type: UserFuncReturnType,
+    body: FunctionBody<Aug>,
+) -> Result<Func, PlanError> {
+    let param_scope = Scope::from_source(
+        Some(PartialItemName {
+            database: None,
+            schema: None,
+            item: name.into(),
+        }),
+        params.iter().enumerate().map(|(i, (name, _))| {
+            name.clone()
+                .unwrap_or_else(|| ColumnName::from(format!("${}", i + 1)))
+        }),
+    );
+    let param_types: Vec<_> = params.into_iter().map(|(_, typ)| typ).collect();
+    let param_list = ParamList::Exact(param_types.iter().cloned().map(ParamType::Plain).collect());
+    let def = UserFuncDefinition {
+        param_scope,
+        param_types: param_types.clone(),
+        body,
+    };
+
+    let func = match return_type {
+        UserFuncReturnType::Scalar(typ) => {
+            let return_type = ReturnType::scalar(ParamType::Plain(typ.clone()));
+            let op = Operation::variadic(move |ecx, args| {
+                let mut expr = def.plan(ecx.qcx, |qcx, body| {
+                    let expr = match body {
+                        FunctionBody::Return(expr) => expr.clone(),
+                        FunctionBody::Atomic(query) => Expr::Subquery(Box::new(query.clone())),
+                    };
+                    let ecx = ExprContext {
+                        qcx,
+                        name: "function body",
+                        scope: &Scope::empty(),
+                        relation_type: &RelationType::empty(),
+                        allow_aggregates: false,
+                        allow_subqueries: true,
+                        allow_parameters: true,
+                        allow_windows: false,
+                    };
+                    let expr = query::plan_expr(&ecx, &expr)?.type_as_any(&ecx)?;
+                    let expr_typ = ecx.scalar_type(&expr);
+                    typeconv::plan_cast(&ecx, CastContext::Assignment, expr, &typ).map_err(|_| {
+                        sql_err!(
+                            "return type mismatch in function declared to return {}: body returns {}",
+                            ecx.humanize_scalar_type(&typ, false),
+                            ecx.humanize_scalar_type(&expr_typ, false),
+                        )
+                    })
+                })?;
+                expr.parameterize_outer_columns(0);
+                expr.splice_parameters(&args, 0);
+                Ok(expr)
+            });
+            Func::Scalar(vec![FuncImpl {
+                oid: oid::INVALID_OID,
+                params: param_list,
+                return_type,
+                op,
+            }])
+        }
+        UserFuncReturnType::Table(columns) => {
+            let (column_names, column_types): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
+            let return_type = match &column_types[..] {
+                [typ] => ReturnType::set_of(ParamType::Plain(typ.clone())),
+                _ => ReturnType::set_of(ParamType::RecordAny),
+            };
+            let op = Operation::variadic(move |ecx, args| {
+                let mut expr = def.plan(ecx.qcx, |qcx, body| {
+                    let FunctionBody::Atomic(query) = body else {
+                        sql_bail!("set-returning functions must have a BEGIN ATOMIC body");
+                    };
+                    let (expr, _scope) = query::plan_nested_query(qcx, query)?;
+                    let arity = expr.arity();
+                    if arity != column_types.len() {
+                        sql_bail!(
+                            "return type mismatch in function declared to return {} columns: \
+                             body returns {} columns",
+                            column_types.len(),
+                            arity,
+                        );
+                    }
+                    query::cast_relation(qcx, CastContext::Assignment, expr, &column_types).map_err(
+                        |e| {
+                            sql_err!(
+                                "return type mismatch in function: column {} is declared as {} \
+                                 but body returns {}",
+                                column_names[e.column].quoted(),
+                                qcx.humanize_scalar_type(&e.target_type, false),
+                                qcx.humanize_scalar_type(&e.source_type, false),
+                            )
+                        },
+                    )
+                })?;
+                expr.parameterize_outer_columns(0);
+                expr.splice_parameters(&args, 0);
+                Ok(TableFuncPlan {
+                    expr,
+                    column_names: column_names.clone(),
+                })
+            });
+            Func::Table(vec![FuncImpl {
+                oid: oid::INVALID_OID,
+                params: param_list,
+                return_type,
+                op,
+            }])
+        }
+    };
+
+    // Plan an invocation of the function whose arguments are the parameters
+    // of an enclosing statement, which validates the body.
+    let mut scx = scx.clone();
+    scx.param_types = RefCell::new(
+        param_types
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| (i + 1, ty.clone()))
+            .collect(),
+    );
+    let qcx = QueryContext::root(&scx, QueryLifetime::View);
+    let ecx = ExprContext {
+        qcx: &qcx,
+        name: "function body",
+        scope: &Scope::empty(),
+        relation_type: &RelationType::empty(),
+        allow_aggregates: false,
+        allow_subqueries: true,
+        allow_parameters: true,
+        allow_windows: false,
+    };
+    let args = || {
+        (1..=param_types.len())
+            .map(|i| HirScalarExpr::Parameter(i).into())
+            .collect()
+    };
+    match &func {
+        Func::Scalar(impls) => {
+            let imp = impls.into_element();
+            (imp.op.0)(&ecx, args(), &imp.params, vec![])?;
+        }
+        Func::Table(impls) => {
+            let imp = impls.into_element();
+            (imp.op.0)(&ecx, args(), &imp.params, vec![])?;
+        }
+        _ => unreachable!("user-defined functions are scalar or table functions"),
+    }
+
+    Ok(func)
+}
+
 /// Describes a single function's implementation.
 pub struct FuncImpl<R> {
     pub oid: u32,
@@ -686,6 +924,9 @@ pub enum ParamType {
     /// A pseudotype permitting any array type, permitting other "Compatibility"-type
     /// parameters to find the best common type.
     ArrayAnyCompatible,
+    /// A pseudotype permitting any enum type, requiring other "Any"-type
+    /// parameters to be of the same type.
+    EnumAny,
     /// An pseudotype permitting any list type, requiring other "Any"-type
     /// parameters to be of the same type.
     ListAny,
@@ -741,6 +982,7 @@ impl ParamType {
         match self {
             Any | AnyElement | AnyCompatible | ListElementAnyCompatible => true,
             ArrayAny | ArrayAnyCompatible => matches!(t, Array(..) | Int2Vector),
+            EnumAny => matches!(t, Enum { .. }),
             ListAny | ListAnyCompatible => matches!(t, List { .. }),
             MapAny | MapAnyCompatible => matches!(t, Map { .. }),
             RangeAny | RangeAnyCompatible => matches!(t, Range { .. }),
@@ -787,6 +1029,7 @@ impl ParamType {
             | ArrayAny
             | ArrayAnyCompatible
             | AnyCompatible
+            | EnumAny
             | ListAny
             | ListAnyCompatible
             | ListElementAnyCompatible
@@ -818,6 +1061,7 @@ impl ParamType {
             ParamType::AnyElement => "anyelement",
             ParamType::ArrayAny => "anyarray",
             ParamType::ArrayAnyCompatible => "anycompatiblearray",
+            ParamType::EnumAny => "anyenum",
             ParamType::Internal => "internal",
             ParamType::ListAny => "list",
             ParamType::ListAnyCompatible => "anycompatiblelist",
@@ -860,7 +1104,7 @@ impl From<ScalarBaseType> for ParamType {
     fn from(s: ScalarBaseType) -> ParamType {
         use ScalarBaseType::*;
         let s = match s {
-            Array | List | Map | Record | Range => {
+            Array | List | Map | Record | Range | Enum => {
                 panic!("use polymorphic parameters rather than {:?}", s);
             }
             AclItem => ScalarType::AclItem,
@@ -894,6 +1138,15 @@ impl From<ScalarBaseType> for ParamType {
             Int2Vector => ScalarType::Int2Vector,
             MzTimestamp => ScalarType::MzTimestamp,
             MzAclItem => ScalarType::MzAclItem,
+            Inet => ScalarType::Inet,
+            Cidr => ScalarType::Cidr,
+            MacAddr => ScalarType::MacAddr,
+            MacAddr8 => ScalarType::MacAddr8,
+            TsVector => ScalarType::TsVector,
+            TsQuery => ScalarType::TsQuery,
+            JsonPath => ScalarType::JsonPath,
+            Geometry => ScalarType::Geometry,
+            Geography => ScalarType::Geography,
         };
         ParamType::Plain(s)
     }
@@ -1325,7 +1578,7 @@ impl TryFrom<&ParamType> for PolymorphicCompatClass {
         use ParamType::*;
 
         Ok(match param {
-            AnyElement | ArrayAny | ListAny | MapAny | NonVecAny | RangeAny => {
+            AnyElement | ArrayAny | EnumAny | ListAny | MapAny | NonVecAny | RangeAny => {
                 PolymorphicCompatClass::Any
             }
             ArrayAnyCompatible | AnyCompatible | RangeAnyCompatible | NonVecAnyCompatible => {
@@ -1398,7 +1651,7 @@ impl PolymorphicSolution {
 
         self.seen.push(match param {
             // These represent the keys of their respective compatibility classes.
-            AnyElement | AnyCompatible | ListAnyCompatible |  MapAnyCompatible | NonVecAny | RecordAny => seen,
+            AnyElement | AnyCompatible | EnumAny | ListAnyCompatible |  MapAnyCompatible | NonVecAny | RecordAny => seen,
             MapAny => seen.map_coerced(|array| array.unwrap_map_value_type().clone()),
             ListAny => seen.map_coerced(|array| array.unwrap_list_element_type().clone()),
             ArrayAny | ArrayAnyCompatible => seen.map_coerced(|array| array.unwrap_array_element_type().clone()),
@@ -1518,9 +1771,8 @@ impl PolymorphicSolution {
         );
 
         match param {
-            AnyElement | AnyCompatible | ListAnyCompatible | MapAnyCompatible | NonVecAny => {
-                self.key.clone()
-            }
+            AnyElement | AnyCompatible | EnumAny | ListAnyCompatible | MapAnyCompatible
+            | NonVecAny => self.key.clone(),
             ArrayAny | ArrayAnyCompatible => self
                 .key
                 .as_ref()
@@ -1787,6 +2039,9 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
             params!(Float32) => UnaryFunc::AbsFloat32(func::AbsFloat32) => Float32, 1394;
             params!(Float64) => UnaryFunc::AbsFloat64(func::AbsFloat64) => Float64, 1395;
         },
+        "abbrev" => Scalar {
+            params!(Inet) => UnaryFunc::InetAbbrev(func::InetAbbrev) => String, 598;
+        },
         "aclexplode" => Table {
             params!(ScalarType::Array(Box::new(ScalarType::AclItem))) =>  Operation::unary(move |_ecx, aclitems| {
                 Ok(TableFuncPlan {
@@ -1875,6 +2130,9 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
             params!(Bytes) => UnaryFunc::BitLengthBytes(func::BitLengthBytes) => Int32, 1810;
             params!(String) => UnaryFunc::BitLengthString(func::BitLengthString) => Int32, 1811;
         },
+        "broadcast" => Scalar {
+            params!(Inet) => UnaryFunc::InetBroadcast(func::InetBroadcast) => Inet, 698;
+        },
         "btrim" => Scalar {
             params!(String) => UnaryFunc::TrimWhitespace(func::TrimWhitespace) => String, 885;
             params!(String, String) => BinaryFunc::Trim => String, 884;
@@ -1961,6 +2219,15 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "cot" => Scalar {
             params!(Float64) => UnaryFunc::Cot(func::Cot) => Float64, 1607;
         },
+        "corr" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("corr")) => Float64, 2829;
+        },
+        "covar_pop" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("covar_pop")) => Float64, 2827;
+        },
+        "covar_samp" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("covar_samp")) => Float64, 2828;
+        },
         "current_schema" => Scalar {
             // TODO: this should be `name`. This is tricky in Materialize
             // because `name` truncates to 63 characters but Materialize does
@@ -2072,6 +2339,9 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
             params!(Float64) => UnaryFunc::Exp(func::Exp) => Float64, 1347;
             params!(Numeric) => UnaryFunc::ExpNumeric(func::ExpNumeric) => Numeric, 1732;
         },
+        "family" => Scalar {
+            params!(Inet) => UnaryFunc::InetFamily(func::InetFamily) => Int32, 711;
+        },
         "floor" => Scalar {
             params!(Float32) => UnaryFunc::FloorFloat32(func::FloorFloat32) => Float32, oid::FUNC_FLOOR_F32_OID;
             params!(Float64) => UnaryFunc::FloorFloat64(func::FloorFloat64) => Float64, 2309;
@@ -2128,6 +2398,12 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
             params!(String, String, String) => VariadicFunc::HmacString => Bytes, oid::FUNC_PG_HMAC_STRING;
             params!(Bytes, Bytes, String) => VariadicFunc::HmacBytes => Bytes, oid::FUNC_PG_HMAC_BYTES;
         },
+        "host" => Scalar {
+            params!(Inet) => UnaryFunc::InetHost(func::InetHost) => String, 699;
+        },
+        "hostmask" => Scalar {
+            params!(Inet) => UnaryFunc::InetHostmask(func::InetHostmask) => Inet, 1362;
+        },
         "initcap" => Scalar {
             params!(String) => UnaryFunc::Initcap(func::Initcap) => String, 872;
         },
@@ -2190,6 +2466,34 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
                 })
             }) => Jsonb, 3273;
         },
+        "jsonb_path_exists" => Scalar {
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathExists, false)
+                => Bool, oid::FUNC_JSONB_PATH_EXISTS_DEFAULT_VARS_OID;
+            params!(Jsonb, JsonPath, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathExists, false)
+                => Bool, oid::FUNC_JSONB_PATH_EXISTS_DEFAULT_SILENT_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathExists => Bool, 4005;
+        },
+        "jsonb_path_match" => Scalar {
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathMatch, false)
+                => Bool, oid::FUNC_JSONB_PATH_MATCH_DEFAULT_VARS_OID;
+            params!(Jsonb, JsonPath, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathMatch, false)
+                => Bool, oid::FUNC_JSONB_PATH_MATCH_DEFAULT_SILENT_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathMatch => Bool, 4009;
+        },
+        "jsonb_path_query_array" => Scalar {
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathQueryArray, false)
+                => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_DEFAULT_VARS_OID;
+            params!(Jsonb, JsonPath, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathQueryArray, false)
+                => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_DEFAULT_SILENT_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathQueryArray => Jsonb, 4007;
+        },
+        "jsonb_path_query_first" => Scalar {
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathQueryFirst, false)
+                => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_DEFAULT_VARS_OID;
+            params!(Jsonb, JsonPath, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathQueryFirst, false)
+                => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_DEFAULT_SILENT_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathQueryFirst => Jsonb, 4008;
+        },
         "jsonb_pretty" => Scalar {
             params!(Jsonb) => UnaryFunc::JsonbPretty(func::JsonbPretty) => String, 3306;
         },
@@ -2257,6 +2561,9 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "make_timestamp" => Scalar {
             params!(Int64, Int64, Int64, Int64, Int64, Float64) => VariadicFunc::MakeTimestamp => Timestamp, 3461;
         },
+        "masklen" => Scalar {
+            params!(Inet) => UnaryFunc::InetMasklen(func::InetMasklen) => Int32, 697;
+        },
         "md5" => Scalar {
             params!(String) => Operation::unary(move |_ecx, input| {
                 let algorithm = HirScalarExpr::literal(Datum::String("md5"), ScalarType::String);
@@ -2278,6 +2585,12 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
             params!(UInt32, UInt32) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => UInt32, oid::FUNC_MOD_UINT32_OID;
             params!(UInt64, UInt64) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => UInt64, oid::FUNC_MOD_UINT64_OID;
         },
+        "netmask" => Scalar {
+            params!(Inet) => UnaryFunc::InetNetmask(func::InetNetmask) => Inet, 696;
+        },
+        "network" => Scalar {
+            params!(Inet) => UnaryFunc::InetNetwork(func::InetNetwork) => Cidr, 683;
+        },
         "now" => Scalar {
             params!() => UnmaterializableFunc::CurrentTimestamp => TimestampTz, 1299;
         },
@@ -2537,6 +2850,13 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
                 Ok(HirScalarExpr::literal(Datum::String(&name), ScalarType::String))
             }) => String, 1619;
         },
+        "plainto_tsquery" => Scalar {
+            params!(String) => Operation::unary(|_ecx, text| {
+                let config = HirScalarExpr::literal(Datum::String("english"), ScalarType::String);
+                Ok(config.call_binary(text, BinaryFunc::PlainToTsQuery))
+            }) => TsQuery, 3751;
+            params!(String, String) => BinaryFunc::PlainToTsQuery => TsQuery, 3747;
+        },
         "position" => Scalar {
             params!(String, String) => BinaryFunc::Position => Int32, 849;
         },
@@ -2556,6 +2876,33 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "repeat" => Scalar {
             params!(String, Int32) => BinaryFunc::RepeatString => String, 1622;
         },
+        "regr_avgx" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_avgx")) => Float64, 2822;
+        },
+        "regr_avgy" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_avgy")) => Float64, 2823;
+        },
+        "regr_count" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_count")) => Int64, 2818;
+        },
+        "regr_intercept" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_intercept")) => Float64, 2826;
+        },
+        "regr_r2" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_r2")) => Float64, 2824;
+        },
+        "regr_slope" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_slope")) => Float64, 2825;
+        },
+        "regr_sxx" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_sxx")) => Float64, 2819;
+        },
+        "regr_sxy" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_sxy")) => Float64, 2821;
+        },
+        "regr_syy" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_syy")) => Float64, 2820;
+        },
         "regexp_match" => Scalar {
             params!(String, String) => VariadicFunc::RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)), 3396;
             params!(String, String, String) => VariadicFunc::RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)), 3397;
@@ -2576,6 +2923,10 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
             params!(String) => UnaryFunc::TrimTrailingWhitespace(func::TrimTrailingWhitespace) => String, 882;
             params!(String, String) => BinaryFunc::TrimTrailing => String, 876;
         },
+        "set_masklen" => Scalar {
+            params!(Inet, Int32) => BinaryFunc::InetSetMasklen => Inet, 605;
+            params!(Cidr, Int32) => BinaryFunc::CidrSetMasklen => Cidr, 635;
+        },
         "sha224" => Scalar {
             params!(Bytes) => digest("sha224") => Bytes, 3419;
         },
@@ -2721,6 +3072,20 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "to_timestamp" => Scalar {
             params!(Float64) => UnaryFunc::ToTimestamp(func::ToTimestamp) => TimestampTz, 1158;
         },
+        "to_tsquery" => Scalar {
+            params!(String) => Operation::unary(|_ecx, query| {
+                let config = HirScalarExpr::literal(Datum::String("english"), ScalarType::String);
+                Ok(config.call_binary(query, BinaryFunc::ToTsQuery))
+            }) => TsQuery, 3750;
+            params!(String, String) => BinaryFunc::ToTsQuery => TsQuery, 3746;
+        },
+        "to_tsvector" => Scalar {
+            params!(String) => Operation::unary(|_ecx, document| {
+                let config = HirScalarExpr::literal(Datum::String("english"), ScalarType::String);
+                Ok(config.call_binary(document, BinaryFunc::ToTsVector))
+            }) => TsVector, 3749;
+            params!(String, String) => BinaryFunc::ToTsVector => TsVector, 3745;
+        },
         "translate" => Scalar {
             params!(String, String, String) => VariadicFunc::Translate => String, 878;
         },
@@ -2729,6 +3094,9 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
             params!(Float64) => UnaryFunc::TruncFloat64(func::TruncFloat64) => Float64, 1343;
             params!(Numeric) => UnaryFunc::TruncNumeric(func::TruncNumeric) => Numeric, 1710;
         },
+        "ts_rank" => Scalar {
+            params!(TsVector, TsQuery) => BinaryFunc::TsRank => Float32, 3706;
+        },
         "tsrange" => Scalar {
             params!(Timestamp, Timestamp) => Operation::variadic(|_ecx, mut exprs| {
                 exprs.push(HirScalarExpr::literal(Datum::String("[)"), ScalarType::String));
@@ -2805,6 +3173,13 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "version" => Scalar {
             params!() => UnmaterializableFunc::Version => String, 89;
         },
+        "websearch_to_tsquery" => Scalar {
+            params!(String) => Operation::unary(|_ecx, query| {
+                let config = HirScalarExpr::literal(Datum::String("english"), ScalarType::String);
+                Ok(config.call_binary(query, BinaryFunc::WebsearchToTsQuery))
+            }) => TsQuery, 5007;
+            params!(String, String) => BinaryFunc::WebsearchToTsQuery => TsQuery, 5006;
+        },
 
         // Internal conversion stubs.
         "aclitemin" => Scalar {
@@ -2925,6 +3300,27 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "uuid_in" => Scalar {
             params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("uuid_in")) => Uuid, 2952;
         },
+        "inet_in" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("inet_in")) => Inet, 910;
+        },
+        "cidr_in" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("cidr_in")) => Cidr, 1267;
+        },
+        "macaddr_in" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("macaddr_in")) => MacAddr, 436;
+        },
+        "macaddr8_in" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("macaddr8_in")) => MacAddr8, 4110;
+        },
+        "tsvectorin" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("tsvectorin")) => TsVector, 3610;
+        },
+        "tsqueryin" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("tsqueryin")) => TsQuery, 3612;
+        },
+        "jsonpath_in" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("jsonpath_in")) => JsonPath, 4001;
+        },
         "boolrecv" => Scalar {
             params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("boolrecv")) => Bool, 2436;
         },
@@ -3000,6 +3396,27 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "uuid_recv" => Scalar {
             params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("uuid_recv")) => Uuid, 2961;
         },
+        "inet_recv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("inet_recv")) => Inet, 2496;
+        },
+        "cidr_recv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("cidr_recv")) => Cidr, 2498;
+        },
+        "macaddr_recv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("macaddr_recv")) => MacAddr, 2494;
+        },
+        "macaddr8_recv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("macaddr8_recv")) => MacAddr8, 4113;
+        },
+        "tsvectorrecv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("tsvectorrecv")) => TsVector, 3639;
+        },
+        "tsqueryrecv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("tsqueryrecv")) => TsQuery, 3641;
+        },
+        "jsonpath_recv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("jsonpath_recv")) => JsonPath, 4002;
+        },
         "varcharrecv" => Scalar {
             params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("varcharrecv")) => VarChar, 2432;
         },
@@ -3149,6 +3566,26 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
                 Ok((e, AggregateFunc::JsonbObjectAgg { order_by }))
             }) => Jsonb, 3270;
         },
+        "mode" => Aggregate {
+            params!(AnyElement) => Operation::unary_ordered(|_ecx, e, order_by| {
+                let desc = within_group_desc(&order_by);
+                Ok((e, AggregateFunc::Mode { desc }))
+            }) => AnyElement, 3984;
+        },
+        "percentile_cont" => Aggregate {
+            params!(Float64, Float64) => Operation::binary_ordered(|_ecx, fraction, e, order_by| {
+                let fraction = percentile_fraction("percentile_cont", fraction)?;
+                let desc = within_group_desc(&order_by);
+                Ok((e, AggregateFunc::PercentileCont { fraction, desc }))
+            }) => Float64, 3974;
+        },
+        "percentile_disc" => Aggregate {
+            params!(Float64, AnyElement) => Operation::binary_ordered(|_ecx, fraction, e, order_by| {
+                let fraction = percentile_fraction("percentile_disc", fraction)?;
+                let desc = within_group_desc(&order_by);
+                Ok((e, AggregateFunc::PercentileDisc { fraction, desc }))
+            }) => AnyElement, 3972;
+        },
         "string_agg" => Aggregate {
             params!(String, String) => Operation::binary_ordered(|_ecx, value, sep, order_by| {
                 let e = HirScalarExpr::CallVariadic {
@@ -3190,6 +3627,15 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "dense_rank" => ScalarWindow {
             params!() => ScalarWindowFunc::DenseRank => Int64, 3102;
         },
+        "percent_rank" => ScalarWindow {
+            params!() => ScalarWindowFunc::PercentRank => Float64, 3103;
+        },
+        "cume_dist" => ScalarWindow {
+            params!() => ScalarWindowFunc::CumeDist => Float64, 3104;
+        },
+        "ntile" => ValueWindow {
+            params!(Int32) => ValueWindowFunc::Ntile => Int32, 3105;
+        },
         "lag" => ValueWindow {
             // All args are encoded into a single record to be handled later
             params!(AnyElement) => Operation::unary(|ecx, e| {
@@ -3260,6 +3706,18 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "last_value" => ValueWindow {
             params!(AnyElement) => ValueWindowFunc::LastValue => AnyElement, 3113;
         },
+        "nth_value" => ValueWindow {
+            // All args are encoded into a single record to be handled later
+            params!(AnyElement, Int32) => Operation::binary(|_ecx, e, n| {
+                let e = HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::RecordCreate {
+                        field_names: vec![ColumnName::from("expr"), ColumnName::from("n")]
+                    },
+                    exprs: vec![e, n],
+                };
+                Ok((e, ValueWindowFunc::NthValue))
+            }) => AnyElement, 3114;
+        },
 
         // Table functions.
         "generate_series" => Table {
@@ -3388,6 +3846,35 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
                 })
             }) => ReturnType::set_of(String.into()), 3931;
         },
+        "jsonb_path_query" => Table {
+            params!(Jsonb, JsonPath) => Operation::variadic(move |_ecx, exprs| {
+                Ok(TableFuncPlan {
+                    expr: HirRelationExpr::CallTable {
+                        func: TableFunc::JsonbPathQuery,
+                        exprs: jsonb_path_args(exprs, false),
+                    },
+                    column_names: vec!["jsonb_path_query".into()],
+                })
+            }) => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_DEFAULT_VARS_OID;
+            params!(Jsonb, JsonPath, Jsonb) => Operation::variadic(move |_ecx, exprs| {
+                Ok(TableFuncPlan {
+                    expr: HirRelationExpr::CallTable {
+                        func: TableFunc::JsonbPathQuery,
+                        exprs: jsonb_path_args(exprs, false),
+                    },
+                    column_names: vec!["jsonb_path_query".into()],
+                })
+            }) => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_DEFAULT_SILENT_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => Operation::variadic(move |_ecx, exprs| {
+                Ok(TableFuncPlan {
+                    expr: HirRelationExpr::CallTable {
+                        func: TableFunc::JsonbPathQuery,
+                        exprs: jsonb_path_args(exprs, false),
+                    },
+                    column_names: vec!["jsonb_path_query".into()],
+                })
+            }) => ReturnType::set_of(Jsonb.into()), 4006;
+        },
         // Note that these implementations' input to `generate_series` is
         // contrived to match Flink's expected values. There are other,
         // equally valid windows we could generate.
@@ -3498,6 +3985,15 @@ pub static MZ_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
     use ParamType::*;
     use ScalarBaseType::*;
     builtins! {
+        "approx_count_distinct" => Aggregate {
+            params!(Any) => AggregateFunc::ApproxCountDistinct => Int64, oid::FUNC_APPROX_COUNT_DISTINCT_OID;
+        },
+        "approx_percentile" => Aggregate {
+            params!(Float64, Float64) => Operation::binary(|_ecx, e, fraction| {
+                let fraction = percentile_fraction("approx_percentile", fraction)?;
+                Ok((e, AggregateFunc::ApproxPercentile { fraction }))
+            }) => Float64, oid::FUNC_APPROX_PERCENTILE_OID;
+        },
         "constant_time_eq" => Scalar {
             params!(Bytes, Bytes) => BinaryFunc::ConstantTimeEqBytes => Bool, oid::FUNC_CONSTANT_TIME_EQ_BYTES_OID;
             params!(String, String) => BinaryFunc::ConstantTimeEqString => Bool, oid::FUNC_CONSTANT_TIME_EQ_STRING_OID;
@@ -3564,6 +4060,9 @@ pub static MZ_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         },
         // We can't use the `privilege_fn!` macro because the macro relies on the object having an
         // OID, and clusters do not have OIDs.
+        "geohash_neighbors" => Scalar {
+            params!(String) => UnaryFunc::GeoHashNeighbors(func::GeoHashNeighbors) => ScalarType::Array(Box::new(ScalarType::String)), oid::FUNC_GEOHASH_NEIGHBORS_OID;
+        },
         "has_cluster_privilege" => Scalar {
             params!(String, String, String) => sql_impl_func("has_cluster_privilege(mz_internal.mz_role_oid($1), $2, $3)") => Bool, oid::FUNC_HAS_CLUSTER_PRIVILEGE_TEXT_TEXT_TEXT_OID;
             params!(Oid, String, String) => sql_impl_func(&format!("
@@ -3857,6 +4356,105 @@ pub static MZ_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
             params!(String) => UnaryFunc::SeahashString(func::SeahashString) => UInt32, oid::FUNC_SEAHASH_STRING_OID;
             params!(Bytes) => UnaryFunc::SeahashBytes(func::SeahashBytes) => UInt32, oid::FUNC_SEAHASH_BYTES_OID;
         },
+        "st_asbinary" => Scalar {
+            params!(Geometry) => UnaryFunc::StAsBinary(func::StAsBinary) => Bytes, oid::FUNC_ST_ASBINARY_GEOMETRY_OID;
+            params!(Geography) => UnaryFunc::StAsBinary(func::StAsBinary) => Bytes, oid::FUNC_ST_ASBINARY_GEOGRAPHY_OID;
+        },
+        "st_asewkt" => Scalar {
+            params!(Geometry) => UnaryFunc::StAsEwkt(func::StAsEwkt) => String, oid::FUNC_ST_ASEWKT_GEOMETRY_OID;
+            params!(Geography) => UnaryFunc::StAsEwkt(func::StAsEwkt) => String, oid::FUNC_ST_ASEWKT_GEOGRAPHY_OID;
+        },
+        "st_asgeojson" => Scalar {
+            params!(Geometry) => UnaryFunc::StAsGeoJson(func::StAsGeoJson) => String, oid::FUNC_ST_ASGEOJSON_GEOMETRY_OID;
+            params!(Geography) => UnaryFunc::StAsGeoJson(func::StAsGeoJson) => String, oid::FUNC_ST_ASGEOJSON_GEOGRAPHY_OID;
+        },
+        "st_astext" => Scalar {
+            params!(Geometry) => UnaryFunc::StAsText(func::StAsText) => String, oid::FUNC_ST_ASTEXT_GEOMETRY_OID;
+            params!(Geography) => UnaryFunc::StAsText(func::StAsText) => String, oid::FUNC_ST_ASTEXT_GEOGRAPHY_OID;
+        },
+        "st_contains" => Scalar {
+            params!(Geometry, Geometry) => BinaryFunc::StContains => Bool, oid::FUNC_ST_CONTAINS_OID;
+        },
+        "st_distance" => Scalar {
+            params!(Geometry, Geometry) => BinaryFunc::StDistance => Float64, oid::FUNC_ST_DISTANCE_GEOMETRY_OID;
+            // Distances between geographies are measured in meters along the
+            // surface of the earth.
+            params!(Geography, Geography) => BinaryFunc::StDistanceSphere => Float64, oid::FUNC_ST_DISTANCE_GEOGRAPHY_OID;
+        },
+        "st_distancesphere" => Scalar {
+            params!(Geometry, Geometry) => Operation::binary(|_ecx, a, b| {
+                let a = a.call_unary(UnaryFunc::CastGeometryToGeography(func::CastGeometryToGeography));
+                let b = b.call_unary(UnaryFunc::CastGeometryToGeography(func::CastGeometryToGeography));
+                Ok(a.call_binary(b, BinaryFunc::StDistanceSphere))
+            }) => Float64, oid::FUNC_ST_DISTANCESPHERE_OID;
+        },
+        "st_dwithin" => Scalar {
+            params!(Geometry, Geometry, Float64) => Operation::variadic(|_ecx, exprs| {
+                let [a, b, distance]: [HirScalarExpr; 3] = exprs.try_into().expect("st_dwithin takes three arguments");
+                Ok(a.call_binary(b, BinaryFunc::StDistance).call_binary(distance, BinaryFunc::Lte))
+            }) => Bool, oid::FUNC_ST_DWITHIN_GEOMETRY_OID;
+            params!(Geography, Geography, Float64) => Operation::variadic(|_ecx, exprs| {
+                let [a, b, distance]: [HirScalarExpr; 3] = exprs.try_into().expect("st_dwithin takes three arguments");
+                Ok(a.call_binary(b, BinaryFunc::StDistanceSphere).call_binary(distance, BinaryFunc::Lte))
+            }) => Bool, oid::FUNC_ST_DWITHIN_GEOGRAPHY_OID;
+        },
+        "st_geogfromtext" => Scalar {
+            params!(String) => UnaryFunc::CastStringToGeography(func::CastStringToGeography) => Geography, oid::FUNC_ST_GEOGFROMTEXT_OID;
+        },
+        "st_geohash" => Scalar {
+            params!(Geometry) => UnaryFunc::StGeoHash(func::StGeoHash) => String, oid::FUNC_ST_GEOHASH_OID;
+            params!(Geometry, Int32) => BinaryFunc::StGeoHashPrecision => String, oid::FUNC_ST_GEOHASH_PRECISION_OID;
+        },
+        "st_geometrytype" => Scalar {
+            params!(Geometry) => UnaryFunc::StGeometryType(func::StGeometryType) => String, oid::FUNC_ST_GEOMETRYTYPE_OID;
+        },
+        "st_geomfromgeojson" => Scalar {
+            params!(String) => UnaryFunc::StGeomFromGeoJson(func::StGeomFromGeoJson) => Geometry, oid::FUNC_ST_GEOMFROMGEOJSON_OID;
+        },
+        "st_geomfromtext" => Scalar {
+            params!(String) => UnaryFunc::CastStringToGeometry(func::CastStringToGeometry) => Geometry, oid::FUNC_ST_GEOMFROMTEXT_OID;
+            params!(String, Int32) => Operation::binary(|_ecx, text, srid| {
+                Ok(text
+                    .call_unary(UnaryFunc::CastStringToGeometry(func::CastStringToGeometry))
+                    .call_binary(srid, BinaryFunc::StSetSrid))
+            }) => Geometry, oid::FUNC_ST_GEOMFROMTEXT_SRID_OID;
+        },
+        "st_geomfromwkb" => Scalar {
+            params!(Bytes) => UnaryFunc::StGeomFromWkb(func::StGeomFromWkb) => Geometry, oid::FUNC_ST_GEOMFROMWKB_OID;
+            params!(Bytes, Int32) => Operation::binary(|_ecx, wkb, srid| {
+                Ok(wkb
+                    .call_unary(UnaryFunc::StGeomFromWkb(func::StGeomFromWkb))
+                    .call_binary(srid, BinaryFunc::StSetSrid))
+            }) => Geometry, oid::FUNC_ST_GEOMFROMWKB_SRID_OID;
+        },
+        "st_intersects" => Scalar {
+            params!(Geometry, Geometry) => BinaryFunc::StIntersects => Bool, oid::FUNC_ST_INTERSECTS_GEOMETRY_OID;
+            params!(Geography, Geography) => BinaryFunc::StIntersects => Bool, oid::FUNC_ST_INTERSECTS_GEOGRAPHY_OID;
+        },
+        "st_makepoint" => Scalar {
+            params!(Float64, Float64) => BinaryFunc::StMakePoint => Geometry, oid::FUNC_ST_MAKEPOINT_OID;
+        },
+        "st_point" => Scalar {
+            params!(Float64, Float64) => BinaryFunc::StMakePoint => Geometry, oid::FUNC_ST_POINT_OID;
+        },
+        "st_setsrid" => Scalar {
+            params!(Geometry, Int32) => BinaryFunc::StSetSrid => Geometry, oid::FUNC_ST_SETSRID_OID;
+        },
+        "st_srid" => Scalar {
+            params!(Geometry) => UnaryFunc::StSrid(func::StSrid) => Int32, oid::FUNC_ST_SRID_GEOMETRY_OID;
+            params!(Geography) => UnaryFunc::StSrid(func::StSrid) => Int32, oid::FUNC_ST_SRID_GEOGRAPHY_OID;
+        },
+        "st_within" => Scalar {
+            params!(Geometry, Geometry) => Operation::binary(|_ecx, a, b| {
+                Ok(b.call_binary(a, BinaryFunc::StContains))
+            }) => Bool, oid::FUNC_ST_WITHIN_OID;
+        },
+        "st_x" => Scalar {
+            params!(Geometry) => UnaryFunc::StX(func::StX) => Float64, oid::FUNC_ST_X_OID;
+        },
+        "st_y" => Scalar {
+            params!(Geometry) => UnaryFunc::StY(func::StY) => Float64, oid::FUNC_ST_Y_OID;
+        },
         "starts_with" => Scalar {
             params!(String, String) => BinaryFunc::StartsWith => Bool, 3696;
         },
@@ -4414,6 +5012,52 @@ fn array_to_string(
     })
 }
 
+/// Plans the fraction argument of `percentile_cont` or `percentile_disc`, which
+/// must be a constant between 0 and 1.
+fn percentile_fraction(
+    name: &str,
+    fraction: HirScalarExpr,
+) -> Result<OrderedFloat<f64>, PlanError> {
+    match fraction.into_literal_float64() {
+        Some(fraction) if (0.0..=1.0).contains(&fraction) => Ok(OrderedFloat(fraction)),
+        Some(fraction) => sql_bail!("percentile value {} is not between 0 and 1", fraction),
+        None => bail_unsupported!(format!("non-constant {} fraction", name)),
+    }
+}
+
+/// Reports whether the `WITHIN GROUP (ORDER BY ...)` clause of an ordered-set
+/// aggregate sorts its input in descending order.
+fn within_group_desc(order_by: &[ColumnOrder]) -> bool {
+    order_by.first().is_some_and(|o| o.desc)
+}
+
+/// Fills in the optional `vars` and `silent` arguments of a SQL/JSON path
+/// function with an empty object and `silent`, if they are omitted.
+fn jsonb_path_args(mut exprs: Vec<HirScalarExpr>, silent: bool) -> Vec<HirScalarExpr> {
+    if exprs.len() < 3 {
+        exprs.push(HirScalarExpr::literal(
+            Datum::empty_map(),
+            ScalarType::Jsonb,
+        ));
+    }
+    if exprs.len() < 4 {
+        exprs.push(HirScalarExpr::literal(
+            Datum::from(silent),
+            ScalarType::Bool,
+        ));
+    }
+    exprs
+}
+
+fn jsonb_path_func(func: VariadicFunc, silent: bool) -> Operation<HirScalarExpr> {
+    Operation::variadic(move |_ecx, exprs| {
+        Ok(HirScalarExpr::CallVariadic {
+            func: func.clone(),
+            exprs: jsonb_path_args(exprs, silent),
+        })
+    })
+}
+
 /// Correlates an operator with all of its implementations.
 pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
     use BinaryFunc::*;
@@ -4592,6 +5236,7 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(UInt32, UInt32) => BitShiftLeftUInt32 => UInt32, oid::FUNC_SHIFT_LEFT_UINT32;
             params!(UInt64, UInt32) => BitShiftLeftUInt64 => UInt64, oid::FUNC_SHIFT_LEFT_UINT64;
             params!(RangeAny, RangeAny) => RangeBefore => Bool, 3893;
+            params!(Inet, Inet) => InetContainedBy => Bool, 931;
         },
         ">>" => Scalar {
             params!(Int16, Int32) => BitShiftRightInt16 => Int16, 1879;
@@ -4601,6 +5246,13 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(UInt32, UInt32) => BitShiftRightUInt32 => UInt32, oid::FUNC_SHIFT_RIGHT_UINT32;
             params!(UInt64, UInt32) => BitShiftRightUInt64 => UInt64, oid::FUNC_SHIFT_RIGHT_UINT64;
             params!(RangeAny, RangeAny) => RangeAfter => Bool, 3894;
+            params!(Inet, Inet) => InetContains => Bool, 933;
+        },
+        "<<=" => Scalar {
+            params!(Inet, Inet) => InetContainedByOrEq => Bool, 932;
+        },
+        ">>=" => Scalar {
+            params!(Inet, Inet) => InetContainsOrEq => Bool, 934;
         },
 
         // ILIKE
@@ -4827,6 +5479,7 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
         },
         "&&" => Scalar {
             params!(RangeAny, RangeAny) => BinaryFunc::RangeOverlaps => Bool, 3888;
+            params!(Inet, Inet) => BinaryFunc::InetOverlaps => Bool, 3552;
         },
         "&<" => Scalar {
             params!(RangeAny, RangeAny) => BinaryFunc::RangeOverleft => Bool, 3895;
@@ -4837,6 +5490,16 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
         "-|-" => Scalar {
             params!(RangeAny, RangeAny) => BinaryFunc::RangeAdjacent => Bool, 3897;
         },
+        "@@" => Scalar {
+            params!(TsVector, TsQuery) => BinaryFunc::TsMatch => Bool, 3636;
+            params!(TsQuery, TsVector) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(rhs.call_binary(lhs, BinaryFunc::TsMatch))
+            }) => Bool, 3637;
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathMatch, true) => Bool, 4013;
+        },
+        "@?" => Scalar {
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathExists, true) => Bool, 4012;
+        },
 
         // COMPARISON OPS
         "<" => Scalar {
@@ -4856,6 +5519,9 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Lt => Bool, 2062;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Lt => Bool, 1322;
             params!(Uuid, Uuid) => BinaryFunc::Lt => Bool, 2974;
+            params!(Inet, Inet) => BinaryFunc::Lt => Bool, 1203;
+            params!(MacAddr, MacAddr) => BinaryFunc::Lt => Bool, 1222;
+            params!(MacAddr8, MacAddr8) => BinaryFunc::Lt => Bool, 3364;
             params!(Interval, Interval) => BinaryFunc::Lt => Bool, 1332;
             params!(Bytes, Bytes) => BinaryFunc::Lt => Bool, 1957;
             params!(String, String) => BinaryFunc::Lt => Bool, 664;
@@ -4867,6 +5533,7 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Lt => Bool, 2990;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Lt =>Bool, oid::FUNC_MZ_TIMESTAMP_LT_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Lt => Bool, 3884;
+            params!(EnumAny, EnumAny) => BinaryFunc::Lt => Bool, 3518;
         },
         "<=" => Scalar {
             params!(Numeric, Numeric) => BinaryFunc::Lte => Bool, 1755;
@@ -4885,6 +5552,9 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Lte => Bool, 2063;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Lte => Bool, 1323;
             params!(Uuid, Uuid) => BinaryFunc::Lte => Bool, 2976;
+            params!(Inet, Inet) => BinaryFunc::Lte => Bool, 1204;
+            params!(MacAddr, MacAddr) => BinaryFunc::Lte => Bool, 1223;
+            params!(MacAddr8, MacAddr8) => BinaryFunc::Lte => Bool, 3365;
             params!(Interval, Interval) => BinaryFunc::Lte => Bool, 1333;
             params!(Bytes, Bytes) => BinaryFunc::Lte => Bool, 1958;
             params!(String, String) => BinaryFunc::Lte => Bool, 665;
@@ -4896,6 +5566,7 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Lte => Bool, 2992;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Lte =>Bool, oid::FUNC_MZ_TIMESTAMP_LTE_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Lte => Bool, 3885;
+            params!(EnumAny, EnumAny) => BinaryFunc::Lte => Bool, 3520;
         },
         ">" => Scalar {
             params!(Numeric, Numeric) => BinaryFunc::Gt => Bool, 1756;
@@ -4914,6 +5585,9 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Gt => Bool, 2064;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Gt => Bool, 1324;
             params!(Uuid, Uuid) => BinaryFunc::Gt => Bool, 2975;
+            params!(Inet, Inet) => BinaryFunc::Gt => Bool, 1205;
+            params!(MacAddr, MacAddr) => BinaryFunc::Gt => Bool, 1224;
+            params!(MacAddr8, MacAddr8) => BinaryFunc::Gt => Bool, 3366;
             params!(Interval, Interval) => BinaryFunc::Gt => Bool, 1334;
             params!(Bytes, Bytes) => BinaryFunc::Gt => Bool, 1959;
             params!(String, String) => BinaryFunc::Gt => Bool, 666;
@@ -4925,6 +5599,7 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Gt => Bool, 2991;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Gt =>Bool, oid::FUNC_MZ_TIMESTAMP_GT_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Gt => Bool, 3887;
+            params!(EnumAny, EnumAny) => BinaryFunc::Gt => Bool, 3519;
         },
         ">=" => Scalar {
             params!(Numeric, Numeric) => BinaryFunc::Gte => Bool, 1757;
@@ -4943,6 +5618,9 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Gte => Bool, 2065;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Gte => Bool, 1325;
             params!(Uuid, Uuid) => BinaryFunc::Gte => Bool, 2977;
+            params!(Inet, Inet) => BinaryFunc::Gte => Bool, 1206;
+            params!(MacAddr, MacAddr) => BinaryFunc::Gte => Bool, 1225;
+            params!(MacAddr8, MacAddr8) => BinaryFunc::Gte => Bool, 3367;
             params!(Interval, Interval) => BinaryFunc::Gte => Bool, 1335;
             params!(Bytes, Bytes) => BinaryFunc::Gte => Bool, 1960;
             params!(String, String) => BinaryFunc::Gte => Bool, 667;
@@ -4954,6 +5632,7 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Gte => Bool, 2993;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Gte =>Bool, oid::FUNC_MZ_TIMESTAMP_GTE_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Gte => Bool, 3886;
+            params!(EnumAny, EnumAny) => BinaryFunc::Gte => Bool, 3521;
         },
         // Warning!
         // - If you are writing functions here that do not simply use
@@ -4981,6 +5660,13 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Eq => Bool, 2060;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Eq => Bool, 1320;
             params!(Uuid, Uuid) => BinaryFunc::Eq => Bool, 2972;
+            params!(Inet, Inet) => BinaryFunc::Eq => Bool, 1201;
+            params!(MacAddr, MacAddr) => BinaryFunc::Eq => Bool, 1220;
+            params!(MacAddr8, MacAddr8) => BinaryFunc::Eq => Bool, 3362;
+            params!(TsVector, TsVector) => BinaryFunc::Eq => Bool, 3629;
+            params!(TsQuery, TsQuery) => BinaryFunc::Eq => Bool, 3676;
+            params!(Geometry, Geometry) => BinaryFunc::Eq => Bool, oid::FUNC_EQ_GEOMETRY_OID;
+            params!(Geography, Geography) => BinaryFunc::Eq => Bool, oid::FUNC_EQ_GEOGRAPHY_OID;
             params!(Interval, Interval) => BinaryFunc::Eq => Bool, 1330;
             params!(Bytes, Bytes) => BinaryFunc::Eq => Bool, 1955;
             params!(String, String) => BinaryFunc::Eq => Bool, 98;
@@ -4993,6 +5679,7 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Eq => Bool, 2988;
             params!(MzTimestamp, MzTimestamp) => BinaryFunc::Eq => Bool, oid::FUNC_MZ_TIMESTAMP_EQ_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Eq => Bool, 3882;
+            params!(EnumAny, EnumAny) => BinaryFunc::Eq => Bool, 3516;
             params!(MzAclItem, MzAclItem) => BinaryFunc::Eq => Bool, oid::FUNC_MZ_ACL_ITEM_EQ_MZ_ACL_ITEM_OID;
             params!(AclItem, AclItem) => BinaryFunc::Eq => Bool, 974;
         },
@@ -5013,6 +5700,13 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::NotEq => Bool, 2061;
             params!(TimestampTz, TimestampTz) => BinaryFunc::NotEq => Bool, 1321;
             params!(Uuid, Uuid) => BinaryFunc::NotEq => Bool, 2973;
+            params!(Inet, Inet) => BinaryFunc::NotEq => Bool, 1202;
+            params!(MacAddr, MacAddr) => BinaryFunc::NotEq => Bool, 1221;
+            params!(MacAddr8, MacAddr8) => BinaryFunc::NotEq => Bool, 3363;
+            params!(TsVector, TsVector) => BinaryFunc::NotEq => Bool, 3630;
+            params!(TsQuery, TsQuery) => BinaryFunc::NotEq => Bool, 3677;
+            params!(Geometry, Geometry) => BinaryFunc::NotEq => Bool, oid::FUNC_NOT_EQ_GEOMETRY_OID;
+            params!(Geography, Geography) => BinaryFunc::NotEq => Bool, oid::FUNC_NOT_EQ_GEOGRAPHY_OID;
             params!(Interval, Interval) => BinaryFunc::NotEq => Bool, 1331;
             params!(Bytes, Bytes) => BinaryFunc::NotEq => Bool, 1956;
             params!(String, String) => BinaryFunc::NotEq => Bool, 531;
@@ -5024,6 +5718,7 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::NotEq => Bool, 2989;
             params!(MzTimestamp, MzTimestamp) => BinaryFunc::NotEq => Bool, oid::FUNC_MZ_TIMESTAMP_NOT_EQ_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::NotEq => Bool, 3883;
+            params!(EnumAny, EnumAny) => BinaryFunc::NotEq => Bool, 3517;
             params!(MzAclItem, MzAclItem) => BinaryFunc::NotEq => Bool, oid::FUNC_MZ_ACL_ITEM_NOT_EQ_MZ_ACL_ITEM_OID;
         }
     }
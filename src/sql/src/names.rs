@@ -38,7 +38,8 @@ use crate::ast::visit_mut::VisitMut;
 use crate::ast::{
     self, AstInfo, Cte, CteBlock, CteMutRec, DocOnIdentifier, GrantTargetSpecification,
     GrantTargetSpecificationInner, Ident, MutRecBlock, ObjectType, Query, Raw, RawClusterName,
-    RawDataType, RawItemName, Statement, UnresolvedItemName, UnresolvedObjectName,
+    RawDataType, RawItemName, ShowCreateFunctionStatement, Statement, UnresolvedItemName,
+    UnresolvedObjectName,
 };
 use crate::catalog::{
     CatalogError, CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails, SessionCatalog,
@@ -2167,6 +2168,25 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                 join: Box::new(self.fold_table_with_joins(*join)),
                 alias: alias.map(|alias| self.fold_table_alias(alias)),
             },
+            JsonTable {
+                context,
+                path,
+                passing,
+                columns,
+                alias,
+            } => JsonTable {
+                context: self.fold_expr(context),
+                path,
+                passing: passing
+                    .into_iter()
+                    .map(|p| self.fold_json_table_passing(p))
+                    .collect(),
+                columns: columns
+                    .into_iter()
+                    .map(|c| self.fold_json_table_column(c))
+                    .collect(),
+                alias: alias.map(|alias| self.fold_table_alias(alias)),
+            },
         }
     }
 
@@ -2176,10 +2196,10 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
     ) -> GrantTargetSpecification<Aug> {
         match node {
             GrantTargetSpecification::Object {
-                object_type: ObjectType::Type,
+                object_type: object_type @ (ObjectType::Type | ObjectType::Func),
                 object_spec_inner: GrantTargetSpecificationInner::Objects { names },
             } => GrantTargetSpecification::Object {
-                object_type: ObjectType::Type,
+                object_type,
                 object_spec_inner: GrantTargetSpecificationInner::Objects {
                     names: names
                         .into_iter()
@@ -2187,11 +2207,11 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                             UnresolvedObjectName::Item(name) => {
                                 ResolvedObjectName::Item(self.resolve_item_name_name(
                                     name,
-                                    // `{GRANT|REVOKE} ... ON TYPE ...` can only
-                                    // refer to type names.
+                                    // `{GRANT|REVOKE} ... ON {TYPE|FUNCTION} ...`
+                                    // can only refer to names of that kind.
                                     ItemResolutionConfig {
-                                        functions: false,
-                                        types: true,
+                                        functions: object_type == ObjectType::Func,
+                                        types: object_type == ObjectType::Type,
                                         relations: false,
                                     },
                                 ))
@@ -2205,6 +2225,23 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
         }
     }
 
+    fn fold_show_create_function_statement(
+        &mut self,
+        node: ShowCreateFunctionStatement<Raw>,
+    ) -> ShowCreateFunctionStatement<Aug> {
+        ShowCreateFunctionStatement {
+            function_name: self.resolve_item_name(
+                node.function_name,
+                // `SHOW CREATE FUNCTION ...` can only refer to function names.
+                ItemResolutionConfig {
+                    functions: true,
+                    types: false,
+                    relations: false,
+                },
+            ),
+        }
+    }
+
     fn fold_doc_on_identifier(&mut self, node: DocOnIdentifier<Raw>) -> DocOnIdentifier<Aug> {
         match node {
             DocOnIdentifier::Column(name) => DocOnIdentifier::Column(self.fold_column_name(name)),
@@ -22,11 +22,12 @@ use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
     ContinualTaskStmt, CreateConnectionStatement, CreateContinualTaskStatement,
-    CreateContinualTaskSugar, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement,
-    CreateTableFromSourceStatement, CreateTableStatement, CreateTypeStatement, CreateViewStatement,
-    CreateWebhookSourceStatement, CteBlock, Function, FunctionArgs, Ident, IfExistsBehavior,
-    MutRecBlock, Op, Query, Statement, TableFactor, TableFromSourceColumns, UnresolvedItemName,
+    CreateContinualTaskSugar, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateSecretStatement, CreateSinkStatement,
+    CreateSourceStatement, CreateSubsourceStatement, CreateTableFromSourceStatement,
+    CreateTableStatement, CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement,
+    CteBlock, Function, FunctionArgs, FunctionBody, Ident, IfExistsBehavior, MutRecBlock, Op,
+    Query, Statement, TableFactor, TableFromSourceColumns, UnresolvedItemName,
     UnresolvedSchemaName, Value, ViewDefinition,
 };
 
@@ -221,7 +222,8 @@ pub fn create_statement(
         fn visit_function_mut(&mut self, func: &'ast mut Function<Aug>) {
             match &mut func.args {
                 FunctionArgs::Star => (),
-                FunctionArgs::Args { args, order_by } => {
+                FunctionArgs::Args { args, order_by }
+                | FunctionArgs::WithinGroup { args, order_by } => {
                     for arg in args {
                         self.visit_expr_mut(arg);
                     }
@@ -480,6 +482,25 @@ pub fn create_statement(
                 return Err(err);
             }
         }
+        Statement::CreateFunction(CreateFunctionStatement {
+            if_exists,
+            name,
+            parameters: _,
+            returns: _,
+            language: _,
+            body,
+        }) => {
+            *name = allocate_name(name)?;
+            let mut normalizer = QueryNormalizer::new();
+            match body {
+                FunctionBody::Return(expr) => normalizer.visit_expr_mut(expr),
+                FunctionBody::Atomic(query) => normalizer.visit_query_mut(query),
+            }
+            if let Some(err) = normalizer.err {
+                return Err(err);
+            }
+            *if_exists = IfExistsBehavior::Error;
+        }
         Statement::CreateSecret(CreateSecretStatement {
             name,
             if_not_exists,
@@ -60,7 +60,7 @@ use mz_storage_types::connections::aws::AwsConnection;
 use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::connections::{
     AwsPrivatelinkConnection, CsrConnection, KafkaConnection, MySqlConnection, PostgresConnection,
-    SshConnection,
+    SqlServerConnection, SshConnection,
 };
 use mz_storage_types::instances::StorageInstanceId;
 use mz_storage_types::sinks::{S3SinkFormat, SinkEnvelope, StorageSinkConnection};
@@ -78,6 +78,7 @@ use crate::catalog::{
     CatalogType, DefaultPrivilegeAclItem, DefaultPrivilegeObject, IdReference, ObjectType,
     RoleAttributes,
 };
+use crate::func::Func;
 use crate::names::{
     Aug, CommentObjectId, DependencyIds, FullItemName, ObjectId, QualifiedItemName,
     ResolvedDatabaseSpecifier, ResolvedIds, SchemaSpecifier, SystemObjectId,
@@ -110,12 +111,12 @@ pub use hir::{
 };
 pub use lowering::Config as HirToMirConfig;
 pub use notice::PlanNotice;
-pub use query::{ExprContext, QueryContext, QueryLifetime};
+pub use query::{scalar_type_from_catalog, ExprContext, QueryContext, QueryLifetime};
 pub use scope::Scope;
 pub use side_effecting_func::SideEffectingFunc;
 pub use statement::ddl::{
     AlterSourceAddSubsourceOptionExtracted, MySqlConfigOptionExtracted, PgConfigOptionExtracted,
-    PlannedAlterRoleOption, PlannedRoleVariable,
+    PlannedAlterRoleOption, PlannedRoleVariable, SqlServerConfigOptionExtracted,
 };
 pub use statement::{
     describe, plan, plan_copy_from, resolve_cluster_for_materialized_view, StatementClassification,
@@ -146,6 +147,7 @@ pub enum Plan {
     CreateNetworkPolicy(CreateNetworkPolicyPlan),
     CreateIndex(CreateIndexPlan),
     CreateType(CreateTypePlan),
+    CreateFunction(CreateFunctionPlan),
     Comment(CommentPlan),
     DiscardTemp,
     DiscardAll,
@@ -251,6 +253,11 @@ impl Plan {
             StatementKind::AlterTableAddColumn => {
                 &[PlanKind::AlterNoop, PlanKind::AlterTableAddColumn]
             }
+            StatementKind::AlterViewQuery => &[
+                PlanKind::AlterNoop,
+                PlanKind::CreateView,
+                PlanKind::CreateMaterializedView,
+            ],
             StatementKind::Close => &[PlanKind::Close],
             StatementKind::Comment => &[PlanKind::Comment],
             StatementKind::Commit => &[PlanKind::CommitTransaction],
@@ -279,6 +286,7 @@ impl Plan {
             StatementKind::CreateTable => &[PlanKind::CreateTable],
             StatementKind::CreateTableFromSource => &[PlanKind::CreateTable],
             StatementKind::CreateType => &[PlanKind::CreateType],
+            StatementKind::CreateFunction => &[PlanKind::CreateFunction],
             StatementKind::CreateView => &[PlanKind::CreateView],
             StatementKind::Deallocate => &[PlanKind::Deallocate],
             StatementKind::Declare => &[PlanKind::Declare],
@@ -294,8 +302,9 @@ impl Plan {
             StatementKind::Fetch => &[PlanKind::Fetch],
             StatementKind::GrantPrivileges => &[PlanKind::GrantPrivileges],
             StatementKind::GrantRole => &[PlanKind::GrantRole],
-            StatementKind::Insert => &[PlanKind::Insert],
+            StatementKind::Insert => &[PlanKind::Insert, PlanKind::ReadThenWrite],
             StatementKind::Prepare => &[PlanKind::Prepare],
+            StatementKind::Merge => &[PlanKind::ReadThenWrite],
             StatementKind::Raise => &[PlanKind::Raise],
             StatementKind::ReassignOwned => &[PlanKind::ReassignOwned],
             StatementKind::ResetVariable => &[PlanKind::ResetVariable],
@@ -340,6 +349,7 @@ impl Plan {
             Plan::CreateContinualTask(_) => "create continual task",
             Plan::CreateIndex(_) => "create index",
             Plan::CreateType(_) => "create type",
+            Plan::CreateFunction(_) => "create function",
             Plan::CreateNetworkPolicy(_) => "create network policy",
             Plan::Comment(_) => "comment",
             Plan::DiscardTemp => "discard temp",
@@ -448,6 +458,8 @@ impl Plan {
                 MutationKind::Insert => "insert into select",
                 MutationKind::Update => "update",
                 MutationKind::Delete => "delete",
+                MutationKind::Upsert => "insert on conflict",
+                MutationKind::Merge => "merge",
             },
             Plan::Prepare(_) => "prepare",
             Plan::Execute(_) => "execute",
@@ -726,6 +738,8 @@ pub struct CreateViewPlan {
     pub replace: Option<CatalogItemId>,
     /// The Catalog objects that need to be dropped. This includes `replace` and any dependents.
     pub drop_ids: Vec<CatalogItemId>,
+    /// Set if `replace` is updated in place rather than dropped and recreated.
+    pub replace_in_place: Option<InPlaceReplacement>,
     pub if_not_exists: bool,
     /// True if the view contains an expression that can make the exact column list
     /// ambiguous. For example `NATURAL JOIN` or `SELECT *`.
@@ -739,13 +753,28 @@ pub struct CreateMaterializedViewPlan {
     /// The Catalog objects that this materialized view is replacing, if any.
     pub replace: Option<CatalogItemId>,
     /// The Catalog objects that need to be dropped. This includes `replace` and any dependents.
+    ///
+    /// If `replace_in_place` is set, these are only dropped if the materialized view turns out
+    /// not to be replaceable in place, and are empty if that must fail instead.
     pub drop_ids: Vec<CatalogItemId>,
+    /// Set if `replace` is updated in place rather than dropped and recreated.
+    pub replace_in_place: Option<InPlaceReplacement>,
     pub if_not_exists: bool,
     /// True if the materialized view contains an expression that can make the exact column list
     /// ambiguous. For example `NATURAL JOIN` or `SELECT *`.
     pub ambiguous_columns: bool,
 }
 
+/// How a view or materialized view definition is replaced in place, keeping
+/// its [`CatalogItemId`] and any dependents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InPlaceReplacement {
+    /// `CREATE OR REPLACE [MATERIALIZED] VIEW`.
+    CreateOrReplace,
+    /// `ALTER [MATERIALIZED] VIEW ... AS`.
+    Alter,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateContinualTaskPlan {
     pub name: QualifiedItemName,
@@ -786,6 +815,16 @@ pub struct CreateTypePlan {
     pub typ: Type,
 }
 
+#[derive(Debug)]
+pub struct CreateFunctionPlan {
+    pub name: QualifiedItemName,
+    pub function: Function,
+    /// The function that this function is replacing, if any.
+    pub replace: Option<CatalogItemId>,
+    /// The Catalog objects that need to be dropped. This includes `replace` and any dependents.
+    pub drop_ids: Vec<CatalogItemId>,
+}
+
 #[derive(Debug)]
 pub struct DropObjectsPlan {
     /// The IDs of only the objects directly referenced in the `DROP` statement.
@@ -1112,6 +1151,9 @@ pub struct SendDiffsPlan {
     pub kind: MutationKind,
     pub returning: Vec<(Row, NonZeroUsize)>,
     pub max_result_size: u64,
+    /// The number of rows affected by the write, if it can't be derived from
+    /// `updates`.
+    pub affected_rows: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -1129,6 +1171,36 @@ pub struct ReadThenWritePlan {
     pub assignments: BTreeMap<usize, mz_expr::MirScalarExpr>,
     pub kind: MutationKind,
     pub returning: Vec<mz_expr::MirScalarExpr>,
+    /// For [`MutationKind::Upsert`] and [`MutationKind::Merge`], describes how
+    /// the rows of `selection` translate into changes to the table.
+    pub changes: Option<ChangesPlan>,
+    /// The unique keys of the table that the write must not violate.
+    ///
+    /// If not empty, each row of `selection` has the form
+    /// `[current, selected.., row..]`. If `current` is true, `row` is one of
+    /// the rows of the table at the time of the read. Otherwise, `selected` is
+    /// a row selected for the write, with the form that the other fields
+    /// describe.
+    pub enforced_keys: Vec<Vec<usize>>,
+}
+
+/// Describes how the rows selected by an `INSERT ... ON CONFLICT` or `MERGE`
+/// statement translate into changes to the target table.
+///
+/// Each selected row has the form `[retract, old.., insert, new..]`, where
+/// `old` and `new` are rows of the table. If `retract` is not null, it is the
+/// number of copies of `old` to retract. If `insert` is true, `new` is
+/// inserted once for every retracted copy of `old`, or once if no copies were
+/// retracted.
+#[derive(Debug, Clone)]
+pub struct ChangesPlan {
+    /// The privileges that the changes require on the target table.
+    pub acl_mode: AclMode,
+    /// The keys that must be unique among the inserted rows.
+    pub unique_keys: Vec<Vec<usize>>,
+    /// Whether inserted rows that duplicate a unique key of a previously
+    /// inserted row are skipped, rather than reported as an error.
+    pub skip_duplicates: bool,
 }
 
 /// Generated by `ALTER ... IF EXISTS` if the named object did not exist.
@@ -1600,6 +1672,7 @@ pub enum ConnectionDetails {
     Aws(AwsConnection),
     AwsPrivatelink(AwsPrivatelinkConnection),
     MySql(MySqlConnection<ReferencedConnection>),
+    SqlServer(SqlServerConnection<ReferencedConnection>),
 }
 
 impl ConnectionDetails {
@@ -1622,6 +1695,9 @@ impl ConnectionDetails {
             ConnectionDetails::MySql(c) => {
                 mz_storage_types::connections::Connection::MySql(c.clone())
             }
+            ConnectionDetails::SqlServer(c) => {
+                mz_storage_types::connections::Connection::SqlServer(c.clone())
+            }
         }
     }
 }
@@ -1801,6 +1877,14 @@ pub struct Type {
     pub inner: CatalogType<IdReference>,
 }
 
+#[derive(Debug)]
+pub struct Function {
+    /// Parse-able SQL that is stored durably and defines this function.
+    pub create_sql: String,
+    /// The implementation of the function.
+    pub inner: Func,
+}
+
 /// Specifies when a `Peek` or `Subscribe` should occur.
 #[derive(Deserialize, Clone, Debug, PartialEq)]
 pub enum QueryWhen {
@@ -1877,6 +1961,10 @@ pub enum MutationKind {
     Insert,
     Update,
     Delete,
+    /// `INSERT ... ON CONFLICT`.
+    Upsert,
+    /// `MERGE`.
+    Merge,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -1885,6 +1973,9 @@ pub enum CopyFormat {
     Csv,
     Binary,
     Parquet,
+    Avro,
+    Orc,
+    Json { array: bool },
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -30,6 +30,7 @@ use mz_repr::{strconv, CatalogItemId, ColumnName};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{IdentError, UnresolvedItemName};
 use mz_sql_parser::parser::{ParserError, ParserStatementError};
+use mz_sql_server_util::SqlServerError;
 use mz_storage_types::sources::ExternalReferenceResolutionError;
 
 use crate::catalog::{
@@ -43,6 +44,7 @@ use crate::plan::ObjectType;
 use crate::pure::error::{
     CsrPurificationError, KafkaSinkPurificationError, KafkaSourcePurificationError,
     LoadGeneratorSourcePurificationError, MySqlSourcePurificationError, PgSourcePurificationError,
+    SqlServerSourcePurificationError,
 };
 use crate::session::vars::VarError;
 
@@ -163,6 +165,9 @@ pub enum PlanError {
     MySqlConnectionErr {
         cause: Arc<MySqlError>,
     },
+    SqlServerConnectionErr {
+        cause: Arc<SqlServerError>,
+    },
     SubsourceNameConflict {
         name: UnresolvedItemName,
         upstream_references: Vec<UnresolvedItemName>,
@@ -246,6 +251,7 @@ pub enum PlanError {
     LoadGeneratorSourcePurification(LoadGeneratorSourcePurificationError),
     CsrPurification(CsrPurificationError),
     MySqlSourcePurification(MySqlSourcePurificationError),
+    SqlServerSourcePurification(SqlServerSourcePurificationError),
     UseTablesForSources(String),
     MissingName(CatalogItemType),
     InvalidRefreshAt,
@@ -327,6 +333,7 @@ impl PlanError {
             Self::InternalFunctionCall => Some("This function is for the internal use of the database system and cannot be called directly.".into()),
             Self::PgSourcePurification(e) => e.detail(),
             Self::MySqlSourcePurification(e) => e.detail(),
+            Self::SqlServerSourcePurification(e) => e.detail(),
             Self::KafkaSourcePurification(e) => e.detail(),
             Self::LoadGeneratorSourcePurification(e) => e.detail(),
             Self::CsrPurification(e) => e.detail(),
@@ -439,6 +446,7 @@ impl PlanError {
             Self::LoadGeneratorSourcePurification(e) => e.hint(),
             Self::CsrPurification(e) => e.hint(),
             Self::KafkaSinkPurification(e) => e.hint(),
+            Self::SqlServerSourcePurification(e) => e.hint(),
             Self::UnknownColumn { table, similar, .. } => {
                 let suffix = "Make sure to surround case sensitive names in double quotes.";
                 match &similar[..] {
@@ -619,6 +627,9 @@ impl fmt::Display for PlanError {
             Self::MySqlConnectionErr { cause } => {
                 write!(f, "failed to connect to MySQL database: {}", cause)
             }
+            Self::SqlServerConnectionErr { cause } => {
+                write!(f, "failed to connect to SQL Server database: {}", cause)
+            }
             Self::SubsourceNameConflict {
                 name , upstream_references: _,
             } => {
@@ -732,6 +743,7 @@ impl fmt::Display for PlanError {
             Self::KafkaSinkPurification(e) => write!(f, "KAFKA sink validation: {}", e),
             Self::CsrPurification(e) => write!(f, "CONFLUENT SCHEMA REGISTRY validation: {}", e),
             Self::MySqlSourcePurification(e) => write!(f, "MYSQL source validation: {}", e),
+            Self::SqlServerSourcePurification(e) => write!(f, "SQL SERVER source validation: {}", e),
             Self::UseTablesForSources(command) => write!(f, "{command} not supported; use CREATE TABLE .. FROM SOURCE instead"),
             Self::MangedReplicaName(name) => {
                 write!(f, "{name} is reserved for replicas of managed clusters")
@@ -885,6 +897,12 @@ impl From<MySqlError> for PlanError {
     }
 }
 
+impl From<SqlServerError> for PlanError {
+    fn from(e: SqlServerError) -> PlanError {
+        PlanError::SqlServerConnectionErr { cause: Arc::new(e) }
+    }
+}
+
 impl From<VarError> for PlanError {
     fn from(e: VarError) -> Self {
         PlanError::VarError(e)
@@ -927,6 +945,12 @@ impl From<MySqlSourcePurificationError> for PlanError {
     }
 }
 
+impl From<SqlServerSourcePurificationError> for PlanError {
+    fn from(e: SqlServerSourcePurificationError) -> Self {
+        PlanError::SqlServerSourcePurification(e)
+    }
+}
+
 impl From<IdentError> for PlanError {
     fn from(e: IdentError) -> Self {
         PlanError::InvalidIdent(e)
@@ -31,6 +31,7 @@ use mz_ore::str::separated;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::*;
+use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 
 use crate::plan::error::PlanError;
@@ -463,6 +464,8 @@ impl ScalarWindowExpr {
             ScalarWindowFunc::RowNumber => {}
             ScalarWindowFunc::Rank => {}
             ScalarWindowFunc::DenseRank => {}
+            ScalarWindowFunc::PercentRank => {}
+            ScalarWindowFunc::CumeDist => {}
         }
         Ok(())
     }
@@ -476,6 +479,8 @@ impl ScalarWindowExpr {
             ScalarWindowFunc::RowNumber => {}
             ScalarWindowFunc::Rank => {}
             ScalarWindowFunc::DenseRank => {}
+            ScalarWindowFunc::PercentRank => {}
+            ScalarWindowFunc::CumeDist => {}
         }
         Ok(())
     }
@@ -500,6 +505,12 @@ impl ScalarWindowExpr {
             ScalarWindowFunc::DenseRank => mz_expr::AggregateFunc::DenseRank {
                 order_by: self.order_by,
             },
+            ScalarWindowFunc::PercentRank => mz_expr::AggregateFunc::PercentRank {
+                order_by: self.order_by,
+            },
+            ScalarWindowFunc::CumeDist => mz_expr::AggregateFunc::CumeDist {
+                order_by: self.order_by,
+            },
         }
     }
 }
@@ -510,6 +521,8 @@ pub enum ScalarWindowFunc {
     RowNumber,
     Rank,
     DenseRank,
+    PercentRank,
+    CumeDist,
 }
 
 impl Display for ScalarWindowFunc {
@@ -518,6 +531,8 @@ impl Display for ScalarWindowFunc {
             ScalarWindowFunc::RowNumber => write!(f, "row_number"),
             ScalarWindowFunc::Rank => write!(f, "rank"),
             ScalarWindowFunc::DenseRank => write!(f, "dense_rank"),
+            ScalarWindowFunc::PercentRank => write!(f, "percent_rank"),
+            ScalarWindowFunc::CumeDist => write!(f, "cume_dist"),
         }
     }
 }
@@ -528,6 +543,8 @@ impl ScalarWindowFunc {
             ScalarWindowFunc::RowNumber => ScalarType::Int64.nullable(false),
             ScalarWindowFunc::Rank => ScalarType::Int64.nullable(false),
             ScalarWindowFunc::DenseRank => ScalarType::Int64.nullable(false),
+            ScalarWindowFunc::PercentRank => ScalarType::Float64.nullable(false),
+            ScalarWindowFunc::CumeDist => ScalarType::Float64.nullable(false),
         }
     }
 }
@@ -554,6 +571,8 @@ impl Display for ValueWindowFunc {
             ValueWindowFunc::Lead => write!(f, "lead"),
             ValueWindowFunc::FirstValue => write!(f, "first_value"),
             ValueWindowFunc::LastValue => write!(f, "last_value"),
+            ValueWindowFunc::NthValue => write!(f, "nth_value"),
+            ValueWindowFunc::Ntile => write!(f, "ntile"),
             ValueWindowFunc::Fused(funcs) => write!(f, "fused[{}]", separated(", ", funcs)),
         }
     }
@@ -634,14 +653,17 @@ pub enum ValueWindowFunc {
     Lead,
     FirstValue,
     LastValue,
+    NthValue,
+    Ntile,
     Fused(Vec<ValueWindowFunc>),
 }
 
 impl ValueWindowFunc {
     pub fn output_type(&self, input_type: ColumnType) -> ColumnType {
         match self {
-            ValueWindowFunc::Lag | ValueWindowFunc::Lead => {
-                // The input is a (value, offset, default) record, so extract the type of the first arg
+            ValueWindowFunc::Lag | ValueWindowFunc::Lead | ValueWindowFunc::NthValue => {
+                // The input is a (value, offset, default) or (value, n) record, so extract the type
+                // of the first arg
                 input_type.scalar_type.unwrap_record_element_type()[0]
                     .clone()
                     .nullable(true)
@@ -649,6 +671,8 @@ impl ValueWindowFunc {
             ValueWindowFunc::FirstValue | ValueWindowFunc::LastValue => {
                 input_type.scalar_type.nullable(true)
             }
+            // Null when the number of buckets is null or not positive
+            ValueWindowFunc::Ntile => ScalarType::Int32.nullable(true),
             ValueWindowFunc::Fused(funcs) => {
                 let input_types = input_type.scalar_type.unwrap_record_element_column_type();
                 ScalarType::Record {
@@ -658,6 +682,7 @@ impl ValueWindowFunc {
                         .map(|(f, t)| (ColumnName::from(""), f.output_type(t.clone())))
                         .collect(),
                     custom_id: None,
+                    custom_oid: None,
                 }
                 .nullable(false)
             }
@@ -685,11 +710,19 @@ impl ValueWindowFunc {
             ValueWindowFunc::FirstValue => mz_expr::AggregateFunc::FirstValue {
                 order_by,
                 window_frame,
+                ignore_nulls,
             },
             ValueWindowFunc::LastValue => mz_expr::AggregateFunc::LastValue {
                 order_by,
                 window_frame,
+                ignore_nulls,
             },
+            ValueWindowFunc::NthValue => mz_expr::AggregateFunc::NthValue {
+                order_by,
+                window_frame,
+                ignore_nulls,
+            },
+            ValueWindowFunc::Ntile => mz_expr::AggregateFunc::Ntile { order_by },
             ValueWindowFunc::Fused(funcs) => mz_expr::AggregateFunc::FusedValueWindowFunc {
                 funcs: funcs
                     .into_iter()
@@ -755,6 +788,7 @@ impl AggregateWindowExpr {
                     wrapped_aggregate: Box::new(self.aggregate_expr.func.into_expr()),
                     order_by: self.order_by,
                     window_frame: self.window_frame,
+                    distinct: self.aggregate_expr.distinct,
                 },
             )
         }
@@ -938,6 +972,7 @@ impl CoercibleScalarType {
             ScalarType::Record {
                 fields: fields.into(),
                 custom_id: None,
+                custom_oid: None,
             }
         }
 
@@ -1175,6 +1210,29 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// Computes the value at `fraction` of the way through the sorted
+    /// `Datum::Float64`s, interpolating between adjacent values if needed.
+    PercentileCont {
+        fraction: OrderedFloat<f64>,
+        desc: bool,
+    },
+    /// Computes the first value whose position in the sorted input equals or
+    /// exceeds `fraction`.
+    PercentileDisc {
+        fraction: OrderedFloat<f64>,
+        desc: bool,
+    },
+    /// Computes the most frequent value.
+    Mode {
+        desc: bool,
+    },
+    /// Estimates the number of distinct non-null values.
+    ApproxCountDistinct,
+    /// Estimates the first `Datum::Float64` whose position in the sorted input
+    /// equals or exceeds `fraction`.
+    ApproxPercentile {
+        fraction: OrderedFloat<f64>,
+    },
     /// A bundle of fused window aggregations: its input is a record, whose each
     /// component will be the input to one of the `AggregateFunc`s.
     ///
@@ -1258,6 +1316,17 @@ impl AggregateFunc {
                 mz_expr::AggregateFunc::ListConcat { order_by }
             }
             AggregateFunc::StringAgg { order_by } => mz_expr::AggregateFunc::StringAgg { order_by },
+            AggregateFunc::PercentileCont { fraction, desc } => {
+                mz_expr::AggregateFunc::PercentileCont { fraction, desc }
+            }
+            AggregateFunc::PercentileDisc { fraction, desc } => {
+                mz_expr::AggregateFunc::PercentileDisc { fraction, desc }
+            }
+            AggregateFunc::Mode { desc } => mz_expr::AggregateFunc::Mode { desc },
+            AggregateFunc::ApproxCountDistinct => mz_expr::AggregateFunc::ApproxCountDistinct,
+            AggregateFunc::ApproxPercentile { fraction } => {
+                mz_expr::AggregateFunc::ApproxPercentile { fraction }
+            }
             // `AggregateFunc::FusedWindowAgg` should be specially handled in
             // `AggregateWindowExpr::into_expr`.
             AggregateFunc::FusedWindowAgg { funcs: _ } => {
@@ -1327,7 +1396,12 @@ impl AggregateFunc {
             | AggregateFunc::JsonbAgg { .. }
             | AggregateFunc::JsonbObjectAgg { .. }
             | AggregateFunc::MapAgg { .. }
-            | AggregateFunc::StringAgg { .. } => Datum::Null,
+            | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::ApproxCountDistinct
+            | AggregateFunc::ApproxPercentile { .. } => Datum::Null,
             AggregateFunc::FusedWindowAgg { funcs: _ } => {
                 // `identity_datum` is used only in HIR planning, and `FusedWindowAgg` can't occur
                 // in HIR planning, because it is introduced only during HIR transformation.
@@ -1350,12 +1424,15 @@ impl AggregateFunc {
     /// counts.
     pub fn output_type(&self, input_type: ColumnType) -> ColumnType {
         let scalar_type = match self {
-            AggregateFunc::Count => ScalarType::Int64,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => ScalarType::Int64,
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
             AggregateFunc::JsonbAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::JsonbObjectAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::PercentileCont { .. } | AggregateFunc::ApproxPercentile { .. } => {
+                ScalarType::Float64
+            }
             AggregateFunc::SumInt16 | AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => ScalarType::Numeric {
                 max_scale: Some(NumericMaxScale::ZERO),
@@ -1412,6 +1489,8 @@ impl AggregateFunc {
             | AggregateFunc::SumFloat32
             | AggregateFunc::SumFloat64
             | AggregateFunc::SumNumeric
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
             | AggregateFunc::Dummy => input_type.scalar_type,
             AggregateFunc::FusedWindowAgg { funcs } => {
                 let input_types = input_type.scalar_type.unwrap_record_element_column_type();
@@ -1422,11 +1501,15 @@ impl AggregateFunc {
                         .map(|(f, t)| (ColumnName::from(""), f.output_type(t.clone())))
                         .collect(),
                     custom_id: None,
+                    custom_oid: None,
                 }
             }
         };
         // max/min/sum return null on empty sets
-        let nullable = !matches!(self, AggregateFunc::Count);
+        let nullable = !matches!(
+            self,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct
+        );
         scalar_type.nullable(nullable)
     }
 
@@ -1442,6 +1525,16 @@ impl AggregateFunc {
                 | StringAgg { .. }
         )
     }
+
+    /// Returns whether this is an ordered-set aggregate, which must be called
+    /// with a `WITHIN GROUP (ORDER BY ...)` clause.
+    pub fn is_ordered_set(&self) -> bool {
+        use AggregateFunc::*;
+        matches!(
+            self,
+            PercentileCont { .. } | PercentileDisc { .. } | Mode { .. }
+        )
+    }
 }
 
 impl HirRelationExpr {
@@ -2102,6 +2195,17 @@ impl HirRelationExpr {
         });
     }
 
+    /// See the documentation for [`HirScalarExpr::parameterize_outer_columns`].
+    pub fn parameterize_outer_columns(&mut self, depth: usize) {
+        #[allow(deprecated)]
+        let _ = self.visit_scalar_expressions_mut(depth, &mut |e: &mut HirScalarExpr,
+                                                               depth: usize|
+         -> Result<(), ()> {
+            e.parameterize_outer_columns(depth);
+            Ok(())
+        });
+    }
+
     /// Constructs a constant collection from specific rows and schema.
     pub fn constant(rows: Vec<Vec<Datum>>, typ: RelationType) -> Self {
         let rows = rows
@@ -2914,6 +3018,27 @@ impl HirScalarExpr {
         });
     }
 
+    /// Replaces references to the columns of the scope directly enclosing the
+    /// expression with parameters, such that column `0` of that scope becomes
+    /// `$1`, column `1` becomes `$2`, and so on.
+    ///
+    /// This is the inverse of [`HirScalarExpr::splice_parameters`] for
+    /// expressions that were planned with their parameters bound to an outer
+    /// scope.
+    pub fn parameterize_outer_columns(&mut self, depth: usize) {
+        #[allow(deprecated)]
+        let _ = self.visit_recursively_mut(depth, &mut |depth: usize,
+                                                        e: &mut HirScalarExpr|
+         -> Result<(), ()> {
+            if let HirScalarExpr::Column(ColumnRef { level, column }) = e {
+                if *level == depth + 1 {
+                    *e = HirScalarExpr::Parameter(*column + 1);
+                }
+            }
+            Ok(())
+        });
+    }
+
     /// Whether the expression contains an [`UnmaterializableFunc::MzNow`] call.
     pub fn contains_temporal(&self) -> bool {
         let mut contains = false;
@@ -3283,6 +3408,25 @@ impl HirScalarExpr {
         })
     }
 
+    /// Attempts to simplify this expression to a literal 64-bit float.
+    ///
+    /// Returns `None` if this expression cannot be simplified, e.g. because it
+    /// contains non-literal values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this expression does not have type [`ScalarType::Float64`].
+    pub fn into_literal_float64(self) -> Option<f64> {
+        self.simplify_to_literal().and_then(|row| {
+            let datum = row.unpack_first();
+            if datum.is_null() {
+                None
+            } else {
+                Some(datum.unwrap_float64())
+            }
+        })
+    }
+
     /// Attempts to simplify this expression to a literal string.
     ///
     /// Returns `None` if this expression cannot be simplified, e.g. because it
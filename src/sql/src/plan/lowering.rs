@@ -1172,6 +1172,7 @@ impl HirScalarExpr {
                                     })
                                     .collect(),
                                 custom_id: None,
+                                custom_oid: None,
                             }
                             .nullable(false);
 
@@ -1226,6 +1227,7 @@ impl HirScalarExpr {
                             let fn_input_record_type = ScalarType::Record {
                                 fields: fn_input_record_fields,
                                 custom_id: None,
+                                custom_oid: None,
                             }
                             .nullable(false);
 
@@ -1249,6 +1251,7 @@ impl HirScalarExpr {
                                 )]
                                 .into(),
                                 custom_id: None,
+                                custom_oid: None,
                             }
                             .nullable(false);
 
@@ -1449,6 +1452,7 @@ impl HirScalarExpr {
                     let original_row_record_type = ScalarType::Record {
                         fields,
                         custom_id: None,
+                        custom_oid: None,
                     };
 
                     let (agg_input, agg_input_type) = lower_args(
@@ -52,6 +52,7 @@ use mz_ore::option::FallibleMapExt;
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 use mz_ore::str::StrExt;
 use mz_repr::adt::char::CharLength;
+use mz_repr::adt::mz_acl_item::AclMode;
 use mz_repr::adt::numeric::{NumericMaxScale, NUMERIC_DATUM_MAX_PRECISION};
 use mz_repr::adt::timestamp::TimestampPrecision;
 use mz_repr::adt::varchar::VarCharMaxLength;
@@ -66,8 +67,9 @@ use mz_sql_parser::ast::{
     visit, AsOf, Assignment, AstInfo, CreateWebhookSourceBody, CreateWebhookSourceCheck,
     CreateWebhookSourceHeader, CreateWebhookSourceSecret, CteBlock, DeleteStatement, Distinct,
     Expr, Function, FunctionArgs, HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join,
-    JoinConstraint, JoinOperator, Limit, MapEntry, MutRecBlock, MutRecBlockOption,
-    MutRecBlockOptionName, OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName,
+    JoinConstraint, JoinOperator, JsonTableColumn, JsonTablePassing, Limit, MapEntry, MergeAction,
+    MergeClause, MergeStatement, MutRecBlock, MutRecBlockOption, MutRecBlockOptionName, OnConflict,
+    OnConflictAction, OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName,
     SetExpr, SetOperator, ShowStatement, SubscriptPosition, TableAlias, TableFactor,
     TableWithJoins, UnresolvedItemName, UpdateStatement, Value, Values, WindowFrame,
     WindowFrameBound, WindowFrameUnits, WindowSpec,
@@ -94,8 +96,8 @@ use crate::plan::statement::{show, StatementContext, StatementDesc};
 use crate::plan::typeconv::{self, CastContext};
 use crate::plan::PlanError::InvalidWmrRecursionLimit;
 use crate::plan::{
-    literal, transform_ast, Params, PlanContext, QueryWhen, ShowCreatePlan, WebhookValidation,
-    WebhookValidationSecret,
+    literal, transform_ast, ChangesPlan, Params, PlanContext, QueryWhen, ShowCreatePlan,
+    WebhookValidation, WebhookValidationSecret,
 };
 use crate::session::vars::{self, FeatureFlag};
 
@@ -476,6 +478,188 @@ pub fn plan_insert_query(
     ))
 }
 
+/// Plans an `INSERT ... ON CONFLICT` statement.
+///
+/// The rows to insert are left joined with the distinct rows of the table that
+/// they conflict with on one of the table's keys. Rows without a conflict are
+/// inserted, while rows with a conflict are skipped or update the row they
+/// conflict with, depending on the conflict action. The selected rows describe
+/// the changes to the table as documented on [`ChangesPlan`].
+pub fn plan_upsert_query(
+    scx: &StatementContext,
+    table_name: ResolvedItemName,
+    columns: Vec<Ident>,
+    source: InsertSource<Aug>,
+    mut on_conflict: OnConflict<Aug>,
+    returning: Vec<SelectItem<Aug>>,
+) -> Result<(ReadThenWritePlan, PlannedRootQuery<Vec<HirScalarExpr>>), PlanError> {
+    let (id, source, returning) =
+        plan_insert_query(scx, table_name.clone(), columns, source, returning)?;
+    transform_ast::transform(scx, &mut on_conflict)?;
+    let OnConflict {
+        columns: target,
+        action,
+    } = on_conflict;
+
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let (_, desc, _) = plan_mutation_target(scx, &table_name)?;
+    let arity = desc.arity();
+
+    // Determine the keys on which rows conflict. Without a conflict target,
+    // rows conflict on any key of the table.
+    let keys = &desc.typ().keys;
+    let conflict_keys = if target.is_empty() {
+        if let OnConflictAction::DoUpdate { .. } = action {
+            sql_bail!("ON CONFLICT DO UPDATE requires a conflict target");
+        }
+        keys.clone()
+    } else {
+        let mut target_columns = BTreeSet::new();
+        for column in target {
+            let name = normalize::column_name(column);
+            match desc.get_by_name(&name) {
+                Some((idx, _)) => target_columns.insert(idx),
+                None => sql_bail!("column {} does not exist", name.as_str().quoted()),
+            };
+        }
+        match keys
+            .iter()
+            .find(|key| key.iter().copied().collect::<BTreeSet<_>>() == target_columns)
+        {
+            Some(key) => vec![key.clone()],
+            None => {
+                sql_bail!("there is no unique constraint matching the ON CONFLICT specification")
+            }
+        }
+    };
+
+    // Produces `[row.., table_row.., copies]`, where `table_row` is the row of
+    // the table that `row` conflicts with, if any.
+    let (table, table_scope) = qcx.resolve_table_name(table_name.clone())?;
+    let on = HirScalarExpr::variadic_or(
+        conflict_keys
+            .iter()
+            .map(|key| {
+                HirScalarExpr::variadic_and(
+                    key.iter()
+                        .map(|i| {
+                            let (l, r) =
+                                (HirScalarExpr::column(*i), HirScalarExpr::column(arity + i));
+                            let eq = l.clone().call_binary(r.clone(), BinaryFunc::Eq);
+                            // Keys consider nulls equal to each other.
+                            if desc.typ().column_types[*i].nullable {
+                                eq.or(l.call_is_null().and(r.call_is_null()))
+                            } else {
+                                eq
+                            }
+                        })
+                        .collect(),
+                )
+            })
+            .collect(),
+    );
+    let joined = source.join(plan_counted_rows(table, arity), on, JoinKind::LeftOuter);
+    let copies = HirScalarExpr::column(2 * arity);
+
+    let inserts = plan_encoded_changes(
+        joined.clone().filter(vec![copies.clone().call_is_null()]),
+        arity,
+        arity,
+        HirScalarExpr::literal_true(),
+        (0..arity).map(HirScalarExpr::column).collect(),
+    );
+    let (selection, acl_mode, skip_duplicates) = match action {
+        OnConflictAction::DoNothing => (inserts, AclMode::INSERT, true),
+        OnConflictAction::DoUpdate {
+            assignments,
+            selection,
+        } => {
+            // The row proposed for insertion is only accessible through the
+            // `excluded` table name, while bare column names refer to the row
+            // of the table.
+            let mut scope = Scope::from_source(
+                Some(PartialItemName {
+                    database: None,
+                    schema: None,
+                    item: "excluded".into(),
+                }),
+                desc.iter_names(),
+            );
+            for item in &mut scope.items {
+                item.allow_unqualified_references = false;
+            }
+            let mut scope = scope.product(table_scope)?;
+            scope.items.push(hidden_scope_item());
+            let relation_type = qcx.relation_type(&joined);
+
+            let mut updates = joined.filter(vec![copies.call_is_null().not()]);
+            if let Some(selection) = selection {
+                let ecx = &ExprContext {
+                    qcx: &qcx,
+                    name: "WHERE clause",
+                    scope: &scope,
+                    relation_type: &relation_type,
+                    allow_aggregates: false,
+                    allow_subqueries: true,
+                    allow_parameters: true,
+                    allow_windows: false,
+                };
+                let expr = plan_expr(ecx, &selection)?.type_as(ecx, &ScalarType::Bool)?;
+                updates = updates.filter(vec![expr]);
+            }
+
+            let ecx = &ExprContext {
+                qcx: &qcx,
+                name: "SET clause",
+                scope: &scope,
+                relation_type: &relation_type,
+                allow_aggregates: false,
+                allow_subqueries: false,
+                allow_parameters: true,
+                allow_windows: false,
+            };
+            let mut new: Vec<_> = (arity..2 * arity).map(HirScalarExpr::column).collect();
+            for (idx, expr) in plan_assignments(ecx, &desc, assignments)? {
+                // Updating a conflict target column could introduce a conflict
+                // with another row of the table.
+                if conflict_keys.iter().any(|key| key.contains(&idx)) {
+                    bail_unsupported!("ON CONFLICT DO UPDATE SET of conflict target columns");
+                }
+                new[idx] = expr;
+            }
+            let updates =
+                plan_encoded_changes(updates, arity, arity, HirScalarExpr::literal_true(), new);
+            (
+                inserts.union(updates),
+                AclMode::INSERT | AclMode::UPDATE,
+                false,
+            )
+        }
+    };
+
+    let mut plan = ReadThenWritePlan {
+        id,
+        selection,
+        assignments: BTreeMap::new(),
+        finishing: RowSetFinishing {
+            order_by: vec![],
+            limit: None,
+            offset: 0,
+            project: (0..2 * arity + 2).collect(),
+        },
+        changes: Some(ChangesPlan {
+            acl_mode,
+            unique_keys: conflict_keys,
+            skip_duplicates,
+        }),
+        enforced_keys: vec![],
+    };
+    // Rows that don't conflict on the conflict target can still violate
+    // another key of the table.
+    plan_unique_key_check(&qcx, table_name, &mut plan)?;
+    Ok((plan, returning))
+}
+
 pub fn plan_copy_item(
     scx: &StatementContext,
     item_name: ResolvedItemName,
@@ -554,6 +738,16 @@ pub fn plan_copy_from(
             table_name.full_name_str()
         );
     }
+    // Copied rows are written without reading the table, so can't be checked
+    // against its unique keys.
+    if !table
+        .desc(&scx.catalog.resolve_full_name(table.name()))?
+        .typ()
+        .keys
+        .is_empty()
+    {
+        bail_unsupported!("COPY FROM into a table with unique keys");
+    }
     let (id, desc, ordering) = plan_copy_item(scx, table_name, columns)?;
 
     Ok((id, desc, ordering))
@@ -643,6 +837,12 @@ pub struct ReadThenWritePlan {
     /// Map from column index to SET expression. Empty for DELETE statements.
     pub assignments: BTreeMap<usize, HirScalarExpr>,
     pub finishing: RowSetFinishing,
+    /// How the rows of `selection` translate into changes to the table, for
+    /// `INSERT ... ON CONFLICT` and `MERGE` statements.
+    pub changes: Option<ChangesPlan>,
+    /// The unique keys of the table that the write must not violate. See
+    /// [`plan_unique_key_check`].
+    pub enforced_keys: Vec<Vec<usize>>,
 }
 
 pub fn plan_delete_query(
@@ -680,6 +880,251 @@ pub fn plan_update_query(
     )
 }
 
+/// Plans a `MERGE` statement.
+///
+/// The source relation is left joined with the distinct rows of the target
+/// table that satisfy the join condition. Each `WHEN` clause applies to the
+/// joined rows that satisfy its condition but not the condition of an earlier
+/// clause of the same kind. The selected rows describe the changes to the
+/// table as documented on [`ChangesPlan`].
+pub fn plan_merge_query(
+    scx: &StatementContext,
+    mut merge_stmt: MergeStatement<Aug>,
+) -> Result<ReadThenWritePlan, PlanError> {
+    transform_ast::transform(scx, &mut merge_stmt)?;
+    let MergeStatement {
+        table_name,
+        alias,
+        using,
+        on,
+        clauses,
+    } = merge_stmt;
+
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let (id, desc, defaults) = plan_mutation_target(scx, &table_name)?;
+    let full_name = table_name.full_name_str();
+    let arity = desc.arity();
+
+    // Produces `[source_row.., target_row.., copies]`, where `target_row` is
+    // the row of the target table that `source_row` matches, if any.
+    let (source, source_scope) = plan_table_with_joins(&qcx, &using)?;
+    let (target, target_scope) = qcx.resolve_table_name(table_name.clone())?;
+    let target_scope = plan_table_alias(target_scope, alias.as_ref())?;
+    let source_arity = source_scope.len();
+    let target = plan_counted_rows(target, arity);
+
+    let mut scope = source_scope.product(target_scope)?;
+    scope.items.push(hidden_scope_item());
+    let relation_type = qcx.relation_type(&source.clone().join(
+        target.clone(),
+        HirScalarExpr::literal_true(),
+        JoinKind::LeftOuter,
+    ));
+    let on = {
+        let ecx = &ExprContext {
+            qcx: &qcx,
+            name: "ON clause",
+            scope: &scope,
+            relation_type: &relation_type,
+            allow_aggregates: false,
+            allow_subqueries: true,
+            allow_parameters: true,
+            allow_windows: false,
+        };
+        plan_expr(ecx, &on)?.type_as(ecx, &ScalarType::Bool)?
+    };
+    let joined = source.join(target, on, JoinKind::LeftOuter);
+    let copies = HirScalarExpr::column(source_arity + arity);
+
+    // The columns of the target table are null for source rows that don't
+    // match any row, so they may not be referenced by `WHEN NOT MATCHED`
+    // clauses.
+    let mut not_matched_scope = scope.clone();
+    for item in &mut not_matched_scope.items[source_arity..source_arity + arity] {
+        item.error_if_referenced = Some(|_table, column| {
+            sql_err!(
+                "invalid reference to column {} of the target table in WHEN NOT MATCHED clause",
+                column.as_str().quoted()
+            )
+        });
+    }
+
+    let null_row: Vec<_> = desc
+        .iter_types()
+        .map(|typ| HirScalarExpr::literal_null(typ.scalar_type.clone()))
+        .collect();
+    let mut matched_preds = vec![copies.clone().call_is_null().not()];
+    let mut not_matched_preds = vec![copies.call_is_null()];
+    let mut matched_unconditional = false;
+    let mut not_matched_unconditional = false;
+    let mut acl_mode = AclMode::empty();
+    let mut branches = vec![];
+    for MergeClause {
+        matched,
+        condition,
+        action,
+    } in clauses
+    {
+        let (preds, unconditional, scope) = if matched {
+            (&mut matched_preds, &mut matched_unconditional, &scope)
+        } else {
+            (
+                &mut not_matched_preds,
+                &mut not_matched_unconditional,
+                &not_matched_scope,
+            )
+        };
+        if *unconditional {
+            sql_bail!("unreachable WHEN clause specified after unconditional WHEN clause");
+        }
+
+        // A clause applies to the rows that satisfy its condition, but not
+        // the condition of any preceding clause.
+        let mut filter = preds.clone();
+        match condition {
+            Some(condition) => {
+                let ecx = &ExprContext {
+                    qcx: &qcx,
+                    name: "WHEN clause",
+                    scope,
+                    relation_type: &relation_type,
+                    allow_aggregates: false,
+                    allow_subqueries: true,
+                    allow_parameters: true,
+                    allow_windows: false,
+                };
+                let condition = plan_expr(ecx, &condition)?
+                    .type_as(ecx, &ScalarType::Bool)?
+                    .call_unary(UnaryFunc::IsTrue(expr_func::IsTrue));
+                filter.push(condition.clone());
+                preds.push(condition.not());
+            }
+            None => *unconditional = true,
+        }
+        let rows = joined.clone().filter(filter);
+
+        let ecx = &ExprContext {
+            qcx: &qcx,
+            name: "MERGE action",
+            scope,
+            relation_type: &relation_type,
+            allow_aggregates: false,
+            allow_subqueries: false,
+            allow_parameters: true,
+            allow_windows: false,
+        };
+        let (insert, new) = match action {
+            MergeAction::Update { assignments } => {
+                acl_mode |= AclMode::UPDATE;
+                let mut new: Vec<_> = (source_arity..source_arity + arity)
+                    .map(HirScalarExpr::column)
+                    .collect();
+                for (idx, expr) in plan_assignments(ecx, &desc, assignments)? {
+                    new[idx] = expr;
+                }
+                (HirScalarExpr::literal_true(), new)
+            }
+            MergeAction::Delete => {
+                acl_mode |= AclMode::DELETE;
+                (HirScalarExpr::literal_false(), null_row.clone())
+            }
+            MergeAction::Insert { columns, values } => {
+                acl_mode |= AclMode::INSERT;
+                let new = plan_merge_insert(ecx, &full_name, &desc, &defaults, columns, values)?;
+                (HirScalarExpr::literal_true(), new)
+            }
+            MergeAction::DoNothing => continue,
+        };
+        branches.push(plan_encoded_changes(rows, source_arity, arity, insert, new));
+    }
+
+    let selection = match branches.into_iter().reduce(|l, r| l.union(r)) {
+        Some(selection) => selection,
+        None => plan_encoded_changes(
+            joined.filter(vec![HirScalarExpr::literal_false()]),
+            source_arity,
+            arity,
+            HirScalarExpr::literal_false(),
+            null_row,
+        ),
+    };
+
+    let mut plan = ReadThenWritePlan {
+        id,
+        selection,
+        assignments: BTreeMap::new(),
+        finishing: RowSetFinishing {
+            order_by: vec![],
+            limit: None,
+            offset: 0,
+            project: (0..2 * arity + 2).collect(),
+        },
+        changes: Some(ChangesPlan {
+            acl_mode,
+            unique_keys: vec![],
+            skip_duplicates: false,
+        }),
+        enforced_keys: vec![],
+    };
+    plan_unique_key_check(&qcx, table_name, &mut plan)?;
+    Ok(plan)
+}
+
+/// Plans the row inserted by the `INSERT` action of a `MERGE` statement,
+/// filling in the defaults of omitted columns.
+fn plan_merge_insert(
+    ecx: &ExprContext,
+    table_name: &str,
+    desc: &RelationDesc,
+    defaults: &[Expr<Aug>],
+    columns: Vec<Ident>,
+    values: Vec<Expr<Aug>>,
+) -> Result<Vec<HirScalarExpr>, PlanError> {
+    let columns: Vec<_> = columns.into_iter().map(normalize::column_name).collect();
+    if let Some(dup) = columns.iter().duplicates().next() {
+        sql_bail!("column {} specified more than once", dup.as_str().quoted());
+    }
+
+    let mut ordering = Vec::with_capacity(values.len());
+    if columns.is_empty() {
+        if values.len() > desc.arity() {
+            sql_bail!("INSERT has more expressions than target columns");
+        }
+        ordering.extend(0..values.len());
+    } else {
+        for c in &columns {
+            match desc.get_by_name(c) {
+                Some((idx, _)) => ordering.push(idx),
+                None => sql_bail!(
+                    "column {} of relation {} does not exist",
+                    c.as_str().quoted(),
+                    table_name.quoted()
+                ),
+            }
+        }
+        if values.len() > columns.len() {
+            sql_bail!("INSERT has more expressions than target columns");
+        }
+        if values.len() < columns.len() {
+            sql_bail!("INSERT has more target columns than expressions");
+        }
+    }
+
+    let mut new = Vec::with_capacity(desc.arity());
+    for (idx, (typ, default)) in desc.iter_types().zip_eq(defaults).enumerate() {
+        let expr = match ordering.iter().position(|i| *i == idx) {
+            Some(pos) => plan_expr(ecx, &values[pos])?.cast_to(
+                ecx,
+                CastContext::Assignment,
+                &typ.scalar_type,
+            )?,
+            None => plan_default_expr(ecx.qcx.scx, default, &typ.scalar_type)?,
+        };
+        new.push(expr);
+    }
+    Ok(new)
+}
+
 pub fn plan_mutation_query_inner(
     qcx: QueryContext,
     table_name: ResolvedItemName,
@@ -688,38 +1133,11 @@ pub fn plan_mutation_query_inner(
     assignments: Vec<Assignment<Aug>>,
     selection: Option<Expr<Aug>>,
 ) -> Result<ReadThenWritePlan, PlanError> {
-    // Get ID and version of the relation desc.
-    let (id, version) = match table_name {
-        ResolvedItemName::Item { id, version, .. } => (id, version),
-        _ => sql_bail!("cannot mutate non-user table"),
-    };
-
-    // Perform checks on item with given ID.
-    let item = qcx.scx.get_item(&id).at_version(version);
-    if item.item_type() != CatalogItemType::Table {
-        sql_bail!(
-            "cannot mutate {} '{}'",
-            item.item_type(),
-            table_name.full_name_str()
-        );
-    }
-    let _ = item.writable_table_details().ok_or_else(|| {
-        sql_err!(
-            "cannot mutate non-writeable table '{}'",
-            table_name.full_name_str()
-        )
-    })?;
-    if id.is_system() {
-        sql_bail!(
-            "cannot mutate system table '{}'",
-            table_name.full_name_str()
-        );
-    }
+    let (id, desc, _defaults) = plan_mutation_target(qcx.scx, &table_name)?;
 
     // Derive structs for operation from validated table
-    let (mut get, scope) = qcx.resolve_table_name(table_name)?;
+    let (mut get, scope) = qcx.resolve_table_name(table_name.clone())?;
     let scope = plan_table_alias(scope, alias.as_ref())?;
-    let desc = item.desc(&qcx.scx.catalog.resolve_full_name(item.name()))?;
     let relation_type = qcx.relation_type(&get);
 
     if using.is_empty() {
@@ -741,22 +1159,53 @@ pub fn plan_mutation_query_inner(
         get = handle_mutation_using_clause(&qcx, selection, using, get, scope.clone())?;
     }
 
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "SET clause",
+        scope: &scope,
+        relation_type: &relation_type,
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: true,
+        allow_windows: false,
+    };
+    let sets = plan_assignments(ecx, &desc, assignments)?;
+
+    let finishing = RowSetFinishing {
+        order_by: vec![],
+        limit: None,
+        offset: 0,
+        project: (0..desc.arity()).collect(),
+    };
+
+    let mut plan = ReadThenWritePlan {
+        id,
+        selection: get,
+        finishing,
+        assignments: sets,
+        changes: None,
+        enforced_keys: vec![],
+    };
+    // Deletes can't violate a unique key, but updates can.
+    if !plan.assignments.is_empty() {
+        plan_unique_key_check(&qcx, table_name, &mut plan)?;
+    }
+    Ok(plan)
+}
+
+/// Plans the assignments of a `SET` clause, returning a map from column index
+/// to the column's new value.
+fn plan_assignments(
+    ecx: &ExprContext,
+    desc: &RelationDesc,
+    assignments: Vec<Assignment<Aug>>,
+) -> Result<BTreeMap<usize, HirScalarExpr>, PlanError> {
     let mut sets = BTreeMap::new();
     for Assignment { id, value } in assignments {
         // Get the index and type of the column.
         let name = normalize::column_name(id);
         match desc.get_by_name(&name) {
             Some((idx, typ)) => {
-                let ecx = &ExprContext {
-                    qcx: &qcx,
-                    name: "SET clause",
-                    scope: &scope,
-                    relation_type: &relation_type,
-                    allow_aggregates: false,
-                    allow_subqueries: false,
-                    allow_parameters: true,
-                    allow_windows: false,
-                };
                 let expr = plan_expr(ecx, &value)?.cast_to(
                     ecx,
                     CastContext::Assignment,
@@ -770,20 +1219,189 @@ pub fn plan_mutation_query_inner(
             None => sql_bail!("unknown column {}", name),
         };
     }
+    Ok(sets)
+}
 
-    let finishing = RowSetFinishing {
-        order_by: vec![],
-        limit: None,
-        offset: 0,
-        project: (0..desc.arity()).collect(),
+/// Validates that `table_name` can be the target of a mutation, returning the
+/// table's ID, description, and column defaults.
+fn plan_mutation_target(
+    scx: &StatementContext,
+    table_name: &ResolvedItemName,
+) -> Result<(CatalogItemId, RelationDesc, Vec<Expr<Aug>>), PlanError> {
+    // Get ID and version of the relation desc.
+    let (id, version) = match table_name {
+        ResolvedItemName::Item { id, version, .. } => (*id, *version),
+        _ => sql_bail!("cannot mutate non-user table"),
     };
 
-    Ok(ReadThenWritePlan {
+    // Perform checks on item with given ID.
+    let item = scx.get_item(&id).at_version(version);
+    if item.item_type() != CatalogItemType::Table {
+        sql_bail!(
+            "cannot mutate {} '{}'",
+            item.item_type(),
+            table_name.full_name_str()
+        );
+    }
+    let mut defaults = item
+        .writable_table_details()
+        .ok_or_else(|| {
+            sql_err!(
+                "cannot mutate non-writeable table '{}'",
+                table_name.full_name_str()
+            )
+        })?
+        .to_vec();
+    if id.is_system() {
+        sql_bail!(
+            "cannot mutate system table '{}'",
+            table_name.full_name_str()
+        );
+    }
+
+    for default in &mut defaults {
+        transform_ast::transform(scx, default)?;
+    }
+
+    let desc = item
+        .desc(&scx.catalog.resolve_full_name(item.name()))?
+        .into_owned();
+    Ok((id, desc, defaults))
+}
+
+/// Plans an `INSERT` of the rows of `expr`, as planned by
+/// [`plan_insert_query`], as a read-then-write.
+///
+/// Inserts into tables with unique keys must be planned this way, so that the
+/// inserted rows are checked against the contents of the table.
+pub fn plan_insert_read_then_write(
+    scx: &StatementContext,
+    table_name: ResolvedItemName,
+    id: CatalogItemId,
+    expr: HirRelationExpr,
+) -> Result<ReadThenWritePlan, PlanError> {
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let arity = qcx.relation_type(&expr).arity();
+    let mut plan = ReadThenWritePlan {
         id,
-        selection: get,
-        finishing,
-        assignments: sets,
-    })
+        selection: expr,
+        assignments: BTreeMap::new(),
+        finishing: RowSetFinishing {
+            order_by: vec![],
+            limit: None,
+            offset: 0,
+            project: (0..arity).collect(),
+        },
+        changes: None,
+        enforced_keys: vec![],
+    };
+    plan_unique_key_check(&qcx, table_name, &mut plan)?;
+    Ok(plan)
+}
+
+/// Extends `plan` to check that the write does not violate the unique keys of
+/// the table, if it has any.
+///
+/// The current rows of the table are added to the selection, in the form
+/// documented on [`crate::plan::ReadThenWritePlan::enforced_keys`]. Because the
+/// table is read at the same time as the rest of the selection, and no other
+/// writes to the table can happen until the write completes, applying the
+/// write to these rows yields the contents of the table after the write.
+fn plan_unique_key_check(
+    qcx: &QueryContext,
+    table_name: ResolvedItemName,
+    plan: &mut ReadThenWritePlan,
+) -> Result<(), PlanError> {
+    let (table, _) = qcx.resolve_table_name(table_name)?;
+    let table_type = qcx.relation_type(&table);
+    if table_type.keys.is_empty() {
+        return Ok(());
+    }
+    let arity = table_type.arity();
+    let selection_type = qcx.relation_type(&plan.selection);
+    let selection_arity = selection_type.arity();
+    let nulls = |typ: &RelationType| {
+        typ.column_types
+            .iter()
+            .map(|typ| HirScalarExpr::literal_null(typ.scalar_type.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    let selected = plan
+        .selection
+        .take()
+        .map(
+            iter::once(HirScalarExpr::literal_false())
+                .chain(nulls(&table_type))
+                .collect(),
+        )
+        .project(
+            iter::once(selection_arity)
+                .chain(0..selection_arity)
+                .chain(selection_arity + 1..selection_arity + arity + 1)
+                .collect(),
+        );
+    let current = table
+        .map(
+            iter::once(HirScalarExpr::literal_true())
+                .chain(nulls(&selection_type))
+                .collect(),
+        )
+        .project(
+            iter::once(arity)
+                .chain(arity + 1..arity + selection_arity + 1)
+                .chain(0..arity)
+                .collect(),
+        );
+    plan.selection = selected.union(current);
+    plan.finishing.project = iter::once(0)
+        .chain(plan.finishing.project.iter().map(|i| i + 1))
+        .chain(selection_arity + 1..selection_arity + arity + 1)
+        .collect();
+    plan.enforced_keys = table_type.keys;
+    Ok(())
+}
+
+/// Reduces `expr`, whose arity is `arity`, to its distinct rows, each extended
+/// with the number of copies of the row.
+fn plan_counted_rows(expr: HirRelationExpr, arity: usize) -> HirRelationExpr {
+    expr.reduce(
+        (0..arity).collect(),
+        vec![AggregateExpr {
+            func: AggregateFunc::Count,
+            expr: Box::new(HirScalarExpr::literal_true()),
+            distinct: false,
+        }],
+        None,
+    )
+}
+
+/// Returns a scope item for a column that can't be referenced.
+fn hidden_scope_item() -> ScopeItem {
+    let mut item = ScopeItem::empty();
+    item.allow_unqualified_references = false;
+    item
+}
+
+/// Encodes changes to a table in the form documented on [`ChangesPlan`].
+///
+/// The rows of `expr` must end with a row of the table starting at column
+/// `old`, followed by its number of copies. `insert` and `new` are evaluated
+/// against the rows of `expr`, and `arity` is the arity of the table.
+fn plan_encoded_changes(
+    expr: HirRelationExpr,
+    old: usize,
+    arity: usize,
+    insert: HirScalarExpr,
+    new: Vec<HirScalarExpr>,
+) -> HirRelationExpr {
+    let copies = old + arity;
+    let project = iter::once(copies)
+        .chain(old..copies)
+        .chain(copies + 1..copies + arity + 2)
+        .collect();
+    expr.map(iter::once(insert).chain(new).collect())
+        .project(project)
 }
 
 // Adjust `get` to perform an existential subquery on `using` accounting for
@@ -1897,6 +2515,10 @@ fn plan_set_expr(
                     show::plan_show_create_view(qcx.scx, stmt.clone())?,
                     show::describe_show_create_view(qcx.scx, stmt)?,
                 ),
+                ShowStatement::ShowCreateFunction(stmt) => to_hirscope(
+                    show::plan_show_create_function(qcx.scx, stmt.clone())?,
+                    show::describe_show_create_function(qcx.scx, stmt)?,
+                ),
                 ShowStatement::ShowCreateMaterializedView(stmt) => to_hirscope(
                     show::plan_show_create_materialized_view(qcx.scx, stmt.clone())?,
                     show::describe_show_create_materialized_view(qcx.scx, stmt)?,
@@ -2848,9 +3470,164 @@ fn plan_table_factor(
             let scope = plan_table_alias(scope, alias.as_ref())?;
             Ok((expr, scope))
         }
+
+        TableFactor::JsonTable {
+            context,
+            path,
+            passing,
+            columns,
+            alias,
+        } => plan_json_table(qcx, context, path, passing, columns, alias.as_ref()),
     }
 }
 
+/// Plans a `JSON_TABLE` expression.
+///
+/// The rows are produced by `TableFunc::JsonTable`, which yields the values of
+/// columns as text, `jsonb` or booleans. The columns are then cast to their
+/// declared types.
+fn plan_json_table(
+    qcx: &QueryContext,
+    context: &Expr<Aug>,
+    path: &str,
+    passing: &[JsonTablePassing<Aug>],
+    columns: &[JsonTableColumn<Aug>],
+    alias: Option<&TableAlias>,
+) -> Result<(HirRelationExpr, Scope), PlanError> {
+    let ecx = &ExprContext {
+        qcx,
+        name: "JSON_TABLE",
+        scope: &Scope::empty(),
+        relation_type: &RelationType::empty(),
+        allow_aggregates: false,
+        allow_subqueries: true,
+        allow_parameters: true,
+        allow_windows: false,
+    };
+    let context = plan_expr(ecx, context)?.type_as(ecx, &ScalarType::Jsonb)?;
+    let vars = if passing.is_empty() {
+        HirScalarExpr::literal(Datum::empty_map(), ScalarType::Jsonb)
+    } else {
+        let mut exprs = vec![];
+        for JsonTablePassing { expr, name } in passing {
+            let expr = plan_expr(ecx, expr)?.type_as_any(ecx)?;
+            exprs.push(HirScalarExpr::literal(
+                Datum::String(name.as_str()),
+                ScalarType::String,
+            ));
+            exprs.push(typeconv::to_jsonb(ecx, expr));
+        }
+        HirScalarExpr::CallVariadic {
+            func: VariadicFunc::JsonbBuildObject,
+            exprs,
+        }
+    };
+
+    let mut table_columns = vec![];
+    let mut column_names = vec![];
+    // The type to cast each column to, if it is not already of that type.
+    let mut casts = vec![];
+    for column in columns {
+        let (name, table_column, cast) = match column {
+            JsonTableColumn::Ordinality { name } => {
+                (name, mz_expr::JsonTableColumn::Ordinality, None)
+            }
+            JsonTableColumn::Value {
+                name,
+                data_type,
+                path,
+            } => {
+                let ty = scalar_type_from_sql(qcx.scx, data_type)?;
+                let path = plan_json_table_path(name, path.as_deref())?;
+                let jsonb = ty == ScalarType::Jsonb;
+                let column = mz_expr::JsonTableColumn::Value { path, jsonb };
+                (name, column, (!jsonb).then_some(ty))
+            }
+            JsonTableColumn::Exists {
+                name,
+                data_type,
+                path,
+            } => {
+                let ty = scalar_type_from_sql(qcx.scx, data_type)?;
+                let path = plan_json_table_path(name, path.as_deref())?;
+                let column = mz_expr::JsonTableColumn::Exists { path };
+                (name, column, (ty != ScalarType::Bool).then_some(ty))
+            }
+        };
+        let name = normalize::column_name(name.clone());
+        if column_names.contains(&name) {
+            sql_bail!(
+                "duplicate JSON_TABLE column name: {}",
+                name.as_str().quoted()
+            );
+        }
+        table_columns.push(table_column);
+        column_names.push(name);
+        casts.push(cast);
+    }
+
+    let func = mz_expr::TableFunc::JsonTable {
+        path: strconv::parse_jsonpath(path)?.to_string(),
+        columns: table_columns,
+    };
+    let relation_type = func.output_type();
+    let expr = HirRelationExpr::CallTable {
+        func,
+        exprs: vec![context, vars],
+    };
+
+    let ecx = &ExprContext {
+        qcx,
+        name: "JSON_TABLE",
+        scope: &Scope::empty(),
+        relation_type: &relation_type,
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    let mut map_exprs = vec![];
+    let mut outputs = vec![];
+    for (i, cast) in casts.into_iter().enumerate() {
+        match cast {
+            Some(ty) => {
+                let column = HirScalarExpr::column(i);
+                outputs.push(columns.len() + map_exprs.len());
+                map_exprs.push(typeconv::plan_cast(
+                    ecx,
+                    CastContext::Explicit,
+                    column,
+                    &ty,
+                )?);
+            }
+            None => outputs.push(i),
+        }
+    }
+    let expr = expr.map(map_exprs).project(outputs);
+
+    let scope_name = Some(PartialItemName {
+        database: None,
+        schema: None,
+        item: "json_table".into(),
+    });
+    let scope = Scope::from_source(scope_name, column_names);
+    let scope = plan_table_alias(scope, alias)?;
+    Ok((expr, scope))
+}
+
+/// Plans the path of a `JSON_TABLE` column named `name`, which defaults to the
+/// member of the row's item with the same name.
+fn plan_json_table_path(name: &Ident, path: Option<&str>) -> Result<String, PlanError> {
+    let path = match path {
+        Some(path) => strconv::parse_jsonpath(path)?,
+        None => {
+            let key = name.as_str().replace('\\', "\\\\").replace('"', "\\\"");
+            strconv::parse_jsonpath(&format!("$.\"{}\"", key))?
+        }
+    };
+    Ok(path.to_string())
+}
+
 /// Plans a `ROWS FROM` expression.
 ///
 /// `ROWS FROM` concatenates table functions into a single table, filling in
@@ -3107,6 +3884,10 @@ fn plan_table_function_internal(
 
     let scalar_args = match args {
         FunctionArgs::Star => sql_bail!("{} does not accept * as an argument", name),
+        FunctionArgs::WithinGroup { .. } => sql_bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            name
+        ),
         FunctionArgs::Args { args, order_by } => {
             if !order_by.is_empty() {
                 sql_bail!(
@@ -4880,7 +5661,7 @@ fn plan_aggregate_common(
         name,
         args,
         filter,
-        over: _,
+        over,
         distinct,
     }: &Function<Aug>,
 ) -> Result<AggregateExpr, PlanError> {
@@ -4911,8 +5692,19 @@ fn plan_aggregate_common(
     // rules to all aggregates, not just `count`, since we may one day support
     // user-defined aggregates, including user-defined aggregates that take no
     // parameters.
+    let within_group = matches!(args, FunctionArgs::WithinGroup { .. });
     let (args, order_by) = match &args {
         FunctionArgs::Star => (vec![], vec![]),
+        // The aggregated arguments of an ordered-set aggregate are the
+        // expressions in its `WITHIN GROUP (ORDER BY ...)` clause, which the
+        // function selection framework sees after the direct arguments.
+        FunctionArgs::WithinGroup { args, order_by } => {
+            let mut args = plan_exprs(ecx, args)?;
+            for obe in order_by {
+                args.push(plan_expr(ecx, &obe.expr)?);
+            }
+            (args, order_by.clone())
+        }
         FunctionArgs::Args { args, order_by } => {
             if args.is_empty() {
                 sql_bail!(
@@ -4931,6 +5723,27 @@ fn plan_aggregate_common(
     let (order_by_exprs, col_orders) = plan_function_order_by(ecx, &order_by)?;
 
     let (mut expr, func) = func::select_impl(ecx, FuncSpec::Func(name), impls, args, col_orders)?;
+    if func.is_ordered_set() != within_group {
+        let name = ecx
+            .qcx
+            .scx
+            .humanize_resolved_name(name)
+            .expect("name actually resolved");
+        if within_group {
+            sql_bail!("{name} is not an ordered-set aggregate, so it cannot have WITHIN GROUP");
+        } else {
+            sql_bail!("WITHIN GROUP is required for ordered-set aggregate {name}");
+        }
+    }
+    if over.is_some() && func.is_ordered_set() {
+        bail_unsupported!(format!(
+            "OVER for ordered-set aggregate {}",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("name actually resolved")
+        ));
+    }
     if let Some(filter) = &filter {
         // If a filter is present, as in
         //
@@ -5168,11 +5981,31 @@ fn plan_function<'a>(
 
             if ignore_nulls {
                 match func {
-                    ValueWindowFunc::Lag | ValueWindowFunc::Lead => {}
+                    ValueWindowFunc::Lag
+                    | ValueWindowFunc::Lead
+                    | ValueWindowFunc::FirstValue
+                    | ValueWindowFunc::LastValue
+                    | ValueWindowFunc::NthValue => {}
                     _ => bail_unsupported!(IGNORE_NULLS_ERROR_MSG),
                 }
             }
 
+            // Postgres errors out at runtime on a non-positive argument. We can only catch this
+            // for literals; otherwise, the result is null.
+            let n = match func {
+                ValueWindowFunc::Ntile => Some(&args_encoded),
+                ValueWindowFunc::NthValue => match &args_encoded {
+                    HirScalarExpr::CallVariadic { exprs, .. } => exprs.get(1),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(Datum::Int32(n)) = n.and_then(|n| n.as_literal()) {
+                if n <= 0 {
+                    sql_bail!("argument of {} must be greater than zero", func);
+                }
+            }
+
             return Ok(HirScalarExpr::Windowing(WindowExpr {
                 func: WindowExprType::Value(ValueWindowExpr {
                     func,
@@ -5208,27 +6041,6 @@ fn plan_function<'a>(
                 let (ignore_nulls, order_by_exprs, col_orders, window_frame, partition_by) =
                     plan_window_function_common(ecx, &f.name, &f.over)?;
 
-                // https://github.com/MaterializeInc/database-issues/issues/6720
-                match (&window_frame.start_bound, &window_frame.end_bound) {
-                    (
-                        mz_expr::WindowFrameBound::UnboundedPreceding,
-                        mz_expr::WindowFrameBound::OffsetPreceding(..),
-                    )
-                    | (
-                        mz_expr::WindowFrameBound::UnboundedPreceding,
-                        mz_expr::WindowFrameBound::OffsetFollowing(..),
-                    )
-                    | (
-                        mz_expr::WindowFrameBound::OffsetPreceding(..),
-                        mz_expr::WindowFrameBound::UnboundedFollowing,
-                    )
-                    | (
-                        mz_expr::WindowFrameBound::OffsetFollowing(..),
-                        mz_expr::WindowFrameBound::UnboundedFollowing,
-                    ) => bail_unsupported!("mixed unbounded - offset frames"),
-                    (_, _) => {} // other cases are ok
-                }
-
                 if ignore_nulls {
                     // https://github.com/MaterializeInc/database-issues/issues/6722
                     // If we ever add support for ignore_nulls for a window aggregate, then don't
@@ -5238,11 +6050,6 @@ fn plan_function<'a>(
 
                 let aggregate_expr = plan_aggregate_common(ecx, f)?;
 
-                if aggregate_expr.distinct {
-                    // https://github.com/MaterializeInc/database-issues/issues/6626
-                    bail_unsupported!("DISTINCT in window aggregates");
-                }
-
                 return Ok(HirScalarExpr::Windowing(WindowExpr {
                     func: WindowExprType::Aggregate(AggregateWindowExpr {
                         aggregate_expr,
@@ -5289,6 +6096,15 @@ fn plan_function<'a>(
                     .expect("already resolved")
             )
         }
+        FunctionArgs::WithinGroup { .. } => {
+            sql_bail!(
+                "WITHIN GROUP specified, but {} is not an aggregate function",
+                ecx.qcx
+                    .scx
+                    .humanize_resolved_name(name)
+                    .expect("already resolved")
+            )
+        }
         FunctionArgs::Args { args, order_by } => {
             if !order_by.is_empty() {
                 sql_bail!(
@@ -5306,19 +6122,18 @@ fn plan_function<'a>(
     func::select_impl(ecx, FuncSpec::Func(name), impls, scalar_args, vec![])
 }
 
-pub const IGNORE_NULLS_ERROR_MSG: &str =
-    "IGNORE NULLS and RESPECT NULLS options for functions other than LAG and LEAD";
+pub const IGNORE_NULLS_ERROR_MSG: &str = "IGNORE NULLS and RESPECT NULLS options for functions other than LAG, LEAD, FIRST_VALUE, LAST_VALUE and NTH_VALUE";
 
 /// Resolves the name to a set of function implementations.
 ///
-/// If the name does not specify a known built-in function, returns an error.
-pub fn resolve_func(
-    ecx: &ExprContext,
+/// If the name does not specify a known function, returns an error.
+pub fn resolve_func<'a>(
+    ecx: &ExprContext<'a>,
     name: &ResolvedItemName,
     args: &mz_sql_parser::ast::FunctionArgs<Aug>,
-) -> Result<&'static Func, PlanError> {
-    if let Ok(i) = ecx.qcx.scx.get_item_by_resolved_name(name) {
-        if let Ok(f) = i.func() {
+) -> Result<&'a Func, PlanError> {
+    if let ResolvedItemName::Item { id, .. } = name {
+        if let Ok(f) = ecx.qcx.scx.catalog.get_item(id).func() {
             return Ok(f);
         }
     }
@@ -5336,6 +6151,13 @@ pub fn resolve_func(
             }
             plan_exprs(ecx, args)?
         }
+        mz_sql_parser::ast::FunctionArgs::WithinGroup { args, order_by } => {
+            let mut cexprs = plan_exprs(ecx, args)?;
+            for obe in order_by {
+                cexprs.push(plan_expr(ecx, &obe.expr)?);
+            }
+            cexprs
+        }
     };
 
     let arg_types: Vec<_> = cexprs
@@ -5520,6 +6342,12 @@ fn plan_window_function_non_aggr<'a>(
         FunctionArgs::Star => {
             sql_bail!("* argument is invalid with non-aggregate function {}", name)
         }
+        FunctionArgs::WithinGroup { .. } => {
+            sql_bail!(
+                "WITHIN GROUP specified, but {} is not an aggregate function",
+                name
+            )
+        }
         FunctionArgs::Args { args, order_by } => {
             if !order_by.is_empty() {
                 sql_bail!(
@@ -5582,6 +6410,45 @@ fn plan_window_function_common(
 
     let (order_by_exprs, col_orders) = plan_function_order_by(ecx, &window_spec.order_by)?;
 
+    let has_offset = |bound: &mz_expr::WindowFrameBound| {
+        matches!(
+            bound,
+            mz_expr::WindowFrameBound::OffsetPreceding(_)
+                | mz_expr::WindowFrameBound::OffsetFollowing(_)
+        )
+    };
+    match window_frame.units {
+        mz_expr::WindowFrameUnits::Range
+            if has_offset(&window_frame.start_bound) || has_offset(&window_frame.end_bound) =>
+        {
+            // The offsets are added to or subtracted from the value of the ORDER BY expression.
+            if order_by_exprs.len() != 1 {
+                sql_bail!(
+                    "RANGE with offset PRECEDING/FOLLOWING requires exactly one ORDER BY column"
+                );
+            }
+            match ecx.scalar_type(&order_by_exprs[0]) {
+                ScalarType::Int16
+                | ScalarType::Int32
+                | ScalarType::Int64
+                | ScalarType::UInt16
+                | ScalarType::UInt32
+                | ScalarType::UInt64
+                | ScalarType::Float32
+                | ScalarType::Float64
+                | ScalarType::Numeric { .. } => {}
+                typ => bail_unsupported!(format!(
+                    "RANGE with offset PRECEDING/FOLLOWING for type {}",
+                    ecx.humanize_scalar_type(&typ, false)
+                )),
+            }
+        }
+        mz_expr::WindowFrameUnits::Groups if order_by_exprs.is_empty() => {
+            sql_bail!("GROUPS mode requires an ORDER BY clause")
+        }
+        _ => {}
+    }
+
     Ok((
         window_spec.ignore_nulls,
         order_by_exprs,
@@ -5657,14 +6524,6 @@ fn plan_window_frame(
         (_, _) => (),
     }
 
-    // RANGE is only supported in the default frame
-    // https://github.com/MaterializeInc/database-issues/issues/6585
-    if units == mz_expr::WindowFrameUnits::Range
-        && (start_bound != UnboundedPreceding || end_bound != CurrentRow)
-    {
-        bail_unsupported!("RANGE in non-default window frames")
-    }
-
     let frame = mz_expr::WindowFrame {
         units,
         start_bound,
@@ -5679,7 +6538,7 @@ fn window_frame_unit_ast_to_expr(
     match unit {
         WindowFrameUnits::Rows => Ok(mz_expr::WindowFrameUnits::Rows),
         WindowFrameUnits::Range => Ok(mz_expr::WindowFrameUnits::Range),
-        WindowFrameUnits::Groups => bail_unsupported!("GROUPS in window frames"),
+        WindowFrameUnits::Groups => Ok(mz_expr::WindowFrameUnits::Groups),
     }
 }
 
@@ -5894,6 +6753,7 @@ pub fn scalar_type_from_catalog(
                     Ok(ScalarType::Record {
                         fields: scalars,
                         custom_id: Some(id),
+                        custom_oid: Some(entry.oid()),
                     })
                 }
                 CatalogType::AclItem => Ok(ScalarType::AclItem),
@@ -5928,6 +6788,20 @@ pub fn scalar_type_from_catalog(
                 CatalogType::Uuid => Ok(ScalarType::Uuid),
                 CatalogType::Int2Vector => Ok(ScalarType::Int2Vector),
                 CatalogType::MzAclItem => Ok(ScalarType::MzAclItem),
+                CatalogType::Inet => Ok(ScalarType::Inet),
+                CatalogType::Cidr => Ok(ScalarType::Cidr),
+                CatalogType::MacAddr => Ok(ScalarType::MacAddr),
+                CatalogType::MacAddr8 => Ok(ScalarType::MacAddr8),
+                CatalogType::TsVector => Ok(ScalarType::TsVector),
+                CatalogType::TsQuery => Ok(ScalarType::TsQuery),
+                CatalogType::JsonPath => Ok(ScalarType::JsonPath),
+                CatalogType::Geometry => Ok(ScalarType::Geometry),
+                CatalogType::Geography => Ok(ScalarType::Geography),
+                CatalogType::Enum { labels } => Ok(ScalarType::Enum {
+                    labels: labels.clone().into(),
+                    custom_id: id,
+                    custom_oid: entry.oid(),
+                }),
                 CatalogType::Numeric => unreachable!("handled above"),
                 CatalogType::Char => unreachable!("handled above"),
                 CatalogType::VarChar => unreachable!("handled above"),
@@ -50,7 +50,9 @@ mod tcl;
 mod validate;
 
 use crate::session::vars;
-pub(crate) use ddl::PgConfigOptionExtracted;
+pub(crate) use ddl::{
+    postgres_sink_table, PgConfigOptionExtracted, PostgresSinkConfigOptionExtracted,
+};
 use mz_controller_types::ClusterId;
 use mz_pgrepr::oid::{FIRST_MATERIALIZE_OID, FIRST_USER_OID};
 use mz_repr::role_id::RoleId;
@@ -136,12 +138,14 @@ pub fn describe(
         Statement::AlterSystemReset(stmt) => ddl::describe_alter_system_reset(&scx, stmt)?,
         Statement::AlterSystemResetAll(stmt) => ddl::describe_alter_system_reset_all(&scx, stmt)?,
         Statement::AlterTableAddColumn(stmt) => ddl::describe_alter_table_add_column(&scx, stmt)?,
+        Statement::AlterViewQuery(stmt) => ddl::describe_alter_view_query(&scx, stmt)?,
         Statement::AlterNetworkPolicy(stmt) => ddl::describe_alter_network_policy(&scx, stmt)?,
         Statement::Comment(stmt) => ddl::describe_comment(&scx, stmt)?,
         Statement::CreateCluster(stmt) => ddl::describe_create_cluster(&scx, stmt)?,
         Statement::CreateClusterReplica(stmt) => ddl::describe_create_cluster_replica(&scx, stmt)?,
         Statement::CreateConnection(stmt) => ddl::describe_create_connection(&scx, stmt)?,
         Statement::CreateDatabase(stmt) => ddl::describe_create_database(&scx, stmt)?,
+        Statement::CreateFunction(stmt) => ddl::describe_create_function(&scx, stmt)?,
         Statement::CreateIndex(stmt) => ddl::describe_create_index(&scx, stmt)?,
         Statement::CreateRole(stmt) => ddl::describe_create_role(&scx, stmt)?,
         Statement::CreateSchema(stmt) => ddl::describe_create_schema(&scx, stmt)?,
@@ -200,6 +204,9 @@ pub fn describe(
         Statement::Show(ShowStatement::ShowCreateView(stmt)) => {
             show::describe_show_create_view(&scx, stmt)?
         }
+        Statement::Show(ShowStatement::ShowCreateFunction(stmt)) => {
+            show::describe_show_create_function(&scx, stmt)?
+        }
         Statement::Show(ShowStatement::ShowCreateMaterializedView(stmt)) => {
             show::describe_show_create_materialized_view(&scx, stmt)?
         }
@@ -229,6 +236,7 @@ pub fn describe(
         Statement::ExplainTimestamp(stmt) => dml::describe_explain_timestamp(&scx, stmt)?,
         Statement::ExplainSinkSchema(stmt) => dml::describe_explain_schema(&scx, stmt)?,
         Statement::Insert(stmt) => dml::describe_insert(&scx, stmt)?,
+        Statement::Merge(stmt) => dml::describe_merge(&scx, stmt)?,
         Statement::Select(stmt) => dml::describe_select(&scx, stmt)?,
         Statement::Subscribe(stmt) => dml::describe_subscribe(&scx, stmt)?,
         Statement::Update(stmt) => dml::describe_update(&scx, stmt)?,
@@ -322,12 +330,14 @@ pub fn plan(
         Statement::AlterSystemReset(stmt) => ddl::plan_alter_system_reset(scx, stmt),
         Statement::AlterSystemResetAll(stmt) => ddl::plan_alter_system_reset_all(scx, stmt),
         Statement::AlterTableAddColumn(stmt) => ddl::plan_alter_table_add_column(scx, stmt),
+        Statement::AlterViewQuery(stmt) => ddl::plan_alter_view_query(scx, stmt, params),
         Statement::AlterNetworkPolicy(stmt) => ddl::plan_alter_network_policy(scx, stmt),
         Statement::Comment(stmt) => ddl::plan_comment(scx, stmt),
         Statement::CreateCluster(stmt) => ddl::plan_create_cluster(scx, stmt),
         Statement::CreateClusterReplica(stmt) => ddl::plan_create_cluster_replica(scx, stmt),
         Statement::CreateConnection(stmt) => ddl::plan_create_connection(scx, stmt),
         Statement::CreateDatabase(stmt) => ddl::plan_create_database(scx, stmt),
+        Statement::CreateFunction(stmt) => ddl::plan_create_function(scx, stmt),
         Statement::CreateIndex(stmt) => ddl::plan_create_index(scx, stmt),
         Statement::CreateRole(stmt) => ddl::plan_create_role(scx, stmt),
         Statement::CreateSchema(stmt) => ddl::plan_create_schema(scx, stmt),
@@ -365,6 +375,7 @@ pub fn plan(
         Statement::ExplainTimestamp(stmt) => dml::plan_explain_timestamp(scx, stmt, params),
         Statement::ExplainSinkSchema(stmt) => dml::plan_explain_schema(scx, stmt),
         Statement::Insert(stmt) => dml::plan_insert(scx, stmt, params),
+        Statement::Merge(stmt) => dml::plan_merge(scx, stmt, params),
         Statement::Select(stmt) => dml::plan_select(scx, stmt, params, None),
         Statement::Subscribe(stmt) => dml::plan_subscribe(scx, stmt, params, None),
         Statement::Update(stmt) => dml::plan_update(scx, stmt, params),
@@ -392,6 +403,9 @@ pub fn plan(
         Statement::Show(ShowStatement::ShowCreateView(stmt)) => {
             show::plan_show_create_view(scx, stmt).map(Plan::ShowCreate)
         }
+        Statement::Show(ShowStatement::ShowCreateFunction(stmt)) => {
+            show::plan_show_create_function(scx, stmt).map(Plan::ShowCreate)
+        }
         Statement::Show(ShowStatement::ShowCreateMaterializedView(stmt)) => {
             show::plan_show_create_materialized_view(scx, stmt).map(Plan::ShowCreate)
         }
@@ -1035,12 +1049,14 @@ impl<T: mz_sql_parser::ast::AstInfo> From<&Statement<T>> for StatementClassifica
             Statement::AlterSystemReset(_) => DDL,
             Statement::AlterSystemResetAll(_) => DDL,
             Statement::AlterTableAddColumn(_) => DDL,
+            Statement::AlterViewQuery(_) => DDL,
             Statement::Comment(_) => DDL,
             Statement::CreateCluster(_) => DDL,
             Statement::CreateClusterReplica(_) => DDL,
             Statement::CreateConnection(_) => DDL,
             Statement::CreateContinualTask(_) => DDL,
             Statement::CreateDatabase(_) => DDL,
+            Statement::CreateFunction(_) => DDL,
             Statement::CreateIndex(_) => DDL,
             Statement::CreateRole(_) => DDL,
             Statement::CreateSchema(_) => DDL,
@@ -1075,6 +1091,7 @@ impl<T: mz_sql_parser::ast::AstInfo> From<&Statement<T>> for StatementClassifica
             Statement::ExplainTimestamp(_) => DML,
             Statement::ExplainSinkSchema(_) => DML,
             Statement::Insert(_) => DML,
+            Statement::Merge(_) => DML,
             Statement::Select(_) => DML,
             Statement::Subscribe(_) => DML,
             Statement::Update(_) => DML,
@@ -1083,6 +1100,7 @@ impl<T: mz_sql_parser::ast::AstInfo> From<&Statement<T>> for StatementClassifica
             Statement::Show(ShowStatement::ShowColumns(_)) => Show,
             Statement::Show(ShowStatement::ShowCreateConnection(_)) => Show,
             Statement::Show(ShowStatement::ShowCreateCluster(_)) => Show,
+            Statement::Show(ShowStatement::ShowCreateFunction(_)) => Show,
             Statement::Show(ShowStatement::ShowCreateIndex(_)) => Show,
             Statement::Show(ShowStatement::ShowCreateSink(_)) => Show,
             Statement::Show(ShowStatement::ShowCreateSource(_)) => Show,
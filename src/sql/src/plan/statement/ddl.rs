@@ -20,15 +20,19 @@ use std::time::Duration;
 use itertools::{Either, Itertools};
 use mz_adapter_types::compaction::{CompactionWindow, DEFAULT_LOGICAL_COMPACTION_WINDOW_DURATION};
 use mz_adapter_types::dyncfgs::ENABLE_MULTI_REPLICA_SOURCES;
+use mz_arrow_util::builder::ArrowBuilder;
 use mz_controller_types::{ClusterId, ReplicaId, DEFAULT_REPLICA_LOGGING_INTERVAL};
 use mz_expr::{CollectionPlan, UnmaterializableFunc};
 use mz_interchange::avro::{AvroSchemaGenerator, DocTarget};
+use mz_interchange::protobuf::ProtobufSchemaGenerator;
 use mz_ore::cast::{CastFrom, TryCastFrom};
 use mz_ore::collections::{CollectionExt, HashSet};
 use mz_ore::num::NonNeg;
 use mz_ore::soft_panic_or_log;
 use mz_ore::str::StrExt;
 use mz_ore::vec::VecExt;
+use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams};
+use mz_pgwire_common::ScramSecret;
 use mz_postgres_util::tunnel::PostgresFlavor;
 use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
@@ -38,8 +42,9 @@ use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::refresh_schedule::{RefreshEvery, RefreshSchedule};
 use mz_repr::role_id::RoleId;
 use mz_repr::{
-    preserves_order, strconv, CatalogItemId, ColumnName, ColumnType, RelationDesc, RelationType,
-    RelationVersion, RelationVersionSelector, ScalarType, Timestamp, VersionedRelationDesc,
+    preserves_order, strconv, CatalogItemId, ColumnName, ColumnType, GlobalId, RelationDesc,
+    RelationType, RelationVersion, RelationVersionSelector, ScalarType, Timestamp,
+    VersionedRelationDesc,
 };
 use mz_sql_parser::ast::{
     self, AlterClusterAction, AlterClusterStatement, AlterConnectionAction, AlterConnectionOption,
@@ -49,48 +54,52 @@ use mz_sql_parser::ast::{
     AlterSetClusterStatement, AlterSinkAction, AlterSinkStatement, AlterSourceAction,
     AlterSourceAddSubsourceOption, AlterSourceAddSubsourceOptionName, AlterSourceStatement,
     AlterSystemResetAllStatement, AlterSystemResetStatement, AlterSystemSetStatement,
-    AlterTableAddColumnStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName,
-    ClusterAlterOption, ClusterAlterOptionName, ClusterAlterOptionValue,
+    AlterTableAddColumnStatement, AlterViewQueryStatement, AvroSchema, AvroSchemaOption,
+    AvroSchemaOptionName, ClusterAlterOption, ClusterAlterOptionName, ClusterAlterOptionValue,
     ClusterAlterUntilReadyOption, ClusterAlterUntilReadyOptionName, ClusterFeature,
     ClusterFeatureName, ClusterOption, ClusterOptionName, ClusterScheduleOptionValue, ColumnDef,
     ColumnOption, CommentObjectType, CommentStatement, ConnectionOption, ConnectionOptionName,
     ContinualTaskOption, ContinualTaskOptionName, CreateClusterReplicaStatement,
     CreateClusterStatement, CreateConnectionOption, CreateConnectionOptionName,
     CreateConnectionStatement, CreateConnectionType, CreateContinualTaskStatement,
-    CreateDatabaseStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateNetworkPolicyStatement, CreateRoleStatement, CreateSchemaStatement,
-    CreateSecretStatement, CreateSinkConnection, CreateSinkOption, CreateSinkOptionName,
-    CreateSinkStatement, CreateSourceConnection, CreateSourceOption, CreateSourceOptionName,
-    CreateSourceStatement, CreateSubsourceOption, CreateSubsourceOptionName,
-    CreateSubsourceStatement, CreateTableFromSourceStatement, CreateTableStatement, CreateTypeAs,
-    CreateTypeListOption, CreateTypeListOptionName, CreateTypeMapOption, CreateTypeMapOptionName,
-    CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement, CsrConfigOption,
-    CsrConfigOptionName, CsrConnection, CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf,
+    CreateDatabaseStatement, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateNetworkPolicyStatement, CreateRoleStatement,
+    CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection, CreateSinkOption,
+    CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection, CreateSourceOption,
+    CreateSourceOptionName, CreateSourceStatement, CreateSubsourceOption,
+    CreateSubsourceOptionName, CreateSubsourceStatement, CreateTableFromSourceStatement,
+    CreateTableStatement, CreateTypeAs, CreateTypeListOption, CreateTypeListOptionName,
+    CreateTypeMapOption, CreateTypeMapOptionName, CreateTypeStatement, CreateViewStatement,
+    CreateWebhookSourceStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
+    CsrConnectionAvro, CsrConnectionJson, CsrConnectionProtobuf, CsrSeedJson, CsrSeedProtobuf,
     CsvColumns, DeferredItemName, DocOnIdentifier, DocOnSchema, DropObjectsStatement,
-    DropOwnedStatement, Expr, Format, FormatSpecifier, Ident, IfExistsBehavior, IndexOption,
-    IndexOptionName, KafkaSinkConfigOption, KeyConstraint, LoadGeneratorOption,
+    DropOwnedStatement, Expr, Format, FormatSpecifier, FunctionBody, FunctionColumn,
+    FunctionParameter, FunctionReturnType, IcebergSinkConfigOption, Ident, IfExistsBehavior,
+    IndexOption, IndexOptionName, KafkaSinkConfigOption, KeyConstraint, LoadGeneratorOption,
     LoadGeneratorOptionName, MaterializedViewOption, MaterializedViewOptionName, MySqlConfigOption,
     MySqlConfigOptionName, NetworkPolicyOption, NetworkPolicyOptionName,
     NetworkPolicyRuleDefinition, NetworkPolicyRuleOption, NetworkPolicyRuleOptionName,
-    PgConfigOption, PgConfigOptionName, ProtobufSchema, QualifiedReplica, RefreshAtOptionValue,
-    RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition, ReplicaOption,
-    ReplicaOptionName, RoleAttribute, SetRoleVar, SourceErrorPolicy, SourceIncludeMetadata,
-    Statement, TableConstraint, TableFromSourceColumns, TableFromSourceOption,
-    TableFromSourceOptionName, TableOption, TableOptionName, UnresolvedDatabaseName,
-    UnresolvedItemName, UnresolvedObjectName, UnresolvedSchemaName, Value, ViewDefinition,
-    WithOptionValue,
+    PgConfigOption, PgConfigOptionName, PostgresSinkConfigOption, ProtobufSchema, QualifiedReplica,
+    RefreshAtOptionValue, RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition,
+    ReplicaOption, ReplicaOptionName, RoleAttribute, S3SinkConfigOption, SetRoleVar,
+    SourceErrorPolicy, SourceIncludeMetadata, Statement, TableConstraint, TableFromSourceColumns,
+    TableFromSourceOption, TableFromSourceOptionName, TableOption, TableOptionName,
+    UnresolvedDatabaseName, UnresolvedItemName, UnresolvedObjectName, UnresolvedSchemaName, Value,
+    ViewDefinition, WithOptionValue,
 };
 use mz_sql_parser::ident;
 use mz_sql_parser::parser::StatementParseResult;
 use mz_storage_types::connections::inline::{ConnectionAccess, ReferencedConnection};
 use mz_storage_types::connections::{Connection, KafkaTopicOptions};
+use mz_storage_types::sinks::iceberg::IcebergTableLocation;
+use mz_storage_types::sinks::s3::S3SinkLocation;
 use mz_storage_types::sinks::{
-    KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, KafkaSinkFormatType, SinkEnvelope,
-    StorageSinkConnection,
+    IcebergSinkConnection, KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, KafkaSinkFormatType,
+    PostgresSinkConnection, S3SinkConnection, S3SinkFormat, SinkEnvelope, StorageSinkConnection,
 };
 use mz_storage_types::sources::encoding::{
-    included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, ProtobufEncoding,
-    RegexEncoding, SourceDataEncoding,
+    included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, JsonSchemaEncoding,
+    ProtobufEncoding, RegexEncoding, SourceDataEncoding,
 };
 use mz_storage_types::sources::envelope::{
     KeyEnvelope, NoneEnvelope, SourceEnvelope, UnplannedSourceEnvelope, UpsertStyle,
@@ -109,10 +118,13 @@ use mz_storage_types::sources::postgres::{
     PostgresSourceConnection, PostgresSourcePublicationDetails,
     ProtoPostgresSourcePublicationDetails,
 };
+use mz_storage_types::sources::sql_server::{
+    ProtoSqlServerSourceExtras, SqlServerSourceExportDetails, SqlServerSourceExtras,
+};
 use mz_storage_types::sources::{
     GenericSourceConnection, MySqlSourceExportDetails, PostgresSourceExportDetails,
     ProtoSourceExportStatementDetails, SourceConnection, SourceDesc, SourceExportDataConfig,
-    SourceExportDetails, SourceExportStatementDetails, Timeline,
+    SourceExportDetails, SourceExportStatementDetails, SqlServerSourceConnection, Timeline,
 };
 use prost::Message;
 
@@ -121,6 +133,7 @@ use crate::catalog::{
     CatalogCluster, CatalogDatabase, CatalogError, CatalogItem, CatalogItemType,
     CatalogRecordField, CatalogType, CatalogTypeDetails, ObjectType, SystemObjectType,
 };
+use crate::func::{self, UserFuncReturnType};
 use crate::kafka_util::{KafkaSinkConfigOptionExtracted, KafkaSourceConfigOptionExtracted};
 use crate::names::{
     Aug, CommentObjectId, DatabaseId, ObjectId, PartialItemName, QualifiedItemName,
@@ -148,14 +161,14 @@ use crate::plan::{
     ClusterSchedule, CommentPlan, ComputeReplicaConfig, ComputeReplicaIntrospectionConfig,
     ConnectionDetails, CreateClusterManagedPlan, CreateClusterPlan, CreateClusterReplicaPlan,
     CreateClusterUnmanagedPlan, CreateClusterVariant, CreateConnectionPlan,
-    CreateContinualTaskPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateNetworkPolicyPlan, CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan,
-    CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc,
-    DropObjectsPlan, DropOwnedPlan, Index, Ingestion, MaterializedView, NetworkPolicyRule,
-    NetworkPolicyRuleAction, NetworkPolicyRuleDirection, Params, Plan, PlanClusterOption,
-    PlanNotice, PolicyAddress, QueryContext, ReplicaConfig, Secret, Sink, Source, Table,
-    TableDataSource, Type, VariableValue, View, WebhookBodyFormat, WebhookHeaderFilters,
-    WebhookHeaders, WebhookValidation,
+    CreateContinualTaskPlan, CreateDatabasePlan, CreateFunctionPlan, CreateIndexPlan,
+    CreateMaterializedViewPlan, CreateNetworkPolicyPlan, CreateRolePlan, CreateSchemaPlan,
+    CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
+    CreateViewPlan, DataSourceDesc, DropObjectsPlan, DropOwnedPlan, Function, InPlaceReplacement,
+    Index, Ingestion, MaterializedView, NetworkPolicyRule, NetworkPolicyRuleAction,
+    NetworkPolicyRuleDirection, Params, Plan, PlanClusterOption, PlanNotice, PolicyAddress,
+    QueryContext, ReplicaConfig, Secret, Sink, Source, Table, TableDataSource, Type, VariableValue,
+    View, WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders, WebhookValidation,
 };
 use crate::session::vars::{
     self, ENABLE_CLUSTER_SCHEDULE_REFRESH, ENABLE_COLLECTION_PARTITION_BY,
@@ -304,6 +317,7 @@ pub fn plan_create_table(
         let mut nullable = true;
         let mut default = Expr::null();
         let mut versioned = false;
+        let mut unique = false;
         for option in &c.options {
             match &option.option {
                 ColumnOption::NotNull => nullable = false,
@@ -317,6 +331,7 @@ pub fn plan_create_table(
                 }
                 ColumnOption::Unique { is_primary } => {
                     keys.push(vec![i]);
+                    unique = true;
                     if *is_primary {
                         nullable = false;
                     }
@@ -335,6 +350,12 @@ pub fn plan_create_table(
                 }
             }
         }
+        if unique && nullable {
+            // Keys consider NULLs equal to each other, unlike unique
+            // constraints on nullable columns. We allow them with feature
+            // flags for sqllogictest's sake.
+            scx.require_feature_flag(&vars::UNSAFE_ENABLE_TABLE_KEYS)?;
+        }
         // TODO(alter_table): This assumes all versioned columns are at the
         // end. This will no longer be true when we support dropping columns.
         if !versioned {
@@ -378,6 +399,9 @@ pub fn plan_create_table(
                             } else if !(*nulls_not_distinct || !*nullable) {
                                 // Non-primary key unique constraints are only keys if all of their
                                 // columns are `NOT NULL` or the constraint is `NULLS NOT DISTINCT`.
+                                // Other unique constraints are not enforced. We allow them with
+                                // feature flags for sqllogictest's sake.
+                                scx.require_feature_flag(&vars::UNSAFE_ENABLE_TABLE_KEYS)?;
                                 break 'c;
                             }
 
@@ -405,12 +429,6 @@ pub fn plan_create_table(
         }
     }
 
-    if !keys.is_empty() {
-        // Unique constraints are not presently enforced. We allow them with feature flags for
-        // sqllogictest's sake.
-        scx.require_feature_flag(&vars::UNSAFE_ENABLE_TABLE_KEYS)?
-    }
-
     let typ = RelationType::new(column_types).with_keys(keys);
 
     let temporary = *temporary;
@@ -527,6 +545,14 @@ generate_extracted_config!(
     (ExcludeColumns, Vec::<UnresolvedItemName>, Default(vec![]))
 );
 
+generate_extracted_config!(
+    SqlServerConfigOption,
+    (CaptureInstance, String),
+    (Details, String),
+    (TextColumns, Vec::<UnresolvedItemName>, Default(vec![])),
+    (ExcludeColumns, Vec::<UnresolvedItemName>, Default(vec![]))
+);
+
 pub fn plan_create_webhook_source(
     scx: &StatementContext,
     mut stmt: CreateWebhookSourceStatement<Aug>,
@@ -952,12 +978,49 @@ pub fn plan_create_source(
 
             connection
         }
-        CreateSourceConnection::SqlServer { .. } => {
-            // TODO(sql_server1)
-            return Err(PlanError::Unsupported {
-                feature: "SQL SERVER".to_string(),
-                discussion_no: None,
-            });
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            scx.require_feature_flag(&vars::ENABLE_SQL_SERVER_SOURCE)?;
+
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            match connection_item.connection()? {
+                Connection::SqlServer(connection) => connection,
+                _ => sql_bail!(
+                    "{} is not a SQL Server connection",
+                    scx.catalog.resolve_full_name(connection_item.name())
+                ),
+            };
+            let SqlServerConfigOptionExtracted {
+                details,
+                // The capture instance is rejected during purification.
+                capture_instance: _,
+                // text/exclude columns are already part of the source-exports and are only included
+                // in these options for round-tripping of a `CREATE SOURCE` statement. This should
+                // be removed once we drop support for implicitly created subsources.
+                text_columns: _,
+                exclude_columns: _,
+                seen: _,
+            } = options.clone().try_into()?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: SQL Server source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let extras =
+                ProtoSqlServerSourceExtras::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let extras =
+                SqlServerSourceExtras::from_proto(extras).map_err(|e| sql_err!("{}", e))?;
+
+            let connection =
+                GenericSourceConnection::<ReferencedConnection>::from(SqlServerSourceConnection {
+                    connection: connection_item.id(),
+                    connection_id: connection_item.id(),
+                    extras,
+                });
+
+            connection
         }
         CreateSourceConnection::MySql {
             connection,
@@ -1577,6 +1640,7 @@ pub fn plan_create_subsource(
                         scx,
                         &table,
                         &text_columns,
+                        columns,
                     )?,
                     table,
                 })
@@ -1593,6 +1657,18 @@ pub fn plan_create_subsource(
                     .map(|c| c.into_string())
                     .collect(),
             }),
+            SourceExportStatementDetails::SqlServer {
+                capture_instance,
+                table,
+            } => SourceExportDetails::SqlServer(SqlServerSourceExportDetails {
+                capture_instance,
+                table,
+                text_columns: text_columns.into_iter().map(|c| c.into_string()).collect(),
+                exclude_columns: exclude_columns
+                    .into_iter()
+                    .map(|c| c.into_string())
+                    .collect(),
+            }),
             SourceExportStatementDetails::LoadGenerator { output } => {
                 SourceExportDetails::LoadGenerator(LoadGeneratorSourceExportDetails { output })
             }
@@ -1717,6 +1793,10 @@ pub fn plan_create_table_from_source(
                     scx,
                     &table,
                     &text_columns,
+                    match columns {
+                        TableFromSourceColumns::Defined(columns) => columns.as_slice(),
+                        _ => &[],
+                    },
                 )?,
                 table,
             })
@@ -1733,6 +1813,18 @@ pub fn plan_create_table_from_source(
                 .map(|c| c.into_string())
                 .collect(),
         }),
+        SourceExportStatementDetails::SqlServer {
+            capture_instance,
+            table,
+        } => SourceExportDetails::SqlServer(SqlServerSourceExportDetails {
+            capture_instance,
+            table,
+            text_columns: text_columns.into_iter().map(|c| c.into_string()).collect(),
+            exclude_columns: exclude_columns
+                .into_iter()
+                .map(|c| c.into_string())
+                .collect(),
+        }),
         SourceExportStatementDetails::LoadGenerator { output } => {
             SourceExportDetails::LoadGenerator(LoadGeneratorSourceExportDetails { output })
         }
@@ -2144,6 +2236,17 @@ fn get_encoding(
         sql_bail!("ENVELOPE [DEBEZIUM] UPSERT requires that KEY FORMAT be specified");
     };
 
+    // A JSON array expands into any number of records, which only makes sense
+    // for append-only values.
+    if matches!(encoding.key, Some(DataEncoding::JsonArray)) {
+        sql_bail!("JSON ARRAY format is not supported for keys");
+    }
+    if matches!(encoding.value, DataEncoding::JsonArray)
+        && !matches!(envelope, ast::SourceEnvelope::None)
+    {
+        sql_bail!("JSON ARRAY format requires ENVELOPE NONE");
+    }
+
     Ok(encoding)
 }
 
@@ -2271,6 +2374,7 @@ fn get_encoding_inner(
         }
         Format::Protobuf(schema) => match schema {
             ProtobufSchema::Csr {
+                message_name: _,
                 csr_connection:
                     CsrConnectionProtobuf {
                         connection:
@@ -2356,7 +2460,52 @@ fn get_encoding_inner(
             })
         }
         Format::Json { array: false } => DataEncoding::Json,
-        Format::Json { array: true } => bail_unsupported!("JSON ARRAY format in sources"),
+        Format::Json { array: true } => DataEncoding::JsonArray,
+        Format::JsonSchema(CsrConnectionJson {
+            connection:
+                CsrConnection {
+                    connection,
+                    options,
+                },
+            seed,
+        }) => {
+            let Some(CsrSeedJson {
+                key_schema,
+                value_schema,
+            }) = seed
+            else {
+                unreachable!("CSR seed resolution should already have been called: Json")
+            };
+            let item = scx.get_item_by_resolved_name(connection)?;
+            if !matches!(item.connection()?, Connection::Csr(_)) {
+                sql_bail!(
+                    "{} is not a schema registry connection",
+                    scx.catalog
+                        .resolve_full_name(item.name())
+                        .to_string()
+                        .quoted()
+                )
+            }
+
+            if !options.is_empty() {
+                sql_bail!("JSON CSR connections do not support any options");
+            }
+
+            let value = DataEncoding::JsonSchema(JsonSchemaEncoding {
+                schema: value_schema.clone(),
+                confluent_wire_format: true,
+            });
+            if let Some(key_schema) = key_schema {
+                return Ok(SourceDataEncoding {
+                    key: Some(DataEncoding::JsonSchema(JsonSchemaEncoding {
+                        schema: key_schema.clone(),
+                        confluent_wire_format: true,
+                    })),
+                    value,
+                });
+            }
+            value
+        }
         Format::Text => DataEncoding::Text,
     };
     Ok(SourceDataEncoding { key: None, value })
@@ -2403,10 +2552,13 @@ fn get_unnamed_key_envelope(
     //
     // Otherwise it gets the names of the columns in the type
     let is_composite = match key {
-        Some(DataEncoding::Bytes | DataEncoding::Json | DataEncoding::Text) => false,
+        Some(
+            DataEncoding::Bytes | DataEncoding::Json | DataEncoding::JsonArray | DataEncoding::Text,
+        ) => false,
         Some(
             DataEncoding::Avro(_)
             | DataEncoding::Csv(_)
+            | DataEncoding::JsonSchema(_)
             | DataEncoding::Protobuf(_)
             | DataEncoding::Regex { .. },
         ) => true,
@@ -2497,9 +2649,20 @@ pub fn plan_view(
 }
 
 pub fn plan_create_view(
+    scx: &StatementContext,
+    stmt: CreateViewStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    plan_create_view_inner(scx, stmt, params, InPlaceReplacement::CreateOrReplace)
+}
+
+/// Plans `stmt`, replacing an existing view of the same name in place as
+/// described by `in_place` if possible.
+fn plan_create_view_inner(
     scx: &StatementContext,
     mut stmt: CreateViewStatement<Aug>,
     params: &Params,
+    in_place: InPlaceReplacement,
 ) -> Result<Plan, PlanError> {
     let CreateViewStatement {
         temporary,
@@ -2512,16 +2675,49 @@ pub fn plan_create_view(
     // explicitly requested in the PlanContext (the default is `false`).
     let ignore_if_exists_errors = scx.pcx().map_or(false, |pcx| pcx.ignore_if_exists_errors);
 
+    let mut replace_in_place = None;
     let replace = if *if_exists == IfExistsBehavior::Replace && !ignore_if_exists_errors {
-        let if_exists = true;
-        let cascade = false;
-        let maybe_item_to_drop = plan_drop_item(
-            scx,
-            ObjectType::View,
-            if_exists,
-            definition.name.clone(),
-            cascade,
-        )?;
+        let in_place_check =
+            match resolve_item_or_type(scx, ObjectType::View, definition.name.clone(), true) {
+                Ok(Some(item)) if *temporary => Err(sql_err!(
+                    "cannot replace view {} in place with a temporary view",
+                    scx.catalog
+                        .resolve_full_name(item.name())
+                        .to_string()
+                        .quoted()
+                )),
+                Ok(Some(item)) => {
+                    let typ = view.expr.typ(&[], &BTreeMap::new());
+                    check_replace_in_place(
+                        scx,
+                        item,
+                        &view.column_names,
+                        &typ,
+                        &view.expr.depends_on(),
+                    )
+                    .map(|()| item.id())
+                }
+                Ok(None) => Err(sql_err!("view {} does not exist", definition.name)),
+                Err(e) => Err(e),
+            };
+        let maybe_item_to_drop = match (in_place_check, in_place) {
+            (Ok(id), _) => {
+                replace_in_place = Some(in_place);
+                Some(id)
+            }
+            (Err(e), InPlaceReplacement::Alter) => return Err(e),
+            (Err(_), InPlaceReplacement::CreateOrReplace) => {
+                let if_exists = true;
+                let cascade = false;
+                plan_drop_item(
+                    scx,
+                    ObjectType::View,
+                    if_exists,
+                    definition.name.clone(),
+                    cascade,
+                )?
+            }
+        };
 
         // Check if the new View depends on the item that we would be replacing.
         if let Some(id) = maybe_item_to_drop {
@@ -2546,6 +2742,7 @@ pub fn plan_create_view(
         None
     };
     let drop_ids = replace
+        .filter(|_| replace_in_place.is_none())
         .map(|id| {
             scx.catalog
                 .item_dependents(id)
@@ -2576,6 +2773,7 @@ pub fn plan_create_view(
         view,
         replace,
         drop_ids,
+        replace_in_place,
         if_not_exists: *if_exists == IfExistsBehavior::Skip,
         ambiguous_columns: *scx.ambiguous_columns.borrow(),
     }))
@@ -2610,9 +2808,20 @@ pub fn describe_alter_network_policy(
 }
 
 pub fn plan_create_materialized_view(
+    scx: &StatementContext,
+    stmt: CreateMaterializedViewStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    plan_create_materialized_view_inner(scx, stmt, params, InPlaceReplacement::CreateOrReplace)
+}
+
+/// Plans `stmt`, replacing an existing materialized view of the same name in
+/// place as described by `in_place` if possible.
+fn plan_create_materialized_view_inner(
     scx: &StatementContext,
     mut stmt: CreateMaterializedViewStatement<Aug>,
     params: &Params,
+    in_place: InPlaceReplacement,
 ) -> Result<Plan, PlanError> {
     let cluster_id =
         crate::plan::statement::resolve_cluster_for_materialized_view(scx.catalog, &stmt)?;
@@ -2811,18 +3020,65 @@ pub fn plan_create_materialized_view(
     };
 
     let mut replace = None;
+    let mut replace_in_place = None;
+    let mut can_drop_replaced = false;
     let mut if_not_exists = false;
     match if_exists {
         IfExistsBehavior::Replace => {
-            let if_exists = true;
-            let cascade = false;
-            let replace_id = plan_drop_item(
+            let unresolved_name = UnresolvedItemName::from(partial_name);
+            let in_place_check = match resolve_item_or_type(
                 scx,
                 ObjectType::MaterializedView,
-                if_exists,
-                partial_name.into(),
-                cascade,
-            )?;
+                unresolved_name.clone(),
+                true,
+            ) {
+                Ok(Some(item)) if refresh_schedule.is_some() => Err(sql_err!(
+                    "cannot replace materialized view {} in place with a REFRESH schedule",
+                    scx.catalog
+                        .resolve_full_name(item.name())
+                        .to_string()
+                        .quoted()
+                )),
+                Ok(Some(item)) => {
+                    check_replace_in_place(scx, item, &column_names, desc.typ(), &expr.depends_on())
+                        .map(|()| item.id())
+                }
+                Ok(None) => Err(sql_err!(
+                    "materialized view {} does not exist",
+                    unresolved_name
+                )),
+                Err(e) => Err(e),
+            };
+            let plan_drop = || {
+                let if_exists = true;
+                let cascade = false;
+                plan_drop_item(
+                    scx,
+                    ObjectType::MaterializedView,
+                    if_exists,
+                    unresolved_name.clone(),
+                    cascade,
+                )
+            };
+            let replace_id = match (in_place_check, in_place) {
+                (Ok(id), InPlaceReplacement::Alter) => {
+                    replace_in_place = Some(in_place);
+                    Some(id)
+                }
+                (Ok(id), InPlaceReplacement::CreateOrReplace) => {
+                    replace_in_place = Some(in_place);
+                    // Whether the materialized view can be replaced in place also
+                    // depends on its write frontier, so keep dropping it as a
+                    // fallback if that is allowed.
+                    can_drop_replaced = plan_drop().is_ok();
+                    Some(id)
+                }
+                (Err(e), InPlaceReplacement::Alter) => return Err(e),
+                (Err(_), InPlaceReplacement::CreateOrReplace) => {
+                    can_drop_replaced = true;
+                    plan_drop()?
+                }
+            };
 
             // Check if the new Materialized View depends on the item that we would be replacing.
             if let Some(id) = replace_id {
@@ -2845,6 +3101,7 @@ pub fn plan_create_materialized_view(
         IfExistsBehavior::Error => (),
     }
     let drop_ids = replace
+        .filter(|_| can_drop_replaced)
         .map(|id| {
             scx.catalog
                 .item_dependents(id)
@@ -2888,11 +3145,132 @@ pub fn plan_create_materialized_view(
         },
         replace,
         drop_ids,
+        replace_in_place,
         if_not_exists,
         ambiguous_columns: *scx.ambiguous_columns.borrow(),
     }))
 }
 
+/// Checks whether the definition of `item`, a view or materialized view, can
+/// be replaced in place by a new definition producing `column_names` of type
+/// `typ` from the collections in `depends_on`, keeping the item's ID and its
+/// dependents.
+fn check_replace_in_place(
+    scx: &StatementContext,
+    item: &dyn CatalogItem,
+    column_names: &[ColumnName],
+    typ: &RelationType,
+    depends_on: &BTreeSet<GlobalId>,
+) -> Result<(), PlanError> {
+    let full_name = scx.catalog.resolve_full_name(item.name());
+    let item_type = item.item_type();
+    if item.id().is_system() {
+        sql_bail!(
+            "cannot replace {item_type} {} because it is required by the database system",
+            full_name.to_string().quoted()
+        );
+    }
+    match item_type {
+        CatalogItemType::View => {
+            if item.name().qualifiers.schema_spec == SchemaSpecifier::Temporary {
+                sql_bail!(
+                    "cannot replace temporary view {} in place",
+                    full_name.to_string().quoted()
+                );
+            }
+            // Dependents of a view inline its definition, so they would keep
+            // running the old one.
+            if let Some(dep) = item.used_by().first() {
+                let dep = scx.catalog.get_item(dep);
+                sql_bail!(
+                    "cannot replace view {} in place: {} {} depends on it",
+                    full_name.to_string().quoted(),
+                    dep.item_type(),
+                    scx.catalog
+                        .minimal_qualification(dep.name())
+                        .to_string()
+                        .quoted()
+                );
+            }
+        }
+        CatalogItemType::MaterializedView => {
+            let stmt = mz_sql_parser::parser::parse_statements(item.create_sql())?
+                .into_element()
+                .ast;
+            let Statement::CreateMaterializedView(stmt) = stmt else {
+                unreachable!("invalid create SQL for materialized view item");
+            };
+            if stmt
+                .with_options
+                .iter()
+                .any(|o| o.name == MaterializedViewOptionName::Refresh)
+            {
+                sql_bail!(
+                    "cannot replace materialized view {} in place: it has a REFRESH schedule",
+                    full_name.to_string().quoted()
+                );
+            }
+        }
+        _ => unreachable!("only views and materialized views are replaced in place"),
+    }
+
+    // Depending on one of the item's dependents would introduce a cycle.
+    let dependents = scx.catalog.item_dependents(item.id());
+    for dep in depends_on {
+        let dep = scx.catalog.resolve_item_id(dep);
+        if dep != item.id() && dependents.contains(&ObjectId::Item(dep)) {
+            let dep = scx.catalog.get_item(&dep);
+            sql_bail!(
+                "cannot replace {item_type} {} in place: new definition depends on {} {}, which \
+                 depends on it",
+                full_name.to_string().quoted(),
+                dep.item_type(),
+                scx.catalog
+                    .minimal_qualification(dep.name())
+                    .to_string()
+                    .quoted()
+            );
+        }
+    }
+
+    let desc = item
+        .at_version(RelationVersionSelector::Latest)
+        .desc(&full_name)?
+        .into_owned();
+    if desc.arity() != column_names.len() {
+        sql_bail!(
+            "cannot replace {item_type} {} in place: new definition has {} columns, expected {}",
+            full_name.to_string().quoted(),
+            column_names.len(),
+            desc.arity()
+        );
+    }
+    for ((old_name, old_typ), (new_name, new_typ)) in desc
+        .iter()
+        .zip(column_names.iter().zip(typ.column_types.iter()))
+    {
+        if old_name != new_name {
+            sql_bail!(
+                "cannot replace {item_type} {} in place: column {} is named {} in new definition",
+                full_name.to_string().quoted(),
+                old_name.as_str().quoted(),
+                new_name.as_str().quoted()
+            );
+        }
+        if old_typ.scalar_type != new_typ.scalar_type {
+            sql_bail!(
+                "cannot replace {item_type} {} in place: column {} has type {} in new \
+                 definition, expected {}",
+                full_name.to_string().quoted(),
+                old_name.as_str().quoted(),
+                scx.humanize_scalar_type(&new_typ.scalar_type, false),
+                scx.humanize_scalar_type(&old_typ.scalar_type, false)
+            );
+        }
+    }
+    Ok(())
+}
+
 generate_extracted_config!(
     MaterializedViewOption,
     (AssertNotNull, Ident, AllowMultiple),
@@ -3139,9 +3517,10 @@ fn continual_task_query<'a>(
             table_name: _,
             columns,
             source,
+            on_conflict,
             returning,
         }) => {
-            if !columns.is_empty() || !returning.is_empty() {
+            if !columns.is_empty() || on_conflict.is_some() || !returning.is_empty() {
                 return None;
             }
             match source {
@@ -3250,10 +3629,14 @@ fn plan_sink(
     };
     let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
 
-    let envelope = match envelope {
-        Some(ast::SinkEnvelope::Upsert) => SinkEnvelope::Upsert,
-        Some(ast::SinkEnvelope::Debezium) => SinkEnvelope::Debezium,
-        None => sql_bail!("ENVELOPE clause is required"),
+    let envelope = match (&connection, envelope) {
+        (_, Some(ast::SinkEnvelope::Upsert)) => SinkEnvelope::Upsert,
+        (_, Some(ast::SinkEnvelope::Debezium)) => SinkEnvelope::Debezium,
+        // S3 and Postgres sinks write every update as its own row unless told otherwise.
+        (CreateSinkConnection::S3 { .. } | CreateSinkConnection::Postgres { .. }, None) => {
+            SinkEnvelope::Append
+        }
+        (_, None) => sql_bail!("ENVELOPE clause is required"),
     };
 
     let from_name = &from;
@@ -3263,7 +3646,9 @@ fn plan_sink(
     }
     let desc = from.desc(&scx.catalog.resolve_full_name(from.name()))?;
     let key_indices = match &connection {
-        CreateSinkConnection::Kafka { key, .. } => {
+        CreateSinkConnection::Kafka { key, .. }
+        | CreateSinkConnection::Iceberg { key, .. }
+        | CreateSinkConnection::Postgres { key, .. } => {
             if let Some(key) = key.clone() {
                 let key_columns = key
                     .key_columns
@@ -3325,6 +3710,7 @@ fn plan_sink(
                 None
             }
         }
+        CreateSinkConnection::S3 { .. } => None,
     };
 
     let headers_index = match &connection {
@@ -3339,6 +3725,7 @@ fn plan_sink(
                 SinkEnvelope::Debezium => {
                     sql_bail!("HEADERS option is not supported with ENVELOPE DEBEZIUM")
                 }
+                SinkEnvelope::Append => unreachable!("Kafka sinks require an ENVELOPE clause"),
             };
 
             let headers = normalize::column_name(headers.clone());
@@ -3398,6 +3785,46 @@ fn plan_sink(
             envelope,
             from.id(),
         )?,
+        CreateSinkConnection::Iceberg {
+            connection,
+            options,
+            ..
+        } => iceberg_sink_builder(
+            scx,
+            connection,
+            options,
+            format,
+            relation_key_indices,
+            key_desc_and_indices,
+            desc.into_owned(),
+            envelope,
+        )?,
+        CreateSinkConnection::Postgres {
+            connection,
+            options,
+            ..
+        } => postgres_sink_builder(
+            scx,
+            connection,
+            options,
+            format,
+            relation_key_indices,
+            key_desc_and_indices,
+            desc.into_owned(),
+            envelope,
+        )?,
+        CreateSinkConnection::S3 {
+            connection,
+            options,
+        } => s3_sink_builder(
+            scx,
+            connection,
+            options,
+            format,
+            relation_key_indices,
+            desc.into_owned(),
+            envelope,
+        )?,
     };
 
     let CreateSinkOptionExtracted {
@@ -3695,6 +4122,50 @@ fn kafka_sink_builder(
         Ok((csr_connection, extracted_options))
     };
 
+    // Helper method to parse connection options for format specifiers that use
+    // protobuf for either key or value encoding.
+    let gen_protobuf_schema_options = |conn| {
+        let CsrConnectionProtobuf {
+            connection:
+                CsrConnection {
+                    connection,
+                    options,
+                },
+            seed,
+        } = conn;
+        if seed.is_some() {
+            sql_bail!("SEED option does not make sense with sinks");
+        }
+
+        let item = scx.get_item_by_resolved_name(&connection)?;
+        let csr_connection = match item.connection()? {
+            Connection::Csr(_) => item.id(),
+            _ => {
+                sql_bail!(
+                    "{} is not a schema registry connection",
+                    scx.catalog
+                        .resolve_full_name(item.name())
+                        .to_string()
+                        .quoted()
+                )
+            }
+        };
+        let extracted_options: CsrConfigOptionExtracted = options.try_into()?;
+        if extracted_options.avro_key_fullname.is_some()
+            || extracted_options.avro_value_fullname.is_some()
+            || extracted_options.null_defaults
+            || !extracted_options.key_doc_options.is_empty()
+            || !extracted_options.value_doc_options.is_empty()
+        {
+            sql_bail!(
+                "Protobuf CSR connections only support the KEY COMPATIBILITY LEVEL and \
+                VALUE COMPATIBILITY LEVEL options"
+            );
+        }
+
+        Ok((csr_connection, extracted_options))
+    };
+
     let map_format = |format: Format<Aug>, desc: &RelationDesc, is_key: bool| match format {
         Format::Json { array: false } => Ok::<_, PlanError>(KafkaSinkFormatType::Json),
         Format::Bytes if desc.arity() == 1 => {
@@ -3750,6 +4221,32 @@ fn kafka_sink_builder(
                 csr_connection,
             })
         }
+        Format::Protobuf(ProtobufSchema::Csr {
+            message_name,
+            csr_connection,
+        }) => {
+            if message_name.is_some() {
+                sql_bail!("MESSAGE option does not make sense with sinks");
+            }
+            let (csr_connection, options) = gen_protobuf_schema_options(csr_connection)?;
+            let debezium = !is_key && matches!(envelope, SinkEnvelope::Debezium);
+            let message_name = if debezium { "Envelope" } else { "Row" };
+            let schema =
+                ProtobufSchemaGenerator::new(desc.clone(), debezium, message_name).schema();
+            Ok(KafkaSinkFormatType::Protobuf {
+                schema,
+                message_name: message_name.into(),
+                compatibility_level: if is_key {
+                    options.key_compatibility_level
+                } else {
+                    options.value_compatibility_level
+                },
+                csr_connection,
+            })
+        }
+        Format::Protobuf(ProtobufSchema::InlineSchema { .. }) => {
+            bail_unsupported!("PROTOBUF format with an inline schema in sinks")
+        }
         format => bail_unsupported!(format!("sink format {:?}", format)),
     };
 
@@ -3778,6 +4275,7 @@ fn kafka_sink_builder(
                         }
                     }
                 }
+                SinkEnvelope::Append => unreachable!("Kafka sinks require an ENVELOPE clause"),
             };
 
             let ecx = &ExprContext {
@@ -3849,6 +4347,214 @@ fn kafka_sink_builder(
     }))
 }
 
+generate_extracted_config!(IcebergSinkConfigOption, (Path, String));
+
+fn iceberg_sink_builder(
+    scx: &StatementContext,
+    connection: ResolvedItemName,
+    options: Vec<IcebergSinkConfigOption<Aug>>,
+    format: Option<FormatSpecifier<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_ICEBERG_SINK)?;
+
+    // Get AWS connection.
+    let connection_item = scx.get_item_by_resolved_name(&connection)?;
+    let connection_id = connection_item.id();
+    match connection_item.connection()? {
+        Connection::Aws(_) => (),
+        _ => sql_bail!(
+            "{} is not an AWS connection",
+            scx.catalog.resolve_full_name(connection_item.name())
+        ),
+    };
+
+    let IcebergSinkConfigOptionExtracted { path, seen: _ } = options.try_into()?;
+    let table_uri = path.ok_or_else(|| sql_err!("ICEBERG CONNECTION must specify PATH"))?;
+    if let Err(e) = IcebergTableLocation::parse(&table_uri) {
+        sql_bail!("invalid PATH: {}", e);
+    }
+
+    if format.is_some() {
+        sql_bail!("Iceberg sinks do not support the FORMAT option; data is written as Parquet");
+    }
+    match envelope {
+        SinkEnvelope::Upsert => (),
+        SinkEnvelope::Debezium | SinkEnvelope::Append => {
+            sql_bail!("Iceberg sinks only support ENVELOPE UPSERT")
+        }
+    }
+    let Some(key_desc_and_indices) = key_desc_and_indices else {
+        return Err(PlanError::UpsertSinkWithoutKey);
+    };
+
+    if let Err(e) = mz_storage_types::sinks::iceberg::validate_desc(&value_desc) {
+        sql_bail!("{}", e);
+    }
+    // Iceberg resolves columns by name, so every column must have a distinct one.
+    let mut names = BTreeSet::new();
+    for (name, _) in value_desc.iter() {
+        if !names.insert(name) {
+            sql_bail!(
+                "cannot sink to Iceberg: column name {} is ambiguous",
+                name.as_str().quoted()
+            );
+        }
+    }
+
+    Ok(StorageSinkConnection::Iceberg(IcebergSinkConnection {
+        connection_id,
+        aws_connection: connection_id,
+        table_uri,
+        relation_key_indices,
+        key_desc_and_indices,
+        value_desc,
+    }))
+}
+
+generate_extracted_config!(S3SinkConfigOption, (Path, String), (Format, String));
+
+fn s3_sink_builder(
+    scx: &StatementContext,
+    connection: ResolvedItemName,
+    options: Vec<S3SinkConfigOption<Aug>>,
+    format: Option<FormatSpecifier<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_S3_SINK)?;
+
+    // Get AWS connection.
+    let connection_item = scx.get_item_by_resolved_name(&connection)?;
+    let connection_id = connection_item.id();
+    match connection_item.connection()? {
+        Connection::Aws(_) => (),
+        _ => sql_bail!(
+            "{} is not an AWS connection",
+            scx.catalog.resolve_full_name(connection_item.name())
+        ),
+    };
+
+    let S3SinkConfigOptionExtracted {
+        path,
+        format: file_format,
+        seen: _,
+    } = options.try_into()?;
+    let uri = path.ok_or_else(|| sql_err!("S3 CONNECTION must specify PATH"))?;
+    if let Err(e) = S3SinkLocation::parse(&uri) {
+        sql_bail!("invalid PATH: {}", e);
+    }
+
+    if format.is_some() {
+        sql_bail!("S3 sinks do not support the FORMAT clause; specify the FORMAT option instead");
+    }
+    match envelope {
+        SinkEnvelope::Append | SinkEnvelope::Debezium => (),
+        SinkEnvelope::Upsert => sql_bail!("S3 sinks do not support ENVELOPE UPSERT"),
+    }
+
+    let output_desc = match mz_storage_types::sinks::s3::output_desc(&value_desc, envelope) {
+        Ok(desc) => desc,
+        Err(e) => sql_bail!("{}", e),
+    };
+    let file_format = file_format.ok_or_else(|| sql_err!("S3 CONNECTION must specify FORMAT"))?;
+    let format = match file_format.to_lowercase().as_str() {
+        "csv" => S3SinkFormat::PgCopy(CopyFormatParams::Csv(
+            CopyCsvFormatParams::try_new(None, None, None, Some(true), None)
+                .map_err(|e| sql_err!("{}", e))?,
+        )),
+        "parquet" => {
+            // Validate that the output desc can be formatted as parquet
+            ArrowBuilder::validate_desc(&output_desc).map_err(|e| sql_err!("{}", e))?;
+            S3SinkFormat::Parquet
+        }
+        _ => sql_bail!(
+            "invalid FORMAT: {}; S3 sinks support 'parquet' and 'csv'",
+            file_format.quoted()
+        ),
+    };
+
+    Ok(StorageSinkConnection::S3(S3SinkConnection {
+        connection_id,
+        aws_connection: connection_id,
+        uri,
+        format,
+        relation_key_indices,
+        value_desc,
+    }))
+}
+
+generate_extracted_config!(PostgresSinkConfigOption, (Table, UnresolvedItemName));
+
+/// Returns the schema and name of the upstream table of a Postgres sink.
+///
+/// Unqualified names refer to the `public` schema.
+pub(crate) fn postgres_sink_table(name: UnresolvedItemName) -> Result<(String, String), PlanError> {
+    let mut parts = name.0.into_iter().map(normalize::ident);
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(table), None, None) => Ok(("public".into(), table)),
+        (Some(schema), Some(table), None) => Ok((schema, table)),
+        _ => sql_bail!("TABLE must be a table name, optionally qualified by its schema"),
+    }
+}
+
+fn postgres_sink_builder(
+    scx: &StatementContext,
+    connection: ResolvedItemName,
+    options: Vec<PostgresSinkConfigOption<Aug>>,
+    format: Option<FormatSpecifier<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_POSTGRES_SINK)?;
+
+    // Get Postgres connection.
+    let connection_item = scx.get_item_by_resolved_name(&connection)?;
+    let connection_id = connection_item.id();
+    match connection_item.connection()? {
+        Connection::Postgres(_) => (),
+        _ => sql_bail!(
+            "{} is not a POSTGRES CONNECTION",
+            scx.catalog.resolve_full_name(connection_item.name())
+        ),
+    };
+
+    let PostgresSinkConfigOptionExtracted { table, seen: _ } = options.try_into()?;
+    let table = table.ok_or_else(|| sql_err!("POSTGRES CONNECTION must specify TABLE"))?;
+    let (schema, table) = postgres_sink_table(table)?;
+
+    if format.is_some() {
+        sql_bail!("Postgres sinks do not support the FORMAT option");
+    }
+    match envelope {
+        SinkEnvelope::Upsert => (),
+        SinkEnvelope::Append if key_desc_and_indices.is_some() => {
+            sql_bail!("KEY is only supported with ENVELOPE UPSERT for Postgres sinks")
+        }
+        SinkEnvelope::Append => (),
+        SinkEnvelope::Debezium => sql_bail!("Postgres sinks do not support ENVELOPE DEBEZIUM"),
+    }
+    if let Err(e) = mz_storage_types::sinks::postgres::upstream_columns(&value_desc, envelope) {
+        sql_bail!("cannot sink to Postgres: {}", e);
+    }
+
+    Ok(StorageSinkConnection::Postgres(PostgresSinkConnection {
+        connection_id,
+        connection: connection_id,
+        schema,
+        table,
+        relation_key_indices,
+        key_desc_and_indices,
+        value_desc,
+    }))
+}
+
 pub fn describe_create_index(
     _: &StatementContext,
     _: CreateIndexStatement<Aug>,
@@ -4102,6 +4808,23 @@ pub fn plan_create_type(
             }
             CatalogType::Record { fields }
         }
+        CreateTypeAs::Enum { labels } => {
+            let mut seen = BTreeSet::new();
+            for label in &labels {
+                // PostgreSQL stores enum labels as `name`s, which limits them
+                // to 63 bytes.
+                if label.len() > 63 {
+                    sql_bail!(
+                        "invalid enum label {}: labels must be 63 bytes or less",
+                        label.quoted()
+                    );
+                }
+                if !seen.insert(label) {
+                    sql_bail!("enum label {} used more than once", label.quoted());
+                }
+            }
+            CatalogType::Enum { labels }
+        }
     };
 
     let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
@@ -4126,6 +4849,139 @@ pub fn plan_create_type(
     }))
 }
 
+pub fn describe_create_function(
+    _: &StatementContext,
+    _: CreateFunctionStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_create_function(
+    scx: &StatementContext,
+    stmt: CreateFunctionStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let create_sql = normalize::create_statement(scx, Statement::CreateFunction(stmt.clone()))?;
+    let CreateFunctionStatement {
+        if_exists,
+        name: unresolved_name,
+        parameters,
+        returns,
+        language,
+        body,
+    } = stmt;
+
+    if let Some(language) = language {
+        let language = normalize::ident(language);
+        if language != "sql" {
+            bail_unsupported!(format!("LANGUAGE {language}"));
+        }
+    }
+
+    let function_type = |data_type: &ResolvedDataType| -> Result<ScalarType, PlanError> {
+        let typ = scalar_type_from_sql(scx, data_type)?;
+        if typ.is_custom_type() {
+            bail_unsupported!("user-defined types in function signatures");
+        }
+        Ok(typ)
+    };
+
+    let mut params = Vec::with_capacity(parameters.len());
+    let mut seen = BTreeSet::new();
+    for FunctionParameter { name, data_type } in parameters {
+        let name = name.map(normalize::column_name);
+        if let Some(name) = &name {
+            if !seen.insert(name.clone()) {
+                sql_bail!(
+                    "parameter name {} used more than once",
+                    name.as_str().quoted()
+                );
+            }
+        }
+        params.push((name, function_type(&data_type)?));
+    }
+
+    let name =
+        scx.allocate_qualified_name(normalize::unresolved_item_name(unresolved_name.clone())?)?;
+
+    let return_type = match returns {
+        FunctionReturnType::Scalar(data_type) => {
+            UserFuncReturnType::Scalar(function_type(&data_type)?)
+        }
+        FunctionReturnType::SetOf(data_type) => UserFuncReturnType::Table(vec![(
+            ColumnName::from(name.item.clone()),
+            function_type(&data_type)?,
+        )]),
+        FunctionReturnType::Table(columns) => {
+            let mut table = Vec::with_capacity(columns.len());
+            for FunctionColumn { name, data_type } in columns {
+                table.push((normalize::column_name(name), function_type(&data_type)?));
+            }
+            if let Some(dup) = table.iter().map(|(name, _)| name).duplicates().next() {
+                sql_bail!("column {} specified more than once", dup.as_str().quoted());
+            }
+            UserFuncReturnType::Table(table)
+        }
+    };
+    if matches!(
+        (&return_type, &body),
+        (UserFuncReturnType::Table(_), FunctionBody::Return(_))
+    ) {
+        sql_bail!("set-returning functions must have a BEGIN ATOMIC body");
+    }
+
+    let dependencies = names::visit_dependencies(scx.catalog, &body);
+    let inner = func::plan_user_defined_func(scx, &name.item, params, return_type, body)?;
+
+    let replace = if if_exists == IfExistsBehavior::Replace {
+        let if_exists = true;
+        let cascade = false;
+        let maybe_item_to_drop =
+            plan_drop_item(scx, ObjectType::Func, if_exists, unresolved_name, cascade)?;
+
+        // Check if the new function depends on the function that we would be replacing.
+        if let Some(id) = maybe_item_to_drop {
+            if dependencies.contains_item(&id) {
+                let item = scx.catalog.get_item(&id);
+                sql_bail!(
+                    "cannot replace function {0}: depended upon by new {0} definition",
+                    scx.catalog.resolve_full_name(item.name())
+                );
+            }
+        }
+        maybe_item_to_drop
+    } else {
+        None
+    };
+    let drop_ids = replace
+        .map(|id| {
+            scx.catalog
+                .item_dependents(id)
+                .into_iter()
+                .map(|id| id.unwrap_item_id())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Check for a function in the catalog with this same name.
+    let full_name = scx.catalog.resolve_full_name(&name);
+    let partial_name = PartialItemName::from(full_name.clone());
+    if let (Ok(item), IfExistsBehavior::Error) =
+        (scx.catalog.resolve_function(&partial_name), if_exists)
+    {
+        return Err(PlanError::ItemAlreadyExists {
+            name: full_name.to_string(),
+            item_type: item.item_type(),
+        });
+    }
+
+    Ok(Plan::CreateFunction(CreateFunctionPlan {
+        name,
+        function: Function { create_sql, inner },
+        replace,
+        drop_ids,
+    }))
+}
+
 generate_extracted_config!(CreateTypeListOption, (ElementType, ResolvedDataType));
 
 generate_extracted_config!(
@@ -4143,16 +4999,20 @@ pub enum PlannedAlterRoleOption {
 #[derive(Debug)]
 pub struct PlannedRoleAttributes {
     pub inherit: Option<bool>,
+    pub login: Option<bool>,
+    /// The SCRAM-SHA-256 verifier of the new password, where `Some(None)` removes the password.
+    pub password_hash: Option<Option<String>>,
 }
 
 fn plan_role_attributes(options: Vec<RoleAttribute>) -> Result<PlannedRoleAttributes, PlanError> {
-    let mut planned_attributes = PlannedRoleAttributes { inherit: None };
+    let mut planned_attributes = PlannedRoleAttributes {
+        inherit: None,
+        login: None,
+        password_hash: None,
+    };
 
     for option in options {
         match option {
-            RoleAttribute::Login | RoleAttribute::NoLogin => {
-                bail_never_supported!("LOGIN attribute", "sql/create-role/#details");
-            }
             RoleAttribute::SuperUser | RoleAttribute::NoSuperUser => {
                 bail_never_supported!("SUPERUSER attribute", "sql/create-role/#details");
             }
@@ -4161,6 +5021,12 @@ fn plan_role_attributes(options: Vec<RoleAttribute>) -> Result<PlannedRoleAttrib
             {
                 sql_bail!("conflicting or redundant options");
             }
+            RoleAttribute::Login | RoleAttribute::NoLogin if planned_attributes.login.is_some() => {
+                sql_bail!("conflicting or redundant options");
+            }
+            RoleAttribute::Password(_) if planned_attributes.password_hash.is_some() => {
+                sql_bail!("conflicting or redundant options");
+            }
             RoleAttribute::CreateCluster | RoleAttribute::NoCreateCluster => {
                 bail_never_supported!(
                     "CREATECLUSTER attribute",
@@ -4185,6 +5051,21 @@ fn plan_role_attributes(options: Vec<RoleAttribute>) -> Result<PlannedRoleAttrib
 
             RoleAttribute::Inherit => planned_attributes.inherit = Some(true),
             RoleAttribute::NoInherit => planned_attributes.inherit = Some(false),
+            RoleAttribute::Login => planned_attributes.login = Some(true),
+            RoleAttribute::NoLogin => planned_attributes.login = Some(false),
+            RoleAttribute::Password(None) => planned_attributes.password_hash = Some(None),
+            RoleAttribute::Password(Some(password)) => {
+                if password.is_empty() {
+                    sql_bail!("empty string is not a valid password");
+                }
+                // Like PostgreSQL, accept passwords that are already hashed, so that roles can
+                // be copied between deployments without knowing their passwords.
+                let password_hash = match password.parse::<ScramSecret>() {
+                    Ok(secret) => secret.to_string(),
+                    Err(_) => ScramSecret::new(&password).to_string(),
+                };
+                planned_attributes.password_hash = Some(Some(password_hash));
+            }
         }
     }
     if planned_attributes.inherit == Some(false) {
@@ -4636,6 +5517,7 @@ pub fn unplan_create_cluster(
             let OptimizerFeatureOverrides {
                 enable_consolidate_after_union_negate: _,
                 enable_reduce_mfp_fusion: _,
+                enable_incremental_window_functions: _,
                 enable_cardinality_estimates: _,
                 persist_fast_path_limit: _,
                 reoptimize_imported_views,
@@ -5152,11 +6034,6 @@ pub fn plan_drop_objects(
         cascade,
     }: DropObjectsStatement,
 ) -> Result<Plan, PlanError> {
-    assert_ne!(
-        object_type,
-        mz_sql_parser::ast::ObjectType::Func,
-        "rejected in parser"
-    );
     let object_type = object_type.into();
 
     let mut referenced_ids = Vec::new();
@@ -5314,7 +6191,9 @@ fn contains_single_replica_objects(scx: &StatementContext, cluster: &dyn Catalog
                         ENABLE_MULTI_REPLICA_SOURCES.get(scx.catalog.system_vars().dyncfgs());
                     !enable_multi_replica_sources
                 }
-                GenericSourceConnection::MySql(_) | GenericSourceConnection::Postgres(_) => true,
+                GenericSourceConnection::MySql(_)
+                | GenericSourceConnection::Postgres(_)
+                | GenericSourceConnection::SqlServer(_) => true,
             },
             _ => false,
         };
@@ -6703,6 +7582,7 @@ pub fn plan_alter_connection(
         Connection::Postgres(_) => CreateConnectionType::Postgres,
         Connection::Ssh(_) => CreateConnectionType::Ssh,
         Connection::MySql(_) => CreateConnectionType::MySql,
+        Connection::SqlServer(_) => CreateConnectionType::SqlServer,
     };
 
     // Collect all options irrespective of action taken on them.
@@ -7115,6 +7995,67 @@ pub fn plan_alter_table_add_column(
     }))
 }
 
+pub fn describe_alter_view_query(
+    _: &StatementContext,
+    _: AlterViewQueryStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_view_query(
+    scx: &StatementContext,
+    stmt: AlterViewQueryStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    let AlterViewQueryStatement {
+        object_type,
+        if_exists,
+        name,
+        query,
+    } = stmt;
+    let object_type = object_type.into();
+
+    let item = match resolve_item_or_type(scx, object_type, name.clone(), if_exists) {
+        Ok(Some(item)) => item,
+        Ok(None) => {
+            scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
+                name: name.to_ast_string(),
+                object_type,
+            });
+            return Ok(Plan::AlterNoop(AlterNoopPlan { object_type }));
+        }
+        // Return a more helpful error on `ALTER VIEW <materialized-view>`.
+        Err(PlanError::MismatchedObjectType {
+            name,
+            is_type: ObjectType::MaterializedView,
+            expected_type: ObjectType::View,
+        }) => return Err(PlanError::AlterViewOnMaterializedView(name.to_string())),
+        Err(e) => return Err(e),
+    };
+
+    // Reconstruct the original CREATE statement, and plan it as a replacement
+    // with the new query.
+    let stmt = mz_sql_parser::parser::parse_statements(item.create_sql())?
+        .into_element()
+        .ast;
+    let (stmt, _) = crate::names::resolve(scx.catalog, stmt)?;
+    match stmt {
+        Statement::CreateView(mut stmt) => {
+            stmt.if_exists = IfExistsBehavior::Replace;
+            stmt.definition.query = query;
+            plan_create_view_inner(scx, stmt, params, InPlaceReplacement::Alter)
+        }
+        Statement::CreateMaterializedView(mut stmt) => {
+            stmt.if_exists = IfExistsBehavior::Replace;
+            stmt.query = query;
+            // The new definition picks its own `AS OF`.
+            stmt.as_of = None;
+            plan_create_materialized_view_inner(scx, stmt, params, InPlaceReplacement::Alter)
+        }
+        _ => unreachable!("invalid create SQL for {object_type} item"),
+    }
+}
+
 pub fn describe_comment(
     _: &StatementContext,
     _: CommentStatement<Aug>,
@@ -7355,6 +8296,7 @@ pub(crate) fn resolve_item_or_type<'a>(
     let name = normalize::unresolved_item_name(name)?;
     let catalog_item = match object_type {
         ObjectType::Type => scx.catalog.resolve_type(&name),
+        ObjectType::Func => scx.catalog.resolve_function(&name),
         _ => scx.catalog.resolve_item(&name),
     };
 
@@ -35,7 +35,8 @@ use mz_storage_types::connections::string_or_secret::StringOrSecret;
 use mz_storage_types::connections::{
     AwsPrivatelink, AwsPrivatelinkConnection, CsrConnection, CsrConnectionHttpAuth,
     KafkaConnection, KafkaSaslConfig, KafkaTlsConfig, KafkaTopicOptions, MySqlConnection,
-    MySqlSslMode, PostgresConnection, SshConnection, SshTunnel, TlsIdentity, Tunnel,
+    MySqlSslMode, PostgresConnection, SqlServerConnection, SshConnection, SshTunnel, TlsIdentity,
+    Tunnel,
 };
 
 use crate::names::Aug;
@@ -509,11 +510,50 @@ impl ConnectionOptionExtracted {
             CreateConnectionType::SqlServer => {
                 scx.require_feature_flag(&vars::ENABLE_SQL_SERVER_SOURCE)?;
 
-                // TODO(sql_server1)
-                return Err(PlanError::Unsupported {
-                    feature: "SQL SERVER".to_string(),
-                    discussion_no: None,
-                });
+                // TODO(sql_server2): Support TLS.
+                if self.ssl_certificate.is_some()
+                    || self.ssl_certificate_authority.is_some()
+                    || self.ssl_key.is_some()
+                {
+                    sql_bail!("invalid CONNECTION: SQL SERVER connections do not yet support TLS")
+                }
+                match self
+                    .ssl_mode
+                    .map(|f| f.to_uppercase())
+                    .as_ref()
+                    .map(|m| m.as_str())
+                {
+                    None | Some("DISABLED") => (),
+                    Some(m) => sql_bail!(
+                        "invalid CONNECTION: SSL MODE {} is not yet supported for SQL SERVER",
+                        m.quoted()
+                    ),
+                }
+
+                if let Some(privatelink) = self.aws_privatelink.as_ref() {
+                    if privatelink.port.is_some() {
+                        sql_bail!("invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka")
+                    }
+                }
+                let tunnel = scx.build_tunnel_definition(self.ssh_tunnel, self.aws_privatelink)?;
+
+                ConnectionDetails::SqlServer(SqlServerConnection {
+                    host: self
+                        .host
+                        .ok_or_else(|| sql_err!("HOST option is required"))?,
+                    port: self.port.unwrap_or(1433_u16),
+                    database: self
+                        .database
+                        .ok_or_else(|| sql_err!("DATABASE option is required"))?,
+                    user: self
+                        .user
+                        .ok_or_else(|| sql_err!("USER option is required"))?,
+                    password: self
+                        .password
+                        .map(|password| password.into())
+                        .ok_or_else(|| sql_err!("PASSWORD option is required"))?,
+                    tunnel,
+                })
             }
         };
 
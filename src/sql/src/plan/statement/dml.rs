@@ -18,10 +18,12 @@ use std::collections::BTreeMap;
 use itertools::Itertools;
 
 use mz_arrow_util::builder::ArrowBuilder;
-use mz_expr::{MirRelationExpr, RowSetFinishing};
+use mz_expr::{MirRelationExpr, MirScalarExpr, RowSetFinishing};
 use mz_ore::num::NonNeg;
 use mz_ore::soft_panic_or_log;
-use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
+use mz_pgcopy::{
+    CopyCsvFormatParams, CopyFormatParams, CopyJsonFormatParams, CopyTextFormatParams,
+};
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::bytes::ByteSize;
 use mz_repr::explain::{ExplainConfig, ExplainFormat};
@@ -41,9 +43,9 @@ use mz_storage_types::sinks::{
 use crate::ast::display::AstDisplay;
 use crate::ast::{
     AstInfo, CopyDirection, CopyOption, CopyOptionName, CopyRelation, CopyStatement, CopyTarget,
-    DeleteStatement, ExplainPlanStatement, ExplainStage, Explainee, Ident, InsertStatement, Query,
-    SelectStatement, SubscribeOption, SubscribeOptionName, SubscribeRelation, SubscribeStatement,
-    UpdateStatement,
+    DeleteStatement, ExplainPlanStatement, ExplainStage, Explainee, Ident, InsertStatement,
+    MergeStatement, Query, SelectStatement, SubscribeOption, SubscribeOptionName,
+    SubscribeRelation, SubscribeStatement, UpdateStatement,
 };
 use crate::catalog::CatalogItemType;
 use crate::names::{Aug, ResolvedItemName};
@@ -74,10 +76,22 @@ pub fn describe_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
-    let (_, _, returning) = query::plan_insert_query(scx, table_name, columns, source, returning)?;
+    let returning = match on_conflict {
+        Some(on_conflict) => {
+            let (_, returning) =
+                query::plan_upsert_query(scx, table_name, columns, source, on_conflict, returning)?;
+            returning
+        }
+        None => {
+            let (_, _, returning) =
+                query::plan_insert_query(scx, table_name, columns, source, returning)?;
+            returning
+        }
+    };
     let desc = if returning.expr.is_empty() {
         None
     } else {
@@ -92,19 +106,39 @@ pub fn plan_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
-    let (id, mut expr, returning) =
-        query::plan_insert_query(scx, table_name, columns, source, returning)?;
-    expr.bind_parameters(params)?;
+    if let Some(on_conflict) = on_conflict {
+        let (rtw_plan, returning) =
+            query::plan_upsert_query(scx, table_name, columns, source, on_conflict, returning)?;
+        let returning = returning
+            .expr
+            .into_iter()
+            .map(|expr| expr.lower_uncorrelated())
+            .collect::<Result<Vec<_>, _>>()?;
+        return plan_read_then_write(MutationKind::Upsert, params, rtw_plan, returning);
+    }
+
+    let (id, expr, returning) =
+        query::plan_insert_query(scx, table_name.clone(), columns, source, returning)?;
     let returning = returning
         .expr
         .into_iter()
         .map(|expr| expr.lower_uncorrelated())
         .collect::<Result<Vec<_>, _>>()?;
 
+    let rtw_plan = query::plan_insert_read_then_write(scx, table_name, id, expr)?;
+    if !rtw_plan.enforced_keys.is_empty() {
+        // Inserts into tables with unique keys read the table, to check the
+        // inserted rows against its contents.
+        return plan_read_then_write(MutationKind::Insert, params, rtw_plan, returning);
+    }
+    let mut expr = rtw_plan.selection;
+    expr.bind_parameters(params)?;
+
     Ok(Plan::Insert(InsertPlan {
         id,
         values: expr,
@@ -126,7 +160,7 @@ pub fn plan_delete(
     params: &Params,
 ) -> Result<Plan, PlanError> {
     let rtw_plan = query::plan_delete_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Delete, params, rtw_plan)
+    plan_read_then_write(MutationKind::Delete, params, rtw_plan, Vec::new())
 }
 
 pub fn describe_update(
@@ -143,7 +177,24 @@ pub fn plan_update(
     params: &Params,
 ) -> Result<Plan, PlanError> {
     let rtw_plan = query::plan_update_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Update, params, rtw_plan)
+    plan_read_then_write(MutationKind::Update, params, rtw_plan, Vec::new())
+}
+
+pub fn describe_merge(
+    scx: &StatementContext,
+    stmt: MergeStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    query::plan_merge_query(scx, stmt)?;
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_merge(
+    scx: &StatementContext,
+    stmt: MergeStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    let rtw_plan = query::plan_merge_query(scx, stmt)?;
+    plan_read_then_write(MutationKind::Merge, params, rtw_plan, Vec::new())
 }
 
 pub fn plan_read_then_write(
@@ -154,7 +205,10 @@ pub fn plan_read_then_write(
         mut selection,
         finishing,
         assignments,
+        changes,
+        enforced_keys,
     }: query::ReadThenWritePlan,
+    returning: Vec<MirScalarExpr>,
 ) -> Result<Plan, PlanError> {
     selection.bind_parameters(params)?;
     let mut assignments_outer = BTreeMap::new();
@@ -170,7 +224,9 @@ pub fn plan_read_then_write(
         finishing,
         assignments: assignments_outer,
         kind,
-        returning: Vec::new(),
+        returning,
+        changes,
+        enforced_keys,
     }))
 }
 
@@ -422,6 +478,7 @@ impl TryFrom<ExplainPlanOptionExtracted> for ExplainConfig {
                 enable_letrec_fixpoint_analysis: v.enable_letrec_fixpoint_analysis,
                 enable_consolidate_after_union_negate: Default::default(),
                 enable_reduce_mfp_fusion: Default::default(),
+                enable_incremental_window_functions: Default::default(),
                 enable_cardinality_estimates: Default::default(),
                 persist_fast_path_limit: Default::default(),
                 reoptimize_imported_views: v.reoptimize_imported_views,
@@ -1058,6 +1115,9 @@ fn plan_copy_to_expr(
         }
         CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
         CopyFormat::Text => bail_unsupported!("FORMAT TEXT"),
+        CopyFormat::Avro => bail_unsupported!("FORMAT AVRO"),
+        CopyFormat::Orc => bail_unsupported!("FORMAT ORC"),
+        CopyFormat::Json { .. } => bail_unsupported!("FORMAT JSON"),
     };
 
     // Converting the to expr to a HirScalarExpr
@@ -1159,6 +1219,10 @@ fn plan_copy_from(
         CopyTarget::Stdout => bail_never_supported!("COPY FROM {} not supported", target),
     };
 
+    if options.json_paths.is_some() && !matches!(format, CopyFormat::Json { .. }) {
+        sql_bail!("COPY JSON PATHS available only in JSON mode");
+    }
+
     let params = match format {
         CopyFormat::Text => {
             only_available_with_csv(options.quote, "quote")?;
@@ -1189,6 +1253,25 @@ fn plan_copy_from(
         }
         CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
         CopyFormat::Parquet => CopyFormatParams::Parquet,
+        CopyFormat::Avro => CopyFormatParams::Avro,
+        CopyFormat::Orc => CopyFormatParams::Orc,
+        CopyFormat::Json { array } => {
+            if options.delimiter.is_some()
+                || options.null.is_some()
+                || options.quote.is_some()
+                || options.escape.is_some()
+                || options.header.is_some()
+            {
+                sql_bail!(
+                    "COPY FORMAT JSON does not support DELIMITER, NULL, QUOTE, ESCAPE or HEADER"
+                );
+            }
+            // The paths are validated against the target columns below.
+            CopyFormatParams::Json(CopyJsonFormatParams {
+                array,
+                paths: options.json_paths,
+            })
+        }
     };
 
     let filter = match (options.files, options.pattern) {
@@ -1202,7 +1285,22 @@ fn plan_copy_from(
         bail_unsupported!("COPY FROM ... WITH (FILES ...) only supported from a URL")
     }
 
-    let (id, _, columns) = query::plan_copy_from(scx, table_name, columns)?;
+    let (id, desc, columns) = query::plan_copy_from(scx, table_name, columns)?;
+
+    let params = match params {
+        CopyFormatParams::Json(CopyJsonFormatParams { array, paths }) => CopyFormatParams::Json(
+            CopyJsonFormatParams::try_new(array, paths, desc.arity())
+                .map_err(|e| sql_err!("{}", e))?,
+        ),
+        params => params,
+    };
+
+    if let CopyFormatParams::Avro = params {
+        // Validate that the table can be decoded from Avro. The name of the record only matters
+        // for schema resolution, which happens per file.
+        mz_interchange::avro::relationdesc_to_reader_schema(&desc, "row")
+            .map_err(|e| sql_err!("{}", e))?;
+    }
     Ok(Plan::CopyFrom(CopyFromPlan {
         id,
         source,
@@ -1231,7 +1329,8 @@ generate_extracted_config!(
     (AwsConnection, with_options::Object),
     (MaxFileSize, ByteSize, Default(ByteSize::mb(256))),
     (Files, Vec<String>),
-    (Pattern, String)
+    (Pattern, String),
+    (JsonPaths, Vec<String>)
 );
 
 pub fn plan_copy(
@@ -1254,6 +1353,10 @@ pub fn plan_copy(
             "csv" => Ok(CopyFormat::Csv),
             "binary" => Ok(CopyFormat::Binary),
             "parquet" => Ok(CopyFormat::Parquet),
+            "avro" => Ok(CopyFormat::Avro),
+            "orc" => Ok(CopyFormat::Orc),
+            "json" => Ok(CopyFormat::Json { array: false }),
+            "json array" => Ok(CopyFormat::Json { array: true }),
             _ => sql_bail!("unknown FORMAT: {}", format),
         })
         .transpose()?;
@@ -1269,6 +1372,9 @@ pub fn plan_copy(
             if options.null.is_some() {
                 sql_bail!("COPY TO does not support NULL option yet");
             }
+            if let Some(CopyFormat::Json { .. }) = format {
+                bail_unsupported!("COPY TO STDOUT with FORMAT JSON");
+            }
             match relation {
                 CopyRelation::Named { .. } => sql_bail!("named with COPY TO STDOUT unsupported"),
                 CopyRelation::Select(stmt) => Ok(plan_select(
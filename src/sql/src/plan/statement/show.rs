@@ -22,8 +22,9 @@ use mz_repr::{CatalogItemId, Datum, RelationDesc, Row, ScalarType};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
     CreateSubsourceOptionName, ExternalReferenceExport, ExternalReferences, ObjectType,
-    ShowCreateClusterStatement, ShowCreateConnectionStatement, ShowCreateMaterializedViewStatement,
-    ShowObjectType, SystemObjectType, UnresolvedItemName, WithOptionValue,
+    ShowCreateClusterStatement, ShowCreateConnectionStatement, ShowCreateFunctionStatement,
+    ShowCreateMaterializedViewStatement, ShowObjectType, SystemObjectType, UnresolvedItemName,
+    WithOptionValue,
 };
 use query::QueryContext;
 
@@ -112,7 +113,7 @@ fn plan_show_create_item(
     if item.id().is_system()
         && matches!(
             expect_type,
-            CatalogItemType::Table | CatalogItemType::Source
+            CatalogItemType::Table | CatalogItemType::Source | CatalogItemType::Func
         )
     {
         sql_bail!("cannot show create for system object {name}");
@@ -241,6 +242,25 @@ pub fn plan_show_create_connection(
     plan_show_create_item(scx, &connection_name, CatalogItemType::Connection)
 }
 
+pub fn describe_show_create_function(
+    _: &StatementContext,
+    _: ShowCreateFunctionStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(Some(
+        RelationDesc::builder()
+            .with_column("name", ScalarType::String.nullable(false))
+            .with_column("create_sql", ScalarType::String.nullable(false))
+            .finish(),
+    )))
+}
+
+pub fn plan_show_create_function(
+    scx: &StatementContext,
+    ShowCreateFunctionStatement { function_name }: ShowCreateFunctionStatement<Aug>,
+) -> Result<ShowCreatePlan, PlanError> {
+    plan_show_create_item(scx, &function_name, CatalogItemType::Func)
+}
+
 pub fn show_databases<'a>(
     scx: &'a StatementContext<'a>,
     filter: Option<ShowStatementFilter<Aug>>,
@@ -1000,7 +1020,10 @@ fn humanize_sql_for_show_create(
     id: CatalogItemId,
     sql: &str,
 ) -> Result<String, PlanError> {
-    use mz_sql_parser::ast::{CreateSourceConnection, MySqlConfigOptionName, PgConfigOptionName};
+    use mz_sql_parser::ast::{
+        CreateSourceConnection, MySqlConfigOptionName, PgConfigOptionName,
+        SqlServerConfigOptionName,
+    };
 
     let parsed = parse::parse(sql)?.into_element().ast;
     let (mut resolved, _) = names::resolve(catalog, parsed)?;
@@ -1078,10 +1101,41 @@ fn humanize_sql_for_show_create(
                         }
                     });
                 }
-                CreateSourceConnection::SqlServer { .. } => {
-                    return Err(PlanError::Unsupported {
-                        feature: "SQL SERVER".to_string(),
-                        discussion_no: None,
+                CreateSourceConnection::SqlServer { options, .. } => {
+                    options.retain_mut(|o| {
+                        match o.name {
+                            // Dropping a subsource does not remove any `TEXT
+                            // COLUMNS` values that refer to the table it
+                            // ingests, which we'll handle below.
+                            SqlServerConfigOptionName::TextColumns
+                            | SqlServerConfigOptionName::ExcludeColumns => {}
+                            // Rejected during purification, so never stored.
+                            SqlServerConfigOptionName::CaptureInstance => return true,
+                            // Drop details, which does not rountrip.
+                            SqlServerConfigOptionName::Details => return false,
+                        };
+
+                        match &mut o.value {
+                            Some(WithOptionValue::Sequence(seq_unresolved_item_names)) => {
+                                seq_unresolved_item_names.retain(|v| match v {
+                                    WithOptionValue::UnresolvedItemName(n) => {
+                                        // Column references are `schema.table.column` while
+                                        // references are `database.schema.table`.
+                                        let table = &n.0[..n.0.len() - 1];
+                                        curr_references
+                                            .keys()
+                                            .any(|reference| reference.0.ends_with(table))
+                                    }
+                                    _ => unreachable!(
+                                        "TEXT COLUMNS + EXCLUDE COLUMNS must be sequence of unresolved item names"
+                                    ),
+                                });
+                                !seq_unresolved_item_names.is_empty()
+                            }
+                            _ => unreachable!(
+                                "TEXT COLUMNS + EXCLUDE COLUMNS must be sequence of unresolved item names"
+                            ),
+                        }
                     });
                 }
                 CreateSourceConnection::MySql { options, .. } => {
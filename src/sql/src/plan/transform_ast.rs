@@ -99,6 +99,14 @@ impl<'a> FuncRewriter<'a> {
         })
     }
 
+    fn float64_data_type(&self) -> ResolvedDataType {
+        self.resolve_known_valid_data_type(&PartialItemName {
+            database: None,
+            schema: Some(PG_CATALOG_SCHEMA.into()),
+            item: "float8".into(),
+        })
+    }
+
     // Divides `lhs` by `rhs` but replaces division-by-zero errors with NULL;
     // note that this is semantically equivalent to `NULLIF(rhs, 0)`.
     fn plan_divide(lhs: Expr<Aug>, rhs: Expr<Aug>) -> Expr<Aug> {
@@ -271,15 +279,21 @@ impl<'a> FuncRewriter<'a> {
         // number very close to zero when it should mathematically be zero.
         // This makes it so `stddev` fails as it tries to take the square root
         // of a negative number.
-        // So, we need the following logic:
-        // If `result` is NULL, return NULL (no surprise here)
-        // Otherwise, if `result` is >0, return `result` (no surprise here either)
-        // Otherwise, return 0.
-        //
-        // Unfortunately, we can't use `GREATEST` directly for this,
-        // since `greatest(NULL, 0)` is 0, not NULL, so we need to
-        // create a `Case` expression that computes `result`
-        // twice. Hopefully the optimizer can deal with this!
+        Self::plan_nonnegative(result)
+    }
+
+    // Clamps `result`, which is mathematically nonnegative but may be slightly
+    // negative due to numerical inaccuracy, to zero. We need the following
+    // logic:
+    // If `result` is NULL, return NULL (no surprise here)
+    // Otherwise, if `result` is >0, return `result` (no surprise here either)
+    // Otherwise, return 0.
+    //
+    // Unfortunately, we can't use `GREATEST` directly for this,
+    // since `greatest(NULL, 0)` is 0, not NULL, so we need to
+    // create a `Case` expression that computes `result`
+    // twice. Hopefully the optimizer can deal with this!
+    fn plan_nonnegative(result: Expr<Aug>) -> Expr<Aug> {
         let result_is_null = Expr::IsExpr {
             expr: Box::new(result.clone()),
             construct: IsExprConstruct::Null,
@@ -296,6 +310,114 @@ impl<'a> FuncRewriter<'a> {
         }
     }
 
+    fn plan_bivariate(
+        &mut self,
+        name: &str,
+        y: Expr<Aug>,
+        x: Expr<Aug>,
+        filter: Option<Box<Expr<Aug>>>,
+        distinct: bool,
+        over: Option<WindowSpec<Aug>>,
+    ) -> Expr<Aug> {
+        // Like `plan_variance`, this uses the "textbook" formulas, expressed
+        // in terms of N = count(x), Sx = sum(x), Sy = sum(y), and
+        //
+        //     SXX = sum(x²) - sum(x)² / N
+        //     SYY = sum(y²) - sum(y)² / N
+        //     SXY = sum(x * y) - sum(x) * sum(y) / N
+        //
+        // so that every function is computed from accumulable aggregates.
+        //
+        // `DISTINCT` would need to apply to (y, x) pairs rather than to each
+        // of the sums independently, which we can't express this way.
+        if distinct && self.status.is_ok() {
+            self.status = Err(PlanError::Unsupported {
+                feature: format!("{name} with DISTINCT"),
+                discussion_no: None,
+            });
+        }
+
+        // Only rows in which both inputs are non-null contribute. The typed
+        // NULL also implicitly casts each input to `float8`, as PostgreSQL
+        // would when selecting the function.
+        let null = Expr::null().cast(self.float64_data_type());
+        let if_other_not_null = |expr: Expr<Aug>, other: &Expr<Aug>| Expr::Case {
+            operand: None,
+            conditions: vec![Expr::IsExpr {
+                expr: Box::new(other.clone()),
+                construct: IsExprConstruct::Null,
+                negated: false,
+            }],
+            results: vec![null.clone()],
+            else_result: Some(Box::new(expr)),
+        };
+        let (y, x) = (if_other_not_null(y.clone(), &x), if_other_not_null(x, &y));
+
+        let agg = |this: &mut Self, func: &str, expr: Expr<Aug>| {
+            this.plan_agg(
+                this.scx
+                    .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, func]),
+                expr,
+                vec![],
+                filter.clone(),
+                distinct,
+                over.clone(),
+            )
+        };
+        let n = agg(self, "count", x.clone());
+        let sum_x = agg(self, "sum", x.clone());
+        let sum_y = agg(self, "sum", y.clone());
+        let sum_xx = agg(self, "sum", x.clone().multiply(x.clone()));
+        let sum_yy = agg(self, "sum", y.clone().multiply(y.clone()));
+        let sum_xy = agg(self, "sum", x.multiply(y));
+
+        let sxx = Self::plan_nonnegative(sum_xx.minus(Self::plan_divide(
+            sum_x.clone().multiply(sum_x.clone()),
+            n.clone(),
+        )));
+        let syy = Self::plan_nonnegative(sum_yy.minus(Self::plan_divide(
+            sum_y.clone().multiply(sum_y.clone()),
+            n.clone(),
+        )));
+        let sxy = sum_xy.minus(Self::plan_divide(
+            sum_x.clone().multiply(sum_y.clone()),
+            n.clone(),
+        ));
+        let slope = Self::plan_divide(sxy.clone(), sxx.clone());
+
+        match name {
+            "regr_count" => n,
+            "regr_sxx" => sxx,
+            "regr_syy" => syy,
+            "regr_sxy" => sxy,
+            "regr_avgx" => Self::plan_divide(sum_x, n),
+            "regr_avgy" => Self::plan_divide(sum_y, n),
+            "regr_slope" => slope,
+            "regr_intercept" => Self::plan_divide(sum_y.minus(sum_x.multiply(slope)), n),
+            "regr_r2" => Expr::Case {
+                operand: None,
+                conditions: vec![
+                    sxx.clone().equals(Expr::number("0")),
+                    syy.clone().equals(Expr::number("0")),
+                ],
+                results: vec![Expr::null(), Expr::number("1")],
+                else_result: Some(Box::new(
+                    sxy.clone().multiply(sxy).divide(sxx.multiply(syy)),
+                )),
+            },
+            "covar_pop" => Self::plan_divide(sxy, n),
+            "covar_samp" => Self::plan_divide(sxy, n.minus(Expr::number("1"))),
+            "corr" => Self::plan_divide(
+                sxy,
+                sxx.multiply(syy).call_unary(
+                    self.scx
+                        .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "sqrt"]),
+                ),
+            ),
+            _ => unreachable!("{name} is not a bivariate aggregate"),
+        }
+    }
+
     fn plan_stddev(
         &mut self,
         expr: Expr<Aug>,
@@ -429,6 +551,11 @@ impl<'a> FuncRewriter<'a> {
                 let (lhs, rhs) = (args[0].clone(), args[1].clone());
                 match name.as_str() {
                     "mod" => lhs.modulo(rhs),
+                    "corr" | "covar_pop" | "covar_samp" | "regr_avgx" | "regr_avgy"
+                    | "regr_count" | "regr_intercept" | "regr_r2" | "regr_slope" | "regr_sxx"
+                    | "regr_sxy" | "regr_syy" => {
+                        self.plan_bivariate(&name, lhs, rhs, filter, distinct, over)
+                    }
                     "pow" => Expr::call(
                         self.scx
                             .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "power"]),
@@ -609,11 +609,15 @@ pub fn fuse_window_functions(
             HirScalarExpr::Windowing(WindowExpr {
                 func:
                     WindowExprType::Aggregate(AggregateWindowExpr {
-                        aggregate_expr: AggregateExpr { func, .. },
+                        aggregate_expr: AggregateExpr { func, distinct, .. },
                         ..
                     }),
                 ..
-            }) => !matches!(func, AggregateFunc::FusedWindowAgg { .. }),
+            }) => {
+                // Also exclude DISTINCT window aggregations, because the fused evaluation doesn't
+                // support them.
+                !matches!(func, AggregateFunc::FusedWindowAgg { .. }) && !distinct
+            }
             _ => false,
         }
     };
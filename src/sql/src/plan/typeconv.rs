@@ -596,6 +596,31 @@ static VALID_CASTS: LazyLock<BTreeMap<(ScalarBaseType, ScalarBaseType), CastImpl
             (String, Bytes) => Explicit: CastStringToBytes(func::CastStringToBytes),
             (String, Jsonb) => Explicit: CastStringToJsonb(func::CastStringToJsonb),
             (String, Uuid) => Explicit: CastStringToUuid(func::CastStringToUuid),
+            (String, Inet) => Explicit: CastStringToInet(func::CastStringToInet),
+            (String, Cidr) => Explicit: CastStringToCidr(func::CastStringToCidr),
+            (String, MacAddr) => Explicit: CastStringToMacAddr(func::CastStringToMacAddr),
+            (String, MacAddr8) => Explicit: CastStringToMacAddr8(func::CastStringToMacAddr8),
+            (String, TsVector) => Explicit: CastStringToTsVector(func::CastStringToTsVector),
+            (String, TsQuery) => Explicit: CastStringToTsQuery(func::CastStringToTsQuery),
+            (String, JsonPath) => Explicit: CastStringToJsonPath(func::CastStringToJsonPath),
+            (String, Geometry) => Explicit: CastStringToGeometry(func::CastStringToGeometry),
+            (String, Geography) => Explicit: CastStringToGeography(func::CastStringToGeography),
+            (String, Enum) => Explicit: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
+                let return_ty = to_type.clone();
+                Some(|e: HirScalarExpr| e.call_unary(UnaryFunc::CastStringToEnum(func::CastStringToEnum { return_ty })))
+            }),
+            (String, Record) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
+                // Only named composite types know the types of their fields.
+                if to_type.unwrap_record_element_type().is_empty() {
+                    return None;
+                }
+                let cast_exprs = to_type.unwrap_record_element_type()
+                    .into_iter()
+                    .map(|t| plan_hypothetical_cast(ecx, ccx, from_type, t))
+                    .collect::<Option<Box<_>>>()?;
+                let return_ty = to_type.clone();
+                Some(|e: HirScalarExpr| e.call_unary(UnaryFunc::CastStringToRecord(func::CastStringToRecord { return_ty, cast_exprs })))
+            }),
             (String, Array) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
                 let return_ty = to_type.clone();
                 let to_el_type = to_type.unwrap_array_element_type();
@@ -781,6 +806,45 @@ static VALID_CASTS: LazyLock<BTreeMap<(ScalarBaseType, ScalarBaseType), CastImpl
             // UUID
             (Uuid, String) => Assignment: CastUuidToString(func::CastUuidToString),
 
+            // INET
+            (Inet, String) => Assignment: CastInetToString(func::CastInetToString),
+            (Inet, Cidr) => Assignment: CastInetToCidr(func::CastInetToCidr),
+
+            // CIDR
+            (Cidr, String) => Assignment: CastInetToString(func::CastInetToString),
+            (Cidr, Inet) => Implicit: CastCidrToInet(func::CastCidrToInet),
+
+            // MACADDR
+            (MacAddr, String) => Assignment: CastMacAddrToString(func::CastMacAddrToString),
+            (MacAddr, MacAddr8) => Implicit: CastMacAddrToMacAddr8(func::CastMacAddrToMacAddr8),
+
+            // MACADDR8
+            (MacAddr8, String) => Assignment: CastMacAddr8ToString(func::CastMacAddr8ToString),
+            (MacAddr8, MacAddr) => Implicit: CastMacAddr8ToMacAddr(func::CastMacAddr8ToMacAddr),
+
+            // TSVECTOR
+            (TsVector, String) => Assignment: CastTsVectorToString(func::CastTsVectorToString),
+
+            // TSQUERY
+            (TsQuery, String) => Assignment: CastTsQueryToString(func::CastTsQueryToString),
+
+            // JSONPATH
+            (JsonPath, String) => Assignment: CastJsonPathToString(func::CastJsonPathToString),
+
+            // GEOMETRY
+            (Geometry, String) => Assignment: CastGeometryToString(func::CastGeometryToString),
+            (Geometry, Geography) => Assignment: CastGeometryToGeography(func::CastGeometryToGeography),
+
+            // GEOGRAPHY
+            (Geography, String) => Assignment: CastGeographyToString(func::CastGeographyToString),
+            (Geography, Geometry) => Explicit: CastGeographyToGeometry(func::CastGeographyToGeometry),
+
+            // ENUM
+            (Enum, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
+                let ty = from_type.clone();
+                Some(|e: HirScalarExpr| e.call_unary(UnaryFunc::CastEnumToString(func::CastEnumToString { ty })))
+            }),
+
             // Numeric
             (Numeric, Numeric) => Assignment: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
                 let scale = to_type.unwrap_numeric_max_scale();
@@ -1030,6 +1094,7 @@ pub fn guess_best_common_type(
             return Ok(ScalarType::Record {
                 fields: fields.into(),
                 custom_id: None,
+                custom_oid: None,
             });
         }
     }
@@ -30,6 +30,7 @@ use mz_ore::{assert_none, soft_panic_or_log};
 use mz_postgres_util::desc::PostgresTableDesc;
 use mz_postgres_util::replication::WalLevel;
 use mz_postgres_util::tunnel::PostgresFlavor;
+use mz_postgres_util::PostgresEnumDesc;
 use mz_proto::RustType;
 use mz_repr::{strconv, CatalogItemId, RelationDesc, RelationVersionSelector, Timestamp};
 use mz_sql_parser::ast::display::AstDisplay;
@@ -40,14 +41,16 @@ use mz_sql_parser::ast::{
     ColumnName, CreateMaterializedViewStatement, CreateSinkConnection, CreateSinkOptionName,
     CreateSinkStatement, CreateSubsourceOption, CreateSubsourceOptionName,
     CreateTableFromSourceStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
-    CsrSeedAvro, CsrSeedProtobuf, CsrSeedProtobufSchema, DeferredItemName, DocOnIdentifier,
-    DocOnSchema, Expr, Function, FunctionArgs, Ident, KafkaSourceConfigOption,
+    CsrSeedAvro, CsrSeedJson, CsrSeedProtobuf, CsrSeedProtobufSchema, DeferredItemName,
+    DocOnIdentifier, DocOnSchema, Expr, Function, FunctionArgs, Ident, KafkaSourceConfigOption,
     KafkaSourceConfigOptionName, LoadGenerator, LoadGeneratorOption, LoadGeneratorOptionName,
     MaterializedViewOption, MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName,
     PgConfigOption, PgConfigOptionName, RawItemName, ReaderSchemaSelectionStrategy,
-    RefreshAtOptionValue, RefreshEveryOptionValue, RefreshOptionValue, SourceEnvelope, Statement,
-    TableFromSourceColumns, TableFromSourceOption, TableFromSourceOptionName, UnresolvedItemName,
+    RefreshAtOptionValue, RefreshEveryOptionValue, RefreshOptionValue, SourceEnvelope,
+    SqlServerConfigOption, SqlServerConfigOptionName, Statement, TableFromSourceColumns,
+    TableFromSourceOption, TableFromSourceOptionName, UnresolvedItemName,
 };
+use mz_sql_server_util::SqlServerTableDesc;
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::connections::{Connection, PostgresConnection};
@@ -55,6 +58,7 @@ use mz_storage_types::errors::ContextCreationError;
 use mz_storage_types::sources::load_generator::LoadGeneratorOutput;
 use mz_storage_types::sources::mysql::MySqlSourceDetails;
 use mz_storage_types::sources::postgres::PostgresSourcePublicationDetails;
+use mz_storage_types::sources::sql_server::SqlServerSourceExtras;
 use mz_storage_types::sources::{
     GenericSourceConnection, PostgresSourceConnection, SourceConnection, SourceDesc,
     SourceExportStatementDetails,
@@ -68,8 +72,9 @@ use uuid::Uuid;
 
 use crate::ast::{
     AlterSourceAddSubsourceOption, AvroSchema, CreateSourceConnection, CreateSourceStatement,
-    CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionProtobuf, ExternalReferenceExport,
-    ExternalReferences, Format, FormatSpecifier, ProtobufSchema, Value, WithOptionValue,
+    CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionJson, CsrConnectionProtobuf,
+    ExternalReferenceExport, ExternalReferences, Format, FormatSpecifier, ProtobufSchema, Value,
+    WithOptionValue,
 };
 use crate::catalog::{CatalogItemType, SessionCatalog};
 use crate::kafka_util::{KafkaSinkConfigOptionExtracted, KafkaSourceConfigOptionExtracted};
@@ -85,12 +90,14 @@ use crate::{kafka_util, normalize};
 use self::error::{
     CsrPurificationError, KafkaSinkPurificationError, KafkaSourcePurificationError,
     LoadGeneratorSourcePurificationError, MySqlSourcePurificationError, PgSourcePurificationError,
+    SqlServerSourcePurificationError,
 };
 
 pub(crate) mod error;
 pub mod mysql;
 pub mod postgres;
 mod references;
+pub mod sql_server;
 
 pub(crate) struct RequestedSourceExport<T> {
     external_reference: UnresolvedItemName,
@@ -238,6 +245,17 @@ pub enum PurifiedExportDetails {
     Postgres {
         table: PostgresTableDesc,
         text_columns: Option<Vec<Ident>>,
+        /// The upstream enum types used by the table's columns, keyed by OID.
+        enum_types: BTreeMap<u32, PostgresEnumDesc>,
+        /// The Materialize types of the upstream PostGIS types used by the
+        /// table's columns, keyed by OID.
+        postgis_types: BTreeMap<u32, mz_pgrepr::Type>,
+    },
+    SqlServer {
+        table: SqlServerTableDesc,
+        capture_instance: String,
+        text_columns: Option<Vec<Ident>>,
+        exclude_columns: Option<Vec<Ident>>,
     },
     Kafka {},
     LoadGenerator {
@@ -314,6 +332,7 @@ pub(crate) fn purify_create_sink_avro_doc_on_options(
         | Format::Bytes
         | Format::Csv { .. }
         | Format::Json { .. }
+        | Format::JsonSchema(..)
         | Format::Protobuf(..)
         | Format::Regex(..)
         | Format::Text => (),
@@ -503,6 +522,66 @@ async fn purify_create_sink(
                 Err(KafkaSinkPurificationError::ZeroBrokers)?;
             }
         }
+        CreateSinkConnection::Postgres {
+            connection,
+            options,
+            key: _,
+        } => {
+            let scx = StatementContext::new(None, &catalog);
+            let connection = {
+                let item = scx.get_item_by_resolved_name(connection)?;
+                match item.connection()? {
+                    Connection::Postgres(connection) => {
+                        connection.clone().into_inline_connection(scx.catalog)
+                    }
+                    _ => sql_bail!(
+                        "{} is not a POSTGRES CONNECTION",
+                        scx.catalog.resolve_full_name(item.name())
+                    ),
+                }
+            };
+
+            let crate::plan::statement::PostgresSinkConfigOptionExtracted { table, .. } =
+                options.clone().try_into()?;
+            // A missing TABLE option is reported during planning.
+            if let Some(table) = table {
+                let (schema, table) = crate::plan::statement::postgres_sink_table(table)?;
+
+                // Verify that we can connect upstream and that the table exists. The columns of
+                // the table are only checked once the sink starts.
+                let config = connection
+                    .config(
+                        &storage_configuration.connection_context.secrets_reader,
+                        storage_configuration,
+                        InTask::No,
+                    )
+                    .await?;
+                let client = config
+                    .connect(
+                        "postgres_purification",
+                        &storage_configuration.connection_context.ssh_tunnel_manager,
+                    )
+                    .await?;
+                let exists = client
+                    .query_opt(
+                        "SELECT 1 FROM pg_catalog.pg_tables WHERE schemaname = $1 AND tablename = $2",
+                        &[&schema, &table],
+                    )
+                    .await
+                    .map_err(mz_postgres_util::PostgresError::from)?
+                    .is_some();
+                if !exists {
+                    sql_bail!(
+                        "upstream table {}.{} does not exist",
+                        schema.quoted(),
+                        table.quoted()
+                    );
+                }
+            }
+        }
+        // Access to the sink's bucket is only checked once the sink starts, like it is for
+        // `COPY ... TO` S3.
+        CreateSinkConnection::Iceberg { .. } | CreateSinkConnection::S3 { .. } => {}
     }
 
     let mut csr_connection_ids = BTreeSet::new();
@@ -519,7 +598,9 @@ async fn purify_create_sink(
         })
         | Format::Protobuf(ProtobufSchema::Csr {
             csr_connection: CsrConnectionProtobuf { connection, .. },
-        }) => {
+            ..
+        })
+        | Format::JsonSchema(CsrConnectionJson { connection, .. }) => {
             csr_connection_ids.insert(*connection.connection.item_id());
         }
     });
@@ -912,12 +993,96 @@ async fn purify_create_source(
                 )))),
             })
         }
-        CreateSourceConnection::SqlServer { .. } => {
-            // TODO(sql_server1)
-            return Err(PlanError::Unsupported {
-                feature: "SQL SERVER".to_string(),
-                discussion_no: None,
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::SqlServer(connection) => {
+                    connection.clone().into_inline_connection(&catalog)
+                }
+                _ => Err(SqlServerSourcePurificationError::NotSqlServerConnection(
+                    scx.catalog.resolve_full_name(connection_item.name()),
+                ))?,
+            };
+            let crate::plan::statement::ddl::SqlServerConfigOptionExtracted {
+                capture_instance,
+                details,
+                text_columns,
+                exclude_columns,
+                seen: _,
+            } = options.clone().try_into()?;
+
+            if details.is_some() {
+                Err(SqlServerSourcePurificationError::UserSpecifiedDetails)?;
+            }
+            if capture_instance.is_some() {
+                Err(SqlServerSourcePurificationError::UserSpecifiedCaptureInstance)?;
+            }
+
+            let config = connection
+                .config(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+            let (mut client, conn) = mz_sql_server_util::Client::connect(config).await?;
+            mz_ore::task::spawn(|| "sql server purification", async move { conn.await });
+
+            sql_server::ensure_cdc_enabled(&mut client, &connection.database).await?;
+            sql_server::ensure_snapshot_isolation_enabled(&mut client, &connection.database)
+                .await?;
+
+            let reference_client = SourceReferenceClient::SqlServer {
+                client: &mut client,
+                database: &connection.database,
+            };
+            retrieved_source_references = reference_client.get_source_references().await?;
+
+            let sql_server::PurifiedSourceExports {
+                source_exports: subsources,
+                normalized_text_columns,
+                normalized_exclude_columns,
+            } = sql_server::purify_source_exports(
+                &connection.database,
+                &retrieved_source_references,
+                external_references,
+                text_columns,
+                exclude_columns,
+                source_name,
+                &reference_policy,
+            )?;
+            requested_subsource_map.extend(subsources);
+
+            // We don't have any fields in this details struct but keep this around for
+            // conformity with the other sources.
+            let extras = SqlServerSourceExtras {};
+            // Update options with the purified details
+            options.retain(|SqlServerConfigOption { name, .. }| {
+                name != &SqlServerConfigOptionName::Details
+            });
+            options.push(SqlServerConfigOption {
+                name: SqlServerConfigOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    extras.into_proto().encode_to_vec(),
+                )))),
             });
+
+            if let Some(text_cols_option) = options
+                .iter_mut()
+                .find(|option| option.name == SqlServerConfigOptionName::TextColumns)
+            {
+                text_cols_option.value = Some(WithOptionValue::Sequence(normalized_text_columns));
+            }
+            if let Some(exclude_cols_option) = options
+                .iter_mut()
+                .find(|option| option.name == SqlServerConfigOptionName::ExcludeColumns)
+            {
+                exclude_cols_option.value =
+                    Some(WithOptionValue::Sequence(normalized_exclude_columns));
+            }
         }
         CreateSourceConnection::MySql {
             connection,
@@ -1302,6 +1467,7 @@ async fn purify_alter_source_add_subsources(
             ..
         }) => {}
         GenericSourceConnection::MySql(_) => {}
+        GenericSourceConnection::SqlServer(_) => {}
         _ => sql_bail!(
             "source {} does not support ALTER SOURCE.",
             partial_source_name
@@ -1448,6 +1614,56 @@ async fn purify_alter_source_add_subsources(
                     Some(WithOptionValue::Sequence(normalized_exclude_columns));
             }
         }
+        GenericSourceConnection::SqlServer(sql_server_source_connection) => {
+            let sql_server_connection = &sql_server_source_connection.connection;
+            let config = sql_server_connection
+                .config(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+            let (mut client, conn) = mz_sql_server_util::Client::connect(config).await?;
+            mz_ore::task::spawn(|| "sql server purification", async move { conn.await });
+
+            let requested_references = Some(ExternalReferences::SubsetTables(external_references));
+
+            let reference_client = SourceReferenceClient::SqlServer {
+                client: &mut client,
+                database: &sql_server_connection.database,
+            };
+            let retrieved_source_references = reference_client.get_source_references().await?;
+
+            let sql_server::PurifiedSourceExports {
+                source_exports: subsources,
+                normalized_text_columns,
+                normalized_exclude_columns,
+            } = sql_server::purify_source_exports(
+                &sql_server_connection.database,
+                &retrieved_source_references,
+                &requested_references,
+                text_columns,
+                exclude_columns,
+                &unresolved_source_name,
+                &SourceReferencePolicy::Required,
+            )?;
+            requested_subsource_map.extend(subsources);
+
+            // Update options with the purified details
+            if let Some(text_cols_option) = options
+                .iter_mut()
+                .find(|option| option.name == AlterSourceAddSubsourceOptionName::TextColumns)
+            {
+                text_cols_option.value = Some(WithOptionValue::Sequence(normalized_text_columns));
+            }
+            if let Some(exclude_cols_option) = options
+                .iter_mut()
+                .find(|option| option.name == AlterSourceAddSubsourceOptionName::ExcludeColumns)
+            {
+                exclude_cols_option.value =
+                    Some(WithOptionValue::Sequence(normalized_exclude_columns));
+            }
+        }
         _ => unreachable!(),
     };
 
@@ -1512,6 +1728,24 @@ async fn purify_alter_source_refresh_references(
             };
             reference_client.get_source_references().await?
         }
+        GenericSourceConnection::SqlServer(sql_server_source_connection) => {
+            let sql_server_connection = &sql_server_source_connection.connection;
+            let config = sql_server_connection
+                .config(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+            let (mut client, conn) = mz_sql_server_util::Client::connect(config).await?;
+            mz_ore::task::spawn(|| "sql server purification", async move { conn.await });
+
+            let reference_client = SourceReferenceClient::SqlServer {
+                client: &mut client,
+                database: &sql_server_connection.database,
+            };
+            reference_client.get_source_references().await?
+        }
         GenericSourceConnection::LoadGenerator(load_gen_connection) => {
             let reference_client = SourceReferenceClient::LoadGenerator {
                 generator: &load_gen_connection.load_generator,
@@ -1740,6 +1974,44 @@ async fn purify_create_table_from_source(
             let (_, purified_export) = source_exports.into_iter().next().unwrap();
             purified_export
         }
+        GenericSourceConnection::SqlServer(sql_server_source_connection) => {
+            let sql_server_connection = &sql_server_source_connection.connection;
+            let config = sql_server_connection
+                .config(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+            let (mut client, conn) = mz_sql_server_util::Client::connect(config).await?;
+            mz_ore::task::spawn(|| "sql server purification", async move { conn.await });
+
+            let reference_client = SourceReferenceClient::SqlServer {
+                client: &mut client,
+                database: &sql_server_connection.database,
+            };
+            retrieved_source_references = reference_client.get_source_references().await?;
+
+            let sql_server::PurifiedSourceExports {
+                source_exports,
+                // TODO(database-issues#8620): Remove once subsources are removed
+                // `normalized_text/exclude_columns` is not relevant for us and is only returned for
+                // `CREATE SOURCE` statements that automatically generate subsources
+                normalized_text_columns: _,
+                normalized_exclude_columns: _,
+            } = sql_server::purify_source_exports(
+                &sql_server_connection.database,
+                &retrieved_source_references,
+                &requested_references,
+                qualified_text_columns,
+                qualified_exclude_columns,
+                &unresolved_source_name,
+                &SourceReferencePolicy::Required,
+            )?;
+            // There should be exactly one source_export returned for this statement
+            let (_, purified_export) = source_exports.into_iter().next().unwrap();
+            purified_export
+        }
         GenericSourceConnection::LoadGenerator(load_gen_connection) => {
             let reference_client = SourceReferenceClient::LoadGenerator {
                 generator: &load_gen_connection.load_generator,
@@ -1901,6 +2173,58 @@ async fn purify_create_table_from_source(
                 )))),
             })
         }
+        PurifiedExportDetails::SqlServer { .. } => {
+            let sql_server::SqlServerExportStatementValues {
+                columns: gen_columns,
+                constraints: gen_constraints,
+                text_columns: gen_text_columns,
+                exclude_columns: gen_exclude_columns,
+                details: gen_details,
+                external_reference: _,
+            } = sql_server::generate_source_export_statement_values(&scx, purified_export)?;
+
+            if let Some(text_cols_option) = with_options
+                .iter_mut()
+                .find(|option| option.name == TableFromSourceOptionName::TextColumns)
+            {
+                if let Some(gen_text_columns) = gen_text_columns {
+                    text_cols_option.value = Some(WithOptionValue::Sequence(gen_text_columns));
+                } else {
+                    soft_panic_or_log!(
+                        "text_columns should be Some if text_cols_option is present"
+                    );
+                }
+            }
+            if let Some(exclude_cols_option) = with_options
+                .iter_mut()
+                .find(|option| option.name == TableFromSourceOptionName::ExcludeColumns)
+            {
+                if let Some(gen_exclude_columns) = gen_exclude_columns {
+                    exclude_cols_option.value =
+                        Some(WithOptionValue::Sequence(gen_exclude_columns));
+                } else {
+                    soft_panic_or_log!(
+                        "exclude_columns should be Some if exclude_cols_option is present"
+                    );
+                }
+            }
+            match columns {
+                TableFromSourceColumns::Defined(_) => unreachable!(),
+                TableFromSourceColumns::NotSpecified => {
+                    *columns = TableFromSourceColumns::Defined(gen_columns);
+                    *constraints = gen_constraints;
+                }
+                TableFromSourceColumns::Named(_) => {
+                    sql_bail!("columns cannot be named for SQL Server sources")
+                }
+            }
+            with_options.push(TableFromSourceOption {
+                name: TableFromSourceOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    gen_details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
         PurifiedExportDetails::LoadGenerator { .. } => {
             let (desc, output) = match purified_export.details {
                 PurifiedExportDetails::LoadGenerator { table, output } => (table, output),
@@ -2008,10 +2332,14 @@ async fn purify_source_format_single(
             AvroSchema::InlineSchema { .. } => {}
         },
         Format::Protobuf(schema) => match schema {
-            ProtobufSchema::Csr { csr_connection } => {
+            ProtobufSchema::Csr {
+                message_name,
+                csr_connection,
+            } => {
                 purify_csr_connection_proto(
                     catalog,
                     options,
+                    message_name.as_deref(),
                     csr_connection,
                     envelope,
                     storage_configuration,
@@ -2020,6 +2348,16 @@ async fn purify_source_format_single(
             }
             ProtobufSchema::InlineSchema { .. } => {}
         },
+        Format::JsonSchema(csr_connection) => {
+            purify_csr_connection_json(
+                catalog,
+                options,
+                csr_connection,
+                envelope,
+                storage_configuration,
+            )
+            .await?;
+        }
         Format::Bytes
         | Format::Regex(_)
         | Format::Json { .. }
@@ -2051,6 +2389,13 @@ pub fn generate_subsource_statements(
         PurifiedExportDetails::MySql { .. } => {
             crate::pure::mysql::generate_create_subsource_statements(scx, source_name, subsources)?
         }
+        PurifiedExportDetails::SqlServer { .. } => {
+            crate::pure::sql_server::generate_create_subsource_statements(
+                scx,
+                source_name,
+                subsources,
+            )?
+        }
         PurifiedExportDetails::LoadGenerator { .. } => {
             let mut subsource_stmts = Vec::with_capacity(subsources.len());
             for (subsource_name, purified_export) in subsources {
@@ -2111,6 +2456,7 @@ pub fn generate_subsource_statements(
 async fn purify_csr_connection_proto(
     catalog: &dyn SessionCatalog,
     options: &SourceFormatOptions,
+    message_name: Option<&str>,
     csr_connection: &mut CsrConnectionProtobuf<Aug>,
     envelope: &Option<SourceEnvelope>,
     storage_configuration: &StorageConfiguration,
@@ -2140,8 +2486,9 @@ async fn purify_csr_connection_proto(
                 .await
                 .map_err(|e| CsrPurificationError::ClientError(Arc::new(e)))?;
 
-            let value = compile_proto(&format!("{}-value", topic), &ccsr_client).await?;
-            let key = compile_proto(&format!("{}-key", topic), &ccsr_client)
+            let value =
+                compile_proto(&format!("{}-value", topic), message_name, &ccsr_client).await?;
+            let key = compile_proto(&format!("{}-key", topic), None, &ccsr_client)
                 .await
                 .ok();
 
@@ -2208,6 +2555,61 @@ async fn purify_csr_connection_avro(
     Ok(())
 }
 
+async fn purify_csr_connection_json(
+    catalog: &dyn SessionCatalog,
+    options: &SourceFormatOptions,
+    csr_connection: &mut CsrConnectionJson<Aug>,
+    envelope: &Option<SourceEnvelope>,
+    storage_configuration: &StorageConfiguration,
+) -> Result<(), PlanError> {
+    let SourceFormatOptions::Kafka { topic } = options else {
+        sql_bail!("Confluent Schema Registry is only supported with Kafka sources")
+    };
+
+    let CsrConnectionJson {
+        connection: CsrConnection { connection, .. },
+        seed,
+    } = csr_connection;
+    if seed.is_none() {
+        let scx = StatementContext::new(None, &*catalog);
+        let csr_connection = match scx.get_item_by_resolved_name(connection)?.connection()? {
+            Connection::Csr(connection) => connection.clone().into_inline_connection(catalog),
+            _ => sql_bail!("{} is not a schema registry connection", connection),
+        };
+        let ccsr_client = csr_connection
+            .connect(storage_configuration, InTask::No)
+            .await
+            .map_err(|e| CsrPurificationError::ClientError(Arc::new(e)))?;
+
+        // The registry doesn't report a schema's type alongside it, so a
+        // schema of the wrong type is only caught when planning derives the
+        // source's columns from it.
+        let value_schema = get_schema_with_strategy(
+            &ccsr_client,
+            ReaderSchemaSelectionStrategy::Latest,
+            &format!("{}-value", topic),
+        )
+        .await?
+        .ok_or_else(|| anyhow!("No value schema found"))?;
+        let key_schema = get_schema_with_strategy(
+            &ccsr_client,
+            ReaderSchemaSelectionStrategy::Latest,
+            &format!("{}-key", topic),
+        )
+        .await?;
+        if matches!(envelope, Some(SourceEnvelope::Debezium)) && key_schema.is_none() {
+            sql_bail!("Key schema is required for ENVELOPE DEBEZIUM");
+        }
+
+        *seed = Some(CsrSeedJson {
+            key_schema,
+            value_schema,
+        })
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct Schema {
     pub key_schema: Option<String>,
@@ -2262,8 +2664,14 @@ async fn get_remote_csr_schema(
 }
 
 /// Collect protobuf message descriptor from CSR and compile the descriptor.
+/// Compiles the Protobuf schema registered under `subject_name`, along with
+/// all of its transitive references, into a file descriptor set.
+///
+/// The message to decode is `message_name`, if specified, and otherwise the
+/// first message declared in the subject's schema.
 async fn compile_proto(
     subject_name: &String,
+    message_name: Option<&str>,
     ccsr_client: &Client,
 ) -> Result<CsrSeedProtobufSchema, PlanError> {
     let (primary_subject, dependency_subjects) = ccsr_client
@@ -2288,18 +2696,22 @@ async fn compile_proto(
         .build_file_descriptor_set(&[Path::new(&primary_subject.name)])
         .map_err(|cause| PlanError::InvalidProtobufSchema { cause })?;
 
-    // Ensure there is exactly one message in the file.
-    let primary_fd = fds.file(0);
-    let message_name = match primary_fd.message_type_size() {
-        1 => String::from_utf8_lossy(primary_fd.message_type(0).name()).into_owned(),
-        0 => bail_unsupported!(29603, "Protobuf schemas with no messages"),
-        _ => bail_unsupported!(29603, "Protobuf schemas with multiple messages"),
-    };
-
     // Encode the file descriptor set into a SQL byte string.
     let bytes = &fds
         .serialize()
         .map_err(|cause| PlanError::InvalidProtobufSchema { cause })?;
+
+    // Determine which message to decode. Its existence is validated when the
+    // descriptors are decoded during planning.
+    let message_name = match message_name {
+        Some(message_name) => message_name.to_owned(),
+        None => match mz_interchange::protobuf::first_message_name(bytes, &primary_subject.name)
+            .map_err(|e| sql_err!("{}", e.display_with_causes()))?
+        {
+            Some(message_name) => message_name,
+            None => bail_unsupported!(29603, "Protobuf schemas with no messages"),
+        },
+    };
     let mut schema = String::new();
     strconv::format_bytes(&mut schema, bytes);
 
@@ -116,7 +116,8 @@ impl PgSourcePurificationError {
                 cols: _,
             } => Some(
                 "Use the TEXT COLUMNS option naming the listed columns, and Materialize can ingest their values \
-                as text."
+                as text. Columns of enum types can instead be ingested by creating an enum type with the same \
+                name and labels using CREATE TYPE ... AS ENUM."
                     .into(),
             ),
             Self::InsufficientReplicationSlotsAvailable { .. } => Some(
@@ -354,3 +355,96 @@ impl MySqlSourcePurificationError {
         }
     }
 }
+
+/// Logical errors detectable during purification for a SQL SERVER SOURCE.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SqlServerSourcePurificationError {
+    #[error("CREATE SOURCE specifies DETAILS option")]
+    UserSpecifiedDetails,
+    #[error("CREATE SOURCE specifies CAPTURE INSTANCE option")]
+    UserSpecifiedCaptureInstance,
+    #[error("{0} option is unnecessary when no tables are added")]
+    UnnecessaryOptionsWithoutReferences(String),
+    #[error("{0} is not a SQL SERVER CONNECTION")]
+    NotSqlServerConnection(FullItemName),
+    #[error("Change Data Capture is not enabled for database {0}")]
+    CdcNotEnabled(String),
+    #[error("snapshot isolation is not enabled for database {0}")]
+    SnapshotIsolationNotEnabled(String),
+    #[error("referenced tables use unsupported types")]
+    UnrecognizedTypes { cols: Vec<(String, String, String)> },
+    #[error("{option_name} refers to table not currently being added")]
+    DanglingColumns {
+        option_name: String,
+        items: Vec<UnresolvedItemName>,
+    },
+    #[error("No tables found for provided reference")]
+    EmptyDatabase,
+    #[error("missing TABLES specification")]
+    RequiresExternalReferences,
+}
+
+impl SqlServerSourcePurificationError {
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Self::DanglingColumns {
+                option_name: _,
+                items,
+            } => Some(format!(
+                "the following columns are referenced but not added: {}",
+                itertools::join(items, ", ")
+            )),
+            Self::UnrecognizedTypes { cols } => Some(format!(
+                "the following columns contain unsupported types:\n{}",
+                itertools::join(
+                    cols.into_iter().map(|(table, column, data_type)| format!(
+                        "'{}' for {}.{}",
+                        data_type, column, table
+                    )),
+                    "\n"
+                )
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::UserSpecifiedDetails => Some(
+                "If trying to use the output of SHOW CREATE SOURCE, remove the DETAILS option."
+                    .into(),
+            ),
+            Self::UserSpecifiedCaptureInstance => Some(
+                "Remove the CAPTURE INSTANCE option; the capture instance of each table is \
+                determined automatically."
+                    .into(),
+            ),
+            Self::CdcNotEnabled(_) => Some(
+                "Enable Change Data Capture for the database using sys.sp_cdc_enable_db.".into(),
+            ),
+            Self::SnapshotIsolationNotEnabled(_) => Some(
+                "Tables are snapshot using snapshot isolation, enable it for the database using \
+                ALTER DATABASE ... SET ALLOW_SNAPSHOT_ISOLATION ON."
+                    .into(),
+            ),
+            Self::RequiresExternalReferences => {
+                Some("provide a FOR TABLES (..), FOR SCHEMAS (..), or FOR ALL TABLES clause".into())
+            }
+            Self::UnrecognizedTypes { cols: _ } => Some(
+                "Check the docs -- some types can be supported using the TEXT COLUMNS option to \
+                ingest their values as text, or ignored using EXCLUDE COLUMNS."
+                    .into(),
+            ),
+            Self::EmptyDatabase => Some(
+                "No tables were found to replicate. Only tables that have Change Data Capture \
+                enabled, using sys.sp_cdc_enable_table, can be replicated."
+                    .into(),
+            ),
+            Self::UnnecessaryOptionsWithoutReferences(option) => Some(format!(
+                "Remove the {} option, as no tables are being added.",
+                option
+            )),
+            _ => None,
+        }
+    }
+}
@@ -13,13 +13,14 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use mz_expr::MirScalarExpr;
 use mz_postgres_util::desc::PostgresTableDesc;
-use mz_postgres_util::Config;
+use mz_postgres_util::{Config, PostgresEnumDesc};
 use mz_proto::RustType;
 use mz_repr::{ColumnType, RelationType, ScalarType};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
     ColumnDef, CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement,
-    ExternalReferences, Ident, TableConstraint, UnresolvedItemName, Value, WithOptionValue,
+    ExternalReferences, Ident, RawDataType, RawItemName, TableConstraint, UnresolvedItemName,
+    Value, WithOptionValue,
 };
 use mz_storage_types::sources::postgres::CastType;
 use mz_storage_types::sources::SourceExportStatementDetails;
@@ -27,7 +28,8 @@ use prost::Message;
 use tokio_postgres::types::Oid;
 use tokio_postgres::Client;
 
-use crate::names::{Aug, ResolvedItemName};
+use crate::catalog::{CatalogType, CatalogTypeDetails};
+use crate::names::{self, Aug, ResolvedDataType, ResolvedItemName};
 use crate::normalize;
 use crate::plan::hir::ColumnRef;
 use crate::plan::typeconv::{plan_cast, CastContext};
@@ -219,11 +221,13 @@ pub(super) fn generate_source_export_statement_values(
     purified_export: PurifiedSourceExport,
     unsupported_cols: &mut Vec<(String, mz_repr::adt::system::Oid)>,
 ) -> Result<PostgresExportStatementValues, PlanError> {
-    let (text_columns, table) = match purified_export.details {
+    let (text_columns, table, enum_types, postgis_types) = match purified_export.details {
         PurifiedExportDetails::Postgres {
             text_columns,
             table,
-        } => (text_columns, table),
+            enum_types,
+            postgis_types,
+        } => (text_columns, table, enum_types, postgis_types),
         _ => unreachable!("purified export details must be postgres"),
     };
 
@@ -236,23 +240,33 @@ pub(super) fn generate_source_export_statement_values(
     for c in table.columns.iter() {
         let name = Ident::new(c.name.clone())?;
 
-        let ty = match text_column_set {
-            Some(ref names) if names.contains(c.name.as_str()) => mz_pgrepr::Type::Text,
+        let data_type = match text_column_set {
+            Some(ref names) if names.contains(c.name.as_str()) => {
+                scx.resolve_type(mz_pgrepr::Type::Text)?
+            }
             _ => match mz_pgrepr::Type::from_oid_and_typmod(c.type_oid, c.type_mod) {
-                Ok(t) => t,
-                Err(_) => {
-                    let mut full_name = purified_export.external_reference.0.clone();
-                    full_name.push(name);
-                    unsupported_cols.push((
-                        UnresolvedItemName(full_name).to_ast_string(),
-                        mz_repr::adt::system::Oid(c.type_oid),
-                    ));
-                    continue;
+                Ok(t) => scx.resolve_type(t)?,
+                Err(_) if postgis_types.contains_key(&c.type_oid) => {
+                    scx.resolve_type(postgis_types[&c.type_oid].clone())?
                 }
+                Err(_) => match enum_types
+                    .get(&c.type_oid)
+                    .and_then(|enum_type| resolve_enum_type(scx, enum_type))
+                {
+                    Some(data_type) => data_type,
+                    None => {
+                        let mut full_name = purified_export.external_reference.0.clone();
+                        full_name.push(name);
+                        unsupported_cols.push((
+                            UnresolvedItemName(full_name).to_ast_string(),
+                            mz_repr::adt::system::Oid(c.type_oid),
+                        ));
+                        continue;
+                    }
+                },
             },
         };
 
-        let data_type = scx.resolve_type(ty)?;
         let mut options = vec![];
 
         if !c.nullable {
@@ -320,6 +334,41 @@ pub(super) fn generate_source_export_statement_values(
     })
 }
 
+/// Returns the Materialize type that values of the upstream PostGIS type named
+/// `type_name` can be ingested as, if any.
+fn postgis_type(type_name: &str) -> Option<mz_pgrepr::Type> {
+    match type_name {
+        "geometry" => Some(mz_pgrepr::Type::Geometry),
+        "geography" => Some(mz_pgrepr::Type::Geography),
+        _ => None,
+    }
+}
+
+/// Resolves the Materialize type to use for columns of the upstream enum type
+/// `enum_type`, which must be an enum type of the same name with the same
+/// labels in the same order.
+fn resolve_enum_type(
+    scx: &StatementContext,
+    enum_type: &PostgresEnumDesc,
+) -> Option<ResolvedDataType> {
+    let name = Ident::new(enum_type.name.clone()).ok()?;
+    let data_type = RawDataType::Other {
+        name: RawItemName::Name(UnresolvedItemName(vec![name])),
+        typ_mod: vec![],
+    };
+    let (data_type, _) = names::resolve(scx.catalog, data_type).ok()?;
+    let ResolvedDataType::Named { id, .. } = &data_type else {
+        return None;
+    };
+    match scx.catalog.get_item(id).type_details() {
+        Some(CatalogTypeDetails {
+            typ: CatalogType::Enum { labels },
+            ..
+        }) if *labels == enum_type.labels => Some(data_type),
+        _ => None,
+    }
+}
+
 pub(super) struct PurifiedSourceExports {
     pub(super) source_exports: BTreeMap<UnresolvedItemName, PurifiedSourceExport>,
     // NOTE(roshan): The text columns are already part of their
@@ -389,6 +438,29 @@ pub(super) async fn purify_source_exports(
 
     let mut text_column_map = generate_text_columns(retrieved_references, &mut text_columns)?;
 
+    // Look up any upstream types we don't natively support, so that columns of
+    // enum types can be mapped to Materialize enum types of the same name and
+    // columns of PostGIS types can be ingested as geospatial values.
+    let unknown_type_oids: BTreeSet<_> = requested_exports
+        .iter()
+        .flat_map(|r| &r.meta.postgres_desc().expect("is postgres").columns)
+        .map(|c| c.type_oid)
+        .filter(|oid| mz_pgrepr::Type::from_oid(*oid).is_err())
+        .collect();
+    let (enum_types, postgis_types) = if unknown_type_oids.is_empty() {
+        (BTreeMap::new(), BTreeMap::new())
+    } else {
+        let unknown_type_oids: Vec<_> = unknown_type_oids.into_iter().collect();
+        let enum_types = mz_postgres_util::get_enum_types(client, &unknown_type_oids).await?;
+        let postgis_types: BTreeMap<_, _> =
+            mz_postgres_util::get_type_names(client, &unknown_type_oids)
+                .await?
+                .into_iter()
+                .filter_map(|(oid, name)| Some((oid, postgis_type(&name)?)))
+                .collect();
+        (enum_types, postgis_types)
+    };
+
     // Normalize options to contain full qualified values.
     text_columns.sort();
     text_columns.dedup();
@@ -411,6 +483,22 @@ pub(super) async fn purify_source_exports(
                                 .map(|s| Ident::new(s).expect("validated above"))
                                 .collect()
                         }),
+                        enum_types: desc
+                            .columns
+                            .iter()
+                            .filter_map(|c| {
+                                let enum_type = enum_types.get(&c.type_oid)?;
+                                Some((c.type_oid, enum_type.clone()))
+                            })
+                            .collect(),
+                        postgis_types: desc
+                            .columns
+                            .iter()
+                            .filter_map(|c| {
+                                let postgis_type = postgis_types.get(&c.type_oid)?;
+                                Some((c.type_oid, postgis_type.clone()))
+                            })
+                            .collect(),
                         table: desc.clone(),
                     },
                 },
@@ -456,10 +544,30 @@ pub(super) async fn purify_source_exports(
     })
 }
 
+/// Returns the type of the column named `name` among `columns`, if it is an
+/// enum or geospatial type to which an upstream type without a native
+/// counterpart was mapped.
+fn column_mapped_type(
+    scx: &StatementContext,
+    columns: &[ColumnDef<Aug>],
+    name: &str,
+) -> Result<Option<ScalarType>, PlanError> {
+    let Some(column) = columns.iter().find(|c| c.name.as_str() == name) else {
+        return Ok(None);
+    };
+    match crate::plan::query::scalar_type_from_sql(scx, &column.data_type)? {
+        ty @ (ScalarType::Enum { .. } | ScalarType::Geometry | ScalarType::Geography) => {
+            Ok(Some(ty))
+        }
+        _ => Ok(None),
+    }
+}
+
 pub(crate) fn generate_column_casts(
     scx: &StatementContext,
     table: &PostgresTableDesc,
     text_columns: &Vec<Ident>,
+    columns: &[ColumnDef<Aug>],
 ) -> Result<Vec<(CastType, MirScalarExpr)>, PlanError> {
     // Generate the cast expressions required to convert the text encoded columns into
     // the appropriate target types, creating a Vec<MirScalarExpr>
@@ -498,47 +606,58 @@ pub(crate) fn generate_column_casts(
     // column and casts it to the appropriate target type
     let mut table_cast = vec![];
     for (i, column) in table.columns.iter().enumerate() {
-        let (cast_type, ty) = if text_columns.contains(column.name.as_str()) {
+        let (cast_type, scalar_type) = if text_columns.contains(column.name.as_str()) {
             // Treat the column as text if it was referenced in
             // `TEXT COLUMNS`. This is the only place we need to
             // perform this logic; even if the type is unsupported,
             // we'll be able to ingest its values as text in
             // storage.
-            (CastType::Text, mz_pgrepr::Type::Text)
+            (CastType::Text, ScalarType::String)
         } else {
             match mz_pgrepr::Type::from_oid_and_typmod(column.type_oid, column.type_mod) {
-                Ok(t) => (CastType::Natural, t),
-                // If this reference survived purification, we
-                // do not expect it to be from a table that the
-                // user will consume., i.e. expect this table to
-                // be filtered out of table casts.
-                Err(_) => {
-                    table_cast.push((
-                        CastType::Natural,
-                        HirScalarExpr::CallVariadic {
-                            func: mz_expr::VariadicFunc::ErrorIfNull,
-                            exprs: vec![
-                                HirScalarExpr::literal_null(ScalarType::String),
-                                HirScalarExpr::literal(
-                                    mz_repr::Datum::from(
-                                        format!("Unsupported type with OID {}", column.type_oid)
+                Ok(t) => {
+                    let data_type = scx.resolve_type(t)?;
+                    let scalar_type = crate::plan::query::scalar_type_from_sql(scx, &data_type)?;
+                    (CastType::Natural, scalar_type)
+                }
+                Err(_) => match column_mapped_type(scx, columns, &column.name)? {
+                    // Columns of upstream enum and PostGIS types were mapped to
+                    // Materialize enum and geospatial types during
+                    // purification, which we can cast the text-encoded values
+                    // to.
+                    Some(scalar_type) => (CastType::Natural, scalar_type),
+                    // If this reference survived purification, we
+                    // do not expect it to be from a table that the
+                    // user will consume., i.e. expect this table to
+                    // be filtered out of table casts.
+                    None => {
+                        table_cast.push((
+                            CastType::Natural,
+                            HirScalarExpr::CallVariadic {
+                                func: mz_expr::VariadicFunc::ErrorIfNull,
+                                exprs: vec![
+                                    HirScalarExpr::literal_null(ScalarType::String),
+                                    HirScalarExpr::literal(
+                                        mz_repr::Datum::from(
+                                            format!(
+                                                "Unsupported type with OID {}",
+                                                column.type_oid
+                                            )
                                             .as_str(),
+                                        ),
+                                        ScalarType::String,
                                     ),
-                                    ScalarType::String,
-                                ),
-                            ],
-                        }
-                        .lower_uncorrelated()
-                        .expect("no correlation"),
-                    ));
-                    continue;
-                }
+                                ],
+                            }
+                            .lower_uncorrelated()
+                            .expect("no correlation"),
+                        ));
+                        continue;
+                    }
+                },
             }
         };
 
-        let data_type = scx.resolve_type(ty)?;
-        let scalar_type = crate::plan::query::scalar_type_from_sql(scx, &data_type)?;
-
         let col_expr = HirScalarExpr::Column(ColumnRef {
             level: 0,
             column: i,
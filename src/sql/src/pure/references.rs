@@ -35,6 +35,10 @@ pub(super) enum SourceReferenceClient<'a> {
         /// retrieved references.
         include_system_schemas: bool,
     },
+    SqlServer {
+        client: &'a mut mz_sql_server_util::Client,
+        database: &'a str,
+    },
     Kafka {
         topic: &'a str,
     },
@@ -51,6 +55,10 @@ pub(super) enum ReferenceMetadata {
         database: String,
     },
     MySql(mz_mysql_util::MySqlTableSchema),
+    SqlServer {
+        table: mz_sql_server_util::desc::SqlServerTableRaw,
+        database: String,
+    },
     Kafka(String),
     LoadGenerator {
         name: String,
@@ -65,6 +73,7 @@ impl ReferenceMetadata {
         match self {
             ReferenceMetadata::Postgres { table, .. } => Some(&table.namespace),
             ReferenceMetadata::MySql(table) => Some(&table.schema_name),
+            ReferenceMetadata::SqlServer { table, .. } => Some(&table.schema_name),
             ReferenceMetadata::Kafka(_) => None,
             ReferenceMetadata::LoadGenerator { namespace, .. } => Some(namespace),
         }
@@ -74,6 +83,7 @@ impl ReferenceMetadata {
         match self {
             ReferenceMetadata::Postgres { table, .. } => &table.name,
             ReferenceMetadata::MySql(table) => &table.name,
+            ReferenceMetadata::SqlServer { table, .. } => &table.name,
             ReferenceMetadata::Kafka(topic) => topic,
             ReferenceMetadata::LoadGenerator { name, .. } => name,
         }
@@ -93,6 +103,13 @@ impl ReferenceMetadata {
         }
     }
 
+    pub(super) fn sql_server_table(&self) -> Option<&mz_sql_server_util::desc::SqlServerTableRaw> {
+        match self {
+            ReferenceMetadata::SqlServer { table, .. } => Some(table),
+            _ => None,
+        }
+    }
+
     pub(super) fn load_generator_desc(&self) -> Option<&Option<RelationDesc>> {
         match self {
             ReferenceMetadata::LoadGenerator { desc, .. } => Some(desc),
@@ -123,6 +140,13 @@ impl ReferenceMetadata {
                 Ident::new(&table.schema_name)?,
                 Ident::new(&table.name)?,
             ])),
+            ReferenceMetadata::SqlServer { table, database } => {
+                Ok(UnresolvedItemName::qualified(&[
+                    Ident::new(database)?,
+                    Ident::new(&table.schema_name)?,
+                    Ident::new(&table.name)?,
+                ]))
+            }
             ReferenceMetadata::Kafka(topic) => {
                 Ok(UnresolvedItemName::qualified(&[Ident::new(topic)?]))
             }
@@ -151,11 +175,11 @@ pub(super) struct RetrievedSourceReferences {
     resolver: SourceReferenceResolver,
 }
 
-/// The name of the fake database that we use for non-Postgres sources
-/// to fit the model of a 3-layer catalog used to resolve references
-/// in the `SourceReferenceResolver`. This isn't actually stored in
-/// the catalog since the `ReferenceMetadata::external_reference`
-/// method only includes the database name for Postgres sources.
+/// The name of the fake database that we use for sources other than Postgres
+/// and SQL Server to fit the model of a 3-layer catalog used to resolve
+/// references in the `SourceReferenceResolver`. This isn't actually stored in
+/// the catalog since the `ReferenceMetadata::external_reference` method only
+/// includes the database name for Postgres and SQL Server sources.
 pub(crate) static DATABASE_FAKE_NAME: &str = "database";
 
 impl<'a> SourceReferenceClient<'a> {
@@ -203,6 +227,26 @@ impl<'a> SourceReferenceClient<'a> {
 
                 tables.into_iter().map(ReferenceMetadata::MySql).collect()
             }
+            SourceReferenceClient::SqlServer {
+                ref mut client,
+                database,
+            } => {
+                let tables = mz_sql_server_util::inspect::get_tables(client).await?;
+
+                // A table can be tracked by up to two capture instances, e.g. while its schema is
+                // being migrated upstream. We only expose one reference per table.
+                //
+                // TODO(sql_server2): Allow users to pick the capture instance.
+                let mut seen = BTreeSet::new();
+                tables
+                    .into_iter()
+                    .filter(|table| seen.insert((table.schema_name.clone(), table.name.clone())))
+                    .map(|table| ReferenceMetadata::SqlServer {
+                        table,
+                        database: database.to_string(),
+                    })
+                    .collect()
+            }
             SourceReferenceClient::Kafka { topic } => {
                 vec![ReferenceMetadata::Kafka(topic.to_string())]
             }
@@ -244,7 +288,8 @@ impl<'a> SourceReferenceClient<'a> {
             })
             .collect();
         let resolver = match self {
-            SourceReferenceClient::Postgres { database, .. } => {
+            SourceReferenceClient::Postgres { database, .. }
+            | SourceReferenceClient::SqlServer { database, .. } => {
                 SourceReferenceResolver::new(database, &reference_names)
             }
             _ => SourceReferenceResolver::new(DATABASE_FAKE_NAME, &reference_names),
@@ -282,6 +327,11 @@ impl RetrievedSourceReferences {
                             .map(|column| column.name())
                             .collect(),
                     },
+                    ReferenceMetadata::SqlServer { table, .. } => SourceReference {
+                        name: table.name,
+                        namespace: Some(table.schema_name),
+                        columns: table.columns.into_iter().map(|c| c.name).collect(),
+                    },
                     ReferenceMetadata::Kafka(topic) => SourceReference {
                         name: topic,
                         namespace: None,
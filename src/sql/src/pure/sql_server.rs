@@ -0,0 +1,456 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL Server utilities for SQL purification.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_proto::RustType;
+use mz_sql_parser::ast::display::AstDisplay;
+use mz_sql_parser::ast::{
+    ColumnDef, CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement,
+    ExternalReferences, Ident, SqlServerConfigOptionName, TableConstraint, UnresolvedItemName,
+    Value, WithOptionValue,
+};
+use mz_sql_server_util::{SqlServerError, SqlServerTableDesc};
+use mz_storage_types::sources::{SourceExportStatementDetails, SourceReferenceResolver};
+use prost::Message;
+
+use crate::names::{Aug, ResolvedItemName};
+use crate::plan::{PlanError, StatementContext};
+use crate::pure::SqlServerSourcePurificationError;
+
+use super::references::RetrievedSourceReferences;
+use super::{
+    PurifiedExportDetails, PurifiedSourceExport, RequestedSourceExport, SourceReferencePolicy,
+};
+
+/// Ensures that Change Data Capture is enabled for the database `client` is
+/// connected to.
+pub(super) async fn ensure_cdc_enabled(
+    client: &mut mz_sql_server_util::Client,
+    database: &str,
+) -> Result<(), PlanError> {
+    match mz_sql_server_util::inspect::ensure_database_cdc_enabled(client).await {
+        Ok(()) => Ok(()),
+        Err(SqlServerError::InvalidSetup(_)) => {
+            Err(SqlServerSourcePurificationError::CdcNotEnabled(database.to_string()).into())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Ensures that snapshot isolation is enabled for the database `client` is
+/// connected to.
+pub(super) async fn ensure_snapshot_isolation_enabled(
+    client: &mut mz_sql_server_util::Client,
+    database: &str,
+) -> Result<(), PlanError> {
+    match mz_sql_server_util::inspect::ensure_snapshot_isolation_enabled(client).await {
+        Ok(()) => Ok(()),
+        Err(SqlServerError::InvalidSetup(_)) => Err(
+            SqlServerSourcePurificationError::SnapshotIsolationNotEnabled(database.to_string())
+                .into(),
+        ),
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub fn generate_create_subsource_statements(
+    scx: &StatementContext,
+    source_name: ResolvedItemName,
+    requested_subsources: BTreeMap<UnresolvedItemName, PurifiedSourceExport>,
+) -> Result<Vec<CreateSubsourceStatement<Aug>>, PlanError> {
+    let mut subsources = Vec::with_capacity(requested_subsources.len());
+
+    for (subsource_name, purified_export) in requested_subsources {
+        let SqlServerExportStatementValues {
+            columns,
+            constraints,
+            text_columns,
+            exclude_columns,
+            details,
+            external_reference,
+        } = generate_source_export_statement_values(scx, purified_export)?;
+
+        let mut with_options = vec![
+            CreateSubsourceOption {
+                name: CreateSubsourceOptionName::ExternalReference,
+                value: Some(WithOptionValue::UnresolvedItemName(external_reference)),
+            },
+            CreateSubsourceOption {
+                name: CreateSubsourceOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            },
+        ];
+
+        if let Some(text_columns) = text_columns {
+            with_options.push(CreateSubsourceOption {
+                name: CreateSubsourceOptionName::TextColumns,
+                value: Some(WithOptionValue::Sequence(text_columns)),
+            });
+        }
+
+        if let Some(exclude_columns) = exclude_columns {
+            with_options.push(CreateSubsourceOption {
+                name: CreateSubsourceOptionName::ExcludeColumns,
+                value: Some(WithOptionValue::Sequence(exclude_columns)),
+            });
+        }
+
+        // Create the subsource statement
+        let subsource = CreateSubsourceStatement {
+            name: subsource_name,
+            columns,
+            of_source: Some(source_name.clone()),
+            constraints,
+            if_not_exists: false,
+            with_options,
+        };
+        subsources.push(subsource);
+    }
+
+    Ok(subsources)
+}
+
+pub(super) struct SqlServerExportStatementValues {
+    pub(super) columns: Vec<ColumnDef<Aug>>,
+    pub(super) constraints: Vec<TableConstraint<Aug>>,
+    pub(super) text_columns: Option<Vec<WithOptionValue<Aug>>>,
+    pub(super) exclude_columns: Option<Vec<WithOptionValue<Aug>>>,
+    pub(super) details: SourceExportStatementDetails,
+    pub(super) external_reference: UnresolvedItemName,
+}
+
+pub(super) fn generate_source_export_statement_values(
+    scx: &StatementContext,
+    purified_export: PurifiedSourceExport,
+) -> Result<SqlServerExportStatementValues, PlanError> {
+    let PurifiedExportDetails::SqlServer {
+        table,
+        capture_instance,
+        text_columns,
+        exclude_columns,
+    } = purified_export.details
+    else {
+        unreachable!("purified export details must be sql server")
+    };
+
+    // Figure out the schema of the subsource
+    let mut columns = vec![];
+    for c in table.columns.iter() {
+        match c.column_type {
+            // This column is intentionally ignored, so we don't generate a column for it in
+            // the subsource.
+            None => {}
+            Some(ref column_type) => {
+                let name = Ident::new(&c.name)?;
+
+                let ty = mz_pgrepr::Type::from(&column_type.scalar_type);
+                let data_type = scx.resolve_type(ty)?;
+                let mut col_options = vec![];
+
+                if !column_type.nullable {
+                    col_options.push(mz_sql_parser::ast::ColumnOptionDef {
+                        name: None,
+                        option: mz_sql_parser::ast::ColumnOption::NotNull,
+                    });
+                }
+                columns.push(ColumnDef {
+                    name,
+                    data_type,
+                    collation: None,
+                    options: col_options,
+                });
+            }
+        }
+    }
+
+    // The primary key is only meaningful if none of its columns are excluded.
+    let mut constraints = vec![];
+    let key_columns: Vec<_> = table.columns.iter().filter(|c| c.primary_key).collect();
+    if !key_columns.is_empty() && key_columns.iter().all(|c| c.column_type.is_some()) {
+        let columns: Result<Vec<Ident>, _> =
+            key_columns.iter().map(|c| Ident::new(&c.name)).collect();
+        constraints.push(mz_sql_parser::ast::TableConstraint::Unique {
+            name: None,
+            columns: columns?,
+            is_primary: true,
+            nulls_not_distinct: false,
+        });
+    }
+
+    let details = SourceExportStatementDetails::SqlServer {
+        capture_instance,
+        table,
+    };
+
+    let text_columns = text_columns.map(|mut columns| {
+        columns.sort();
+        columns
+            .into_iter()
+            .map(WithOptionValue::Ident::<Aug>)
+            .collect()
+    });
+
+    let exclude_columns = exclude_columns.map(|mut columns| {
+        columns.sort();
+        columns
+            .into_iter()
+            .map(WithOptionValue::Ident::<Aug>)
+            .collect()
+    });
+    Ok(SqlServerExportStatementValues {
+        columns,
+        constraints,
+        text_columns,
+        exclude_columns,
+        details,
+        external_reference: purified_export.external_reference,
+    })
+}
+
+/// Map a list of column references to a map of `(schema, table)` references to column names.
+fn map_column_refs<'a>(
+    cols: &'a [UnresolvedItemName],
+    option_type: SqlServerConfigOptionName,
+) -> Result<BTreeMap<(&'a str, &'a str), BTreeSet<&'a str>>, PlanError> {
+    let mut table_to_cols = BTreeMap::new();
+    for name in cols.iter() {
+        // We only support fully qualified references for now (e.g. `schema_name.table_name.column_name`)
+        if name.0.len() == 3 {
+            let key = (name.0[0].as_str(), name.0[1].as_str());
+            let new = table_to_cols
+                .entry(key)
+                .or_insert_with(BTreeSet::new)
+                .insert(name.0[2].as_str());
+            if !new {
+                return Err(PlanError::InvalidOptionValue {
+                    option_name: option_type.to_ast_string(),
+                    err: Box::new(PlanError::UnexpectedDuplicateReference { name: name.clone() }),
+                });
+            }
+        } else {
+            return Err(PlanError::InvalidOptionValue {
+                option_name: option_type.to_ast_string(),
+                err: Box::new(PlanError::UnderqualifiedColumnName(name.to_string())),
+            });
+        }
+    }
+    Ok(table_to_cols)
+}
+
+/// Normalize column references to a sorted, deduplicated options list of column names.
+fn normalize_column_refs(
+    cols: Vec<UnresolvedItemName>,
+    reference_resolver: &SourceReferenceResolver,
+    tables: &[RequestedSourceExport<SqlServerTableDesc>],
+    option_name: &str,
+) -> Result<Vec<WithOptionValue<Aug>>, SqlServerSourcePurificationError> {
+    let (seq, unknown): (Vec<_>, Vec<_>) = cols.into_iter().partition(|name| {
+        let (column_name, qual) = name.0.split_last().expect("non-empty");
+        match reference_resolver.resolve_idx(qual) {
+            Ok(idx) => tables[idx]
+                .meta
+                .columns
+                .iter()
+                .any(|n| &n.name == column_name.as_str()),
+            Err(_) => false,
+        }
+    });
+
+    if !unknown.is_empty() {
+        return Err(SqlServerSourcePurificationError::DanglingColumns {
+            option_name: option_name.to_string(),
+            items: unknown,
+        });
+    }
+
+    let mut seq: Vec<_> = seq
+        .into_iter()
+        .map(WithOptionValue::UnresolvedItemName)
+        .collect();
+    seq.sort();
+    seq.dedup();
+    Ok(seq)
+}
+
+pub(super) struct PurifiedSourceExports {
+    /// map of source export names to the details of the export
+    pub(super) source_exports: BTreeMap<UnresolvedItemName, PurifiedSourceExport>,
+    // NOTE(roshan): The text columns and exclude columns are already part of their
+    // appropriate `source_exports` above, but these are returned to allow
+    // round-tripping a `CREATE SOURCE` statement while we still allow creating
+    // implicit subsources from `CREATE SOURCE`. Remove once
+    // fully deprecating that feature and forcing users to use explicit
+    // `CREATE TABLE .. FROM SOURCE` statements.
+    pub(super) normalized_text_columns: Vec<WithOptionValue<Aug>>,
+    pub(super) normalized_exclude_columns: Vec<WithOptionValue<Aug>>,
+}
+
+// Purify the requested external references, returning a set of purified
+// source exports corresponding to external tables, and and additional
+// fields necessary to generate relevant statements and update statement options
+pub(super) fn purify_source_exports(
+    database: &str,
+    retrieved_references: &RetrievedSourceReferences,
+    requested_references: &Option<ExternalReferences>,
+    text_columns: Vec<UnresolvedItemName>,
+    exclude_columns: Vec<UnresolvedItemName>,
+    unresolved_source_name: &UnresolvedItemName,
+    reference_policy: &SourceReferencePolicy,
+) -> Result<PurifiedSourceExports, PlanError> {
+    let requested_exports = match requested_references.as_ref() {
+        Some(requested) if matches!(reference_policy, SourceReferencePolicy::NotAllowed) => {
+            Err(PlanError::UseTablesForSources(requested.to_string()))?
+        }
+        Some(requested) => retrieved_references
+            .requested_source_exports(Some(requested), unresolved_source_name)?,
+        None => {
+            if matches!(reference_policy, SourceReferencePolicy::Required) {
+                Err(SqlServerSourcePurificationError::RequiresExternalReferences)?
+            }
+
+            // If no external reference is specified, it does not make sense to include
+            // or text or exclude columns.
+            if !text_columns.is_empty() {
+                Err(
+                    SqlServerSourcePurificationError::UnnecessaryOptionsWithoutReferences(
+                        "TEXT COLUMNS".to_string(),
+                    ),
+                )?
+            }
+            if !exclude_columns.is_empty() {
+                Err(
+                    SqlServerSourcePurificationError::UnnecessaryOptionsWithoutReferences(
+                        "EXCLUDE COLUMNS".to_string(),
+                    ),
+                )?
+            }
+
+            return Ok(PurifiedSourceExports {
+                source_exports: BTreeMap::new(),
+                normalized_text_columns: vec![],
+                normalized_exclude_columns: vec![],
+            });
+        }
+    };
+
+    if requested_exports.is_empty() {
+        sql_bail!(
+            "SQL Server source must ingest at least one table, but {} matched none",
+            requested_references.as_ref().unwrap().to_ast_string()
+        );
+    }
+
+    super::validate_source_export_names(&requested_exports)?;
+
+    let text_cols_map = map_column_refs(&text_columns, SqlServerConfigOptionName::TextColumns)?;
+    let exclude_cols_map =
+        map_column_refs(&exclude_columns, SqlServerConfigOptionName::ExcludeColumns)?;
+
+    // Convert the raw tables into a format that we can use to generate source exports
+    // using any applicable text_columns and exclude_columns.
+    let mut unsupported_cols = vec![];
+    let mut tables = vec![];
+    let mut capture_instances = vec![];
+    for requested_export in requested_exports {
+        let table = requested_export
+            .meta
+            .sql_server_table()
+            .expect("is sql server");
+        let table_ref = (table.schema_name.as_str(), table.name.as_str());
+        let text_cols = text_cols_map.get(&table_ref).cloned().unwrap_or_default();
+        let exclude_cols = exclude_cols_map
+            .get(&table_ref)
+            .cloned()
+            .unwrap_or_default();
+        match SqlServerTableDesc::new(table, &text_cols, &exclude_cols) {
+            Ok(desc) => {
+                capture_instances.push(table.capture_instance.clone());
+                tables.push(requested_export.change_meta(desc));
+            }
+            Err(SqlServerError::UnsupportedDataTypes { columns }) => {
+                unsupported_cols.extend(
+                    columns
+                        .into_iter()
+                        .map(|c| (c.qualified_table_name, c.column_name, c.column_type)),
+                );
+            }
+            Err(err) => Err(err)?,
+        }
+    }
+
+    if !unsupported_cols.is_empty() {
+        Err(SqlServerSourcePurificationError::UnrecognizedTypes {
+            cols: unsupported_cols,
+        })?;
+    }
+
+    if tables.is_empty() {
+        Err(SqlServerSourcePurificationError::EmptyDatabase)?;
+    }
+
+    let reference_resolver = SourceReferenceResolver::new(
+        database,
+        &tables.iter().map(|r| &r.meta).collect::<Vec<_>>(),
+    )?;
+
+    // Normalize column options and remove unused column references.
+    let normalized_text_columns = normalize_column_refs(
+        text_columns.clone(),
+        &reference_resolver,
+        &tables,
+        "TEXT COLUMNS",
+    )?;
+    let normalized_exclude_columns = normalize_column_refs(
+        exclude_columns.clone(),
+        &reference_resolver,
+        &tables,
+        "EXCLUDE COLUMNS",
+    )?;
+
+    let source_exports = tables
+        .into_iter()
+        .zip(capture_instances)
+        .map(|(r, capture_instance)| {
+            let table_ref = (r.meta.schema_name.as_str(), r.meta.name.as_str());
+            let text_columns = text_cols_map.get(&table_ref).map(|cols| {
+                cols.iter()
+                    .map(|c| Ident::new(*c).expect("validated above"))
+                    .collect()
+            });
+            let exclude_columns = exclude_cols_map.get(&table_ref).map(|cols| {
+                cols.iter()
+                    .map(|c| Ident::new(*c).expect("validated above"))
+                    .collect()
+            });
+            (
+                r.name,
+                PurifiedSourceExport {
+                    external_reference: r.external_reference,
+                    details: PurifiedExportDetails::SqlServer {
+                        table: r.meta,
+                        capture_instance,
+                        text_columns,
+                        exclude_columns,
+                    },
+                },
+            )
+        })
+        .collect();
+
+    Ok(PurifiedSourceExports {
+        source_exports,
+        normalized_text_columns,
+        normalized_exclude_columns,
+    })
+}
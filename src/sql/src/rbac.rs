@@ -563,6 +563,7 @@ fn generate_rbac_requirements(
             view: _,
             replace,
             drop_ids: _,
+            replace_in_place: _,
             if_not_exists: _,
             ambiguous_columns: _,
         }) => RbacRequirements {
@@ -582,6 +583,7 @@ fn generate_rbac_requirements(
             materialized_view,
             replace,
             drop_ids: _,
+            replace_in_place: _,
             if_not_exists: _,
             ambiguous_columns: _,
         }) => RbacRequirements {
@@ -650,6 +652,23 @@ fn generate_rbac_requirements(
                 ..Default::default()
             }
         }
+        Plan::CreateFunction(plan::CreateFunctionPlan {
+            name,
+            function: _,
+            replace,
+            drop_ids: _,
+        }) => RbacRequirements {
+            ownership: replace
+                .map(|id| vec![ObjectId::Item(id)])
+                .unwrap_or_default(),
+            privileges: vec![(
+                SystemObjectId::Object(name.qualifiers.clone().into()),
+                AclMode::CREATE,
+                role_id,
+            )],
+            item_usage: &CREATE_ITEM_USAGE,
+            ..Default::default()
+        },
         Plan::CreateType(plan::CreateTypePlan { name, typ: _ }) => RbacRequirements {
             privileges: vec![(
                 SystemObjectId::Object(name.qualifiers.clone().into()),
@@ -1234,11 +1253,15 @@ fn generate_rbac_requirements(
             assignments,
             kind,
             returning,
+            changes,
+            enforced_keys: _,
         }) => {
-            let acl_mode = match kind {
-                MutationKind::Insert => AclMode::INSERT,
-                MutationKind::Update => AclMode::UPDATE,
-                MutationKind::Delete => AclMode::DELETE,
+            let acl_mode = match (kind, changes) {
+                (_, Some(changes)) => changes.acl_mode,
+                (MutationKind::Insert | MutationKind::Upsert, None) => AclMode::INSERT,
+                (MutationKind::Update, None) => AclMode::UPDATE,
+                (MutationKind::Delete, None) => AclMode::DELETE,
+                (MutationKind::Merge, None) => AclMode::empty(),
             };
             let schema_id: ObjectId = catalog.get_item(id).name().qualifiers.clone().into();
             let mut privileges = vec![
@@ -1628,6 +1651,13 @@ fn generate_read_privileges_inner(
                 CatalogItemType::Type | CatalogItemType::Secret | CatalogItemType::Connection => {
                     privileges.push((SystemObjectId::Object(id.into()), AclMode::USAGE, role_id));
                 }
+                // User-defined functions are inlined into the calling query,
+                // so the caller needs privileges on everything they reference.
+                // Builtin functions are usable by everyone.
+                CatalogItemType::Func if id.is_user() => {
+                    privileges.push((SystemObjectId::Object(id.into()), AclMode::USAGE, role_id));
+                    views.push((item.references().items().copied(), role_id));
+                }
                 CatalogItemType::Sink | CatalogItemType::Index | CatalogItemType::Func => {}
             }
         }
@@ -1748,7 +1778,7 @@ pub const fn all_object_privileges(object_type: SystemObjectType) -> AclMode {
         SystemObjectType::Object(ObjectType::Connection) => AclMode::USAGE,
         SystemObjectType::Object(ObjectType::Database) => USAGE_CREATE_ACL_MODE,
         SystemObjectType::Object(ObjectType::Schema) => USAGE_CREATE_ACL_MODE,
-        SystemObjectType::Object(ObjectType::Func) => EMPTY_ACL_MODE,
+        SystemObjectType::Object(ObjectType::Func) => AclMode::USAGE,
         SystemObjectType::Object(ObjectType::ContinualTask) => AclMode::SELECT,
         SystemObjectType::System => ALL_SYSTEM_PRIVILEGES,
     }
@@ -1106,6 +1106,7 @@ impl SystemVars {
             &MAX_KAFKA_CONNECTIONS,
             &MAX_POSTGRES_CONNECTIONS,
             &MAX_MYSQL_CONNECTIONS,
+            &MAX_SQL_SERVER_CONNECTIONS,
             &MAX_AWS_PRIVATELINK_CONNECTIONS,
             &MAX_TABLES,
             &MAX_SOURCES,
@@ -1577,6 +1578,11 @@ impl SystemVars {
         *self.expect_value(&MAX_MYSQL_CONNECTIONS)
     }
 
+    /// Returns the value of the `max_sql_server_connections` configuration parameter.
+    pub fn max_sql_server_connections(&self) -> u32 {
+        *self.expect_value(&MAX_SQL_SERVER_CONNECTIONS)
+    }
+
     /// Returns the value of the `max_aws_privatelink_connections` configuration parameter.
     pub fn max_aws_privatelink_connections(&self) -> u32 {
         *self.expect_value(&MAX_AWS_PRIVATELINK_CONNECTIONS)
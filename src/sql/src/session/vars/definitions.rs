@@ -442,6 +442,13 @@ pub static MAX_MYSQL_CONNECTIONS: VarDefinition = VarDefinition::new(
     true,
 );
 
+pub static MAX_SQL_SERVER_CONNECTIONS: VarDefinition = VarDefinition::new(
+    "max_sql_server_connections",
+    value!(u32; 1000),
+    "The maximum number of SQL Server connections in the region, across all schemas (Materialize).",
+    true,
+);
+
 pub static MAX_AWS_PRIVATELINK_CONNECTIONS: VarDefinition = VarDefinition::new(
     "max_aws_privatelink_connections",
     value!(u32; 0),
@@ -1912,7 +1919,7 @@ feature_flags!(
     },
     {
         name: unsafe_enable_table_keys,
-        desc: "CREATE TABLE with a primary key or unique constraint",
+        desc: "CREATE TABLE with a unique constraint on nullable columns",
         default: false,
         enable_for_item_parsing: true,
     },
@@ -2061,6 +2068,12 @@ feature_flags!(
         default: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_incremental_window_functions,
+        desc: "incremental rendering of the window functions row_number, rank, dense_rank, lag, and lead",
+        default: false,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_worker_core_affinity,
         desc: "set core affinity for replica worker threads",
@@ -2199,6 +2212,24 @@ feature_flags!(
         default: false,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_iceberg_sink,
+        desc: "Creating an ICEBERG sink",
+        default: false,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_s3_sink,
+        desc: "Creating an S3 sink",
+        default: false,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_postgres_sink,
+        desc: "Creating a POSTGRES sink",
+        default: false,
+        enable_for_item_parsing: false,
+    },
 );
 
 impl From<&super::SystemVars> for OptimizerFeatures {
@@ -2208,6 +2239,7 @@ impl From<&super::SystemVars> for OptimizerFeatures {
             enable_eager_delta_joins: vars.enable_eager_delta_joins(),
             enable_new_outer_join_lowering: vars.enable_new_outer_join_lowering(),
             enable_reduce_mfp_fusion: vars.enable_reduce_mfp_fusion(),
+            enable_incremental_window_functions: vars.enable_incremental_window_functions(),
             enable_variadic_left_join_lowering: vars.enable_variadic_left_join_lowering(),
             enable_letrec_fixpoint_analysis: vars.enable_letrec_fixpoint_analysis(),
             enable_cardinality_estimates: vars.enable_cardinality_estimates(),
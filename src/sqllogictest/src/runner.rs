@@ -69,7 +69,10 @@ use mz_persist_client::rpc::{
     MetricsSameProcessPubSubSender, PersistGrpcPubSubServer, PubSubClientConnection, PubSubSender,
 };
 use mz_persist_client::PersistLocation;
-use mz_pgrepr::{oid, Interval, Jsonb, Numeric, UInt2, UInt4, UInt8, Value};
+use mz_pgrepr::{
+    oid, Geometry, Inet, Interval, JsonPath, Jsonb, MacAddr, MacAddr8, Numeric, TsQuery, TsVector,
+    UInt2, UInt4, UInt8, Value,
+};
 use mz_repr::adt::date::Date;
 use mz_repr::adt::mz_acl_item::{AclItem, MzAclItem};
 use mz_repr::adt::numeric;
@@ -463,8 +466,12 @@ impl<'a> FromSql<'a> for Slt {
             PgType::INT2 => Self(Value::Int2(types::int2_from_sql(raw)?)),
             PgType::INT4 => Self(Value::Int4(types::int4_from_sql(raw)?)),
             PgType::INT8 => Self(Value::Int8(types::int8_from_sql(raw)?)),
+            PgType::INET => Self(Value::Inet(Inet::from_sql(ty, raw)?)),
+            PgType::CIDR => Self(Value::Cidr(Inet::from_sql(ty, raw)?)),
             PgType::INTERVAL => Self(Value::Interval(Interval::from_sql(ty, raw)?)),
             PgType::JSONB => Self(Value::Jsonb(Jsonb::from_sql(ty, raw)?)),
+            PgType::MACADDR => Self(Value::MacAddr(MacAddr::from_sql(ty, raw)?)),
+            PgType::MACADDR8 => Self(Value::MacAddr8(MacAddr8::from_sql(ty, raw)?)),
             PgType::NAME => Self(Value::Name(types::text_from_sql(raw)?.to_string())),
             PgType::NUMERIC => Self(Value::Numeric(Numeric::from_sql(ty, raw)?)),
             PgType::OID => Self(Value::Oid(types::oid_from_sql(raw)?)),
@@ -482,6 +489,9 @@ impl<'a> FromSql<'a> for Slt {
                 DateTime::<Utc>::from_sql(ty, raw)?.try_into()?,
             )),
             PgType::UUID => Self(Value::Uuid(Uuid::from_sql(ty, raw)?)),
+            PgType::TS_VECTOR => Self(Value::TsVector(TsVector::from_sql(ty, raw)?)),
+            PgType::TSQUERY => Self(Value::TsQuery(TsQuery::from_sql(ty, raw)?)),
+            PgType::JSONPATH => Self(Value::JsonPath(JsonPath::from_sql(ty, raw)?)),
             PgType::RECORD => {
                 let num_fields = read_be_i32(&mut raw)?;
                 let mut tuple = vec![];
@@ -536,6 +546,22 @@ impl<'a> FromSql<'a> for Slt {
                         elements,
                     })
                 }
+                PgKind::Composite(fields) => {
+                    let num_fields = read_be_i32(&mut raw)?;
+                    if usize::try_from(num_fields) != Ok(fields.len()) {
+                        return Err("wrong number of fields".into());
+                    }
+                    let mut tuple = vec![];
+                    for field in fields {
+                        // The field types are already known from the type, which
+                        // unlike the OIDs in the value can describe custom types.
+                        let _oid = read_be_i32(&mut raw)?;
+                        let v = read_value::<Option<Slt>>(field.type_(), &mut raw)?;
+                        tuple.push(v.map(|v| v.0));
+                    }
+                    Self(Value::Record(tuple))
+                }
+                PgKind::Enum(_) => Self(Value::Enum(types::text_from_sql(raw)?.to_string())),
                 _ => match ty.oid() {
                     oid::TYPE_UINT2_OID => Self(Value::UInt2(UInt2::from_sql(ty, raw)?)),
                     oid::TYPE_UINT4_OID => Self(Value::UInt4(UInt4::from_sql(ty, raw)?)),
@@ -548,6 +574,8 @@ impl<'a> FromSql<'a> for Slt {
                     oid::TYPE_MZ_ACL_ITEM_OID => Self(Value::MzAclItem(MzAclItem::decode_binary(
                         types::bytea_from_sql(raw),
                     )?)),
+                    oid::TYPE_GEOMETRY_OID => Self(Value::Geometry(Geometry::from_sql(ty, raw)?)),
+                    oid::TYPE_GEOGRAPHY_OID => Self(Value::Geography(Geometry::from_sql(ty, raw)?)),
                     _ => unreachable!(),
                 },
             },
@@ -555,7 +583,7 @@ impl<'a> FromSql<'a> for Slt {
     }
     fn accepts(ty: &PgType) -> bool {
         match ty.kind() {
-            PgKind::Array(_) | PgKind::Composite(_) => return true,
+            PgKind::Array(_) | PgKind::Composite(_) | PgKind::Enum(_) => return true,
             _ => {}
         }
         match ty.oid() {
@@ -563,7 +591,9 @@ impl<'a> FromSql<'a> for Slt {
             | oid::TYPE_UINT4_OID
             | oid::TYPE_UINT8_OID
             | oid::TYPE_MZ_TIMESTAMP_OID
-            | oid::TYPE_MZ_ACL_ITEM_OID => return true,
+            | oid::TYPE_MZ_ACL_ITEM_OID
+            | oid::TYPE_GEOMETRY_OID
+            | oid::TYPE_GEOGRAPHY_OID => return true,
             _ => {}
         }
         matches!(
@@ -578,8 +608,12 @@ impl<'a> FromSql<'a> for Slt {
                 | PgType::INT2
                 | PgType::INT4
                 | PgType::INT8
+                | PgType::INET
+                | PgType::CIDR
                 | PgType::INTERVAL
                 | PgType::JSONB
+                | PgType::MACADDR
+                | PgType::MACADDR8
                 | PgType::NAME
                 | PgType::NUMERIC
                 | PgType::OID
@@ -594,6 +628,9 @@ impl<'a> FromSql<'a> for Slt {
                 | PgType::TIMESTAMP
                 | PgType::TIMESTAMPTZ
                 | PgType::UUID
+                | PgType::TS_VECTOR
+                | PgType::TSQUERY
+                | PgType::JSONPATH
                 | PgType::INT4_RANGE
                 | PgType::INT4_RANGE_ARRAY
                 | PgType::INT8_RANGE
@@ -1071,6 +1108,8 @@ impl<'a> RunnerInner<'a> {
             cloud_resource_controller: None,
             tls: None,
             frontegg: None,
+            oidc: None,
+            password_auth: false,
             cors_allowed_origin: AllowOrigin::list([]),
             unsafe_mode: true,
             all_features: false,
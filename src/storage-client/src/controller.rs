@@ -125,7 +125,13 @@ pub enum DataSource<T> {
     },
     /// This source's data does not need to be managed by the storage
     /// controller, e.g. it's a materialized view or the catalog collection.
-    Other,
+    Other {
+        /// This collection is a newer definition of a materialized view that
+        /// writes into the shard of the "primary" collection. Within the
+        /// `storage-controller` we track the primary as a dependency, and let
+        /// it drive compaction of the shard.
+        primary: Option<GlobalId>,
+    },
     /// This collection is the output collection of a sink.
     Sink { desc: ExportDescription<T> },
 }
@@ -673,7 +679,7 @@ impl<T> DataSource<T> {
     pub fn in_txns(&self) -> bool {
         match self {
             DataSource::Table { .. } => true,
-            DataSource::Other
+            DataSource::Other { .. }
             | DataSource::Ingestion(_)
             | DataSource::IngestionExport { .. }
             | DataSource::Introspection(_)
@@ -887,9 +887,12 @@ where
             | DataSource::Webhook
             | DataSource::Table { primary: None }
             | DataSource::Progress
-            | DataSource::Other => Vec::new(),
+            | DataSource::Other { primary: None } => Vec::new(),
             DataSource::Table {
                 primary: Some(primary),
+            }
+            | DataSource::Other {
+                primary: Some(primary),
             } => vec![*primary],
             DataSource::IngestionExport {
                 ingestion_id,
@@ -1301,11 +1304,12 @@ where
         let mut persist_compaction_commands = Vec::with_capacity(collections_net.len());
         for (key, (mut changes, frontier)) in collections_net {
             if !changes.is_empty() {
-                // If the table has a "primary" collection, let that collection drive compaction.
+                // If the collection has a "primary" collection, let that collection drive
+                // compaction.
                 let collection = collections.get(&key).expect("must still exist");
                 let should_emit_persist_compaction = !matches!(
                     collection.description.data_source,
-                    DataSource::Table { primary: Some(_) }
+                    DataSource::Table { primary: Some(_) } | DataSource::Other { primary: Some(_) }
                 );
 
                 if frontier.is_empty() {
@@ -1873,7 +1877,7 @@ where
                         | DataSource::Webhook
                         | DataSource::Ingestion(_)
                         | DataSource::Progress
-                        | DataSource::Other => {}
+                        | DataSource::Other { .. } => {}
                         DataSource::Sink { .. } => {}
                         DataSource::Table { .. } => {
                             let register_ts = register_ts.expect(
@@ -2083,7 +2087,7 @@ where
                     }
                     self_collections.insert(id, collection_state);
                 }
-                DataSource::Progress | DataSource::Other => {
+                DataSource::Progress | DataSource::Other { .. } => {
                     self_collections.insert(id, collection_state);
                 }
                 DataSource::Ingestion(_) => {
@@ -944,7 +944,7 @@ where
                     );
                     table_registers.push((id, write));
                 }
-                DataSource::Progress | DataSource::Other => {
+                DataSource::Progress | DataSource::Other { .. } => {
                     debug!(
                         ?data_source, meta = ?metadata,
                         "not registering {id} with a controller persist worker",
@@ -1085,7 +1085,7 @@ where
                 DataSource::IngestionExport { .. } => unreachable!(
                     "ingestion exports do not execute directly, but instead schedule their source to be re-executed"
                 ),
-                DataSource::Introspection(_) | DataSource::Webhook | DataSource::Table { .. } | DataSource::Progress | DataSource::Other => {}
+                DataSource::Introspection(_) | DataSource::Webhook | DataSource::Table { .. } | DataSource::Progress | DataSource::Other { .. } => {}
                 DataSource::Sink { .. } => {
                     if !self.read_only {
                         self.run_export(id)?;
@@ -1787,7 +1787,9 @@ where
                         // same as for the "main" ingestion.
                         ingestions_to_drop.insert(id);
                     }
-                    DataSource::Other | DataSource::Introspection(_) | DataSource::Progress => (),
+                    DataSource::Other { .. }
+                    | DataSource::Introspection(_)
+                    | DataSource::Progress => (),
                     DataSource::Sink { .. } => {}
                 }
             }
@@ -2153,7 +2155,7 @@ where
                             DataSource::IngestionExport { .. } => (),
                             DataSource::Introspection(_) => (),
                             DataSource::Progress => (),
-                            DataSource::Other => (),
+                            DataSource::Other { .. } => (),
                             DataSource::Sink { .. } => (),
                         }
                     }
@@ -2313,7 +2315,8 @@ where
                 }) => match connection {
                     GenericSourceConnection::Kafka(_)
                     | GenericSourceConnection::Postgres(_)
-                    | GenericSourceConnection::MySql(_) => {
+                    | GenericSourceConnection::MySql(_)
+                    | GenericSourceConnection::SqlServer(_) => {
                         (connection.clone(), *remap_collection_id)
                     }
 
@@ -3016,9 +3019,12 @@ where
             | DataSource::Webhook
             | DataSource::Table { primary: None }
             | DataSource::Progress
-            | DataSource::Other => vec![],
+            | DataSource::Other { primary: None } => vec![],
             DataSource::Table {
                 primary: Some(primary),
+            }
+            | DataSource::Other {
+                primary: Some(primary),
             } => vec![*primary],
             DataSource::IngestionExport { ingestion_id, .. } => {
                 // Ingestion exports depend on their primary source's remap
@@ -0,0 +1,593 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Writing to Apache Iceberg tables stored in S3.
+//!
+//! This implements the subset of the Iceberg table spec (format version 2) that the Iceberg sink
+//! needs: unpartitioned tables whose schema is derived from a [`RelationDesc`], Parquet data
+//! files, equality delete files, and snapshots that are committed by writing a new version of the
+//! table metadata.
+//!
+//! Table versions are tracked the way Iceberg's Hadoop catalog does it: version `N` of the
+//! metadata lives at `metadata/vN.metadata.json` and `metadata/version-hint.text` names the latest
+//! version. S3 offers no atomic rename, so the metadata of a new version is written with a
+//! conditional write that fails if the version already exists. Of several writers that commit
+//! concurrently, for example two instances of a sink while a replica restarts, or a sink and an
+//! external maintenance tool, exactly one creates each version. The others have lost their commit
+//! and retry it on top of the latest version, after validating it again (see
+//! `mz_storage::sink::iceberg` for how instances of a sink fence each other out).
+//!
+//! Table maintenance, like compacting small files, expiring snapshots and removing files left
+//! behind by failed commits, is left to external tools.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context};
+use aws_types::sdk_config::SdkConfig;
+use mz_aws_util::s3::Client;
+use mz_ore::future::OreFutureExt;
+use mz_ore::now::SYSTEM_TIME;
+use mz_repr::{RelationDesc, Row};
+use mz_storage_types::sinks::iceberg::{IcebergTableLocation, IcebergType};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::iceberg::manifest::ManifestFile;
+use crate::iceberg::metadata::{Snapshot, TableMetadata};
+use crate::s3_oneshot_sink::parquet::ParquetFile;
+use crate::s3_sink;
+
+mod manifest;
+mod metadata;
+
+pub use manifest::{DataFile, DataFileContent};
+
+/// The name of the file pointing at the latest version of the table metadata.
+const VERSION_HINT_FILE: &str = "version-hint.text";
+
+/// Returns the field id of the column at `index` of the sinked relation.
+pub fn field_id(index: usize) -> i32 {
+    i32::try_from(index + 1).expect("too many columns")
+}
+
+/// Returns the Iceberg schema of a table storing `desc`, whose rows are identified by the
+/// columns at `key_indices`.
+pub fn table_schema(
+    desc: &RelationDesc,
+    key_indices: &[usize],
+) -> Result<serde_json::Value, anyhow::Error> {
+    let mut fields = vec![];
+    for (index, (name, typ)) in desc.iter().enumerate() {
+        let iceberg_type = IcebergType::from_scalar_type(&typ.scalar_type)?;
+        fields.push(serde_json::json!({
+            "id": field_id(index),
+            "name": name.as_str(),
+            "required": !typ.nullable,
+            "type": iceberg_type.to_string(),
+        }));
+    }
+    let mut schema = serde_json::json!({
+        "type": "struct",
+        "schema-id": 0,
+        "fields": fields,
+    });
+    // Iceberg only allows required, non-floating point columns as identifier fields.
+    let key_is_identifier = key_indices.iter().all(|i| {
+        let typ = &desc.typ().column_types[*i];
+        !typ.nullable
+            && !matches!(
+                IcebergType::from_scalar_type(&typ.scalar_type),
+                Ok(IcebergType::Float | IcebergType::Double)
+            )
+    });
+    if key_is_identifier {
+        let ids: Vec<_> = key_indices.iter().map(|i| field_id(*i)).collect();
+        schema["identifier-field-ids"] = serde_json::json!(ids);
+    }
+    Ok(schema)
+}
+
+/// Returns whether two Iceberg schemas describe the same columns.
+fn schemas_match(a: &serde_json::Value, b: &serde_json::Value) -> bool {
+    let columns = |schema: &serde_json::Value| {
+        schema["fields"].as_array().map(|fields| {
+            fields
+                .iter()
+                .map(|f| {
+                    (
+                        f["id"].clone(),
+                        f["name"].clone(),
+                        f["required"].clone(),
+                        f["type"].clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+    columns(a).is_some() && columns(a) == columns(b)
+}
+
+/// An Iceberg table that is being written to.
+pub struct IcebergTable {
+    client: Client,
+    sdk_config: Arc<SdkConfig>,
+    location: IcebergTableLocation,
+    /// The latest known version of the table metadata.
+    metadata: TableMetadata,
+    /// The manifests of the current snapshot, if they have been loaded.
+    manifests: Option<(i64, Vec<ManifestFile>)>,
+}
+
+impl IcebergTable {
+    /// Loads the table at `location`, creating it if it does not exist yet.
+    ///
+    /// Returns an error if the table exists but its schema does not match the schema derived from
+    /// `desc` and `key_indices`. The `properties` are only used when creating the table.
+    pub async fn load_or_create(
+        sdk_config: SdkConfig,
+        location: IcebergTableLocation,
+        desc: &RelationDesc,
+        key_indices: &[usize],
+        properties: BTreeMap<String, String>,
+    ) -> Result<Self, anyhow::Error> {
+        let client = mz_aws_util::s3::new_client(&sdk_config);
+        let schema = table_schema(desc, key_indices)?;
+
+        let metadata = loop {
+            if let Some((_, metadata)) = load_latest(&client, &location).await? {
+                let current_schema = metadata
+                    .current_schema()
+                    .ok_or_else(|| anyhow!("table metadata is missing the current schema"))?;
+                if !schemas_match(current_schema, &schema) {
+                    bail!(
+                        "the schema of Iceberg table {} does not match the schema of the sink",
+                        location.uri()
+                    );
+                }
+                break metadata;
+            }
+            let metadata = TableMetadata::new(
+                Uuid::new_v4().to_string(),
+                location.uri(),
+                schema.clone(),
+                i32::try_from(desc.arity())?,
+                properties.clone(),
+                now_ms(),
+            );
+            // If someone else created the table first, we load their version instead.
+            if write_metadata(&client, &location, 1, &metadata).await? {
+                info!("created Iceberg table {}", location.uri());
+                break metadata;
+            }
+        };
+
+        Ok(IcebergTable {
+            client,
+            sdk_config: Arc::new(sdk_config),
+            location,
+            metadata,
+            manifests: None,
+        })
+    }
+
+    /// Returns the properties of the table, as of the latest commit or load.
+    pub fn properties(&self) -> &BTreeMap<String, String> {
+        &self.metadata.properties
+    }
+
+    /// Returns a writer for new files of the table.
+    ///
+    /// The rows passed to the writer must match `desc`, whose columns are stored with the given
+    /// field ids.
+    pub fn file_writer(
+        &self,
+        content: DataFileContent,
+        desc: Arc<RelationDesc>,
+        field_ids: Vec<i32>,
+        target_file_size: u64,
+    ) -> DataFileWriter {
+        DataFileWriter {
+            sdk_config: Arc::clone(&self.sdk_config),
+            location: self.location.clone(),
+            content,
+            desc,
+            field_ids,
+            target_file_size,
+            active_file: None,
+            files: vec![],
+        }
+    }
+
+    /// Commits a new version of the table.
+    ///
+    /// Each group of `files` is assigned its own data sequence number, in order, so that the
+    /// equality deletes of a group apply to the data files of all previous groups, but not to
+    /// the data files of the same group. If all groups are empty, only the properties of the
+    /// table are updated.
+    ///
+    /// Before committing, the latest version of the table is loaded and its properties are
+    /// passed to `validate`, which can abort the commit by returning an error. If another writer
+    /// commits a new version of the table first, the commit is retried on top of that version,
+    /// which is validated again.
+    pub async fn commit<F>(
+        &mut self,
+        files: Vec<Vec<DataFile>>,
+        properties: BTreeMap<String, String>,
+        validate: F,
+    ) -> Result<(), anyhow::Error>
+    where
+        F: Fn(&BTreeMap<String, String>) -> Result<(), anyhow::Error>,
+    {
+        let files: Vec<_> = files.into_iter().filter(|g| !g.is_empty()).collect();
+        loop {
+            let (version, mut metadata) = load_latest(&self.client, &self.location)
+                .await?
+                .ok_or_else(|| anyhow!("Iceberg table {} was deleted", self.location.uri()))?;
+            validate(&metadata.properties)?;
+
+            let now = now_ms();
+            if !files.is_empty() {
+                let snapshot = self.write_snapshot(&metadata, files.clone(), now).await?;
+                metadata.add_snapshot(snapshot);
+            }
+
+            metadata.properties.extend(properties.clone());
+            metadata.last_updated_ms = now;
+            let previous_file = self
+                .location
+                .object_uri(&metadata_key(&self.location, version));
+            metadata.push_metadata_log(previous_file, now);
+
+            let next_version = version + 1;
+            if write_metadata(&self.client, &self.location, next_version, &metadata).await? {
+                self.metadata = metadata;
+                return Ok(());
+            }
+            info!(
+                "lost commit of version {} of Iceberg table {}, retrying",
+                next_version,
+                self.location.uri()
+            );
+        }
+    }
+
+    /// Writes the manifests and manifest list of a new snapshot that adds `files` to the current
+    /// snapshot of `metadata`.
+    async fn write_snapshot(
+        &mut self,
+        metadata: &TableMetadata,
+        files: Vec<Vec<DataFile>>,
+        now: i64,
+    ) -> Result<Snapshot, anyhow::Error> {
+        let parent = metadata.current_snapshot();
+        let mut manifests = match (parent, self.manifests.take()) {
+            (None, _) => vec![],
+            (Some(parent), Some((id, manifests))) if parent.snapshot_id == id => manifests,
+            (Some(parent), _) => {
+                let key = self.key_for_uri(&parent.manifest_list)?;
+                let bytes = self.get(&key).await?;
+                manifest::read_manifest_list(&bytes)?
+            }
+        };
+
+        // Snapshot ids must be positive.
+        let snapshot_id = i64::try_from(Uuid::new_v4().as_u64_pair().0 >> 1)?;
+        let first_sequence_number = metadata.last_sequence_number + 1;
+        let sequence_number = first_sequence_number + i64::try_from(files.len())? - 1;
+        let schema = metadata
+            .current_schema()
+            .ok_or_else(|| anyhow!("table metadata is missing the current schema"))?;
+
+        let mut data_files = vec![];
+        let mut delete_files = vec![];
+        for (sequence_number, group) in (first_sequence_number..).zip(files) {
+            for file in group {
+                match file.content {
+                    DataFileContent::Data => data_files.push((sequence_number, file)),
+                    DataFileContent::EqualityDeletes => delete_files.push((sequence_number, file)),
+                }
+            }
+        }
+
+        let mut summary = BTreeMap::new();
+        summary.insert("added-data-files".to_string(), data_files.len().to_string());
+        summary.insert(
+            "added-records".to_string(),
+            data_files
+                .iter()
+                .map(|(_, f)| f.record_count)
+                .sum::<u64>()
+                .to_string(),
+        );
+        summary.insert(
+            "added-delete-files".to_string(),
+            delete_files.len().to_string(),
+        );
+        summary.insert(
+            "added-equality-deletes".to_string(),
+            delete_files
+                .iter()
+                .map(|(_, f)| f.record_count)
+                .sum::<u64>()
+                .to_string(),
+        );
+        let operation = if delete_files.is_empty() {
+            "append"
+        } else {
+            "overwrite"
+        };
+        summary.insert("operation".to_string(), operation.to_string());
+
+        // New manifests go first, as the most recent changes are the most likely to be relevant
+        // to readers.
+        let mut new_manifests = vec![];
+        for (content, files) in [
+            (DataFileContent::Data, data_files),
+            (DataFileContent::EqualityDeletes, delete_files),
+        ] {
+            if files.is_empty() {
+                continue;
+            }
+            let (bytes, mut manifest) =
+                manifest::write_manifest(schema, content, snapshot_id, sequence_number, &files)?;
+            let key = self.location.metadata_key(&format!(
+                "{}-m{}.avro",
+                Uuid::new_v4(),
+                new_manifests.len()
+            ));
+            self.put(&key, bytes).await?;
+            manifest.manifest_path = self.location.object_uri(&key);
+            new_manifests.push(manifest);
+        }
+        new_manifests.append(&mut manifests);
+        let manifests = new_manifests;
+
+        let parent_snapshot_id = parent.map(|p| p.snapshot_id);
+        let bytes = manifest::write_manifest_list(
+            snapshot_id,
+            parent_snapshot_id,
+            sequence_number,
+            &manifests,
+        )?;
+        let key =
+            self.location
+                .metadata_key(&format!("snap-{}-{}.avro", snapshot_id, Uuid::new_v4()));
+        self.put(&key, bytes).await?;
+        self.manifests = Some((snapshot_id, manifests));
+
+        Ok(Snapshot {
+            snapshot_id,
+            parent_snapshot_id,
+            sequence_number,
+            timestamp_ms: now,
+            manifest_list: self.location.object_uri(&key),
+            summary,
+            schema_id: Some(metadata.current_schema_id),
+            other: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the key of the object with the given `s3://` URI in the table's bucket.
+    fn key_for_uri(&self, uri: &str) -> Result<String, anyhow::Error> {
+        uri.strip_prefix(&format!("s3://{}/", self.location.bucket))
+            .map(|key| key.to_string())
+            .ok_or_else(|| anyhow!("file {} is not stored in the table's bucket", uri))
+    }
+
+    async fn get(&self, key: &str) -> Result<bytes::Bytes, anyhow::Error> {
+        mz_aws_util::s3::get_object_if_exists(&self.client, &self.location.bucket, key)
+            .await?
+            .ok_or_else(|| anyhow!("missing file {}", key))
+    }
+
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), anyhow::Error> {
+        self.client
+            .put_object()
+            .bucket(&self.location.bucket)
+            .key(key)
+            .body(bytes.into())
+            .send()
+            .await
+            .with_context(|| format!("writing {}", key))?;
+        Ok(())
+    }
+}
+
+/// Writes rows to Parquet files in the data directory of a table, starting a new file whenever
+/// the current one exceeds the target file size.
+pub struct DataFileWriter {
+    sdk_config: Arc<SdkConfig>,
+    location: IcebergTableLocation,
+    content: DataFileContent,
+    desc: Arc<RelationDesc>,
+    field_ids: Vec<i32>,
+    target_file_size: u64,
+    active_file: Option<(String, ParquetFile)>,
+    files: Vec<DataFile>,
+}
+
+impl DataFileWriter {
+    /// Appends a row to the current file.
+    pub async fn add_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        let target_file_size = self.target_file_size;
+        let (_, active_file) = match self.active_file {
+            Some(ref mut file) => file,
+            None => self.start_new_file().await?,
+        };
+        active_file.add_row(row)?;
+        if active_file.size_estimate() >= target_file_size {
+            self.finish_active_file().await?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the current file and returns all files written by this writer.
+    pub async fn finish(mut self) -> Result<Vec<DataFile>, anyhow::Error> {
+        self.finish_active_file().await?;
+        Ok(self.files)
+    }
+
+    async fn start_new_file(&mut self) -> Result<&mut (String, ParquetFile), anyhow::Error> {
+        let suffix = match self.content {
+            DataFileContent::Data => "",
+            DataFileContent::EqualityDeletes => "-deletes",
+        };
+        let key = self
+            .location
+            .data_key(&format!("{}{}.parquet", Uuid::new_v4(), suffix));
+        let file = s3_sink::new_parquet_file(
+            self.location.bucket.clone(),
+            key.clone(),
+            Arc::clone(&self.desc),
+            Arc::clone(&self.sdk_config),
+            self.target_file_size,
+            Some(self.field_ids.clone()),
+        )
+        .await?;
+        Ok(self.active_file.insert((key, file)))
+    }
+
+    async fn finish_active_file(&mut self) -> Result<(), anyhow::Error> {
+        if let Some((key, file)) = self.active_file.take() {
+            let record_count = file.row_count();
+            let upload = file.finish().run_in_task(|| "ParquetFile::finish").await?;
+            self.files.push(DataFile {
+                content: self.content,
+                file_path: self.location.object_uri(&key),
+                record_count,
+                file_size_in_bytes: upload.total_bytes_uploaded,
+                equality_ids: match self.content {
+                    DataFileContent::Data => vec![],
+                    DataFileContent::EqualityDeletes => self.field_ids.clone(),
+                },
+            });
+        }
+        Ok(())
+    }
+}
+
+fn metadata_key(location: &IcebergTableLocation, version: u64) -> String {
+    location.metadata_key(&format!("v{}.metadata.json", version))
+}
+
+/// Loads the latest version of the table metadata, if the table exists.
+async fn load_latest(
+    client: &Client,
+    location: &IcebergTableLocation,
+) -> Result<Option<(u64, TableMetadata)>, anyhow::Error> {
+    let hint_key = location.metadata_key(VERSION_HINT_FILE);
+    let mut version =
+        match mz_aws_util::s3::get_object_if_exists(client, &location.bucket, &hint_key).await? {
+            Some(hint) => std::str::from_utf8(&hint)?
+                .trim()
+                .parse::<u64>()
+                .context("invalid version hint")?,
+            None => 0,
+        };
+    // The version hint is updated after the metadata file is written, so it can lag behind.
+    while mz_aws_util::s3::object_exists(
+        client,
+        &location.bucket,
+        &metadata_key(location, version + 1),
+    )
+    .await?
+    {
+        version += 1;
+    }
+    if version == 0 {
+        return Ok(None);
+    }
+
+    let key = metadata_key(location, version);
+    let bytes = mz_aws_util::s3::get_object_if_exists(client, &location.bucket, &key)
+        .await?
+        .ok_or_else(|| anyhow!("missing table metadata file {}", key))?;
+    let metadata: TableMetadata =
+        serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", key))?;
+    if metadata.format_version != 2 {
+        bail!(
+            "unsupported Iceberg format version {}",
+            metadata.format_version
+        );
+    }
+    Ok(Some((version, metadata)))
+}
+
+/// Writes version `version` of the table metadata and points the version hint at it.
+///
+/// Returns `false` without writing anything if the version already exists, in which case another
+/// writer has committed it first.
+async fn write_metadata(
+    client: &Client,
+    location: &IcebergTableLocation,
+    version: u64,
+    metadata: &TableMetadata,
+) -> Result<bool, anyhow::Error> {
+    let key = metadata_key(location, version);
+    let written = mz_aws_util::s3::put_object_if(
+        client,
+        &location.bucket,
+        &key,
+        None,
+        serde_json::to_vec(metadata)?,
+    )
+    .await
+    .with_context(|| format!("writing {}", key))?;
+    if written.is_none() {
+        return Ok(false);
+    }
+    // The version hint is only a hint, so it doesn't matter if a slow writer moves it back.
+    client
+        .put_object()
+        .bucket(&location.bucket)
+        .key(location.metadata_key(VERSION_HINT_FILE))
+        .body(version.to_string().into_bytes().into())
+        .send()
+        .await
+        .context("writing version hint")?;
+    Ok(true)
+}
+
+fn now_ms() -> i64 {
+    i64::try_from((SYSTEM_TIME)()).expect("timestamp fits")
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_table_schema() {
+        let desc = RelationDesc::builder()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("value", ScalarType::String.nullable(true))
+            .finish();
+        let schema = table_schema(&desc, &[0]).unwrap();
+        assert_eq!(
+            schema,
+            serde_json::json!({
+                "type": "struct",
+                "schema-id": 0,
+                "identifier-field-ids": [1],
+                "fields": [
+                    {"id": 1, "name": "id", "required": true, "type": "long"},
+                    {"id": 2, "name": "value", "required": false, "type": "string"},
+                ],
+            })
+        );
+
+        // Nullable keys can't be identifier fields.
+        let schema = table_schema(&desc, &[1]).unwrap();
+        assert!(schema.get("identifier-field-ids").is_none());
+        assert!(schemas_match(&schema, &table_schema(&desc, &[0]).unwrap()));
+    }
+}
@@ -0,0 +1,403 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The Avro manifest and manifest list files of Iceberg tables.
+//!
+//! See <https://iceberg.apache.org/spec/#manifests>. Iceberg readers resolve the columns of these
+//! files by their field ids, which is why the Avro schemas below are written to the file headers
+//! verbatim instead of being re-serialized from the parsed [`Schema`].
+
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use anyhow::{anyhow, Context};
+use mz_avro::types::Value;
+use mz_avro::{Reader, Schema, Writer};
+
+/// The Avro schema of the entries of a (format version 2) manifest file of an unpartitioned
+/// table.
+const MANIFEST_ENTRY_SCHEMA: &str = r#"{
+  "type": "record",
+  "name": "manifest_entry",
+  "fields": [
+    {"name": "status", "type": "int", "field-id": 0},
+    {"name": "snapshot_id", "type": ["null", "long"], "default": null, "field-id": 1},
+    {"name": "sequence_number", "type": ["null", "long"], "default": null, "field-id": 3},
+    {"name": "file_sequence_number", "type": ["null", "long"], "default": null, "field-id": 4},
+    {"name": "data_file", "type": {
+      "type": "record",
+      "name": "r2",
+      "fields": [
+        {"name": "content", "type": "int", "field-id": 134},
+        {"name": "file_path", "type": "string", "field-id": 100},
+        {"name": "file_format", "type": "string", "field-id": 101},
+        {"name": "partition", "type": {"type": "record", "name": "r102", "fields": []}, "field-id": 102},
+        {"name": "record_count", "type": "long", "field-id": 103},
+        {"name": "file_size_in_bytes", "type": "long", "field-id": 104},
+        {"name": "equality_ids", "type": ["null", {"type": "array", "items": "int", "element-id": 135}], "default": null, "field-id": 135}
+      ]
+    }, "field-id": 2}
+  ]
+}"#;
+
+/// The Avro schema of the entries of a (format version 2) manifest list file.
+const MANIFEST_FILE_SCHEMA: &str = r#"{
+  "type": "record",
+  "name": "manifest_file",
+  "fields": [
+    {"name": "manifest_path", "type": "string", "field-id": 500},
+    {"name": "manifest_length", "type": "long", "field-id": 501},
+    {"name": "partition_spec_id", "type": "int", "field-id": 502},
+    {"name": "content", "type": "int", "field-id": 517},
+    {"name": "sequence_number", "type": "long", "field-id": 515},
+    {"name": "min_sequence_number", "type": "long", "field-id": 516},
+    {"name": "added_snapshot_id", "type": "long", "field-id": 503},
+    {"name": "added_files_count", "type": "int", "field-id": 504},
+    {"name": "existing_files_count", "type": "int", "field-id": 505},
+    {"name": "deleted_files_count", "type": "int", "field-id": 506},
+    {"name": "added_rows_count", "type": "long", "field-id": 512},
+    {"name": "existing_rows_count", "type": "long", "field-id": 513},
+    {"name": "deleted_rows_count", "type": "long", "field-id": 514}
+  ]
+}"#;
+
+static MANIFEST_ENTRY: LazyLock<Schema> =
+    LazyLock::new(|| Schema::from_str(MANIFEST_ENTRY_SCHEMA).expect("valid schema"));
+static MANIFEST_FILE: LazyLock<Schema> =
+    LazyLock::new(|| Schema::from_str(MANIFEST_FILE_SCHEMA).expect("valid schema"));
+
+/// The status of a manifest entry for a file added in the manifest's snapshot.
+const STATUS_ADDED: i32 = 1;
+
+/// The type of content stored in a data file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DataFileContent {
+    /// The file contains rows of the table.
+    Data,
+    /// The file contains equality deletes, which delete all rows with matching values in the
+    /// equality columns from files with a lower data sequence number.
+    EqualityDeletes,
+}
+
+impl DataFileContent {
+    fn data_file_content(&self) -> i32 {
+        match self {
+            DataFileContent::Data => 0,
+            DataFileContent::EqualityDeletes => 2,
+        }
+    }
+
+    fn manifest_content(&self) -> i32 {
+        match self {
+            DataFileContent::Data => 0,
+            DataFileContent::EqualityDeletes => 1,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            DataFileContent::Data => "data",
+            DataFileContent::EqualityDeletes => "deletes",
+        }
+    }
+}
+
+/// A data or delete file that has been written to the table's location but not yet committed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataFile {
+    pub content: DataFileContent,
+    /// The full `s3://` URI of the file.
+    pub file_path: String,
+    pub record_count: u64,
+    pub file_size_in_bytes: u64,
+    /// The field ids of the columns that equality deletes match on.
+    pub equality_ids: Vec<i32>,
+}
+
+/// An entry of a manifest list, describing one manifest file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ManifestFile {
+    pub manifest_path: String,
+    pub manifest_length: i64,
+    pub partition_spec_id: i32,
+    pub content: i32,
+    pub sequence_number: i64,
+    pub min_sequence_number: i64,
+    pub added_snapshot_id: i64,
+    pub added_files_count: i32,
+    pub existing_files_count: i32,
+    pub deleted_files_count: i32,
+    pub added_rows_count: i64,
+    pub existing_rows_count: i64,
+    pub deleted_rows_count: i64,
+}
+
+/// Encodes a manifest for files of a single `content` type added by `snapshot_id`.
+///
+/// Each file is accompanied by its data sequence number. Returns the encoded manifest and the
+/// corresponding manifest list entry, which the caller must complete with the path and length
+/// of the manifest.
+pub(crate) fn write_manifest(
+    table_schema: &serde_json::Value,
+    content: DataFileContent,
+    snapshot_id: i64,
+    snapshot_sequence_number: i64,
+    files: &[(i64, DataFile)],
+) -> Result<(Vec<u8>, ManifestFile), anyhow::Error> {
+    let mut writer = Writer::new(MANIFEST_ENTRY.clone(), Vec::new());
+    writer.set_header_schema(MANIFEST_ENTRY_SCHEMA.into())?;
+    writer.add_user_metadata("schema".into(), serde_json::to_vec(table_schema)?)?;
+    writer.add_user_metadata("schema-id".into(), table_schema["schema-id"].to_string())?;
+    writer.add_user_metadata("partition-spec".into(), "[]")?;
+    writer.add_user_metadata("partition-spec-id".into(), "0")?;
+    writer.add_user_metadata("format-version".into(), "2")?;
+    writer.add_user_metadata("content".into(), content.name())?;
+
+    let mut min_sequence_number = snapshot_sequence_number;
+    let mut added_rows_count = 0;
+    for (sequence_number, file) in files {
+        assert_eq!(file.content, content, "mixed content in manifest");
+        min_sequence_number = std::cmp::min(min_sequence_number, *sequence_number);
+        added_rows_count += i64::try_from(file.record_count)?;
+
+        let equality_ids = match file.content {
+            DataFileContent::Data => nullable(None),
+            DataFileContent::EqualityDeletes => nullable(Some(Value::Array(
+                file.equality_ids.iter().map(|id| Value::Int(*id)).collect(),
+            ))),
+        };
+        let data_file = Value::Record(vec![
+            (
+                "content".into(),
+                Value::Int(file.content.data_file_content()),
+            ),
+            ("file_path".into(), Value::String(file.file_path.clone())),
+            ("file_format".into(), Value::String("PARQUET".into())),
+            ("partition".into(), Value::Record(vec![])),
+            (
+                "record_count".into(),
+                Value::Long(i64::try_from(file.record_count)?),
+            ),
+            (
+                "file_size_in_bytes".into(),
+                Value::Long(i64::try_from(file.file_size_in_bytes)?),
+            ),
+            ("equality_ids".into(), equality_ids),
+        ]);
+        let entry = Value::Record(vec![
+            ("status".into(), Value::Int(STATUS_ADDED)),
+            (
+                "snapshot_id".into(),
+                nullable(Some(Value::Long(snapshot_id))),
+            ),
+            (
+                "sequence_number".into(),
+                nullable(Some(Value::Long(*sequence_number))),
+            ),
+            // The file sequence number is inherited from the snapshot.
+            ("file_sequence_number".into(), nullable(None)),
+            ("data_file".into(), data_file),
+        ]);
+        writer.append(entry)?;
+    }
+    writer.flush()?;
+    let bytes = writer.into_inner();
+
+    let manifest = ManifestFile {
+        manifest_path: String::new(),
+        manifest_length: i64::try_from(bytes.len())?,
+        partition_spec_id: 0,
+        content: content.manifest_content(),
+        sequence_number: snapshot_sequence_number,
+        min_sequence_number,
+        added_snapshot_id: snapshot_id,
+        added_files_count: i32::try_from(files.len())?,
+        existing_files_count: 0,
+        deleted_files_count: 0,
+        added_rows_count,
+        existing_rows_count: 0,
+        deleted_rows_count: 0,
+    };
+    Ok((bytes, manifest))
+}
+
+/// Encodes the manifest list of a snapshot.
+pub(crate) fn write_manifest_list(
+    snapshot_id: i64,
+    parent_snapshot_id: Option<i64>,
+    sequence_number: i64,
+    manifests: &[ManifestFile],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let mut writer = Writer::new(MANIFEST_FILE.clone(), Vec::new());
+    writer.set_header_schema(MANIFEST_FILE_SCHEMA.into())?;
+    writer.add_user_metadata("snapshot-id".into(), snapshot_id.to_string())?;
+    writer.add_user_metadata(
+        "parent-snapshot-id".into(),
+        parent_snapshot_id.map_or_else(|| "null".into(), |id| id.to_string()),
+    )?;
+    writer.add_user_metadata("sequence-number".into(), sequence_number.to_string())?;
+    writer.add_user_metadata("format-version".into(), "2")?;
+
+    for manifest in manifests {
+        let entry = Value::Record(vec![
+            (
+                "manifest_path".into(),
+                Value::String(manifest.manifest_path.clone()),
+            ),
+            (
+                "manifest_length".into(),
+                Value::Long(manifest.manifest_length),
+            ),
+            (
+                "partition_spec_id".into(),
+                Value::Int(manifest.partition_spec_id),
+            ),
+            ("content".into(), Value::Int(manifest.content)),
+            (
+                "sequence_number".into(),
+                Value::Long(manifest.sequence_number),
+            ),
+            (
+                "min_sequence_number".into(),
+                Value::Long(manifest.min_sequence_number),
+            ),
+            (
+                "added_snapshot_id".into(),
+                Value::Long(manifest.added_snapshot_id),
+            ),
+            (
+                "added_files_count".into(),
+                Value::Int(manifest.added_files_count),
+            ),
+            (
+                "existing_files_count".into(),
+                Value::Int(manifest.existing_files_count),
+            ),
+            (
+                "deleted_files_count".into(),
+                Value::Int(manifest.deleted_files_count),
+            ),
+            (
+                "added_rows_count".into(),
+                Value::Long(manifest.added_rows_count),
+            ),
+            (
+                "existing_rows_count".into(),
+                Value::Long(manifest.existing_rows_count),
+            ),
+            (
+                "deleted_rows_count".into(),
+                Value::Long(manifest.deleted_rows_count),
+            ),
+        ]);
+        writer.append(entry)?;
+    }
+    writer.flush()?;
+    Ok(writer.into_inner())
+}
+
+/// Decodes a manifest list, as written by any Iceberg implementation.
+pub(crate) fn read_manifest_list(bytes: &[u8]) -> Result<Vec<ManifestFile>, anyhow::Error> {
+    let reader = Reader::new(bytes)?;
+    let mut manifests = vec![];
+    for value in reader {
+        let Value::Record(fields) = value? else {
+            anyhow::bail!("manifest list entry is not a record");
+        };
+        let field = |names: &[&str]| {
+            fields
+                .iter()
+                .find(|(name, _)| names.contains(&name.as_str()))
+                .map(|(_, value)| match value {
+                    Value::Union { inner, .. } => inner.as_ref().clone(),
+                    value => value.clone(),
+                })
+        };
+        let long = |names: &[&str]| -> Result<i64, anyhow::Error> {
+            field(names)
+                .and_then(|v| v.into_integral())
+                .ok_or_else(|| anyhow!("missing manifest list field {}", names[0]))
+        };
+        let int = |names: &[&str]| -> Result<i32, anyhow::Error> {
+            i32::try_from(long(names)?).context("manifest list field out of range")
+        };
+        // Format version 1 manifest lists have neither content nor sequence numbers, and use
+        // different names for the file counts.
+        manifests.push(ManifestFile {
+            manifest_path: field(&["manifest_path"])
+                .and_then(|v| v.into_string())
+                .ok_or_else(|| anyhow!("missing manifest list field manifest_path"))?,
+            manifest_length: long(&["manifest_length"])?,
+            partition_spec_id: int(&["partition_spec_id"])?,
+            content: int(&["content"]).unwrap_or(0),
+            sequence_number: long(&["sequence_number"]).unwrap_or(0),
+            min_sequence_number: long(&["min_sequence_number"]).unwrap_or(0),
+            added_snapshot_id: long(&["added_snapshot_id"])?,
+            added_files_count: int(&["added_files_count", "added_data_files_count"])?,
+            existing_files_count: int(&["existing_files_count", "existing_data_files_count"])?,
+            deleted_files_count: int(&["deleted_files_count", "deleted_data_files_count"])?,
+            added_rows_count: long(&["added_rows_count"])?,
+            existing_rows_count: long(&["existing_rows_count"])?,
+            deleted_rows_count: long(&["deleted_rows_count"])?,
+        });
+    }
+    Ok(manifests)
+}
+
+/// Returns the value of a `["null", T]` union.
+fn nullable(value: Option<Value>) -> Value {
+    let (index, inner) = match value {
+        None => (0, Value::Null),
+        Some(value) => (1, value),
+    };
+    Value::Union {
+        index,
+        inner: Box::new(inner),
+        n_variants: 2,
+        null_variant: Some(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_manifest_list_roundtrip() {
+        let schema = serde_json::json!({"type": "struct", "schema-id": 0, "fields": []});
+        let file = DataFile {
+            content: DataFileContent::EqualityDeletes,
+            file_path: "s3://bucket/table/data/1-deletes.parquet".into(),
+            record_count: 3,
+            file_size_in_bytes: 1024,
+            equality_ids: vec![1],
+        };
+        let (bytes, mut manifest) = write_manifest(
+            &schema,
+            DataFileContent::EqualityDeletes,
+            42,
+            7,
+            &[(6, file.clone()), (7, file)],
+        )
+        .unwrap();
+        manifest.manifest_path = "s3://bucket/table/metadata/m0.avro".into();
+        assert_eq!(manifest.min_sequence_number, 6);
+        assert_eq!(manifest.added_rows_count, 6);
+        assert_eq!(manifest.content, 1);
+
+        let entries: Vec<_> = Reader::new(&bytes[..])
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let list = write_manifest_list(42, Some(41), 7, &[manifest.clone()]).unwrap();
+        assert_eq!(read_manifest_list(&list).unwrap(), vec![manifest]);
+    }
+}
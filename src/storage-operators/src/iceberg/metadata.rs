@@ -0,0 +1,164 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The JSON table metadata files of Iceberg tables.
+//!
+//! See <https://iceberg.apache.org/spec/#table-metadata-fields>. Only the fields that the sink
+//! needs to interpret are modeled explicitly. Everything else is carried along untouched, so that
+//! committing a new version of the metadata does not drop information written by other tools.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of previous metadata files tracked in the metadata log.
+const MAX_METADATA_LOG_ENTRIES: usize = 100;
+
+/// The contents of a `vN.metadata.json` file.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct TableMetadata {
+    pub format_version: u8,
+    pub table_uuid: String,
+    pub location: String,
+    pub last_sequence_number: i64,
+    pub last_updated_ms: i64,
+    pub last_column_id: i32,
+    pub schemas: Vec<serde_json::Value>,
+    pub current_schema_id: i32,
+    pub partition_specs: Vec<serde_json::Value>,
+    pub default_spec_id: i32,
+    pub last_partition_id: i32,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+    #[serde(default)]
+    pub snapshot_log: Vec<SnapshotLogEntry>,
+    #[serde(default)]
+    pub metadata_log: Vec<MetadataLogEntry>,
+    pub sort_orders: Vec<serde_json::Value>,
+    pub default_sort_order_id: i32,
+    #[serde(default)]
+    pub refs: BTreeMap<String, serde_json::Value>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl TableMetadata {
+    /// Returns the metadata of a new, empty, unpartitioned table.
+    pub fn new(
+        table_uuid: String,
+        location: String,
+        schema: serde_json::Value,
+        last_column_id: i32,
+        properties: BTreeMap<String, String>,
+        now_ms: i64,
+    ) -> Self {
+        TableMetadata {
+            format_version: 2,
+            table_uuid,
+            location,
+            last_sequence_number: 0,
+            last_updated_ms: now_ms,
+            last_column_id,
+            schemas: vec![schema],
+            current_schema_id: 0,
+            partition_specs: vec![serde_json::json!({"spec-id": 0, "fields": []})],
+            default_spec_id: 0,
+            // Partition field ids start at 1000, so this is the value used by tables that
+            // have never been partitioned.
+            last_partition_id: 999,
+            properties,
+            current_snapshot_id: None,
+            snapshots: vec![],
+            snapshot_log: vec![],
+            metadata_log: vec![],
+            sort_orders: vec![serde_json::json!({"order-id": 0, "fields": []})],
+            default_sort_order_id: 0,
+            refs: BTreeMap::new(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the current schema of the table.
+    pub fn current_schema(&self) -> Option<&serde_json::Value> {
+        self.schemas
+            .iter()
+            .find(|s| s["schema-id"].as_i64() == Some(i64::from(self.current_schema_id)))
+    }
+
+    /// Returns the current snapshot of the table, if any.
+    pub fn current_snapshot(&self) -> Option<&Snapshot> {
+        // Some writers use -1 to indicate that there is no current snapshot.
+        let id = self.current_snapshot_id.filter(|id| *id != -1)?;
+        self.snapshots.iter().find(|s| s.snapshot_id == id)
+    }
+
+    /// Adds `snapshot` to the table and makes it the current snapshot of the `main` branch.
+    pub fn add_snapshot(&mut self, snapshot: Snapshot) {
+        self.last_sequence_number =
+            std::cmp::max(self.last_sequence_number, snapshot.sequence_number);
+        self.current_snapshot_id = Some(snapshot.snapshot_id);
+        self.refs.insert(
+            "main".into(),
+            serde_json::json!({"snapshot-id": snapshot.snapshot_id, "type": "branch"}),
+        );
+        self.snapshot_log.push(SnapshotLogEntry {
+            snapshot_id: snapshot.snapshot_id,
+            timestamp_ms: snapshot.timestamp_ms,
+        });
+        self.snapshots.push(snapshot);
+    }
+
+    /// Records `previous_metadata_file` as the metadata file this version replaces.
+    pub fn push_metadata_log(&mut self, previous_metadata_file: String, timestamp_ms: i64) {
+        self.metadata_log.push(MetadataLogEntry {
+            metadata_file: previous_metadata_file,
+            timestamp_ms,
+        });
+        if self.metadata_log.len() > MAX_METADATA_LOG_ENTRIES {
+            let excess = self.metadata_log.len() - MAX_METADATA_LOG_ENTRIES;
+            self.metadata_log.drain(..excess);
+        }
+    }
+}
+
+/// A snapshot of an Iceberg table.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Snapshot {
+    pub snapshot_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_snapshot_id: Option<i64>,
+    pub sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub manifest_list: String,
+    pub summary: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<i32>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct SnapshotLogEntry {
+    pub snapshot_id: i64,
+    pub timestamp_ms: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct MetadataLogEntry {
+    pub metadata_file: String,
+    pub timestamp_ms: i64,
+}
@@ -9,8 +9,10 @@
 
 //! Shared Storage dataflow operators
 
+pub mod iceberg;
 pub mod metrics;
 pub mod oneshot_source;
 pub mod persist_source;
 pub mod s3_oneshot_sink;
+pub mod s3_sink;
 pub mod stats;
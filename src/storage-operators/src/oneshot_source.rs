@@ -96,12 +96,18 @@ use timely::dataflow::{Scope, Stream as TimelyStream};
 use timely::progress::Antichain;
 use tracing::info;
 
+use crate::oneshot_source::avro::{AvroBlock, AvroFormat, AvroWorkRequest};
 use crate::oneshot_source::aws_source::{AwsS3Source, S3Checksum, S3Object};
 use crate::oneshot_source::csv::{CsvDecoder, CsvRecord, CsvWorkRequest};
 use crate::oneshot_source::http_source::{HttpChecksum, HttpObject, HttpOneshotSource};
+use crate::oneshot_source::json::{JsonFormat, JsonRecord, JsonWorkRequest};
+use crate::oneshot_source::orc::{OrcFormat, OrcRecordBatch, OrcWorkRequest};
 use crate::oneshot_source::parquet::{ParquetFormat, ParquetRowGroup, ParquetWorkRequest};
 
+pub mod avro;
 pub mod csv;
+pub mod json;
+pub mod orc;
 pub mod parquet;
 
 pub mod aws_source;
@@ -170,6 +176,18 @@ where
             let format = ParquetFormat::new(collection_meta.relation_desc.clone());
             FormatKind::Parquet(format)
         }
+        ContentFormat::Avro => {
+            let format = AvroFormat::new(collection_meta.relation_desc.clone());
+            FormatKind::Avro(format)
+        }
+        ContentFormat::Orc => {
+            let format = OrcFormat::new(collection_meta.relation_desc.clone());
+            FormatKind::Orc(format)
+        }
+        ContentFormat::Json(params) => {
+            let format = JsonFormat::new(params, &collection_meta.relation_desc);
+            FormatKind::Json(format)
+        }
     };
 
     // Discover what objects are available to copy.
@@ -830,6 +848,9 @@ pub trait OneshotFormat: Clone {
 pub(crate) enum FormatKind {
     Csv(CsvDecoder),
     Parquet(ParquetFormat),
+    Avro(AvroFormat),
+    Orc(OrcFormat),
+    Json(JsonFormat),
 }
 
 impl OneshotFormat for FormatKind {
@@ -866,6 +887,36 @@ impl OneshotFormat for FormatKind {
                     .collect();
                 Ok(work)
             }
+            FormatKind::Avro(avro) => {
+                let work = avro
+                    .split_work(source, object, checksum)
+                    .await
+                    .context("avro")?
+                    .into_iter()
+                    .map(RequestKind::Avro)
+                    .collect();
+                Ok(work)
+            }
+            FormatKind::Orc(orc) => {
+                let work = orc
+                    .split_work(source, object, checksum)
+                    .await
+                    .context("orc")?
+                    .into_iter()
+                    .map(RequestKind::Orc)
+                    .collect();
+                Ok(work)
+            }
+            FormatKind::Json(json) => {
+                let work = json
+                    .split_work(source, object, checksum)
+                    .await
+                    .context("json")?
+                    .into_iter()
+                    .map(RequestKind::Json)
+                    .collect();
+                Ok(work)
+            }
         }
     }
 
@@ -885,8 +936,29 @@ impl OneshotFormat for FormatKind {
                 .map_ok(RecordChunkKind::Parquet)
                 .map(|result| result.context("parquet"))
                 .boxed(),
-            (FormatKind::Parquet(_), RequestKind::Csv(_))
-            | (FormatKind::Csv(_), RequestKind::Parquet(_)) => {
+            (FormatKind::Avro(avro), RequestKind::Avro(request)) => avro
+                .fetch_work(source, request)
+                .map_ok(RecordChunkKind::Avro)
+                .map(|result| result.context("avro"))
+                .boxed(),
+            (FormatKind::Orc(orc), RequestKind::Orc(request)) => orc
+                .fetch_work(source, request)
+                .map_ok(RecordChunkKind::Orc)
+                .map(|result| result.context("orc"))
+                .boxed(),
+            (FormatKind::Json(json), RequestKind::Json(request)) => json
+                .fetch_work(source, request)
+                .map_ok(RecordChunkKind::Json)
+                .map(|result| result.context("json"))
+                .boxed(),
+            (
+                FormatKind::Csv(_)
+                | FormatKind::Parquet(_)
+                | FormatKind::Avro(_)
+                | FormatKind::Orc(_)
+                | FormatKind::Json(_),
+                _,
+            ) => {
                 unreachable!("programming error, {self:?}")
             }
         }
@@ -904,8 +976,23 @@ impl OneshotFormat for FormatKind {
             (FormatKind::Parquet(parquet), RecordChunkKind::Parquet(chunk)) => {
                 parquet.decode_chunk(chunk, rows).context("parquet")
             }
-            (FormatKind::Parquet(_), RecordChunkKind::Csv(_))
-            | (FormatKind::Csv(_), RecordChunkKind::Parquet(_)) => {
+            (FormatKind::Avro(avro), RecordChunkKind::Avro(chunk)) => {
+                avro.decode_chunk(chunk, rows).context("avro")
+            }
+            (FormatKind::Orc(orc), RecordChunkKind::Orc(chunk)) => {
+                orc.decode_chunk(chunk, rows).context("orc")
+            }
+            (FormatKind::Json(json), RecordChunkKind::Json(chunk)) => {
+                json.decode_chunk(chunk, rows).context("json")
+            }
+            (
+                FormatKind::Csv(_)
+                | FormatKind::Parquet(_)
+                | FormatKind::Avro(_)
+                | FormatKind::Orc(_)
+                | FormatKind::Json(_),
+                _,
+            ) => {
                 unreachable!("programming error, {self:?}")
             }
         }
@@ -916,12 +1003,18 @@ impl OneshotFormat for FormatKind {
 pub(crate) enum RequestKind<O, C> {
     Csv(CsvWorkRequest<O, C>),
     Parquet(ParquetWorkRequest<O, C>),
+    Avro(AvroWorkRequest<O, C>),
+    Orc(OrcWorkRequest<O, C>),
+    Json(JsonWorkRequest<O, C>),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) enum RecordChunkKind {
     Csv(CsvRecord),
     Parquet(ParquetRowGroup),
+    Avro(AvroBlock),
+    Orc(OrcRecordBatch),
+    Json(JsonRecord),
 }
 
 pub(crate) enum ObjectFilter {
@@ -982,6 +1075,12 @@ pub enum StorageErrorXKind {
     CsvDecoding(Arc<str>),
     #[error("parquet error: {0}")]
     ParquetError(Arc<str>),
+    #[error("avro error: {0}")]
+    AvroError(Arc<str>),
+    #[error("orc error: {0}")]
+    OrcError(Arc<str>),
+    #[error("json decoding error: {0}")]
+    JsonDecoding(Arc<str>),
     #[error("reqwest error: {0}")]
     Reqwest(Arc<str>),
     #[error("aws s3 request error: {0}")]
@@ -1032,6 +1131,24 @@ impl From<::parquet::errors::ParquetError> for StorageErrorXKind {
     }
 }
 
+impl From<mz_avro::error::Error> for StorageErrorXKind {
+    fn from(err: mz_avro::error::Error) -> Self {
+        StorageErrorXKind::AvroError(err.to_string().into())
+    }
+}
+
+impl From<serde_json::Error> for StorageErrorXKind {
+    fn from(err: serde_json::Error) -> Self {
+        StorageErrorXKind::JsonDecoding(err.to_string().into())
+    }
+}
+
+impl From<::orc_rust::error::OrcError> for StorageErrorXKind {
+    fn from(err: ::orc_rust::error::OrcError) -> Self {
+        StorageErrorXKind::OrcError(err.to_string().into())
+    }
+}
+
 impl StorageErrorXKind {
     pub fn with_context<C: Display>(self, context: C) -> StorageErrorX {
         StorageErrorX {
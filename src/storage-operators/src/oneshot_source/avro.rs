@@ -0,0 +1,152 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Avro Object Container File [`OneshotFormat`].
+
+use std::str::FromStr;
+
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use mz_avro::schema::{resolve_schemas, SchemaPieceOrNamed};
+use mz_avro::{BlockDecoder, Schema};
+use mz_repr::{RelationDesc, Row};
+use serde::{Deserialize, Serialize};
+
+use crate::oneshot_source::{
+    OneshotFormat, OneshotObject, OneshotSource, StorageErrorX, StorageErrorXContext,
+    StorageErrorXKind,
+};
+
+#[derive(Debug, Clone)]
+pub struct AvroFormat {
+    desc: RelationDesc,
+}
+
+impl AvroFormat {
+    pub fn new(desc: RelationDesc) -> Self {
+        AvroFormat { desc }
+    }
+
+    /// Resolves the writer schema of a file against a reader schema for our [`RelationDesc`].
+    ///
+    /// This is where incompatible types are detected, e.g. a column that is missing from the
+    /// file or a field that can't be promoted to the type of its column.
+    fn resolve(&self, writer_schema: &Schema) -> Result<Schema, StorageErrorX> {
+        // Schema resolution requires the names of the top-level records to match.
+        let SchemaPieceOrNamed::Named(idx) = writer_schema.top else {
+            let msg = "the top-level Avro schema must be a record";
+            return Err(StorageErrorXKind::invalid_record_batch(msg).into());
+        };
+        let fullname = writer_schema.lookup(idx).name.human_name();
+        let reader_schema =
+            mz_interchange::avro::relationdesc_to_reader_schema(&self.desc, &fullname)
+                .map_err(|err| StorageErrorXKind::AvroError(format!("{err:#}").into()))
+                .context("reader schema")?;
+
+        resolve_schemas(writer_schema, &reader_schema).context("resolve schemas")
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AvroWorkRequest<O, C> {
+    object: O,
+    checksum: C,
+}
+
+/// A block of records from an Avro Object Container File.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AvroBlock {
+    /// JSON of the schema the records were written with.
+    writer_schema: String,
+    /// Raw, decompressed, bytes of the records.
+    bytes: Vec<u8>,
+    /// Number of records in the block.
+    len: usize,
+}
+
+impl OneshotFormat for AvroFormat {
+    type WorkRequest<S>
+        = AvroWorkRequest<S::Object, S::Checksum>
+    where
+        S: OneshotSource;
+    type RecordChunk = AvroBlock;
+
+    async fn split_work<S: OneshotSource + Send>(
+        &self,
+        _source: S,
+        object: S::Object,
+        checksum: S::Checksum,
+    ) -> Result<Vec<Self::WorkRequest<S>>, StorageErrorX> {
+        // TODO(cf3): Object Container Files are split into blocks by sync markers, so we could
+        // fetch ranges of a file in parallel and scan for the first marker in each.
+        tracing::info!(object = object.name(), "splitting Avro object");
+        Ok(vec![AvroWorkRequest { object, checksum }])
+    }
+
+    fn fetch_work<'a, S: OneshotSource + Sync + 'static>(
+        &'a self,
+        source: &'a S,
+        request: Self::WorkRequest<S>,
+    ) -> BoxStream<'a, Result<Self::RecordChunk, StorageErrorX>> {
+        let AvroWorkRequest { object, checksum } = request;
+
+        async_stream::try_stream! {
+            let mut decoder = BlockDecoder::new();
+            let mut writer_schema = None;
+            let mut bytes = source.get(object.clone(), checksum, None);
+            while let Some(chunk) = bytes.next().await {
+                decoder.push(&chunk?);
+                loop {
+                    let block = decoder.next_block().context("block")?;
+                    // Resolve the schema as soon as the header arrives, so incompatible files
+                    // fail before we decode any of their records.
+                    if writer_schema.is_none() {
+                        if let Some(schema) = decoder.writer_schema() {
+                            self.resolve(schema)
+                                .context(format!("object {}", object.name()))?;
+                            writer_schema = Some(schema.to_string());
+                        }
+                    }
+                    let Some(block) = block else {
+                        break;
+                    };
+                    yield AvroBlock {
+                        writer_schema: writer_schema.clone().expect("header decoded"),
+                        bytes: block.bytes,
+                        len: block.len,
+                    };
+                }
+            }
+            decoder.finish().context("end of file")?;
+        }
+        .boxed()
+    }
+
+    fn decode_chunk(
+        &self,
+        chunk: Self::RecordChunk,
+        rows: &mut Vec<Row>,
+    ) -> Result<usize, StorageErrorX> {
+        let AvroBlock {
+            writer_schema,
+            bytes,
+            len,
+        } = chunk;
+
+        // Blocks of the same file are decoded by different workers, so each of them resolves
+        // the schema again. This is cheap compared to decoding the records of a block.
+        let writer_schema = Schema::from_str(&writer_schema).context("writer schema")?;
+        let resolved_schema = self.resolve(&writer_schema)?;
+        mz_interchange::avro::decode_ocf_block(&resolved_schema, &bytes, len, rows)
+            .map_err(|err| StorageErrorXKind::invalid_record_batch(format!("{err:#}")))
+            .context("decode")?;
+
+        Ok(len)
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Newline-delimited JSON and JSON array [`OneshotFormat`].
+
+use std::io;
+use std::sync::Arc;
+
+use futures::stream::{BoxStream, StreamExt};
+use futures::TryStreamExt;
+use mz_pgcopy::CopyJsonFormatParams;
+use mz_repr::{RelationDesc, Row};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio_util::io::StreamReader;
+
+use crate::oneshot_source::util;
+use crate::oneshot_source::{
+    Encoding, OneshotFormat, OneshotObject, OneshotSource, StorageErrorX, StorageErrorXContext,
+    StorageErrorXKind,
+};
+
+#[derive(Debug, Clone)]
+pub struct JsonFormat {
+    /// Properties of the JSON reader.
+    params: CopyJsonFormatParams,
+    /// Types of the table we're copying into.
+    column_types: Arc<[mz_pgrepr::Type]>,
+}
+
+impl JsonFormat {
+    pub fn new(params: CopyJsonFormatParams, desc: &RelationDesc) -> Self {
+        let column_types = desc
+            .iter_types()
+            .map(|x| &x.scalar_type)
+            .map(mz_pgrepr::Type::from)
+            .collect();
+        JsonFormat {
+            params,
+            column_types,
+        }
+    }
+}
+
+/// Instructions on how to parse a single JSON file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonWorkRequest<O, C> {
+    object: O,
+    checksum: C,
+    encodings: SmallVec<[Encoding; 1]>,
+}
+
+/// The text of a single JSON value.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonRecord(String);
+
+impl OneshotFormat for JsonFormat {
+    type WorkRequest<S>
+        = JsonWorkRequest<S::Object, S::Checksum>
+    where
+        S: OneshotSource;
+    type RecordChunk = JsonRecord;
+
+    async fn split_work<S: OneshotSource + Send>(
+        &self,
+        _source: S,
+        object: S::Object,
+        checksum: S::Checksum,
+    ) -> Result<Vec<Self::WorkRequest<S>>, StorageErrorX> {
+        // Like CSV, we can't know where a record starts without reading the
+        // object from the beginning, so each object is a single unit of work.
+        let encodings = util::encodings_from_name(object.name());
+        let request = JsonWorkRequest {
+            object,
+            checksum,
+            encodings,
+        };
+        Ok(vec![request])
+    }
+
+    fn fetch_work<'a, S: OneshotSource + Sync + 'static>(
+        &'a self,
+        source: &'a S,
+        request: Self::WorkRequest<S>,
+    ) -> BoxStream<'a, Result<Self::RecordChunk, StorageErrorX>> {
+        let JsonWorkRequest {
+            object,
+            checksum,
+            encodings,
+        } = request;
+
+        // Wrap our `Stream<Bytes>` into a type that implements `tokio::io::AsyncRead`.
+        let raw_byte_stream = source
+            .get(object, checksum, None)
+            .map_err(|e| io::Error::new(io::ErrorKind::Interrupted, format!("{e:?}")));
+        let reader = util::decompress(StreamReader::new(raw_byte_stream), encodings);
+        let reader = BufReader::new(reader);
+
+        if self.params.array {
+            // A top-level array has to be parsed in its entirety before we
+            // know it's valid, so buffer the whole object.
+            let work = async move {
+                let mut reader = reader;
+                let mut bytes = Vec::new();
+                reader
+                    .read_to_end(&mut bytes)
+                    .await
+                    .map_err(|err| StorageErrorXKind::generic(err).with_context("json reading"))?;
+                let values: Vec<serde_json::Value> =
+                    serde_json::from_slice(&bytes).context("json array")?;
+                let records: Vec<_> = values
+                    .into_iter()
+                    .map(|value| Ok::<_, StorageErrorX>(JsonRecord(value.to_string())))
+                    .collect();
+                Ok::<_, StorageErrorX>(futures::stream::iter(records))
+            };
+            futures::stream::once(work).try_flatten().boxed()
+        } else {
+            futures::stream::try_unfold(reader.lines(), |mut lines| async move {
+                loop {
+                    let line = lines.next_line().await.map_err(|err| {
+                        StorageErrorXKind::generic(err).with_context("json reading")
+                    })?;
+                    match line {
+                        None => return Ok(None),
+                        // Skip blank lines, e.g. a trailing newline.
+                        Some(line) if line.trim().is_empty() => continue,
+                        Some(line) => return Ok(Some((JsonRecord(line), lines))),
+                    }
+                }
+            })
+            .boxed()
+        }
+    }
+
+    fn decode_chunk(
+        &self,
+        chunk: Self::RecordChunk,
+        rows: &mut Vec<Row>,
+    ) -> Result<usize, StorageErrorX> {
+        let JsonRecord(text) = chunk;
+
+        // Make sure every column has a value to decode.
+        let num_values = match &self.params.paths {
+            Some(paths) => paths.len(),
+            None => 1,
+        };
+        if self.column_types.len() != num_values {
+            let msg = format!(
+                "wrong number of columns, desc: {} json: {}",
+                self.column_types.len(),
+                num_values
+            );
+            return Err(StorageErrorXKind::invalid_record_batch(msg).into());
+        }
+
+        let value: serde_json::Value = serde_json::from_str(&text).context("json value")?;
+        let mut row = Row::default();
+        mz_pgcopy::pack_copy_json_value(
+            &value,
+            &self.column_types,
+            self.params.paths.as_deref(),
+            &mut row,
+        )
+        .map_err(|err| {
+            StorageErrorXKind::invalid_record_batch(err.to_string()).with_context("decode json")
+        })?;
+        rows.push(row);
+
+        Ok(1)
+    }
+}
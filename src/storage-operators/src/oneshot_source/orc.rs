@@ -0,0 +1,181 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! ORC [`OneshotFormat`].
+
+use std::fmt;
+use std::io;
+
+use arrow::array::StructArray;
+use bytes::{Bytes, BytesMut};
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use mz_ore::cast::CastFrom;
+use mz_repr::{RelationDesc, Row};
+use orc_rust::reader::AsyncChunkReader;
+use orc_rust::ArrowReaderBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::oneshot_source::parquet::ParquetRowGroup;
+use crate::oneshot_source::{
+    OneshotFormat, OneshotObject, OneshotSource, StorageErrorX, StorageErrorXContext,
+    StorageErrorXKind,
+};
+
+#[derive(Debug, Clone)]
+pub struct OrcFormat {
+    desc: RelationDesc,
+}
+
+impl OrcFormat {
+    pub fn new(desc: RelationDesc) -> Self {
+        OrcFormat { desc }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrcWorkRequest<O, C> {
+    object: O,
+    checksum: C,
+}
+
+/// A batch of records read from an ORC file.
+///
+/// ORC files are read into Arrow [`RecordBatch`](arrow::array::RecordBatch)es, just like Parquet
+/// files, so we reuse the serialization of [`ParquetRowGroup`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrcRecordBatch(ParquetRowGroup);
+
+impl OneshotFormat for OrcFormat {
+    type WorkRequest<S>
+        = OrcWorkRequest<S::Object, S::Checksum>
+    where
+        S: OneshotSource;
+    type RecordChunk = OrcRecordBatch;
+
+    async fn split_work<S: OneshotSource + Send>(
+        &self,
+        _source: S,
+        object: S::Object,
+        checksum: S::Checksum,
+    ) -> Result<Vec<Self::WorkRequest<S>>, StorageErrorX> {
+        // TODO(cf3): Split up the file by its stripes, like we split Parquet files by RowGroups.
+        tracing::info!(object = object.name(), "splitting ORC object");
+        Ok(vec![OrcWorkRequest { object, checksum }])
+    }
+
+    fn fetch_work<'a, S: OneshotSource + Sync + 'static>(
+        &'a self,
+        source: &'a S,
+        request: Self::WorkRequest<S>,
+    ) -> BoxStream<'a, Result<Self::RecordChunk, StorageErrorX>> {
+        let OrcWorkRequest { object, checksum } = request;
+
+        let adapter = OrcReaderAdapter::new(source.clone(), object, checksum);
+
+        let initial_work = async move {
+            let reader = ArrowReaderBuilder::try_new_async(adapter)
+                .await?
+                .build_async()
+                .map_err(|err| StorageErrorXKind::OrcError(err.to_string().into()));
+            Ok::<_, StorageErrorXKind>(reader)
+        };
+
+        futures::stream::once(initial_work)
+            .try_flatten()
+            .map_ok(|record_batch| OrcRecordBatch(ParquetRowGroup { record_batch }))
+            .err_into()
+            .boxed()
+    }
+
+    fn decode_chunk(
+        &self,
+        chunk: Self::RecordChunk,
+        rows: &mut Vec<Row>,
+    ) -> Result<usize, StorageErrorX> {
+        let OrcRecordBatch(ParquetRowGroup { record_batch }) = chunk;
+
+        let struct_array = StructArray::from(record_batch);
+        let reader = mz_arrow_util::reader::ArrowReader::new(&self.desc, struct_array)
+            .map_err(|err| StorageErrorXKind::OrcError(err.to_string().into()))
+            .context("reader")?;
+        let rows_read = reader
+            .read_all(rows)
+            .map_err(|err| StorageErrorXKind::OrcError(err.to_string().into()))
+            .context("read_all")?;
+
+        Ok(rows_read)
+    }
+}
+
+/// A newtype wrapper around a [`OneshotSource`] that allows us to implement
+/// [`AsyncChunkReader`] for all types that implement [`OneshotSource`].
+struct OrcReaderAdapter<S: OneshotSource> {
+    source: S,
+    object: S::Object,
+    checksum: S::Checksum,
+}
+
+impl<S: OneshotSource> fmt::Debug for OrcReaderAdapter<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrcReaderAdapter").finish()
+    }
+}
+
+impl<S: OneshotSource> OrcReaderAdapter<S> {
+    fn new(source: S, object: S::Object, checksum: S::Checksum) -> Self {
+        OrcReaderAdapter {
+            source,
+            object,
+            checksum,
+        }
+    }
+}
+
+impl<S: OneshotSource> AsyncChunkReader for OrcReaderAdapter<S> {
+    fn len(&mut self) -> BoxFuture<'_, io::Result<u64>> {
+        let len = u64::cast_from(self.object.size());
+        Box::pin(async move { Ok(len) })
+    }
+
+    fn get_bytes(
+        &mut self,
+        offset_from_start: u64,
+        length: u64,
+    ) -> BoxFuture<'_, io::Result<Bytes>> {
+        let start = usize::cast_from(offset_from_start);
+        let length = usize::cast_from(length);
+
+        Box::pin(async move {
+            if length == 0 {
+                return Ok(Bytes::new());
+            }
+
+            // Fetch the specified range.
+            let inclusive_end = start + length - 1;
+            let bytes: Vec<_> = self
+                .source
+                .get(
+                    self.object.clone(),
+                    self.checksum.clone(),
+                    Some(start..=inclusive_end),
+                )
+                .try_collect()
+                .await
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+
+            // Join the stream into a single chunk.
+            let mut joined_bytes = BytesMut::with_capacity(length);
+            joined_bytes.extend(bytes);
+
+            Ok(joined_bytes.freeze())
+        })
+    }
+}
@@ -54,7 +54,7 @@ pub struct ParquetWorkRequest<O, C> {
 
 #[derive(Clone, Debug)]
 pub struct ParquetRowGroup {
-    record_batch: RecordBatch,
+    pub(crate) record_batch: RecordBatch,
 }
 
 impl OneshotFormat for ParquetFormat {
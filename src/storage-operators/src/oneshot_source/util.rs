@@ -9,6 +9,54 @@
 
 //! Utility functions for Oneshot sources.
 
+use std::pin::Pin;
+
+use smallvec::{smallvec, SmallVec};
+use tokio::io::{AsyncBufRead, AsyncRead};
+
+use crate::oneshot_source::Encoding;
+
+/// Infers the [`Encoding`]s of an object from the extension of its name.
+///
+/// TODO(cf1): Check the encodings from the object itself, and support the user
+/// manually specifying certain encodings.
+pub fn encodings_from_name(name: &str) -> SmallVec<[Encoding; 1]> {
+    if name.ends_with(".gz") {
+        smallvec![Encoding::Gzip]
+    } else if name.ends_with(".bz2") {
+        smallvec![Encoding::Bzip2]
+    } else if name.ends_with(".xz") {
+        smallvec![Encoding::Xz]
+    } else if name.ends_with(".zst") {
+        smallvec![Encoding::Zstd]
+    } else {
+        smallvec![]
+    }
+}
+
+/// Wraps `reader` so the bytes it returns are decompressed according to `encodings`.
+pub fn decompress<'a, R>(
+    reader: R,
+    encodings: SmallVec<[Encoding; 1]>,
+) -> Pin<Box<dyn AsyncRead + Send + 'a>>
+where
+    R: AsyncBufRead + Send + 'a,
+{
+    // TODO(cf3): Support multiple encodings.
+    assert!(encodings.len() <= 1, "TODO support multiple encodings");
+
+    let Some(encoding) = encodings.into_iter().next() else {
+        return Box::pin(reader);
+    };
+    tracing::info!(?encoding, "decompressing byte stream");
+    match encoding {
+        Encoding::Bzip2 => Box::pin(async_compression::tokio::bufread::BzDecoder::new(reader)),
+        Encoding::Gzip => Box::pin(async_compression::tokio::bufread::GzipDecoder::new(reader)),
+        Encoding::Xz => Box::pin(async_compression::tokio::bufread::XzDecoder::new(reader)),
+        Encoding::Zstd => Box::pin(async_compression::tokio::bufread::ZstdDecoder::new(reader)),
+    }
+}
+
 /// Utility trait for converting various Rust Range types into a header value.
 /// according to the MDN Web Docs.
 ///
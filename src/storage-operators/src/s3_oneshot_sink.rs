@@ -39,7 +39,7 @@ use timely::progress::Antichain;
 use timely::PartialOrder;
 use tracing::debug;
 
-mod parquet;
+pub(crate) mod parquet;
 mod pgcopy;
 
 /// Copy the rows from the input collection to s3.
@@ -9,6 +9,8 @@
 
 use std::sync::Arc;
 
+use arrow::datatypes::{Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
 use aws_types::sdk_config::SdkConfig;
 use mz_arrow_util::builder::ArrowBuilder;
 use mz_aws_util::s3_uploader::{
@@ -245,6 +247,7 @@ impl ParquetUploader {
             self.arrow_builder_buffer_bytes,
             self.row_group_size_bytes,
             u64::cast_from(self.params.s3_multipart_part_size_bytes),
+            None,
         )
         .run_in_task(|| "ParquetFile::new")
         .await?;
@@ -256,7 +259,7 @@ impl ParquetUploader {
 
 /// Helper to tie the lifecycle of the `ArrowBuilder`, `ArrowWriter`, and `S3MultiPartUploader`
 /// together for a single parquet file.
-struct ParquetFile {
+pub(crate) struct ParquetFile {
     /// The active arrow builder.
     builder: ArrowBuilder,
     writer: ArrowWriter<Vec<u8>>,
@@ -266,10 +269,19 @@ struct ParquetFile {
     arrow_builder_buffer_bytes: u64,
     row_group_size: u64,
     desc: Arc<RelationDesc>,
+    /// The schema of the file, if it differs from the schema produced by the `ArrowBuilder`.
+    schema: Option<SchemaRef>,
+    /// The number of rows added to the file.
+    row_count: u64,
 }
 
 impl ParquetFile {
-    async fn new(
+    /// Starts the upload of a new parquet file.
+    ///
+    /// If `field_ids` is provided, the top-level columns of the file are annotated with the
+    /// given parquet field ids, in order. Table formats like Iceberg use these to map columns
+    /// to their table schema.
+    pub(crate) async fn new(
         bucket: String,
         key: String,
         desc: Arc<RelationDesc>,
@@ -277,12 +289,24 @@ impl ParquetFile {
         arrow_builder_buffer_bytes: u64,
         row_group_size: u64,
         part_size_limit: u64,
+        field_ids: Option<Vec<i32>>,
     ) -> Result<Self, anyhow::Error> {
         let builder = ArrowBuilder::new(
             &desc,
             DEFAULT_ARRAY_BUILDER_ITEM_CAPACITY,
             DEFAULT_ARRAY_BUILDER_DATA_CAPACITY,
         )?;
+        let schema = match field_ids {
+            Some(field_ids) => Some(Arc::new(schema_with_field_ids(
+                &builder.schema(),
+                &field_ids,
+            )?)),
+            None => None,
+        };
+        let writer_schema = match &schema {
+            Some(schema) => Arc::clone(schema),
+            None => builder.schema().into(),
+        };
 
         let props = WriterProperties::builder()
             // This refers to the number of rows per row-group, which we don't want the writer
@@ -295,7 +319,7 @@ impl ParquetFile {
             .build();
 
         // TODO: Consider using an lgalloc buffer here instead of a vec
-        let writer = ArrowWriter::try_new(Vec::new(), builder.schema().into(), Some(props))?;
+        let writer = ArrowWriter::try_new(Vec::new(), writer_schema, Some(props))?;
         let uploader = S3MultiPartUploader::try_new(
             sdk_config.as_ref(),
             bucket,
@@ -320,11 +344,14 @@ impl ParquetFile {
             row_group_size,
             desc,
             builder,
+            schema,
+            row_count: 0,
         })
     }
 
-    fn add_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+    pub(crate) fn add_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
         self.builder.add_row(row)?;
+        self.row_count += 1;
 
         if u64::cast_from(self.builder.row_size_bytes()) > self.arrow_builder_buffer_bytes {
             self.flush_builder()?;
@@ -334,7 +361,7 @@ impl ParquetFile {
     }
 
     /// Flush the current arrow builder, the parquet writer, and the uploader.
-    async fn finish(mut self) -> Result<CompletedUpload, anyhow::Error> {
+    pub(crate) async fn finish(mut self) -> Result<CompletedUpload, anyhow::Error> {
         self.flush_builder()?;
         let buffer = self.writer.into_inner()?;
         self.uploader.buffer_chunk(buffer.as_slice())?;
@@ -357,7 +384,10 @@ impl ParquetFile {
                 DEFAULT_ARRAY_BUILDER_DATA_CAPACITY,
             )?,
         );
-        let arrow_batch = builder.to_record_batch()?;
+        let mut arrow_batch = builder.to_record_batch()?;
+        if let Some(schema) = &self.schema {
+            arrow_batch = RecordBatch::try_new(Arc::clone(schema), arrow_batch.columns().to_vec())?;
+        }
 
         if arrow_batch.num_rows() == 0 {
             return Ok(());
@@ -383,11 +413,42 @@ impl ParquetFile {
         Ok(())
     }
 
+    /// Returns the number of rows added to the file.
+    pub(crate) fn row_count(&self) -> u64 {
+        self.row_count
+    }
+
     /// Returns an approximate size estimate of the file being written.
-    fn size_estimate(&self) -> u64 {
+    pub(crate) fn size_estimate(&self) -> u64 {
         // ArrowWriter.in_progress_size() is just an estimate since it doesn't seem
         // to account for data page compression and metadata that will be written for the next
         // row-group.
         u64::cast_from(self.writer.in_progress_size()) + self.uploader.added_bytes()
     }
 }
+
+/// Returns a copy of `schema` with the parquet field id of each top-level field set to the
+/// corresponding entry of `field_ids`.
+fn schema_with_field_ids(schema: &Schema, field_ids: &[i32]) -> Result<Schema, anyhow::Error> {
+    if schema.fields().len() != field_ids.len() {
+        anyhow::bail!(
+            "expected {} field ids, got {}",
+            schema.fields().len(),
+            field_ids.len()
+        );
+    }
+    let fields: Vec<Field> = schema
+        .fields()
+        .iter()
+        .zip(field_ids)
+        .map(|(field, id)| {
+            let mut metadata = field.metadata().clone();
+            metadata.insert(
+                parquet::arrow::PARQUET_FIELD_ID_META_KEY.to_string(),
+                id.to_string(),
+            );
+            field.as_ref().clone().with_metadata(metadata)
+        })
+        .collect();
+    Ok(Schema::new(fields))
+}
@@ -0,0 +1,699 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Writing the data files and manifests of continuous S3 sinks.
+//!
+//! A continuous S3 sink writes the updates of every range of timestamps `[lower, upper)` to a
+//! batch of data files and then commits the batch by writing a manifest to
+//! `_manifests/{lower}.json`. The manifest lists the data files of the batch and records `upper`,
+//! which is the `lower` of the next batch. The `_progress.json` object records the id and version
+//! of the sink and the frontier up to which batches have been committed.
+//!
+//! Manifests are only ever written for the batch that starts at the frontier recorded in the
+//! progress object, and the progress object is only advanced after the manifest has been written,
+//! so the committed batches always form a contiguous chain of manifests. A sink that restarts
+//! follows that chain from the recorded frontier, which makes a manifest that was written right
+//! before a crash count as committed, and resumes at its end. Every batch is therefore committed
+//! exactly once, even though its data files might be written several times.
+//!
+//! Data files that were written for a batch that was never committed are not referenced by any
+//! manifest. Readers that need exactly-once semantics must only read the files listed in
+//! manifests.
+//!
+//! Several instances of a sink can run at the same time, for example while a replica restarts or
+//! when a newer version of the sink has been started. All writes of the manifests and of the
+//! progress object are therefore conditional: a manifest is only written if no manifest exists for
+//! its `lower` yet, and the progress object is only written if it hasn't changed since the store
+//! last read or wrote it. A store that finds a condition violated has been fenced off by another
+//! instance and stops writing. A fenced off instance can at worst have written the manifest of the
+//! batch that starts at the frontier, which extends the chain like any other manifest.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context};
+use aws_types::sdk_config::SdkConfig;
+use mz_aws_util::s3::Client;
+use mz_aws_util::s3_uploader::{
+    CompletedUpload, S3MultiPartUploader, S3MultiPartUploaderConfig, AWS_S3_MAX_PART_COUNT,
+};
+use mz_ore::future::OreFutureExt;
+use mz_pgcopy::{encode_copy_format, encode_copy_format_header};
+use mz_repr::{GlobalId, RelationDesc, Row, Timestamp};
+use mz_storage_types::sinks::s3::S3SinkLocation;
+use mz_storage_types::sinks::S3SinkFormat;
+use serde::{Deserialize, Serialize};
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::s3_oneshot_sink::parquet::ParquetFile;
+
+/// The ratio (defined as a percentage) of row-group size to target file size.
+const PARQUET_ROW_GROUP_RATIO: u64 = 20;
+/// The ratio (defined as a percentage) of arrow-builder size to row-group size.
+const ARROW_BUILDER_BUFFER_RATIO: u64 = 150;
+/// The size of each part in the multi-part uploads of data files.
+const MULTIPART_PART_SIZE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// A data file that belongs to a batch.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BatchFile {
+    /// The full `s3://` URI of the file.
+    pub path: String,
+    /// The partition the file belongs to.
+    pub partition: String,
+    pub record_count: u64,
+    pub file_size_in_bytes: u64,
+}
+
+/// The manifest that commits a batch.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    sink_id: String,
+    sink_version: u64,
+    lower: u64,
+    /// The upper of the batch, or `None` if the batch is the last one the sink writes.
+    upper: Option<u64>,
+    files: Vec<BatchFile>,
+}
+
+/// The contents of the progress object of a sink.
+#[derive(Debug, Serialize, Deserialize)]
+struct Progress {
+    sink_id: String,
+    sink_version: u64,
+    /// The frontier up to which batches have been committed, or `None` if the sink has written
+    /// all of its batches.
+    frontier: Option<u64>,
+}
+
+/// The files of a continuous S3 sink.
+pub struct S3SinkStore {
+    client: Client,
+    sdk_config: Arc<SdkConfig>,
+    location: S3SinkLocation,
+    sink_id: GlobalId,
+    sink_version: u64,
+    /// The frontier up to which batches have been committed.
+    frontier: Antichain<Timestamp>,
+    /// The entity tag of the progress object that this store last wrote.
+    progress_e_tag: String,
+}
+
+impl S3SinkStore {
+    /// Opens the files of the sink at `location` and claims them for the given version of the
+    /// sink.
+    ///
+    /// Fails if the location holds files that were not written by this sink, or if it has been
+    /// claimed by a newer version of the sink.
+    pub async fn open(
+        sdk_config: SdkConfig,
+        location: S3SinkLocation,
+        sink_id: GlobalId,
+        sink_version: u64,
+    ) -> Result<Self, anyhow::Error> {
+        let client = mz_aws_util::s3::new_client(&sdk_config);
+        let (progress, e_tag) = match load_progress(&client, &location).await? {
+            Some((progress, e_tag)) => (progress, Some(e_tag)),
+            None => {
+                // A location without progress must not hold any files, so that the sink never
+                // mixes its files with those of something else.
+                let files =
+                    mz_aws_util::s3::list_bucket_path(&client, &location.bucket, &location.prefix)
+                        .await?;
+                if files.is_some_and(|files| !files.is_empty()) {
+                    bail!(
+                        "S3 sink location {} is not empty",
+                        location.object_uri(&location.prefix)
+                    );
+                }
+                let progress = Progress {
+                    sink_id: sink_id.to_string(),
+                    sink_version,
+                    frontier: Some(Timestamp::minimum().into()),
+                };
+                (progress, None)
+            }
+        };
+        check_progress(sink_id, sink_version, &progress)?;
+
+        // The progress object is advanced after a manifest is written, so it can lag behind.
+        let mut frontier = progress.frontier;
+        while let Some(lower) = frontier {
+            let key = location.manifest_key(lower);
+            let Some(bytes) =
+                mz_aws_util::s3::get_object_if_exists(&client, &location.bucket, &key).await?
+            else {
+                break;
+            };
+            let manifest: Manifest =
+                serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", key))?;
+            if manifest.lower != lower {
+                bail!("manifest {} has unexpected lower {}", key, manifest.lower);
+            }
+            frontier = manifest.upper;
+        }
+        let frontier =
+            frontier.map_or_else(Antichain::new, |t| Antichain::from_elem(Timestamp::from(t)));
+
+        // Claiming the location fences out older versions of the sink, and any other instance
+        // that read the progress object before us.
+        let progress_e_tag = write_progress(
+            &client,
+            &location,
+            sink_id,
+            sink_version,
+            &frontier,
+            e_tag.as_deref(),
+        )
+        .await?;
+        Ok(S3SinkStore {
+            client,
+            sdk_config: Arc::new(sdk_config),
+            location,
+            sink_id,
+            sink_version,
+            frontier,
+            progress_e_tag,
+        })
+    }
+
+    /// The frontier up to which batches have been committed.
+    pub fn frontier(&self) -> &Antichain<Timestamp> {
+        &self.frontier
+    }
+
+    /// Returns a writer for the data files of a new batch.
+    pub fn batch_writer(
+        &self,
+        format: S3SinkFormat,
+        desc: Arc<RelationDesc>,
+        target_file_size: u64,
+    ) -> BatchWriter {
+        BatchWriter {
+            sdk_config: Arc::clone(&self.sdk_config),
+            location: self.location.clone(),
+            format,
+            desc,
+            target_file_size,
+            active_file: None,
+            files: vec![],
+        }
+    }
+
+    /// Commits the batch of `files` that holds all updates between the current frontier and
+    /// `upper`, and advances the frontier to `upper`.
+    ///
+    /// Batches without files only advance the frontier. Fails if another instance of the sink has
+    /// fenced off this store.
+    pub async fn commit(
+        &mut self,
+        upper: Antichain<Timestamp>,
+        files: Vec<BatchFile>,
+    ) -> Result<(), anyhow::Error> {
+        let Some(lower) = self.frontier.as_option().copied() else {
+            bail!("S3 sink has already committed all of its batches");
+        };
+        // Not required for correctness, but avoids writing the manifest of a batch if we have
+        // already been fenced off.
+        let progress_key = self.location.progress_key();
+        if !mz_aws_util::s3::object_has_e_tag(
+            &self.client,
+            &self.location.bucket,
+            &progress_key,
+            &self.progress_e_tag,
+        )
+        .await?
+        {
+            bail!(fenced(&progress_key));
+        }
+
+        if !files.is_empty() {
+            let key = self.location.manifest_key(lower.into());
+            let manifest = Manifest {
+                sink_id: self.sink_id.to_string(),
+                sink_version: self.sink_version,
+                lower: lower.into(),
+                upper: upper.as_option().map(|t| (*t).into()),
+                files,
+            };
+            let written = mz_aws_util::s3::put_object_if(
+                &self.client,
+                &self.location.bucket,
+                &key,
+                None,
+                serde_json::to_vec(&manifest)?,
+            )
+            .await
+            .with_context(|| format!("writing {}", key))?;
+            if written.is_none() {
+                bail!(fenced(&key));
+            }
+        }
+
+        self.progress_e_tag = write_progress(
+            &self.client,
+            &self.location,
+            self.sink_id,
+            self.sink_version,
+            &upper,
+            Some(&self.progress_e_tag),
+        )
+        .await?;
+        self.frontier = upper;
+        Ok(())
+    }
+}
+
+/// Writes the progress object of a sink, if its entity tag is still `e_tag`, or if it doesn't
+/// exist and `e_tag` is `None`.
+///
+/// Returns the entity tag of the new progress object.
+async fn write_progress(
+    client: &Client,
+    location: &S3SinkLocation,
+    sink_id: GlobalId,
+    sink_version: u64,
+    frontier: &Antichain<Timestamp>,
+    e_tag: Option<&str>,
+) -> Result<String, anyhow::Error> {
+    let progress = Progress {
+        sink_id: sink_id.to_string(),
+        sink_version,
+        frontier: frontier.as_option().map(|t| (*t).into()),
+    };
+    let key = location.progress_key();
+    let written = mz_aws_util::s3::put_object_if(
+        client,
+        &location.bucket,
+        &key,
+        e_tag,
+        serde_json::to_vec(&progress)?,
+    )
+    .await
+    .context("writing S3 sink progress")?;
+    written.ok_or_else(|| anyhow!(fenced(&key)))
+}
+
+/// The error message of a store that lost a conditional write of `key` to another instance of the
+/// sink.
+fn fenced(key: &str) -> String {
+    format!("Fenced off by another instance of the sink: {key} was written concurrently")
+}
+
+/// Checks that the progress was written by this sink and not by a newer version of it.
+fn check_progress(
+    sink_id: GlobalId,
+    sink_version: u64,
+    progress: &Progress,
+) -> Result<(), anyhow::Error> {
+    if progress.sink_id != sink_id.to_string() {
+        bail!(
+            "S3 sink location is written to by another sink: {}",
+            progress.sink_id
+        );
+    }
+    if sink_version < progress.sink_version {
+        bail!(
+            "Fenced off by newer version of the sink. ours={} theirs={}",
+            sink_version,
+            progress.sink_version
+        );
+    }
+    Ok(())
+}
+
+/// Loads the progress object of a sink and its entity tag.
+async fn load_progress(
+    client: &Client,
+    location: &S3SinkLocation,
+) -> Result<Option<(Progress, String)>, anyhow::Error> {
+    let key = location.progress_key();
+    match mz_aws_util::s3::get_object_with_e_tag_if_exists(client, &location.bucket, &key).await? {
+        Some((bytes, e_tag)) => {
+            let progress =
+                serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", key))?;
+            Ok(Some((progress, e_tag)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Starts a Parquet data file at `key`, whose row groups are sized for files of about
+/// `target_file_size` bytes.
+///
+/// The Iceberg sink writes its data files the same way, with the given Iceberg `field_ids`.
+pub(crate) async fn new_parquet_file(
+    bucket: String,
+    key: String,
+    desc: Arc<RelationDesc>,
+    sdk_config: Arc<SdkConfig>,
+    target_file_size: u64,
+    field_ids: Option<Vec<i32>>,
+) -> Result<ParquetFile, anyhow::Error> {
+    let row_group_size = target_file_size * PARQUET_ROW_GROUP_RATIO / 100;
+    ParquetFile::new(
+        bucket,
+        key,
+        desc,
+        sdk_config,
+        row_group_size * ARROW_BUILDER_BUFFER_RATIO / 100,
+        row_group_size,
+        MULTIPART_PART_SIZE_BYTES,
+        field_ids,
+    )
+    .run_in_task(|| "ParquetFile::new")
+    .await
+}
+
+/// An in-progress upload of a data file.
+enum FileUpload {
+    Parquet(ParquetFile),
+    PgCopy {
+        uploader: S3MultiPartUploader,
+        record_count: u64,
+    },
+}
+
+/// Writes the data files of a batch, rolling over to a new file whenever the partition changes or
+/// the current file reaches the target file size.
+pub struct BatchWriter {
+    sdk_config: Arc<SdkConfig>,
+    location: S3SinkLocation,
+    format: S3SinkFormat,
+    desc: Arc<RelationDesc>,
+    target_file_size: u64,
+    /// The partition and upload of the current file.
+    active_file: Option<(String, FileUpload)>,
+    files: Vec<BatchFile>,
+}
+
+impl BatchWriter {
+    /// Appends a row to the current file of `partition`.
+    pub async fn add_row(&mut self, partition: &str, row: &Row) -> Result<(), anyhow::Error> {
+        if self
+            .active_file
+            .as_ref()
+            .is_some_and(|(active, _)| active != partition)
+        {
+            self.finish_active_file().await?;
+        }
+        if self.active_file.is_none() {
+            self.start_new_file(partition).await?;
+        }
+        let (_, upload) = self.active_file.as_mut().expect("started above");
+        let size = match upload {
+            FileUpload::Parquet(file) => {
+                file.add_row(row)?;
+                file.size_estimate()
+            }
+            FileUpload::PgCopy {
+                uploader,
+                record_count,
+            } => {
+                let S3SinkFormat::PgCopy(params) = &self.format else {
+                    unreachable!("pgcopy upload for {:?}", self.format);
+                };
+                let mut buf = vec![];
+                encode_copy_format(params, row, self.desc.typ(), &mut buf)
+                    .map_err(|_| anyhow!("error encoding row"))?;
+                uploader.buffer_chunk(&buf)?;
+                *record_count += 1;
+                uploader.added_bytes()
+            }
+        };
+        if size >= self.target_file_size {
+            self.finish_active_file().await?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the current file and returns all files written by this writer.
+    pub async fn finish(mut self) -> Result<Vec<BatchFile>, anyhow::Error> {
+        self.finish_active_file().await?;
+        Ok(self.files)
+    }
+
+    async fn start_new_file(&mut self, partition: &str) -> Result<(), anyhow::Error> {
+        let extension = match &self.format {
+            S3SinkFormat::PgCopy(params) => params.file_extension(),
+            S3SinkFormat::Parquet => "parquet",
+        };
+        let key = self
+            .location
+            .data_key(partition, &format!("part-{}.{}", Uuid::new_v4(), extension));
+        let bucket = self.location.bucket.clone();
+        let upload = match &self.format {
+            S3SinkFormat::Parquet => {
+                let file = new_parquet_file(
+                    bucket,
+                    key,
+                    Arc::clone(&self.desc),
+                    Arc::clone(&self.sdk_config),
+                    self.target_file_size,
+                    None,
+                )
+                .await?;
+                FileUpload::Parquet(file)
+            }
+            S3SinkFormat::PgCopy(params) => {
+                let sdk_config = Arc::clone(&self.sdk_config);
+                let mut uploader = async move {
+                    S3MultiPartUploader::try_new(
+                        &sdk_config,
+                        bucket,
+                        key,
+                        S3MultiPartUploaderConfig {
+                            part_size_limit: MULTIPART_PART_SIZE_BYTES,
+                            file_size_limit: MULTIPART_PART_SIZE_BYTES
+                                * u64::try_from(AWS_S3_MAX_PART_COUNT).expect("known safe"),
+                        },
+                    )
+                    .await
+                }
+                .run_in_task(|| "S3MultiPartUploader::try_new")
+                .await?;
+                if params.requires_header() {
+                    let mut buf = vec![];
+                    encode_copy_format_header(params, &self.desc, &mut buf)
+                        .map_err(|_| anyhow!("error encoding header"))?;
+                    uploader.buffer_chunk(&buf)?;
+                }
+                FileUpload::PgCopy {
+                    uploader,
+                    record_count: 0,
+                }
+            }
+        };
+        self.active_file = Some((partition.to_string(), upload));
+        Ok(())
+    }
+
+    async fn finish_active_file(&mut self) -> Result<(), anyhow::Error> {
+        let Some((partition, upload)) = self.active_file.take() else {
+            return Ok(());
+        };
+        let (upload, record_count) = match upload {
+            FileUpload::Parquet(file) => {
+                let record_count = file.row_count();
+                let upload = file.finish().run_in_task(|| "ParquetFile::finish").await?;
+                (upload, record_count)
+            }
+            FileUpload::PgCopy {
+                uploader,
+                record_count,
+            } => {
+                let upload = uploader
+                    .finish()
+                    .run_in_task(|| "S3MultiPartUploader::finish")
+                    .await?;
+                info!(
+                    "finished upload: bucket {}, key {}, bytes_uploaded {}, parts_uploaded {}",
+                    upload.bucket, upload.key, upload.total_bytes_uploaded, upload.part_count
+                );
+                (upload, record_count)
+            }
+        };
+        let CompletedUpload {
+            bucket: _,
+            key,
+            total_bytes_uploaded,
+            part_count: _,
+        } = upload;
+        self.files.push(BatchFile {
+            path: self.location.object_uri(&key),
+            partition,
+            record_count,
+            file_size_in_bytes: total_bytes_uploaded,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_check_progress() {
+        let sink_id = GlobalId::User(1);
+        let progress = Progress {
+            sink_id: sink_id.to_string(),
+            sink_version: 1,
+            frontier: Some(42),
+        };
+        assert!(check_progress(sink_id, 1, &progress).is_ok());
+        assert!(check_progress(sink_id, 2, &progress).is_ok());
+        // Newer versions of the sink fence out older ones.
+        assert!(check_progress(sink_id, 0, &progress).is_err());
+        // Locations belonging to other sinks are never written to.
+        assert!(check_progress(GlobalId::User(2), 1, &progress).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_manifest_format() {
+        let manifest = Manifest {
+            sink_id: "u1".into(),
+            sink_version: 0,
+            lower: 10,
+            upper: None,
+            files: vec![BatchFile {
+                path: "s3://bucket/prefix/date=2024-01-01/hour=00/part-0.csv".into(),
+                partition: "date=2024-01-01/hour=00".into(),
+                record_count: 3,
+                file_size_in_bytes: 12,
+            }],
+        };
+        assert_eq!(
+            serde_json::to_value(&manifest).unwrap(),
+            serde_json::json!({
+                "sink_id": "u1",
+                "sink_version": 0,
+                "lower": 10,
+                "upper": null,
+                "files": [{
+                    "path": "s3://bucket/prefix/date=2024-01-01/hour=00/part-0.csv",
+                    "partition": "date=2024-01-01/hour=00",
+                    "record_count": 3,
+                    "file_size_in_bytes": 12,
+                }],
+            })
+        );
+    }
+
+    /// Returns a location for a test sink below `MZ_S3_UPLOADER_TEST_S3_BUCKET`, or `None` if tests
+    /// against S3 are not enabled. See the tests of [`mz_aws_util::s3_uploader`] for how to enable
+    /// them.
+    fn s3_location_for_test() -> Option<S3SinkLocation> {
+        let bucket = match std::env::var("MZ_S3_UPLOADER_TEST_S3_BUCKET") {
+            Ok(bucket) => bucket,
+            Err(_) => {
+                if mz_ore::env::is_var_truthy("CI") {
+                    panic!("CI is supposed to run this test but something has gone wrong!");
+                }
+                return None;
+            }
+        };
+        Some(S3SinkLocation {
+            bucket,
+            prefix: format!("cargo_test/{}/sink", Uuid::new_v4()),
+        })
+    }
+
+    /// Opens the sink at `location` and commits the next batch with a single file called `name`.
+    async fn open_and_commit(
+        sdk_config: SdkConfig,
+        location: S3SinkLocation,
+        name: String,
+    ) -> Result<BatchFile, anyhow::Error> {
+        let mut store =
+            S3SinkStore::open(sdk_config, location.clone(), GlobalId::User(1), 0).await?;
+        let lower = u64::from(*store.frontier().as_option().expect("sink not finished"));
+        let file = BatchFile {
+            path: location.object_uri(&name),
+            partition: "p".into(),
+            record_count: 1,
+            file_size_in_bytes: 1,
+        };
+        let upper = Antichain::from_elem(Timestamp::from(lower + 1));
+        store.commit(upper, vec![file.clone()]).await?;
+        Ok(file)
+    }
+
+    #[mz_ore::test(tokio::test(flavor = "multi_thread"))]
+    #[cfg_attr(coverage, ignore)] // https://github.com/MaterializeInc/database-issues/issues/5586
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `TLS_method` on OS `linux`
+    async fn test_concurrent_writers() -> Result<(), anyhow::Error> {
+        let Some(location) = s3_location_for_test() else {
+            return Ok(());
+        };
+        let sdk_config = mz_aws_util::defaults().load().await;
+        let client = mz_aws_util::s3::new_client(&sdk_config);
+        let sink_id = GlobalId::User(1);
+        let file = |name: &str| BatchFile {
+            path: location.object_uri(name),
+            partition: "p".into(),
+            record_count: 1,
+            file_size_in_bytes: 1,
+        };
+
+        // Opening the location fences off the instances that opened it before.
+        let mut old = S3SinkStore::open(sdk_config.clone(), location.clone(), sink_id, 0).await?;
+        let mut new = S3SinkStore::open(sdk_config.clone(), location.clone(), sink_id, 0).await?;
+        let upper = Antichain::from_elem(Timestamp::from(1u64));
+        let err = old
+            .commit(upper.clone(), vec![file("old")])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Fenced off"), "{err:#}");
+        new.commit(upper, vec![file("new")]).await?;
+        let mut committed = vec![file("new")];
+
+        // Of two instances that race to commit the same batch, at most one succeeds, and the
+        // other one is fenced off.
+        for round in 0..5 {
+            let (a, b) = tokio::join!(
+                open_and_commit(sdk_config.clone(), location.clone(), format!("a-{round}")),
+                open_and_commit(sdk_config.clone(), location.clone(), format!("b-{round}")),
+            );
+            for res in [a, b] {
+                match res {
+                    Ok(file) => committed.push(file),
+                    Err(err) => assert!(err.to_string().starts_with("Fenced off"), "{err:#}"),
+                }
+            }
+        }
+
+        // The manifests form a contiguous chain that holds every batch that was committed
+        // successfully exactly once.
+        let mut chain = vec![];
+        let mut lower = 0;
+        while let Some(bytes) = mz_aws_util::s3::get_object_if_exists(
+            &client,
+            &location.bucket,
+            &location.manifest_key(lower),
+        )
+        .await?
+        {
+            let manifest: Manifest = serde_json::from_slice(&bytes)?;
+            assert_eq!(manifest.lower, lower);
+            assert_eq!(manifest.upper, Some(lower + 1));
+            chain.extend(manifest.files);
+            lower += 1;
+        }
+        for file in &committed {
+            assert_eq!(chain.iter().filter(|f| *f == file).count(), 1, "{file:?}");
+        }
+        assert!(!chain.contains(&file("old")));
+
+        Ok(())
+    }
+}
@@ -200,6 +200,7 @@ pub enum Connection<C: ConnectionAccess = InlinedConnection> {
     Aws(AwsConnection),
     AwsPrivatelink(AwsPrivatelinkConnection),
     MySql(MySqlConnection<C>),
+    SqlServer(SqlServerConnection<C>),
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
@@ -214,6 +215,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
             Connection::Aws(aws) => Connection::Aws(aws),
             Connection::AwsPrivatelink(awspl) => Connection::AwsPrivatelink(awspl),
             Connection::MySql(mysql) => Connection::MySql(mysql.into_inline_connection(r)),
+            Connection::SqlServer(sql_server) => {
+                Connection::SqlServer(sql_server.into_inline_connection(r))
+            }
         }
     }
 }
@@ -229,6 +233,7 @@ impl<C: ConnectionAccess> Connection<C> {
             Connection::Aws(conn) => conn.validate_by_default(),
             Connection::AwsPrivatelink(conn) => conn.validate_by_default(),
             Connection::MySql(conn) => conn.validate_by_default(),
+            Connection::SqlServer(conn) => conn.validate_by_default(),
         }
     }
 }
@@ -248,6 +253,7 @@ impl Connection<InlinedConnection> {
             Connection::Aws(conn) => conn.validate(id, storage_configuration).await?,
             Connection::AwsPrivatelink(conn) => conn.validate(id, storage_configuration).await?,
             Connection::MySql(conn) => conn.validate(id, storage_configuration).await?,
+            Connection::SqlServer(conn) => conn.validate(id, storage_configuration).await?,
         }
         Ok(())
     }
@@ -273,6 +279,13 @@ impl Connection<InlinedConnection> {
         }
     }
 
+    pub fn unwrap_sql_server(self) -> <InlinedConnection as ConnectionAccess>::SqlServer {
+        match self {
+            Self::SqlServer(conn) => conn,
+            o => unreachable!("{o:?} is not a SQL Server connection"),
+        }
+    }
+
     pub fn unwrap_aws(self) -> <InlinedConnection as ConnectionAccess>::Aws {
         match self {
             Self::Aws(conn) => conn,
@@ -332,6 +345,7 @@ impl<C: ConnectionAccess> AlterCompatible for Connection<C> {
             (Self::Kafka(s), Self::Kafka(o)) => s.alter_compatible(id, o),
             (Self::Postgres(s), Self::Postgres(o)) => s.alter_compatible(id, o),
             (Self::MySql(s), Self::MySql(o)) => s.alter_compatible(id, o),
+            (Self::SqlServer(s), Self::SqlServer(o)) => s.alter_compatible(id, o),
             _ => {
                 tracing::warn!(
                     "Connection incompatible:\nself:\n{:#?}\n\nother\n{:#?}",
@@ -1990,6 +2004,212 @@ impl<C: ConnectionAccess> AlterCompatible for MySqlConnection<C> {
     }
 }
 
+/// A connection to a SQL Server instance.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerConnection<C: ConnectionAccess = InlinedConnection> {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The database to connect to.
+    pub database: String,
+    /// The username to authenticate as.
+    pub user: StringOrSecret,
+    /// Password used for authentication.
+    pub password: CatalogItemId,
+    /// A tunnel through which to route traffic.
+    pub tunnel: Tunnel<C>,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<SqlServerConnection, R>
+    for SqlServerConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> SqlServerConnection {
+        let SqlServerConnection {
+            host,
+            port,
+            database,
+            user,
+            password,
+            tunnel,
+        } = self;
+
+        SqlServerConnection {
+            host,
+            port,
+            database,
+            user,
+            password,
+            tunnel: tunnel.into_inline_connection(r),
+        }
+    }
+}
+
+impl<C: ConnectionAccess> SqlServerConnection<C> {
+    fn validate_by_default(&self) -> bool {
+        true
+    }
+}
+
+impl SqlServerConnection<InlinedConnection> {
+    pub async fn config(
+        &self,
+        secrets_reader: &Arc<dyn mz_secrets::SecretsReader>,
+        storage_configuration: &StorageConfiguration,
+        in_task: InTask,
+    ) -> Result<mz_sql_server_util::Config, anyhow::Error> {
+        let user = self.user.get_string(in_task, secrets_reader).await?;
+        let password = secrets_reader
+            .read_string_in_task_if(in_task, self.password)
+            .await?;
+
+        let tunnel = match &self.tunnel {
+            Tunnel::Direct => {
+                // Ensure any host we connect to is resolved to an external address.
+                let resolved = resolve_address(
+                    &self.host,
+                    ENFORCE_EXTERNAL_ADDRESSES.get(storage_configuration.config_set()),
+                )
+                .await?;
+                mz_sql_server_util::TunnelConfig::Direct {
+                    resolved_ips: Some(resolved),
+                }
+            }
+            Tunnel::Ssh(SshTunnel {
+                connection_id,
+                connection,
+            }) => {
+                let secret = secrets_reader
+                    .read_in_task_if(in_task, *connection_id)
+                    .await?;
+                let key_pair = SshKeyPair::from_bytes(&secret)?;
+                // Ensure any ssh-bastion host we connect to is resolved to an external address.
+                let resolved = resolve_address(
+                    &connection.host,
+                    ENFORCE_EXTERNAL_ADDRESSES.get(storage_configuration.config_set()),
+                )
+                .await?;
+                mz_sql_server_util::TunnelConfig::Ssh {
+                    config: SshTunnelConfig {
+                        host: resolved
+                            .iter()
+                            .map(|a| a.to_string())
+                            .collect::<BTreeSet<_>>(),
+                        port: connection.port,
+                        user: connection.user.clone(),
+                        key_pair,
+                    },
+                    manager: storage_configuration
+                        .connection_context
+                        .ssh_tunnel_manager
+                        .clone(),
+                    timeout: storage_configuration.parameters.ssh_timeout_config,
+                }
+            }
+            Tunnel::AwsPrivatelink(connection) => {
+                assert_none!(connection.port);
+                mz_sql_server_util::TunnelConfig::AwsPrivatelink {
+                    connection_id: connection.connection_id,
+                }
+            }
+        };
+
+        Ok(mz_sql_server_util::Config::new(
+            self.host.clone(),
+            self.port,
+            self.database.clone(),
+            user,
+            password,
+            tunnel,
+            in_task,
+        ))
+    }
+
+    async fn validate(
+        &self,
+        _id: CatalogItemId,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<(), anyhow::Error> {
+        let config = self
+            .config(
+                &storage_configuration.connection_context.secrets_reader,
+                storage_configuration,
+                // We are in a normal tokio context during validation, already.
+                InTask::No,
+            )
+            .await?;
+        let (mut client, connection) = mz_sql_server_util::Client::connect(config).await?;
+        mz_ore::task::spawn(|| "sql-server-validate-connection", async move {
+            connection.await;
+        });
+        mz_sql_server_util::inspect::ensure_database_cdc_enabled(&mut client).await?;
+        Ok(())
+    }
+}
+
+impl RustType<ProtoSqlServerConnection> for SqlServerConnection {
+    fn into_proto(&self) -> ProtoSqlServerConnection {
+        ProtoSqlServerConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            database: self.database.into_proto(),
+            user: Some(self.user.into_proto()),
+            password: Some(self.password.into_proto()),
+            tunnel: Some(self.tunnel.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerConnection) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            database: proto.database,
+            user: proto
+                .user
+                .into_rust_if_some("ProtoSqlServerConnection::user")?,
+            password: proto
+                .password
+                .into_rust_if_some("ProtoSqlServerConnection::password")?,
+            tunnel: proto
+                .tunnel
+                .into_rust_if_some("ProtoSqlServerConnection::tunnel")?,
+        })
+    }
+}
+
+impl<C: ConnectionAccess> AlterCompatible for SqlServerConnection<C> {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        let SqlServerConnection {
+            tunnel,
+            database,
+            // All other options may change arbitrarily
+            host: _,
+            port: _,
+            user: _,
+            password: _,
+        } = self;
+
+        let compatibility_checks = [
+            (tunnel.alter_compatible(id, &other.tunnel).is_ok(), "tunnel"),
+            // The LSNs we track are only meaningful within a single database.
+            (database == &other.database, "database"),
+        ];
+
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "SqlServerConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+        Ok(())
+    }
+}
+
 /// A connection to an SSH tunnel.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct SshConnection {
@@ -111,6 +111,15 @@ pub trait ConnectionAccess:
         + Serialize
         + for<'a> Deserialize<'a>
         + AlterCompatible;
+    type SqlServer: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>
+        + AlterCompatible;
 }
 
 /// Expresses that the struct contains references to connections. Use a
@@ -126,6 +135,7 @@ impl ConnectionAccess for ReferencedConnection {
     type Ssh = CatalogItemId;
     type Csr = CatalogItemId;
     type MySql = CatalogItemId;
+    type SqlServer = CatalogItemId;
 }
 
 /// Expresses that the struct contains an inlined definition of a connection.
@@ -139,4 +149,5 @@ impl ConnectionAccess for InlinedConnection {
     type Ssh = super::SshConnection;
     type Csr = super::CsrConnection;
     type MySql = super::MySqlConnection;
+    type SqlServer = super::SqlServerConnection;
 }
@@ -138,6 +138,65 @@ pub const MYSQL_OFFSET_KNOWN_INTERVAL: Config<Duration> = Config::new(
     "Interval to fetch `offset_known`, from `@gtid_executed`",
 );
 
+// SQL Server
+
+/// Interval at which we poll SQL Server for new changes in CDC change tables.
+pub const SQL_SERVER_CDC_POLL_INTERVAL: Config<Duration> = Config::new(
+    "sql_server_cdc_poll_interval",
+    Duration::from_secs(1),
+    "Interval at which we poll SQL Server for new changes in CDC change tables.",
+);
+
+/// Interval to fetch `offset_known`, from `sys.fn_cdc_get_max_lsn()`
+pub const SQL_SERVER_OFFSET_KNOWN_INTERVAL: Config<Duration> = Config::new(
+    "sql_server_offset_known_interval",
+    Duration::from_secs(10),
+    "Interval to fetch `offset_known`, from `sys.fn_cdc_get_max_lsn()`",
+);
+
+// Sinks
+
+/// The number of buffered updates that sinks retain the allocations for.
+pub const SINK_BUFFERED_UPDATES_RESIZE_THRESHOLD_ELEMENTS: Config<usize> = Config::new(
+    "sink_buffered_updates_resize_threshold_elements",
+    1000,
+    "The Iceberg, Postgres and S3 sinks buffer updates until their timestamp is complete. As a \
+        performance optimization we reuse the buffer allocations, but shrink them to retain at \
+        most this number of elements.",
+);
+
+// Iceberg
+
+/// The size at which the Iceberg sink rolls over to a new data file.
+pub const ICEBERG_SINK_TARGET_FILE_SIZE_BYTES: Config<usize> = Config::new(
+    "iceberg_sink_target_file_size_bytes",
+    128 * 1024 * 1024,
+    "The size at which the Iceberg sink rolls over to a new data file.",
+);
+
+/// The minimum interval between two snapshots committed by the Iceberg sink.
+pub const ICEBERG_SINK_COMMIT_INTERVAL: Config<Duration> = Config::new(
+    "iceberg_sink_commit_interval",
+    Duration::from_secs(10),
+    "The minimum interval between two snapshots committed by the Iceberg sink.",
+);
+
+// S3
+
+/// The size at which the S3 sink rolls over to a new data file.
+pub const S3_SINK_TARGET_FILE_SIZE_BYTES: Config<usize> = Config::new(
+    "s3_sink_target_file_size_bytes",
+    128 * 1024 * 1024,
+    "The size at which the S3 sink rolls over to a new data file.",
+);
+
+/// The minimum interval between two batches committed by the S3 sink.
+pub const S3_SINK_COMMIT_INTERVAL: Config<Duration> = Config::new(
+    "s3_sink_commit_interval",
+    Duration::from_secs(60),
+    "The minimum interval between two batches committed by the S3 sink.",
+);
+
 // Postgres
 
 /// Interval to poll `confirmed_flush_lsn` to get a resumption lsn.
@@ -161,6 +220,15 @@ pub const PG_SCHEMA_VALIDATION_INTERVAL: Config<Duration> = Config::new(
     "Interval to re-validate the schemas of ingested tables.",
 );
 
+/// The minimum interval between two progress updates a Postgres sink writes upstream when it has
+/// no updates to write.
+pub const PG_SINK_PROGRESS_INTERVAL: Config<Duration> = Config::new(
+    "pg_sink_progress_interval",
+    Duration::from_secs(10),
+    "The minimum interval between two progress updates a Postgres sink writes upstream when it \
+     has no updates to write.",
+);
+
 // Networking
 
 /// Whether or not to enforce that external connection addresses are global
@@ -265,9 +333,17 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS)
         .add(&MYSQL_REPLICATION_HEARTBEAT_INTERVAL)
         .add(&MYSQL_OFFSET_KNOWN_INTERVAL)
+        .add(&SQL_SERVER_CDC_POLL_INTERVAL)
+        .add(&SQL_SERVER_OFFSET_KNOWN_INTERVAL)
+        .add(&ICEBERG_SINK_TARGET_FILE_SIZE_BYTES)
+        .add(&ICEBERG_SINK_COMMIT_INTERVAL)
+        .add(&S3_SINK_TARGET_FILE_SIZE_BYTES)
+        .add(&S3_SINK_COMMIT_INTERVAL)
+        .add(&SINK_BUFFERED_UPDATES_RESIZE_THRESHOLD_ELEMENTS)
         .add(&PG_FETCH_SLOT_RESUME_LSN_INTERVAL)
         .add(&PG_OFFSET_KNOWN_INTERVAL)
         .add(&PG_SCHEMA_VALIDATION_INTERVAL)
+        .add(&PG_SINK_PROGRESS_INTERVAL)
         .add(&ENFORCE_EXTERNAL_ADDRESSES)
         .add(&STORAGE_UPSERT_PREVENT_SNAPSHOT_BUFFERING)
         .add(&STORAGE_ROCKSDB_USE_MERGE_OPERATOR)
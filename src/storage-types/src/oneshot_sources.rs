@@ -9,7 +9,7 @@
 
 //! Types for oneshot sources.
 
-use mz_pgcopy::CopyCsvFormatParams;
+use mz_pgcopy::{CopyCsvFormatParams, CopyJsonFormatParams};
 use mz_proto::{IntoRustIfSome, RustType};
 use mz_repr::CatalogItemId;
 use mz_timely_util::builder_async::PressOnDropButton;
@@ -132,6 +132,11 @@ impl RustType<proto_oneshot_ingestion_request::Source> for ContentSource {
 pub enum ContentFormat {
     Csv(CopyCsvFormatParams<'static>),
     Parquet,
+    /// Avro Object Container Files.
+    Avro,
+    Orc,
+    /// Newline-delimited JSON or a top-level JSON array.
+    Json(CopyJsonFormatParams),
 }
 
 impl RustType<proto_oneshot_ingestion_request::Format> for ContentFormat {
@@ -145,6 +150,17 @@ impl RustType<proto_oneshot_ingestion_request::Format> for ContentFormat {
             ContentFormat::Parquet => proto_oneshot_ingestion_request::Format::Parquet(
                 ProtoParquetContentFormat::default(),
             ),
+            ContentFormat::Avro => {
+                proto_oneshot_ingestion_request::Format::Avro(ProtoAvroContentFormat::default())
+            }
+            ContentFormat::Orc => {
+                proto_oneshot_ingestion_request::Format::Orc(ProtoOrcContentFormat::default())
+            }
+            ContentFormat::Json(params) => {
+                proto_oneshot_ingestion_request::Format::Json(ProtoJsonContentFormat {
+                    params: Some(params.into_proto()),
+                })
+            }
         }
     }
 
@@ -159,6 +175,16 @@ impl RustType<proto_oneshot_ingestion_request::Format> for ContentFormat {
             proto_oneshot_ingestion_request::Format::Parquet(ProtoParquetContentFormat {}) => {
                 Ok(ContentFormat::Parquet)
             }
+            proto_oneshot_ingestion_request::Format::Avro(ProtoAvroContentFormat {}) => {
+                Ok(ContentFormat::Avro)
+            }
+            proto_oneshot_ingestion_request::Format::Orc(ProtoOrcContentFormat {}) => {
+                Ok(ContentFormat::Orc)
+            }
+            proto_oneshot_ingestion_request::Format::Json(ProtoJsonContentFormat { params }) => {
+                let params = params.into_rust_if_some("ProtoJsonContentFormat::params")?;
+                Ok(ContentFormat::Json(params))
+            }
         }
     }
 }
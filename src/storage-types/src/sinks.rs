@@ -35,6 +35,9 @@ use crate::AlterCompatible;
 
 include!(concat!(env!("OUT_DIR"), "/mz_storage_types.sinks.rs"));
 
+pub mod iceberg;
+pub mod postgres;
+pub mod s3;
 pub mod s3_oneshot_sink;
 
 /// A sink for updates to a relational collection.
@@ -213,6 +216,9 @@ impl RustType<ProtoStorageSinkDesc> for StorageSinkDesc<CollectionMetadata, mz_r
 pub enum SinkEnvelope {
     Debezium,
     Upsert,
+    /// Every update is written as a row of its own, annotated with its
+    /// timestamp and diff. Only supported by S3 and Postgres sinks.
+    Append,
 }
 
 impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
@@ -222,6 +228,7 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
             kind: Some(match self {
                 SinkEnvelope::Debezium => Kind::Debezium(()),
                 SinkEnvelope::Upsert => Kind::Upsert(()),
+                SinkEnvelope::Append => Kind::Append(()),
             }),
         }
     }
@@ -234,6 +241,7 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
         Ok(match kind {
             Kind::Debezium(()) => SinkEnvelope::Debezium,
             Kind::Upsert(()) => SinkEnvelope::Upsert,
+            Kind::Append(()) => SinkEnvelope::Append,
         })
     }
 }
@@ -241,6 +249,9 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StorageSinkConnection<C: ConnectionAccess = InlinedConnection> {
     Kafka(KafkaSinkConnection<C>),
+    Iceberg(IcebergSinkConnection<C>),
+    S3(S3SinkConnection<C>),
+    Postgres(PostgresSinkConnection<C>),
 }
 
 impl<C: ConnectionAccess> StorageSinkConnection<C> {
@@ -260,6 +271,23 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
             (StorageSinkConnection::Kafka(s), StorageSinkConnection::Kafka(o)) => {
                 s.alter_compatible(id, o)?
             }
+            (StorageSinkConnection::Iceberg(s), StorageSinkConnection::Iceberg(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            (StorageSinkConnection::S3(s), StorageSinkConnection::S3(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            (StorageSinkConnection::Postgres(s), StorageSinkConnection::Postgres(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            _ => {
+                tracing::warn!(
+                    "StorageSinkConnection incompatible:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+                return Err(AlterError { id });
+            }
         }
 
         Ok(())
@@ -272,6 +300,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<StorageSinkConnection, R>
     fn into_inline_connection(self, r: R) -> StorageSinkConnection {
         match self {
             Self::Kafka(conn) => StorageSinkConnection::Kafka(conn.into_inline_connection(r)),
+            Self::Iceberg(conn) => StorageSinkConnection::Iceberg(conn.into_inline_connection(r)),
+            Self::S3(conn) => StorageSinkConnection::S3(conn.into_inline_connection(r)),
+            Self::Postgres(conn) => StorageSinkConnection::Postgres(conn.into_inline_connection(r)),
         }
     }
 }
@@ -283,6 +314,9 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
         ProtoStorageSinkConnection {
             kind: Some(match self {
                 Self::Kafka(conn) => KafkaV2(conn.into_proto()),
+                Self::Iceberg(conn) => Iceberg(conn.into_proto()),
+                Self::S3(conn) => S3(conn.into_proto()),
+                Self::Postgres(conn) => Postgres(conn.into_proto()),
             }),
         }
     }
@@ -295,6 +329,9 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
 
         Ok(match kind {
             KafkaV2(proto) => Self::Kafka(proto.into_rust()?),
+            Iceberg(proto) => Self::Iceberg(proto.into_rust()?),
+            S3(proto) => Self::S3(proto.into_rust()?),
+            Postgres(proto) => Self::Postgres(proto.into_rust()?),
         })
     }
 }
@@ -305,6 +342,9 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(KafkaSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Iceberg(IcebergSinkConnection { connection_id, .. }) => Some(*connection_id),
+            S3(S3SinkConnection { connection_id, .. }) => Some(*connection_id),
+            Postgres(PostgresSinkConnection { connection_id, .. }) => Some(*connection_id),
         }
     }
 
@@ -313,6 +353,9 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(_) => "kafka",
+            Iceberg(_) => "iceberg",
+            S3(_) => "s3",
+            Postgres(_) => "postgres",
         }
     }
 }
@@ -585,6 +628,406 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
     }
 }
 
+/// A sink that continuously writes a collection into an Apache Iceberg table
+/// stored in an object store.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IcebergSinkConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: CatalogItemId,
+    pub aws_connection: C::Aws,
+    /// The `s3://` URI of the table location. Data, delete and metadata files
+    /// are all written below this prefix.
+    pub table_uri: String,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    /// The user-specified key for the sink, used to derive the equality
+    /// deletes that retract previous versions of a row.
+    pub key_desc_and_indices: (RelationDesc, Vec<usize>),
+    pub value_desc: RelationDesc,
+}
+
+impl<C: ConnectionAccess> IcebergSinkConnection<C> {
+    /// Determines if `self` is compatible with another `StorageSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &IcebergSinkConnection<C>,
+    ) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+        let IcebergSinkConnection {
+            connection_id,
+            aws_connection,
+            table_uri,
+            relation_key_indices,
+            key_desc_and_indices,
+            value_desc,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (
+                aws_connection
+                    .alter_compatible(id, &other.aws_connection)
+                    .is_ok(),
+                "aws_connection",
+            ),
+            (table_uri == &other.table_uri, "table_uri"),
+            (
+                relation_key_indices == &other.relation_key_indices,
+                "relation_key_indices",
+            ),
+            (
+                key_desc_and_indices == &other.key_desc_and_indices,
+                "key_desc_and_indices",
+            ),
+            (value_desc == &other.value_desc, "value_desc"),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "IcebergSinkConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<IcebergSinkConnection, R>
+    for IcebergSinkConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> IcebergSinkConnection {
+        let IcebergSinkConnection {
+            connection_id,
+            aws_connection,
+            table_uri,
+            relation_key_indices,
+            key_desc_and_indices,
+            value_desc,
+        } = self;
+        IcebergSinkConnection {
+            connection_id,
+            aws_connection: r.resolve_connection(aws_connection).unwrap_aws(),
+            table_uri,
+            relation_key_indices,
+            key_desc_and_indices,
+            value_desc,
+        }
+    }
+}
+
+impl RustType<ProtoIcebergSinkConnection> for IcebergSinkConnection {
+    fn into_proto(&self) -> ProtoIcebergSinkConnection {
+        ProtoIcebergSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            aws_connection: Some(self.aws_connection.into_proto()),
+            table_uri: self.table_uri.clone(),
+            key_desc_and_indices: Some(self.key_desc_and_indices.into_proto()),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoIcebergSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(IcebergSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoIcebergSinkConnection::connection_id")?,
+            aws_connection: proto
+                .aws_connection
+                .into_rust_if_some("ProtoIcebergSinkConnection::aws_connection")?,
+            table_uri: proto.table_uri,
+            key_desc_and_indices: proto
+                .key_desc_and_indices
+                .into_rust_if_some("ProtoIcebergSinkConnection::key_desc_and_indices")?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoIcebergSinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// A sink that continuously appends the changes to a collection to files in
+/// an S3 bucket.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct S3SinkConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: CatalogItemId,
+    pub aws_connection: C::Aws,
+    /// The `s3://` URI below which data files, manifests and the progress of
+    /// the sink are written.
+    pub uri: String,
+    /// The format of the data files.
+    pub format: S3SinkFormat,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+}
+
+impl<C: ConnectionAccess> S3SinkConnection<C> {
+    /// Determines if `self` is compatible with another `StorageSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &S3SinkConnection<C>,
+    ) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+        let S3SinkConnection {
+            connection_id,
+            aws_connection,
+            uri,
+            format,
+            relation_key_indices,
+            value_desc,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (
+                aws_connection
+                    .alter_compatible(id, &other.aws_connection)
+                    .is_ok(),
+                "aws_connection",
+            ),
+            (uri == &other.uri, "uri"),
+            (format == &other.format, "format"),
+            (
+                relation_key_indices == &other.relation_key_indices,
+                "relation_key_indices",
+            ),
+            (value_desc == &other.value_desc, "value_desc"),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "S3SinkConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The name of the format of the data files.
+    pub fn format_name(&self) -> &'static str {
+        match &self.format {
+            S3SinkFormat::Parquet => "parquet",
+            S3SinkFormat::PgCopy(CopyFormatParams::Csv(_)) => "csv",
+            S3SinkFormat::PgCopy(CopyFormatParams::Text(_)) => "text",
+            S3SinkFormat::PgCopy(CopyFormatParams::Binary) => "binary",
+            S3SinkFormat::PgCopy(CopyFormatParams::Parquet) => "parquet",
+            S3SinkFormat::PgCopy(CopyFormatParams::Avro) => "avro",
+            S3SinkFormat::PgCopy(CopyFormatParams::Orc) => "orc",
+            S3SinkFormat::PgCopy(CopyFormatParams::Json(_)) => "json",
+        }
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<S3SinkConnection, R>
+    for S3SinkConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> S3SinkConnection {
+        let S3SinkConnection {
+            connection_id,
+            aws_connection,
+            uri,
+            format,
+            relation_key_indices,
+            value_desc,
+        } = self;
+        S3SinkConnection {
+            connection_id,
+            aws_connection: r.resolve_connection(aws_connection).unwrap_aws(),
+            uri,
+            format,
+            relation_key_indices,
+            value_desc,
+        }
+    }
+}
+
+impl RustType<ProtoS3SinkConnection> for S3SinkConnection {
+    fn into_proto(&self) -> ProtoS3SinkConnection {
+        ProtoS3SinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            aws_connection: Some(self.aws_connection.into_proto()),
+            uri: self.uri.clone(),
+            format: Some(self.format.into_proto()),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3SinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(S3SinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoS3SinkConnection::connection_id")?,
+            aws_connection: proto
+                .aws_connection
+                .into_rust_if_some("ProtoS3SinkConnection::aws_connection")?,
+            uri: proto.uri,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoS3SinkConnection::format")?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoS3SinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// A sink that continuously writes a collection into a table of an upstream
+/// PostgreSQL database.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PostgresSinkConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: CatalogItemId,
+    pub connection: C::Pg,
+    /// The schema of the upstream table.
+    pub schema: String,
+    /// The name of the upstream table.
+    pub table: String,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    /// The user-specified key for the sink, used to upsert rows into the
+    /// upstream table. Must be present for [`SinkEnvelope::Upsert`].
+    pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    pub value_desc: RelationDesc,
+}
+
+impl<C: ConnectionAccess> PostgresSinkConnection<C> {
+    /// Determines if `self` is compatible with another `StorageSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &PostgresSinkConnection<C>,
+    ) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+        let PostgresSinkConnection {
+            connection_id,
+            connection,
+            schema,
+            table,
+            relation_key_indices,
+            key_desc_and_indices,
+            value_desc,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (
+                connection.alter_compatible(id, &other.connection).is_ok(),
+                "connection",
+            ),
+            (schema == &other.schema, "schema"),
+            (table == &other.table, "table"),
+            (
+                relation_key_indices == &other.relation_key_indices,
+                "relation_key_indices",
+            ),
+            (
+                key_desc_and_indices == &other.key_desc_and_indices,
+                "key_desc_and_indices",
+            ),
+            (value_desc == &other.value_desc, "value_desc"),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "PostgresSinkConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<PostgresSinkConnection, R>
+    for PostgresSinkConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> PostgresSinkConnection {
+        let PostgresSinkConnection {
+            connection_id,
+            connection,
+            schema,
+            table,
+            relation_key_indices,
+            key_desc_and_indices,
+            value_desc,
+        } = self;
+        PostgresSinkConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_pg(),
+            schema,
+            table,
+            relation_key_indices,
+            key_desc_and_indices,
+            value_desc,
+        }
+    }
+}
+
+impl RustType<ProtoPostgresSinkConnection> for PostgresSinkConnection {
+    fn into_proto(&self) -> ProtoPostgresSinkConnection {
+        ProtoPostgresSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            schema: self.schema.clone(),
+            table: self.table.clone(),
+            key_desc_and_indices: self.key_desc_and_indices.into_proto(),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoPostgresSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(PostgresSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection")?,
+            schema: proto.schema,
+            table: proto.table,
+            key_desc_and_indices: proto.key_desc_and_indices.into_rust()?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoPostgresSinkConnection::value_desc")?,
+        })
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum KafkaIdStyle {
     /// A new-style id that is optionally prefixed.
@@ -712,6 +1155,16 @@ pub enum KafkaSinkFormatType<C: ConnectionAccess = InlinedConnection> {
         compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
         csr_connection: C::Csr,
     },
+    /// Protobuf messages described by a schema generated from the sinked
+    /// relation.
+    Protobuf {
+        /// The `.proto` source of the schema, as published to the schema
+        /// registry.
+        schema: String,
+        message_name: String,
+        compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
+        csr_connection: C::Csr,
+    },
     Json,
     Text,
     Bytes,
@@ -721,6 +1174,7 @@ impl<C: ConnectionAccess> KafkaSinkFormatType<C> {
     pub fn get_format_name(&self) -> &str {
         match self {
             Self::Avro { .. } => "avro",
+            Self::Protobuf { .. } => "protobuf",
             Self::Json => "json",
             Self::Text => "text",
             Self::Bytes => "bytes",
@@ -740,6 +1194,9 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                     "avro".into()
                 }
                 (KafkaSinkFormatType::Json, KafkaSinkFormatType::Json) => "json".into(),
+                (KafkaSinkFormatType::Protobuf { .. }, KafkaSinkFormatType::Protobuf { .. }) => {
+                    "protobuf".into()
+                }
                 (keyf, valuef) => format!(
                     "key-{}-value-{}",
                     keyf.get_format_name(),
@@ -862,6 +1319,17 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkFormatType, R>
                 compatibility_level,
                 csr_connection: r.resolve_connection(csr_connection).unwrap_csr(),
             },
+            KafkaSinkFormatType::Protobuf {
+                schema,
+                message_name,
+                compatibility_level,
+                csr_connection,
+            } => KafkaSinkFormatType::Protobuf {
+                schema,
+                message_name,
+                compatibility_level,
+                csr_connection: r.resolve_connection(csr_connection).unwrap_csr(),
+            },
             KafkaSinkFormatType::Json => KafkaSinkFormatType::Json,
             KafkaSinkFormatType::Text => KafkaSinkFormatType::Text,
             KafkaSinkFormatType::Bytes => KafkaSinkFormatType::Bytes,
@@ -883,6 +1351,17 @@ impl RustType<ProtoKafkaSinkFormatType> for KafkaSinkFormatType {
                     compatibility_level: csr_compat_level_to_proto(compatibility_level),
                     csr_connection: Some(csr_connection.into_proto()),
                 }),
+                Self::Protobuf {
+                    schema,
+                    message_name,
+                    compatibility_level,
+                    csr_connection,
+                } => Type::Protobuf(proto_kafka_sink_format_type::ProtoKafkaSinkProtobufFormat {
+                    schema: schema.clone(),
+                    message_name: message_name.clone(),
+                    compatibility_level: csr_compat_level_to_proto(compatibility_level),
+                    csr_connection: Some(csr_connection.into_proto()),
+                }),
                 Self::Json => Type::Json(()),
                 Self::Text => Type::Text(()),
                 Self::Bytes => Type::Bytes(()),
@@ -904,6 +1383,14 @@ impl RustType<ProtoKafkaSinkFormatType> for KafkaSinkFormatType {
                     .csr_connection
                     .into_rust_if_some("ProtoKafkaSinkFormatType::csr_connection")?,
             },
+            Type::Protobuf(proto) => Self::Protobuf {
+                schema: proto.schema,
+                message_name: proto.message_name,
+                compatibility_level: csr_compat_level_from_proto(proto.compatibility_level),
+                csr_connection: proto
+                    .csr_connection
+                    .into_rust_if_some("ProtoKafkaSinkFormatType::csr_connection")?,
+            },
             Type::Json(()) => Self::Json,
             Type::Text(()) => Self::Text,
             Type::Bytes(()) => Self::Bytes,
@@ -0,0 +1,211 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to the Iceberg sink.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::bail;
+use http::Uri;
+use mz_repr::{RelationDesc, ScalarType};
+
+/// The scale used for `numeric` columns without a declared scale, matching
+/// the scale the Arrow encoder uses for them.
+const DEFAULT_NUMERIC_SCALE: u8 = 10;
+
+/// The precision used for all `numeric` columns. This is the largest precision
+/// that fits in the 128-bit decimals the Arrow encoder produces.
+const NUMERIC_PRECISION: u8 = 38;
+
+/// An Iceberg primitive type.
+///
+/// See <https://iceberg.apache.org/spec/#primitive-types>.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IcebergType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Decimal { precision: u8, scale: u8 },
+    Date,
+    Time,
+    Timestamp,
+    Timestamptz,
+    String,
+    Uuid,
+    Binary,
+}
+
+impl IcebergType {
+    /// Returns the Iceberg type that values of `typ` are written as, or an
+    /// error if the type cannot be sinked to Iceberg.
+    ///
+    /// The mapping must agree with the Arrow types chosen by
+    /// `mz_arrow_util::builder`, which produces the Parquet data files.
+    pub fn from_scalar_type(typ: &ScalarType) -> Result<Self, anyhow::Error> {
+        let iceberg_type = match typ {
+            ScalarType::Bool => IcebergType::Boolean,
+            ScalarType::Int16 | ScalarType::Int32 => IcebergType::Int,
+            ScalarType::Int64 => IcebergType::Long,
+            ScalarType::Float32 => IcebergType::Float,
+            ScalarType::Float64 => IcebergType::Double,
+            ScalarType::Numeric { max_scale } => IcebergType::Decimal {
+                precision: NUMERIC_PRECISION,
+                scale: max_scale.map_or(DEFAULT_NUMERIC_SCALE, |s| s.into_u8()),
+            },
+            ScalarType::Date => IcebergType::Date,
+            ScalarType::Time => IcebergType::Time,
+            ScalarType::Timestamp { .. } => IcebergType::Timestamp,
+            ScalarType::TimestampTz { .. } => IcebergType::Timestamptz,
+            ScalarType::String
+            | ScalarType::Char { .. }
+            | ScalarType::VarChar { .. }
+            | ScalarType::Jsonb => IcebergType::String,
+            ScalarType::Uuid => IcebergType::Uuid,
+            ScalarType::Bytes => IcebergType::Binary,
+            _ => bail!("{:?} is not supported in Iceberg sinks", typ),
+        };
+        if let IcebergType::Decimal { scale, .. } = iceberg_type {
+            if scale > NUMERIC_PRECISION {
+                bail!("numeric scale {} is not supported in Iceberg sinks", scale);
+            }
+        }
+        Ok(iceberg_type)
+    }
+}
+
+impl fmt::Display for IcebergType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcebergType::Boolean => f.write_str("boolean"),
+            IcebergType::Int => f.write_str("int"),
+            IcebergType::Long => f.write_str("long"),
+            IcebergType::Float => f.write_str("float"),
+            IcebergType::Double => f.write_str("double"),
+            IcebergType::Decimal { precision, scale } => {
+                write!(f, "decimal({}, {})", precision, scale)
+            }
+            IcebergType::Date => f.write_str("date"),
+            IcebergType::Time => f.write_str("time"),
+            IcebergType::Timestamp => f.write_str("timestamp"),
+            IcebergType::Timestamptz => f.write_str("timestamptz"),
+            IcebergType::String => f.write_str("string"),
+            IcebergType::Uuid => f.write_str("uuid"),
+            IcebergType::Binary => f.write_str("binary"),
+        }
+    }
+}
+
+/// Validates that every column of `desc` can be written to an Iceberg table.
+pub fn validate_desc(desc: &RelationDesc) -> Result<(), anyhow::Error> {
+    let errs: Vec<_> = desc
+        .iter()
+        .filter(|(_, typ)| IcebergType::from_scalar_type(&typ.scalar_type).is_err())
+        .map(|(name, typ)| format!("{}: {:?}", name, typ.scalar_type))
+        .collect();
+    if !errs.is_empty() {
+        bail!(
+            "cannot sink the following columns to Iceberg: {}",
+            errs.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// The location of an Iceberg table in an S3 bucket.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IcebergTableLocation {
+    /// The bucket the table is stored in.
+    pub bucket: String,
+    /// The key prefix below which all files of the table are stored, without a
+    /// leading or trailing slash.
+    pub prefix: String,
+}
+
+impl IcebergTableLocation {
+    /// Parses an `s3://bucket/path` table URI.
+    pub fn parse(uri: &str) -> Result<Self, anyhow::Error> {
+        let parsed = Uri::from_str(uri)?;
+        if parsed.scheme_str() != Some("s3") {
+            bail!("only 's3://...' urls are supported as Iceberg table locations");
+        }
+        let Some(bucket) = parsed.host() else {
+            bail!("missing bucket name from 's3://...' url");
+        };
+        let prefix = parsed.path().trim_matches('/');
+        if prefix.is_empty() {
+            bail!("Iceberg table location must include a path below the bucket");
+        }
+        Ok(IcebergTableLocation {
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+        })
+    }
+
+    /// The `s3://` URI of the table, as recorded in its metadata.
+    pub fn uri(&self) -> String {
+        format!("s3://{}/{}", self.bucket, self.prefix)
+    }
+
+    /// The full `s3://` URI of the object with the given key.
+    pub fn object_uri(&self, key: &str) -> String {
+        format!("s3://{}/{}", self.bucket, key)
+    }
+
+    /// The key of a file in the table's `data` directory.
+    pub fn data_key(&self, file_name: &str) -> String {
+        format!("{}/data/{}", self.prefix, file_name)
+    }
+
+    /// The key of a file in the table's `metadata` directory.
+    pub fn metadata_key(&self, file_name: &str) -> String {
+        format!("{}/metadata/{}", self.prefix, file_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::adt::numeric::NumericMaxScale;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_table_location() {
+        let location = IcebergTableLocation::parse("s3://bucket/warehouse/db/table/").unwrap();
+        assert_eq!(location.bucket, "bucket");
+        assert_eq!(location.prefix, "warehouse/db/table");
+        assert_eq!(location.uri(), "s3://bucket/warehouse/db/table");
+        assert_eq!(
+            location.metadata_key("v1.metadata.json"),
+            "warehouse/db/table/metadata/v1.metadata.json"
+        );
+
+        assert!(IcebergTableLocation::parse("s3://bucket").is_err());
+        assert!(IcebergTableLocation::parse("gs://bucket/table").is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_type_mapping() {
+        let numeric = ScalarType::Numeric {
+            max_scale: Some(NumericMaxScale::try_from(2i64).unwrap()),
+        };
+        assert_eq!(
+            IcebergType::from_scalar_type(&numeric).unwrap().to_string(),
+            "decimal(38, 2)"
+        );
+        assert_eq!(
+            IcebergType::from_scalar_type(&ScalarType::Int16).unwrap(),
+            IcebergType::Int
+        );
+        assert!(IcebergType::from_scalar_type(&ScalarType::UInt64).is_err());
+        assert!(IcebergType::from_scalar_type(&ScalarType::MzTimestamp).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to the Postgres sink.
+
+use std::collections::BTreeSet;
+
+use anyhow::bail;
+use mz_repr::RelationDesc;
+
+use crate::sinks::SinkEnvelope;
+
+/// The name of the upstream column that holds the timestamp of an update in
+/// append mode.
+pub const TIMESTAMP_COLUMN: &str = "mz_timestamp";
+/// The name of the upstream column that holds the diff of an update in append
+/// mode.
+pub const DIFF_COLUMN: &str = "mz_diff";
+/// The name of the table, in the schema of the sinked table, that records the
+/// progress of all Postgres sinks writing to that schema.
+pub const PROGRESS_TABLE: &str = "mz_sink_progress";
+
+/// Returns the names of the upstream columns a Postgres sink writes for
+/// updates to a relation described by `value_desc`.
+///
+/// Columns are matched to the upstream table by name. With
+/// [`SinkEnvelope::Upsert`] the sink writes the columns of the relation. With
+/// [`SinkEnvelope::Append`] it additionally writes the timestamp and diff of
+/// every update.
+pub fn upstream_columns(
+    value_desc: &RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<Vec<String>, anyhow::Error> {
+    let mut columns = vec![];
+    let mut seen = BTreeSet::new();
+    for name in value_desc.iter_names() {
+        if !seen.insert(name) {
+            bail!("column name {} is ambiguous", name);
+        }
+        columns.push(name.to_string());
+    }
+    match envelope {
+        SinkEnvelope::Upsert => (),
+        SinkEnvelope::Append => {
+            for reserved in [TIMESTAMP_COLUMN, DIFF_COLUMN] {
+                if columns.iter().any(|c| c == reserved) {
+                    bail!("column name {} is reserved by Postgres sinks", reserved);
+                }
+                columns.push(reserved.to_string());
+            }
+        }
+        SinkEnvelope::Debezium => bail!("Postgres sinks do not support ENVELOPE DEBEZIUM"),
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_upstream_columns() {
+        let desc = RelationDesc::builder()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("name", ScalarType::String.nullable(true))
+            .finish();
+
+        let upsert = upstream_columns(&desc, SinkEnvelope::Upsert).unwrap();
+        assert_eq!(upsert, ["id", "name"]);
+
+        let append = upstream_columns(&desc, SinkEnvelope::Append).unwrap();
+        assert_eq!(append, ["id", "name", TIMESTAMP_COLUMN, DIFF_COLUMN]);
+
+        assert!(upstream_columns(&desc, SinkEnvelope::Debezium).is_err());
+
+        let reserved = RelationDesc::builder()
+            .with_column(DIFF_COLUMN, ScalarType::Int64.nullable(false))
+            .finish();
+        assert!(upstream_columns(&reserved, SinkEnvelope::Upsert).is_ok());
+        assert!(upstream_columns(&reserved, SinkEnvelope::Append).is_err());
+
+        let ambiguous = RelationDesc::builder()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .finish();
+        assert!(upstream_columns(&ambiguous, SinkEnvelope::Upsert).is_err());
+    }
+}
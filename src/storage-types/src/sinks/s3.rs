@@ -0,0 +1,169 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to the continuous S3 sink.
+
+use std::str::FromStr;
+
+use anyhow::bail;
+use http::Uri;
+use mz_repr::{RelationDesc, ScalarType};
+
+use crate::sinks::SinkEnvelope;
+
+/// The name of the column that holds the timestamp of an update.
+pub const TIMESTAMP_COLUMN: &str = "mz_timestamp";
+/// The name of the column that holds the diff of an update.
+pub const DIFF_COLUMN: &str = "mz_diff";
+
+/// Returns the description of the rows an S3 sink writes for updates to a
+/// relation described by `value_desc`.
+///
+/// With [`SinkEnvelope::Append`] every row holds the columns of the relation,
+/// followed by the timestamp and diff of the update. With
+/// [`SinkEnvelope::Debezium`] every row holds the value before and after the
+/// update as records, followed by the timestamp of the update.
+pub fn output_desc(
+    value_desc: &RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<RelationDesc, anyhow::Error> {
+    let timestamp_type = ScalarType::MzTimestamp.nullable(false);
+    let desc = match envelope {
+        SinkEnvelope::Append => {
+            let mut builder = RelationDesc::builder();
+            for (name, typ) in value_desc.iter() {
+                if name.as_str() == TIMESTAMP_COLUMN || name.as_str() == DIFF_COLUMN {
+                    bail!("column name {} is reserved by S3 sinks", name);
+                }
+                builder = builder.with_column(name.clone(), typ.clone());
+            }
+            builder
+                .with_column(TIMESTAMP_COLUMN, timestamp_type)
+                .with_column(DIFF_COLUMN, ScalarType::Int64.nullable(false))
+                .finish()
+        }
+        SinkEnvelope::Debezium => {
+            let record = ScalarType::Record {
+                fields: value_desc
+                    .iter()
+                    .map(|(name, typ)| (name.clone(), typ.clone()))
+                    .collect(),
+                custom_id: None,
+                custom_oid: None,
+            };
+            RelationDesc::builder()
+                .with_column("before", record.clone().nullable(true))
+                .with_column("after", record.nullable(true))
+                .with_column(TIMESTAMP_COLUMN, timestamp_type)
+                .finish()
+        }
+        SinkEnvelope::Upsert => bail!("S3 sinks do not support ENVELOPE UPSERT"),
+    };
+    Ok(desc)
+}
+
+/// The location of a continuous S3 sink.
+///
+/// Data files are written to Hive-style partitions below the prefix of the
+/// sink. The manifests that commit the data files and the progress of the sink
+/// are written to directories whose names start with an underscore, which most
+/// readers of partitioned data sets skip.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct S3SinkLocation {
+    /// The bucket the sink writes to.
+    pub bucket: String,
+    /// The key prefix below which all files of the sink are written, without a
+    /// leading or trailing slash.
+    pub prefix: String,
+}
+
+impl S3SinkLocation {
+    /// Parses an `s3://bucket/path` sink URI.
+    pub fn parse(uri: &str) -> Result<Self, anyhow::Error> {
+        let parsed = Uri::from_str(uri)?;
+        if parsed.scheme_str() != Some("s3") {
+            bail!("only 's3://...' urls are supported as S3 sink locations");
+        }
+        let Some(bucket) = parsed.host() else {
+            bail!("missing bucket name from 's3://...' url");
+        };
+        let prefix = parsed.path().trim_matches('/');
+        if prefix.is_empty() {
+            bail!("S3 sink location must include a path below the bucket");
+        }
+        Ok(S3SinkLocation {
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+        })
+    }
+
+    /// The full `s3://` URI of the object with the given key.
+    pub fn object_uri(&self, key: &str) -> String {
+        format!("s3://{}/{}", self.bucket, key)
+    }
+
+    /// The key of a data file in the given partition.
+    pub fn data_key(&self, partition: &str, file_name: &str) -> String {
+        format!("{}/{}/{}", self.prefix, partition, file_name)
+    }
+
+    /// The key of the manifest of the batch that starts at `lower`.
+    ///
+    /// Timestamps are zero-padded so that manifests sort in timestamp order.
+    pub fn manifest_key(&self, lower: u64) -> String {
+        format!("{}/_manifests/{:020}.json", self.prefix, lower)
+    }
+
+    /// The key of the object that records the progress of the sink.
+    pub fn progress_key(&self) -> String {
+        format!("{}/_progress.json", self.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_location() {
+        let location = S3SinkLocation::parse("s3://bucket/archive/orders/").unwrap();
+        assert_eq!(location.bucket, "bucket");
+        assert_eq!(location.prefix, "archive/orders");
+        assert_eq!(
+            location.data_key("date=2024-01-01/hour=05", "part.parquet"),
+            "archive/orders/date=2024-01-01/hour=05/part.parquet"
+        );
+        assert_eq!(
+            location.manifest_key(42),
+            "archive/orders/_manifests/00000000000000000042.json"
+        );
+        assert_eq!(location.progress_key(), "archive/orders/_progress.json");
+
+        assert!(S3SinkLocation::parse("s3://bucket").is_err());
+        assert!(S3SinkLocation::parse("gs://bucket/orders").is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_output_desc() {
+        let desc = RelationDesc::builder()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .finish();
+
+        let append = output_desc(&desc, SinkEnvelope::Append).unwrap();
+        let names: Vec<_> = append.iter_names().map(|n| n.as_str()).collect();
+        assert_eq!(names, ["id", TIMESTAMP_COLUMN, DIFF_COLUMN]);
+
+        let debezium = output_desc(&desc, SinkEnvelope::Debezium).unwrap();
+        let names: Vec<_> = debezium.iter_names().map(|n| n.as_str()).collect();
+        assert_eq!(names, ["before", "after", TIMESTAMP_COLUMN]);
+
+        assert!(output_desc(&desc, SinkEnvelope::Upsert).is_err());
+        assert!(output_desc(&append, SinkEnvelope::Append).is_err());
+    }
+}
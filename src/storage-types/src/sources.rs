@@ -71,6 +71,7 @@ pub use crate::sources::kafka::KafkaSourceConnection;
 pub use crate::sources::load_generator::LoadGeneratorSourceConnection;
 pub use crate::sources::mysql::{MySqlSourceConnection, MySqlSourceExportDetails};
 pub use crate::sources::postgres::{PostgresSourceConnection, PostgresSourceExportDetails};
+pub use crate::sources::sql_server::{SqlServerSourceConnection, SqlServerSourceExportDetails};
 
 include!(concat!(env!("OUT_DIR"), "/mz_storage_types.sources.rs"));
 
@@ -771,6 +772,8 @@ impl<C: ConnectionAccess> SourceExportDataConfig<C> {
                     GenericSourceConnection::Postgres(_) => false,
                     // MySQL can produce retractions (deletes)
                     GenericSourceConnection::MySql(_) => false,
+                    // SQL Server can produce retractions (deletes)
+                    GenericSourceConnection::SqlServer(_) => false,
                     // Loadgen
                     GenericSourceConnection::LoadGenerator(g) => g.load_generator.is_monotonic(),
                     // Kafka exports with `None` envelope are append-only
@@ -907,6 +910,7 @@ pub enum GenericSourceConnection<C: ConnectionAccess = InlinedConnection> {
     Kafka(KafkaSourceConnection<C>),
     Postgres(PostgresSourceConnection<C>),
     MySql(MySqlSourceConnection<C>),
+    SqlServer(SqlServerSourceConnection<C>),
     LoadGenerator(LoadGeneratorSourceConnection),
 }
 
@@ -928,6 +932,12 @@ impl<C: ConnectionAccess> From<MySqlSourceConnection<C>> for GenericSourceConnec
     }
 }
 
+impl<C: ConnectionAccess> From<SqlServerSourceConnection<C>> for GenericSourceConnection<C> {
+    fn from(conn: SqlServerSourceConnection<C>) -> Self {
+        Self::SqlServer(conn)
+    }
+}
+
 impl<C: ConnectionAccess> From<LoadGeneratorSourceConnection> for GenericSourceConnection<C> {
     fn from(conn: LoadGeneratorSourceConnection) -> Self {
         Self::LoadGenerator(conn)
@@ -948,6 +958,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<GenericSourceConnection, R>
             GenericSourceConnection::MySql(mysql) => {
                 GenericSourceConnection::MySql(mysql.into_inline_connection(r))
             }
+            GenericSourceConnection::SqlServer(sql_server) => {
+                GenericSourceConnection::SqlServer(sql_server.into_inline_connection(r))
+            }
             GenericSourceConnection::LoadGenerator(lg) => {
                 GenericSourceConnection::LoadGenerator(lg)
             }
@@ -961,6 +974,7 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Kafka(conn) => conn.name(),
             Self::Postgres(conn) => conn.name(),
             Self::MySql(conn) => conn.name(),
+            Self::SqlServer(conn) => conn.name(),
             Self::LoadGenerator(conn) => conn.name(),
         }
     }
@@ -970,6 +984,7 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Kafka(conn) => conn.external_reference(),
             Self::Postgres(conn) => conn.external_reference(),
             Self::MySql(conn) => conn.external_reference(),
+            Self::SqlServer(conn) => conn.external_reference(),
             Self::LoadGenerator(conn) => conn.external_reference(),
         }
     }
@@ -979,6 +994,7 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Kafka(conn) => conn.default_key_desc(),
             Self::Postgres(conn) => conn.default_key_desc(),
             Self::MySql(conn) => conn.default_key_desc(),
+            Self::SqlServer(conn) => conn.default_key_desc(),
             Self::LoadGenerator(conn) => conn.default_key_desc(),
         }
     }
@@ -988,6 +1004,7 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Kafka(conn) => conn.default_value_desc(),
             Self::Postgres(conn) => conn.default_value_desc(),
             Self::MySql(conn) => conn.default_value_desc(),
+            Self::SqlServer(conn) => conn.default_value_desc(),
             Self::LoadGenerator(conn) => conn.default_value_desc(),
         }
     }
@@ -997,6 +1014,7 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Kafka(conn) => conn.timestamp_desc(),
             Self::Postgres(conn) => conn.timestamp_desc(),
             Self::MySql(conn) => conn.timestamp_desc(),
+            Self::SqlServer(conn) => conn.timestamp_desc(),
             Self::LoadGenerator(conn) => conn.timestamp_desc(),
         }
     }
@@ -1006,6 +1024,7 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Kafka(conn) => conn.connection_id(),
             Self::Postgres(conn) => conn.connection_id(),
             Self::MySql(conn) => conn.connection_id(),
+            Self::SqlServer(conn) => conn.connection_id(),
             Self::LoadGenerator(conn) => conn.connection_id(),
         }
     }
@@ -1015,6 +1034,7 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Kafka(conn) => conn.primary_export_details(),
             Self::Postgres(conn) => conn.primary_export_details(),
             Self::MySql(conn) => conn.primary_export_details(),
+            Self::SqlServer(conn) => conn.primary_export_details(),
             Self::LoadGenerator(conn) => conn.primary_export_details(),
         }
     }
@@ -1024,6 +1044,7 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             GenericSourceConnection::Kafka(conn) => conn.supports_read_only(),
             GenericSourceConnection::Postgres(conn) => conn.supports_read_only(),
             GenericSourceConnection::MySql(conn) => conn.supports_read_only(),
+            GenericSourceConnection::SqlServer(conn) => conn.supports_read_only(),
             GenericSourceConnection::LoadGenerator(conn) => conn.supports_read_only(),
         }
     }
@@ -1038,6 +1059,7 @@ impl<C: ConnectionAccess> crate::AlterCompatible for GenericSourceConnection<C>
             (Self::Kafka(conn), Self::Kafka(other)) => conn.alter_compatible(id, other),
             (Self::Postgres(conn), Self::Postgres(other)) => conn.alter_compatible(id, other),
             (Self::MySql(conn), Self::MySql(other)) => conn.alter_compatible(id, other),
+            (Self::SqlServer(conn), Self::SqlServer(other)) => conn.alter_compatible(id, other),
             (Self::LoadGenerator(conn), Self::LoadGenerator(other)) => {
                 conn.alter_compatible(id, other)
             }
@@ -1066,6 +1088,9 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection<InlinedConnecti
                     Kind::Postgres(postgres.into_proto())
                 }
                 GenericSourceConnection::MySql(mysql) => Kind::Mysql(mysql.into_proto()),
+                GenericSourceConnection::SqlServer(sql_server) => {
+                    Kind::SqlServer(sql_server.into_proto())
+                }
                 GenericSourceConnection::LoadGenerator(loadgen) => {
                     Kind::Loadgen(loadgen.into_proto())
                 }
@@ -1082,6 +1107,9 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection<InlinedConnecti
             Kind::Kafka(kafka) => GenericSourceConnection::Kafka(kafka.into_rust()?),
             Kind::Postgres(postgres) => GenericSourceConnection::Postgres(postgres.into_rust()?),
             Kind::Mysql(mysql) => GenericSourceConnection::MySql(mysql.into_rust()?),
+            Kind::SqlServer(sql_server) => {
+                GenericSourceConnection::SqlServer(sql_server.into_rust()?)
+            }
             Kind::Loadgen(loadgen) => GenericSourceConnection::LoadGenerator(loadgen.into_rust()?),
         })
     }
@@ -1097,6 +1125,7 @@ pub enum SourceExportDetails {
     Kafka(KafkaSourceExportDetails),
     Postgres(PostgresSourceExportDetails),
     MySql(MySqlSourceExportDetails),
+    SqlServer(SqlServerSourceExportDetails),
     LoadGenerator(LoadGeneratorSourceExportDetails),
 }
 
@@ -1110,6 +1139,7 @@ impl crate::AlterCompatible for SourceExportDetails {
             (Self::Kafka(s), Self::Kafka(o)) => s.alter_compatible(id, o),
             (Self::Postgres(s), Self::Postgres(o)) => s.alter_compatible(id, o),
             (Self::MySql(s), Self::MySql(o)) => s.alter_compatible(id, o),
+            (Self::SqlServer(s), Self::SqlServer(o)) => s.alter_compatible(id, o),
             (Self::LoadGenerator(s), Self::LoadGenerator(o)) => s.alter_compatible(id, o),
             _ => Err(AlterError { id }),
         };
@@ -1137,6 +1167,9 @@ impl RustType<ProtoSourceExportDetails> for SourceExportDetails {
                     Some(Kind::Postgres(details.into_proto()))
                 }
                 SourceExportDetails::MySql(details) => Some(Kind::Mysql(details.into_proto())),
+                SourceExportDetails::SqlServer(details) => {
+                    Some(Kind::SqlServer(details.into_proto()))
+                }
                 SourceExportDetails::LoadGenerator(details) => {
                     Some(Kind::Loadgen(details.into_proto()))
                 }
@@ -1151,6 +1184,7 @@ impl RustType<ProtoSourceExportDetails> for SourceExportDetails {
             Some(Kind::Kafka(details)) => SourceExportDetails::Kafka(details.into_rust()?),
             Some(Kind::Postgres(details)) => SourceExportDetails::Postgres(details.into_rust()?),
             Some(Kind::Mysql(details)) => SourceExportDetails::MySql(details.into_rust()?),
+            Some(Kind::SqlServer(details)) => SourceExportDetails::SqlServer(details.into_rust()?),
             Some(Kind::Loadgen(details)) => {
                 SourceExportDetails::LoadGenerator(details.into_rust()?)
             }
@@ -1171,6 +1205,10 @@ pub enum SourceExportStatementDetails {
         table: mz_mysql_util::MySqlTableDesc,
         initial_gtid_set: String,
     },
+    SqlServer {
+        capture_instance: String,
+        table: mz_sql_server_util::SqlServerTableDesc,
+    },
     LoadGenerator {
         output: LoadGeneratorOutput,
     },
@@ -1198,6 +1236,17 @@ impl RustType<ProtoSourceExportStatementDetails> for SourceExportStatementDetail
                     },
                 )),
             },
+            SourceExportStatementDetails::SqlServer {
+                capture_instance,
+                table,
+            } => ProtoSourceExportStatementDetails {
+                kind: Some(proto_source_export_statement_details::Kind::SqlServer(
+                    sql_server::ProtoSqlServerSourceExportStatementDetails {
+                        capture_instance: capture_instance.clone(),
+                        table: Some(table.into_proto()),
+                    },
+                )),
+            },
             SourceExportStatementDetails::LoadGenerator { output } => {
                 ProtoSourceExportStatementDetails {
                     kind: Some(proto_source_export_statement_details::Kind::Loadgen(
@@ -1230,6 +1279,12 @@ impl RustType<ProtoSourceExportStatementDetails> for SourceExportStatementDetail
 
                 initial_gtid_set: details.initial_gtid_set,
             },
+            Some(Kind::SqlServer(details)) => SourceExportStatementDetails::SqlServer {
+                capture_instance: details.capture_instance,
+                table: details
+                    .table
+                    .into_rust_if_some("ProtoSqlServerSourceExportStatementDetails::table")?,
+            },
             Some(Kind::Loadgen(details)) => SourceExportStatementDetails::LoadGenerator {
                 output: details
                     .output
@@ -1401,6 +1456,16 @@ impl ExternalCatalogReference for &mz_mysql_util::MySqlTableDesc {
     }
 }
 
+impl ExternalCatalogReference for &mz_sql_server_util::SqlServerTableDesc {
+    fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    fn item_name(&self) -> &str {
+        &self.name
+    }
+}
+
 impl ExternalCatalogReference for mz_postgres_util::desc::PostgresTableDesc {
     fn schema_name(&self) -> &str {
         &self.namespace
@@ -10,7 +10,7 @@
 //! Types and traits related to the *decoding* of data for sources.
 
 use anyhow::Context;
-use mz_interchange::{avro, protobuf};
+use mz_interchange::{avro, json, protobuf};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::regex::any_regex;
 use mz_repr::{ColumnType, GlobalId, RelationDesc, ScalarType};
@@ -120,6 +120,10 @@ pub enum DataEncoding<C: ConnectionAccess = InlinedConnection> {
     Regex(RegexEncoding),
     Bytes,
     Json,
+    /// A JSON array whose elements are each decoded as a separate record.
+    JsonArray,
+    /// JSON objects decoded into typed columns according to a JSON Schema.
+    JsonSchema(JsonSchemaEncoding),
     Text,
 }
 
@@ -134,6 +138,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<DataEncoding, R>
             Self::Regex(conn) => DataEncoding::Regex(conn),
             Self::Bytes => DataEncoding::Bytes,
             Self::Json => DataEncoding::Json,
+            Self::JsonArray => DataEncoding::JsonArray,
+            Self::JsonSchema(conn) => DataEncoding::JsonSchema(conn),
             Self::Text => DataEncoding::Text,
         }
     }
@@ -151,6 +157,8 @@ impl RustType<ProtoDataEncoding> for DataEncoding {
                 DataEncoding::Bytes => Kind::Bytes(()),
                 DataEncoding::Text => Kind::Text(()),
                 DataEncoding::Json => Kind::Json(()),
+                DataEncoding::JsonArray => Kind::JsonArray(()),
+                DataEncoding::JsonSchema(e) => Kind::JsonSchema(e.into_proto()),
             }),
         }
     }
@@ -168,6 +176,8 @@ impl RustType<ProtoDataEncoding> for DataEncoding {
             Kind::Bytes(()) => DataEncoding::Bytes,
             Kind::Text(()) => DataEncoding::Text,
             Kind::Json(()) => DataEncoding::Json,
+            Kind::JsonArray(()) => DataEncoding::JsonArray,
+            Kind::JsonSchema(e) => DataEncoding::JsonSchema(e.into_rust()?),
         })
     }
 }
@@ -190,6 +200,8 @@ impl<C: ConnectionAccess> DataEncoding<C> {
             Self::Regex(_) => "regex",
             Self::Bytes => "bytes",
             Self::Json => "json",
+            Self::JsonArray => "json array",
+            Self::JsonSchema(_) => "json schema",
             Self::Text => "text",
         }
     }
@@ -202,9 +214,18 @@ impl<C: ConnectionAccess> DataEncoding<C> {
             Self::Bytes => RelationDesc::builder()
                 .with_column("data", ScalarType::Bytes.nullable(false))
                 .finish(),
-            Self::Json => RelationDesc::builder()
+            Self::Json | Self::JsonArray => RelationDesc::builder()
                 .with_column("data", ScalarType::Jsonb.nullable(false))
                 .finish(),
+            Self::JsonSchema(JsonSchemaEncoding { schema, .. }) => {
+                json::json_schema_to_columns(schema)
+                    .context("validating json schema")?
+                    .into_iter()
+                    .fold(RelationDesc::builder(), |desc, (name, ty)| {
+                        desc.with_column(name, ty)
+                    })
+                    .finish()
+            }
             Self::Avro(AvroEncoding { schema, .. }) => {
                 let parsed_schema = avro::parse_schema(schema).context("validating avro schema")?;
                 avro::schema_to_relationdesc(parsed_schema).context("validating avro schema")?
@@ -261,6 +282,8 @@ impl<C: ConnectionAccess> DataEncoding<C> {
         match self {
             Self::Bytes => "Bytes",
             Self::Json => "Json",
+            Self::JsonArray => "JsonArray",
+            Self::JsonSchema(_) => "JsonSchema",
             Self::Avro(_) => "Avro",
             Self::Protobuf(_) => "Protobuf",
             Self::Regex { .. } => "Regex",
@@ -409,6 +432,29 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
     }
 }
 
+/// Encoding in JSON format, typed by a JSON Schema.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonSchemaEncoding {
+    pub schema: String,
+    pub confluent_wire_format: bool,
+}
+
+impl RustType<ProtoJsonSchemaEncoding> for JsonSchemaEncoding {
+    fn into_proto(&self) -> ProtoJsonSchemaEncoding {
+        ProtoJsonSchemaEncoding {
+            schema: self.schema.clone(),
+            confluent_wire_format: self.confluent_wire_format,
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonSchemaEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(JsonSchemaEncoding {
+            schema: proto.schema,
+            confluent_wire_format: proto.confluent_wire_format,
+        })
+    }
+}
+
 /// Arguments necessary to define how to decode from CSV format
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct CsvEncoding {
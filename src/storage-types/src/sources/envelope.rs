@@ -308,6 +308,7 @@ impl UnplannedSourceEnvelope {
                                             .map(|(name, ty)| (name.clone(), ty.clone()))
                                             .collect(),
                                         custom_id: None,
+                                        custom_oid: None,
                                     },
                                 }]);
 
@@ -466,6 +467,7 @@ fn compute_envelope_value_desc(
                     )]
                     .into(),
                     custom_id: None,
+                    custom_oid: None,
                 },
             });
             types.extend(value_desc.iter_types().map(|t| t.clone().nullable(true)));
@@ -367,6 +367,7 @@ pub fn kafka_metadata_columns_desc(
                         ]
                         .into(),
                         custom_id: None,
+                        custom_oid: None,
                     }),
                     custom_id: None,
                 }
@@ -21,12 +21,15 @@ use std::time::Duration;
 use differential_dataflow::capture::{Message, Progress};
 use differential_dataflow::{AsCollection, Collection, Hashable};
 use futures::StreamExt;
+use mz_interchange::json::JsonSchemaDecoder;
 use mz_ore::error::ErrorExt;
 use mz_ore::future::InTask;
 use mz_repr::{Datum, Diff, Row};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::errors::{CsrConnectError, DecodeError, DecodeErrorKind};
-use mz_storage_types::sources::encoding::{AvroEncoding, DataEncoding, RegexEncoding};
+use mz_storage_types::sources::encoding::{
+    AvroEncoding, DataEncoding, JsonSchemaEncoding, RegexEncoding,
+};
 use mz_timely_util::builder_async::{
     Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
 };
@@ -209,6 +212,7 @@ pub(crate) enum PreDelimitedFormat {
     Bytes,
     Text,
     Json,
+    JsonSchema(JsonSchemaDecoder),
     Regex(Regex, Row),
     Protobuf(ProtobufDecoderState),
 }
@@ -225,6 +229,14 @@ impl PreDelimitedFormat {
                 })?;
                 Ok(Some(j.into_row()))
             }
+            PreDelimitedFormat::JsonSchema(decoder) => {
+                let row = decoder.decode(bytes).map_err(|e| {
+                    DecodeErrorKind::Bytes(
+                        format!("Failed to decode JSON: {}", e.display_with_causes()).into(),
+                    )
+                })?;
+                Ok(Some(row))
+            }
             PreDelimitedFormat::Text => {
                 let s = std::str::from_utf8(bytes)
                     .map_err(|_| DecodeErrorKind::Text("Failed to decode UTF-8".into()))?;
@@ -258,6 +270,9 @@ pub(crate) enum DataDecoderInner {
         format: PreDelimitedFormat,
     },
     Csv(CsvDecoderState),
+    /// Expands each message, a JSON array, into one row per element. See
+    /// [`decode_json_array`].
+    JsonArray,
 
     PreDelimited(PreDelimitedFormat),
 }
@@ -286,6 +301,9 @@ impl DataDecoder {
             }
             DataDecoderInner::Avro(avro) => avro.decode(bytes).await?,
             DataDecoderInner::Csv(csv) => csv.decode(bytes),
+            DataDecoderInner::JsonArray => {
+                unreachable!("JSON arrays are expanded by `decode_json_array`")
+            }
             DataDecoderInner::PreDelimited(format) => {
                 let result = format.decode(*bytes);
                 *bytes = &[];
@@ -373,6 +391,7 @@ async fn get_decoder(
         DataEncoding::Text
         | DataEncoding::Bytes
         | DataEncoding::Json
+        | DataEncoding::JsonSchema(_)
         | DataEncoding::Protobuf(_)
         | DataEncoding::Regex(_) => {
             let after_delimiting = match encoding {
@@ -387,6 +406,13 @@ async fn get_decoder(
                 }
                 DataEncoding::Bytes => PreDelimitedFormat::Bytes,
                 DataEncoding::Json => PreDelimitedFormat::Json,
+                DataEncoding::JsonSchema(JsonSchemaEncoding {
+                    schema,
+                    confluent_wire_format,
+                }) => PreDelimitedFormat::JsonSchema(
+                    JsonSchemaDecoder::new(&schema, confluent_wire_format)
+                        .expect("JSON schema provided to source is pre-validated"),
+                ),
                 DataEncoding::Text => PreDelimitedFormat::Text,
                 _ => unreachable!(),
             };
@@ -407,6 +433,10 @@ async fn get_decoder(
                 metrics,
             }
         }
+        DataEncoding::JsonArray => DataDecoder {
+            inner: DataDecoderInner::JsonArray,
+            metrics,
+        },
     };
     Ok(decoder)
 }
@@ -441,9 +471,38 @@ async fn decode_delimited(
     }))
 }
 
+/// Decode a message containing a JSON array into one `jsonb` row per element.
+fn decode_json_array(buf: &[u8]) -> Result<Vec<Row>, DecodeError> {
+    let to_error = |msg: String| DecodeError {
+        kind: DecodeErrorKind::Bytes(msg.into()),
+        raw: buf.to_vec(),
+    };
+    let value: serde_json::Value = serde_json::from_slice(buf).map_err(|e| {
+        to_error(format!(
+            "Failed to decode JSON: {}",
+            e.display_with_causes()
+        ))
+    })?;
+    let serde_json::Value::Array(elements) = value else {
+        return Err(to_error(
+            "Failed to decode JSON: expected a JSON array".into(),
+        ));
+    };
+    elements
+        .into_iter()
+        .map(|element| {
+            let jsonb = mz_repr::adt::jsonb::Jsonb::from_serde_json(element)
+                .map_err(|e| to_error(format!("Failed to decode JSON: {e:#}")))?;
+            Ok(jsonb.into_row())
+        })
+        .collect()
+}
+
 /// Decode already delimited records of data.
 ///
 /// Precondition: each record in the stream has at most one key and at most one value.
+/// The only exception are JSON ARRAY values, which are expanded into one record
+/// per array element that all share the same key and metadata.
 /// This function is useful mainly for decoding data from systems like Kafka,
 /// that have already separated the stream into records/messages/etc. before we
 /// decode them.
@@ -527,27 +586,44 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
                                 None => None,
                             };
 
-                            let value = match output.value.unpack_first() {
+                            let values = match output.value.unpack_first() {
+                                Datum::Bytes(buf)
+                                    if matches!(
+                                        value_decoder.inner,
+                                        DataDecoderInner::JsonArray
+                                    ) =>
+                                {
+                                    match decode_json_array(buf) {
+                                        Ok(rows) => {
+                                            rows.into_iter().map(|row| Some(Ok(row))).collect()
+                                        }
+                                        Err(err) => vec![Some(Err(err))],
+                                    }
+                                }
                                 Datum::Bytes(buf) => {
-                                    decode_delimited(&mut value_decoder, buf).await?.transpose()
+                                    vec![decode_delimited(&mut value_decoder, buf)
+                                        .await?
+                                        .transpose()]
                                 }
-                                Datum::Null => None,
+                                Datum::Null => vec![None],
                                 d => unreachable!("invalid datum: {d}"),
                             };
 
-                            if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
-                                n_errors += 1;
-                            } else if matches!(&value, Some(Ok(_))) {
-                                n_successes += 1;
-                            }
+                            for value in values {
+                                if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
+                                    n_errors += 1;
+                                } else if matches!(&value, Some(Ok(_))) {
+                                    n_successes += 1;
+                                }
 
-                            let result = DecodeResult {
-                                key,
-                                value,
-                                metadata: output.metadata.clone(),
-                                from_time: output.from_time.clone(),
-                            };
-                            output_container.push((result, ts.clone(), *diff));
+                                let result = DecodeResult {
+                                    key: key.clone(),
+                                    value,
+                                    metadata: output.metadata.clone(),
+                                    from_time: output.from_time.clone(),
+                                };
+                                output_container.push((result, ts.clone(), *diff));
+                            }
                         }
 
                         // Matching historical practice, we only log metrics on the value decoder.
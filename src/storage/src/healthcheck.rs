@@ -50,6 +50,9 @@ pub enum StatusNamespace {
     Kafka,
     Postgres,
     MySql,
+    SqlServer,
+    Iceberg,
+    S3,
     Ssh,
     Upsert,
     Decode,
@@ -70,6 +73,9 @@ impl fmt::Display for StatusNamespace {
             Kafka => write!(f, "kafka"),
             Postgres => write!(f, "postgres"),
             MySql => write!(f, "mysql"),
+            SqlServer => write!(f, "sql-server"),
+            Iceberg => write!(f, "iceberg"),
+            S3 => write!(f, "s3"),
             Ssh => write!(f, "ssh"),
             Upsert => write!(f, "upsert"),
             Decode => write!(f, "decode"),
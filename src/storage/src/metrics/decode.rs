@@ -37,10 +37,12 @@ impl DecodeMetricDefs {
         let format_label = match decoder {
             DataDecoderInner::Avro(_) => "avro",
             DataDecoderInner::Csv(_) => "csv",
+            DataDecoderInner::JsonArray => "json_array",
             DataDecoderInner::DelimitedBytes { format, .. }
             | DataDecoderInner::PreDelimited(format) => match format {
                 PreDelimitedFormat::Bytes => "raw",
                 PreDelimitedFormat::Json => "json",
+                PreDelimitedFormat::JsonSchema(..) => "json_schema",
                 PreDelimitedFormat::Text => "text",
                 PreDelimitedFormat::Regex(..) => "regex",
                 PreDelimitedFormat::Protobuf(..) => "protobuf",
@@ -223,5 +223,8 @@ where
 {
     match connection {
         StorageSinkConnection::Kafka(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Iceberg(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::S3(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Postgres(connection) => Box::new(connection.clone()),
     }
 }
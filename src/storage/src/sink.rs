@@ -9,4 +9,8 @@
 
 //! Moving data to external systems
 
+mod iceberg;
 mod kafka;
+mod postgres;
+mod s3;
+mod writer;
@@ -0,0 +1,472 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of an [`IcebergSinkConnection`].
+//!
+//! All updates are exchanged to a single worker, which writes them to Parquet files in the
+//! table's data directory and periodically commits the accumulated files as a new snapshot of the
+//! table. The table is written with upsert semantics: every update to a key produces an equality
+//! delete of the key, and every update that has a new value also produces a data row.
+//!
+//! # Sequence numbers
+//!
+//! An equality delete in Iceberg only applies to data rows that were committed with a lower data
+//! sequence number. A snapshot usually assigns a single sequence number to all of its files, which
+//! would make it impossible to delete a row that was inserted at an earlier timestamp covered by
+//! the same snapshot. Instead, the files written for each timestamp form a group that is assigned
+//! its own sequence number, so a snapshot can cover many timestamps without losing updates.
+//!
+//! # Progress
+//!
+//! The sink records its progress in the properties of the table, next to the id and version of
+//! the sink. Properties are committed atomically with the snapshot that contains the data up to
+//! the recorded frontier, and the sink resumes from that frontier after a restart. Files written
+//! after the last commit are orphaned when the sink restarts and are never referenced by the
+//! table.
+//!
+//! A newer version of the sink fences out older versions by recording its version in the table
+//! properties. Every commit first checks the properties of the latest version of the table.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context};
+use differential_dataflow::Collection;
+use mz_dyncfg::ConfigValHandle;
+use mz_interchange::avro::DiffPair;
+use mz_ore::cast::CastFrom;
+use mz_ore::future::InTask;
+use mz_persist_client::Diagnostics;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_repr::{Diff, GlobalId, RelationDesc, Row, Timestamp};
+use mz_storage_operators::iceberg::{DataFile, DataFileContent, DataFileWriter, IcebergTable};
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::dyncfgs::{
+    ICEBERG_SINK_COMMIT_INTERVAL, ICEBERG_SINK_TARGET_FILE_SIZE_BYTES,
+};
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::iceberg::IcebergTableLocation;
+use mz_storage_types::sinks::{IcebergSinkConnection, StorageSinkDesc};
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::PressOnDropButton;
+use timely::dataflow::operators::{Concatenate, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::debug;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::sink::writer::{sink_collection, SinkWriter};
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+/// The table property that records the id of the sink writing to the table.
+const SINK_ID_PROPERTY: &str = "materialize.sink-id";
+/// The table property that records the version of the sink writing to the table.
+const SINK_VERSION_PROPERTY: &str = "materialize.sink-version";
+/// The table property that records the frontier up to which the table contains all updates.
+const FRONTIER_PROPERTY: &str = "materialize.frontier";
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for IcebergSinkConnection {
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        Some(&self.key_desc_and_indices.1)
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_handle = {
+            let persist = Arc::clone(&storage_state.persist_clients);
+            let shard_meta = sink.to_storage_metadata.clone();
+            async move {
+                let client = persist.open(shard_meta.persist_location).await?;
+                let handle = client
+                    .open_writer(
+                        shard_meta.data_shard,
+                        Arc::new(shard_meta.relation_desc),
+                        Arc::new(UnitSchema),
+                        Diagnostics::from_purpose("sink handle"),
+                    )
+                    .await?;
+                Ok(handle)
+            }
+        };
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let writer = {
+            let connection = self.clone();
+            let sink_version = sink.version;
+            let storage_configuration = storage_state.storage_configuration.clone();
+            async move {
+                IcebergWriter::new(
+                    sink_id,
+                    &connection,
+                    &storage_configuration,
+                    statistics,
+                    sink_version,
+                )
+                .await
+            }
+        };
+        let (sink_status, sink_token) = sink_collection(
+            format!("iceberg-{sink_id}-sink"),
+            &input,
+            sink_id,
+            sink,
+            &storage_state.storage_configuration,
+            write_handle,
+            write_frontier,
+            writer,
+            StatusNamespace::Iceberg,
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            id: None,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::Iceberg,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// The files written for a single timestamp.
+struct FileGroup {
+    data: DataFileWriter,
+    deletes: DataFileWriter,
+}
+
+/// Writes updates to an Iceberg table and tracks the files that have yet to be committed.
+struct IcebergWriter {
+    sink_id: GlobalId,
+    sink_version: u64,
+    table: IcebergTable,
+    value_desc: Arc<RelationDesc>,
+    key_desc: Arc<RelationDesc>,
+    key_field_ids: Vec<i32>,
+    target_file_size: u64,
+    /// The frontier that the sink recorded in the table with its last commit.
+    committed_upper: Antichain<Timestamp>,
+    /// The timestamp that is currently being written and its files, if any.
+    active_group: Option<(Timestamp, FileGroup)>,
+    /// The files of all finished timestamps since the last commit, in timestamp order.
+    pending_groups: Vec<Vec<DataFile>>,
+    statistics: SinkStatistics,
+    staged_messages: u64,
+    staged_bytes: u64,
+    commit_interval: ConfigValHandle<Duration>,
+    /// When the last snapshot was committed.
+    last_commit: Instant,
+}
+
+impl IcebergWriter {
+    /// Loads or creates the table of the sink and returns a writer for it. The frontier from which
+    /// the sink must resume is the committed upper of the writer.
+    async fn new(
+        sink_id: GlobalId,
+        connection: &IcebergSinkConnection,
+        storage_configuration: &StorageConfiguration,
+        statistics: SinkStatistics,
+        sink_version: u64,
+    ) -> Result<Self, anyhow::Error> {
+        let sdk_config = connection
+            .aws_connection
+            .load_sdk_config(
+                &storage_configuration.connection_context,
+                connection.connection_id,
+                InTask::Yes,
+            )
+            .await?;
+        let location = IcebergTableLocation::parse(&connection.table_uri)?;
+        let (key_desc, key_indices) = &connection.key_desc_and_indices;
+
+        let mut properties = BTreeMap::new();
+        properties.insert(SINK_ID_PROPERTY.to_string(), sink_id.to_string());
+        properties.insert(SINK_VERSION_PROPERTY.to_string(), sink_version.to_string());
+        properties.insert(
+            FRONTIER_PROPERTY.to_string(),
+            encode_frontier(&Antichain::from_elem(Timestamp::minimum())),
+        );
+        let table = IcebergTable::load_or_create(
+            sdk_config,
+            location,
+            &connection.value_desc,
+            key_indices,
+            properties,
+        )
+        .await?;
+
+        let resume_upper = check_properties(sink_id, sink_version, table.properties())?;
+
+        let target_file_size = u64::cast_from(
+            ICEBERG_SINK_TARGET_FILE_SIZE_BYTES.get(storage_configuration.config_set()),
+        );
+        let commit_interval =
+            ICEBERG_SINK_COMMIT_INTERVAL.handle(storage_configuration.config_set());
+        let writer = IcebergWriter {
+            sink_id,
+            sink_version,
+            table,
+            value_desc: Arc::new(connection.value_desc.clone()),
+            key_desc: Arc::new(key_desc.clone()),
+            key_field_ids: key_indices
+                .iter()
+                .map(|i| mz_storage_operators::iceberg::field_id(*i))
+                .collect(),
+            target_file_size,
+            committed_upper: resume_upper,
+            active_group: None,
+            pending_groups: vec![],
+            statistics,
+            staged_messages: 0,
+            staged_bytes: 0,
+            commit_interval,
+            last_commit: Instant::now(),
+        };
+        Ok(writer)
+    }
+
+    /// Finishes the files of the current timestamp, if any.
+    async fn finish_group(&mut self) -> Result<(), anyhow::Error> {
+        if let Some((_, group)) = self.active_group.take() {
+            let mut files = group.deletes.finish().await?;
+            files.extend(group.data.finish().await?);
+            self.pending_groups.push(files);
+        }
+        Ok(())
+    }
+
+    /// Commits all pending files to the table and records `upper` as the new frontier of the
+    /// sink.
+    async fn commit(&mut self, upper: &Antichain<Timestamp>) -> Result<(), anyhow::Error> {
+        self.finish_group().await?;
+
+        let mut properties = BTreeMap::new();
+        properties.insert(
+            SINK_VERSION_PROPERTY.to_string(),
+            self.sink_version.to_string(),
+        );
+        properties.insert(FRONTIER_PROPERTY.to_string(), encode_frontier(upper));
+
+        // A commit that lost against another writer is retried, but only if that writer didn't
+        // commit updates of the sink, which would otherwise be applied out of order.
+        let (sink_id, sink_version) = (self.sink_id, self.sink_version);
+        let committed_upper = &self.committed_upper;
+        let groups = std::mem::take(&mut self.pending_groups);
+        self.table
+            .commit(groups, properties, |properties| {
+                let frontier = check_properties(sink_id, sink_version, properties)?;
+                if frontier != *committed_upper {
+                    bail!(
+                        "concurrent commit to Iceberg table: expected frontier {}, found {}",
+                        committed_upper.pretty(),
+                        frontier.pretty()
+                    );
+                }
+                Ok(())
+            })
+            .await?;
+        self.committed_upper.clone_from(upper);
+        self.last_commit = Instant::now();
+
+        self.statistics
+            .inc_messages_committed_by(self.staged_messages);
+        self.statistics.inc_bytes_committed_by(self.staged_bytes);
+        self.staged_messages = 0;
+        self.staged_bytes = 0;
+        Ok(())
+    }
+
+    /// Reports whether there are files that have yet to be committed.
+    fn has_pending_files(&self) -> bool {
+        self.active_group.is_some() || !self.pending_groups.is_empty()
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl SinkWriter for IcebergWriter {
+    fn committed_upper(&self) -> &Antichain<Timestamp> {
+        &self.committed_upper
+    }
+
+    /// Writes an update to the files of its timestamp, which starts a new group of files if the
+    /// timestamp differs from that of the previous update.
+    async fn write(
+        &mut self,
+        key: Option<Row>,
+        value: DiffPair<Row>,
+        time: Timestamp,
+        diff: Diff,
+    ) -> Result<(), anyhow::Error> {
+        assert_eq!(diff, 1, "invalid sink update");
+        let key = key.expect("Iceberg sinks always have a key");
+
+        if self.active_group.as_ref().is_some_and(|(t, _)| *t != time) {
+            self.finish_group().await?;
+        }
+        let group = match self.active_group {
+            Some((_, ref mut group)) => group,
+            None => {
+                let all_field_ids = (0..self.value_desc.arity())
+                    .map(mz_storage_operators::iceberg::field_id)
+                    .collect();
+                let group = FileGroup {
+                    data: self.table.file_writer(
+                        DataFileContent::Data,
+                        Arc::clone(&self.value_desc),
+                        all_field_ids,
+                        self.target_file_size,
+                    ),
+                    deletes: self.table.file_writer(
+                        DataFileContent::EqualityDeletes,
+                        Arc::clone(&self.key_desc),
+                        self.key_field_ids.clone(),
+                        self.target_file_size,
+                    ),
+                };
+                &mut self.active_group.insert((time, group)).1
+            }
+        };
+
+        // Every update replaces whatever row the table previously held for the key, which keeps
+        // the table correct even if the same update is written more than once.
+        group.deletes.add_row(&key).await?;
+        let mut bytes = key.byte_len();
+        if let Some(after) = value.after {
+            group.data.add_row(&after).await?;
+            bytes += after.byte_len();
+        }
+
+        let bytes = u64::cast_from(bytes);
+        self.statistics.inc_messages_staged_by(1);
+        self.staged_messages += 1;
+        self.statistics.inc_bytes_staged_by(bytes);
+        self.staged_bytes += bytes;
+        Ok(())
+    }
+
+    /// Committing creates a new snapshot and metadata file, so we only do it periodically, or
+    /// when the input is complete.
+    async fn advance(&mut self, frontier: &Antichain<Timestamp>) -> Result<(), anyhow::Error> {
+        let commit_due = self.last_commit.elapsed() >= self.commit_interval.get()
+            && (self.has_pending_files() || self.committed_upper != *frontier);
+        if commit_due || frontier.is_empty() {
+            debug!("committing Iceberg snapshot for {}", frontier.pretty());
+            self.commit(frontier).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks that the table properties were written by this sink and not by a newer version of it,
+/// and returns the frontier they record.
+fn check_properties(
+    sink_id: GlobalId,
+    sink_version: u64,
+    properties: &BTreeMap<String, String>,
+) -> Result<Antichain<Timestamp>, anyhow::Error> {
+    match properties.get(SINK_ID_PROPERTY) {
+        Some(id) if *id == sink_id.to_string() => {}
+        Some(id) => bail!("Iceberg table is written to by another sink: {id}"),
+        None => bail!("Iceberg table was not created by this sink"),
+    }
+    let version: u64 = properties
+        .get(SINK_VERSION_PROPERTY)
+        .ok_or_else(|| anyhow!("Iceberg table is missing the {SINK_VERSION_PROPERTY} property"))?
+        .parse()
+        .context("invalid sink version")?;
+    if sink_version < version {
+        bail!(
+            "Fenced off by newer version of the sink. ours={} theirs={}",
+            sink_version,
+            version
+        );
+    }
+    let frontier = properties
+        .get(FRONTIER_PROPERTY)
+        .ok_or_else(|| anyhow!("Iceberg table is missing the {FRONTIER_PROPERTY} property"))?;
+    decode_frontier(frontier)
+}
+
+fn encode_frontier(frontier: &Antichain<Timestamp>) -> String {
+    match frontier.as_option() {
+        Some(t) => t.to_string(),
+        None => String::new(),
+    }
+}
+
+fn decode_frontier(frontier: &str) -> Result<Antichain<Timestamp>, anyhow::Error> {
+    if frontier.is_empty() {
+        return Ok(Antichain::new());
+    }
+    let t: u64 = frontier.parse().context("invalid sink frontier")?;
+    Ok(Antichain::from_elem(Timestamp::from(t)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_check_properties() {
+        let sink_id = GlobalId::User(1);
+        let mut properties = BTreeMap::new();
+        assert!(check_properties(sink_id, 1, &properties).is_err());
+
+        properties.insert(SINK_ID_PROPERTY.to_string(), sink_id.to_string());
+        properties.insert(SINK_VERSION_PROPERTY.to_string(), "1".to_string());
+        properties.insert(FRONTIER_PROPERTY.to_string(), "42".to_string());
+        assert_eq!(
+            check_properties(sink_id, 1, &properties).unwrap(),
+            Antichain::from_elem(Timestamp::from(42u64))
+        );
+        assert_eq!(
+            check_properties(sink_id, 2, &properties).unwrap(),
+            Antichain::from_elem(Timestamp::from(42u64))
+        );
+        // Newer versions of the sink fence out older ones.
+        assert!(check_properties(sink_id, 0, &properties).is_err());
+        // Tables belonging to other sinks are never written to.
+        assert!(check_properties(GlobalId::User(2), 1, &properties).is_err());
+
+        properties.insert(FRONTIER_PROPERTY.to_string(), String::new());
+        assert_eq!(
+            check_properties(sink_id, 1, &properties).unwrap(),
+            Antichain::new()
+        );
+    }
+}
@@ -88,6 +88,7 @@ use mz_interchange::avro::{AvroEncoder, DiffPair};
 use mz_interchange::encode::Encode;
 use mz_interchange::envelopes::dbz_format;
 use mz_interchange::json::JsonEncoder;
+use mz_interchange::protobuf::{ProtobufEncoder, ProtobufSchemaGenerator};
 use mz_interchange::text_binary::{BinaryEncoder, TextEncoder};
 use mz_kafka_util::client::{
     GetPartitionsError, MzClientContext, TimeoutConfig, TunnelingClientContext,
@@ -1353,6 +1354,29 @@ fn encode_collection<G: Scope>(
 
                         Some(Box::new(AvroEncoder::new(desc, false, &schema, schema_id)))
                     }
+                    (Some(desc), Some(KafkaSinkFormatType::Protobuf {
+                        schema,
+                        message_name,
+                        compatibility_level,
+                        csr_connection,
+                    })) => {
+                        let ccsr = csr_connection
+                            .connect(&storage_configuration, InTask::Yes)
+                            .await?;
+
+                        let schema_id = mz_storage_client::sink::publish_kafka_schema(
+                            ccsr,
+                            format!("{}-key", connection.topic),
+                            schema,
+                            mz_ccsr::SchemaType::Protobuf,
+                            compatibility_level,
+                        )
+                        .await
+                        .context("error publishing kafka schemas for sink")?;
+
+                        let schema = ProtobufSchemaGenerator::new(desc, false, &message_name);
+                        Some(Box::new(ProtobufEncoder::new(schema, schema_id)))
+                    }
                     (None, None) => None,
                     (desc, format) => {
                         return Err(anyhow!(
@@ -1395,6 +1419,31 @@ fn encode_collection<G: Scope>(
 
                     Box::new(AvroEncoder::new(value_desc, debezium, &schema, schema_id))
                 }
+                KafkaSinkFormatType::Protobuf {
+                    schema,
+                    message_name,
+                    compatibility_level,
+                    csr_connection,
+                } => {
+                    let ccsr = csr_connection
+                        .connect(&storage_configuration, InTask::Yes)
+                        .await?;
+
+                    let schema_id = mz_storage_client::sink::publish_kafka_schema(
+                        ccsr,
+                        format!("{}-value", connection.topic),
+                        schema,
+                        mz_ccsr::SchemaType::Protobuf,
+                        compatibility_level,
+                    )
+                    .await
+                    .context("error publishing kafka schemas for sink")?;
+
+                    // The encoder regenerates the same descriptors that the
+                    // published schema was rendered from.
+                    let schema = ProtobufSchemaGenerator::new(value_desc, debezium, &message_name);
+                    Box::new(ProtobufEncoder::new(schema, schema_id))
+                }
             };
 
             // !IMPORTANT!
@@ -1455,6 +1504,9 @@ fn encode_collection<G: Scope>(
                                 dbz_format(&mut row_buf.packer(), value);
                                 Some(row_buf.clone())
                             }
+                            SinkEnvelope::Append => {
+                                unreachable!("Kafka sinks do not support the append envelope")
+                            }
                         };
                         let value = value.map(|value| value_encoder.encode_unchecked(value));
                         let message = KafkaMessage {
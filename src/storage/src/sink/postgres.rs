@@ -0,0 +1,686 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of a [`PostgresSinkConnection`].
+//!
+//! All updates are exchanged to a single worker, which applies the updates at each timestamp to
+//! the upstream table in a transaction of its own. With `ENVELOPE UPSERT` every update upserts or
+//! deletes the row with its key, which requires a unique constraint on the key columns of the
+//! upstream table. Without an envelope every update is appended as a row of its own, annotated
+//! with its timestamp and diff.
+//!
+//! # Progress
+//!
+//! The sink records its progress in the `mz_sink_progress` table in the schema of the upstream
+//! table, next to the id and version of the sink. Every transaction that applies updates also
+//! advances the recorded frontier, so the sink resumes exactly where it left off after a restart.
+//!
+//! A newer version of the sink fences out older versions by recording its version in the progress
+//! table. Every transaction first locks the row of the sink and checks the recorded version.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::pin::pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context};
+use bytes::Bytes;
+use differential_dataflow::Collection;
+use futures::SinkExt;
+use mz_dyncfg::ConfigValHandle;
+use mz_interchange::avro::DiffPair;
+use mz_ore::cast::CastFrom;
+use mz_ore::future::InTask;
+use mz_persist_client::Diagnostics;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_pgcopy::{encode_copy_format, CopyFormatParams, CopyTextFormatParams};
+use mz_repr::{Datum, Diff, GlobalId, RelationType, Row, ScalarType, Timestamp};
+use mz_sql_parser::ast::{display::AstDisplay, Ident};
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::dyncfgs::PG_SINK_PROGRESS_INTERVAL;
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::postgres::{upstream_columns, PROGRESS_TABLE};
+use mz_storage_types::sinks::{PostgresSinkConnection, SinkEnvelope, StorageSinkDesc};
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::PressOnDropButton;
+use timely::dataflow::operators::{Concatenate, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use tokio_postgres::Transaction;
+use tracing::debug;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::sink::writer::{sink_collection, SinkWriter};
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+/// The temporary table that stages the rows upserted at a timestamp.
+const UPSERTS_TABLE: &str = "mz_sink_upserts";
+/// The temporary table that stages the keys deleted at a timestamp.
+const DELETES_TABLE: &str = "mz_sink_deletes";
+/// The size of the chunks in which data is sent to `COPY ... FROM STDIN`.
+const COPY_CHUNK_SIZE: usize = 1 << 20;
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for PostgresSinkConnection {
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_desc_and_indices
+            .as_ref()
+            .map(|(_desc, indices)| indices.as_slice())
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_handle = {
+            let persist = Arc::clone(&storage_state.persist_clients);
+            let shard_meta = sink.to_storage_metadata.clone();
+            async move {
+                let client = persist.open(shard_meta.persist_location).await?;
+                let handle = client
+                    .open_writer(
+                        shard_meta.data_shard,
+                        Arc::new(shard_meta.relation_desc),
+                        Arc::new(UnitSchema),
+                        Diagnostics::from_purpose("sink handle"),
+                    )
+                    .await?;
+                Ok(handle)
+            }
+        };
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let writer = {
+            let connection = self.clone();
+            let envelope = sink.envelope;
+            let sink_version = sink.version;
+            let storage_configuration = storage_state.storage_configuration.clone();
+            async move {
+                PostgresWriter::new(
+                    sink_id,
+                    &connection,
+                    envelope,
+                    &storage_configuration,
+                    statistics,
+                    sink_version,
+                )
+                .await
+            }
+        };
+        let (sink_status, sink_token) = sink_collection(
+            format!("postgres-{sink_id}-sink"),
+            &input,
+            sink_id,
+            sink,
+            &storage_state.storage_configuration,
+            write_handle,
+            write_frontier,
+            writer,
+            StatusNamespace::Postgres,
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            id: None,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::Postgres,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// The progress of a sink, as recorded in the progress table.
+#[derive(Debug, PartialEq)]
+struct Progress {
+    sink_id: String,
+    sink_version: u64,
+    upper: Antichain<Timestamp>,
+}
+
+impl Progress {
+    fn from_row(row: &tokio_postgres::Row) -> Result<Self, anyhow::Error> {
+        let sink_version: i64 = row.try_get("sink_version")?;
+        let upper: Option<i64> = row.try_get("upper")?;
+        let upper = match upper {
+            Some(upper) => Antichain::from_elem(Timestamp::from(
+                u64::try_from(upper).context("invalid upper")?,
+            )),
+            None => Antichain::new(),
+        };
+        Ok(Progress {
+            sink_id: row.try_get("sink_id")?,
+            sink_version: u64::try_from(sink_version).context("invalid sink version")?,
+            upper,
+        })
+    }
+}
+
+/// Checks that the recorded progress belongs to our sink and that we have not been fenced out by
+/// a newer version of it.
+fn check_progress(
+    progress: &Progress,
+    sink_id: GlobalId,
+    sink_version: u64,
+) -> Result<(), anyhow::Error> {
+    if progress.sink_id != sink_id.to_string() {
+        bail!(
+            "Postgres table is written to by another sink: {}",
+            progress.sink_id
+        );
+    }
+    if progress.sink_version > sink_version {
+        bail!(
+            "Fenced off by newer version of the sink. ours={} theirs={}",
+            sink_version,
+            progress.sink_version
+        );
+    }
+    Ok(())
+}
+
+/// Converts a frontier to the value of the `upper` column of the progress table.
+fn upper_to_sql(upper: &Antichain<Timestamp>) -> Result<Option<i64>, anyhow::Error> {
+    upper
+        .as_option()
+        .map(|t| i64::try_from(u64::from(*t)))
+        .transpose()
+        .context("frontier cannot be recorded in Postgres")
+}
+
+/// Returns the statements that apply the staged deletes and upserts to `table`.
+fn upsert_statements(table: &str, columns: &[String], key_columns: &[String]) -> (String, String) {
+    let key_matches = key_columns
+        .iter()
+        .map(|c| format!("{table}.{c} = d.{c}"))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let delete = format!("DELETE FROM {table} USING pg_temp.{DELETES_TABLE} d WHERE {key_matches}");
+
+    let column_list = columns.join(", ");
+    let updates = columns
+        .iter()
+        .filter(|c| !key_columns.contains(c))
+        .map(|c| format!("{c} = EXCLUDED.{c}"))
+        .collect::<Vec<_>>();
+    let conflict_action = if updates.is_empty() {
+        "DO NOTHING".to_string()
+    } else {
+        format!("DO UPDATE SET {}", updates.join(", "))
+    };
+    let insert = format!(
+        "INSERT INTO {table} ({column_list}) SELECT {column_list} FROM pg_temp.{UPSERTS_TABLE} \
+         ON CONFLICT ({}) {conflict_action}",
+        key_columns.join(", ")
+    );
+    (delete, insert)
+}
+
+/// Returns the size of an update for the purpose of statistics.
+fn update_bytes(value: &DiffPair<Row>) -> usize {
+    value.before.as_ref().map_or(0, |row| row.byte_len())
+        + value.after.as_ref().map_or(0, |row| row.byte_len())
+}
+
+/// Streams `data` into a `COPY ... FROM STDIN` statement.
+async fn copy_in(
+    txn: &Transaction<'_>,
+    statement: &str,
+    data: Vec<u8>,
+) -> Result<u64, anyhow::Error> {
+    let sink = txn.copy_in::<_, Bytes>(statement).await?;
+    let mut sink = pin!(sink);
+    let mut data = Bytes::from(data);
+    while !data.is_empty() {
+        let chunk = data.split_to(std::cmp::min(data.len(), COPY_CHUNK_SIZE));
+        sink.send(chunk).await?;
+    }
+    Ok(sink.as_mut().finish().await?)
+}
+
+/// Applies updates to the upstream table and records the progress of the sink.
+struct PostgresWriter {
+    client: mz_postgres_util::Client,
+    sink_id: GlobalId,
+    sink_version: u64,
+    envelope: SinkEnvelope,
+    /// The name of the upstream table, which identifies the row of the sink in the progress
+    /// table.
+    table_name: String,
+    /// The quoted and qualified name of the upstream table.
+    table: String,
+    /// The quoted and qualified name of the progress table.
+    progress_table: String,
+    /// The quoted names of the upstream columns the sink writes.
+    columns: Vec<String>,
+    /// The quoted names of the key columns, if the sink upserts.
+    key_columns: Vec<String>,
+    /// The type of the rows written to the upstream table.
+    row_typ: RelationType,
+    /// The type of the keys of the sink, if it upserts.
+    key_typ: RelationType,
+    /// The frontier up to which the upstream table contains all updates.
+    upper: Antichain<Timestamp>,
+    /// The updates of the timestamp that is currently being written, if any.
+    pending: Option<(Timestamp, Vec<(Option<Row>, DiffPair<Row>)>)>,
+    statistics: SinkStatistics,
+    row_buf: Row,
+    progress_interval: ConfigValHandle<Duration>,
+    /// When the recorded frontier was last advanced.
+    last_progress: Instant,
+}
+
+impl PostgresWriter {
+    /// Connects to the upstream database and claims the upstream table for the sink. The frontier
+    /// from which the sink must resume is the upper of the writer.
+    async fn new(
+        sink_id: GlobalId,
+        connection: &PostgresSinkConnection,
+        envelope: SinkEnvelope,
+        storage_configuration: &StorageConfiguration,
+        statistics: SinkStatistics,
+        sink_version: u64,
+    ) -> Result<Self, anyhow::Error> {
+        let config = connection
+            .connection
+            .config(
+                &storage_configuration.connection_context.secrets_reader,
+                storage_configuration,
+                InTask::Yes,
+            )
+            .await?;
+        let client = config
+            .connect(
+                "postgres_sink",
+                &storage_configuration.connection_context.ssh_tunnel_manager,
+            )
+            .await?;
+
+        let quote = |name: &str| Ident::new_unchecked(name).to_ast_string();
+        let schema = quote(&connection.schema);
+        let table = format!("{schema}.{}", quote(&connection.table));
+        let progress_table = format!("{schema}.{}", quote(PROGRESS_TABLE));
+        let columns: Vec<_> = upstream_columns(&connection.value_desc, envelope)?
+            .iter()
+            .map(|c| quote(c))
+            .collect();
+
+        let mut row_typ = connection.value_desc.typ().clone();
+        if envelope == SinkEnvelope::Append {
+            row_typ
+                .column_types
+                .push(ScalarType::MzTimestamp.nullable(false));
+            row_typ.column_types.push(ScalarType::Int64.nullable(false));
+        }
+        let (key_columns, key_typ) = match &connection.key_desc_and_indices {
+            Some((key_desc, _)) => (
+                key_desc.iter_names().map(|c| quote(c.as_str())).collect(),
+                key_desc.typ().clone(),
+            ),
+            None => (vec![], RelationType::empty()),
+        };
+
+        // Fail early if the upstream table does not have the columns we write.
+        client
+            .batch_execute(&format!(
+                "SELECT {} FROM {table} LIMIT 0",
+                columns.join(", ")
+            ))
+            .await
+            .with_context(|| format!("validating upstream table {table}"))?;
+        client
+            .batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS {progress_table} (
+                    table_name text PRIMARY KEY,
+                    sink_id text NOT NULL,
+                    sink_version bigint NOT NULL,
+                    upper bigint
+                )"
+            ))
+            .await?;
+        if envelope == SinkEnvelope::Upsert {
+            client
+                .batch_execute(&format!(
+                    "CREATE TEMPORARY TABLE IF NOT EXISTS {UPSERTS_TABLE} ON COMMIT DELETE ROWS
+                        AS SELECT {} FROM {table} WITH NO DATA;
+                    CREATE TEMPORARY TABLE IF NOT EXISTS {DELETES_TABLE} ON COMMIT DELETE ROWS
+                        AS SELECT {} FROM {table} WITH NO DATA;",
+                    columns.join(", "),
+                    key_columns.join(", "),
+                ))
+                .await?;
+        }
+
+        let mut writer = PostgresWriter {
+            client,
+            sink_id,
+            sink_version,
+            envelope,
+            table_name: connection.table.clone(),
+            table,
+            progress_table,
+            columns,
+            key_columns,
+            row_typ,
+            key_typ,
+            upper: Antichain::from_elem(Timestamp::minimum()),
+            pending: None,
+            statistics,
+            row_buf: Row::default(),
+            progress_interval: PG_SINK_PROGRESS_INTERVAL.handle(storage_configuration.config_set()),
+            last_progress: Instant::now(),
+        };
+        writer.claim().await?;
+        Ok(writer)
+    }
+
+    /// Records the version of the sink in the progress table, which fences out older versions,
+    /// and loads the frontier the sink must resume from.
+    async fn claim(&mut self) -> Result<(), anyhow::Error> {
+        let sink_version = i64::try_from(self.sink_version)?;
+        let txn = self.client.transaction().await?;
+        let row = txn
+            .query_opt(
+                &format!(
+                    "SELECT sink_id, sink_version, upper FROM {} WHERE table_name = $1 FOR UPDATE",
+                    self.progress_table
+                ),
+                &[&self.table_name],
+            )
+            .await?;
+        match row {
+            Some(row) => {
+                let progress = Progress::from_row(&row)?;
+                check_progress(&progress, self.sink_id, self.sink_version)?;
+                txn.execute(
+                    &format!(
+                        "UPDATE {} SET sink_version = $2 WHERE table_name = $1",
+                        self.progress_table
+                    ),
+                    &[&self.table_name, &sink_version],
+                )
+                .await?;
+                self.upper = progress.upper;
+            }
+            None => {
+                let upper = upper_to_sql(&self.upper)?;
+                txn.execute(
+                    &format!(
+                        "INSERT INTO {} (table_name, sink_id, sink_version, upper) \
+                         VALUES ($1, $2, $3, $4)",
+                        self.progress_table
+                    ),
+                    &[
+                        &self.table_name,
+                        &self.sink_id.to_string(),
+                        &sink_version,
+                        &upper,
+                    ],
+                )
+                .await?;
+            }
+        }
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Applies the `updates` at `time` in a single transaction, which also advances the recorded
+    /// frontier to `upper`.
+    ///
+    /// Calling this without updates only advances the recorded frontier.
+    async fn apply(
+        &mut self,
+        time: Timestamp,
+        updates: Vec<(Option<Row>, DiffPair<Row>)>,
+        upper: Antichain<Timestamp>,
+    ) -> Result<(), anyhow::Error> {
+        let messages = u64::cast_from(updates.len());
+        let bytes = u64::cast_from(updates.iter().map(|(_, value)| update_bytes(value)).sum());
+        let txn = self.client.transaction().await?;
+
+        let row = txn
+            .query_opt(
+                &format!(
+                    "SELECT sink_id, sink_version, upper FROM {} WHERE table_name = $1 FOR UPDATE",
+                    self.progress_table
+                ),
+                &[&self.table_name],
+            )
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "progress of the sink is missing from {}",
+                    self.progress_table
+                )
+            })?;
+        let progress = Progress::from_row(&row)?;
+        check_progress(&progress, self.sink_id, self.sink_version)?;
+        if progress.upper != self.upper {
+            bail!(
+                "concurrent commit: expected upper {}, found {}",
+                self.upper.pretty(),
+                progress.upper.pretty()
+            );
+        }
+
+        let params = CopyFormatParams::Text(CopyTextFormatParams::default());
+        match self.envelope {
+            SinkEnvelope::Append => {
+                let mut data = vec![];
+                for (_key, value) in updates {
+                    let changes = [(value.before, -1i64), (value.after, 1i64)];
+                    for (row, diff) in changes {
+                        let Some(row) = row else { continue };
+                        let mut packer = self.row_buf.packer();
+                        packer.extend_by_row(&row);
+                        packer.push(Datum::MzTimestamp(time));
+                        packer.push(Datum::Int64(diff));
+                        encode_copy_format(&params, &self.row_buf, &self.row_typ, &mut data)?;
+                    }
+                }
+                if !data.is_empty() {
+                    let statement = format!(
+                        "COPY {} ({}) FROM STDIN",
+                        self.table,
+                        self.columns.join(", ")
+                    );
+                    copy_in(&txn, &statement, data).await?;
+                }
+            }
+            SinkEnvelope::Upsert => {
+                // A key that is not enforced can have several updates at the same timestamp, in
+                // which case the last one wins.
+                let mut latest = BTreeMap::new();
+                for (key, value) in updates {
+                    let key = key.expect("upsert sinks have a key");
+                    latest.insert(key, value.after);
+                }
+
+                let mut upserts = vec![];
+                let mut deletes = vec![];
+                for (key, value) in latest {
+                    match value {
+                        Some(row) => {
+                            encode_copy_format(&params, &row, &self.row_typ, &mut upserts)?
+                        }
+                        None => encode_copy_format(&params, &key, &self.key_typ, &mut deletes)?,
+                    }
+                }
+                let (delete, insert) =
+                    upsert_statements(&self.table, &self.columns, &self.key_columns);
+                if !deletes.is_empty() {
+                    let statement = format!(
+                        "COPY pg_temp.{DELETES_TABLE} ({}) FROM STDIN",
+                        self.key_columns.join(", ")
+                    );
+                    copy_in(&txn, &statement, deletes).await?;
+                    txn.batch_execute(&delete).await?;
+                }
+                if !upserts.is_empty() {
+                    let statement = format!(
+                        "COPY pg_temp.{UPSERTS_TABLE} ({}) FROM STDIN",
+                        self.columns.join(", ")
+                    );
+                    copy_in(&txn, &statement, upserts).await?;
+                    txn.batch_execute(&insert).await?;
+                }
+            }
+            SinkEnvelope::Debezium => {
+                unreachable!("Postgres sinks do not support ENVELOPE DEBEZIUM")
+            }
+        }
+
+        txn.execute(
+            &format!(
+                "UPDATE {} SET upper = $2 WHERE table_name = $1",
+                self.progress_table
+            ),
+            &[&self.table_name, &upper_to_sql(&upper)?],
+        )
+        .await?;
+        txn.commit().await?;
+        self.upper = upper;
+        self.last_progress = Instant::now();
+
+        self.statistics.inc_messages_committed_by(messages);
+        self.statistics.inc_bytes_committed_by(bytes);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl SinkWriter for PostgresWriter {
+    fn committed_upper(&self) -> &Antichain<Timestamp> {
+        &self.upper
+    }
+
+    /// Buffers an update until all updates of its timestamp have been written, which are then
+    /// applied in a transaction of their own.
+    async fn write(
+        &mut self,
+        key: Option<Row>,
+        value: DiffPair<Row>,
+        time: Timestamp,
+        diff: Diff,
+    ) -> Result<(), anyhow::Error> {
+        assert_eq!(diff, 1, "invalid sink update");
+        self.statistics.inc_messages_staged_by(1);
+        self.statistics
+            .inc_bytes_staged_by(u64::cast_from(update_bytes(&value)));
+
+        if let Some((pending_time, _)) = self.pending {
+            if pending_time != time {
+                let (time, updates) = self.pending.take().expect("known to exist");
+                debug!(
+                    "applying {} updates at {time} to {}",
+                    updates.len(),
+                    self.table
+                );
+                self.apply(time, updates, Antichain::from_elem(time.step_forward()))
+                    .await?;
+            }
+        }
+        self.pending
+            .get_or_insert_with(|| (time, vec![]))
+            .1
+            .push((key, value));
+        Ok(())
+    }
+
+    /// The transaction of the last timestamp advances the recorded frontier all the way to
+    /// `frontier`. Without updates, the recorded frontier is only advanced periodically, or when
+    /// the input is complete.
+    async fn advance(&mut self, frontier: &Antichain<Timestamp>) -> Result<(), anyhow::Error> {
+        if let Some((time, updates)) = self.pending.take() {
+            debug!(
+                "applying {} updates at {time} to {}",
+                updates.len(),
+                self.table
+            );
+            self.apply(time, updates, frontier.clone()).await?;
+        }
+        let progress_due = self.last_progress.elapsed() >= self.progress_interval.get();
+        if self.upper != *frontier && (progress_due || frontier.is_empty()) {
+            self.apply(Timestamp::minimum(), vec![], frontier.clone())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_check_progress() {
+        let sink_id = GlobalId::User(1);
+        let progress = Progress {
+            sink_id: sink_id.to_string(),
+            sink_version: 1,
+            upper: Antichain::from_elem(Timestamp::from(10u64)),
+        };
+        assert!(check_progress(&progress, sink_id, 1).is_ok());
+        assert!(check_progress(&progress, sink_id, 2).is_ok());
+        assert!(check_progress(&progress, sink_id, 0).is_err());
+        assert!(check_progress(&progress, GlobalId::User(2), 1).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_upper_to_sql() {
+        let upper = Antichain::from_elem(Timestamp::from(42u64));
+        assert_eq!(upper_to_sql(&upper).unwrap(), Some(42));
+        assert_eq!(upper_to_sql(&Antichain::new()).unwrap(), None);
+        assert!(upper_to_sql(&Antichain::from_elem(Timestamp::MAX)).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_upsert_statements() {
+        let columns = ["id".to_string(), "name".to_string()];
+        let (delete, insert) = upsert_statements("public.t", &columns, &columns[..1]);
+        assert_eq!(
+            delete,
+            "DELETE FROM public.t USING pg_temp.mz_sink_deletes d WHERE public.t.id = d.id"
+        );
+        assert_eq!(
+            insert,
+            "INSERT INTO public.t (id, name) SELECT id, name FROM pg_temp.mz_sink_upserts \
+             ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name"
+        );
+
+        let (_, insert) = upsert_statements("public.t", &columns, &columns);
+        assert!(insert.ends_with("ON CONFLICT (id, name) DO NOTHING"));
+    }
+}
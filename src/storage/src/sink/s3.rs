@@ -0,0 +1,314 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of an [`S3SinkConnection`].
+//!
+//! All updates are exchanged to a single worker, which appends them to Parquet or CSV files below
+//! the location of the sink and periodically commits the files written since the last commit as a
+//! batch. Data files are partitioned by the hour of the timestamp of their updates, using
+//! Hive-style `date=YYYY-MM-DD/hour=HH` directories.
+//!
+//! See [`mz_storage_operators::s3_sink`] for how batches are committed exactly once.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use chrono::DateTime;
+use differential_dataflow::Collection;
+use mz_dyncfg::ConfigValHandle;
+use mz_interchange::avro::DiffPair;
+use mz_interchange::envelopes::dbz_format;
+use mz_ore::cast::CastFrom;
+use mz_ore::future::InTask;
+use mz_persist_client::Diagnostics;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, Timestamp};
+use mz_storage_operators::s3_sink::{BatchWriter, S3SinkStore};
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::dyncfgs::{S3_SINK_COMMIT_INTERVAL, S3_SINK_TARGET_FILE_SIZE_BYTES};
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::s3::S3SinkLocation;
+use mz_storage_types::sinks::{S3SinkConnection, SinkEnvelope, StorageSinkDesc};
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::PressOnDropButton;
+use timely::dataflow::operators::{Concatenate, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::debug;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::sink::writer::{sink_collection, SinkWriter};
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for S3SinkConnection {
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_handle = {
+            let persist = Arc::clone(&storage_state.persist_clients);
+            let shard_meta = sink.to_storage_metadata.clone();
+            async move {
+                let client = persist.open(shard_meta.persist_location).await?;
+                let handle = client
+                    .open_writer(
+                        shard_meta.data_shard,
+                        Arc::new(shard_meta.relation_desc),
+                        Arc::new(UnitSchema),
+                        Diagnostics::from_purpose("sink handle"),
+                    )
+                    .await?;
+                Ok(handle)
+            }
+        };
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let writer = {
+            let connection = self.clone();
+            let envelope = sink.envelope;
+            let sink_version = sink.version;
+            let storage_configuration = storage_state.storage_configuration.clone();
+            async move {
+                S3Writer::new(
+                    sink_id,
+                    connection,
+                    envelope,
+                    &storage_configuration,
+                    statistics,
+                    sink_version,
+                )
+                .await
+            }
+        };
+        let (sink_status, sink_token) = sink_collection(
+            format!("s3-{sink_id}-sink"),
+            &input,
+            sink_id,
+            sink,
+            &storage_state.storage_configuration,
+            write_handle,
+            write_frontier,
+            writer,
+            StatusNamespace::S3,
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            id: None,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::S3,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// Writes updates to the data files of the current batch and commits batches.
+struct S3Writer {
+    store: S3SinkStore,
+    connection: S3SinkConnection,
+    envelope: SinkEnvelope,
+    /// The description of the rows written to the data files.
+    desc: Arc<RelationDesc>,
+    target_file_size: u64,
+    /// The writer of the current batch, if any updates have been written since the last commit.
+    batch: Option<BatchWriter>,
+    row_buf: Row,
+    statistics: SinkStatistics,
+    staged_messages: u64,
+    staged_bytes: u64,
+    commit_interval: ConfigValHandle<Duration>,
+    /// When the last batch was committed.
+    last_commit: Instant,
+}
+
+impl S3Writer {
+    /// Opens the location of the sink and returns a writer for it. The frontier from which the
+    /// sink must resume is the frontier of the store.
+    async fn new(
+        sink_id: GlobalId,
+        connection: S3SinkConnection,
+        envelope: SinkEnvelope,
+        storage_configuration: &StorageConfiguration,
+        statistics: SinkStatistics,
+        sink_version: u64,
+    ) -> Result<Self, anyhow::Error> {
+        let sdk_config = connection
+            .aws_connection
+            .load_sdk_config(
+                &storage_configuration.connection_context,
+                connection.connection_id,
+                InTask::Yes,
+            )
+            .await?;
+        let location = S3SinkLocation::parse(&connection.uri)?;
+        let store = S3SinkStore::open(sdk_config, location, sink_id, sink_version).await?;
+        let desc = mz_storage_types::sinks::s3::output_desc(&connection.value_desc, envelope)?;
+        let target_file_size =
+            u64::cast_from(S3_SINK_TARGET_FILE_SIZE_BYTES.get(storage_configuration.config_set()));
+        let commit_interval = S3_SINK_COMMIT_INTERVAL.handle(storage_configuration.config_set());
+        Ok(S3Writer {
+            store,
+            connection,
+            envelope,
+            desc: Arc::new(desc),
+            target_file_size,
+            batch: None,
+            row_buf: Row::default(),
+            statistics,
+            staged_messages: 0,
+            staged_bytes: 0,
+            commit_interval,
+            last_commit: Instant::now(),
+        })
+    }
+
+    /// Commits the current batch, which holds all updates up to `upper`.
+    async fn commit(&mut self, upper: &Antichain<Timestamp>) -> Result<(), anyhow::Error> {
+        let files = match self.batch.take() {
+            Some(batch) => batch.finish().await?,
+            None => vec![],
+        };
+        self.store.commit(upper.clone(), files).await?;
+        self.last_commit = Instant::now();
+
+        self.statistics
+            .inc_messages_committed_by(self.staged_messages);
+        self.statistics.inc_bytes_committed_by(self.staged_bytes);
+        self.staged_messages = 0;
+        self.staged_bytes = 0;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl SinkWriter for S3Writer {
+    fn committed_upper(&self) -> &Antichain<Timestamp> {
+        self.store.frontier()
+    }
+
+    /// Writes an update to the data files of the current batch.
+    async fn write(
+        &mut self,
+        _key: Option<Row>,
+        value: DiffPair<Row>,
+        time: Timestamp,
+        diff: Diff,
+    ) -> Result<(), anyhow::Error> {
+        assert_eq!(diff, 1, "invalid sink update");
+
+        let partition = partition(time)?;
+        let batch = self.batch.get_or_insert_with(|| {
+            self.store.batch_writer(
+                self.connection.format.clone(),
+                Arc::clone(&self.desc),
+                self.target_file_size,
+            )
+        });
+
+        let mut bytes = 0;
+        match self.envelope {
+            SinkEnvelope::Append => {
+                let changes = [(value.before, -1i64), (value.after, 1i64)];
+                for (row, diff) in changes {
+                    let Some(row) = row else { continue };
+                    let mut packer = self.row_buf.packer();
+                    packer.extend_by_row(&row);
+                    packer.push(Datum::MzTimestamp(time));
+                    packer.push(Datum::Int64(diff));
+                    batch.add_row(&partition, &self.row_buf).await?;
+                    bytes += self.row_buf.byte_len();
+                }
+            }
+            SinkEnvelope::Debezium => {
+                let mut packer = self.row_buf.packer();
+                dbz_format(&mut packer, value);
+                packer.push(Datum::MzTimestamp(time));
+                batch.add_row(&partition, &self.row_buf).await?;
+                bytes += self.row_buf.byte_len();
+            }
+            SinkEnvelope::Upsert => unreachable!("S3 sinks do not support ENVELOPE UPSERT"),
+        }
+
+        let bytes = u64::cast_from(bytes);
+        self.statistics.inc_messages_staged_by(1);
+        self.staged_messages += 1;
+        self.statistics.inc_bytes_staged_by(bytes);
+        self.staged_bytes += bytes;
+        Ok(())
+    }
+
+    /// Commits the current batch periodically, or when the input is complete.
+    async fn advance(&mut self, frontier: &Antichain<Timestamp>) -> Result<(), anyhow::Error> {
+        let commit_due = self.last_commit.elapsed() >= self.commit_interval.get()
+            && (self.batch.is_some() || self.store.frontier() != frontier);
+        if commit_due || frontier.is_empty() {
+            debug!("committing S3 sink batch up to {}", frontier.pretty());
+            self.commit(frontier).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the partition of the data files that hold updates at `time`.
+///
+/// Timestamps are interpreted as milliseconds since the Unix epoch, and data files are
+/// partitioned by the hour (in UTC) of their updates.
+fn partition(time: Timestamp) -> Result<String, anyhow::Error> {
+    let datetime = i64::try_from(u64::from(time))
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .ok_or_else(|| anyhow!("timestamp {} cannot be used as a partition", time))?;
+    Ok(datetime.format("date=%Y-%m-%d/hour=%H").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_partition() {
+        // 2024-03-05T07:08:09.010Z
+        let time = Timestamp::from(1_709_622_489_010u64);
+        assert_eq!(partition(time).unwrap(), "date=2024-03-05/hour=07");
+        assert!(partition(Timestamp::MAX).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The operator shared by sinks that record their progress in the external system they write to.
+//!
+//! The Iceberg, Postgres and S3 sinks commit the updates up to some frontier together with that
+//! frontier, and resume from the recorded frontier after a restart. They only differ in how they
+//! write and commit updates, which is described by a [`SinkWriter`].
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::future::Future;
+use std::rc::Rc;
+
+use anyhow::anyhow;
+use differential_dataflow::{Collection, Hashable};
+use futures::StreamExt;
+use mz_interchange::avro::DiffPair;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::vec::VecExt;
+use mz_persist_client::write::WriteHandle;
+use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::dyncfgs::SINK_BUFFERED_UPDATES_RESIZE_THRESHOLD_ELEMENTS;
+use mz_storage_types::sinks::StorageSinkDesc;
+use mz_storage_types::sources::SourceData;
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::{
+    Event, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::Map;
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use timely::PartialOrder;
+use tracing::{error, info};
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+
+/// Writes the updates of a sink to an external system, which records the frontier up to which
+/// the sink has committed updates.
+#[async_trait::async_trait(?Send)]
+pub(super) trait SinkWriter {
+    /// The frontier up to which updates have been committed.
+    ///
+    /// When the writer is created, this is the frontier from which the sink resumes.
+    fn committed_upper(&self) -> &Antichain<Timestamp>;
+
+    /// Writes an update.
+    ///
+    /// Updates are written in ascending timestamp order, and only once all updates at earlier
+    /// times have been written.
+    async fn write(
+        &mut self,
+        key: Option<Row>,
+        value: DiffPair<Row>,
+        time: Timestamp,
+        diff: Diff,
+    ) -> Result<(), anyhow::Error>;
+
+    /// Reports that all updates before `frontier` have been written.
+    ///
+    /// The writer can commit the updates and advance its committed upper to `frontier`, and must
+    /// do so if `frontier` is empty.
+    async fn advance(&mut self, frontier: &Antichain<Timestamp>) -> Result<(), anyhow::Error>;
+}
+
+/// Sinks a collection of updates with the writer that `writer` resolves to.
+///
+/// This operator exchanges all updates to a single worker by hashing on the given sink `id`, and
+/// only that worker creates a writer.
+///
+/// Updates are written in ascending timestamp order. Once the writer has committed updates, the
+/// upper of the sink's persist shard and `write_frontier` are advanced to its committed upper.
+pub(super) fn sink_collection<G, W>(
+    name: String,
+    input: &Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+    sink_id: GlobalId,
+    sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+    storage_configuration: &StorageConfiguration,
+    write_handle: impl Future<Output = anyhow::Result<WriteHandle<SourceData, (), Timestamp, Diff>>>
+        + 'static,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    writer: impl Future<Output = Result<W, anyhow::Error>> + 'static,
+    namespace: StatusNamespace,
+) -> (Stream<G, HealthStatusMessage>, PressOnDropButton)
+where
+    G: Scope<Timestamp = Timestamp>,
+    W: SinkWriter + 'static,
+{
+    let scope = input.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), input.inner.scope());
+
+    // We want exactly one worker to write all the data.
+    let hashed_id = sink_id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+    let buffer_min_capacity =
+        SINK_BUFFERED_UPDATES_RESIZE_THRESHOLD_ELEMENTS.handle(storage_configuration.config_set());
+
+    let mut input = builder.new_disconnected_input(&input.inner, Exchange::new(move |_| hashed_id));
+
+    let as_of = sink.as_of.clone();
+    let (button, errors) = builder.build_fallible(move |_caps| {
+        Box::pin(async move {
+            if !is_active_worker {
+                write_frontier.borrow_mut().clear();
+                return Ok(());
+            }
+
+            let mut write_handle = write_handle.await?;
+            let mut writer = writer.await?;
+            let resume_upper = writer.committed_upper().clone();
+
+            // The input has overcompacted if
+            let overcompacted =
+                // ..we have made some progress in the past
+                *resume_upper != [Timestamp::minimum()] &&
+                // ..but the since frontier is now beyond that
+                !PartialOrder::less_equal(&as_of, &resume_upper);
+            if overcompacted {
+                let err = format!(
+                    "{name}: input compacted past resume upper: as_of {}, resume_upper: {}",
+                    as_of.pretty(),
+                    resume_upper.pretty()
+                );
+                // This would normally be an assertion but because it can happen after a
+                // Materialize backup/restore we log an error so that it appears on Sentry but
+                // leaves the rest of the objects in the cluster unaffected.
+                error!("{err}");
+                return Err(anyhow!("{err}"));
+            }
+
+            info!(
+                "{name}: as_of: {}, resume upper: {}",
+                as_of.pretty(),
+                resume_upper.pretty()
+            );
+
+            // The section below relies on TotalOrder for correctness so we'll work with timestamps
+            // directly to make sure this doesn't compile if someone attempts to make this operator
+            // generic over partial orders in the future.
+            let Some(mut upper) = resume_upper.clone().into_option() else {
+                write_frontier.borrow_mut().clear();
+                return Ok(());
+            };
+
+            let mut deferred_updates = vec![];
+            let mut ready_updates = vec![];
+            while let Some(event) = input.next().await {
+                match event {
+                    Event::Data(_cap, batch) => {
+                        for ((key, value), time, diff) in batch {
+                            // Updates exactly at upper belong to the minimum pending time, so
+                            // writing them right away preserves the timestamp order. See the
+                            // Kafka sink for why this matters for large snapshots.
+                            match upper.cmp(&time) {
+                                Ordering::Less => deferred_updates.push(((key, value), time, diff)),
+                                Ordering::Equal => writer.write(key, value, time, diff).await?,
+                                Ordering::Greater => continue,
+                            }
+                        }
+                    }
+                    Event::Progress(progress) => {
+                        // Ignore progress updates before our resumption frontier
+                        if !PartialOrder::less_equal(&resume_upper, &progress) {
+                            continue;
+                        }
+                        // Also ignore progress updates until we are past the as_of frontier, for
+                        // the same reasons as the Kafka sink.
+                        if !as_of.iter().all(|t| !progress.less_equal(t)) {
+                            continue;
+                        }
+
+                        deferred_updates.shrink_to(buffer_min_capacity.get());
+                        ready_updates.extend(
+                            deferred_updates
+                                .drain_filter_swapping(|(_, time, _)| !progress.less_equal(time)),
+                        );
+                        ready_updates.sort_unstable_by(|a, b| a.1.cmp(&b.1));
+                        for ((key, value), time, diff) in ready_updates.drain(..) {
+                            writer.write(key, value, time, diff).await?;
+                        }
+                        ready_updates.shrink_to(buffer_min_capacity.get());
+
+                        writer.advance(&progress).await?;
+
+                        let committed_upper = writer.committed_upper();
+                        let mut expect_upper = write_handle.shared_upper();
+                        loop {
+                            if PartialOrder::less_equal(committed_upper, &expect_upper) {
+                                // The frontier has already been advanced as far as necessary.
+                                break;
+                            }
+                            const EMPTY: &[((SourceData, ()), Timestamp, Diff)] = &[];
+                            match write_handle
+                                .compare_and_append(EMPTY, expect_upper, committed_upper.clone())
+                                .await
+                                .expect("valid usage")
+                            {
+                                Ok(()) => break,
+                                Err(mismatch) => {
+                                    expect_upper = mismatch.current;
+                                }
+                            }
+                        }
+                        write_frontier.borrow_mut().clone_from(committed_upper);
+
+                        match progress.into_option() {
+                            Some(new_upper) => upper = new_upper,
+                            None => break,
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    });
+
+    let statuses = errors.map(move |error: Rc<anyhow::Error>| HealthStatusMessage {
+        id: None,
+        update: HealthStatusUpdate::halting(format!("{}", error.display_with_causes()), None),
+        namespace,
+    });
+
+    (statuses, button.press_on_drop())
+}
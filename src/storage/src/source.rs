@@ -34,6 +34,7 @@ mod postgres;
 mod probe;
 pub(crate) mod reclock;
 mod source_reader_pipeline;
+mod sql_server;
 mod statistics;
 pub mod types;
 
@@ -0,0 +1,282 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the ingestion dataflow of a [`SqlServerSourceConnection`].
+//!
+//! This dataflow is split into a Replication and a Progress operator.
+//!
+//! # Replication
+//!
+//! The replication operator is responsible for snapshotting all of the tables that have not yet
+//! been snapshot and then tailing the Change Data Capture (CDC) tables of each capture instance
+//! the source depends on. It runs on a single worker.
+//!
+//! Timestamps of this dataflow are SQL Server Log Sequence Numbers ([`Lsn`]s). The snapshot of a
+//! table is emitted at the [`Lsn`] of the snapshot transaction and all changes are emitted at the
+//! [`Lsn`] of the transaction that committed them.
+//!
+//! See the [replication] module for more information.
+//!
+//! # Progress
+//!
+//! The progress operator periodically probes the upstream database for the maximum [`Lsn`] that
+//! has been processed by the CDC capture job, which is used to report statistics and drive
+//! reclocking.
+//!
+//! # Error handling
+//!
+//! [`DefiniteError`]s are errors that happen during processing of a specific collection record,
+//! or that make it impossible to ever produce correct data for a collection, e.g. the CDC data
+//! that we need to resume from having been cleaned up. These are the only errors that can ever
+//! end up in the error collection of a subsource.
+//!
+//! [`TransientError`]s are any errors that can happen for reasons that are unrelated to the data
+//! itself. This could be authentication failures, connection failures, etc. Both operators are
+//! built with the `AsyncOperatorBuilder::build_fallible` method which allows transient errors
+//! to be propagated upwards with the standard `?` operator without risking downgrading the
+//! capability and producing bogus frontiers.
+//!
+//! The error streams from both of those operators are published to the source status and also
+//! trigger a restart of the dataflow.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use differential_dataflow::containers::TimelyStack;
+use differential_dataflow::AsCollection;
+use itertools::Itertools;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_repr::{Diff, GlobalId};
+use mz_sql_server_util::cdc::Lsn;
+use mz_sql_server_util::{SqlServerError, SqlServerTableDesc};
+use mz_storage_types::errors::{DataflowError, SourceError, SourceErrorDetails};
+use mz_storage_types::sources::{
+    SourceExport, SourceExportDetails, SourceTimestamp, SqlServerSourceConnection,
+};
+use mz_timely_util::builder_async::{AsyncOutputHandle, PressOnDropButton};
+use mz_timely_util::containers::stack::AccountedStackBuilder;
+use serde::{Deserialize, Serialize};
+use timely::container::CapacityContainerBuilder;
+use timely::dataflow::channels::pushers::Tee;
+use timely::dataflow::operators::core::Partition;
+use timely::dataflow::operators::{CapabilitySet, Concat, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::types::{Probe, ProgressStatisticsUpdate, SourceRender, StackedCollection};
+use crate::source::{RawSourceCreationConfig, SourceMessage};
+
+mod progress;
+mod replication;
+
+impl SourceRender for SqlServerSourceConnection {
+    type Time = Lsn;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::SqlServer;
+
+    /// Render the ingestion dataflow. This function only connects things together and contains no
+    /// actual processing logic.
+    fn render<G: Scope<Timestamp = Lsn>>(
+        self,
+        scope: &mut G,
+        config: RawSourceCreationConfig,
+        resume_uppers: impl futures::Stream<Item = Antichain<Lsn>> + 'static,
+        _start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        BTreeMap<GlobalId, StackedCollection<G, Result<SourceMessage, DataflowError>>>,
+        Stream<G, Infallible>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Option<Stream<G, Probe<Lsn>>>,
+        Vec<PressOnDropButton>,
+    ) {
+        // Collect the source outputs that we will be exporting.
+        let mut source_outputs = Vec::new();
+        for (idx, (id, export)) in config.source_exports.iter().enumerate() {
+            let SourceExport {
+                details,
+                storage_metadata: _,
+                data_config: _,
+            } = export;
+            let details = match details {
+                SourceExportDetails::SqlServer(details) => details,
+                // This is an export that doesn't need any data output to it.
+                SourceExportDetails::None => continue,
+                _ => panic!("unexpected source export details: {:?}", details),
+            };
+
+            let resume_upper = Antichain::from_iter(
+                config
+                    .source_resume_uppers
+                    .get(id)
+                    .expect("missing resume upper")
+                    .iter()
+                    .map(Lsn::decode_row),
+            );
+            source_outputs.push(SourceOutputInfo {
+                output_index: idx,
+                capture_instance: details.capture_instance.clone(),
+                desc: details.table.clone(),
+                resume_upper,
+            });
+        }
+
+        let (repl_updates, uppers, repl_stats, repl_err, repl_token) =
+            replication::render(scope.clone(), config.clone(), self.clone(), source_outputs);
+
+        let (stats_stream, stats_err, probe_stream, stats_token) =
+            progress::render(scope.clone(), config.clone(), self, resume_uppers);
+
+        let stats_stream = stats_stream.concat(&repl_stats);
+
+        let partition_count = u64::cast_from(config.source_exports.len());
+        let data_streams: Vec<_> = repl_updates
+            .inner
+            .partition::<CapacityContainerBuilder<_>, _, _>(
+                partition_count,
+                |((output, data), time, diff): &(
+                    (usize, Result<SourceMessage, DataflowError>),
+                    _,
+                    Diff,
+                )| {
+                    let output = u64::cast_from(*output);
+                    (output, (data.clone(), time.clone(), diff.clone()))
+                },
+            );
+        let mut data_collections = BTreeMap::new();
+        for (id, data_stream) in config.source_exports.keys().zip_eq(data_streams) {
+            data_collections.insert(*id, data_stream.as_collection());
+        }
+
+        let health_init = std::iter::once(HealthStatusMessage {
+            id: None,
+            namespace: Self::STATUS_NAMESPACE,
+            update: HealthStatusUpdate::Running,
+        })
+        .to_stream(scope);
+
+        let health_errs = repl_err.concat(&stats_err).map(move |err| {
+            // This update will cause the dataflow to restart
+            let err_string = err.display_with_causes().to_string();
+            let update = HealthStatusUpdate::halting(err_string.clone(), None);
+
+            let namespace = match err {
+                ReplicationError::Transient(err)
+                    if matches!(&*err, TransientError::SqlServer(SqlServerError::Ssh(_))) =>
+                {
+                    StatusNamespace::Ssh
+                }
+                _ => Self::STATUS_NAMESPACE,
+            };
+
+            HealthStatusMessage {
+                id: None,
+                namespace: namespace.clone(),
+                update,
+            }
+        });
+        let health = health_init.concat(&health_errs);
+
+        (
+            data_collections,
+            uppers,
+            health,
+            stats_stream,
+            Some(probe_stream),
+            vec![repl_token, stats_token],
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SourceOutputInfo {
+    output_index: usize,
+    capture_instance: String,
+    desc: SqlServerTableDesc,
+    resume_upper: Antichain<Lsn>,
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ReplicationError {
+    #[error(transparent)]
+    Transient(#[from] Rc<TransientError>),
+    #[error(transparent)]
+    Definite(#[from] Rc<DefiniteError>),
+}
+
+/// A transient error that never ends up in the collection of a specific table.
+#[derive(Debug, thiserror::Error)]
+pub enum TransientError {
+    #[error(transparent)]
+    SqlServer(#[from] SqlServerError),
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+}
+
+/// A definite error that always ends up in the collection of a specific table.
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+pub enum DefiniteError {
+    #[error("unable to decode: {0}")]
+    ValueDecodeError(String),
+    #[error("capture instance was dropped: {0}")]
+    CaptureInstanceDropped(String),
+    #[error("change data for capture instance {0} is no longer available at {1}, earliest available is {2}")]
+    ChangesNotAvailable(String, String, String),
+    #[error("sql server configuration: {0}")]
+    ServerConfigurationError(String),
+}
+
+impl From<DefiniteError> for DataflowError {
+    fn from(err: DefiniteError) -> Self {
+        let m = err.to_string().into();
+        DataflowError::SourceError(Box::new(SourceError {
+            error: match &err {
+                DefiniteError::ValueDecodeError(_) => SourceErrorDetails::Other(m),
+                DefiniteError::CaptureInstanceDropped(_) => SourceErrorDetails::Other(m),
+                DefiniteError::ChangesNotAvailable(_, _, _) => {
+                    SourceErrorDetails::Initialization(m)
+                }
+                DefiniteError::ServerConfigurationError(_) => SourceErrorDetails::Initialization(m),
+            },
+        }))
+    }
+}
+
+type StackedAsyncOutputHandle<T, D> = AsyncOutputHandle<
+    T,
+    AccountedStackBuilder<CapacityContainerBuilder<TimelyStack<(D, T, Diff)>>>,
+    Tee<T, TimelyStack<(D, T, Diff)>>,
+>;
+
+/// Emits `err` to all of `outputs` at the maximum [`Lsn`], permanently
+/// rendering them unusable, and reports it to the source status.
+async fn return_definite_error(
+    err: DefiniteError,
+    outputs: &[usize],
+    data_handle: &StackedAsyncOutputHandle<Lsn, (usize, Result<SourceMessage, DataflowError>)>,
+    data_cap_set: &CapabilitySet<Lsn>,
+    definite_error_handle: &AsyncOutputHandle<
+        Lsn,
+        CapacityContainerBuilder<Vec<ReplicationError>>,
+        Tee<Lsn, Vec<ReplicationError>>,
+    >,
+    definite_error_cap_set: &CapabilitySet<Lsn>,
+) {
+    for output_index in outputs {
+        let update = ((*output_index, Err(err.clone().into())), Lsn::MAX, 1);
+        data_handle.give_fueled(&data_cap_set[0], update).await;
+    }
+    definite_error_handle.give(
+        &definite_error_cap_set[0],
+        ReplicationError::Definite(Rc::new(err)),
+    );
+}
@@ -0,0 +1,161 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the progress and statistics collection of the [`SqlServerSourceConnection`]
+//! ingestion dataflow.
+
+use std::cell::{Cell, RefCell};
+
+use futures::StreamExt;
+use mz_ore::future::InTask;
+use mz_sql_server_util::cdc::{self, Lsn};
+use mz_sql_server_util::Client;
+use mz_storage_types::dyncfgs::SQL_SERVER_OFFSET_KNOWN_INTERVAL;
+use mz_storage_types::sources::SqlServerSourceConnection;
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+use timely::dataflow::operators::Map;
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp};
+
+use crate::source::types::{Probe, ProgressStatisticsUpdate};
+use crate::source::{probe, RawSourceCreationConfig};
+
+use super::{ReplicationError, TransientError};
+
+static STATISTICS: &str = "statistics";
+
+/// Renders the progress dataflow.
+pub(crate) fn render<G: Scope<Timestamp = Lsn>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: SqlServerSourceConnection,
+    resume_uppers: impl futures::Stream<Item = Antichain<Lsn>> + 'static,
+) -> (
+    Stream<G, ProgressStatisticsUpdate>,
+    Stream<G, ReplicationError>,
+    Stream<G, Probe<Lsn>>,
+    PressOnDropButton,
+) {
+    let op_name = format!("SqlServerProgress({})", config.id);
+    let mut builder = AsyncOperatorBuilder::new(op_name, scope);
+
+    let (stats_output, stats_stream) = builder.new_output();
+    let (probe_output, probe_stream) = builder.new_output();
+
+    let (button, transient_errors) = builder.build_fallible::<TransientError, _>(move |caps| {
+        Box::pin(async move {
+            let worker_id = config.worker_id;
+            let [stats_cap, probe_cap]: &mut [_; 2] = caps.try_into().unwrap();
+
+            // Only run the progress reader on the worker responsible for it.
+            if !config.responsible_for(STATISTICS) {
+                // Emit 0, to mark this worker as having started up correctly.
+                stats_output.give(
+                    &stats_cap[0],
+                    ProgressStatisticsUpdate::SteadyState {
+                        offset_known: 0,
+                        offset_committed: 0,
+                    },
+                );
+                return Ok(());
+            }
+
+            let connection_config = connection
+                .connection
+                .config(
+                    &config.config.connection_context.secrets_reader,
+                    &config.config,
+                    InTask::Yes,
+                )
+                .await?;
+            let (mut client, conn) = Client::connect(connection_config).await?;
+            mz_ore::task::spawn(
+                || format!("timely-{worker_id} SQL Server progress"),
+                async move { conn.await },
+            );
+
+            tokio::pin!(resume_uppers);
+
+            let prev_offset_known = Cell::new(None);
+            let prev_offset_committed = Cell::new(None);
+            let stats_output = RefCell::new(stats_output);
+
+            let mut probe_ticker = probe::Ticker::new(
+                || SQL_SERVER_OFFSET_KNOWN_INTERVAL.get(config.config.config_set()),
+                config.now_fn,
+            );
+            let probe_loop = async {
+                loop {
+                    let probe_ts = probe_ticker.tick().await;
+
+                    // Until the capture job has processed a change there is nothing to read.
+                    let upstream_upper = cdc::get_max_lsn(&mut client)
+                        .await?
+                        .map(|lsn| lsn.increment())
+                        .unwrap_or_else(Lsn::minimum);
+
+                    let offset_known = lsn_offset(&upstream_upper);
+                    if let Some(offset_committed) = prev_offset_committed.get() {
+                        stats_output.borrow_mut().give(
+                            &stats_cap[0],
+                            ProgressStatisticsUpdate::SteadyState {
+                                offset_known,
+                                offset_committed,
+                            },
+                        );
+                    }
+                    prev_offset_known.set(Some(offset_known));
+
+                    probe_output.give(
+                        &probe_cap[0],
+                        Probe {
+                            probe_ts,
+                            upstream_frontier: Antichain::from_elem(upstream_upper),
+                        },
+                    );
+                }
+            };
+            let commit_loop = async {
+                while let Some(committed_frontier) = resume_uppers.next().await {
+                    let offset_committed = match committed_frontier.as_option() {
+                        Some(lsn) => lsn_offset(lsn),
+                        None => continue,
+                    };
+                    if let Some(offset_known) = prev_offset_known.get() {
+                        stats_output.borrow_mut().give(
+                            &stats_cap[0],
+                            ProgressStatisticsUpdate::SteadyState {
+                                offset_known,
+                                offset_committed,
+                            },
+                        );
+                    }
+                    prev_offset_committed.set(Some(offset_committed));
+                }
+            };
+
+            futures::future::join(probe_loop, commit_loop).await.0
+        })
+    });
+
+    (
+        stats_stream,
+        transient_errors.map(ReplicationError::from),
+        probe_stream,
+        button.press_on_drop(),
+    )
+}
+
+/// Maps an [`Lsn`] to a single number that increases with the [`Lsn`].
+///
+/// The record ID is dropped because the full [`Lsn`] does not fit into a
+/// `u64`, so this is only precise up to the log block.
+fn lsn_offset(lsn: &Lsn) -> u64 {
+    (u64::from(lsn.vlf_id) << 32) | u64::from(lsn.block_id)
+}
@@ -0,0 +1,388 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the replication side of the [`SqlServerSourceConnection`] ingestion dataflow.
+//!
+//! # Snapshot
+//!
+//! Outputs whose resume upper is the minimum [`Lsn`] have not yet committed a snapshot. All of
+//! them are snapshot together in a single `SNAPSHOT` transaction, which requires snapshot
+//! isolation to be allowed for the database:
+//!
+//! 1. In a separate `READ COMMITTED` transaction, a shared table lock is acquired on every table
+//!    that is snapshot. Acquiring the lock waits for all in-flight transactions that modified the
+//!    tables to complete, and holding it prevents any new modifications.
+//! 2. The [`Lsn`] of the lock transaction is determined. Every transaction that modified the
+//!    tables committed before this [`Lsn`], and every transaction that modifies them afterwards
+//!    will commit after it.
+//! 3. The snapshot transaction counts the rows of the tables. Its first read fixes the data it
+//!    sees to what was committed at that point, so the lock transaction commits right after,
+//!    allowing writes to the tables again.
+//! 4. The contents of the tables are streamed from the snapshot transaction and emitted at the
+//!    snapshot [`Lsn`].
+//!
+//! Replication for a snapshot output then starts just beyond the snapshot [`Lsn`], while all
+//! other outputs resume from their resume upper. This means no per-output state has to be stored
+//! durably, if the snapshot is interrupted before it is committed it is simply taken again.
+//!
+//! # Replication
+//!
+//! Changes are read by periodically polling the CDC change functions of each capture instance
+//! for the range of [`Lsn`]s between the current upper of the dataflow and the maximum [`Lsn`]
+//! processed by the CDC capture job. Each change is emitted at the [`Lsn`] of the transaction
+//! that committed it, and once all capture instances have been read the upper of the dataflow
+//! is advanced beyond the maximum [`Lsn`].
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use itertools::Itertools;
+use mz_ore::future::InTask;
+use mz_repr::{Diff, Row};
+use mz_sql_server_util::cdc::{self, Lsn, CHANGE_METADATA_COLUMNS};
+use mz_sql_server_util::{
+    inspect, Client, SqlServerError, SqlServerTableDesc, TransactionIsolationLevel,
+};
+use mz_storage_types::dyncfgs::SQL_SERVER_CDC_POLL_INTERVAL;
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sources::SqlServerSourceConnection;
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+use mz_timely_util::containers::stack::AccountedStackBuilder;
+use timely::container::CapacityContainerBuilder;
+use timely::dataflow::operators::{Concat, Map};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp};
+use tracing::trace;
+
+use crate::source::types::{
+    ProgressStatisticsUpdate, SignaledFuture, SourceMessage, StackedCollection,
+};
+use crate::source::RawSourceCreationConfig;
+
+use super::{
+    return_definite_error, DefiniteError, ReplicationError, SourceOutputInfo, TransientError,
+};
+
+/// Used as a partition id to determine if the worker is
+/// responsible for snapshotting and reading the CDC tables.
+static REPL_READER: &str = "reader";
+
+/// Renders the replication dataflow. See the module documentation for more
+/// information.
+pub(crate) fn render<G: Scope<Timestamp = Lsn>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: SqlServerSourceConnection,
+    source_outputs: Vec<SourceOutputInfo>,
+) -> (
+    StackedCollection<G, (usize, Result<SourceMessage, DataflowError>)>,
+    Stream<G, Infallible>,
+    Stream<G, ProgressStatisticsUpdate>,
+    Stream<G, ReplicationError>,
+    PressOnDropButton,
+) {
+    let op_name = format!("SqlServerReplicationReader({})", config.id);
+    let mut builder = AsyncOperatorBuilder::new(op_name, scope);
+
+    let (data_output, data_stream) = builder.new_output::<AccountedStackBuilder<_>>();
+    let (_upper_output, upper_stream) = builder.new_output::<CapacityContainerBuilder<_>>();
+    // Captures DefiniteErrors that affect the entire source, including all outputs
+    let (definite_error_handle, definite_errors) =
+        builder.new_output::<CapacityContainerBuilder<_>>();
+    let (stats_output, stats_stream) = builder.new_output::<CapacityContainerBuilder<_>>();
+
+    let output_indexes = source_outputs
+        .iter()
+        .map(|output| output.output_index)
+        .collect_vec();
+
+    let (button, transient_errors) = builder.build_fallible::<TransientError, _>(move |caps| {
+        let busy_signal = Arc::clone(&config.busy_signal);
+        Box::pin(SignaledFuture::new(busy_signal, async move {
+            let (id, worker_id) = (config.id, config.worker_id);
+            let [data_cap_set, upper_cap_set, definite_error_cap_set, stats_cap]: &mut [_; 4] =
+                caps.try_into().unwrap();
+
+            // Determine which outputs need to be snapshot and which already have been.
+            let (snapshot_outputs, resume_outputs): (Vec<_>, Vec<_>) = source_outputs
+                .into_iter()
+                .partition(|output| *output.resume_upper == [Lsn::minimum()]);
+
+            // Only run the replication reader on the worker responsible for it.
+            if !config.responsible_for(REPL_READER) {
+                if !snapshot_outputs.is_empty() {
+                    // Emit 0, to mark this worker as having started up correctly,
+                    // but having done no snapshotting.
+                    stats_output.give(
+                        &stats_cap[0],
+                        ProgressStatisticsUpdate::Snapshot {
+                            records_known: 0,
+                            records_staged: 0,
+                        },
+                    );
+                }
+                return Ok(());
+            }
+
+            let connection_config = connection
+                .connection
+                .config(
+                    &config.config.connection_context.secrets_reader,
+                    &config.config,
+                    InTask::Yes,
+                )
+                .await?;
+            let lock_config = connection_config.clone();
+            let (mut client, conn) = Client::connect(connection_config).await?;
+            mz_ore::task::spawn(
+                || format!("timely-{worker_id} SQL Server replication reader"),
+                async move { conn.await },
+            );
+
+            // Without CDC enabled for the database all capture instances have been dropped, so
+            // there is no way to ever make progress again.
+            match inspect::ensure_database_cdc_enabled(&mut client).await {
+                Ok(()) => (),
+                Err(SqlServerError::InvalidSetup(msg)) => {
+                    return Ok(return_definite_error(
+                        DefiniteError::ServerConfigurationError(msg),
+                        &output_indexes,
+                        &data_output,
+                        data_cap_set,
+                        &definite_error_handle,
+                        definite_error_cap_set,
+                    )
+                    .await);
+                }
+                Err(err) => return Err(err.into()),
+            }
+
+            // The Lsn from which each output should be replicated.
+            let mut outputs: Vec<(SourceOutputInfo, Lsn)> = resume_outputs
+                .into_iter()
+                .map(|output| {
+                    let start = output
+                        .resume_upper
+                        .as_option()
+                        .copied()
+                        .expect("resume upper of a running output is never empty");
+                    (output, start)
+                })
+                .collect();
+
+            if !snapshot_outputs.is_empty() {
+                trace!(%id, "timely-{worker_id} snapshotting {} outputs", snapshot_outputs.len());
+
+                inspect::ensure_snapshot_isolation_enabled(&mut client).await?;
+
+                // The table locks are held by a second connection, so that they can be released
+                // as soon as the snapshot transaction has started to read.
+                let (mut lock_client, lock_conn) = Client::connect(lock_config).await?;
+                mz_ore::task::spawn(
+                    || format!("timely-{worker_id} SQL Server snapshot lock"),
+                    async move { lock_conn.await },
+                );
+                lock_client
+                    .set_transaction_isolation(TransactionIsolationLevel::ReadCommitted)
+                    .await?;
+                let mut lock_txn = lock_client.transaction().await?;
+
+                let tables = snapshot_outputs
+                    .iter()
+                    .map(|output| (&output.desc.schema_name, &output.desc.name))
+                    .unique();
+                for (schema_name, table_name) in tables {
+                    inspect::lock_table_shared(&mut lock_txn, schema_name, table_name).await?;
+                }
+                let snapshot_lsn = inspect::get_transaction_lsn(&mut lock_txn).await?;
+                trace!(%id, "timely-{worker_id} snapshot lsn {snapshot_lsn}");
+
+                client
+                    .set_transaction_isolation(TransactionIsolationLevel::Snapshot)
+                    .await?;
+                let mut txn = client.transaction().await?;
+                // A snapshot transaction reads the data committed before its first read, which
+                // counting the rows of the tables performs while the locks are held.
+                let mut records_known = 0;
+                for output in &snapshot_outputs {
+                    records_known += inspect::get_table_row_count(&mut txn, &output.desc).await?;
+                }
+                lock_txn.commit().await?;
+
+                stats_output.give(
+                    &stats_cap[0],
+                    ProgressStatisticsUpdate::Snapshot {
+                        records_known,
+                        records_staged: 0,
+                    },
+                );
+
+                let mut records_staged = 0;
+                for output in &snapshot_outputs {
+                    let rows = inspect::snapshot_table(&mut txn, &output.desc);
+                    let mut rows = std::pin::pin!(rows);
+                    while let Some(row) = rows.next().await {
+                        let data = (output.output_index, decode(&output.desc, &row?, 0)?);
+                        data_output
+                            .give_fueled(&data_cap_set[0], (data, snapshot_lsn, 1))
+                            .await;
+                        records_staged += 1;
+                        if records_staged % 1000 == 0 {
+                            stats_output.give(
+                                &stats_cap[0],
+                                ProgressStatisticsUpdate::Snapshot {
+                                    records_known,
+                                    records_staged,
+                                },
+                            );
+                        }
+                    }
+                }
+                txn.commit().await?;
+                stats_output.give(
+                    &stats_cap[0],
+                    ProgressStatisticsUpdate::Snapshot {
+                        records_known,
+                        records_staged,
+                    },
+                );
+
+                let start = snapshot_lsn.increment();
+                outputs.extend(snapshot_outputs.into_iter().map(|output| (output, start)));
+            }
+
+            let mut upper = match outputs.iter().map(|(_, start)| *start).min() {
+                Some(upper) => upper,
+                // If there are no outputs to replicate then we will just be updating the
+                // source progress collection, so we start from the current head.
+                None => cdc::get_max_lsn(&mut client)
+                    .await?
+                    .map(|lsn| lsn.increment())
+                    .unwrap_or_else(Lsn::minimum),
+            };
+            data_cap_set.downgrade([&upper]);
+            upper_cap_set.downgrade([&upper]);
+            trace!(%id, "timely-{worker_id} replication reader started at {upper}");
+
+            loop {
+                let max_lsn = cdc::get_max_lsn(&mut client).await?;
+                if let Some(max_lsn) = max_lsn.filter(|max_lsn| *max_lsn >= upper) {
+                    trace!(%id, "timely-{worker_id} reading changes in [{upper}, {max_lsn}]");
+
+                    let mut min_lsns = BTreeMap::new();
+                    let mut failed_outputs = vec![];
+                    for (idx, (output, start)) in outputs.iter().enumerate() {
+                        let from = std::cmp::max(upper, *start);
+                        if from > max_lsn {
+                            continue;
+                        }
+
+                        let min_lsn = match min_lsns.get(&output.capture_instance) {
+                            Some(min_lsn) => *min_lsn,
+                            None => {
+                                let min_lsn =
+                                    cdc::get_min_lsn(&mut client, &output.capture_instance).await?;
+                                min_lsns.insert(output.capture_instance.clone(), min_lsn);
+                                min_lsn
+                            }
+                        };
+                        let err = match min_lsn {
+                            None => Some(DefiniteError::CaptureInstanceDropped(
+                                output.capture_instance.clone(),
+                            )),
+                            Some(min_lsn) if from < min_lsn => {
+                                Some(DefiniteError::ChangesNotAvailable(
+                                    output.capture_instance.clone(),
+                                    from.to_string(),
+                                    min_lsn.to_string(),
+                                ))
+                            }
+                            Some(_) => None,
+                        };
+                        if let Some(err) = err {
+                            // There is no way to produce correct data for this output ever
+                            // again, so stop replicating it.
+                            let data = (output.output_index, Err(err.into()));
+                            data_output
+                                .give_fueled(&data_cap_set[0], (data, from, 1))
+                                .await;
+                            failed_outputs.push(idx);
+                            continue;
+                        }
+
+                        let changes = cdc::get_changes(
+                            &mut client,
+                            &output.capture_instance,
+                            &output.desc,
+                            from,
+                            max_lsn,
+                        );
+                        let mut changes = std::pin::pin!(changes);
+                        while let Some(change) = changes.next().await {
+                            let change = change?;
+                            let data = (
+                                output.output_index,
+                                decode(&output.desc, &change.row, CHANGE_METADATA_COLUMNS)?,
+                            );
+                            let diff: Diff = change.operation.diff();
+                            data_output
+                                .give_fueled(&data_cap_set[0], (data, change.lsn, diff))
+                                .await;
+                        }
+                    }
+                    for idx in failed_outputs.into_iter().rev() {
+                        outputs.remove(idx);
+                    }
+
+                    upper = max_lsn.increment();
+                    data_cap_set.downgrade([&upper]);
+                    upper_cap_set.downgrade([&upper]);
+                }
+
+                tokio::time::sleep(SQL_SERVER_CDC_POLL_INTERVAL.get(config.config.config_set()))
+                    .await;
+            }
+        }))
+    });
+
+    let errors = definite_errors.concat(&transient_errors.map(ReplicationError::from));
+
+    (
+        data_stream.as_collection(),
+        upper_stream,
+        stats_stream,
+        errors,
+        button.press_on_drop(),
+    )
+}
+
+/// Decodes the columns of `row`, starting at `offset`, according to `desc`.
+///
+/// Values that fail to decode produce a [`DefiniteError`] in the output, any
+/// other failure is transient.
+fn decode(
+    desc: &SqlServerTableDesc,
+    row: &tiberius::Row,
+    offset: usize,
+) -> Result<Result<SourceMessage, DataflowError>, TransientError> {
+    let mut value = Row::default();
+    match desc.decode_row(row, offset, &mut value.packer()) {
+        Ok(()) => Ok(Ok(SourceMessage {
+            key: Row::default(),
+            value,
+            metadata: Row::default(),
+        })),
+        Err(err @ SqlServerError::ValueDecodeError { .. }) => Ok(Err(DataflowError::from(
+            DefiniteError::ValueDecodeError(err.to_string()),
+        ))),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -29,7 +29,7 @@ use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::sources::{
     GenericSourceConnection, IngestionDescription, KafkaSourceConnection,
     LoadGeneratorSourceConnection, MySqlSourceConnection, PostgresSourceConnection,
-    SourceConnection, SourceData, SourceEnvelope, SourceTimestamp,
+    SourceConnection, SourceData, SourceEnvelope, SourceTimestamp, SqlServerSourceConnection,
 };
 use timely::order::PartialOrder;
 use timely::progress::frontier::MutableAntichain;
@@ -361,6 +361,17 @@ impl<T: Timestamp + TimestampManipulation + Lattice + Codec64 + Display + Sync>
                                 .await;
                                 to_vec_row(uppers)
                             }
+                            GenericSourceConnection::SqlServer(_) => {
+                                let uppers = reclock_resume_uppers::<SqlServerSourceConnection, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
                             GenericSourceConnection::LoadGenerator(_) => {
                                 let uppers =
                                     reclock_resume_uppers::<LoadGeneratorSourceConnection, _>(
@@ -803,6 +803,7 @@ impl Run for PosCommand {
                         protobuf::run_compile_descriptors(builtin, state).await
                     }
                     "psql-execute" => psql::run_execute(builtin, state).await,
+                    "s3-file-upload" => s3::run_upload(builtin, state).await,
                     "s3-verify-data" => s3::run_verify_data(builtin, state).await,
                     "s3-verify-keys" => s3::run_verify_keys(builtin, state).await,
                     "schema-registry-publish" => schema_registry::run_publish(builtin, state).await,
@@ -8,17 +8,155 @@
 // by the Apache License, Version 2.0.
 
 use std::str;
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
-use anyhow::bail;
+use anyhow::{bail, Context};
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{Field, Schema as ArrowSchema};
+use arrow::record_batch::RecordBatch;
 use arrow::util::display::ArrayFormatter;
 use arrow::util::display::FormatOptions;
+use aws_sdk_s3::primitives::ByteStream;
 use regex::Regex;
+use serde_json::Value as JsonValue;
 
 use crate::action::{ControlFlow, State};
+use crate::format::{avro, bytes};
 use crate::parser::BuiltinCommand;
 
+pub async fn run_upload(
+    mut cmd: BuiltinCommand,
+    state: &State,
+) -> Result<ControlFlow, anyhow::Error> {
+    let bucket: String = cmd.args.parse("bucket")?;
+    let key: String = cmd.args.parse("key")?;
+    let format = cmd.args.string("format")?;
+    let schema = cmd.args.opt_string("schema");
+    let rows_per_block = cmd.args.opt_parse("rows-per-block")?;
+    let repeat = cmd.args.opt_parse("repeat")?.unwrap_or(1);
+    // Drops the given number of bytes from the end of the file, to upload a truncated file.
+    let truncate = cmd.args.opt_parse("truncate")?.unwrap_or(0);
+    cmd.args.done()?;
+
+    let lines: Vec<_> = std::iter::repeat(cmd.input.iter())
+        .take(repeat)
+        .flatten()
+        .collect();
+    let mut contents = match format.as_str() {
+        // The input is uploaded as is, e.g. to upload a file that is not in the expected format.
+        "bytes" => {
+            let mut contents = vec![];
+            for line in lines {
+                contents.extend(bytes::unescape(line.as_bytes())?);
+                contents.push(b'\n');
+            }
+            contents
+        }
+        // Each line of the input is the JSON of a record.
+        "avro" => {
+            let Some(schema) = schema else {
+                bail!("missing schema argument for avro format");
+            };
+            let schema = avro::parse_schema(&schema)
+                .with_context(|| format!("parsing avro schema: {}", schema))?;
+            let mut writer = mz_avro::Writer::new(schema.clone(), vec![]);
+            for (i, line) in lines.into_iter().enumerate() {
+                let json = serde_json::from_str(line)
+                    .with_context(|| format!("parsing json: {}", line))?;
+                writer.append(avro::from_json(&json, schema.top_node())?)?;
+                if rows_per_block.map_or(false, |n: usize| (i + 1) % n == 0) {
+                    writer.flush()?;
+                }
+            }
+            writer.flush()?;
+            writer.into_inner()
+        }
+        // Each line of the input is a JSON object, whose fields are the columns of a row.
+        "orc" => {
+            let rows = lines
+                .into_iter()
+                .map(|line| {
+                    serde_json::from_str(line).with_context(|| format!("parsing json: {}", line))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let batch = record_batch_from_json(&rows)?;
+            let mut contents = vec![];
+            let mut writer =
+                orc_rust::ArrowWriterBuilder::new(&mut contents, batch.schema()).try_build()?;
+            writer.write(&batch)?;
+            writer.close()?;
+            contents
+        }
+        f => bail!("unknown format: {}", f),
+    };
+    contents.truncate(contents.len().saturating_sub(truncate));
+
+    println!(
+        "Uploading {} bytes to S3 bucket {bucket} key {key}...",
+        contents.len()
+    );
+
+    let client = mz_aws_util::s3::new_client(&state.aws_config);
+    client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(ByteStream::from(contents))
+        .send()
+        .await?;
+
+    Ok(ControlFlow::Continue)
+}
+
+/// Builds a record batch from rows of JSON objects.
+///
+/// The columns are the fields of the first row, and the type of each column is inferred from its
+/// first non-null value.
+fn record_batch_from_json(rows: &[JsonValue]) -> Result<RecordBatch, anyhow::Error> {
+    let Some(JsonValue::Object(first)) = rows.first() else {
+        bail!("expected at least one row of JSON objects");
+    };
+    let mut fields = vec![];
+    let mut columns: Vec<ArrayRef> = vec![];
+    for name in first.keys() {
+        let values: Vec<_> = rows.iter().map(|row| &row[name]).collect();
+        let column: ArrayRef = match values.iter().find(|value| !value.is_null()) {
+            Some(JsonValue::Bool(_)) => Arc::new(
+                values
+                    .iter()
+                    .map(|value| value.as_bool())
+                    .collect::<BooleanArray>(),
+            ),
+            Some(JsonValue::Number(n)) if n.is_i64() => Arc::new(
+                values
+                    .iter()
+                    .map(|value| value.as_i64())
+                    .collect::<Int64Array>(),
+            ),
+            Some(JsonValue::Number(_)) => Arc::new(
+                values
+                    .iter()
+                    .map(|value| value.as_f64())
+                    .collect::<Float64Array>(),
+            ),
+            Some(JsonValue::String(_)) => Arc::new(
+                values
+                    .iter()
+                    .map(|value| value.as_str())
+                    .collect::<StringArray>(),
+            ),
+            Some(value) => bail!("unsupported JSON value for column {name}: {value}"),
+            None => bail!("unable to infer the type of column {name}"),
+        };
+        fields.push(Field::new(name, column.data_type().clone(), true));
+        columns.push(column);
+    }
+    let schema = Arc::new(ArrowSchema::new(fields));
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
 pub async fn run_verify_data(
     mut cmd: BuiltinCommand,
     state: &State,
@@ -67,10 +67,11 @@ impl ReduceReduction {
                 Vec::new();
 
             // Our rendering currently produces independent dataflow paths for 1. all accumulable aggregations,
-            // 2. all hierarchical aggregations, and 3. *each* basic aggregation.
-            // We'll form groups for accumulable, hierarchical, and a list of basic aggregates.
+            // 2. all hierarchical aggregations, 3. *each* basic aggregation, and 4. all sketch aggregations.
+            // We'll form groups for accumulable, hierarchical, sketch, and a list of basic aggregates.
             let mut accumulable = (Vec::new(), Vec::new());
             let mut hierarchical = (Vec::new(), Vec::new());
+            let mut sketch = (Vec::new(), Vec::new());
 
             use mz_compute_types::plan::reduce::ReductionType;
             for (index, aggr) in aggregates.iter().enumerate() {
@@ -85,13 +86,20 @@ impl ReduceReduction {
                     }
                     ReductionType::Basic => segmented_aggregates
                         .push((vec![aggr.clone()], vec![group_key.len() + index])),
+                    ReductionType::Sketch => {
+                        sketch.0.push(aggr.clone());
+                        sketch.1.push(group_key.len() + index);
+                    }
                 }
             }
 
-            // Fold in hierarchical and accumulable aggregates.
+            // Fold in hierarchical, accumulable and sketch aggregates.
             if !hierarchical.0.is_empty() {
                 segmented_aggregates.push(hierarchical);
             }
+            if !sketch.0.is_empty() {
+                segmented_aggregates.push(sketch);
+            }
             if !accumulable.0.is_empty() {
                 segmented_aggregates.push(accumulable);
             }
@@ -177,6 +177,20 @@ def workflow_replication_disabled(
         c.run_testdrive_files("override/replication-disabled.td")
 
 
+def workflow_postgis(c: Composition, parser: WorkflowArgumentParser) -> None:
+    """
+    Test ingesting the geometry and geography types of the PostGIS extension.
+    """
+
+    with c.override(
+        Postgres(
+            image="postgis/postgis:16-3.4", extra_command=DEFAULT_PG_EXTRA_COMMAND
+        )
+    ):
+        c.up("materialized", "postgres")
+        c.run_testdrive_files("override/types-postgis.td")
+
+
 def workflow_silent_connection_drop(
     c: Composition, parser: WorkflowArgumentParser
 ) -> None:
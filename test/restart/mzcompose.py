@@ -865,6 +865,107 @@ def workflow_index_compute_dependencies(c: Composition) -> None:
     depends_on(c, "ix2", "t2_y_idx", True)
 
 
+def workflow_replace_materialized_view(c: Composition) -> None:
+    """
+    Assert that a materialized view that was replaced in place, and the index
+    on it, keep serving the results of the latest definition across restarts.
+
+    A materialized view replaced in place has one storage collection per
+    definition, all backed by the same shard, and bootstrapping has to restore
+    each of them.
+    """
+    c.up("testdrive_no_reset", persistent=True)
+    c.up("materialized")
+
+    c.testdrive(
+        service="testdrive_no_reset",
+        input=dedent(
+            """
+            > DROP TABLE IF EXISTS replace_t CASCADE;
+
+            > CREATE TABLE replace_t (a int, b int);
+            > INSERT INTO replace_t VALUES (1, 2), (3, 4);
+
+            > CREATE MATERIALIZED VIEW replace_mv AS SELECT a FROM replace_t;
+            > CREATE DEFAULT INDEX ON replace_mv;
+            > CREATE VIEW replace_mv_dep AS SELECT a + 1 AS c FROM replace_mv;
+
+            > ALTER MATERIALIZED VIEW replace_mv AS SELECT b AS a FROM replace_t;
+
+            > SELECT * FROM replace_mv
+            2
+            4
+
+            > ALTER MATERIALIZED VIEW replace_mv AS SELECT a + b AS a FROM replace_t;
+
+            > SELECT * FROM replace_mv
+            3
+            7
+
+            > INSERT INTO replace_t VALUES (5, 6);
+
+            > SELECT * FROM replace_mv_dep
+            4
+            8
+            12
+            """
+        ),
+    )
+
+    c.kill("materialized")
+    c.up("materialized")
+
+    c.testdrive(
+        service="testdrive_no_reset",
+        input=dedent(
+            """
+            > SELECT definition LIKE '%a + b AS a%'
+              FROM mz_materialized_views
+              WHERE name = 'replace_mv'
+            true
+
+            > SELECT i.name
+              FROM mz_indexes i
+              JOIN mz_materialized_views mv ON i.on_id = mv.id
+              WHERE mv.name = 'replace_mv'
+            replace_mv_primary_idx
+
+            > SELECT * FROM replace_mv
+            3
+            7
+            11
+
+            # Only the latest definition writes to the materialized view, and
+            # the index picks up its writes.
+            > INSERT INTO replace_t VALUES (7, 8);
+
+            > SELECT * FROM replace_mv
+            3
+            7
+            11
+            15
+
+            > SELECT * FROM replace_mv_dep
+            4
+            8
+            12
+            16
+
+            # The replaced materialized view can be replaced again.
+            > ALTER MATERIALIZED VIEW replace_mv AS SELECT a FROM replace_t;
+
+            > SELECT * FROM replace_mv
+            1
+            3
+            5
+            7
+
+            > DROP TABLE replace_t CASCADE;
+            """
+        ),
+    )
+
+
 def workflow_default(c: Composition) -> None:
     def process(name: str) -> None:
         if name == "default":